@@ -32,9 +32,11 @@ pub const SCALE_SNAP_INTERVAL: f64 = 0.1;
 pub const SLOWING_DIVISOR: f64 = 10.;
 pub const NUDGE_AMOUNT: f64 = 1.;
 pub const BIG_NUDGE_AMOUNT: f64 = 10.;
+pub const AUTO_ALIGN_MIN_CONFIDENCE: f64 = 0.1; // Unrelated images correlate with a confidence of a few hundredths, while overlapping images score well above this
 
 // Select tool
 pub const SELECTION_TOLERANCE: f64 = 5.;
+pub const PICKING_BUFFER_MIN_LAYERS: usize = 2000; // Below this many layers, hit testing the click targets keeps up with hovering
 pub const SELECTION_DRAG_ANGLE: f64 = 90.;
 pub const PIVOT_CROSSHAIR_THICKNESS: f64 = 1.;
 pub const PIVOT_CROSSHAIR_LENGTH: f64 = 9.;
@@ -70,9 +72,20 @@ pub const COLOR_OVERLAY_BLUE: &str = "#00a8ff";
 pub const COLOR_OVERLAY_YELLOW: &str = "#ffc848";
 pub const COLOR_OVERLAY_WHITE: &str = "#ffffff";
 pub const COLOR_OVERLAY_GRAY: &str = "#cccccc";
+pub const COLOR_OVERLAY_MAGENTA: &str = "#ff3ec8";
+pub const COLOR_OVERLAY_RED: &str = "#ef5454";
+pub const COLOR_COMMENT_FRAME: &str = "#ffc84840";
 
 // Document
 pub const DEFAULT_DOCUMENT_NAME: &str = "Untitled Document";
 pub const FILE_SAVE_SUFFIX: &str = ".graphite";
+pub const NODE_GRAPH_SAVE_SUFFIX: &str = ".graphite-graph";
+pub const NODE_PRESETS_SAVE_SUFFIX: &str = ".graphite-presets";
 pub const MAX_UNDO_HISTORY_LEN: usize = 100; // TODO: Add this to user preferences
 pub const AUTO_SAVE_TIMEOUT_SECONDS: u64 = 15;
+
+// Node graph
+/// The width and height in pixels which the thumbnails of the nodes' outputs are rendered to fit within.
+pub const NODE_OUTPUT_THUMBNAIL_SIZE: f64 = 48.;
+/// The number of megabytes the outputs of the cache nodes may hold before the least recently used are cleared, until changed in the preferences.
+pub const DEFAULT_CACHE_MEMORY_BUDGET: f64 = 2048.;
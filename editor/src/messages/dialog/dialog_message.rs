@@ -35,4 +35,5 @@ pub enum DialogMessage {
 	},
 	RequestNewDocumentDialog,
 	RequestPreferencesDialog,
+	RequestSafeModeDialog,
 }
@@ -1,5 +1,6 @@
-use super::simple_dialogs::{self, AboutGraphiteDialog, ComingSoonDialog, DemoArtworkDialog, LicensesDialog};
+use super::simple_dialogs::{self, AboutGraphiteDialog, ComingSoonDialog, DemoArtworkDialog, LicensesDialog, SafeModeDialog};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::animation_export::AnimationExportOptions;
 use crate::messages::prelude::*;
 
 pub struct DialogMessageData<'a> {
@@ -89,6 +90,14 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 						scale_factor: 1.,
 						artboards,
 						has_selection: document.selected_nodes.selected_layers(document.metadata()).next().is_some(),
+						variants: document.variables.variants.clone(),
+						animated: document.timeline.animates(document.network()),
+						image_ppi: 300.,
+						limit_to_palette: document.show_output_palette,
+						output_palette: document.output_palette,
+						output_profile: document.output_profile,
+						document_units: document.units,
+						animation: AnimationExportOptions::for_timeline(&document.timeline),
 						..Default::default()
 					};
 					self.export_dialog.send_dialog_to_frontend(responses);
@@ -111,6 +120,10 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 				self.preferences_dialog = PreferencesDialogMessageHandler {};
 				self.preferences_dialog.send_dialog_to_frontend(responses, preferences);
 			}
+			DialogMessage::RequestSafeModeDialog => {
+				let dialog = SafeModeDialog;
+				dialog.send_dialog_to_frontend(responses);
+			}
 		}
 	}
 
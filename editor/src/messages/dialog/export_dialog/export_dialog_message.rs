@@ -1,6 +1,10 @@
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::portfolio::animation_export::AnimationFormat;
+use crate::messages::portfolio::poster_export::PageSize;
 use crate::messages::prelude::*;
 
+use graphene_core::renderer::ImageEncoding;
+
 #[impl_message(Message, DialogMessage, ExportDialog)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum ExportDialogMessage {
@@ -8,6 +12,26 @@ pub enum ExportDialogMessage {
 	ScaleFactor(f64),
 	TransparentBackground(bool),
 	ExportBounds(ExportBounds),
+	AllVariants(bool),
+	OptimizeSvg(bool),
+	SvgPrecision(f64),
+	LimitImagePpi(bool),
+	ImagePpi(f64),
+	ImageEncoding(ImageEncoding),
+	JpegQuality(f64),
+	LinkImages(bool),
+	LimitToPalette(bool),
+	IconPadding(f64),
+	IconSharpen(bool),
+	PosterPageSize(PageSize),
+	PosterLandscape(bool),
+	PosterPagesAcross(f64),
+	PosterOverlap(f64),
+	PosterMarks(bool),
+	AnimationFormat(AnimationFormat),
+	AnimationFrameRate(f64),
+	AnimationStart(f64),
+	AnimationEnd(f64),
 
 	Submit,
 }
@@ -1,8 +1,14 @@
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::animation_export::{AnimationExportOptions, AnimationFormat};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::icon_set::IconSetOptions;
+use crate::messages::portfolio::poster_export::{PageSize, PosterOptions};
 use crate::messages::prelude::*;
 
+use graphene_core::renderer::{ImageEncoding, ImageExportPolicy, OutputPalette, OutputProfile, SvgOptimization};
+use graphene_core::units::DocumentUnits;
+
 pub struct ExportDialogMessageData<'a> {
 	pub portfolio: &'a PortfolioMessageHandler,
 }
@@ -16,6 +22,27 @@ pub struct ExportDialogMessageHandler {
 	pub transparent_background: bool,
 	pub artboards: HashMap<LayerNodeIdentifier, String>,
 	pub has_selection: bool,
+	pub all_variants: bool,
+	pub variants: Vec<String>,
+	pub icon_set: IconSetOptions,
+	pub poster: PosterOptions,
+	/// Whether SVG exports are run through the optimizer, with the rounding precision kept even while it's turned off.
+	pub optimize_svg: bool,
+	pub svg_optimization: SvgOptimization,
+	/// Whether images in SVG exports are downsampled to the PPI limit, which is kept even while it's turned off.
+	pub limit_image_ppi: bool,
+	pub image_ppi: f64,
+	pub image_export: ImageExportPolicy,
+	/// Whether the colors of the export are limited to the document's output palette, which starts out matching whether the viewport previews it.
+	pub limit_to_palette: bool,
+	pub output_palette: OutputPalette,
+	/// The document's output profile, whose CMYK inks TIFF and poster exports are separated into.
+	pub output_profile: OutputProfile,
+	/// The document's units, whose resolution relates the scale factor to the DPI of raster exports.
+	pub document_units: DocumentUnits,
+	/// Whether the document has keyframes, so it can be exported as an animation.
+	pub animated: bool,
+	pub animation: AnimationExportOptions,
 }
 
 impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for ExportDialogMessageHandler {
@@ -27,13 +54,40 @@ impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for Export
 			ExportDialogMessage::ScaleFactor(factor) => self.scale_factor = factor,
 			ExportDialogMessage::TransparentBackground(transparent_background) => self.transparent_background = transparent_background,
 			ExportDialogMessage::ExportBounds(export_area) => self.bounds = export_area,
+			ExportDialogMessage::AllVariants(all_variants) => self.all_variants = all_variants,
+			ExportDialogMessage::OptimizeSvg(optimize_svg) => self.optimize_svg = optimize_svg,
+			ExportDialogMessage::SvgPrecision(precision) => self.svg_optimization.precision = precision.round().clamp(0., u8::MAX as f64) as u8,
+			ExportDialogMessage::LimitImagePpi(limit_image_ppi) => self.limit_image_ppi = limit_image_ppi,
+			ExportDialogMessage::ImagePpi(ppi) => self.image_ppi = ppi,
+			ExportDialogMessage::ImageEncoding(encoding) => self.image_export.encoding = encoding,
+			ExportDialogMessage::JpegQuality(quality) => self.image_export.jpeg_quality = quality.round().clamp(1., 100.) as u8,
+			ExportDialogMessage::LinkImages(link) => self.image_export.link = link,
+			ExportDialogMessage::LimitToPalette(limit_to_palette) => self.limit_to_palette = limit_to_palette,
+			ExportDialogMessage::IconPadding(padding) => self.icon_set.padding = padding,
+			ExportDialogMessage::IconSharpen(sharpen) => self.icon_set.sharpen = sharpen,
+			ExportDialogMessage::PosterPageSize(page_size) => self.poster.page_size = page_size,
+			ExportDialogMessage::PosterLandscape(landscape) => self.poster.landscape = landscape,
+			ExportDialogMessage::PosterPagesAcross(pages_across) => self.poster.pages_across = pages_across.round().clamp(1., 100.) as u32,
+			ExportDialogMessage::PosterOverlap(overlap) => self.poster.overlap = overlap.max(0.),
+			ExportDialogMessage::PosterMarks(marks) => self.poster.marks = marks,
+			ExportDialogMessage::AnimationFormat(format) => self.animation.format = format,
+			ExportDialogMessage::AnimationFrameRate(frame_rate) => self.animation.frame_rate = frame_rate,
+			ExportDialogMessage::AnimationStart(start) => self.animation.start = start,
+			ExportDialogMessage::AnimationEnd(end) => self.animation.end = end,
 
 			ExportDialogMessage::Submit => responses.add_front(PortfolioMessage::SubmitDocumentExport {
 				file_name: portfolio.active_document().map(|document| document.name.clone()).unwrap_or_default(),
 				file_type: self.file_type,
 				scale_factor: self.scale_factor,
 				bounds: self.bounds,
-				transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
+				transparent_background: self.supports_transparency() && self.transparent_background,
+				all_variants: !self.variants.is_empty() && self.all_variants,
+				svg_optimization: (self.file_type == FileType::Svg && self.optimize_svg).then_some(self.svg_optimization),
+				image_export: self.image_export_policy(),
+				palette: self.limit_to_palette.then_some(self.output_palette),
+				icon_set: self.icon_set,
+				poster: self.poster,
+				animation: self.animation,
 			}),
 		}
 
@@ -43,6 +97,26 @@ impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for Export
 	advertise_actions! {ExportDialogUpdate;}
 }
 
+impl ExportDialogMessageHandler {
+	/// The handling of images chosen for SVG exports, or `None` when images are embedded as they are.
+	fn image_export_policy(&self) -> Option<ImageExportPolicy> {
+		let policy = ImageExportPolicy {
+			max_ppi: self.limit_image_ppi.then_some(self.image_ppi),
+			..self.image_export
+		};
+		(self.file_type == FileType::Svg && policy != ImageExportPolicy::default()).then_some(policy)
+	}
+
+	/// Whether the file type can keep the transparency of the background, which CMYK TIFF files can't since their inks are printed on paper.
+	fn supports_transparency(&self) -> bool {
+		match self.file_type {
+			FileType::Jpg | FileType::Poster => false,
+			FileType::Tiff => !self.output_profile.is_cmyk(),
+			_ => true,
+		}
+	}
+}
+
 impl DialogLayoutHolder for ExportDialogMessageHandler {
 	const ICON: &'static str = "File";
 	const TITLE: &'static str = "Export";
@@ -67,7 +141,18 @@ impl DialogLayoutHolder for ExportDialogMessageHandler {
 
 impl LayoutHolder for ExportDialogMessageHandler {
 	fn layout(&self) -> Layout {
-		let entries = [(FileType::Png, "PNG"), (FileType::Jpg, "JPG"), (FileType::Svg, "SVG")]
+		let mut file_types = vec![
+			(FileType::Png, "PNG"),
+			(FileType::Jpg, "JPG"),
+			(FileType::Svg, "SVG"),
+			(FileType::Icons, "Icons"),
+			(FileType::Poster, "Poster"),
+			(FileType::Tiff, "TIFF"),
+		];
+		if self.animated {
+			file_types.push((FileType::Animation, "Animation"));
+		}
+		let entries = file_types
 			.into_iter()
 			.map(|(val, name)| RadioEntryData::new(format!("{val:?}")).label(name).on_update(move |_| ExportDialogMessage::FileType(val).into()))
 			.collect();
@@ -85,12 +170,28 @@ impl LayoutHolder for ExportDialogMessageHandler {
 				.unit("")
 				.min(0.)
 				.max((1_u64 << std::f64::MANTISSA_DIGITS) as f64)
-				.disabled(self.file_type == FileType::Svg)
+				.disabled(matches!(self.file_type, FileType::Svg | FileType::Icons | FileType::Poster))
 				.on_update(|number_input: &NumberInput| ExportDialogMessage::ScaleFactor(number_input.value.unwrap()).into())
 				.min_width(200)
 				.widget_holder(),
 		];
 
+		// Documents measured in physical units are sized by their resolution, as an alternative to the scale factor
+		let dpi = self.document_units.dpi;
+		let export_dpi = vec![
+			TextLabel::new("Resolution").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.scale_factor * dpi))
+				.unit(" DPI")
+				.min(0.)
+				.max((1_u64 << std::f64::MANTISSA_DIGITS) as f64)
+				.disabled(matches!(self.file_type, FileType::Svg | FileType::Icons | FileType::Poster))
+				.tooltip("The number of pixels of the exported image in each inch of the artwork, as measured in the document's units")
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::ScaleFactor(number_input.value.unwrap() / dpi).into())
+				.min_width(200)
+				.widget_holder(),
+		];
+
 		let artboards = self.artboards.iter().map(|(&layer, name)| (ExportBounds::Artboard(layer), name.to_string(), false));
 		let mut export_area_options = vec![
 			(ExportBounds::AllArtwork, "All Artwork".to_string(), false),
@@ -118,16 +219,274 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			TextLabel::new("Transparency").table_align(true).min_width(100).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
 			CheckboxInput::new(self.transparent_background)
-				.disabled(self.file_type == FileType::Jpg)
+				.disabled(!self.supports_transparency())
 				.on_update(move |value: &CheckboxInput| ExportDialogMessage::TransparentBackground(value.checked).into())
 				.widget_holder(),
 		];
 
-		Layout::WidgetLayout(WidgetLayout::new(vec![
-			LayoutGroup::Row { widgets: export_type },
-			LayoutGroup::Row { widgets: resolution },
+		let limit_to_palette = vec![
+			TextLabel::new("Limit to Palette").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.limit_to_palette)
+				.tooltip("Draw every color with the nearest color of the palette chosen in the document's View Mode options")
+				.on_update(move |value: &CheckboxInput| ExportDialogMessage::LimitToPalette(value.checked).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextLabel::new(self.output_palette.preset.name()).disabled(!self.limit_to_palette).widget_holder(),
+		];
+
+		let mut rows = vec![LayoutGroup::Row { widgets: export_type }, LayoutGroup::Row { widgets: resolution }];
+		if self.document_units.length_unit.per_inch().is_some() {
+			rows.push(LayoutGroup::Row { widgets: export_dpi });
+		}
+		rows.extend([
 			LayoutGroup::Row { widgets: export_area },
 			LayoutGroup::Row { widgets: transparent_background },
-		]))
+			LayoutGroup::Row { widgets: limit_to_palette },
+		]);
+
+		if matches!(self.file_type, FileType::Tiff | FileType::Poster) {
+			let colors = match self.output_profile.is_cmyk() {
+				true => format!("CMYK ({})", self.output_profile.name()),
+				false => "RGB".to_string(),
+			};
+			let output_profile = vec![
+				TextLabel::new("Colors").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(colors)
+					.tooltip("The colors are separated into the inks of the output profile chosen in the document's View Mode options")
+					.widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: output_profile });
+		}
+
+		if self.file_type == FileType::Svg {
+			let optimize = vec![
+				TextLabel::new("Optimize").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(self.optimize_svg)
+					.tooltip("Make the file smaller by merging groups, transforms, and duplicate gradients, and by rounding coordinates")
+					.on_update(move |value: &CheckboxInput| ExportDialogMessage::OptimizeSvg(value.checked).into())
+					.widget_holder(),
+			];
+			let precision = vec![
+				TextLabel::new("Precision").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				NumberInput::new(Some(self.svg_optimization.precision as f64))
+					.unit(" decimals")
+					.int()
+					.min(0.)
+					.max(8.)
+					.disabled(!self.optimize_svg)
+					.tooltip("Number of decimal places that coordinates are rounded to")
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::SvgPrecision(number_input.value.unwrap()).into())
+					.min_width(200)
+					.widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: optimize });
+			rows.push(LayoutGroup::Row { widgets: precision });
+
+			let image_ppi = vec![
+				TextLabel::new("Image PPI Limit").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(self.limit_image_ppi)
+					.tooltip("Downsample images with more pixels per inch than this at the size they're drawn")
+					.on_update(move |value: &CheckboxInput| ExportDialogMessage::LimitImagePpi(value.checked).into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(self.image_ppi))
+					.unit(" ppi")
+					.min(1.)
+					.disabled(!self.limit_image_ppi)
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::ImagePpi(number_input.value.unwrap()).into())
+					.min_width(168)
+					.widget_holder(),
+			];
+			let entries = [(ImageEncoding::Png, "PNG"), (ImageEncoding::Jpeg, "JPG")]
+				.into_iter()
+				.map(|(val, name)| RadioEntryData::new(format!("{val:?}")).label(name).on_update(move |_| ExportDialogMessage::ImageEncoding(val).into()))
+				.collect();
+			let mut image_format = vec![
+				TextLabel::new("Image Format").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				RadioInput::new(entries).selected_index(Some(self.image_export.encoding as u32)).widget_holder(),
+			];
+			if self.image_export.encoding == ImageEncoding::Jpeg {
+				image_format.extend([
+					Separator::new(SeparatorType::Related).widget_holder(),
+					NumberInput::new(Some(self.image_export.jpeg_quality as f64))
+						.unit("%")
+						.int()
+						.min(1.)
+						.max(100.)
+						.tooltip("JPEG quality, where lower values give smaller files with more compression artifacts")
+						.on_update(|number_input: &NumberInput| ExportDialogMessage::JpegQuality(number_input.value.unwrap()).into())
+						.widget_holder(),
+				]);
+			}
+			let link_images = vec![
+				TextLabel::new("Link Images").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(self.image_export.link)
+					.tooltip("Save images as separate files that the SVG links to, downloaded together with the SVG in a ZIP archive, instead of embedding them in the SVG")
+					.on_update(move |value: &CheckboxInput| ExportDialogMessage::LinkImages(value.checked).into())
+					.widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: image_ppi });
+			rows.push(LayoutGroup::Row { widgets: image_format });
+			rows.push(LayoutGroup::Row { widgets: link_images });
+		}
+
+		if self.file_type == FileType::Icons {
+			let icon_padding = vec![
+				TextLabel::new("Icon Padding").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				NumberInput::new(Some(self.icon_set.padding))
+					.unit("%")
+					.min(0.)
+					.max(45.)
+					.tooltip("Empty margin around the artwork in each icon, as a percentage of the icon size")
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::IconPadding(number_input.value.unwrap()).into())
+					.min_width(200)
+					.widget_holder(),
+			];
+			let icon_sharpen = vec![
+				TextLabel::new("Sharpen Small").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(self.icon_set.sharpen)
+					.tooltip("Sharpen the icons of 64x64 pixels and smaller to keep detail that is blurred away by downscaling")
+					.on_update(move |value: &CheckboxInput| ExportDialogMessage::IconSharpen(value.checked).into())
+					.widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: icon_padding });
+			rows.push(LayoutGroup::Row { widgets: icon_sharpen });
+		}
+
+		if self.file_type == FileType::Poster {
+			let entries = PageSize::ALL
+				.into_iter()
+				.map(|page_size| {
+					MenuListEntry::new(format!("{page_size:?}"))
+						.label(page_size.name())
+						.on_commit(move |_| ExportDialogMessage::PosterPageSize(page_size).into())
+				})
+				.collect::<Vec<_>>();
+			let page_size_index = PageSize::ALL.iter().position(|&page_size| page_size == self.poster.page_size);
+			let orientation_entries = [(false, "Portrait"), (true, "Landscape")]
+				.into_iter()
+				.map(|(landscape, name)| RadioEntryData::new(name).label(name).on_update(move |_| ExportDialogMessage::PosterLandscape(landscape).into()))
+				.collect();
+
+			let page_size = vec![
+				TextLabel::new("Page Size").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(vec![entries]).selected_index(page_size_index.map(|index| index as u32)).widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				RadioInput::new(orientation_entries).selected_index(Some(self.poster.landscape as u32)).widget_holder(),
+			];
+			let pages_across = vec![
+				TextLabel::new("Pages Across").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				NumberInput::new(Some(self.poster.pages_across as f64))
+					.int()
+					.min(1.)
+					.max(100.)
+					.tooltip("Number of pages the artwork is scaled to span across its width, with as many rows of pages as its height needs")
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::PosterPagesAcross(number_input.value.unwrap()).into())
+					.min_width(200)
+					.widget_holder(),
+			];
+			let overlap = vec![
+				TextLabel::new("Overlap").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				NumberInput::new(Some(self.poster.overlap))
+					.unit(" mm")
+					.min(0.)
+					.tooltip("Width of the strip of artwork repeated along the edges of neighboring pages, where they're glued over each other")
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::PosterOverlap(number_input.value.unwrap()).into())
+					.min_width(200)
+					.widget_holder(),
+			];
+			let marks = vec![
+				TextLabel::new("Assembly Marks").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(self.poster.marks)
+					.tooltip("Print crop marks at the corners of the artwork, dashed guides where the neighboring pages line up, and a label naming each page's row and column")
+					.on_update(move |value: &CheckboxInput| ExportDialogMessage::PosterMarks(value.checked).into())
+					.widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: page_size });
+			rows.push(LayoutGroup::Row { widgets: pages_across });
+			rows.push(LayoutGroup::Row { widgets: overlap });
+			rows.push(LayoutGroup::Row { widgets: marks });
+		}
+
+		if self.file_type == FileType::Animation {
+			let entries = [
+				(AnimationFormat::Gif, "GIF"),
+				(AnimationFormat::Apng, "APNG"),
+				(AnimationFormat::WebM, "WebM"),
+				(AnimationFormat::ImageSequence, "PNG Sequence"),
+				(AnimationFormat::Lottie, "Lottie"),
+			]
+			.into_iter()
+			.map(|(val, name)| MenuListEntry::new(format!("{val:?}")).label(name).on_commit(move |_| ExportDialogMessage::AnimationFormat(val).into()))
+			.collect::<Vec<_>>();
+			let format_index = entries.iter().position(|entry| entry.value == format!("{:?}", self.animation.format));
+
+			let animation_format = vec![
+				TextLabel::new("Format").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(vec![entries]).selected_index(format_index.map(|index| index as u32)).widget_holder(),
+			];
+			let frame_rate = vec![
+				TextLabel::new("Frame Rate").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				NumberInput::new(Some(self.animation.frame_rate))
+					.unit(" fps")
+					.min(1.)
+					.max(120.)
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::AnimationFrameRate(number_input.value.unwrap()).into())
+					.min_width(200)
+					.widget_holder(),
+			];
+			let time_range = vec![
+				TextLabel::new("Time Range").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				NumberInput::new(Some(self.animation.start))
+					.unit(" s")
+					.min(0.)
+					.max(self.animation.end)
+					.tooltip("Time of the first exported frame")
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::AnimationStart(number_input.value.unwrap()).into())
+					.min_width(96)
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				NumberInput::new(Some(self.animation.end))
+					.unit(" s")
+					.min(self.animation.start)
+					.tooltip("Time the animation ends, which is shown for the whole duration of the last frame")
+					.on_update(|number_input: &NumberInput| ExportDialogMessage::AnimationEnd(number_input.value.unwrap()).into())
+					.min_width(96)
+					.widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: animation_format });
+			rows.push(LayoutGroup::Row { widgets: frame_rate });
+			rows.push(LayoutGroup::Row { widgets: time_range });
+		}
+
+		if !self.variants.is_empty() {
+			let all_variants = vec![
+				TextLabel::new("All Variants").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(self.all_variants)
+					.tooltip(format!("Export a separate file for each variant ({})", self.variants.join(", ")))
+					.on_update(move |value: &CheckboxInput| ExportDialogMessage::AllVariants(value.checked).into())
+					.widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: all_variants });
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
 	}
 }
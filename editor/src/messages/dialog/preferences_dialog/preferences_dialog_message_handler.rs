@@ -69,11 +69,62 @@ impl PreferencesDialogMessageHandler {
 				.widget_holder(),
 		];
 
-		Layout::WidgetLayout(WidgetLayout::new(vec![
-			LayoutGroup::Row { widgets: zoom_with_scroll },
-			LayoutGroup::Row { widgets: imaginate_server_hostname },
-			LayoutGroup::Row { widgets: imaginate_refresh_frequency },
-		]))
+		let cache_memory_budget = vec![
+			TextLabel::new("Memory").min_width(60).italic(true).widget_holder(),
+			TextLabel::new("Cache Budget").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.cache_memory_budget))
+				.tooltip("How much memory the cached results of the node graph may use before the least recently used are cleared (0 for no limit)")
+				.unit(" MB")
+				.min(0.)
+				.max((1_u64 << std::f64::MANTISSA_DIGITS) as f64)
+				.is_integer(true)
+				.min_width(200)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::CacheMemoryBudget {
+						megabytes: number_input.value.unwrap(),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let node_presets = preferences.node_presets.presets.iter().enumerate().map(|(index, preset)| {
+			let id = preset.id;
+			let widgets = vec![
+				TextLabel::new(if index == 0 { "Nodes" } else { "" }).min_width(60).italic(true).widget_holder(),
+				TextLabel::new(if index == 0 { "Presets" } else { "" }).table_align(true).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(&preset.name)
+					.min_width(200)
+					.on_update(move |text_input: &TextInput| PreferencesMessage::RenameNodePreset { id, name: text_input.value.clone() }.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				IconButton::new("Trash", 24)
+					.tooltip("Delete Preset")
+					.on_update(move |_| {
+						// Reopen the dialog so the deleted preset's row disappears
+						DialogMessage::CloseDialogAndThen {
+							followups: vec![PreferencesMessage::DeleteNodePreset { id }.into(), DialogMessage::RequestPreferencesDialog.into()],
+						}
+						.into()
+					})
+					.widget_holder(),
+			];
+			LayoutGroup::Row { widgets }
+		});
+
+		Layout::WidgetLayout(WidgetLayout::new(
+			[
+				LayoutGroup::Row { widgets: zoom_with_scroll },
+				LayoutGroup::Row { widgets: imaginate_server_hostname },
+				LayoutGroup::Row { widgets: imaginate_refresh_frequency },
+				LayoutGroup::Row { widgets: cache_memory_budget },
+			]
+			.into_iter()
+			.chain(node_presets)
+			.collect(),
+		))
 	}
 	pub fn send_layout(&self, responses: &mut VecDeque<Message>, layout_target: LayoutTarget, preferences: &PreferencesMessageHandler) {
 		responses.add(LayoutMessage::SendLayout {
@@ -0,0 +1,29 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A dialog to notify users that the editor was started in safe mode, explaining what's turned off until it's restarted normally.
+pub struct SafeModeDialog;
+
+impl DialogLayoutHolder for SafeModeDialog {
+	const ICON: &'static str = "Warning";
+	const TITLE: &'static str = "Safe Mode";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![TextButton::new("OK").emphasized(true).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder()];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for SafeModeDialog {
+	fn layout(&self) -> Layout {
+		let header = vec![TextLabel::new("The editor was started in safe mode").bold(true).widget_holder()];
+		let row1 = vec![TextLabel::new(
+			"Plugin nodes, custom nodes, and expression nodes are turned off, passing\nthrough their primary input instead, and graphs saved with documents aren't\nreused. Delete or fix whatever stops the document from opening and save it,\nthen reload the editor without safe mode to work as usual.",
+		)
+		.multiline(true)
+		.widget_holder()];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets: header }, LayoutGroup::Row { widgets: row1 }]))
+	}
+}
@@ -1,13 +1,20 @@
 use super::utility_types::{FrontendDocumentDetails, MouseCursorIcon};
 use crate::messages::layout::utility_types::widget_prelude::*;
-use crate::messages::portfolio::document::node_graph::utility_types::{BoxSelection, ContextMenuInformation, FrontendNode, FrontendNodeType, FrontendNodeWire, Transform, WirePath};
+use crate::messages::portfolio::animation_export::AnimationFormat;
+use crate::messages::portfolio::document::node_graph::utility_types::{
+	BoxSelection, ContextMenuInformation, FrontendCommentFrame, FrontendFrameProfile, FrontendGraphSearchResult, FrontendNode, FrontendNodeInspection, FrontendNodeType, FrontendNodeWire, Transform,
+	WirePath,
+};
+use crate::messages::portfolio::document::spellcheck::utility_types::SpellcheckRun;
 use crate::messages::portfolio::document::utility_types::nodes::{JsRawBuffer, LayerPanelEntry, RawBuffer};
+use crate::messages::portfolio::icon_set::IconRasterization;
+use crate::messages::portfolio::poster_export::PosterPageRasterization;
 use crate::messages::prelude::*;
 use crate::messages::tool::utility_types::HintData;
 
 use graph_craft::document::NodeId;
 use graphene_core::raster::color::Color;
-use graphene_core::text::Font;
+use graphene_core::text::{Font, TextAlign};
 
 #[impl_message(Message, Frontend)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
@@ -28,6 +35,9 @@ pub enum FrontendMessage {
 		line_width: Option<f64>,
 		#[serde(rename = "fontSize")]
 		font_size: f64,
+		#[serde(rename = "lineHeightRatio")]
+		line_height_ratio: f64,
+		align: TextAlign,
 		color: Color,
 		url: String,
 		transform: [f64; 6],
@@ -52,11 +62,19 @@ pub enum FrontendMessage {
 		#[serde(rename = "blobUrl")]
 		blob_url: String,
 	},
+	TriggerDownloadBinaryFile {
+		name: String,
+		mime: String,
+		data: Vec<u8>,
+	},
 	TriggerDownloadImage {
 		svg: String,
 		name: String,
 		mime: String,
 		size: (f64, f64),
+		/// Chunks encoding the color profile and document info, which are inserted after the header chunk of a PNG image, or segments encoding the color profile, which are inserted at the start of a JPEG image.
+		#[serde(rename = "metadataChunks")]
+		metadata_chunks: Vec<u8>,
 	},
 	TriggerDownloadTextFile {
 		document: String,
@@ -75,6 +93,10 @@ pub enum FrontendMessage {
 		open: bool,
 	},
 	TriggerImport,
+	TriggerImportNodePresets,
+	TriggerImportSwatches {
+		global: bool,
+	},
 	TriggerIndexedDbRemoveDocument {
 		#[serde(rename = "documentId")]
 		document_id: DocumentId,
@@ -84,9 +106,53 @@ pub enum FrontendMessage {
 		details: FrontendDocumentDetails,
 	},
 	TriggerLoadAutoSaveDocuments,
+	TriggerLoadPlugin,
 	TriggerLoadPreferences,
 	TriggerOpenDocument,
 	TriggerPaste,
+	TriggerRasterizeAnimation {
+		svgs: Vec<String>,
+		name: String,
+		size: (f64, f64),
+		#[serde(rename = "frameRate")]
+		frame_rate: f64,
+		format: AnimationFormat,
+	},
+	TriggerRasterizeIconSet {
+		svg: String,
+		name: String,
+		size: (f64, f64),
+		icons: Vec<IconRasterization>,
+	},
+	TriggerRasterizePoster {
+		svg: String,
+		name: String,
+		size: (f64, f64),
+		pages: Vec<PosterPageRasterization>,
+		/// Whether the pages are handed back as their RGBA pixels to be separated into CMYK, instead of as JPEG images.
+		cmyk: bool,
+	},
+	TriggerRasterizeTiff {
+		svg: String,
+		name: String,
+		size: (f64, f64),
+	},
+	TriggerRasterizeOnionSkinFrame {
+		svg: String,
+		key: u64,
+		size: (f64, f64),
+	},
+	/// Rasterizes the click targets of the layers, each drawn in a color encoding its ID, and hands the pixels back to be read from while hovering.
+	TriggerRasterizePickingBuffer {
+		svg: String,
+		key: u64,
+		size: (f64, f64),
+	},
+	/// Rasterizes the render of a single pixel of the document and hands its color back to be picked by the eyedropper.
+	TriggerSampleColor {
+		svg: String,
+		key: u64,
+	},
 	TriggerRefreshBoundsOfViewports,
 	TriggerRevokeBlobUrl {
 		url: String,
@@ -94,6 +160,9 @@ pub enum FrontendMessage {
 	TriggerSavePreferences {
 		preferences: PreferencesMessageHandler,
 	},
+	TriggerSpellcheck {
+		runs: Vec<SpellcheckRun>,
+	},
 	TriggerTextCommit,
 	TriggerTextCopy {
 		#[serde(rename = "copyText")]
@@ -113,6 +182,10 @@ pub enum FrontendMessage {
 		#[serde(rename = "box")]
 		box_selection: Option<BoxSelection>,
 	},
+	UpdateCacheMemoryUsage {
+		used: f64,
+		budget: Option<f64>,
+	},
 	UpdateContextMenuInformation {
 		#[serde(rename = "contextMenuInformation")]
 		context_menu_information: Option<ContextMenuInformation>,
@@ -178,8 +251,9 @@ pub enum FrontendMessage {
 		primary_color: String,
 		#[serde(rename = "secondaryColor")]
 		secondary_color: String,
-		#[serde(rename = "setColorChoice")]
-		set_color_choice: Option<String>,
+	},
+	UpdateGraphSearchResults {
+		results: Vec<FrontendGraphSearchResult>,
 	},
 	UpdateInputHints {
 		#[serde(rename = "hintData")]
@@ -201,6 +275,7 @@ pub enum FrontendMessage {
 	UpdateNodeGraph {
 		nodes: Vec<FrontendNode>,
 		wires: Vec<FrontendNodeWire>,
+		frames: Vec<FrontendCommentFrame>,
 	},
 	UpdateNodeGraphBarLayout {
 		#[serde(rename = "layoutTarget")]
@@ -213,6 +288,15 @@ pub enum FrontendMessage {
 	UpdateNodeGraphTransform {
 		transform: Transform,
 	},
+	UpdateNodeInspection {
+		inspection: Option<FrontendNodeInspection>,
+	},
+	UpdateNodeOutputThumbnails {
+		thumbnails: HashMap<NodeId, String>,
+	},
+	UpdateNodeProfile {
+		profile: Option<FrontendFrameProfile>,
+	},
 	UpdateNodeThumbnail {
 		id: NodeId,
 		value: String,
@@ -225,6 +309,11 @@ pub enum FrontendMessage {
 		#[serde(rename = "openDocuments")]
 		open_documents: Vec<FrontendDocumentDetails>,
 	},
+	UpdateParametersPanelLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
 	UpdatePropertyPanelOptionsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -239,6 +328,12 @@ pub enum FrontendMessage {
 		#[serde(rename = "subgraphPath")]
 		subgraph_path: Vec<String>,
 	},
+	UpdateUserFonts {
+		#[serde(rename = "userFonts")]
+		user_fonts: Vec<Font>,
+		#[serde(rename = "previewUrls")]
+		preview_urls: Vec<String>,
+	},
 	UpdateToolOptionsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
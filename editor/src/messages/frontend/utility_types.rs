@@ -35,6 +35,13 @@ pub enum FileType {
 	Png,
 	Jpg,
 	Svg,
+	Icons,
+	/// A multi-page PDF which tiles the artwork across pages that are assembled into a poster, laid out with the [`PosterOptions`](crate::messages::portfolio::poster_export::PosterOptions) chosen for the export.
+	Poster,
+	/// A TIFF image whose colors are separated into the CMYK inks of the document's [`OutputProfile`](graphene_core::renderer::OutputProfile) for print, or kept as RGB for an RGB profile.
+	Tiff,
+	/// A range of the document's timeline, in the [`AnimationFormat`](crate::messages::portfolio::animation_export::AnimationFormat) chosen for the export.
+	Animation,
 }
 
 impl FileType {
@@ -43,6 +50,10 @@ impl FileType {
 			FileType::Png => "image/png",
 			FileType::Jpg => "image/jpeg",
 			FileType::Svg => "image/svg+xml",
+			FileType::Icons => "application/zip",
+			FileType::Poster => "application/pdf",
+			FileType::Tiff => "image/tiff",
+			FileType::Animation => "application/octet-stream",
 		}
 	}
 }
@@ -6,6 +6,7 @@ use crate::messages::input_mapper::utility_types::macros::*;
 use crate::messages::input_mapper::utility_types::misc::MappingEntry;
 use crate::messages::input_mapper::utility_types::misc::{KeyMappingEntries, Mapping};
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
+use crate::messages::portfolio::document::utility_types::misc::ViewportDirection;
 use crate::messages::prelude::*;
 use crate::messages::tool::tool_messages::brush_tool::BrushToolMessageOptionsUpdate;
 use crate::messages::tool::tool_messages::select_tool::SelectToolPointerKeys;
@@ -146,6 +147,8 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(Escape); action_dispatch=EyedropperToolMessage::Abort),
 		//
 		// TextToolMessage
+		entry!(KeyDown(Lmb); action_dispatch=TextToolMessage::DragStart),
+		entry!(PointerMove; action_dispatch=TextToolMessage::PointerMove),
 		entry!(KeyUp(Lmb); action_dispatch=TextToolMessage::Interact),
 		entry!(KeyDown(Escape); action_dispatch=TextToolMessage::Abort),
 		entry!(KeyDown(Enter); modifiers=[Accel], action_dispatch=TextToolMessage::CommitText),
@@ -310,6 +313,9 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(KeyY); modifiers=[Accel], action_dispatch=DocumentMessage::Redo),
 		entry!(KeyDown(KeyZ); modifiers=[Accel], action_dispatch=DocumentMessage::Undo),
 		entry!(KeyDown(KeyA); modifiers=[Accel], action_dispatch=DocumentMessage::SelectAllLayers),
+		entry!(KeyDown(KeyF); modifiers=[Accel], action_dispatch=SearchMessage::OpenDialog),
+		entry!(KeyDown(F3); action_dispatch=SearchMessage::FindNext),
+		entry!(KeyDown(F3); modifiers=[Shift], action_dispatch=SearchMessage::FindPrevious),
 		entry!(KeyDown(KeyA); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::DeselectAllLayers),
 		entry!(KeyDown(KeyS); modifiers=[Accel], action_dispatch=DocumentMessage::SaveDocument),
 		entry!(KeyDown(KeyD); modifiers=[Accel], action_dispatch=DocumentMessage::DuplicateSelectedLayers),
@@ -324,6 +330,14 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(BracketRight); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::SelectedLayersRaiseToFront),
 		entry!(KeyDown(BracketLeft); modifiers=[Accel], action_dispatch=DocumentMessage::SelectedLayersLower),
 		entry!(KeyDown(BracketRight); modifiers=[Accel], action_dispatch=DocumentMessage::SelectedLayersRaise),
+		entry!(KeyDown(Tab); modifiers=[Shift], action_dispatch=DocumentMessage::SelectPreviousSiblingLayer),
+		entry!(KeyDown(Tab); action_dispatch=DocumentMessage::SelectNextSiblingLayer),
+		entry!(KeyDown(Backslash); modifiers=[Shift], action_dispatch=DocumentMessage::SelectChildLayers),
+		entry!(KeyDown(Backslash); action_dispatch=DocumentMessage::SelectParentLayer),
+		entry!(KeyDown(ArrowUp); modifiers=[Accel], action_dispatch=DocumentMessage::SelectLayerInDirection { direction: ViewportDirection::Up }),
+		entry!(KeyDown(ArrowDown); modifiers=[Accel], action_dispatch=DocumentMessage::SelectLayerInDirection { direction: ViewportDirection::Down }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Accel], action_dispatch=DocumentMessage::SelectLayerInDirection { direction: ViewportDirection::Left }),
+		entry!(KeyDown(ArrowRight); modifiers=[Accel], action_dispatch=DocumentMessage::SelectLayerInDirection { direction: ViewportDirection::Right }),
 		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
 		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
 		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
@@ -38,6 +38,8 @@ pub enum LayoutTarget {
 	MenuBar,
 	/// Bar at the top of the node graph containing the location and the "Preview" and "Hide" buttons.
 	NodeGraphBar,
+	/// The list of the document's parameters, with their values and buttons for adding more, which fills the Parameters panel.
+	ParametersPanel,
 	/// The bar at the top of the Properties panel containing the layer name and icon.
 	PropertiesOptions,
 	/// The body of the Properties panel containing many collapsable sections.
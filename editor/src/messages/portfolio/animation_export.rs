@@ -0,0 +1,257 @@
+//! Encoding of the frames exported by the "Animation" export file type, made from the document rendered at each frame time of its timeline.
+
+use super::document::utility_types::timeline::DocumentTimeline;
+use super::icc_profile;
+use super::icon_set::{crc32, encode_zip};
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, ImageFormat};
+
+/// The file the frames of an animation are exported as.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum AnimationFormat {
+	/// A ZIP archive with a numbered PNG image of each frame.
+	ImageSequence,
+	#[default]
+	Gif,
+	Apng,
+	/// Encoded by the browser, since the editor has no video encoder of its own.
+	WebM,
+	/// The animated vector shapes, rather than rasterized frames, as Lottie JSON.
+	Lottie,
+}
+
+impl AnimationFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			AnimationFormat::ImageSequence => "zip",
+			AnimationFormat::Gif => "gif",
+			AnimationFormat::Apng => "png",
+			AnimationFormat::WebM => "webm",
+			AnimationFormat::Lottie => "json",
+		}
+	}
+
+	pub fn to_mime(self) -> &'static str {
+		match self {
+			AnimationFormat::ImageSequence => "application/zip",
+			AnimationFormat::Gif => "image/gif",
+			AnimationFormat::Apng => "image/apng",
+			AnimationFormat::WebM => "video/webm",
+			AnimationFormat::Lottie => "application/json",
+		}
+	}
+}
+
+/// The user's choices for which part of the timeline is exported, and how.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct AnimationExportOptions {
+	pub format: AnimationFormat,
+	pub frame_rate: f64,
+	/// The time of the first frame in seconds.
+	pub start: f64,
+	/// The time in seconds the last frame is shown until, which isn't itself exported so the animation can loop seamlessly.
+	pub end: f64,
+}
+
+impl Default for AnimationExportOptions {
+	fn default() -> Self {
+		Self::for_timeline(&DocumentTimeline::default())
+	}
+}
+
+impl AnimationExportOptions {
+	/// Exports the whole timeline at its own frame rate.
+	pub fn for_timeline(timeline: &DocumentTimeline) -> Self {
+		Self {
+			format: AnimationFormat::default(),
+			frame_rate: timeline.frame_rate,
+			start: 0.,
+			end: timeline.duration,
+		}
+	}
+
+	/// The time of every exported frame, in order.
+	pub fn frame_times(&self) -> Vec<f64> {
+		if !(self.frame_rate > 0.) || !(self.end > self.start) {
+			return Vec::new();
+		}
+		let frame_count = ((self.end - self.start) * self.frame_rate).ceil() as usize;
+		(0..frame_count).map(|frame| self.start + frame as f64 / self.frame_rate).collect()
+	}
+}
+
+/// Encodes the rasterized PNG image of each frame into a file of the given format.
+/// WebM videos arrive already encoded by the frontend, as the only "frame", and are passed through unchanged.
+pub fn encode_animation(name: &str, format: AnimationFormat, frame_rate: f64, frames: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+	if frames.is_empty() {
+		return Err("The animation has no frames".to_string());
+	}
+
+	match format {
+		AnimationFormat::ImageSequence => {
+			let digits = frames.len().to_string().len().max(4);
+			let files = frames
+				.iter()
+				.enumerate()
+				.map(|(index, png)| (format!("{name}/{name}_{index:0digits$}.png"), png.clone()))
+				.collect::<Vec<_>>();
+			Ok(encode_zip(&files))
+		}
+		AnimationFormat::Gif => encode_gif(frame_rate, frames),
+		AnimationFormat::Apng => encode_apng(frame_rate, frames),
+		AnimationFormat::WebM => Ok(frames[0].clone()),
+		AnimationFormat::Lottie => Err("Lottie animations are made from the vector shapes of each frame rather than rasterized frames".to_string()),
+	}
+}
+
+fn encode_gif(frame_rate: f64, frames: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+	// GIF delays are stored in hundredths of a second, so the timing of each frame is rounded to that
+	let delay = Delay::from_numer_denom_ms((1000. / frame_rate).round().max(10.) as u32, 1);
+
+	let mut gif = Vec::new();
+	{
+		let mut encoder = GifEncoder::new_with_speed(&mut gif, 10);
+		encoder.set_repeat(Repeat::Infinite).map_err(|error| error.to_string())?;
+		for png in frames {
+			let image = image::load_from_memory_with_format(png, ImageFormat::Png).map_err(|error| error.to_string())?.to_rgba8();
+			encoder.encode_frame(Frame::from_parts(image, 0, 0, delay)).map_err(|error| error.to_string())?;
+		}
+	}
+	Ok(gif)
+}
+
+/// Assembles an animated PNG from the PNG image of each frame by moving their compressed image data into the frame chunks, without decoding them.
+fn encode_apng(frame_rate: f64, frames: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+	const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+	let frames = frames.iter().map(|png| png_chunks(png)).collect::<Result<Vec<_>, _>>()?;
+	// The width and height at the start of each frame's header must all match, since every frame covers the whole canvas
+	let header_size = png_size(&frames[0]).ok_or("The frame has no PNG header")?;
+	if frames.iter().any(|chunks| png_size(chunks) != Some(header_size)) {
+		return Err("The frames of the animation aren't all the same size".to_string());
+	}
+
+	let mut apng = SIGNATURE.to_vec();
+	let mut write_chunk = |chunk_type: &[u8; 4], data: &[u8]| {
+		apng.extend((data.len() as u32).to_be_bytes());
+		let start = apng.len();
+		apng.extend(chunk_type);
+		apng.extend(data);
+		let crc = crc32(&apng[start..]);
+		apng.extend(crc.to_be_bytes());
+	};
+
+	// Ancillary chunks like the color space go before the animation control chunk, taken from the first frame
+	let tagged = frames[0].iter().any(|(chunk_type, _)| chunk_type == b"iCCP" || chunk_type == b"sRGB");
+	for (chunk_type, data) in frames[0].iter().take_while(|(chunk_type, _)| chunk_type != b"IDAT") {
+		write_chunk(chunk_type, data);
+		// The frames are tagged as sRGB right after the header, unless the browser's encoder already tagged them
+		if chunk_type == b"IHDR" && !tagged {
+			write_chunk(b"iCCP", &icc_profile::png_iccp_data(&icc_profile::srgb_icc_profile()));
+		}
+	}
+
+	// Frame count, and zero plays meaning the animation loops forever
+	let mut animation_control = (frames.len() as u32).to_be_bytes().to_vec();
+	animation_control.extend(0_u32.to_be_bytes());
+	write_chunk(b"acTL", &animation_control);
+
+	// The delay of each frame as a fraction of a second, which must fit in 16 bits
+	let delay_numerator = 100_u16;
+	let delay_denominator = (frame_rate * 100.).round().clamp(1., u16::MAX as f64) as u16;
+
+	let mut sequence_number = 0_u32;
+	for (index, chunks) in frames.iter().enumerate() {
+		// Sequence number, size, offset, delay, and the dispose and blend operations which replace the whole canvas with each frame
+		let mut frame_control = sequence_number.to_be_bytes().to_vec();
+		frame_control.extend(header_size);
+		frame_control.extend(0_u32.to_be_bytes());
+		frame_control.extend(0_u32.to_be_bytes());
+		frame_control.extend(delay_numerator.to_be_bytes());
+		frame_control.extend(delay_denominator.to_be_bytes());
+		frame_control.extend([0, 0]);
+		write_chunk(b"fcTL", &frame_control);
+		sequence_number += 1;
+
+		// The first frame doubles as the still image shown by viewers without animation support
+		for (_, data) in chunks.iter().filter(|(chunk_type, _)| chunk_type == b"IDAT") {
+			if index == 0 {
+				write_chunk(b"IDAT", data);
+			} else {
+				let mut frame_data = sequence_number.to_be_bytes().to_vec();
+				frame_data.extend(*data);
+				write_chunk(b"fdAT", &frame_data);
+				sequence_number += 1;
+			}
+		}
+	}
+
+	write_chunk(b"IEND", &[]);
+	Ok(apng)
+}
+
+/// Splits a PNG file into the type and data of each of its chunks, leaving out the end chunk.
+fn png_chunks(png: &[u8]) -> Result<Vec<([u8; 4], &[u8])>, String> {
+	let mut remaining = png.get(8..).ok_or("The frame isn't a PNG image")?;
+	let mut chunks = Vec::new();
+	while remaining.len() >= 12 {
+		let length = u32::from_be_bytes(remaining[..4].try_into().unwrap()) as usize;
+		let chunk_type: [u8; 4] = remaining[4..8].try_into().unwrap();
+		let data = remaining.get(8..8 + length).ok_or("The frame's PNG image is truncated")?;
+		if &chunk_type == b"IEND" {
+			break;
+		}
+		chunks.push((chunk_type, data));
+		remaining = &remaining[(12 + length).min(remaining.len())..];
+	}
+	Ok(chunks)
+}
+
+/// The width and height stored at the start of the header chunk of a PNG image.
+fn png_size<'a>(chunks: &[([u8; 4], &'a [u8])]) -> Option<&'a [u8]> {
+	chunks.iter().find(|(chunk_type, _)| chunk_type == b"IHDR").and_then(|(_, data)| data.get(..8))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn png_frame(shade: u8) -> Vec<u8> {
+		let image = image::RgbaImage::from_pixel(4, 3, image::Rgba([shade, shade, shade, 255]));
+		let mut png = std::io::Cursor::new(Vec::new());
+		image.write_to(&mut png, ImageFormat::Png).unwrap();
+		png.into_inner()
+	}
+
+	#[test]
+	fn apng_frames_and_delays_are_decoded() {
+		let frames = [png_frame(0), png_frame(128), png_frame(255)];
+		let apng = encode_animation("Animation", AnimationFormat::Apng, 24., &frames).unwrap();
+
+		let mut reader = png::Decoder::new(apng.as_slice()).read_info().unwrap();
+		let animation_control = reader.info().animation_control.unwrap();
+		assert_eq!(animation_control.num_frames, 3);
+		assert_eq!(animation_control.num_plays, 0);
+
+		let mut buffer = vec![0; reader.output_buffer_size()];
+		let mut shades = Vec::new();
+		for _ in 0..frames.len() {
+			reader.next_frame(&mut buffer).unwrap();
+			let frame_control = reader.info().frame_control.unwrap();
+			assert_eq!((frame_control.width, frame_control.height), (4, 3));
+			assert_eq!(frame_control.delay_num as f64 / frame_control.delay_den as f64, 1. / 24.);
+			shades.push(buffer[0]);
+		}
+		assert_eq!(shades, [0, 128, 255]);
+	}
+
+	#[test]
+	fn apng_frames_must_match_in_size() {
+		let small = image::RgbaImage::from_pixel(2, 2, image::Rgba([0, 0, 0, 255]));
+		let mut png = std::io::Cursor::new(Vec::new());
+		small.write_to(&mut png, ImageFormat::Png).unwrap();
+
+		assert!(encode_animation("Animation", AnimationFormat::Apng, 24., &[png_frame(0), png.into_inner()]).is_err());
+	}
+}
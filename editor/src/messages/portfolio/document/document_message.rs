@@ -1,13 +1,21 @@
 use crate::messages::input_mapper::utility_types::input_keyboard::Key;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::portfolio::document::utility_types::document_info::DocumentInfo;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GridSnapping};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GridSnapping, ViewportDirection};
+use crate::messages::portfolio::document::utility_types::node_presets::PresetNode;
+use crate::messages::portfolio::document::utility_types::palette_file::PaletteFormat;
+use crate::messages::portfolio::document::utility_types::parameters::ParameterKind;
+use crate::messages::portfolio::document::utility_types::timeline::Interpolation;
 use crate::messages::prelude::*;
 
+use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{NodeId, NodeNetwork};
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::Image;
-use graphene_core::vector::style::ViewMode;
+use graphene_core::renderer::{OnionSkin, OutputPalette, OutputProfile};
+use graphene_core::units::DocumentUnits;
+use graphene_core::vector::style::{Fill, ViewMode};
 use graphene_core::Color;
 
 use glam::DAffine2;
@@ -29,30 +37,67 @@ pub enum DocumentMessage {
 	Overlays(OverlaysMessage),
 	#[child]
 	PropertiesPanel(PropertiesPanelMessage),
+	#[child]
+	Search(SearchMessage),
+	#[child]
+	Spellcheck(SpellcheckMessage),
 
 	// Messages
 	AbortTransaction,
+	AddParameter {
+		kind: ParameterKind,
+	},
+	AddSwatch {
+		name: String,
+		fill: Fill,
+	},
+	AddVariant,
 	AlignSelectedLayers {
 		axis: AlignAxis,
 		aggregate: AlignAggregate,
 	},
+	ApplyNodePreset {
+		nodes: Vec<PresetNode>,
+	},
+	AutoAlignSelectedImages,
 	BackupDocument {
 		network: NodeNetwork,
 	},
+	BindInputToParameter {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+		parameter: u64,
+	},
+	BindInputToVariable {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+		variable: String,
+	},
 	ClearArtboards,
+	ClearGuides,
 	ClearLayersPanel,
 	CommitTransaction,
+	ConvertSelectedPathsToGuides,
 	CreateEmptyFolder,
 	DebugPrintDocument,
 	DeleteLayer {
 		layer: LayerNodeIdentifier,
 	},
+	DeleteParameter {
+		id: u64,
+	},
 	DeleteSelectedLayers,
+	DeleteSwatch {
+		id: u64,
+	},
 	DeselectAllLayers,
 	DocumentHistoryBackward,
 	DocumentHistoryForward,
 	DocumentStructureChanged,
 	DuplicateSelectedLayers,
+	ExportSwatches {
+		format: PaletteFormat,
+	},
 	FlipSelectedLayers {
 		flip_axis: FlipAxis,
 	},
@@ -64,6 +109,8 @@ pub enum DocumentMessage {
 	GridOverlays(OverlayContext),
 	GridVisibility(bool),
 	GroupSelectedLayers,
+	GuideOverlays(OverlayContext),
+	GuidesFromSelection,
 	ImaginateGenerate,
 	ImaginateRandom {
 		imaginate_node: Vec<NodeId>,
@@ -76,10 +123,23 @@ pub enum DocumentMessage {
 		parent: LayerNodeIdentifier,
 		insert_index: isize,
 	},
+	ImportSwatches {
+		filename: String,
+		content: Vec<u8>,
+	},
+	LinkInputToSwatch {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+		swatch: u64,
+	},
 	MoveSelectedLayersTo {
 		parent: LayerNodeIdentifier,
 		insert_index: isize,
 	},
+	MoveSwatch {
+		id: u64,
+		index: usize,
+	},
 	NudgeSelectedLayers {
 		delta_x: f64,
 		delta_y: f64,
@@ -90,18 +150,58 @@ pub enum DocumentMessage {
 		image: Image<Color>,
 		mouse: Option<(f64, f64)>,
 	},
+	/// Places a low resolution placeholder of an image which is still being decoded, at the size of the full image, to be swapped for it by `ReplacePlaceholderImage` with the same key.
+	PastePlaceholderImage {
+		placeholder: Image<Color>,
+		width: u32,
+		height: u32,
+		key: u64,
+		mouse: Option<(f64, f64)>,
+	},
 	PasteSvg {
 		svg: String,
 		mouse: Option<(f64, f64)>,
 	},
+	PickingBufferRasterized {
+		key: u64,
+		rgba: Vec<u8>,
+	},
 	Redo,
+	RemoveKeyframe {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+		time: f64,
+	},
+	RemoveVariant {
+		variant_index: usize,
+	},
 	RenameDocument {
 		new_name: String,
 	},
+	RenameParameter {
+		id: u64,
+		name: String,
+	},
+	RenameSwatch {
+		id: u64,
+		name: String,
+	},
 	RenderRulers,
 	RenderScrollbars,
+	ReplacePlaceholderImage {
+		key: u64,
+		image: Image<Color>,
+	},
+	ScrubTimeline {
+		time: f64,
+	},
 	SaveDocument,
+	SaveNodeGraph,
+	SaveNodePreset {
+		name: String,
+	},
 	SelectAllLayers,
+	SelectChildLayers,
 	SelectedLayersLower,
 	SelectedLayersLowerToBack,
 	SelectedLayersRaise,
@@ -114,23 +214,72 @@ pub enum DocumentMessage {
 		ctrl: bool,
 		shift: bool,
 	},
+	SelectLayerInDirection {
+		direction: ViewportDirection,
+	},
+	SelectNextSiblingLayer,
+	SelectParentLayer,
+	SelectPreviousSiblingLayer,
+	SetActiveVariant {
+		variant_index: usize,
+	},
 	SetBlendModeForSelectedLayers {
 		blend_mode: BlendMode,
 	},
+	SetDocumentUnits {
+		units: DocumentUnits,
+	},
+	SetKeyframe {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+		interpolation: Interpolation,
+	},
+	SetKeyframeInterpolation {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+		time: f64,
+		interpolation: Interpolation,
+	},
+	SetLinearCompositing {
+		linear_compositing: bool,
+	},
+	SetOnionSkin {
+		show_onion_skin: bool,
+		onion_skin: OnionSkin,
+	},
+	SetOutputPalette {
+		show_output_palette: bool,
+		output_palette: OutputPalette,
+	},
+	SetOutputProfile {
+		output_profile: OutputProfile,
+	},
 	SetOpacityForSelectedLayers {
 		opacity: f64,
 	},
 	SetOverlaysVisibility {
 		visible: bool,
 	},
+	SetParameterValue {
+		id: u64,
+		value: TaggedValue,
+	},
 	SetRangeSelectionLayer {
 		new_layer: Option<LayerNodeIdentifier>,
 	},
+	SetSoftProof {
+		show_soft_proof: bool,
+		gamut_warning: bool,
+	},
 	SetSnapping {
 		snapping_enabled: Option<bool>,
 		bounding_box_snapping: Option<OptionBoundsSnapping>,
 		geometry_snapping: Option<OptionPointSnapping>,
 	},
+	SetSwatchFill {
+		id: u64,
+		fill: Fill,
+	},
 	SetViewMode {
 		view_mode: ViewMode,
 	},
@@ -141,12 +290,32 @@ pub enum DocumentMessage {
 	ToggleGridVisibility,
 	ToggleOverlaysVisibility,
 	ToggleSnapping,
+	UnbindInputFromParameter {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+	},
+	UnbindInputFromVariable {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+	},
 	Undo,
 	UndoFinished,
 	UngroupSelectedLayers,
+	UnlinkInputFromSwatch {
+		node_path: Vec<NodeId>,
+		input_index: usize,
+	},
+	UpdateDocumentInfo {
+		info: DocumentInfo,
+	},
 	UpdateDocumentTransform {
 		transform: glam::DAffine2,
 	},
+	UpdateExportProgress {
+		progress: Option<String>,
+	},
+	UpdateParametersPanel,
+	UpdatePickingBuffer,
 	ZoomCanvasTo100Percent,
 	ZoomCanvasTo200Percent,
 	ZoomCanvasToFitAll,
@@ -1,33 +1,48 @@
 use super::node_graph::utility_types::Transform;
 use super::utility_types::clipboards::Clipboard;
+use super::utility_types::custom_nodes::CustomNodeLibrary;
+use super::utility_types::document_info::DocumentInfo;
+use super::utility_types::document_migration;
 use super::utility_types::error::EditorError;
+use super::utility_types::guides::{DocumentGuides, Guide};
 use super::utility_types::misc::{BoundingBoxSnapTarget, GeometrySnapTarget, OptionBoundsSnapping, OptionPointSnapping, SnappingOptions, SnappingState};
+use super::utility_types::node_presets::{apply_preset, PresetNode};
 use super::utility_types::nodes::{CollapsedLayers, SelectedNodes};
+use super::utility_types::palette_file;
+use super::utility_types::parameters::{DocumentParameters, ParameterKind};
+use super::utility_types::swatches::{DocumentSwatches, Swatch};
+use super::utility_types::timeline::DocumentTimeline;
+use super::utility_types::variables::{DocumentVariables, VariableBinding};
 use crate::application::{generate_uuid, GRAPHITE_GIT_COMMIT_HASH};
-use crate::consts::{ASYMPTOTIC_EFFECT, DEFAULT_DOCUMENT_NAME, FILE_SAVE_SUFFIX, SCALE_EFFECT, SCROLLBAR_SPACING, VIEWPORT_ROTATE_SNAP_INTERVAL};
+use crate::consts::{ASYMPTOTIC_EFFECT, AUTO_ALIGN_MIN_CONFIDENCE, DEFAULT_DOCUMENT_NAME, FILE_SAVE_SUFFIX, NODE_GRAPH_SAVE_SUFFIX, SCALE_EFFECT, SCROLLBAR_SPACING, VIEWPORT_ROTATE_SNAP_INTERVAL};
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
 use crate::messages::portfolio::document::node_graph::NodeGraphHandlerData;
 use crate::messages::portfolio::document::overlays::grid_overlays::{grid_overlay, overlay_options};
+use crate::messages::portfolio::document::overlays::guide_overlays::guide_overlay;
 use crate::messages::portfolio::document::properties_panel::utility_types::PropertiesPanelMessageHandlerData;
 use crate::messages::portfolio::document::utility_types::document_metadata::{is_artboard, DocumentMetadata, LayerNodeIdentifier};
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, FlipAxis, PTZ};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, FlipAxis, ViewportDirection, PTZ};
 use crate::messages::portfolio::document::utility_types::nodes::RawBuffer;
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::*;
-use crate::messages::tool::common_functionality::graph_modification_utils::{get_blend_mode, get_opacity};
+use crate::messages::tool::common_functionality::graph_modification_utils::{get_blend_mode, get_image_frame, get_opacity};
 use crate::messages::tool::utility_types::ToolType;
-use crate::node_graph_executor::NodeGraphExecutor;
+use crate::node_graph_executor::{CompiledGraph, NodeGraphExecutor, SavedEvaluationCache};
 
+use graph_craft::document::text_format::{network_from_text, network_to_text};
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::FlowType;
-use graph_craft::document::{NodeId, NodeInput, NodeNetwork};
+use graph_craft::document::{DocumentNodeImplementation, NodeId, NodeInput, NodeNetwork};
+use graphene_core::raster::brush_cache::BrushCache;
+use graphene_core::raster::image_alignment::align_images;
 use graphene_core::raster::BlendMode;
-use graphene_core::raster::ImageFrame;
-use graphene_core::renderer::ClickTarget;
-use graphene_core::vector::style::ViewMode;
-use graphene_std::vector::style::{Fill, FillType, Gradient};
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::renderer::{ClickTarget, Dithering, OnionSkin, OutputPalette, OutputProfile, PalettePreset};
+use graphene_core::units::{DocumentUnits, LengthUnit};
+use graphene_core::vector::style::{FillChoice, ViewMode};
+use graphene_core::Color;
 
 use glam::{DAffine2, DVec2, IVec2};
 
@@ -38,6 +53,18 @@ pub struct DocumentMessageData<'a> {
 	pub ipp: &'a InputPreprocessorMessageHandler,
 	pub persistent_data: &'a PersistentData,
 	pub executor: &'a mut NodeGraphExecutor,
+	pub preferences: &'a PreferencesMessageHandler,
+}
+
+/// A state of the document in the undo history, holding the document network along with the parts of the document which are edited alongside it.
+#[derive(Clone, Debug, Default)]
+struct DocumentSnapshot {
+	network: NodeNetwork,
+	variables: DocumentVariables,
+	timeline: DocumentTimeline,
+	swatches: DocumentSwatches,
+	parameters: DocumentParameters,
+	custom_nodes: CustomNodeLibrary,
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -55,6 +82,10 @@ pub struct DocumentMessageHandler {
 	overlays_message_handler: OverlaysMessageHandler,
 	#[serde(skip)]
 	properties_panel_message_handler: PropertiesPanelMessageHandler,
+	#[serde(skip)]
+	search_message_handler: SearchMessageHandler,
+	#[serde(skip)]
+	spellcheck_message_handler: SpellcheckMessageHandler,
 
 	// ============================================
 	// Fields that are saved in the document format
@@ -73,6 +104,9 @@ pub struct DocumentMessageHandler {
 	/// The full Git commit hash of the Graphite repository that was used to build the editor.
 	/// We save this to provide a hint about which version of the editor was used to create the document.
 	commit_hash: String,
+	/// The version of the saved document format, which determines the migrations that are applied to bring the document up to date when it's opened.
+	/// Documents saved before the format was versioned lack this field and are treated as version 0.
+	document_version: u32,
 	/// The current pan, tilt, and zoom state of the viewport's view of the document canvas.
 	pub navigation: PTZ,
 	/// The current pan, and zoom state of the viewport's view of the node graph.
@@ -82,26 +116,63 @@ pub struct DocumentMessageHandler {
 	/// The current view mode that the user has set for rendering the document within the viewport.
 	/// This is usually "Normal" but can be set to "Outline" or "Pixels" to see the canvas differently.
 	pub view_mode: ViewMode,
+	/// Composites blends and gradients in linear RGB, encoding the result as sRGB for display, rather than mixing the gamma encoded sRGB colors as browsers do.
+	/// Mixing in linear RGB is physically accurate, avoiding the dark fringes between saturated colors, but differs from how most other software mixes colors.
+	pub linear_compositing: bool,
+	/// Limits the colors of the viewport to the output palette, previewing how the artwork looks when exported with its colors limited.
+	pub show_output_palette: bool,
+	/// The palette, and dithering of images, that the colors of the artwork are limited to when previewed or exported that way.
+	pub output_palette: OutputPalette,
+	/// The printing condition that the artwork is intended for, whose CMYK inks the colors are separated into when exported for print, or sRGB for screens.
+	pub output_profile: OutputProfile,
+	/// Simulates the colors of the viewport as printed with the output profile, previewing how the artwork looks on paper.
+	pub show_soft_proof: bool,
+	/// Draws the colors that can't be printed with the output profile in a warning color while soft proofing.
+	pub gamut_warning: bool,
 	/// Sets whether or not all the viewport overlays should be drawn on top of the artwork.
 	/// This includes tool interaction visualizations (like the transform cage and path anchors/handles), the grid, and more.
 	overlays_visible: bool,
 	/// Sets whether or not the rulers should be drawn along the top and left edges of the viewport area.
 	pub rulers_visible: bool,
+	/// Sets whether coordinates shown in the rulers and the Properties panel are measured from the top left corner of the containing artboard, rather than from the document origin.
+	pub artboard_relative_coordinates: bool,
+	/// The unit that lengths in the document are shown and typed in, such as by the rulers, the Properties panel, and dimensions, and the document's resolution in DPI.
+	pub units: DocumentUnits,
 	/// Sets whether or not the node graph is drawn (as an overlay) on top of the viewport area, or otherwise if it's hidden.
 	graph_view_overlay_open: bool,
 	/// The current user choices for snapping behavior, including whether snapping is enabled at all.
 	pub snapping_state: SnappingState,
+	/// Named values that node inputs can be bound to, with one value per variant (such as light and dark themes) so each variant can be exported from the same artwork.
+	pub variables: DocumentVariables,
+	/// Keyframes animating node parameters over time, and the time shown in the document.
+	pub timeline: DocumentTimeline,
+	/// Reusable nodes made by the user from groups of nodes, which can be placed any number of times in the node graph.
+	pub custom_nodes: CustomNodeLibrary,
+	/// Named colors and gradients, and the fills linked to them which are re-colored whenever a swatch is edited.
+	pub swatches: DocumentSwatches,
+	/// Named numbers, colors, vectors, and booleans of the document, which node inputs can be bound to so they're edited in one place from the Parameters panel.
+	pub parameters: DocumentParameters,
+	/// Non-printing lines and shapes drawn over the canvas for snapping artwork into place.
+	pub guides: DocumentGuides,
+	/// Descriptive information about the document, such as its title and author, which is embedded into its exports.
+	pub info: DocumentInfo,
+	/// The node graph as it was last compiled for rendering, so reopening the document can skip compiling it again.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub compiled_graph: Option<CompiledGraph>,
+	/// The outputs of the cache nodes of the graph as it was last rendered, so reopening the document can skip evaluating the expensive nodes behind the caches again.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub evaluation_cache: Option<SavedEvaluationCache>,
 
 	// =============================================
 	// Fields omitted from the saved document format
 	// =============================================
 	//
-	/// Stack of document network snapshots for previous history states.
+	/// Stack of document snapshots for previous history states.
 	#[serde(skip)]
-	document_undo_history: VecDeque<NodeNetwork>,
-	/// Stack of document network snapshots for future history states.
+	document_undo_history: VecDeque<DocumentSnapshot>,
+	/// Stack of document snapshots for future history states.
 	#[serde(skip)]
-	document_redo_history: VecDeque<NodeNetwork>,
+	document_redo_history: VecDeque<DocumentSnapshot>,
 	/// Hash of the document snapshot that was most recently saved to disk by the user.
 	#[serde(skip)]
 	saved_hash: Option<u64>,
@@ -115,6 +186,12 @@ pub struct DocumentMessageHandler {
 	/// If the user clicks or Ctrl-clicks one layer, it becomes the start of the range selection and then Shift-clicking another layer selects all layers between the start and end.
 	#[serde(skip)]
 	layer_range_selection_reference: Option<LayerNodeIdentifier>,
+	/// A description of how far along a long running export (such as an animation being rendered frame by frame) is, shown in the document bar until it finishes.
+	#[serde(skip)]
+	export_progress: Option<String>,
+	/// The layers of images placed with a low resolution placeholder while the full image is being decoded, keyed by the key the full image is handed back with.
+	#[serde(skip)]
+	placeholder_images: HashMap<u64, LayerNodeIdentifier>,
 	/// Stores stateful information about the document's network such as the graph's structural topology and which layers are hidden, locked, etc.
 	/// This is updated frequently, whenever the information it's derived from changes.
 	#[serde(skip)]
@@ -131,6 +208,8 @@ impl Default for DocumentMessageHandler {
 			node_graph_handler: NodeGraphMessageHandler::default(),
 			overlays_message_handler: OverlaysMessageHandler::default(),
 			properties_panel_message_handler: PropertiesPanelMessageHandler::default(),
+			search_message_handler: SearchMessageHandler::default(),
+			spellcheck_message_handler: SpellcheckMessageHandler::default(),
 			// ============================================
 			// Fields that are saved in the document format
 			// ============================================
@@ -139,14 +218,32 @@ impl Default for DocumentMessageHandler {
 			collapsed: CollapsedLayers::default(),
 			name: DEFAULT_DOCUMENT_NAME.to_string(),
 			commit_hash: GRAPHITE_GIT_COMMIT_HASH.to_string(),
+			document_version: document_migration::DOCUMENT_VERSION,
 			navigation: PTZ::default(),
 			node_graph_transform: PTZ::default(),
 			document_mode: DocumentMode::DesignMode,
 			view_mode: ViewMode::default(),
+			linear_compositing: false,
+			show_output_palette: false,
+			output_palette: OutputPalette::default(),
+			output_profile: OutputProfile::default(),
+			show_soft_proof: false,
+			gamut_warning: false,
 			overlays_visible: true,
 			rulers_visible: true,
+			artboard_relative_coordinates: false,
+			units: DocumentUnits::default(),
 			graph_view_overlay_open: false,
 			snapping_state: SnappingState::default(),
+			variables: DocumentVariables::default(),
+			timeline: DocumentTimeline::default(),
+			custom_nodes: CustomNodeLibrary::default(),
+			swatches: DocumentSwatches::default(),
+			parameters: DocumentParameters::default(),
+			guides: DocumentGuides::default(),
+			info: DocumentInfo::default(),
+			compiled_graph: None,
+			evaluation_cache: None,
 			// =============================================
 			// Fields omitted from the saved document format
 			// =============================================
@@ -156,6 +253,8 @@ impl Default for DocumentMessageHandler {
 			auto_saved_hash: None,
 			undo_in_progress: false,
 			layer_range_selection_reference: None,
+			export_progress: None,
+			placeholder_images: HashMap::new(),
 			metadata: Default::default(),
 		}
 	}
@@ -168,6 +267,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			ipp,
 			persistent_data,
 			executor,
+			preferences,
 		} = data;
 
 		match message {
@@ -198,13 +298,35 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					node_graph_message_handler: &self.node_graph_handler,
 					executor,
 					document_name: self.name.as_str(),
+					document_info: &self.info,
 					document_network: &self.network,
 					document_metadata: &mut self.metadata,
+					document_swatches: &self.swatches,
+					document_parameters: &self.parameters,
+					document_variables: &self.variables,
+					node_presets: &preferences.node_presets,
 					selected_nodes: &self.selected_nodes,
+					artboard_relative_coordinates: self.artboard_relative_coordinates,
+					units: self.units,
 				};
 				self.properties_panel_message_handler
 					.process_message(message, responses, (persistent_data, properties_panel_message_handler_data));
 			}
+			DocumentMessage::Search(message) => {
+				let data = SearchMessageData {
+					document_network: &self.network,
+					metadata: &self.metadata,
+				};
+				self.search_message_handler.process_message(message, responses, data);
+			}
+			DocumentMessage::Spellcheck(message) => {
+				let data = SpellcheckMessageData {
+					document_network: &self.network,
+					metadata: &self.metadata,
+					font_cache: &persistent_data.font_cache,
+				};
+				self.spellcheck_message_handler.process_message(message, responses, data);
+			}
 			DocumentMessage::NodeGraph(message) => {
 				self.node_graph_handler.process_message(
 					message,
@@ -218,6 +340,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 						collapsed: &mut self.collapsed,
 						ipp,
 						graph_view_overlay_open: self.graph_view_overlay_open,
+						custom_nodes: &mut self.custom_nodes,
 					},
 				);
 			}
@@ -240,6 +363,23 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					responses.add(OverlaysMessage::Draw);
 				}
 			}
+			DocumentMessage::AddParameter { kind } => {
+				self.backup(responses);
+				self.parameters.add(generate_uuid(), kind);
+				responses.add(DocumentMessage::UpdateParametersPanel);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			DocumentMessage::AddSwatch { name, fill } => {
+				self.backup(responses);
+				self.swatches.library.add(Swatch { id: generate_uuid(), name, fill });
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			DocumentMessage::AddVariant => {
+				self.backup(responses);
+				self.variables.add_variant(&self.network);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::AlignSelectedLayers { axis, aggregate } => {
 				self.backup(responses);
 
@@ -274,11 +414,95 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					});
 				}
 			}
+			DocumentMessage::ApplyNodePreset { nodes } => {
+				let selected = self.selected_nodes.selected_nodes_ref().clone();
+				self.backup(responses);
+				let Some(network) = self.network.nested_network_for_selected_nodes_mut(&self.node_graph_handler.network, selected.iter()) else {
+					return;
+				};
+
+				// The nodes take the preset's values in the order they were selected
+				let mut selected_nodes = network
+					.nodes
+					.iter_mut()
+					.filter_map(|(node_id, node)| selected.iter().position(|selected_id| selected_id == node_id).map(|order| (order, node)))
+					.collect::<Vec<_>>();
+				selected_nodes.sort_by_key(|(order, _)| *order);
+
+				if apply_preset(&nodes, selected_nodes.into_iter().map(|(_, node)| node)) {
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+					responses.add(NodeGraphMessage::SendGraph);
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+			}
+			DocumentMessage::AutoAlignSelectedImages => {
+				let image_layers = self
+					.selected_nodes
+					.selected_unlocked_layers(self.metadata())
+					.filter_map(|layer| get_image_frame(layer, &self.network).map(|image_frame| (layer, image_frame)))
+					.collect::<Vec<_>>();
+
+				// The upper layer is moved to line up with the lower layer
+				let [(moving_layer, moving_frame), (reference_layer, reference_frame)] = image_layers[..] else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Cannot auto-align images".to_string(),
+						description: "Select exactly two unlocked image layers to align the upper one to the lower one".to_string(),
+					});
+					return;
+				};
+
+				// Each image's pixels are mapped onto the unit square of its layer
+				let pixels_to_document = |layer: LayerNodeIdentifier, image_frame: &ImageFrame<Color>| {
+					let size = DVec2::new(image_frame.image.width as f64, image_frame.image.height as f64);
+					self.metadata().transform_to_document(layer) * DAffine2::from_scale(size.recip())
+				};
+				let alignment = align_images(
+					&reference_frame.image,
+					pixels_to_document(reference_layer, reference_frame),
+					&moving_frame.image,
+					pixels_to_document(moving_layer, moving_frame),
+				);
+				let Some(alignment) = alignment.filter(|alignment| alignment.confidence >= AUTO_ALIGN_MIN_CONFIDENCE) else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Cannot auto-align images".to_string(),
+						description: "No matching area was found where the two images overlap".to_string(),
+					});
+					return;
+				};
+
+				self.backup(responses);
+				responses.add(GraphOperationMessage::TransformChange {
+					layer: moving_layer,
+					transform: alignment.transform,
+					transform_in: TransformIn::Scope {
+						scope: self.metadata().document_to_viewport.inverse(),
+					},
+					skip_rerender: false,
+				});
+			}
 			DocumentMessage::BackupDocument { network } => self.backup_with_document(network, responses),
+			DocumentMessage::BindInputToParameter { node_path, input_index, parameter } => {
+				self.backup(responses);
+				self.parameters.bind_input(VariableBinding { node_path, input_index }, parameter, &mut self.network);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(DocumentMessage::UpdateParametersPanel);
+			}
+			DocumentMessage::BindInputToVariable { node_path, input_index, variable } => {
+				self.backup(responses);
+				self.variables.bind_input(VariableBinding { node_path, input_index }, variable, &mut self.network);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			DocumentMessage::ClearArtboards => {
 				self.backup(responses);
 				responses.add(GraphOperationMessage::ClearArtboards);
 			}
+			DocumentMessage::ClearGuides => {
+				self.guides.clear();
+				responses.add(OverlaysMessage::Draw);
+			}
 			DocumentMessage::ClearLayersPanel => {
 				// Send an empty layer list
 				let data_buffer: RawBuffer = Self::default().serialize_root();
@@ -289,8 +513,57 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					layout: Layout::WidgetLayout(Default::default()),
 					layout_target: LayoutTarget::LayersPanelOptions,
 				});
+
+				// Clear the Parameters panel, which belongs to the document too
+				responses.add(LayoutMessage::SendLayout {
+					layout: Layout::WidgetLayout(Default::default()),
+					layout_target: LayoutTarget::ParametersPanel,
+				});
 			}
 			DocumentMessage::CommitTransaction => (),
+			DocumentMessage::ConvertSelectedPathsToGuides => {
+				let metadata = &self.metadata;
+				let layers = self
+					.selected_nodes
+					.selected_unlocked_layers(metadata)
+					.filter(|&layer| !metadata.is_folder(layer) && !metadata.is_artboard(layer) && get_image_frame(layer, &self.network).is_none())
+					.collect::<Vec<_>>();
+
+				let mut converted = Vec::new();
+				for layer in layers {
+					let transform = metadata.transform_to_document(layer);
+					let subpaths = metadata
+						.layer_outline(layer)
+						.map(|subpath| {
+							let mut subpath = subpath.clone();
+							subpath.apply_transform(transform);
+							subpath
+						})
+						.collect::<Vec<_>>();
+					if subpaths.is_empty() {
+						continue;
+					}
+
+					self.guides.add(Guide::Path(subpaths));
+					converted.push(layer);
+				}
+
+				if converted.is_empty() {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Cannot convert to guides".to_string(),
+						description: "Select one or more unlocked path layers to turn them into guides".to_string(),
+					});
+					return;
+				}
+
+				// The guides take the place of the layers they were made from
+				self.backup(responses);
+				responses.add_front(BroadcastEvent::SelectionChanged);
+				for layer in converted {
+					responses.add_front(DocumentMessage::DeleteLayer { layer });
+				}
+				responses.add(OverlaysMessage::Draw);
+			}
 			DocumentMessage::CreateEmptyFolder => {
 				let id = NodeId(generate_uuid());
 
@@ -331,6 +604,17 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					responses.add_front(DocumentMessage::DeleteLayer { layer: *path.last().unwrap() });
 				}
 			}
+			DocumentMessage::DeleteParameter { id } => {
+				self.backup(responses);
+				self.parameters.remove(id);
+				responses.add(DocumentMessage::UpdateParametersPanel);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			DocumentMessage::DeleteSwatch { id } => {
+				self.backup(responses);
+				self.swatches.remove(id);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::DeselectAllLayers => {
 				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![] });
 				self.layer_range_selection_reference = None;
@@ -356,6 +640,14 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					insert_index: calculated_insert_index,
 				});
 			}
+			DocumentMessage::ExportSwatches { format } => {
+				let data = palette_file::write_palette(&self.swatches.library, &self.name, format);
+				responses.add(FrontendMessage::TriggerDownloadBinaryFile {
+					name: format!("{}.{}", self.name, format.extension()),
+					mime: format.mime().into(),
+					data,
+				});
+			}
 			DocumentMessage::FlipSelectedLayers { flip_axis } => {
 				self.backup(responses);
 				let scale = match flip_axis {
@@ -488,6 +780,11 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 						pre_node_id: first_unselected_parent_folder.to_node(),
 					});
 
+					// Fold the transforms of the folders the layer was lifted out of into the layer, so it doesn't visually move
+					let old_parent_transform = self.metadata.downstream_transform_to_viewport(layer);
+					let new_parent_transform = self.metadata.downstream_transform_to_viewport(first_unselected_parent_folder);
+					self.preserve_layer_placement(layer, old_parent_transform, new_parent_transform, responses);
+
 					responses.add(GraphOperationMessage::ShiftUpstream {
 						node_id: first_unselected_parent_folder.to_node(),
 						shift: IVec2::new(0, 3),
@@ -513,6 +810,23 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(DocumentMessage::DocumentStructureChanged);
 				responses.add(NodeGraphMessage::SendGraph);
 			}
+			DocumentMessage::GuideOverlays(mut overlay_context) => {
+				if !self.guides.is_empty() {
+					guide_overlay(self, &mut overlay_context)
+				}
+			}
+			DocumentMessage::GuidesFromSelection => {
+				let Some(bounds) = self.metadata().selected_bounds_document_space(true, &self.selected_nodes) else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Cannot add guides".to_string(),
+						description: "Select one or more layers to add guides along the edges and centers of their bounds".to_string(),
+					});
+					return;
+				};
+
+				self.guides.add_from_bounds(bounds);
+				responses.add(OverlaysMessage::Draw);
+			}
 			DocumentMessage::ImaginateGenerate => responses.add(PortfolioMessage::SubmitGraphRender { document_id }),
 			DocumentMessage::ImaginateRandom { imaginate_node, then_generate } => {
 				// Generate a random seed. We only want values between -2^53 and 2^53, because integer values
@@ -550,66 +864,45 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					insert_index,
 				});
 			}
+			DocumentMessage::ImportSwatches { filename, content } => match palette_file::read_palette(&filename, &content) {
+				Ok(swatches) => {
+					self.backup(responses);
+					for swatch in swatches {
+						self.swatches.library.add(swatch);
+					}
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+				Err(description) => responses.add(DialogMessage::DisplayDialogError {
+					title: "Couldn't import swatches".into(),
+					description,
+				}),
+			},
+			DocumentMessage::LinkInputToSwatch { node_path, input_index, swatch } => {
+				self.backup(responses);
+				self.swatches.link_input(VariableBinding { node_path, input_index }, swatch, &mut self.network);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::MoveSelectedLayersTo { parent, insert_index } => {
 				responses.add(DocumentMessage::StartTransaction);
 
 				let selected_layers = self.selected_nodes.selected_layers(self.metadata()).collect::<Vec<_>>();
-				// Disallow trying to insert into self.
-				if selected_layers.iter().any(|&layer| parent.ancestors(self.metadata()).any(|ancestor| ancestor == layer)) {
-					return;
-				}
-				// Artboards can only have `ROOT_PARENT` as the parent.
-				let any_artboards = selected_layers.iter().any(|&layer| self.metadata.is_artboard(layer));
-				if any_artboards && parent != LayerNodeIdentifier::ROOT_PARENT {
-					return;
-				}
-
-				// Non-artboards cannot be put at the top level if artboards also exist there
-				let selected_any_non_artboards = selected_layers.iter().any(|&layer| !self.metadata.is_artboard(layer));
-				let top_level_artboards = LayerNodeIdentifier::ROOT_PARENT.children(self.metadata()).any(|layer| self.metadata.is_artboard(layer));
-				if selected_any_non_artboards && parent == LayerNodeIdentifier::ROOT_PARENT && top_level_artboards {
+				if !self.can_move_layers_to(&selected_layers, parent) {
 					return;
 				}
-				let mut insert_index = if insert_index < 0 { 0 } else { insert_index as usize };
-
-				let layer_above_insertion = if insert_index == 0 { Some(parent) } else { parent.children(&self.metadata).nth(insert_index - 1) };
 
 				let binding = self.metadata.shallowest_unique_layers(self.selected_nodes.selected_layers(&self.metadata));
-				let get_last_elements = binding.iter().map(|x| x.last().expect("empty path")).collect::<Vec<_>>();
-
-				// TODO: The `.collect()` is necessary to avoid borrowing issues with `self`. See if this can be avoided to improve performance.
-				let ordered_last_elements = self.metadata.all_layers().filter(|layer| get_last_elements.contains(&layer)).rev().collect::<Vec<_>>();
-				for layer_to_move in ordered_last_elements {
-					if insert_index > 0
-						&& layer_to_move
-							.upstream_siblings(&self.metadata)
-							.any(|layer| layer_above_insertion.is_some_and(|layer_above_insertion| layer_above_insertion == layer))
-					{
-						insert_index -= 1;
-					}
-
-					// `layer_to_move` should never be `ROOT_PARENT`, since it is not included in `all_layers()`
-					if layer_to_move == LayerNodeIdentifier::ROOT_PARENT {
-						log::error!("Layer to move cannot be root parent");
-						continue;
-					}
-
-					// Disconnect layer to move and reconnect downstream node to upstream sibling if it exists.
-					responses.add(GraphOperationMessage::DisconnectNodeFromStack {
-						node_id: layer_to_move.to_node(),
-						reconnect_to_sibling: true,
-					});
-					// Reconnect layer_to_move to new parent at insert index.
-					responses.add(GraphOperationMessage::InsertNodeAtStackIndex {
-						node_id: layer_to_move.to_node(),
-						parent: parent,
-						insert_index,
-					});
-				}
+				let layers_to_move = binding.iter().filter_map(|path| path.last().copied()).collect::<Vec<_>>();
+				self.move_layers_to(&layers_to_move, parent, insert_index, responses);
 
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 				responses.add(NodeGraphMessage::SendGraph);
 			}
+			DocumentMessage::MoveSwatch { id, index } => {
+				self.backup(responses);
+				self.swatches.library.reorder(id, index);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::NudgeSelectedLayers {
 				delta_x,
 				delta_y,
@@ -678,67 +971,86 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				}
 			}
 			DocumentMessage::PasteImage { image, mouse } => {
-				// All the image's pixels have been converted to 0..=1, linear, and premultiplied by `Color::from_rgba8_srgb`
-
 				let image_size = DVec2::new(image.width as f64, image.height as f64);
-
-				// Align the layer with the mouse or center of viewport
-				let viewport_location = mouse.map_or(ipp.viewport_bounds.center() + ipp.viewport_bounds.top_left, |pos| pos.into());
-				let center_in_viewport = DAffine2::from_translation(self.metadata().document_to_viewport.inverse().transform_point2(viewport_location - ipp.viewport_bounds.top_left));
-				let center_in_viewport_layerspace = center_in_viewport;
-
-				// Scale the image to fit into a 512x512 box
-				let image_size = image_size / DVec2::splat((image_size.max_element() / 512.).max(1.));
-
-				// Make layer the size of the image
-				let fit_image_size = DAffine2::from_scale_angle_translation(image_size, 0., image_size / -2.);
-
-				let transform = center_in_viewport_layerspace * fit_image_size;
-
-				responses.add(DocumentMessage::StartTransaction);
-
-				let image_frame = ImageFrame { image, ..Default::default() };
-
-				use crate::messages::tool::common_functionality::graph_modification_utils;
-				let layer = graph_modification_utils::new_image_layer(image_frame, NodeId(generate_uuid()), self.new_layer_parent(true), responses);
-
-				// `layer` cannot be `ROOT_PARENT` since it is the newly created layer
-				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
-
-				responses.add(GraphOperationMessage::TransformSet {
-					layer,
-					transform,
-					transform_in: TransformIn::Local,
-					skip_rerender: false,
-				});
-
-				// Force chosen tool to be Select Tool after importing image.
-				responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Select });
+				self.paste_image(image, image_size, mouse, ipp, responses);
+			}
+			DocumentMessage::PastePlaceholderImage {
+				placeholder,
+				width,
+				height,
+				key,
+				mouse,
+			} => {
+				// The layer is given the size of the full image, which it keeps once the placeholder is replaced by the full image
+				let layer = self.paste_image(placeholder, DVec2::new(width as f64, height as f64), mouse, ipp, responses);
+				self.placeholder_images.insert(key, layer);
 			}
 			DocumentMessage::PasteSvg { svg, mouse } => {
 				use crate::messages::tool::common_functionality::graph_modification_utils;
 				let viewport_location = mouse.map_or(ipp.viewport_bounds.center() + ipp.viewport_bounds.top_left, |pos| pos.into());
 				let center_in_viewport = DAffine2::from_translation(self.metadata().document_to_viewport.inverse().transform_point2(viewport_location - ipp.viewport_bounds.top_left));
-				let layer = graph_modification_utils::new_svg_layer(svg, center_in_viewport, NodeId(generate_uuid()), self.new_layer_parent(true), responses);
+
+				// Center the SVG on the location rather than placing its top left corner there
+				let svg_size = usvg::Tree::from_str(&svg, &usvg::Options::default()).map_or(DVec2::ZERO, |tree| DVec2::new(tree.size.width() as f64, tree.size.height() as f64));
+				let transform = center_in_viewport * DAffine2::from_translation(svg_size / -2.);
+
+				let layer = graph_modification_utils::new_svg_layer(svg, transform, NodeId(generate_uuid()), self.new_layer_parent(true), responses);
 				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
 				responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Select });
 			}
+			DocumentMessage::PickingBufferRasterized { key, rgba } => {
+				self.metadata.fill_picking_buffer(key, &rgba);
+			}
 			DocumentMessage::Redo => {
 				responses.add(SelectToolMessage::Abort);
 				responses.add(DocumentMessage::DocumentHistoryForward);
 				responses.add(ToolMessage::Redo);
 				responses.add(OverlaysMessage::Draw);
 			}
+			DocumentMessage::RemoveKeyframe { node_path, input_index, time } => {
+				self.backup(responses);
+				self.timeline.remove_keyframe(&VariableBinding { node_path, input_index }, time);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::RemoveVariant { variant_index } => {
+				self.backup(responses);
+				self.variables.remove_variant(variant_index, &mut self.network);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			DocumentMessage::RenameDocument { new_name } => {
 				self.name = new_name;
 				responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 				responses.add(NodeGraphMessage::UpdateNewNodeGraph);
 			}
+			DocumentMessage::RenameParameter { id, name } => {
+				self.backup(responses);
+				self.parameters.rename(id, name);
+				responses.add(DocumentMessage::UpdateParametersPanel);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			DocumentMessage::RenameSwatch { id, name } => {
+				self.backup(responses);
+				self.swatches.library.rename(id, name);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::RenderRulers => {
 				let document_transform_scale = self.navigation_handler.snapped_zoom(self.navigation.zoom);
 
 				let ruler_origin = if !self.graph_view_overlay_open {
-					self.metadata().document_to_viewport.transform_point2(DVec2::ZERO)
+					let document_origin = if self.artboard_relative_coordinates {
+						// Measure from the artboard containing the selection, or otherwise the first artboard
+						let layer = self
+							.metadata()
+							.deepest_common_ancestor(self.selected_nodes.selected_layers(self.metadata()), true)
+							.unwrap_or_else(|| self.metadata().active_artboard());
+						self.metadata().artboard_origin(layer).unwrap_or_default()
+					} else {
+						DVec2::ZERO
+					};
+					self.metadata().document_to_viewport.transform_point2(document_origin)
 				} else {
 					let Some(network) = self.network.nested_network(&self.node_graph_handler.network) else {
 						log::error!("Nested network not found in UpdateDocumentTransform");
@@ -746,9 +1058,12 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					};
 					network.node_graph_to_viewport.transform_point2(DVec2::ZERO)
 				};
-				let log = document_transform_scale.log2();
+				// The numbers along the rulers count the document's units, except in the node graph which is measured in pixels
+				let units_scale = if self.graph_view_overlay_open { 1. } else { self.units.pixels_per_unit() };
+				let viewport_per_unit = document_transform_scale * units_scale;
+				let log = viewport_per_unit.log2();
 				let ruler_interval: f64 = if log < 0. { 100. * 2_f64.powf(-log.ceil()) } else { 100. / 2_f64.powf(log.ceil()) };
-				let ruler_spacing = ruler_interval * document_transform_scale;
+				let ruler_spacing = ruler_interval * viewport_per_unit;
 
 				responses.add(FrontendMessage::UpdateDocumentRulers {
 					origin: ruler_origin.into(),
@@ -782,6 +1097,18 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					multiplier: scrollbar_multiplier.into(),
 				});
 			}
+			DocumentMessage::ReplacePlaceholderImage { key, image } => {
+				let Some(layer) = self.placeholder_images.remove(&key) else { return };
+				if self.replace_placeholder_image(layer, image) {
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+				}
+			}
+			DocumentMessage::ScrubTimeline { time } => {
+				self.timeline.set_current_time(time, &mut self.network);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			DocumentMessage::SaveDocument => {
 				self.set_save_state(true);
 				responses.add(PortfolioMessage::AutoSaveActiveDocument);
@@ -797,6 +1124,27 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					name,
 				})
 			}
+			DocumentMessage::SaveNodeGraph => {
+				let name = self.name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&self.name).to_string() + NODE_GRAPH_SAVE_SUFFIX;
+				match network_to_text(&self.network) {
+					Ok(document) => responses.add(FrontendMessage::TriggerDownloadTextFile { document, name }),
+					Err(description) => responses.add(DialogMessage::DisplayDialogError {
+						title: "Failed to save the node graph".to_string(),
+						description,
+					}),
+				}
+			}
+			DocumentMessage::SaveNodePreset { name } => {
+				let selected = self.selected_nodes.selected_nodes_ref();
+				let Some(network) = self.network.nested_network_for_selected_nodes(&self.node_graph_handler.network, selected.iter()) else {
+					return;
+				};
+
+				let nodes = selected.iter().filter_map(|node_id| network.nodes.get(node_id)).map(PresetNode::from_node).collect::<Vec<_>>();
+				if !nodes.is_empty() {
+					responses.add(PreferencesMessage::AddNodePreset { name, nodes });
+				}
+			}
 			DocumentMessage::SelectAllLayers => {
 				let metadata = self.metadata();
 				let all_layers_except_artboards_invisible_and_locked = metadata
@@ -806,6 +1154,23 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				let nodes = all_layers_except_artboards_invisible_and_locked.map(|layer| layer.to_node()).collect();
 				responses.add(NodeGraphMessage::SelectedNodesSet { nodes });
 			}
+			DocumentMessage::SelectChildLayers => {
+				let metadata = self.metadata();
+				let layers = self
+					.selected_nodes
+					.selected_layers(metadata)
+					.flat_map(|layer| {
+						// Layers which aren't groups stay selected
+						let children = layer.children(metadata).collect::<Vec<_>>();
+						if children.is_empty() {
+							vec![layer]
+						} else {
+							children
+						}
+					})
+					.collect();
+				self.select_traversed_layers(layers, responses);
+			}
 			DocumentMessage::SelectedLayersLower => {
 				responses.add(DocumentMessage::SelectedLayersReorder { relative_index_offset: 1 });
 			}
@@ -876,11 +1241,92 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					}
 				}
 			}
+			DocumentMessage::SelectLayerInDirection { direction } => {
+				self.select_layer_in_direction(direction, responses);
+			}
+			DocumentMessage::SelectNextSiblingLayer => {
+				self.select_sibling_layers(true, responses);
+			}
+			DocumentMessage::SelectParentLayer => {
+				let metadata = self.metadata();
+				let layers = self
+					.selected_nodes
+					.selected_layers(metadata)
+					.map(|layer| layer.parent(metadata).filter(|&parent| parent != LayerNodeIdentifier::ROOT_PARENT).unwrap_or(layer))
+					.collect();
+				self.select_traversed_layers(layers, responses);
+			}
+			DocumentMessage::SelectPreviousSiblingLayer => {
+				self.select_sibling_layers(false, responses);
+			}
+			DocumentMessage::SetActiveVariant { variant_index } => {
+				self.backup(responses);
+				self.variables.set_active_variant(variant_index, &mut self.network);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			DocumentMessage::SetBlendModeForSelectedLayers { blend_mode } => {
 				for layer in self.selected_nodes.selected_layers_except_artboards(self.metadata()) {
 					responses.add(GraphOperationMessage::BlendModeSet { layer, blend_mode });
 				}
 			}
+			DocumentMessage::SetDocumentUnits { units } => {
+				self.units = units;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(DocumentMessage::RenderRulers);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::SetKeyframe {
+				node_path,
+				input_index,
+				interpolation,
+			} => {
+				self.backup(responses);
+				self.timeline.set_keyframe(VariableBinding { node_path, input_index }, interpolation, &self.network);
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::SetKeyframeInterpolation {
+				node_path,
+				input_index,
+				time,
+				interpolation,
+			} => {
+				self.backup(responses);
+				self.timeline.set_interpolation(&VariableBinding { node_path, input_index }, time, interpolation);
+				self.timeline.set_current_time(self.timeline.current_time, &mut self.network);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+			}
+			DocumentMessage::SetLinearCompositing { linear_compositing } => {
+				self.linear_compositing = linear_compositing;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::SetOutputPalette { show_output_palette, output_palette } => {
+				self.show_output_palette = show_output_palette;
+				self.output_palette = output_palette;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::SetOutputProfile { output_profile } => {
+				self.output_profile = output_profile;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::SetSoftProof { show_soft_proof, gamut_warning } => {
+				self.show_soft_proof = show_soft_proof;
+				self.gamut_warning = gamut_warning;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::SetOnionSkin { show_onion_skin, onion_skin } => {
+				self.timeline.show_onion_skin = show_onion_skin;
+				self.timeline.onion_skin = onion_skin;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			DocumentMessage::SetOpacityForSelectedLayers { opacity } => {
 				self.backup(responses);
 				let opacity = opacity.clamp(0., 1.);
@@ -956,6 +1402,21 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					};
 				}
 			}
+			DocumentMessage::SetParameterValue { id, value } => {
+				// Every input bound to the parameter takes on its new value
+				if self.parameters.set_value(id, value, &mut self.network) {
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+				responses.add(DocumentMessage::UpdateParametersPanel);
+			}
+			DocumentMessage::SetSwatchFill { id, fill } => {
+				// Every input linked to the swatch is re-colored with it
+				if self.swatches.set_fill(id, fill, &mut self.network) {
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+				}
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::SetViewMode { view_mode } => {
 				self.view_mode = view_mode;
 				responses.add_front(NodeGraphMessage::RunDocumentGraph);
@@ -984,6 +1445,17 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				self.snapping_state.snapping_enabled = !self.snapping_state.snapping_enabled;
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 			}
+			DocumentMessage::UnbindInputFromParameter { node_path, input_index } => {
+				self.backup(responses);
+				self.parameters.unbind_input(&VariableBinding { node_path, input_index });
+				responses.add(PropertiesPanelMessage::Refresh);
+				responses.add(DocumentMessage::UpdateParametersPanel);
+			}
+			DocumentMessage::UnbindInputFromVariable { node_path, input_index } => {
+				self.backup(responses);
+				self.variables.unbind_input(&VariableBinding { node_path, input_index });
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
 			DocumentMessage::Undo => {
 				self.undo_in_progress = true;
 				responses.add(ToolMessage::PreUndo);
@@ -999,7 +1471,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(DocumentMessage::StartTransaction);
 
 				let folder_paths = self.metadata().folders_sorted_by_most_nested(self.selected_nodes.selected_layers(self.metadata()));
-				for folder in folder_paths {
+				for &folder in &folder_paths {
 					if folder == LayerNodeIdentifier::ROOT_PARENT {
 						log::error!("ROOT_PARENT cannot be selected when ungrouping selected layers");
 						continue;
@@ -1016,6 +1488,21 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 						return;
 					};
 
+					// Fold the folder's transform into each layer that becomes a child of the folder's parent, so nothing visually moves.
+					// Children of nested folders that are also being ungrouped are reached through those folders, which were already ungrouped since they're more nested.
+					let old_parent_transform = self.metadata.downstream_transform_to_viewport(child_layer);
+					let new_parent_transform = self.metadata.downstream_transform_to_viewport(folder);
+					let mut folders_to_visit = vec![folder];
+					while let Some(folder_to_visit) = folders_to_visit.pop() {
+						for child in folder_to_visit.children(&self.metadata) {
+							if folder_paths.contains(&child) {
+								folders_to_visit.push(child);
+							} else {
+								self.preserve_layer_placement(child, old_parent_transform, new_parent_transform, responses);
+							}
+						}
+					}
+
 					// Move child_layer stack x position to folder stack
 					let child_layer_node = self.network.nodes.get(&child_layer.to_node()).expect("Child node should always exist for layer");
 					let offset = folder_node.metadata.position - child_layer_node.metadata.position;
@@ -1086,6 +1573,15 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(DocumentMessage::DocumentStructureChanged);
 				responses.add(NodeGraphMessage::SendGraph);
 			}
+			DocumentMessage::UnlinkInputFromSwatch { node_path, input_index } => {
+				self.backup(responses);
+				self.swatches.unlink_input(&VariableBinding { node_path, input_index });
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			DocumentMessage::UpdateDocumentInfo { info } => {
+				self.info = info;
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 			DocumentMessage::UpdateDocumentTransform { transform } => {
 				responses.add(DocumentMessage::RenderRulers);
 				responses.add(DocumentMessage::RenderScrollbars);
@@ -1112,6 +1608,22 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 			}
+			DocumentMessage::UpdateExportProgress { progress } => {
+				self.export_progress = progress;
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::UpdateParametersPanel => self.update_parameters_panel(responses),
+			DocumentMessage::UpdatePickingBuffer => {
+				let key = generate_uuid();
+				let size = ipp.viewport_bounds.size().as_uvec2();
+				if let Some(svg) = self.metadata.request_picking_buffer(key, size) {
+					responses.add(FrontendMessage::TriggerRasterizePickingBuffer {
+						svg,
+						key,
+						size: size.as_dvec2().into(),
+					});
+				}
+			}
 			DocumentMessage::ZoomCanvasTo100Percent => {
 				responses.add_front(NavigationMessage::CanvasZoomSet { zoom_factor: 1. });
 			}
@@ -1146,6 +1658,8 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			Redo,
 			SaveDocument,
 			SelectAllLayers,
+			SelectNextSiblingLayer,
+			SelectPreviousSiblingLayer,
 			SetSnapping,
 			ToggleGridVisibility,
 			ToggleOverlaysVisibility,
@@ -1163,10 +1677,13 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				DuplicateSelectedLayers,
 				GroupSelectedLayers,
 				NudgeSelectedLayers,
+				SelectChildLayers,
 				SelectedLayersLower,
 				SelectedLayersLowerToBack,
 				SelectedLayersRaise,
 				SelectedLayersRaiseToFront,
+				SelectLayerInDirection,
+				SelectParentLayer,
 				UngroupSelectedLayers,
 			);
 			common.extend(select);
@@ -1181,6 +1698,8 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 		// More additional actions
 		common.extend(self.navigation_handler.actions());
 		common.extend(self.node_graph_handler.actions());
+		common.extend(self.search_message_handler.actions());
+		common.extend(self.spellcheck_message_handler.actions());
 		common.extend(actions!(GraphOperationMessageDiscriminant; ToggleSelectedLocked, ToggleSelectedVisibility));
 		common
 	}
@@ -1191,7 +1710,7 @@ impl DocumentMessageHandler {
 	pub fn intersect_quad<'a>(&'a self, viewport_quad: graphene_core::renderer::Quad, network: &'a NodeNetwork) -> impl Iterator<Item = LayerNodeIdentifier> + 'a {
 		let document_quad = self.metadata.document_to_viewport.inverse() * viewport_quad;
 		self.metadata
-			.all_layers()
+			.layers_intersecting_bounds(document_quad.bounding_box())
 			.filter(|&layer| self.selected_nodes.layer_visible(layer, self.metadata()))
 			.filter(|&layer| !self.selected_nodes.layer_locked(layer, self.metadata()))
 			.filter(|&layer| !is_artboard(layer, network))
@@ -1203,8 +1722,13 @@ impl DocumentMessageHandler {
 	/// Find all of the layers that were clicked on from a viewport space location
 	pub fn click_xray(&self, viewport_location: DVec2) -> impl Iterator<Item = LayerNodeIdentifier> + '_ {
 		let point = self.metadata.document_to_viewport.inverse().transform_point2(viewport_location);
-		self.metadata
-			.all_layers()
+		// Only the layers whose bounds contain the point are tested, but they are still listed in layer order
+		let candidates = self.metadata.layers_at_point(point).collect::<HashSet<_>>();
+		(!candidates.is_empty())
+			.then(|| self.metadata.all_layers())
+			.into_iter()
+			.flatten()
+			.filter(move |layer| candidates.contains(layer))
 			.filter(|&layer| self.selected_nodes.layer_visible(layer, self.metadata()))
 			.filter(|&layer| !self.selected_nodes.layer_locked(layer, self.metadata()))
 			.filter_map(|layer| self.metadata.click_target(layer).map(|targets| (layer, targets)))
@@ -1256,6 +1780,11 @@ impl DocumentMessageHandler {
 		self.click_list(viewport_location, network).last().copied()
 	}
 
+	/// Find the deepest layer under the cursor at a location in viewport space while hovering, which is read from the picking buffer on documents dense enough to have one.
+	pub fn hover(&self, viewport_location: DVec2) -> Option<LayerNodeIdentifier> {
+		self.metadata.picked_layer(viewport_location).unwrap_or_else(|| self.click(viewport_location, &self.network))
+	}
+
 	/// Get the combined bounding box of the click targets of the selected visible layers in viewport space
 	pub fn selected_visible_layers_bounding_box_viewport(&self) -> Option<[DVec2; 2]> {
 		self.selected_nodes
@@ -1311,66 +1840,14 @@ impl DocumentMessageHandler {
 	}
 
 	pub fn deserialize_document(serialized_content: &str) -> Result<Self, EditorError> {
-		// serde_json::from_str(serialized_content).map_err(|e| EditorError::DocumentDeserialization(e.to_string()))
-
-		match serde_json::from_str::<Self>(serialized_content).map_err(|e| EditorError::DocumentDeserialization(e.to_string())) {
-			Ok(mut document) => {
-				for (_, node) in &mut document.network.nodes {
-					// Upgrade Fill nodes to the format change in #1778
-					// TODO: Eventually remove this (probably starting late 2024)
-					if node.name == "Fill" && node.inputs.len() == 8 {
-						let node_definition = crate::messages::portfolio::document::node_graph::document_node_types::resolve_document_node_type(&node.name).unwrap();
-						let default_definition_node = node_definition.default_document_node();
-
-						node.implementation = default_definition_node.implementation.clone();
-						let old_inputs = std::mem::replace(&mut node.inputs, default_definition_node.inputs.clone());
-
-						node.inputs[0] = old_inputs[0].clone();
-
-						let Some(fill_type) = old_inputs[1].as_value().cloned() else { continue };
-						let TaggedValue::FillType(fill_type) = fill_type else { continue };
-						let Some(solid_color) = old_inputs[2].as_value().cloned() else { continue };
-						let TaggedValue::OptionalColor(solid_color) = solid_color else { continue };
-						let Some(gradient_type) = old_inputs[3].as_value().cloned() else { continue };
-						let TaggedValue::GradientType(gradient_type) = gradient_type else { continue };
-						let Some(start) = old_inputs[4].as_value().cloned() else { continue };
-						let TaggedValue::DVec2(start) = start else { continue };
-						let Some(end) = old_inputs[5].as_value().cloned() else { continue };
-						let TaggedValue::DVec2(end) = end else { continue };
-						let Some(transform) = old_inputs[6].as_value().cloned() else { continue };
-						let TaggedValue::DAffine2(transform) = transform else { continue };
-						let Some(positions) = old_inputs[7].as_value().cloned() else { continue };
-						let TaggedValue::GradientStops(positions) = positions else { continue };
-
-						let fill = match (fill_type, solid_color) {
-							(FillType::Solid, None) => Fill::None,
-							(FillType::Solid, Some(color)) => Fill::Solid(color),
-							(FillType::Gradient, _) => Fill::Gradient(Gradient {
-								stops: positions,
-								gradient_type,
-								start,
-								end,
-								transform,
-							}),
-						};
-						node.inputs[1] = NodeInput::value(TaggedValue::Fill(fill.clone()), false);
-						match fill {
-							Fill::None => {
-								node.inputs[2] = NodeInput::value(TaggedValue::OptionalColor(None), false);
-							}
-							Fill::Solid(color) => {
-								node.inputs[2] = NodeInput::value(TaggedValue::OptionalColor(Some(color)), false);
-							}
-							Fill::Gradient(gradient) => {
-								node.inputs[3] = NodeInput::value(TaggedValue::Gradient(gradient), false);
-							}
-						}
-					}
-				}
-				Ok(document)
-			}
-			Err(e) => Err(e),
-		}
+		let mut serialized_document = serde_json::from_str::<serde_json::Value>(serialized_content).map_err(|e| EditorError::DocumentDeserialization(e.to_string()))?;
+		let version = document_migration::migrate_serialized_document(&mut serialized_document)?;
+
+		let mut document = serde_json::from_value::<Self>(serialized_document).map_err(|e| EditorError::DocumentDeserialization(e.to_string()))?;
+		document_migration::migrate_document(&mut document, version);
+		document.document_version = document_migration::DOCUMENT_VERSION;
+
+		Ok(document)
 
 		// TODO: This can be used, if uncommented, to upgrade demo artwork with outdated document node internals from their definitions. Delete when it's no longer needed.
 		// Used for upgrading old internal networks for demo artwork nodes. Will reset all node internals for any opened file
@@ -1398,11 +1875,29 @@ impl DocumentMessageHandler {
 	}
 
 	pub fn with_name_and_content(name: String, serialized_content: String) -> Result<Self, EditorError> {
+		// A node graph saved on its own as text opens as a new document containing just that graph
+		if let Some(name) = name.strip_suffix(NODE_GRAPH_SAVE_SUFFIX) {
+			let network = network_from_text(&serialized_content).map_err(EditorError::DocumentDeserialization)?;
+			return Ok(Self {
+				name: name.to_string(),
+				network,
+				..Default::default()
+			});
+		}
+
 		let mut document = Self::deserialize_document(&serialized_content)?;
 		document.name = name;
 		Ok(document)
 	}
 
+	/// Drops the caches saved with the document, which are its compiled graph, the outputs of its cache nodes, and the images cached by its Brush nodes, so they're made again from scratch.
+	/// This is done in safe mode, since a cache made by a build or plugin that crashed the editor may crash it again.
+	pub fn discard_caches(&mut self) {
+		self.compiled_graph = None;
+		self.evaluation_cache = None;
+		discard_brush_caches(&mut self.network);
+	}
+
 	/// Called recursively by the entry function [`serialize_root`].
 	fn serialize_structure(&self, folder: LayerNodeIdentifier, structure_section: &mut Vec<u64>, data_section: &mut Vec<u64>, path: &mut Vec<LayerNodeIdentifier>) {
 		let mut space = 0;
@@ -1471,8 +1966,9 @@ impl DocumentMessageHandler {
 
 	/// Places a document into the history system
 	fn backup_with_document(&mut self, network: NodeNetwork, responses: &mut VecDeque<Message>) {
+		let snapshot = self.snapshot(network);
 		self.document_redo_history.clear();
-		self.document_undo_history.push_back(network);
+		self.document_undo_history.push_back(snapshot);
 		if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 			self.document_undo_history.pop_front();
 		}
@@ -1497,10 +1993,37 @@ impl DocumentMessageHandler {
 		std::mem::replace(&mut self.network, network)
 	}
 
+	/// Pairs the network with the current state of the rest of the document which is kept in the history.
+	fn snapshot(&self, network: NodeNetwork) -> DocumentSnapshot {
+		DocumentSnapshot {
+			network,
+			variables: self.variables.clone(),
+			timeline: self.timeline.clone(),
+			swatches: self.swatches.clone(),
+			parameters: self.parameters.clone(),
+			custom_nodes: self.custom_nodes.clone(),
+		}
+	}
+
+	/// Brings the document back to the snapshot, returning a snapshot of the state it replaced.
+	fn restore_snapshot(&mut self, snapshot: DocumentSnapshot, responses: &mut VecDeque<Message>) -> DocumentSnapshot {
+		let previous = DocumentSnapshot {
+			network: std::mem::replace(&mut self.network, snapshot.network),
+			variables: std::mem::replace(&mut self.variables, snapshot.variables),
+			timeline: std::mem::replace(&mut self.timeline, snapshot.timeline),
+			swatches: std::mem::replace(&mut self.swatches, snapshot.swatches),
+			parameters: std::mem::replace(&mut self.parameters, snapshot.parameters),
+			custom_nodes: std::mem::replace(&mut self.custom_nodes, snapshot.custom_nodes),
+		};
+		responses.add(PortfolioMessage::UpdateDocumentWidgets);
+		responses.add(DocumentMessage::UpdateParametersPanel);
+		previous
+	}
+
 	pub fn undo_with_history(&mut self, responses: &mut VecDeque<Message>) {
-		let Some(previous_network) = self.undo(responses) else { return };
+		let Some(previous_snapshot) = self.undo(responses) else { return };
 
-		self.document_redo_history.push_back(previous_network);
+		self.document_redo_history.push_back(previous_snapshot);
 		if self.document_redo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 			self.document_redo_history.pop_front();
 		}
@@ -1509,33 +2032,31 @@ impl DocumentMessageHandler {
 			self.node_graph_handler.update_all_click_targets(&mut self.network, self.node_graph_handler.network.clone())
 		}
 	}
-	pub fn undo(&mut self, responses: &mut VecDeque<Message>) -> Option<NodeNetwork> {
+	fn undo(&mut self, responses: &mut VecDeque<Message>) -> Option<DocumentSnapshot> {
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 		// If there is no history return and don't broadcast SelectionChanged
-		let Some(network) = self.document_undo_history.pop_back() else { return None };
+		let Some(snapshot) = self.document_undo_history.pop_back() else { return None };
 
 		responses.add(BroadcastEvent::SelectionChanged);
 
-		let previous_network = std::mem::replace(&mut self.network, network);
-		Some(previous_network)
+		Some(self.restore_snapshot(snapshot, responses))
 	}
-	pub fn redo(&mut self, responses: &mut VecDeque<Message>) -> Option<NodeNetwork> {
+	fn redo(&mut self, responses: &mut VecDeque<Message>) -> Option<DocumentSnapshot> {
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 		// If there is no history return and don't broadcast SelectionChanged
-		let Some(network) = self.document_redo_history.pop_back() else { return None };
+		let Some(snapshot) = self.document_redo_history.pop_back() else { return None };
 
 		responses.add(BroadcastEvent::SelectionChanged);
 
-		let previous_network = std::mem::replace(&mut self.network, network);
-		Some(previous_network)
+		Some(self.restore_snapshot(snapshot, responses))
 	}
 	pub fn redo_with_history(&mut self, responses: &mut VecDeque<Message>) {
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
-		let Some(previous_network) = self.redo(responses) else { return };
+		let Some(previous_snapshot) = self.redo(responses) else { return };
 
-		self.document_undo_history.push_back(previous_network);
+		self.document_undo_history.push_back(previous_snapshot);
 		if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 			self.document_undo_history.pop_front();
 		}
@@ -1546,7 +2067,7 @@ impl DocumentMessageHandler {
 	}
 
 	pub fn current_hash(&self) -> Option<u64> {
-		self.document_undo_history.iter().last().map(|network| network.current_hash())
+		self.document_undo_history.iter().last().map(|snapshot| snapshot.network.current_hash())
 	}
 
 	pub fn is_auto_saved(&self) -> bool {
@@ -1618,9 +2139,77 @@ impl DocumentMessageHandler {
 			})
 	}
 
-	/// Finds the parent folder which, based on the current selections, should be the container of any newly added layers.
-	pub fn new_layer_parent(&self, include_self: bool) -> LayerNodeIdentifier {
-		self.metadata()
+	/// Places the image in a new layer of the given size, scaled down to fit a 512x512 box, which is centered on the mouse or the center of the viewport.
+	fn paste_image(&self, image: Image<Color>, image_size: DVec2, mouse: Option<(f64, f64)>, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> LayerNodeIdentifier {
+		// All the image's pixels have been converted to 0..=1, linear, and premultiplied by `Color::from_rgba8_srgb`
+
+		// Align the layer with the mouse or center of viewport
+		let viewport_location = mouse.map_or(ipp.viewport_bounds.center() + ipp.viewport_bounds.top_left, |pos| pos.into());
+		let center_in_viewport = DAffine2::from_translation(self.metadata().document_to_viewport.inverse().transform_point2(viewport_location - ipp.viewport_bounds.top_left));
+		let center_in_viewport_layerspace = center_in_viewport;
+
+		// Scale the image to fit into a 512x512 box
+		let image_size = image_size / DVec2::splat((image_size.max_element() / 512.).max(1.));
+
+		// Make layer the size of the image
+		let fit_image_size = DAffine2::from_scale_angle_translation(image_size, 0., image_size / -2.);
+
+		let transform = center_in_viewport_layerspace * fit_image_size;
+
+		responses.add(DocumentMessage::StartTransaction);
+
+		let image_frame = ImageFrame { image, ..Default::default() };
+
+		use crate::messages::tool::common_functionality::graph_modification_utils;
+		let layer = graph_modification_utils::new_image_layer(image_frame, NodeId(generate_uuid()), self.new_layer_parent(true), responses);
+
+		// `layer` cannot be `ROOT_PARENT` since it is the newly created layer
+		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
+
+		responses.add(GraphOperationMessage::TransformSet {
+			layer,
+			transform,
+			transform_in: TransformIn::Local,
+			skip_rerender: false,
+		});
+
+		// Force chosen tool to be Select Tool after importing image.
+		responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Select });
+
+		layer
+	}
+
+	/// Swaps the full image into the Image node of a layer placed with a low resolution placeholder, also in the undo and redo history so the placeholder doesn't come back.
+	/// The image is left alone if the placeholder has since been replaced by another image.
+	fn replace_placeholder_image(&mut self, layer: LayerNodeIdentifier, image: Image<Color>) -> bool {
+		use crate::messages::tool::common_functionality::graph_modification_utils::NodeGraphLayer;
+
+		let Some(image_node) = NodeGraphLayer::new(layer, &self.network).upstream_node_id_from_name("Image") else {
+			return false;
+		};
+		fn image_input(network: &mut NodeNetwork, image_node: NodeId) -> Option<&mut Image<Color>> {
+			match network.nodes.get_mut(&image_node)?.inputs.get_mut(0)? {
+				NodeInput::Value {
+					tagged_value: TaggedValue::ImageFrame(image_frame),
+					..
+				} => Some(&mut image_frame.image),
+				_ => None,
+			}
+		}
+		let Some(placeholder) = image_input(&mut self.network, image_node).cloned() else { return false };
+
+		let history = self.document_undo_history.iter_mut().chain(&mut self.document_redo_history).map(|snapshot| &mut snapshot.network);
+		for network in std::iter::once(&mut self.network).chain(history) {
+			if let Some(existing) = image_input(network, image_node).filter(|existing| **existing == placeholder) {
+				*existing = image.clone();
+			}
+		}
+		true
+	}
+
+	/// Finds the parent folder which, based on the current selections, should be the container of any newly added layers.
+	pub fn new_layer_parent(&self, include_self: bool) -> LayerNodeIdentifier {
+		self.metadata()
 			.deepest_common_ancestor(self.selected_nodes.selected_layers(self.metadata()), include_self)
 			.unwrap_or_else(|| self.metadata().active_artboard())
 	}
@@ -1650,12 +2239,19 @@ impl DocumentMessageHandler {
 		let mut fonts = HashSet::new();
 		for (_node_id, node) in self.network.recursive_nodes() {
 			for input in &node.inputs {
-				if let NodeInput::Value {
-					tagged_value: TaggedValue::Font(font),
-					..
-				} = input
-				{
-					fonts.insert(font.clone());
+				match input {
+					NodeInput::Value {
+						tagged_value: TaggedValue::Font(font),
+						..
+					} => {
+						fonts.insert(font.clone());
+					}
+					// The styled runs of text can each have their own font
+					NodeInput::Value {
+						tagged_value: TaggedValue::TextRuns(runs),
+						..
+					} => fonts.extend(runs.0.iter().filter_map(|run| run.style.font.clone())),
+					_ => {}
 				}
 			}
 		}
@@ -1845,6 +2441,72 @@ impl DocumentMessageHandler {
 				.popover_min_width(Some(320))
 				.widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
+		];
+
+		if !self.variables.is_empty() {
+			let entries = vec![self
+				.variables
+				.variants
+				.iter()
+				.enumerate()
+				.map(|(variant_index, name)| {
+					MenuListEntry::new(name.clone())
+						.label(name.clone())
+						.on_commit(move |_| DocumentMessage::SetActiveVariant { variant_index }.into())
+				})
+				.collect()];
+
+			widgets.extend([
+				DropdownInput::new(entries)
+					.selected_index(Some(self.variables.active_variant as u32))
+					.tooltip("Variant whose variable values are shown and edited in the document")
+					.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+			]);
+		}
+
+		if let Some(progress) = &self.export_progress {
+			widgets.extend([TextLabel::new(progress).italic(true).widget_holder(), Separator::new(SeparatorType::Unrelated).widget_holder()]);
+		}
+
+		if self.timeline.animates(self.network()) {
+			widgets.extend([
+				NumberInput::new(Some(self.timeline.current_time))
+					.unit(" s")
+					.min(0.)
+					.max(self.timeline.duration)
+					.step(1. / self.timeline.frame_rate)
+					.tooltip("Time in the animation shown in the document, which scrubs through the keyframes")
+					.on_update(|number_input: &NumberInput| {
+						DocumentMessage::ScrubTimeline {
+							time: number_input.value.unwrap_or_default(),
+						}
+						.into()
+					})
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				CheckboxInput::new(self.timeline.show_onion_skin)
+					.icon("Stack")
+					.tooltip("Onion Skin")
+					.on_update({
+						let onion_skin = self.timeline.onion_skin;
+						move |checkbox: &CheckboxInput| {
+							DocumentMessage::SetOnionSkin {
+								show_onion_skin: checkbox.checked,
+								onion_skin,
+							}
+							.into()
+						}
+					})
+					.widget_holder(),
+				PopoverButton::new()
+					.popover_layout(onion_skin_options(self.timeline.show_onion_skin, self.timeline.onion_skin))
+					.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+			]);
+		}
+
+		widgets.extend([
 			RadioInput::new(vec![
 				RadioEntryData::new("normal")
 					.icon("ViewModeNormal")
@@ -1865,14 +2527,27 @@ impl DocumentMessageHandler {
 			})
 			.widget_holder(),
 			PopoverButton::new()
-				.popover_layout(vec![
-					LayoutGroup::Row {
-						widgets: vec![TextLabel::new("View Mode").bold(true).widget_holder()],
-					},
-					LayoutGroup::Row {
-						widgets: vec![TextLabel::new("Coming soon").widget_holder()],
-					},
-				])
+				.popover_layout(
+					vec![
+						LayoutGroup::Row {
+							widgets: vec![TextLabel::new("View Mode").bold(true).widget_holder()],
+						},
+						LayoutGroup::Row {
+							widgets: vec![
+								CheckboxInput::new(self.linear_compositing)
+									.tooltip("Mix the colors of blends and gradients in linear RGB, which is physically accurate, instead of in sRGB like most other software")
+									.on_update(|checkbox: &CheckboxInput| DocumentMessage::SetLinearCompositing { linear_compositing: checkbox.checked }.into())
+									.widget_holder(),
+								TextLabel::new("Linear Compositing").widget_holder(),
+							],
+						},
+					]
+					.into_iter()
+					.chain(output_palette_options(self.show_output_palette, self.output_palette))
+					.chain(soft_proof_options(self.output_profile, self.show_soft_proof, self.gamut_warning))
+					.chain(units_options(self.units))
+					.collect(),
+				)
 				.widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
 			IconButton::new("ZoomIn", 24)
@@ -1935,7 +2610,7 @@ impl DocumentMessageHandler {
 				.increment_callback_decrease(|_| NavigationMessage::CanvasZoomDecrease { center_on_mouse: false }.into())
 				.increment_callback_increase(|_| NavigationMessage::CanvasZoomIncrease { center_on_mouse: false }.into())
 				.widget_holder(),
-		];
+		]);
 
 		let tilt_value = self.navigation_handler.snapped_tilt(self.navigation.tilt) / (std::f64::consts::PI / 180.);
 		if tilt_value.abs() > 0.00001 {
@@ -2021,7 +2696,7 @@ impl DocumentMessageHandler {
 			})
 			.unwrap_or((None, None));
 
-		let blend_mode_menu_entries = BlendMode::list_svg_subset()
+		let blend_mode_menu_entries = BlendMode::list()
 			.iter()
 			.map(|modes| {
 				modes
@@ -2043,7 +2718,7 @@ impl DocumentMessageHandler {
 		let layers_panel_options_bar = WidgetLayout::new(vec![LayoutGroup::Row {
 			widgets: vec![
 				DropdownInput::new(blend_mode_menu_entries)
-					.selected_index(blend_mode.and_then(|blend_mode| blend_mode.index_in_list_svg_subset()).map(|index| index as u32))
+					.selected_index(blend_mode.and_then(|blend_mode| blend_mode.index_in_list()).map(|index| index as u32))
 					.disabled(disabled)
 					.draw_icon(false)
 					.widget_holder(),
@@ -2112,43 +2787,527 @@ impl DocumentMessageHandler {
 		});
 	}
 
-	pub fn selected_layers_reorder(&mut self, relative_index_offset: isize, responses: &mut VecDeque<Message>) {
-		self.backup(responses);
+	/// Sends the Parameters panel, which lists the document's parameters with their values and how many inputs are bound to each.
+	pub fn update_parameters_panel(&self, responses: &mut VecDeque<Message>) {
+		let mut rows = self
+			.parameters
+			.parameters
+			.iter()
+			.map(|parameter| {
+				let id = parameter.id;
+				let mut widgets = vec![
+					TextInput::new(parameter.name.clone())
+						.tooltip("Parameter name")
+						.on_update(move |text_input: &TextInput| DocumentMessage::RenameParameter { id, name: text_input.value.clone() }.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+				];
+
+				let set_value = move |value: TaggedValue| DocumentMessage::SetParameterValue { id, value }.into();
+				match parameter.value {
+					TaggedValue::F64(number) => widgets.push(
+						NumberInput::new(Some(number))
+							.on_update(move |number_input: &NumberInput| number_input.value.map_or(Message::NoOp, |number| set_value(TaggedValue::F64(number))))
+							.widget_holder(),
+					),
+					TaggedValue::Color(color) => widgets.push(
+						ColorButton::new(FillChoice::Solid(color))
+							.allow_none(false)
+							.on_update(move |color_button: &ColorButton| color_button.value.as_solid().map_or(Message::NoOp, |color| set_value(TaggedValue::Color(color))))
+							.widget_holder(),
+					),
+					TaggedValue::DVec2(vector) => widgets.extend([
+						NumberInput::new(Some(vector.x))
+							.label("X")
+							.on_update(move |number_input: &NumberInput| number_input.value.map_or(Message::NoOp, |x| set_value(TaggedValue::DVec2(DVec2::new(x, vector.y)))))
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						NumberInput::new(Some(vector.y))
+							.label("Y")
+							.on_update(move |number_input: &NumberInput| number_input.value.map_or(Message::NoOp, |y| set_value(TaggedValue::DVec2(DVec2::new(vector.x, y)))))
+							.widget_holder(),
+					]),
+					TaggedValue::Bool(checked) => widgets.push(
+						CheckboxInput::new(checked)
+							.on_update(move |checkbox_input: &CheckboxInput| set_value(TaggedValue::Bool(checkbox_input.checked)))
+							.widget_holder(),
+					),
+					_ => {}
+				}
 
-		let mut selected_layers = self.selected_nodes.selected_layers(self.metadata());
+				let bound_inputs = parameter.bindings.len();
+				widgets.extend([
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextLabel::new(format!("{bound_inputs}"))
+						.tooltip(format!("Bound to {bound_inputs} input{}", if bound_inputs == 1 { "" } else { "s" }))
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					IconButton::new("Trash", 24)
+						.tooltip("Delete Parameter (the bound inputs keep its value)")
+						.on_update(move |_| DocumentMessage::DeleteParameter { id }.into())
+						.widget_holder(),
+				]);
+
+				LayoutGroup::Row { widgets }
+			})
+			.collect::<Vec<_>>();
 
-		let first_or_last_selected_layer = match relative_index_offset.signum() {
-			-1 => selected_layers.next(),
-			1 => selected_layers.last(),
-			_ => panic!("selected_layers_reorder() must be given a non-zero value"),
-		};
+		let mut add_buttons = vec![TextLabel::new("Add").widget_holder()];
+		for kind in ParameterKind::ALL {
+			add_buttons.extend([
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new(kind.label())
+					.tooltip(format!("Add a {} parameter to the document", kind.label().to_lowercase()))
+					.on_update(move |_| DocumentMessage::AddParameter { kind }.into())
+					.widget_holder(),
+			]);
+		}
+		rows.push(LayoutGroup::Row { widgets: add_buttons });
 
-		let Some(pivot_layer) = first_or_last_selected_layer else {
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout::new(rows)),
+			layout_target: LayoutTarget::ParametersPanel,
+		});
+	}
+
+	/// Queues a change to the layer's own transform that compensates for moving it from a parent with `old_parent_transform` to one with `new_parent_transform`, keeping it in place in the viewport.
+	fn preserve_layer_placement(&self, layer: LayerNodeIdentifier, old_parent_transform: DAffine2, new_parent_transform: DAffine2, responses: &mut VecDeque<Message>) {
+		if new_parent_transform.matrix2.determinant() == 0. {
 			return;
-		};
-		let parent = pivot_layer.parent(self.metadata()).unwrap_or(LayerNodeIdentifier::ROOT_PARENT);
+		}
 
-		let sibling_layer_paths: Vec<_> = parent.children(self.metadata()).collect();
-		let Some(pivot_index) = sibling_layer_paths.iter().position(|path| *path == pivot_layer) else {
+		let transform = new_parent_transform.inverse() * old_parent_transform;
+		if transform.abs_diff_eq(DAffine2::IDENTITY, 1e-10) {
 			return;
-		};
+		}
 
-		let max = sibling_layer_paths.len() as i64 - 1;
-		let insert_index = (pivot_index as i64 + relative_index_offset as i64).clamp(0, max) as usize;
+		responses.add(GraphOperationMessage::TransformPrepend {
+			layer,
+			transform,
+			skip_rerender: true,
+		});
+	}
 
-		let Some(&neighbor) = sibling_layer_paths.get(insert_index) else {
-			return;
+	/// Whether the layers are allowed to be moved into `parent`.
+	fn can_move_layers_to(&self, layers: &[LayerNodeIdentifier], parent: LayerNodeIdentifier) -> bool {
+		// Disallow trying to insert into self.
+		if layers.iter().any(|&layer| parent.ancestors(self.metadata()).any(|ancestor| ancestor == layer)) {
+			return false;
+		}
+		// Artboards can only have `ROOT_PARENT` as the parent.
+		let any_artboards = layers.iter().any(|&layer| self.metadata.is_artboard(layer));
+		if any_artboards && parent != LayerNodeIdentifier::ROOT_PARENT {
+			return false;
+		}
+
+		// Non-artboards cannot be put at the top level if artboards also exist there
+		let any_non_artboards = layers.iter().any(|&layer| !self.metadata.is_artboard(layer));
+		let top_level_artboards = LayerNodeIdentifier::ROOT_PARENT.children(self.metadata()).any(|layer| self.metadata.is_artboard(layer));
+		!(any_non_artboards && parent == LayerNodeIdentifier::ROOT_PARENT && top_level_artboards)
+	}
+
+	/// Moves the given layers into `parent` at `insert_index`, keeping their existing stacking order relative to each other.
+	/// Callers check [`Self::can_move_layers_to`] first.
+	fn move_layers_to(&self, layers: &[LayerNodeIdentifier], parent: LayerNodeIdentifier, insert_index: isize, responses: &mut VecDeque<Message>) {
+		let mut insert_index = if insert_index < 0 { 0 } else { insert_index as usize };
+
+		let layer_above_insertion = if insert_index == 0 { Some(parent) } else { parent.children(&self.metadata).nth(insert_index - 1) };
+
+		// TODO: The `.collect()` is necessary to avoid borrowing issues with `self`. See if this can be avoided to improve performance.
+		let ordered_layers = self.metadata.all_layers().filter(|layer| layers.contains(layer)).rev().collect::<Vec<_>>();
+		for layer_to_move in ordered_layers {
+			if insert_index > 0
+				&& layer_to_move
+					.upstream_siblings(&self.metadata)
+					.any(|layer| layer_above_insertion.is_some_and(|layer_above_insertion| layer_above_insertion == layer))
+			{
+				insert_index -= 1;
+			}
+
+			// `layer_to_move` should never be `ROOT_PARENT`, since it is not included in `all_layers()`
+			if layer_to_move == LayerNodeIdentifier::ROOT_PARENT {
+				log::error!("Layer to move cannot be root parent");
+				continue;
+			}
+
+			// Disconnect layer to move and reconnect downstream node to upstream sibling if it exists.
+			responses.add(GraphOperationMessage::DisconnectNodeFromStack {
+				node_id: layer_to_move.to_node(),
+				reconnect_to_sibling: true,
+			});
+			// Reconnect layer_to_move to new parent at insert index.
+			responses.add(GraphOperationMessage::InsertNodeAtStackIndex {
+				node_id: layer_to_move.to_node(),
+				parent,
+				insert_index,
+			});
+		}
+	}
+
+	/// Replaces the selection with the layers reached by a keyboard traversal, which also become the reference for range selection.
+	fn select_traversed_layers(&mut self, mut layers: Vec<LayerNodeIdentifier>, responses: &mut VecDeque<Message>) {
+		let mut seen = HashSet::new();
+		layers.retain(|&layer| layer != LayerNodeIdentifier::ROOT_PARENT && seen.insert(layer));
+		let Some(&last) = layers.last() else { return };
+
+		self.layer_range_selection_reference = Some(last);
+		let nodes = layers.into_iter().map(|layer| layer.to_node()).collect();
+		responses.add(NodeGraphMessage::SelectedNodesSet { nodes });
+	}
+
+	/// Moves each selected layer to its next (or previous) sibling, wrapping around at the ends of its group.
+	fn select_sibling_layers(&mut self, forward: bool, responses: &mut VecDeque<Message>) {
+		let metadata = self.metadata();
+		let sibling = |layer: LayerNodeIdentifier| {
+			let sibling = if forward { layer.next_sibling(metadata) } else { layer.previous_sibling(metadata) };
+			sibling.or_else(|| {
+				let parent = layer.parent(metadata)?;
+				if forward {
+					parent.first_child(metadata)
+				} else {
+					parent.last_child(metadata)
+				}
+			})
 		};
-		let Some(neighbor_index) = sibling_layer_paths.iter().position(|path| *path == neighbor) else {
-			return;
+
+		let selected = self.selected_nodes.selected_layers(metadata).collect::<Vec<_>>();
+		let layers = if selected.is_empty() {
+			// With nothing selected, start from the top or bottom of the layer stack
+			let root = LayerNodeIdentifier::ROOT_PARENT;
+			let first = if forward { root.first_child(metadata) } else { root.last_child(metadata) };
+			first.into_iter().collect()
+		} else {
+			selected.into_iter().filter_map(sibling).collect()
 		};
+		self.select_traversed_layers(layers, responses);
+	}
+
+	/// Selects the visible, unlocked sibling of the most recently selected layer which lies nearest to it in the given direction on screen.
+	fn select_layer_in_direction(&mut self, direction: ViewportDirection, responses: &mut VecDeque<Message>) {
+		let metadata = self.metadata();
+		let direction = direction.vector();
+
+		// Start from the layer selected last, so repeated presses walk from one layer to the next
+		let reference = self
+			.layer_range_selection_reference
+			.filter(|&layer| self.selected_nodes.selected_layers_contains(layer, metadata))
+			.or_else(|| self.selected_nodes.selected_layers(metadata).last());
+		let Some(reference) = reference else { return };
+		let Some(parent) = reference.parent(metadata) else { return };
+		let center = |[min, max]: [DVec2; 2]| (min + max) / 2.;
+		let Some(origin) = metadata.bounding_box_viewport(reference).map(center) else { return };
+
+		let nearest = parent
+			.children(metadata)
+			.filter(|&layer| layer != reference && self.selected_nodes.layer_visible(layer, metadata) && !self.selected_nodes.layer_locked(layer, metadata))
+			.filter_map(|layer| Some((layer, center(metadata.bounding_box_viewport(layer)?) - origin)))
+			.filter(|(_, offset)| offset.dot(direction) > 0.)
+			// Favor layers lying straight ahead over ones that are closer but far off to the side
+			.map(|(layer, offset)| (layer, offset.dot(direction) + 2. * offset.perp_dot(direction).abs()))
+			.min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+		if let Some((layer, _)) = nearest {
+			self.select_traversed_layers(vec![layer], responses);
+		}
+	}
+
+	pub fn selected_layers_reorder(&mut self, relative_index_offset: isize, responses: &mut VecDeque<Message>) {
+		if relative_index_offset == 0 {
+			panic!("selected_layers_reorder() must be given a non-zero value");
+		}
+
+		let selected_layers = self.metadata.shallowest_unique_layers(self.selected_nodes.selected_layers(self.metadata()));
+
+		// Selected layers are reordered among their own siblings, so a selection spanning several groups is never merged into a single parent
+		let mut layers_by_parent: Vec<(LayerNodeIdentifier, Vec<LayerNodeIdentifier>)> = Vec::new();
+		for layer in selected_layers.iter().filter_map(|path| path.last().copied()) {
+			let parent = layer.parent(self.metadata()).unwrap_or(LayerNodeIdentifier::ROOT_PARENT);
+			match layers_by_parent.iter_mut().find(|(existing_parent, _)| *existing_parent == parent) {
+				Some((_, layers)) => layers.push(layer),
+				None => layers_by_parent.push((parent, vec![layer])),
+			}
+		}
+
+		// Each group of siblings is held to the same rules as moving the selection into their parent
+		layers_by_parent.retain(|(parent, layers)| self.can_move_layers_to(layers, *parent));
+		if layers_by_parent.is_empty() {
+			return;
+		}
+		self.backup(responses);
+
+		for (parent, layers) in layers_by_parent {
+			let sibling_layers: Vec<_> = parent.children(self.metadata()).collect();
+			let mut selected_indices = sibling_layers.iter().enumerate().filter(|(_, sibling)| layers.contains(sibling)).map(|(index, _)| index);
+
+			// Raising pivots around the topmost selected sibling, lowering around the bottommost one
+			let pivot_index = if relative_index_offset < 0 { selected_indices.next() } else { selected_indices.last() };
+			let Some(pivot_index) = pivot_index else {
+				continue;
+			};
+
+			let max = sibling_layers.len() as i64 - 1;
+			let neighbor_index = (pivot_index as i64).saturating_add(relative_index_offset as i64).clamp(0, max) as usize;
+			if neighbor_index == pivot_index {
+				continue;
+			}
 
-		// If moving down, insert below this layer. If moving up, insert above this layer.
-		let insert_index = if relative_index_offset < 0 { neighbor_index } else { neighbor_index + 1 } as isize;
-		responses.add(DocumentMessage::MoveSelectedLayersTo { parent, insert_index });
+			// If moving down, insert below this layer. If moving up, insert above this layer.
+			let insert_index = if relative_index_offset < 0 { neighbor_index } else { neighbor_index + 1 } as isize;
+			self.move_layers_to(&layers, parent, insert_index, responses);
+		}
+
+		responses.add(NodeGraphMessage::RunDocumentGraph);
+		responses.add(NodeGraphMessage::SendGraph);
 	}
 }
 
+/// The settings of the output palette shown in the View Mode popover of the document bar.
+fn discard_brush_caches(network: &mut NodeNetwork) {
+	for node in network.nodes.values_mut() {
+		for input in &mut node.inputs {
+			if let NodeInput::Value {
+				tagged_value: TaggedValue::BrushCache(cache),
+				..
+			} = input
+			{
+				*cache = BrushCache::new_proto();
+			}
+		}
+		if let DocumentNodeImplementation::Network(nested_network) = &mut node.implementation {
+			discard_brush_caches(nested_network);
+		}
+	}
+}
+
+fn output_palette_options(show_output_palette: bool, output_palette: OutputPalette) -> Vec<LayoutGroup> {
+	let preset_entries = vec![PalettePreset::ALL
+		.into_iter()
+		.map(|preset| {
+			MenuListEntry::new(format!("{preset:?}")).label(preset.name()).on_commit(move |_| {
+				DocumentMessage::SetOutputPalette {
+					show_output_palette,
+					output_palette: OutputPalette { preset, ..output_palette },
+				}
+				.into()
+			})
+		})
+		.collect()];
+	let dithering_entries = [(Dithering::None, "None"), (Dithering::Ordered, "Ordered"), (Dithering::ErrorDiffusion, "Error Diffusion")]
+		.into_iter()
+		.map(|(dithering, label)| {
+			RadioEntryData::new(format!("{dithering:?}")).label(label).on_update(move |_| {
+				DocumentMessage::SetOutputPalette {
+					show_output_palette,
+					output_palette: OutputPalette { dithering, ..output_palette },
+				}
+				.into()
+			})
+		})
+		.collect();
+
+	vec![
+		LayoutGroup::Row {
+			widgets: vec![
+				CheckboxInput::new(show_output_palette)
+					.tooltip("Preview the artwork with its colors limited to a palette, as when exported with \"Limit to Palette\"")
+					.on_update(move |checkbox: &CheckboxInput| {
+						DocumentMessage::SetOutputPalette {
+							show_output_palette: checkbox.checked,
+							output_palette,
+						}
+						.into()
+					})
+					.widget_holder(),
+				TextLabel::new("Limit to Palette").widget_holder(),
+			],
+		},
+		LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Palette").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(preset_entries)
+					.selected_index(PalettePreset::ALL.iter().position(|preset| *preset == output_palette.preset).map(|index| index as u32))
+					.widget_holder(),
+			],
+		},
+		LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Dithering").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				RadioInput::new(dithering_entries)
+					.selected_index(Some(output_palette.dithering as u32))
+					.tooltip("How images are drawn with patterns of the palette's colors to show the colors between them")
+					.widget_holder(),
+			],
+		},
+	]
+}
+
+/// The output profile and soft proofing settings shown in the View Mode popover of the document bar.
+fn units_options(units: DocumentUnits) -> Vec<LayoutGroup> {
+	let unit_entries = vec![LengthUnit::list()
+		.into_iter()
+		.map(|length_unit| {
+			MenuListEntry::new(format!("{length_unit:?}")).label(length_unit.to_string()).on_commit(move |_| {
+				DocumentMessage::SetDocumentUnits {
+					units: DocumentUnits { length_unit, ..units },
+				}
+				.into()
+			})
+		})
+		.collect()];
+
+	vec![
+		LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Units").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(unit_entries)
+					.selected_index(LengthUnit::list().iter().position(|unit| *unit == units.length_unit).map(|index| index as u32))
+					.tooltip("The unit that lengths are shown and typed in by the rulers, the Properties panel, and dimensions")
+					.widget_holder(),
+			],
+		},
+		LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Resolution").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				NumberInput::new(Some(units.dpi))
+					.unit(" DPI")
+					.min(1.)
+					.max(10000.)
+					.tooltip("The number of document pixels in an inch, which sets how large the artwork measures in physical units and the resolution it's exported at")
+					.on_update(move |number_input: &NumberInput| {
+						DocumentMessage::SetDocumentUnits {
+							units: DocumentUnits {
+								dpi: number_input.value.unwrap(),
+								..units
+							},
+						}
+						.into()
+					})
+					.widget_holder(),
+			],
+		},
+	]
+}
+
+fn soft_proof_options(output_profile: OutputProfile, show_soft_proof: bool, gamut_warning: bool) -> Vec<LayoutGroup> {
+	let profile_entries = vec![OutputProfile::ALL
+		.into_iter()
+		.map(|output_profile| {
+			MenuListEntry::new(format!("{output_profile:?}"))
+				.label(output_profile.name())
+				.on_commit(move |_| DocumentMessage::SetOutputProfile { output_profile }.into())
+		})
+		.collect()];
+	let cmyk = output_profile.is_cmyk();
+
+	vec![
+		LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new("Output Profile").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(profile_entries)
+					.selected_index(OutputProfile::ALL.iter().position(|profile| *profile == output_profile).map(|index| index as u32))
+					.tooltip("The printing condition the artwork is made for, whose CMYK inks its colors are separated into when exported as a TIFF or poster PDF")
+					.widget_holder(),
+			],
+		},
+		LayoutGroup::Row {
+			widgets: vec![
+				CheckboxInput::new(show_soft_proof && cmyk)
+					.disabled(!cmyk)
+					.tooltip("Preview how the colors of the artwork look once printed with the inks and on the paper of the output profile")
+					.on_update(move |checkbox: &CheckboxInput| {
+						DocumentMessage::SetSoftProof {
+							show_soft_proof: checkbox.checked,
+							gamut_warning,
+						}
+						.into()
+					})
+					.widget_holder(),
+				TextLabel::new("Soft Proof").disabled(!cmyk).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				CheckboxInput::new(gamut_warning)
+					.disabled(!cmyk || !show_soft_proof)
+					.tooltip("Show the colors that the output profile can't print in gray while soft proofing")
+					.on_update(move |checkbox: &CheckboxInput| {
+						DocumentMessage::SetSoftProof {
+							show_soft_proof,
+							gamut_warning: checkbox.checked,
+						}
+						.into()
+					})
+					.widget_holder(),
+				TextLabel::new("Gamut Warning").disabled(!cmyk || !show_soft_proof).widget_holder(),
+			],
+		},
+	]
+}
+
+/// The settings of the onion skin shown in the popover next to its checkbox in the document bar.
+fn onion_skin_options(show_onion_skin: bool, onion_skin: OnionSkin) -> Vec<LayoutGroup> {
+	let update = move |modify: fn(&mut OnionSkin, f64)| {
+		move |number_input: &NumberInput| {
+			let mut onion_skin = onion_skin;
+			modify(&mut onion_skin, number_input.value.unwrap_or_default());
+			DocumentMessage::SetOnionSkin { show_onion_skin, onion_skin }.into()
+		}
+	};
+	let row = |name: &str, widget: WidgetHolder| LayoutGroup::Row {
+		widgets: vec![
+			TextLabel::new(name).table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			widget,
+		],
+	};
+
+	vec![
+		LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Onion Skin").bold(true).widget_holder()],
+		},
+		row(
+			"Frames Before",
+			NumberInput::new(Some(onion_skin.frames_before as f64))
+				.int()
+				.min(0.)
+				.max(10.)
+				.on_update(update(|onion_skin, value| onion_skin.frames_before = value as u32))
+				.widget_holder(),
+		),
+		row(
+			"Frames After",
+			NumberInput::new(Some(onion_skin.frames_after as f64))
+				.int()
+				.min(0.)
+				.max(10.)
+				.on_update(update(|onion_skin, value| onion_skin.frames_after = value as u32))
+				.widget_holder(),
+		),
+		row(
+			"Opacity",
+			NumberInput::new(Some(onion_skin.opacity * 100.))
+				.unit("%")
+				.min(0.)
+				.max(100.)
+				.tooltip("Opacity of the frames right next to the current frame")
+				.on_update(update(|onion_skin, value| onion_skin.opacity = value / 100.))
+				.widget_holder(),
+		),
+		row(
+			"Falloff",
+			NumberInput::new(Some(onion_skin.falloff * 100.))
+				.unit("%")
+				.min(0.)
+				.max(100.)
+				.tooltip("How much of the opacity is kept by each frame further away from the current frame")
+				.on_update(update(|onion_skin, value| onion_skin.falloff = value / 100.))
+				.widget_holder(),
+		),
+	]
+}
+
 fn root_network() -> NodeNetwork {
 	{
 		let mut network = NodeNetwork::default();
@@ -2159,3 +3318,244 @@ fn root_network() -> NodeNetwork {
 		network
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::messages::portfolio::document::utility_types::custom_nodes::CustomNodeDefinition;
+	use crate::messages::portfolio::document::utility_types::timeline::Interpolation;
+
+	use graph_craft::document::DocumentNode;
+	use graphene_core::vector::style::Fill;
+
+	#[test]
+	fn undo_restores_the_variables() {
+		let mut document = DocumentMessageHandler::default();
+		let mut responses = VecDeque::new();
+
+		document.backup(&mut responses);
+		document.variables.add_variant(&document.network);
+
+		document.undo_with_history(&mut responses);
+		assert!(document.variables.is_empty());
+		document.redo_with_history(&mut responses);
+		assert_eq!(document.variables.variants, ["Variant 1"]);
+	}
+
+	#[test]
+	fn undo_restores_the_keyframes() {
+		let mut document = DocumentMessageHandler::default();
+		let mut responses = VecDeque::new();
+		let node = DocumentNode {
+			inputs: vec![NodeInput::value(TaggedValue::F64(1.), false)],
+			..Default::default()
+		};
+		document.network.nodes.insert(NodeId(1), node);
+		let binding = VariableBinding {
+			node_path: vec![NodeId(1)],
+			input_index: 0,
+		};
+
+		document.backup(&mut responses);
+		document.timeline.set_keyframe(binding.clone(), Interpolation::Linear, &document.network);
+		document.backup(&mut responses);
+		document.timeline.set_interpolation(&binding, 0., Interpolation::Constant);
+
+		document.undo_with_history(&mut responses);
+		assert_eq!(document.timeline.tracks[0].keyframes[0].interpolation, Interpolation::Linear);
+		document.undo_with_history(&mut responses);
+		assert!(!document.timeline.is_animated(&binding));
+
+		document.redo_with_history(&mut responses);
+		assert!(document.timeline.is_animated(&binding));
+		document.backup(&mut responses);
+		document.timeline.remove_keyframe(&binding, 0.);
+		document.undo_with_history(&mut responses);
+		assert!(document.timeline.is_animated(&binding));
+	}
+
+	#[test]
+	fn undo_restores_the_swatches() {
+		let mut document = DocumentMessageHandler::default();
+		let mut responses = VecDeque::new();
+		let node = DocumentNode {
+			inputs: vec![NodeInput::value(TaggedValue::Fill(Fill::None), false)],
+			..Default::default()
+		};
+		document.network.nodes.insert(NodeId(1), node);
+		let binding = VariableBinding {
+			node_path: vec![NodeId(1)],
+			input_index: 0,
+		};
+		document.swatches.library.add(Swatch {
+			id: 1,
+			name: "Red".to_string(),
+			fill: Fill::Solid(Color::RED),
+		});
+
+		document.backup(&mut responses);
+		document.swatches.link_input(binding.clone(), 1, &mut document.network);
+		document.backup(&mut responses);
+		document.swatches.remove(1);
+
+		document.undo_with_history(&mut responses);
+		assert_eq!(document.swatches.linked_swatch(&binding).map(|swatch| swatch.id), Some(1));
+		document.undo_with_history(&mut responses);
+		assert!(document.swatches.linked_swatch(&binding).is_none());
+		assert_eq!(document.network.nodes[&NodeId(1)].inputs[0].as_value(), Some(&TaggedValue::Fill(Fill::None)));
+	}
+
+	#[test]
+	fn undo_restores_the_parameters() {
+		let mut document = DocumentMessageHandler::default();
+		let mut responses = VecDeque::new();
+		let node = DocumentNode {
+			inputs: vec![NodeInput::value(TaggedValue::F64(1.), false)],
+			..Default::default()
+		};
+		document.network.nodes.insert(NodeId(1), node);
+		let binding = VariableBinding {
+			node_path: vec![NodeId(1)],
+			input_index: 0,
+		};
+		document.parameters.add(1, ParameterKind::Number);
+
+		document.backup(&mut responses);
+		document.parameters.bind_input(binding.clone(), 1, &mut document.network);
+		document.backup(&mut responses);
+		document.parameters.set_value(1, TaggedValue::F64(4.), &mut document.network);
+
+		document.undo_with_history(&mut responses);
+		assert_eq!(document.parameters.get(1).map(|parameter| &parameter.value), Some(&TaggedValue::F64(0.)));
+		assert_eq!(document.network.nodes[&NodeId(1)].inputs[0].as_value(), Some(&TaggedValue::F64(0.)));
+		document.undo_with_history(&mut responses);
+		assert!(document.parameters.bound_parameter(&binding).is_none());
+		assert_eq!(document.network.nodes[&NodeId(1)].inputs[0].as_value(), Some(&TaggedValue::F64(1.)));
+	}
+
+	/// A document with two groups of layers, holding the layers with the IDs 11 to 13 and 21 to 22 respectively, with the given layers selected.
+	fn document_with_two_groups(selected: &[u64]) -> DocumentMessageHandler {
+		let mut document = DocumentMessageHandler::default();
+		let layer = |id| LayerNodeIdentifier::new_unchecked(NodeId(id));
+		for (group, children) in [(1, &[11, 12, 13][..]), (2, &[21, 22][..])] {
+			LayerNodeIdentifier::ROOT_PARENT.push_child(&mut document.metadata, layer(group));
+			for &child in children {
+				layer(group).push_child(&mut document.metadata, layer(child));
+			}
+		}
+		document.selected_nodes = SelectedNodes(selected.iter().copied().map(NodeId).collect());
+		document
+	}
+
+	/// The moves into a new place in the layer stack requested by the responses, as the moved node with its new parent (or 0 for the root) and index.
+	fn layer_moves(responses: &VecDeque<Message>) -> Vec<(u64, u64, usize)> {
+		responses
+			.iter()
+			.filter_map(|message| match message {
+				Message::Portfolio(PortfolioMessage::Document(DocumentMessage::GraphOperation(GraphOperationMessage::InsertNodeAtStackIndex { node_id, parent, insert_index }))) => {
+					let parent = if *parent == LayerNodeIdentifier::ROOT_PARENT { 0 } else { parent.to_node().0 };
+					Some((node_id.0, parent, *insert_index))
+				}
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn reordering_keeps_layers_in_their_own_groups() {
+		let mut document = document_with_two_groups(&[12, 22]);
+		let mut responses = VecDeque::new();
+		document.selected_layers_reorder(-1, &mut responses);
+		assert_eq!(layer_moves(&responses), [(12, 1, 0), (22, 2, 0)]);
+
+		let mut document = document_with_two_groups(&[11, 21]);
+		let mut responses = VecDeque::new();
+		document.selected_layers_reorder(isize::MAX, &mut responses);
+		assert_eq!(layer_moves(&responses), [(11, 1, 2), (21, 2, 1)]);
+	}
+
+	#[test]
+	fn reordering_skips_groups_already_at_the_end() {
+		// The layer at the top of the first group stays put, while the one in the second group is raised
+		let mut document = document_with_two_groups(&[11, 22]);
+		let mut responses = VecDeque::new();
+		document.selected_layers_reorder(-1, &mut responses);
+		assert_eq!(layer_moves(&responses), [(22, 2, 0)]);
+
+		// Selecting a group along with a layer inside it only reorders the group, among the other groups
+		let mut document = document_with_two_groups(&[2, 21]);
+		let mut responses = VecDeque::new();
+		document.selected_layers_reorder(-1, &mut responses);
+		assert_eq!(layer_moves(&responses), [(2, 0, 0)]);
+	}
+
+	/// The transforms prepended to layers by the responses, as the layer with its transform.
+	fn prepended_transforms(responses: &VecDeque<Message>) -> Vec<(u64, DAffine2)> {
+		responses
+			.iter()
+			.filter_map(|message| match message {
+				Message::Portfolio(PortfolioMessage::Document(DocumentMessage::GraphOperation(GraphOperationMessage::TransformPrepend { layer, transform, .. }))) => {
+					Some((layer.to_node().0, *transform))
+				}
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn moved_layers_keep_their_placement_in_the_viewport() {
+		let document = DocumentMessageHandler::default();
+		let layer = LayerNodeIdentifier::new_unchecked(NodeId(11));
+		let local = DAffine2::from_scale_angle_translation(DVec2::new(1., 3.), 0.5, DVec2::new(-4., 7.));
+		let folder = DAffine2::from_scale_angle_translation(DVec2::splat(2.), 0.25, DVec2::new(10., 20.));
+		let viewport = DAffine2::from_translation(DVec2::new(100., 50.));
+
+		// Ungrouping lifts the layer out of the folder, so the folder's transform is folded in before the layer's own transform
+		let mut responses = VecDeque::new();
+		document.preserve_layer_placement(layer, viewport * folder, viewport, &mut responses);
+		let [(11, transform)] = prepended_transforms(&responses)[..] else {
+			panic!("Expected one transform prepended to the layer")
+		};
+		assert!((viewport * transform * local).abs_diff_eq(viewport * folder * local, 1e-10));
+
+		// Grouping moves the layer into the folder, so the folder's transform is undone instead
+		let mut responses = VecDeque::new();
+		document.preserve_layer_placement(layer, viewport, viewport * folder, &mut responses);
+		let [(11, transform)] = prepended_transforms(&responses)[..] else {
+			panic!("Expected one transform prepended to the layer")
+		};
+		assert!((viewport * folder * transform * local).abs_diff_eq(viewport * local, 1e-10));
+	}
+
+	#[test]
+	fn layers_moved_between_matching_or_collapsed_parents_are_left_alone() {
+		let document = DocumentMessageHandler::default();
+		let layer = LayerNodeIdentifier::new_unchecked(NodeId(11));
+		let parent = DAffine2::from_translation(DVec2::new(5., 5.));
+
+		let mut responses = VecDeque::new();
+		document.preserve_layer_placement(layer, parent, parent, &mut responses);
+		assert!(prepended_transforms(&responses).is_empty());
+
+		// A parent scaled to nothing can't be undone, so the layer is left as it was
+		document.preserve_layer_placement(layer, parent, DAffine2::from_scale(DVec2::new(0., 1.)), &mut responses);
+		assert!(prepended_transforms(&responses).is_empty());
+	}
+
+	#[test]
+	fn undo_restores_the_custom_nodes() {
+		let mut document = DocumentMessageHandler::default();
+		let mut responses = VecDeque::new();
+
+		document.backup(&mut responses);
+		document.custom_nodes.definitions.push(CustomNodeDefinition {
+			name: "Custom Node".to_string(),
+			node: DocumentNode::default(),
+		});
+
+		document.undo_with_history(&mut responses);
+		assert!(document.custom_nodes.get("Custom Node").is_none());
+		document.redo_with_history(&mut responses);
+		assert!(document.custom_nodes.get("Custom Node").is_some());
+	}
+}
@@ -6,7 +6,7 @@ use crate::messages::prelude::*;
 use bezier_rs::Subpath;
 use graph_craft::document::{DocumentNode, NodeId, NodeInput};
 use graphene_core::raster::{BlendMode, ImageFrame};
-use graphene_core::text::Font;
+use graphene_core::text::{Font, TextAlign};
 use graphene_core::uuid::ManipulatorGroupId;
 use graphene_core::vector::brush_stroke::BrushStroke;
 use graphene_core::vector::style::{Fill, Stroke};
@@ -92,6 +92,11 @@ pub enum GraphOperationMessage {
 		transform_in: TransformIn,
 		skip_rerender: bool,
 	},
+	TransformPrepend {
+		layer: LayerNodeIdentifier,
+		transform: DAffine2,
+		skip_rerender: bool,
+	},
 	TransformSet {
 		layer: LayerNodeIdentifier,
 		transform: DAffine2,
@@ -138,6 +143,9 @@ pub enum GraphOperationMessage {
 		text: String,
 		font: Font,
 		size: f64,
+		/// The size of the text box the text is wrapped within, where a width or height of 0 leaves the text free to extend in that direction.
+		box_size: DVec2,
+		align: TextAlign,
 		parent: LayerNodeIdentifier,
 		insert_index: isize,
 	},
@@ -9,7 +9,7 @@ use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{generate_uuid, NodeId, NodeInput, NodeNetwork, Previewing};
 use graphene_core::renderer::Quad;
 use graphene_core::text::Font;
-use graphene_core::vector::style::{Fill, Gradient, GradientType, LineCap, LineJoin, Stroke};
+use graphene_core::vector::style::{Fill, Gradient, GradientType, LineCap, LineJoin, Stroke, StrokeAlign};
 use graphene_core::Color;
 use graphene_std::vector::convert_usvg_path;
 use graphene_std::vector::style::GradientStops;
@@ -438,6 +438,15 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 					modify_inputs.transform_change(transform, transform_in, parent_transform, bounds, skip_rerender);
 				}
 			}
+			GraphOperationMessage::TransformPrepend { layer, transform, skip_rerender } => {
+				if layer == LayerNodeIdentifier::ROOT_PARENT {
+					log::error!("Cannot run TransformPrepend on ROOT_PARENT");
+					return;
+				}
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer.to_node(), document_network, document_metadata, node_graph, responses) {
+					modify_inputs.transform_prepend(transform, skip_rerender);
+				}
+			}
 			GraphOperationMessage::TransformSet {
 				layer,
 				transform,
@@ -642,12 +651,14 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 				text,
 				font,
 				size,
+				box_size,
+				align,
 				parent,
 				insert_index,
 			} => {
 				let mut modify_inputs = ModifyInputsContext::new(document_network, document_metadata, node_graph, responses);
 				if let Some(layer) = modify_inputs.create_layer(id, parent, insert_index) {
-					modify_inputs.insert_text(text, font, size, layer);
+					modify_inputs.insert_text(text, font, size, box_size, align, layer);
 				}
 				load_network_structure(document_network, document_metadata, collapsed);
 			}
@@ -872,6 +883,7 @@ fn apply_usvg_stroke(stroke: &Option<usvg::Stroke>, modify_inputs: &mut ModifyIn
 					usvg::LineJoin::Bevel => LineJoin::Bevel,
 				},
 				line_join_miter_limit: stroke.miterlimit.get() as f64,
+				align: StrokeAlign::Center,
 			})
 		} else {
 			warn!("Skip non-solid stroke")
@@ -906,6 +918,7 @@ fn apply_usvg_fill(fill: &Option<usvg::Fill>, modify_inputs: &mut ModifyInputsCo
 					transform: DAffine2::IDENTITY,
 					gradient_type: GradientType::Linear,
 					stops,
+					..Default::default()
 				})
 			}
 			usvg::Paint::RadialGradient(radial) => {
@@ -931,6 +944,7 @@ fn apply_usvg_fill(fill: &Option<usvg::Fill>, modify_inputs: &mut ModifyInputsCo
 					transform: DAffine2::IDENTITY,
 					gradient_type: GradientType::Radial,
 					stops,
+					..Default::default()
 				})
 			}
 			usvg::Paint::Pattern(_) => {
@@ -9,7 +9,7 @@ use graph_craft::concrete;
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{generate_uuid, DocumentNode, DocumentNodeImplementation, NodeId, NodeInput, NodeNetwork, Previewing};
 use graphene_core::raster::{BlendMode, ImageFrame};
-use graphene_core::text::Font;
+use graphene_core::text::{Font, FontVariations, TextAlign, TextAutoResize, TextKerning, TextRuns};
 use graphene_core::uuid::ManipulatorGroupId;
 use graphene_core::vector::brush_stroke::BrushStroke;
 use graphene_core::vector::style::{Fill, Stroke};
@@ -334,13 +334,21 @@ impl<'a> ModifyInputsContext<'a> {
 		self.responses.add(NodeGraphMessage::RunDocumentGraph);
 	}
 
-	pub fn insert_text(&mut self, text: String, font: Font, size: f64, layer: NodeId) {
+	pub fn insert_text(&mut self, text: String, font: Font, size: f64, box_size: DVec2, align: TextAlign, layer: NodeId) {
 		let text = resolve_document_node_type("Text").expect("Text node does not exist").to_document_node(
 			[
 				NodeInput::network(graph_craft::concrete!(graphene_std::wasm_application_io::WasmEditorApi), 0),
 				NodeInput::value(TaggedValue::String(text), false),
 				NodeInput::value(TaggedValue::Font(font), false),
 				NodeInput::value(TaggedValue::F64(size), false),
+				NodeInput::value(TaggedValue::F64(1.), false),
+				NodeInput::value(TaggedValue::F64(box_size.x), false),
+				NodeInput::value(TaggedValue::F64(box_size.y), false),
+				NodeInput::value(TaggedValue::TextAlign(align), false),
+				NodeInput::value(TaggedValue::TextAutoResize(TextAutoResize::Fixed), false),
+				NodeInput::value(TaggedValue::FontVariations(FontVariations::default()), false),
+				NodeInput::value(TaggedValue::TextRuns(TextRuns::default()), false),
+				NodeInput::value(TaggedValue::TextKerning(TextKerning::default()), false),
 			],
 			Default::default(),
 		);
@@ -621,6 +629,7 @@ impl<'a> ModifyInputsContext<'a> {
 			inputs[5] = NodeInput::value(TaggedValue::LineCap(stroke.line_cap), false);
 			inputs[6] = NodeInput::value(TaggedValue::LineJoin(stroke.line_join), false);
 			inputs[7] = NodeInput::value(TaggedValue::F64(stroke.line_join_miter_limit), false);
+			inputs[8] = NodeInput::value(TaggedValue::StrokeAlign(stroke.align), false);
 		});
 	}
 
@@ -639,6 +648,15 @@ impl<'a> ModifyInputsContext<'a> {
 		});
 	}
 
+	/// Applies the transform in the space of the layer's parent, before the layer's own transform.
+	/// Unlike [`Self::transform_change`], the transform isn't applied around the layer's pivot, so the transform of a parent can be folded into the layer when it's moved to another parent.
+	pub fn transform_prepend(&mut self, transform: DAffine2, skip_rerender: bool) {
+		self.modify_inputs("Transform", skip_rerender, |inputs, _, _| {
+			let layer_transform = transform_utils::get_current_transform(inputs);
+			transform_utils::update_transform(inputs, transform * layer_transform);
+		});
+	}
+
 	pub fn transform_set(&mut self, mut transform: DAffine2, transform_in: TransformIn, parent_transform: DAffine2, current_transform: Option<DAffine2>, bounds: LayerBounds, skip_rerender: bool) {
 		self.modify_inputs("Transform", skip_rerender, |inputs, node_id, metadata| {
 			let upstream_transform = metadata.upstream_transform(node_id);
@@ -915,8 +933,11 @@ impl<'a> ModifyInputsContext<'a> {
 				can_reconnect = false;
 			}
 
+			// An artboard linked to the one being deleted goes back to showing its own contents, rather than being linked to the artboards beneath
+			let is_artboard_link = input_index == 6 && network.nodes.get(&node_id).is_some_and(|node| node.name == "Artboard");
+
 			// Only reconnect if the output index for the node to be deleted is 0
-			if can_reconnect && reconnect_to_input.is_some() {
+			if can_reconnect && reconnect_to_input.is_some() && !is_artboard_link {
 				// None means to use reconnect_to_input, which can be safely unwrapped
 				nodes_to_set_input.push((node_id, input_index, None));
 
@@ -1099,3 +1120,68 @@ impl<'a> ModifyInputsContext<'a> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// A network holding a layer for each of the transforms, each with its own Transform node.
+	fn network_with_layers(transforms: &[DAffine2]) -> NodeNetwork {
+		let mut network = NodeNetwork::default();
+		for (index, &transform) in transforms.iter().enumerate() {
+			let (layer_id, transform_id) = (NodeId(index as u64 * 2), NodeId(index as u64 * 2 + 1));
+			let mut inputs = vec![
+				NodeInput::value(TaggedValue::None, false),
+				NodeInput::value(TaggedValue::DVec2(DVec2::ZERO), false),
+				NodeInput::value(TaggedValue::F64(0.), false),
+				NodeInput::value(TaggedValue::DVec2(DVec2::ONE), false),
+				NodeInput::value(TaggedValue::DVec2(DVec2::ZERO), false),
+				NodeInput::value(TaggedValue::DVec2(DVec2::splat(0.5)), false),
+			];
+			transform_utils::update_transform(&mut inputs, transform);
+			let transform_node = DocumentNode {
+				name: "Transform".to_string(),
+				inputs,
+				..Default::default()
+			};
+			let layer = DocumentNode {
+				name: "Merge".to_string(),
+				is_layer: true,
+				inputs: vec![NodeInput::value(TaggedValue::None, false), NodeInput::node(transform_id, 0)],
+				..Default::default()
+			};
+			network.nodes.insert(layer_id, layer);
+			network.nodes.insert(transform_id, transform_node);
+		}
+		network
+	}
+
+	#[test]
+	fn ungrouping_a_rotated_and_scaled_group_keeps_its_children_in_place() {
+		let parent = DAffine2::from_translation(DVec2::new(100., 50.));
+		let group = DAffine2::from_scale_angle_translation(DVec2::new(2., 0.5), 0.6, DVec2::new(30., -10.));
+		let children = [
+			DAffine2::from_scale_angle_translation(DVec2::ONE, 0.3, DVec2::new(5., 7.)),
+			DAffine2::from_scale_angle_translation(DVec2::new(1.5, 3.), -1.1, DVec2::new(-4., 12.)),
+		];
+
+		let mut network = network_with_layers(&children);
+		let mut metadata = DocumentMetadata::default();
+		let mut node_graph = NodeGraphMessageHandler::default();
+		let mut responses = VecDeque::new();
+
+		// Ungrouping moves each child from inside the group to the group's parent, so the group's transform is folded into it
+		let (old_parent_transform, new_parent_transform) = (parent * group, parent);
+		for index in 0..children.len() {
+			let layer = NodeId(index as u64 * 2);
+			let mut modify_inputs = ModifyInputsContext::new_with_layer(layer, &mut network, &mut metadata, &mut node_graph, &mut responses).unwrap();
+			modify_inputs.transform_prepend(new_parent_transform.inverse() * old_parent_transform, true);
+		}
+
+		for (index, child) in children.iter().enumerate() {
+			let inputs = &network.nodes[&NodeId(index as u64 * 2 + 1)].inputs;
+			let document_transform = new_parent_transform * transform_utils::get_current_transform(inputs);
+			assert!(document_transform.abs_diff_eq(old_parent_transform * *child, 1e-9), "Child {index} moved to {document_transform:?}");
+		}
+	}
+}
@@ -6,6 +6,8 @@ pub mod navigation;
 pub mod node_graph;
 pub mod overlays;
 pub mod properties_panel;
+pub mod search;
+pub mod spellcheck;
 pub mod utility_types;
 
 #[doc(inline)]
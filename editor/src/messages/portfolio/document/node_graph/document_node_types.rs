@@ -1,7 +1,11 @@
 use super::node_properties;
 use super::utility_types::{FrontendGraphDataType, FrontendNodeType};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::properties_panel::utility_types::TextSelection;
 use crate::messages::portfolio::document::utility_types::document_metadata::DocumentMetadata;
+use crate::messages::portfolio::document::utility_types::parameters::DocumentParameters;
+use crate::messages::portfolio::document::utility_types::swatches::DocumentSwatches;
+use crate::messages::portfolio::document::utility_types::variables::DocumentVariables;
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::Message;
 use crate::node_graph_executor::NodeGraphExecutor;
@@ -11,14 +15,22 @@ use graph_craft::document::value::*;
 use graph_craft::document::*;
 use graph_craft::imaginate_input::ImaginateSamplingMethod;
 use graph_craft::ProtoNodeIdentifier;
+use graphene_core::oscillator::Waveform;
+use graphene_core::projection::{Axonometry, ProjectionFace};
 use graphene_core::raster::brush_cache::BrushCache;
 use graphene_core::raster::{
-	BlendMode, CellularDistanceFunction, CellularReturnType, Color, DomainWarpType, FractalType, Image, ImageFrame, LuminanceCalculation, NoiseType, RedGreenBlue, RedGreenBlueAlpha, RelativeAbsolute,
-	SelectiveColorChoice,
+	BlendMode, BorderMode, CellularDistanceFunction, CellularReturnType, ChannelSource, Color, ColorHarmony, ConvolutionPreset, DomainWarpType, FractalType, Image, ImageFrame, LuminanceCalculation,
+	MorphologyChannel, MorphologyOperation, MorphologyShape, NoiseType, RedGreenBlue, RedGreenBlueAlpha, RelativeAbsolute, SelectiveColorChoice,
 };
-use graphene_core::text::Font;
+use graphene_core::text::{Font, FontVariations, TextAlign, TextAutoResize, TextKerning, TextRuns};
 use graphene_core::transform::Footprint;
+use graphene_core::vector::barcode::{BarcodeSymbology, QrErrorCorrection};
+use graphene_core::vector::misc::ArcType;
+use graphene_core::vector::point_cloud::{PointCloud, PointRegion, PointSampling};
+use graphene_core::vector::style::GradientStops;
+use graphene_core::vector::voronoi::PointDistribution;
 use graphene_core::vector::VectorData;
+use graphene_core::warp::WarpMode;
 use graphene_core::*;
 use graphene_std::wasm_application_io::WasmEditorApi;
 #[cfg(feature = "gpu")]
@@ -73,6 +85,16 @@ pub struct NodePropertiesContext<'a> {
 	pub executor: &'a mut NodeGraphExecutor,
 	pub document_network: &'a NodeNetwork,
 	pub metadata: &'a mut DocumentMetadata,
+	/// The swatches of the document, which fills can be linked to.
+	pub swatches: &'a DocumentSwatches,
+	/// The parameters of the document, which inputs can be bound to.
+	pub parameters: &'a DocumentParameters,
+	/// The variables of the document, which inputs can be bound to so they take on a different value in each variant.
+	pub variables: &'a DocumentVariables,
+	pub artboard_relative_coordinates: bool,
+	/// The unit that lengths are shown and typed in.
+	pub units: graphene_core::units::DocumentUnits,
+	pub text_selection: Option<&'a TextSelection>,
 }
 
 /// Acts as a description for a [DocumentNode] before it gets instantiated as one.
@@ -87,6 +109,8 @@ pub struct DocumentNodeDefinition {
 	pub has_primary_output: bool,
 	pub properties: fn(&DocumentNode, NodeId, &mut NodePropertiesContext) -> Vec<LayoutGroup>,
 	pub manual_composition: Option<graphene_core::Type>,
+	/// The input which the timeline sets to the time of the animation in seconds, for nodes that change as it plays.
+	pub time_input: Option<usize>,
 }
 
 impl Default for DocumentNodeDefinition {
@@ -101,10 +125,15 @@ impl Default for DocumentNodeDefinition {
 			has_primary_output: true,
 			properties: node_properties::node_no_properties,
 			manual_composition: Default::default(),
+			time_input: None,
 		}
 	}
 }
 
+/// Shown by the nodes which run the network connected to their function input once for every item of a list.
+const LIST_FUNCTION_DESCRIPTION: &str =
+	"The network connected to the function is run once for each item of the list, which it receives from a List Item node (or a Group Element node when the list is a group)";
+
 // We use the once cell for lazy initialization to avoid the overhead of reconstructing the node list every time.
 // TODO: make document nodes not require a `'static` lifetime to avoid having to split the construction into const and non-const parts.
 static DOCUMENT_NODE_TYPES: once_cell::sync::Lazy<Vec<DocumentNodeDefinition>> = once_cell::sync::Lazy::new(static_nodes);
@@ -165,6 +194,16 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::vector2_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Parameter",
+			category: "Inputs",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"),
+			// The value is written by the document parameter the input is bound to, taking on its type
+			inputs: vec![DocumentInputType::value("Value", TaggedValue::None, false)],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::General)],
+			properties: node_properties::parameter_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Identity",
 			category: "Structural",
@@ -178,6 +217,93 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: |_document_node, _node_id, _context| node_properties::string_properties("The identity node simply returns the input"),
 			..Default::default()
 		},
+		// Drawn as a dot in the graph. Once connected, it's removed along with the other redundant identity nodes when the graph is compiled.
+		DocumentNodeDefinition {
+			name: "Reroute",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"),
+			inputs: vec![DocumentInputType {
+				name: "In",
+				data_type: FrontendGraphDataType::General,
+				default: NodeInput::value(TaggedValue::None, true),
+			}],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::General)],
+			properties: |_document_node, _node_id, _context| node_properties::string_properties("The reroute node passes its input through unchanged, to route wires around other nodes"),
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Map List",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::list::MapListNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("List", TaggedValue::VecF64(Vec::new()), true),
+				DocumentInputType::value("Function", TaggedValue::F64(0.), true),
+			],
+			outputs: vec![DocumentOutputType::new("List", FrontendGraphDataType::General)],
+			properties: |_document_node, _node_id, _context| node_properties::string_properties(LIST_FUNCTION_DESCRIPTION),
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Filter List",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::list::FilterListNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("List", TaggedValue::VecF64(Vec::new()), true),
+				DocumentInputType::value("Predicate", TaggedValue::Bool(true), true),
+			],
+			outputs: vec![DocumentOutputType::new("List", FrontendGraphDataType::General)],
+			properties: |_document_node, _node_id, _context| node_properties::string_properties(LIST_FUNCTION_DESCRIPTION),
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Fold List",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::list::FoldListNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("List", TaggedValue::VecF64(Vec::new()), true),
+				DocumentInputType::value("Initial", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Function", TaggedValue::F64(0.), true),
+			],
+			outputs: vec![DocumentOutputType::new("Value", FrontendGraphDataType::Number)],
+			properties: node_properties::fold_list_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "List Item",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"),
+			manual_composition: Some(concrete!(f64)),
+			inputs: vec![],
+			outputs: vec![DocumentOutputType::new("Item", FrontendGraphDataType::Number)],
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Group Element",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"),
+			manual_composition: Some(concrete!(graphene_core::GraphicElement)),
+			inputs: vec![],
+			outputs: vec![DocumentOutputType::new("Element", FrontendGraphDataType::Graphic)],
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Fold Accumulator",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::FirstOfPairNode"),
+			manual_composition: Some(concrete!((f64, f64))),
+			inputs: vec![],
+			outputs: vec![DocumentOutputType::new("Accumulator", FrontendGraphDataType::Number)],
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Fold Item",
+			category: "Structural",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::SecondOfPairNode"),
+			manual_composition: Some(concrete!((f64, f64))),
+			inputs: vec![],
+			outputs: vec![DocumentOutputType::new("Item", FrontendGraphDataType::Number)],
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Monitor",
 			category: "Structural",
@@ -191,6 +317,20 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: |_document_node, _node_id, _context| node_properties::string_properties("The Monitor node stores the value of its last evaluation"),
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "SVG Attributes",
+			category: "General",
+			implementation: DocumentNodeImplementation::proto("graphene_core::SvgAttributesNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Graphical Data", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("ID", TaggedValue::String(String::new()), false),
+				DocumentInputType::value("Classes", TaggedValue::String(String::new()), false),
+				DocumentInputType::value("Data Attributes", TaggedValue::String(String::new()), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::Graphic)],
+			properties: node_properties::svg_attributes_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Merge",
 			category: "General",
@@ -271,8 +411,9 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 								NodeInput::network(concrete!(TaggedValue), 3),
 								NodeInput::network(concrete!(TaggedValue), 4),
 								NodeInput::network(concrete!(TaggedValue), 5),
+								NodeInput::network(graphene_core::Type::Fn(Box::new(concrete!(Footprint)), Box::new(concrete!(ArtboardGroup))), 6),
 							],
-							implementation: DocumentNodeImplementation::proto("graphene_core::ConstructArtboardNode<_, _, _, _, _>"),
+							implementation: DocumentNodeImplementation::proto("graphene_core::ConstructArtboardNode<_, _, _, _, _, _>"),
 							metadata: DocumentNodeMetadata { position: glam::IVec2::new(-10, -3) }, // To Artboard
 							..Default::default()
 						},
@@ -314,11 +455,74 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 				DocumentInputType::value("Dimensions", TaggedValue::IVec2(glam::IVec2::new(1920, 1080)), false),
 				DocumentInputType::value("Background", TaggedValue::Color(Color::WHITE), false),
 				DocumentInputType::value("Clip", TaggedValue::Bool(false), false),
+				DocumentInputType::value("Linked To", TaggedValue::ArtboardGroup(ArtboardGroup::EMPTY), true),
 			],
 			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::Artboard)],
 			properties: node_properties::artboard_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Contact Sheet",
+			category: "General",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ContactSheetNode<_, _, _, _, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType {
+					name: "api",
+					data_type: FrontendGraphDataType::General,
+					default: NodeInput::network(concrete!(WasmEditorApi), 0),
+				},
+				DocumentInputType::value("Elements", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Columns", TaggedValue::U32(4), false),
+				DocumentInputType::value("Gutter", TaggedValue::F64(20.), false),
+				DocumentInputType::value("Cell Size", TaggedValue::DVec2(DVec2::new(200., 200.)), false),
+				DocumentInputType::value("Fit", TaggedValue::CellFit(CellFit::Contain), false),
+				DocumentInputType::value("Captions", TaggedValue::String(String::new()), false),
+				DocumentInputType::value(
+					"Font",
+					TaggedValue::Font(Font::new(graphene_core::consts::DEFAULT_FONT_FAMILY.into(), graphene_core::consts::DEFAULT_FONT_STYLE.into())),
+					false,
+				),
+				DocumentInputType::value("Font Size", TaggedValue::F64(16.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::Graphic)],
+			properties: node_properties::contact_sheet_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Dimension",
+			category: "General",
+			implementation: DocumentNodeImplementation::proto("graphene_core::DimensionNode<_, _, _, _, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("From", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("From Point", TaggedValue::U32(0), false),
+				DocumentInputType::value("To", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("To Point", TaggedValue::U32(1), false),
+				DocumentInputType::value("Kind", TaggedValue::DimensionKind(DimensionKind::Distance), false),
+				DocumentInputType::value("Offset", TaggedValue::F64(20.), false),
+				DocumentInputType::value("Precision", TaggedValue::U32(1), false),
+				DocumentInputType::value("Color", TaggedValue::Color(Color::from_rgbaf32_unchecked(0.9, 0.2, 0.2, 1.)), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::Graphic)],
+			manual_composition: Some(concrete!(Footprint)),
+			properties: node_properties::dimension_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Mask",
+			category: "General",
+			implementation: DocumentNodeImplementation::proto("graphene_core::MaskNode<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Content", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Mask", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Mode", TaggedValue::MaskMode(MaskMode::Alpha), false),
+				DocumentInputType::value("Invert", TaggedValue::Bool(false), false),
+				DocumentInputType::value("Feather", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::Graphic)],
+			manual_composition: Some(concrete!(Footprint)),
+			properties: node_properties::mask_properties,
+			..Default::default()
+		},
 		// TODO: Does this need an internal Cull node to be added to its implementation?
 		DocumentNodeDefinition {
 			name: "Input Frame",
@@ -387,6 +591,58 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::load_image_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Audio Analysis",
+			category: "Inputs",
+			implementation: DocumentNodeImplementation::Network(NodeNetwork {
+				exports: vec![NodeInput::node(NodeId(1), 0)],
+				nodes: [
+					DocumentNode {
+						name: "Load Resource".to_string(),
+						inputs: vec![NodeInput::network(concrete!(WasmEditorApi), 0), NodeInput::network(concrete!(String), 1)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_std::wasm_application_io::LoadResourceNode<_>")),
+						..Default::default()
+					},
+					DocumentNode {
+						name: "Audio Analysis".to_string(),
+						inputs: vec![
+							NodeInput::node(NodeId(0), 0),
+							NodeInput::network(concrete!(f64), 2),
+							NodeInput::network(concrete!(u32), 3),
+							NodeInput::network(concrete!(u32), 4),
+							NodeInput::network(concrete!(f64), 5),
+						],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_std::audio::AudioAnalysisNode<_, _, _, _>")),
+						..Default::default()
+					},
+				]
+				.into_iter()
+				.enumerate()
+				.map(|(id, node)| (NodeId(id as u64), node))
+				.collect(),
+				..Default::default()
+			}),
+			inputs: vec![
+				DocumentInputType {
+					name: "api",
+					data_type: FrontendGraphDataType::General,
+					default: NodeInput::network(concrete!(WasmEditorApi), 0),
+				},
+				DocumentInputType {
+					name: "path",
+					data_type: FrontendGraphDataType::General,
+					default: NodeInput::value(TaggedValue::String("graphite:null".to_string()), false),
+				},
+				DocumentInputType::value("Time", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Band", TaggedValue::U32(0), false),
+				DocumentInputType::value("Band Count", TaggedValue::U32(8), false),
+				DocumentInputType::value("Gain", TaggedValue::F64(1.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Level", FrontendGraphDataType::Number)],
+			properties: node_properties::audio_analysis_properties,
+			time_input: Some(2),
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Create Canvas",
 			category: "Structural",
@@ -730,6 +986,22 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			..Default::default()
 		},
 		// TODO: This needs to work with resolution-aware (raster with footprint, post-Cull node) data.
+		DocumentNodeDefinition {
+			name: "Pixel Diff",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::raster::PixelDiffNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Reference", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Mode", TaggedValue::PixelDiffMode(PixelDiffMode::Heatmap), false),
+				DocumentInputType::value("Threshold", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Time", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::pixel_diff_properties,
+			..Default::default()
+		},
+		// TODO: This needs to work with resolution-aware (raster with footprint, post-Cull node) data.
 		DocumentNodeDefinition {
 			name: "Insert Channel",
 			category: "Image Adjustments",
@@ -771,6 +1043,8 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 				DocumentInputType::value("Second", TaggedValue::ImageFrame(ImageFrame::empty()), true),
 				DocumentInputType::value("BlendMode", TaggedValue::BlendMode(BlendMode::Normal), false),
 				DocumentInputType::value("Opacity", TaggedValue::F64(100.), false),
+				// Set from the document's compositing setting whenever the graph is evaluated
+				DocumentInputType::value("Linear RGB", TaggedValue::Bool(true), false),
 			],
 			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
 			properties: node_properties::blend_properties,
@@ -981,6 +1255,22 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			has_primary_output: false,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Shuffle Channels",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::raster::ShuffleChannelsNode<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Other", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Red", TaggedValue::ChannelSource(ChannelSource::Red), false),
+				DocumentInputType::value("Green", TaggedValue::ChannelSource(ChannelSource::Green), false),
+				DocumentInputType::value("Blue", TaggedValue::ChannelSource(ChannelSource::Blue), false),
+				DocumentInputType::value("Alpha", TaggedValue::ChannelSource(ChannelSource::Alpha), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::shuffle_channels_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Brush",
 			category: "Brush",
@@ -1917,6 +2207,148 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::exposure_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Lens Correction",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::warp::LensCorrectionNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Amount", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::lens_correction_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Perspective Crop",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::warp::PerspectiveCropNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Top Left", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Top Right", TaggedValue::DVec2(DVec2::X), false),
+				DocumentInputType::value("Bottom Right", TaggedValue::DVec2(DVec2::ONE), false),
+				DocumentInputType::value("Bottom Left", TaggedValue::DVec2(DVec2::Y), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::perspective_crop_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Chromatic Aberration",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::stylize::ChromaticAberrationNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Amount", TaggedValue::F64(4.), false),
+				DocumentInputType::value("Angle", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::chromatic_aberration_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Vignette",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::stylize::VignetteNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Amount", TaggedValue::F64(50.), false),
+				DocumentInputType::value("Size", TaggedValue::F64(50.), false),
+				DocumentInputType::value("Feather", TaggedValue::F64(50.), false),
+				DocumentInputType::value("Roundness", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::vignette_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Grain",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::stylize::GrainNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Amount", TaggedValue::F64(20.), false),
+				DocumentInputType::value("Size", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::grain_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Convolve",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::convolution::ConvolveNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Kernel", TaggedValue::VecF64(vec![0., 0., 0., 0., 1., 0., 0., 0., 0.]), false),
+				DocumentInputType::value("Normalize", TaggedValue::Bool(true), false),
+				DocumentInputType::value("Border", TaggedValue::BorderMode(BorderMode::Extend), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::convolve_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Convolution Preset",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::convolution::ConvolutionPresetNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Preset", TaggedValue::ConvolutionPreset(ConvolutionPreset::Sobel), false),
+				DocumentInputType::value("Border", TaggedValue::BorderMode(BorderMode::Extend), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::convolution_preset_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Unsharp Mask",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::convolution::UnsharpMaskNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Amount", TaggedValue::F64(100.), false),
+				DocumentInputType::value("Radius", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Threshold", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::unsharp_mask_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Morphology",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_std::morphology::MorphologyNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Image", TaggedValue::ImageFrame(ImageFrame::empty()), true),
+				DocumentInputType::value("Operation", TaggedValue::MorphologyOperation(MorphologyOperation::Dilate), false),
+				DocumentInputType::value("Channel", TaggedValue::MorphologyChannel(MorphologyChannel::Alpha), false),
+				DocumentInputType::value("Radius", TaggedValue::F64(2.), false),
+				DocumentInputType::value("Shape", TaggedValue::MorphologyShape(MorphologyShape::Circle), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::morphology_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Oscillator",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::oscillator::OscillatorNode<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Time", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Waveform", TaggedValue::Waveform(Waveform::Sine), false),
+				DocumentInputType::value("Frequency", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Amplitude", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Phase", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::oscillator_properties,
+			time_input: Some(1),
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Add",
 			category: "Math",
@@ -1965,6 +2397,36 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::multiply_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Expression",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_std::expression::ExpressionNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("X", TaggedValue::F64(0.), true),
+				DocumentInputType::value("Expression", TaggedValue::String("x * y".into()), false),
+				DocumentInputType::value("Y", TaggedValue::F64(0.), false),
+				DocumentInputType::value("U", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("V", TaggedValue::DVec2(DVec2::ZERO), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::expression_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Vector Expression",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_std::expression::VectorExpressionNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("X", TaggedValue::F64(0.), true),
+				DocumentInputType::value("Expression", TaggedValue::String("u + v * x".into()), false),
+				DocumentInputType::value("Y", TaggedValue::F64(0.), false),
+				DocumentInputType::value("U", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("V", TaggedValue::DVec2(DVec2::ZERO), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::expression_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Exponent",
 			category: "Math",
@@ -2062,7 +2524,116 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			..Default::default()
 		},
 		DocumentNodeDefinition {
-			name: "Max",
+			name: "Arcsine",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::ArcsineNode"),
+			inputs: vec![DocumentInputType::value("Primary", TaggedValue::F64(0.), true)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Arccosine",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::ArccosineNode"),
+			inputs: vec![DocumentInputType::value("Primary", TaggedValue::F64(0.), true)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Arctangent",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::ArctangentNode"),
+			inputs: vec![DocumentInputType::value("Primary", TaggedValue::F64(0.), true)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Arctangent 2",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::Arctangent2Node<_>"),
+			inputs: vec![DocumentInputType::value("Y", TaggedValue::F64(0.), true), DocumentInputType::value("X", TaggedValue::F64(1.), false)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::atan2_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Square Root",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::SquareRootNode"),
+			inputs: vec![DocumentInputType::value("Primary", TaggedValue::F64(0.), true)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Natural Exponent",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::NaturalExponentNode"),
+			inputs: vec![DocumentInputType::value("Primary", TaggedValue::F64(0.), true)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Clamp",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::ClampNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Primary", TaggedValue::F64(0.), true),
+				DocumentInputType::value("Min", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Max", TaggedValue::F64(1.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::clamp_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Remap",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::RemapNode<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Primary", TaggedValue::F64(0.), true),
+				DocumentInputType::value("Input Min", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Input Max", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Output Min", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Output Max", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Clamped", TaggedValue::Bool(false), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::remap_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Smoothstep",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::SmoothstepNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Primary", TaggedValue::F64(0.), true),
+				DocumentInputType::value("Edge 0", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Edge 1", TaggedValue::F64(1.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::smoothstep_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Lerp",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::LerpNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Primary", TaggedValue::F64(0.), true),
+				DocumentInputType::value("Second", TaggedValue::F64(1.), true),
+				DocumentInputType::value("Factor", TaggedValue::F64(0.5), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::lerp_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Max",
 			category: "Math",
 			implementation: DocumentNodeImplementation::proto("graphene_core::ops::MaximumNode<_>"),
 			inputs: vec![
@@ -2109,6 +2680,117 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::modulo_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Dot Product",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::DotProductNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Primary", TaggedValue::DVec2(DVec2::ZERO), true),
+				DocumentInputType::value("Second", TaggedValue::DVec2(DVec2::ZERO), true),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::vector_operand_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Cross Product",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::CrossProductNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Primary", TaggedValue::DVec2(DVec2::ZERO), true),
+				DocumentInputType::value("Second", TaggedValue::DVec2(DVec2::ZERO), true),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::vector_operand_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Vector Length",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::VectorLengthNode"),
+			inputs: vec![DocumentInputType::value("Primary", TaggedValue::DVec2(DVec2::ZERO), true)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Normalize",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::NormalizeNode"),
+			inputs: vec![DocumentInputType::value("Primary", TaggedValue::DVec2(DVec2::ZERO), true)],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Construct Transform",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::ConstructTransformNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Translation", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Rotation", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Scale", TaggedValue::DVec2(DVec2::ONE), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::General)],
+			properties: node_properties::construct_transform_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Transform Point",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::TransformPointNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Primary", TaggedValue::DVec2(DVec2::ZERO), true),
+				DocumentInputType::value("Transform", TaggedValue::DAffine2(DAffine2::IDENTITY), true),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Random Number",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::random::RandomNumberNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Min", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Max", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+				DocumentInputType::value("Index", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::random_number_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Random Vector",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::random::RandomVectorNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Min", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Max", TaggedValue::DVec2(DVec2::ONE), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+				DocumentInputType::value("Index", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
+			properties: node_properties::random_vector_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Shuffle List",
+			category: "Math",
+			implementation: DocumentNodeImplementation::proto("graphene_core::random::ShuffleListNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("List", TaggedValue::VecF64(Vec::new()), true),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+				DocumentInputType::value("Index", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::General)],
+			properties: node_properties::shuffle_list_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Log to Console",
 			category: "Logic",
@@ -2163,6 +2845,63 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::node_no_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Conditional",
+			category: "Logic",
+			implementation: DocumentNodeImplementation::proto("graphene_core::logic::ConditionalNode<_, _, _>"),
+			manual_composition: Some(concrete!(Footprint)),
+			inputs: vec![
+				DocumentInputType::value("Condition", TaggedValue::Bool(true), false),
+				DocumentInputType::value("If True", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("If False", TaggedValue::VectorData(VectorData::empty()), true),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::General)],
+			properties: node_properties::conditional_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Switch",
+			category: "Logic",
+			implementation: DocumentNodeImplementation::proto("graphene_core::logic::SwitchNode<_, _, _, _, _>"),
+			manual_composition: Some(concrete!(Footprint)),
+			inputs: vec![
+				DocumentInputType::value("Index", TaggedValue::U32(0), false),
+				DocumentInputType::value("Option 0", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Option 1", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Option 2", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Option 3", TaggedValue::VectorData(VectorData::empty()), true),
+			],
+			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::General)],
+			properties: node_properties::switch_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Assert Equal",
+			category: "Logic",
+			implementation: DocumentNodeImplementation::proto("graphene_std::validation::AssertEqualNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Value", TaggedValue::F64(0.), true),
+				DocumentInputType::value("Expected", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Tolerance", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Value", FrontendGraphDataType::General)],
+			properties: node_properties::assert_equal_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Assert Bounds",
+			category: "Logic",
+			implementation: DocumentNodeImplementation::proto("graphene_std::validation::AssertBoundsNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Graphic", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Min", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Max", TaggedValue::DVec2(DVec2::new(100., 100.)), false),
+				DocumentInputType::value("Tolerance", TaggedValue::F64(0.001), false),
+			],
+			outputs: vec![DocumentOutputType::new("Graphic", FrontendGraphDataType::General)],
+			properties: node_properties::assert_bounds_properties,
+			..Default::default()
+		},
 		(*IMAGINATE_NODE).clone(),
 		DocumentNodeDefinition {
 			name: "Circle",
@@ -2251,6 +2990,35 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::star_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Spiral",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::generator_nodes::SpiralGenerator<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Turns", TaggedValue::F64(3.), false),
+				DocumentInputType::value("Inner Radius", TaggedValue::F64(5.), false),
+				DocumentInputType::value("Outer Radius", TaggedValue::F64(50.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::spiral_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Arc",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::generator_nodes::ArcGenerator<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Radius", TaggedValue::F64(50.), false),
+				DocumentInputType::value("Start Angle", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Sweep Angle", TaggedValue::F64(270.), false),
+				DocumentInputType::value("Arc Type", TaggedValue::ArcType(ArcType::Open), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::arc_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Line",
 			category: "Vector",
@@ -2276,6 +3044,56 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::spline_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "L-System",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::l_system::LSystemNode<_, _, _, _, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Axiom", TaggedValue::String("X".to_string()), false),
+				DocumentInputType::value("Rules", TaggedValue::String("X=F+[[X]-X]-F[-FX]+X\nF=FF".to_string()), false),
+				DocumentInputType::value("Iterations", TaggedValue::U32(5), false),
+				DocumentInputType::value("Angle", TaggedValue::F64(25.), false),
+				DocumentInputType::value("Length", TaggedValue::F64(4.), false),
+				DocumentInputType::value("Angle Randomness", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Length Randomness", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::l_system_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "QR Code",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::generator_nodes::QrCodeGenerator<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Text", TaggedValue::String("https://graphite.rs".to_string()), false),
+				DocumentInputType::value("Error Correction", TaggedValue::QrErrorCorrection(QrErrorCorrection::Medium), false),
+				DocumentInputType::value("Quiet Zone", TaggedValue::U32(4), false),
+				DocumentInputType::value("Module Size", TaggedValue::F64(4.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::qr_code_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Barcode",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::generator_nodes::BarcodeGenerator<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Text", TaggedValue::String("Graphite".to_string()), false),
+				DocumentInputType::value("Symbology", TaggedValue::BarcodeSymbology(BarcodeSymbology::Code128), false),
+				DocumentInputType::value("Quiet Zone", TaggedValue::U32(10), false),
+				DocumentInputType::value("Module Size", TaggedValue::F64(2.), false),
+				DocumentInputType::value("Height", TaggedValue::F64(80.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::barcode_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Shape",
 			category: "Vector",
@@ -2317,7 +3135,7 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 		DocumentNodeDefinition {
 			name: "Text",
 			category: "Vector",
-			implementation: DocumentNodeImplementation::proto("graphene_core::text::TextGeneratorNode<_, _, _>"),
+			implementation: DocumentNodeImplementation::proto("graphene_core::text::TextGeneratorNode<_, _, _, _, _, _, _, _, _, _, _>"),
 			inputs: vec![
 				DocumentInputType::none(),
 				DocumentInputType::value("Text", TaggedValue::String("Lorem ipsum".to_string()), false),
@@ -2327,6 +3145,14 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 					false,
 				),
 				DocumentInputType::value("Size", TaggedValue::F64(24.), false),
+				DocumentInputType::value("Line Height", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Box Width", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Box Height", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Align", TaggedValue::TextAlign(TextAlign::Start), false),
+				DocumentInputType::value("Auto Resize", TaggedValue::TextAutoResize(TextAutoResize::Fixed), false),
+				DocumentInputType::value("Variations", TaggedValue::FontVariations(FontVariations::default()), false),
+				DocumentInputType::value("Style Runs", TaggedValue::TextRuns(TextRuns::default()), false),
+				DocumentInputType::value("Kerning", TaggedValue::TextKerning(TextKerning::default()), false),
 			],
 			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
 			properties: node_properties::node_section_font,
@@ -2364,7 +3190,7 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 				..Default::default()
 			}),
 			inputs: vec![
-				DocumentInputType::value("Vector Data", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
 				DocumentInputType::value("Translation", TaggedValue::DVec2(DVec2::ZERO), false),
 				DocumentInputType::value("Rotation", TaggedValue::F64(0.), false),
 				DocumentInputType::value("Scale", TaggedValue::DVec2(DVec2::ONE), false),
@@ -2386,17 +3212,73 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			outputs: vec![DocumentOutputType::new("Data", FrontendGraphDataType::VectorData)],
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Warp",
+			category: "Transform",
+			implementation: DocumentNodeImplementation::proto("graphene_std::warp::WarpNode<_, _, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Data", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Mode", TaggedValue::WarpMode(WarpMode::Perspective), false),
+				DocumentInputType::value("Top Left", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Top Right", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Bottom Right", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Bottom Left", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("Mesh", TaggedValue::VecDVec2(Vec::new()), false),
+			],
+			outputs: vec![DocumentOutputType::new("Data", FrontendGraphDataType::VectorData)],
+			properties: node_properties::warp_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Axonometric Projection",
+			category: "Transform",
+			implementation: DocumentNodeImplementation::proto("graphene_core::projection::AxonometricProjectionNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Data", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Face", TaggedValue::ProjectionFace(ProjectionFace::Top), false),
+				DocumentInputType::value("Left Angle", TaggedValue::F64(Axonometry::ISOMETRIC_ANGLE), false),
+				DocumentInputType::value("Right Angle", TaggedValue::F64(Axonometry::ISOMETRIC_ANGLE), false),
+			],
+			outputs: vec![DocumentOutputType::new("Data", FrontendGraphDataType::VectorData)],
+			manual_composition: Some(concrete!(Footprint)),
+			properties: node_properties::axonometric_projection_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Depth Stack",
+			category: "Transform",
+			implementation: DocumentNodeImplementation::proto("graphene_core::projection::DepthStackNode<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Data", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Face", TaggedValue::ProjectionFace(ProjectionFace::Top), false),
+				DocumentInputType::value("Count", TaggedValue::U32(5), false),
+				DocumentInputType::value("Spacing", TaggedValue::F64(10.), false),
+				DocumentInputType::value("Left Angle", TaggedValue::F64(Axonometry::ISOMETRIC_ANGLE), false),
+				DocumentInputType::value("Right Angle", TaggedValue::F64(Axonometry::ISOMETRIC_ANGLE), false),
+			],
+			outputs: vec![DocumentOutputType::new("Out", FrontendGraphDataType::Graphic)],
+			properties: node_properties::depth_stack_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Fill",
 			category: "Vector",
 			implementation: DocumentNodeImplementation::Network(NodeNetwork {
-				exports: vec![NodeInput::node(NodeId(0), 0)],
-				nodes: vec![DocumentNode {
-					name: "Set Fill".to_string(),
-					inputs: vec![NodeInput::network(concrete!(VectorData), 0), NodeInput::network(concrete!(vector::style::Fill), 1)],
-					implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::vector::SetFillNode<_>")),
-					..Default::default()
-				}]
+				exports: vec![NodeInput::node(NodeId(1), 0)],
+				nodes: vec![
+					DocumentNode {
+						name: "Set Fill".to_string(),
+						inputs: vec![NodeInput::network(concrete!(VectorData), 0), NodeInput::network(concrete!(vector::style::Fill), 1)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::vector::SetFillNode<_>")),
+						..Default::default()
+					},
+					DocumentNode {
+						name: "Set Fill Blending".to_string(),
+						inputs: vec![NodeInput::node(NodeId(0), 0), NodeInput::network(concrete!(f64), 4), NodeInput::network(concrete!(BlendMode), 5)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::vector::SetFillBlendingNode<_, _>")),
+						..Default::default()
+					},
+				]
 				.into_iter()
 				.enumerate()
 				.map(|(id, node)| (NodeId(id as u64), node))
@@ -2409,6 +3291,8 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 				// These backup values aren't exposed to the user, but are used to store the previous fill choices so the user can flip back from Solid to Gradient (or vice versa) without losing their settings
 				DocumentInputType::value("Backup Color", TaggedValue::OptionalColor(Some(Color::BLACK)), false),
 				DocumentInputType::value("Backup Gradient", TaggedValue::Gradient(Default::default()), false),
+				DocumentInputType::value("Opacity", TaggedValue::F64(100.), false),
+				DocumentInputType::value("Blend Mode", TaggedValue::BlendMode(BlendMode::Normal), false),
 			],
 			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
 			properties: node_properties::fill_properties,
@@ -2417,7 +3301,38 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 		DocumentNodeDefinition {
 			name: "Stroke",
 			category: "Vector",
-			implementation: DocumentNodeImplementation::proto("graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _>"),
+			implementation: DocumentNodeImplementation::Network(NodeNetwork {
+				exports: vec![NodeInput::node(NodeId(1), 0)],
+				nodes: vec![
+					DocumentNode {
+						name: "Set Stroke".to_string(),
+						inputs: vec![
+							NodeInput::network(concrete!(VectorData), 0),
+							NodeInput::network(concrete!(Option<Color>), 1),
+							NodeInput::network(concrete!(f64), 2),
+							NodeInput::network(concrete!(Vec<f64>), 3),
+							NodeInput::network(concrete!(f64), 4),
+							NodeInput::network(concrete!(vector::style::LineCap), 5),
+							NodeInput::network(concrete!(vector::style::LineJoin), 6),
+							NodeInput::network(concrete!(f64), 7),
+							NodeInput::network(concrete!(vector::style::StrokeAlign), 8),
+						],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _, _>")),
+						..Default::default()
+					},
+					DocumentNode {
+						name: "Set Stroke Blending".to_string(),
+						inputs: vec![NodeInput::node(NodeId(0), 0), NodeInput::network(concrete!(f64), 9), NodeInput::network(concrete!(BlendMode), 10)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::vector::SetStrokeBlendingNode<_, _>")),
+						..Default::default()
+					},
+				]
+				.into_iter()
+				.enumerate()
+				.map(|(id, node)| (NodeId(id as u64), node))
+				.collect(),
+				..Default::default()
+			}),
 			inputs: vec![
 				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
 				DocumentInputType::value("Color", TaggedValue::OptionalColor(Some(Color::BLACK)), false),
@@ -2427,6 +3342,9 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 				DocumentInputType::value("Line Cap", TaggedValue::LineCap(graphene_core::vector::style::LineCap::Butt), false),
 				DocumentInputType::value("Line Join", TaggedValue::LineJoin(graphene_core::vector::style::LineJoin::Miter), false),
 				DocumentInputType::value("Miter Limit", TaggedValue::F64(4.), false),
+				DocumentInputType::value("Align", TaggedValue::StrokeAlign(graphene_core::vector::style::StrokeAlign::Center), false),
+				DocumentInputType::value("Opacity", TaggedValue::F64(100.), false),
+				DocumentInputType::value("Blend Mode", TaggedValue::BlendMode(BlendMode::Normal), false),
 			],
 			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
 			properties: node_properties::stroke_properties,
@@ -2594,6 +3512,88 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::poisson_disk_points_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Sample Point Cloud",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::point_cloud::SamplePointCloudNode<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Region", TaggedValue::PointRegion(PointRegion::Inside), false),
+				DocumentInputType::value("Sampling", TaggedValue::PointSampling(PointSampling::PoissonDisk), false),
+				DocumentInputType::value("Spacing", TaggedValue::F64(20.), false),
+				DocumentInputType::value("Count", TaggedValue::U32(500), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Points", FrontendGraphDataType::General)],
+			properties: node_properties::sample_point_cloud_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Point Cloud from Anchors",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::point_cloud::PointCloudFromAnchorsNode"),
+			inputs: vec![DocumentInputType::value("Vector Data", TaggedValue::VectorData(VectorData::empty()), true)],
+			outputs: vec![DocumentOutputType::new("Points", FrontendGraphDataType::General)],
+			properties: node_properties::node_no_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Random Point Attribute",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::point_cloud::RandomPointAttributeNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Points", TaggedValue::PointCloud(PointCloud::EMPTY), true),
+				DocumentInputType::value("Name", TaggedValue::String(PointCloud::SCALE.to_string()), false),
+				DocumentInputType::value("Min", TaggedValue::F64(0.5), false),
+				DocumentInputType::value("Max", TaggedValue::F64(1.5), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Points", FrontendGraphDataType::General)],
+			properties: node_properties::random_point_attribute_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Random Vec2 Point Attribute",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::point_cloud::RandomVec2PointAttributeNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Points", TaggedValue::PointCloud(PointCloud::EMPTY), true),
+				DocumentInputType::value("Name", TaggedValue::String(PointCloud::OFFSET.to_string()), false),
+				DocumentInputType::value("Min", TaggedValue::DVec2(DVec2::splat(-5.)), false),
+				DocumentInputType::value("Max", TaggedValue::DVec2(DVec2::splat(5.)), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Points", FrontendGraphDataType::General)],
+			properties: node_properties::random_vec2_point_attribute_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Gradient Point Attribute",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::point_cloud::GradientPointAttributeNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Points", TaggedValue::PointCloud(PointCloud::EMPTY), true),
+				DocumentInputType::value("Name", TaggedValue::String(PointCloud::COLOR.to_string()), false),
+				DocumentInputType::value("Gradient", TaggedValue::GradientStops(GradientStops::default()), false),
+				DocumentInputType::value("Start", TaggedValue::DVec2(DVec2::ZERO), false),
+				DocumentInputType::value("End", TaggedValue::DVec2(DVec2::new(100., 0.)), false),
+			],
+			outputs: vec![DocumentOutputType::new("Points", FrontendGraphDataType::General)],
+			properties: node_properties::gradient_point_attribute_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Point Attribute Values",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::point_cloud::PointAttributeValuesNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Points", TaggedValue::PointCloud(PointCloud::EMPTY), true),
+				DocumentInputType::value("Name", TaggedValue::String(PointCloud::SCALE.to_string()), false),
+			],
+			outputs: vec![DocumentOutputType::new("Values", FrontendGraphDataType::Number)],
+			properties: node_properties::point_attribute_values_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Splines from Points",
 			category: "Vector",
@@ -2603,6 +3603,36 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::node_no_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Voronoi",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::voronoi::VoronoiNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Distribution", TaggedValue::PointDistribution(PointDistribution::Random), false),
+				DocumentInputType::value("Count", TaggedValue::U32(50), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+				DocumentInputType::value("Relaxation", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::voronoi_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Delaunay",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::voronoi::DelaunayNode<_, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Distribution", TaggedValue::PointDistribution(PointDistribution::Random), false),
+				DocumentInputType::value("Count", TaggedValue::U32(50), false),
+				DocumentInputType::value("Seed", TaggedValue::U32(0), false),
+				DocumentInputType::value("Relaxation", TaggedValue::U32(0), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::voronoi_properties,
+			..Default::default()
+		},
 		DocumentNodeDefinition {
 			name: "Area",
 			category: "Vector",
@@ -2618,7 +3648,7 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			category: "Vector",
 			implementation: DocumentNodeImplementation::proto("graphene_core::vector::CentroidNode<_, _>"),
 			inputs: vec![
-				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(VectorData::empty()), true),
 				DocumentInputType::value("Centroid Type", TaggedValue::CentroidType(graphene_core::vector::misc::CentroidType::Area), false),
 			],
 			outputs: vec![DocumentOutputType::new("Output", FrontendGraphDataType::Number)],
@@ -2641,6 +3671,42 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::morph_properties,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Split at Point",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::SplitAtPointNode<_>"),
+			inputs: vec![
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Point", TaggedValue::DVec2(DVec2::ZERO), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			properties: node_properties::split_at_point_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Cut Along Path",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::CutAlongPathNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Path", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Cutter", TaggedValue::VectorData(VectorData::empty()), true),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			manual_composition: Some(concrete!(Footprint)),
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Divide Shapes",
+			category: "Vector",
+			implementation: DocumentNodeImplementation::proto("graphene_core::vector::DivideShapesNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Shapes", TaggedValue::VectorData(VectorData::empty()), true),
+				DocumentInputType::value("Cutter", TaggedValue::VectorData(VectorData::empty()), true),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::VectorData)],
+			manual_composition: Some(concrete!(Footprint)),
+			..Default::default()
+		},
 		// TODO: This needs to work with resolution-aware (raster with footprint, post-Cull node) data.
 		DocumentNodeDefinition {
 			name: "Image Segmentation",
@@ -2704,6 +3770,128 @@ fn static_nodes() -> Vec<DocumentNodeDefinition> {
 			properties: node_properties::image_color_palette,
 			..Default::default()
 		},
+		DocumentNodeDefinition {
+			name: "Color Harmony",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::proto("graphene_core::raster::ColorHarmonyNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Color", TaggedValue::Color(Color::RED), true),
+				DocumentInputType::value("Harmony", TaggedValue::ColorHarmony(ColorHarmony::Complementary), false),
+				DocumentInputType::value("Spread", TaggedValue::F64(30.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Colors", FrontendGraphDataType::General)],
+			properties: node_properties::color_harmony_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Stitch Panorama",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::Network(NodeNetwork {
+				exports: vec![NodeInput::node(NodeId(1), 0)],
+				nodes: [
+					DocumentNode {
+						name: "Align Images".to_string(),
+						inputs: vec![NodeInput::network(concrete!(GraphicGroup), 0)],
+						implementation: DocumentNodeImplementation::proto("graphene_std::panorama::AlignImagesNode<_>"),
+						manual_composition: Some(concrete!(Footprint)),
+						..Default::default()
+					},
+					DocumentNode {
+						name: "Blend Panorama".to_string(),
+						inputs: vec![NodeInput::node(NodeId(0), 0), NodeInput::network(concrete!(f64), 1)],
+						implementation: DocumentNodeImplementation::proto("graphene_std::panorama::BlendPanoramaNode<_>"),
+						..Default::default()
+					},
+				]
+				.into_iter()
+				.enumerate()
+				.map(|(id, node)| (NodeId(id as u64), node))
+				.collect(),
+				..Default::default()
+			}),
+			inputs: vec![
+				DocumentInputType::value("Images", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Feather", TaggedValue::F64(50.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::stitch_panorama_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Focus Stack",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::Network(NodeNetwork {
+				exports: vec![NodeInput::node(NodeId(1), 0)],
+				nodes: [
+					DocumentNode {
+						name: "Align Images".to_string(),
+						inputs: vec![NodeInput::network(concrete!(GraphicGroup), 0)],
+						implementation: DocumentNodeImplementation::proto("graphene_std::panorama::AlignImagesNode<_>"),
+						manual_composition: Some(concrete!(Footprint)),
+						..Default::default()
+					},
+					DocumentNode {
+						name: "Focus Stack".to_string(),
+						inputs: vec![NodeInput::node(NodeId(0), 0), NodeInput::network(concrete!(f64), 1)],
+						implementation: DocumentNodeImplementation::proto("graphene_std::image_stacking::FocusStackNode<_>"),
+						..Default::default()
+					},
+				]
+				.into_iter()
+				.enumerate()
+				.map(|(id, node)| (NodeId(id as u64), node))
+				.collect(),
+				..Default::default()
+			}),
+			inputs: vec![
+				DocumentInputType::value("Images", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Smoothness", TaggedValue::F64(4.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::focus_stack_properties,
+			..Default::default()
+		},
+		DocumentNodeDefinition {
+			name: "Exposure Fusion",
+			category: "Image Adjustments",
+			implementation: DocumentNodeImplementation::Network(NodeNetwork {
+				exports: vec![NodeInput::node(NodeId(1), 0)],
+				nodes: [
+					DocumentNode {
+						name: "Align Images".to_string(),
+						inputs: vec![NodeInput::network(concrete!(GraphicGroup), 0)],
+						implementation: DocumentNodeImplementation::proto("graphene_std::panorama::AlignImagesNode<_>"),
+						manual_composition: Some(concrete!(Footprint)),
+						..Default::default()
+					},
+					DocumentNode {
+						name: "Exposure Fusion".to_string(),
+						inputs: vec![
+							NodeInput::node(NodeId(0), 0),
+							NodeInput::network(concrete!(f64), 1),
+							NodeInput::network(concrete!(f64), 2),
+							NodeInput::network(concrete!(f64), 3),
+						],
+						implementation: DocumentNodeImplementation::proto("graphene_std::image_stacking::ExposureFusionNode<_, _, _>"),
+						..Default::default()
+					},
+				]
+				.into_iter()
+				.enumerate()
+				.map(|(id, node)| (NodeId(id as u64), node))
+				.collect(),
+				..Default::default()
+			}),
+			inputs: vec![
+				DocumentInputType::value("Images", TaggedValue::GraphicGroup(GraphicGroup::EMPTY), true),
+				DocumentInputType::value("Contrast Weight", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Saturation Weight", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Exposure Weight", TaggedValue::F64(1.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Image", FrontendGraphDataType::Raster)],
+			properties: node_properties::exposure_fusion_properties,
+			..Default::default()
+		},
 	]
 }
 
@@ -2783,6 +3971,30 @@ pub fn resolve_document_node_type(name: &str) -> Option<&DocumentNodeDefinition>
 	DOCUMENT_NODE_TYPES.iter().find(|node| node.name == name)
 }
 
+/// The category of the nodes provided by loaded plugins, which are listed in the node catalog as `<plugin name>/<node name>`.
+pub const PLUGIN_NODE_CATEGORY: &str = "Plugins";
+
+/// The nodes of every loaded plugin, as they're listed in the node catalog.
+pub fn collect_plugin_node_types() -> Vec<FrontendNodeType> {
+	graphene_std::plugin::loaded_plugins()
+		.iter()
+		.flat_map(|plugin| {
+			plugin.manifest.nodes.iter().map(|node| FrontendNodeType {
+				name: format!("{}/{}", plugin.manifest.name, node.name),
+				category: PLUGIN_NODE_CATEGORY.to_string(),
+			})
+		})
+		.collect()
+}
+
+/// Creates the document node of a loaded plugin's node from the name it's listed under in the node catalog.
+pub fn resolve_plugin_node(name: &str) -> Option<DocumentNode> {
+	let (plugin_name, node_name) = name.split_once('/')?;
+	let plugin = graphene_std::plugin::plugin(plugin_name)?;
+	let node = plugin.manifest.nodes.iter().find(|node| node.name == node_name)?;
+	Some(graphene_std::plugin::plugin_document_node(plugin_name, node))
+}
+
 pub fn collect_node_types() -> Vec<FrontendNodeType> {
 	DOCUMENT_NODE_TYPES
 		.iter()
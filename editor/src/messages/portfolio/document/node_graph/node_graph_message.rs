@@ -2,9 +2,11 @@ use crate::messages::input_mapper::utility_types::input_keyboard::Key;
 use crate::messages::prelude::*;
 
 use graph_craft::document::value::TaggedValue;
-use graph_craft::document::{DocumentNode, NodeId, NodeInput};
+use graph_craft::document::{CommentFrame, DocumentNode, NodeId, NodeInput};
 use graph_craft::proto::GraphErrors;
+use graphene_core::Color;
 use interpreted_executor::dynamic_executor::ResolvedDocumentNodeTypes;
+use interpreted_executor::node_outputs::InspectedValue;
 
 #[impl_message(Message, DocumentMessage, NodeGraph)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -12,6 +14,8 @@ pub enum NodeGraphMessage {
 	// Messages
 	Init,
 	SelectedNodesUpdated,
+	/// Adds a comment frame around the selected nodes
+	AddCommentFrame,
 	ConnectNodesByWire {
 		output_node: NodeId,
 		output_node_connector_index: usize,
@@ -20,6 +24,10 @@ pub enum NodeGraphMessage {
 	},
 	Copy,
 	CloseCreateNodeMenu,
+	CreateCustomNode {
+		node_ids: Vec<NodeId>,
+	},
+	CreateCustomNodeFromSelection,
 	CreateNode {
 		node_id: Option<NodeId>,
 		node_type: String,
@@ -27,6 +35,9 @@ pub enum NodeGraphMessage {
 		y: i32,
 	},
 	Cut,
+	DeleteCommentFrame {
+		id: u64,
+	},
 	DeleteNodes {
 		node_ids: Vec<NodeId>,
 		reconnect: bool,
@@ -64,10 +75,20 @@ pub enum NodeGraphMessage {
 		pre_node_output_index: usize,
 		pre_node_id: NodeId,
 	},
+	MoveCommentFrame {
+		id: u64,
+		displacement_x: i32,
+		displacement_y: i32,
+		nodes: Vec<NodeId>,
+	},
 	MoveSelectedNodes {
 		displacement_x: i32,
 		displacement_y: i32,
 	},
+	/// Opens the network containing the node, selects it, and centers the graph view on it
+	NavigateToNode {
+		node_path: Vec<NodeId>,
+	},
 	PasteNodes {
 		serialized_nodes: String,
 	},
@@ -82,7 +103,16 @@ pub enum NodeGraphMessage {
 	},
 	PointerUp,
 	PrintSelectedNodeCoordinates,
+	ResizeCommentFrame {
+		id: u64,
+		displacement_x: i32,
+		displacement_y: i32,
+	},
 	RunDocumentGraph,
+	/// Finds the nodes in every network of the document by their name, output type, or input values, listing them in the node graph bar
+	SearchGraph {
+		query: String,
+	},
 	SelectedNodesAdd {
 		nodes: Vec<NodeId>,
 	},
@@ -93,6 +123,18 @@ pub enum NodeGraphMessage {
 		nodes: Vec<NodeId>,
 	},
 	SendGraph,
+	SendNodeTypes,
+	SetCommentFrameColor {
+		id: u64,
+		color: Color,
+	},
+	SetCommentFrames {
+		frames: Vec<CommentFrame>,
+	},
+	SetCommentFrameTitle {
+		id: u64,
+		title: String,
+	},
 	SetInputValue {
 		node_id: NodeId,
 		input_index: usize,
@@ -135,6 +177,9 @@ pub enum NodeGraphMessage {
 	StartPreviewingWithoutRestore {
 		node_id: NodeId,
 	},
+	ToggleInspection {
+		node_id: NodeId,
+	},
 	TogglePreview {
 		node_id: NodeId,
 	},
@@ -146,7 +191,15 @@ pub enum NodeGraphMessage {
 	ToggleVisibility {
 		node_id: NodeId,
 	},
+	UpdateInspection {
+		#[serde(skip)]
+		inspection: Option<InspectedValue>,
+	},
 	UpdateNewNodeGraph,
+	UpdateNodeOutputThumbnails {
+		#[serde(skip)]
+		thumbnails: HashMap<Vec<NodeId>, String>,
+	},
 	UpdateTypes {
 		#[serde(skip)]
 		resolved_types: ResolvedDocumentNodeTypes,
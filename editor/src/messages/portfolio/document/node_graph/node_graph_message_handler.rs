@@ -1,23 +1,32 @@
-use super::utility_types::{BoxSelection, ContextMenuInformation, DragStart, FrontendGraphInput, FrontendGraphOutput, FrontendNode, FrontendNodeWire, WirePath};
+use super::utility_types::{
+	BoxSelection, CommentFrameDrag, ContextMenuInformation, DragStart, FrontendCommentFrame, FrontendGraphInput, FrontendGraphOutput, FrontendGraphSearchResult, FrontendNode, FrontendNodeInspection,
+	FrontendNodeWire, WirePath,
+};
 use super::{document_node_types, node_properties};
 use crate::application::generate_uuid;
+use crate::consts::COLOR_COMMENT_FRAME;
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::graph_operation::load_network_structure;
 use crate::messages::portfolio::document::graph_operation::utility_types::ModifyInputsContext;
 use crate::messages::portfolio::document::node_graph::document_node_types::NodePropertiesContext;
 use crate::messages::portfolio::document::node_graph::utility_types::{ContextMenuData, FrontendGraphDataType};
+use crate::messages::portfolio::document::utility_types::custom_nodes::{subgraph_export_name, subgraph_import_name, CustomNodeDefinition, CustomNodeLibrary};
 use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
 use crate::messages::portfolio::document::utility_types::node_metadata::{NetworkMetadata, NodeMetadata};
 use crate::messages::portfolio::document::utility_types::nodes::{CollapsedLayers, LayerPanelEntry, SelectedNodes};
 use crate::messages::prelude::*;
 
 use bezier_rs::Subpath;
+use graph_craft::concrete;
+use graph_craft::document::search::{search_network, SearchMatch};
 use graph_craft::document::value::TaggedValue;
-use graph_craft::document::{DocumentNode, DocumentNodeImplementation, FlowType, NodeId, NodeInput, NodeNetwork, Previewing, Source};
-use graph_craft::proto::GraphErrors;
+use graph_craft::document::{CommentFrame, DocumentNode, DocumentNodeImplementation, FlowType, NodeId, NodeInput, NodeNetwork, Previewing, Source};
+use graph_craft::proto::{GraphErrorType, GraphErrors};
+use graphene_core::vector::style::FillChoice;
 use graphene_core::*;
 use interpreted_executor::dynamic_executor::ResolvedDocumentNodeTypes;
+use interpreted_executor::node_outputs::InspectedValue;
 
 use glam::{DAffine2, DVec2, IVec2, UVec2};
 use renderer::{ClickTarget, Quad};
@@ -33,6 +42,7 @@ pub struct NodeGraphHandlerData<'a> {
 	pub collapsed: &'a mut CollapsedLayers,
 	pub ipp: &'a InputPreprocessorMessageHandler,
 	pub graph_view_overlay_open: bool,
+	pub custom_nodes: &'a mut CustomNodeLibrary,
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +67,18 @@ pub struct NodeGraphMessageHandler {
 	wire_in_progress_to_connector: Option<(DVec2, bool)>,
 	// State for the context menu popups
 	context_menu: Option<ContextMenuInformation>,
+	// The comment frame whose title and color are shown in the node graph bar
+	selected_comment_frame: Option<u64>,
+	comment_frame_drag: Option<CommentFrameDrag>,
+	// The query typed into the node graph bar and the nodes in the document found by it
+	graph_search_query: String,
+	graph_search_results: Vec<FrontendGraphSearchResult>,
+	/// Thumbnails of the output of every node in every network, by the path to that node, while they're shown in the graph
+	node_output_thumbnails: HashMap<Vec<NodeId>, String>,
+	/// The path to the node whose output is described after each evaluation of the graph, for debugging why a result further down the graph is wrong
+	pub inspected_node: Option<Vec<NodeId>>,
+	/// What the inspected node output when the graph was last evaluated
+	inspection: Option<InspectedValue>,
 	/// Click targets for every node in every network by using the path to that node
 	/// TODO: Only store click targets for nodes in the current network
 	pub node_metadata: HashMap<Vec<NodeId>, NodeMetadata>,
@@ -76,6 +98,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 			collapsed,
 			graph_view_overlay_open,
 			ipp,
+			custom_nodes,
 			..
 		} = data;
 
@@ -98,6 +121,40 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 				responses.add(ArtboardToolMessage::UpdateSelectedArtboard);
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 			}
+			NodeGraphMessage::AddCommentFrame => {
+				let Some(network) = document_network.nested_network(&self.network) else {
+					return;
+				};
+				let bounds = selected_nodes
+					.selected_nodes(network)
+					.filter_map(|&node_id| {
+						let mut node_id_path = self.network.clone();
+						node_id_path.push(node_id);
+						self.node_metadata.get(&node_id_path)?.node_click_target.subpath.bounding_box()
+					})
+					.reduce(Quad::combine_bounds);
+				let Some([min, max]) = bounds else { return };
+
+				// Leave a grid cell of space around the nodes, plus a row above them for the title
+				let position = (min / 24.).floor().as_ivec2() - IVec2::new(1, 2);
+				let size = (max / 24.).ceil().as_ivec2() + IVec2::ONE - position;
+				let color = COLOR_COMMENT_FRAME
+					.strip_prefix('#')
+					.and_then(Color::from_rgba_str)
+					.expect("Should create Color from prefixed hex string");
+
+				let id = generate_uuid();
+				self.selected_comment_frame = Some(id);
+				self.modify_comment_frames(document_network, responses, |frames| {
+					frames.push(CommentFrame {
+						id,
+						title: "Comment".to_string(),
+						color,
+						position,
+						size,
+					})
+				});
+			}
 			NodeGraphMessage::ConnectNodesByWire {
 				output_node,
 				output_node_connector_index,
@@ -200,21 +257,97 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 				self.wire_in_progress_to_connector = None;
 				responses.add(FrontendMessage::UpdateWirePathInProgress { wire_path: None });
 			}
+			NodeGraphMessage::CreateCustomNode { node_ids } => {
+				let network_path = if node_ids.iter().any(|node_id| document_network.nodes.contains_key(node_id)) {
+					Vec::new()
+				} else {
+					self.network.clone()
+				};
+				let Some(network) = document_network.nested_network(&network_path) else {
+					warn!("No network in NodeGraphMessage::CreateCustomNode");
+					return;
+				};
+
+				// The types of the inputs have to be found before the nodes are moved into the custom node's network, since they're resolved by each node's path
+				let input_types = node_ids
+					.iter()
+					.filter_map(|&node_id| network.nodes.get(&node_id).map(|node| (node_id, node.inputs.len())))
+					.flat_map(|(node_id, input_count)| (0..input_count).map(move |input_index| (node_id, input_index)))
+					.map(|(node_id, input_index)| {
+						let input_type = ModifyInputsContext::get_input_type(document_network, &network_path, node_id, &self.resolved_types, input_index);
+						((node_id, input_index), input_type)
+					})
+					.collect::<HashMap<_, _>>();
+				let input_type = |node_id, input_index| input_types.get(&(node_id, input_index)).cloned().unwrap_or(concrete!(()));
+
+				let name = custom_nodes.unique_name("Custom Node");
+				let custom_node_id = NodeId(generate_uuid());
+				let Some(network) = document_network.nested_network_mut(&network_path) else { return };
+				let Some(collapsed_subgraph) = network.collapse_into_subgraph(&node_ids, custom_node_id, name.clone(), &input_type) else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Cannot create custom node".to_string(),
+						description: "The selected nodes are connected to each other through nodes that aren't selected, so they can't be replaced by a single node".to_string(),
+					});
+					return;
+				};
+
+				// Instances are placed without any wires, so the definition gives a value to each input that's wired into the new node
+				let mut definition_node = network.nodes[&custom_node_id].clone();
+				definition_node.metadata = Default::default();
+				for (input, &(node_id, input_index)) in definition_node.inputs.iter_mut().zip(&collapsed_subgraph.imports) {
+					if !matches!(input, NodeInput::Value { .. }) {
+						*input = NodeInput::value(TaggedValue::from_type(&input_type(node_id, input_index)), true);
+					}
+				}
+				custom_nodes.definitions.push(CustomNodeDefinition { name, node: definition_node });
+
+				if network_path.is_empty() {
+					load_network_structure(document_network, document_metadata, collapsed);
+				}
+				self.update_all_click_targets(document_network, self.network.clone());
+
+				selected_nodes.clear_selected_nodes();
+				selected_nodes.add_selected_nodes(vec![custom_node_id], document_network, &self.network);
+				responses.add(BroadcastEvent::SelectionChanged);
+
+				responses.add(NodeGraphMessage::SendNodeTypes);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(NodeGraphMessage::SendGraph);
+			}
+			NodeGraphMessage::CreateCustomNodeFromSelection => {
+				let Some(network) = document_network.nested_network_for_selected_nodes(&self.network, selected_nodes.selected_nodes_ref().iter()) else {
+					warn!("No network in NodeGraphMessage::CreateCustomNodeFromSelection");
+					return;
+				};
+				let node_ids = selected_nodes
+					.selected_nodes(network)
+					.copied()
+					.filter(|node_id| network.nodes.contains_key(node_id))
+					.collect::<Vec<_>>();
+				if node_ids.is_empty() || node_ids.iter().any(|node_id| network.nodes[node_id].is_layer) {
+					return;
+				}
+
+				responses.add(DocumentMessage::StartTransaction);
+				responses.add(NodeGraphMessage::CreateCustomNode { node_ids });
+			}
 			NodeGraphMessage::CreateNode { node_id, node_type, x, y } => {
 				let node_id = node_id.unwrap_or_else(|| NodeId(generate_uuid()));
-
-				let Some(document_node_type) = document_node_types::resolve_document_node_type(&node_type) else {
+				let metadata = graph_craft::document::DocumentNodeMetadata::position((x / 24, y / 24));
+
+				let document_node = if let Some(document_node_type) = document_node_types::resolve_document_node_type(&node_type) {
+					document_node_type.to_document_node(document_node_type.inputs.iter().map(|input| input.default.clone()), metadata)
+				} else if let Some(custom_node) = custom_nodes.get(&node_type) {
+					custom_node.to_document_node(metadata)
+				} else if let Some(plugin_node) = document_node_types::resolve_plugin_node(&node_type) {
+					DocumentNode { metadata, ..plugin_node }
+				} else {
 					responses.add(DialogMessage::DisplayDialogError {
 						title: "Cannot insert node".to_string(),
 						description: format!("The document node '{node_type}' does not exist in the document node list"),
 					});
 					return;
 				};
-
-				let document_node = document_node_type.to_document_node(
-					document_node_type.inputs.iter().map(|input| input.default.clone()),
-					graph_craft::document::DocumentNodeMetadata::position((x / 24, y / 24)),
-				);
 				self.context_menu = None;
 
 				responses.add(DocumentMessage::StartTransaction);
@@ -260,6 +393,9 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 				responses.add(NodeGraphMessage::Copy);
 				responses.add(NodeGraphMessage::DeleteSelectedNodes { reconnect: true });
 			}
+			NodeGraphMessage::DeleteCommentFrame { id } => {
+				self.modify_comment_frames(document_network, responses, |frames| frames.retain(|frame| frame.id != id));
+			}
 			NodeGraphMessage::DeleteNodes { node_ids, reconnect } => {
 				ModifyInputsContext::delete_nodes(self, document_network, selected_nodes, node_ids, reconnect, responses, self.network.clone());
 
@@ -531,6 +667,31 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 				responses.add(NodeGraphMessage::SendGraph);
 			}
+			NodeGraphMessage::MoveCommentFrame {
+				id,
+				displacement_x,
+				displacement_y,
+				nodes,
+			} => {
+				let Some(network) = document_network.nested_network_mut(&self.network) else {
+					return;
+				};
+				let Some(frame) = network.comment_frames.iter_mut().find(|frame| frame.id == id) else {
+					return;
+				};
+				let displacement = IVec2::new(displacement_x, displacement_y);
+				frame.position += displacement;
+				for node_id in &nodes {
+					if let Some(node) = network.nodes.get_mut(node_id) {
+						node.metadata.position += displacement;
+					}
+				}
+
+				for node_id in nodes {
+					self.update_click_target(node_id, document_network, self.network.clone());
+				}
+				self.send_node_graph_update(document_network, graph_view_overlay_open, responses);
+			}
 			NodeGraphMessage::MoveSelectedNodes { displacement_x, displacement_y } => {
 				let network_path = if selected_nodes.selected_nodes_ref().iter().any(|node_id| document_network.nodes.contains_key(node_id)) {
 					Vec::new()
@@ -564,11 +725,42 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 					};
 					let wires = Self::collect_wires(network);
 					let nodes = self.collect_nodes(document_network, network, &wires);
-					responses.add(FrontendMessage::UpdateNodeGraph { nodes, wires });
+					let frames = self.collect_comment_frames(network);
+					responses.add(FrontendMessage::UpdateNodeGraph { nodes, wires, frames });
 					responses.add(DocumentMessage::RenderRulers);
 					responses.add(DocumentMessage::RenderScrollbars);
 				}
 			}
+			NodeGraphMessage::NavigateToNode { node_path } => {
+				let Some((&node_id, network_path)) = node_path.split_last() else {
+					return;
+				};
+				if !document_network.nested_network(network_path).is_some_and(|network| network.nodes.contains_key(&node_id)) {
+					warn!("No node to navigate to at {node_path:?}");
+					return;
+				}
+
+				if !graph_view_overlay_open {
+					responses.add(DocumentMessage::GraphViewOverlay { open: true });
+				}
+				if self.network != network_path {
+					selected_nodes.clear_selected_nodes();
+					self.network = network_path.to_vec();
+					if let Some(network) = document_network.nested_network(network_path) {
+						responses.add(DocumentMessage::UpdateDocumentTransform {
+							transform: network.node_graph_to_viewport,
+						});
+					}
+					responses.add(NodeGraphMessage::SendGraph);
+				}
+				// The click targets are stale when the graph was closed or showed another network
+				self.update_all_click_targets(document_network, self.network.clone());
+
+				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![node_id] });
+				if let Some(bounds) = self.node_metadata.get(&node_path).and_then(|node_metadata| node_metadata.node_click_target.subpath.bounding_box()) {
+					responses.add(NavigationMessage::FitViewportToBounds { bounds, prevent_zoom_past_100: true });
+				}
+			}
 			NodeGraphMessage::PasteNodes { serialized_nodes } => {
 				let Some(network) = document_network.nested_network_for_selected_nodes(&self.network, selected_nodes.selected_nodes_ref().iter()) else {
 					warn!("No network");
@@ -806,7 +998,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 							// The 4.95 is to ensure wire generated here aligns with the frontend wire when the mouse is moved within a node connector, but the wire is not disconnected yet. Eventually all wires should be generated in Rust so that all positions will be aligned.
 							Some((
 								DVec2::new(
-									output_node.metadata.position.x as f64 * 24. + 5. * 24. + 4.95,
+									output_node.metadata.position.x as f64 * 24. + Self::node_width(output_node) + 4.95,
 									output_node.metadata.position.y as f64 * 24. + 24. + 24. * *output_index as f64,
 								),
 								false,
@@ -857,7 +1049,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 						} else {
 							Some((
 								DVec2::new(
-									clicked_output_node.metadata.position.x as f64 * 24. + 5. * 24.,
+									clicked_output_node.metadata.position.x as f64 * 24. + Self::node_width(clicked_output_node),
 									clicked_output_node.metadata.position.y as f64 * 24. + 24. + 24. * clicked_output.1 as f64,
 								),
 								false,
@@ -877,6 +1069,34 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 					return;
 				}
 
+				// Comment frames are drawn behind the nodes, so they're only clicked where no node is
+				let clicked_frame = if clicked_id.is_none() { Self::comment_frame_at_point(network, point) } else { None };
+				let selected_comment_frame = clicked_frame.map(|(frame, _)| frame.id);
+				if self.selected_comment_frame != selected_comment_frame {
+					self.selected_comment_frame = selected_comment_frame;
+					self.update_selection_action_buttons(document_network, selected_nodes, responses);
+					self.send_node_graph_update(document_network, graph_view_overlay_open, responses);
+				}
+
+				// Begin dragging the comment frame by its title bar, or resizing it by its bottom right corner
+				if let Some((frame, resizing)) = clicked_frame {
+					let nodes = if resizing {
+						Vec::new()
+					} else {
+						network.nodes.iter().filter(|(_, node)| frame.contains(node.metadata.position)).map(|(&node_id, _)| node_id).collect()
+					};
+					let start = DragStart {
+						start_x: point.x,
+						start_y: point.y,
+						round_x: 0,
+						round_y: 0,
+					};
+					self.comment_frame_drag = Some(CommentFrameDrag { id: frame.id, resizing, start, nodes });
+					self.begin_dragging = true;
+					responses.add(NodeGraphMessage::SelectedNodesSet { nodes: Vec::new() });
+					return;
+				}
+
 				if let Some(clicked_id) = clicked_id {
 					let mut updated_selected = selected_nodes.selected_nodes(network).cloned().collect::<Vec<_>>();
 					let mut modified_selected = false;
@@ -981,7 +1201,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 							)
 						};
 						self.wire_in_progress_to_connector = Some((to_connector_position, input_index == 0 && is_layer));
-					} else if let Some((to_connector_node_position, is_layer, output_index)) = NodeGraphMessageHandler::get_key_from_point(
+					} else if let Some((to_connector_node_position, is_layer, output_index, node_width)) = NodeGraphMessageHandler::get_key_from_point(
 						&self
 							.node_metadata
 							.iter()
@@ -998,16 +1218,20 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 						point,
 					)
 					.and_then(|(node_id, output_index)| {
-						network
-							.nodes
-							.get(&node_id)
-							.map(|node| (node.metadata.position, node.is_layer, output_index + if node.has_primary_output { 0 } else { 1 }))
+						network.nodes.get(&node_id).map(|node| {
+							(
+								node.metadata.position,
+								node.is_layer,
+								output_index + if node.has_primary_output { 0 } else { 1 },
+								Self::node_width(node),
+							)
+						})
 					}) {
 						let to_connector_position = if is_layer {
 							DVec2::new(to_connector_node_position.x as f64 * 24. + 2. * 24., to_connector_node_position.y as f64 * 24. - 12.)
 						} else {
 							DVec2::new(
-								to_connector_node_position.x as f64 * 24. + 5. * 24. + 2.95,
+								to_connector_node_position.x as f64 * 24. + node_width + 2.95,
 								to_connector_node_position.y as f64 * 24. + output_index as f64 * 24. + 24.,
 							)
 						};
@@ -1042,6 +1266,29 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 						};
 						responses.add(FrontendMessage::UpdateWirePathInProgress { wire_path: Some(wire_path) });
 					}
+				} else if let Some(frame_drag) = &mut self.comment_frame_drag {
+					if self.begin_dragging {
+						responses.add(DocumentMessage::StartTransaction);
+						self.begin_dragging = false;
+					}
+					let drag_start = &mut frame_drag.start;
+					let graph_delta = IVec2::new(((point.x - drag_start.start_x) / 24.).round() as i32, ((point.y - drag_start.start_y) / 24.).round() as i32);
+					if drag_start.round_x != graph_delta.x || drag_start.round_y != graph_delta.y {
+						let (id, displacement_x, displacement_y) = (frame_drag.id, graph_delta.x - drag_start.round_x, graph_delta.y - drag_start.round_y);
+						if frame_drag.resizing {
+							responses.add(NodeGraphMessage::ResizeCommentFrame { id, displacement_x, displacement_y });
+						} else {
+							let nodes = frame_drag.nodes.clone();
+							responses.add(NodeGraphMessage::MoveCommentFrame {
+								id,
+								displacement_x,
+								displacement_y,
+								nodes,
+							});
+						}
+						drag_start.round_x = graph_delta.x;
+						drag_start.round_y = graph_delta.y;
+					}
 				} else if let Some(drag_start) = &mut self.drag_start {
 					if self.begin_dragging {
 						responses.add(DocumentMessage::StartTransaction);
@@ -1271,6 +1518,7 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 					self.select_if_not_dragged = None
 				}
 				self.drag_start = None;
+				self.comment_frame_drag = None;
 				self.begin_dragging = false;
 				self.box_selection_start = None;
 				self.wire_in_progress_from_connector = None;
@@ -1324,6 +1572,16 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 			NodeGraphMessage::RunDocumentGraph => {
 				responses.add(PortfolioMessage::SubmitGraphRender { document_id });
 			}
+			NodeGraphMessage::SearchGraph { query } => {
+				self.graph_search_results = self.search_graph(document_network, &query);
+				self.graph_search_query = query;
+				responses.add(FrontendMessage::UpdateGraphSearchResults {
+					results: self.graph_search_results.clone(),
+				});
+
+				self.widgets[1] = LayoutGroup::Row { widgets: self.right_side_widgets() };
+				self.send_node_bar_layout(responses);
+			}
 			NodeGraphMessage::SelectedNodesAdd { nodes } => {
 				selected_nodes.add_selected_nodes(nodes, document_network, &self.network);
 				responses.add(BroadcastEvent::SelectionChanged);
@@ -1337,9 +1595,55 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 				responses.add(BroadcastEvent::SelectionChanged);
 				responses.add(PropertiesPanelMessage::Refresh);
 			}
+			NodeGraphMessage::ResizeCommentFrame { id, displacement_x, displacement_y } => {
+				let Some(frame) = document_network
+					.nested_network_mut(&self.network)
+					.and_then(|network| network.comment_frames.iter_mut().find(|frame| frame.id == id))
+				else {
+					return;
+				};
+				// Keep room for the title
+				frame.size = (frame.size + IVec2::new(displacement_x, displacement_y)).max(IVec2::new(4, 2));
+
+				self.send_node_graph_update(document_network, graph_view_overlay_open, responses);
+			}
 			NodeGraphMessage::SendGraph => {
 				self.send_graph(document_network, document_metadata, collapsed, graph_view_overlay_open, responses);
 			}
+			NodeGraphMessage::SendNodeTypes => {
+				let node_types = document_node_types::collect_node_types()
+					.into_iter()
+					.chain(custom_nodes.node_types())
+					.chain(document_node_types::collect_plugin_node_types())
+					.collect();
+				responses.add(FrontendMessage::UpdateNodeTypes { node_types });
+			}
+			NodeGraphMessage::SetCommentFrameColor { id, color } => {
+				self.modify_comment_frames(document_network, responses, |frames| {
+					if let Some(frame) = frames.iter_mut().find(|frame| frame.id == id) {
+						frame.color = color;
+					}
+				});
+			}
+			NodeGraphMessage::SetCommentFrames { frames } => {
+				let Some(network) = document_network.nested_network_mut(&self.network) else {
+					return;
+				};
+				network.comment_frames = frames;
+				if self.selected_comment_frame.is_some_and(|id| !network.comment_frames.iter().any(|frame| frame.id == id)) {
+					self.selected_comment_frame = None;
+				}
+
+				self.update_selection_action_buttons(document_network, selected_nodes, responses);
+				self.send_node_graph_update(document_network, graph_view_overlay_open, responses);
+			}
+			NodeGraphMessage::SetCommentFrameTitle { id, title } => {
+				self.modify_comment_frames(document_network, responses, |frames| {
+					if let Some(frame) = frames.iter_mut().find(|frame| frame.id == id) {
+						frame.title = title;
+					}
+				});
+			}
 			NodeGraphMessage::SetInputValue { node_id, input_index, value } => {
 				let Some(network) = document_network.nested_network_for_selected_nodes(&self.network, std::iter::once(&node_id)) else {
 					return;
@@ -1581,6 +1885,16 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 				};
 				network.start_previewing_without_restore();
 			}
+			NodeGraphMessage::ToggleInspection { node_id } => {
+				let mut node_path = self.network.clone();
+				node_path.push(node_id);
+				self.inspected_node = if self.inspected_node.as_ref() == Some(&node_path) { None } else { Some(node_path) };
+				self.inspection = None;
+
+				self.update_selection_action_buttons(document_network, selected_nodes, responses);
+				self.send_inspection(document_network, responses);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+			}
 			NodeGraphMessage::TogglePreview { node_id } => {
 				let Some(network) = document_network.nested_network_for_selected_nodes(&self.network, std::iter::once(&node_id)) else {
 					return;
@@ -1659,17 +1973,26 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 			}
+			NodeGraphMessage::UpdateInspection { inspection } => {
+				if self.inspection != inspection {
+					self.inspection = inspection;
+					self.update_selection_action_buttons(document_network, selected_nodes, responses);
+					self.send_inspection(document_network, responses);
+				}
+			}
 			NodeGraphMessage::UpdateNewNodeGraph => {
 				selected_nodes.clear_selected_nodes();
 				responses.add(BroadcastEvent::SelectionChanged);
 
 				self.send_graph(document_network, document_metadata, collapsed, graph_view_overlay_open, responses);
 
-				let node_types = document_node_types::collect_node_types();
-				responses.add(FrontendMessage::UpdateNodeTypes { node_types });
+				responses.add(NodeGraphMessage::SendNodeTypes);
 
 				self.update_selected(document_network, selected_nodes, responses);
 			}
+			NodeGraphMessage::UpdateNodeOutputThumbnails { thumbnails } => {
+				self.node_output_thumbnails = thumbnails;
+			}
 			NodeGraphMessage::UpdateTypes { resolved_types, node_graph_errors } => {
 				self.resolved_types = resolved_types;
 				self.node_graph_errors = node_graph_errors;
@@ -1805,7 +2128,7 @@ impl NodeGraphMessageHandler {
 
 				layer_width_cells * grid_size
 			} else {
-				5 * grid_size
+				Self::node_width(node) as u32
 			};
 			let height = if node.is_layer {
 				2 * grid_size
@@ -1819,7 +2142,10 @@ impl NodeGraphMessageHandler {
 				std::cmp::max(inputs_count, outputs_count) as u32 * grid_size
 			};
 			let mut corner1 = DVec2::new((node.metadata.position.x * grid_size as i32) as f64, (node.metadata.position.y * grid_size as i32) as f64);
-			let radius = if !node.is_layer {
+			let radius = if Self::is_reroute(node) {
+				corner1 += DVec2::new(0., (grid_size / 2) as f64);
+				(grid_size / 2) as f64
+			} else if !node.is_layer {
 				corner1 += DVec2::new(0., (grid_size / 2) as f64);
 				3.
 			} else {
@@ -1842,7 +2168,7 @@ impl NodeGraphMessageHandler {
 			let mut visibility_click_target = None;
 
 			if !node.is_layer {
-				let mut node_top_right: DVec2 = corner1 + DVec2::new(5. * 24., 0.);
+				let mut node_top_right: DVec2 = corner1 + DVec2::new(width as f64, 0.);
 
 				let number_of_inputs = node.inputs.iter().filter(|input| input.is_exposed()).count();
 				let number_of_outputs = if let DocumentNodeImplementation::Network(network) = &node.implementation {
@@ -2100,6 +2426,104 @@ impl NodeGraphMessageHandler {
 		});
 	}
 
+	/// Finds the nodes matching the query in every network of the document, described by what they were found by
+	fn search_graph(&self, document_network: &NodeNetwork, query: &str) -> Vec<FrontendGraphSearchResult> {
+		let output_type = |node_path: &[NodeId]| self.resolved_types.outputs.get(&Source { node: node_path.to_vec(), index: 0 }).map(|ty| ty.to_string());
+
+		search_network(document_network, query, output_type)
+			.into_iter()
+			.map(|result| {
+				let found_by = match result.found_by {
+					SearchMatch::Name => "Name".to_string(),
+					SearchMatch::Type(ty) => format!("Type: {ty}"),
+					SearchMatch::Value { input_index, value } => {
+						let input_name = result
+							.node_path
+							.split_last()
+							.and_then(|(node_id, network_path)| document_network.nested_network(network_path)?.nodes.get(node_id))
+							.and_then(|node| document_node_types::resolve_document_node_type(&node.name))
+							.and_then(|definition| definition.inputs.get(input_index))
+							.map_or_else(|| format!("Input {}", input_index + 1), |input| input.name.to_string());
+						format!("{input_name}: {value}")
+					}
+				};
+				FrontendGraphSearchResult {
+					node_path: result.node_path,
+					name: result.name,
+					found_by,
+					position: result.position.into(),
+				}
+			})
+			.collect()
+	}
+
+	/// Sends the description of what the inspected node output to the frontend
+	fn send_inspection(&self, document_network: &NodeNetwork, responses: &mut VecDeque<Message>) {
+		let inspection = self.inspected_node.as_ref().map(|node_path| {
+			let name = node_path
+				.split_last()
+				.and_then(|(node_id, network_path)| document_network.nested_network(network_path)?.nodes.get(node_id))
+				.map(|node| if node.alias.is_empty() { node.name.clone() } else { node.alias.clone() })
+				.unwrap_or_default();
+			let output_type = self.resolved_types.outputs.get(&Source { node: node_path.clone(), index: 0 }).map(|ty| ty.to_string());
+
+			FrontendNodeInspection {
+				node_path: node_path.clone(),
+				name,
+				output_type,
+				details: self.inspection.as_ref().map(inspection_details).unwrap_or_default(),
+			}
+		});
+		responses.add(FrontendMessage::UpdateNodeInspection { inspection });
+	}
+
+	/// The search field and its results, followed by the button which hides the node graph
+	fn right_side_widgets(&self) -> Vec<WidgetHolder> {
+		let mut widgets = vec![TextInput::new(&self.graph_search_query)
+			.min_width(160)
+			.tooltip("Find nodes by their name, output type, or input values")
+			.on_update(|text_input: &TextInput| NodeGraphMessage::SearchGraph { query: text_input.value.clone() }.into())
+			.widget_holder()];
+
+		if !self.graph_search_query.trim().is_empty() {
+			let entries = self
+				.graph_search_results
+				.iter()
+				.map(|result| {
+					let node_path = result.node_path.clone();
+					MenuListEntry::new(format!("{:?}", result.node_path))
+						.label(format!("{} ({})", result.name, result.found_by))
+						.on_update(move |_| NodeGraphMessage::NavigateToNode { node_path: node_path.clone() }.into())
+				})
+				.collect::<Vec<_>>();
+			let no_results = entries.is_empty();
+
+			widgets.extend([
+				Separator::new(SeparatorType::Related).widget_holder(),
+				DropdownInput::new(vec![entries])
+					.selected_index(None)
+					.disabled(no_results)
+					.tooltip(if no_results { "No nodes were found" } else { "Show the found node in the graph" })
+					.widget_holder(),
+			]);
+		}
+
+		widgets.extend([
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			// TODO: Replace this with an "Add Node" button, also next to an "Add Layer" button
+			TextLabel::new("Right Click in Graph to Add Nodes").italic(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextButton::new("Node Graph")
+				.icon(Some("GraphViewOpen".into()))
+				.hover_icon(Some("GraphViewClosed".into()))
+				.tooltip("Hide Node Graph")
+				.tooltip_shortcut(action_keys!(DocumentMessageDiscriminant::GraphViewOverlayToggle))
+				.on_update(move |_| DocumentMessage::GraphViewOverlayToggle.into())
+				.widget_holder(),
+		]);
+		widgets
+	}
+
 	/// Updates the buttons for visibility, locked, and preview
 	fn update_selection_action_buttons(&mut self, document_network: &NodeNetwork, selected_nodes: &SelectedNodes, responses: &mut VecDeque<Message>) {
 		if let Some(current_network) = document_network.nested_network_for_selected_nodes(&self.network, selected_nodes.selected_nodes_ref().iter()) {
@@ -2140,6 +2564,54 @@ impl NodeGraphMessageHandler {
 				widgets.push(hide_button);
 
 				widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+
+				// Layers belong to the layer tree of the document network, so only groups of plain nodes can be made into a custom node
+				let any_layers = selected_nodes.selected_nodes(network).any(|id| network.nodes.get(id).is_some_and(|node| node.is_layer));
+				if !any_layers {
+					let custom_node_button = TextButton::new("Make Custom Node")
+						.icon(Some("NodeNodes".to_string()))
+						.tooltip("Replace the selected nodes with a reusable custom node, which is added to the node menu")
+						.on_update(move |_| NodeGraphMessage::CreateCustomNodeFromSelection.into())
+						.widget_holder();
+					widgets.push(custom_node_button);
+
+					widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+				}
+
+				let comment_frame_button = TextButton::new("Add Frame")
+					.icon(Some("Artboard".to_string()))
+					.tooltip("Group the selected nodes in a comment frame, which moves them along when dragged by its title")
+					.on_update(move |_| NodeGraphMessage::AddCommentFrame.into())
+					.widget_holder();
+				widgets.push(comment_frame_button);
+
+				widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+			}
+
+			// If a comment frame is selected then show its title and color
+			let selected_comment_frame = self
+				.selected_comment_frame
+				.and_then(|id| document_network.nested_network(&self.network)?.comment_frames.iter().find(|frame| frame.id == id));
+			if let Some(frame) = selected_comment_frame {
+				let id = frame.id;
+				widgets.extend([
+					TextInput::new(frame.title.clone())
+						.tooltip("Comment frame title")
+						.on_update(move |text_input: &TextInput| NodeGraphMessage::SetCommentFrameTitle { id, title: text_input.value.clone() }.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					ColorButton::new(FillChoice::Solid(frame.color))
+						.allow_none(false)
+						.tooltip("Comment frame color")
+						.on_update(move |color_button: &ColorButton| color_button.value.as_solid().map_or(Message::NoOp, |color| NodeGraphMessage::SetCommentFrameColor { id, color }.into()))
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					IconButton::new("Trash", 24)
+						.tooltip("Delete comment frame")
+						.on_update(move |_| NodeGraphMessage::DeleteCommentFrame { id }.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+				]);
 			}
 
 			let mut selection = selected_nodes.selected_nodes(network);
@@ -2158,9 +2630,29 @@ impl NodeGraphMessageHandler {
 						.on_update(move |_| NodeGraphMessage::TogglePreview { node_id }.into())
 						.widget_holder();
 					widgets.push(output_button);
+
+					widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+
+					let is_inspected = self.inspected_node.as_ref().is_some_and(|node_path| node_path.split_last() == Some((&node_id, &self.network)));
+					let inspect_button = TextButton::new(if is_inspected { "End Inspect" } else { "Inspect" })
+						.icon(Some("ZoomIn".to_string()))
+						.tooltip(if is_inspected {
+							"Stop describing the output of the selected node"
+						} else {
+							"Describe the output of the selected node each time the graph is evaluated"
+						})
+						.on_update(move |_| NodeGraphMessage::ToggleInspection { node_id }.into())
+						.widget_holder();
+					widgets.push(inspect_button);
 				}
 			}
 
+			// Summarize what the inspected node output, wherever it is in the graph
+			if let Some(inspection) = &self.inspection {
+				let summary = inspection_details(inspection).iter().map(|(label, value)| format!("{label}: {value}")).collect::<Vec<_>>().join(", ");
+				widgets.extend([Separator::new(SeparatorType::Unrelated).widget_holder(), TextLabel::new(summary).italic(true).widget_holder()]);
+			}
+
 			self.widgets[0] = LayoutGroup::Row { widgets };
 		}
 		self.send_node_bar_layout(responses);
@@ -2286,6 +2778,30 @@ impl NodeGraphMessageHandler {
 		wires
 	}
 
+	/// Describes a type error of a node in terms of the graph the user sees, naming the node, its input, and what that input is connected to.
+	fn describe_graph_error(error: &GraphErrorType, node: &DocumentNode, network: &NodeNetwork, input_names: &[String]) -> String {
+		let GraphErrorType::MismatchedConnection { index, expected, found } = error else {
+			return format!("{error:?}");
+		};
+		let Some(input) = node.inputs.get(*index) else {
+			return format!("{error:?}");
+		};
+
+		let input_name = input_names.get(*index).cloned().unwrap_or_else(|| GraphErrorType::describe_input(*index));
+		let source = match input {
+			NodeInput::Node { node_id, .. } => match network.nodes.get(node_id) {
+				Some(upstream_node) if !upstream_node.alias.is_empty() => format!("the connected \"{}\" node", upstream_node.alias),
+				Some(upstream_node) => format!("the connected \"{}\" node", upstream_node.name),
+				None => "its connection".to_string(),
+			},
+			NodeInput::Network { import_index, .. } => format!("import {import_index} of this graph"),
+			_ => "its value".to_string(),
+		};
+		// Parameters are passed as nodes, so their types are shown as the types those nodes output
+		let (expected, found) = (expected.clone().nested_type(), found.clone().nested_type());
+		format!("The \"{input_name}\" input of \"{}\" expects {expected}, but {source} provides {found}", node.name)
+	}
+
 	fn collect_nodes(&self, document_network: &NodeNetwork, network: &NodeNetwork, wires: &[FrontendNodeWire]) -> Vec<FrontendNode> {
 		let connected_node_to_output_lookup = wires
 			.iter()
@@ -2328,6 +2844,8 @@ impl NodeGraphMessageHandler {
 						None
 					}
 				});
+				// Nodes without a definition, like custom nodes, take the names of the nested node inputs that their network's imports lead to
+				let definition_name = definition_name.or_else(|| node.implementation.get_network().and_then(|network| subgraph_import_name(network, index)));
 
 				let input_name = definition_name.unwrap_or(
 					ModifyInputsContext::get_input_type(document_network, &self.network, node_id, &self.resolved_types, index)
@@ -2342,6 +2860,8 @@ impl NodeGraphMessageHandler {
 					connected: None,
 				}
 			});
+			let frontend_graph_inputs = frontend_graph_inputs.collect::<Vec<_>>();
+			let input_names = frontend_graph_inputs.iter().map(|input| input.name.clone()).collect::<Vec<_>>();
 
 			let mut inputs = node.inputs.iter().zip(frontend_graph_inputs).map(|(node_input, mut frontend_graph_input)| {
 				if let NodeInput::Node { node_id: connected_node_id, .. } = node_input {
@@ -2404,6 +2924,7 @@ impl NodeGraphMessageHandler {
 						// If a node has multiple outputs, node and definition must have Network implementations
 						node_definition.outputs.get(index).map(|output| output.name.to_string())
 					})
+					.or_else(|| node.implementation.get_network().and_then(|network| subgraph_export_name(network, index)))
 					.unwrap_or(format!("Output {}", index + 1));
 
 				let (connected, connected_index) = connected_node_to_output_lookup.get(&(node_id, index)).unwrap_or(&(Vec::new(), Vec::new())).clone();
@@ -2422,8 +2943,9 @@ impl NodeGraphMessageHandler {
 			let errors = self
 				.node_graph_errors
 				.iter()
-				.find(|error| error.node_path == *node_id_path)
-				.map(|error| format!("{:?}", error.error.clone()))
+				.filter(|error| error.node_path == *node_id_path)
+				.map(|error| Self::describe_graph_error(&error.error, node, network, &input_names))
+				.reduce(|errors, error| format!("{errors}\n{error}"))
 				.or_else(|| {
 					if self.node_graph_errors.iter().any(|error| error.node_path.starts_with(node_id_path)) {
 						Some("Node graph type error within this node".to_string())
@@ -2448,6 +2970,7 @@ impl NodeGraphMessageHandler {
 				locked: node.locked,
 				errors: errors,
 				ui_only: false,
+				is_reroute: Self::is_reroute(node),
 			});
 		}
 
@@ -2479,7 +3002,7 @@ impl NodeGraphMessageHandler {
 					}
 				});
 
-				import_names.push(definition_name);
+				import_names.push(definition_name.or_else(|| subgraph_import_name(network, index)));
 			}
 
 			// Get all export names from definition
@@ -2493,7 +3016,7 @@ impl NodeGraphMessageHandler {
 						None
 					}
 				});
-				export_names[index] = definition_name;
+				export_names[index] = definition_name.or_else(|| subgraph_export_name(network, index));
 			}
 		}
 
@@ -2568,6 +3091,7 @@ impl NodeGraphMessageHandler {
 			locked: false,
 			errors,
 			ui_only: true,
+			is_reroute: false,
 		});
 
 		// Add "Import" UI-only node
@@ -2613,6 +3137,7 @@ impl NodeGraphMessageHandler {
 				locked: false,
 				errors: None,
 				ui_only: true,
+				is_reroute: false,
 			});
 		}
 		nodes
@@ -2684,6 +3209,7 @@ impl NodeGraphMessageHandler {
 					parents_visible,
 					unlocked: !node.locked,
 					parents_unlocked,
+					text_overflows: metadata.text_overflows(layer),
 				};
 				responses.add(FrontendMessage::UpdateDocumentLayerDetails { data });
 			}
@@ -2713,8 +3239,9 @@ impl NodeGraphMessageHandler {
 		if graph_open {
 			let wires = Self::collect_wires(network);
 			let nodes = self.collect_nodes(document_network, network, &wires);
+			let frames = self.collect_comment_frames(network);
 
-			responses.add(FrontendMessage::UpdateNodeGraph { nodes, wires });
+			responses.add(FrontendMessage::UpdateNodeGraph { nodes, wires, frames });
 			responses.add(FrontendMessage::UpdateSubgraphPath { subgraph_path: nested_path });
 			let layer_widths = self
 				.node_metadata
@@ -2732,6 +3259,14 @@ impl NodeGraphMessageHandler {
 				})
 				.collect::<HashMap<NodeId, u32>>();
 			responses.add(FrontendMessage::UpdateLayerWidths { layer_widths });
+
+			let thumbnails = self
+				.node_output_thumbnails
+				.iter()
+				.filter(|(node_path, _)| node_path.starts_with(&self.network) && node_path.len() == self.network.len() + 1)
+				.map(|(node_path, thumbnail)| (*node_path.last().unwrap(), thumbnail.clone()))
+				.collect();
+			responses.add(FrontendMessage::UpdateNodeOutputThumbnails { thumbnails });
 		}
 	}
 
@@ -2830,6 +3365,9 @@ impl NodeGraphMessageHandler {
 		}
 
 		let Some(node) = document_network.nodes.get(&node_id) else { return false };
+		if Self::is_reroute(node) {
+			return false;
+		}
 
 		let exposed_value_count = node.inputs.iter().filter(|input| if let NodeInput::Value { exposed, .. } = input { *exposed } else { false }).count();
 		let node_input_count = node
@@ -2850,6 +3388,73 @@ impl NodeGraphMessageHandler {
 		node.has_primary_output && output_count == 1 && (input_count == 1 || input_count == 2)
 	}
 
+	/// The topmost comment frame whose title bar or bottom right corner is at the point in node graph space, and whether it's the corner used to resize the frame.
+	fn comment_frame_at_point(network: &NodeNetwork, point: DVec2) -> Option<(&CommentFrame, bool)> {
+		network.comment_frames.iter().rev().find_map(|frame| {
+			let top_left = frame.position.as_dvec2() * 24.;
+			let bottom_right = (frame.position + frame.size).as_dvec2() * 24.;
+			let within_frame = point.cmpge(top_left).all() && point.cmple(bottom_right).all();
+			let resizing = within_frame && (bottom_right - point).cmple(DVec2::splat(12.)).all();
+			let in_title_bar = within_frame && point.y <= top_left.y + 24.;
+			(resizing || in_title_bar).then_some((frame, resizing))
+		})
+	}
+
+	/// Undoably changes the comment frames of the network being viewed.
+	fn modify_comment_frames(&self, document_network: &NodeNetwork, responses: &mut VecDeque<Message>, modify: impl FnOnce(&mut Vec<CommentFrame>)) {
+		let Some(network) = document_network.nested_network(&self.network) else {
+			return;
+		};
+		let mut frames = network.comment_frames.clone();
+		modify(&mut frames);
+		if frames != network.comment_frames {
+			responses.add(DocumentMessage::StartTransaction);
+			responses.add(NodeGraphMessage::SetCommentFrames { frames });
+		}
+	}
+
+	fn collect_comment_frames(&self, network: &NodeNetwork) -> Vec<FrontendCommentFrame> {
+		network
+			.comment_frames
+			.iter()
+			.map(|frame| FrontendCommentFrame {
+				id: frame.id,
+				title: frame.title.clone(),
+				color: "#".to_string() + &frame.color.rgba_hex(),
+				position: frame.position.into(),
+				size: frame.size.into(),
+				selected: self.selected_comment_frame == Some(frame.id),
+			})
+			.collect()
+	}
+
+	/// Sends the nodes, wires, and comment frames of the network being viewed, for changes which only move things around in the graph.
+	fn send_node_graph_update(&self, document_network: &NodeNetwork, graph_open: bool, responses: &mut VecDeque<Message>) {
+		let Some(network) = document_network.nested_network(&self.network) else {
+			return;
+		};
+		if graph_open {
+			let wires = Self::collect_wires(network);
+			let nodes = self.collect_nodes(document_network, network, &wires);
+			let frames = self.collect_comment_frames(network);
+			responses.add(FrontendMessage::UpdateNodeGraph { nodes, wires, frames });
+		}
+	}
+
+	/// Whether the node is a reroute node, which is drawn as a dot instead of a full node.
+	pub fn is_reroute(node: &DocumentNode) -> bool {
+		!node.is_layer && node.name == "Reroute"
+	}
+
+	/// The width in pixels of a node which isn't a layer.
+	fn node_width(node: &DocumentNode) -> f64 {
+		if Self::is_reroute(node) {
+			24.
+		} else {
+			5. * 24.
+		}
+	}
+
 	fn untitled_layer_label(node: &DocumentNode) -> String {
 		(node.alias != "")
 			.then_some(node.alias.to_string())
@@ -2942,27 +3547,43 @@ impl NodeGraphMessageHandler {
 	}
 }
 
+/// Labeled values describing what a node output, such as the number of points in a path or the size of an image
+fn inspection_details(inspection: &InspectedValue) -> Vec<(String, String)> {
+	let detail = |label: &str, value: String| (label.to_string(), value);
+	match inspection {
+		InspectedValue::Number(number) => vec![detail("Value", number.to_string())],
+		InspectedValue::Bool(boolean) => vec![detail("Value", boolean.to_string())],
+		InspectedValue::Text(text) => vec![detail("Value", format!("{text:?}"))],
+		InspectedValue::Color(color) => vec![detail("Color", format!("#{}", color.rgba_hex()))],
+		InspectedValue::Vector(vector) => vec![detail("X", vector.x.to_string()), detail("Y", vector.y.to_string())],
+		InspectedValue::VectorData { points, segments, subpaths, bounds } => {
+			let bounds = bounds.map_or_else(|| "None".to_string(), |[min, max]| format!("({:.2}, {:.2}) to ({:.2}, {:.2})", min.x, min.y, max.x, max.y));
+			vec![
+				detail("Points", points.to_string()),
+				detail("Segments", segments.to_string()),
+				detail("Subpaths", subpaths.to_string()),
+				detail("Bounds", bounds),
+			]
+		}
+		InspectedValue::Image { width, height } => vec![detail("Width", format!("{width} px")), detail("Height", format!("{height} px"))],
+		InspectedValue::GraphicGroup { elements } => vec![detail("Elements", elements.to_string())],
+		InspectedValue::Artboard { location, dimensions, elements } => vec![
+			detail("Location", format!("({}, {})", location.x, location.y)),
+			detail("Dimensions", format!("{} × {}", dimensions.x, dimensions.y)),
+			detail("Elements", elements.to_string()),
+		],
+		InspectedValue::Opaque => vec![detail("Value", "Not viewable".to_string())],
+	}
+}
+
 impl Default for NodeGraphMessageHandler {
 	fn default() -> Self {
-		let right_side_widgets = vec![
-			// TODO: Replace this with an "Add Node" button, also next to an "Add Layer" button
-			TextLabel::new("Right Click in Graph to Add Nodes").italic(true).widget_holder(),
-			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			TextButton::new("Node Graph")
-				.icon(Some("GraphViewOpen".into()))
-				.hover_icon(Some("GraphViewClosed".into()))
-				.tooltip("Hide Node Graph")
-				.tooltip_shortcut(action_keys!(DocumentMessageDiscriminant::GraphViewOverlayToggle))
-				.on_update(move |_| DocumentMessage::GraphViewOverlayToggle.into())
-				.widget_holder(),
-		];
-
-		Self {
+		let mut node_graph = Self {
 			network: Vec::new(),
 			resolved_types: ResolvedDocumentNodeTypes::default(),
 			node_graph_errors: Vec::new(),
 			has_selection: false,
-			widgets: [LayoutGroup::Row { widgets: Vec::new() }, LayoutGroup::Row { widgets: right_side_widgets }],
+			widgets: [LayoutGroup::Row { widgets: Vec::new() }, LayoutGroup::Row { widgets: Vec::new() }],
 			drag_start: None,
 			begin_dragging: false,
 			box_selection_start: None,
@@ -2972,9 +3593,20 @@ impl Default for NodeGraphMessageHandler {
 			wire_in_progress_from_connector: None,
 			wire_in_progress_to_connector: None,
 			context_menu: None,
+			selected_comment_frame: None,
+			comment_frame_drag: None,
+			graph_search_query: String::new(),
+			graph_search_results: Vec::new(),
+			node_output_thumbnails: HashMap::new(),
+			inspected_node: None,
+			inspection: None,
 			node_metadata: HashMap::new(),
 			network_metadata: HashMap::new(),
-		}
+		};
+		node_graph.widgets[1] = LayoutGroup::Row {
+			widgets: node_graph.right_side_widgets(),
+		};
+		node_graph
 	}
 }
 
@@ -2994,5 +3626,12 @@ impl PartialEq for NodeGraphMessageHandler {
 			&& self.wire_in_progress_from_connector == other.wire_in_progress_from_connector
 			&& self.wire_in_progress_to_connector == other.wire_in_progress_to_connector
 			&& self.context_menu == other.context_menu
+			&& self.selected_comment_frame == other.selected_comment_frame
+			&& self.comment_frame_drag == other.comment_frame_drag
+			&& self.graph_search_query == other.graph_search_query
+			&& self.graph_search_results == other.graph_search_results
+			&& self.node_output_thumbnails == other.node_output_thumbnails
+			&& self.inspected_node == other.inspected_node
+			&& self.inspection == other.inspection
 	}
 }
@@ -3,19 +3,31 @@
 use super::document_node_types::{NodePropertiesContext, IMAGINATE_NODE};
 use super::utility_types::FrontendGraphDataType;
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::properties_panel::utility_types::TextSelection;
+use crate::messages::portfolio::document::utility_types::custom_nodes::subgraph_import_name;
+use crate::messages::portfolio::document::utility_types::parameters::DocumentParameters;
+use crate::messages::portfolio::document::utility_types::swatches::DocumentSwatches;
+use crate::messages::portfolio::document::utility_types::variables::{DocumentVariables, VariableBinding};
 use crate::messages::prelude::*;
 
 use graph_craft::document::value::TaggedValue;
-use graph_craft::document::{DocumentNode, NodeId, NodeInput};
+use graph_craft::document::{DocumentNode, DocumentNodeImplementation, NodeId, NodeInput, NodeNetwork};
 use graph_craft::imaginate_input::{ImaginateSamplingMethod, ImaginateServerStatus, ImaginateStatus};
 use graphene_core::memo::IORecord;
+use graphene_core::oscillator::Waveform;
+use graphene_core::projection::ProjectionFace;
 use graphene_core::raster::{
-	BlendMode, CellularDistanceFunction, CellularReturnType, Color, DomainWarpType, FractalType, ImageFrame, LuminanceCalculation, NoiseType, RedGreenBlue, RedGreenBlueAlpha, RelativeAbsolute,
-	SelectiveColorChoice,
+	BlendMode, BorderMode, CellularDistanceFunction, CellularReturnType, ChannelSource, Color, ColorHarmony, ConvolutionPreset, DomainWarpType, FractalType, ImageFrame, LuminanceCalculation,
+	MorphologyChannel, MorphologyOperation, MorphologyShape, NoiseType, PixelDiffMode, RedGreenBlue, RedGreenBlueAlpha, RelativeAbsolute, SelectiveColorChoice,
 };
-use graphene_core::text::Font;
-use graphene_core::vector::misc::CentroidType;
-use graphene_core::vector::style::{GradientType, LineCap, LineJoin};
+use graphene_core::text::{font_axes, load_face, Font, FontCache, KerningMode, TextAlign, TextAutoResize, TextKerning, TextStyle};
+use graphene_core::vector::barcode::{BarcodeSymbology, QrErrorCorrection};
+use graphene_core::vector::misc::{ArcType, CentroidType};
+use graphene_core::vector::point_cloud::{PointRegion, PointSampling};
+use graphene_core::vector::style::{Gradient, GradientInterpolation, GradientType, HueInterpolation, LineCap, LineJoin, StrokeAlign};
+use graphene_core::vector::voronoi::PointDistribution;
+use graphene_core::warp::WarpMode;
+use graphene_core::{CellFit, DimensionKind, MaskMode};
 use graphene_std::vector::style::{Fill, FillChoice};
 
 use glam::{DVec2, IVec2, UVec2};
@@ -137,7 +149,25 @@ fn bool_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name
 	widgets
 }
 
-fn vec2_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, x: &str, y: &str, unit: &str, min: Option<f64>, mut assist: impl FnMut(&mut Vec<WidgetHolder>)) -> LayoutGroup {
+fn vec2_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, x: &str, y: &str, unit: &str, min: Option<f64>, assist: impl FnMut(&mut Vec<WidgetHolder>)) -> LayoutGroup {
+	vec2_widget_with_origin(document_node, node_id, index, name, x, y, unit, min, DVec2::ZERO, 1., assist)
+}
+
+/// Like [`vec2_widget`], but a `DVec2` value is displayed and entered relative to the given origin, while still being stored relative to zero.
+/// It's also displayed and entered in a unit of length with the given number of pixels, while still being stored in pixels.
+fn vec2_widget_with_origin(
+	document_node: &DocumentNode,
+	node_id: NodeId,
+	index: usize,
+	name: &str,
+	x: &str,
+	y: &str,
+	unit: &str,
+	min: Option<f64>,
+	origin: DVec2,
+	pixels_per_unit: f64,
+	mut assist: impl FnMut(&mut Vec<WidgetHolder>),
+) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::Number, false);
 
 	assist(&mut widgets);
@@ -149,21 +179,29 @@ fn vec2_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name
 	{
 		widgets.extend_from_slice(&[
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			NumberInput::new(Some(dvec2.x))
+			NumberInput::new(Some((dvec2.x - origin.x) / pixels_per_unit))
 				.label(x)
 				.unit(unit)
 				.min(min.unwrap_or(-((1_u64 << std::f64::MANTISSA_DIGITS) as f64)))
 				.max((1_u64 << std::f64::MANTISSA_DIGITS) as f64)
-				.on_update(update_value(move |input: &NumberInput| TaggedValue::DVec2(DVec2::new(input.value.unwrap(), dvec2.y)), node_id, index))
+				.on_update(update_value(
+					move |input: &NumberInput| TaggedValue::DVec2(DVec2::new(input.value.unwrap() * pixels_per_unit + origin.x, dvec2.y)),
+					node_id,
+					index,
+				))
 				.on_commit(commit_value)
 				.widget_holder(),
 			Separator::new(SeparatorType::Related).widget_holder(),
-			NumberInput::new(Some(dvec2.y))
+			NumberInput::new(Some((dvec2.y - origin.y) / pixels_per_unit))
 				.label(y)
 				.unit(unit)
 				.min(min.unwrap_or(-((1_u64 << std::f64::MANTISSA_DIGITS) as f64)))
 				.max((1_u64 << std::f64::MANTISSA_DIGITS) as f64)
-				.on_update(update_value(move |input: &NumberInput| TaggedValue::DVec2(DVec2::new(dvec2.x, input.value.unwrap())), node_id, index))
+				.on_update(update_value(
+					move |input: &NumberInput| TaggedValue::DVec2(DVec2::new(dvec2.x, input.value.unwrap() * pixels_per_unit + origin.y)),
+					node_id,
+					index,
+				))
 				.on_commit(commit_value)
 				.widget_holder(),
 		]);
@@ -445,6 +483,56 @@ fn noise_type(document_node: &DocumentNode, node_id: NodeId, index: usize, name:
 	LayoutGroup::Row { widgets }.with_tooltip("Style of noise pattern")
 }
 
+fn waveform(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::Waveform(waveform),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = Waveform::list()
+			.iter()
+			.map(|waveform| {
+				MenuListEntry::new(format!("{waveform:?}"))
+					.label(waveform.to_string())
+					.on_update(update_value(move |_| TaggedValue::Waveform(*waveform), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(waveform as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("The shape of the wave the value follows over time")
+}
+
+fn color_harmony(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::ColorHarmony(harmony),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = ColorHarmony::list()
+			.iter()
+			.map(|harmony| {
+				MenuListEntry::new(format!("{harmony:?}"))
+					.label(harmony.to_string())
+					.on_update(update_value(move |_| TaggedValue::ColorHarmony(*harmony), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(harmony as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Which hues around the color wheel are picked to go with the color")
+}
+
 // TODO: Generalize this instead of using a separate function per dropdown menu enum
 fn fractal_type(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool, disabled: bool) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
@@ -560,7 +648,7 @@ fn blend_mode(document_node: &DocumentNode, node_id: NodeId, index: usize, name:
 		exposed: false,
 	} = &document_node.inputs[index]
 	{
-		let entries = BlendMode::list_svg_subset()
+		let entries = BlendMode::list()
 			.iter()
 			.map(|category| {
 				category
@@ -577,9 +665,7 @@ fn blend_mode(document_node: &DocumentNode, node_id: NodeId, index: usize, name:
 
 		widgets.extend_from_slice(&[
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			DropdownInput::new(entries)
-				.selected_index(blend_mode.index_in_list_svg_subset().map(|index| index as u32))
-				.widget_holder(),
+			DropdownInput::new(entries).selected_index(blend_mode.index_in_list().map(|index| index as u32)).widget_holder(),
 		]);
 	}
 	LayoutGroup::Row { widgets }.with_tooltip("Formula used for blending")
@@ -693,6 +779,586 @@ fn line_join_widget(document_node: &DocumentNode, node_id: NodeId, index: usize,
 	LayoutGroup::Row { widgets }
 }
 
+fn stroke_align_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::StrokeAlign(stroke_align),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = [("Center", StrokeAlign::Center), ("Inside", StrokeAlign::Inside), ("Outside", StrokeAlign::Outside)]
+			.into_iter()
+			.map(|(name, val)| {
+				RadioEntryData::new(format!("{val:?}"))
+					.label(name)
+					.on_update(update_value(move |_| TaggedValue::StrokeAlign(val), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(stroke_align as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Where a closed path's stroke is placed relative to the path (open paths are always stroked along their center)")
+}
+
+fn cell_fit_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::CellFit(cell_fit),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = CellFit::list()
+			.into_iter()
+			.map(|fit| {
+				RadioEntryData::new(format!("{fit:?}"))
+					.label(fit.to_string())
+					.on_update(update_value(move |_| TaggedValue::CellFit(fit), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(cell_fit as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("How each element is scaled to fit its cell")
+}
+
+fn dimension_kind_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::DimensionKind(dimension_kind),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = DimensionKind::list()
+			.into_iter()
+			.map(|kind| {
+				RadioEntryData::new(format!("{kind:?}"))
+					.label(kind.to_string())
+					.on_update(update_value(move |_| TaggedValue::DimensionKind(kind), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(dimension_kind as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Whether the length of the line between the points or its angle is measured")
+}
+
+fn mask_mode_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::MaskMode(mask_mode),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = MaskMode::list()
+			.into_iter()
+			.map(|mode| {
+				RadioEntryData::new(format!("{mode:?}"))
+					.label(mode.to_string())
+					.on_update(update_value(move |_| TaggedValue::MaskMode(mode), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(mask_mode as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Whether the content is shown where the mask is opaque or where it's bright")
+}
+
+fn pixel_diff_mode_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::PixelDiffMode(pixel_diff_mode),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = PixelDiffMode::list()
+			.into_iter()
+			.map(|mode| {
+				RadioEntryData::new(format!("{mode:?}"))
+					.label(mode.to_string())
+					.on_update(update_value(move |_| TaggedValue::PixelDiffMode(mode), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(pixel_diff_mode as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("How the differences from the reference are visualized")
+}
+
+fn convolution_preset_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::ConvolutionPreset(convolution_preset),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = ConvolutionPreset::list()
+			.into_iter()
+			.map(|preset| {
+				MenuListEntry::new(format!("{preset:?}"))
+					.label(preset.to_string())
+					.on_update(update_value(move |_| TaggedValue::ConvolutionPreset(preset), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(convolution_preset as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("The standard kernel the image is convolved with")
+}
+
+fn border_mode_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::BorderMode(border_mode),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = BorderMode::list()
+			.into_iter()
+			.map(|mode| {
+				MenuListEntry::new(format!("{mode:?}"))
+					.label(mode.to_string())
+					.on_update(update_value(move |_| TaggedValue::BorderMode(mode), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(border_mode as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("What the kernel reads beyond the edges of the image")
+}
+
+fn morphology_operation_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::MorphologyOperation(operation),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = MorphologyOperation::list()
+			.into_iter()
+			.map(|value| {
+				RadioEntryData::new(format!("{value:?}"))
+					.label(value.to_string())
+					.on_update(update_value(move |_| TaggedValue::MorphologyOperation(value), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(operation as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("How the opaque or bright areas of the image are reshaped")
+}
+
+fn morphology_channel_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::MorphologyChannel(channel),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = MorphologyChannel::list()
+			.into_iter()
+			.map(|value| {
+				RadioEntryData::new(format!("{value:?}"))
+					.label(value.to_string())
+					.on_update(update_value(move |_| TaggedValue::MorphologyChannel(value), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(channel as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("The channel pixels are compared by, taking the whole color of the highest or lowest pixel")
+}
+
+fn morphology_shape_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::MorphologyShape(shape),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = MorphologyShape::list()
+			.into_iter()
+			.map(|value| {
+				RadioEntryData::new(format!("{value:?}"))
+					.label(value.to_string())
+					.on_update(update_value(move |_| TaggedValue::MorphologyShape(value), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(shape as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("The shape of the neighborhood, which rounds or squares off the corners that grow and shrink")
+}
+
+fn channel_source_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::ChannelSource(channel_source),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = ChannelSource::list()
+			.iter()
+			.map(|category| {
+				category
+					.iter()
+					.map(|source| {
+						MenuListEntry::new(format!("{source:?}"))
+							.label(source.to_string())
+							.on_update(update_value(move |_| TaggedValue::ChannelSource(*source), node_id, index))
+							.on_commit(commit_value)
+					})
+					.collect()
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(entries).selected_index(Some(channel_source as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("The channel of the image or the other image, or the constant, this channel is made from")
+}
+
+fn text_align_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::TextAlign(text_align),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = TextAlign::list()
+			.into_iter()
+			.map(|align| {
+				RadioEntryData::new(format!("{align:?}"))
+					.label(align.to_string())
+					.on_update(update_value(move |_| TaggedValue::TextAlign(align), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(text_align as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("How the lines of each paragraph are placed across the width of the text, where Start is the side the paragraph's script starts from")
+}
+
+fn text_auto_resize_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::TextAutoResize(auto_resize),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = TextAutoResize::list()
+			.into_iter()
+			.map(|mode| {
+				RadioEntryData::new(format!("{mode:?}"))
+					.label(mode.to_string())
+					.on_update(update_value(move |_| TaggedValue::TextAutoResize(mode), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(auto_resize as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("What happens when the text doesn't fit the height of its text box: its last lines are left out, the box grows to fit them, or the text shrinks to fit")
+}
+
+fn font_variations_widgets(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, font_cache: &FontCache, blank_assist: bool) -> Vec<LayoutGroup> {
+	let mut first_widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let NodeInput::Value {
+		tagged_value: TaggedValue::FontVariations(variations),
+		exposed: false,
+	} = &document_node.inputs[index]
+	else {
+		return vec![LayoutGroup::Row { widgets: first_widgets }];
+	};
+
+	let axes = match document_node.inputs.get(2).and_then(|input| input.as_value()) {
+		Some(TaggedValue::Font(font)) => font_cache.get(font).and_then(|data| load_face(data)).map(|buzz_face| font_axes(&buzz_face)).unwrap_or_default(),
+		_ => Vec::new(),
+	};
+	if axes.is_empty() {
+		first_widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Not a variable font").italic(true).widget_holder(),
+		]);
+		return vec![LayoutGroup::Row { widgets: first_widgets }];
+	}
+
+	// Each axis of the font gets its own row, with the rows after the first lined up beneath it
+	let mut rows = Vec::with_capacity(axes.len());
+	for (axis_index, axis) in axes.into_iter().enumerate() {
+		let mut widgets = if axis_index == 0 {
+			std::mem::take(&mut first_widgets)
+		} else {
+			let mut widgets = vec![TextLabel::new("").widget_holder()];
+			add_blank_assist(&mut widgets);
+			widgets
+		};
+
+		let value = variations.get(&axis.tag).unwrap_or(axis.default);
+		let updated_variations = variations.clone();
+		let tag = axis.tag.clone();
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(value))
+				.label(axis.name.clone())
+				.mode_range()
+				.min(axis.min)
+				.max(axis.max)
+				.range_min(Some(axis.min))
+				.range_max(Some(axis.max))
+				.on_update(update_value(
+					move |x: &NumberInput| TaggedValue::FontVariations(updated_variations.with(&tag, x.value.unwrap())),
+					node_id,
+					index,
+				))
+				.on_commit(commit_value)
+				.widget_holder(),
+		]);
+		rows.push(LayoutGroup::Row { widgets }.with_tooltip(format!("Position of the font along its \"{}\" axis of variation", axis.tag)));
+	}
+	rows
+}
+
+fn text_runs_widgets(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, selection: Option<&TextSelection>, blank_assist: bool) -> Vec<LayoutGroup> {
+	let mut first_widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let NodeInput::Value {
+		tagged_value: TaggedValue::TextRuns(runs),
+		exposed: false,
+	} = &document_node.inputs[index]
+	else {
+		return vec![LayoutGroup::Row { widgets: first_widgets }];
+	};
+
+	let text_length = match document_node.inputs.get(1).and_then(|input| input.as_value()) {
+		Some(TaggedValue::String(text)) => text.len(),
+		_ => 0,
+	};
+	let range = selection
+		.filter(|selection| selection.node_id == node_id && selection.start < selection.end && selection.end <= text_length)
+		.map(|selection| selection.start..selection.end);
+	let Some(range) = range else {
+		let description = match runs.0.len() {
+			0 => "Select text while editing it to style it".to_string(),
+			1 => "1 styled range".to_string(),
+			count => format!("{count} styled ranges"),
+		};
+		first_widgets.extend_from_slice(&[Separator::new(SeparatorType::Unrelated).widget_holder(), TextLabel::new(description).italic(true).widget_holder()]);
+		return vec![LayoutGroup::Row { widgets: first_widgets }];
+	};
+
+	// The properties not set for the whole selection show the values of the node, which the selection falls back to
+	let common = runs.common_style(range.clone());
+	let node_font = match document_node.inputs.get(2).and_then(|input| input.as_value()) {
+		Some(TaggedValue::Font(font)) => font.clone(),
+		_ => Font::new(graphene_core::consts::DEFAULT_FONT_FAMILY.into(), graphene_core::consts::DEFAULT_FONT_STYLE.into()),
+	};
+	let node_size = match document_node.inputs.get(3).and_then(|input| input.as_value()) {
+		Some(&TaggedValue::F64(size)) => size,
+		_ => 24.,
+	};
+	let node_weight = match document_node.inputs.get(9).and_then(|input| input.as_value()) {
+		Some(TaggedValue::FontVariations(variations)) => variations.get("wght").unwrap_or(400.),
+		_ => 400.,
+	};
+
+	let row = |label: &str, widget: WidgetHolder| {
+		let mut widgets = vec![TextLabel::new("").widget_holder()];
+		add_blank_assist(&mut widgets);
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new(label).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			widget,
+		]);
+		LayoutGroup::Row { widgets }
+	};
+
+	let font = common.font.clone().unwrap_or(node_font);
+	let (runs_for_font, range_for_font) = (runs.clone(), range.clone());
+	let font_widget = FontInput::new(font.font_family, font.font_style)
+		.on_update(update_value(
+			move |x: &FontInput| {
+				let mut runs = runs_for_font.clone();
+				let font = Font::new(x.font_family.clone(), x.font_style.clone());
+				runs.apply(
+					range_for_font.clone(),
+					&TextStyle {
+						font: Some(font),
+						..Default::default()
+					},
+				);
+				TaggedValue::TextRuns(runs)
+			},
+			node_id,
+			index,
+		))
+		.on_commit(commit_value)
+		.widget_holder();
+
+	let (runs_for_size, range_for_size) = (runs.clone(), range.clone());
+	let size_widget = NumberInput::new(Some(common.font_size.unwrap_or(node_size)))
+		.unit(" px")
+		.min(1.)
+		.on_update(update_value(
+			move |x: &NumberInput| {
+				let mut runs = runs_for_size.clone();
+				runs.apply(
+					range_for_size.clone(),
+					&TextStyle {
+						font_size: x.value,
+						..Default::default()
+					},
+				);
+				TaggedValue::TextRuns(runs)
+			},
+			node_id,
+			index,
+		))
+		.on_commit(commit_value)
+		.widget_holder();
+
+	let (runs_for_weight, range_for_weight) = (runs.clone(), range.clone());
+	let weight_widget = NumberInput::new(Some(common.weight.unwrap_or(node_weight)))
+		.mode_range()
+		.min(1.)
+		.max(1000.)
+		.range_min(Some(100.))
+		.range_max(Some(900.))
+		.on_update(update_value(
+			move |x: &NumberInput| {
+				let mut runs = runs_for_weight.clone();
+				runs.apply(
+					range_for_weight.clone(),
+					&TextStyle {
+						weight: x.value,
+						..Default::default()
+					},
+				);
+				TaggedValue::TextRuns(runs)
+			},
+			node_id,
+			index,
+		))
+		.on_commit(commit_value)
+		.widget_holder();
+
+	let (runs_for_tracking, range_for_tracking) = (runs.clone(), range.clone());
+	let tracking_widget = NumberInput::new(Some(common.tracking.unwrap_or_default()))
+		.on_update(update_value(
+			move |x: &NumberInput| {
+				let mut runs = runs_for_tracking.clone();
+				runs.apply(
+					range_for_tracking.clone(),
+					&TextStyle {
+						tracking: x.value,
+						..Default::default()
+					},
+				);
+				TaggedValue::TextRuns(runs)
+			},
+			node_id,
+			index,
+		))
+		.on_commit(commit_value)
+		.widget_holder();
+
+	let (runs_for_fill, range_for_fill) = (runs.clone(), range.clone());
+	let fill_widget = ColorButton::new(common.fill.map_or(FillChoice::None, FillChoice::Solid))
+		.allow_none(false)
+		.on_update(update_value(
+			move |x: &ColorButton| {
+				let mut runs = runs_for_fill.clone();
+				runs.apply(
+					range_for_fill.clone(),
+					&TextStyle {
+						fill: x.value.as_solid(),
+						..Default::default()
+					},
+				);
+				TaggedValue::TextRuns(runs)
+			},
+			node_id,
+			index,
+		))
+		.on_commit(commit_value)
+		.widget_holder();
+
+	let (runs_for_clear, range_for_clear) = (runs.clone(), range.clone());
+	let clear_widget = TextButton::new("Clear Style")
+		.on_update(update_value(
+			move |_: &TextButton| {
+				let mut runs = runs_for_clear.clone();
+				runs.clear(range_for_clear.clone());
+				TaggedValue::TextRuns(runs)
+			},
+			node_id,
+			index,
+		))
+		.widget_holder();
+
+	first_widgets.extend_from_slice(&[
+		Separator::new(SeparatorType::Unrelated).widget_holder(),
+		TextLabel::new(format!("Selected characters {} to {}", range.start, range.end)).italic(true).widget_holder(),
+	]);
+	vec![
+		LayoutGroup::Row { widgets: first_widgets },
+		row("Font", font_widget).with_tooltip("Font of the selected text"),
+		row("Size", size_widget).with_tooltip("Font size of the selected text"),
+		row("Weight", weight_widget).with_tooltip("Position along the weight axis of a variable font for the selected text, where 400 is regular and 700 is bold"),
+		row("Tracking", tracking_widget).with_tooltip("Space added after each selected character, in thousandths of the em"),
+		row("Fill", fill_widget).with_tooltip("Color that the selected text is filled with instead of the fill of the layer"),
+		row("", clear_widget).with_tooltip("Give the selected text the style of the rest of the text"),
+	]
+}
+
 fn color_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, color_props: ColorButton, blank_assist: bool) -> LayoutGroup {
 	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
 
@@ -734,60 +1400,274 @@ fn color_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, nam
 		),
 		_ => {}
 	}
-
-	LayoutGroup::Row { widgets }
+
+	LayoutGroup::Row { widgets }
+}
+
+fn curves_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+
+	if let NodeInput::Value {
+		tagged_value: TaggedValue::Curve(curve),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CurveInput::new(curve.clone())
+				.on_update(update_value(|x: &CurveInput| TaggedValue::Curve(x.value.clone()), node_id, index))
+				.on_commit(commit_value)
+				.widget_holder(),
+		])
+	}
+	LayoutGroup::Row { widgets }
+}
+
+fn centroid_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Centroid Type", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::CentroidType(centroid_type),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = vec![
+			RadioEntryData::new("area")
+				.label("Area")
+				.tooltip("Center of mass for the interior area of the shape")
+				.on_update(update_value(move |_| TaggedValue::CentroidType(CentroidType::Area), node_id, index))
+				.on_commit(commit_value),
+			RadioEntryData::new("length")
+				.label("Length")
+				.tooltip("Center of mass for the perimeter arc length of the shape")
+				.on_update(update_value(move |_| TaggedValue::CentroidType(CentroidType::Length), node_id, index))
+				.on_commit(commit_value),
+		];
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries)
+				.selected_index(match centroid_type {
+					CentroidType::Area => Some(0),
+					CentroidType::Length => Some(1),
+				})
+				.widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }
+}
+
+fn arc_type_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Arc Type", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::ArcType(arc_type),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = [
+			(ArcType::Open, "open", "Open", "Only the curve of the arc"),
+			(ArcType::Closed, "closed", "Closed", "The curve of the arc closed by a line between its ends"),
+			(ArcType::PieSlice, "pie-slice", "Pie Slice", "The curve of the arc closed by lines from its ends to its center"),
+		]
+		.into_iter()
+		.map(|(value, name, label, tooltip)| {
+			RadioEntryData::new(name)
+				.label(label)
+				.tooltip(tooltip)
+				.on_update(update_value(move |_| TaggedValue::ArcType(value), node_id, index))
+				.on_commit(commit_value)
+		})
+		.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(arc_type as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }
+}
+
+fn qr_error_correction_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Error Correction", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::QrErrorCorrection(error_correction),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = QrErrorCorrection::list()
+			.into_iter()
+			.map(|level| {
+				RadioEntryData::new(format!("{level:?}"))
+					.label(level.to_string())
+					.on_update(update_value(move |_| TaggedValue::QrErrorCorrection(level), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(error_correction as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("How much of the code can be damaged or covered while it still scans, with higher levels making a larger code")
+}
+
+fn barcode_symbology_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Symbology", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::BarcodeSymbology(symbology),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = BarcodeSymbology::list()
+			.into_iter()
+			.map(|symbology| {
+				RadioEntryData::new(format!("{symbology:?}"))
+					.label(symbology.to_string())
+					.on_update(update_value(move |_| TaggedValue::BarcodeSymbology(symbology), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(symbology as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Code 128 encodes any printable ASCII text, while EAN-13 and UPC-A encode product numbers of 12 or 11 digits (with an optional check digit)")
+}
+
+fn point_region_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Region", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::PointRegion(region),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = PointRegion::list()
+			.into_iter()
+			.map(|region| {
+				RadioEntryData::new(format!("{region:?}"))
+					.label(region.to_string())
+					.on_update(update_value(move |_| TaggedValue::PointRegion(region), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(region as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Whether the points are placed along the outline of the path or inside its filled area")
 }
 
-fn curves_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
-	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+fn point_sampling_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Sampling", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::PointSampling(sampling),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = PointSampling::list()
+			.into_iter()
+			.map(|sampling| {
+				RadioEntryData::new(format!("{sampling:?}"))
+					.label(sampling.to_string())
+					.on_update(update_value(move |_| TaggedValue::PointSampling(sampling), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
 
-	if let NodeInput::Value {
-		tagged_value: TaggedValue::Curve(curve),
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(sampling as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Even places points at the spacing apart, Poisson-Disk scatters them randomly no closer than the spacing, and Random scatters them anywhere")
+}
+
+fn point_distribution_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Distribution", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::PointDistribution(distribution),
 		exposed: false,
 	} = &document_node.inputs[index]
 	{
+		let entries = PointDistribution::list()
+			.into_iter()
+			.map(|distribution| {
+				RadioEntryData::new(format!("{distribution:?}"))
+					.label(distribution.to_string())
+					.on_update(update_value(move |_| TaggedValue::PointDistribution(distribution), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
+
 		widgets.extend_from_slice(&[
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			CurveInput::new(curve.clone())
-				.on_update(update_value(|x: &CurveInput| TaggedValue::Curve(x.value.clone()), node_id, index))
+			RadioInput::new(entries).selected_index(Some(distribution as u32)).widget_holder(),
+		]);
+	}
+	LayoutGroup::Row { widgets }.with_tooltip("Whether the points are the anchors of the input, or are scattered randomly or in a grid over its bounds")
+}
+
+fn warp_mode_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, "Mode", FrontendGraphDataType::General, true);
+	if let &NodeInput::Value {
+		tagged_value: TaggedValue::WarpMode(mode),
+		exposed: false,
+	} = &document_node.inputs[index]
+	{
+		let entries = [
+			(
+				WarpMode::Perspective,
+				"perspective",
+				"Perspective",
+				"Move the corners like a plane seen in perspective, keeping straight lines straight",
+			),
+			(WarpMode::Envelope, "envelope", "Envelope", "Bend the data along a 4×4 mesh of control points spanning its bounds"),
+		]
+		.into_iter()
+		.map(|(value, name, label, tooltip)| {
+			RadioEntryData::new(name)
+				.label(label)
+				.tooltip(tooltip)
+				.on_update(update_value(move |_| TaggedValue::WarpMode(value), node_id, index))
 				.on_commit(commit_value)
-				.widget_holder(),
-		])
+		})
+		.collect();
+
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(mode as u32)).widget_holder(),
+		]);
 	}
 	LayoutGroup::Row { widgets }
 }
 
-fn centroid_widget(document_node: &DocumentNode, node_id: NodeId, index: usize) -> LayoutGroup {
-	let mut widgets = start_widgets(document_node, node_id, index, "Centroid Type", FrontendGraphDataType::General, true);
+fn projection_face_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
 	if let &NodeInput::Value {
-		tagged_value: TaggedValue::CentroidType(centroid_type),
+		tagged_value: TaggedValue::ProjectionFace(projection_face),
 		exposed: false,
 	} = &document_node.inputs[index]
 	{
-		let entries = vec![
-			RadioEntryData::new("area")
-				.label("Area")
-				.tooltip("Center of mass for the interior area of the shape")
-				.on_update(update_value(move |_| TaggedValue::CentroidType(CentroidType::Area), node_id, index))
-				.on_commit(commit_value),
-			RadioEntryData::new("length")
-				.label("Length")
-				.tooltip("Center of mass for the perimeter arc length of the shape")
-				.on_update(update_value(move |_| TaggedValue::CentroidType(CentroidType::Length), node_id, index))
-				.on_commit(commit_value),
-		];
+		let entries = ProjectionFace::list()
+			.into_iter()
+			.map(|face| {
+				RadioEntryData::new(format!("{face:?}"))
+					.label(face.to_string())
+					.on_update(update_value(move |_| TaggedValue::ProjectionFace(face), node_id, index))
+					.on_commit(commit_value)
+			})
+			.collect();
 
 		widgets.extend_from_slice(&[
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			RadioInput::new(entries)
-				.selected_index(match centroid_type {
-					CentroidType::Area => Some(0),
-					CentroidType::Length => Some(1),
-				})
-				.widget_holder(),
+			RadioInput::new(entries).selected_index(Some(projection_face as u32)).widget_holder(),
 		]);
 	}
-	LayoutGroup::Row { widgets }
+	LayoutGroup::Row { widgets }.with_tooltip("The face of the box the artwork is laid onto")
 }
 
 pub fn levels_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
@@ -850,6 +1730,22 @@ pub fn vector2_properties(document_node: &DocumentNode, node_id: NodeId, _contex
 	vec![LayoutGroup::Row { widgets: x }, LayoutGroup::Row { widgets: y }]
 }
 
+pub fn oscillator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let waveform = waveform(document_node, node_id, 2, "Waveform", true);
+	let frequency = number_widget(document_node, node_id, 3, "Frequency", NumberInput::default().min(0.).unit(" Hz"), true);
+	let amplitude = number_widget(document_node, node_id, 4, "Amplitude", NumberInput::default(), true);
+	let phase = number_widget(document_node, node_id, 5, "Phase", NumberInput::default().mode_range().min(0.).max(360.).unit("°"), true);
+	let info = TextLabel::new("The time follows the timeline, so connect the output to a number input to animate it").widget_holder();
+
+	vec![
+		waveform,
+		LayoutGroup::Row { widgets: frequency },
+		LayoutGroup::Row { widgets: amplitude },
+		LayoutGroup::Row { widgets: phase },
+		LayoutGroup::Row { widgets: vec![info] },
+	]
+}
+
 pub fn boolean_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let widgets = bool_widget(document_node, node_id, 0, "Bool", true);
 
@@ -860,12 +1756,36 @@ pub fn color_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 	vec![color_widget(document_node, node_id, 0, "Color", ColorButton::default(), true)]
 }
 
+pub fn parameter_properties(_document_node: &DocumentNode, node_id: NodeId, context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let node_path = [context.nested_path, &[node_id]].concat();
+	let binding = parameter_binding_widget(context.parameters, node_path, 0);
+	let info = TextLabel::new("Outputs the value of the document parameter, which is edited in the Parameters panel").widget_holder();
+
+	vec![binding, LayoutGroup::Row { widgets: vec![info] }]
+}
+
 pub fn load_image_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let url = text_widget(document_node, node_id, 1, "Url", true);
 
 	vec![LayoutGroup::Row { widgets: url }]
 }
 
+pub fn audio_analysis_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let url = text_widget(document_node, node_id, 1, "Url", true);
+	let band = number_widget(document_node, node_id, 3, "Band", NumberInput::default().int().min(0.), true);
+	let band_count = number_widget(document_node, node_id, 4, "Band Count", NumberInput::default().int().min(1.).max(64.), true);
+	let gain = number_widget(document_node, node_id, 5, "Gain", NumberInput::default().min(0.), true);
+	let info = TextLabel::new("Band 0 follows the loudness of the whole WAV file, while the others follow its frequencies from low to high").widget_holder();
+
+	vec![
+		LayoutGroup::Row { widgets: url },
+		LayoutGroup::Row { widgets: band },
+		LayoutGroup::Row { widgets: band_count },
+		LayoutGroup::Row { widgets: gain },
+		LayoutGroup::Row { widgets: vec![info] },
+	]
+}
+
 pub fn output_properties(_document_node: &DocumentNode, _node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let label = TextLabel::new("Graphics fed into the Output are drawn in the viewport").widget_holder();
 
@@ -878,6 +1798,14 @@ pub fn mask_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 	vec![mask]
 }
 
+pub fn pixel_diff_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let mode = pixel_diff_mode_widget(document_node, node_id, 2, "Mode", true);
+	let threshold = number_widget(document_node, node_id, 3, "Threshold", NumberInput::default().percentage(), true);
+	let time = number_widget(document_node, node_id, 4, "Time", NumberInput::default().unit(" s").min(0.), true);
+
+	vec![mode, LayoutGroup::Row { widgets: threshold }, LayoutGroup::Row { widgets: time }]
+}
+
 pub fn color_channel_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	vec![color_channel(document_node, node_id, 0, "Channel", true)]
 }
@@ -1317,6 +2245,21 @@ pub fn opacity_properties(document_node: &DocumentNode, node_id: NodeId, _contex
 	vec![LayoutGroup::Row { widgets: gamma }]
 }
 
+pub fn svg_attributes_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let id = text_widget(document_node, node_id, 1, "ID", true);
+	let classes = text_widget(document_node, node_id, 2, "Classes", true);
+	let data = text_area_widget(document_node, node_id, 3, "Data Attributes", true);
+
+	vec![
+		LayoutGroup::Row { widgets: id },
+		LayoutGroup::Row { widgets: classes },
+		LayoutGroup::Row { widgets: data },
+		LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Written on the layer's SVG element when exporting. Data attributes go one per line as name=value.").widget_holder()],
+		},
+	]
+}
+
 pub fn blend_mode_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	vec![blend_mode(document_node, node_id, 1, "Blend Mode", true)]
 }
@@ -1351,6 +2294,100 @@ pub fn exposure_properties(document_node: &DocumentNode, node_id: NodeId, _conte
 	]
 }
 
+pub fn lens_correction_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let amount = number_widget(document_node, node_id, 1, "Amount", NumberInput::default().mode_range().min(-100.).max(100.).unit("%"), true);
+
+	vec![LayoutGroup::Row { widgets: amount }.with_tooltip("Positive amounts straighten barrel distortion, negative amounts straighten pincushion distortion")]
+}
+
+pub fn perspective_crop_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let corner = |name: &str, index| vec2_widget(document_node, node_id, index, name, "X", "Y", "", None, add_blank_assist).with_tooltip("Fraction of the image's width and height, from 0 to 1");
+
+	vec![corner("Top Left", 1), corner("Top Right", 2), corner("Bottom Right", 3), corner("Bottom Left", 4)]
+}
+
+pub fn chromatic_aberration_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let amount = number_widget(document_node, node_id, 1, "Amount", NumberInput::default().min(0.).unit(" px"), true);
+	let angle = number_widget(document_node, node_id, 2, "Angle", NumberInput::default().mode_range().min(-180.).max(180.).unit("°"), true);
+
+	vec![
+		LayoutGroup::Row { widgets: amount }.with_tooltip("Distance the red and blue channels are each moved apart"),
+		LayoutGroup::Row { widgets: angle }.with_tooltip("Direction the red channel is moved, with the blue channel moved the opposite way"),
+	]
+}
+
+pub fn vignette_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let amount = number_widget(document_node, node_id, 1, "Amount", NumberInput::default().mode_range().min(-100.).max(100.).unit("%"), true);
+	let size = number_widget(document_node, node_id, 2, "Size", NumberInput::default().min(0.).unit("%"), true);
+	let feather = number_widget(document_node, node_id, 3, "Feather", NumberInput::default().min(0.).unit("%"), true);
+	let roundness = number_widget(document_node, node_id, 4, "Roundness", NumberInput::default().mode_range().min(0.).max(100.).unit("%"), true);
+
+	vec![
+		LayoutGroup::Row { widgets: amount }.with_tooltip("Positive amounts darken the edges, negative amounts lighten them"),
+		LayoutGroup::Row { widgets: size }.with_tooltip("Distance from the center where the vignette starts, as a percentage of the distance to the edges"),
+		LayoutGroup::Row { widgets: feather }.with_tooltip("Distance over which the vignette fades in, as a percentage of the distance to the edges"),
+		LayoutGroup::Row { widgets: roundness }.with_tooltip("How close the vignette is to a circle rather than following the proportions of the image"),
+	]
+}
+
+pub fn grain_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let amount = number_widget(document_node, node_id, 1, "Amount", NumberInput::default().mode_range().min(0.).max(100.).unit("%"), true);
+	let size = number_widget(document_node, node_id, 2, "Size", NumberInput::default().min(0.01).unit(" px"), true);
+	let seed = number_widget(document_node, node_id, 3, "Seed", NumberInput::default().min(0.).int(), true);
+
+	vec![LayoutGroup::Row { widgets: amount }, LayoutGroup::Row { widgets: size }, LayoutGroup::Row { widgets: seed }]
+}
+
+pub fn convolve_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let kernel = vec_f64_input(document_node, node_id, 1, "Kernel", TextInput::default().centered(true), true);
+	let normalize = bool_widget(document_node, node_id, 2, "Normalize", true);
+
+	vec![
+		LayoutGroup::Row { widgets: kernel }.with_tooltip("The weights of a square kernel with an odd width, row by row from the top left, like 9 weights for a 3×3 kernel"),
+		LayoutGroup::Row { widgets: normalize }.with_tooltip("Divide the kernel by the sum of its weights, so it keeps the overall brightness"),
+		border_mode_widget(document_node, node_id, 3, "Border", true),
+	]
+}
+
+pub fn convolution_preset_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	vec![
+		convolution_preset_widget(document_node, node_id, 1, "Preset", true),
+		border_mode_widget(document_node, node_id, 2, "Border", true),
+	]
+}
+
+pub fn unsharp_mask_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let amount = number_widget(document_node, node_id, 1, "Amount", NumberInput::default().min(0.).max(500.).unit("%"), true);
+	let radius = number_widget(document_node, node_id, 2, "Radius", NumberInput::default().min(0.).max(100.).unit(" px"), true);
+	let threshold = number_widget(document_node, node_id, 3, "Threshold", NumberInput::default().mode_range().min(0.).max(100.).unit("%"), true);
+
+	vec![
+		LayoutGroup::Row { widgets: amount },
+		LayoutGroup::Row { widgets: radius }.with_tooltip("Size of the detail which is sharpened"),
+		LayoutGroup::Row { widgets: threshold }.with_tooltip("Differences from the surroundings smaller than this are left alone, to avoid sharpening noise"),
+	]
+}
+
+pub fn morphology_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let radius = number_widget(document_node, node_id, 3, "Radius", NumberInput::default().min(0.).max(100.).int().unit(" px"), true);
+
+	vec![
+		morphology_operation_widget(document_node, node_id, 1, "Operation", true),
+		morphology_channel_widget(document_node, node_id, 2, "Channel", true),
+		LayoutGroup::Row { widgets: radius },
+		morphology_shape_widget(document_node, node_id, 4, "Shape", true),
+	]
+}
+
+pub fn shuffle_channels_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let red = channel_source_widget(document_node, node_id, 2, "Red", true);
+	let green = channel_source_widget(document_node, node_id, 3, "Green", true);
+	let blue = channel_source_widget(document_node, node_id, 4, "Blue", true);
+	let alpha = channel_source_widget(document_node, node_id, 5, "Alpha", true);
+
+	vec![red, green, blue, alpha]
+}
+
 pub fn add_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let widgets = number_widget(document_node, node_id, 1, "Addend", NumberInput::default(), true);
 
@@ -1375,6 +2412,15 @@ pub fn multiply_properties(document_node: &DocumentNode, node_id: NodeId, _conte
 	vec![LayoutGroup::Row { widgets }]
 }
 
+pub fn expression_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let expression = text_widget(document_node, node_id, 1, "Expression", true);
+	let y = number_widget(document_node, node_id, 2, "Y", NumberInput::default(), true);
+	let u = vec2_widget(document_node, node_id, 3, "U", "X", "Y", "", None, add_blank_assist);
+	let v = vec2_widget(document_node, node_id, 4, "V", "X", "Y", "", None, add_blank_assist);
+
+	vec![LayoutGroup::Row { widgets: expression }, LayoutGroup::Row { widgets: y }, u, v]
+}
+
 pub fn exponent_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let widgets = number_widget(document_node, node_id, 1, "Power", NumberInput::new(Some(2.)), true);
 
@@ -1387,6 +2433,61 @@ pub fn log_properties(document_node: &DocumentNode, node_id: NodeId, _context: &
 	vec![LayoutGroup::Row { widgets }]
 }
 
+pub fn atan2_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let widgets = number_widget(document_node, node_id, 1, "X", NumberInput::default(), true);
+
+	vec![LayoutGroup::Row { widgets }]
+}
+
+pub fn clamp_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let min = number_widget(document_node, node_id, 1, "Min", NumberInput::default(), true);
+	let max = number_widget(document_node, node_id, 2, "Max", NumberInput::default(), true);
+
+	vec![LayoutGroup::Row { widgets: min }, LayoutGroup::Row { widgets: max }]
+}
+
+pub fn remap_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let input_min = number_widget(document_node, node_id, 1, "Input Min", NumberInput::default(), true);
+	let input_max = number_widget(document_node, node_id, 2, "Input Max", NumberInput::default(), true);
+	let output_min = number_widget(document_node, node_id, 3, "Output Min", NumberInput::default(), true);
+	let output_max = number_widget(document_node, node_id, 4, "Output Max", NumberInput::default(), true);
+	let clamped = bool_widget(document_node, node_id, 5, "Clamped", true);
+
+	vec![
+		LayoutGroup::Row { widgets: input_min },
+		LayoutGroup::Row { widgets: input_max },
+		LayoutGroup::Row { widgets: output_min },
+		LayoutGroup::Row { widgets: output_max },
+		LayoutGroup::Row { widgets: clamped },
+	]
+}
+
+pub fn smoothstep_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let edge0 = number_widget(document_node, node_id, 1, "Edge 0", NumberInput::default(), true);
+	let edge1 = number_widget(document_node, node_id, 2, "Edge 1", NumberInput::default(), true);
+
+	vec![LayoutGroup::Row { widgets: edge0 }, LayoutGroup::Row { widgets: edge1 }]
+}
+
+pub fn lerp_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let second = number_widget(document_node, node_id, 1, "Second", NumberInput::default(), true);
+	let factor = number_widget(document_node, node_id, 2, "Factor", NumberInput::default().mode_range().min(0.).max(1.), true);
+
+	vec![LayoutGroup::Row { widgets: second }, LayoutGroup::Row { widgets: factor }]
+}
+
+pub fn vector_operand_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	vec![vec2_widget(document_node, node_id, 1, "Second", "X", "Y", "", None, add_blank_assist)]
+}
+
+pub fn construct_transform_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let translation = vec2_widget(document_node, node_id, 1, "Translation", "X", "Y", " px", None, add_blank_assist);
+	let rotation = number_widget(document_node, node_id, 2, "Rotation", NumberInput::default().unit("°"), true);
+	let scale = vec2_widget(document_node, node_id, 3, "Scale", "W", "H", "x", None, add_blank_assist);
+
+	vec![translation, LayoutGroup::Row { widgets: rotation }, scale]
+}
+
 pub fn max_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let widgets = number_widget(document_node, node_id, 1, "Maximum", NumberInput::default(), true);
 
@@ -1411,6 +2512,56 @@ pub fn modulo_properties(document_node: &DocumentNode, node_id: NodeId, _context
 	vec![LayoutGroup::Row { widgets }]
 }
 
+const RANDOM_INDEX_TOOLTIP: &str = "Draws different values for each copy or item while keeping them the same every time the graph is evaluated";
+
+fn random_seed_widgets(document_node: &DocumentNode, node_id: NodeId, seed_index: usize) -> [LayoutGroup; 2] {
+	let seed = number_widget(document_node, node_id, seed_index, "Seed", NumberInput::default().int().min(0.), true);
+	let index = number_widget(document_node, node_id, seed_index + 1, "Index", NumberInput::default().int().min(0.), true);
+
+	[LayoutGroup::Row { widgets: seed }, LayoutGroup::Row { widgets: index }.with_tooltip(RANDOM_INDEX_TOOLTIP)]
+}
+
+pub fn conditional_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let condition = bool_widget(document_node, node_id, 0, "Condition", true);
+	let info = TextLabel::new("Only the branch picked by the condition is evaluated").widget_holder();
+
+	vec![LayoutGroup::Row { widgets: condition }, LayoutGroup::Row { widgets: vec![info] }]
+}
+
+pub fn switch_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let index = number_widget(document_node, node_id, 0, "Index", NumberInput::default().int().min(0.).max(3.), true);
+	let info = TextLabel::new("Only the option at the index is evaluated").widget_holder();
+
+	vec![LayoutGroup::Row { widgets: index }, LayoutGroup::Row { widgets: vec![info] }]
+}
+
+pub fn fold_list_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let initial = number_widget(document_node, node_id, 1, "Initial", NumberInput::default(), true);
+	let info = TextLabel::new("The function is run for each item with the Fold Accumulator and Fold Item nodes, and its result becomes the accumulator for the next item").widget_holder();
+
+	vec![LayoutGroup::Row { widgets: initial }, LayoutGroup::Row { widgets: vec![info] }]
+}
+
+pub fn random_number_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let min = number_widget(document_node, node_id, 1, "Min", NumberInput::default(), true);
+	let max = number_widget(document_node, node_id, 2, "Max", NumberInput::default(), true);
+	let [seed, index] = random_seed_widgets(document_node, node_id, 3);
+
+	vec![LayoutGroup::Row { widgets: min }, LayoutGroup::Row { widgets: max }, seed, index]
+}
+
+pub fn random_vector_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let min = vec2_widget(document_node, node_id, 1, "Min", "X", "Y", "", None, add_blank_assist);
+	let max = vec2_widget(document_node, node_id, 2, "Max", "X", "Y", "", None, add_blank_assist);
+	let [seed, index] = random_seed_widgets(document_node, node_id, 3);
+
+	vec![min, max, seed, index]
+}
+
+pub fn shuffle_list_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	random_seed_widgets(document_node, node_id, 1).into()
+}
+
 pub fn circle_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	vec![LayoutGroup::Row {
 		widgets: number_widget(document_node, node_id, 1, "Radius", NumberInput::default(), true),
@@ -1547,41 +2698,262 @@ pub fn rectangle_properties(document_node: &DocumentNode, node_id: NodeId, _cont
 		corner_radius_row_2.push(input_widget);
 	}
 
-	// Clamped
-	let clamped = bool_widget(document_node, node_id, clamped_index, "Clamped", true);
+	// Clamped
+	let clamped = bool_widget(document_node, node_id, clamped_index, "Clamped", true);
+
+	vec![
+		LayoutGroup::Row { widgets: size_x },
+		LayoutGroup::Row { widgets: size_y },
+		LayoutGroup::Row { widgets: corner_radius_row_1 },
+		LayoutGroup::Row { widgets: corner_radius_row_2 },
+		LayoutGroup::Row { widgets: clamped },
+	]
+}
+
+pub fn regular_polygon_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let points = number_widget(document_node, node_id, 1, "Points", NumberInput::default().min(3.), true);
+	let radius = number_widget(document_node, node_id, 2, "Radius", NumberInput::default(), true);
+
+	vec![LayoutGroup::Row { widgets: points }, LayoutGroup::Row { widgets: radius }]
+}
+
+pub fn star_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let points = number_widget(document_node, node_id, 1, "Points", NumberInput::default().min(2.), true);
+	let radius = number_widget(document_node, node_id, 2, "Radius", NumberInput::default(), true);
+	let inner_radius = number_widget(document_node, node_id, 3, "Inner Radius", NumberInput::default(), true);
+
+	vec![LayoutGroup::Row { widgets: points }, LayoutGroup::Row { widgets: radius }, LayoutGroup::Row { widgets: inner_radius }]
+}
+
+pub fn spiral_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let turns = number_widget(document_node, node_id, 1, "Turns", NumberInput::default().min(0.), true);
+	let inner_radius = number_widget(document_node, node_id, 2, "Inner Radius", NumberInput::default().unit(" px"), true);
+	let outer_radius = number_widget(document_node, node_id, 3, "Outer Radius", NumberInput::default().unit(" px"), true);
+
+	vec![
+		LayoutGroup::Row { widgets: turns },
+		LayoutGroup::Row { widgets: inner_radius },
+		LayoutGroup::Row { widgets: outer_radius },
+	]
+}
+
+pub fn arc_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let radius = number_widget(document_node, node_id, 1, "Radius", NumberInput::default().unit(" px"), true);
+	let start_angle = number_widget(document_node, node_id, 2, "Start Angle", NumberInput::default().unit("°"), true);
+	let sweep_angle = number_widget(document_node, node_id, 3, "Sweep Angle", NumberInput::default().min(-360.).max(360.).unit("°"), true);
+
+	vec![
+		LayoutGroup::Row { widgets: radius },
+		LayoutGroup::Row { widgets: start_angle }.with_tooltip("Degrees clockwise from the right where the arc starts"),
+		LayoutGroup::Row { widgets: sweep_angle }.with_tooltip("Degrees the arc sweeps clockwise from its start, or counterclockwise if negative"),
+		arc_type_widget(document_node, node_id, 4),
+	]
+}
+
+pub fn line_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let operand = |name: &str, index| vec2_widget(document_node, node_id, index, name, "X", "Y", "px", None, add_blank_assist);
+	vec![operand("Start", 1), operand("End", 2)]
+}
+pub fn warp_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let corner = |name: &str, index| vec2_widget(document_node, node_id, index, name, "X", "Y", " px", None, add_blank_assist);
+	let mesh = vec_dvec2_input(document_node, node_id, 6, "Mesh", TextInput::default().centered(true), true);
+
+	vec![
+		warp_mode_widget(document_node, node_id, 1),
+		corner("Top Left", 2),
+		corner("Top Right", 3),
+		corner("Bottom Right", 4),
+		corner("Bottom Left", 5),
+		LayoutGroup::Row { widgets: mesh }.with_tooltip("Offsets of the 16 envelope control points, row by row from the top left, used in Envelope mode"),
+	]
+}
+
+pub fn axonometric_projection_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let face = projection_face_widget(document_node, node_id, 1, "Face", true);
+	let left_angle = number_widget(document_node, node_id, 2, "Left Angle", NumberInput::default().unit("°").min(0.).max(89.), true);
+	let right_angle = number_widget(document_node, node_id, 3, "Right Angle", NumberInput::default().unit("°").min(0.).max(89.), true);
+
+	vec![face, LayoutGroup::Row { widgets: left_angle }, LayoutGroup::Row { widgets: right_angle }]
+}
+
+pub fn depth_stack_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let face = projection_face_widget(document_node, node_id, 1, "Face", true);
+	let count = number_widget(document_node, node_id, 2, "Count", NumberInput::default().int().min(1.).max(1000.), true);
+	let spacing = number_widget(document_node, node_id, 3, "Spacing", NumberInput::default().unit(" px"), true);
+	let left_angle = number_widget(document_node, node_id, 4, "Left Angle", NumberInput::default().unit("°").min(0.).max(89.), true);
+	let right_angle = number_widget(document_node, node_id, 5, "Right Angle", NumberInput::default().unit("°").min(0.).max(89.), true);
+
+	vec![
+		face,
+		LayoutGroup::Row { widgets: count },
+		LayoutGroup::Row { widgets: spacing },
+		LayoutGroup::Row { widgets: left_angle },
+		LayoutGroup::Row { widgets: right_angle },
+	]
+}
+
+pub fn spline_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	vec![LayoutGroup::Row {
+		widgets: vec_dvec2_input(document_node, node_id, 1, "Points", TextInput::default().centered(true), true),
+	}]
+}
+
+pub fn l_system_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let axiom = text_widget(document_node, node_id, 1, "Axiom", true);
+	let rules = text_area_widget(document_node, node_id, 2, "Rules", true);
+	let iterations = number_widget(document_node, node_id, 3, "Iterations", NumberInput::default().int().min(0.).max(16.), true);
+	let angle = number_widget(document_node, node_id, 4, "Angle", NumberInput::default().unit("°"), true);
+	let length = number_widget(document_node, node_id, 5, "Length", NumberInput::default().unit(" px"), true);
+	let angle_randomness = number_widget(document_node, node_id, 6, "Angle Randomness", NumberInput::default().min(0.).unit("°"), true);
+	let length_randomness = number_widget(document_node, node_id, 7, "Length Randomness", NumberInput::default().mode_range().min(0.).max(1.), true);
+	let seed = number_widget(document_node, node_id, 8, "Seed", NumberInput::default().min(0.).int(), true);
+
+	vec![
+		LayoutGroup::Row { widgets: axiom }.with_tooltip("The symbols the system starts from"),
+		LayoutGroup::Row { widgets: rules }.with_tooltip(
+			"One rule per line replacing a symbol, like \"F=F+F--F+F\". When drawn, F and G move forward drawing a line, f moves without drawing, + and - turn counterclockwise and clockwise, | turns around, and [ and ] save and return to the position for branches",
+		),
+		LayoutGroup::Row { widgets: iterations }.with_tooltip("How many times the rules are applied, each time to every symbol at once"),
+		LayoutGroup::Row { widgets: angle },
+		LayoutGroup::Row { widgets: length },
+		LayoutGroup::Row { widgets: angle_randomness }.with_tooltip("The most each turn randomly differs from the angle"),
+		LayoutGroup::Row { widgets: length_randomness }.with_tooltip("The most each move randomly differs from the length, as a fraction of it"),
+		LayoutGroup::Row { widgets: seed },
+	]
+}
+
+pub fn qr_code_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let text = text_area_widget(document_node, node_id, 1, "Text", true);
+	let quiet_zone = number_widget(document_node, node_id, 3, "Quiet Zone", NumberInput::default().int().min(0.).unit(" modules"), true);
+	let module_size = number_widget(document_node, node_id, 4, "Module Size", NumberInput::default().min(0.).unit(" px"), true);
+
+	vec![
+		LayoutGroup::Row { widgets: text },
+		qr_error_correction_widget(document_node, node_id, 2),
+		LayoutGroup::Row { widgets: quiet_zone }.with_tooltip("The light margin scanners need around the code, which the standard sets at 4 modules"),
+		LayoutGroup::Row { widgets: module_size },
+	]
+}
+
+pub fn barcode_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let text = text_widget(document_node, node_id, 1, "Text", true);
+	let quiet_zone = number_widget(document_node, node_id, 3, "Quiet Zone", NumberInput::default().int().min(0.).unit(" modules"), true);
+	let module_size = number_widget(document_node, node_id, 4, "Module Size", NumberInput::default().min(0.).unit(" px"), true);
+	let height = number_widget(document_node, node_id, 5, "Height", NumberInput::default().min(0.).unit(" px"), true);
 
 	vec![
-		LayoutGroup::Row { widgets: size_x },
-		LayoutGroup::Row { widgets: size_y },
-		LayoutGroup::Row { widgets: corner_radius_row_1 },
-		LayoutGroup::Row { widgets: corner_radius_row_2 },
-		LayoutGroup::Row { widgets: clamped },
+		LayoutGroup::Row { widgets: text },
+		barcode_symbology_widget(document_node, node_id, 2),
+		LayoutGroup::Row { widgets: quiet_zone }.with_tooltip("The light margin scanners need on either side of the bars, at least 10 modules for Code 128 and 11 for EAN-13 and UPC-A"),
+		LayoutGroup::Row { widgets: module_size }.with_tooltip("The width of the narrowest bar"),
+		LayoutGroup::Row { widgets: height },
 	]
 }
 
-pub fn regular_polygon_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
-	let points = number_widget(document_node, node_id, 1, "Points", NumberInput::default().min(3.), true);
-	let radius = number_widget(document_node, node_id, 2, "Radius", NumberInput::default(), true);
+const POINT_ATTRIBUTE_NAME_TOOLTIP: &str = "Copy to Points scales the copies by \"scale\", rotates them by \"rotation\" degrees, moves them by \"offset\", and fills them with \"color\"";
 
-	vec![LayoutGroup::Row { widgets: points }, LayoutGroup::Row { widgets: radius }]
+pub fn sample_point_cloud_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let sampling = match &document_node.inputs[2] {
+		NodeInput::Value {
+			tagged_value: TaggedValue::PointSampling(sampling),
+			..
+		} => Some(*sampling),
+		_ => None,
+	};
+	let random = sampling != Some(PointSampling::Even);
+
+	let spacing = number_widget(
+		document_node,
+		node_id,
+		3,
+		"Spacing",
+		NumberInput::default().min(0.).unit(" px").disabled(sampling == Some(PointSampling::Random)),
+		true,
+	);
+	let count = number_widget(document_node, node_id, 4, "Count", NumberInput::default().int().min(0.).max(100_000.).disabled(!random), true);
+	let seed = number_widget(document_node, node_id, 5, "Seed", NumberInput::default().int().min(0.).disabled(!random), true);
+
+	vec![
+		point_region_widget(document_node, node_id, 1),
+		point_sampling_widget(document_node, node_id, 2),
+		LayoutGroup::Row { widgets: spacing },
+		LayoutGroup::Row { widgets: count }.with_tooltip("The most points scattered randomly"),
+		LayoutGroup::Row { widgets: seed },
+	]
 }
 
-pub fn star_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
-	let points = number_widget(document_node, node_id, 1, "Points", NumberInput::default().min(2.), true);
-	let radius = number_widget(document_node, node_id, 2, "Radius", NumberInput::default(), true);
-	let inner_radius = number_widget(document_node, node_id, 3, "Inner Radius", NumberInput::default(), true);
+pub fn random_point_attribute_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let name = text_widget(document_node, node_id, 1, "Name", true);
+	let min = number_widget(document_node, node_id, 2, "Min", NumberInput::default(), true);
+	let max = number_widget(document_node, node_id, 3, "Max", NumberInput::default(), true);
+	let seed = number_widget(document_node, node_id, 4, "Seed", NumberInput::default().int().min(0.), true);
 
-	vec![LayoutGroup::Row { widgets: points }, LayoutGroup::Row { widgets: radius }, LayoutGroup::Row { widgets: inner_radius }]
+	vec![
+		LayoutGroup::Row { widgets: name }.with_tooltip(POINT_ATTRIBUTE_NAME_TOOLTIP),
+		LayoutGroup::Row { widgets: min },
+		LayoutGroup::Row { widgets: max },
+		LayoutGroup::Row { widgets: seed },
+	]
 }
 
-pub fn line_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
-	let operand = |name: &str, index| vec2_widget(document_node, node_id, index, name, "X", "Y", "px", None, add_blank_assist);
-	vec![operand("Start", 1), operand("End", 2)]
+pub fn random_vec2_point_attribute_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let name = text_widget(document_node, node_id, 1, "Name", true);
+	let min = vec2_widget(document_node, node_id, 2, "Min", "X", "Y", "", None, add_blank_assist);
+	let max = vec2_widget(document_node, node_id, 3, "Max", "X", "Y", "", None, add_blank_assist);
+	let seed = number_widget(document_node, node_id, 4, "Seed", NumberInput::default().int().min(0.), true);
+
+	vec![
+		LayoutGroup::Row { widgets: name }.with_tooltip(POINT_ATTRIBUTE_NAME_TOOLTIP),
+		min,
+		max,
+		LayoutGroup::Row { widgets: seed },
+	]
 }
-pub fn spline_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
-	vec![LayoutGroup::Row {
-		widgets: vec_dvec2_input(document_node, node_id, 1, "Points", TextInput::default().centered(true), true),
-	}]
+
+pub fn gradient_point_attribute_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let name = text_widget(document_node, node_id, 1, "Name", true);
+	let gradient = color_widget(document_node, node_id, 2, "Gradient", ColorButton::default(), true);
+	let start = vec2_widget(document_node, node_id, 3, "Start", "X", "Y", " px", None, add_blank_assist);
+	let end = vec2_widget(document_node, node_id, 4, "End", "X", "Y", " px", None, add_blank_assist);
+
+	vec![LayoutGroup::Row { widgets: name }.with_tooltip(POINT_ATTRIBUTE_NAME_TOOLTIP), gradient, start, end]
+}
+
+pub fn point_attribute_values_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let name = text_widget(document_node, node_id, 1, "Name", true);
+	vec![LayoutGroup::Row { widgets: name }]
+}
+
+pub fn voronoi_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let distribution = match &document_node.inputs[1] {
+		NodeInput::Value {
+			tagged_value: TaggedValue::PointDistribution(distribution),
+			..
+		} => Some(*distribution),
+		_ => None,
+	};
+	let scattered = distribution != Some(PointDistribution::Anchors);
+
+	let count = number_widget(document_node, node_id, 2, "Count", NumberInput::default().int().min(0.).max(5000.).disabled(!scattered), true);
+	let seed = number_widget(
+		document_node,
+		node_id,
+		3,
+		"Seed",
+		NumberInput::default()
+			.int()
+			.min(0.)
+			.disabled(distribution.is_some_and(|distribution| distribution != PointDistribution::Random)),
+		true,
+	);
+	let relaxation = number_widget(document_node, node_id, 4, "Relaxation", NumberInput::default().int().min(0.).max(100.), true);
+
+	vec![
+		point_distribution_widget(document_node, node_id, 1),
+		LayoutGroup::Row { widgets: count },
+		LayoutGroup::Row { widgets: seed },
+		LayoutGroup::Row { widgets: relaxation }.with_tooltip("How many times the points are moved to the centers of their cells, evening them out into a more organic pattern"),
+	]
 }
 
 pub fn logic_operator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
@@ -1589,7 +2961,54 @@ pub fn logic_operator_properties(document_node: &DocumentNode, node_id: NodeId,
 	vec![LayoutGroup::Row { widgets }]
 }
 
-pub fn transform_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+pub fn assert_equal_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let expected = number_widget(document_node, node_id, 1, "Expected", NumberInput::default(), true);
+	let tolerance = number_widget(document_node, node_id, 2, "Tolerance", NumberInput::default().min(0.), true);
+	let info = TextLabel::new("Fails the graph with an error on this node if the value isn't the expected one").widget_holder();
+
+	vec![
+		LayoutGroup::Row { widgets: expected },
+		LayoutGroup::Row { widgets: tolerance },
+		LayoutGroup::Row { widgets: vec![info] },
+	]
+}
+
+pub fn assert_bounds_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let min = vec2_widget(document_node, node_id, 1, "Min", "X", "Y", "px", None, add_blank_assist);
+	let max = vec2_widget(document_node, node_id, 2, "Max", "X", "Y", "px", None, add_blank_assist);
+	let tolerance = number_widget(document_node, node_id, 3, "Tolerance", NumberInput::default().min(0.).unit(" px"), true);
+	let info = TextLabel::new("Fails the graph with an error on this node if the bounds of the graphic differ").widget_holder();
+
+	vec![min, max, LayoutGroup::Row { widgets: tolerance }, LayoutGroup::Row { widgets: vec![info] }]
+}
+
+/// The origin, in the parent space of the layer that the Transform node belongs to, of the artboard containing that layer.
+/// Translations are shown relative to this origin when the document uses artboard-relative coordinates.
+fn artboard_origin_in_parent_space(node_id: NodeId, context: &NodePropertiesContext) -> Option<DVec2> {
+	if !context.artboard_relative_coordinates || !context.nested_path.is_empty() {
+		return None;
+	}
+
+	let metadata = &*context.metadata;
+	let network = context.document_network;
+
+	// The deepest layer whose chain of nodes contains the Transform node, since the chains of folders also contain the nodes of their children
+	let layer = metadata
+		.all_layers()
+		.filter(|layer| {
+			network
+				.upstream_flow_back_from_nodes(vec![layer.to_node()], graph_craft::document::FlowType::HorizontalFlow)
+				.any(|(_, upstream_id)| upstream_id == node_id)
+		})
+		.last()?;
+	// Artboards have no transform of their own, so only artboards containing the layer are relevant
+	let origin = metadata.artboard_origin(layer.parent(metadata)?)?;
+
+	let parent_to_document = metadata.document_to_viewport.inverse() * metadata.downstream_transform_to_viewport(layer);
+	(parent_to_document.matrix2.determinant() != 0.).then(|| parent_to_document.inverse().transform_point2(origin))
+}
+
+pub fn transform_properties(document_node: &DocumentNode, node_id: NodeId, context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let translation_assist = |widgets: &mut Vec<WidgetHolder>| {
 		let pivot_index = 5;
 		if let NodeInput::Value {
@@ -1608,7 +3027,21 @@ pub fn transform_properties(document_node: &DocumentNode, node_id: NodeId, _cont
 			add_blank_assist(widgets);
 		}
 	};
-	let translation = vec2_widget(document_node, node_id, 1, "Translation", "X", "Y", " px", None, translation_assist);
+	let origin = artboard_origin_in_parent_space(node_id, context).unwrap_or_default();
+	let units = context.units;
+	let translation = vec2_widget_with_origin(
+		document_node,
+		node_id,
+		1,
+		"Translation",
+		"X",
+		"Y",
+		&units.input_unit(),
+		None,
+		origin,
+		units.pixels_per_unit(),
+		translation_assist,
+	);
 
 	let rotation = {
 		let index = 2;
@@ -1641,16 +3074,101 @@ pub fn transform_properties(document_node: &DocumentNode, node_id: NodeId, _cont
 	vec![translation, rotation, scale]
 }
 
-pub fn node_section_font(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+fn text_kerning_widgets(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, selection: Option<&TextSelection>, blank_assist: bool) -> Vec<LayoutGroup> {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::General, blank_assist);
+	let NodeInput::Value {
+		tagged_value: TaggedValue::TextKerning(kerning),
+		exposed: false,
+	} = &document_node.inputs[index]
+	else {
+		return vec![LayoutGroup::Row { widgets }];
+	};
+
+	let entries = KerningMode::list()
+		.into_iter()
+		.map(|mode| {
+			let kerning = kerning.clone();
+			MenuListEntry::new(format!("{mode:?}"))
+				.label(mode.to_string())
+				.on_update(update_value(move |_| TaggedValue::TextKerning(TextKerning { mode, ..kerning.clone() }), node_id, index))
+				.on_commit(commit_value)
+		})
+		.collect();
+	widgets.extend_from_slice(&[
+		Separator::new(SeparatorType::Unrelated).widget_holder(),
+		DropdownInput::new(vec![entries]).selected_index(Some(kerning.mode as u32)).widget_holder(),
+	]);
+	let mut result = vec![LayoutGroup::Row { widgets }
+		.with_tooltip("Where the space between pairs of characters comes from: the pairs designed into the font, the shapes of the characters so the space looks even, or neither")];
+
+	// Pairs are kerned with the text caret placed between their characters
+	let text_length = match document_node.inputs.get(1).and_then(|input| input.as_value()) {
+		Some(TaggedValue::String(text)) => text.len(),
+		_ => 0,
+	};
+	let caret = selection
+		.filter(|selection| selection.node_id == node_id && selection.start == selection.end && selection.start > 0 && selection.start < text_length)
+		.map(|selection| selection.start);
+	if let Some(caret) = caret {
+		let kerning_for_pair = kerning.clone();
+		let pair_widget = NumberInput::new(Some(kerning.adjustment(caret)))
+			.on_update(update_value(
+				move |x: &NumberInput| {
+					let mut kerning = kerning_for_pair.clone();
+					kerning.set_adjustment(caret, x.value.unwrap_or_default());
+					TaggedValue::TextKerning(kerning)
+				},
+				node_id,
+				index,
+			))
+			.on_commit(commit_value)
+			.widget_holder();
+
+		let mut widgets = vec![TextLabel::new("").widget_holder()];
+		add_blank_assist(&mut widgets);
+		widgets.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Pair Kerning").widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			pair_widget,
+		]);
+		result.push(LayoutGroup::Row { widgets }.with_tooltip("Space added between the characters on either side of the text caret, in thousandths of the em, on top of the kerning mode"));
+	}
+	result
+}
+
+pub fn node_section_font(document_node: &DocumentNode, node_id: NodeId, context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let text = text_area_widget(document_node, node_id, 1, "Text", true);
 	let (font, style) = font_inputs(document_node, node_id, 2, "Font", true);
 	let size = number_widget(document_node, node_id, 3, "Size", NumberInput::default().unit(" px").min(1.), true);
-
-	let mut result = vec![LayoutGroup::Row { widgets: text }, LayoutGroup::Row { widgets: font }];
+	let line_height = number_widget(document_node, node_id, 4, "Line Height", NumberInput::default().unit("x").min(0.).step(0.1), true);
+	let box_width = number_widget(document_node, node_id, 5, "Box Width", NumberInput::default().unit(" px").min(0.), true);
+	let box_height = number_widget(document_node, node_id, 6, "Box Height", NumberInput::default().unit(" px").min(0.), true);
+	let align = text_align_widget(document_node, node_id, 7, "Align", true);
+	let auto_resize = text_auto_resize_widget(document_node, node_id, 8, "Auto Resize", true);
+	let variations = font_variations_widgets(document_node, node_id, 9, "Variations", &context.persistent_data.font_cache, true);
+	let runs = text_runs_widgets(document_node, node_id, 10, "Style Runs", context.text_selection, true);
+	let kerning = text_kerning_widgets(document_node, node_id, 11, "Kerning", context.text_selection, true);
+
+	let text_variable = variable_binding_widget(context.variables, [context.nested_path, &[node_id]].concat(), 1);
+
+	let mut result = vec![LayoutGroup::Row { widgets: text }];
+	result.extend(text_variable);
+	result.push(LayoutGroup::Row { widgets: font });
 	if let Some(style) = style {
 		result.push(LayoutGroup::Row { widgets: style });
 	}
-	result.push(LayoutGroup::Row { widgets: size });
+	result.extend([
+		LayoutGroup::Row { widgets: size },
+		LayoutGroup::Row { widgets: line_height }.with_tooltip("Distance from one line to the next, as a multiple of the font size"),
+		LayoutGroup::Row { widgets: box_width }.with_tooltip("Width that the words are wrapped within, or 0 to only break lines at new lines"),
+		LayoutGroup::Row { widgets: box_height }.with_tooltip("Height below which lines are left out, or 0 to show every line"),
+		align,
+		auto_resize,
+	]);
+	result.extend(variations);
+	result.extend(runs);
+	result.extend(kerning);
 	result
 }
 
@@ -2145,6 +3663,32 @@ fn unknown_node_properties(document_node: &DocumentNode) -> Vec<LayoutGroup> {
 	string_properties(format!("Node '{}' cannot be found in library", document_node.name))
 }
 
+/// Lists the inputs of a node without a definition, such as a custom node, using a widget chosen by the type of each input's value.
+fn custom_node_properties(document_node: &DocumentNode, node_id: NodeId, network: &NodeNetwork) -> Vec<LayoutGroup> {
+	let layout = document_node
+		.inputs
+		.iter()
+		.enumerate()
+		.filter_map(|(index, input)| {
+			let name = subgraph_import_name(network, index).unwrap_or_else(|| format!("Input {}", index + 1));
+			let widgets = match input.as_value()? {
+				TaggedValue::F64(_) | TaggedValue::U32(_) => number_widget(document_node, node_id, index, &name, NumberInput::default(), true),
+				TaggedValue::Bool(_) => bool_widget(document_node, node_id, index, &name, true),
+				TaggedValue::String(_) => text_widget(document_node, node_id, index, &name, true),
+				TaggedValue::DVec2(_) => return Some(vec2_widget(document_node, node_id, index, &name, "X", "Y", "", None, add_blank_assist)),
+				TaggedValue::Color(_) | TaggedValue::OptionalColor(_) | TaggedValue::GradientStops(_) => return Some(color_widget(document_node, node_id, index, &name, ColorButton::default(), true)),
+				_ => start_widgets(document_node, node_id, index, &name, FrontendGraphDataType::General, true),
+			};
+			Some(LayoutGroup::Row { widgets })
+		})
+		.collect::<Vec<_>>();
+
+	if layout.is_empty() {
+		return string_properties("Custom node has no parameters");
+	}
+	layout
+}
+
 pub fn node_no_properties(document_node: &DocumentNode, _node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	string_properties(if document_node.is_layer { "Layer has no properties" } else { "Node has no properties" })
 }
@@ -2159,7 +3703,10 @@ pub fn generate_node_properties(document_node: &DocumentNode, node_id: NodeId, c
 	let name = document_node.name.clone();
 	let layout = match super::document_node_types::resolve_document_node_type(&name) {
 		Some(document_node_type) => (document_node_type.properties)(document_node, node_id, context),
-		None => unknown_node_properties(document_node),
+		None => match &document_node.implementation {
+			DocumentNodeImplementation::Network(network) => custom_node_properties(document_node, node_id, network),
+			_ => unknown_node_properties(document_node),
+		},
 	};
 	LayoutGroup::Section {
 		name,
@@ -2177,6 +3724,9 @@ pub fn stroke_properties(document_node: &DocumentNode, node_id: NodeId, _context
 	let line_cap_index = 5;
 	let line_join_index = 6;
 	let miter_limit_index = 7;
+	let align_index = 8;
+	let opacity_index = 9;
+	let blend_mode_index = 10;
 
 	let color = color_widget(document_node, node_id, color_index, "Color", ColorButton::default(), true);
 	let weight = number_widget(document_node, node_id, weight_index, "Weight", NumberInput::default().unit("px").min(0.), true);
@@ -2185,6 +3735,9 @@ pub fn stroke_properties(document_node: &DocumentNode, node_id: NodeId, _context
 	let line_cap = line_cap_widget(document_node, node_id, line_cap_index, "Line Cap", true);
 	let line_join = line_join_widget(document_node, node_id, line_join_index, "Line Join", true);
 	let miter_limit = number_widget(document_node, node_id, miter_limit_index, "Miter Limit", NumberInput::default().min(0.), true);
+	let align = stroke_align_widget(document_node, node_id, align_index, "Align", true);
+	let opacity = number_widget(document_node, node_id, opacity_index, "Opacity", NumberInput::default().mode_range().min(0.).max(100.).unit("%"), true);
+	let blend_mode = blend_mode(document_node, node_id, blend_mode_index, "Blend Mode", true);
 
 	vec![
 		color,
@@ -2194,6 +3747,9 @@ pub fn stroke_properties(document_node: &DocumentNode, node_id: NodeId, _context
 		line_cap,
 		line_join,
 		LayoutGroup::Row { widgets: miter_limit },
+		align,
+		LayoutGroup::Row { widgets: opacity },
+		blend_mode,
 	]
 }
 
@@ -2301,11 +3857,19 @@ pub fn morph_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 	]
 }
 
+pub fn split_at_point_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let point = vec2_widget(document_node, node_id, 1, "Point", "X", "Y", " px", None, add_blank_assist);
+
+	vec![point.with_tooltip("The path nearest to this point is cut where it passes closest to it")]
+}
+
 /// Fill Node Widgets LayoutGroup
-pub fn fill_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+pub fn fill_properties(document_node: &DocumentNode, node_id: NodeId, context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let fill_index = 1;
 	let backup_color_index = 2;
 	let backup_gradient_index = 3;
+	let opacity_index = 4;
+	let blend_mode_index = 5;
 
 	let mut widgets_first_row = start_widgets(document_node, node_id, fill_index, "Fill", FrontendGraphDataType::General, true);
 
@@ -2335,11 +3899,27 @@ pub fn fill_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 	let backup_color_fill: Fill = backup_color.clone().into();
 	let backup_gradient_fill: Fill = backup_gradient.clone().into();
 
+	let node_path = [context.nested_path, &[node_id]].concat();
+	let binding = VariableBinding {
+		node_path: node_path.clone(),
+		input_index: fill_index,
+	};
+	let linked_swatch = context.swatches.linked_swatch(&binding).map(|swatch| swatch.id);
+
 	widgets_first_row.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 	widgets_first_row.push(
 		ColorButton::default()
 			.value(fill.clone().into())
 			.on_update(move |x: &ColorButton| {
+				// Editing a fill linked to a swatch edits the swatch, which re-colors every fill linked to it
+				if let Some(id) = linked_swatch {
+					return DocumentMessage::SetSwatchFill {
+						id,
+						fill: x.value.to_fill(fill2.as_gradient()),
+					}
+					.into();
+				}
+
 				Message::Batched(Box::new([
 					match &fill2 {
 						Fill::None => NodeGraphMessage::SetInputValue {
@@ -2397,6 +3977,8 @@ pub fn fill_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 		LayoutGroup::Row { widgets: row }
 	};
 	widgets.push(fill_type_switch);
+	widgets.push(swatch_link_widget(context.swatches, node_path.clone(), fill_index, linked_swatch, fill.clone()));
+	widgets.extend(variable_binding_widget(context.variables, node_path, fill_index));
 
 	if let Fill::Gradient(gradient) = fill {
 		let mut row = vec![TextLabel::new("").widget_holder()];
@@ -2438,18 +4020,337 @@ pub fn fill_properties(document_node: &DocumentNode, node_id: NodeId, _context:
 		]);
 
 		widgets.push(LayoutGroup::Row { widgets: row });
+
+		let hue_interpolation = match gradient.interpolation {
+			GradientInterpolation::Oklch(hue_interpolation) => hue_interpolation,
+			_ => HueInterpolation::default(),
+		};
+		let interpolations = [
+			(GradientInterpolation::Srgb, "sRGB"),
+			(GradientInterpolation::LinearRgb, "Linear RGB"),
+			(GradientInterpolation::Oklab, "OKLab"),
+			(GradientInterpolation::Oklch(hue_interpolation), "OKLCH"),
+			(GradientInterpolation::Lab, "CIELAB"),
+		];
+		let entries = interpolations
+			.iter()
+			.map(|&(interpolation, label)| {
+				let new_gradient = gradient.clone();
+				MenuListEntry::new(label)
+					.label(label)
+					.on_update(update_value(
+						move |_| {
+							TaggedValue::Fill(Fill::Gradient(Gradient {
+								interpolation,
+								..new_gradient.clone()
+							}))
+						},
+						node_id,
+						fill_index,
+					))
+					.on_commit(commit_value)
+			})
+			.collect();
+		let selected_index = interpolations.iter().position(|(interpolation, _)| *interpolation == gradient.interpolation);
+
+		let mut row = vec![TextLabel::new("Interpolation").widget_holder()];
+		add_blank_assist(&mut row);
+		row.extend_from_slice(&[
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries])
+				.selected_index(selected_index.map(|index| index as u32))
+				.tooltip("Color space that the colors between the stops are mixed in")
+				.widget_holder(),
+		]);
+		widgets.push(LayoutGroup::Row { widgets: row });
+
+		if let GradientInterpolation::Oklch(hue_interpolation) = gradient.interpolation {
+			let hue_interpolations = [HueInterpolation::Shorter, HueInterpolation::Longer, HueInterpolation::Increasing, HueInterpolation::Decreasing];
+			let entries = hue_interpolations
+				.iter()
+				.map(|&hue_interpolation| {
+					let new_gradient = gradient.clone();
+					let interpolation = GradientInterpolation::Oklch(hue_interpolation);
+					RadioEntryData::new(format!("{hue_interpolation:?}"))
+						.label(format!("{hue_interpolation:?}"))
+						.on_update(update_value(
+							move |_| {
+								TaggedValue::Fill(Fill::Gradient(Gradient {
+									interpolation,
+									..new_gradient.clone()
+								}))
+							},
+							node_id,
+							fill_index,
+						))
+						.on_commit(commit_value)
+				})
+				.collect();
+
+			let mut row = vec![TextLabel::new("Hue").widget_holder()];
+			add_blank_assist(&mut row);
+			row.extend_from_slice(&[
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				RadioInput::new(entries).selected_index(Some(hue_interpolation as u32)).widget_holder(),
+			]);
+			widgets.push(LayoutGroup::Row { widgets: row });
+		}
 	}
 
+	let opacity = number_widget(document_node, node_id, opacity_index, "Opacity", NumberInput::default().mode_range().min(0.).max(100.).unit("%"), true);
+	widgets.push(LayoutGroup::Row { widgets: opacity });
+	widgets.push(blend_mode(document_node, node_id, blend_mode_index, "Blend Mode", true));
+
 	widgets
 }
 
-pub fn artboard_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+/// A dropdown which binds the input to one of the document's parameters, so it takes on the parameter's value whenever it's edited in the Parameters panel.
+fn parameter_binding_widget(parameters: &DocumentParameters, node_path: Vec<NodeId>, input_index: usize) -> LayoutGroup {
+	let mut widgets = vec![TextLabel::new("Parameter").widget_holder()];
+	add_blank_assist(&mut widgets);
+
+	let binding = VariableBinding {
+		node_path: node_path.clone(),
+		input_index,
+	};
+	let bound_parameter = parameters.bound_parameter(&binding).map(|parameter| parameter.id);
+
+	let unbind_path = node_path.clone();
+	let none = MenuListEntry::new("None").label("None").on_update(move |_| {
+		DocumentMessage::UnbindInputFromParameter {
+			node_path: unbind_path.clone(),
+			input_index,
+		}
+		.into()
+	});
+	let entries = std::iter::once(none)
+		.chain(parameters.parameters.iter().map(|parameter| {
+			let node_path = node_path.clone();
+			let id = parameter.id;
+			MenuListEntry::new(id.to_string()).label(parameter.name.clone()).on_update(move |_| {
+				DocumentMessage::BindInputToParameter {
+					node_path: node_path.clone(),
+					input_index,
+					parameter: id,
+				}
+				.into()
+			})
+		}))
+		.collect();
+	let selected_index = bound_parameter
+		.and_then(|id| parameters.parameters.iter().position(|parameter| parameter.id == id))
+		.map_or(0, |index| index + 1);
+
+	widgets.extend([
+		Separator::new(SeparatorType::Unrelated).widget_holder(),
+		DropdownInput::new(vec![entries])
+			.selected_index(Some(selected_index as u32))
+			.tooltip("Bind the input to a parameter of the document, which gives it the parameter's value whenever it's edited")
+			.widget_holder(),
+	]);
+
+	LayoutGroup::Row { widgets }
+}
+
+/// A dropdown which binds the input to one of the document's variables, so it takes on a different value in each variant, or to a new variable holding its current value.
+/// Returns nothing while the document has no variants to switch between.
+fn variable_binding_widget(variables: &DocumentVariables, node_path: Vec<NodeId>, input_index: usize) -> Option<LayoutGroup> {
+	if variables.is_empty() {
+		return None;
+	}
+
+	let mut widgets = vec![TextLabel::new("Variable").widget_holder()];
+	add_blank_assist(&mut widgets);
+
+	let binding = VariableBinding {
+		node_path: node_path.clone(),
+		input_index,
+	};
+	let bound_variable = variables.bound_variable(&binding).map(|variable| variable.name.as_str());
+
+	let bind = |node_path: Vec<NodeId>, variable: String| {
+		move |_: &()| -> Message {
+			DocumentMessage::BindInputToVariable {
+				node_path: node_path.clone(),
+				input_index,
+				variable: variable.clone(),
+			}
+			.into()
+		}
+	};
+	let unbind_path = node_path.clone();
+	let none = MenuListEntry::new("None").label("None").on_update(move |_| {
+		DocumentMessage::UnbindInputFromVariable {
+			node_path: unbind_path.clone(),
+			input_index,
+		}
+		.into()
+	});
+	let new_name = (1..)
+		.map(|number| format!("Variable {number}"))
+		.find(|name| variables.variables.iter().all(|variable| &variable.name != name))
+		.unwrap_or_default();
+	let new = MenuListEntry::new("New Variable").label("New Variable").on_update(bind(node_path.clone(), new_name));
+	let entries = std::iter::once(none)
+		.chain(variables.variables.iter().map(|variable| {
+			MenuListEntry::new(variable.name.clone())
+				.label(variable.name.clone())
+				.on_update(bind(node_path.clone(), variable.name.clone()))
+		}))
+		.chain(std::iter::once(new))
+		.collect();
+	let selected_index = bound_variable
+		.and_then(|name| variables.variables.iter().position(|variable| variable.name == name))
+		.map_or(0, |index| index + 1);
+
+	widgets.extend([
+		Separator::new(SeparatorType::Unrelated).widget_holder(),
+		DropdownInput::new(vec![entries])
+			.selected_index(Some(selected_index as u32))
+			.tooltip("Bind the input to a variable of the document, which gives it a different value in each variant")
+			.widget_holder(),
+	]);
+
+	Some(LayoutGroup::Row { widgets })
+}
+
+/// A dropdown which links the input to one of the document's swatches, so it takes on the swatch's fill whenever the swatch is edited, and a button which adds the input's fill as a new swatch.
+fn swatch_link_widget(swatches: &DocumentSwatches, node_path: Vec<NodeId>, input_index: usize, linked_swatch: Option<u64>, fill: Fill) -> LayoutGroup {
+	let mut widgets = vec![TextLabel::new("Swatch").widget_holder()];
+	add_blank_assist(&mut widgets);
+
+	let unlink_path = node_path.clone();
+	let none = MenuListEntry::new("None").label("None").on_update(move |_| {
+		DocumentMessage::UnlinkInputFromSwatch {
+			node_path: unlink_path.clone(),
+			input_index,
+		}
+		.into()
+	});
+	let entries = std::iter::once(none)
+		.chain(swatches.library.swatches.iter().map(|swatch| {
+			let node_path = node_path.clone();
+			let id = swatch.id;
+			MenuListEntry::new(id.to_string()).label(swatch.name.clone()).on_update(move |_| {
+				DocumentMessage::LinkInputToSwatch {
+					node_path: node_path.clone(),
+					input_index,
+					swatch: id,
+				}
+				.into()
+			})
+		}))
+		.collect();
+	let selected_index = linked_swatch
+		.and_then(|id| swatches.library.swatches.iter().position(|swatch| swatch.id == id))
+		.map_or(0, |index| index + 1);
+
+	let name = format!("Swatch {}", swatches.library.swatches.len() + 1);
+	widgets.extend([
+		Separator::new(SeparatorType::Unrelated).widget_holder(),
+		DropdownInput::new(vec![entries])
+			.selected_index(Some(selected_index as u32))
+			.tooltip("Link the fill to a swatch of the document, which re-colors it whenever the swatch is edited")
+			.widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		TextButton::new("Add Swatch")
+			.tooltip("Add the fill to the swatches of the document")
+			.on_update(move |_| {
+				DocumentMessage::AddSwatch {
+					name: name.clone(),
+					fill: fill.clone(),
+				}
+				.into()
+			})
+			.widget_holder(),
+	]);
+
+	LayoutGroup::Row { widgets }
+}
+
+fn linked_artboard_widget(document_node: &DocumentNode, node_id: NodeId, index: usize, name: &str, context: &NodePropertiesContext, blank_assist: bool) -> LayoutGroup {
+	let mut widgets = start_widgets(document_node, node_id, index, name, FrontendGraphDataType::Artboard, blank_assist);
+	let linked = match &document_node.inputs[index] {
+		NodeInput::Node { node_id: source, .. } => Some(*source),
+		NodeInput::Value { .. } => None,
+		_ => return LayoutGroup::Row { widgets },
+	};
+	if !context.nested_path.is_empty() {
+		return LayoutGroup::Row { widgets };
+	}
+
+	// Artboards further up the stack take this one's output as their input, so linking to them would make the graph loop back on itself
+	let mut sources = context
+		.metadata
+		.all_artboards()
+		.iter()
+		.map(|artboard| artboard.to_node())
+		.filter(|&artboard| artboard != node_id)
+		.filter(|&artboard| {
+			!context
+				.document_network
+				.upstream_flow_back_from_nodes(vec![artboard], graph_craft::document::FlowType::UpstreamFlow)
+				.any(|(_, upstream)| upstream == node_id)
+		})
+		.filter_map(|artboard| {
+			let node = context.document_network.nodes.get(&artboard)?;
+			let label = if node.alias.is_empty() { node.name.clone() } else { node.alias.clone() };
+			Some((label, artboard))
+		})
+		.collect::<Vec<_>>();
+	sources.sort();
+
+	// The input may be wired to something other than an artboard in the graph, which the dropdown can't show
+	let selected = match linked {
+		Some(linked) => match sources.iter().position(|&(_, source)| source == linked) {
+			Some(position) => position + 1,
+			None => return LayoutGroup::Row { widgets },
+		},
+		None => 0,
+	};
+
+	let link = move |input: NodeInput| {
+		move |_: &()| {
+			Message::Batched(Box::new([
+				NodeGraphMessage::SetNodeInput {
+					node_id,
+					input_index: index,
+					input: input.clone(),
+				}
+				.into(),
+				NodeGraphMessage::RunDocumentGraph.into(),
+			]))
+		}
+	};
+	let none = MenuListEntry::new("None")
+		.label("None")
+		.on_update(link(NodeInput::value(TaggedValue::ArtboardGroup(graphene_core::ArtboardGroup::EMPTY), true)))
+		.on_commit(commit_value);
+	let entries = std::iter::once(none)
+		.chain(sources.into_iter().map(|(label, source)| {
+			MenuListEntry::new(source.0.to_string())
+				.label(label)
+				.on_update(link(NodeInput::node(source, 0)))
+				.on_commit(commit_value)
+		}))
+		.collect();
+
+	widgets.extend_from_slice(&[
+		Separator::new(SeparatorType::Unrelated).widget_holder(),
+		DropdownInput::new(vec![entries]).selected_index(Some(selected as u32)).widget_holder(),
+	]);
+	LayoutGroup::Row { widgets }
+		.with_tooltip("Artboard whose contents this artboard shows in place of its own, so edits to them appear in both, while the location, dimensions, background, and name stay this artboard's own")
+}
+
+pub fn artboard_properties(document_node: &DocumentNode, node_id: NodeId, context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let location = vec2_widget(document_node, node_id, 2, "Location", "X", "Y", " px", None, add_blank_assist);
 	let dimensions = vec2_widget(document_node, node_id, 3, "Dimensions", "W", "H", " px", None, add_blank_assist);
 	let background = color_widget(document_node, node_id, 4, "Background", ColorButton::default().allow_none(false), true);
 	let clip = bool_widget(document_node, node_id, 5, "Clip", true);
 	let clip = LayoutGroup::Row { widgets: clip };
-	vec![location, dimensions, background, clip]
+	let linked = linked_artboard_widget(document_node, node_id, 6, "Linked To", context, true);
+	vec![location, dimensions, background, clip, linked]
 }
 
 pub fn color_fill_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
@@ -2471,8 +4372,94 @@ pub fn image_color_palette(document_node: &DocumentNode, node_id: NodeId, _conte
 	vec![LayoutGroup::Row { widgets: size }]
 }
 
+pub fn color_harmony_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let harmony = color_harmony(document_node, node_id, 1, "Harmony", true);
+
+	// Only analogous palettes spread their hues apart by a chosen angle
+	let analogous = matches!(
+		&document_node.inputs[1],
+		NodeInput::Value {
+			tagged_value: TaggedValue::ColorHarmony(ColorHarmony::Analogous),
+			..
+		}
+	);
+	let spread = number_widget(document_node, node_id, 2, "Spread", NumberInput::default().min(0.).max(180.).unit("°").disabled(!analogous), true);
+
+	vec![harmony, LayoutGroup::Row { widgets: spread }]
+}
+
+pub fn stitch_panorama_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let feather = number_widget(document_node, node_id, 1, "Feather", NumberInput::default().mode_range().min(0.).max(100.).unit("%"), true);
+
+	vec![LayoutGroup::Row { widgets: feather }]
+}
+
+pub fn focus_stack_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let smoothness = number_widget(document_node, node_id, 1, "Smoothness", NumberInput::default().unit(" px").min(0.).max(100.), true);
+
+	vec![LayoutGroup::Row { widgets: smoothness }]
+}
+
+pub fn exposure_fusion_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let contrast = number_widget(document_node, node_id, 1, "Contrast Weight", NumberInput::default().min(0.), true);
+	let saturation = number_widget(document_node, node_id, 2, "Saturation Weight", NumberInput::default().min(0.), true);
+	let exposure = number_widget(document_node, node_id, 3, "Exposure Weight", NumberInput::default().min(0.), true);
+
+	vec![LayoutGroup::Row { widgets: contrast }, LayoutGroup::Row { widgets: saturation }, LayoutGroup::Row { widgets: exposure }]
+}
+
 pub fn centroid_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let centroid_type = centroid_widget(document_node, node_id, 1);
 
 	vec![centroid_type]
 }
+
+pub fn contact_sheet_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let columns = number_widget(document_node, node_id, 2, "Columns", NumberInput::default().int().min(1.), true);
+	let gutter = number_widget(document_node, node_id, 3, "Gutter", NumberInput::default().unit(" px").min(0.), true);
+	let cell_size = vec2_widget(document_node, node_id, 4, "Cell Size", "W", "H", " px", Some(1.), add_blank_assist);
+	let fit = cell_fit_widget(document_node, node_id, 5, "Fit", true);
+	let captions = text_area_widget(document_node, node_id, 6, "Captions", true);
+	let (font, style) = font_inputs(document_node, node_id, 7, "Font", true);
+	let font_size = number_widget(document_node, node_id, 8, "Font Size", NumberInput::default().unit(" px").min(1.), true);
+
+	let mut result = vec![
+		LayoutGroup::Row { widgets: columns },
+		LayoutGroup::Row { widgets: gutter },
+		cell_size,
+		fit,
+		LayoutGroup::Row { widgets: captions },
+		LayoutGroup::Row { widgets: font },
+	];
+	if let Some(style) = style {
+		result.push(LayoutGroup::Row { widgets: style });
+	}
+	result.push(LayoutGroup::Row { widgets: font_size });
+	result
+}
+
+pub fn dimension_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let from_point = number_widget(document_node, node_id, 1, "From Point", NumberInput::default().int().min(0.), true);
+	let to_point = number_widget(document_node, node_id, 3, "To Point", NumberInput::default().int().min(0.), true);
+	let kind = dimension_kind_widget(document_node, node_id, 4, "Kind", true);
+	let offset = number_widget(document_node, node_id, 5, "Offset", NumberInput::default().unit(" px"), true);
+	let precision = number_widget(document_node, node_id, 6, "Precision", NumberInput::default().int().min(0.).max(6.), true);
+	let color = color_widget(document_node, node_id, 7, "Color", ColorButton::default(), true);
+
+	vec![
+		LayoutGroup::Row { widgets: from_point },
+		LayoutGroup::Row { widgets: to_point },
+		kind,
+		LayoutGroup::Row { widgets: offset },
+		LayoutGroup::Row { widgets: precision },
+		color,
+	]
+}
+
+pub fn mask_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let mode = mask_mode_widget(document_node, node_id, 2, "Mode", true);
+	let invert = bool_widget(document_node, node_id, 3, "Invert", true);
+	let feather = number_widget(document_node, node_id, 4, "Feather", NumberInput::default().unit(" px").min(0.), true);
+
+	vec![mode, LayoutGroup::Row { widgets: invert }, LayoutGroup::Row { widgets: feather }]
+}
@@ -81,6 +81,8 @@ pub struct FrontendNode {
 	pub errors: Option<String>,
 	#[serde(rename = "uiOnly")]
 	pub ui_only: bool,
+	#[serde(rename = "isReroute")]
+	pub is_reroute: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
@@ -96,6 +98,40 @@ pub struct FrontendNodeWire {
 	pub dashed: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendCommentFrame {
+	pub id: u64,
+	pub title: String,
+	/// The color as an RGBA hex string with a # prefix.
+	pub color: String,
+	pub position: (i32, i32),
+	pub size: (i32, i32),
+	pub selected: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendGraphSearchResult {
+	#[serde(rename = "nodePath")]
+	pub node_path: Vec<NodeId>,
+	pub name: String,
+	/// Describes what matched the query, such as the type or the value of an input.
+	#[serde(rename = "foundBy")]
+	pub found_by: String,
+	pub position: (i32, i32),
+}
+
+/// What the inspected node output when the graph was last evaluated, described by labeled values such as the number of points in a path or the size of an image.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendNodeInspection {
+	#[serde(rename = "nodePath")]
+	pub node_path: Vec<NodeId>,
+	pub name: String,
+	#[serde(rename = "outputType")]
+	pub output_type: Option<String>,
+	/// Empty if the node hasn't been evaluated since it began being inspected.
+	pub details: Vec<(String, String)>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
 pub struct FrontendNodeType {
 	pub name: String,
@@ -110,6 +146,40 @@ impl FrontendNodeType {
 		}
 	}
 }
+/// The time spent evaluating one document node during the last execution of the graph.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendNodeProfile {
+	#[serde(rename = "nodePath")]
+	pub node_path: Vec<NodeId>,
+	pub name: String,
+	pub evaluations: u32,
+	#[serde(rename = "totalTime")]
+	pub total_time: f64,
+	#[serde(rename = "selfTime")]
+	pub self_time: f64,
+	#[serde(rename = "cacheHits")]
+	pub cache_hits: u32,
+	#[serde(rename = "cacheMisses")]
+	pub cache_misses: u32,
+}
+
+/// One bar of the flame graph, where `node` is an index into [`FrontendFrameProfile::nodes`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendNodeEvaluation {
+	pub node: usize,
+	pub start: f64,
+	pub duration: f64,
+	pub depth: usize,
+}
+
+/// The node timings of the last execution of the graph, in milliseconds.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FrontendFrameProfile {
+	pub duration: f64,
+	pub nodes: Vec<FrontendNodeProfile>,
+	pub evaluations: Vec<FrontendNodeEvaluation>,
+}
+
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
 pub struct DragStart {
 	pub start_x: f64,
@@ -118,6 +188,16 @@ pub struct DragStart {
 	pub round_y: i32,
 }
 
+/// A comment frame being dragged by its title bar or resized by its bottom right corner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommentFrameDrag {
+	pub id: u64,
+	pub resizing: bool,
+	pub start: DragStart,
+	/// The nodes inside the frame when the drag began, which move along with it.
+	pub nodes: Vec<NodeId>,
+}
+
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
 pub struct Transform {
 	pub scale: f64,
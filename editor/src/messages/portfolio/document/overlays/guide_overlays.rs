@@ -0,0 +1,33 @@
+use crate::consts::COLOR_OVERLAY_MAGENTA;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::prelude::*;
+
+use graphene_core::renderer::Quad;
+
+use glam::DVec2;
+
+/// How many straight lines each curve of a path guide is approximated with when drawn.
+const GUIDE_CURVE_STEPS: usize = 16;
+
+pub fn guide_overlay(document: &DocumentMessageHandler, overlay_context: &mut OverlayContext) {
+	let document_to_viewport = document.metadata().document_to_viewport;
+	let bounds = (document_to_viewport.inverse() * Quad::from_box([DVec2::ZERO, overlay_context.size])).bounding_box();
+
+	for guide in &document.guides.guides {
+		for curve in guide.curves(bounds) {
+			let points = if curve.is_linear() {
+				vec![curve.start(), curve.end()]
+			} else {
+				curve.compute_lookup_table(Some(GUIDE_CURVE_STEPS), None).collect()
+			};
+			for segment in points.windows(2) {
+				overlay_context.line(
+					document_to_viewport.transform_point2(segment[0]),
+					document_to_viewport.transform_point2(segment[1]),
+					Some(COLOR_OVERLAY_MAGENTA),
+					None,
+				);
+			}
+		}
+	}
+}
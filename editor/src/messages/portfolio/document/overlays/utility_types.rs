@@ -2,8 +2,8 @@ use super::utility_functions::overlay_canvas_context;
 use crate::consts::{COLOR_OVERLAY_BLUE, COLOR_OVERLAY_WHITE, COLOR_OVERLAY_YELLOW, MANIPULATOR_GROUP_MARKER_SIZE, PIVOT_CROSSHAIR_LENGTH, PIVOT_CROSSHAIR_THICKNESS, PIVOT_DIAMETER};
 use crate::messages::prelude::Message;
 
-use bezier_rs::Subpath;
-use graphene_core::renderer::Quad;
+use bezier_rs::{BezierHandles, Subpath};
+use graphene_core::renderer::{OverlayLayer, OverlayShape, Quad};
 use wasm_bindgen::JsValue;
 
 use core::f64::consts::TAU;
@@ -30,51 +30,54 @@ impl core::hash::Hash for OverlayContext {
 
 impl OverlayContext {
 	pub fn quad(&mut self, quad: Quad) {
-		self.render_context.begin_path();
-		self.render_context.move_to(quad.0[3].x.round() - 0.5, quad.0[3].y.round() - 0.5);
-		for i in 0..4 {
-			self.render_context.line_to(quad.0[i].x.round() - 0.5, quad.0[i].y.round() - 0.5);
-		}
-		self.render_context.set_stroke_style(&wasm_bindgen::JsValue::from_str(COLOR_OVERLAY_BLUE));
-		self.render_context.stroke();
+		self.draw(&OverlayShape::Polyline {
+			points: quad.0.iter().map(|&point| snap_to_pixel(point)).collect(),
+			closed: true,
+			color: COLOR_OVERLAY_BLUE.into(),
+		});
 	}
 
 	pub fn line(&mut self, start: DVec2, end: DVec2, color: Option<&str>, dash_width: Option<f64>) {
-		let start = start.round() - DVec2::splat(0.5);
-		let end = end.round() - DVec2::splat(0.5);
-		if let Some(dash_width) = dash_width {
-			let array = js_sys::Array::new();
-			array.push(&JsValue::from(1));
-			array.push(&JsValue::from(dash_width - 1.));
-			self.render_context
-				.set_line_dash(&JsValue::from(array))
-				.map_err(|error| log::warn!("Error drawing dashed line: {:?}", error))
-				.ok();
-		} else {
-			let array = js_sys::Array::new();
-			self.render_context
-				.set_line_dash(&JsValue::from(array))
-				.map_err(|error| log::warn!("Error drawing dashed line: {:?}", error))
-				.ok();
-		}
-		self.render_context.begin_path();
-		self.render_context.move_to(start.x, start.y);
-		self.render_context.line_to(end.x, end.y);
-		self.render_context.set_stroke_style(&wasm_bindgen::JsValue::from_str(color.unwrap_or(COLOR_OVERLAY_BLUE)));
-		self.render_context.stroke();
+		self.draw(&OverlayShape::Line {
+			start: snap_to_pixel(start),
+			end: snap_to_pixel(end),
+			color: color.unwrap_or(COLOR_OVERLAY_BLUE).into(),
+			dash_width,
+		});
 	}
 
-	pub fn manipulator_handle(&mut self, position: DVec2, selected: bool) {
-		let position = position.round() - DVec2::splat(0.5);
+	/// A wavy line zigzagging along from the start to the end, as drawn under misspelled words.
+	pub fn squiggle(&mut self, start: DVec2, end: DVec2, color: Option<&str>) {
+		const WAVELENGTH: f64 = 4.;
+		const AMPLITUDE: f64 = 1.5;
 
-		self.render_context.begin_path();
-		self.render_context.arc(position.x, position.y, MANIPULATOR_GROUP_MARKER_SIZE / 2., 0., TAU).expect("draw circle");
+		let length = start.distance(end);
+		if length < f64::EPSILON {
+			return;
+		}
+		let direction = (end - start) / length;
+		let normal = direction.perp();
+
+		let steps = (length / (WAVELENGTH / 2.)).ceil() as usize;
+		let zigzag = (1..=steps).map(|step| {
+			let side = if step % 2 == 0 { -AMPLITUDE } else { AMPLITUDE };
+			start + direction * (length * step as f64 / steps as f64) + normal * side
+		});
+		self.draw(&OverlayShape::Polyline {
+			points: std::iter::once(start).chain(zigzag).collect(),
+			closed: false,
+			color: color.unwrap_or(COLOR_OVERLAY_BLUE).into(),
+		});
+	}
 
+	pub fn manipulator_handle(&mut self, position: DVec2, selected: bool) {
 		let fill = if selected { COLOR_OVERLAY_BLUE } else { COLOR_OVERLAY_WHITE };
-		self.render_context.set_fill_style(&wasm_bindgen::JsValue::from_str(fill));
-		self.render_context.set_stroke_style(&wasm_bindgen::JsValue::from_str(COLOR_OVERLAY_BLUE));
-		self.render_context.fill();
-		self.render_context.stroke();
+		self.draw(&OverlayShape::Circle {
+			center: snap_to_pixel(position),
+			radius: MANIPULATOR_GROUP_MARKER_SIZE / 2.,
+			fill: Some(fill.into()),
+			stroke: Some(COLOR_OVERLAY_BLUE.into()),
+		});
 	}
 
 	pub fn manipulator_anchor(&mut self, position: DVec2, selected: bool, color: Option<&str>) {
@@ -84,135 +87,200 @@ impl OverlayContext {
 	}
 
 	pub fn square(&mut self, position: DVec2, size: Option<f64>, color_fill: Option<&str>, color_stroke: Option<&str>) {
-		let size = size.unwrap_or(MANIPULATOR_GROUP_MARKER_SIZE);
-		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
-		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
-
-		let position = position.round() - DVec2::splat(0.5);
-		let corner = position - DVec2::splat(size) / 2.;
-
-		self.render_context.begin_path();
-		self.render_context.rect(corner.x, corner.y, size, size);
-		self.render_context.set_fill_style(&wasm_bindgen::JsValue::from_str(color_fill));
-		self.render_context.set_stroke_style(&wasm_bindgen::JsValue::from_str(color_stroke));
-		self.render_context.fill();
-		self.render_context.stroke();
+		self.draw(&OverlayShape::Square {
+			center: snap_to_pixel(position),
+			size: size.unwrap_or(MANIPULATOR_GROUP_MARKER_SIZE),
+			fill: Some(color_fill.unwrap_or(COLOR_OVERLAY_WHITE).into()),
+			stroke: Some(color_stroke.unwrap_or(COLOR_OVERLAY_BLUE).into()),
+		});
 	}
 
 	pub fn pixel(&mut self, position: DVec2, color: Option<&str>) {
-		let size = 1.;
-		let color_fill = color.unwrap_or(COLOR_OVERLAY_WHITE);
-
-		let position = position.round() - DVec2::splat(0.5);
-		let corner = position - DVec2::splat(size) / 2.;
-
-		self.render_context.begin_path();
-		self.render_context.rect(corner.x, corner.y, size, size);
-		self.render_context.set_fill_style(&wasm_bindgen::JsValue::from_str(color_fill));
-		self.render_context.fill();
+		self.draw(&OverlayShape::Square {
+			center: snap_to_pixel(position),
+			size: 1.,
+			fill: Some(color.unwrap_or(COLOR_OVERLAY_WHITE).into()),
+			stroke: None,
+		});
 	}
 
 	pub fn circle(&mut self, position: DVec2, radius: f64, color_fill: Option<&str>, color_stroke: Option<&str>) {
-		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
-		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
-		let position = position.round();
-		self.render_context.begin_path();
-		self.render_context.arc(position.x, position.y, radius, 0., TAU).expect("draw circle");
-		self.render_context.set_fill_style(&wasm_bindgen::JsValue::from_str(color_fill));
-		self.render_context.set_stroke_style(&wasm_bindgen::JsValue::from_str(color_stroke));
-		self.render_context.fill();
-		self.render_context.stroke();
+		self.draw(&OverlayShape::Circle {
+			center: position.round(),
+			radius,
+			fill: Some(color_fill.unwrap_or(COLOR_OVERLAY_WHITE).into()),
+			stroke: Some(color_stroke.unwrap_or(COLOR_OVERLAY_BLUE).into()),
+		});
 	}
-	pub fn pivot(&mut self, position: DVec2) {
-		let (x, y) = (position.round() - DVec2::splat(0.5)).into();
-
-		// Circle
 
-		self.render_context.begin_path();
-		self.render_context.arc(x, y, PIVOT_DIAMETER / 2., 0., TAU).expect("draw circle");
-		self.render_context.set_fill_style(&wasm_bindgen::JsValue::from_str(COLOR_OVERLAY_YELLOW));
-		self.render_context.fill();
-
-		// Crosshair
-
-		// Round line caps add half the stroke width to the length on each end, so we subtract that here before halving to get the radius
-		let crosshair_radius = (PIVOT_CROSSHAIR_LENGTH - PIVOT_CROSSHAIR_THICKNESS) / 2.;
+	pub fn pivot(&mut self, position: DVec2) {
+		self.draw(&OverlayShape::Pivot {
+			center: snap_to_pixel(position),
+			radius: PIVOT_DIAMETER / 2.,
+			// Round line caps add half the stroke width to the length on each end, so we subtract that here before halving to get the radius
+			crosshair_radius: (PIVOT_CROSSHAIR_LENGTH - PIVOT_CROSSHAIR_THICKNESS) / 2.,
+			color: COLOR_OVERLAY_YELLOW.into(),
+		});
+	}
 
-		self.render_context.set_stroke_style(&wasm_bindgen::JsValue::from_str(COLOR_OVERLAY_YELLOW));
-		self.render_context.set_line_cap("round");
+	pub fn outline<'a, Id: bezier_rs::Identifier>(&mut self, subpaths: impl Iterator<Item = &'a Subpath<Id>>, transform: DAffine2) {
+		self.draw(&outline_shape(subpaths, transform, COLOR_OVERLAY_BLUE));
+	}
 
-		self.render_context.begin_path();
-		self.render_context.move_to(x - crosshair_radius, y);
-		self.render_context.line_to(x + crosshair_radius, y);
-		self.render_context.stroke();
+	pub fn text(&self, text: &str, pos: DVec2, background: &str, padding: f64) {
+		self.draw(&OverlayShape::Text {
+			text: text.into(),
+			position: pos.round(),
+			background: background.into(),
+			padding,
+		});
+	}
 
-		self.render_context.begin_path();
-		self.render_context.move_to(x, y - crosshair_radius);
-		self.render_context.line_to(x, y + crosshair_radius);
-		self.render_context.stroke();
+	/// Draws the overlays which a tool has described as data.
+	pub fn draw_layer(&self, layer: &OverlayLayer) {
+		for shape in layer.shapes() {
+			self.draw(shape);
+		}
 	}
 
-	pub fn outline<'a, Id: bezier_rs::Identifier>(&mut self, subpaths: impl Iterator<Item = &'a Subpath<Id>>, transform: DAffine2) {
-		self.render_context.begin_path();
-		for subpath in subpaths {
-			let mut curves = subpath.iter().peekable();
-
-			let Some(first) = curves.peek() else {
-				continue;
-			};
-
-			self.render_context.move_to(transform.transform_point2(first.start()).x, transform.transform_point2(first.start()).y);
-			for curve in curves {
-				match curve.handles {
-					bezier_rs::BezierHandles::Linear => {
-						let a = transform.transform_point2(curve.end());
-						let a = a.round() - DVec2::splat(0.5);
-
-						self.render_context.line_to(a.x, a.y)
-					}
-					bezier_rs::BezierHandles::Quadratic { handle } => {
-						let a = transform.transform_point2(handle);
-						let b = transform.transform_point2(curve.end());
-						let a = a.round() - DVec2::splat(0.5);
-						let b = b.round() - DVec2::splat(0.5);
+	/// Draws a shape into the overlay canvas.
+	pub fn draw(&self, shape: &OverlayShape) {
+		let context = &self.render_context;
+
+		// Only lines are dashed and only the pivot's crosshair has round caps, so the dashes and caps of earlier shapes are reset
+		let dash = js_sys::Array::new();
+		if let OverlayShape::Line { dash_width: Some(dash_width), .. } = shape {
+			dash.push(&JsValue::from(1));
+			dash.push(&JsValue::from(dash_width - 1.));
+		}
+		context.set_line_dash(&JsValue::from(dash)).map_err(|error| log::warn!("Error drawing dashed line: {:?}", error)).ok();
+		context.set_line_cap(if matches!(shape, OverlayShape::Pivot { .. }) { "round" } else { "butt" });
 
-						self.render_context.quadratic_curve_to(a.x, a.y, b.x, b.y)
+		let paint = |fill: &Option<String>, stroke: &Option<String>| {
+			if let Some(fill) = fill {
+				context.set_fill_style(&JsValue::from_str(fill));
+				context.fill();
+			}
+			if let Some(stroke) = stroke {
+				context.set_stroke_style(&JsValue::from_str(stroke));
+				context.stroke();
+			}
+		};
+
+		match shape {
+			OverlayShape::Line { start, end, color, .. } => {
+				context.begin_path();
+				context.move_to(start.x, start.y);
+				context.line_to(end.x, end.y);
+				context.set_stroke_style(&JsValue::from_str(color));
+				context.stroke();
+			}
+			OverlayShape::Polyline { points, closed, color } => {
+				let Some(first) = points.first() else { return };
+				context.begin_path();
+				context.move_to(first.x, first.y);
+				for point in &points[1..] {
+					context.line_to(point.x, point.y);
+				}
+				if *closed {
+					context.close_path();
+				}
+				context.set_stroke_style(&JsValue::from_str(color));
+				context.stroke();
+			}
+			OverlayShape::Circle { center, radius, fill, stroke } => {
+				context.begin_path();
+				context.arc(center.x, center.y, *radius, 0., TAU).expect("draw circle");
+				paint(fill, stroke);
+			}
+			OverlayShape::Square { center, size, fill, stroke } => {
+				let corner = *center - DVec2::splat(*size) / 2.;
+				context.begin_path();
+				context.rect(corner.x, corner.y, *size, *size);
+				paint(fill, stroke);
+			}
+			OverlayShape::Pivot {
+				center,
+				radius,
+				crosshair_radius,
+				color,
+			} => {
+				let (x, y) = (*center).into();
+				let color = JsValue::from_str(color);
+
+				// Circle
+
+				context.begin_path();
+				context.arc(x, y, *radius, 0., TAU).expect("draw circle");
+				context.set_fill_style(&color);
+				context.fill();
+
+				// Crosshair
+
+				context.set_stroke_style(&color);
+
+				context.begin_path();
+				context.move_to(x - crosshair_radius, y);
+				context.line_to(x + crosshair_radius, y);
+				context.stroke();
+
+				context.begin_path();
+				context.move_to(x, y - crosshair_radius);
+				context.line_to(x, y + crosshair_radius);
+				context.stroke();
+			}
+			OverlayShape::Outline { subpaths, color } => {
+				context.begin_path();
+				for (curves, closed) in subpaths {
+					let Some(first) = curves.first() else { continue };
+					context.move_to(first.start.x, first.start.y);
+					for curve in curves {
+						match curve.handles {
+							BezierHandles::Linear => context.line_to(curve.end.x, curve.end.y),
+							BezierHandles::Quadratic { handle } => context.quadratic_curve_to(handle.x, handle.y, curve.end.x, curve.end.y),
+							BezierHandles::Cubic { handle_start, handle_end } => context.bezier_curve_to(handle_start.x, handle_start.y, handle_end.x, handle_end.y, curve.end.x, curve.end.y),
+						}
 					}
-					bezier_rs::BezierHandles::Cubic { handle_start, handle_end } => {
-						let a = transform.transform_point2(handle_start);
-						let b = transform.transform_point2(handle_end);
-						let c = transform.transform_point2(curve.end());
-						let a = a.round() - DVec2::splat(0.5);
-						let b = b.round() - DVec2::splat(0.5);
-						let c = c.round() - DVec2::splat(0.5);
-
-						self.render_context.bezier_curve_to(a.x, a.y, b.x, b.y, c.x, c.y)
+					if *closed {
+						context.close_path();
 					}
 				}
+				context.set_stroke_style(&JsValue::from_str(color));
+				context.stroke();
 			}
-
-			if subpath.closed() {
-				self.render_context.close_path();
+			OverlayShape::Text { text, position, background, padding } => {
+				let metrics = context.measure_text(text).expect("measure text");
+				context.set_fill_style(&background.as_str().into());
+				context.fill_rect(
+					position.x + metrics.actual_bounding_box_left(),
+					position.y - metrics.font_bounding_box_ascent() - metrics.font_bounding_box_descent() - padding * 2.,
+					metrics.actual_bounding_box_right() - metrics.actual_bounding_box_left() + padding * 2.,
+					metrics.font_bounding_box_ascent() + metrics.font_bounding_box_descent() + padding * 2.,
+				);
+				context.set_fill_style(&"white".into());
+				context
+					.fill_text(text, position.x + padding, position.y - padding - metrics.font_bounding_box_descent())
+					.expect("draw text");
 			}
 		}
-
-		self.render_context.set_stroke_style(&wasm_bindgen::JsValue::from_str(COLOR_OVERLAY_BLUE));
-		self.render_context.stroke();
 	}
+}
 
-	pub fn text(&self, text: &str, pos: DVec2, background: &str, padding: f64) {
-		let pos = pos.round();
-		let metrics = self.render_context.measure_text(text).expect("measure text");
-		self.render_context.set_fill_style(&background.into());
-		self.render_context.fill_rect(
-			pos.x + metrics.actual_bounding_box_left(),
-			pos.y - metrics.font_bounding_box_ascent() - metrics.font_bounding_box_descent() - padding * 2.,
-			metrics.actual_bounding_box_right() - metrics.actual_bounding_box_left() + padding * 2.,
-			metrics.font_bounding_box_ascent() + metrics.font_bounding_box_descent() + padding * 2.,
-		);
-		self.render_context.set_fill_style(&"white".into());
-		self.render_context
-			.fill_text(text, pos.x + padding, pos.y - padding - metrics.font_bounding_box_descent())
-			.expect("draw text");
-	}
+/// Moves a point in viewport space to the center of the pixel it's in, so one pixel wide lines through it are crisp rather than blurred across two pixels.
+pub fn snap_to_pixel(point: DVec2) -> DVec2 {
+	point.round() - DVec2::splat(0.5)
+}
+
+/// The outline of the subpaths as an overlay shape in viewport space, with their points snapped to pixels.
+pub fn outline_shape<'a, Id: bezier_rs::Identifier>(subpaths: impl Iterator<Item = &'a Subpath<Id>>, transform: DAffine2, color: &str) -> OverlayShape {
+	let subpaths = subpaths
+		.map(|subpath| {
+			let curves = subpath
+				.iter()
+				.map(|curve| curve.apply_transformation(|point| snap_to_pixel(transform.transform_point2(point))))
+				.collect();
+			(curves, subpath.closed())
+		})
+		.collect();
+	OverlayShape::Outline { subpaths, color: color.into() }
 }
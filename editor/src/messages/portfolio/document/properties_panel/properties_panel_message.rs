@@ -1,3 +1,4 @@
+use super::utility_types::TextSelection;
 use crate::messages::prelude::*;
 
 #[impl_message(Message, DocumentMessage, PropertiesPanel)]
@@ -6,4 +7,5 @@ pub enum PropertiesPanelMessage {
 	// Messages
 	Clear,
 	Refresh,
+	SetTextSelection { selection: Option<TextSelection> },
 }
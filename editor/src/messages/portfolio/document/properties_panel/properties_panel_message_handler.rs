@@ -1,11 +1,18 @@
-use super::utility_types::PropertiesPanelMessageHandlerData;
+use super::utility_types::{PropertiesPanelMessageHandlerData, TextSelection};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::document_node_types::NodePropertiesContext;
+use crate::messages::portfolio::document::utility_types::document_info::DocumentInfo;
+use crate::messages::portfolio::document::utility_types::node_presets::NodePresetLibrary;
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::*;
 
+use graph_craft::document::DocumentNode;
+
 #[derive(Debug, Clone, Default)]
-pub struct PropertiesPanelMessageHandler {}
+pub struct PropertiesPanelMessageHandler {
+	/// The text selected in the Text node being edited, whose style runs are shown in the panel.
+	text_selection: Option<TextSelection>,
+}
 
 impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPanelMessageHandlerData<'a>)> for PropertiesPanelMessageHandler {
 	fn process_message(&mut self, message: PropertiesPanelMessage, responses: &mut VecDeque<Message>, (persistent_data, data): (&PersistentData, PropertiesPanelMessageHandlerData)) {
@@ -14,8 +21,15 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 			executor,
 			document_network: network,
 			document_metadata: metadata,
+			document_swatches: swatches,
+			document_parameters: parameters,
+			document_variables: variables,
+			node_presets,
 			selected_nodes,
 			document_name,
+			document_info,
+			artboard_relative_coordinates,
+			units,
 		} = data;
 
 		match message {
@@ -37,19 +51,36 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 					executor,
 					document_network: network,
 					metadata,
+					swatches,
+					parameters,
+					variables,
+					artboard_relative_coordinates,
+					units,
+					text_selection: self.text_selection.as_ref(),
 				};
 
-				let properties_sections = node_graph_message_handler.collate_properties(&mut context, selected_nodes);
+				// With nothing selected, the panel shows the properties of the document itself
+				let properties_sections = if selected_nodes.selected_nodes_ref().is_empty() {
+					document_info_properties(document_info)
+				} else {
+					node_graph_message_handler.collate_properties(&mut context, selected_nodes)
+				};
 
-				let options_bar = vec![LayoutGroup::Row {
-					widgets: vec![
-						IconLabel::new("File").tooltip("Document name").widget_holder(),
-						Separator::new(SeparatorType::Related).widget_holder(),
-						TextInput::new(document_name)
-							.on_update(|text_input| DocumentMessage::RenameDocument { new_name: text_input.value.clone() }.into())
-							.widget_holder(),
-					],
-				}];
+				let mut options_bar_widgets = vec![
+					IconLabel::new("File").tooltip("Document name").widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextInput::new(document_name)
+						.on_update(|text_input| DocumentMessage::RenameDocument { new_name: text_input.value.clone() }.into())
+						.widget_holder(),
+				];
+				let selected_network = network.nested_network_for_selected_nodes(&node_graph_message_handler.network, selected_nodes.selected_nodes_ref().iter());
+				if let Some(selected_network) = selected_network {
+					let nodes = selected_nodes.selected_nodes_ref().iter().filter_map(|node_id| selected_network.nodes.get(node_id)).collect::<Vec<_>>();
+					if !nodes.is_empty() {
+						options_bar_widgets.extend(node_preset_widgets(node_presets, &nodes));
+					}
+				}
+				let options_bar = vec![LayoutGroup::Row { widgets: options_bar_widgets }];
 
 				context.responses.add(LayoutMessage::SendLayout {
 					layout: Layout::WidgetLayout(WidgetLayout::new(options_bar)),
@@ -60,6 +91,12 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 					layout_target: LayoutTarget::PropertiesSections,
 				});
 			}
+			PropertiesPanelMessage::SetTextSelection { selection } => {
+				if self.text_selection != selection {
+					self.text_selection = selection;
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+			}
 		}
 	}
 
@@ -67,3 +104,86 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 		actions!(PropertiesMessageDiscriminant;)
 	}
 }
+
+/// A dropdown of the presets which can be applied to the selected nodes, and a button which saves their input values as a new preset.
+fn node_preset_widgets(node_presets: &NodePresetLibrary, nodes: &[&DocumentNode]) -> Vec<WidgetHolder> {
+	let entries = node_presets
+		.applicable(nodes)
+		.map(|preset| {
+			let preset_nodes = preset.nodes.clone();
+			MenuListEntry::new(preset.id.to_string())
+				.label(preset.name.clone())
+				.on_update(move |_| DocumentMessage::ApplyNodePreset { nodes: preset_nodes.clone() }.into())
+		})
+		.collect::<Vec<_>>();
+	let no_presets = entries.is_empty();
+
+	let number = node_presets.presets.len() + 1;
+	let name = match nodes {
+		[node] => format!("{} Preset {number}", node.name),
+		_ => format!("Preset {number}"),
+	};
+
+	vec![
+		Separator::new(SeparatorType::Unrelated).widget_holder(),
+		DropdownInput::new(vec![entries])
+			.selected_index(None)
+			.disabled(no_presets)
+			.tooltip(if no_presets {
+				"No presets have been saved from nodes of these kinds"
+			} else {
+				"Apply a preset to the selected nodes, which take the values saved from nodes of the same kind"
+			})
+			.widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		TextButton::new("Save Preset")
+			.tooltip("Save the input values of the selected nodes as a preset, which is kept in the preferences")
+			.on_update(move |_| DocumentMessage::SaveNodePreset { name: name.clone() }.into())
+			.widget_holder(),
+	]
+}
+
+/// The fields of the document's info, which is embedded into its exports.
+fn document_info_properties(info: &DocumentInfo) -> Vec<LayoutGroup> {
+	let text_row = |name: &str, tooltip: &str, value: String, update: fn(&mut DocumentInfo, &str)| {
+		let info = info.clone();
+		LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new(name).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextInput::new(value)
+					.on_update(move |text_input: &TextInput| {
+						let mut info = info.clone();
+						update(&mut info, &text_input.value);
+						DocumentMessage::UpdateDocumentInfo { info }.into()
+					})
+					.widget_holder(),
+			],
+		}
+		.with_tooltip(tooltip)
+	};
+
+	vec![
+		LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Document Info").bold(true).widget_holder()],
+		},
+		text_row("Title", "Title of the artwork, embedded into exported files", info.title.clone(), |info, text| {
+			info.title = text.to_string()
+		}),
+		text_row("Author", "Creator of the artwork, embedded into exported files", info.author.clone(), |info, text| {
+			info.author = text.to_string()
+		}),
+		text_row(
+			"License",
+			"License or copyright notice that the artwork is distributed under, embedded into exported files",
+			info.license.clone(),
+			|info, text| info.license = text.to_string(),
+		),
+		text_row(
+			"Keywords",
+			"Comma separated keywords that asset management systems can find the artwork by, embedded into exported files",
+			info.keywords.join(", "),
+			|info, text| info.keywords = DocumentInfo::parse_keywords(text),
+		),
+	]
+}
@@ -1,15 +1,37 @@
+use crate::messages::portfolio::document::utility_types::document_info::DocumentInfo;
 use crate::messages::portfolio::document::utility_types::document_metadata::DocumentMetadata;
+use crate::messages::portfolio::document::utility_types::node_presets::NodePresetLibrary;
 use crate::messages::portfolio::document::utility_types::nodes::SelectedNodes;
+use crate::messages::portfolio::document::utility_types::parameters::DocumentParameters;
+use crate::messages::portfolio::document::utility_types::swatches::DocumentSwatches;
+use crate::messages::portfolio::document::utility_types::variables::DocumentVariables;
 use crate::messages::prelude::NodeGraphMessageHandler;
 use crate::node_graph_executor::NodeGraphExecutor;
 
-use graph_craft::document::NodeNetwork;
+use graph_craft::document::{NodeId, NodeNetwork};
+use graphene_core::units::DocumentUnits;
 
 pub struct PropertiesPanelMessageHandlerData<'a> {
 	pub document_name: &'a str,
+	pub document_info: &'a DocumentInfo,
 	pub document_network: &'a NodeNetwork,
 	pub document_metadata: &'a mut DocumentMetadata,
+	pub document_swatches: &'a DocumentSwatches,
+	pub document_parameters: &'a DocumentParameters,
+	pub document_variables: &'a DocumentVariables,
+	/// The node presets saved in the preferences, which can be applied to the selected nodes.
+	pub node_presets: &'a NodePresetLibrary,
 	pub selected_nodes: &'a SelectedNodes,
 	pub node_graph_message_handler: &'a NodeGraphMessageHandler,
 	pub executor: &'a mut NodeGraphExecutor,
+	pub artboard_relative_coordinates: bool,
+	pub units: DocumentUnits,
+}
+
+/// The range of text selected while editing the text of a Text node, given by the byte indices of its start and end, which the Properties panel can style.
+#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TextSelection {
+	pub node_id: NodeId,
+	pub start: usize,
+	pub end: usize,
 }
@@ -0,0 +1,8 @@
+mod search_message;
+mod search_message_handler;
+pub mod utility_types;
+
+#[doc(inline)]
+pub use search_message::{SearchMessage, SearchMessageDiscriminant};
+#[doc(inline)]
+pub use search_message_handler::{SearchMessageData, SearchMessageHandler};
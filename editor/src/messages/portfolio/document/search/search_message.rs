@@ -0,0 +1,16 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DocumentMessage, Search)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum SearchMessage {
+	// Messages
+	CaseSensitive(bool),
+	FindNext,
+	FindPrevious,
+	OpenDialog,
+	Query(String),
+	Regex(bool),
+	Replace,
+	ReplaceAll,
+	Replacement(String),
+}
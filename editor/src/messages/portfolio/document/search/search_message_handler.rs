@@ -0,0 +1,254 @@
+use super::utility_types::{SearchOptions, TextLayerIndex};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
+use crate::messages::prelude::*;
+
+use graph_craft::document::NodeNetwork;
+
+use std::ops::Range;
+
+pub struct SearchMessageData<'a> {
+	pub document_network: &'a NodeNetwork,
+	pub metadata: &'a DocumentMetadata,
+}
+
+/// Finds and replaces text across the text layers of the document, navigating between the matches by selecting their layers.
+#[derive(Debug, Clone, Default)]
+pub struct SearchMessageHandler {
+	options: SearchOptions,
+	/// The match that was last navigated to, as its layer and the byte range of the match in the layer's text.
+	current: Option<(LayerNodeIdentifier, Range<usize>)>,
+	/// Where the search continues from after a match is replaced, as the layer and the byte index in its text just after the replacement.
+	resume: Option<(LayerNodeIdentifier, usize)>,
+	/// The outcome of the last search, such as the number of matches, shown in the dialog.
+	status: String,
+}
+
+impl MessageHandler<SearchMessage, SearchMessageData<'_>> for SearchMessageHandler {
+	fn process_message(&mut self, message: SearchMessage, responses: &mut VecDeque<Message>, data: SearchMessageData) {
+		let SearchMessageData { document_network, metadata } = data;
+
+		// The text layers are indexed anew for each message, since their text may have been edited in the meantime
+		let mut index = TextLayerIndex::new(document_network, metadata);
+
+		match message {
+			SearchMessage::CaseSensitive(case_sensitive) => {
+				self.options.case_sensitive = case_sensitive;
+				self.restart();
+			}
+			SearchMessage::FindNext => self.navigate(&index, true, metadata, responses),
+			SearchMessage::FindPrevious => self.navigate(&index, false, metadata, responses),
+			SearchMessage::OpenDialog => self.restart(),
+			SearchMessage::Query(query) => {
+				self.options.query = query;
+				self.restart();
+			}
+			SearchMessage::Regex(regex) => {
+				self.options.regex = regex;
+				self.restart();
+			}
+			SearchMessage::Replace => self.replace(&mut index, metadata, responses),
+			SearchMessage::ReplaceAll => self.replace_all(&mut index, responses),
+			SearchMessage::Replacement(replacement) => self.options.replacement = replacement,
+		}
+
+		self.update_status(&index);
+		self.send_dialog_to_frontend(responses);
+	}
+
+	advertise_actions!(SearchMessageDiscriminant;
+		FindNext,
+		FindPrevious,
+		OpenDialog,
+	);
+}
+
+impl SearchMessageHandler {
+	/// Forgets the match that was navigated to, so the search starts over from the first match.
+	fn restart(&mut self) {
+		self.current = None;
+		self.resume = None;
+	}
+
+	/// Selects the layer of the next or previous match and frames it in the viewport, wrapping around at the ends of the document.
+	fn navigate(&mut self, index: &TextLayerIndex, forward: bool, metadata: &DocumentMetadata, responses: &mut VecDeque<Message>) {
+		let Ok(Some(matcher)) = self.options.matcher() else { return };
+		let matches = index.find(&matcher);
+
+		let found = if forward {
+			let from = match &self.current {
+				Some((layer, range)) => index.entry_of(*layer).map(|entry| (entry, range.end)),
+				None => self.resume.and_then(|(layer, offset)| index.entry_of(layer).map(|entry| (entry, offset))),
+			};
+			from.and_then(|from| matches.iter().find(|found| found.position() >= from)).or(matches.first())
+		} else {
+			let before = match &self.current {
+				Some((layer, range)) => index.entry_of(*layer).map(|entry| (entry, range.start)),
+				None => self.resume.and_then(|(layer, offset)| index.entry_of(layer).map(|entry| (entry, offset))),
+			};
+			before.and_then(|before| matches.iter().rev().find(|found| found.position() < before)).or(matches.last())
+		};
+
+		let Some(found) = found else {
+			self.restart();
+			return;
+		};
+		let layer = index.entries[found.entry].layer;
+		self.current = Some((layer, found.range.clone()));
+		self.resume = None;
+
+		responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
+		if let Some(bounds) = metadata.bounding_box_document(layer) {
+			responses.add(NavigationMessage::FitViewportToBounds { bounds, prevent_zoom_past_100: true });
+		}
+	}
+
+	/// Replaces the match that was navigated to and moves on to the next one, or only finds the next match if none was navigated to yet.
+	fn replace(&mut self, index: &mut TextLayerIndex, metadata: &DocumentMetadata, responses: &mut VecDeque<Message>) {
+		let Ok(Some(matcher)) = self.options.matcher() else { return };
+
+		// The match is only replaced if its text hasn't changed since it was navigated to
+		let current = self.current.clone().and_then(|(layer, range)| {
+			let entry = index.entry_of(layer)?;
+			index.find(&matcher).into_iter().any(|found| found.entry == entry && found.range == range).then_some((entry, range))
+		});
+		let Some((entry, range)) = current else {
+			self.current = None;
+			self.navigate(index, true, metadata, responses);
+			return;
+		};
+
+		let replacement = self.options.replacement_for(&matcher, &index.entries[entry].text, range.clone());
+		self.current = None;
+		self.resume = Some((index.entries[entry].layer, range.start + replacement.len()));
+
+		responses.add(DocumentMessage::StartTransaction);
+		index.replace(entry, &[(range, replacement)], responses);
+
+		self.navigate(index, true, metadata, responses);
+	}
+
+	/// Replaces every match in every text layer, as a single step that can be undone.
+	fn replace_all(&mut self, index: &mut TextLayerIndex, responses: &mut VecDeque<Message>) {
+		let Ok(Some(matcher)) = self.options.matcher() else { return };
+		let matches = index.find(&matcher);
+		if matches.is_empty() {
+			return;
+		}
+
+		responses.add(DocumentMessage::StartTransaction);
+		for entry in 0..index.entries.len() {
+			let text = &index.entries[entry].text;
+			let replacements = matches
+				.iter()
+				.filter(|found| found.entry == entry)
+				.map(|found| (found.range.clone(), self.options.replacement_for(&matcher, text, found.range.clone())))
+				.collect::<Vec<_>>();
+			if !replacements.is_empty() {
+				index.replace(entry, &replacements, responses);
+			}
+		}
+		self.restart();
+	}
+
+	fn update_status(&mut self, index: &TextLayerIndex) {
+		self.status = match self.options.matcher() {
+			Err(_) => "Invalid regular expression".to_string(),
+			Ok(None) => String::new(),
+			Ok(Some(matcher)) => {
+				let matches = index.find(&matcher);
+				let current = self
+					.current
+					.as_ref()
+					.and_then(|(layer, range)| matches.iter().position(|found| index.entries[found.entry].layer == *layer && found.range == *range));
+
+				match (matches.len(), current) {
+					(0, _) => "No matches".to_string(),
+					(count, Some(current)) => format!("Match {} of {count}", current + 1),
+					(1, None) => "1 match".to_string(),
+					(count, None) => {
+						let mut layers = matches.iter().map(|found| found.entry).collect::<Vec<_>>();
+						layers.dedup();
+						let layer_count = layers.len();
+						format!("{count} matches in {layer_count} layer{}", if layer_count == 1 { "" } else { "s" })
+					}
+				}
+			}
+		};
+	}
+}
+
+impl DialogLayoutHolder for SearchMessageHandler {
+	const ICON: &'static str = "NodeText";
+	const TITLE: &'static str = "Find and Replace";
+
+	fn layout_buttons(&self) -> Layout {
+		let no_query = self.options.query.is_empty();
+		let widgets = vec![
+			TextButton::new("Find Next")
+				.emphasized(true)
+				.disabled(no_query)
+				.on_update(|_| SearchMessage::FindNext.into())
+				.widget_holder(),
+			TextButton::new("Find Previous").disabled(no_query).on_update(|_| SearchMessage::FindPrevious.into()).widget_holder(),
+			TextButton::new("Replace").disabled(no_query).on_update(|_| SearchMessage::Replace.into()).widget_holder(),
+			TextButton::new("Replace All").disabled(no_query).on_update(|_| SearchMessage::ReplaceAll.into()).widget_holder(),
+			TextButton::new("Close").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for SearchMessageHandler {
+	fn layout(&self) -> Layout {
+		let find = vec![
+			TextLabel::new("Find").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(&self.options.query)
+				.min_width(240)
+				.on_update(|text_input: &TextInput| SearchMessage::Query(text_input.value.clone()).into())
+				.widget_holder(),
+		];
+
+		let replace = vec![
+			TextLabel::new("Replace With").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(&self.options.replacement)
+				.min_width(240)
+				.tooltip("Text that replaces each match, where $1 or ${name} insert the groups captured by a regular expression")
+				.on_update(|text_input: &TextInput| SearchMessage::Replacement(text_input.value.clone()).into())
+				.widget_holder(),
+		];
+
+		let options = vec![
+			TextLabel::new("Options").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.options.case_sensitive)
+				.on_update(|checkbox_input: &CheckboxInput| SearchMessage::CaseSensitive(checkbox_input.checked).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextLabel::new("Match Case").widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.options.regex)
+				.tooltip("Find text matching the query as a regular expression")
+				.on_update(|checkbox_input: &CheckboxInput| SearchMessage::Regex(checkbox_input.checked).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextLabel::new("Regular Expression").widget_holder(),
+		];
+
+		let status = vec![
+			TextLabel::new("").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new(&self.status).italic(true).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row { widgets: find },
+			LayoutGroup::Row { widgets: replace },
+			LayoutGroup::Row { widgets: options },
+			LayoutGroup::Row { widgets: status },
+		]))
+	}
+}
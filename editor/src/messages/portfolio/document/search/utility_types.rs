@@ -0,0 +1,259 @@
+use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{NodeId, NodeNetwork};
+use graphene_core::text::{TextKerning, TextRuns};
+
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+/// What to search the text layers for, and what to replace the matches with.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchOptions {
+	pub query: String,
+	/// The text that replaces each match, where `$1` or `${name}` insert the groups captured by a regular expression.
+	pub replacement: String,
+	pub case_sensitive: bool,
+	/// Whether the query is a regular expression, rather than text that's matched as it's written.
+	pub regex: bool,
+}
+
+impl SearchOptions {
+	/// The pattern which finds the query, or `None` for an empty query which finds nothing.
+	pub fn matcher(&self) -> Result<Option<Regex>, regex::Error> {
+		if self.query.is_empty() {
+			return Ok(None);
+		}
+		let pattern = if self.regex { self.query.clone() } else { regex::escape(&self.query) };
+		RegexBuilder::new(&pattern).case_insensitive(!self.case_sensitive).build().map(Some)
+	}
+
+	/// The text that replaces the match at the range of the text, with the groups captured by a regular expression inserted into it.
+	pub fn replacement_for(&self, matcher: &Regex, text: &str, range: Range<usize>) -> String {
+		if !self.regex {
+			return self.replacement.clone();
+		}
+		let mut replacement = String::new();
+		if let Some(captures) = matcher.captures_at(text, range.start) {
+			captures.expand(&self.replacement, &mut replacement);
+		}
+		replacement
+	}
+}
+
+/// A text layer along with its text, as indexed for searching.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayerEntry {
+	pub layer: LayerNodeIdentifier,
+	/// The Text node which generates the layer's text.
+	pub node_id: NodeId,
+	pub text: String,
+	pub runs: TextRuns,
+	pub kerning: TextKerning,
+}
+
+/// The text of every text layer in the document, in the order of the layers from top to bottom as in the Layers panel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLayerIndex {
+	pub entries: Vec<TextLayerEntry>,
+}
+
+impl TextLayerIndex {
+	pub fn new(document_network: &NodeNetwork, metadata: &DocumentMetadata) -> Self {
+		let entries = metadata
+			.all_layers()
+			.filter_map(|layer| {
+				let node_id = graph_modification_utils::get_text_id(layer, document_network)?;
+				let (text, _, _) = graph_modification_utils::get_text(layer, document_network)?;
+				Some(TextLayerEntry {
+					layer,
+					node_id,
+					text: text.clone(),
+					runs: graph_modification_utils::get_text_runs(layer, document_network),
+					kerning: graph_modification_utils::get_text_kerning(layer, document_network),
+				})
+			})
+			.collect();
+
+		Self { entries }
+	}
+
+	/// The index of the entry for the layer, if it's a text layer.
+	pub fn entry_of(&self, layer: LayerNodeIdentifier) -> Option<usize> {
+		self.entries.iter().position(|entry| entry.layer == layer)
+	}
+
+	/// Every match of the pattern, in the order of the layers and then in the order they appear in the text of each layer.
+	/// Empty matches, which a regular expression like `a*` finds between every character, are left out.
+	pub fn find(&self, matcher: &Regex) -> Vec<SearchMatch> {
+		self.entries
+			.iter()
+			.enumerate()
+			.flat_map(|(entry, text_layer)| {
+				matcher
+					.find_iter(&text_layer.text)
+					.filter(|found| !found.is_empty())
+					.map(move |found| SearchMatch { entry, range: found.range() })
+			})
+			.collect()
+	}
+
+	/// Writes the text of the entry with the ranges replaced to its Text node, along with its style runs and kerned pairs moved to follow the replacements, keeping the index up to date.
+	pub fn replace(&mut self, entry: usize, replacements: &[(Range<usize>, String)], responses: &mut VecDeque<Message>) {
+		let text_layer = &mut self.entries[entry];
+		let mut runs = text_layer.runs.clone();
+		let mut kerning = text_layer.kerning.clone();
+		let text = replace_ranges(&text_layer.text, &mut runs, &mut kerning, replacements);
+
+		responses.add(NodeGraphMessage::SetQualifiedInputValue {
+			node_id: text_layer.node_id,
+			input_index: 1,
+			value: TaggedValue::String(text.clone()),
+		});
+		if runs != text_layer.runs {
+			responses.add(NodeGraphMessage::SetQualifiedInputValue {
+				node_id: text_layer.node_id,
+				input_index: 10,
+				value: TaggedValue::TextRuns(runs.clone()),
+			});
+		}
+		if kerning != text_layer.kerning {
+			responses.add(NodeGraphMessage::SetQualifiedInputValue {
+				node_id: text_layer.node_id,
+				input_index: 11,
+				value: TaggedValue::TextKerning(kerning.clone()),
+			});
+		}
+
+		text_layer.text = text;
+		text_layer.runs = runs;
+		text_layer.kerning = kerning;
+	}
+}
+
+/// A match found in the text of a text layer, given by the index of the layer's entry in the [`TextLayerIndex`] and the byte range of the match in its text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+	pub entry: usize,
+	pub range: Range<usize>,
+}
+
+impl SearchMatch {
+	/// Where the match starts, for comparing it to other positions in the document's text.
+	pub fn position(&self) -> (usize, usize) {
+		(self.entry, self.range.start)
+	}
+}
+
+/// Replaces the ranges of the text, which are in order without overlapping, with the text given for each, moving its style runs and kerned pairs to follow the replacements.
+pub fn replace_ranges(text: &str, runs: &mut TextRuns, kerning: &mut TextKerning, replacements: &[(Range<usize>, String)]) -> String {
+	let mut text = text.to_string();
+	// Replacing from the end leaves the ranges before each replacement where they are
+	for (range, replacement) in replacements.iter().rev() {
+		text.replace_range(range.clone(), replacement);
+		runs.edited(range.start, range.end, replacement.len());
+		kerning.edited(range.start, range.end, replacement.len());
+	}
+	text
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use graphene_core::text::{KerningPair, TextRun, TextStyle};
+
+	fn options(query: &str, replacement: &str, case_sensitive: bool, regex: bool) -> SearchOptions {
+		SearchOptions {
+			query: query.into(),
+			replacement: replacement.into(),
+			case_sensitive,
+			regex,
+		}
+	}
+
+	fn index(texts: &[&str]) -> TextLayerIndex {
+		let entries = texts
+			.iter()
+			.enumerate()
+			.map(|(index, text)| TextLayerEntry {
+				layer: LayerNodeIdentifier::new_unchecked(NodeId(index as u64 + 1)),
+				node_id: NodeId(index as u64 + 100),
+				text: text.to_string(),
+				runs: TextRuns::default(),
+				kerning: TextKerning::default(),
+			})
+			.collect();
+		TextLayerIndex { entries }
+	}
+
+	#[test]
+	fn plain_queries_match_their_characters_literally() {
+		let matcher = options("a.b", "", true, false).matcher().unwrap().unwrap();
+		assert!(matcher.is_match("a.b"));
+		assert!(!matcher.is_match("axb"));
+
+		let matcher = options("Cat", "", false, false).matcher().unwrap().unwrap();
+		assert!(matcher.is_match("CAT"));
+		let matcher = options("Cat", "", true, false).matcher().unwrap().unwrap();
+		assert!(!matcher.is_match("CAT"));
+
+		assert!(options("", "", false, false).matcher().unwrap().is_none());
+		assert!(options("(", "", false, true).matcher().is_err());
+	}
+
+	#[test]
+	fn only_regular_expressions_insert_their_captures() {
+		let text = "size 12px";
+		let regex = options(r"(\d+)px", "${1}pt", true, true);
+		let matcher = regex.matcher().unwrap().unwrap();
+		let found = matcher.find(text).unwrap();
+		assert_eq!(regex.replacement_for(&matcher, text, found.range()), "12pt");
+
+		let plain = options("12px", "$1", true, false);
+		let matcher = plain.matcher().unwrap().unwrap();
+		assert_eq!(plain.replacement_for(&matcher, text, 5..9), "$1");
+	}
+
+	#[test]
+	fn matches_are_found_in_layer_order_without_empty_ones() {
+		let index = index(&["banana", "", "cab"]);
+		let matcher = options("a*", "", true, true).matcher().unwrap().unwrap();
+		let matches = index.find(&matcher);
+		let positions = matches.iter().map(|found| (found.entry, found.range.clone())).collect::<Vec<_>>();
+		assert_eq!(positions, vec![(0, 1..2), (0, 3..4), (0, 5..6), (2, 1..2)]);
+	}
+
+	#[test]
+	fn replacing_moves_the_runs_and_kerning_after_it() {
+		let style = TextStyle {
+			font_size: Some(20.),
+			..Default::default()
+		};
+		let mut runs = TextRuns(vec![TextRun {
+			start: 6,
+			end: 9,
+			style: style.clone(),
+		}]);
+		let mut kerning = TextKerning {
+			pairs: vec![KerningPair { index: 7, adjustment: 50. }],
+			..Default::default()
+		};
+
+		let text = replace_ranges("a cat sat", &mut runs, &mut kerning, &[(2..5, "tiger".into())]);
+		assert_eq!(text, "a tiger sat");
+		assert_eq!(runs, TextRuns(vec![TextRun { start: 8, end: 11, style }]));
+		assert_eq!(kerning.pairs, vec![KerningPair { index: 9, adjustment: 50. }]);
+	}
+
+	#[test]
+	fn replacing_updates_the_text_node_and_the_index() {
+		let mut index = index(&["one two one"]);
+		let mut responses = VecDeque::new();
+		index.replace(0, &[(0..3, "1".into()), (8..11, "1".into())], &mut responses);
+
+		assert_eq!(index.entries[0].text, "1 two 1");
+		assert_eq!(responses.len(), 1);
+	}
+}
@@ -0,0 +1,8 @@
+mod spellcheck_message;
+mod spellcheck_message_handler;
+pub mod utility_types;
+
+#[doc(inline)]
+pub use spellcheck_message::{SpellcheckMessage, SpellcheckMessageDiscriminant};
+#[doc(inline)]
+pub use spellcheck_message_handler::{SpellcheckMessageData, SpellcheckMessageHandler};
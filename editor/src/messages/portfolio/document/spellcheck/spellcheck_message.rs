@@ -0,0 +1,27 @@
+use super::utility_types::SpellcheckResult;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DocumentMessage, Spellcheck)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum SpellcheckMessage {
+	// Messages
+	/// Replaces a flagged word, given by its range in UTF-16 code units within the run, with the suggestion wherever the run appears in the text layers.
+	AcceptSuggestion {
+		run: u64,
+		start: usize,
+		end: usize,
+		suggestion: String,
+	},
+	Check,
+	IgnoreWord {
+		word: String,
+	},
+	Overlays(OverlayContext),
+	Results {
+		results: Vec<SpellcheckResult>,
+	},
+	SetEnabled {
+		enabled: bool,
+	},
+}
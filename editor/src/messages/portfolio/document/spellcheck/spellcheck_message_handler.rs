@@ -0,0 +1,157 @@
+use super::utility_types::{text_runs, SpellcheckIssue, SpellcheckRun};
+use crate::consts::COLOR_OVERLAY_RED;
+use crate::messages::portfolio::document::search::utility_types::TextLayerIndex;
+use crate::messages::portfolio::document::utility_types::document_metadata::DocumentMetadata;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::tool_messages::text_tool::utf16_to_byte_index;
+
+use graph_craft::document::NodeNetwork;
+use graphene_core::text::{layout_rich_text, load_face_with_variations, load_run_faces, FontCache};
+
+pub struct SpellcheckMessageData<'a> {
+	pub document_network: &'a NodeNetwork,
+	pub metadata: &'a DocumentMetadata,
+	pub font_cache: &'a FontCache,
+}
+
+/// Hands the paragraphs of the text layers to the spellchecker plugged in by the frontend, and underlines the words it flags with squiggles that offer its suggestions.
+/// Only the runs whose text has changed since they were last checked are sent to the spellchecker.
+#[derive(Debug, Clone, Default)]
+pub struct SpellcheckMessageHandler {
+	/// Whether a spellchecker has been plugged in, without which nothing is checked.
+	enabled: bool,
+	/// The words flagged in each run that's been checked, by the ID of the run.
+	results: HashMap<u64, Vec<SpellcheckIssue>>,
+	/// The runs sent to the spellchecker whose results haven't arrived yet.
+	pending: HashSet<u64>,
+	/// The words the user chose to leave unflagged.
+	ignored: HashSet<String>,
+}
+
+impl MessageHandler<SpellcheckMessage, SpellcheckMessageData<'_>> for SpellcheckMessageHandler {
+	fn process_message(&mut self, message: SpellcheckMessage, responses: &mut VecDeque<Message>, data: SpellcheckMessageData) {
+		let SpellcheckMessageData {
+			document_network,
+			metadata,
+			font_cache,
+		} = data;
+
+		match message {
+			SpellcheckMessage::AcceptSuggestion { run, start, end, suggestion } => {
+				let mut index = TextLayerIndex::new(document_network, metadata);
+
+				// The run is found again by its text, since it moves within the text of each layer as earlier paragraphs are edited
+				let replacements = index
+					.entries
+					.iter()
+					.map(|entry| {
+						text_runs(&entry.text)
+							.filter(|(_, id)| *id == run)
+							.map(|(range, _)| {
+								let paragraph = &entry.text[range.clone()];
+								let replaced = range.start + utf16_to_byte_index(paragraph, start)..range.start + utf16_to_byte_index(paragraph, end);
+								(replaced, suggestion.clone())
+							})
+							.collect::<Vec<_>>()
+					})
+					.collect::<Vec<_>>();
+				if replacements.iter().all(Vec::is_empty) {
+					return;
+				}
+
+				responses.add(DocumentMessage::StartTransaction);
+				for (entry, replacements) in replacements.iter().enumerate() {
+					if !replacements.is_empty() {
+						index.replace(entry, replacements, responses);
+					}
+				}
+			}
+			SpellcheckMessage::Check => {
+				if !self.enabled {
+					return;
+				}
+				let index = TextLayerIndex::new(document_network, metadata);
+				let mut runs = Vec::new();
+				let mut current = HashSet::new();
+				for entry in &index.entries {
+					for (range, id) in text_runs(&entry.text) {
+						current.insert(id);
+						if !self.results.contains_key(&id) && self.pending.insert(id) {
+							runs.push(SpellcheckRun::new(&entry.text[range]));
+						}
+					}
+				}
+
+				// The results of runs which no longer appear in the document are forgotten
+				self.results.retain(|id, _| current.contains(id));
+
+				if !runs.is_empty() {
+					responses.add(FrontendMessage::TriggerSpellcheck { runs });
+				}
+			}
+			SpellcheckMessage::IgnoreWord { word } => {
+				self.ignored.insert(word);
+				responses.add(OverlaysMessage::Draw);
+			}
+			SpellcheckMessage::Overlays(mut overlay_context) => {
+				if !self.enabled || self.results.values().all(Vec::is_empty) {
+					return;
+				}
+				let index = TextLayerIndex::new(document_network, metadata);
+				for entry in &index.entries {
+					let flagged = text_runs(&entry.text)
+						.filter_map(|(range, id)| Some((range, self.results.get(&id)?)))
+						.flat_map(|(range, issues)| {
+							let paragraph = &entry.text[range.clone()];
+							issues
+								.iter()
+								.map(move |issue| range.start + utf16_to_byte_index(paragraph, issue.start)..range.start + utf16_to_byte_index(paragraph, issue.end))
+						})
+						.filter(|flagged| !self.ignored.contains(&entry.text[flagged.clone()]))
+						.collect::<Vec<_>>();
+					if flagged.is_empty() {
+						continue;
+					}
+
+					let Some((text, font, typesetting)) = graph_modification_utils::get_text(entry.layer, document_network) else {
+						continue;
+					};
+					let variations = graph_modification_utils::get_text_variations(entry.layer, document_network);
+					let Some(buzz_face) = font_cache.get(font).and_then(|data| load_face_with_variations(data, &variations)) else {
+						continue;
+					};
+					let segments = entry.runs.segments(text.len());
+					let (faces, shaping_runs) = load_run_faces(font_cache, buzz_face, font, typesetting.font_size, &variations, &segments);
+					let faces = faces.iter().collect::<Vec<_>>();
+					let layout = layout_rich_text(text, &faces, &shaping_runs, &entry.kerning.pairs, typesetting);
+
+					let transform = metadata.transform_to_viewport(entry.layer);
+					for range in flagged {
+						for [start, end] in layout.underlines(range, faces[0]) {
+							overlay_context.squiggle(transform.transform_point2(start), transform.transform_point2(end), Some(COLOR_OVERLAY_RED));
+						}
+					}
+				}
+			}
+			SpellcheckMessage::Results { results } => {
+				for result in results {
+					self.pending.remove(&result.run);
+					self.results.insert(result.run, result.issues);
+				}
+				responses.add(OverlaysMessage::Draw);
+			}
+			SpellcheckMessage::SetEnabled { enabled } => {
+				self.enabled = enabled;
+				self.results.clear();
+				self.pending.clear();
+				if enabled {
+					responses.add(SpellcheckMessage::Check);
+				}
+				responses.add(OverlaysMessage::Draw);
+			}
+		}
+	}
+
+	advertise_actions!(SpellcheckMessageDiscriminant;);
+}
@@ -0,0 +1,53 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+/// A paragraph of the text of a text layer, as given to the spellchecker.
+/// Runs are identified by their text, so a run is only checked again once its text is edited, and runs with the same text in different layers are checked once.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct SpellcheckRun {
+	pub id: u64,
+	pub text: String,
+}
+
+impl SpellcheckRun {
+	pub fn new(text: &str) -> Self {
+		Self {
+			id: run_id(text),
+			text: text.to_string(),
+		}
+	}
+}
+
+/// The ID of the run with the text, which is a hash of the text.
+pub fn run_id(text: &str) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	text.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// The byte ranges of the paragraphs of the text which have something to check, along with their IDs.
+pub fn text_runs(text: &str) -> impl Iterator<Item = (Range<usize>, u64)> + '_ {
+	let mut start = 0;
+	text.split('\n').filter_map(move |paragraph| {
+		let range = start..start + paragraph.len();
+		start = range.end + 1;
+		(!paragraph.trim().is_empty()).then(|| (range, run_id(paragraph)))
+	})
+}
+
+/// A word flagged by the spellchecker, given by its range in the text of its run counted in UTF-16 code units as used by the browser.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct SpellcheckIssue {
+	pub start: usize,
+	pub end: usize,
+	/// The replacements for the word that the spellchecker suggests, with the most likely first.
+	pub suggestions: Vec<String>,
+}
+
+/// The words flagged by the spellchecker in the run with the ID, which is empty if none were.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct SpellcheckResult {
+	pub run: u64,
+	pub issues: Vec<SpellcheckIssue>,
+}
@@ -1,5 +1,9 @@
 use graph_craft::document::DocumentNode;
 use graph_craft::document::NodeId;
+use graphene_core::raster::Image;
+use graphene_core::Color;
+
+use glam::DVec2;
 
 use std::collections::HashMap;
 
@@ -24,3 +28,73 @@ pub struct CopyBufferEntry {
 	pub collapsed: bool,
 	pub alias: String,
 }
+
+/// The layers copied to the device clipboard, which can be pasted into any open document.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ClipboardContent {
+	pub entries: Vec<CopyBufferEntry>,
+	/// The bounding box of the copied layers in the document space of the document they were copied from, used to place the pasted layers around the cursor.
+	pub bounds: Option<[DVec2; 2]>,
+}
+
+impl ClipboardContent {
+	/// Parses the content, also accepting the older format which only consisted of the list of entries.
+	pub fn deserialize(data: &str) -> Option<Self> {
+		serde_json::from_str::<Self>(data)
+			.ok()
+			.or_else(|| serde_json::from_str::<Vec<CopyBufferEntry>>(data).ok().map(|entries| Self { entries, bounds: None }))
+	}
+}
+
+/// Content read from the device clipboard, identified by the MIME type it was offered as.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalClipboardContent {
+	/// Layers copied from Graphite, serialized as [`ClipboardContent`].
+	Layers(String),
+	/// Nodes copied from the Graphite node graph.
+	Nodes(String),
+	/// The source text of an SVG file.
+	Svg(String),
+	/// A raster image, such as a screenshot or an image copied from a web page.
+	Image(Image<Color>),
+}
+
+impl ExternalClipboardContent {
+	/// Interprets the data according to its MIME type, returning `None` if it isn't content that can be pasted into a document.
+	pub fn from_mime_type(mime_type: &str, data: &[u8]) -> Option<Self> {
+		let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+
+		match mime_type {
+			"text/plain" => {
+				let text = std::str::from_utf8(data).ok()?;
+				if let Some(layers) = text.strip_prefix("graphite/layer: ") {
+					Some(Self::Layers(layers.to_string()))
+				} else if let Some(nodes) = text.strip_prefix("graphite/nodes: ") {
+					Some(Self::Nodes(nodes.to_string()))
+				} else if is_svg(text) {
+					// SVG source copied from a text editor or a browser's developer tools
+					Some(Self::Svg(text.to_string()))
+				} else {
+					None
+				}
+			}
+			"image/svg+xml" => Some(Self::Svg(std::str::from_utf8(data).ok()?.to_string())),
+			_ if mime_type.starts_with("image/") => {
+				let image = match image::load_from_memory(data) {
+					Ok(image) => image.to_rgba8(),
+					Err(error) => {
+						log::warn!("Could not decode the pasted image of type {mime_type}: {error}");
+						return None;
+					}
+				};
+				Some(Self::Image(Image::from_image_data(image.as_raw(), image.width(), image.height())))
+			}
+			_ => None,
+		}
+	}
+}
+
+fn is_svg(text: &str) -> bool {
+	let text = text.trim_start();
+	(text.starts_with("<svg") || text.starts_with("<?xml")) && text.contains("<svg")
+}
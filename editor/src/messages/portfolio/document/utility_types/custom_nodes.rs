@@ -0,0 +1,83 @@
+use crate::messages::portfolio::document::node_graph::document_node_types::resolve_document_node_type;
+use crate::messages::portfolio::document::node_graph::utility_types::FrontendNodeType;
+
+use graph_craft::document::{DocumentNode, DocumentNodeImplementation, DocumentNodeMetadata, NodeInput, NodeNetwork};
+
+/// The node menu category that lists the custom nodes of the document.
+pub const CUSTOM_NODE_CATEGORY: &str = "Custom";
+
+/// A reusable node made by the user from a group of other nodes, implemented by a nested network.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CustomNodeDefinition {
+	pub name: String,
+	/// The node that each instance copies, with the values of its inputs from when it was created as their defaults.
+	pub node: DocumentNode,
+}
+
+impl CustomNodeDefinition {
+	/// Makes an instance of the custom node, which is an independent copy of the definition's network.
+	/// Editing the network inside one instance changes neither the definition nor the other instances.
+	pub fn to_document_node(&self, metadata: DocumentNodeMetadata) -> DocumentNode {
+		DocumentNode {
+			name: self.name.clone(),
+			metadata,
+			..self.node.clone()
+		}
+	}
+}
+
+/// The custom nodes stored in a document, which can be instantiated any number of times in its node graph.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CustomNodeLibrary {
+	pub definitions: Vec<CustomNodeDefinition>,
+}
+
+impl CustomNodeLibrary {
+	pub fn get(&self, name: &str) -> Option<&CustomNodeDefinition> {
+		self.definitions.iter().find(|definition| definition.name == name)
+	}
+
+	/// Picks a name, starting from the given one, which isn't already taken by a built-in or custom node.
+	pub fn unique_name(&self, name: &str) -> String {
+		let taken = |name: &str| resolve_document_node_type(name).is_some() || self.get(name).is_some();
+		if !taken(name) {
+			return name.to_string();
+		}
+		(2..).map(|number| format!("{name} {number}")).find(|name| !taken(name)).unwrap_or_default()
+	}
+
+	pub fn node_types(&self) -> impl Iterator<Item = FrontendNodeType> + '_ {
+		self.definitions.iter().map(|definition| FrontendNodeType {
+			name: definition.name.clone(),
+			category: CUSTOM_NODE_CATEGORY.to_string(),
+		})
+	}
+}
+
+/// The name of a nested network's import, taken from the definition of the first nested node input it's connected to.
+/// This names the inputs of nodes like custom nodes which have no definition of their own.
+pub fn subgraph_import_name(network: &NodeNetwork, import_index: usize) -> Option<String> {
+	network.nodes.values().find_map(|node| {
+		let input_index = node
+			.inputs
+			.iter()
+			.position(|input| matches!(input, NodeInput::Network { import_index: index, .. } if *index == import_index))?;
+		let definition = resolve_document_node_type(&node.name)?;
+		definition.inputs.get(input_index).map(|input| input.name.to_string())
+	})
+}
+
+/// The name of a nested network's export, taken from the definition of the nested node output it's connected to.
+pub fn subgraph_export_name(network: &NodeNetwork, export_index: usize) -> Option<String> {
+	let NodeInput::Node { node_id, output_index, .. } = network.exports.get(export_index)? else {
+		return None;
+	};
+	let node = network.nodes.get(node_id)?;
+	if let DocumentNodeImplementation::Network(nested_network) = &node.implementation {
+		if resolve_document_node_type(&node.name).is_none() {
+			return subgraph_export_name(nested_network, *output_index);
+		}
+	}
+	resolve_document_node_type(&node.name)?.outputs.get(*output_index).map(|output| output.name.to_string())
+}
@@ -0,0 +1,181 @@
+use crate::messages::portfolio::icon_set::crc32;
+
+use std::fmt::Write;
+
+/// Descriptive information about a document, which is saved with it and embedded into the files exported from it so the artwork can be attributed and catalogued by asset management systems.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DocumentInfo {
+	pub title: String,
+	pub author: String,
+	/// The license or copyright notice that the artwork is distributed under.
+	pub license: String,
+	pub keywords: Vec<String>,
+}
+
+impl DocumentInfo {
+	pub fn is_empty(&self) -> bool {
+		self.title.is_empty() && self.author.is_empty() && self.license.is_empty() && self.keywords.is_empty()
+	}
+
+	/// Splits comma separated keywords as typed by the user, leaving out the empty ones.
+	pub fn parse_keywords(text: &str) -> Vec<String> {
+		text.split(',').map(str::trim).filter(|keyword| !keyword.is_empty()).map(String::from).collect()
+	}
+
+	/// The fields that are set, named by the keywords that PNG files use for them.
+	fn entries(&self) -> Vec<(&'static str, String)> {
+		[
+			("Title", self.title.clone()),
+			("Author", self.author.clone()),
+			("Copyright", self.license.clone()),
+			("Keywords", self.keywords.join(", ")),
+		]
+		.into_iter()
+		.filter(|(_, value)| !value.is_empty())
+		.collect()
+	}
+
+	/// Inserts a `<metadata>` element describing the document with the Dublin Core vocabulary in RDF, as read by Inkscape and other SVG tools, right after the opening `<svg>` tag.
+	pub fn embed_in_svg(&self, svg: &mut String) {
+		if self.is_empty() {
+			return;
+		}
+		let Some(insertion_index) = svg.find("<svg").and_then(|start| svg[start..].find('>').map(|end| start + end + 1)) else {
+			return;
+		};
+
+		let mut metadata = String::from(
+			r#"<metadata><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:cc="http://creativecommons.org/ns#"><cc:Work rdf:about="">"#,
+		);
+		if !self.title.is_empty() {
+			let _ = write!(metadata, "<dc:title>{}</dc:title>", escape_xml(&self.title));
+		}
+		if !self.author.is_empty() {
+			let _ = write!(metadata, "<dc:creator><cc:Agent><dc:title>{}</dc:title></cc:Agent></dc:creator>", escape_xml(&self.author));
+		}
+		if !self.license.is_empty() {
+			let _ = write!(metadata, "<dc:rights><cc:Agent><dc:title>{}</dc:title></cc:Agent></dc:rights>", escape_xml(&self.license));
+		}
+		if !self.keywords.is_empty() {
+			metadata.push_str("<dc:subject><rdf:Bag>");
+			for keyword in &self.keywords {
+				let _ = write!(metadata, "<rdf:li>{}</rdf:li>", escape_xml(keyword));
+			}
+			metadata.push_str("</rdf:Bag></dc:subject>");
+		}
+		metadata.push_str("</cc:Work></rdf:RDF></metadata>");
+
+		svg.insert_str(insertion_index, &metadata);
+	}
+
+	/// Encodes the fields as PNG text chunks, to be inserted after the header chunk of a PNG file.
+	/// Text that can be written in Latin-1 goes in `tEXt` chunks, while other text goes in `iTXt` chunks which store UTF-8.
+	pub fn png_text_chunks(&self) -> Vec<u8> {
+		let mut chunks = Vec::new();
+		for (keyword, value) in self.entries() {
+			let latin1 = value.chars().map(|character| u8::try_from(character as u32).ok()).collect::<Option<Vec<u8>>>();
+			let (chunk_type, data) = match latin1 {
+				Some(text) => (b"tEXt", [keyword.as_bytes(), &[0], text.as_slice()].concat()),
+				// The keyword is followed by the compression flag, compression method, and empty language tag and translated keyword
+				None => (b"iTXt", [keyword.as_bytes(), &[0, 0, 0, 0, 0], value.as_bytes()].concat()),
+			};
+
+			chunks.extend((data.len() as u32).to_be_bytes());
+			let start = chunks.len();
+			chunks.extend(chunk_type);
+			chunks.extend(&data);
+			let crc = crc32(&chunks[start..]);
+			chunks.extend(crc.to_be_bytes());
+		}
+		chunks
+	}
+
+	/// The entries of a PDF document information dictionary, with the text encoded as UTF-16 so any characters can be written.
+	pub fn pdf_info_dictionary(&self) -> String {
+		let entries = [("Title", &self.title), ("Author", &self.author), ("Copyright", &self.license), ("Keywords", &self.keywords.join(", "))]
+			.into_iter()
+			.filter(|(_, value)| !value.is_empty())
+			.map(|(key, value)| {
+				let hex = value.encode_utf16().map(|unit| format!("{unit:04X}")).collect::<String>();
+				format!("/{key} <FEFF{hex}>")
+			})
+			.collect::<Vec<_>>();
+		format!("<< /Producer (Graphite) {} >>", entries.join(" "))
+	}
+}
+
+/// Escapes the characters with special meaning in XML text.
+pub fn escape_xml(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Reads back the type and data of each chunk, checking their lengths and CRCs.
+	fn read_png_chunks(mut chunks: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+		let mut read = Vec::new();
+		while !chunks.is_empty() {
+			let length = u32::from_be_bytes(chunks[..4].try_into().unwrap()) as usize;
+			let (body, crc) = chunks[4..].split_at(4 + length);
+			assert_eq!(u32::from_be_bytes(crc[..4].try_into().unwrap()), crc32(body));
+			read.push((body[..4].try_into().unwrap(), body[4..].to_vec()));
+			chunks = &crc[4..];
+		}
+		read
+	}
+
+	#[test]
+	fn png_chunks_store_latin1_as_text_and_the_rest_as_utf8() {
+		let info = DocumentInfo {
+			title: "Café".into(),
+			author: "山田".into(),
+			keywords: vec!["a".into(), "b".into()],
+			..Default::default()
+		};
+		let chunks = read_png_chunks(&info.png_text_chunks());
+		assert_eq!(
+			chunks,
+			vec![
+				(*b"tEXt", b"Title\0Caf\xE9".to_vec()),
+				(*b"iTXt", [b"Author\0\0\0\0\0".as_slice(), "山田".as_bytes()].concat()),
+				(*b"tEXt", b"Keywords\0a, b".to_vec()),
+			]
+		);
+		assert!(DocumentInfo::default().png_text_chunks().is_empty());
+	}
+
+	#[test]
+	fn pdf_info_encodes_the_set_fields_as_utf16() {
+		let info = DocumentInfo {
+			title: "Hi".into(),
+			license: "©".into(),
+			..Default::default()
+		};
+		assert_eq!(info.pdf_info_dictionary(), "<< /Producer (Graphite) /Title <FEFF00480069> /Copyright <FEFF00A9> >>");
+	}
+
+	#[test]
+	fn svg_metadata_is_escaped_and_placed_inside_the_root() {
+		let info = DocumentInfo {
+			title: "Fish & <Chips>".into(),
+			..Default::default()
+		};
+		let mut svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><rect /></svg>"#.to_string();
+		info.embed_in_svg(&mut svg);
+		assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg"><metadata>"#));
+		assert!(svg.contains("<dc:title>Fish &amp; &lt;Chips&gt;</dc:title>"));
+		assert!(svg.ends_with("</metadata><rect /></svg>"));
+
+		let mut unchanged = "<svg></svg>".to_string();
+		DocumentInfo::default().embed_in_svg(&mut unchanged);
+		assert_eq!(unchanged, "<svg></svg>");
+	}
+
+	#[test]
+	fn keywords_are_split_on_commas() {
+		assert_eq!(DocumentInfo::parse_keywords(" poster, ,print ,"), vec!["poster".to_string(), "print".to_string()]);
+	}
+}
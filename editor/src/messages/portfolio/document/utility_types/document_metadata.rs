@@ -1,4 +1,7 @@
 use super::nodes::SelectedNodes;
+use super::picking_buffer::PickingBuffer;
+use super::spatial_index::LayerSpatialIndex;
+use crate::consts::PICKING_BUFFER_MIN_LAYERS;
 
 use graph_craft::document::FlowType;
 use graph_craft::document::{NodeId, NodeNetwork};
@@ -6,7 +9,7 @@ use graphene_core::renderer::ClickTarget;
 use graphene_core::renderer::Quad;
 use graphene_core::transform::Footprint;
 
-use glam::{DAffine2, DVec2};
+use glam::{DAffine2, DVec2, UVec2};
 use graphene_std::vector::PointId;
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroU64;
@@ -26,6 +29,11 @@ pub struct DocumentMetadata {
 	hidden: HashSet<NodeId>,
 	locked: HashSet<NodeId>,
 	click_targets: HashMap<LayerNodeIdentifier, Vec<ClickTarget>>,
+	/// The text layers whose text doesn't fit its text box, so the lines at its end are hidden.
+	overflowing_text: HashSet<LayerNodeIdentifier>,
+	spatial_index: LayerSpatialIndex,
+	/// The render of the layers' click targets which hovering reads from on dense documents, until the layers change.
+	picking_buffer: Option<PickingBuffer>,
 	/// Transform from document space to viewport space.
 	pub document_to_viewport: DAffine2,
 }
@@ -40,6 +48,9 @@ impl Default for DocumentMetadata {
 			hidden: HashSet::new(),
 			locked: HashSet::new(),
 			click_targets: HashMap::new(),
+			overflowing_text: HashSet::new(),
+			spatial_index: LayerSpatialIndex::default(),
+			picking_buffer: None,
 			document_to_viewport: DAffine2::IDENTITY,
 		}
 	}
@@ -225,6 +236,9 @@ impl DocumentMetadata {
 
 		self.upstream_transforms.retain(|node, _| graph.nodes.contains_key(node));
 		self.click_targets.retain(|layer, _| self.structure.contains_key(layer));
+		// Moving layers between folders can change the transforms they inherit
+		self.refresh_spatial_index(|_, _| false);
+		self.picking_buffer = None;
 	}
 }
 
@@ -236,6 +250,8 @@ impl DocumentMetadata {
 	/// Update the cached transforms of the layers
 	pub fn update_transforms(&mut self, new_upstream_transforms: HashMap<NodeId, (Footprint, DAffine2)>) {
 		self.upstream_transforms = new_upstream_transforms;
+		self.refresh_spatial_index(|_, _| false);
+		self.picking_buffer = None;
 	}
 
 	/// Access the cached transformation to document space from layer space
@@ -283,7 +299,81 @@ impl DocumentMetadata {
 impl DocumentMetadata {
 	/// Update the cached click targets of the layers
 	pub fn update_click_targets(&mut self, new_click_targets: HashMap<LayerNodeIdentifier, Vec<ClickTarget>>) {
-		self.click_targets = new_click_targets;
+		let previous_click_targets = std::mem::replace(&mut self.click_targets, new_click_targets);
+		self.refresh_spatial_index(|layer, click_targets| previous_click_targets.get(&layer).map(Vec::as_slice) != Some(click_targets));
+		self.picking_buffer = None;
+	}
+
+	/// Starts a picking buffer of the viewport's size for documents with enough layers to need one, returning its key and the SVG the frontend rasterizes into it.
+	pub fn request_picking_buffer(&mut self, key: u64, viewport_size: UVec2) -> Option<String> {
+		if self.click_targets.len() < PICKING_BUFFER_MIN_LAYERS || viewport_size.cmpeq(UVec2::ZERO).any() {
+			self.picking_buffer = None;
+			return None;
+		}
+
+		let (picking_buffer, svg) = PickingBuffer::new(key, self, viewport_size);
+		self.picking_buffer = Some(picking_buffer);
+		Some(svg)
+	}
+
+	/// Fills the picking buffer with the pixels the frontend rasterized for it, unless the layers have changed since it was requested.
+	pub fn fill_picking_buffer(&mut self, key: u64, rgba: &[u8]) {
+		if let Some(picking_buffer) = self.picking_buffer.as_mut().filter(|picking_buffer| picking_buffer.key() == key) {
+			picking_buffer.fill(rgba);
+		}
+	}
+
+	/// The deepest layer under a point in viewport space, read from the picking buffer, or `None` if the buffer isn't available to tell.
+	pub fn picked_layer(&self, viewport_location: DVec2) -> Option<Option<LayerNodeIdentifier>> {
+		self.picking_buffer.as_ref()?.layer_at(self.document_to_viewport, viewport_location)
+	}
+
+	/// Update the text layers whose text overflows its text box
+	pub fn update_overflowing_text(&mut self, overflowing_text: HashSet<LayerNodeIdentifier>) {
+		self.overflowing_text = overflowing_text;
+	}
+
+	/// Whether the text of the layer doesn't fit its text box, so the lines at its end are hidden
+	pub fn text_overflows(&self, layer: LayerNodeIdentifier) -> bool {
+		self.overflowing_text.contains(&layer)
+	}
+
+	/// Recomputes the spatial index bounds of the layers whose transform has changed or for which `click_targets_changed` returns true, and drops the layers without click targets.
+	fn refresh_spatial_index(&mut self, click_targets_changed: impl Fn(LayerNodeIdentifier, &[ClickTarget]) -> bool) {
+		let updates = self
+			.click_targets
+			.iter()
+			.filter_map(|(&layer, click_targets)| {
+				let transform = self.transform_to_document(layer);
+				let unchanged = self.spatial_index.indexed_transform(layer) == Some(transform) && !click_targets_changed(layer, click_targets);
+				(!unchanged).then(|| (layer, transform, Self::click_target_bounds(click_targets, transform)))
+			})
+			.collect::<Vec<_>>();
+
+		for (layer, transform, bounds) in updates {
+			self.spatial_index.update(layer, transform, bounds);
+		}
+		self.spatial_index.retain(|layer| self.click_targets.contains_key(&layer));
+	}
+
+	/// The document space bounds of the click targets, grown by half the stroke width in the same way as [`ClickTarget::intersect_point`] so that every hit is inside them.
+	fn click_target_bounds(click_targets: &[ClickTarget], transform: DAffine2) -> Option<[DVec2; 2]> {
+		let [min, max] = click_targets
+			.iter()
+			.filter_map(|click_target| click_target.subpath.bounding_box_with_transform(transform))
+			.reduce(Quad::combine_bounds)?;
+		let stroke_width = click_targets.iter().map(|click_target| click_target.stroke_width).fold(0., f64::max);
+		Some([min - DVec2::splat(stroke_width / 2.), max + DVec2::splat(stroke_width / 2.)])
+	}
+
+	/// The layers whose click target bounds overlap the given document space bounds, in no particular order.
+	pub fn layers_intersecting_bounds(&self, bounds: [DVec2; 2]) -> impl Iterator<Item = LayerNodeIdentifier> + '_ {
+		self.spatial_index.intersecting(bounds)
+	}
+
+	/// The layers whose click target bounds contain the given document space point, in no particular order.
+	pub fn layers_at_point(&self, point: DVec2) -> impl Iterator<Item = LayerNodeIdentifier> + '_ {
+		self.spatial_index.containing_point(point)
 	}
 
 	/// Get the bounding box of the click target of the specified layer in the specified transform space
@@ -344,6 +434,13 @@ impl DocumentMetadata {
 			.reduce(Quad::combine_bounds)
 	}
 
+	/// The top left corner, in document space, of the artboard which contains the layer (or is the layer itself).
+	/// This is the origin that artboard-relative coordinates are measured from.
+	pub fn artboard_origin(&self, layer: LayerNodeIdentifier) -> Option<DVec2> {
+		let artboard = layer.ancestors(self).find(|&ancestor| self.is_artboard(ancestor))?;
+		self.bounding_box_document(artboard).map(|[top_left, _]| top_left)
+	}
+
 	pub fn layer_outline(&self, layer: LayerNodeIdentifier) -> impl Iterator<Item = &bezier_rs::Subpath<PointId>> {
 		static EMPTY: Vec<ClickTarget> = Vec::new();
 		let click_targets = self.click_targets.get(&layer).unwrap_or(&EMPTY);
@@ -0,0 +1,386 @@
+use super::error::EditorError;
+use crate::messages::portfolio::document::node_graph::document_node_types::resolve_document_node_type;
+use crate::messages::prelude::DocumentMessageHandler;
+
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{DocumentNode, DocumentNodeImplementation, NodeInput, NodeNetwork};
+use graphene_core::raster::BlendMode;
+use graphene_core::text::{FontVariations, TextAlign, TextAutoResize, TextKerning, TextRuns};
+use graphene_core::{ArtboardGroup, Color};
+use graphene_std::vector::style::{Fill, FillType, Gradient, StrokeAlign};
+
+use serde_json::Value;
+
+/// The version of the document format written by this build of the editor.
+/// Increment this whenever a change to the editor or the node graph would stop older documents from loading (or loading correctly), and add a [`Migration`] from the previous version.
+pub const DOCUMENT_VERSION: u32 = 8;
+
+/// The name of the field in the serialized document which stores its [`DOCUMENT_VERSION`].
+const VERSION_FIELD: &str = "document_version";
+
+/// Upgrades documents saved with one version of the document format to the next version.
+struct Migration {
+	/// The version which this migration upgrades documents from, to the version after it.
+	from_version: u32,
+	/// Restructures the serialized document before it's deserialized, for changes which would otherwise cause deserialization to fail, such as renamed or restructured types.
+	serialized: Option<fn(&mut Value)>,
+	/// Modifies the deserialized document, for changes which deserialize successfully but need updating, such as node definitions with new inputs.
+	deserialized: Option<fn(&mut DocumentMessageHandler)>,
+}
+
+/// Every migration, in order of the version they upgrade from.
+const MIGRATIONS: &[Migration] = &[
+	Migration {
+		from_version: 0,
+		serialized: None,
+		deserialized: Some(upgrade_unversioned_nodes),
+	},
+	Migration {
+		from_version: 1,
+		serialized: None,
+		deserialized: Some(add_text_layout_inputs),
+	},
+	Migration {
+		from_version: 2,
+		serialized: None,
+		deserialized: Some(add_text_auto_resize_input),
+	},
+	Migration {
+		from_version: 3,
+		serialized: None,
+		deserialized: Some(add_text_variations_input),
+	},
+	Migration {
+		from_version: 4,
+		serialized: None,
+		deserialized: Some(add_text_runs_input),
+	},
+	Migration {
+		from_version: 5,
+		serialized: None,
+		deserialized: Some(add_text_kerning_input),
+	},
+	Migration {
+		from_version: 6,
+		serialized: None,
+		deserialized: Some(add_artboard_linked_input),
+	},
+	Migration {
+		from_version: 7,
+		serialized: None,
+		deserialized: Some(add_fill_and_stroke_blending_inputs),
+	},
+];
+
+/// Reads the version of the serialized document and applies the migrations which restructure it, returning the version it was saved with.
+pub fn migrate_serialized_document(document: &mut Value) -> Result<u32, EditorError> {
+	let version = match document.get(VERSION_FIELD) {
+		Some(version) => version
+			.as_u64()
+			.and_then(|version| u32::try_from(version).ok())
+			.ok_or_else(|| EditorError::DocumentDeserialization(format!("Invalid document version: {version}")))?,
+		None => 0,
+	};
+	if version > DOCUMENT_VERSION {
+		return Err(EditorError::DocumentFromNewerVersion(version, DOCUMENT_VERSION));
+	}
+
+	for migration in migrations_from(version) {
+		if let Some(migrate) = migration.serialized {
+			migrate(document);
+		}
+	}
+
+	Ok(version)
+}
+
+/// Applies the migrations which modify the deserialized document, for a document that was saved with the given version.
+pub fn migrate_document(document: &mut DocumentMessageHandler, version: u32) {
+	for migration in migrations_from(version) {
+		if let Some(migrate) = migration.deserialized {
+			migrate(document);
+		}
+	}
+}
+
+fn migrations_from(version: u32) -> impl Iterator<Item = &'static Migration> {
+	MIGRATIONS.iter().filter(move |migration| migration.from_version >= version)
+}
+
+/// Calls `migrate` on every node of the network, including the nodes of the networks nested within it, which are migrated before the node containing them.
+fn for_each_node(network: &mut NodeNetwork, migrate: &mut impl FnMut(&mut DocumentNode)) {
+	for node in network.nodes.values_mut() {
+		if let DocumentNodeImplementation::Network(nested_network) = &mut node.implementation {
+			for_each_node(nested_network, migrate);
+		}
+		migrate(node);
+	}
+}
+
+/// Replaces the implementation of the node with the one from its definition in this build, which expects the inputs added by the migrations.
+fn update_implementation(node: &mut DocumentNode) {
+	if let Some(node_definition) = resolve_document_node_type(&node.name) {
+		node.implementation = node_definition.default_document_node().implementation.clone();
+	}
+}
+
+/// Appends the inputs which a migration added to nodes with the given name that still have the number of inputs from before the migration.
+///
+/// The added inputs are given by the migration rather than taken from the node's current definition, which may have gained more inputs (or changed their defaults) in later versions.
+fn add_inputs(document: &mut DocumentMessageHandler, name: &str, old_input_count: usize, added_inputs: impl Fn() -> Vec<NodeInput>) {
+	for_each_node(&mut document.network, &mut |node| {
+		if node.name == name && node.inputs.len() == old_input_count {
+			update_implementation(node);
+			node.inputs.extend(added_inputs());
+		}
+	});
+}
+
+/// Upgrades nodes of documents saved before the document format was versioned.
+fn upgrade_unversioned_nodes(document: &mut DocumentMessageHandler) {
+	for_each_node(&mut document.network, &mut |node| {
+		// Upgrade Fill nodes to the format change in #1778
+		// TODO: Eventually remove this (probably starting late 2024)
+		if node.name == "Fill" && node.inputs.len() == 8 {
+			update_implementation(node);
+			let fill = unversioned_fill(&node.inputs);
+			let (backup_color, backup_gradient) = match &fill {
+				Some(Fill::Solid(color)) => (Some(*color), Gradient::default()),
+				Some(Fill::Gradient(gradient)) => (Some(Color::BLACK), gradient.clone()),
+				Some(Fill::None) => (None, Gradient::default()),
+				None => (Some(Color::BLACK), Gradient::default()),
+			};
+			node.inputs = vec![
+				node.inputs[0].clone(),
+				NodeInput::value(TaggedValue::Fill(fill.unwrap_or(Fill::Solid(Color::BLACK))), false),
+				NodeInput::value(TaggedValue::OptionalColor(backup_color), false),
+				NodeInput::value(TaggedValue::Gradient(backup_gradient), false),
+			];
+		}
+
+		// Upgrade Stroke nodes to include the stroke alignment input
+		if node.name == "Stroke" && node.inputs.len() == 8 {
+			update_implementation(node);
+			node.inputs.push(NodeInput::value(TaggedValue::StrokeAlign(StrokeAlign::Center), false));
+		}
+	});
+}
+
+/// Reads the fill from the fill type, solid color, and gradient inputs which Fill nodes had before #1778.
+fn unversioned_fill(inputs: &[NodeInput]) -> Option<Fill> {
+	let value = |index: usize| inputs[index].as_value();
+	let (
+		Some(TaggedValue::FillType(fill_type)),
+		Some(TaggedValue::OptionalColor(solid_color)),
+		Some(TaggedValue::GradientType(gradient_type)),
+		Some(TaggedValue::DVec2(start)),
+		Some(TaggedValue::DVec2(end)),
+		Some(TaggedValue::DAffine2(transform)),
+		Some(TaggedValue::GradientStops(positions)),
+	) = (value(1), value(2), value(3), value(4), value(5), value(6), value(7))
+	else {
+		return None;
+	};
+
+	Some(match (fill_type, solid_color) {
+		(FillType::Solid, None) => Fill::None,
+		(FillType::Solid, Some(color)) => Fill::Solid(*color),
+		(FillType::Gradient, _) => Fill::Gradient(Gradient {
+			stops: positions.clone(),
+			gradient_type: *gradient_type,
+			start: *start,
+			end: *end,
+			transform: *transform,
+			..Default::default()
+		}),
+	})
+}
+
+/// Adds the line height, text box, and alignment inputs to Text nodes, which default to laying out the text as before.
+fn add_text_layout_inputs(document: &mut DocumentMessageHandler) {
+	add_inputs(document, "Text", 4, || {
+		vec![
+			NodeInput::value(TaggedValue::F64(1.), false),
+			NodeInput::value(TaggedValue::F64(0.), false),
+			NodeInput::value(TaggedValue::F64(0.), false),
+			NodeInput::value(TaggedValue::TextAlign(TextAlign::Start), false),
+		]
+	});
+}
+
+/// Adds the auto-resize input to Text nodes, which defaults to keeping the text box's height.
+fn add_text_auto_resize_input(document: &mut DocumentMessageHandler) {
+	add_inputs(document, "Text", 8, || vec![NodeInput::value(TaggedValue::TextAutoResize(TextAutoResize::Fixed), false)]);
+}
+
+/// Adds the font variations input to Text nodes, which defaults to the font's default instance.
+fn add_text_variations_input(document: &mut DocumentMessageHandler) {
+	add_inputs(document, "Text", 9, || vec![NodeInput::value(TaggedValue::FontVariations(FontVariations::default()), false)]);
+}
+
+/// Adds the style runs input to Text nodes, which defaults to the whole text having the style of the node.
+fn add_text_runs_input(document: &mut DocumentMessageHandler) {
+	add_inputs(document, "Text", 10, || vec![NodeInput::value(TaggedValue::TextRuns(TextRuns::default()), false)]);
+}
+
+/// Adds the kerning input to Text nodes, which defaults to the font's own kerning without any manually kerned pairs.
+fn add_text_kerning_input(document: &mut DocumentMessageHandler) {
+	add_inputs(document, "Text", 11, || vec![NodeInput::value(TaggedValue::TextKerning(TextKerning::default()), false)]);
+}
+
+/// Adds the linked input to Artboard nodes, which defaults to the artboard showing its own contents.
+fn add_artboard_linked_input(document: &mut DocumentMessageHandler) {
+	add_inputs(document, "Artboard", 6, || vec![NodeInput::value(TaggedValue::ArtboardGroup(ArtboardGroup::EMPTY), true)]);
+}
+
+/// Adds the opacity and blend mode inputs to Fill and Stroke nodes, which default to compositing them together with the rest of the layer as before.
+fn add_fill_and_stroke_blending_inputs(document: &mut DocumentMessageHandler) {
+	let blending_inputs = || vec![NodeInput::value(TaggedValue::F64(100.), false), NodeInput::value(TaggedValue::BlendMode(BlendMode::Normal), false)];
+	add_inputs(document, "Fill", 4, blending_inputs);
+	add_inputs(document, "Stroke", 9, blending_inputs);
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graph_craft::document::NodeId;
+	use graphene_core::text::Font;
+	use graphene_core::GraphicGroup;
+	use graphene_std::vector::style::GradientType;
+
+	use glam::{DAffine2, DVec2, IVec2};
+
+	fn node(name: &str, inputs: Vec<TaggedValue>) -> DocumentNode {
+		DocumentNode {
+			name: name.to_string(),
+			inputs: inputs.into_iter().map(|value| NodeInput::value(value, false)).collect(),
+			..Default::default()
+		}
+	}
+
+	fn version_1_text() -> DocumentNode {
+		let font = Font::new(graphene_core::consts::DEFAULT_FONT_FAMILY.into(), graphene_core::consts::DEFAULT_FONT_STYLE.into());
+		node(
+			"Text",
+			vec![TaggedValue::None, TaggedValue::String("Migrated".to_string()), TaggedValue::Font(font), TaggedValue::F64(30.)],
+		)
+	}
+
+	fn current_input_count(name: &str) -> usize {
+		resolve_document_node_type(name).unwrap().default_document_node().inputs.len()
+	}
+
+	/// Saves the document as though it was saved with the given version of the document format, then opens it again.
+	fn reopen_as_version(document: &DocumentMessageHandler, version: u32) -> DocumentMessageHandler {
+		let mut serialized = serde_json::to_value(document).unwrap();
+		serialized[VERSION_FIELD] = version.into();
+		DocumentMessageHandler::deserialize_document(&serialized.to_string()).unwrap()
+	}
+
+	#[test]
+	fn version_1_document_is_migrated() {
+		let nested_network = NodeNetwork {
+			nodes: [(NodeId(10), version_1_text())].into_iter().collect(),
+			..Default::default()
+		};
+		let group = DocumentNode {
+			name: "Text Group".to_string(),
+			implementation: DocumentNodeImplementation::Network(nested_network),
+			..Default::default()
+		};
+		let fill = node(
+			"Fill",
+			vec![
+				TaggedValue::None,
+				TaggedValue::Fill(Fill::Solid(Color::RED)),
+				TaggedValue::OptionalColor(Some(Color::RED)),
+				TaggedValue::Gradient(Gradient::default()),
+			],
+		);
+		let artboard = node(
+			"Artboard",
+			vec![
+				TaggedValue::ArtboardGroup(ArtboardGroup::EMPTY),
+				TaggedValue::GraphicGroup(GraphicGroup::EMPTY),
+				TaggedValue::IVec2(IVec2::ZERO),
+				TaggedValue::IVec2(IVec2::new(800, 600)),
+				TaggedValue::Color(Color::WHITE),
+				TaggedValue::Bool(true),
+			],
+		);
+
+		let mut document = DocumentMessageHandler::default();
+		document
+			.network
+			.nodes
+			.extend([(NodeId(1), version_1_text()), (NodeId(2), group), (NodeId(3), fill), (NodeId(4), artboard)]);
+		let document = reopen_as_version(&document, 1);
+		let nodes = &document.network.nodes;
+
+		let text = &nodes[&NodeId(1)];
+		assert_eq!(text.inputs.len(), current_input_count("Text"));
+		assert_eq!(text.inputs[3].as_value(), Some(&TaggedValue::F64(30.)));
+		assert_eq!(text.inputs[4].as_value(), Some(&TaggedValue::F64(1.)));
+		assert_eq!(text.inputs[8].as_value(), Some(&TaggedValue::TextAutoResize(TextAutoResize::Fixed)));
+
+		// Nodes within nested networks are migrated too, while the node containing them keeps its own implementation
+		let DocumentNodeImplementation::Network(nested_network) = &nodes[&NodeId(2)].implementation else {
+			panic!("The group's network was replaced")
+		};
+		assert_eq!(nested_network.nodes[&NodeId(10)].inputs, text.inputs);
+
+		let fill = &nodes[&NodeId(3)];
+		assert_eq!(fill.inputs.len(), current_input_count("Fill"));
+		assert_eq!(fill.inputs[1].as_value(), Some(&TaggedValue::Fill(Fill::Solid(Color::RED))));
+		assert_eq!(fill.inputs[4].as_value(), Some(&TaggedValue::F64(100.)));
+
+		let artboard = &nodes[&NodeId(4)];
+		assert_eq!(artboard.inputs.len(), current_input_count("Artboard"));
+		assert_eq!(artboard.inputs[5].as_value(), Some(&TaggedValue::Bool(true)));
+		assert!(artboard.inputs[6].is_exposed());
+	}
+
+	#[test]
+	fn unversioned_fill_is_migrated() {
+		let stops = Gradient::default().stops;
+		let transform = DAffine2::from_scale(DVec2::splat(2.));
+		let fill = node(
+			"Fill",
+			vec![
+				TaggedValue::None,
+				TaggedValue::FillType(FillType::Gradient),
+				TaggedValue::OptionalColor(None),
+				TaggedValue::GradientType(GradientType::Radial),
+				TaggedValue::DVec2(DVec2::ZERO),
+				TaggedValue::DVec2(DVec2::ONE),
+				TaggedValue::DAffine2(transform),
+				TaggedValue::GradientStops(stops.clone()),
+			],
+		);
+
+		let mut document = DocumentMessageHandler::default();
+		document.network.nodes.insert(NodeId(1), fill);
+		let document = reopen_as_version(&document, 0);
+
+		let gradient = Gradient {
+			stops,
+			gradient_type: GradientType::Radial,
+			start: DVec2::ZERO,
+			end: DVec2::ONE,
+			transform,
+			..Default::default()
+		};
+		let fill = &document.network.nodes[&NodeId(1)];
+		assert_eq!(fill.inputs.len(), current_input_count("Fill"));
+		assert_eq!(fill.inputs[1].as_value(), Some(&TaggedValue::Fill(Fill::Gradient(gradient.clone()))));
+		assert_eq!(fill.inputs[3].as_value(), Some(&TaggedValue::Gradient(gradient)));
+	}
+
+	#[test]
+	fn documents_from_newer_versions_are_rejected() {
+		let mut serialized = serde_json::json!({});
+		serialized[VERSION_FIELD] = (DOCUMENT_VERSION + 1).into();
+		assert!(matches!(migrate_serialized_document(&mut serialized), Err(EditorError::DocumentFromNewerVersion(..))));
+	}
+}
@@ -20,6 +20,9 @@ pub enum EditorError {
 	#[error("This document was created in an older version of the editor.\n\nBackwards compatibility is, regrettably, not present in the current alpha release.\n\nTechnical details:\n{0:?}")]
 	DocumentDeserialization(String),
 
+	#[error("This document was saved by a newer version of the editor, using document format version {0}, but this version of the editor only supports up to version {1}.")]
+	DocumentFromNewerVersion(u32, u32),
+
 	#[error("{0}")]
 	Misc(String),
 }
@@ -0,0 +1,60 @@
+use bezier_rs::{Bezier, Subpath};
+use glam::DVec2;
+use graphene_core::vector::PointId;
+
+/// A non-printing line or shape drawn over the canvas, which artwork can be snapped to but which is never rendered or exported.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Guide {
+	/// A horizontal line across the whole canvas at this y coordinate in document space.
+	Horizontal(f64),
+	/// A vertical line across the whole canvas at this x coordinate in document space.
+	Vertical(f64),
+	/// The outline of a former path layer, in document space.
+	Path(Vec<Subpath<PointId>>),
+}
+
+impl Guide {
+	/// The curves that make up the guide, where its lines are cut off to span the given bounds in document space.
+	pub fn curves(&self, [min, max]: [DVec2; 2]) -> Vec<Bezier> {
+		match self {
+			Self::Horizontal(y) => vec![Bezier::from_linear_dvec2(DVec2::new(min.x, *y), DVec2::new(max.x, *y))],
+			Self::Vertical(x) => vec![Bezier::from_linear_dvec2(DVec2::new(*x, min.y), DVec2::new(*x, max.y))],
+			Self::Path(subpaths) => subpaths.iter().flat_map(|subpath| subpath.iter()).collect(),
+		}
+	}
+}
+
+/// The guides stored in a document, which are drawn as overlays and participate in snapping.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DocumentGuides {
+	pub guides: Vec<Guide>,
+}
+
+impl DocumentGuides {
+	pub fn is_empty(&self) -> bool {
+		self.guides.is_empty()
+	}
+
+	/// Adds a guide unless an identical one already exists.
+	pub fn add(&mut self, guide: Guide) {
+		if !self.guides.contains(&guide) {
+			self.guides.push(guide);
+		}
+	}
+
+	/// Adds guides along the left, center, and right as well as the top, center, and bottom of the bounds in document space.
+	pub fn add_from_bounds(&mut self, [min, max]: [DVec2; 2]) {
+		let center = (min + max) / 2.;
+		for x in [min.x, center.x, max.x] {
+			self.add(Guide::Vertical(x));
+		}
+		for y in [min.y, center.y, max.y] {
+			self.add(Guide::Horizontal(y));
+		}
+	}
+
+	pub fn clear(&mut self) {
+		self.guides.clear();
+	}
+}
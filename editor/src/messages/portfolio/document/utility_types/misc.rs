@@ -15,6 +15,26 @@ pub enum FlipAxis {
 	Y,
 }
 
+/// A direction on screen, used to move the selection to the nearest layer on that side of it.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize, Hash)]
+pub enum ViewportDirection {
+	Up,
+	Down,
+	Left,
+	Right,
+}
+
+impl ViewportDirection {
+	pub fn vector(self) -> glam::DVec2 {
+		match self {
+			Self::Up => -glam::DVec2::Y,
+			Self::Down => glam::DVec2::Y,
+			Self::Left => -glam::DVec2::X,
+			Self::Right => glam::DVec2::X,
+		}
+	}
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize, Hash, specta::Type)]
 pub enum AlignAxis {
 	X,
@@ -67,6 +87,7 @@ pub struct SnappingState {
 	pub grid: GridSnapping,
 	pub tolerance: f64,
 	pub artboards: bool,
+	pub guides: bool,
 }
 
 impl Default for SnappingState {
@@ -79,6 +100,7 @@ impl Default for SnappingState {
 			grid: Default::default(),
 			tolerance: 8.,
 			artboards: true,
+			guides: true,
 		}
 	}
 }
@@ -106,6 +128,7 @@ impl SnappingState {
 			},
 			SnapTarget::Board(_) => self.artboards,
 			SnapTarget::Grid(_) => self.grid_snapping,
+			SnapTarget::Guide(_) => self.guides,
 			_ => false,
 		}
 	}
@@ -370,6 +393,12 @@ pub enum GridSnapTarget {
 	Intersection,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuideSnapTarget {
+	Line,
+	Path,
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SnapTarget {
 	#[default]
@@ -378,6 +407,7 @@ pub enum SnapTarget {
 	Geometry(GeometrySnapTarget),
 	Board(BoardSnapTarget),
 	Grid(GridSnapTarget),
+	Guide(GuideSnapTarget),
 }
 
 impl SnapTarget {
@@ -1,7 +1,19 @@
 pub mod clipboards;
+pub mod custom_nodes;
+pub mod document_info;
 pub mod document_metadata;
+pub mod document_migration;
 pub mod error;
+pub mod guides;
 pub mod misc;
 pub mod node_metadata;
+pub mod node_presets;
 pub mod nodes;
+pub mod palette_file;
+pub mod parameters;
+pub mod picking_buffer;
+pub mod spatial_index;
+pub mod swatches;
+pub mod timeline;
 pub mod transformation;
+pub mod variables;
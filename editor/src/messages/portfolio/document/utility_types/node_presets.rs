@@ -0,0 +1,239 @@
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{DocumentNode, NodeInput};
+
+use std::collections::HashMap;
+
+/// The version of the node presets file format, which is raised whenever the format changes in a way older versions of the editor can't read.
+pub const NODE_PRESETS_FORMAT_VERSION: u32 = 1;
+
+/// The saved input values of one node, which can be given to any node made from the same definition.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PresetNode {
+	/// The name of the definition the node was made from, such as "Blur".
+	pub reference: String,
+	/// The value of each input, or none for inputs which were connected to another node when the preset was saved.
+	pub values: Vec<Option<TaggedValue>>,
+}
+
+impl PresetNode {
+	pub fn from_node(node: &DocumentNode) -> Self {
+		Self {
+			reference: node.name.clone(),
+			values: node.inputs.iter().map(|input| input.as_value().cloned()).collect(),
+		}
+	}
+
+	/// Whether the node is made from the same definition, with the same inputs, as the node the values were saved from.
+	pub fn is_compatible(&self, node: &DocumentNode) -> bool {
+		node.name == self.reference && node.inputs.len() == self.values.len()
+	}
+
+	/// Gives the saved values to the inputs of the node which hold a value of the same type, returning whether any of them changed.
+	/// Inputs connected to another node are left alone.
+	pub fn apply(&self, node: &mut DocumentNode) -> bool {
+		if !self.is_compatible(node) {
+			return false;
+		}
+
+		let mut changed = false;
+		for (input, value) in node.inputs.iter_mut().zip(&self.values) {
+			let (NodeInput::Value { tagged_value, .. }, Some(value)) = (input, value) else { continue };
+			if core::mem::discriminant(tagged_value) == core::mem::discriminant(value) && tagged_value != value {
+				*tagged_value = value.clone();
+				changed = true;
+			}
+		}
+		changed
+	}
+}
+
+/// A named bundle of input values saved from one or more nodes, which can be applied to other nodes of the same kinds.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NodePreset {
+	pub id: u64,
+	pub name: String,
+	pub nodes: Vec<PresetNode>,
+}
+
+impl NodePreset {
+	/// Whether any of the saved nodes can give its values to the node.
+	pub fn applies_to(&self, node: &DocumentNode) -> bool {
+		self.nodes.iter().any(|preset_node| preset_node.is_compatible(node))
+	}
+}
+
+/// The node presets kept in the preferences, which are shared by every document.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct NodePresetLibrary {
+	pub presets: Vec<NodePreset>,
+}
+
+impl NodePresetLibrary {
+	pub fn get(&self, id: u64) -> Option<&NodePreset> {
+		self.presets.iter().find(|preset| preset.id == id)
+	}
+
+	pub fn add(&mut self, preset: NodePreset) {
+		self.presets.push(preset);
+	}
+
+	pub fn remove(&mut self, id: u64) -> Option<NodePreset> {
+		let index = self.presets.iter().position(|preset| preset.id == id)?;
+		Some(self.presets.remove(index))
+	}
+
+	pub fn rename(&mut self, id: u64, name: String) {
+		if let Some(preset) = self.presets.iter_mut().find(|preset| preset.id == id) {
+			preset.name = name;
+		}
+	}
+
+	/// The presets which can be applied to at least one of the nodes.
+	pub fn applicable<'a>(&'a self, nodes: &'a [&'a DocumentNode]) -> impl Iterator<Item = &'a NodePreset> + 'a {
+		self.presets.iter().filter(|preset| nodes.iter().any(|node| preset.applies_to(node)))
+	}
+}
+
+/// Gives the saved values to the nodes, each taking them from the saved node of its kind in the same order, so a preset saved from two Blur nodes gives
+/// the first selected Blur node the values of the first and every other one the values of the second. Returns whether any node changed.
+pub fn apply_preset<'a>(preset_nodes: &[PresetNode], nodes: impl Iterator<Item = &'a mut DocumentNode>) -> bool {
+	let mut applied_counts = HashMap::<String, usize>::new();
+	let mut changed = false;
+
+	for node in nodes {
+		let compatible = preset_nodes.iter().filter(|preset_node| preset_node.is_compatible(node)).collect::<Vec<_>>();
+		let Some(&last) = compatible.last() else { continue };
+
+		let count = applied_counts.entry(node.name.clone()).or_default();
+		let preset_node = compatible.get(*count).copied().unwrap_or(last);
+		*count += 1;
+
+		changed |= preset_node.apply(node);
+	}
+	changed
+}
+
+/// The contents of a node presets file, which holds the version of its format so files saved by newer versions of the editor are recognized.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct NodePresetsFile {
+	version: u32,
+	presets: Vec<NodePreset>,
+}
+
+/// Writes the presets as the JSON of a node presets file.
+pub fn write_presets(presets: &[NodePreset]) -> String {
+	let file = NodePresetsFile {
+		version: NODE_PRESETS_FORMAT_VERSION,
+		presets: presets.to_vec(),
+	};
+	serde_json::to_string_pretty(&file).expect("Node presets should always serialize")
+}
+
+/// Reads the presets of a node presets file, describing why if it can't be read.
+pub fn read_presets(content: &str) -> Result<Vec<NodePreset>, String> {
+	let file = serde_json::from_str::<NodePresetsFile>(content).map_err(|error| format!("The file isn't a valid node presets file: {error}"))?;
+	if file.version > NODE_PRESETS_FORMAT_VERSION {
+		return Err("The node presets were saved by a newer version of Graphite, which must be used to import them".into());
+	}
+	Ok(file.presets)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use graph_craft::document::NodeId;
+
+	fn node(name: &str, inputs: Vec<NodeInput>) -> DocumentNode {
+		DocumentNode {
+			name: name.into(),
+			inputs,
+			..Default::default()
+		}
+	}
+
+	fn blur(radius: f64) -> DocumentNode {
+		node("Blur", vec![NodeInput::node(NodeId(1), 0), NodeInput::value(TaggedValue::F64(radius), false)])
+	}
+
+	fn radius(node: &DocumentNode) -> Option<&TaggedValue> {
+		node.inputs[1].as_value()
+	}
+
+	#[test]
+	fn only_nodes_of_the_same_definition_and_inputs_are_compatible() {
+		let preset_node = PresetNode::from_node(&blur(4.));
+		assert_eq!(preset_node.values, vec![None, Some(TaggedValue::F64(4.))]);
+
+		assert!(preset_node.is_compatible(&blur(1.)));
+		assert!(!preset_node.is_compatible(&node("Blur", vec![NodeInput::value(TaggedValue::F64(1.), false)])));
+		assert!(!preset_node.is_compatible(&node("Dehaze", vec![NodeInput::node(NodeId(1), 0), NodeInput::value(TaggedValue::F64(1.), false)])));
+	}
+
+	#[test]
+	fn applying_skips_connected_inputs_and_values_of_another_type() {
+		let preset_node = PresetNode {
+			reference: "Blur".into(),
+			values: vec![Some(TaggedValue::F64(9.)), Some(TaggedValue::U32(9))],
+		};
+		let mut target = blur(1.);
+		assert!(!preset_node.apply(&mut target));
+		assert_eq!(target.inputs[0].as_node(), Some(NodeId(1)));
+		assert_eq!(radius(&target), Some(&TaggedValue::F64(1.)));
+
+		let mut target = blur(1.);
+		assert!(PresetNode::from_node(&blur(4.)).apply(&mut target));
+		assert_eq!(radius(&target), Some(&TaggedValue::F64(4.)));
+		assert!(!PresetNode::from_node(&blur(4.)).apply(&mut target));
+	}
+
+	#[test]
+	fn saved_nodes_are_given_in_order_with_the_last_repeated() {
+		let preset_nodes = [PresetNode::from_node(&blur(2.)), PresetNode::from_node(&blur(3.))];
+		let mut nodes = [blur(1.), node("Dehaze", vec![]), blur(1.), blur(1.)];
+
+		assert!(apply_preset(&preset_nodes, nodes.iter_mut()));
+		let radii = nodes.iter().filter(|node| node.name == "Blur").map(radius).collect::<Vec<_>>();
+		assert_eq!(radii, [Some(&TaggedValue::F64(2.)), Some(&TaggedValue::F64(3.)), Some(&TaggedValue::F64(3.))]);
+	}
+
+	#[test]
+	fn applicable_presets_match_any_selected_node() {
+		let mut library = NodePresetLibrary::default();
+		library.add(NodePreset {
+			id: 1,
+			name: "Soft".into(),
+			nodes: vec![PresetNode::from_node(&blur(2.))],
+		});
+		library.add(NodePreset {
+			id: 2,
+			name: "Clear".into(),
+			nodes: vec![PresetNode::from_node(&node("Dehaze", vec![]))],
+		});
+
+		let (dehaze, blurred) = (node("Dehaze", vec![]), blur(1.));
+		let ids = |nodes: &[&DocumentNode]| library.applicable(nodes).map(|preset| preset.id).collect::<Vec<_>>();
+		assert_eq!(ids(&[&blurred]), [1]);
+		assert_eq!(ids(&[&dehaze, &blurred]), [1, 2]);
+		assert!(ids(&[&node("Levels", vec![])]).is_empty());
+
+		library.rename(2, "Haze".into());
+		assert_eq!(library.get(2).map(|preset| preset.name.as_str()), Some("Haze"));
+		assert_eq!(library.remove(1).map(|preset| preset.id), Some(1));
+		assert!(library.get(1).is_none() && library.remove(1).is_none());
+	}
+
+	#[test]
+	fn presets_files_round_trip_and_newer_versions_are_refused() {
+		let presets = vec![NodePreset {
+			id: 7,
+			name: "Soft".into(),
+			nodes: vec![PresetNode::from_node(&blur(2.))],
+		}];
+		assert_eq!(read_presets(&write_presets(&presets)), Ok(presets));
+
+		let newer = format!(r#"{{ "version": {}, "presets": [] }}"#, NODE_PRESETS_FORMAT_VERSION + 1);
+		assert!(read_presets(&newer).is_err());
+		assert!(read_presets("not json").is_err());
+	}
+}
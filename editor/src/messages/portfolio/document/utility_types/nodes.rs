@@ -50,6 +50,9 @@ pub struct LayerPanelEntry {
 	pub parents_unlocked: bool,
 	#[serde(rename = "parentId")]
 	pub parent_id: Option<NodeId>,
+	/// Whether the layer's text doesn't fit its text box, which is shown with a badge.
+	#[serde(rename = "textOverflows")]
+	pub text_overflows: bool,
 }
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, specta::Type)]
@@ -0,0 +1,377 @@
+//! Reading and writing the palette files of other applications, so palettes made elsewhere can be brought in as swatches and swatches can be taken back out.
+//! Adobe Swatch Exchange (`.ase`) files are used by Adobe's applications and many others, while GIMP palette (`.gpl`) files are used by GIMP, Inkscape, Krita, and Aseprite.
+//!
+//! Both formats only hold solid colors, so gradients are left out when writing them, and neither has an alpha channel, so colors are written as opaque.
+
+use super::swatches::{Swatch, SwatchLibrary};
+use crate::application::generate_uuid;
+
+use graphene_core::vector::style::Fill;
+use graphene_core::Color;
+
+use std::fmt::Write;
+
+/// The block types of an Adobe Swatch Exchange file.
+const ASE_GROUP_START: u16 = 0xC001;
+const ASE_GROUP_END: u16 = 0xC002;
+const ASE_COLOR: u16 = 0x0001;
+
+/// The color type of an Adobe Swatch Exchange color which is neither a global nor a spot color.
+const ASE_COLOR_TYPE_NORMAL: u16 = 2;
+
+/// The D50 white point which the Lab colors of Adobe Swatch Exchange files are relative to.
+const D50_WHITE: [f32; 3] = [0.9642, 1., 0.8249];
+
+/// Converts from XYZ relative to D50 to linear sRGB, including the Bradford adaptation from the D50 white point to sRGB's D65 white point.
+const XYZ_D50_TO_LINEAR_SRGB: [[f32; 3]; 3] = [[3.133_856, -1.616_867, -0.490_615], [-0.978_768, 1.916_142, 0.033_454], [0.071_945, -0.228_991, 1.405_243]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PaletteFormat {
+	/// Adobe Swatch Exchange
+	Ase,
+	/// GIMP palette
+	Gpl,
+}
+
+impl PaletteFormat {
+	pub fn from_filename(filename: &str) -> Option<Self> {
+		let (_, extension) = filename.rsplit_once('.')?;
+		match extension.to_ascii_lowercase().as_str() {
+			"ase" => Some(Self::Ase),
+			"gpl" => Some(Self::Gpl),
+			_ => None,
+		}
+	}
+
+	/// Tells the format apart by the signature at the start of the file, for files with some other extension.
+	pub fn from_contents(data: &[u8]) -> Option<Self> {
+		if data.starts_with(b"ASEF") {
+			Some(Self::Ase)
+		} else if data.starts_with(b"GIMP Palette") {
+			Some(Self::Gpl)
+		} else {
+			None
+		}
+	}
+
+	pub fn extension(self) -> &'static str {
+		match self {
+			Self::Ase => "ase",
+			Self::Gpl => "gpl",
+		}
+	}
+
+	pub fn mime(self) -> &'static str {
+		match self {
+			Self::Ase => "application/octet-stream",
+			Self::Gpl => "text/plain",
+		}
+	}
+}
+
+/// Reads the colors of a palette file as new swatches, in the order they're listed in the file.
+/// Colors without a name are named after their hex code.
+pub fn read_palette(filename: &str, data: &[u8]) -> Result<Vec<Swatch>, String> {
+	let format = PaletteFormat::from_filename(filename)
+		.or_else(|| PaletteFormat::from_contents(data))
+		.ok_or_else(|| format!("\"{filename}\" isn't an Adobe Swatch Exchange (.ase) or GIMP palette (.gpl) file"))?;
+
+	let colors = match format {
+		PaletteFormat::Ase => read_ase(data)?,
+		PaletteFormat::Gpl => read_gpl(std::str::from_utf8(data).map_err(|_| "The GIMP palette isn't valid UTF-8 text".to_string())?)?,
+	};
+
+	let swatches = colors
+		.into_iter()
+		.map(|(name, color)| Swatch {
+			id: generate_uuid(),
+			name: if name.is_empty() { format!("#{}", color.rgb_hex()) } else { name },
+			fill: Fill::Solid(color),
+		})
+		.collect();
+	Ok(swatches)
+}
+
+/// Writes the solid color swatches of the library as a palette file with the given name.
+pub fn write_palette(library: &SwatchLibrary, name: &str, format: PaletteFormat) -> Vec<u8> {
+	let colors = library.swatches.iter().filter_map(|swatch| match &swatch.fill {
+		Fill::Solid(color) => Some((swatch.name.as_str(), *color)),
+		_ => None,
+	});
+
+	match format {
+		PaletteFormat::Ase => write_ase(colors, name),
+		PaletteFormat::Gpl => write_gpl(colors, name).into_bytes(),
+	}
+}
+
+/// Reads big endian values from the bytes of a file, failing once it runs out of bytes.
+struct Reader<'a> {
+	data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	fn bytes(&mut self, length: usize) -> Result<&'a [u8], String> {
+		if self.data.len() < length {
+			return Err("The Adobe Swatch Exchange file ends unexpectedly".into());
+		}
+		let (bytes, rest) = self.data.split_at(length);
+		self.data = rest;
+		Ok(bytes)
+	}
+
+	fn u16(&mut self) -> Result<u16, String> {
+		Ok(u16::from_be_bytes(self.bytes(2)?.try_into().unwrap()))
+	}
+
+	fn u32(&mut self) -> Result<u32, String> {
+		Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+	}
+
+	fn f32(&mut self) -> Result<f32, String> {
+		Ok(f32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+	}
+}
+
+fn read_ase(data: &[u8]) -> Result<Vec<(String, Color)>, String> {
+	let mut reader = Reader { data };
+	if reader.bytes(4)? != b"ASEF" {
+		return Err("The file isn't an Adobe Swatch Exchange file".into());
+	}
+	let major_version = reader.u16()?;
+	let _minor_version = reader.u16()?;
+	if major_version != 1 {
+		return Err(format!("Adobe Swatch Exchange files of version {major_version} aren't supported"));
+	}
+
+	let block_count = reader.u32()?;
+	let mut colors = Vec::new();
+	for _ in 0..block_count {
+		let block_type = reader.u16()?;
+		let length = reader.u32()? as usize;
+		let mut block = Reader { data: reader.bytes(length)? };
+
+		// Groups only organize the colors within them, which are read into one list
+		if block_type != ASE_COLOR {
+			if block_type != ASE_GROUP_START && block_type != ASE_GROUP_END {
+				log::warn!("Skipping unknown Adobe Swatch Exchange block of type {block_type:#06x}");
+			}
+			continue;
+		}
+
+		// The name is UTF-16 text ending with a null character, which is counted in its length
+		let name_length = block.u16()? as usize;
+		let name = block.bytes(name_length * 2)?.chunks_exact(2).map(|unit| u16::from_be_bytes([unit[0], unit[1]])).collect::<Vec<_>>();
+		let name = String::from_utf16_lossy(&name).trim_end_matches('\0').to_string();
+
+		let color = match block.bytes(4)? {
+			b"RGB " => {
+				let [red, green, blue] = [block.f32()?, block.f32()?, block.f32()?];
+				opaque_color(red, green, blue)
+			}
+			b"Gray" => {
+				let gray = block.f32()?;
+				opaque_color(gray, gray, gray)
+			}
+			b"CMYK" => {
+				// Without the profile of the inks, the colors are converted as if the inks were ideal
+				let [cyan, magenta, yellow, black] = [block.f32()?, block.f32()?, block.f32()?, block.f32()?];
+				opaque_color((1. - cyan) * (1. - black), (1. - magenta) * (1. - black), (1. - yellow) * (1. - black))
+			}
+			b"LAB " => {
+				let [lightness, a, b] = [block.f32()?, block.f32()?, block.f32()?];
+				lab_to_srgb(lightness * 100., a, b)
+			}
+			model => return Err(format!("The color \"{name}\" has the unknown color model \"{}\"", String::from_utf8_lossy(model).trim())),
+		};
+		colors.push((name, color));
+	}
+
+	Ok(colors)
+}
+
+fn write_ase<'a>(colors: impl Iterator<Item = (&'a str, Color)>, name: &str) -> Vec<u8> {
+	let mut blocks = Vec::new();
+	let mut block_count = 0_u32;
+	let mut write_block = |block_type: u16, contents: &[u8]| {
+		blocks.extend(block_type.to_be_bytes());
+		blocks.extend((contents.len() as u32).to_be_bytes());
+		blocks.extend(contents);
+		block_count += 1;
+	};
+
+	// The colors are put in a group with the palette's name, which applications show as the name of the palette
+	write_block(ASE_GROUP_START, &ase_name(name));
+	for (name, color) in colors {
+		let mut contents = ase_name(name);
+		contents.extend(b"RGB ");
+		for channel in [color.r(), color.g(), color.b()] {
+			contents.extend(channel.to_be_bytes());
+		}
+		contents.extend(ASE_COLOR_TYPE_NORMAL.to_be_bytes());
+		write_block(ASE_COLOR, &contents);
+	}
+	write_block(ASE_GROUP_END, &[]);
+
+	let mut data = b"ASEF".to_vec();
+	data.extend(1_u16.to_be_bytes());
+	data.extend(0_u16.to_be_bytes());
+	data.extend(block_count.to_be_bytes());
+	data.extend(blocks);
+	data
+}
+
+/// A name as written in Adobe Swatch Exchange blocks: its length in UTF-16 code units, counting the null character that ends it, followed by the UTF-16 text.
+fn ase_name(name: &str) -> Vec<u8> {
+	let units = name.encode_utf16().chain(std::iter::once(0)).collect::<Vec<_>>();
+	let mut bytes = (units.len() as u16).to_be_bytes().to_vec();
+	bytes.extend(units.iter().flat_map(|unit| unit.to_be_bytes()));
+	bytes
+}
+
+fn read_gpl(text: &str) -> Result<Vec<(String, Color)>, String> {
+	let mut lines = text.lines();
+	if lines.next().map(str::trim) != Some("GIMP Palette") {
+		return Err("The file isn't a GIMP palette".into());
+	}
+
+	let mut colors = Vec::new();
+	for line in lines {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+			continue;
+		}
+
+		// Each color is written as its red, green, and blue channels from 0 to 255, followed by its name which may contain spaces
+		let mut rest = line;
+		let mut channels = [0_u8; 3];
+		for channel in &mut channels {
+			let trimmed = rest.trim_start();
+			let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+			*channel = trimmed[..end].parse().map_err(|_| format!("The line \"{line}\" of the GIMP palette isn't a color"))?;
+			rest = &trimmed[end..];
+		}
+
+		let [red, green, blue] = channels.map(|channel| channel as f32 / 255.);
+		colors.push((rest.trim().to_string(), opaque_color(red, green, blue)));
+	}
+
+	Ok(colors)
+}
+
+fn write_gpl<'a>(colors: impl Iterator<Item = (&'a str, Color)>, name: &str) -> String {
+	// Names can't span more than one line
+	let single_line = |text: &str| text.replace(['\r', '\n'], " ");
+
+	let mut text = format!("GIMP Palette\nName: {}\n#\n", single_line(name));
+	for (name, color) in colors {
+		let [red, green, blue] = [color.r(), color.g(), color.b()].map(|channel| (channel.clamp(0., 1.) * 255.).round() as u8);
+		let _ = writeln!(text, "{red:3} {green:3} {blue:3}\t{}", single_line(name));
+	}
+	text
+}
+
+/// A color from gamma encoded sRGB channels, which are clamped since palette files may hold colors outside of sRGB.
+fn opaque_color(red: f32, green: f32, blue: f32) -> Color {
+	Color::from_rgbaf32_unchecked(red.clamp(0., 1.), green.clamp(0., 1.), blue.clamp(0., 1.), 1.)
+}
+
+/// Converts a CIE Lab color relative to D50, with its lightness from 0 to 100, to the nearest gamma encoded sRGB color.
+fn lab_to_srgb(lightness: f32, a: f32, b: f32) -> Color {
+	let f_y = (lightness + 16.) / 116.;
+	let f_x = f_y + a / 500.;
+	let f_z = f_y - b / 200.;
+	let inverse = |t: f32| if t > 6. / 29. { t.powi(3) } else { 3. * (6_f32 / 29.).powi(2) * (t - 4. / 29.) };
+	let xyz = [inverse(f_x) * D50_WHITE[0], inverse(f_y) * D50_WHITE[1], inverse(f_z) * D50_WHITE[2]];
+
+	let [red, green, blue] = XYZ_D50_TO_LINEAR_SRGB.map(|row| (row[0] * xyz[0] + row[1] * xyz[1] + row[2] * xyz[2]).clamp(0., 1.));
+	let linear = Color::from_rgbaf32_unchecked(red, green, blue, 1.);
+	linear.to_gamma_srgb()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::vector::style::Gradient;
+
+	fn library(colors: &[(&str, Color)]) -> SwatchLibrary {
+		let mut swatches = colors
+			.iter()
+			.map(|(name, color)| Swatch {
+				id: generate_uuid(),
+				name: name.to_string(),
+				fill: Fill::Solid(*color),
+			})
+			.collect::<Vec<_>>();
+		swatches.push(Swatch {
+			id: generate_uuid(),
+			name: "Gradient".to_string(),
+			fill: Fill::Gradient(Gradient::default()),
+		});
+		SwatchLibrary { swatches }
+	}
+
+	fn names_and_colors(swatches: &[Swatch]) -> Vec<(String, Fill)> {
+		swatches.iter().map(|swatch| (swatch.name.clone(), swatch.fill.clone())).collect()
+	}
+
+	/// Colors whose channels are whole steps of 255, which both formats can hold exactly.
+	fn byte_color(red: u8, green: u8, blue: u8) -> Color {
+		Color::from_rgbaf32_unchecked(red as f32 / 255., green as f32 / 255., blue as f32 / 255., 1.)
+	}
+
+	#[test]
+	fn ase_roundtrip() {
+		let colors = [("Red", Color::from_rgbaf32_unchecked(1., 0., 0., 1.)), ("Ünïcode 色", Color::from_rgbaf32_unchecked(0.2, 0.4, 0.6, 1.))];
+		let data = write_palette(&library(&colors), "Palette", PaletteFormat::Ase);
+		assert_eq!(PaletteFormat::from_contents(&data), Some(PaletteFormat::Ase));
+
+		// The gradient is left out, since the format only holds solid colors
+		let swatches = read_palette("palette.ase", &data).unwrap();
+		let expected = colors.iter().map(|(name, color)| (name.to_string(), Fill::Solid(*color))).collect::<Vec<_>>();
+		assert_eq!(names_and_colors(&swatches), expected);
+	}
+
+	#[test]
+	fn gpl_roundtrip() {
+		let colors = [("Dark Slate", byte_color(47, 79, 79)), ("", byte_color(255, 128, 0)), ("Two\nLines", byte_color(0, 0, 0))];
+		let data = write_palette(&library(&colors), "Palette", PaletteFormat::Gpl);
+
+		// Files with another extension are recognized by their contents
+		let swatches = read_palette("palette.txt", &data).unwrap();
+		// Unnamed colors are named after their hex code, and names are kept to one line
+		let hex_name = format!("#{}", colors[1].1.rgb_hex());
+		let expected = [("Dark Slate", colors[0].1), (hex_name.as_str(), colors[1].1), ("Two Lines", colors[2].1)]
+			.map(|(name, color)| (name.to_string(), Fill::Solid(color)))
+			.to_vec();
+		assert_eq!(names_and_colors(&swatches), expected);
+	}
+
+	#[test]
+	fn truncated_ase_is_rejected() {
+		let data = write_palette(&library(&[("Red", Color::from_rgbaf32_unchecked(1., 0., 0., 1.))]), "Palette", PaletteFormat::Ase);
+		for length in 0..data.len() {
+			assert!(read_palette("palette.ase", &data[..length]).is_err(), "A file truncated to {length} bytes was read");
+		}
+	}
+
+	#[test]
+	fn malformed_palettes_are_rejected() {
+		let mut unknown_version = write_palette(&SwatchLibrary::default(), "Palette", PaletteFormat::Ase);
+		unknown_version[4..6].copy_from_slice(&2_u16.to_be_bytes());
+		assert!(read_palette("palette.ase", &unknown_version).is_err());
+
+		let mut unknown_model = write_palette(&library(&[("Red", Color::from_rgbaf32_unchecked(1., 0., 0., 1.))]), "Palette", PaletteFormat::Ase);
+		let model = unknown_model.windows(4).position(|window| window == b"RGB ").unwrap();
+		unknown_model[model..model + 4].copy_from_slice(b"HSV ");
+		assert!(read_palette("palette.ase", &unknown_model).is_err());
+
+		assert!(read_palette("palette.gpl", b"Not a palette\n0 0 0 Black").is_err());
+		assert!(read_palette("palette.gpl", b"GIMP Palette\n0 zero 0 Black").is_err());
+		assert!(read_palette("palette.gpl", b"GIMP Palette\n255 0").is_err());
+		assert!(read_palette("palette.gpl", b"GIMP Palette\n256 0 0 Too Red").is_err());
+		assert!(read_palette("palette.gpl", b"GIMP Palette\n\xFF\xFE").is_err());
+		assert!(read_palette("palette.png", b"\x89PNG").is_err());
+	}
+}
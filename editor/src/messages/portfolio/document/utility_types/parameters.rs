@@ -0,0 +1,190 @@
+use super::variables::VariableBinding;
+
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{DocumentNodeImplementation, NodeNetwork};
+use graphene_core::Color;
+
+use glam::DVec2;
+
+/// The kinds of value a document parameter can hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ParameterKind {
+	Number,
+	Color,
+	Vector,
+	Boolean,
+}
+
+impl ParameterKind {
+	pub const ALL: [ParameterKind; 4] = [ParameterKind::Number, ParameterKind::Color, ParameterKind::Vector, ParameterKind::Boolean];
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			ParameterKind::Number => "Number",
+			ParameterKind::Color => "Color",
+			ParameterKind::Vector => "Vector",
+			ParameterKind::Boolean => "Boolean",
+		}
+	}
+
+	/// The value a newly added parameter of this kind starts out with.
+	pub fn default_value(&self) -> TaggedValue {
+		match self {
+			ParameterKind::Number => TaggedValue::F64(0.),
+			ParameterKind::Color => TaggedValue::Color(Color::BLACK),
+			ParameterKind::Vector => TaggedValue::DVec2(DVec2::ZERO),
+			ParameterKind::Boolean => TaggedValue::Bool(false),
+		}
+	}
+}
+
+/// A named value of the document, such as a margin or a brand color, which any number of node inputs can take on so it's edited in one place.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DocumentParameter {
+	pub id: u64,
+	pub name: String,
+	pub value: TaggedValue,
+	/// The node inputs which take on the value of this parameter.
+	pub bindings: Vec<VariableBinding>,
+}
+
+/// The parameters of a document, shown in the Parameters panel.
+///
+/// Like the values of document variables, the values of bound inputs live in the document network like any other input value, and they're written there again whenever their parameter is edited.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DocumentParameters {
+	pub parameters: Vec<DocumentParameter>,
+}
+
+impl DocumentParameters {
+	pub fn get(&self, id: u64) -> Option<&DocumentParameter> {
+		self.parameters.iter().find(|parameter| parameter.id == id)
+	}
+
+	fn get_mut(&mut self, id: u64) -> Option<&mut DocumentParameter> {
+		self.parameters.iter_mut().find(|parameter| parameter.id == id)
+	}
+
+	/// Adds a parameter of the kind with its default value, named after its kind and numbered so its name isn't taken by another parameter.
+	pub fn add(&mut self, id: u64, kind: ParameterKind) {
+		let name = (1..)
+			.map(|number| format!("{} {number}", kind.label()))
+			.find(|name| self.parameters.iter().all(|parameter| &parameter.name != name))
+			.unwrap_or_default();
+
+		self.parameters.push(DocumentParameter {
+			id,
+			name,
+			value: kind.default_value(),
+			bindings: Vec::new(),
+		});
+	}
+
+	/// Removes the parameter, leaving the inputs which were bound to it with its value.
+	pub fn remove(&mut self, id: u64) {
+		self.parameters.retain(|parameter| parameter.id != id);
+	}
+
+	pub fn rename(&mut self, id: u64, name: String) {
+		if let Some(parameter) = self.get_mut(id) {
+			parameter.name = name;
+		}
+	}
+
+	/// The parameter which the input is bound to, if any.
+	pub fn bound_parameter(&self, binding: &VariableBinding) -> Option<&DocumentParameter> {
+		self.parameters.iter().find(|parameter| parameter.bindings.contains(binding))
+	}
+
+	/// Binds the input to the parameter, replacing any previous binding of the input, and gives it the parameter's value.
+	/// Only inputs holding a value of the parameter's type (or one it converts to, like a whole number) can be bound, besides the input of a node passing it straight through, like the Parameter node.
+	pub fn bind_input(&mut self, binding: VariableBinding, id: u64, network: &mut NodeNetwork) {
+		let Some(value) = self.get(id).map(|parameter| parameter.value.clone()) else { return };
+		let passes_through = binding
+			.node(network)
+			.is_some_and(|node| node.implementation == DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"));
+		let bound = binding.apply(network, |input_value| if passes_through { Some(value.clone()) } else { parameter_value(&value, input_value) });
+		if !bound {
+			log::warn!("The input can't take on the value of the parameter");
+			return;
+		}
+
+		self.unbind_input(&binding);
+		if let Some(parameter) = self.get_mut(id) {
+			parameter.bindings.push(binding);
+		}
+	}
+
+	/// Removes the binding of the input, which keeps the value it had from the parameter.
+	pub fn unbind_input(&mut self, binding: &VariableBinding) {
+		for parameter in &mut self.parameters {
+			parameter.bindings.retain(|existing| existing != binding);
+		}
+	}
+
+	/// Changes the value of the parameter and writes it into every input bound to it, returning whether the document network changed.
+	/// The value must be of the same type as the parameter's current value.
+	pub fn set_value(&mut self, id: u64, value: TaggedValue, network: &mut NodeNetwork) -> bool {
+		let Some(parameter) = self.get_mut(id) else { return false };
+		if core::mem::discriminant(&parameter.value) != core::mem::discriminant(&value) || parameter.value == value {
+			return false;
+		}
+		parameter.value = value;
+
+		let mut changed = false;
+		for binding in &parameter.bindings {
+			changed |= binding.apply(network, |input_value| parameter_value(&parameter.value, input_value));
+		}
+		changed
+	}
+}
+
+/// The value of the parameter in the same type as the value of the input it's written into.
+fn parameter_value(value: &TaggedValue, input_value: &TaggedValue) -> Option<TaggedValue> {
+	match (input_value, value) {
+		(input_value, value) if core::mem::discriminant(input_value) == core::mem::discriminant(value) => Some(value.clone()),
+		(TaggedValue::U32(_), &TaggedValue::F64(number)) => Some(TaggedValue::U32(number.round().max(0.) as u32)),
+		(TaggedValue::OptionalColor(_), &TaggedValue::Color(color)) => Some(TaggedValue::OptionalColor(Some(color))),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::messages::portfolio::document::utility_types::variables::test_bindings::{self, binding, value};
+
+	fn network() -> NodeNetwork {
+		test_bindings::network([TaggedValue::F64(1.), TaggedValue::U32(1), TaggedValue::Bool(true)])
+	}
+
+	#[test]
+	fn bound_inputs_take_on_the_parameter_value() {
+		let mut network = network();
+		let mut parameters = DocumentParameters::default();
+		parameters.add(1, ParameterKind::Number);
+		parameters.set_value(1, TaggedValue::F64(2.6), &mut network);
+
+		parameters.bind_input(binding(1), 1, &mut network);
+		parameters.bind_input(binding(2), 1, &mut network);
+		assert_eq!(value(&network, 1), Some(&TaggedValue::F64(2.6)));
+		assert_eq!(value(&network, 2), Some(&TaggedValue::U32(3)));
+
+		// Inputs of other types can't be bound
+		parameters.bind_input(binding(3), 1, &mut network);
+		assert_eq!(value(&network, 3), Some(&TaggedValue::Bool(true)));
+		assert_eq!(parameters.get(1).map(|parameter| parameter.bindings.len()), Some(2));
+
+		assert!(parameters.set_value(1, TaggedValue::F64(5.), &mut network));
+		assert_eq!(value(&network, 1), Some(&TaggedValue::F64(5.)));
+		assert_eq!(value(&network, 2), Some(&TaggedValue::U32(5)));
+
+		// Values of another type than the parameter's are rejected
+		assert!(!parameters.set_value(1, TaggedValue::Bool(false), &mut network));
+
+		parameters.unbind_input(&binding(1));
+		assert!(parameters.set_value(1, TaggedValue::F64(7.), &mut network));
+		assert_eq!(value(&network, 1), Some(&TaggedValue::F64(5.)));
+	}
+}
@@ -0,0 +1,124 @@
+use super::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
+
+use glam::{DAffine2, DVec2, UVec2};
+use std::fmt::Write;
+
+/// The largest ID that fits in the red, green, and blue channels of a pixel, which limits how many layers can be drawn into the buffer.
+const MAX_ID: usize = 0xFF_FFFF;
+
+/// A render of the viewport in which each layer is filled with a color encoding its own ID, so the layer under the cursor is found by reading a single pixel.
+/// This stands in for testing the click targets while hovering over very dense documents, where there are too many layers near the cursor to keep up with it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PickingBuffer {
+	/// Identifies the request for the buffer, so the pixels rasterized for an earlier one aren't used once the layers have changed.
+	key: u64,
+	/// The layers drawn into the buffer, where the pixels with the ID `n` are covered by the layer at index `n - 1` and the ID 0 is where no layer is.
+	layers: Vec<LayerNodeIdentifier>,
+	/// The transform the layers were drawn with, since the buffer no longer lines up with the viewport once it's panned or zoomed.
+	document_to_viewport: DAffine2,
+	size: UVec2,
+	/// The ID of each pixel, row by row, which is empty until the frontend has rasterized the buffer.
+	ids: Vec<u32>,
+}
+
+impl PickingBuffer {
+	/// Starts a buffer of the given size in pixels for the current view of the document, returning it together with the SVG the frontend rasterizes into it.
+	/// Just like hovering picks the deepest layer, only layers without child layers are drawn, and artboards, hidden layers, and locked layers are left out.
+	/// The layers are drawn from the bottom up, so each pixel holds the topmost layer over it.
+	pub fn new(key: u64, metadata: &DocumentMetadata, size: UVec2) -> (Self, String) {
+		let document_to_viewport = metadata.document_to_viewport;
+		let zoom = document_to_viewport.matrix2.determinant().abs().sqrt();
+
+		let mut layers = metadata
+			.all_layers()
+			.filter(|&layer| !metadata.is_folder(layer) && !metadata.is_artboard(layer))
+			.filter(|&layer| {
+				layer
+					.ancestors(metadata)
+					.all(|ancestor| ancestor == LayerNodeIdentifier::ROOT_PARENT || metadata.node_is_visible(ancestor.to_node()))
+			})
+			.filter(|&layer| {
+				!layer
+					.ancestors(metadata)
+					.any(|ancestor| ancestor != LayerNodeIdentifier::ROOT_PARENT && metadata.node_is_locked(ancestor.to_node()))
+			})
+			.filter(|&layer| metadata.click_target(layer).is_some_and(|targets| !targets.is_empty()))
+			.take(MAX_ID)
+			.collect::<Vec<_>>();
+		layers.reverse();
+
+		// Edges aren't anti-aliased, since the blend of two layers' colors would be read as the ID of some unrelated layer
+		let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" shape-rendering="crispEdges">"#, size.x, size.y);
+		for (index, &layer) in layers.iter().enumerate() {
+			let Some(click_targets) = metadata.click_target(layer) else { continue };
+			let transform = metadata.transform_to_viewport(layer);
+			let color = format!("#{:06x}", index + 1);
+
+			// Each click target is drawn on its own, since it's also hit tested on its own, and its stroke is as wide on screen as the stroke which hit testing allows for
+			for click_target in click_targets {
+				let mut path = String::new();
+				if click_target.subpath.subpath_to_svg(&mut path, transform).is_err() || path.is_empty() {
+					continue;
+				}
+				let fill = if click_target.subpath.closed() { color.as_str() } else { "none" };
+				let stroke_width = click_target.stroke_width * zoom;
+				let _ = if stroke_width > 0. {
+					write!(
+						svg,
+						r#"<path d="{path}" fill="{fill}" stroke="{color}" stroke-width="{stroke_width}" stroke-linecap="round" stroke-linejoin="round" />"#
+					)
+				} else {
+					write!(svg, r#"<path d="{path}" fill="{fill}" />"#)
+				};
+			}
+		}
+		svg.push_str("</svg>");
+
+		let buffer = Self {
+			key,
+			layers,
+			document_to_viewport,
+			size,
+			ids: Vec::new(),
+		};
+		(buffer, svg)
+	}
+
+	pub fn key(&self) -> u64 {
+		self.key
+	}
+
+	/// Reads the IDs from the 8-bit RGBA pixels of the rasterized SVG, leaving the buffer empty if there are too few pixels to cover it.
+	pub fn fill(&mut self, rgba: &[u8]) {
+		let pixel_count = self.size.x as usize * self.size.y as usize;
+		if rgba.len() < pixel_count * 4 {
+			return;
+		}
+
+		self.ids = rgba
+			.chunks_exact(4)
+			.take(pixel_count)
+			.map(|pixel| match pixel {
+				// Pixels which aren't opaque are where no layer is drawn
+				&[red, green, blue, 255] => u32::from_be_bytes([0, red, green, blue]),
+				_ => 0,
+			})
+			.collect();
+	}
+
+	/// The layer at a point in viewport space, or `None` inside if no layer is there.
+	/// This is `None` when the buffer can't tell, since it hasn't been rasterized yet, the view has changed since it was drawn, or the point is outside of it.
+	pub fn layer_at(&self, document_to_viewport: DAffine2, viewport_location: DVec2) -> Option<Option<LayerNodeIdentifier>> {
+		if self.ids.is_empty() || document_to_viewport != self.document_to_viewport {
+			return None;
+		}
+
+		let pixel = viewport_location.floor();
+		if pixel.x < 0. || pixel.y < 0. || pixel.x >= self.size.x as f64 || pixel.y >= self.size.y as f64 {
+			return None;
+		}
+
+		let id = self.ids[pixel.y as usize * self.size.x as usize + pixel.x as usize] as usize;
+		Some(id.checked_sub(1).and_then(|index| self.layers.get(index).copied()))
+	}
+}
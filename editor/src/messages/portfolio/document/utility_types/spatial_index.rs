@@ -0,0 +1,158 @@
+use super::document_metadata::LayerNodeIdentifier;
+
+use glam::{DAffine2, DVec2};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+use std::collections::HashMap;
+
+type IndexedBounds = GeomWithData<Rectangle<[f64; 2]>, LayerNodeIdentifier>;
+
+fn indexed_bounds(layer: LayerNodeIdentifier, [min, max]: [DVec2; 2]) -> IndexedBounds {
+	GeomWithData::new(Rectangle::from_corners(min.into(), max.into()), layer)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct IndexedLayer {
+	/// The layer to document transform which the bounds were computed with, used to tell if they need to be recomputed.
+	transform: DAffine2,
+	bounds: [DVec2; 2],
+}
+
+/// An R-tree of the document space bounds of the layers' click targets.
+/// This lets hit testing, marquee selection, and snapping look up just the layers in a region instead of visiting every layer in the document.
+#[derive(Clone)]
+pub struct LayerSpatialIndex {
+	tree: RTree<IndexedBounds>,
+	layers: HashMap<LayerNodeIdentifier, IndexedLayer>,
+}
+
+impl Default for LayerSpatialIndex {
+	fn default() -> Self {
+		Self {
+			tree: RTree::new(),
+			layers: HashMap::new(),
+		}
+	}
+}
+
+impl core::fmt::Debug for LayerSpatialIndex {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("LayerSpatialIndex").field("layers", &self.layers.len()).finish()
+	}
+}
+
+impl LayerSpatialIndex {
+	/// The transform which the layer's bounds were last computed with, if the layer is in the index.
+	pub fn indexed_transform(&self, layer: LayerNodeIdentifier) -> Option<DAffine2> {
+		self.layers.get(&layer).map(|indexed| indexed.transform)
+	}
+
+	pub fn bounds(&self, layer: LayerNodeIdentifier) -> Option<[DVec2; 2]> {
+		self.layers.get(&layer).map(|indexed| indexed.bounds)
+	}
+
+	/// Moves the layer to its new bounds in the tree, or takes it out of the tree if it no longer has any.
+	/// Layers whose bounds haven't changed are left untouched.
+	pub fn update(&mut self, layer: LayerNodeIdentifier, transform: DAffine2, bounds: Option<[DVec2; 2]>) {
+		let bounds = bounds.filter(|[min, max]| min.is_finite() && max.is_finite());
+		let previous = self.layers.get(&layer).copied();
+
+		if previous.map(|previous| previous.bounds) != bounds {
+			if let Some(previous) = previous {
+				self.tree.remove(&indexed_bounds(layer, previous.bounds));
+			}
+			if let Some(bounds) = bounds {
+				self.tree.insert(indexed_bounds(layer, bounds));
+			}
+		}
+
+		match bounds {
+			Some(bounds) => self.layers.insert(layer, IndexedLayer { transform, bounds }),
+			None => self.layers.remove(&layer),
+		};
+	}
+
+	/// Takes every layer for which `keep` returns false out of the tree.
+	pub fn retain(&mut self, mut keep: impl FnMut(LayerNodeIdentifier) -> bool) {
+		let removed = self.layers.keys().copied().filter(|&layer| !keep(layer)).collect::<Vec<_>>();
+		for layer in removed {
+			self.update(layer, DAffine2::IDENTITY, None);
+		}
+	}
+
+	/// The layers with bounds that overlap the given bounds, in no particular order.
+	pub fn intersecting(&self, [min, max]: [DVec2; 2]) -> impl Iterator<Item = LayerNodeIdentifier> + '_ {
+		let envelope = AABB::<[f64; 2]>::from_corners(min.min(max).into(), min.max(max).into());
+		self.tree.locate_in_envelope_intersecting(&envelope).map(|indexed| indexed.data)
+	}
+
+	/// The layers with bounds that contain the given point, in no particular order.
+	pub fn containing_point(&self, point: DVec2) -> impl Iterator<Item = LayerNodeIdentifier> + '_ {
+		self.tree.locate_all_at_point(&point.into()).map(|indexed| indexed.data)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graph_craft::document::NodeId;
+
+	fn layer(id: u64) -> LayerNodeIdentifier {
+		LayerNodeIdentifier::new_unchecked(NodeId(id))
+	}
+
+	fn ids(layers: impl Iterator<Item = LayerNodeIdentifier>) -> Vec<u64> {
+		let mut ids = layers.map(|layer| layer.to_node().0).collect::<Vec<_>>();
+		ids.sort_unstable();
+		ids
+	}
+
+	fn index_with_squares() -> LayerSpatialIndex {
+		let mut index = LayerSpatialIndex::default();
+		index.update(layer(1), DAffine2::IDENTITY, Some([DVec2::new(0., 0.), DVec2::new(10., 10.)]));
+		index.update(layer(2), DAffine2::IDENTITY, Some([DVec2::new(5., 5.), DVec2::new(15., 15.)]));
+		index.update(layer(3), DAffine2::IDENTITY, Some([DVec2::new(100., 100.), DVec2::new(110., 110.)]));
+		index
+	}
+
+	#[test]
+	fn insert_and_query() {
+		let index = index_with_squares();
+		assert_eq!(ids(index.containing_point(DVec2::new(7., 7.))), [1, 2]);
+		assert_eq!(ids(index.containing_point(DVec2::new(2., 2.))), [1]);
+		assert_eq!(ids(index.containing_point(DVec2::new(50., 50.))), Vec::<u64>::new());
+
+		// The corners of the queried bounds may be given in either order
+		assert_eq!(ids(index.intersecting([DVec2::new(12., 12.), DVec2::new(105., 105.)])), [2, 3]);
+		assert_eq!(ids(index.intersecting([DVec2::new(105., 105.), DVec2::new(12., 12.)])), [2, 3]);
+		assert_eq!(index.bounds(layer(3)), Some([DVec2::new(100., 100.), DVec2::new(110., 110.)]));
+	}
+
+	#[test]
+	fn update_moves_layers() {
+		let mut index = index_with_squares();
+		let transform = DAffine2::from_translation(DVec2::new(200., 0.));
+		index.update(layer(1), transform, Some([DVec2::new(200., 0.), DVec2::new(210., 10.)]));
+
+		assert_eq!(ids(index.containing_point(DVec2::new(2., 2.))), Vec::<u64>::new());
+		assert_eq!(ids(index.containing_point(DVec2::new(205., 5.))), [1]);
+		assert_eq!(index.indexed_transform(layer(1)), Some(transform));
+
+		// Bounds which can't be indexed take the layer out of the tree
+		index.update(layer(2), DAffine2::IDENTITY, Some([DVec2::new(5., 5.), DVec2::new(f64::NAN, 15.)]));
+		assert_eq!(ids(index.containing_point(DVec2::new(7., 7.))), Vec::<u64>::new());
+		assert_eq!(index.indexed_transform(layer(2)), None);
+	}
+
+	#[test]
+	fn remove_and_retain() {
+		let mut index = index_with_squares();
+		index.update(layer(2), DAffine2::IDENTITY, None);
+		assert_eq!(ids(index.containing_point(DVec2::new(7., 7.))), [1]);
+		assert_eq!(index.bounds(layer(2)), None);
+
+		index.retain(|layer| layer.to_node() != NodeId(3));
+		assert_eq!(ids(index.intersecting([DVec2::splat(-1000.), DVec2::splat(1000.)])), [1]);
+	}
+}
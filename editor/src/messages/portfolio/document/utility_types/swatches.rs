@@ -0,0 +1,182 @@
+use super::variables::VariableBinding;
+
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeNetwork;
+use graphene_core::vector::style::Fill;
+
+/// A named color or gradient, which fills can be linked to so that editing the swatch re-colors everything filled with it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct Swatch {
+	pub id: u64,
+	pub name: String,
+	pub fill: Fill,
+}
+
+/// An ordered collection of swatches. Each document has its own, and another is kept in the preferences to be shared by every document.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(default)]
+pub struct SwatchLibrary {
+	pub swatches: Vec<Swatch>,
+}
+
+impl SwatchLibrary {
+	pub fn get(&self, id: u64) -> Option<&Swatch> {
+		self.swatches.iter().find(|swatch| swatch.id == id)
+	}
+
+	fn get_mut(&mut self, id: u64) -> Option<&mut Swatch> {
+		self.swatches.iter_mut().find(|swatch| swatch.id == id)
+	}
+
+	pub fn add(&mut self, swatch: Swatch) {
+		self.swatches.push(swatch);
+	}
+
+	pub fn remove(&mut self, id: u64) -> Option<Swatch> {
+		let index = self.swatches.iter().position(|swatch| swatch.id == id)?;
+		Some(self.swatches.remove(index))
+	}
+
+	pub fn rename(&mut self, id: u64, name: String) {
+		if let Some(swatch) = self.get_mut(id) {
+			swatch.name = name;
+		}
+	}
+
+	/// Changes the fill of the swatch, returning whether it changed.
+	pub fn set_fill(&mut self, id: u64, fill: Fill) -> bool {
+		let Some(swatch) = self.get_mut(id) else { return false };
+		let changed = swatch.fill != fill;
+		swatch.fill = fill;
+		changed
+	}
+
+	/// Moves the swatch to the index, counted among the other swatches, so an index past the end moves it to the end.
+	pub fn reorder(&mut self, id: u64, index: usize) {
+		let Some(swatch) = self.remove(id) else { return };
+		let index = index.min(self.swatches.len());
+		self.swatches.insert(index, swatch);
+	}
+}
+
+/// A node input linked to a swatch of the document, which takes on the swatch's fill whenever it's edited.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SwatchLink {
+	pub swatch: u64,
+	pub binding: VariableBinding,
+}
+
+/// The swatches of a document together with the node inputs linked to them.
+///
+/// Like the values of document variables, the fills of linked inputs live in the document network like any other input value, and they're written there again whenever their swatch is edited.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DocumentSwatches {
+	pub library: SwatchLibrary,
+	pub links: Vec<SwatchLink>,
+}
+
+impl DocumentSwatches {
+	/// The swatch which the input is linked to, if any.
+	pub fn linked_swatch(&self, binding: &VariableBinding) -> Option<&Swatch> {
+		let link = self.links.iter().find(|link| &link.binding == binding)?;
+		self.library.get(link.swatch)
+	}
+
+	/// Links the input to the swatch, replacing any previous link of the input, and gives it the swatch's fill.
+	/// Only inputs holding a fill, a color, or a gradient can be linked, and colors and gradients are only linked to swatches of their own kind.
+	pub fn link_input(&mut self, binding: VariableBinding, swatch: u64, network: &mut NodeNetwork) {
+		let Some(fill) = self.library.get(swatch).map(|swatch| swatch.fill.clone()) else { return };
+		if !binding.apply(network, |value| swatch_value(&fill, value)) {
+			log::warn!("The input can't take on the fill of the swatch");
+			return;
+		}
+
+		self.unlink_input(&binding);
+		self.links.push(SwatchLink { swatch, binding });
+	}
+
+	/// Removes the link of the input, which keeps the fill it had from the swatch.
+	pub fn unlink_input(&mut self, binding: &VariableBinding) {
+		self.links.retain(|link| &link.binding != binding);
+	}
+
+	/// Changes the fill of the swatch and writes it into every input linked to it, returning whether the document network changed.
+	pub fn set_fill(&mut self, id: u64, fill: Fill, network: &mut NodeNetwork) -> bool {
+		if !self.library.set_fill(id, fill.clone()) {
+			return false;
+		}
+
+		let mut changed = false;
+		for link in self.links.iter().filter(|link| link.swatch == id) {
+			changed |= link.binding.apply(network, |value| swatch_value(&fill, value));
+		}
+		changed
+	}
+
+	/// Removes the swatch along with its links, leaving the inputs which were linked to it with its fill.
+	pub fn remove(&mut self, id: u64) {
+		self.library.remove(id);
+		self.links.retain(|link| link.swatch != id);
+	}
+}
+
+/// The value of the swatch's fill in the same type as the value of the input it's written into.
+fn swatch_value(fill: &Fill, input_value: &TaggedValue) -> Option<TaggedValue> {
+	match (input_value, fill) {
+		(TaggedValue::Fill(_), fill) => Some(TaggedValue::Fill(fill.clone())),
+		(TaggedValue::Color(_), Fill::Solid(color)) => Some(TaggedValue::Color(*color)),
+		(TaggedValue::OptionalColor(_), Fill::Solid(color)) => Some(TaggedValue::OptionalColor(Some(*color))),
+		(TaggedValue::OptionalColor(_), Fill::None) => Some(TaggedValue::OptionalColor(None)),
+		(TaggedValue::Gradient(_), Fill::Gradient(gradient)) => Some(TaggedValue::Gradient(gradient.clone())),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::messages::portfolio::document::utility_types::variables::test_bindings::{self, binding, connect, value};
+	use graphene_core::Color;
+
+	fn network() -> NodeNetwork {
+		test_bindings::network([TaggedValue::Fill(Fill::None), TaggedValue::Color(Color::BLACK)])
+	}
+
+	fn swatches() -> DocumentSwatches {
+		let mut swatches = DocumentSwatches::default();
+		swatches.library.add(Swatch {
+			id: 1,
+			name: "Red".to_string(),
+			fill: Fill::Solid(Color::RED),
+		});
+		swatches
+	}
+
+	#[test]
+	fn linked_inputs_take_on_the_swatch_fill() {
+		let mut network = network();
+		let mut swatches = swatches();
+		swatches.link_input(binding(1), 1, &mut network);
+		swatches.link_input(binding(2), 1, &mut network);
+		assert_eq!(value(&network, 1), Some(&TaggedValue::Fill(Fill::Solid(Color::RED))));
+		assert_eq!(value(&network, 2), Some(&TaggedValue::Color(Color::RED)));
+
+		assert!(swatches.set_fill(1, Fill::Solid(Color::BLUE), &mut network));
+		assert_eq!(value(&network, 1), Some(&TaggedValue::Fill(Fill::Solid(Color::BLUE))));
+		assert_eq!(value(&network, 2), Some(&TaggedValue::Color(Color::BLUE)));
+
+		// Colors can't take on the fill once the swatch holds no color
+		assert!(swatches.set_fill(1, Fill::None, &mut network));
+		assert_eq!(value(&network, 2), Some(&TaggedValue::Color(Color::BLUE)));
+	}
+
+	#[test]
+	fn connected_inputs_cant_be_linked() {
+		let mut network = network();
+		let mut swatches = swatches();
+		connect(&mut network, 2, 1);
+		swatches.link_input(binding(2), 1, &mut network);
+		assert!(swatches.linked_swatch(&binding(2)).is_none());
+	}
+}
@@ -0,0 +1,258 @@
+use super::variables::VariableBinding;
+use crate::messages::portfolio::document::node_graph::document_node_types::resolve_document_node_type;
+
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{DocumentNodeImplementation, NodeInput, NodeNetwork};
+use graphene_core::renderer::OnionSkin;
+
+use glam::DVec2;
+
+/// How the value of a parameter changes from one keyframe to the next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Interpolation {
+	/// Holds the value of the keyframe until the next keyframe is reached.
+	Constant,
+	/// Changes at a constant rate towards the value of the next keyframe.
+	#[default]
+	Linear,
+	/// Eases towards the value of the next keyframe along a cubic Bézier curve from (0, 0) to (1, 1), with these two control points, like a CSS `cubic-bezier()` timing function.
+	Bezier(DVec2, DVec2),
+}
+
+impl Interpolation {
+	pub const EASE_IN_OUT: Self = Self::Bezier(DVec2::new(0.42, 0.), DVec2::new(0.58, 1.));
+
+	/// Maps the fraction of time elapsed between two keyframes to the fraction of the change in value.
+	pub fn ease(&self, t: f64) -> f64 {
+		let t = t.clamp(0., 1.);
+		match *self {
+			Self::Constant => 0.,
+			Self::Linear => t,
+			Self::Bezier(first, second) => {
+				let bezier = |a: f64, b: f64, s: f64| 3. * (1. - s) * (1. - s) * s * a + 3. * (1. - s) * s * s * b + s * s * s;
+				let (x1, x2) = (first.x.clamp(0., 1.), second.x.clamp(0., 1.));
+
+				// The curve's x is monotonic in its parameter since the control points are within [0, 1], so bisection always finds the parameter giving time `t`
+				let (mut low, mut high) = (0., 1.);
+				for _ in 0..32 {
+					let middle = (low + high) / 2.;
+					if bezier(x1, x2, middle) < t {
+						low = middle;
+					} else {
+						high = middle;
+					}
+				}
+				bezier(first.y, second.y, (low + high) / 2.)
+			}
+		}
+	}
+}
+
+/// The value of a parameter at one point in time.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Keyframe {
+	/// The time of the keyframe in seconds.
+	pub time: f64,
+	pub value: TaggedValue,
+	/// How the value changes between this keyframe and the next one.
+	pub interpolation: Interpolation,
+}
+
+/// The keyframes animating one node input, sorted by time.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct KeyframeTrack {
+	pub binding: VariableBinding,
+	pub keyframes: Vec<Keyframe>,
+}
+
+impl KeyframeTrack {
+	/// The value of the animated input at the given time, holding the first and last values before and after the keyframes.
+	pub fn value_at(&self, time: f64) -> Option<TaggedValue> {
+		let next_index = self.keyframes.partition_point(|keyframe| keyframe.time <= time);
+		let Some(previous) = next_index.checked_sub(1).and_then(|index| self.keyframes.get(index)) else {
+			return self.keyframes.first().map(|keyframe| keyframe.value.clone());
+		};
+		let Some(next) = self.keyframes.get(next_index) else {
+			return Some(previous.value.clone());
+		};
+
+		let duration = next.time - previous.time;
+		let t = if duration > 0. { (time - previous.time) / duration } else { 1. };
+		Some(interpolate(&previous.value, &next.value, previous.interpolation.ease(t)))
+	}
+}
+
+/// Blends between two values of the same type, with values that can't be blended switching over once the next keyframe is reached.
+fn interpolate(from: &TaggedValue, to: &TaggedValue, t: f64) -> TaggedValue {
+	let lerp = |a: f64, b: f64| a + (b - a) * t;
+	match (from, to) {
+		(TaggedValue::F64(a), TaggedValue::F64(b)) => TaggedValue::F64(lerp(*a, *b)),
+		(TaggedValue::U32(a), TaggedValue::U32(b)) => TaggedValue::U32(lerp(*a as f64, *b as f64).round().max(0.) as u32),
+		(TaggedValue::U64(a), TaggedValue::U64(b)) => TaggedValue::U64(lerp(*a as f64, *b as f64).round().max(0.) as u64),
+		(TaggedValue::DVec2(a), TaggedValue::DVec2(b)) => TaggedValue::DVec2(a.lerp(*b, t)),
+		(TaggedValue::IVec2(a), TaggedValue::IVec2(b)) => TaggedValue::IVec2(a.as_dvec2().lerp(b.as_dvec2(), t).round().as_ivec2()),
+		(TaggedValue::UVec2(a), TaggedValue::UVec2(b)) => TaggedValue::UVec2(a.as_dvec2().lerp(b.as_dvec2(), t).round().max(DVec2::ZERO).as_uvec2()),
+		(TaggedValue::Color(a), TaggedValue::Color(b)) => TaggedValue::Color(a.lerp(b, t as f32)),
+		(TaggedValue::OptionalColor(Some(a)), TaggedValue::OptionalColor(Some(b))) => TaggedValue::OptionalColor(Some(a.lerp(b, t as f32))),
+		(TaggedValue::F64Array4(a), TaggedValue::F64Array4(b)) => TaggedValue::F64Array4(core::array::from_fn(|index| lerp(a[index], b[index]))),
+		(TaggedValue::FontVariations(a), TaggedValue::FontVariations(b)) => TaggedValue::FontVariations(a.lerp(b, t)),
+		_ if t < 1. => from.clone(),
+		_ => to.clone(),
+	}
+}
+
+/// Keyframe tracks animating the parameters of nodes over time, along with the time being shown in the document.
+///
+/// Like document variables, the values at the current time live in the document network, so scrubbing the timeline writes the animated values into the network.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DocumentTimeline {
+	pub tracks: Vec<KeyframeTrack>,
+	/// The time being shown in the document, in seconds.
+	pub current_time: f64,
+	/// The length of the animation in seconds.
+	pub duration: f64,
+	pub frame_rate: f64,
+	/// Whether the frames around the current time are drawn faintly beneath the artwork.
+	pub show_onion_skin: bool,
+	pub onion_skin: OnionSkin,
+}
+
+impl Default for DocumentTimeline {
+	fn default() -> Self {
+		Self {
+			tracks: Vec::new(),
+			current_time: 0.,
+			duration: 5.,
+			frame_rate: 30.,
+			show_onion_skin: false,
+			onion_skin: OnionSkin::default(),
+		}
+	}
+}
+
+impl DocumentTimeline {
+	pub fn is_empty(&self) -> bool {
+		self.tracks.is_empty()
+	}
+
+	/// Whether the artwork changes over time, either from keyframes or from nodes following the time (such as Audio Analysis).
+	pub fn animates(&self, network: &NodeNetwork) -> bool {
+		!self.is_empty() || has_time_inputs(network)
+	}
+
+	pub fn is_animated(&self, binding: &VariableBinding) -> bool {
+		self.tracks.iter().any(|track| &track.binding == binding)
+	}
+
+	/// The time of every frame of the animation, in order.
+	pub fn frame_times(&self) -> impl Iterator<Item = f64> + '_ {
+		let frame_count = (self.duration * self.frame_rate).ceil().max(1.) as usize;
+		(0..frame_count).map(|frame| frame as f64 / self.frame_rate)
+	}
+
+	/// The time and opacity of each frame drawn by the onion skin, leaving out the frames outside the animation.
+	pub fn onion_skin_frames(&self, network: &NodeNetwork) -> Vec<(f64, f64)> {
+		if !self.show_onion_skin || !self.animates(network) || !(self.frame_rate > 0.) {
+			return Vec::new();
+		}
+
+		// Offsets are counted from the frame the current time falls in
+		let current_frame = (self.current_time * self.frame_rate + 1e-6).floor();
+		self.onion_skin
+			.frame_offsets()
+			.into_iter()
+			.map(|(offset, opacity)| ((current_frame + offset as f64) / self.frame_rate, opacity))
+			.filter(|(time, _)| (0.0..self.duration).contains(time))
+			.collect()
+	}
+
+	/// Adds a keyframe at the current time holding the input's current value, replacing any keyframe already there.
+	pub fn set_keyframe(&mut self, binding: VariableBinding, interpolation: Interpolation, network: &NodeNetwork) {
+		let Some(value) = binding.input(network).and_then(|input| input.as_value()).cloned() else {
+			log::warn!("Only inputs with a value can be animated");
+			return;
+		};
+		let time = self.current_time;
+
+		let track_index = match self.tracks.iter().position(|track| track.binding == binding) {
+			Some(index) => index,
+			None => {
+				self.tracks.push(KeyframeTrack { binding, keyframes: Vec::new() });
+				self.tracks.len() - 1
+			}
+		};
+		let keyframes = &mut self.tracks[track_index].keyframes;
+
+		let keyframe = Keyframe { time, value, interpolation };
+		match keyframes.iter().position(|existing| existing.time == time) {
+			Some(index) => keyframes[index] = keyframe,
+			None => keyframes.insert(keyframes.partition_point(|existing| existing.time < time), keyframe),
+		}
+	}
+
+	/// Removes the keyframe at the given time, along with the input's track once it has no keyframes left. The input keeps its current value.
+	pub fn remove_keyframe(&mut self, binding: &VariableBinding, time: f64) {
+		for track in self.tracks.iter_mut().filter(|track| &track.binding == binding) {
+			track.keyframes.retain(|keyframe| keyframe.time != time);
+		}
+		self.tracks.retain(|track| !track.keyframes.is_empty());
+	}
+
+	/// Changes how the value changes after the keyframe at the given time.
+	pub fn set_interpolation(&mut self, binding: &VariableBinding, time: f64, interpolation: Interpolation) {
+		let keyframes = self.tracks.iter_mut().filter(|track| &track.binding == binding).flat_map(|track| track.keyframes.iter_mut());
+		for keyframe in keyframes.filter(|keyframe| keyframe.time == time) {
+			keyframe.interpolation = interpolation;
+		}
+	}
+
+	/// Moves the current time, writing the values the animated inputs take at that time into the network.
+	pub fn set_current_time(&mut self, time: f64, network: &mut NodeNetwork) {
+		self.current_time = time.clamp(0., self.duration.max(0.));
+		self.apply_time(network, self.current_time);
+	}
+
+	/// Returns a copy of the network with the values the animated inputs take at the given time, leaving the current time untouched.
+	pub fn network_at_time(&self, network: &NodeNetwork, time: f64) -> NodeNetwork {
+		let mut network = network.clone();
+		self.apply_time(&mut network, time);
+		network
+	}
+
+	fn apply_time(&self, network: &mut NodeNetwork, time: f64) {
+		for track in &self.tracks {
+			let Some(value) = track.value_at(time) else { continue };
+			track.binding.apply(network, |_| Some(value));
+		}
+		set_time_inputs(network, time);
+	}
+}
+
+/// The index of the input which the node's definition marks to be given the time of the animation.
+fn time_input_index(node_name: &str) -> Option<usize> {
+	resolve_document_node_type(node_name).and_then(|definition| definition.time_input)
+}
+
+/// Whether any node in the network, or in the networks nested within it, follows the time of the animation.
+pub fn has_time_inputs(network: &NodeNetwork) -> bool {
+	network.nodes.values().any(|node| {
+		let nested = match &node.implementation {
+			DocumentNodeImplementation::Network(nested) => has_time_inputs(nested),
+			_ => false,
+		};
+		nested || time_input_index(&node.name).is_some_and(|index| matches!(node.inputs.get(index), Some(NodeInput::Value { .. })))
+	})
+}
+
+/// Gives the time in seconds to the time inputs of the nodes following the animation, leaving those connected to another node alone.
+fn set_time_inputs(network: &mut NodeNetwork, time: f64) {
+	for node in network.nodes.values_mut() {
+		if let Some(NodeInput::Value { tagged_value, .. }) = time_input_index(&node.name).and_then(|index| node.inputs.get_mut(index)) {
+			*tagged_value = TaggedValue::F64(time);
+		}
+		if let DocumentNodeImplementation::Network(nested) = &mut node.implementation {
+			set_time_inputs(nested, time);
+		}
+	}
+}
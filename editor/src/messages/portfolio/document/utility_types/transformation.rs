@@ -8,6 +8,7 @@ use crate::messages::tool::utility_types::ToolType;
 
 use graph_craft::document::NodeNetwork;
 use graphene_core::renderer::Quad;
+use graphene_core::units::DocumentUnits;
 use graphene_core::vector::{ManipulatorPointId, SelectedType};
 
 use glam::{DAffine2, DVec2};
@@ -241,7 +242,7 @@ impl TransformOperation {
 			};
 
 			selected.update_transforms(transformation);
-			self.hints(snapping, axis_constraint, selected.responses);
+			self.hints(snapping, axis_constraint, selected.units, selected.document_metadata.document_to_viewport, selected.responses);
 		}
 	}
 
@@ -259,7 +260,11 @@ impl TransformOperation {
 	pub fn grs_typed(&mut self, typed: Option<f64>, selected: &mut Selected, snapping: bool) {
 		match self {
 			TransformOperation::None => (),
-			TransformOperation::Grabbing(translation) => translation.typed_distance = typed,
+			// The distance is typed in the document's units, and applied in the viewport
+			TransformOperation::Grabbing(translation) => {
+				let viewport_per_pixel = selected.document_metadata.document_to_viewport.transform_vector2(DVec2::X).length();
+				translation.typed_distance = typed.map(|distance| selected.units.to_pixels(distance) * viewport_per_pixel);
+			}
 			TransformOperation::Rotating(rotation) => rotation.typed_angle = typed,
 			TransformOperation::Scaling(scale) => scale.typed_factor = typed,
 		};
@@ -273,7 +278,7 @@ impl TransformOperation {
 		self.apply_transform_operation(selected, snapping, axis_constraint);
 	}
 
-	pub fn hints(&self, snapping: bool, axis_constraint: Axis, responses: &mut VecDeque<Message>) {
+	pub fn hints(&self, snapping: bool, axis_constraint: Axis, units: DocumentUnits, document_to_viewport: DAffine2, responses: &mut VecDeque<Message>) {
 		use crate::messages::input_mapper::utility_types::input_keyboard::Key;
 		use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -296,8 +301,10 @@ impl TransformOperation {
 		};
 		let grs_value_text = match self {
 			TransformOperation::None => String::new(),
-			// TODO: Fix that the translation is showing numbers in viewport space, not document space
-			TransformOperation::Grabbing(translation) => format!("Translating {}", axis_text(translation.to_dvec(), true)),
+			TransformOperation::Grabbing(translation) => {
+				let distance = document_to_viewport.inverse().transform_vector2(translation.to_dvec()) / units.pixels_per_unit();
+				format!("Translating {} {}", axis_text(distance, true), units.length_unit.symbol())
+			}
 			TransformOperation::Rotating(rotation) => format!("Rotating by {:.3}°", rotation.to_f64(snapping) * 360. / std::f64::consts::TAU),
 			TransformOperation::Scaling(scale) => format!("Scaling {}", axis_text(scale.to_dvec(snapping), false)),
 		};
@@ -317,6 +324,8 @@ pub struct Selected<'a> {
 	pub pivot: &'a mut DVec2,
 	pub shape_editor: Option<&'a ShapeState>,
 	pub tool_type: &'a ToolType,
+	/// The unit that distances are typed and shown in while translating.
+	pub units: DocumentUnits,
 }
 
 impl<'a> Selected<'a> {
@@ -346,6 +355,7 @@ impl<'a> Selected<'a> {
 			pivot,
 			shape_editor,
 			tool_type,
+			units: DocumentUnits::default(),
 		}
 	}
 
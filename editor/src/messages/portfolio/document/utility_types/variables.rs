@@ -0,0 +1,300 @@
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{DocumentNode, NodeId, NodeInput, NodeNetwork};
+
+/// A node input whose value is supplied by a [`DocumentVariable`], animated by a keyframe track, or linked to a swatch.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VariableBinding {
+	/// The path through the nested networks of the document network, ending with the ID of the bound node itself.
+	pub node_path: Vec<NodeId>,
+	pub input_index: usize,
+}
+
+impl VariableBinding {
+	pub(super) fn node<'a>(&self, network: &'a NodeNetwork) -> Option<&'a DocumentNode> {
+		let (node_id, nested_path) = self.node_path.split_last()?;
+		network.nested_network(nested_path)?.nodes.get(node_id)
+	}
+
+	pub(super) fn input<'a>(&self, network: &'a NodeNetwork) -> Option<&'a NodeInput> {
+		self.node(network)?.inputs.get(self.input_index)
+	}
+
+	pub(super) fn input_mut<'a>(&self, network: &'a mut NodeNetwork) -> Option<&'a mut NodeInput> {
+		let (node_id, nested_path) = self.node_path.split_last()?;
+		network.nested_network_mut(nested_path)?.nodes.get_mut(node_id)?.inputs.get_mut(self.input_index)
+	}
+
+	/// Writes a value into the bound input, made by `value` from the input's current value so it can be converted to the input's type, returning whether the input took it on.
+	/// Inputs that have since been exposed and connected to another node are left alone, as are those `value` returns `None` for.
+	pub(super) fn apply(&self, network: &mut NodeNetwork, value: impl FnOnce(&TaggedValue) -> Option<TaggedValue>) -> bool {
+		let Some(NodeInput::Value { tagged_value, .. }) = self.input_mut(network) else { return false };
+		let Some(value) = value(tagged_value) else { return false };
+		*tagged_value = value;
+		true
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DocumentVariable {
+	pub name: String,
+	/// One value for each variant, in the same order as [`DocumentVariables::variants`].
+	pub values: Vec<TaggedValue>,
+	/// The node inputs which take on the value of this variable.
+	pub bindings: Vec<VariableBinding>,
+}
+
+impl DocumentVariable {
+	/// The value currently held by the bound inputs in the network.
+	fn current_value(&self, network: &NodeNetwork) -> Option<TaggedValue> {
+		self.bindings.iter().find_map(|binding| binding.input(network)?.as_value().cloned())
+	}
+}
+
+/// Named values (such as the colors of a light and dark theme, or the strings of each locale) which node inputs can be bound to.
+/// Every variant supplies its own value for each variable, so the same artwork can be rendered once per variant without duplicating it.
+///
+/// The values of the active variant live in the document network like any other input value, so editing a bound input edits the active variant.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DocumentVariables {
+	/// The names of the variants, such as "Light" and "Dark".
+	pub variants: Vec<String>,
+	pub variables: Vec<DocumentVariable>,
+	/// The index of the variant whose values are currently written into the document network.
+	pub active_variant: usize,
+}
+
+impl DocumentVariables {
+	pub fn is_empty(&self) -> bool {
+		self.variants.is_empty()
+	}
+
+	/// The variable which the input is bound to, if any.
+	pub fn bound_variable(&self, binding: &VariableBinding) -> Option<&DocumentVariable> {
+		self.variables.iter().find(|variable| variable.bindings.contains(binding))
+	}
+
+	/// Adds a variant which starts out with the same values as the active variant, numbered so its name isn't taken by another variant.
+	pub fn add_variant(&mut self, network: &NodeNetwork) {
+		self.store_active_variant(network);
+		let name = (1..).map(|number| format!("Variant {number}")).find(|name| !self.variants.contains(name)).unwrap_or_default();
+
+		for variable in &mut self.variables {
+			let value = variable
+				.values
+				.get(self.active_variant)
+				.cloned()
+				.or_else(|| variable.current_value(network))
+				.unwrap_or(TaggedValue::None);
+			variable.values.push(value);
+		}
+		self.variants.push(name);
+	}
+
+	pub fn remove_variant(&mut self, variant_index: usize, network: &mut NodeNetwork) {
+		if variant_index >= self.variants.len() {
+			return;
+		}
+		self.store_active_variant(network);
+
+		self.variants.remove(variant_index);
+		for variable in &mut self.variables {
+			if variant_index < variable.values.len() {
+				variable.values.remove(variant_index);
+			}
+		}
+
+		if self.active_variant == variant_index {
+			self.active_variant = 0;
+			self.apply_variant(network, self.active_variant);
+		} else if self.active_variant > variant_index {
+			self.active_variant -= 1;
+		}
+	}
+
+	/// Stores the values of the bound inputs into the active variant, then writes the values of the newly active variant into the bound inputs.
+	pub fn set_active_variant(&mut self, variant_index: usize, network: &mut NodeNetwork) {
+		if variant_index >= self.variants.len() {
+			return;
+		}
+
+		self.store_active_variant(network);
+		self.active_variant = variant_index;
+		self.apply_variant(network, variant_index);
+	}
+
+	/// Binds the input to the named variable, creating the variable from the input's current value if it doesn't exist yet.
+	/// An input is bound to at most one variable, so any previous binding of the input is replaced.
+	pub fn bind_input(&mut self, binding: VariableBinding, variable_name: String, network: &mut NodeNetwork) {
+		let Some(value) = binding.input(network).and_then(|input| input.as_value()).cloned() else {
+			log::warn!("Only inputs with a value can be bound to a document variable");
+			return;
+		};
+
+		self.unbind_input(&binding);
+
+		if let Some(variable) = self.variables.iter_mut().find(|variable| variable.name == variable_name) {
+			// Take on the variable's existing value for the active variant
+			if let Some(value) = variable.values.get(self.active_variant) {
+				binding.apply(network, |_| Some(value.clone()));
+			}
+			variable.bindings.push(binding);
+		} else {
+			self.variables.push(DocumentVariable {
+				name: variable_name,
+				values: vec![value; self.variants.len()],
+				bindings: vec![binding],
+			});
+		}
+	}
+
+	/// Removes the binding of the input, along with the variable it was bound to if no other inputs remain bound to it.
+	/// The input keeps the value of the active variant.
+	pub fn unbind_input(&mut self, binding: &VariableBinding) {
+		for variable in &mut self.variables {
+			variable.bindings.retain(|existing| existing != binding);
+		}
+		self.variables.retain(|variable| !variable.bindings.is_empty());
+	}
+
+	/// Returns a copy of the network with the values of the given variant written into the bound inputs.
+	pub fn network_with_variant(&self, network: &NodeNetwork, variant_index: usize) -> NodeNetwork {
+		let mut network = network.clone();
+		self.apply_variant(&mut network, variant_index);
+		network
+	}
+
+	fn apply_variant(&self, network: &mut NodeNetwork, variant_index: usize) {
+		for variable in &self.variables {
+			let Some(value) = variable.values.get(variant_index) else { continue };
+
+			for binding in &variable.bindings {
+				binding.apply(network, |_| Some(value.clone()));
+			}
+		}
+	}
+
+	/// Reads the current values of the bound inputs, which may have been edited since the active variant was applied, back into the active variant.
+	pub fn store_active_variant(&mut self, network: &NodeNetwork) {
+		let variant_count = self.variants.len();
+		let active_variant = self.active_variant;
+
+		for variable in &mut self.variables {
+			let Some(value) = variable.current_value(network) else { continue };
+
+			if variable.values.len() < variant_count {
+				variable.values.resize(variant_count, value.clone());
+			}
+			if let Some(active_value) = variable.values.get_mut(active_variant) {
+				*active_value = value;
+			}
+		}
+	}
+}
+
+/// Networks of nodes whose inputs are bound, shared by the tests of the variables, parameters, and swatches binding them.
+#[cfg(test)]
+pub(super) mod test_bindings {
+	use super::*;
+
+	/// A network with a node for each value, numbered from 1, whose only input holds that value.
+	pub fn network(values: impl IntoIterator<Item = TaggedValue>) -> NodeNetwork {
+		let node = |value| DocumentNode {
+			inputs: vec![NodeInput::value(value, false)],
+			..Default::default()
+		};
+		NodeNetwork {
+			nodes: (1..).map(NodeId).zip(values.into_iter().map(node)).collect(),
+			..Default::default()
+		}
+	}
+
+	pub fn binding(node_id: u64) -> VariableBinding {
+		VariableBinding {
+			node_path: vec![NodeId(node_id)],
+			input_index: 0,
+		}
+	}
+
+	pub fn value(network: &NodeNetwork, node_id: u64) -> Option<&TaggedValue> {
+		binding(node_id).input(network)?.as_value()
+	}
+
+	/// Connects the input of the node to the output of another node, as if it had been exposed and wired up, returning the connection.
+	pub fn connect(network: &mut NodeNetwork, node_id: u64, upstream_node_id: u64) -> NodeInput {
+		let connected = NodeInput::node(NodeId(upstream_node_id), 0);
+		*binding(node_id).input_mut(network).unwrap() = connected.clone();
+		connected
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::test_bindings::{binding, connect, value};
+	use super::*;
+
+	fn network() -> NodeNetwork {
+		test_bindings::network([TaggedValue::F64(1.), TaggedValue::F64(2.)])
+	}
+
+	#[test]
+	fn bound_inputs_take_on_the_active_variant() {
+		let mut network = network();
+		let mut variables = DocumentVariables::default();
+		variables.add_variant(&network);
+		variables.bind_input(binding(1), "Size".to_string(), &mut network);
+		variables.add_variant(&network);
+		assert_eq!(variables.variants, ["Variant 1", "Variant 2"]);
+
+		// Binding another input to the variable gives it the variable's value
+		variables.bind_input(binding(2), "Size".to_string(), &mut network);
+		assert_eq!(value(&network, 2), Some(&TaggedValue::F64(1.)));
+		assert_eq!(variables.bound_variable(&binding(2)).map(|variable| variable.name.as_str()), Some("Size"));
+
+		// Edits to the bound inputs are kept in the variant they were made in
+		binding(1).apply(&mut network, |_| Some(TaggedValue::F64(5.)));
+		variables.set_active_variant(1, &mut network);
+		assert_eq!(value(&network, 1), Some(&TaggedValue::F64(1.)));
+		assert_eq!(value(&network, 2), Some(&TaggedValue::F64(1.)));
+		variables.set_active_variant(0, &mut network);
+		assert_eq!(value(&network, 1), Some(&TaggedValue::F64(5.)));
+		assert_eq!(value(&network, 2), Some(&TaggedValue::F64(5.)));
+
+		variables.unbind_input(&binding(1));
+		variables.unbind_input(&binding(2));
+		assert!(variables.variables.is_empty());
+	}
+
+	#[test]
+	fn exported_variants_include_unstored_edits() {
+		let mut network = network();
+		let mut variables = DocumentVariables::default();
+		variables.add_variant(&network);
+		variables.bind_input(binding(1), "Size".to_string(), &mut network);
+		variables.add_variant(&network);
+
+		binding(1).apply(&mut network, |_| Some(TaggedValue::F64(7.)));
+		assert_eq!(value(&variables.network_with_variant(&network, 0), 1), Some(&TaggedValue::F64(1.)));
+
+		let mut exported = variables.clone();
+		exported.store_active_variant(&network);
+		assert_eq!(value(&exported.network_with_variant(&network, 0), 1), Some(&TaggedValue::F64(7.)));
+		assert_eq!(value(&exported.network_with_variant(&network, 1), 1), Some(&TaggedValue::F64(1.)));
+	}
+
+	#[test]
+	fn connected_inputs_are_left_alone() {
+		let mut network = network();
+		let mut variables = DocumentVariables::default();
+		variables.add_variant(&network);
+		variables.bind_input(binding(1), "Size".to_string(), &mut network);
+		variables.bind_input(binding(2), "Size".to_string(), &mut network);
+		variables.add_variant(&network);
+
+		let connected = connect(&mut network, 2, 1);
+		assert!(!binding(2).apply(&mut network, |_| Some(TaggedValue::F64(3.))));
+
+		variables.set_active_variant(1, &mut network);
+		assert_eq!(binding(2).input(&network), Some(&connected));
+	}
+}
@@ -0,0 +1,260 @@
+//! The ICC color profile embedded in raster exports, which tags their pixels as sRGB so other applications show the same colors as Graphite.
+//!
+//! Graphite renders in sRGB, which is also the color space the browser gives the pixels of exported images in, so tagging is all that's needed and no conversion is done.
+
+use super::icon_set::crc32;
+
+/// The number of entries of the table that samples the sRGB transfer curve, which is plenty for 8-bit and 16-bit pixels.
+const CURVE_ENTRIES: usize = 1024;
+
+/// The D50 white point of the profile connection space that ICC profiles describe colors relative to.
+const D50_WHITE: [f64; 3] = [0.9642, 1., 0.8249];
+
+/// The sRGB primaries, chromatically adapted from their D65 white point to D50 with the Bradford transform, as given in the sRGB profiles published by the ICC.
+const SRGB_PRIMARIES: [[f64; 3]; 3] = [
+	[0.436_074_7, 0.222_504_5, 0.013_932_2],
+	[0.385_064_9, 0.716_878_6, 0.097_104_5],
+	[0.143_080_4, 0.060_616_9, 0.714_173_3],
+];
+
+/// Writes a version 2 ICC display profile for sRGB, which every application that reads color profiles understands.
+pub fn srgb_icc_profile() -> Vec<u8> {
+	let description = text_description_tag("sRGB");
+	let copyright = text_tag("No copyright, use freely");
+	let white_point = xyz_tag(D50_WHITE);
+	let [red, green, blue] = SRGB_PRIMARIES.map(xyz_tag);
+	let curve = curve_tag();
+
+	// The red, green, and blue tone curves are the same, so they share their data
+	let tags: [(&[u8; 4], &[u8]); 9] = [
+		(b"desc", &description),
+		(b"cprt", &copyright),
+		(b"wtpt", &white_point),
+		(b"rXYZ", &red),
+		(b"gXYZ", &green),
+		(b"bXYZ", &blue),
+		(b"rTRC", &curve),
+		(b"gTRC", &curve),
+		(b"bTRC", &curve),
+	];
+
+	let table_length = 4 + tags.len() * 12;
+	let mut data = Vec::new();
+	let mut table = Vec::with_capacity(table_length);
+	table.extend((tags.len() as u32).to_be_bytes());
+	let mut written: Vec<(&[u8], usize)> = Vec::new();
+	for (signature, tag) in tags {
+		let offset = match written.iter().find(|(existing, _)| *existing == tag) {
+			Some(&(_, offset)) => offset,
+			None => {
+				let offset = 128 + table_length + data.len();
+				data.extend(tag);
+				// Each tag starts on a four byte boundary
+				data.resize(data.len().next_multiple_of(4), 0);
+				written.push((tag, offset));
+				offset
+			}
+		};
+		table.extend(signature);
+		table.extend((offset as u32).to_be_bytes());
+		table.extend((tag.len() as u32).to_be_bytes());
+	}
+
+	let size = 128 + table.len() + data.len();
+	let mut profile = Vec::with_capacity(size);
+	profile.extend((size as u32).to_be_bytes());
+	// Preferred color management module
+	profile.extend([0; 4]);
+	profile.extend([2, 0x10, 0, 0]);
+	profile.extend(b"mntrRGB XYZ ");
+	// Date and time of creation, which is left unset so the exported files are the same every time
+	profile.extend([0; 12]);
+	profile.extend(b"acsp");
+	// Platform, flags, device manufacturer, device model, device attributes, and the perceptual rendering intent
+	profile.extend([0; 28]);
+	profile.extend(D50_WHITE.iter().flat_map(|&value| s15_fixed16(value)));
+	// Creator, profile ID, and reserved bytes
+	profile.extend([0; 48]);
+	debug_assert_eq!(profile.len(), 128);
+
+	profile.extend(table);
+	profile.extend(data);
+	profile
+}
+
+/// Encodes the profile as a PNG `iCCP` chunk, to be inserted after the header chunk of a PNG file.
+pub fn png_iccp_chunk(profile: &[u8]) -> Vec<u8> {
+	let data = png_iccp_data(profile);
+
+	let mut chunk = Vec::with_capacity(data.len() + 12);
+	chunk.extend((data.len() as u32).to_be_bytes());
+	chunk.extend(b"iCCP");
+	chunk.extend(&data);
+	let crc = crc32(&chunk[4..]);
+	chunk.extend(crc.to_be_bytes());
+	chunk
+}
+
+/// The data of a PNG `iCCP` chunk holding the profile.
+pub fn png_iccp_data(profile: &[u8]) -> Vec<u8> {
+	// The profile's name is followed by the compression method, which is the only one PNG has
+	[b"sRGB\0\0".as_slice(), &zlib_stored(profile)].concat()
+}
+
+/// Encodes the profile as a JPEG `APP2` segment, to be inserted after the start of image marker and any `APP0` segment of a JPEG file.
+/// Profiles too long for one segment would be split across several, but the sRGB profile fits in one.
+pub fn jpeg_icc_segment(profile: &[u8]) -> Vec<u8> {
+	// The identifier is followed by the number of this segment and the count of segments, counted from 1
+	let data = [b"ICC_PROFILE\0\x01\x01".as_slice(), profile].concat();
+
+	let mut segment = vec![0xFF, 0xE2];
+	segment.extend(((data.len() + 2) as u16).to_be_bytes());
+	segment.extend(data);
+	segment
+}
+
+/// Wraps the data in a zlib stream without compressing it, as stored deflate blocks, since the profile is small enough that compressing it isn't worthwhile.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+	let mut stream = vec![0x78, 0x01];
+	let mut blocks = data.chunks(u16::MAX as usize).peekable();
+	if blocks.peek().is_none() {
+		stream.extend([1, 0, 0, 0xFF, 0xFF]);
+	}
+	while let Some(block) = blocks.next() {
+		stream.push(blocks.peek().is_none() as u8);
+		stream.extend((block.len() as u16).to_le_bytes());
+		stream.extend((!(block.len() as u16)).to_le_bytes());
+		stream.extend(block);
+	}
+
+	// The Adler-32 checksum of the uncompressed data
+	let (a, b) = data.iter().fold((1_u32, 0_u32), |(a, b), &byte| {
+		let a = (a + byte as u32) % 65521;
+		(a, (b + a) % 65521)
+	});
+	stream.extend(((b << 16) | a).to_be_bytes());
+	stream
+}
+
+/// A number in the ICC profile's signed fixed point format with 16 fractional bits.
+fn s15_fixed16(value: f64) -> [u8; 4] {
+	((value * 65536.).round() as i32).to_be_bytes()
+}
+
+fn xyz_tag(xyz: [f64; 3]) -> Vec<u8> {
+	let mut tag = b"XYZ \0\0\0\0".to_vec();
+	tag.extend(xyz.iter().flat_map(|&value| s15_fixed16(value)));
+	tag
+}
+
+/// A table sampling the sRGB transfer curve from the encoded values of the pixels to linear light.
+fn curve_tag() -> Vec<u8> {
+	let mut tag = b"curv\0\0\0\0".to_vec();
+	tag.extend((CURVE_ENTRIES as u32).to_be_bytes());
+	for index in 0..CURVE_ENTRIES {
+		let encoded = index as f64 / (CURVE_ENTRIES - 1) as f64;
+		let linear = if encoded <= 0.04045 { encoded / 12.92 } else { ((encoded + 0.055) / 1.055).powf(2.4) };
+		tag.extend(((linear * u16::MAX as f64).round() as u16).to_be_bytes());
+	}
+	tag
+}
+
+fn text_tag(text: &str) -> Vec<u8> {
+	[b"text\0\0\0\0".as_slice(), text.as_bytes(), &[0]].concat()
+}
+
+/// A version 2 text description, which has an ASCII description followed by empty Unicode and ScriptCode descriptions.
+fn text_description_tag(text: &str) -> Vec<u8> {
+	let mut tag = b"desc\0\0\0\0".to_vec();
+	tag.extend((text.len() as u32 + 1).to_be_bytes());
+	tag.extend(text.as_bytes());
+	tag.push(0);
+	// Unicode language code and character count
+	tag.extend([0; 8]);
+	// ScriptCode code, character count, and its description which always takes 67 bytes
+	tag.extend([0; 3]);
+	tag.extend([0; 67]);
+	tag
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn u32_be(bytes: &[u8], at: usize) -> u32 {
+		u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap())
+	}
+
+	/// The signature, offset, and length of each tag in the profile's tag table.
+	fn tags(profile: &[u8]) -> Vec<([u8; 4], usize, usize)> {
+		(0..u32_be(profile, 128) as usize)
+			.map(|index| {
+				let entry = 132 + 12 * index;
+				(profile[entry..entry + 4].try_into().unwrap(), u32_be(profile, entry + 4) as usize, u32_be(profile, entry + 8) as usize)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn profile_header_and_tags_are_consistent() {
+		let profile = srgb_icc_profile();
+		assert_eq!(u32_be(&profile, 0) as usize, profile.len());
+		assert_eq!(&profile[12..24], b"mntrRGB XYZ ");
+		assert_eq!(&profile[36..40], b"acsp");
+
+		let tags = tags(&profile);
+		assert_eq!(tags.len(), 9);
+		for &(signature, offset, length) in &tags {
+			assert_eq!(offset % 4, 0, "{:?} isn't aligned", std::str::from_utf8(&signature));
+			assert!(offset >= 128 + 4 + 12 * tags.len() && offset + length <= profile.len());
+		}
+
+		let offset_of = |signature: &[u8; 4]| tags.iter().find(|(tag, _, _)| tag == signature).map(|&(_, offset, _)| offset).unwrap();
+		assert_eq!(offset_of(b"rTRC"), offset_of(b"gTRC"));
+		assert_eq!(offset_of(b"gTRC"), offset_of(b"bTRC"));
+		let white_point = offset_of(b"wtpt");
+		assert_eq!(&profile[white_point..white_point + 4], b"XYZ ");
+		assert_eq!(profile[white_point + 8..white_point + 20], D50_WHITE.iter().flat_map(|&value| s15_fixed16(value)).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn curve_runs_from_black_to_white() {
+		let curve = curve_tag();
+		assert_eq!(curve.len(), 12 + 2 * CURVE_ENTRIES);
+		assert_eq!(&curve[12..14], &[0, 0]);
+		assert_eq!(&curve[curve.len() - 2..], &[0xFF, 0xFF]);
+	}
+
+	#[test]
+	fn png_with_the_chunk_reads_back_the_profile() {
+		let image = image::RgbaImage::from_pixel(2, 2, image::Rgba([10, 20, 30, 255]));
+		let mut png = std::io::Cursor::new(Vec::new());
+		image.write_to(&mut png, image::ImageFormat::Png).unwrap();
+		let png = png.into_inner();
+
+		// The chunk goes right after the header chunk, which follows the 8 byte signature and is 25 bytes long
+		let profile = srgb_icc_profile();
+		let tagged = [&png[..33], &png_iccp_chunk(&profile), &png[33..]].concat();
+
+		let reader = png::Decoder::new(tagged.as_slice()).read_info().unwrap();
+		assert_eq!(reader.info().icc_profile.as_deref(), Some(profile.as_slice()));
+	}
+
+	#[test]
+	fn zlib_stream_checksums_the_data() {
+		let stream = zlib_stored(b"Wikipedia");
+		assert_eq!(&stream[..2], &[0x78, 0x01]);
+		// One final stored block holding all nine bytes
+		assert_eq!(&stream[2..7], &[1, 9, 0, !9, 0xFF]);
+		assert_eq!(&stream[7..16], b"Wikipedia");
+		assert_eq!(u32_be(&stream, 16), 0x11E6_0398);
+	}
+
+	#[test]
+	fn jpeg_segment_counts_its_own_length() {
+		let segment = jpeg_icc_segment(b"profile");
+		assert_eq!(&segment[..2], &[0xFF, 0xE2]);
+		assert_eq!(u16::from_be_bytes([segment[2], segment[3]]) as usize, segment.len() - 2);
+		assert!(segment.ends_with(b"ICC_PROFILE\0\x01\x01profile"));
+	}
+}
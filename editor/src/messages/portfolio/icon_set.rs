@@ -0,0 +1,331 @@
+//! Bundling of the icon files exported by the "Icon Set" export file type, made from the artwork rasterized at each standard icon size.
+
+/// Every size, in pixels, that the artwork is rasterized at for an icon set.
+pub const ICON_SIZES: [u32; 10] = [16, 32, 48, 64, 128, 180, 192, 256, 512, 1024];
+
+/// The sizes included in the Windows `.ico` file, which can't store sizes over 256 pixels.
+const ICO_SIZES: [u32; 6] = [16, 32, 48, 64, 128, 256];
+
+/// The sizes included in the `favicon.ico` file of the favicon set, which browsers only display at small sizes.
+const FAVICON_ICO_SIZES: [u32; 3] = [16, 32, 48];
+
+/// The macOS `.icns` element types which store a PNG image of each size, including the high-density variants of smaller sizes.
+const ICNS_TYPES: [(&[u8; 4], u32); 11] = [
+	(b"icp4", 16),
+	(b"icp5", 32),
+	(b"icp6", 64),
+	(b"ic07", 128),
+	(b"ic08", 256),
+	(b"ic09", 512),
+	(b"ic10", 1024),
+	(b"ic11", 32),
+	(b"ic12", 64),
+	(b"ic13", 256),
+	(b"ic14", 512),
+];
+
+/// The PNG files of the favicon set, along with their size and whether they're listed in the web app manifest.
+const FAVICON_PNGS: [(&str, u32, bool); 5] = [
+	("favicon-16x16.png", 16, false),
+	("favicon-32x32.png", 32, false),
+	("apple-touch-icon.png", 180, false),
+	("android-chrome-192x192.png", 192, true),
+	("android-chrome-512x512.png", 512, true),
+];
+
+/// The user's choices for how the artwork is fit into each icon.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct IconSetOptions {
+	/// The empty margin around the artwork, as a percentage of the icon size.
+	pub padding: f64,
+	/// Whether the smallest sizes are sharpened to counteract the blurring from downscaling the artwork.
+	pub sharpen: bool,
+}
+
+impl Default for IconSetOptions {
+	fn default() -> Self {
+		Self { padding: 0., sharpen: true }
+	}
+}
+
+/// How the frontend should rasterize the artwork for one size of the icon set.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct IconRasterization {
+	pub size: u32,
+	/// The empty margin on each side of the artwork in whole pixels, so the artwork's edges stay aligned to the pixel grid.
+	pub padding: u32,
+	/// The strength of the unsharp mask applied after downscaling, where 0 leaves the image unchanged.
+	pub sharpen: f64,
+}
+
+/// Decides the padding and sharpening used for each of the [`ICON_SIZES`].
+pub fn icon_rasterizations(options: IconSetOptions) -> Vec<IconRasterization> {
+	ICON_SIZES
+		.into_iter()
+		.map(|size| {
+			let padding = (size as f64 * options.padding.clamp(0., 45.) / 100.).round() as u32;
+			// Detail is lost the most when downscaling to the smallest sizes, while larger sizes look best left untouched
+			let sharpen = match size {
+				_ if !options.sharpen => 0.,
+				..=32 => 0.5,
+				..=64 => 0.25,
+				_ => 0.,
+			};
+			IconRasterization { size, padding, sharpen }
+		})
+		.collect()
+}
+
+/// Bundles the rasterized PNG image of each icon size into a ZIP archive containing an `.ico` file, an `.icns` file, and a favicon set with its web app manifest.
+pub fn bundle_icon_set(name: &str, icons: &[(u32, Vec<u8>)]) -> Vec<u8> {
+	let png = |size: u32| icons.iter().find(|(icon_size, _)| *icon_size == size).map(|(_, png)| png.as_slice());
+
+	let mut files = vec![(format!("{name}.ico"), encode_ico(&ICO_SIZES, png)), (format!("{name}.icns"), encode_icns(png))];
+
+	files.push(("favicon/favicon.ico".to_string(), encode_ico(&FAVICON_ICO_SIZES, png)));
+	for (file_name, size, _) in FAVICON_PNGS {
+		if let Some(png) = png(size) {
+			files.push((format!("favicon/{file_name}"), png.to_vec()));
+		}
+	}
+	files.push(("favicon/site.webmanifest".to_string(), web_manifest(name, png).into_bytes()));
+
+	encode_zip(&files)
+}
+
+/// Writes an `.ico` file which embeds the PNG image of each size, a format supported since Windows Vista.
+fn encode_ico<'a>(sizes: &[u32], png: impl Fn(u32) -> Option<&'a [u8]>) -> Vec<u8> {
+	let images = sizes.iter().filter_map(|&size| png(size).map(|png| (size, png))).collect::<Vec<_>>();
+
+	let mut ico = Vec::new();
+	ico.extend(0_u16.to_le_bytes());
+	// Image type 1 is an icon, rather than a cursor
+	ico.extend(1_u16.to_le_bytes());
+	ico.extend((images.len() as u16).to_le_bytes());
+
+	let mut offset = 6 + 16 * images.len() as u32;
+	for &(size, png) in &images {
+		// A dimension of 256 pixels doesn't fit in a byte, so it's stored as 0
+		let dimension = if size >= 256 { 0 } else { size as u8 };
+		ico.extend([dimension, dimension, 0, 0]);
+		// Color planes and bits per pixel
+		ico.extend(1_u16.to_le_bytes());
+		ico.extend(32_u16.to_le_bytes());
+		ico.extend((png.len() as u32).to_le_bytes());
+		ico.extend(offset.to_le_bytes());
+		offset += png.len() as u32;
+	}
+	for (_, png) in images {
+		ico.extend(png);
+	}
+
+	ico
+}
+
+/// Writes an `.icns` file with an element holding the PNG image of each size, which are all big-endian and prefixed with their type and length.
+fn encode_icns<'a>(png: impl Fn(u32) -> Option<&'a [u8]>) -> Vec<u8> {
+	let mut elements = Vec::new();
+	for (element_type, size) in ICNS_TYPES {
+		let Some(png) = png(size) else { continue };
+		elements.extend(element_type);
+		elements.extend((8 + png.len() as u32).to_be_bytes());
+		elements.extend(png);
+	}
+
+	let mut icns = Vec::with_capacity(8 + elements.len());
+	icns.extend(b"icns");
+	icns.extend((8 + elements.len() as u32).to_be_bytes());
+	icns.extend(elements);
+	icns
+}
+
+/// Writes the web app manifest which tells browsers and mobile operating systems which icons to use when the site is installed as an app.
+fn web_manifest<'a>(name: &str, png: impl Fn(u32) -> Option<&'a [u8]>) -> String {
+	let icons = FAVICON_PNGS
+		.into_iter()
+		.filter(|&(_, size, in_manifest)| in_manifest && png(size).is_some())
+		.map(|(file_name, size, _)| serde_json::json!({ "src": format!("/{file_name}"), "sizes": format!("{size}x{size}"), "type": "image/png" }))
+		.collect::<Vec<_>>();
+
+	let manifest = serde_json::json!({
+		"name": name,
+		"short_name": name,
+		"icons": icons,
+		"display": "standalone",
+	});
+	serde_json::to_string_pretty(&manifest).unwrap_or_default()
+}
+
+/// Writes a ZIP archive which stores the files without compression, since PNG images are already compressed.
+pub(crate) fn encode_zip(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+	// The MS-DOS timestamp of 1980-01-01 00:00, the earliest that can be represented
+	const TIME: u16 = 0;
+	const DATE: u16 = (1 << 5) | 1;
+	const VERSION: u16 = 20;
+
+	let mut zip = Vec::new();
+	let mut central_directory = Vec::new();
+
+	for (file_name, data) in files {
+		let offset = zip.len() as u32;
+		let crc = crc32(data);
+		let size = data.len() as u32;
+
+		// Fields shared by the local file header and the central directory entry: flags, compression method, time, date, CRC, compressed and uncompressed sizes, name length, and extra field length
+		let mut common = Vec::new();
+		common.extend(0_u16.to_le_bytes());
+		common.extend(0_u16.to_le_bytes());
+		common.extend(TIME.to_le_bytes());
+		common.extend(DATE.to_le_bytes());
+		common.extend(crc.to_le_bytes());
+		common.extend(size.to_le_bytes());
+		common.extend(size.to_le_bytes());
+		common.extend((file_name.len() as u16).to_le_bytes());
+		common.extend(0_u16.to_le_bytes());
+
+		zip.extend(0x04034b50_u32.to_le_bytes());
+		zip.extend(VERSION.to_le_bytes());
+		zip.extend(&common);
+		zip.extend(file_name.as_bytes());
+		zip.extend(data);
+
+		central_directory.extend(0x02014b50_u32.to_le_bytes());
+		// Version made by and version needed to extract
+		central_directory.extend(VERSION.to_le_bytes());
+		central_directory.extend(VERSION.to_le_bytes());
+		central_directory.extend(&common);
+		// Comment length, disk number, internal attributes, and external attributes
+		central_directory.extend(0_u16.to_le_bytes());
+		central_directory.extend(0_u16.to_le_bytes());
+		central_directory.extend(0_u16.to_le_bytes());
+		central_directory.extend(0_u32.to_le_bytes());
+		central_directory.extend(offset.to_le_bytes());
+		central_directory.extend(file_name.as_bytes());
+	}
+
+	let central_directory_offset = zip.len() as u32;
+	zip.extend(&central_directory);
+
+	// End of central directory record
+	zip.extend(0x06054b50_u32.to_le_bytes());
+	zip.extend(0_u16.to_le_bytes());
+	zip.extend(0_u16.to_le_bytes());
+	zip.extend((files.len() as u16).to_le_bytes());
+	zip.extend((files.len() as u16).to_le_bytes());
+	zip.extend((central_directory.len() as u32).to_le_bytes());
+	zip.extend(central_directory_offset.to_le_bytes());
+	zip.extend(0_u16.to_le_bytes());
+
+	zip
+}
+
+/// The CRC-32 checksum (as used by ZIP archives and PNG chunks) of the data.
+pub(super) fn crc32(data: &[u8]) -> u32 {
+	let mut crc = !0_u32;
+	for &byte in data {
+		crc ^= byte as u32;
+		for _ in 0..8 {
+			crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+		}
+	}
+	!crc
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Stand-ins for the PNG image of each size, which are only copied into the files.
+	fn pngs() -> Vec<(u32, Vec<u8>)> {
+		ICON_SIZES.into_iter().map(|size| (size, format!("png {size}").into_bytes())).collect()
+	}
+
+	fn png<'a>(pngs: &'a [(u32, Vec<u8>)]) -> impl Fn(u32) -> Option<&'a [u8]> {
+		move |size| pngs.iter().find(|(icon_size, _)| *icon_size == size).map(|(_, png)| png.as_slice())
+	}
+
+	fn u16_le(bytes: &[u8], at: usize) -> u16 {
+		u16::from_le_bytes(bytes[at..at + 2].try_into().unwrap())
+	}
+
+	fn u32_le(bytes: &[u8], at: usize) -> u32 {
+		u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+	}
+
+	#[test]
+	fn ico_directory_points_to_each_image() {
+		let pngs = pngs();
+		let ico = encode_ico(&ICO_SIZES, png(&pngs));
+		assert_eq!(u16_le(&ico, 2), 1);
+		assert_eq!(u16_le(&ico, 4) as usize, ICO_SIZES.len());
+
+		for (index, size) in ICO_SIZES.into_iter().enumerate() {
+			let entry = &ico[6 + 16 * index..6 + 16 * (index + 1)];
+			assert_eq!(entry[0], if size == 256 { 0 } else { size as u8 });
+			assert_eq!(u16_le(entry, 6), 32);
+
+			let (length, offset) = (u32_le(entry, 8) as usize, u32_le(entry, 12) as usize);
+			assert_eq!(&ico[offset..offset + length], png(&pngs)(size).unwrap());
+		}
+	}
+
+	#[test]
+	fn icns_elements_hold_each_image_by_type() {
+		let pngs = pngs();
+		let icns = encode_icns(png(&pngs));
+		assert_eq!(&icns[..4], b"icns");
+		assert_eq!(u32::from_be_bytes(icns[4..8].try_into().unwrap()) as usize, icns.len());
+
+		let mut elements = Vec::new();
+		let mut at = 8;
+		while at < icns.len() {
+			let length = u32::from_be_bytes(icns[at + 4..at + 8].try_into().unwrap()) as usize;
+			elements.push((&icns[at..at + 4], &icns[at + 8..at + length]));
+			at += length;
+		}
+		assert_eq!(at, icns.len());
+
+		let expected = ICNS_TYPES.into_iter().map(|(element_type, size)| (&element_type[..], png(&pngs)(size).unwrap())).collect::<Vec<_>>();
+		assert_eq!(elements, expected);
+	}
+
+	#[test]
+	fn missing_sizes_are_left_out() {
+		let pngs = vec![(16, b"small".to_vec())];
+		let ico = encode_ico(&FAVICON_ICO_SIZES, png(&pngs));
+		assert_eq!(u16_le(&ico, 4), 1);
+		assert_eq!(&ico[6 + 16..], b"small");
+
+		let icns = encode_icns(png(&pngs));
+		assert_eq!(&icns[8..12], b"icp4");
+		assert_eq!(icns.len(), 8 + 8 + 5);
+	}
+
+	#[test]
+	fn zip_lists_every_file() {
+		let files = vec![("a.txt".to_string(), b"first".to_vec()), ("b/c.txt".to_string(), b"second".to_vec())];
+		let zip = encode_zip(&files);
+
+		let end = zip.len() - 22;
+		assert_eq!(u32_le(&zip, end), 0x06054b50);
+		assert_eq!(u16_le(&zip, end + 10), 2);
+
+		let mut entry = u32_le(&zip, end + 16) as usize;
+		for (file_name, data) in &files {
+			assert_eq!(u32_le(&zip, entry), 0x02014b50);
+			assert_eq!(u32_le(&zip, entry + 16), crc32(data));
+			let name_length = u16_le(&zip, entry + 28) as usize;
+			assert_eq!(&zip[entry + 46..entry + 46 + name_length], file_name.as_bytes());
+
+			let local = u32_le(&zip, entry + 42) as usize;
+			let data_start = local + 30 + name_length;
+			assert_eq!(&zip[data_start..data_start + data.len()], data.as_slice());
+			entry += 46 + name_length;
+		}
+	}
+
+	#[test]
+	fn crc32_matches_the_standard_check_value() {
+		assert_eq!(crc32(b"123456789"), 0xCBF43926);
+	}
+}
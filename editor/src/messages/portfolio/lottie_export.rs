@@ -0,0 +1,247 @@
+//! Encoding of the "Lottie" animation format, the JSON description of animated vector layers which is played by the Lottie libraries for the web and mobile platforms.
+
+use graphene_core::renderer::LottieShape;
+use graphene_core::vector::style::{LineCap, LineJoin};
+use graphene_core::vector::PointId;
+
+use bezier_rs::Subpath;
+use glam::{DAffine2, DVec2};
+use serde_json::{json, Value};
+
+/// The version of the format that's written, which is supported by the players of the last several years.
+const LOTTIE_VERSION: &str = "5.7.4";
+
+/// Encodes the vector shapes drawn at each frame as a Lottie animation.
+/// Shapes are matched up between frames by the order they're drawn in, so each becomes a layer with its transform, paths, colors, and opacity keyframed wherever they change.
+pub fn encode_lottie(name: &str, frames: &[Vec<LottieShape>], frame_rate: f64, size: DVec2) -> Result<String, String> {
+	if frames.is_empty() {
+		return Err("The animation has no frames".to_string());
+	}
+
+	// Lottie draws its first layer on top, which is the reverse of the order the shapes are drawn in
+	let layer_count = frames.iter().map(Vec::len).max().unwrap_or_default();
+	let layers = (0..layer_count)
+		.rev()
+		.enumerate()
+		.map(|(index, shape_index)| encode_layer(index + 1, frames.iter().map(|shapes| shapes.get(shape_index)).collect(), frames.len()))
+		.collect::<Vec<_>>();
+
+	let animation = json!({
+		"v": LOTTIE_VERSION,
+		"nm": name,
+		"fr": frame_rate,
+		"ip": 0,
+		"op": frames.len(),
+		"w": size.x.round(),
+		"h": size.y.round(),
+		"ddd": 0,
+		"assets": [],
+		"layers": layers,
+	});
+	serde_json::to_string(&animation).map_err(|error| error.to_string())
+}
+
+/// A shape layer drawing the shape at the same index of each frame, which is hidden in the frames drawing fewer shapes.
+fn encode_layer(index: usize, shapes: Vec<Option<&LottieShape>>, frame_count: usize) -> Value {
+	// The frames without the shape keep its previous (or otherwise first) appearance, but fully transparent
+	let Some(mut previous) = shapes.iter().flatten().next().copied() else { return Value::Null };
+	let frames = shapes
+		.iter()
+		.map(|shape| match shape {
+			Some(shape) => {
+				previous = *shape;
+				(*shape, 1.)
+			}
+			None => (previous, 0.),
+		})
+		.collect::<Vec<_>>();
+
+	// A layer can't be skewed, so skewed shapes have their transforms applied to their paths instead
+	let bake_transform = frames.iter().any(|(shape, _)| !shape.has_decomposable_transform());
+	let layer_transform = |shape: &LottieShape| if bake_transform { DAffine2::IDENTITY } else { shape.transform };
+	let path_transform = |shape: &LottieShape| if bake_transform { shape.transform } else { DAffine2::IDENTITY };
+
+	let decomposed = frames.iter().map(|(shape, _)| layer_transform(shape).to_scale_angle_translation()).collect::<Vec<_>>();
+	let position = decomposed.iter().map(|(_, _, translation)| json!([translation.x, translation.y])).collect();
+	let scale = decomposed.iter().map(|(scale, _, _)| json!([scale.x * 100., scale.y * 100.])).collect();
+	// Angles are unwrapped so turning past a half turn doesn't interpolate the long way around
+	let mut angle = 0.;
+	let rotation = decomposed
+		.iter()
+		.enumerate()
+		.map(|(frame, (_, frame_angle, _))| {
+			let difference = if frame == 0 { *frame_angle } else { frame_angle - angle };
+			angle += (difference + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU) - std::f64::consts::PI;
+			json!(angle.to_degrees())
+		})
+		.collect();
+	let opacity = frames.iter().map(|(shape, visible)| json!(shape.opacity * visible * 100.)).collect();
+
+	let subpath_count = frames.iter().map(|(shape, _)| shape.subpaths.len()).max().unwrap_or_default();
+	let mut items = (0..subpath_count)
+		.map(|subpath_index| {
+			let paths = frames
+				.iter()
+				.map(|(shape, _)| encode_path(shape.subpaths.get(subpath_index), path_transform(shape)))
+				.collect::<Vec<_>>();
+			// Paths can only morph into paths with as many points, so the others switch over at once
+			let hold = |a: &Value, b: &Value| a["v"].as_array().map(Vec::len) != b["v"].as_array().map(Vec::len);
+			json!({ "ty": "sh", "nm": format!("Path {}", subpath_index + 1), "ks": animated_with_holds(paths, hold) })
+		})
+		.collect::<Vec<_>>();
+
+	// Styles apply to the paths before them, and the first ones are drawn on top, so the stroke is put before the fill
+	if let Some(stroke) = frames.iter().find_map(|(shape, _)| shape.stroke.as_ref()) {
+		let color = frames.iter().map(|(shape, _)| shape.stroke.as_ref().and_then(|stroke| stroke.color)).collect::<Vec<_>>();
+		let weight = frames
+			.iter()
+			.map(|(shape, _)| {
+				let scale = if bake_transform { shape.transform.matrix2.determinant().abs().sqrt() } else { 1. };
+				json!(shape.stroke.as_ref().map_or(0., |stroke| stroke.weight) * scale)
+			})
+			.collect();
+		let line_cap = match stroke.line_cap {
+			LineCap::Butt => 1,
+			LineCap::Round => 2,
+			LineCap::Square => 3,
+		};
+		let line_join = match stroke.line_join {
+			LineJoin::Miter => 1,
+			LineJoin::Round => 2,
+			LineJoin::Bevel => 3,
+		};
+
+		let mut stroke_item = json!({
+			"ty": "st",
+			"nm": "Stroke",
+			"c": animated(color.iter().map(|color| encode_color(*color)).collect()),
+			"o": animated(color.iter().map(|color| json!(color.map_or(0., |color| color.a() as f64 * 100.))).collect()),
+			"w": animated(weight),
+			"lc": line_cap,
+			"lj": line_join,
+			"ml": stroke.line_join_miter_limit,
+		});
+		if !stroke.dash_lengths.is_empty() {
+			// Dashes alternate between drawn and gap lengths, and an odd number of them is repeated to make the pattern even
+			let lengths = if stroke.dash_lengths.len() % 2 == 0 {
+				stroke.dash_lengths.clone()
+			} else {
+				stroke.dash_lengths.repeat(2)
+			};
+			let mut dashes = lengths
+				.iter()
+				.enumerate()
+				.map(|(index, length)| {
+					let (kind, name) = if index % 2 == 0 { ("d", "dash") } else { ("g", "gap") };
+					json!({ "n": kind, "nm": name, "v": { "a": 0, "k": length } })
+				})
+				.collect::<Vec<_>>();
+			dashes.push(json!({ "n": "o", "nm": "offset", "v": { "a": 0, "k": stroke.dash_offset } }));
+			stroke_item["d"] = Value::Array(dashes);
+		}
+		items.push(stroke_item);
+	}
+	if frames.iter().any(|(shape, _)| shape.fill.is_some()) {
+		let color = frames.iter().map(|(shape, _)| shape.fill).collect::<Vec<_>>();
+		items.push(json!({
+			"ty": "fl",
+			"nm": "Fill",
+			"c": animated(color.iter().map(|color| encode_color(*color)).collect()),
+			"o": animated(color.iter().map(|color| json!(color.map_or(0., |color| color.a() as f64 * 100.))).collect()),
+			"r": 1,
+		}));
+	}
+	items.push(json!({
+		"ty": "tr",
+		"p": { "a": 0, "k": [0, 0] },
+		"a": { "a": 0, "k": [0, 0] },
+		"s": { "a": 0, "k": [100, 100] },
+		"r": { "a": 0, "k": 0 },
+		"o": { "a": 0, "k": 100 },
+	}));
+
+	json!({
+		"ddd": 0,
+		"ind": index,
+		"ty": 4,
+		"nm": format!("Shape {index}"),
+		"sr": 1,
+		"ks": {
+			"o": animated(opacity),
+			"r": animated(rotation),
+			"p": animated(position),
+			"a": { "a": 0, "k": [0, 0] },
+			"s": animated(scale),
+		},
+		"ao": 0,
+		"shapes": [{ "ty": "gr", "nm": "Shape", "it": items }],
+		"ip": 0,
+		"op": frame_count,
+		"st": 0,
+		"bm": 0,
+	})
+}
+
+/// The points of a subpath, with the handles of each point relative to it as Lottie expects.
+fn encode_path(subpath: Option<&Subpath<PointId>>, transform: DAffine2) -> Value {
+	let Some(subpath) = subpath else {
+		return json!({ "i": [], "o": [], "v": [], "c": false });
+	};
+
+	let (mut in_tangents, mut out_tangents, mut vertices) = (Vec::new(), Vec::new(), Vec::new());
+	for group in subpath.manipulator_groups() {
+		let anchor = transform.transform_point2(group.anchor);
+		let tangent = |handle: Option<DVec2>| {
+			let tangent = handle.map_or(DVec2::ZERO, |handle| transform.transform_point2(handle) - anchor);
+			json!([tangent.x, tangent.y])
+		};
+		in_tangents.push(tangent(group.in_handle));
+		out_tangents.push(tangent(group.out_handle));
+		vertices.push(json!([anchor.x, anchor.y]));
+	}
+	json!({ "i": in_tangents, "o": out_tangents, "v": vertices, "c": subpath.closed() })
+}
+
+/// Lottie colors are RGB from 0 to 1, with their alpha given separately as the opacity of the fill or stroke.
+fn encode_color(color: Option<graphene_core::Color>) -> Value {
+	let color = color.unwrap_or(graphene_core::Color::BLACK);
+	json!([color.r(), color.g(), color.b(), 1])
+}
+
+/// A property with a value at each frame, written as a static value if it never changes.
+fn animated(values: Vec<Value>) -> Value {
+	animated_with_holds(values, |_, _| false)
+}
+
+/// A property with a value at each frame, keyframed with linear easing at each frame where it starts or stops changing.
+/// The value is held until the next keyframe, rather than interpolated, between the values which `hold` is true for.
+fn animated_with_holds(values: Vec<Value>, hold: impl Fn(&Value, &Value) -> bool) -> Value {
+	if values.iter().all(|value| *value == values[0]) {
+		return json!({ "a": 0, "k": values[0] });
+	}
+
+	// Frames with the same value as the frames on both sides of them are left out, since they're already interpolated to that value
+	let keyframe_indices = (0..values.len())
+		.filter(|&frame| frame == 0 || frame == values.len() - 1 || values[frame - 1] != values[frame] || values[frame + 1] != values[frame])
+		.collect::<Vec<_>>();
+
+	let keyframes = keyframe_indices
+		.iter()
+		.enumerate()
+		.map(|(index, &frame)| {
+			let value = &values[frame];
+			let start = if value.is_array() { value.clone() } else { json!([value]) };
+			let mut keyframe = json!({
+				"t": frame,
+				"s": start,
+				"o": { "x": 0, "y": 0 },
+				"i": { "x": 1, "y": 1 },
+			});
+			if keyframe_indices.get(index + 1).is_some_and(|&next| hold(value, &values[next])) {
+				keyframe["h"] = json!(1);
+			}
+			keyframe
+		})
+		.collect::<Vec<_>>();
+	json!({ "a": 1, "k": keyframes })
+}
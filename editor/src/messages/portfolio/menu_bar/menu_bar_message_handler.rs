@@ -1,19 +1,26 @@
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
+use crate::messages::portfolio::document::utility_types::palette_file::PaletteFormat;
 use crate::messages::prelude::*;
 
 pub struct MenuBarMessageData {
 	pub has_active_document: bool,
 	pub rulers_visible: bool,
+	pub artboard_relative_coordinates: bool,
 	pub node_graph_open: bool,
+	pub node_profiling: bool,
+	pub node_thumbnails: bool,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct MenuBarMessageHandler {
 	has_active_document: bool,
 	rulers_visible: bool,
+	artboard_relative_coordinates: bool,
 	node_graph_open: bool,
+	node_profiling: bool,
+	node_thumbnails: bool,
 }
 
 impl MessageHandler<MenuBarMessage, MenuBarMessageData> for MenuBarMessageHandler {
@@ -21,11 +28,17 @@ impl MessageHandler<MenuBarMessage, MenuBarMessageData> for MenuBarMessageHandle
 		let MenuBarMessageData {
 			has_active_document,
 			rulers_visible,
+			artboard_relative_coordinates,
 			node_graph_open,
+			node_profiling,
+			node_thumbnails,
 		} = data;
 		self.has_active_document = has_active_document;
 		self.rulers_visible = rulers_visible;
+		self.artboard_relative_coordinates = artboard_relative_coordinates;
 		self.node_graph_open = node_graph_open;
+		self.node_profiling = node_profiling;
+		self.node_thumbnails = node_thumbnails;
 
 		match message {
 			MenuBarMessage::SendLayout => self.send_layout(responses, LayoutTarget::MenuBar),
@@ -91,13 +104,21 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
-					vec![MenuBarEntry {
-						label: "Save".into(),
-						shortcut: action_keys!(DocumentMessageDiscriminant::SaveDocument),
-						action: MenuBarEntry::create_action(|_| DocumentMessage::SaveDocument.into()),
-						disabled: no_active_document,
-						..MenuBarEntry::default()
-					}],
+					vec![
+						MenuBarEntry {
+							label: "Save".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::SaveDocument),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SaveDocument.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Save Node Graph as Text".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SaveNodeGraph.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+					],
 					vec![
 						MenuBarEntry {
 							label: "Import…".into(),
@@ -113,6 +134,74 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Swatches".into(),
+							action: MenuBarEntry::no_action(),
+							children: MenuBarEntryChildren(vec![
+								vec![
+									MenuBarEntry {
+										label: "Import into Document…".into(),
+										action: MenuBarEntry::create_action(|_| FrontendMessage::TriggerImportSwatches { global: false }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Import into Global Swatches…".into(),
+										action: MenuBarEntry::create_action(|_| FrontendMessage::TriggerImportSwatches { global: true }.into()),
+										..MenuBarEntry::default()
+									},
+								],
+								vec![
+									MenuBarEntry {
+										label: "Export Document Swatches (.ase)".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::ExportSwatches { format: PaletteFormat::Ase }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Export Document Swatches (.gpl)".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::ExportSwatches { format: PaletteFormat::Gpl }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+								],
+								vec![
+									MenuBarEntry {
+										label: "Export Global Swatches (.ase)".into(),
+										action: MenuBarEntry::create_action(|_| PreferencesMessage::ExportSwatches { format: PaletteFormat::Ase }.into()),
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Export Global Swatches (.gpl)".into(),
+										action: MenuBarEntry::create_action(|_| PreferencesMessage::ExportSwatches { format: PaletteFormat::Gpl }.into()),
+										..MenuBarEntry::default()
+									},
+								],
+							]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Node Presets".into(),
+							action: MenuBarEntry::no_action(),
+							children: MenuBarEntryChildren(vec![vec![
+								MenuBarEntry {
+									label: "Import Node Presets…".into(),
+									action: MenuBarEntry::create_action(|_| FrontendMessage::TriggerImportNodePresets.into()),
+									..MenuBarEntry::default()
+								},
+								MenuBarEntry {
+									label: "Export Node Presets".into(),
+									action: MenuBarEntry::create_action(|_| PreferencesMessage::ExportNodePresets.into()),
+									..MenuBarEntry::default()
+								},
+							]]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Load Plugin…".into(),
+							action: MenuBarEntry::create_action(|_| FrontendMessage::TriggerLoadPlugin.into()),
+							..MenuBarEntry::default()
+						},
 					],
 					vec![MenuBarEntry {
 						label: "Preferences…".into(),
@@ -168,6 +257,13 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
+					vec![MenuBarEntry {
+						label: "Find and Replace…".into(),
+						shortcut: action_keys!(SearchMessageDiscriminant::OpenDialog),
+						action: MenuBarEntry::create_action(|_| SearchMessage::OpenDialog.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
 				]),
 			),
 			MenuBarEntry::new_root(
@@ -189,6 +285,20 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Select Parent".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::SelectParentLayer),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SelectParentLayer.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Select Children".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::SelectChildLayers),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SelectChildLayers.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
 					],
 					vec![MenuBarEntry {
 						label: "Delete Selected".into(),
@@ -257,17 +367,31 @@ impl LayoutHolder for MenuBarMessageHandler {
 						]]),
 						..MenuBarEntry::default()
 					}],
+					vec![MenuBarEntry {
+						label: "Auto-Align Images".into(),
+						action: MenuBarEntry::create_action(|_| DocumentMessage::AutoAlignSelectedImages.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
 				]),
 			),
 			MenuBarEntry::new_root(
 				"Document".into(),
 				no_active_document,
-				MenuBarEntryChildren(vec![vec![MenuBarEntry {
-					label: "Clear Artboards".into(),
-					action: MenuBarEntry::create_action(|_| DocumentMessage::ClearArtboards.into()),
-					disabled: no_active_document,
-					..MenuBarEntry::default()
-				}]]),
+				MenuBarEntryChildren(vec![
+					vec![MenuBarEntry {
+						label: "Clear Artboards".into(),
+						action: MenuBarEntry::create_action(|_| DocumentMessage::ClearArtboards.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
+					vec![MenuBarEntry {
+						label: "Add Variant".into(),
+						action: MenuBarEntry::create_action(|_| DocumentMessage::AddVariant.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
+				]),
 			),
 			MenuBarEntry::new_root(
 				"View".into(),
@@ -337,12 +461,55 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
+					vec![
+						MenuBarEntry {
+							label: "Rulers".into(),
+							icon: Some(if self.rulers_visible { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							shortcut: action_keys!(PortfolioMessageDiscriminant::ToggleRulers),
+							action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleRulers.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Artboard-Relative Coordinates".into(),
+							icon: Some(if self.artboard_relative_coordinates { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleArtboardRelativeCoordinates.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Node Output Thumbnails".into(),
+							icon: Some(if self.node_thumbnails { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleNodeThumbnails.into()),
+							..MenuBarEntry::default()
+						},
+					],
 					vec![MenuBarEntry {
-						label: "Rulers".into(),
-						icon: Some(if self.rulers_visible { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
-						shortcut: action_keys!(PortfolioMessageDiscriminant::ToggleRulers),
-						action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleRulers.into()),
+						label: "Guides".into(),
+						action: MenuBarEntry::no_action(),
 						disabled: no_active_document,
+						children: MenuBarEntryChildren(vec![
+							vec![
+								MenuBarEntry {
+									label: "Add Guides from Selection".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::GuidesFromSelection.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								},
+								MenuBarEntry {
+									label: "Convert Selected Paths to Guides".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::ConvertSelectedPathsToGuides.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								},
+							],
+							vec![MenuBarEntry {
+								label: "Clear Guides".into(),
+								action: MenuBarEntry::create_action(|_| DocumentMessage::ClearGuides.into()),
+								disabled: no_active_document,
+								..MenuBarEntry::default()
+							}],
+						]),
 						..MenuBarEntry::default()
 					}],
 				]),
@@ -423,6 +590,12 @@ impl LayoutHolder for MenuBarMessageHandler {
 							action: MenuBarEntry::create_action(|_| DebugMessage::ToggleTraceLogs.into()),
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Debug: Profile Node Graph".into(),
+							icon: Some(if self.node_profiling { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+							action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleNodeProfiling.into()),
+							..MenuBarEntry::default()
+						},
 						MenuBarEntry {
 							label: "Debug: Print Document".into(),
 							shortcut: action_keys!(DocumentMessageDiscriminant::DebugPrintDocument),
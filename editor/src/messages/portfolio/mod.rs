@@ -1,8 +1,14 @@
 mod portfolio_message;
 mod portfolio_message_handler;
 
+pub mod animation_export;
 pub mod document;
+pub mod icc_profile;
+pub mod icon_set;
+pub mod lottie_export;
 pub mod menu_bar;
+pub mod poster_export;
+pub mod tiff_export;
 pub mod utility_types;
 
 #[doc(inline)]
@@ -1,8 +1,14 @@
 use super::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::portfolio::animation_export::{AnimationExportOptions, AnimationFormat};
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
+use crate::messages::portfolio::icon_set::IconSetOptions;
+use crate::messages::portfolio::poster_export::PosterOptions;
 use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ColorSampleTarget;
 
+use glam::DVec2;
+use graphene_core::renderer::{ImageExportPolicy, OutputPalette, SvgOptimization};
 use graphene_core::text::Font;
 
 #[impl_message(Message, Portfolio)]
@@ -24,6 +30,25 @@ pub enum PortfolioMessage {
 	AutoSaveDocument {
 		document_id: DocumentId,
 	},
+	BundleAnimation {
+		name: String,
+		format: AnimationFormat,
+		frame_rate: f64,
+		frames: Vec<Vec<u8>>,
+	},
+	BundleIconSet {
+		name: String,
+		icons: Vec<(u32, Vec<u8>)>,
+	},
+	BundlePoster {
+		name: String,
+		pages: Vec<Vec<u8>>,
+	},
+	CacheOnionSkinFrame {
+		key: u64,
+		png: Vec<u8>,
+	},
+	CacheMemoryBudget,
 	CloseActiveDocumentWithConfirmation,
 	CloseAllDocuments,
 	CloseAllDocumentsWithConfirmation,
@@ -33,6 +58,10 @@ pub enum PortfolioMessage {
 	CloseDocumentWithConfirmation {
 		document_id: DocumentId,
 	},
+	ColorSampled {
+		key: u64,
+		rgba: Vec<u8>,
+	},
 	Copy {
 		clipboard: Clipboard,
 	},
@@ -43,6 +72,13 @@ pub enum PortfolioMessage {
 		document_id: DocumentId,
 	},
 	DestroyAllDocuments,
+	EnableSafeMode,
+	EncodeTiff {
+		name: String,
+		width: u32,
+		height: u32,
+		pixels: Vec<u8>,
+	},
 	FontLoaded {
 		font_family: String,
 		font_style: String,
@@ -50,6 +86,10 @@ pub enum PortfolioMessage {
 		data: Vec<u8>,
 		is_default: bool,
 	},
+	LoadUserFont {
+		preview_url: String,
+		data: Vec<u8>,
+	},
 	ImaginateCheckServerStatus,
 	ImaginatePollServerStatus,
 	ImaginatePreferences,
@@ -62,6 +102,9 @@ pub enum PortfolioMessage {
 		font: Font,
 		is_default: bool,
 	},
+	LoadPlugin {
+		module: Vec<u8>,
+	},
 	NewDocumentWithName {
 		name: String,
 	},
@@ -83,6 +126,11 @@ pub enum PortfolioMessage {
 		parent: LayerNodeIdentifier,
 		insert_index: isize,
 	},
+	PasteClipboardContent {
+		mime_type: String,
+		data: Vec<u8>,
+		mouse: Option<(f64, f64)>,
+	},
 	PasteSerializedData {
 		data: String,
 	},
@@ -90,16 +138,31 @@ pub enum PortfolioMessage {
 	SelectDocument {
 		document_id: DocumentId,
 	},
+	SubmitColorSample {
+		/// The point in document space whose color is sampled.
+		position: DVec2,
+		target: ColorSampleTarget,
+	},
 	SubmitDocumentExport {
 		file_name: String,
 		file_type: FileType,
 		scale_factor: f64,
 		bounds: ExportBounds,
 		transparent_background: bool,
+		all_variants: bool,
+		svg_optimization: Option<SvgOptimization>,
+		image_export: Option<ImageExportPolicy>,
+		palette: Option<OutputPalette>,
+		icon_set: IconSetOptions,
+		poster: PosterOptions,
+		animation: AnimationExportOptions,
 	},
 	SubmitGraphRender {
 		document_id: DocumentId,
 	},
+	ToggleArtboardRelativeCoordinates,
+	ToggleNodeProfiling,
+	ToggleNodeThumbnails,
 	ToggleRulers,
 	UpdateDocumentWidgets,
 	UpdateOpenDocumentsList,
@@ -4,15 +4,22 @@ use crate::consts::DEFAULT_DOCUMENT_NAME;
 use crate::messages::dialog::simple_dialogs;
 use crate::messages::frontend::utility_types::FrontendDocumentDetails;
 use crate::messages::layout::utility_types::widget_prelude::*;
-use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, CopyBufferEntry, INTERNAL_CLIPBOARD_COUNT};
+use crate::messages::portfolio::animation_export;
+use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
+use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, ClipboardContent, CopyBufferEntry, ExternalClipboardContent, INTERNAL_CLIPBOARD_COUNT};
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::portfolio::document::DocumentMessageData;
+use crate::messages::portfolio::icon_set;
 use crate::messages::prelude::*;
-use crate::messages::tool::utility_types::{HintData, HintGroup};
+use crate::messages::tool::utility_types::{ColorSampleTarget, HintData, HintGroup};
 use crate::node_graph_executor::{ExportConfig, NodeGraphExecutor};
 
 use graph_craft::document::NodeId;
+use graphene_core::raster::color::Color;
 use graphene_core::text::Font;
 
+use glam::DAffine2;
+
 use std::sync::Arc;
 
 pub struct PortfolioMessageData<'a> {
@@ -29,6 +36,8 @@ pub struct PortfolioMessageHandler {
 	copy_buffer: [Vec<CopyBufferEntry>; INTERNAL_CLIPBOARD_COUNT as usize],
 	pub persistent_data: PersistentData,
 	pub executor: NodeGraphExecutor,
+	node_profiling: bool,
+	node_thumbnails: bool,
 }
 
 impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMessageHandler {
@@ -40,11 +49,13 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			PortfolioMessage::MenuBar(message) => {
 				let mut has_active_document = false;
 				let mut rulers_visible = false;
+				let mut artboard_relative_coordinates = false;
 				let mut node_graph_open = false;
 
 				if let Some(document) = self.active_document_id.and_then(|document_id| self.documents.get_mut(&document_id)) {
 					has_active_document = true;
 					rulers_visible = document.rulers_visible;
+					artboard_relative_coordinates = document.artboard_relative_coordinates;
 					node_graph_open = document.is_graph_overlay_open();
 				}
 				self.menu_bar_message_handler.process_message(
@@ -53,7 +64,10 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					MenuBarMessageData {
 						has_active_document,
 						rulers_visible,
+						artboard_relative_coordinates,
 						node_graph_open,
+						node_profiling: self.node_profiling,
+						node_thumbnails: self.node_thumbnails,
 					},
 				);
 			}
@@ -65,6 +79,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 							ipp,
 							persistent_data: &self.persistent_data,
 							executor: &mut self.executor,
+							preferences,
 						};
 						document.process_message(message, responses, document_inputs)
 					}
@@ -79,6 +94,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 						ipp,
 						persistent_data: &self.persistent_data,
 						executor: &mut self.executor,
+						preferences,
 					};
 					document.process_message(message, responses, document_inputs)
 				}
@@ -111,6 +127,60 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					},
 				})
 			}
+			PortfolioMessage::BundleAnimation { name, format, frame_rate, frames } => {
+				responses.add(DocumentMessage::UpdateExportProgress { progress: None });
+
+				match animation_export::encode_animation(&name, format, frame_rate, &frames) {
+					Ok(data) => responses.add(FrontendMessage::TriggerDownloadBinaryFile {
+						name: format!("{name}.{}", format.extension()),
+						mime: format.to_mime().to_string(),
+						data,
+					}),
+					Err(description) => responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to export animation".to_string(),
+						description,
+					}),
+				}
+			}
+			PortfolioMessage::BundleIconSet { name, icons } => {
+				let data = icon_set::bundle_icon_set(&name, &icons);
+				responses.add(FrontendMessage::TriggerDownloadBinaryFile {
+					name: format!("{name}.zip"),
+					mime: "application/zip".to_string(),
+					data,
+				});
+			}
+			PortfolioMessage::BundlePoster { name, pages } => {
+				// The layout is kept by the executor from when the export was rendered, and is gone if another export has replaced it since
+				let Some(layout) = self.executor.take_poster_layout() else { return };
+				responses.add(FrontendMessage::TriggerDownloadBinaryFile {
+					name: format!("{name}.pdf"),
+					mime: "application/pdf".to_string(),
+					data: layout.encode_pdf(&pages),
+				});
+			}
+			PortfolioMessage::CacheOnionSkinFrame { key, png } => {
+				if self.executor.cache_onion_skin_frame(key, png) {
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+				}
+			}
+			PortfolioMessage::CacheMemoryBudget => self.executor.set_memory_budget(preferences.cache_memory_budget),
+			PortfolioMessage::ColorSampled { key, rgba } => {
+				let Some(target) = self.executor.take_color_sample(key) else { return };
+				// Pixels which nothing was drawn on, or which failed to rasterize, leave the working colors as they were
+				let [red, green, blue, alpha] = rgba[..] else { return };
+				if alpha == 0 {
+					return;
+				}
+
+				let Some(color) = Color::from_rgbaf32(red as f32 / 255., green as f32 / 255., blue as f32 / 255., 1.) else {
+					return;
+				};
+				match target {
+					ColorSampleTarget::Primary => responses.add(ToolMessage::SelectPrimaryColor { color }),
+					ColorSampleTarget::Secondary => responses.add(ToolMessage::SelectSecondaryColor { color }),
+				}
+			}
 			PortfolioMessage::CloseActiveDocumentWithConfirmation => {
 				if let Some(document_id) = self.active_document_id {
 					responses.add(PortfolioMessage::CloseDocumentWithConfirmation { document_id });
@@ -230,10 +300,14 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				};
 
 				if clipboard == Clipboard::Device {
-					let mut buffer = Vec::new();
-					copy_val(&mut buffer);
+					let mut entries = Vec::new();
+					copy_val(&mut entries);
+					let content = ClipboardContent {
+						entries,
+						bounds: active_document.metadata().selected_bounds_document_space(false, &active_document.selected_nodes),
+					};
 					let mut copy_text = String::from("graphite/layer: ");
-					copy_text += &serde_json::to_string(&buffer).expect("Could not serialize paste");
+					copy_text += &serde_json::to_string(&content).expect("Could not serialize paste");
 
 					responses.add(FrontendMessage::TriggerTextCopy { copy_text });
 				} else {
@@ -272,6 +346,19 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				self.active_document_id = None;
 				responses.add(MenuBarMessage::SendLayout);
 			}
+			PortfolioMessage::EnableSafeMode => {
+				self.executor.enable_safe_mode();
+				responses.add(DialogMessage::RequestSafeModeDialog);
+			}
+			PortfolioMessage::EncodeTiff { name, width, height, pixels } => {
+				// Like a poster's layout, the export is kept by the executor from when it was rendered
+				let Some(tiff_export) = self.executor.take_tiff_export() else { return };
+				responses.add(FrontendMessage::TriggerDownloadBinaryFile {
+					name: format!("{name}.tiff"),
+					mime: "image/tiff".to_string(),
+					data: tiff_export.encode(width, height, &pixels),
+				});
+			}
 			PortfolioMessage::FontLoaded {
 				font_family,
 				font_style,
@@ -288,6 +375,24 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					responses.add(NodeGraphMessage::RunDocumentGraph);
 				}
 			}
+			PortfolioMessage::LoadUserFont { preview_url, data } => {
+				let Some(_) = self.persistent_data.font_cache.insert_user_font(preview_url, data) else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Failed to load font".to_string(),
+						description: "The file isn't an OpenType or TrueType font".to_string(),
+					});
+					return;
+				};
+				self.executor.update_font_cache(self.persistent_data.font_cache.clone());
+				let font_cache = &self.persistent_data.font_cache;
+				let user_fonts = font_cache.user_fonts();
+				let preview_urls = user_fonts.iter().map(|font| font_cache.get_preview_url(font).cloned().unwrap_or_default()).collect();
+				responses.add(FrontendMessage::UpdateUserFonts { user_fonts, preview_urls });
+
+				if self.active_document_mut().is_some() {
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+				}
+			}
 			PortfolioMessage::ImaginateCheckServerStatus => {
 				let server_status = self.persistent_data.imaginate.server_status().clone();
 				self.persistent_data.imaginate.poll_server_check();
@@ -333,6 +438,21 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					responses.add_front(FrontendMessage::TriggerFontLoad { font, is_default });
 				}
 			}
+			PortfolioMessage::LoadPlugin { module } => {
+				let loaded = graphene_std::plugin::embedded_manifest(&module).and_then(|manifest| graphene_std::plugin::load_plugin(manifest, &module));
+				match loaded {
+					Ok(()) => {
+						if self.active_document().is_some() {
+							responses.add(NodeGraphMessage::SendNodeTypes);
+							responses.add(NodeGraphMessage::RunDocumentGraph);
+						}
+					}
+					Err(error) => responses.add(DialogMessage::DisplayDialogError {
+						title: "Couldn't load plugin".into(),
+						description: error.to_string(),
+					}),
+				}
+			}
 			PortfolioMessage::NewDocumentWithName { name } => {
 				let new_document = DocumentMessageHandler::with_name(name, ipp, responses);
 				let document_id = DocumentId(generate_uuid());
@@ -378,6 +498,9 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				let document = DocumentMessageHandler::with_name_and_content(document_name, document_serialized_content);
 				match document {
 					Ok(mut document) => {
+						if self.executor.safe_mode() {
+							document.discard_caches();
+						}
 						document.set_auto_save_state(document_is_auto_saved);
 						document.set_save_state(document_is_saved);
 						self.load_document(document, document_id, responses);
@@ -411,24 +534,54 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					paste(entry, responses)
 				}
 			}
+			PortfolioMessage::PasteClipboardContent { mime_type, data, mouse } => match ExternalClipboardContent::from_mime_type(&mime_type, &data) {
+				Some(ExternalClipboardContent::Layers(data)) => responses.add(PortfolioMessage::PasteSerializedData { data }),
+				Some(ExternalClipboardContent::Nodes(serialized_nodes)) => responses.add(NodeGraphMessage::PasteNodes { serialized_nodes }),
+				Some(ExternalClipboardContent::Svg(svg)) => responses.add(DocumentMessage::PasteSvg { svg, mouse }),
+				Some(ExternalClipboardContent::Image(image)) => responses.add(DocumentMessage::PasteImage { image, mouse }),
+				None => {}
+			},
 			PortfolioMessage::PasteSerializedData { data } => {
 				if let Some(document) = self.active_document() {
-					if let Ok(data) = serde_json::from_str::<Vec<CopyBufferEntry>>(&data) {
+					if let Some(content) = ClipboardContent::deserialize(&data) {
 						let parent = document.new_layer_parent(false);
 
+						// Center the pasted layers on the cursor, or on the viewport if the cursor is outside of it
+						let target = if ipp.viewport_bounds.in_bounds(ipp.mouse.position) {
+							ipp.mouse.position
+						} else {
+							ipp.viewport_bounds.center()
+						};
+						let offset = content.bounds.map(|[min, max]| target - document.metadata().document_to_viewport.transform_point2((min + max) / 2.));
+
 						responses.add(DocumentMessage::DeselectAllLayers);
 						responses.add(DocumentMessage::StartTransaction);
 
-						for entry in data.into_iter().rev() {
+						for entry in content.entries.into_iter().rev() {
 							document.load_layer_resources(responses);
+							// IDs are remapped so content copied from another document, or pasted more than once, never collides with existing nodes
 							let new_ids: HashMap<_, _> = entry.nodes.iter().map(|(&id, _)| (id, NodeId(generate_uuid()))).collect();
+							// The layer node of each entry is always given the ID of 0 when copying
+							let layer = new_ids.get(&NodeId(0)).copied().map(LayerNodeIdentifier::new_unchecked);
+
 							responses.add(GraphOperationMessage::AddNodesAsChild {
 								nodes: entry.nodes,
 								new_ids,
 								parent,
 								insert_index: -1,
 							});
+
+							if let (Some(layer), Some(offset)) = (layer, offset) {
+								responses.add(GraphOperationMessage::TransformChange {
+									layer,
+									transform: DAffine2::from_translation(offset),
+									transform_in: TransformIn::Viewport,
+									skip_rerender: true,
+								});
+							}
 						}
+
+						responses.add(NodeGraphMessage::RunDocumentGraph);
 					}
 				}
 			}
@@ -457,6 +610,15 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				// Set the new active document ID
 				self.active_document_id = Some(document_id);
 
+				// Reuse the graph compiled when the document was saved, or last shown, unless it has changed since
+				if let Some(compiled_graph) = self.documents.get(&document_id).and_then(|document| document.compiled_graph.clone()) {
+					self.executor.load_compiled_graph(compiled_graph);
+				}
+				// Likewise reuse the outputs of its cache nodes, for each cache whose upstream nodes haven't changed since
+				if let Some(evaluation_cache) = self.documents.get(&document_id).and_then(|document| document.evaluation_cache.clone()) {
+					self.executor.load_evaluation_cache(evaluation_cache);
+				}
+
 				responses.add(MenuBarMessage::SendLayout);
 				responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 				responses.add(FrontendMessage::UpdateActiveDocument { document_id });
@@ -464,16 +626,35 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				responses.add(BroadcastEvent::ToolAbort);
 				responses.add(BroadcastEvent::SelectionChanged);
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+				responses.add(DocumentMessage::UpdateParametersPanel);
 				responses.add(NavigationMessage::CanvasPan { delta: (0., 0.).into() });
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 				responses.add(DocumentMessage::GraphViewOverlay { open: node_graph_open });
 			}
+			PortfolioMessage::SubmitColorSample { position, target } => {
+				let Some(document) = self.active_document_id.and_then(|id| self.documents.get(&id)) else { return };
+				let result = self.executor.submit_color_sample(document, position, target);
+
+				if let Err(description) = result {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Unable to sample color".to_string(),
+						description,
+					});
+				}
+			}
 			PortfolioMessage::SubmitDocumentExport {
 				file_name,
 				file_type,
 				scale_factor,
 				bounds,
 				transparent_background,
+				all_variants,
+				svg_optimization,
+				image_export,
+				palette,
+				icon_set,
+				poster,
+				animation,
 			} => {
 				let document = self.active_document_id.and_then(|id| self.documents.get_mut(&id)).expect("Tried to render no existent Document");
 				let export_config = ExportConfig {
@@ -482,6 +663,13 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					scale_factor,
 					bounds,
 					transparent_background,
+					all_variants,
+					svg_optimization,
+					image_export,
+					palette,
+					icon_set,
+					poster,
+					animation,
 					..Default::default()
 				};
 				let result = self.executor.submit_document_export(document, export_config);
@@ -506,6 +694,34 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					});
 				}
 			}
+			PortfolioMessage::ToggleArtboardRelativeCoordinates => {
+				if let Some(document) = self.active_document_mut() {
+					document.artboard_relative_coordinates = !document.artboard_relative_coordinates;
+
+					responses.add(DocumentMessage::RenderRulers);
+					responses.add(MenuBarMessage::SendLayout);
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+			}
+			PortfolioMessage::ToggleNodeProfiling => {
+				self.node_profiling = !self.node_profiling;
+				self.executor.set_profiling(self.node_profiling);
+
+				if !self.node_profiling {
+					responses.add(FrontendMessage::UpdateNodeProfile { profile: None });
+				}
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			PortfolioMessage::ToggleNodeThumbnails => {
+				self.node_thumbnails = !self.node_thumbnails;
+				self.executor.set_node_thumbnails(self.node_thumbnails);
+
+				responses.add(MenuBarMessage::SendLayout);
+				// Render again so the thumbnails are shown, or cleared away
+				if let Some(document_id) = self.active_document_id {
+					responses.add(PortfolioMessage::SubmitGraphRender { document_id });
+				}
+			}
 			PortfolioMessage::ToggleRulers => {
 				if let Some(document) = self.active_document_mut() {
 					document.rulers_visible = !document.rulers_visible;
@@ -0,0 +1,457 @@
+//! Tiling for the "Poster" export file type. It splits the artwork across the pages of a multi-page PDF, which are printed on a consumer printer and assembled into a poster.
+
+use crate::messages::portfolio::document::utility_types::document_info::DocumentInfo;
+use crate::messages::portfolio::tiff_export::pack_bits;
+
+use glam::DVec2;
+use graphene_core::renderer::OutputProfile;
+use std::fmt::Write;
+
+/// The resolution that the artwork is rasterized at for printing, in pixels per inch.
+const PRINT_PPI: f64 = 150.;
+
+/// PDF measures lengths in points of 1/72 inch.
+const POINTS_PER_MM: f64 = 72. / 25.4;
+
+/// The blank margin around each page, which consumer printers can't print up to the edge of.
+const PRINTER_MARGIN_MM: f64 = 10.;
+
+/// The length of the crop marks at the corners of the artwork on each page, which stop short of the artwork by the gap.
+const CROP_MARK_MM: f64 = 6.;
+const CROP_MARK_GAP_MM: f64 = 2.;
+
+/// The paper sizes that poster pages can be printed on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum PageSize {
+	#[default]
+	A4,
+	A3,
+	Letter,
+	Legal,
+	Tabloid,
+}
+
+impl PageSize {
+	pub const ALL: [Self; 5] = [Self::A4, Self::A3, Self::Letter, Self::Legal, Self::Tabloid];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::A4 => "A4",
+			Self::A3 => "A3",
+			Self::Letter => "US Letter",
+			Self::Legal => "US Legal",
+			Self::Tabloid => "US Tabloid",
+		}
+	}
+
+	/// The width and height of the paper in portrait orientation, in millimeters.
+	fn size_mm(self) -> DVec2 {
+		match self {
+			Self::A4 => DVec2::new(210., 297.),
+			Self::A3 => DVec2::new(297., 420.),
+			Self::Letter => DVec2::new(215.9, 279.4),
+			Self::Legal => DVec2::new(215.9, 355.6),
+			Self::Tabloid => DVec2::new(279.4, 431.8),
+		}
+	}
+}
+
+/// The user's choices for how the artwork is tiled across the pages of a poster.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct PosterOptions {
+	pub page_size: PageSize,
+	pub landscape: bool,
+	/// The number of pages across the width of the poster, which also decides its size since the artwork is scaled to fill them.
+	pub pages_across: u32,
+	/// How far neighboring pages repeat the same strip of the artwork, in millimeters, so they can be glued together without gaps.
+	pub overlap: f64,
+	/// Whether crop marks, overlap guides, and page labels are printed in the margins to help with assembly.
+	pub marks: bool,
+}
+
+impl Default for PosterOptions {
+	fn default() -> Self {
+		Self {
+			page_size: PageSize::A4,
+			landscape: false,
+			pages_across: 2,
+			overlap: 10.,
+			marks: true,
+		}
+	}
+}
+
+/// The region of the rasterized poster, in pixels, that the frontend crops out as the image of one page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct PosterPageRasterization {
+	pub x: u32,
+	pub y: u32,
+	pub width: u32,
+	pub height: u32,
+}
+
+/// One page of the poster and the part of the artwork printed on it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PosterPage {
+	column: u32,
+	row: u32,
+	/// The top left corner of the page's part of the poster, in points from the top left corner of the poster.
+	position: DVec2,
+	/// The size of the page's part of the poster in points, which is smaller than the printable area on the pages along the right and bottom edges.
+	size: DVec2,
+}
+
+/// The pages that the artwork is split across, with every length in points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PosterLayout {
+	page_size: DVec2,
+	margin: f64,
+	overlap: f64,
+	columns: u32,
+	rows: u32,
+	/// The size the artwork is printed at.
+	poster_size: DVec2,
+	marks: bool,
+	pages: Vec<PosterPage>,
+	/// The title, author, and other info of the document, which is written into the PDF's document information dictionary.
+	info: DocumentInfo,
+	/// The printing condition the pages are separated into the CMYK inks of, or kept as RGB for an RGB profile.
+	output_profile: OutputProfile,
+}
+
+impl PosterLayout {
+	/// Scales the artwork to fill the chosen number of pages across, and adds as many rows of pages as it takes to fit its height.
+	/// Returns `None` for artwork without an area.
+	pub fn new(artwork_size: DVec2, options: PosterOptions) -> Option<Self> {
+		if artwork_size.x <= 0. || artwork_size.y <= 0. {
+			return None;
+		}
+
+		let page_size = options.page_size.size_mm() * POINTS_PER_MM;
+		let page_size = if options.landscape { DVec2::new(page_size.y, page_size.x) } else { page_size };
+		let margin = PRINTER_MARGIN_MM * POINTS_PER_MM;
+		let printable = page_size - 2. * margin;
+		// The overlap can't cover more than half a page, or a page would be entirely covered by its neighbors
+		let overlap = (options.overlap * POINTS_PER_MM).clamp(0., printable.min_element() / 2.);
+		let step = printable - overlap;
+
+		let columns = options.pages_across.max(1);
+		let width = printable.x + (columns - 1) as f64 * step.x;
+		let poster_size = DVec2::new(width, width * artwork_size.y / artwork_size.x);
+		// A tiny tolerance keeps rounding errors from adding a row with only a sliver of the artwork
+		let rows = 1 + ((poster_size.y - printable.y) / step.y - 1e-6).ceil().max(0.) as u32;
+
+		let pages = (0..rows)
+			.flat_map(|row| (0..columns).map(move |column| (column, row)))
+			.map(|(column, row)| {
+				let position = DVec2::new(column as f64, row as f64) * step;
+				let size = printable.min(poster_size - position);
+				PosterPage { column, row, position, size }
+			})
+			.collect();
+
+		Some(Self {
+			page_size,
+			margin,
+			overlap,
+			columns,
+			rows,
+			poster_size,
+			marks: options.marks,
+			pages,
+			info: DocumentInfo::default(),
+			output_profile: OutputProfile::default(),
+		})
+	}
+
+	/// Sets the document info that's written into the PDF.
+	pub fn with_info(self, info: DocumentInfo) -> Self {
+		Self { info, ..self }
+	}
+
+	/// Sets the output profile whose inks the pages are separated into.
+	pub fn with_output_profile(self, output_profile: OutputProfile) -> Self {
+		Self { output_profile, ..self }
+	}
+
+	/// The size in pixels that the whole poster is rasterized at.
+	pub fn raster_size(&self) -> DVec2 {
+		(self.poster_size * PRINT_PPI / 72.).round().max(DVec2::ONE)
+	}
+
+	/// The region of the rasterized poster shown on each page, in the order of the pages.
+	pub fn rasterizations(&self) -> Vec<PosterPageRasterization> {
+		let scale = PRINT_PPI / 72.;
+		self.pages
+			.iter()
+			.map(|page| {
+				let start = (page.position * scale).round();
+				let end = ((page.position + page.size) * scale).round().max(start + 1.);
+				PosterPageRasterization {
+					x: start.x as u32,
+					y: start.y as u32,
+					width: (end.x - start.x) as u32,
+					height: (end.y - start.y) as u32,
+				}
+			})
+			.collect()
+	}
+
+	/// Writes the PDF document with a page for each of the images rasterized for the pages, in the order of [`Self::rasterizations`].
+	/// The images are JPEGs, or for a CMYK output profile the RGBA pixels of the page which are separated into its inks, and the PDF declares the printing condition as its output intent.
+	/// Pages whose image is missing are left blank apart from their marks.
+	pub fn encode_pdf(&self, images: &[Vec<u8>]) -> Vec<u8> {
+		let proofer = self.output_profile.proofer(false);
+		let mut pdf = PdfWriter::default();
+		pdf.buffer.extend(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+
+		// Objects 1 to 4 are the catalog, the page tree, the font of the labels, and the document information dictionary, followed by three objects for each page
+		let page_ids = (0..self.pages.len()).map(|index| 5 + 3 * index).collect::<Vec<_>>();
+		let output_intents = match (self.output_profile.output_condition_identifier(), &proofer) {
+			(Some(identifier), Some(_)) => format!(
+				" /OutputIntents [<< /Type /OutputIntent /S /GTS_PDFX /OutputConditionIdentifier ({}) /RegistryName (http://www.color.org) /Info ({}) >>]",
+				escape_pdf_string(identifier),
+				escape_pdf_string(self.output_profile.name())
+			),
+			_ => String::new(),
+		};
+		pdf.object(1, format!("<< /Type /Catalog /Pages 2 0 R{output_intents} >>").as_bytes());
+		let kids = page_ids.iter().map(|id| format!("{id} 0 R")).collect::<Vec<_>>().join(" ");
+		pdf.object(2, format!("<< /Type /Pages /Kids [{kids}] /Count {} >>", self.pages.len()).as_bytes());
+		pdf.object(3, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>");
+		pdf.object(4, self.info.pdf_info_dictionary().as_bytes());
+
+		for ((index, page), (id, rasterization)) in self.pages.iter().enumerate().zip(page_ids.into_iter().zip(self.rasterizations())) {
+			let (content_id, image_id) = (id + 1, id + 2);
+			let pixel_count = rasterization.width as usize * rasterization.height as usize;
+			let image = images.get(index).filter(|image| !image.is_empty()).and_then(|image| match &proofer {
+				// Run length encoded CMYK pixels end with the end of data marker
+				Some(proofer) => (image.len() == pixel_count * 4).then(|| {
+					(
+						"/DeviceCMYK /BitsPerComponent 8 /Filter /RunLengthDecode",
+						[pack_bits(&proofer.separate_rgba8(image)), vec![128]].concat(),
+					)
+				}),
+				None => Some(("/DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode", image.clone())),
+			});
+
+			let image_resource = if image.is_some() { format!(" /XObject << /Im0 {image_id} 0 R >>") } else { String::new() };
+			let page_object = format!(
+				"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >>{image_resource} >> /Contents {content_id} 0 R >>",
+				number(self.page_size.x),
+				number(self.page_size.y)
+			);
+			pdf.object(id, page_object.as_bytes());
+			pdf.stream(content_id, "", self.page_content(index, page, image.is_some()).as_bytes());
+
+			if let Some((encoding, data)) = image {
+				let dictionary = format!("/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace {encoding}", rasterization.width, rasterization.height);
+				pdf.stream(image_id, &dictionary, &data);
+			}
+		}
+
+		pdf.finish(1, 4)
+	}
+
+	/// The drawing commands of a page, which place its image at the top left of the printable area and draw the assembly marks around it.
+	fn page_content(&self, index: usize, page: &PosterPage, has_image: bool) -> String {
+		let mut content = String::new();
+		// PDF's origin is the bottom left corner of the page
+		let [left, top] = [self.margin, self.page_size.y - self.margin];
+		let [right, bottom] = [left + page.size.x, top - page.size.y];
+
+		if has_image {
+			let _ = writeln!(content, "q {} 0 0 {} {} {} cm /Im0 Do Q", number(page.size.x), number(page.size.y), number(left), number(bottom));
+		}
+		if !self.marks {
+			return content;
+		}
+
+		// Crop marks at each corner of the artwork, along which the edges that go beneath the neighboring pages are trimmed
+		let (length, gap) = (CROP_MARK_MM * POINTS_PER_MM, CROP_MARK_GAP_MM * POINTS_PER_MM);
+		content.push_str("0 G 0.5 w [] 0 d\n");
+		for (x, y, outward_x, outward_y) in [(left, top, -1., 1.), (right, top, 1., 1.), (left, bottom, -1., -1.), (right, bottom, 1., -1.)] {
+			let _ = writeln!(content, "{} {} m {} {} l S", number(x + outward_x * gap), number(y), number(x + outward_x * (gap + length)), number(y));
+			let _ = writeln!(content, "{} {} m {} {} l S", number(x), number(y + outward_y * gap), number(x), number(y + outward_y * (gap + length)));
+		}
+
+		// Dashed guides where the trimmed edges of the pages to the right and below are lined up, over the strip they repeat
+		if self.overlap > 0. {
+			content.push_str("0.5 G [4 3] 0 d\n");
+			if page.column + 1 < self.columns {
+				let x = number(right - self.overlap);
+				let _ = writeln!(content, "{x} {} m {x} {} l S", number(bottom), number(top));
+			}
+			if page.row + 1 < self.rows {
+				let y = number(bottom + self.overlap);
+				let _ = writeln!(content, "{} {y} m {} {y} l S", number(left), number(right));
+			}
+		}
+
+		// The label in the top margin names the page by its row and column, which is how the pages are laid out when assembled
+		let label = format!(
+			"Page {} of {}  -  row {}, column {}  -  poster {:.0} x {:.0} mm",
+			index + 1,
+			self.pages.len(),
+			page.row + 1,
+			page.column + 1,
+			self.poster_size.x / POINTS_PER_MM,
+			self.poster_size.y / POINTS_PER_MM
+		);
+		let _ = writeln!(content, "BT /F1 8 Tf 0 g {} {} Td ({}) Tj ET", number(left), number(top + gap), escape_pdf_string(&label));
+
+		content
+	}
+}
+
+/// Writes the objects of a PDF file while recording where each one starts, for the cross-reference table.
+#[derive(Default)]
+struct PdfWriter {
+	buffer: Vec<u8>,
+	offsets: Vec<(usize, usize)>,
+}
+
+impl PdfWriter {
+	fn object(&mut self, id: usize, body: &[u8]) {
+		self.offsets.push((id, self.buffer.len()));
+		self.buffer.extend(format!("{id} 0 obj\n").as_bytes());
+		self.buffer.extend(body);
+		self.buffer.extend(b"\nendobj\n");
+	}
+
+	fn stream(&mut self, id: usize, dictionary: &str, data: &[u8]) {
+		let separator = if dictionary.is_empty() { "" } else { " " };
+		let mut body = format!("<< {dictionary}{separator}/Length {} >>\nstream\n", data.len()).into_bytes();
+		body.extend(data);
+		body.extend(b"\nendstream");
+		self.object(id, &body);
+	}
+
+	/// Ends the file with the cross-reference table, whose entries are each exactly 20 bytes long, and the trailer pointing to the catalog and document information dictionary.
+	fn finish(mut self, root: usize, info: usize) -> Vec<u8> {
+		self.offsets.sort_unstable();
+		let size = self.offsets.last().map_or(0, |&(id, _)| id) + 1;
+		let xref_offset = self.buffer.len();
+
+		let mut xref = format!("xref\n0 {size}\n0000000000 65535 f \n");
+		for id in 1..size {
+			match self.offsets.binary_search_by_key(&id, |&(id, _)| id) {
+				Ok(index) => xref.push_str(&format!("{:010} 00000 n \n", self.offsets[index].1)),
+				Err(_) => xref.push_str("0000000000 65535 f \n"),
+			}
+		}
+		xref.push_str(&format!("trailer\n<< /Size {size} /Root {root} 0 R /Info {info} 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n"));
+
+		self.buffer.extend(xref.as_bytes());
+		self.buffer
+	}
+}
+
+/// Formats a length for a PDF, which only allows plain decimal numbers.
+fn number(value: f64) -> String {
+	let formatted = format!("{value:.2}");
+	formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Escapes the characters with special meaning in PDF string literals.
+fn escape_pdf_string(text: &str) -> String {
+	text.chars()
+		.filter(char::is_ascii)
+		.flat_map(|character| match character {
+			'(' | ')' | '\\' => vec!['\\', character],
+			_ => vec![character],
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn layout(artwork_size: DVec2) -> PosterLayout {
+		PosterLayout::new(artwork_size, PosterOptions::default()).unwrap()
+	}
+
+	#[test]
+	fn pages_cover_the_poster_with_the_overlap_between_neighbors() {
+		let layout = layout(DVec2::new(100., 200.));
+		let printable = layout.page_size - 2. * layout.margin;
+		assert_eq!(layout.columns, 2);
+		assert!((layout.poster_size.x - (2. * printable.x - layout.overlap)).abs() < 1e-9);
+		assert!((layout.poster_size.y - 2. * layout.poster_size.x).abs() < 1e-9);
+		assert_eq!(layout.pages.len() as u32, layout.columns * layout.rows);
+
+		for page in &layout.pages {
+			assert!(page.size.x <= printable.x + 1e-9 && page.size.y <= printable.y + 1e-9);
+			if page.column + 1 < layout.columns {
+				let right = layout.pages.iter().find(|other| other.row == page.row && other.column == page.column + 1).unwrap();
+				assert!((page.position.x + page.size.x - right.position.x - layout.overlap).abs() < 1e-9);
+			}
+			if page.row + 1 < layout.rows {
+				let below = layout.pages.iter().find(|other| other.column == page.column && other.row == page.row + 1).unwrap();
+				assert!((page.position.y + page.size.y - below.position.y - layout.overlap).abs() < 1e-9);
+			}
+		}
+
+		let far_corner = layout.pages.iter().map(|page| page.position + page.size).fold(DVec2::ZERO, DVec2::max);
+		assert!(far_corner.abs_diff_eq(layout.poster_size, 1e-9));
+	}
+
+	#[test]
+	fn artwork_without_an_area_has_no_layout() {
+		assert!(PosterLayout::new(DVec2::new(0., 10.), PosterOptions::default()).is_none());
+	}
+
+	#[test]
+	fn artwork_fitting_one_row_adds_no_sliver_row() {
+		let options = PosterOptions { overlap: 0., ..Default::default() };
+		let page = PageSize::A4.size_mm() - 2. * PRINTER_MARGIN_MM;
+		let layout = PosterLayout::new(DVec2::new(2. * page.x, page.y), options).unwrap();
+		assert_eq!((layout.columns, layout.rows), (2, 1));
+	}
+
+	#[test]
+	fn rasterizations_stay_within_the_raster() {
+		let layout = layout(DVec2::new(300., 100.));
+		let raster_size = layout.raster_size();
+		for rasterization in layout.rasterizations() {
+			assert!(rasterization.width > 0 && rasterization.height > 0);
+			assert!((rasterization.x + rasterization.width) as f64 <= raster_size.x && (rasterization.y + rasterization.height) as f64 <= raster_size.y);
+		}
+	}
+
+	#[test]
+	fn pdf_cross_references_point_at_each_object() {
+		let layout = layout(DVec2::new(100., 100.));
+		// Only the first page has an image, so the others are left blank apart from their marks
+		let pdf = layout.encode_pdf(&[b"jpeg".to_vec()]);
+		let text = String::from_utf8_lossy(&pdf);
+
+		// The binary comment in the header is the only part that isn't ASCII, so the cross-reference table is read from the bytes at its offset
+		let xref_offset = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse::<usize>().unwrap();
+		let xref = std::str::from_utf8(&pdf[xref_offset..]).unwrap();
+		assert!(xref.contains("/Root 1 0 R /Info 4 0 R"));
+		let mut objects = 0;
+		for (id, entry) in xref.lines().skip(2).take_while(|line| line.len() == 19).enumerate() {
+			if entry.ends_with("n ") {
+				let offset = entry[..10].parse::<usize>().unwrap();
+				assert!(pdf[offset..].starts_with(format!("{id} 0 obj\n").as_bytes()));
+				objects += 1;
+			}
+		}
+		// The catalog, page tree, font, and info, then a page and its contents for each page, and the image of the first page
+		assert_eq!(objects, 4 + 2 * layout.pages.len() + 1);
+
+		assert!(text.contains(&format!("/Count {}", layout.pages.len())));
+		assert_eq!(text.matches("/Subtype /Image").count(), 1);
+		assert_eq!(text.matches("/Title").count(), 0);
+	}
+
+	#[test]
+	fn pdf_numbers_and_strings_are_plain() {
+		assert_eq!(number(12.5), "12.5");
+		assert_eq!(number(3.), "3");
+		assert_eq!(number(0.004), "0");
+		assert_eq!(escape_pdf_string("a (b) \\ ©"), "a \\(b\\) \\\\ ");
+	}
+}
@@ -0,0 +1,200 @@
+//! Encoding for the "TIFF" export file type, which keeps the colors of the artwork as RGB or separates them into the CMYK inks of the document's output profile for print.
+
+use crate::messages::portfolio::document::utility_types::document_info::DocumentInfo;
+use crate::messages::portfolio::icc_profile;
+
+use graphene_core::renderer::OutputProfile;
+
+/// The resolution recorded in the file, in pixels per inch, which is the resolution SVG and the browser measure the artwork in.
+const TIFF_PPI: u32 = 72;
+
+/// The value of the compression tag for PackBits, which compresses runs of repeated bytes.
+const COMPRESSION_PACKBITS: u16 = 32773;
+
+/// The value of the photometric interpretation tag for RGB, and for separations into inks which are CMYK by default.
+const PHOTOMETRIC_RGB: u16 = 2;
+const PHOTOMETRIC_SEPARATED: u16 = 5;
+
+/// The field types of TIFF tags.
+const TYPE_ASCII: u16 = 2;
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+const TYPE_RATIONAL: u16 = 5;
+const TYPE_UNDEFINED: u16 = 7;
+
+/// What's needed to write the TIFF file of an export once the frontend has rasterized it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiffExport {
+	pub output_profile: OutputProfile,
+	/// The title, author, and other info of the document, which is written into the TIFF's description, artist, and copyright tags.
+	pub info: DocumentInfo,
+}
+
+impl TiffExport {
+	/// Writes the TIFF file from the pixels of the image given as 8-bit gamma encoded RGBA, as read from a canvas.
+	/// For a CMYK output profile the pixels are separated into its inks, composited over the paper, and otherwise they're kept as RGBA.
+	pub fn encode(&self, width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+		let (pixels, photometric) = match self.output_profile.proofer(false) {
+			Some(proofer) => (proofer.separate_rgba8(rgba), PHOTOMETRIC_SEPARATED),
+			None => (rgba.to_vec(), PHOTOMETRIC_RGB),
+		};
+
+		// Each row is compressed on its own and stored as its own strip, since PackBits runs may not cross rows
+		let row_length = width as usize * 4;
+		let strips = pixels.chunks(row_length.max(1)).take(height as usize).map(pack_bits).collect::<Vec<_>>();
+
+		let mut tiff = TiffWriter::default();
+		tiff.long(256, &[width]);
+		tiff.long(257, &[height]);
+		tiff.short(258, &[8, 8, 8, 8]);
+		tiff.short(259, &[COMPRESSION_PACKBITS]);
+		tiff.short(262, &[photometric]);
+		tiff.ascii(270, &self.info.title);
+		tiff.short(277, &[4]);
+		tiff.long(278, &[1]);
+		tiff.rational(282, TIFF_PPI, 1);
+		tiff.rational(283, TIFF_PPI, 1);
+		tiff.short(284, &[1]);
+		tiff.short(296, &[2]);
+		tiff.ascii(305, "Graphite");
+		tiff.ascii(315, &self.info.author);
+		if photometric == PHOTOMETRIC_SEPARATED {
+			// The ink set tag marks the separation as CMYK
+			tiff.short(332, &[1]);
+		} else {
+			// The fourth sample of RGB pixels is their alpha, which isn't premultiplied
+			tiff.short(338, &[2]);
+			// RGB pixels are tagged as sRGB, while separated inks are left untagged since the output profile is only an approximation of the printing conditions
+			tiff.undefined(34675, &icc_profile::srgb_icc_profile());
+		}
+		tiff.ascii(33432, &self.info.license);
+
+		tiff.finish(&strips)
+	}
+}
+
+/// A tag of the TIFF file's directory, with its value as the bytes it's written as.
+struct TiffEntry {
+	tag: u16,
+	field_type: u16,
+	count: u32,
+	value: Vec<u8>,
+}
+
+/// Collects the tags of a TIFF file with a single image, written in little endian byte order.
+#[derive(Default)]
+struct TiffWriter {
+	entries: Vec<TiffEntry>,
+}
+
+impl TiffWriter {
+	fn push(&mut self, tag: u16, field_type: u16, count: usize, value: Vec<u8>) {
+		self.entries.push(TiffEntry {
+			tag,
+			field_type,
+			count: count as u32,
+			value,
+		});
+	}
+
+	fn short(&mut self, tag: u16, values: &[u16]) {
+		self.push(tag, TYPE_SHORT, values.len(), values.iter().flat_map(|value| value.to_le_bytes()).collect());
+	}
+
+	fn long(&mut self, tag: u16, values: &[u32]) {
+		self.push(tag, TYPE_LONG, values.len(), values.iter().flat_map(|value| value.to_le_bytes()).collect());
+	}
+
+	fn rational(&mut self, tag: u16, numerator: u32, denominator: u32) {
+		self.push(tag, TYPE_RATIONAL, 1, [numerator.to_le_bytes(), denominator.to_le_bytes()].concat());
+	}
+
+	fn undefined(&mut self, tag: u16, bytes: &[u8]) {
+		self.push(tag, TYPE_UNDEFINED, bytes.len(), bytes.to_vec());
+	}
+
+	/// Adds a text tag, unless the text is empty, ending it with the null byte that TIFF text is terminated by.
+	fn ascii(&mut self, tag: u16, text: &str) {
+		if text.is_empty() {
+			return;
+		}
+		let mut value = text.as_bytes().to_vec();
+		value.push(0);
+		self.push(tag, TYPE_ASCII, value.len(), value);
+	}
+
+	/// Writes the header, the strips of image data, the values too long to fit in the directory, and finally the directory of tags sorted by tag as TIFF requires.
+	fn finish(mut self, strips: &[Vec<u8>]) -> Vec<u8> {
+		let mut buffer = b"II*\0\0\0\0\0".to_vec();
+
+		let mut offsets = Vec::with_capacity(strips.len());
+		for strip in strips {
+			offsets.push(buffer.len() as u32);
+			buffer.extend(strip);
+		}
+		// The place of each strip in the file is only known once they're written
+		self.long(273, &offsets);
+		self.long(279, &strips.iter().map(|strip| strip.len() as u32).collect::<Vec<_>>());
+		self.entries.sort_by_key(|entry| entry.tag);
+
+		// Values longer than four bytes are stored outside the directory, which points to them
+		let mut directory_values = Vec::with_capacity(self.entries.len());
+		for entry in &self.entries {
+			if entry.value.len() <= 4 {
+				let mut inline = entry.value.clone();
+				inline.resize(4, 0);
+				directory_values.push(inline);
+			} else {
+				// Values start on a word boundary
+				if buffer.len() % 2 == 1 {
+					buffer.push(0);
+				}
+				directory_values.push((buffer.len() as u32).to_le_bytes().to_vec());
+				buffer.extend(&entry.value);
+			}
+		}
+
+		if buffer.len() % 2 == 1 {
+			buffer.push(0);
+		}
+		let directory_offset = buffer.len() as u32;
+		buffer[4..8].copy_from_slice(&directory_offset.to_le_bytes());
+
+		buffer.extend((self.entries.len() as u16).to_le_bytes());
+		for (entry, value) in self.entries.iter().zip(directory_values) {
+			buffer.extend(entry.tag.to_le_bytes());
+			buffer.extend(entry.field_type.to_le_bytes());
+			buffer.extend(entry.count.to_le_bytes());
+			buffer.extend(value);
+		}
+		// There's no directory of a next image
+		buffer.extend(0_u32.to_le_bytes());
+
+		buffer
+	}
+}
+
+/// Compresses the data with PackBits, which both TIFF's PackBits compression and PDF's run length decode filter read.
+/// Runs of two or more repeated bytes become a count and the byte, while the bytes between them are copied literally in groups of up to 128.
+pub fn pack_bits(data: &[u8]) -> Vec<u8> {
+	let mut packed = Vec::with_capacity(data.len() + data.len() / 128 + 1);
+	let mut index = 0;
+	while index < data.len() {
+		let run = data[index..].iter().take(128).take_while(|&&byte| byte == data[index]).count();
+		if run >= 2 {
+			packed.push((257 - run) as u8);
+			packed.push(data[index]);
+			index += run;
+			continue;
+		}
+
+		// Literal bytes continue until the next run of repeated bytes
+		let start = index;
+		while index < data.len() && index - start < 128 && !(index + 1 < data.len() && data[index] == data[index + 1]) {
+			index += 1;
+		}
+		packed.push((index - start - 1) as u8);
+		packed.extend(&data[start..index]);
+	}
+	packed
+}
@@ -1,12 +1,32 @@
 use crate::messages::prelude::*;
 
+use crate::messages::portfolio::document::utility_types::node_presets::PresetNode;
+use crate::messages::portfolio::document::utility_types::palette_file::PaletteFormat;
+
+use graphene_core::vector::style::Fill;
+
 #[impl_message(Message, Preferences)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum PreferencesMessage {
 	Load { preferences: String },
 	ResetToDefaults,
 
+	CacheMemoryBudget { megabytes: f64 },
 	ImaginateRefreshFrequency { seconds: f64 },
 	ImaginateServerHostname { hostname: String },
 	ModifyLayout { zoom_with_scroll: bool },
+
+	AddSwatch { name: String, fill: Fill },
+	DeleteSwatch { id: u64 },
+	ExportSwatches { format: PaletteFormat },
+	ImportSwatches { filename: String, content: Vec<u8> },
+	MoveSwatch { id: u64, index: usize },
+	RenameSwatch { id: u64, name: String },
+	SetSwatchFill { id: u64, fill: Fill },
+
+	AddNodePreset { name: String, nodes: Vec<PresetNode> },
+	DeleteNodePreset { id: u64 },
+	ExportNodePresets,
+	ImportNodePresets { content: String },
+	RenameNodePreset { id: u64, name: String },
 }
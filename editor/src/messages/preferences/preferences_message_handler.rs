@@ -1,4 +1,9 @@
+use crate::application::generate_uuid;
+use crate::consts::{DEFAULT_CACHE_MEMORY_BUDGET, NODE_PRESETS_SAVE_SUFFIX};
 use crate::messages::input_mapper::key_mapping::MappingVariant;
+use crate::messages::portfolio::document::utility_types::node_presets::{self, NodePreset, NodePresetLibrary};
+use crate::messages::portfolio::document::utility_types::palette_file;
+use crate::messages::portfolio::document::utility_types::swatches::{Swatch, SwatchLibrary};
 use crate::messages::prelude::*;
 use graph_craft::imaginate_input::ImaginatePreferences;
 
@@ -7,6 +12,16 @@ pub struct PreferencesMessageHandler {
 	pub imaginate_server_hostname: String,
 	pub imaginate_refresh_frequency: f64,
 	pub zoom_with_scroll: bool,
+	/// The global swatch library, shared by every document, from which swatches can be added to a document's own library.
+	#[serde(default)]
+	pub swatches: SwatchLibrary,
+	/// Named bundles of input values saved from nodes, which can be applied to nodes of the same kinds in any document.
+	#[serde(default)]
+	#[specta(skip)]
+	pub node_presets: NodePresetLibrary,
+	/// The number of megabytes the outputs of the cache nodes in the graph may hold, where 0 means there's no limit.
+	#[serde(default = "default_cache_memory_budget")]
+	pub cache_memory_budget: f64,
 }
 
 impl PreferencesMessageHandler {
@@ -24,6 +39,9 @@ impl Default for PreferencesMessageHandler {
 			imaginate_server_hostname: host_name,
 			imaginate_refresh_frequency: 1.,
 			zoom_with_scroll: matches!(MappingVariant::default(), MappingVariant::ZoomWithScroll),
+			swatches: SwatchLibrary::default(),
+			node_presets: NodePresetLibrary::default(),
+			cache_memory_budget: DEFAULT_CACHE_MEMORY_BUDGET,
 		}
 	}
 }
@@ -38,13 +56,20 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 					responses.add(PortfolioMessage::ImaginateServerHostname);
 					responses.add(PortfolioMessage::ImaginateCheckServerStatus);
 					responses.add(PortfolioMessage::ImaginatePreferences);
+					responses.add(PortfolioMessage::CacheMemoryBudget);
 				}
 			}
 			PreferencesMessage::ResetToDefaults => {
 				refresh_dialog(responses);
 				responses.add(KeyMappingMessage::ModifyMapping(MappingVariant::Default));
 
-				*self = Self::default()
+				*self = Self::default();
+				responses.add(PortfolioMessage::CacheMemoryBudget);
+			}
+
+			PreferencesMessage::CacheMemoryBudget { megabytes } => {
+				self.cache_memory_budget = megabytes;
+				responses.add(PortfolioMessage::CacheMemoryBudget);
 			}
 
 			PreferencesMessage::ImaginateRefreshFrequency { seconds } => {
@@ -77,6 +102,79 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 				responses.add(KeyMappingMessage::ModifyMapping(variant));
 				responses.add(FrontendMessage::UpdateZoomWithScroll { zoom_with_scroll });
 			}
+
+			PreferencesMessage::AddSwatch { name, fill } => {
+				self.swatches.add(Swatch { id: generate_uuid(), name, fill });
+			}
+			PreferencesMessage::DeleteSwatch { id } => {
+				self.swatches.remove(id);
+			}
+			PreferencesMessage::ExportSwatches { format } => {
+				let data = palette_file::write_palette(&self.swatches, "Global Swatches", format);
+				responses.add(FrontendMessage::TriggerDownloadBinaryFile {
+					name: format!("Global Swatches.{}", format.extension()),
+					mime: format.mime().into(),
+					data,
+				});
+				// Nothing changed, so there's nothing to save
+				return;
+			}
+			PreferencesMessage::ImportSwatches { filename, content } => match palette_file::read_palette(&filename, &content) {
+				Ok(swatches) => swatches.into_iter().for_each(|swatch| self.swatches.add(swatch)),
+				Err(description) => {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Couldn't import swatches".into(),
+						description,
+					});
+					return;
+				}
+			},
+			PreferencesMessage::MoveSwatch { id, index } => {
+				self.swatches.reorder(id, index);
+			}
+			PreferencesMessage::RenameSwatch { id, name } => {
+				self.swatches.rename(id, name);
+			}
+			PreferencesMessage::SetSwatchFill { id, fill } => {
+				self.swatches.set_fill(id, fill);
+			}
+
+			PreferencesMessage::AddNodePreset { name, nodes } => {
+				self.node_presets.add(NodePreset { id: generate_uuid(), name, nodes });
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			PreferencesMessage::DeleteNodePreset { id } => {
+				self.node_presets.remove(id);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
+			PreferencesMessage::ExportNodePresets => {
+				responses.add(FrontendMessage::TriggerDownloadTextFile {
+					document: node_presets::write_presets(&self.node_presets.presets),
+					name: format!("Node Presets{NODE_PRESETS_SAVE_SUFFIX}"),
+				});
+				// Nothing changed, so there's nothing to save
+				return;
+			}
+			PreferencesMessage::ImportNodePresets { content } => match node_presets::read_presets(&content) {
+				Ok(presets) => {
+					// Imported presets get new IDs so they never collide with the presets already saved
+					for preset in presets {
+						self.node_presets.add(NodePreset { id: generate_uuid(), ..preset });
+					}
+					responses.add(PropertiesPanelMessage::Refresh);
+				}
+				Err(description) => {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Couldn't import node presets".into(),
+						description,
+					});
+					return;
+				}
+			},
+			PreferencesMessage::RenameNodePreset { id, name } => {
+				self.node_presets.rename(id, name);
+				responses.add(PropertiesPanelMessage::Refresh);
+			}
 		}
 
 		responses.add(FrontendMessage::TriggerSavePreferences { preferences: self.clone() });
@@ -86,6 +184,10 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 	);
 }
 
+fn default_cache_memory_budget() -> f64 {
+	DEFAULT_CACHE_MEMORY_BUDGET
+}
+
 fn refresh_dialog(responses: &mut VecDeque<Message>) {
 	responses.add(DialogMessage::CloseDialogAndThen {
 		followups: vec![DialogMessage::RequestPreferencesDialog.into()],
@@ -5,7 +5,7 @@ use crate::messages::prelude::*;
 use bezier_rs::{ManipulatorGroup, Subpath};
 use graph_craft::document::{value::TaggedValue, DocumentNode, NodeId, NodeInput, NodeNetwork};
 use graphene_core::raster::{BlendMode, ImageFrame};
-use graphene_core::text::Font;
+use graphene_core::text::{Font, FontVariations, KerningMode, TextAlign, TextAutoResize, TextKerning, TextRuns, TypesettingConfig};
 use graphene_core::uuid::ManipulatorGroupId;
 use graphene_core::vector::style::Gradient;
 use graphene_core::Color;
@@ -147,8 +147,8 @@ pub fn get_text_id(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -
 	NodeGraphLayer::new(layer, document_network).upstream_node_id_from_name("Text")
 }
 
-/// Gets properties from the Text node
-pub fn get_text(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> Option<(&String, &Font, f64)> {
+/// Gets properties from the Text node, along with the layout of its text from its size, line height, text box, alignment, auto-resize mode, and kerning mode
+pub fn get_text(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> Option<(&String, &Font, TypesettingConfig)> {
 	let inputs = NodeGraphLayer::new(layer, document_network).find_node_inputs("Text")?;
 	let NodeInput::Value {
 		tagged_value: TaggedValue::String(text),
@@ -174,7 +174,68 @@ pub fn get_text(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> O
 		return None;
 	};
 
-	Some((text, font, font_size))
+	let number = |index: usize| match inputs.get(index) {
+		Some(NodeInput::Value {
+			tagged_value: TaggedValue::F64(value),
+			..
+		}) => Some(*value),
+		_ => None,
+	};
+	let align = match inputs.get(7) {
+		Some(NodeInput::Value {
+			tagged_value: TaggedValue::TextAlign(align),
+			..
+		}) => *align,
+		_ => TextAlign::default(),
+	};
+	let auto_resize = match inputs.get(8) {
+		Some(NodeInput::Value {
+			tagged_value: TaggedValue::TextAutoResize(auto_resize),
+			..
+		}) => *auto_resize,
+		_ => TextAutoResize::default(),
+	};
+	let kerning = match inputs.get(11) {
+		Some(NodeInput::Value {
+			tagged_value: TaggedValue::TextKerning(kerning),
+			..
+		}) => kerning.mode,
+		_ => KerningMode::default(),
+	};
+	let config = TypesettingConfig {
+		font_size,
+		line_height_ratio: number(4).unwrap_or(1.),
+		max_width: number(5).filter(|width| *width > 0.),
+		max_height: number(6).filter(|height| *height > 0.),
+		align,
+		auto_resize,
+		kerning,
+	};
+
+	Some((text, font, config))
+}
+
+/// Gets the positions along the axes of variation that the Text node instantiates its font at, if it's a variable font
+pub fn get_text_variations(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> FontVariations {
+	match NodeGraphLayer::new(layer, document_network).find_input("Text", 9) {
+		Some(TaggedValue::FontVariations(variations)) => variations.clone(),
+		_ => FontVariations::default(),
+	}
+}
+
+pub fn get_text_runs(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> TextRuns {
+	match NodeGraphLayer::new(layer, document_network).find_input("Text", 10) {
+		Some(TaggedValue::TextRuns(runs)) => runs.clone(),
+		_ => TextRuns::default(),
+	}
+}
+
+/// Gets the kerning mode and the manually kerned pairs of characters of the Text node.
+pub fn get_text_kerning(layer: LayerNodeIdentifier, document_network: &NodeNetwork) -> TextKerning {
+	match NodeGraphLayer::new(layer, document_network).find_input("Text", 11) {
+		Some(TaggedValue::TextKerning(kerning)) => kerning.clone(),
+		_ => TextKerning::default(),
+	}
 }
 
 pub fn get_stroke_width(layer: LayerNodeIdentifier, network: &NodeNetwork) -> Option<f64> {
@@ -186,6 +247,16 @@ pub fn get_stroke_width(layer: LayerNodeIdentifier, network: &NodeNetwork) -> Op
 	}
 }
 
+/// Get the image frame given to the Image node of a layer made from an imported image.
+pub fn get_image_frame(layer: LayerNodeIdentifier, network: &NodeNetwork) -> Option<&ImageFrame<Color>> {
+	let image_node_input_index = 0;
+	if let TaggedValue::ImageFrame(image_frame) = NodeGraphLayer::new(layer, network).find_input("Image", image_node_input_index)? {
+		Some(image_frame)
+	} else {
+		None
+	}
+}
+
 /// Checks if a specified layer uses an upstream node matching the given name.
 pub fn is_layer_fed_by_node_of_name(layer: LayerNodeIdentifier, document_network: &NodeNetwork, node_name: &str) -> bool {
 	NodeGraphLayer::new(layer, document_network).find_node_inputs(node_name).is_some()
@@ -2,6 +2,7 @@ pub mod auto_panning;
 pub mod color_selector;
 pub mod graph_modification_utils;
 pub mod pivot;
+pub mod planar_arrangement;
 pub mod resize;
 pub mod shape_editor;
 pub mod snapping;
@@ -0,0 +1,525 @@
+use bezier_rs::{Identifier, Subpath, TValue};
+use glam::{DAffine2, DVec2};
+use std::collections::{HashMap, HashSet};
+
+/// The approximate length of the line segments that curves are flattened into, in the units of the arrangement (usually viewport pixels).
+const FLATTENING_LENGTH: f64 = 2.;
+/// The maximum number of line segments a single curve is flattened into.
+const MAX_FLATTENING_STEPS: usize = 64;
+/// Points closer than this distance are treated as the same vertex of the arrangement.
+const VERTEX_MERGE_DISTANCE: f64 = 1e-3;
+
+/// Where an edge of the arrangement came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeSource<S> {
+	/// The edge lies along one of the paths added to the arrangement.
+	Path(S),
+	/// The edge bridges a gap between the open end of a path and a nearby path.
+	Gap,
+}
+
+/// An enclosed region of a [`PlanarArrangement`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanarRegion<S> {
+	/// The outer boundary of the region.
+	pub boundary: Vec<DVec2>,
+	/// The outer boundaries of any unconnected islands of paths which lie within the region and are cut out of it.
+	pub holes: Vec<Vec<DVec2>>,
+	/// The sources of the paths which the boundary and holes of the region lie along, without duplicates.
+	pub sources: Vec<S>,
+	/// Whether the region is only enclosed by bridging one or more gaps.
+	pub bridges_gap: bool,
+}
+
+impl<S> PlanarRegion<S> {
+	/// The bounding box of the region, which contains its holes since they lie within the boundary.
+	pub fn bounding_box(&self) -> [DVec2; 2] {
+		let min = self.boundary.iter().copied().fold(DVec2::INFINITY, DVec2::min);
+		let max = self.boundary.iter().copied().fold(DVec2::NEG_INFINITY, DVec2::max);
+		[min, max]
+	}
+
+	/// Converts the boundary and holes of the region into closed subpaths.
+	pub fn to_subpaths<Id: Identifier>(&self, transform: DAffine2) -> Vec<Subpath<Id>> {
+		std::iter::once(&self.boundary)
+			.chain(&self.holes)
+			.map(|polygon| Subpath::from_anchors(polygon.iter().map(|&point| transform.transform_point2(point)), true))
+			.collect()
+	}
+}
+
+struct Polyline<S> {
+	source: S,
+	points: Vec<DVec2>,
+	closed: bool,
+}
+
+/// The subdivision of the plane formed by a set of overlapping paths, where every crossing between paths becomes a vertex.
+/// Each bounded face of the subdivision is an enclosed region that can be filled, even if no single path encloses it on its own.
+///
+/// Curves are flattened into line segments, so the regions are polygonal approximations of the true regions.
+pub struct PlanarArrangement<S> {
+	polylines: Vec<Polyline<S>>,
+}
+
+impl<S> Default for PlanarArrangement<S> {
+	fn default() -> Self {
+		Self { polylines: Vec::new() }
+	}
+}
+
+impl<S: Copy + PartialEq> PlanarArrangement<S> {
+	/// Adds a subpath to the arrangement, transformed into the space of the arrangement, with the given source that its regions will report.
+	pub fn add_subpath<Id: Identifier>(&mut self, source: S, subpath: &Subpath<Id>, transform: DAffine2) {
+		let mut points = Vec::new();
+		for bezier in subpath.iter() {
+			let bezier = bezier.apply_transformation(|point| transform.transform_point2(point));
+			if points.is_empty() {
+				points.push(bezier.start());
+			}
+
+			let steps = if bezier.is_linear() {
+				1
+			} else {
+				// The length of the control polygon is an upper bound on the length of the curve
+				let control_polygon_length = bezier.get_points().zip(bezier.get_points().skip(1)).map(|(a, b)| a.distance(b)).sum::<f64>();
+				((control_polygon_length / FLATTENING_LENGTH).ceil() as usize).clamp(1, MAX_FLATTENING_STEPS)
+			};
+			points.extend((1..=steps).map(|step| bezier.evaluate(TValue::Parametric(step as f64 / steps as f64))));
+		}
+
+		if points.len() >= 2 {
+			self.polylines.push(Polyline {
+				source,
+				points,
+				closed: subpath.closed(),
+			});
+		}
+	}
+
+	/// Finds the smallest enclosed region of the arrangement which contains the point.
+	///
+	/// Open ends of paths which come within `gap_tolerance` of another path (or of another part of the same path) are bridged to it,
+	/// so regions with small gaps in their outlines are still treated as enclosed.
+	pub fn region_at(&self, point: DVec2, gap_tolerance: f64) -> Option<PlanarRegion<S>> {
+		let segments = self.segments(gap_tolerance);
+		let graph = PlanarGraph::new(&segments);
+		let faces = graph.faces();
+
+		// Bounded faces wind counterclockwise (positive area), while the outer boundary of each connected component of the graph winds clockwise
+		let (region_index, region) = faces
+			.iter()
+			.enumerate()
+			.filter(|(_, face)| face.area > 0. && polygon_contains(&face.points, point))
+			.min_by(|(_, a), (_, b)| a.area.total_cmp(&b.area))?;
+
+		// Islands within the region are the outer boundaries of other components which lie inside the region but don't contain the point
+		let islands = faces
+			.iter()
+			.enumerate()
+			.filter(|&(index, face)| index != region_index && face.area < 0. && polygon_contains(&region.points, face.points[0]) && !polygon_contains(&face.points, point))
+			.collect::<Vec<_>>();
+		// Islands nested inside other islands are already cut out along with the island containing them
+		let holes = islands
+			.iter()
+			.filter(|&&(index, face)| {
+				!islands
+					.iter()
+					.any(|&(other_index, other)| other_index != index && other.area < face.area && polygon_contains(&other.points, face.points[0]))
+			})
+			.map(|(_, face)| *face)
+			.collect::<Vec<_>>();
+
+		let mut sources = Vec::new();
+		let mut bridges_gap = false;
+		for source in std::iter::once(region).chain(holes.iter().copied()).flat_map(|face| &face.sources) {
+			match *source {
+				EdgeSource::Path(source) if !sources.contains(&source) => sources.push(source),
+				EdgeSource::Path(_) => {}
+				EdgeSource::Gap => bridges_gap = true,
+			}
+		}
+
+		Some(PlanarRegion {
+			boundary: region.points.clone(),
+			holes: holes.iter().map(|face| face.points.clone()).collect(),
+			sources,
+			bridges_gap,
+		})
+	}
+
+	/// Lists the line segments of every polyline, followed by the segments which bridge gaps at the open ends of polylines.
+	fn segments(&self, gap_tolerance: f64) -> Vec<(DVec2, DVec2, EdgeSource<S>)> {
+		let mut segments = Vec::new();
+		for polyline in &self.polylines {
+			let source = EdgeSource::Path(polyline.source);
+			segments.extend(polyline.points.windows(2).map(|pair| (pair[0], pair[1], source)));
+			if polyline.closed {
+				segments.push((polyline.points[polyline.points.len() - 1], polyline.points[0], source));
+			}
+		}
+
+		if gap_tolerance <= 0. {
+			return segments;
+		}
+
+		let mut bridges = Vec::new();
+		for (index, polyline) in self.polylines.iter().enumerate().filter(|(_, polyline)| !polyline.closed) {
+			let last = polyline.points.len() - 1;
+			for endpoint_index in [0, last] {
+				let endpoint = polyline.points[endpoint_index];
+
+				// The nearest point on any other polyline, or on a part of the same polyline far enough along it to not be the segments leading up to the endpoint
+				let nearest = self
+					.polylines
+					.iter()
+					.enumerate()
+					.flat_map(|(other_index, other)| {
+						let skip_near_endpoint = other_index == index;
+						let length = other.points.windows(2).map(|pair| pair[0].distance(pair[1])).sum::<f64>();
+						let mut distance_along = 0.;
+						other.points.windows(2).filter_map(move |pair| {
+							let start_distance = distance_along;
+							distance_along += pair[0].distance(pair[1]);
+							if skip_near_endpoint {
+								let from_endpoint = if endpoint_index == 0 { start_distance } else { length - distance_along };
+								if from_endpoint < gap_tolerance * 2. {
+									return None;
+								}
+							}
+							Some(closest_point_on_segment(endpoint, pair[0], pair[1]))
+						})
+					})
+					.chain(
+						// The closing segments of closed polylines
+						self.polylines
+							.iter()
+							.filter(|other| other.closed)
+							.map(|other| closest_point_on_segment(endpoint, other.points[other.points.len() - 1], other.points[0])),
+					)
+					.min_by(|a, b| a.distance_squared(endpoint).total_cmp(&b.distance_squared(endpoint)));
+
+				if let Some(nearest) = nearest {
+					let distance = nearest.distance(endpoint);
+					if distance > VERTEX_MERGE_DISTANCE && distance <= gap_tolerance {
+						bridges.push((endpoint, nearest, EdgeSource::Gap));
+					}
+				}
+			}
+		}
+		segments.extend(bridges);
+
+		segments
+	}
+}
+
+fn closest_point_on_segment(point: DVec2, start: DVec2, end: DVec2) -> DVec2 {
+	let direction = end - start;
+	let length_squared = direction.length_squared();
+	if length_squared == 0. {
+		return start;
+	}
+	let t = ((point - start).dot(direction) / length_squared).clamp(0., 1.);
+	start + direction * t
+}
+
+/// Finds the parameters along each of the two segments at which they intersect, including where an endpoint of one lies on the other.
+fn segment_intersections((a0, a1): (DVec2, DVec2), (b0, b1): (DVec2, DVec2)) -> Vec<(f64, f64)> {
+	let a_direction = a1 - a0;
+	let b_direction = b1 - b0;
+	let denominator = a_direction.perp_dot(b_direction);
+	let offset = b0 - a0;
+
+	if denominator.abs() > f64::EPSILON * a_direction.length() * b_direction.length() {
+		let t = offset.perp_dot(b_direction) / denominator;
+		let u = offset.perp_dot(a_direction) / denominator;
+		let epsilon = 1e-9;
+		if (-epsilon..=1. + epsilon).contains(&t) && (-epsilon..=1. + epsilon).contains(&u) {
+			return vec![(t.clamp(0., 1.), u.clamp(0., 1.))];
+		}
+		return Vec::new();
+	}
+
+	// Parallel segments only meet where they overlap, so split each at the endpoints of the other which lie along it
+	let mut intersections = Vec::new();
+	let on_segment = |point: DVec2, start: DVec2, end: DVec2| {
+		let closest = closest_point_on_segment(point, start, end);
+		(closest.distance(point) < VERTEX_MERGE_DISTANCE).then(|| {
+			let direction = end - start;
+			(closest - start).dot(direction) / direction.length_squared().max(f64::EPSILON)
+		})
+	};
+	for (point, u) in [(b0, 0.), (b1, 1.)] {
+		if let Some(t) = on_segment(point, a0, a1) {
+			intersections.push((t, u));
+		}
+	}
+	for (point, t) in [(a0, 0.), (a1, 1.)] {
+		if let Some(u) = on_segment(point, b0, b1) {
+			intersections.push((t, u));
+		}
+	}
+	intersections
+}
+
+/// Whether the point is inside the polygon, using the even-odd rule.
+fn polygon_contains(polygon: &[DVec2], point: DVec2) -> bool {
+	let mut inside = false;
+	for (index, &current) in polygon.iter().enumerate() {
+		let previous = polygon[(index + polygon.len() - 1) % polygon.len()];
+		if (current.y > point.y) != (previous.y > point.y) && point.x < previous.x + (point.y - previous.y) / (current.y - previous.y) * (current.x - previous.x) {
+			inside = !inside;
+		}
+	}
+	inside
+}
+
+struct Face<S> {
+	points: Vec<DVec2>,
+	sources: Vec<EdgeSource<S>>,
+	/// The signed area of the face, which is positive for counterclockwise faces.
+	area: f64,
+}
+
+/// The vertices and edges formed by splitting a set of line segments at every point where they cross.
+struct PlanarGraph<S> {
+	vertices: Vec<DVec2>,
+	/// The neighbors of each vertex, sorted counterclockwise by angle.
+	neighbors: Vec<Vec<usize>>,
+	edge_sources: HashMap<(usize, usize), EdgeSource<S>>,
+}
+
+impl<S: Copy + PartialEq> PlanarGraph<S> {
+	fn new(segments: &[(DVec2, DVec2, EdgeSource<S>)]) -> Self {
+		// Find the parameters at which each segment is split by crossing other segments
+		let mut splits = vec![vec![0., 1.]; segments.len()];
+		let bounds = segments.iter().map(|&(start, end, _)| (start.min(end), start.max(end))).collect::<Vec<_>>();
+		for i in 0..segments.len() {
+			for j in (i + 1)..segments.len() {
+				let ((min_a, max_a), (min_b, max_b)) = (bounds[i], bounds[j]);
+				let margin = DVec2::splat(VERTEX_MERGE_DISTANCE);
+				if (min_a - margin).cmpgt(max_b).any() || (min_b - margin).cmpgt(max_a).any() {
+					continue;
+				}
+
+				for (t, u) in segment_intersections((segments[i].0, segments[i].1), (segments[j].0, segments[j].1)) {
+					splits[i].push(t);
+					splits[j].push(u);
+				}
+			}
+		}
+
+		let mut graph = Self {
+			vertices: Vec::new(),
+			neighbors: Vec::new(),
+			edge_sources: HashMap::new(),
+		};
+		let mut vertex_lookup = HashMap::new();
+		for (&(start, end, source), mut splits) in segments.iter().zip(splits) {
+			splits.sort_by(f64::total_cmp);
+			let vertices = splits.iter().map(|&t| graph.vertex(start.lerp(end, t), &mut vertex_lookup)).collect::<Vec<_>>();
+			for pair in vertices.windows(2) {
+				graph.add_edge(pair[0], pair[1], source);
+			}
+		}
+
+		graph.remove_dangling_edges();
+
+		for (vertex, neighbors) in graph.neighbors.iter_mut().enumerate() {
+			let position = graph.vertices[vertex];
+			let angle = |&neighbor: &usize| {
+				let direction = graph.vertices[neighbor] - position;
+				direction.y.atan2(direction.x)
+			};
+			neighbors.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+		}
+
+		graph
+	}
+
+	/// Finds the vertex at the position, or adds one if there isn't one there already.
+	fn vertex(&mut self, position: DVec2, lookup: &mut HashMap<(i64, i64), usize>) -> usize {
+		let cell = (position / VERTEX_MERGE_DISTANCE).floor();
+		let (x, y) = (cell.x as i64, cell.y as i64);
+
+		for neighbor_x in (x - 1)..=(x + 1) {
+			for neighbor_y in (y - 1)..=(y + 1) {
+				if let Some(&vertex) = lookup.get(&(neighbor_x, neighbor_y)) {
+					if self.vertices[vertex].distance(position) < VERTEX_MERGE_DISTANCE {
+						return vertex;
+					}
+				}
+			}
+		}
+
+		let vertex = self.vertices.len();
+		self.vertices.push(position);
+		self.neighbors.push(Vec::new());
+		lookup.insert((x, y), vertex);
+		vertex
+	}
+
+	fn add_edge(&mut self, a: usize, b: usize, source: EdgeSource<S>) {
+		if a == b || self.neighbors[a].contains(&b) {
+			return;
+		}
+		self.neighbors[a].push(b);
+		self.neighbors[b].push(a);
+		self.edge_sources.insert((a.min(b), a.max(b)), source);
+	}
+
+	/// Repeatedly removes edges leading to vertices with no other edges, since they can't be part of the boundary of an enclosed region.
+	fn remove_dangling_edges(&mut self) {
+		let mut dangling = (0..self.vertices.len()).filter(|&vertex| self.neighbors[vertex].len() == 1).collect::<Vec<_>>();
+		while let Some(vertex) = dangling.pop() {
+			let Some(neighbor) = self.neighbors[vertex].pop() else { continue };
+			self.neighbors[neighbor].retain(|&other| other != vertex);
+			self.edge_sources.remove(&(vertex.min(neighbor), vertex.max(neighbor)));
+			if self.neighbors[neighbor].len() == 1 {
+				dangling.push(neighbor);
+			}
+		}
+	}
+
+	/// Traces the faces of the graph by walking each directed edge, always turning onto the next edge clockwise around the vertex arrived at.
+	/// This traces each bounded face counterclockwise and the outer boundary of each connected component clockwise.
+	fn faces(&self) -> Vec<Face<S>> {
+		let mut visited = HashSet::new();
+		let mut faces = Vec::new();
+
+		for (start, neighbors) in self.neighbors.iter().enumerate() {
+			for &next in neighbors {
+				if visited.contains(&(start, next)) {
+					continue;
+				}
+
+				let mut face = Face {
+					points: Vec::new(),
+					sources: Vec::new(),
+					area: 0.,
+				};
+				let (mut from, mut to) = (start, next);
+				while visited.insert((from, to)) {
+					let (a, b) = (self.vertices[from], self.vertices[to]);
+					face.points.push(a);
+					face.area += a.perp_dot(b) / 2.;
+					if let Some(&source) = self.edge_sources.get(&(from.min(to), from.max(to))) {
+						if !face.sources.contains(&source) {
+							face.sources.push(source);
+						}
+					}
+
+					let around = &self.neighbors[to];
+					let arrived_from = around.iter().position(|&vertex| vertex == from).unwrap_or_default();
+					let turn_onto = around[(arrived_from + around.len() - 1) % around.len()];
+					(from, to) = (to, turn_onto);
+				}
+
+				if face.points.len() >= 3 {
+					faces.push(face);
+				}
+			}
+		}
+
+		faces
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::uuid::ManipulatorGroupId;
+
+	fn add_polygon(arrangement: &mut PlanarArrangement<u32>, source: u32, points: &[(f64, f64)], closed: bool) {
+		let subpath = Subpath::<ManipulatorGroupId>::from_anchors(points.iter().map(|&(x, y)| DVec2::new(x, y)), closed);
+		arrangement.add_subpath(source, &subpath, DAffine2::IDENTITY);
+	}
+
+	fn add_rectangle(arrangement: &mut PlanarArrangement<u32>, source: u32, min: (f64, f64), max: (f64, f64)) {
+		add_polygon(arrangement, source, &[min, (max.0, min.1), max, (min.0, max.1)], true);
+	}
+
+	fn area(polygon: &[DVec2]) -> f64 {
+		polygon.iter().zip(polygon.iter().cycle().skip(1)).map(|(a, b)| a.perp_dot(*b)).sum::<f64>().abs() / 2.
+	}
+
+	fn sorted(mut sources: Vec<u32>) -> Vec<u32> {
+		sources.sort_unstable();
+		sources
+	}
+
+	#[test]
+	fn overlapping_rectangles() {
+		let mut arrangement = PlanarArrangement::default();
+		add_rectangle(&mut arrangement, 1, (0., 0.), (10., 10.));
+		add_rectangle(&mut arrangement, 2, (5., 5.), (15., 15.));
+
+		let overlap = arrangement.region_at(DVec2::new(7., 7.), 0.).unwrap();
+		assert!((area(&overlap.boundary) - 25.).abs() < 1e-6);
+		assert_eq!(overlap.bounding_box(), [DVec2::new(5., 5.), DVec2::new(10., 10.)]);
+		assert!(overlap.holes.is_empty() && !overlap.bridges_gap);
+		assert_eq!(sorted(overlap.sources), [1, 2]);
+
+		// The part of the first rectangle outside the overlap is bounded by both rectangles
+		let remainder = arrangement.region_at(DVec2::new(2., 2.), 0.).unwrap();
+		assert!((area(&remainder.boundary) - 75.).abs() < 1e-6);
+		assert_eq!(sorted(remainder.sources), [1, 2]);
+
+		assert!(arrangement.region_at(DVec2::new(20., 2.), 0.).is_none());
+	}
+
+	#[test]
+	fn crossing_line_splits_region() {
+		let mut arrangement = PlanarArrangement::default();
+		add_rectangle(&mut arrangement, 1, (0., 0.), (10., 10.));
+		add_polygon(&mut arrangement, 2, &[(-5., 5.), (15., 5.)], false);
+
+		let region = arrangement.region_at(DVec2::new(5., 2.), 0.).unwrap();
+		assert!((area(&region.boundary) - 50.).abs() < 1e-6);
+		assert_eq!(sorted(region.sources), [1, 2]);
+	}
+
+	#[test]
+	fn concave_region() {
+		let mut arrangement = PlanarArrangement::default();
+		let u_shape = [(0., 0.), (30., 0.), (30., 30.), (20., 30.), (20., 10.), (10., 10.), (10., 30.), (0., 30.)];
+		add_polygon(&mut arrangement, 1, &u_shape, true);
+
+		let region = arrangement.region_at(DVec2::new(5., 20.), 0.).unwrap();
+		assert!((area(&region.boundary) - 700.).abs() < 1e-6);
+		assert_eq!(region.sources, [1]);
+
+		// The notch of the shape isn't enclosed
+		assert!(arrangement.region_at(DVec2::new(15., 20.), 0.).is_none());
+	}
+
+	#[test]
+	fn island_is_cut_out_as_hole() {
+		let mut arrangement = PlanarArrangement::default();
+		add_rectangle(&mut arrangement, 1, (0., 0.), (30., 30.));
+		add_rectangle(&mut arrangement, 2, (10., 10.), (20., 20.));
+
+		let region = arrangement.region_at(DVec2::new(5., 5.), 0.).unwrap();
+		assert!((area(&region.boundary) - 900.).abs() < 1e-6);
+		assert_eq!(region.holes.len(), 1);
+		assert!((area(&region.holes[0]) - 100.).abs() < 1e-6);
+		assert_eq!(sorted(region.sources), [1, 2]);
+
+		let island = arrangement.region_at(DVec2::new(15., 15.), 0.).unwrap();
+		assert!((area(&island.boundary) - 100.).abs() < 1e-6);
+		assert!(island.holes.is_empty());
+		assert_eq!(island.sources, [2]);
+	}
+
+	#[test]
+	fn gaps_are_bridged_within_tolerance() {
+		let mut arrangement = PlanarArrangement::default();
+		add_polygon(&mut arrangement, 1, &[(2., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 2.)], false);
+
+		assert!(arrangement.region_at(DVec2::new(5., 5.), 1.).is_none());
+		let region = arrangement.region_at(DVec2::new(5., 5.), 3.).unwrap();
+		assert!(region.bridges_gap);
+		assert_eq!(region.sources, [1]);
+	}
+}
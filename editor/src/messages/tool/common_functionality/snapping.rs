@@ -1,17 +1,18 @@
 mod grid_snapper;
+mod guide_snapper;
 mod layer_snapper;
 mod snap_results;
 use crate::consts::COLOR_OVERLAY_BLUE;
-use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::portfolio::document::overlays::utility_types::{outline_shape, snap_to_pixel, OverlayContext};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::portfolio::document::utility_types::misc::{BoundingBoxSnapTarget, GeometrySnapTarget, GridSnapTarget, SnapTarget};
 use crate::messages::prelude::*;
 use bezier_rs::{Subpath, TValue};
 use glam::{DAffine2, DVec2};
-use graphene_core::renderer::Quad;
+use graphene_core::renderer::{OverlayLayer, OverlayShape, Quad};
 use graphene_core::uuid::ManipulatorGroupId;
 use std::cmp::Ordering;
-pub use {grid_snapper::*, layer_snapper::*, snap_results::*};
+pub use {grid_snapper::*, guide_snapper::*, layer_snapper::*, snap_results::*};
 
 /// Handles snapping and snap overlays
 #[derive(Debug, Clone, Default)]
@@ -19,6 +20,7 @@ pub struct SnapManager {
 	indicator: Option<SnappedPoint>,
 	layer_snapper: LayerSnapper,
 	grid_snapper: GridSnapper,
+	guide_snapper: GuideSnapper,
 	candidates: Option<Vec<LayerNodeIdentifier>>,
 }
 
@@ -166,6 +168,15 @@ impl<'a> SnapData<'a> {
 			manipulators: Vec::new(),
 		}
 	}
+	/// The area of the document which is visible in the viewport
+	fn screen_bounds(&self) -> Quad {
+		self.document.metadata.document_to_viewport.inverse() * Quad::from_box([DVec2::ZERO, self.input.viewport_bounds.size()])
+	}
+	/// The artboards which are at least partly visible in the viewport
+	fn visible_artboards(&self) -> impl Iterator<Item = LayerNodeIdentifier> + '_ {
+		let metadata = &self.document.metadata;
+		metadata.layers_intersecting_bounds(self.screen_bounds().bounding_box()).filter(|&layer| metadata.is_artboard(layer))
+	}
 	fn get_candidates(&self) -> &[LayerNodeIdentifier] {
 		self.candidates.map_or([].as_slice(), |candidates| candidates.as_slice())
 	}
@@ -248,46 +259,34 @@ impl SnapManager {
 		let document = snap_data.document;
 		let offset = snap_tolerance(document);
 		let quad = bbox.map_or_else(|| Quad::from_box([point.document_point - offset, point.document_point + offset]), |quad| quad.inflate(offset));
-		let mut candidates = Vec::new();
+		let screen_bounds = snap_data.screen_bounds();
 
-		fn add_candidates(layer: LayerNodeIdentifier, snap_data: &SnapData, quad: Quad, candidates: &mut Vec<LayerNodeIdentifier>) {
-			let document = snap_data.document;
-			if candidates.len() > 10 {
-				return;
-			}
-			if !document.selected_nodes.layer_visible(layer, &document.metadata) {
-				return;
-			}
-			if snap_data.ignore.contains(&layer) {
-				return;
+		// The layers nearest to the snapped point or box are kept when there are too many to snap to all of them
+		let target = quad.center();
+		let mut candidates = Vec::new();
+		for layer in document.metadata.layers_intersecting_bounds(quad.bounding_box()) {
+			if document.metadata.is_folder(layer) || !document.selected_nodes.layer_visible(layer, &document.metadata) {
+				continue;
 			}
-			if document.metadata.is_folder(layer) {
-				for layer in layer.children(&document.metadata) {
-					add_candidates(layer, snap_data, quad, candidates);
-				}
-				return;
+			if layer.ancestors(&document.metadata).any(|ancestor| snap_data.ignore.contains(&ancestor)) {
+				continue;
 			}
 			let Some(bounds) = document.metadata.bounding_box_with_transform(layer, DAffine2::IDENTITY) else {
-				return;
+				continue;
 			};
 			let layer_bounds = document.metadata.transform_to_document(layer) * Quad::from_box(bounds);
-			let screen_bounds = document.metadata.document_to_viewport.inverse() * Quad::from_box([DVec2::ZERO, snap_data.input.viewport_bounds.size()]);
 			if quad.intersects(layer_bounds) && screen_bounds.intersects(layer_bounds) {
-				candidates.push(layer);
-			}
-		}
-
-		if let Some(root) = snap_data.document.network.get_root_node() {
-			if snap_data.document.network.nodes.get(&root.id).expect("Root should always be a node in find_candidates").is_layer {
-				add_candidates(LayerNodeIdentifier::new(root.id, &snap_data.document.network), snap_data, quad, &mut candidates);
+				let [min, max] = layer_bounds.bounding_box();
+				candidates.push((layer, target.clamp(min, max).distance_squared(target)));
 			}
 		}
 
 		if candidates.len() > 10 {
-			warn!("Snap candidate overflow");
+			candidates.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+			candidates.truncate(10);
 		}
 
-		candidates
+		candidates.into_iter().map(|(layer, _)| layer).collect()
 	}
 
 	pub fn free_snap(&mut self, snap_data: &SnapData, point: &SnapCandidatePoint, bbox: Option<Quad>, to_paths: bool) -> SnappedPoint {
@@ -305,6 +304,7 @@ impl SnapManager {
 		snap_data.candidates = Some(&*self.candidates.get_or_insert_with(|| Self::find_candidates(&snap_data, point, bbox)));
 		self.layer_snapper.free_snap(&mut snap_data, point, &mut snap_results);
 		self.grid_snapper.free_snap(&mut snap_data, point, &mut snap_results);
+		self.guide_snapper.free_snap(&mut snap_data, point, &mut snap_results);
 
 		Self::find_best_snap(&mut snap_data, point, snap_results, false, false, to_paths)
 	}
@@ -324,25 +324,46 @@ impl SnapManager {
 		snap_data.candidates = Some(&*self.candidates.get_or_insert_with(|| Self::find_candidates(&snap_data, point, bbox)));
 		self.layer_snapper.constrained_snap(&mut snap_data, point, &mut snap_results, constraint);
 		self.grid_snapper.constrained_snap(&mut snap_data, point, &mut snap_results, constraint);
+		self.guide_snapper.constrained_snap(&mut snap_data, point, &mut snap_results, constraint);
 
 		Self::find_best_snap(&mut snap_data, point, snap_results, true, false, false)
 	}
 
 	pub fn draw_overlays(&mut self, snap_data: SnapData, overlay_context: &mut OverlayContext) {
-		let to_viewport = snap_data.document.metadata.document_to_viewport;
-		if let Some(ind) = &self.indicator {
-			for curve in &ind.curves {
-				let Some(curve) = curve else { continue };
-				overlay_context.outline::<ManipulatorGroupId>([Subpath::from_bezier(curve)].iter(), to_viewport);
-			}
-			if let Some(quad) = ind.target_bounds {
-				overlay_context.quad(to_viewport * quad);
-			}
-			let viewport = to_viewport.transform_point2(ind.snapped_point_document);
+		overlay_context.draw_layer(&self.indicator_overlays(snap_data.document.metadata.document_to_viewport));
+	}
+
+	/// The shapes indicating the current snap, which are the snapped to curves and bounds, the snapped point, and a label naming what snapped to what.
+	pub fn indicator_overlays(&self, to_viewport: DAffine2) -> OverlayLayer {
+		let mut layer = OverlayLayer::default();
+		let Some(ind) = &self.indicator else { return layer };
 
-			overlay_context.text(&format!("{:?} to {:?}", ind.source, ind.target), viewport - DVec2::new(0., 5.), "rgba(0, 0, 0, 0.8)", 3.);
-			overlay_context.square(viewport, Some(4.), Some(COLOR_OVERLAY_BLUE), Some(COLOR_OVERLAY_BLUE));
+		let curves = ind.curves.iter().flatten().map(|&curve| Subpath::<ManipulatorGroupId>::from_bezier(&curve)).collect::<Vec<_>>();
+		if !curves.is_empty() {
+			layer.push(outline_shape(curves.iter(), to_viewport, COLOR_OVERLAY_BLUE));
 		}
+		if let Some(quad) = ind.target_bounds {
+			layer.push(OverlayShape::Polyline {
+				points: (to_viewport * quad).0.iter().map(|&point| snap_to_pixel(point)).collect(),
+				closed: true,
+				color: COLOR_OVERLAY_BLUE.into(),
+			});
+		}
+
+		let viewport = to_viewport.transform_point2(ind.snapped_point_document);
+		layer.push(OverlayShape::Text {
+			text: format!("{:?} to {:?}", ind.source, ind.target),
+			position: (viewport - DVec2::new(0., 5.)).round(),
+			background: "rgba(0, 0, 0, 0.8)".into(),
+			padding: 3.,
+		});
+		layer.push(OverlayShape::Square {
+			center: snap_to_pixel(viewport),
+			size: 4.,
+			fill: Some(COLOR_OVERLAY_BLUE.into()),
+			stroke: Some(COLOR_OVERLAY_BLUE.into()),
+		});
+		layer
 	}
 
 	/// Removes snap target data and overlays. Call this when snapping is done.
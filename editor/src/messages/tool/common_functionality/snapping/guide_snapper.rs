@@ -0,0 +1,108 @@
+use super::*;
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::guides::Guide;
+use crate::messages::portfolio::document::utility_types::misc::{GuideSnapTarget, SnapTarget};
+use bezier_rs::{Bezier, Subpath, TValue};
+use glam::DVec2;
+use graphene_core::renderer::Quad;
+use graphene_core::uuid::ManipulatorGroupId;
+
+struct GuideCurve {
+	target: SnapTarget,
+	start: ManipulatorGroupId,
+	document_curve: Bezier,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GuideSnapper;
+
+impl GuideSnapper {
+	// Guide lines are infinite, so they are cut off just beyond the edges of the viewport.
+	fn guide_curves(&self, snap_data: &SnapData) -> Vec<GuideCurve> {
+		let document = snap_data.document;
+		let viewport = Quad::from_box([DVec2::ZERO, snap_data.input.viewport_bounds.size()]);
+		let bounds = (document.metadata.document_to_viewport.inverse() * viewport).inflate(snap_tolerance(document)).bounding_box();
+
+		// Each curve needs a distinct ID so the curves of different guides can be intersected with each other
+		let mut next_id = ManipulatorGroupId::ZERO;
+		let mut curves = Vec::new();
+		for guide in &document.guides.guides {
+			let target = match guide {
+				Guide::Horizontal(_) | Guide::Vertical(_) => SnapTarget::Guide(GuideSnapTarget::Line),
+				Guide::Path(_) => SnapTarget::Guide(GuideSnapTarget::Path),
+			};
+			if !document.snapping_state.target_enabled(target) {
+				continue;
+			}
+
+			for document_curve in guide.curves(bounds) {
+				curves.push(GuideCurve {
+					target,
+					start: next_id.next_id(),
+					document_curve,
+				});
+			}
+		}
+		curves
+	}
+
+	pub fn free_snap(&mut self, snap_data: &mut SnapData, point: &SnapCandidatePoint, snap_results: &mut SnapResults) {
+		let tolerance = snap_tolerance(snap_data.document);
+
+		for guide in self.guide_curves(snap_data) {
+			let time = guide.document_curve.project(point.document_point);
+			let snapped_point_document = guide.document_curve.evaluate(TValue::Parametric(time));
+			let distance = snapped_point_document.distance(point.document_point);
+
+			if distance < tolerance {
+				snap_results.curves.push(SnappedCurve {
+					layer: LayerNodeIdentifier::ROOT_PARENT,
+					start: guide.start,
+					document_curve: guide.document_curve,
+					point: SnappedPoint {
+						snapped_point_document,
+						target: guide.target,
+						distance,
+						tolerance,
+						curves: [Some(guide.document_curve), None],
+						source: point.source,
+						..Default::default()
+					},
+				});
+			}
+		}
+	}
+
+	pub fn constrained_snap(&mut self, snap_data: &mut SnapData, point: &SnapCandidatePoint, snap_results: &mut SnapResults, constraint: SnapConstraint) {
+		let tolerance = snap_tolerance(snap_data.document);
+		let constraint_path = if let SnapConstraint::Circle { center, radius } = constraint {
+			Subpath::new_ellipse(center - DVec2::splat(radius), center + DVec2::splat(radius))
+		} else {
+			let constrained_point = constraint.projection(point.document_point);
+			let direction = constraint.direction().normalize_or_zero();
+			Subpath::<ManipulatorGroupId>::new_line(constrained_point - tolerance * direction, constrained_point + tolerance * direction)
+		};
+
+		for guide in self.guide_curves(snap_data) {
+			for constraint_curve in constraint_path.iter() {
+				for time in guide.document_curve.intersections(&constraint_curve, None, None) {
+					let snapped_point_document = guide.document_curve.evaluate(TValue::Parametric(time));
+					let distance = snapped_point_document.distance(point.document_point);
+
+					if distance < tolerance {
+						snap_results.points.push(SnappedPoint {
+							snapped_point_document,
+							target: guide.target,
+							distance,
+							tolerance,
+							curves: [Some(guide.document_curve), Some(constraint_curve)],
+							source: point.source,
+							at_intersection: true,
+							..Default::default()
+						});
+					}
+				}
+			}
+		}
+	}
+}
@@ -46,10 +46,7 @@ impl LayerSnapper {
 		let document = snap_data.document;
 		self.paths_to_snap.clear();
 
-		for layer in document.metadata.all_layers() {
-			if !document.metadata.is_artboard(layer) {
-				continue;
-			}
+		for layer in snap_data.visible_artboards() {
 			self.add_layer_bounds(document, layer, SnapTarget::Board(BoardSnapTarget::Edge));
 		}
 		for &layer in snap_data.get_candidates() {
@@ -168,10 +165,7 @@ impl LayerSnapper {
 		let document = snap_data.document;
 		self.points_to_snap.clear();
 
-		for layer in document.metadata.all_layers() {
-			if !document.metadata.is_artboard(layer) {
-				continue;
-			}
+		for layer in snap_data.visible_artboards() {
 			if document.snapping_state.target_enabled(SnapTarget::Board(BoardSnapTarget::Corner)) {
 				let Some(bounds) = document.metadata.bounding_box_with_transform(layer, DAffine2::IDENTITY) else {
 					continue;
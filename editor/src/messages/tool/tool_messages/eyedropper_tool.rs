@@ -1,5 +1,5 @@
 use super::tool_prelude::*;
-use crate::messages::tool::utility_types::DocumentToolData;
+use crate::messages::tool::utility_types::{ColorSampleTarget, DocumentToolData};
 
 #[derive(Default)]
 pub struct EyedropperTool {
@@ -80,7 +80,9 @@ impl Fsm for EyedropperToolFsmState {
 	type ToolOptions = ();
 
 	fn transition(self, event: ToolMessage, _tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, _tool_options: &(), responses: &mut VecDeque<Message>) -> Self {
-		let ToolActionHandlerData { global_tool_data, input, .. } = tool_action_data;
+		let ToolActionHandlerData {
+			document, global_tool_data, input, ..
+		} = tool_action_data;
 
 		let ToolMessage::Eyedropper(event) = event else {
 			return self;
@@ -88,7 +90,7 @@ impl Fsm for EyedropperToolFsmState {
 		match (self, event) {
 			// Ready -> Sampling
 			(EyedropperToolFsmState::Ready, mouse_down) if matches!(mouse_down, EyedropperToolMessage::SamplePrimaryColorBegin | EyedropperToolMessage::SampleSecondaryColorBegin) => {
-				update_cursor_preview(responses, input, global_tool_data, None);
+				update_cursor_preview(responses, input, global_tool_data);
 
 				if mouse_down == EyedropperToolMessage::SamplePrimaryColorBegin {
 					EyedropperToolFsmState::SamplingPrimary
@@ -99,7 +101,7 @@ impl Fsm for EyedropperToolFsmState {
 			// Sampling -> Sampling
 			(EyedropperToolFsmState::SamplingPrimary | EyedropperToolFsmState::SamplingSecondary, EyedropperToolMessage::PointerMove) => {
 				if input.viewport_bounds.in_bounds(input.mouse.position) {
-					update_cursor_preview(responses, input, global_tool_data, None);
+					update_cursor_preview(responses, input, global_tool_data);
 				} else {
 					disable_cursor_preview(responses);
 				}
@@ -108,10 +110,18 @@ impl Fsm for EyedropperToolFsmState {
 			}
 			// Sampling -> Ready
 			(EyedropperToolFsmState::SamplingPrimary, EyedropperToolMessage::SamplePrimaryColorEnd) | (EyedropperToolFsmState::SamplingSecondary, EyedropperToolMessage::SampleSecondaryColorEnd) => {
-				let set_color_choice = if self == EyedropperToolFsmState::SamplingPrimary { "Primary" } else { "Secondary" }.to_string();
-				update_cursor_preview(responses, input, global_tool_data, Some(set_color_choice));
+				update_cursor_preview(responses, input, global_tool_data);
 				disable_cursor_preview(responses);
 
+				// The color is sampled from a render of the document under the cursor, which is picked once the frontend rasterizes it
+				let target = if self == EyedropperToolFsmState::SamplingPrimary {
+					ColorSampleTarget::Primary
+				} else {
+					ColorSampleTarget::Secondary
+				};
+				let position = document.metadata.document_to_viewport.inverse().transform_point2(input.mouse.position);
+				responses.add(PortfolioMessage::SubmitColorSample { position, target });
+
 				EyedropperToolFsmState::Ready
 			}
 			// Any -> Ready
@@ -154,15 +164,13 @@ fn disable_cursor_preview(responses: &mut VecDeque<Message>) {
 		mouse_position: None,
 		primary_color: "".into(),
 		secondary_color: "".into(),
-		set_color_choice: None,
 	});
 }
 
-fn update_cursor_preview(responses: &mut VecDeque<Message>, input: &InputPreprocessorMessageHandler, global_tool_data: &DocumentToolData, set_color_choice: Option<String>) {
+fn update_cursor_preview(responses: &mut VecDeque<Message>, input: &InputPreprocessorMessageHandler, global_tool_data: &DocumentToolData) {
 	responses.add(FrontendMessage::UpdateEyedropperSamplingState {
 		mouse_position: Some(input.mouse.position.into()),
 		primary_color: "#".to_string() + global_tool_data.primary_color.rgb_hex().as_str(),
 		secondary_color: "#".to_string() + global_tool_data.secondary_color.rgb_hex().as_str(),
-		set_color_choice,
 	});
 }
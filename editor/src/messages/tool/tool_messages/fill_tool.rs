@@ -1,14 +1,32 @@
 use super::tool_prelude::*;
+use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::tool::common_functionality::planar_arrangement::{PlanarArrangement, PlanarRegion};
 
+use graph_craft::document::NodeId;
+use graphene_core::renderer::Quad;
+use graphene_core::uuid::generate_uuid;
 use graphene_core::vector::style::Fill;
 
 #[derive(Default)]
 pub struct FillTool {
 	fsm_state: FillToolFsmState,
+	options: FillOptions,
+}
+
+pub struct FillOptions {
+	/// The largest gap, in viewport pixels, between the open end of a path and another path which is bridged when detecting enclosed regions.
+	gap_tolerance: f64,
+}
+
+impl Default for FillOptions {
+	fn default() -> Self {
+		Self { gap_tolerance: 5. }
+	}
 }
 
 #[impl_message(Message, ToolMessage, Fill)]
-#[derive(PartialEq, Eq, Clone, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum FillToolMessage {
 	// Standard messages
 	Abort,
@@ -17,6 +35,12 @@ pub enum FillToolMessage {
 	PointerUp,
 	FillPrimaryColor,
 	FillSecondaryColor,
+	UpdateOptions(FillOptionsUpdate),
+}
+
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum FillOptionsUpdate {
+	GapTolerance(f64),
 }
 
 impl ToolMetadata for FillTool {
@@ -33,13 +57,29 @@ impl ToolMetadata for FillTool {
 
 impl LayoutHolder for FillTool {
 	fn layout(&self) -> Layout {
-		Layout::WidgetLayout(WidgetLayout::default())
+		let gap_tolerance = NumberInput::new(Some(self.options.gap_tolerance))
+			.unit(" px")
+			.label("Gap Tolerance")
+			.tooltip("Gaps up to this size between paths are closed when filling the region they enclose")
+			.min(0.)
+			.on_update(|number_input: &NumberInput| FillToolMessage::UpdateOptions(FillOptionsUpdate::GapTolerance(number_input.value.unwrap())).into())
+			.widget_holder();
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets: vec![gap_tolerance] }]))
 	}
 }
 
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for FillTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
-		self.fsm_state.process_event(message, &mut (), tool_data, &(), responses, true);
+		let ToolMessage::Fill(FillToolMessage::UpdateOptions(action)) = message else {
+			self.fsm_state.process_event(message, &mut (), tool_data, &self.options, responses, true);
+			return;
+		};
+		match action {
+			FillOptionsUpdate::GapTolerance(gap_tolerance) => self.options.gap_tolerance = gap_tolerance,
+		}
+
+		self.send_layout(responses, LayoutTarget::ToolOptions);
 	}
 	fn actions(&self) -> ActionList {
 		match self.fsm_state {
@@ -74,9 +114,9 @@ enum FillToolFsmState {
 
 impl Fsm for FillToolFsmState {
 	type ToolData = ();
-	type ToolOptions = ();
+	type ToolOptions = FillOptions;
 
-	fn transition(self, event: ToolMessage, _tool_data: &mut Self::ToolData, handler_data: &mut ToolActionHandlerData, _tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
+	fn transition(self, event: ToolMessage, _tool_data: &mut Self::ToolData, handler_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
 		let ToolActionHandlerData {
 			document, global_tool_data, input, ..
 		} = handler_data;
@@ -87,17 +127,25 @@ impl Fsm for FillToolFsmState {
 
 		match (self, event) {
 			(FillToolFsmState::Ready, color_event) => {
-				let Some(layer_identifier) = document.click(input.mouse.position, &document.network) else {
-					return self;
-				};
 				let fill = match color_event {
 					FillToolMessage::FillPrimaryColor => Fill::Solid(global_tool_data.primary_color),
 					FillToolMessage::FillSecondaryColor => Fill::Solid(global_tool_data.secondary_color),
 					_ => return self,
 				};
+				let clicked_layer = document.click(input.mouse.position, &document.network);
+				let region = enclosed_region(document, input.mouse.position, tool_options.gap_tolerance);
+				if clicked_layer.is_none() && region.is_none() {
+					return self;
+				}
 
 				responses.add(DocumentMessage::StartTransaction);
-				responses.add(GraphOperationMessage::FillSet { layer: layer_identifier, fill });
+				match (clicked_layer, region) {
+					// A region enclosed by the outline of the clicked layer alone is filled by filling the layer itself
+					(Some(layer), Some(region)) if region.sources == [layer] && !region.bridges_gap => responses.add(GraphOperationMessage::FillSet { layer, fill }),
+					(_, Some(region)) => fill_region(document, region, fill, responses),
+					(Some(layer), None) => responses.add(GraphOperationMessage::FillSet { layer, fill }),
+					(None, None) => {}
+				}
 				responses.add(DocumentMessage::CommitTransaction);
 
 				FillToolFsmState::Filling
@@ -128,3 +176,65 @@ impl Fsm for FillToolFsmState {
 		responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Default });
 	}
 }
+
+/// Finds the smallest region under the viewport location which is enclosed by the outlines of the visible layers, where the outlines of separate layers may cross to enclose it.
+///
+/// Only the layers whose bounds contain the location are arranged at first, since a region can't be enclosed without them.
+/// Any other layers overlapping the region found (or near enough to bridge a gap to it) may split it into a smaller region, so they're added and the region is found again until no more overlap it.
+fn enclosed_region(document: &DocumentMessageHandler, viewport_location: DVec2, gap_tolerance: f64) -> Option<PlanarRegion<LayerNodeIdentifier>> {
+	let metadata = document.metadata();
+	let viewport_to_document = metadata.document_to_viewport.inverse();
+	let fillable = |layer: &LayerNodeIdentifier| !metadata.is_folder(*layer) && !metadata.is_artboard(*layer) && document.selected_nodes.layer_visible(*layer, metadata);
+
+	let mut candidates = metadata
+		.layers_at_point(viewport_to_document.transform_point2(viewport_location))
+		.filter(fillable)
+		.collect::<HashSet<_>>();
+	loop {
+		let mut arrangement = PlanarArrangement::default();
+		for layer in metadata.all_layers().filter(|layer| candidates.contains(layer)) {
+			let transform = metadata.transform_to_viewport(layer);
+			for subpath in metadata.layer_outline(layer) {
+				arrangement.add_subpath(layer, subpath, transform);
+			}
+		}
+		let region = arrangement.region_at(viewport_location, gap_tolerance)?;
+
+		let [min, max] = region.bounding_box();
+		let margin = DVec2::splat(gap_tolerance.max(0.));
+		let bounds = (viewport_to_document * Quad::from_box([min - margin, max + margin])).bounding_box();
+		let overlapping = metadata
+			.layers_intersecting_bounds(bounds)
+			.filter(fillable)
+			.filter(|layer| !candidates.contains(layer))
+			.collect::<Vec<_>>();
+		if overlapping.is_empty() {
+			return Some(region);
+		}
+		candidates.extend(overlapping);
+	}
+}
+
+/// Creates a new layer with the shape of the region, placed directly below the lowest of the layers whose outlines enclose the region.
+fn fill_region(document: &DocumentMessageHandler, region: PlanarRegion<LayerNodeIdentifier>, fill: Fill, responses: &mut VecDeque<Message>) {
+	let metadata = document.metadata();
+	let lowest_layer = metadata.all_layers().filter(|layer| region.sources.contains(layer)).last();
+	let parent = lowest_layer.and_then(|layer| layer.parent(metadata)).unwrap_or_else(|| document.new_layer_parent(false));
+	let insert_index = lowest_layer
+		.and_then(|lowest_layer| parent.children(metadata).position(|child| child == lowest_layer))
+		.map_or(-1, |index| index as isize + 1);
+
+	// The region is stored in document space, so the layer's content stays the same regardless of the zoom level it was created at
+	let subpaths = region.to_subpaths(metadata.document_to_viewport.inverse());
+	let id = NodeId(generate_uuid());
+	responses.add(GraphOperationMessage::NewVectorLayer { id, subpaths, parent, insert_index });
+
+	let layer = LayerNodeIdentifier::new_unchecked(id);
+	responses.add(GraphOperationMessage::TransformSet {
+		layer,
+		transform: metadata.document_to_viewport,
+		transform_in: TransformIn::Viewport,
+		skip_rerender: false,
+	});
+	responses.add(GraphOperationMessage::FillSet { layer, fill });
+}
@@ -337,7 +337,7 @@ impl Fsm for GradientToolFsmState {
 					// If click is on the line then insert point
 					if distance < (SELECTION_THRESHOLD * 2.) {
 						// Try and insert the new stop
-						if let Some(index) = gradient.insert_stop(mouse, transform) {
+						if let Some(index) = gradient.insert_stop(mouse, transform, document.linear_compositing) {
 							document.backup_nonmut(responses);
 
 							let mut selected_gradient = SelectedGradient::new(gradient, layer, document);
@@ -486,7 +486,7 @@ impl Fsm for SelectToolFsmState {
 					overlay_context.quad(quad);
 				} else {
 					// Get the layer the user is hovering over
-					let click = document.click(input.mouse.position, &document.network);
+					let click = document.hover(input.mouse.position);
 					let not_selected_click = click.filter(|&hovered_layer| !document.selected_nodes.selected_layers_contains(hovered_layer, document.metadata()));
 					if let Some(layer) = not_selected_click {
 						overlay_context.outline(document.metadata().layer_outline(layer), document.metadata().transform_to_viewport(layer));
@@ -2,8 +2,10 @@
 
 use super::tool_prelude::*;
 use crate::application::generate_uuid;
+use crate::consts::{COLOR_OVERLAY_RED, DRAG_THRESHOLD};
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
+use crate::messages::portfolio::document::properties_panel::utility_types::TextSelection;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
 use crate::messages::tool::common_functionality::graph_modification_utils::{self, is_layer_fed_by_node_of_name};
@@ -11,7 +13,7 @@ use crate::messages::tool::common_functionality::graph_modification_utils::{self
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::NodeId;
 use graphene_core::renderer::Quad;
-use graphene_core::text::{load_face, Font, FontCache};
+use graphene_core::text::{load_face_with_variations, Font, FontCache, FontVariations, TextAlign, TextLayoutCache, TypesettingConfig};
 use graphene_core::vector::style::Fill;
 use graphene_core::Color;
 
@@ -26,6 +28,7 @@ pub struct TextOptions {
 	font_size: u32,
 	font_name: String,
 	font_style: String,
+	align: TextAlign,
 	fill: ToolColorOptions,
 }
 
@@ -35,6 +38,7 @@ impl Default for TextOptions {
 			font_size: 24,
 			font_name: graphene_core::consts::DEFAULT_FONT_FAMILY.into(),
 			font_style: graphene_core::consts::DEFAULT_FONT_STYLE.into(),
+			align: TextAlign::default(),
 			fill: ToolColorOptions::new_primary(),
 		}
 	}
@@ -50,15 +54,27 @@ pub enum TextToolMessage {
 
 	// Tool-specific messages
 	CommitText,
+	DragStart,
 	EditSelected,
 	Interact,
-	TextChange { new_text: String },
-	UpdateBounds { new_text: String },
+	PointerMove,
+	TextChange {
+		new_text: String,
+	},
+	UpdateBounds {
+		new_text: String,
+	},
 	UpdateOptions(TextOptionsUpdate),
+	/// The range of the text being edited that is selected, given by the indices of UTF-16 code units as counted by the browser.
+	UpdateSelection {
+		start: usize,
+		end: usize,
+	},
 }
 
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum TextOptionsUpdate {
+	Align(TextAlign),
 	FillColor(Option<Color>),
 	FillColorType(ToolColorType),
 	Font { family: String, style: String },
@@ -107,12 +123,24 @@ fn create_text_widgets(tool: &TextTool) -> Vec<WidgetHolder> {
 		.max((1_u64 << std::f64::MANTISSA_DIGITS) as f64)
 		.on_update(|number_input: &NumberInput| TextToolMessage::UpdateOptions(TextOptionsUpdate::FontSize(number_input.value.unwrap() as u32)).into())
 		.widget_holder();
+	let align_entries = TextAlign::list()
+		.into_iter()
+		.map(|align| {
+			RadioEntryData::new(format!("{align:?}"))
+				.label(align.to_string())
+				.tooltip("Alignment of the lines of each paragraph within the text box, which is set by dragging when placing text")
+				.on_update(move |_| TextToolMessage::UpdateOptions(TextOptionsUpdate::Align(align)).into())
+		})
+		.collect();
+	let align = RadioInput::new(align_entries).selected_index(Some(tool.options.align as u32)).widget_holder();
 	vec![
 		font,
 		Separator::new(SeparatorType::Related).widget_holder(),
 		style,
 		Separator::new(SeparatorType::Related).widget_holder(),
 		size,
+		Separator::new(SeparatorType::Related).widget_holder(),
+		align,
 	]
 }
 
@@ -148,6 +176,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for TextToo
 				self.send_layout(responses, LayoutTarget::ToolOptions);
 			}
 			TextOptionsUpdate::FontSize(font_size) => self.options.font_size = font_size,
+			TextOptionsUpdate::Align(align) => self.options.align = align,
 			TextOptionsUpdate::FillColor(color) => {
 				self.options.fill.custom_color = color;
 				self.options.fill.color_type = ToolColorType::Custom;
@@ -165,7 +194,13 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for TextToo
 	fn actions(&self) -> ActionList {
 		match self.fsm_state {
 			TextToolFsmState::Ready => actions!(TextToolMessageDiscriminant;
+				DragStart,
+				Interact,
+			),
+			TextToolFsmState::Dragging => actions!(TextToolMessageDiscriminant;
+				PointerMove,
 				Interact,
+				Abort,
 			),
 			TextToolFsmState::Editing => actions!(TextToolMessageDiscriminant;
 				Interact,
@@ -192,13 +227,16 @@ impl ToolTransition for TextTool {
 enum TextToolFsmState {
 	#[default]
 	Ready,
+	/// Dragging out the text box of new text, which the text is wrapped within.
+	Dragging,
 	Editing,
 }
 #[derive(Clone, Debug)]
 pub struct EditingText {
 	text: String,
 	font: Font,
-	font_size: f64,
+	typesetting: TypesettingConfig,
+	variations: FontVariations,
 	color: Option<Color>,
 	transform: DAffine2,
 }
@@ -208,6 +246,10 @@ struct TextToolData {
 	layer: LayerNodeIdentifier,
 	editing_text: Option<EditingText>,
 	new_text: String,
+	/// Where the drag for the text box of new text started, in viewport space.
+	drag_start: DVec2,
+	/// The words shaped for the overlay of the text being edited, so typing only shapes the word being typed.
+	layout_cache: TextLayoutCache,
 }
 
 impl TextToolData {
@@ -221,25 +263,29 @@ impl TextToolData {
 		if let Some(editing_text) = self.editing_text.as_ref().filter(|_| editable) {
 			responses.add(FrontendMessage::DisplayEditableTextbox {
 				text: editing_text.text.clone(),
-				line_width: None,
-				font_size: editing_text.font_size,
+				line_width: editing_text.typesetting.max_width,
+				font_size: editing_text.typesetting.font_size,
+				line_height_ratio: editing_text.typesetting.line_height_ratio,
+				align: editing_text.typesetting.align,
 				color: editing_text.color.unwrap_or(Color::BLACK),
 				url: font_cache.get_preview_url(&editing_text.font).cloned().unwrap_or_default(),
 				transform: editing_text.transform.to_cols_array(),
 			});
 		} else {
 			responses.add(FrontendMessage::DisplayRemoveEditableTextbox);
+			responses.add(PropertiesPanelMessage::SetTextSelection { selection: None });
 		}
 	}
 
 	fn load_layer_text_node(&mut self, document: &DocumentMessageHandler) -> Option<()> {
 		let transform = document.metadata().transform_to_viewport(self.layer);
 		let color = graph_modification_utils::get_fill_color(self.layer, &document.network).unwrap_or(Color::BLACK);
-		let (text, font, font_size) = graph_modification_utils::get_text(self.layer, &document.network)?;
+		let (text, font, typesetting) = graph_modification_utils::get_text(self.layer, &document.network)?;
 		self.editing_text = Some(EditingText {
 			text: text.clone(),
 			font: font.clone(),
-			font_size,
+			typesetting,
+			variations: graph_modification_utils::get_text_variations(self.layer, &document.network),
 			color: Some(color),
 			transform,
 		});
@@ -286,7 +332,9 @@ impl TextToolData {
 				id: self.layer.to_node(),
 				text: String::new(),
 				font: editing_text.font.clone(),
-				size: editing_text.font_size,
+				size: editing_text.typesetting.font_size,
+				box_size: DVec2::new(editing_text.typesetting.max_width.unwrap_or_default(), editing_text.typesetting.max_height.unwrap_or_default()),
+				align: editing_text.typesetting.align,
 				parent: document.new_layer_parent(true),
 				insert_index: -1,
 			});
@@ -316,13 +364,33 @@ impl TextToolData {
 
 	fn get_bounds(&self, text: &str, font_cache: &FontCache) -> Option<[DVec2; 2]> {
 		let editing_text = self.editing_text.as_ref()?;
-		let buzz_face = font_cache.get(&editing_text.font).map(|data| load_face(data));
-		let subpaths = graphene_core::text::to_path(text, buzz_face, editing_text.font_size, None);
+		let buzz_face = font_cache.get(&editing_text.font).and_then(|data| load_face_with_variations(data, &editing_text.variations));
+		let subpaths = graphene_core::text::to_path(text, buzz_face, editing_text.typesetting);
 		let bounds = subpaths.iter().filter_map(|subpath| subpath.bounding_box());
 		let combined_bounds = bounds.reduce(|a, b| [a[0].min(b[0]), a[1].max(b[1])]).unwrap_or_default();
 		Some(combined_bounds)
 	}
 
+	/// Writes the text edited so far to its Text node, with its style runs and kerned pairs moved to follow the edits, so they line up with the text while it's still being edited.
+	fn sync_text(&mut self, new_text: &str, document: &DocumentMessageHandler, font_cache: &FontCache, responses: &mut VecDeque<Message>) -> Option<()> {
+		let node_id = graph_modification_utils::get_text_id(self.layer, &document.network)?;
+		let old_text = self.editing_text.as_ref()?.text.clone();
+		if old_text == new_text {
+			return Some(());
+		}
+
+		self.fix_text_bounds(new_text, document, font_cache, responses);
+		responses.add(NodeGraphMessage::SetQualifiedInputValue {
+			node_id,
+			input_index: 1,
+			value: TaggedValue::String(new_text.to_string()),
+		});
+		move_styles_with_edit(self.layer, node_id, &old_text, new_text, document, responses);
+		self.editing_text.as_mut()?.text = new_text.to_string();
+
+		Some(())
+	}
+
 	fn fix_text_bounds(&self, new_text: &str, _document: &DocumentMessageHandler, font_cache: &FontCache, responses: &mut VecDeque<Message>) -> Option<()> {
 		responses.add(GraphOperationMessage::UpdateBounds {
 			layer: self.layer,
@@ -334,6 +402,61 @@ impl TextToolData {
 	}
 }
 
+/// Finds the edit that turned the old text into the new text from the start and end that the texts have in common,
+/// given as the start of the edit, the end of the text it removed, and the length of the text it inserted.
+fn text_edit(old_text: &str, new_text: &str) -> (usize, usize, usize) {
+	let prefix = old_text.chars().zip(new_text.chars()).take_while(|(old, new)| old == new).map(|(old, _)| old.len_utf8()).sum::<usize>();
+	let suffix = old_text[prefix..]
+		.chars()
+		.rev()
+		.zip(new_text[prefix..].chars().rev())
+		.take_while(|(old, new)| old == new)
+		.map(|(old, _)| old.len_utf8())
+		.sum::<usize>();
+	(prefix, old_text.len() - suffix, new_text.len() - prefix - suffix)
+}
+
+/// Moves the style runs and the kerned pairs of the Text node to follow the edit that turned the old text into the new text, for those the layer has.
+fn move_styles_with_edit(layer: LayerNodeIdentifier, node_id: NodeId, old_text: &str, new_text: &str, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+	let (start, removed_end, inserted_length) = text_edit(old_text, new_text);
+
+	let mut runs = graph_modification_utils::get_text_runs(layer, &document.network);
+	if !runs.is_empty() {
+		runs.edited(start, removed_end, inserted_length);
+		responses.add(NodeGraphMessage::SetQualifiedInputValue {
+			node_id,
+			input_index: 10,
+			value: TaggedValue::TextRuns(runs),
+		});
+	}
+	let mut kerning = graph_modification_utils::get_text_kerning(layer, &document.network);
+	if !kerning.pairs.is_empty() {
+		kerning.edited(start, removed_end, inserted_length);
+		responses.add(NodeGraphMessage::SetQualifiedInputValue {
+			node_id,
+			input_index: 11,
+			value: TaggedValue::TextKerning(kerning),
+		});
+	}
+}
+
+/// Converts an index counted in UTF-16 code units, as used by the browser, into a byte index of the text.
+pub fn utf16_to_byte_index(text: &str, utf16_index: usize) -> usize {
+	let mut units = 0;
+	for (index, character) in text.char_indices() {
+		if units >= utf16_index {
+			return index;
+		}
+		units += character.len_utf16();
+	}
+	text.len()
+}
+
+/// Marks text which overflows its text box with a red square at the bottom right corner of the box.
+fn overflow_indicator(overlay_context: &mut OverlayContext, transform: DAffine2, size: DVec2) {
+	overlay_context.square(transform.transform_point2(size), Some(8.), Some(COLOR_OVERLAY_RED), Some(COLOR_OVERLAY_RED));
+}
+
 fn can_edit_selected(document: &DocumentMessageHandler) -> Option<LayerNodeIdentifier> {
 	let mut selected_layers = document.selected_nodes.selected_layers(document.metadata());
 	let layer = selected_layers.next()?;
@@ -371,42 +494,78 @@ impl Fsm for TextToolFsmState {
 					transform: document.metadata().transform_to_viewport(tool_data.layer).to_cols_array(),
 				});
 				if let Some(editing_text) = tool_data.editing_text.as_ref() {
-					let buzz_face = font_cache.get(&editing_text.font).map(|data| load_face(data));
-					let far = graphene_core::text::bounding_box(&tool_data.new_text, buzz_face, editing_text.font_size, None);
-					if far.x != 0. && far.y != 0. {
-						let quad = Quad::from_box([DVec2::ZERO, far]);
-						let transformed_quad = document.metadata().transform_to_viewport(tool_data.layer) * quad;
-						overlay_context.quad(transformed_quad);
+					if let Some(buzz_face) = font_cache.get(&editing_text.font).and_then(|data| load_face_with_variations(data, &editing_text.variations)) {
+						let layout = tool_data.layout_cache.layout(&tool_data.new_text, &buzz_face, &editing_text.font, editing_text.typesetting);
+						if layout.size.x != 0. && layout.size.y != 0. {
+							let transform = document.metadata().transform_to_viewport(tool_data.layer);
+							overlay_context.quad(transform * Quad::from_box([DVec2::ZERO, layout.size]));
+							if layout.overflows {
+								overflow_indicator(&mut overlay_context, transform, layout.size);
+							}
+						}
 					}
 				}
 
 				TextToolFsmState::Editing
 			}
+			(TextToolFsmState::Dragging, TextToolMessage::Overlays(mut overlay_context)) => {
+				overlay_context.quad(Quad::from_box([tool_data.drag_start, input.mouse.position]));
+
+				TextToolFsmState::Dragging
+			}
 			(_, TextToolMessage::Overlays(mut overlay_context)) => {
 				for layer in document.selected_nodes.selected_layers(document.metadata()) {
-					let Some((text, font, font_size)) = graph_modification_utils::get_text(layer, &document.network) else {
+					let Some((text, font, typesetting)) = graph_modification_utils::get_text(layer, &document.network) else {
+						continue;
+					};
+					let variations = graph_modification_utils::get_text_variations(layer, &document.network);
+					let Some(buzz_face) = font_cache.get(font).and_then(|data| load_face_with_variations(data, &variations)) else {
 						continue;
 					};
-					let buzz_face = font_cache.get(font).map(|data| load_face(data));
-					let far = graphene_core::text::bounding_box(text, buzz_face, font_size, None);
-					let quad = Quad::from_box([DVec2::ZERO, far]);
-					let multiplied = document.metadata().transform_to_viewport(layer) * quad;
-					overlay_context.quad(multiplied);
+					let layout = graphene_core::text::layout_text(text, &buzz_face, typesetting);
+					let transform = document.metadata().transform_to_viewport(layer);
+					overlay_context.quad(transform * Quad::from_box([DVec2::ZERO, layout.size]));
+					if layout.overflows {
+						overflow_indicator(&mut overlay_context, transform, layout.size);
+					}
 				}
 
 				self
 			}
+			(TextToolFsmState::Ready, TextToolMessage::DragStart) => {
+				tool_data.drag_start = input.mouse.position;
+
+				TextToolFsmState::Dragging
+			}
+			(TextToolFsmState::Dragging, TextToolMessage::PointerMove) => {
+				responses.add(OverlaysMessage::Draw);
+
+				TextToolFsmState::Dragging
+			}
 			(state, TextToolMessage::Interact) => {
+				// Dragging out a box gives the text a box it's wrapped within, while clicking places text that only breaks lines at new lines
+				let dragged = state == TextToolFsmState::Dragging && tool_data.drag_start.distance(input.mouse.position) > DRAG_THRESHOLD;
+				let [start, end] = [tool_data.drag_start.min(input.mouse.position), tool_data.drag_start.max(input.mouse.position)];
+				let (position, box_size) = if dragged { (start, Some(end - start)) } else { (input.mouse.position, None) };
+
 				tool_data.editing_text = Some(EditingText {
 					text: String::new(),
-					transform: DAffine2::from_translation(input.mouse.position),
-					font_size: tool_options.font_size as f64,
+					transform: DAffine2::from_translation(position),
+					typesetting: TypesettingConfig {
+						font_size: tool_options.font_size as f64,
+						max_width: box_size.map(|size| size.x),
+						max_height: box_size.map(|size| size.y),
+						align: tool_options.align,
+						..Default::default()
+					},
 					font: Font::new(tool_options.font_name.clone(), tool_options.font_style.clone()),
+					variations: FontVariations::default(),
 					color: tool_options.fill.active_color(),
 				});
 				tool_data.new_text = String::new();
 
-				tool_data.interact(state, input.mouse.position, document, font_cache, responses)
+				let state = if state == TextToolFsmState::Dragging { TextToolFsmState::Ready } else { state };
+				tool_data.interact(state, position, document, font_cache, responses)
 			}
 			(state, TextToolMessage::EditSelected) => {
 				if let Some(layer) = can_edit_selected(document) {
@@ -416,6 +575,11 @@ impl Fsm for TextToolFsmState {
 
 				state
 			}
+			(TextToolFsmState::Dragging, TextToolMessage::Abort) => {
+				responses.add(OverlaysMessage::Draw);
+
+				TextToolFsmState::Ready
+			}
 			(state, TextToolMessage::Abort) => {
 				if state == TextToolFsmState::Editing {
 					tool_data.set_editing(false, font_cache, document, responses);
@@ -430,8 +594,12 @@ impl Fsm for TextToolFsmState {
 			}
 			(TextToolFsmState::Editing, TextToolMessage::TextChange { new_text }) => {
 				tool_data.fix_text_bounds(&new_text, document, font_cache, responses);
+				let node_id = graph_modification_utils::get_text_id(tool_data.layer, &document.network).unwrap();
+				if let Some(editing_text) = tool_data.editing_text.as_ref() {
+					move_styles_with_edit(tool_data.layer, node_id, &editing_text.text, &new_text, document, responses);
+				}
 				responses.add(NodeGraphMessage::SetQualifiedInputValue {
-					node_id: graph_modification_utils::get_text_id(tool_data.layer, &document.network).unwrap(),
+					node_id,
 					input_index: 1,
 					value: TaggedValue::String(new_text),
 				});
@@ -445,6 +613,19 @@ impl Fsm for TextToolFsmState {
 				responses.add(OverlaysMessage::Draw);
 				TextToolFsmState::Editing
 			}
+			(TextToolFsmState::Editing, TextToolMessage::UpdateSelection { start, end }) => {
+				// The runs are indexed by the text of the node, so the edits made so far are written to it before the selection can be styled
+				let new_text = tool_data.new_text.clone();
+				tool_data.sync_text(&new_text, document, font_cache, responses);
+
+				let selection = graph_modification_utils::get_text_id(tool_data.layer, &document.network).map(|node_id| TextSelection {
+					node_id,
+					start: utf16_to_byte_index(&new_text, start.min(end)),
+					end: utf16_to_byte_index(&new_text, start.max(end)),
+				});
+				responses.add(PropertiesPanelMessage::SetTextSelection { selection });
+				TextToolFsmState::Editing
+			}
 			(_, TextToolMessage::WorkingColorChanged) => {
 				responses.add(TextToolMessage::UpdateOptions(TextOptionsUpdate::WorkingColors(
 					Some(global_tool_data.primary_color),
@@ -460,8 +641,10 @@ impl Fsm for TextToolFsmState {
 		let hint_data = match self {
 			TextToolFsmState::Ready => HintData(vec![
 				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Place Text")]),
+				HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Place Text Box")]),
 				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Edit Text")]),
 			]),
+			TextToolFsmState::Dragging => HintData(vec![HintGroup(vec![HintInfo::keys([Key::Escape], "Cancel")])]),
 			TextToolFsmState::Editing => HintData(vec![
 				HintGroup(vec![HintInfo::keys([Key::Escape], "Discard Changes")]),
 				HintGroup(vec![HintInfo::keys([Key::Control, Key::Enter], "Commit Changes").add_mac_keys([Key::Command, Key::Enter])]),
@@ -6,9 +6,10 @@ use crate::messages::tool::common_functionality::graph_modification_utils;
 use crate::messages::tool::common_functionality::shape_editor::ShapeState;
 use crate::messages::tool::utility_types::{ToolData, ToolType};
 
+use graphene_core::units::DocumentUnits;
 use graphene_core::vector::ManipulatorPointId;
 
-use glam::DVec2;
+use glam::{DAffine2, DVec2};
 
 #[derive(Debug, Clone, Default)]
 pub struct TransformLayerMessageHandler {
@@ -23,6 +24,10 @@ pub struct TransformLayerMessageHandler {
 
 	original_transforms: OriginalTransforms,
 	pivot: DVec2,
+
+	/// The units and view of the document as of the last message, for the hints shown without the document at hand.
+	units: DocumentUnits,
+	document_to_viewport: DAffine2,
 }
 
 impl TransformLayerMessageHandler {
@@ -36,7 +41,7 @@ impl TransformLayerMessageHandler {
 			TransformOperation::Scaling(scaling) => scaling.constraint,
 			_ => Axis::Both,
 		};
-		self.transform_operation.hints(self.snap, axis_constraint, responses);
+		self.transform_operation.hints(self.snap, axis_constraint, self.units, self.document_to_viewport, responses);
 	}
 }
 
@@ -44,6 +49,8 @@ type TransformData<'a> = (&'a DocumentMessageHandler, &'a InputPreprocessorMessa
 impl<'a> MessageHandler<TransformLayerMessage, TransformData<'a>> for TransformLayerMessageHandler {
 	fn process_message(&mut self, message: TransformLayerMessage, responses: &mut VecDeque<Message>, (document, input, tool_data, shape_editor): TransformData) {
 		let using_path_tool = tool_data.active_tool_type == ToolType::Path;
+		self.units = document.units;
+		self.document_to_viewport = document.metadata().document_to_viewport;
 
 		let selected_layers = document
 			.selected_nodes
@@ -61,6 +68,7 @@ impl<'a> MessageHandler<TransformLayerMessage, TransformData<'a>> for TransformL
 			Some(shape_editor),
 			&tool_data.active_tool_type,
 		);
+		selected.units = document.units;
 
 		let mut begin_operation = |operation: TransformOperation, typing: &mut Typing, mouse_position: &mut DVec2, start_mouse: &mut DVec2| {
 			if operation != TransformOperation::None {
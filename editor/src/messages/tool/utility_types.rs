@@ -127,6 +127,13 @@ pub trait Fsm {
 	}
 }
 
+/// Which of the working colors a color sampled from the canvas is picked as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum ColorSampleTarget {
+	Primary,
+	Secondary,
+}
+
 #[derive(Debug, Clone)]
 pub struct DocumentToolData {
 	pub primary_color: Color,
@@ -1,27 +1,44 @@
-use crate::consts::FILE_SAVE_SUFFIX;
+use crate::application::GRAPHITE_GIT_COMMIT_HASH;
+use crate::consts::{COLOR_OVERLAY_RED, DEFAULT_CACHE_MEMORY_BUDGET, FILE_SAVE_SUFFIX, NODE_OUTPUT_THUMBNAIL_SIZE};
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
-use crate::messages::portfolio::document::node_graph::document_node_types::wrap_network_in_scope;
+use crate::messages::portfolio::animation_export::{AnimationExportOptions, AnimationFormat};
+use crate::messages::portfolio::document::node_graph::document_node_types::{resolve_document_node_type, wrap_network_in_scope};
+use crate::messages::portfolio::document::node_graph::utility_types::{FrontendFrameProfile, FrontendNodeEvaluation, FrontendNodeProfile};
+use crate::messages::portfolio::document::utility_types::document_info::{escape_xml, DocumentInfo};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::icc_profile;
+use crate::messages::portfolio::icon_set::{self, IconSetOptions};
+use crate::messages::portfolio::lottie_export;
+use crate::messages::portfolio::poster_export::{PosterLayout, PosterOptions};
+use crate::messages::portfolio::tiff_export::TiffExport;
 use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ColorSampleTarget;
 
 use graph_craft::concrete;
 use graph_craft::document::value::TaggedValue;
-use graph_craft::document::{generate_uuid, DocumentNodeImplementation, NodeId, NodeNetwork};
+use graph_craft::document::{generate_uuid, DocumentNodeImplementation, NodeId, NodeInput, NodeNetwork, Source};
 use graph_craft::graphene_compiler::Compiler;
 use graph_craft::imaginate_input::ImaginatePreferences;
-use graph_craft::proto::GraphErrors;
+use graph_craft::proto::{ConstructionArgs, GraphErrors, NodeError, ProtoNetwork, PLUGIN_NODE_PREFIX};
 use graphene_core::application_io::{NodeGraphUpdateMessage, NodeGraphUpdateSender, RenderConfig};
 use graphene_core::memo::IORecord;
 use graphene_core::raster::ImageFrame;
-use graphene_core::renderer::{ClickTarget, GraphicElementRendered, ImageRenderMode, RenderParams, SvgRender};
+use graphene_core::renderer::{
+	ClickTarget, GraphicElementRendered, ImageExportPolicy, ImagePreviewCache, ImageRenderMode, LottieShape, OnionSkinFrame, OutputPalette, OutputProfile, RenderParams, SoftProof, SvgOptimization,
+	SvgRender,
+};
 use graphene_core::renderer::{RenderSvgSegmentList, SvgSegment};
 use graphene_core::text::FontCache;
-use graphene_core::transform::{Footprint, Transform};
+use graphene_core::transform::{Footprint, RenderTarget, Transform};
 use graphene_core::vector::style::ViewMode;
 use graphene_core::vector::VectorData;
 use graphene_core::{Color, GraphicElement, SurfaceFrame};
-use graphene_std::wasm_application_io::{WasmApplicationIo, WasmEditorApi};
+use graphene_std::wasm_application_io::{RenderOutput, WasmApplicationIo, WasmEditorApi};
 use interpreted_executor::dynamic_executor::{DynamicExecutor, ResolvedDocumentNodeTypes};
+use interpreted_executor::evaluation_cache::EvaluationCacheChanges;
+use interpreted_executor::memory_budget::MemoryUsage;
+use interpreted_executor::node_outputs::InspectedValue;
+use interpreted_executor::profiling::FrameProfile;
 
 use glam::{DAffine2, DVec2, UVec2};
 use std::cell::RefCell;
@@ -44,6 +61,10 @@ pub struct NodeRuntime {
 	font_cache: FontCache,
 	/// Imaginate preferences made available to the graph through the [`WasmEditorApi`].
 	imaginate_preferences: ImaginatePreferences,
+	/// The rasterized frames of the animation drawn beneath the artwork, made available to the graph through the [`WasmEditorApi`].
+	onion_skin: Arc<[OnionSkinFrame]>,
+	/// The encodings of the images drawn in the viewport, made available to the graph through the [`WasmEditorApi`].
+	image_previews: ImagePreviewCache,
 
 	/// Gives access to APIs like a rendering surface (native window handle or HTML5 canvas) and WGPU (which becomes WebGPU on web).
 	wasm_application_io: Option<WasmApplicationIo>,
@@ -57,8 +78,117 @@ pub struct NodeRuntime {
 	thumbnail_renders: HashMap<NodeId, Vec<SvgSegment>>,
 	/// The current click targets for layer nodes.
 	click_targets: HashMap<NodeId, Vec<ClickTarget>>,
+	/// The layer nodes whose text doesn't fit its text box, as reported by the layout of the Text node.
+	overflowing_text: HashSet<NodeId>,
 	/// The current upstream transforms for nodes.
 	upstream_transforms: HashMap<NodeId, (Footprint, DAffine2)>,
+	/// The thumbnails of the output of every node while they're shown, by the path to the node, along with the hash of the output each was rendered from.
+	node_thumbnails: HashMap<Vec<NodeId>, (u64, String)>,
+	/// Whether any of the node thumbnails changed since they were last sent to the editor.
+	node_thumbnails_changed: bool,
+	/// A compilation of the graph loaded with a document, used instead of compiling the graph if it has the same hash.
+	cached_compilation: Option<CompiledGraph>,
+	/// The graph which was just compiled, to be saved with the document.
+	new_compilation: Option<CompiledGraph>,
+}
+
+/// A node graph compiled into a proto network, which is saved with the document so it doesn't need to be compiled again when the document is reopened.
+/// The values of the proto network's value nodes aren't saved, since the graph already stores them, so they're filled back in from the graph's values with the same hash.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CompiledGraph {
+	/// The hash of the graph together with the fonts and the editor build that compiled it, since another build may compile the same graph differently.
+	pub graph_hash: u64,
+	/// The proto network with the value of each value node replaced by [`TaggedValue::None`].
+	pub proto_network: ProtoNetwork,
+	/// The index of each value node in the proto network, with the hash of the value it had.
+	#[serde(default)]
+	pub values: Vec<(usize, u64)>,
+	pub monitor_nodes: Vec<Vec<NodeId>>,
+}
+
+impl CompiledGraph {
+	fn new(graph_hash: u64, mut proto_network: ProtoNetwork, monitor_nodes: Vec<Vec<NodeId>>) -> Self {
+		let values = proto_network
+			.nodes
+			.iter_mut()
+			.enumerate()
+			.filter_map(|(index, (_, node))| {
+				let ConstructionArgs::Value(value) = &mut node.construction_args else { return None };
+				Some((index, value_hash(&std::mem::replace(value, TaggedValue::None))))
+			})
+			.collect();
+
+		Self {
+			graph_hash,
+			proto_network,
+			values,
+			monitor_nodes,
+		}
+	}
+
+	/// Fills the values back into the proto network from the values of the network it was compiled from, or returns `None` if the network is missing any of them.
+	fn restore(&self, network: &NodeNetwork) -> Option<ProtoNetwork> {
+		let mut network_values = HashMap::new();
+		collect_values(network, &mut network_values);
+
+		let mut proto_network = self.proto_network.clone();
+		for &(index, hash) in &self.values {
+			let (_, node) = proto_network.nodes.get_mut(index)?;
+			node.construction_args = ConstructionArgs::Value((*network_values.get(&hash)?).clone());
+		}
+		Some(proto_network)
+	}
+}
+
+fn value_hash(value: &TaggedValue) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	value.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// Collects the values of the inputs and exports of the network and the networks nested within it, by their hash.
+fn collect_values<'a>(network: &'a NodeNetwork, values: &mut HashMap<u64, &'a TaggedValue>) {
+	let inputs = network.exports.iter().chain(network.nodes.values().flat_map(|node| &node.inputs));
+	for value in inputs.filter_map(NodeInput::as_value) {
+		values.insert(value_hash(value), value);
+	}
+	for node in network.nodes.values() {
+		if let DocumentNodeImplementation::Network(nested_network) = &node.implementation {
+			collect_values(nested_network, values);
+		}
+	}
+}
+
+/// The hash which a compilation of the graph is saved with, which is deterministic so a compilation saved with a document is found again when it's reopened.
+fn compilation_hash(graph: &NodeNetwork, font_hash: u64) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	font_hash.hash(&mut hasher);
+	graph.hash(&mut hasher);
+	GRAPHITE_GIT_COMMIT_HASH.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// The outputs of the cache nodes of a graph, like traced vectors, decoded images, and rendered noise, which are saved with the document so reopening it doesn't need to evaluate
+/// the expensive nodes behind the caches again. Each output is keyed by the stable ID of its cache node, which is a hash of everything upstream of the cache.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SavedEvaluationCache {
+	/// The commit of the editor build that evaluated the nodes, since the nodes of another build may output something different.
+	pub build: String,
+	pub outputs: Vec<(NodeId, TaggedValue)>,
+}
+
+impl SavedEvaluationCache {
+	/// Updates the saved outputs with those recorded and dropped by the runtime, starting over if they were saved by another build.
+	fn apply(&mut self, changes: EvaluationCacheChanges) {
+		if self.build != GRAPHITE_GIT_COMMIT_HASH {
+			self.build = GRAPHITE_GIT_COMMIT_HASH.to_string();
+			self.outputs.clear();
+		}
+
+		let EvaluationCacheChanges { recorded, removed } = changes;
+		self.outputs.retain(|(node, _)| !removed.contains(node) && !recorded.iter().any(|(recorded, _)| recorded == node));
+		self.outputs.extend(recorded);
+	}
 }
 
 /// Messages passed from the editor thread to the node runtime thread.
@@ -66,6 +196,13 @@ enum NodeRuntimeMessage {
 	ExecutionRequest(ExecutionRequest),
 	FontCacheUpdate(FontCache),
 	ImaginatePreferencesUpdate(ImaginatePreferences),
+	OnionSkinUpdate(Arc<[OnionSkinFrame]>),
+	ProfilingUpdate(bool),
+	NodeThumbnailsUpdate(bool),
+	InspectedNodeUpdate(Option<Vec<NodeId>>),
+	CompiledGraphUpdate(CompiledGraph),
+	EvaluationCacheUpdate(SavedEvaluationCache),
+	MemoryBudgetUpdate(Option<usize>),
 }
 
 #[derive(Default, Debug, Clone)]
@@ -75,6 +212,26 @@ pub struct ExportConfig {
 	pub scale_factor: f64,
 	pub bounds: ExportBounds,
 	pub transparent_background: bool,
+	/// Export one file for each variant of the document's variables instead of only the active variant.
+	pub all_variants: bool,
+	/// How the SVG is optimized when exporting an SVG file, if at all.
+	pub svg_optimization: Option<SvgOptimization>,
+	/// How images are resized, encoded, and embedded or linked when exporting an SVG file.
+	pub image_export: Option<ImageExportPolicy>,
+	/// The palette that the colors of the export are limited to, if any.
+	pub palette: Option<OutputPalette>,
+	/// The padding and sharpening of each icon when exporting an icon set.
+	pub icon_set: IconSetOptions,
+	/// The paper, tiling, and marks of the pages when exporting a poster.
+	pub poster: PosterOptions,
+	/// The time in the document's animation to export, instead of the time currently shown in the document.
+	pub time: Option<f64>,
+	/// The time range, frame rate, and format of the frames when exporting an animation.
+	pub animation: AnimationExportOptions,
+	/// The title, author, and other info of the document, which is embedded into the exported file.
+	pub info: DocumentInfo,
+	/// The printing condition whose CMYK inks the colors of a TIFF or poster export are separated into, if it isn't RGB.
+	pub output_profile: OutputProfile,
 	pub size: DVec2,
 }
 
@@ -90,9 +247,22 @@ pub(crate) struct ExecutionResponse {
 	responses: VecDeque<Message>,
 	new_click_targets: HashMap<LayerNodeIdentifier, Vec<ClickTarget>>,
 	new_upstream_transforms: HashMap<NodeId, (Footprint, DAffine2)>,
+	overflowing_text: HashSet<LayerNodeIdentifier>,
 	resolved_types: ResolvedDocumentNodeTypes,
 	node_graph_errors: GraphErrors,
 	transform: DAffine2,
+	/// The time taken by each node, if profiling is enabled.
+	profile: Option<FrameProfile>,
+	/// The newly compiled graph, if the graph had changed since the last execution.
+	compiled_graph: Option<CompiledGraph>,
+	/// The thumbnails of the output of every node by the path to the node, if they changed since the last execution.
+	node_thumbnails: Option<HashMap<Vec<NodeId>, String>>,
+	/// What the inspected node output, if this execution rendered the document as it's shown rather than for an export.
+	inspection: Option<Option<InspectedValue>>,
+	/// The outputs of the cache nodes recorded and dropped since the last execution which rendered the document as it's shown, if there are any.
+	evaluation_cache_changes: Option<EvaluationCacheChanges>,
+	/// The memory held by the outputs of the cache nodes once the least recently used were cleared to fit within the budget.
+	memory_usage: MemoryUsage,
 }
 
 enum NodeGraphUpdate {
@@ -120,13 +290,19 @@ thread_local! {
 
 impl NodeRuntime {
 	fn new(receiver: Receiver<NodeRuntimeMessage>, sender: Sender<NodeGraphUpdate>) -> Self {
+		let executor = DynamicExecutor::default();
+		// The preferences may not have been loaded yet, so the caches are kept within the default budget until they are
+		executor.set_memory_budget(memory_budget_bytes(DEFAULT_CACHE_MEMORY_BUDGET));
+
 		Self {
-			executor: DynamicExecutor::default(),
+			executor,
 			receiver,
 			sender: InternalNodeGraphUpdateSender(sender),
 
 			font_cache: FontCache::default(),
 			imaginate_preferences: Default::default(),
+			onion_skin: Vec::new().into(),
+			image_previews: ImagePreviewCache::default(),
 
 			wasm_application_io: None,
 			graph_hash: None,
@@ -136,7 +312,12 @@ impl NodeRuntime {
 
 			thumbnail_renders: Default::default(),
 			click_targets: HashMap::new(),
+			overflowing_text: HashSet::new(),
 			upstream_transforms: HashMap::new(),
+			node_thumbnails: HashMap::new(),
+			node_thumbnails_changed: false,
+			cached_compilation: None,
+			new_compilation: None,
 		}
 	}
 
@@ -145,21 +326,54 @@ impl NodeRuntime {
 		// TODO: Currently we still render the document after we submit the node graph execution request.
 		// This should be avoided in the future.
 		requests.reverse();
-		requests.dedup_by(|a, b| matches!(a, NodeRuntimeMessage::ExecutionRequest(_)) && matches!(b, NodeRuntimeMessage::ExecutionRequest(_)));
+		// Only the latest render of the document is needed, but every requested export must still be rendered, like each frame of an animation
+		let is_preview = |request: &NodeRuntimeMessage| matches!(request, NodeRuntimeMessage::ExecutionRequest(request) if !request.render_config.for_export);
+		requests.dedup_by(|a, b| is_preview(a) && is_preview(b));
 		requests.reverse();
+
+		// Images are encoded at full resolution only in frames without an execution, so encoding them never holds up the evaluation of the graph
+		let idle = !requests.iter().any(|request| matches!(request, NodeRuntimeMessage::ExecutionRequest(_)));
+		if let Some(pending) = idle.then(|| self.image_previews.take_pending()).flatten() {
+			spawn_image_encoding(move || pending.encode());
+		}
+		if self.image_previews.take_encoded() {
+			self.sender.send(NodeGraphUpdateMessage::ImagePreviewEncoded);
+		}
+
 		for request in requests {
 			match request {
 				NodeRuntimeMessage::FontCacheUpdate(font_cache) => self.font_cache = font_cache,
 				NodeRuntimeMessage::ImaginatePreferencesUpdate(preferences) => self.imaginate_preferences = preferences,
+				NodeRuntimeMessage::OnionSkinUpdate(onion_skin) => self.onion_skin = onion_skin,
+				NodeRuntimeMessage::ProfilingUpdate(enabled) => self.executor.set_profiling(enabled),
+				NodeRuntimeMessage::NodeThumbnailsUpdate(enabled) => {
+					self.executor.set_recording_outputs(enabled);
+					self.node_thumbnails.clear();
+					self.node_thumbnails_changed = true;
+				}
+				NodeRuntimeMessage::InspectedNodeUpdate(node_path) => self.executor.set_inspected_node(node_path),
+				NodeRuntimeMessage::CompiledGraphUpdate(compiled_graph) => self.cached_compilation = Some(compiled_graph),
+				NodeRuntimeMessage::EvaluationCacheUpdate(evaluation_cache) => {
+					if evaluation_cache.build == GRAPHITE_GIT_COMMIT_HASH {
+						self.executor.load_evaluation_cache(evaluation_cache.outputs);
+					}
+				}
+				NodeRuntimeMessage::MemoryBudgetUpdate(budget) => self.executor.set_memory_budget(budget),
 				NodeRuntimeMessage::ExecutionRequest(ExecutionRequest {
 					execution_id, graph, render_config, ..
 				}) => {
 					let transform = render_config.viewport.transform;
+					let for_export = render_config.for_export;
 
 					let result = self.execute_network(graph, render_config).await;
+					let memory_usage = self.executor.enforce_memory_budget();
+					if !for_export {
+						self.image_previews.finish_render();
+					}
 
 					let mut responses = VecDeque::new();
 					self.process_monitor_nodes(&mut responses);
+					self.render_node_thumbnails();
 
 					self.sender.send_generation_response(ExecutionResponse {
 						execution_id,
@@ -167,9 +381,18 @@ impl NodeRuntime {
 						responses,
 						new_click_targets: self.click_targets.clone().into_iter().map(|(id, targets)| (LayerNodeIdentifier::new_unchecked(id), targets)).collect(),
 						new_upstream_transforms: self.upstream_transforms.clone(),
+						overflowing_text: self.overflowing_text.iter().map(|&id| LayerNodeIdentifier::new_unchecked(id)).collect(),
 						resolved_types: self.resolved_types.clone(),
 						node_graph_errors: core::mem::take(&mut self.node_graph_errors),
 						transform,
+						profile: self.executor.take_frame_profile(),
+						compiled_graph: self.new_compilation.take(),
+						node_thumbnails: core::mem::take(&mut self.node_thumbnails_changed)
+							.then(|| self.node_thumbnails.iter().map(|(node_path, (_, thumbnail))| (node_path.clone(), thumbnail.clone())).collect()),
+						// Exports and the frames of the onion skin are evaluated at other times, so their inspection isn't what's shown
+						inspection: (!for_export).then(|| self.executor.inspection()),
+						evaluation_cache_changes: if for_export { None } else { self.executor.take_evaluation_cache_changes() },
+						memory_usage,
 					});
 				}
 			}
@@ -177,6 +400,7 @@ impl NodeRuntime {
 	}
 
 	async fn execute_network(&mut self, graph: NodeNetwork, render_config: RenderConfig) -> Result<TaggedValue, String> {
+		let for_export = render_config.for_export;
 		if self.wasm_application_io.is_none() {
 			self.wasm_application_io = Some(WasmApplicationIo::new().await);
 		}
@@ -188,14 +412,15 @@ impl NodeRuntime {
 			node_graph_message_sender: &self.sender,
 			render_config,
 			image_frame: None,
+			onion_skin: self.onion_skin.clone(),
+			image_previews: self.image_previews.clone(),
 		};
 
 		// Required to ensure that the appropriate proto nodes are reinserted when the Editor API changes.
-		let mut graph_input_hash = DefaultHasher::new();
-		editor_api.font_cache.hash(&mut graph_input_hash);
-		let font_hash_code = graph_input_hash.finish();
-		graph.hash(&mut graph_input_hash);
-		let hash_code = graph_input_hash.finish();
+		let mut font_hasher = DefaultHasher::new();
+		editor_api.font_cache.hash(&mut font_hasher);
+		let font_hash_code = font_hasher.finish();
+		let hash_code = compilation_hash(&graph, font_hash_code);
 
 		if self.graph_hash != Some(hash_code) {
 			self.graph_hash = None;
@@ -204,18 +429,35 @@ impl NodeRuntime {
 		if self.graph_hash.is_none() {
 			let scoped_network = wrap_network_in_scope(graph, font_hash_code);
 
-			self.monitor_nodes = scoped_network
-				.recursive_nodes()
-				.filter(|(_, node)| node.implementation == DocumentNodeImplementation::proto("graphene_core::memo::MonitorNode<_, _, _>"))
-				.map(|(_, node)| node.original_location.path.clone().unwrap_or_default())
-				.collect::<Vec<_>>();
-
-			// We assume only one output
-			assert_eq!(scoped_network.exports.len(), 1, "Graph with multiple outputs not yet handled");
-			let c = Compiler {};
-			let proto_network = match c.compile_single(scoped_network) {
-				Ok(network) => network,
-				Err(e) => return Err(e),
+			// The document was saved along with the compilation of this exact graph, which is kept until the graph matches it (such as once the fonts it used have loaded)
+			let restored = self
+				.cached_compilation
+				.as_ref()
+				.filter(|cached| cached.graph_hash == hash_code)
+				.map(|cached| (cached.restore(&scoped_network), cached.monitor_nodes.clone()));
+			if restored.is_some() {
+				self.cached_compilation = None;
+			}
+			let proto_network = match restored {
+				Some((Some(proto_network), monitor_nodes)) => {
+					self.monitor_nodes = monitor_nodes;
+					proto_network
+				}
+				_ => {
+					self.monitor_nodes = scoped_network
+						.recursive_nodes()
+						.filter(|(_, node)| node.implementation == DocumentNodeImplementation::proto("graphene_core::memo::MonitorNode<_, _, _>"))
+						.map(|(_, node)| node.original_location.path.clone().unwrap_or_default())
+						.collect::<Vec<_>>();
+
+					// We assume only one output
+					assert_eq!(scoped_network.exports.len(), 1, "Graph with multiple outputs not yet handled");
+					let c = Compiler {};
+					let proto_network = c.compile_single(scoped_network)?;
+
+					self.new_compilation = Some(CompiledGraph::new(hash_code, proto_network.clone(), self.monitor_nodes.clone()));
+					proto_network
+				}
 			};
 
 			assert_ne!(proto_network.nodes.len(), 0, "No proto nodes exist?");
@@ -230,14 +472,32 @@ impl NodeRuntime {
 		use graph_craft::graphene_compiler::Executor;
 
 		let result = match self.executor.input_type() {
-			Some(t) if t == concrete!(WasmEditorApi) => (&self.executor).execute(editor_api).await.map_err(|e| e.to_string()),
-			Some(t) if t == concrete!(()) => (&self.executor).execute(()).await.map_err(|e| e.to_string()),
-			Some(t) => Err(format!("Invalid input type {t:?}")),
-			_ => Err("No input type".to_string()),
+			Some(t) if t == concrete!(WasmEditorApi) => (&self.executor).execute(editor_api).await,
+			Some(t) if t == concrete!(()) => (&self.executor).execute(()).await,
+			Some(t) => Err(format!("Invalid input type {t:?}").into()),
+			_ => Err("No input type".into()),
 		};
+		// The graphical nodes which failed during the evaluation were drawn as empty placeholders, and are shown with their errors in the graph
+		for error in self.executor.take_evaluation_errors() {
+			if let Some(graph_error) = self.executor.graph_error(&error) {
+				if !self.node_graph_errors.iter().any(|existing| existing.node_path == graph_error.node_path) {
+					self.node_graph_errors.push(graph_error);
+				}
+			}
+		}
 		let result = match result {
 			Ok(value) => value,
-			Err(e) => return Err(e),
+			Err(e) => {
+				// A node which failed during the evaluation is shown with its error in the graph
+				if let Some(graph_error) = e.downcast_ref::<NodeError>().and_then(|error| self.executor.graph_error(error)) {
+					self.node_graph_errors.push(graph_error);
+				}
+				// An export mustn't silently produce an empty file, but the viewport shows the error in place of the artwork
+				if for_export {
+					return Err(e.to_string());
+				}
+				return Ok(TaggedValue::RenderOutput(RenderOutput::Svg(error_placeholder_svg(&e.to_string()))));
+			}
 		};
 
 		// if let TaggedValue::SurfaceFrame(SurfaceFrame { surface_id, transform: _ }) = result {
@@ -257,6 +517,7 @@ impl NodeRuntime {
 	pub fn process_monitor_nodes(&mut self, responses: &mut VecDeque<Message>) {
 		// TODO: Consider optimizing this since it's currently O(m*n^2), with a sort it could be made O(m * n*log(n))
 		self.thumbnail_renders.retain(|id, _| self.monitor_nodes.iter().any(|monitor_node_path| monitor_node_path.contains(id)));
+		self.overflowing_text.retain(|id| self.monitor_nodes.iter().any(|monitor_node_path| monitor_node_path.contains(id)));
 
 		for monitor_node_path in &self.monitor_nodes {
 			// The monitor nodes are located within a document node, and are thus children in that network, so this gets the parent document node's ID
@@ -301,12 +562,20 @@ impl NodeRuntime {
 				click_targets.clear();
 				graphic_element.add_click_targets(click_targets);
 
+				// The layout of the Text node marks the text which doesn't fit its text box, so its layer can show that some of it is hidden
+				if matches!(&graphic_element, graphene_core::GraphicElement::VectorData(vector_data) if vector_data.text_overflows) {
+					self.overflowing_text.insert(parent_network_node_id);
+				} else {
+					self.overflowing_text.remove(&parent_network_node_id);
+				}
+
 				// RENDER THUMBNAIL
 
 				let bounds = graphic_element.bounding_box(DAffine2::IDENTITY);
 
 				// Render the thumbnail from a `GraphicElement` into an SVG string
-				let render_params = RenderParams::new(ViewMode::Normal, ImageRenderMode::Base64, bounds, true, false, false);
+				let mut render_params = RenderParams::new(ViewMode::Normal, ImageRenderMode::Base64, bounds, true, false, false);
+				render_params.image_previews = Some(self.image_previews.clone());
 				let mut render = SvgRender::new();
 				graphic_element.render_svg(&mut render, &render_params);
 
@@ -345,6 +614,70 @@ impl NodeRuntime {
 			}
 		}
 	}
+
+	/// Renders a thumbnail of the output of each node evaluated during the last execution, reusing the thumbnail of any output with the same hash.
+	fn render_node_thumbnails(&mut self) {
+		let resolved_types = &self.resolved_types;
+		let thumbnail_count = self.node_thumbnails.len();
+		self.node_thumbnails
+			.retain(|node_path, _| resolved_types.outputs.contains_key(&Source { node: node_path.clone(), index: 0 }));
+		self.node_thumbnails_changed |= self.node_thumbnails.len() != thumbnail_count;
+
+		for (node_path, graphic_element) in self.executor.take_node_outputs() {
+			let mut hasher = DefaultHasher::new();
+			graphic_element.hash(&mut hasher);
+			let hash = hasher.finish();
+
+			if self.node_thumbnails.get(&node_path).is_some_and(|(thumbnail_hash, _)| *thumbnail_hash == hash) {
+				continue;
+			}
+			let thumbnail = match self.node_thumbnails.values().find(|(thumbnail_hash, _)| *thumbnail_hash == hash) {
+				Some((_, thumbnail)) => thumbnail.clone(),
+				None => render_node_thumbnail(&graphic_element),
+			};
+			self.node_thumbnails.insert(node_path, (hash, thumbnail));
+			self.node_thumbnails_changed = true;
+		}
+	}
+}
+
+/// Encodes an image on its own thread, or as a separate task after the current one on the web where there is only the one thread, so it never holds up the evaluation of the graph.
+fn spawn_image_encoding(encode: impl FnOnce() + Send + 'static) {
+	#[cfg(target_arch = "wasm32")]
+	wasm_bindgen_futures::spawn_local(async move { encode() });
+	#[cfg(not(target_arch = "wasm32"))]
+	std::thread::spawn(encode);
+}
+
+/// Renders the graphic scaled to fit within a square of [`NODE_OUTPUT_THUMBNAIL_SIZE`] pixels, with its images downsampled to that resolution.
+fn render_node_thumbnail(graphic_element: &GraphicElement) -> String {
+	let Some([min, max]) = graphic_element.bounding_box(DAffine2::IDENTITY) else {
+		return String::new();
+	};
+	let size = (max - min).max_element();
+	let scale = if size > 0. { NODE_OUTPUT_THUMBNAIL_SIZE / size } else { 1. };
+
+	let mut render_params = RenderParams::new(ViewMode::Normal, ImageRenderMode::Base64, None, true, false, false);
+	// Images drawn at one pixel per CSS pixel are downsampled to the size of the thumbnail
+	render_params.image_export = Some(ImageExportPolicy {
+		max_ppi: Some(96.),
+		..Default::default()
+	});
+
+	let mut render = SvgRender::new();
+	render.transform = DAffine2::from_scale(DVec2::splat(scale)) * DAffine2::from_translation(-min);
+	graphic_element.render_svg(&mut render, &render_params);
+	render.format_svg(DVec2::ZERO, (max - min) * scale);
+	render.svg.to_svg_string()
+}
+
+/// Renders the error which stopped the graph from producing any artwork, shown in the viewport in its place.
+fn error_placeholder_svg(message: &str) -> String {
+	format!(
+		r#"<svg xmlns="http://www.w3.org/2000/svg"><text x="16" y="32" font-family="sans-serif" font-size="14" fill="{}">The artwork can't be rendered: {}</text></svg>"#,
+		COLOR_OVERLAY_RED,
+		escape_xml(message)
+	)
 }
 
 pub fn introspect_node(path: &[NodeId]) -> Option<Arc<dyn std::any::Any>> {
@@ -383,11 +716,39 @@ pub struct NodeGraphExecutor {
 	sender: Sender<NodeRuntimeMessage>,
 	receiver: Receiver<NodeGraphUpdate>,
 	futures: HashMap<u64, ExecutionContext>,
+	animation_export: Option<AnimationExport>,
+	/// The pages of the poster export whose images are being rasterized by the frontend, which are needed again to write the PDF once they arrive.
+	poster_export: Option<PosterLayout>,
+	/// The output profile and document info of the TIFF export being rasterized by the frontend, which are needed to write the TIFF once its pixels arrive.
+	tiff_export: Option<TiffExport>,
+	/// The rasterized frames of the onion skin, keyed by the hash of everything their render depends on, so each frame is only rendered again once it changes.
+	onion_skin_rasters: HashMap<u64, ImageFrame<Color>>,
+	/// The placement in document space and the size in pixels of each onion skin frame being rendered or rasterized.
+	pending_onion_skin_frames: HashMap<u64, (DAffine2, DVec2)>,
+	/// The working color each pixel sampled by the eyedropper is picked as, once the frontend has rasterized it.
+	pending_color_samples: HashMap<u64, ColorSampleTarget>,
+	/// Set when the editor is started in safe mode to rescue documents which crash it, turning off the nodes which run code from outside the editor.
+	safe_mode: bool,
+	/// The memory held by the outputs of the cache nodes as last shown in the status bar.
+	memory_usage: MemoryUsage,
 }
 
-#[derive(Debug, Clone)]
+/// The frames of an animation export rendered so far, which are sent to the frontend together once the last one has been rendered.
+#[derive(Debug)]
+struct AnimationExport {
+	frames: Vec<String>,
+	/// The shapes of each frame of a Lottie animation, which is made from them instead of the rendered frames.
+	lottie_frames: Vec<Vec<LottieShape>>,
+	frame_count: usize,
+}
+
+#[derive(Debug, Clone, Default)]
 struct ExecutionContext {
 	export_config: Option<ExportConfig>,
+	/// The key of the onion skin frame being rendered, which is rasterized instead of shown.
+	onion_skin_frame: Option<u64>,
+	/// The working color the pixel being rendered for the eyedropper is picked as.
+	color_sample: Option<ColorSampleTarget>,
 }
 
 impl Default for NodeGraphExecutor {
@@ -400,15 +761,68 @@ impl Default for NodeGraphExecutor {
 
 		Self {
 			futures: Default::default(),
+			animation_export: None,
+			poster_export: None,
+			tiff_export: None,
+			onion_skin_rasters: HashMap::new(),
+			pending_onion_skin_frames: HashMap::new(),
+			pending_color_samples: HashMap::new(),
+			safe_mode: false,
+			memory_usage: MemoryUsage::default(),
 			sender: request_sender,
 			receiver: response_receiver,
 		}
 	}
 }
 
+const BYTES_PER_MEGABYTE: f64 = 1024. * 1024.;
+
+/// The memory budget in bytes for the number of megabytes set in the preferences, where 0 means there's no limit.
+fn memory_budget_bytes(megabytes: f64) -> Option<usize> {
+	(megabytes > 0.).then(|| (megabytes * BYTES_PER_MEGABYTE) as usize)
+}
+
+/// Makes each Blend node composite in the color space of the document, which is given to it by an input that isn't shown in the graph.
+/// Documents from before the input existed don't have it, so it's added to them here too.
+fn set_blend_color_space(network: &mut NodeNetwork, linear_compositing: bool) {
+	for node in network.nodes.values_mut() {
+		match &mut node.implementation {
+			DocumentNodeImplementation::Network(nested_network) => set_blend_color_space(nested_network, linear_compositing),
+			implementation if *implementation == DocumentNodeImplementation::proto("graphene_core::raster::BlendNode<_, _, _, _>") => {
+				node.inputs.truncate(4);
+				node.inputs.push(NodeInput::value(TaggedValue::Bool(linear_compositing), false));
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Hides the nodes which run code from outside the editor, being the nodes of plugins, custom nodes, and expressions, so they're replaced by placeholders passing through their primary input when the graph is compiled.
+/// Custom nodes are recognized by being networks which aren't built-in node definitions.
+fn disable_untrusted_nodes(network: &mut NodeNetwork) {
+	for node in network.nodes.values_mut() {
+		let untrusted = match &node.implementation {
+			DocumentNodeImplementation::ProtoNode(identifier) => identifier.name.starts_with(PLUGIN_NODE_PREFIX) || identifier.name.starts_with("graphene_std::expression::"),
+			DocumentNodeImplementation::Network(_) => resolve_document_node_type(&node.name).is_none(),
+			DocumentNodeImplementation::Extract => false,
+		};
+
+		if untrusted {
+			node.visible = false;
+		} else if let DocumentNodeImplementation::Network(nested_network) = &mut node.implementation {
+			disable_untrusted_nodes(nested_network);
+		}
+	}
+}
+
 impl NodeGraphExecutor {
 	/// Execute the network by flattening it and creating a borrow stack.
-	fn queue_execution(&self, network: NodeNetwork, render_config: RenderConfig) -> u64 {
+	fn queue_execution(&self, mut network: NodeNetwork, render_config: RenderConfig) -> u64 {
+		set_blend_color_space(&mut network, render_config.linear_compositing);
+		if self.safe_mode {
+			disable_untrusted_nodes(&mut network);
+		}
+
 		let execution_id = generate_uuid();
 		let request = ExecutionRequest {
 			graph: network,
@@ -434,6 +848,41 @@ impl NodeGraphExecutor {
 			.expect("Failed to send imaginate preferences");
 	}
 
+	pub fn set_profiling(&self, enabled: bool) {
+		self.sender.send(NodeRuntimeMessage::ProfilingUpdate(enabled)).expect("Failed to send profiling update");
+	}
+
+	/// Starts or stops rendering thumbnails of the output of every node after each execution, which the node graph shows inline.
+	pub fn set_node_thumbnails(&self, enabled: bool) {
+		self.sender.send(NodeRuntimeMessage::NodeThumbnailsUpdate(enabled)).expect("Failed to send node thumbnails update");
+	}
+
+	/// Turns off the nodes which run code from outside the editor, along with the reuse of graphs compiled earlier, until the editor is restarted.
+	pub fn enable_safe_mode(&mut self) {
+		self.safe_mode = true;
+	}
+
+	pub fn safe_mode(&self) -> bool {
+		self.safe_mode
+	}
+
+	/// Offers the runtime a graph compiled earlier, which it uses instead of compiling the graph itself if the graph hasn't changed since.
+	pub fn load_compiled_graph(&self, compiled_graph: CompiledGraph) {
+		self.sender.send(NodeRuntimeMessage::CompiledGraphUpdate(compiled_graph)).expect("Failed to send compiled graph");
+	}
+
+	/// Offers the runtime the outputs of cache nodes saved with a document, which it uses instead of evaluating those cache nodes if they're in the graph.
+	pub fn load_evaluation_cache(&self, evaluation_cache: SavedEvaluationCache) {
+		self.sender.send(NodeRuntimeMessage::EvaluationCacheUpdate(evaluation_cache)).expect("Failed to send evaluation cache");
+	}
+
+	/// Sets the number of megabytes the outputs of the cache nodes may hold before the least recently used are cleared, where 0 means there's no limit.
+	pub fn set_memory_budget(&self, megabytes: f64) {
+		self.sender
+			.send(NodeRuntimeMessage::MemoryBudgetUpdate(memory_budget_bytes(megabytes)))
+			.expect("Failed to send memory budget");
+	}
+
 	pub fn introspect_node_in_network<T: std::any::Any + core::fmt::Debug, U, F1: FnOnce(&NodeNetwork) -> Option<NodeId>, F2: FnOnce(&T) -> U>(
 		&mut self,
 		network: &NodeNetwork,
@@ -472,20 +921,80 @@ impl NodeGraphExecutor {
 			view_mode: document.view_mode,
 			hide_artboards: false,
 			for_export: false,
+			svg_optimization: None,
+			image_export: None,
+			linear_compositing: document.linear_compositing,
+			units: document.units,
+			palette: document.show_output_palette.then_some(document.output_palette),
+			proof: document.show_soft_proof.then_some(SoftProof {
+				profile: document.output_profile,
+				gamut_warning: document.gamut_warning,
+			}),
 		};
 
+		// The frames of the onion skin are rendered first, and the ones already rasterized are drawn beneath the artwork
+		let onion_skin = self.update_onion_skin(document, render_config);
+		self.sender.send(NodeRuntimeMessage::OnionSkinUpdate(onion_skin.into())).expect("Failed to send onion skin update");
+		self.sender
+			.send(NodeRuntimeMessage::InspectedNodeUpdate(document.node_graph_handler.inspected_node.clone()))
+			.expect("Failed to send inspected node update");
+
 		// Execute the node graph
 		let execution_id = self.queue_execution(network, render_config);
 
-		self.futures.insert(execution_id, ExecutionContext { export_config: None });
+		self.futures.insert(execution_id, ExecutionContext::default());
 
 		Ok(())
 	}
 
+	/// Renders the single pixel of the viewport which the point in document space falls on, composited just as the document is shown with every blend mode and filter applied,
+	/// so the frontend can rasterize it and the eyedropper picks the same color that's on screen regardless of how the viewport itself is rendered.
+	pub fn submit_color_sample(&mut self, document: &DocumentMessageHandler, position: DVec2, target: ColorSampleTarget) -> Result<(), String> {
+		let document_to_viewport = document.metadata.document_to_viewport;
+		let pixel = document_to_viewport.transform_point2(position).floor();
+
+		let render_config = RenderConfig {
+			viewport: Footprint {
+				transform: DAffine2::from_translation(-pixel) * document_to_viewport,
+				resolution: UVec2::ONE,
+				..Default::default()
+			},
+			// The pixel is rendered like an export so the runtime doesn't skip it in favor of the latest render of the document
+			export_format: graphene_core::application_io::ExportFormat::Svg,
+			view_mode: document.view_mode,
+			hide_artboards: false,
+			for_export: true,
+			svg_optimization: None,
+			image_export: None,
+			linear_compositing: document.linear_compositing,
+			units: document.units,
+			palette: document.show_output_palette.then_some(document.output_palette),
+			proof: document.show_soft_proof.then_some(SoftProof {
+				profile: document.output_profile,
+				gamut_warning: document.gamut_warning,
+			}),
+		};
+
+		let execution_id = self.queue_execution(document.network().clone(), render_config);
+		self.futures.insert(
+			execution_id,
+			ExecutionContext {
+				export_config: None,
+				onion_skin_frame: None,
+				color_sample: Some(target),
+			},
+		);
+
+		Ok(())
+	}
+
+	/// Takes the working color that the pixel sampled by the eyedropper is picked as, once the frontend has rasterized it.
+	pub fn take_color_sample(&mut self, key: u64) -> Option<ColorSampleTarget> {
+		self.pending_color_samples.remove(&key)
+	}
+
 	/// Evaluates a node graph for export
 	pub fn submit_document_export(&mut self, document: &mut DocumentMessageHandler, mut export_config: ExportConfig) -> Result<(), String> {
-		let network = document.network().clone();
-
 		// Calculate the bounding box of the region to be exported
 		let bounds = match export_config.bounds {
 			ExportBounds::AllArtwork => document.metadata().document_bounds_document_space(!export_config.transparent_background),
@@ -496,30 +1005,205 @@ impl NodeGraphExecutor {
 		let size = bounds[1] - bounds[0];
 		let transform = DAffine2::from_translation(bounds[0]).inverse();
 
+		// Icon sets and posters are rasterized at their own sizes by the frontend, which scales the artwork from its original size
+		if matches!(export_config.file_type, FileType::Icons | FileType::Poster) {
+			export_config.scale_factor = 1.;
+		}
+
 		let render_config = RenderConfig {
 			viewport: Footprint {
 				transform: transform * DAffine2::from_scale(DVec2::splat(export_config.scale_factor)),
 				resolution: (size * export_config.scale_factor).as_uvec2(),
+				target: RenderTarget::Export,
 				..Default::default()
 			},
 			export_format: graphene_core::application_io::ExportFormat::Svg,
 			view_mode: document.view_mode,
 			hide_artboards: export_config.transparent_background,
 			for_export: true,
+			svg_optimization: export_config.svg_optimization.filter(|_| export_config.file_type == FileType::Svg),
+			image_export: export_config.image_export.filter(|_| export_config.file_type == FileType::Svg),
+			linear_compositing: document.linear_compositing,
+			units: document.units,
+			palette: export_config.palette,
+			// Exports for print are separated into the inks of the output profile once rasterized, rather than drawn with the colors of the proof
+			proof: None,
 		};
 		export_config.size = size;
+		export_config.info = document.info.clone();
+		export_config.output_profile = document.output_profile;
+
+		// Animations are evaluated once for each frame, in order, with the animated parameters at the frame's time
+		if export_config.file_type == FileType::Animation {
+			let frame_times = export_config.animation.frame_times();
+			if frame_times.is_empty() {
+				return Err("The time range of the animation contains no frames".to_string());
+			}
 
-		// Execute the node graph
-		let execution_id = self.queue_execution(network, render_config);
-		let execution_context = ExecutionContext { export_config: Some(export_config) };
-		self.futures.insert(execution_id, execution_context);
+			self.animation_export = Some(AnimationExport {
+				frames: Vec::with_capacity(frame_times.len()),
+				lottie_frames: Vec::new(),
+				frame_count: frame_times.len(),
+			});
+			// Lottie animations are made from the vector shapes of each frame, so those are evaluated instead of the rendered frames
+			let render_config = match export_config.animation.format {
+				AnimationFormat::Lottie => RenderConfig {
+					export_format: graphene_core::application_io::ExportFormat::Lottie,
+					..render_config
+				},
+				_ => render_config,
+			};
+			for time in frame_times {
+				let network = document.timeline.network_at_time(document.network(), time);
+				let export_config = ExportConfig {
+					time: Some(time),
+					..export_config.clone()
+				};
+
+				let execution_id = self.queue_execution(network, render_config);
+				let execution_context = ExecutionContext {
+					export_config: Some(export_config),
+					onion_skin_frame: None,
+					color_sample: None,
+				};
+				self.futures.insert(execution_id, execution_context);
+			}
+
+			return Ok(());
+		}
+
+		// Evaluate the graph with the animated parameters at the requested time
+		let network = match export_config.time {
+			Some(time) => document.timeline.network_at_time(document.network(), time),
+			None => document.network().clone(),
+		};
+
+		if !export_config.all_variants || document.variables.is_empty() {
+			// Execute the node graph
+			let execution_id = self.queue_execution(network, render_config);
+			let execution_context = ExecutionContext {
+				export_config: Some(export_config),
+				onion_skin_frame: None,
+				color_sample: None,
+			};
+			self.futures.insert(execution_id, execution_context);
+
+			return Ok(());
+		}
+
+		// Execute the node graph once for each variant, swapping in its variable values, and name each file after its variant
+		// The bound inputs may have been edited since the active variant was last stored, so its values are read from the network first
+		let mut variables = document.variables.clone();
+		variables.store_active_variant(&network);
+		let file_name = export_config.file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&export_config.file_name).to_string();
+		for (variant_index, variant_name) in variables.variants.iter().enumerate() {
+			let network = variables.network_with_variant(&network, variant_index);
+			let export_config = ExportConfig {
+				file_name: format!("{file_name} - {variant_name}"),
+				..export_config.clone()
+			};
+
+			let execution_id = self.queue_execution(network, render_config);
+			let execution_context = ExecutionContext {
+				export_config: Some(export_config),
+				onion_skin_frame: None,
+				color_sample: None,
+			};
+			self.futures.insert(execution_id, execution_context);
+		}
 
 		Ok(())
 	}
 
-	fn export(&self, node_graph_output: TaggedValue, export_config: ExportConfig, responses: &mut VecDeque<Message>) -> Result<(), String> {
-		let TaggedValue::RenderOutput(graphene_std::wasm_application_io::RenderOutput::Svg(svg)) = node_graph_output else {
-			return Err("Incorrect render type for exportign (expected RenderOutput::Svg)".to_string());
+	/// Queues a render of each frame of the onion skin which hasn't been rasterized yet, and returns the ones which have been.
+	fn update_onion_skin(&mut self, document: &DocumentMessageHandler, render_config: RenderConfig) -> Vec<OnionSkinFrame> {
+		let viewport = render_config.viewport;
+		let mut keys = Vec::new();
+		let mut onion_skin = Vec::new();
+
+		for (time, opacity) in document.timeline.onion_skin_frames(document.network()) {
+			let network = document.timeline.network_at_time(document.network(), time);
+
+			let mut hasher = DefaultHasher::new();
+			network.hash(&mut hasher);
+			viewport.transform.to_cols_array().map(f64::to_bits).hash(&mut hasher);
+			viewport.resolution.hash(&mut hasher);
+			render_config.view_mode.hash(&mut hasher);
+			render_config.linear_compositing.hash(&mut hasher);
+			render_config.units.hash(&mut hasher);
+			render_config.palette.hash(&mut hasher);
+			render_config.proof.hash(&mut hasher);
+			let key = hasher.finish();
+			keys.push(key);
+
+			if let Some(raster) = self.onion_skin_rasters.get(&key) {
+				onion_skin.push(OnionSkinFrame { raster: raster.clone(), opacity });
+				continue;
+			}
+			if self.pending_onion_skin_frames.contains_key(&key) {
+				continue;
+			}
+
+			// Frames are rendered like exports so the runtime doesn't skip them in favor of the latest render of the document, and without backgrounds so only the artwork is drawn
+			let frame_render_config = RenderConfig {
+				export_format: graphene_core::application_io::ExportFormat::Svg,
+				hide_artboards: true,
+				for_export: true,
+				..render_config
+			};
+			let execution_id = self.queue_execution(network, frame_render_config);
+			self.futures.insert(
+				execution_id,
+				ExecutionContext {
+					export_config: None,
+					onion_skin_frame: Some(key),
+					color_sample: None,
+				},
+			);
+
+			// The raster covers the viewport, so it's placed over the viewport's area of the document
+			let size = viewport.resolution.as_dvec2();
+			let transform = viewport.transform.inverse() * DAffine2::from_scale(size);
+			self.pending_onion_skin_frames.insert(key, (transform, size));
+		}
+
+		// Frames which are no longer shown are forgotten, which also discards the renders of frames that have since changed
+		self.onion_skin_rasters.retain(|key, _| keys.contains(key));
+		self.pending_onion_skin_frames.retain(|key, _| keys.contains(key));
+
+		onion_skin
+	}
+
+	/// Takes the layout of the poster export whose pages the frontend has rasterized, so its PDF can be written.
+	pub fn take_poster_layout(&mut self) -> Option<PosterLayout> {
+		self.poster_export.take()
+	}
+
+	/// Takes the TIFF export whose image the frontend has rasterized, so its file can be written.
+	pub fn take_tiff_export(&mut self) -> Option<TiffExport> {
+		self.tiff_export.take()
+	}
+
+	/// Stores the PNG image of an onion skin frame rasterized by the frontend, returning whether the frame is still needed and so the document should be rendered again to show it.
+	pub fn cache_onion_skin_frame(&mut self, key: u64, png: Vec<u8>) -> bool {
+		let Some((transform, size)) = self.pending_onion_skin_frames.remove(&key) else {
+			return false;
+		};
+		if png.is_empty() {
+			return false;
+		}
+
+		let raster = OnionSkinFrame::png_raster(&png, size.x as u32, size.y as u32, transform);
+		self.onion_skin_rasters.insert(key, raster);
+		true
+	}
+
+	fn export(&mut self, node_graph_output: TaggedValue, export_config: ExportConfig, responses: &mut VecDeque<Message>) -> Result<(), String> {
+		let (mut svg, linked_images) = match node_graph_output {
+			TaggedValue::RenderOutput(graphene_std::wasm_application_io::RenderOutput::Svg(svg)) => (svg, Vec::new()),
+			TaggedValue::RenderOutput(graphene_std::wasm_application_io::RenderOutput::SvgWithLinkedImages { svg, images }) => (svg, images),
+			TaggedValue::RenderOutput(graphene_std::wasm_application_io::RenderOutput::LottieFrame(shapes)) => return self.export_lottie_frame(shapes, export_config, responses),
+			_ => return Err("Incorrect render type for exportign (expected RenderOutput::Svg)".to_string()),
 		};
 
 		let ExportConfig {
@@ -527,9 +1211,74 @@ impl NodeGraphExecutor {
 			file_name,
 			size,
 			scale_factor,
+			icon_set,
+			poster,
+			animation,
+			info,
+			output_profile,
 			..
 		} = export_config;
 
+		if file_type == FileType::Icons {
+			let name = file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&file_name).to_string();
+			let icons = icon_set::icon_rasterizations(icon_set);
+			responses.add(FrontendMessage::TriggerRasterizeIconSet { svg, name, size: size.into(), icons });
+			return Ok(());
+		}
+
+		if file_type == FileType::Poster {
+			let layout = PosterLayout::new(size, poster).ok_or_else(|| "The artwork has no area to print".to_string())?;
+			let name = file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&file_name).to_string();
+			responses.add(FrontendMessage::TriggerRasterizePoster {
+				svg,
+				name,
+				size: layout.raster_size().into(),
+				pages: layout.rasterizations(),
+				cmyk: output_profile.is_cmyk(),
+			});
+			self.poster_export = Some(layout.with_info(info).with_output_profile(output_profile));
+			return Ok(());
+		}
+
+		if file_type == FileType::Tiff {
+			let name = file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&file_name).to_string();
+			responses.add(FrontendMessage::TriggerRasterizeTiff {
+				svg,
+				name,
+				size: (size * scale_factor).into(),
+			});
+			self.tiff_export = Some(TiffExport { output_profile, info });
+			return Ok(());
+		}
+
+		if file_type == FileType::Animation {
+			// Frames left over from an earlier animation export that has since been replaced are ignored
+			let Some(animation_export) = &mut self.animation_export else { return Ok(()) };
+			animation_export.frames.push(svg);
+
+			let (rendered, frame_count) = (animation_export.frames.len(), animation_export.frame_count);
+			if rendered < frame_count {
+				let progress = format!("Rendering frame {rendered} of {frame_count}");
+				responses.add(DocumentMessage::UpdateExportProgress { progress: Some(progress) });
+				return Ok(());
+			}
+
+			// The frontend rasterizes the frames, then hands them back to the editor to be encoded into the animation
+			let svgs = self.animation_export.take().map(|animation_export| animation_export.frames).unwrap_or_default();
+			let progress = format!("Encoding {frame_count} frames");
+			responses.add(DocumentMessage::UpdateExportProgress { progress: Some(progress) });
+
+			let name = file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&file_name).to_string();
+			responses.add(FrontendMessage::TriggerRasterizeAnimation {
+				svgs,
+				name,
+				size: (size * scale_factor).into(),
+				frame_rate: animation.frame_rate,
+				format: animation.format,
+			});
+			return Ok(());
+		}
+
 		let file_suffix = &format!(".{file_type:?}").to_lowercase();
 		let name = match file_name.ends_with(FILE_SAVE_SUFFIX) {
 			true => file_name.replace(FILE_SAVE_SUFFIX, file_suffix),
@@ -537,12 +1286,70 @@ impl NodeGraphExecutor {
 		};
 
 		if file_type == FileType::Svg {
+			info.embed_in_svg(&mut svg);
+		}
+
+		if file_type == FileType::Svg && !linked_images.is_empty() {
+			// The SVG is downloaded in a ZIP archive together with the image files it links to
+			let archive_name = format!("{}.zip", name.strip_suffix(file_suffix.as_str()).unwrap_or(&name));
+			let files = std::iter::once((name, svg.into_bytes())).chain(linked_images).collect::<Vec<_>>();
+			responses.add(FrontendMessage::TriggerDownloadBinaryFile {
+				name: archive_name,
+				mime: "application/zip".to_string(),
+				data: icon_set::encode_zip(&files),
+			});
+		} else if file_type == FileType::Svg {
 			responses.add(FrontendMessage::TriggerDownloadTextFile { document: svg, name });
 		} else {
 			let mime = file_type.to_mime().to_string();
 			let size = (size * scale_factor).into();
-			responses.add(FrontendMessage::TriggerDownloadImage { svg, name, mime, size });
+			// The browser's encoder doesn't tag the image with its color profile, and only PNG files have a place for the document info that it leaves untouched, so they're inserted afterwards
+			let profile = icc_profile::srgb_icc_profile();
+			let metadata_chunks = match file_type {
+				FileType::Png => [icc_profile::png_iccp_chunk(&profile), info.png_text_chunks()].concat(),
+				FileType::Jpg => icc_profile::jpeg_icc_segment(&profile),
+				_ => Vec::new(),
+			};
+			responses.add(FrontendMessage::TriggerDownloadImage {
+				svg,
+				name,
+				mime,
+				size,
+				metadata_chunks,
+			});
+		}
+		Ok(())
+	}
+
+	/// Collects the shapes of each frame of a Lottie animation, which is encoded and downloaded once the shapes of its last frame arrive.
+	fn export_lottie_frame(&mut self, shapes: Vec<LottieShape>, export_config: ExportConfig, responses: &mut VecDeque<Message>) -> Result<(), String> {
+		// Frames left over from an earlier animation export that has since been replaced are ignored
+		let Some(animation_export) = &mut self.animation_export else { return Ok(()) };
+		animation_export.lottie_frames.push(shapes);
+
+		let (rendered, frame_count) = (animation_export.lottie_frames.len(), animation_export.frame_count);
+		if rendered < frame_count {
+			let progress = format!("Rendering frame {rendered} of {frame_count}");
+			responses.add(DocumentMessage::UpdateExportProgress { progress: Some(progress) });
+			return Ok(());
 		}
+
+		let frames = self.animation_export.take().map(|animation_export| animation_export.lottie_frames).unwrap_or_default();
+		let ExportConfig {
+			file_name,
+			size,
+			scale_factor,
+			animation,
+			..
+		} = export_config;
+		let name = file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&file_name).to_string();
+		let document = lottie_export::encode_lottie(&name, &frames, animation.frame_rate, size * scale_factor)?;
+
+		responses.add(DocumentMessage::UpdateExportProgress { progress: None });
+		responses.add(FrontendMessage::TriggerDownloadTextFile {
+			document,
+			name: format!("{name}.{}", animation.format.extension()),
+		});
 		Ok(())
 	}
 
@@ -557,15 +1364,61 @@ impl NodeGraphExecutor {
 						responses: existing_responses,
 						new_click_targets,
 						new_upstream_transforms,
+						overflowing_text,
 						resolved_types,
 						node_graph_errors,
 						transform,
+						profile,
+						compiled_graph,
+						node_thumbnails,
+						inspection,
+						evaluation_cache_changes,
+						memory_usage,
 					} = execution_response;
 
+					// The graph compiled in safe mode has its untrusted nodes turned off, so it isn't saved for reuse once the editor is restarted normally
+					if compiled_graph.is_some() && !self.safe_mode {
+						document.compiled_graph = compiled_graph;
+					}
+					if let Some(changes) = evaluation_cache_changes.filter(|_| !self.safe_mode) {
+						document.evaluation_cache.get_or_insert_with(SavedEvaluationCache::default).apply(changes);
+					}
+
 					responses.extend(existing_responses);
+					if let Some(profile) = profile {
+						let profile = Self::frontend_profile(profile, document.network());
+						responses.add(FrontendMessage::UpdateNodeProfile { profile: Some(profile) });
+					}
+					if let Some(thumbnails) = node_thumbnails {
+						responses.add(NodeGraphMessage::UpdateNodeOutputThumbnails { thumbnails });
+					}
+					if let Some(inspection) = inspection {
+						responses.add(NodeGraphMessage::UpdateInspection { inspection });
+					}
+					if memory_usage != self.memory_usage {
+						self.memory_usage = memory_usage;
+						let megabytes = |bytes: usize| bytes as f64 / BYTES_PER_MEGABYTE;
+						responses.add(FrontendMessage::UpdateCacheMemoryUsage {
+							used: megabytes(memory_usage.used),
+							budget: memory_usage.budget.map(megabytes),
+						});
+					}
 					responses.add(NodeGraphMessage::UpdateTypes { resolved_types, node_graph_errors });
 					responses.add(NodeGraphMessage::SendGraph);
 					responses.add(OverlaysMessage::Draw);
+					responses.add(SpellcheckMessage::Check);
+
+					let execution_context = self.futures.remove(&execution_id).ok_or_else(|| "Invalid generation ID".to_string())?;
+
+					// The pixel sampled by the eyedropper is rendered at its own footprint, so the document's layers keep their placement from the render of the viewport
+					if let Some(target) = execution_context.color_sample {
+						let Ok(TaggedValue::RenderOutput(graphene_std::wasm_application_io::RenderOutput::Svg(svg))) = result else {
+							return Err("Incorrect render type for the color sample (expected RenderOutput::Svg)".to_string());
+						};
+						self.pending_color_samples.insert(execution_id, target);
+						responses.add(FrontendMessage::TriggerSampleColor { svg, key: execution_id });
+						continue;
+					}
 
 					let Ok(node_graph_output) = result else {
 						// Clear the click targets while the graph is in an un-renderable state
@@ -576,23 +1429,72 @@ impl NodeGraphExecutor {
 
 					document.metadata.update_transforms(new_upstream_transforms);
 					document.metadata.update_click_targets(new_click_targets);
+					document.metadata.update_overflowing_text(overflowing_text);
 
-					let execution_context = self.futures.remove(&execution_id).ok_or_else(|| "Invalid generation ID".to_string())?;
 					if let Some(export_config) = execution_context.export_config {
 						// Special handling for exporting the artwork
 						self.export(node_graph_output, export_config, responses)?
+					} else if let Some(key) = execution_context.onion_skin_frame {
+						// The frontend rasterizes the onion skin frame, then hands it back to be cached
+						let Some((_, size)) = self.pending_onion_skin_frames.get(&key) else { continue };
+						let TaggedValue::RenderOutput(graphene_std::wasm_application_io::RenderOutput::Svg(svg)) = node_graph_output else {
+							return Err("Incorrect render type for the onion skin (expected RenderOutput::Svg)".to_string());
+						};
+						responses.add(FrontendMessage::TriggerRasterizeOnionSkinFrame { svg, key, size: (*size).into() });
 					} else {
-						self.process_node_graph_output(node_graph_output, transform, responses)?
+						self.process_node_graph_output(node_graph_output, transform, responses)?;
+						// The click targets were just updated, so the picking buffer is drawn again from them
+						responses.add(DocumentMessage::UpdatePickingBuffer);
 					}
 				}
 				NodeGraphUpdate::NodeGraphUpdateMessage(NodeGraphUpdateMessage::ImaginateStatusUpdate) => {
 					responses.add(DocumentMessage::PropertiesPanel(PropertiesPanelMessage::Refresh));
 				}
+				NodeGraphUpdate::NodeGraphUpdateMessage(NodeGraphUpdateMessage::ImagePreviewEncoded) => {
+					// Rendering the document again draws the image at full resolution in place of its preview
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+				}
 			}
 		}
 		Ok(())
 	}
 
+	/// Names each profiled node after the document node it was compiled from.
+	fn frontend_profile(profile: FrameProfile, network: &NodeNetwork) -> FrontendFrameProfile {
+		let name = |node_path: &[NodeId], identifier: String| {
+			let Some((node_id, path)) = node_path.split_last() else { return identifier };
+			let node = network.nested_network(path).and_then(|network| network.nodes.get(node_id));
+			node.map(|node| if node.alias.is_empty() { node.name.clone() } else { node.alias.clone() }).unwrap_or(identifier)
+		};
+
+		FrontendFrameProfile {
+			duration: profile.duration,
+			nodes: profile
+				.nodes
+				.into_iter()
+				.map(|node| FrontendNodeProfile {
+					name: name(&node.node_path, node.identifier),
+					node_path: node.node_path,
+					evaluations: node.evaluations,
+					total_time: node.total_time,
+					self_time: node.self_time,
+					cache_hits: node.cache_hits,
+					cache_misses: node.cache_misses,
+				})
+				.collect(),
+			evaluations: profile
+				.evaluations
+				.into_iter()
+				.map(|evaluation| FrontendNodeEvaluation {
+					node: evaluation.node,
+					start: evaluation.start,
+					duration: evaluation.duration,
+					depth: evaluation.depth,
+				})
+				.collect(),
+		}
+	}
+
 	fn debug_render(render_object: impl GraphicElementRendered, transform: DAffine2, responses: &mut VecDeque<Message>) {
 		// Setup rendering
 		let mut render = SvgRender::new();
@@ -652,3 +1554,89 @@ impl NodeGraphExecutor {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graph_craft::document::DocumentNode;
+	use graph_craft::ProtoNodeIdentifier;
+
+	fn network(addend: f64) -> NodeNetwork {
+		let add = DocumentNode {
+			name: "Add".into(),
+			inputs: vec![NodeInput::network(concrete!(f64), 0), NodeInput::value(TaggedValue::F64(addend), false)],
+			implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::ops::AddNode<_>")),
+			..Default::default()
+		};
+		NodeNetwork {
+			exports: vec![NodeInput::node(NodeId(0), 0)],
+			nodes: [(NodeId(0), add)].into_iter().collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn saved_compilation_is_reused_for_the_same_graph() {
+		let graph = network(2.);
+		let proto_network = Compiler {}.compile_single(graph.clone()).unwrap();
+		let compiled_graph = CompiledGraph::new(compilation_hash(&graph, 0), proto_network.clone(), Vec::new());
+
+		// The values are left out of what's saved with the document
+		assert!(!compiled_graph.values.is_empty());
+		assert!(compiled_graph
+			.proto_network
+			.nodes
+			.iter()
+			.all(|(_, node)| !matches!(node.construction_args, ConstructionArgs::Value(TaggedValue::F64(_)))));
+
+		let saved = serde_json::to_string(&compiled_graph).unwrap();
+		let loaded = serde_json::from_str::<CompiledGraph>(&saved).unwrap();
+		assert_eq!(loaded.graph_hash, compilation_hash(&network(2.), 0));
+		assert_eq!(loaded.restore(&network(2.)), Some(proto_network));
+	}
+
+	#[test]
+	fn saved_compilation_is_not_reused_for_an_edited_graph() {
+		let graph = network(2.);
+		let proto_network = Compiler {}.compile_single(graph.clone()).unwrap();
+		let compiled_graph = CompiledGraph::new(compilation_hash(&graph, 0), proto_network, Vec::new());
+
+		let edited = network(3.);
+		assert_ne!(compiled_graph.graph_hash, compilation_hash(&edited, 0));
+		assert_ne!(compiled_graph.graph_hash, compilation_hash(&graph, 1));
+		assert_eq!(compiled_graph.restore(&edited), None);
+	}
+
+	#[test]
+	fn saved_evaluation_cache_is_updated_with_only_the_changes() {
+		let mut saved = SavedEvaluationCache {
+			build: GRAPHITE_GIT_COMMIT_HASH.to_string(),
+			outputs: vec![(NodeId(0), TaggedValue::F64(0.)), (NodeId(1), TaggedValue::F64(1.))],
+		};
+		saved.apply(EvaluationCacheChanges {
+			recorded: vec![(NodeId(1), TaggedValue::F64(2.)), (NodeId(2), TaggedValue::F64(3.))],
+			removed: vec![NodeId(0)],
+		});
+		assert_eq!(saved.outputs, vec![(NodeId(1), TaggedValue::F64(2.)), (NodeId(2), TaggedValue::F64(3.))]);
+
+		// The outputs saved by another build are dropped rather than mixed with those of this one
+		let mut stale = SavedEvaluationCache {
+			build: "another build".into(),
+			outputs: vec![(NodeId(0), TaggedValue::F64(0.))],
+		};
+		stale.apply(EvaluationCacheChanges {
+			recorded: vec![(NodeId(2), TaggedValue::F64(3.))],
+			removed: Vec::new(),
+		});
+		assert_eq!(stale.build, GRAPHITE_GIT_COMMIT_HASH);
+		assert_eq!(stale.outputs, vec![(NodeId(2), TaggedValue::F64(3.))]);
+	}
+
+	#[test]
+	fn error_placeholder_escapes_the_message() {
+		let svg = error_placeholder_svg("Expected <f64> & found <String>");
+		assert!(svg.contains("Expected &lt;f64&gt; &amp; found &lt;String&gt;"));
+		assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+	}
+}
@@ -6,7 +6,7 @@ import { type FullscreenState } from "@graphite/state-providers/fullscreen";
 import { type PortfolioState } from "@graphite/state-providers/portfolio";
 import { makeKeyboardModifiersBitfield, textInputCleanup, getLocalizedScanCode } from "@graphite/utility-functions/keyboard-entry";
 import { platformIsMac } from "@graphite/utility-functions/platform";
-import { extractPixelData } from "@graphite/utility-functions/rasterization";
+import { pasteImageProgressively } from "@graphite/utility-functions/rasterization";
 import { stripIndents } from "@graphite/utility-functions/strip-indents";
 import { type Editor } from "@graphite/wasm-communication/editor";
 import { TriggerPaste } from "@graphite/wasm-communication/messages";
@@ -276,32 +276,33 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		e.preventDefault();
 
 		Array.from(dataTransfer.items).forEach(async (item) => {
-			if (item.type === "text/plain") {
-				item.getAsString((text) => {
-					if (text.startsWith("graphite/layer: ")) {
-						editor.handle.pasteSerializedData(text.substring(16, text.length));
-					} else if (text.startsWith("graphite/nodes: ")) {
-						editor.handle.pasteSerializedNodes(text.substring(16, text.length));
-					}
-				});
-			}
-
-			const file = item.getAsFile();
+			// The item is no longer accessible once the event has finished, so its type is read up front
+			const type = item.type;
 
-			if (file?.type === "svg") {
-				const text = await file.text();
-				editor.handle.pasteSvg(text);
+			if (item.kind === "string") {
+				if (type !== "text/plain" && type !== "image/svg+xml") return;
 
+				item.getAsString((text) => editor.handle.pasteClipboardContent(type, new TextEncoder().encode(text)));
 				return;
 			}
 
-			if (file?.type.startsWith("image")) {
-				const imageData = await extractPixelData(file);
-				editor.handle.pasteImage(new Uint8Array(imageData.data), imageData.width, imageData.height);
-			}
+			const file = item.getAsFile();
+			if (file) await pasteClipboardBlob(file);
 		});
 	}
 
+	async function pasteClipboardBlob(blob: Blob) {
+		// PNG images and SVG text are interpreted by the editor, while other image formats are decoded by the browser
+		if (blob.type === "image/png" || blob.type === "image/svg+xml" || blob.type === "text/plain") {
+			editor.handle.pasteClipboardContent(blob.type, new Uint8Array(await blob.arrayBuffer()));
+			return;
+		}
+
+		if (blob.type.startsWith("image/")) {
+			await pasteImageProgressively(editor, blob);
+		}
+	}
+
 	// Frontend message subscriptions
 
 	editor.subscriptions.subscribeJsMessage(TriggerPaste, async () => {
@@ -319,48 +320,10 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 			const clipboardItems = await navigator.clipboard.read();
 			if (!clipboardItems) throw new Error("Clipboard API unsupported");
 
-			// Read any layer data or images from the clipboard
+			// Read any layer data or images from the clipboard, preferring images over any text that accompanies them
 			Array.from(clipboardItems).forEach(async (item) => {
-				// Read plain text and, if it is a layer, pass it to the editor
-				if (item.types.includes("text/plain")) {
-					const blob = await item.getType("text/plain");
-					const reader = new FileReader();
-					reader.onload = () => {
-						const text = reader.result as string;
-
-						if (text.startsWith("graphite/layer: ")) {
-							editor.handle.pasteSerializedData(text.substring(16, text.length));
-						}
-					};
-					reader.readAsText(blob);
-				}
-
-				// Read an image from the clipboard and pass it to the editor to be loaded
-				const imageType = item.types.find((type) => type.startsWith("image/"));
-
-				if (imageType === "svg") {
-					const blob = await item.getType("text/plain");
-					const reader = new FileReader();
-					reader.onload = () => {
-						const text = reader.result as string;
-						editor.handle.pasteSvg(text);
-					};
-					reader.readAsText(blob);
-
-					return;
-				}
-
-				if (imageType) {
-					const blob = await item.getType(imageType);
-					const reader = new FileReader();
-					reader.onload = async () => {
-						if (reader.result instanceof ArrayBuffer) {
-							const imageData = await extractPixelData(new Blob([reader.result], { type: imageType }));
-							editor.handle.pasteImage(new Uint8Array(imageData.data), imageData.width, imageData.height);
-						}
-					};
-					reader.readAsArrayBuffer(blob);
-				}
+				const type = item.types.find((itemType) => itemType.startsWith("image/")) || item.types.find((itemType) => itemType === "text/plain");
+				if (type) await pasteClipboardBlob(await item.getType(type));
 			});
 		} catch (err) {
 			const unsupported = stripIndents`
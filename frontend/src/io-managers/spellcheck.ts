@@ -0,0 +1,47 @@
+import { type Editor } from "@graphite/wasm-communication/editor";
+import { TriggerSpellcheck, type SpellcheckRun } from "@graphite/wasm-communication/messages";
+
+// A word flagged by a spellchecker, given by its range in the text of its run counted in UTF-16 code units like JavaScript string indices
+export type SpellcheckIssue = {
+	start: number;
+	end: number;
+	suggestions: string[];
+};
+
+// Any spellchecker, such as a dictionary compiled to WebAssembly or a language server, can be plugged in by implementing this
+export type Spellchecker = {
+	check: (text: string) => Promise<SpellcheckIssue[]>;
+};
+
+let registeredSpellchecker: Spellchecker | undefined;
+let registeredEditor: Editor | undefined;
+
+export function createSpellcheckManager(editor: Editor) {
+	registeredEditor = editor;
+	if (registeredSpellchecker) editor.handle.setSpellcheckEnabled(true);
+
+	// Subscribe to process backend event
+	editor.subscriptions.subscribeJsMessage(TriggerSpellcheck, async (triggerSpellcheck) => {
+		const spellchecker = registeredSpellchecker;
+		if (!spellchecker) return;
+
+		const results = await Promise.all(
+			triggerSpellcheck.runs.map(async (run: SpellcheckRun) => {
+				const issues = await spellchecker.check(run.text).catch(() => []);
+				return { run: run.id, issues };
+			}),
+		);
+		editor.handle.spellcheckResults(results);
+	});
+}
+
+// Plugs in the spellchecker which the text layers are checked with, or unplugs it when given `undefined`
+export function registerSpellchecker(spellchecker: Spellchecker | undefined) {
+	registeredSpellchecker = spellchecker;
+	registeredEditor?.handle.setSpellcheckEnabled(spellchecker !== undefined);
+}
+
+// Replaces a word flagged in a run of text with one of its suggestions
+export function acceptSpellcheckSuggestion(run: bigint, issue: SpellcheckIssue, suggestion: string) {
+	registeredEditor?.handle.acceptSpellcheckSuggestion(run, issue.start, issue.end, suggestion);
+}
@@ -1,7 +1,7 @@
 import { writable } from "svelte/store";
 
 import { type Editor } from "@graphite/wasm-communication/editor";
-import { TriggerFontLoad } from "@graphite/wasm-communication/messages";
+import { TriggerFontLoad, UpdateUserFonts } from "@graphite/wasm-communication/messages";
 
 // eslint-disable-next-line @typescript-eslint/explicit-function-return-type
 export function createFontsState(editor: Editor) {
@@ -16,21 +16,37 @@ export function createFontsState(editor: Editor) {
 		return url;
 	}
 
+	// Fonts loaded from files provided by the user, which are listed before the fonts of the catalog
+	const userFonts = new Map<string, Map<string, string>>();
+
 	async function fontNames(): Promise<{ name: string; url: URL | undefined }[]> {
-		return (await fontList).map((font) => ({ name: font.family, url: createURL(font.family) }));
+		const userFontNames = Array.from(userFonts.keys()).map((family) => ({ name: family, url: undefined }));
+		return [...userFontNames, ...(await fontList).map((font) => ({ name: font.family, url: createURL(font.family) }))];
 	}
 
 	async function getFontStyles(fontFamily: string): Promise<{ name: string; url: URL | undefined }[]> {
+		const userFontStyles = userFonts.get(fontFamily);
+		if (userFontStyles) return Array.from(userFontStyles.keys()).map((style) => ({ name: style, url: undefined }));
+
 		const font = (await fontList).find((value) => value.family === fontFamily);
 		return font?.variants.map((variant) => ({ name: variant, url: undefined })) || [];
 	}
 
 	async function getFontFileUrl(fontFamily: string, fontStyle: string): Promise<string | undefined> {
+		const userFontUrl = userFonts.get(fontFamily)?.get(fontStyle);
+		if (userFontUrl) return userFontUrl;
+
 		const font = (await fontList).find((value) => value.family === fontFamily);
 		const fontFileUrl = font?.files.get(fontStyle);
 		return fontFileUrl?.replace("http://", "https://");
 	}
 
+	// Loads an OpenType or TrueType font file, which the editor names from the family and style stored in the file
+	async function loadUserFont(file: File) {
+		const data = new Uint8Array(await file.arrayBuffer());
+		editor.handle.loadUserFont(data, URL.createObjectURL(file));
+	}
+
 	function formatFontStyleName(fontStyle: string): string {
 		const isItalic = fontStyle.endsWith("italic");
 		const weight = fontStyle === "regular" || fontStyle === "italic" ? 400 : parseInt(fontStyle, 10);
@@ -58,6 +74,15 @@ export function createFontsState(editor: Editor) {
 		}
 	});
 
+	editor.subscriptions.subscribeJsMessage(UpdateUserFonts, (updateUserFonts) => {
+		userFonts.clear();
+		updateUserFonts.userFonts.forEach((font, index) => {
+			const styles = userFonts.get(font.fontFamily) || new Map<string, string>();
+			styles.set(font.fontStyle, updateUserFonts.previewUrls[index]);
+			userFonts.set(font.fontFamily, styles);
+		});
+	});
+
 	const fontList = new Promise<{ family: string; variants: string[]; files: Map<string, string> }[]>((resolve) => {
 		fetch(fontListAPI)
 			.then((response) => response.json())
@@ -79,6 +104,7 @@ export function createFontsState(editor: Editor) {
 		fontNames,
 		getFontStyles,
 		getFontFileUrl,
+		loadUserFont,
 	};
 }
 export type FontsState = ReturnType<typeof createFontsState>;
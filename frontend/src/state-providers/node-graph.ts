@@ -4,16 +4,24 @@ import { type Editor } from "@graphite/wasm-communication/editor";
 import {
 	type Box,
 	type ContextMenuInformation,
+	type FrontendCommentFrame,
+	type FrontendFrameProfile,
+	type FrontendGraphSearchResult,
 	type FrontendNode,
+	type FrontendNodeInspection,
 	type FrontendNodeWire as FrontendNodeWire,
 	type FrontendNodeType,
 	type WirePath,
 	UpdateBox,
 	UpdateContextMenuInformation,
+	UpdateGraphSearchResults,
 	UpdateLayerWidths,
 	UpdateNodeGraph,
 	UpdateNodeGraphSelection,
 	UpdateNodeGraphTransform,
+	UpdateNodeInspection,
+	UpdateNodeOutputThumbnails,
+	UpdateNodeProfile,
 	UpdateNodeTypes,
 	UpdateNodeThumbnail,
 	UpdateSubgraphPath,
@@ -29,10 +37,15 @@ export function createNodeGraphState(editor: Editor) {
 		layerWidths: new Map<bigint, number>(),
 		nodes: [] as FrontendNode[],
 		wires: [] as FrontendNodeWire[],
+		frames: [] as FrontendCommentFrame[],
+		searchResults: [] as FrontendGraphSearchResult[],
 		wirePathInProgress: undefined as WirePath | undefined,
 		nodeTypes: [] as FrontendNodeType[],
+		profile: undefined as FrontendFrameProfile | undefined,
+		inspection: undefined as FrontendNodeInspection | undefined,
 		zoomWithScroll: false as boolean,
 		thumbnails: new Map<bigint, string>(),
+		outputThumbnails: new Map<bigint, string>(),
 		selected: [] as bigint[],
 		subgraphPath: [] as string[],
 		transform: { scale: 1, x: 0, y: 0 },
@@ -51,6 +64,12 @@ export function createNodeGraphState(editor: Editor) {
 			return state;
 		});
 	});
+	editor.subscriptions.subscribeJsMessage(UpdateGraphSearchResults, (updateGraphSearchResults) => {
+		update((state) => {
+			state.searchResults = updateGraphSearchResults.results;
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(UpdateLayerWidths, (updateLayerWidths) => {
 		update((state) => {
 			state.layerWidths = updateLayerWidths.layerWidths;
@@ -62,6 +81,7 @@ export function createNodeGraphState(editor: Editor) {
 		update((state) => {
 			state.nodes = updateNodeGraph.nodes;
 			state.wires = updateNodeGraph.wires;
+			state.frames = updateNodeGraph.frames;
 			return state;
 		});
 	});
@@ -77,12 +97,30 @@ export function createNodeGraphState(editor: Editor) {
 			return state;
 		});
 	});
+	editor.subscriptions.subscribeJsMessage(UpdateNodeInspection, (updateNodeInspection) => {
+		update((state) => {
+			state.inspection = updateNodeInspection.inspection;
+			return state;
+		});
+	});
+	editor.subscriptions.subscribeJsMessage(UpdateNodeProfile, (updateNodeProfile) => {
+		update((state) => {
+			state.profile = updateNodeProfile.profile;
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(UpdateNodeTypes, (updateNodeTypes) => {
 		update((state) => {
 			state.nodeTypes = updateNodeTypes.nodeTypes;
 			return state;
 		});
 	});
+	editor.subscriptions.subscribeJsMessage(UpdateNodeOutputThumbnails, (updateNodeOutputThumbnails) => {
+		update((state) => {
+			state.outputThumbnails = updateNodeOutputThumbnails.thumbnails;
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(UpdateNodeThumbnail, (updateNodeThumbnail) => {
 		update((state) => {
 			state.thumbnails.set(updateNodeThumbnail.id, updateNodeThumbnail.value);
@@ -4,17 +4,28 @@ import { writable } from "svelte/store";
 
 import { copyToClipboardFileURL } from "@graphite/io-managers/clipboard";
 import { downloadFileText, downloadFileBlob, upload } from "@graphite/utility-functions/files";
-import { extractPixelData, imageToPNG, rasterizeSVG } from "@graphite/utility-functions/rasterization";
+import { imageToPNG, insertJPEGSegments, insertPNGChunks, pasteImageProgressively, rasterizeIcon, rasterizePosterPagePixels, rasterizePosterPages, rasterizeSVG, rasterizeSVGCanvas, recordWebM } from "@graphite/utility-functions/rasterization";
 import { type Editor } from "@graphite/wasm-communication/editor";
 import {
 	type FrontendDocumentDetails,
 	TriggerCopyToClipboardBlobUrl,
 	TriggerFetchAndOpenDocument,
 	TriggerDownloadBlobUrl,
+	TriggerDownloadBinaryFile,
 	TriggerDownloadImage,
 	TriggerDownloadTextFile,
 	TriggerImport,
+	TriggerImportNodePresets,
+	TriggerImportSwatches,
+	TriggerLoadPlugin,
 	TriggerOpenDocument,
+	TriggerRasterizeAnimation,
+	TriggerRasterizeIconSet,
+	TriggerRasterizePoster,
+	TriggerRasterizeTiff,
+	TriggerRasterizeOnionSkinFrame,
+	TriggerRasterizePickingBuffer,
+	TriggerSampleColor,
 	TriggerRevokeBlobUrl,
 	UpdateActiveDocument,
 	UpdateOpenDocumentsList,
@@ -59,8 +70,8 @@ export function createPortfolioState(editor: Editor) {
 		}
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerOpenDocument, async () => {
-		const extension = editor.handle.fileSaveSuffix();
-		const data = await upload(extension, "text");
+		const extensions = `${editor.handle.fileSaveSuffix()},${editor.handle.nodeGraphSaveSuffix()}`;
+		const data = await upload(extensions, "text");
 		editor.handle.openDocumentFile(data.filename, data.content);
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerImport, async () => {
@@ -73,8 +84,19 @@ export function createPortfolioState(editor: Editor) {
 			return;
 		}
 
-		const imageData = await extractPixelData(new Blob([data.content], { type: data.type }));
-		editor.handle.pasteImage(new Uint8Array(imageData.data), imageData.width, imageData.height);
+		await pasteImageProgressively(editor, new Blob([data.content], { type: data.type }));
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerImportSwatches, async (triggerImportSwatches) => {
+		const data = await upload(".ase,.gpl", "data");
+		editor.handle.importSwatches(triggerImportSwatches.global, data.filename, data.content);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerImportNodePresets, async () => {
+		const data = await upload(editor.handle.nodePresetsSaveSuffix(), "text");
+		editor.handle.importNodePresets(data.content);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerLoadPlugin, async () => {
+		const data = await upload(".wasm", "data");
+		editor.handle.loadPlugin(data.content);
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadTextFile, (triggerFileDownload) => {
 		downloadFileText(triggerFileDownload.name, triggerFileDownload.document);
@@ -92,14 +114,16 @@ export function createPortfolioState(editor: Editor) {
 		copyToClipboardFileURL(triggerDownloadBlobUrl.blobUrl);
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadImage, async (triggerDownloadImage) => {
-		const { svg, name, mime, size } = triggerDownloadImage;
+		const { svg, name, mime, size, metadataChunks } = triggerDownloadImage;
 
 		// Fill the canvas with white if it'll be a JPEG (which does not support transparency and defaults to black)
 		const backgroundColor = mime.endsWith("jpeg") ? "white" : undefined;
 
 		// Rasterize the SVG to an image file
 		try {
-			const blob = await rasterizeSVG(svg, size.x, size.y, mime, backgroundColor);
+			const rasterized = await rasterizeSVG(svg, size.x, size.y, mime, backgroundColor);
+			const insertMetadata = mime.endsWith("jpeg") ? insertJPEGSegments : insertPNGChunks;
+			const blob = metadataChunks.length > 0 ? await insertMetadata(rasterized, metadataChunks) : rasterized;
 
 			// Have the browser download the file to the user's disk
 			downloadFileBlob(name, blob);
@@ -107,6 +131,136 @@ export function createPortfolioState(editor: Editor) {
 			// Fail silently if there's an error rasterizing the SVG, such as a zero-sized image
 		}
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizeIconSet, async (triggerRasterizeIconSet) => {
+		const { svg, name, size, icons } = triggerRasterizeIconSet;
+
+		// Rasterize the SVG at every icon size, then hand the PNG images back to the editor to be bundled into the icon set
+		try {
+			const pngs = await Promise.all(icons.map(async (icon) => new Uint8Array(await (await rasterizeIcon(svg, size.x, size.y, icon.size, icon.padding, icon.sharpen)).arrayBuffer())));
+
+			const data = new Uint8Array(pngs.reduce((total, png) => total + png.length, 0));
+			pngs.reduce((offset, png) => {
+				data.set(png, offset);
+				return offset + png.length;
+			}, 0);
+
+			const sizes = new Uint32Array(icons.map((icon) => icon.size));
+			const lengths = new Uint32Array(pngs.map((png) => png.length));
+			editor.handle.bundleIconSet(name, sizes, lengths, data);
+		} catch {
+			// Fail silently if there's an error rasterizing the SVG, such as a zero-sized image
+		}
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizePoster, async (triggerRasterizePoster) => {
+		const { svg, name, size, pages, cmyk } = triggerRasterizePoster;
+
+		// Rasterize the part of the poster printed on every page, then hand the JPEG images back to the editor to be written into the PDF
+		// Posters printed in CMYK instead hand back the pixels of each page, which the editor separates into the inks
+		try {
+			const images = cmyk
+				? await rasterizePosterPagePixels(svg, size.x, size.y, pages)
+				: await Promise.all((await rasterizePosterPages(svg, size.x, size.y, pages)).map(async (blob) => new Uint8Array(await blob.arrayBuffer())));
+
+			const data = new Uint8Array(images.reduce((total, image) => total + image.length, 0));
+			images.reduce((offset, image) => {
+				data.set(image, offset);
+				return offset + image.length;
+			}, 0);
+
+			const lengths = new Uint32Array(images.map((image) => image.length));
+			editor.handle.bundlePoster(name, lengths, data);
+		} catch {
+			// Fail silently if there's an error rasterizing the SVG, such as a zero-sized image
+		}
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizeTiff, async (triggerRasterizeTiff) => {
+		const { svg, name, size } = triggerRasterizeTiff;
+
+		// Rasterize the SVG, then hand its pixels back to the editor to be written into the TIFF, separated into CMYK inks if the document prints in CMYK
+		try {
+			const canvas = await rasterizeSVGCanvas(svg, size.x, size.y);
+			const context = canvas.getContext("2d");
+			if (!context) return;
+
+			const pixels = new Uint8Array(context.getImageData(0, 0, canvas.width, canvas.height).data.buffer);
+			editor.handle.encodeTiff(name, canvas.width, canvas.height, pixels);
+		} catch {
+			// Fail silently if there's an error rasterizing the SVG, such as a zero-sized image
+		}
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizeAnimation, async (triggerRasterizeAnimation) => {
+		const { svgs, name, size, frameRate, format } = triggerRasterizeAnimation;
+
+		// Rasterize the SVG of every frame, then hand the PNG images back to the editor to be encoded into the animation
+		// WebM videos are instead recorded by the browser and handed back as a single already encoded file
+		let files: Uint8Array[] = [];
+		try {
+			if (format === "WebM") {
+				files = [new Uint8Array(await (await recordWebM(svgs, size.x, size.y, frameRate)).arrayBuffer())];
+			} else {
+				files = await Promise.all(svgs.map(async (svg) => new Uint8Array(await (await rasterizeSVG(svg, size.x, size.y, "image/png")).arrayBuffer())));
+			}
+		} catch {
+			// An empty list of frames tells the editor that rasterizing failed, such as for a zero-sized image
+			files = [];
+		}
+
+		const data = new Uint8Array(files.reduce((total, file) => total + file.length, 0));
+		files.reduce((offset, file) => {
+			data.set(file, offset);
+			return offset + file.length;
+		}, 0);
+
+		const lengths = new Uint32Array(files.map((file) => file.length));
+		editor.handle.bundleAnimation(name, format, frameRate, lengths, data);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizeOnionSkinFrame, async (triggerRasterizeOnionSkinFrame) => {
+		const { svg, key, size } = triggerRasterizeOnionSkinFrame;
+
+		// Rasterize the frame, then hand the PNG image back to the editor to be cached and drawn beneath the artwork
+		let png = new Uint8Array();
+		try {
+			png = new Uint8Array(await (await rasterizeSVG(svg, size.x, size.y, "image/png")).arrayBuffer());
+		} catch {
+			// An empty image tells the editor that rasterizing failed, such as for a zero-sized viewport
+		}
+
+		editor.handle.cacheOnionSkinFrame(key, png);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizePickingBuffer, async (triggerRasterizePickingBuffer) => {
+		const { svg, key, size } = triggerRasterizePickingBuffer;
+
+		// Rasterize the layers drawn in the colors of their IDs, then hand the pixels back to the editor to be read from while hovering
+		try {
+			const canvas = await rasterizeSVGCanvas(svg, size.x, size.y);
+			const context = canvas.getContext("2d");
+			if (!context) return;
+
+			const rgba = new Uint8Array(context.getImageData(0, 0, canvas.width, canvas.height).data.buffer);
+			editor.handle.pickingBufferRasterized(key, rgba);
+		} catch {
+			// Fail silently, since hovering falls back to hit testing the layers without the picking buffer
+		}
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerSampleColor, async (triggerSampleColor) => {
+		const { svg, key } = triggerSampleColor;
+
+		// Rasterize the pixel, then hand its color back to the editor to be picked by the eyedropper
+		let rgba = new Uint8Array();
+		try {
+			const canvas = await rasterizeSVGCanvas(svg, 1, 1);
+			const context = canvas.getContext("2d");
+			if (context) rgba = new Uint8Array(context.getImageData(0, 0, 1, 1).data.buffer);
+		} catch {
+			// An empty color tells the editor that rasterizing failed
+		}
+
+		editor.handle.colorSampled(key, rgba);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerDownloadBinaryFile, (triggerDownloadBinaryFile) => {
+		const { name, mime, data } = triggerDownloadBinaryFile;
+		downloadFileBlob(name, new Blob([data], { type: mime }));
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerRevokeBlobUrl, async (triggerRevokeBlobUrl) => {
 		URL.revokeObjectURL(triggerRevokeBlobUrl.url);
 	});
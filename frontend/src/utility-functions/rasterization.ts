@@ -1,4 +1,10 @@
 import { replaceBlobURLsWithBase64 } from "@graphite/utility-functions/files";
+import { type Editor } from "@graphite/wasm-communication/editor";
+
+// The length of the longer side of the blurry placeholder shown while a placed image is decoded in full
+const PLACEHOLDER_SIZE = 64;
+// Images with fewer pixels than this are decoded in full right away, since they're ready quickly enough that a placeholder would only flicker
+const PLACEHOLDER_MIN_PIXELS = 1024 * 1024;
 
 // Rasterize the string of an SVG document at a given width and height and return the canvas it was drawn onto during the rasterization process
 export async function rasterizeSVGCanvas(svg: string, width: number, height: number, backgroundColor?: string): Promise<HTMLCanvasElement> {
@@ -15,6 +21,15 @@ export async function rasterizeSVGCanvas(svg: string, width: number, height: num
 		context.fillRect(0, 0, width, height);
 	}
 
+	// Draw our SVG to the canvas
+	const image = await loadSVGImage(svg);
+	context?.drawImage(image, 0, 0, width, height);
+
+	return canvas;
+}
+
+// Load the string of an SVG document as an image which can be drawn to a canvas at any size
+async function loadSVGImage(svg: string): Promise<HTMLImageElement> {
 	// This SVG rasterization scheme has the limitation that it cannot access blob URLs, so they must be inlined to base64 URLs
 	const svgWithBase64Images = await replaceBlobURLsWithBase64(svg);
 
@@ -29,13 +44,10 @@ export async function rasterizeSVGCanvas(svg: string, width: number, height: num
 		image.onload = () => resolve();
 	});
 
-	// Draw our SVG to the canvas
-	context?.drawImage(image, 0, 0, width, height);
-
 	// Clean up the SVG blob URL (once the URL is revoked, the SVG blob data itself is garbage collected after `svgBlob` goes out of scope)
 	URL.revokeObjectURL(url);
 
-	return canvas;
+	return image;
 }
 
 // Rasterize the string of an SVG document at a given width and height and turn it into the blob data of an image file matching the given MIME type
@@ -56,6 +68,172 @@ export async function rasterizeSVG(svg: string, width: number, height: number, m
 	return blob;
 }
 
+// Insert the given chunks into a PNG image right after its header chunk, which is always the first chunk and 25 bytes long following the 8 byte PNG signature
+export async function insertPNGChunks(png: Blob, chunks: Uint8Array): Promise<Blob> {
+	const HEADER_END = 8 + 25;
+
+	const data = new Uint8Array(await png.arrayBuffer());
+	return new Blob([data.slice(0, HEADER_END), chunks, data.slice(HEADER_END)], { type: png.type });
+}
+
+// Insert the given segments into a JPEG image after its start of image marker and the JFIF segment that the browser's encoder writes right after it, which must stay first
+export async function insertJPEGSegments(jpeg: Blob, segments: Uint8Array): Promise<Blob> {
+	const data = new Uint8Array(await jpeg.arrayBuffer());
+
+	// The start of image marker is 2 bytes, and the length of a segment counts its 2 length bytes but not its 2 marker bytes
+	let insertAt = 2;
+	if (data[2] === 0xff && data[3] === 0xe0) insertAt += 2 + ((data[4] << 8) | data[5]);
+
+	return new Blob([data.slice(0, insertAt), segments, data.slice(insertAt)], { type: jpeg.type });
+}
+
+// Rasterize the given regions of an SVG document drawn at the given width and height into a white-backed JPEG image for each page of a poster
+// Each page only draws its own region, so the whole poster is never held in one canvas that could exceed the browser's size limit
+export async function rasterizePosterPages(svg: string, width: number, height: number, pages: { x: number; y: number; width: number; height: number }[]): Promise<Blob[]> {
+	const canvases = await rasterizePosterPageCanvases(svg, width, height, pages);
+
+	return Promise.all(
+		canvases.map(async (canvas) => {
+			const blob = await new Promise<Blob | undefined>((resolve) => {
+				canvas.toBlob((blob) => resolve(blob || undefined), "image/jpeg", 0.92);
+			});
+			if (!blob) throw new Error("Converting canvas to blob data failed in rasterizePosterPages()");
+
+			return blob;
+		}),
+	);
+}
+
+// Rasterize the region of the SVG shown on each page of a poster like `rasterizePosterPages()`, but as the RGBA pixels of each page so they can be separated into CMYK inks
+export async function rasterizePosterPagePixels(svg: string, width: number, height: number, pages: { x: number; y: number; width: number; height: number }[]): Promise<Uint8Array[]> {
+	const canvases = await rasterizePosterPageCanvases(svg, width, height, pages);
+
+	return canvases.map((canvas) => {
+		const context = canvas.getContext("2d");
+		if (!context) throw new Error("Can't create 2D context from canvas during poster rasterization");
+
+		return new Uint8Array(context.getImageData(0, 0, canvas.width, canvas.height).data.buffer);
+	});
+}
+
+async function rasterizePosterPageCanvases(svg: string, width: number, height: number, pages: { x: number; y: number; width: number; height: number }[]): Promise<HTMLCanvasElement[]> {
+	if (!width || !height) throw new Error("Width and height must be nonzero when given to rasterizePosterPages()");
+
+	const image = await loadSVGImage(svg);
+
+	return pages.map((page) => {
+		const canvas = document.createElement("canvas");
+		canvas.width = page.width;
+		canvas.height = page.height;
+		const context = canvas.getContext("2d", { willReadFrequently: true });
+		if (!context) throw new Error("Can't create 2D context from canvas during poster rasterization");
+
+		context.fillStyle = "white";
+		context.fillRect(0, 0, page.width, page.height);
+		context.drawImage(image, -page.x, -page.y, width, height);
+
+		return canvas;
+	});
+}
+
+// Rasterize the string of each SVG document as one frame of a WebM video, recorded by the browser in real time at the given frame rate
+export async function recordWebM(svgs: string[], width: number, height: number, frameRate: number): Promise<Blob> {
+	const canvas = document.createElement("canvas");
+	canvas.width = width;
+	canvas.height = height;
+	const context = canvas.getContext("2d");
+	if (!context) throw new Error("Can't create 2D context from canvas during WebM recording");
+
+	// Rasterize every frame up front so they can be drawn at an even pace while recording
+	const frames = await Promise.all(svgs.map((svg) => rasterizeSVGCanvas(svg, width, height)));
+
+	// A frame rate of 0 means the stream only captures a frame when one is requested
+	const stream = canvas.captureStream(0);
+	const track = stream.getVideoTracks()[0] as CanvasCaptureMediaStreamTrack;
+	const recorder = new MediaRecorder(stream, { mimeType: "video/webm" });
+	const chunks: Blob[] = [];
+	recorder.ondataavailable = (event) => chunks.push(event.data);
+	const stopped = new Promise<void>((resolve) => {
+		recorder.onstop = () => resolve();
+	});
+
+	// Draw each frame in turn, holding it for the frame's duration so the recording plays back at the right speed
+	const recordFrame = async (index: number): Promise<void> => {
+		const frame = frames[index];
+		if (!frame) return;
+
+		context.clearRect(0, 0, width, height);
+		context.drawImage(frame, 0, 0);
+		track.requestFrame();
+		await new Promise((resolve) => setTimeout(resolve, 1000 / frameRate));
+		await recordFrame(index + 1);
+	};
+
+	recorder.start();
+	await recordFrame(0);
+	recorder.stop();
+	await stopped;
+
+	return new Blob(chunks, { type: "video/webm" });
+}
+
+// Rasterize the string of an SVG document into a square PNG icon, fitting the artwork within the padding and sharpening it by the given strength to recover detail lost when downscaling
+export async function rasterizeIcon(svg: string, width: number, height: number, size: number, padding: number, sharpen: number): Promise<Blob> {
+	if (!width || !height) throw new Error("Width and height must be nonzero when given to rasterizeIcon()");
+
+	// Fit the artwork within the padded area while keeping its aspect ratio, centered on the pixel grid
+	const available = Math.max(size - 2 * padding, 1);
+	const scale = Math.min(available / width, available / height);
+	const artworkWidth = Math.max(Math.round(width * scale), 1);
+	const artworkHeight = Math.max(Math.round(height * scale), 1);
+	const artwork = await rasterizeSVGCanvas(svg, artworkWidth, artworkHeight);
+
+	const canvas = document.createElement("canvas");
+	canvas.width = size;
+	canvas.height = size;
+	const context = canvas.getContext("2d", { willReadFrequently: true });
+	if (!context) throw new Error("Can't create 2D context from canvas during icon rasterization");
+	context.drawImage(artwork, Math.floor((size - artworkWidth) / 2), Math.floor((size - artworkHeight) / 2));
+
+	if (sharpen > 0) sharpenCanvas(context, size, size, sharpen);
+
+	const blob = await new Promise<Blob | undefined>((resolve) => {
+		canvas.toBlob((blob) => {
+			resolve(blob || undefined);
+		}, "image/png");
+	});
+
+	if (!blob) throw new Error("Converting canvas to blob data failed in rasterizeIcon()");
+
+	return blob;
+}
+
+// Apply an unsharp mask to the canvas color channels, which subtracts the neighboring pixels weighted by the strength from each pixel
+function sharpenCanvas(context: CanvasRenderingContext2D, width: number, height: number, strength: number) {
+	const source = context.getImageData(0, 0, width, height);
+	const result = context.createImageData(width, height);
+
+	const at = (x: number, y: number, channel: number): number => {
+		const clampedX = Math.min(Math.max(x, 0), width - 1);
+		const clampedY = Math.min(Math.max(y, 0), height - 1);
+		return source.data[(clampedY * width + clampedX) * 4 + channel];
+	};
+
+	for (let y = 0; y < height; y += 1) {
+		for (let x = 0; x < width; x += 1) {
+			const index = (y * width + x) * 4;
+			for (let channel = 0; channel < 3; channel += 1) {
+				const neighbors = at(x - 1, y, channel) + at(x + 1, y, channel) + at(x, y - 1, channel) + at(x, y + 1, channel);
+				result.data[index + channel] = at(x, y, channel) * (1 + 4 * strength) - neighbors * strength;
+			}
+			// Leave the alpha channel unchanged so the icon's silhouette doesn't gain a halo
+			result.data[index + 3] = source.data[index + 3];
+		}
+	}
+
+	context.putImageData(result, 0, 0);
+}
+
 /// Convert an image source (e.g. PNG document) into pixel data, a width, and a height
 export async function extractPixelData(imageData: ImageBitmapSource): Promise<ImageData> {
 	const canvasContext = await imageToCanvasContext(imageData);
@@ -65,6 +243,60 @@ export async function extractPixelData(imageData: ImageBitmapSource): Promise<Im
 	return canvasContext.getImageData(0, 0, width, height);
 }
 
+/// Place an image file in the document, which for large images first shows a blurry low resolution placeholder that's swapped for the full image once it's decoded
+export async function pasteImageProgressively(editor: Editor, imageData: Blob, mouseX?: number, mouseY?: number) {
+	const placeholder = await extractPlaceholderPixelData(imageData).catch(() => undefined);
+	if (!placeholder) {
+		const fullImageData = await extractPixelData(imageData);
+		editor.handle.pasteImage(new Uint8Array(fullImageData.data), fullImageData.width, fullImageData.height, mouseX, mouseY);
+		return;
+	}
+
+	const { pixels, width, height } = placeholder;
+	const key = editor.handle.pastePlaceholderImage(new Uint8Array(pixels.data), pixels.width, pixels.height, width, height, mouseX, mouseY);
+
+	const fullImageData = await extractPixelData(imageData);
+	editor.handle.replacePlaceholderImage(key, new Uint8Array(fullImageData.data), fullImageData.width, fullImageData.height);
+}
+
+/// Decode a small, blurred version of an image along with the size of the full image, or nothing if the image is small enough to not need a placeholder
+/// Only the image's header has to be read to learn its size, and browsers decode progressive JPEG and WebP images at a reduced size much faster than in full
+export async function extractPlaceholderPixelData(imageData: Blob): Promise<{ pixels: ImageData; width: number; height: number } | undefined> {
+	if (imageData.type === "image/svg+xml") return undefined;
+
+	const url = URL.createObjectURL(imageData);
+	const image = new Image();
+	image.src = url;
+	try {
+		await new Promise((resolve, reject) => {
+			image.onload = resolve;
+			image.onerror = reject;
+		});
+	} finally {
+		URL.revokeObjectURL(url);
+	}
+
+	const { naturalWidth: width, naturalHeight: height } = image;
+	if (width * height < PLACEHOLDER_MIN_PIXELS) return undefined;
+
+	const scale = PLACEHOLDER_SIZE / Math.max(width, height);
+	const resizeWidth = Math.max(Math.round(width * scale), 1);
+	const resizeHeight = Math.max(Math.round(height * scale), 1);
+	const bitmap = await createImageBitmap(image, { resizeWidth, resizeHeight, resizeQuality: "low", colorSpaceConversion: "default" });
+
+	const canvas = document.createElement("canvas");
+	canvas.width = resizeWidth;
+	canvas.height = resizeHeight;
+	const context = canvas.getContext("2d", { colorSpace: "srgb" });
+	if (!context) throw new Error("Could not create canvas context");
+
+	// The blur hides the blockiness of the placeholder once it's stretched to the size of the full image
+	context.filter = "blur(1px)";
+	context.drawImage(bitmap, 0, 0);
+
+	return { pixels: context.getImageData(0, 0, resizeWidth, resizeHeight), width, height };
+}
+
 /// Convert an image source (e.g. BMP document) into a PNG blob
 export async function imageToPNG(imageData: ImageBitmapSource): Promise<Blob> {
 	const canvasContext = await imageToCanvasContext(imageData);
@@ -98,8 +330,8 @@ export async function imageToCanvasContext(imageData: ImageBitmapSource): Promis
 		svgImageData = await rasterizeSVGCanvas(svgSource, bounds.width, bounds.height);
 	}
 
-	// Decode the image file binary data
-	const image = await createImageBitmap(svgImageData || imageData);
+	// Decode the image file binary data, converting its colors from any color profile embedded in it to sRGB, which Graphite works in
+	const image = await createImageBitmap(svgImageData || imageData, { colorSpaceConversion: "default" });
 
 	let { width, height } = image;
 	width = Math.floor(width);
@@ -110,7 +342,7 @@ export async function imageToCanvasContext(imageData: ImageBitmapSource): Promis
 	canvas.width = width;
 	canvas.height = height;
 
-	const context = canvas.getContext("2d");
+	const context = canvas.getContext("2d", { colorSpace: "srgb" });
 	if (!context) throw new Error("Could not create canvas context");
 	context.drawImage(image, 0, 0, image.width, image.height, 0, 0, width, height);
 
@@ -29,6 +29,14 @@ export class UpdateBox extends JsMessage {
 	readonly box!: Box | undefined;
 }
 
+export class UpdateCacheMemoryUsage extends JsMessage {
+	// In megabytes
+	readonly used!: number;
+
+	// In megabytes, or undefined if there's no limit
+	readonly budget!: number | undefined;
+}
+
 const ContextTupleToVec2 = Transform((data) => {
 	if (data.obj.contextMenuInformation === undefined) return undefined;
 	const contextMenuCoordinates = { x: data.obj.contextMenuInformation.contextMenuCoordinates[0], y: data.obj.contextMenuInformation.contextMenuCoordinates[1] };
@@ -56,6 +64,9 @@ export class UpdateNodeGraph extends JsMessage {
 
 	@Type(() => FrontendNodeWire)
 	readonly wires!: FrontendNodeWire[];
+
+	@Type(() => FrontendCommentFrame)
+	readonly frames!: FrontendCommentFrame[];
 }
 
 export class UpdateNodeGraphTransform extends JsMessage {
@@ -67,6 +78,71 @@ export class UpdateNodeTypes extends JsMessage {
 	readonly nodeTypes!: FrontendNodeType[];
 }
 
+export class FrontendNodeProfile {
+	@Type(() => BigInt)
+	readonly nodePath!: bigint[];
+
+	readonly name!: string;
+
+	readonly evaluations!: number;
+
+	readonly totalTime!: number;
+
+	readonly selfTime!: number;
+
+	readonly cacheHits!: number;
+
+	readonly cacheMisses!: number;
+}
+
+export class FrontendNodeEvaluation {
+	readonly node!: number;
+
+	readonly start!: number;
+
+	readonly duration!: number;
+
+	readonly depth!: number;
+}
+
+export class FrontendFrameProfile {
+	readonly duration!: number;
+
+	@Type(() => FrontendNodeProfile)
+	readonly nodes!: FrontendNodeProfile[];
+
+	@Type(() => FrontendNodeEvaluation)
+	readonly evaluations!: FrontendNodeEvaluation[];
+}
+
+export class UpdateNodeProfile extends JsMessage {
+	@Type(() => FrontendFrameProfile)
+	readonly profile!: FrontendFrameProfile | undefined;
+}
+
+export class FrontendNodeInspection {
+	@Type(() => BigInt)
+	readonly nodePath!: bigint[];
+
+	readonly name!: string;
+
+	readonly outputType!: string | undefined;
+
+	readonly details!: [string, string][];
+}
+
+export class UpdateNodeInspection extends JsMessage {
+	@Type(() => FrontendNodeInspection)
+	readonly inspection!: FrontendNodeInspection | undefined;
+}
+
+const OutputThumbnails = Transform(({ obj }) => obj.thumbnails);
+
+export class UpdateNodeOutputThumbnails extends JsMessage {
+	@OutputThumbnails
+	readonly thumbnails!: Map<bigint, string>;
+}
+
 export class UpdateNodeThumbnail extends JsMessage {
 	readonly id!: bigint;
 
@@ -83,6 +159,13 @@ export class UpdateOpenDocumentsList extends JsMessage {
 	readonly openDocuments!: FrontendDocumentDetails[];
 }
 
+export class UpdateUserFonts extends JsMessage {
+	@Type(() => Font)
+	readonly userFonts!: Font[];
+
+	readonly previewUrls!: string[];
+}
+
 export class UpdateSubgraphPath extends JsMessage {
 	readonly subgraphPath!: string[];
 }
@@ -190,6 +273,8 @@ export class FrontendNode {
 	readonly errors!: string | undefined;
 
 	readonly uiOnly!: boolean;
+
+	readonly isReroute!: boolean;
 }
 
 export class FrontendNodeWire {
@@ -204,6 +289,39 @@ export class FrontendNodeWire {
 	readonly dashed!: boolean;
 }
 
+export class FrontendCommentFrame {
+	readonly id!: bigint;
+
+	readonly title!: string;
+
+	readonly color!: string;
+
+	@TupleToVec2
+	readonly position!: XY | undefined;
+
+	@TupleToVec2
+	readonly size!: XY | undefined;
+
+	readonly selected!: boolean;
+}
+
+export class FrontendGraphSearchResult {
+	@Type(() => BigInt)
+	readonly nodePath!: bigint[];
+
+	readonly name!: string;
+
+	readonly foundBy!: string;
+
+	@TupleToVec2
+	readonly position!: XY | undefined;
+}
+
+export class UpdateGraphSearchResults extends JsMessage {
+	@Type(() => FrontendGraphSearchResult)
+	readonly results!: FrontendGraphSearchResult[];
+}
+
 export class FrontendNodeType {
 	readonly name!: string;
 
@@ -606,8 +724,6 @@ export class UpdateEyedropperSamplingState extends JsMessage {
 	readonly primaryColor!: string;
 
 	readonly secondaryColor!: string;
-
-	readonly setColorChoice!: "Primary" | "Secondary" | undefined;
 }
 
 const mouseCursorIconCSSNames = {
@@ -647,6 +763,14 @@ export class TriggerOpenDocument extends JsMessage {}
 
 export class TriggerImport extends JsMessage {}
 
+export class TriggerImportNodePresets extends JsMessage {}
+
+export class TriggerImportSwatches extends JsMessage {
+	readonly global!: boolean;
+}
+
+export class TriggerLoadPlugin extends JsMessage {}
+
 export class TriggerPaste extends JsMessage {}
 
 export class TriggerCopyToClipboardBlobUrl extends JsMessage {
@@ -659,6 +783,15 @@ export class TriggerDownloadBlobUrl extends JsMessage {
 	readonly blobUrl!: string;
 }
 
+export class TriggerDownloadBinaryFile extends JsMessage {
+	readonly name!: string;
+
+	readonly mime!: string;
+
+	@Transform(({ value }: { value: number[] }) => new Uint8Array(value))
+	readonly data!: Uint8Array;
+}
+
 export class TriggerDownloadImage extends JsMessage {
 	readonly svg!: string;
 
@@ -668,6 +801,9 @@ export class TriggerDownloadImage extends JsMessage {
 
 	@TupleToVec2
 	readonly size!: XY;
+
+	@Transform(({ value }: { value: number[] }) => new Uint8Array(value))
+	readonly metadataChunks!: Uint8Array;
 }
 
 export class TriggerDownloadTextFile extends JsMessage {
@@ -676,6 +812,82 @@ export class TriggerDownloadTextFile extends JsMessage {
 	readonly name!: string;
 }
 
+export type AnimationFormat = "ImageSequence" | "Gif" | "Apng" | "WebM" | "Lottie";
+
+export class TriggerRasterizeAnimation extends JsMessage {
+	readonly svgs!: string[];
+
+	readonly name!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+
+	readonly frameRate!: number;
+
+	readonly format!: AnimationFormat;
+}
+
+export type IconRasterization = { size: number; padding: number; sharpen: number };
+
+export class TriggerRasterizeIconSet extends JsMessage {
+	readonly svg!: string;
+
+	readonly name!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+
+	readonly icons!: IconRasterization[];
+}
+
+export type PosterPageRasterization = { x: number; y: number; width: number; height: number };
+
+export class TriggerRasterizePoster extends JsMessage {
+	readonly svg!: string;
+
+	readonly name!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+
+	readonly pages!: PosterPageRasterization[];
+
+	readonly cmyk!: boolean;
+}
+
+export class TriggerRasterizeTiff extends JsMessage {
+	readonly svg!: string;
+
+	readonly name!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+}
+
+export class TriggerRasterizeOnionSkinFrame extends JsMessage {
+	readonly svg!: string;
+
+	readonly key!: bigint;
+
+	@TupleToVec2
+	readonly size!: XY;
+}
+
+export class TriggerRasterizePickingBuffer extends JsMessage {
+	readonly svg!: string;
+
+	readonly key!: bigint;
+
+	@TupleToVec2
+	readonly size!: XY;
+}
+
+export class TriggerSampleColor extends JsMessage {
+	readonly svg!: string;
+
+	readonly key!: bigint;
+}
+
 export class TriggerRefreshBoundsOfViewports extends JsMessage {}
 
 export class TriggerRevokeBlobUrl extends JsMessage {
@@ -686,6 +898,15 @@ export class TriggerSavePreferences extends JsMessage {
 	readonly preferences!: Record<string, unknown>;
 }
 
+export type SpellcheckRun = {
+	readonly id: bigint;
+	readonly text: string;
+};
+
+export class TriggerSpellcheck extends JsMessage {
+	readonly runs!: SpellcheckRun[];
+}
+
 export class DocumentChanged extends JsMessage {}
 
 export type DataBuffer = {
@@ -704,6 +925,10 @@ export class DisplayEditableTextbox extends JsMessage {
 
 	readonly fontSize!: number;
 
+	readonly lineHeightRatio!: number;
+
+	readonly align!: string;
+
 	@Type(() => Color)
 	readonly color!: Color;
 
@@ -751,6 +976,8 @@ export class LayerPanelEntry {
 	parentsUnlocked!: boolean;
 
 	parentId!: bigint | undefined;
+
+	textOverflows!: boolean;
 }
 
 export class DisplayDialogDismiss extends JsMessage {}
@@ -1378,6 +1605,8 @@ export class UpdateMenuBarLayout extends JsMessage {
 
 export class UpdateNodeGraphBarLayout extends WidgetDiffUpdate {}
 
+export class UpdateParametersPanelLayout extends WidgetDiffUpdate {}
+
 export class UpdatePropertyPanelOptionsLayout extends WidgetDiffUpdate {}
 
 export class UpdatePropertyPanelSectionsLayout extends WidgetDiffUpdate {}
@@ -1423,26 +1652,39 @@ export const messageMakers: Record<string, MessageMaker> = {
 	TriggerCopyToClipboardBlobUrl,
 	TriggerFetchAndOpenDocument,
 	TriggerDownloadBlobUrl,
+	TriggerDownloadBinaryFile,
 	TriggerDownloadImage,
 	TriggerDownloadTextFile,
 	TriggerFontLoad,
 	TriggerGraphViewOverlay,
 	TriggerImport,
+	TriggerImportNodePresets,
+	TriggerImportSwatches,
 	TriggerIndexedDbRemoveDocument,
 	TriggerIndexedDbWriteDocument,
 	TriggerLoadAutoSaveDocuments,
+	TriggerLoadPlugin,
 	TriggerLoadPreferences,
 	TriggerOpenDocument,
 	TriggerPaste,
+	TriggerRasterizeAnimation,
+	TriggerRasterizeIconSet,
+	TriggerRasterizePoster,
+	TriggerRasterizeTiff,
+	TriggerRasterizeOnionSkinFrame,
+	TriggerRasterizePickingBuffer,
+	TriggerSampleColor,
 	TriggerRefreshBoundsOfViewports,
 	TriggerRevokeBlobUrl,
 	TriggerSavePreferences,
+	TriggerSpellcheck,
 	TriggerTextCommit,
 	TriggerTextCopy,
 	TriggerViewportResize,
 	TriggerVisitLink,
 	UpdateActiveDocument,
 	UpdateBox,
+	UpdateCacheMemoryUsage,
 	UpdateContextMenuInformation,
 	UpdateLayerWidths,
 	UpdateDialogButtons,
@@ -1456,6 +1698,7 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateDocumentRulers,
 	UpdateDocumentScrollbars,
 	UpdateEyedropperSamplingState,
+	UpdateGraphSearchResults,
 	UpdateInputHints,
 	UpdateLayersPanelOptionsLayout,
 	UpdateMenuBarLayout,
@@ -1464,14 +1707,19 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateNodeGraphBarLayout,
 	UpdateNodeGraphSelection,
 	UpdateNodeGraphTransform,
+	UpdateNodeInspection,
+	UpdateNodeOutputThumbnails,
+	UpdateNodeProfile,
 	UpdateNodeThumbnail,
 	UpdateNodeTypes,
 	UpdateOpenDocumentsList,
+	UpdateParametersPanelLayout,
 	UpdatePropertyPanelOptionsLayout,
 	UpdatePropertyPanelSectionsLayout,
 	UpdateSubgraphPath,
 	UpdateToolOptionsLayout,
 	UpdateToolShelfLayout,
+	UpdateUserFonts,
 	UpdateWorkingColorsLayout,
 	UpdateWirePathInProgress,
 	UpdateZoomWithScroll,
@@ -9,7 +9,7 @@ use crate::{Error, EDITOR, EDITOR_HANDLE, EDITOR_HAS_CRASHED};
 
 use editor::application::generate_uuid;
 use editor::application::Editor;
-use editor::consts::FILE_SAVE_SUFFIX;
+use editor::consts::{FILE_SAVE_SUFFIX, NODE_GRAPH_SAVE_SUFFIX, NODE_PRESETS_SAVE_SUFFIX};
 use editor::messages::input_mapper::utility_types::input_keyboard::ModifierKeys;
 use editor::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ScrollDelta, ViewportBounds};
 use editor::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
@@ -124,7 +124,7 @@ impl EditorHandle {
 	// ========================================================================
 
 	#[wasm_bindgen(js_name = initAfterFrontendReady)]
-	pub fn init_after_frontend_ready(&self, platform: String) {
+	pub fn init_after_frontend_ready(&self, platform: String, safe_mode: bool) {
 		// Send initialization messages
 		let platform = match platform.as_str() {
 			"Windows" => Platform::Windows,
@@ -133,6 +133,10 @@ impl EditorHandle {
 			_ => Platform::Unknown,
 		};
 		self.dispatch(GlobalsMessage::SetPlatform { platform });
+		// Safe mode is turned on before the auto-saved documents are reopened, so the ones which crash the editor can be rescued
+		if safe_mode {
+			self.dispatch(PortfolioMessage::EnableSafeMode);
+		}
 		self.dispatch(Message::Init);
 
 		// Poll node graph evaluation on `requestAnimationFrame`
@@ -219,6 +223,18 @@ impl EditorHandle {
 		FILE_SAVE_SUFFIX.into()
 	}
 
+	/// Get the constant `NODE_GRAPH_SAVE_SUFFIX`
+	#[wasm_bindgen(js_name = nodeGraphSaveSuffix)]
+	pub fn node_graph_save_suffix(&self) -> String {
+		NODE_GRAPH_SAVE_SUFFIX.into()
+	}
+
+	/// Get the constant `NODE_PRESETS_SAVE_SUFFIX`
+	#[wasm_bindgen(js_name = nodePresetsSaveSuffix)]
+	pub fn node_presets_save_suffix(&self) -> String {
+		NODE_PRESETS_SAVE_SUFFIX.into()
+	}
+
 	/// Update the value of a given UI widget, but don't commit it to the history (unless `commit_layout()` is called, which handles that)
 	#[wasm_bindgen(js_name = widgetValueUpdate)]
 	pub fn widget_value_update(&self, layout_target: JsValue, widget_id: u64, value: JsValue) -> Result<(), JsValue> {
@@ -446,6 +462,15 @@ impl EditorHandle {
 		Ok(())
 	}
 
+	/// A font file provided by the user was loaded, with a URL to it for showing the font while editing text
+	#[wasm_bindgen(js_name = loadUserFont)]
+	pub fn load_user_font(&self, data: Vec<u8>, preview_url: String) -> Result<(), JsValue> {
+		let message = PortfolioMessage::LoadUserFont { preview_url, data };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
 	/// A text box was changed
 	#[wasm_bindgen(js_name = updateBounds)]
 	pub fn update_bounds(&self, new_text: String) -> Result<(), JsValue> {
@@ -455,6 +480,15 @@ impl EditorHandle {
 		Ok(())
 	}
 
+	/// The selected range of the text in a text box was changed
+	#[wasm_bindgen(js_name = updateTextSelection)]
+	pub fn update_text_selection(&self, start: usize, end: usize) -> Result<(), JsValue> {
+		let message = TextToolMessage::UpdateSelection { start, end };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
 	/// Begin sampling a pixel color from the document by entering eyedropper sampling mode
 	#[wasm_bindgen(js_name = eyedropperSampleForColorPicker)]
 	pub fn eyedropper_sample_for_color_picker(&self) -> Result<(), JsValue> {
@@ -492,6 +526,129 @@ impl EditorHandle {
 		Ok(())
 	}
 
+	/// Encode the PNG images rasterized for each frame of an animation export, given as the concatenated data of every image and the byte length of each
+	#[wasm_bindgen(js_name = bundleAnimation)]
+	pub fn bundle_animation(&self, name: String, format: JsValue, frame_rate: f64, lengths: Vec<u32>, data: Vec<u8>) -> Result<(), JsValue> {
+		let format = from_value(format).map_err(|error| Error::new(&format!("Invalid animation format: {error}")))?;
+
+		let mut remaining = data.as_slice();
+		let frames = lengths
+			.into_iter()
+			.map(|length| {
+				let (png, rest) = remaining.split_at((length as usize).min(remaining.len()));
+				remaining = rest;
+				png.to_vec()
+			})
+			.collect();
+
+		let message = PortfolioMessage::BundleAnimation { name, format, frame_rate, frames };
+		self.dispatch(message);
+		Ok(())
+	}
+
+	/// Bundle the PNG images rasterized for each size of an icon set export, given as the concatenated data of every image and the size and byte length of each
+	#[wasm_bindgen(js_name = bundleIconSet)]
+	pub fn bundle_icon_set(&self, name: String, sizes: Vec<u32>, lengths: Vec<u32>, data: Vec<u8>) {
+		let mut remaining = data.as_slice();
+		let icons = sizes
+			.into_iter()
+			.zip(lengths)
+			.map(|(size, length)| {
+				let (png, rest) = remaining.split_at((length as usize).min(remaining.len()));
+				remaining = rest;
+				(size, png.to_vec())
+			})
+			.collect();
+
+		let message = PortfolioMessage::BundleIconSet { name, icons };
+		self.dispatch(message);
+	}
+
+	/// Write the PDF of a poster export from the image rasterized for each of its pages, given as the concatenated data of every image and the byte length of each.
+	/// The images are JPEGs, or the RGBA pixels of each page when the poster is separated into CMYK.
+	#[wasm_bindgen(js_name = bundlePoster)]
+	pub fn bundle_poster(&self, name: String, lengths: Vec<u32>, data: Vec<u8>) {
+		let mut remaining = data.as_slice();
+		let pages = lengths
+			.into_iter()
+			.map(|length| {
+				let (image, rest) = remaining.split_at((length as usize).min(remaining.len()));
+				remaining = rest;
+				image.to_vec()
+			})
+			.collect();
+
+		let message = PortfolioMessage::BundlePoster { name, pages };
+		self.dispatch(message);
+	}
+
+	/// Write the TIFF file of a TIFF export from the RGBA pixels of its rasterized image
+	#[wasm_bindgen(js_name = encodeTiff)]
+	pub fn encode_tiff(&self, name: String, width: u32, height: u32, pixels: Vec<u8>) {
+		let message = PortfolioMessage::EncodeTiff { name, width, height, pixels };
+		self.dispatch(message);
+	}
+
+	/// Cache the PNG image rasterized for a frame of the onion skin, or an empty image if rasterizing it failed
+	#[wasm_bindgen(js_name = cacheOnionSkinFrame)]
+	pub fn cache_onion_skin_frame(&self, key: u64, png: Vec<u8>) {
+		let message = PortfolioMessage::CacheOnionSkinFrame { key, png };
+		self.dispatch(message);
+	}
+
+	/// Read the layers hovered over from the RGBA pixels rasterized for the picking buffer
+	#[wasm_bindgen(js_name = pickingBufferRasterized)]
+	pub fn picking_buffer_rasterized(&self, key: u64, rgba: Vec<u8>) {
+		let message = DocumentMessage::PickingBufferRasterized { key, rgba };
+		self.dispatch(message);
+	}
+
+	/// Pick the color of the pixel rasterized for the eyedropper, given as 8-bit gamma encoded RGBA, or no color if rasterizing it failed
+	#[wasm_bindgen(js_name = colorSampled)]
+	pub fn color_sampled(&self, key: u64, rgba: Vec<u8>) {
+		let message = PortfolioMessage::ColorSampled { key, rgba };
+		self.dispatch(message);
+	}
+
+	/// Turn spellchecking of the text layers on once a spellchecker has been plugged in, or off again
+	#[wasm_bindgen(js_name = setSpellcheckEnabled)]
+	pub fn set_spellcheck_enabled(&self, enabled: bool) {
+		let message = SpellcheckMessage::SetEnabled { enabled };
+		self.dispatch(message);
+	}
+
+	/// Hand back the words flagged by the spellchecker in the runs of text it was given to check
+	#[wasm_bindgen(js_name = spellcheckResults)]
+	pub fn spellcheck_results(&self, results: JsValue) -> Result<(), JsValue> {
+		let results = from_value(results).map_err(|error| Error::new(&format!("Invalid spellcheck results: {error}")))?;
+		let message = SpellcheckMessage::Results { results };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	/// Replace a flagged word in a run of text with one of the suggestions for it
+	#[wasm_bindgen(js_name = acceptSpellcheckSuggestion)]
+	pub fn accept_spellcheck_suggestion(&self, run: u64, start: usize, end: usize, suggestion: String) {
+		let message = SpellcheckMessage::AcceptSuggestion { run, start, end, suggestion };
+		self.dispatch(message);
+	}
+
+	/// Stop flagging a word throughout the document
+	#[wasm_bindgen(js_name = ignoreSpellcheckWord)]
+	pub fn ignore_spellcheck_word(&self, word: String) {
+		let message = SpellcheckMessage::IgnoreWord { word };
+		self.dispatch(message);
+	}
+
+	/// Paste content read from the device clipboard, which the editor interprets according to its MIME type
+	#[wasm_bindgen(js_name = pasteClipboardContent)]
+	pub fn paste_clipboard_content(&self, mime_type: String, data: Vec<u8>, mouse_x: Option<f64>, mouse_y: Option<f64>) {
+		let mouse = mouse_x.and_then(|x| mouse_y.map(|y| (x, y)));
+		let message = PortfolioMessage::PasteClipboardContent { mime_type, data, mouse };
+		self.dispatch(message);
+	}
+
 	/// Paste layers from a serialized json representation
 	#[wasm_bindgen(js_name = pasteSerializedData)]
 	pub fn paste_serialized_data(&self, data: String) {
@@ -573,6 +730,21 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Find the nodes in every network of the document by their name, output type, or input values
+	#[wasm_bindgen(js_name = searchGraph)]
+	pub fn search_graph(&self, query: String) {
+		let message = NodeGraphMessage::SearchGraph { query };
+		self.dispatch(message);
+	}
+
+	/// Open the network containing a node, given the path of node IDs to it, and center the graph view on the node
+	#[wasm_bindgen(js_name = navigateToNode)]
+	pub fn navigate_to_node(&self, node_path: Vec<u64>) {
+		let node_path = node_path.into_iter().map(NodeId).collect();
+		let message = NodeGraphMessage::NavigateToNode { node_path };
+		self.dispatch(message);
+	}
+
 	/// Pastes an image
 	#[wasm_bindgen(js_name = pasteImage)]
 	pub fn paste_image(&self, image_data: Vec<u8>, width: u32, height: u32, mouse_x: Option<f64>, mouse_y: Option<f64>) {
@@ -582,6 +754,53 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Pastes a low resolution placeholder of an image at the size of the full image, returning the key to replace it with the full image by once that's decoded
+	#[wasm_bindgen(js_name = pastePlaceholderImage)]
+	pub fn paste_placeholder_image(&self, image_data: Vec<u8>, width: u32, height: u32, full_width: u32, full_height: u32, mouse_x: Option<f64>, mouse_y: Option<f64>) -> u64 {
+		let mouse = mouse_x.and_then(|x| mouse_y.map(|y| (x, y)));
+		let placeholder = graphene_core::raster::Image::from_image_data(&image_data, width, height);
+		let key = generate_uuid();
+		let message = DocumentMessage::PastePlaceholderImage {
+			placeholder,
+			width: full_width,
+			height: full_height,
+			key,
+			mouse,
+		};
+		self.dispatch(message);
+		key
+	}
+
+	/// Replaces the placeholder pasted by `pastePlaceholderImage` with the full image
+	#[wasm_bindgen(js_name = replacePlaceholderImage)]
+	pub fn replace_placeholder_image(&self, key: u64, image_data: Vec<u8>, width: u32, height: u32) {
+		let image = graphene_core::raster::Image::from_image_data(&image_data, width, height);
+		let message = DocumentMessage::ReplacePlaceholderImage { key, image };
+		self.dispatch(message);
+	}
+
+	/// Adds the colors of an Adobe Swatch Exchange (.ase) or GIMP palette (.gpl) file to the swatches of the active document, or to the global swatches shared by every document
+	#[wasm_bindgen(js_name = importSwatches)]
+	pub fn import_swatches(&self, global: bool, filename: String, content: Vec<u8>) {
+		if global {
+			self.dispatch(PreferencesMessage::ImportSwatches { filename, content });
+		} else {
+			self.dispatch(DocumentMessage::ImportSwatches { filename, content });
+		}
+	}
+
+	/// Adds the presets of a node presets file to those kept in the preferences
+	#[wasm_bindgen(js_name = importNodePresets)]
+	pub fn import_node_presets(&self, content: String) {
+		self.dispatch(PreferencesMessage::ImportNodePresets { content });
+	}
+
+	/// Loads a plugin from its WebAssembly module, which carries its manifest, making its nodes available to add to the graph
+	#[wasm_bindgen(js_name = loadPlugin)]
+	pub fn load_plugin(&self, module: Vec<u8>) {
+		self.dispatch(PortfolioMessage::LoadPlugin { module });
+	}
+
 	#[wasm_bindgen(js_name = pasteSvg)]
 	pub fn paste_svg(&self, svg: String, mouse_x: Option<f64>, mouse_y: Option<f64>) {
 		let mouse = mouse_x.and_then(|x| mouse_y.map(|y| (x, y)));
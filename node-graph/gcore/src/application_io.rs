@@ -1,4 +1,5 @@
 use crate::raster::ImageFrame;
+use crate::renderer::{ImageExportPolicy, ImagePreviewCache, OnionSkinFrame, OutputPalette, SoftProof, SvgOptimization};
 use crate::text::FontCache;
 use crate::transform::{Footprint, Transform, TransformMut};
 use crate::vector::style::ViewMode;
@@ -127,6 +128,8 @@ pub enum ApplicationError {
 #[derive(Debug, Clone)]
 pub enum NodeGraphUpdateMessage {
 	ImaginateStatusUpdate,
+	/// An image drawn from a downscaled preview was encoded at full resolution, so the document can be rendered again to show it.
+	ImagePreviewEncoded,
 }
 
 pub trait NodeGraphUpdateSender {
@@ -146,6 +149,8 @@ pub enum ExportFormat {
 	},
 	Jpeg,
 	Canvas,
+	/// The vector shapes of one frame of a Lottie animation.
+	Lottie,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -155,6 +160,18 @@ pub struct RenderConfig {
 	pub view_mode: ViewMode,
 	pub hide_artboards: bool,
 	pub for_export: bool,
+	/// Optimizes the rendered SVG to make it smaller, see [`SvgOptimization`].
+	pub svg_optimization: Option<SvgOptimization>,
+	/// How images are written into exported SVGs, see [`ImageExportPolicy`].
+	pub image_export: Option<ImageExportPolicy>,
+	/// Composites blends and gradients in linear RGB instead of in the gamma encoded sRGB that browsers use.
+	pub linear_compositing: bool,
+	/// Limits the colors of the output to a palette, see [`OutputPalette`].
+	pub palette: Option<OutputPalette>,
+	/// Simulates how the output looks once printed, see [`SoftProof`].
+	pub proof: Option<SoftProof>,
+	/// The unit which lengths measured by dimensions are labeled in, see [`crate::units::DocumentUnits`].
+	pub units: crate::units::DocumentUnits,
 }
 
 pub struct EditorApi<'a, Io> {
@@ -165,6 +182,10 @@ pub struct EditorApi<'a, Io> {
 	pub node_graph_message_sender: &'a dyn NodeGraphUpdateSender,
 	pub imaginate_preferences: &'a dyn GetImaginatePreferences,
 	pub render_config: RenderConfig,
+	/// Renders of the frames around the current time of the document's animation, drawn faintly beneath the artwork when it isn't being exported.
+	pub onion_skin: Arc<[OnionSkinFrame]>,
+	/// The encodings of the images drawn in the viewport, which belong to the runtime so they're kept between renders.
+	pub image_previews: ImagePreviewCache,
 }
 
 impl<'a, Io> Clone for EditorApi<'a, Io> {
@@ -176,6 +197,8 @@ impl<'a, Io> Clone for EditorApi<'a, Io> {
 			node_graph_message_sender: self.node_graph_message_sender,
 			imaginate_preferences: self.imaginate_preferences,
 			render_config: self.render_config,
+			onion_skin: self.onion_skin.clone(),
+			image_previews: self.image_previews.clone(),
 		}
 	}
 }
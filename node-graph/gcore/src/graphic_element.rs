@@ -13,6 +13,15 @@ use glam::{DAffine2, DVec2, IVec2, UVec2};
 
 pub mod renderer;
 
+#[cfg(feature = "std")]
+mod contact_sheet;
+#[cfg(feature = "std")]
+pub use contact_sheet::*;
+mod dimension;
+pub use dimension::*;
+mod mask;
+pub use mask::*;
+
 #[derive(Copy, Clone, Debug, PartialEq, DynAny, specta::Type)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AlphaBlending {
@@ -39,6 +48,49 @@ impl AlphaBlending {
 	}
 }
 
+/// The `id`, `class`, and `data-*` attributes given to the SVG element of a layer when it's exported, so the exported graphic can be styled and scripted on the web.
+#[derive(Clone, Debug, Default, Hash, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SvgAttributes {
+	pub id: String,
+	pub classes: Vec<String>,
+	/// The names (without the `data-` prefix) and values of the data attributes.
+	pub data: Vec<(String, String)>,
+}
+
+impl SvgAttributes {
+	pub const EMPTY: Self = Self {
+		id: String::new(),
+		classes: Vec::new(),
+		data: Vec::new(),
+	};
+
+	/// Reads the classes separated by whitespace, and the data attributes written as one `name=value` per line.
+	/// Names that aren't valid for a data attribute are skipped.
+	pub fn parse(id: &str, classes: &str, data: &str) -> Self {
+		let data = data
+			.lines()
+			.filter_map(|line| {
+				let (name, value) = line.split_once('=').unwrap_or((line, ""));
+				let name = name.trim();
+				let name = name.strip_prefix("data-").unwrap_or(name).to_ascii_lowercase();
+				let valid = !name.is_empty() && !name.starts_with("xml") && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+				valid.then(|| (name, value.trim().to_string()))
+			})
+			.collect();
+
+		Self {
+			id: id.trim().to_string(),
+			classes: classes.split_whitespace().map(String::from).collect(),
+			data,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.id.is_empty() && self.classes.is_empty() && self.data.is_empty()
+	}
+}
+
 /// A list of [`GraphicElement`]s
 #[derive(Clone, Debug, PartialEq, DynAny, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -46,6 +98,12 @@ pub struct GraphicGroup {
 	elements: Vec<GraphicElement>,
 	pub transform: DAffine2,
 	pub alpha_blending: AlphaBlending,
+	/// Attributes written on the group's `<g>` element when exporting.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub svg_attributes: SvgAttributes,
+	/// Artwork multiplying the alpha of the group's contents as it's composited.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub mask: Option<Box<Mask>>,
 }
 
 impl core::hash::Hash for GraphicGroup {
@@ -53,6 +111,8 @@ impl core::hash::Hash for GraphicGroup {
 		self.transform.to_cols_array().iter().for_each(|element| element.to_bits().hash(state));
 		self.elements.hash(state);
 		self.alpha_blending.hash(state);
+		self.svg_attributes.hash(state);
+		self.mask.hash(state);
 	}
 }
 
@@ -73,6 +133,8 @@ pub enum GraphicElement {
 	Text(String),
 	/// The bounds for displaying a page of contained content
 	Artboard(Artboard),
+	/// An annotation measuring the distance or angle between two points
+	Dimension(Dimension),
 }
 
 // TODO: Can this be removed? It doesn't necessarily make that much sense to have a default when, instead, the entire GraphicElement just shouldn't exist if there's no specific content to assign it.
@@ -165,25 +227,50 @@ fn to_graphic_group<Data: Into<GraphicGroup>>(data: Data) -> GraphicGroup {
 	data.into()
 }
 
-pub struct ConstructArtboardNode<Contents, Location, Dimensions, Background, Clip> {
+pub struct SvgAttributesNode<Id, Classes, Data> {
+	id: Id,
+	classes: Classes,
+	data: Data,
+}
+
+#[node_fn(SvgAttributesNode)]
+fn svg_attributes<Content: Into<GraphicGroup>>(content: Content, id: String, classes: String, data: String) -> GraphicGroup {
+	let mut graphic_group = content.into();
+	graphic_group.svg_attributes = SvgAttributes::parse(&id, &classes, &data);
+	graphic_group
+}
+
+pub struct ConstructArtboardNode<Contents, Location, Dimensions, Background, Clip, Linked> {
 	contents: Contents,
 	location: Location,
 	dimensions: Dimensions,
 	background: Background,
 	clip: Clip,
+	linked: Linked,
 }
 
+/// Constructs an artboard from its contents, or from the contents of the artboard it's linked to when the linked input is given the output of another Artboard layer.
+/// A linked artboard keeps its own location, dimensions, background, and clipping, so it can be a variant of the same design at another size.
 #[node_fn(ConstructArtboardNode)]
-async fn construct_artboard<Fut: Future<Output = GraphicGroup>>(
+async fn construct_artboard<Fut1: Future<Output = GraphicGroup>, Fut2: Future<Output = ArtboardGroup>>(
 	mut footprint: Footprint,
-	contents: impl Node<Footprint, Output = Fut>,
+	contents: impl Node<Footprint, Output = Fut1>,
 	location: IVec2,
 	dimensions: IVec2,
 	background: Color,
 	clip: bool,
+	linked: impl Node<Footprint, Output = Fut2>,
 ) -> Artboard {
-	footprint.transform *= DAffine2::from_translation(location.as_dvec2());
-	let graphic_group = self.contents.eval(footprint).await;
+	// The last artboard of the linked Artboard layer's output is the one that layer added, beneath which are the artboards further down the stack
+	let linked = self.linked.eval(footprint).await;
+
+	let graphic_group = match linked.artboards.last() {
+		Some(source) => source.graphic_group.clone(),
+		None => {
+			footprint.transform *= DAffine2::from_translation(location.as_dvec2());
+			self.contents.eval(footprint).await
+		}
+	};
 	Artboard {
 		graphic_group,
 		location: location.min(location + dimensions),
@@ -212,19 +299,8 @@ async fn add_artboard<Data: Into<Artboard>, Fut1: Future<Output = ArtboardGroup>
 }
 
 impl From<ImageFrame<Color>> for GraphicElement {
-	fn from(mut image_frame: ImageFrame<Color>) -> Self {
-		use base64::Engine;
-
-		let image = &image_frame.image;
-		if !image.data.is_empty() {
-			let output = image.to_png();
-			let preamble = "data:image/png;base64,";
-			let mut base64_string = String::with_capacity(preamble.len() + output.len() * 4);
-			base64_string.push_str(preamble);
-			base64::engine::general_purpose::STANDARD.encode_string(output, &mut base64_string);
-			image_frame.image.base64_string = Some(base64_string);
-		}
-
+	fn from(image_frame: ImageFrame<Color>) -> Self {
+		// The image is encoded for display by the renderer, which does so away from the evaluation of the graph
 		GraphicElement::ImageFrame(image_frame)
 	}
 }
@@ -243,6 +319,11 @@ impl From<Artboard> for GraphicElement {
 		GraphicElement::Artboard(artboard)
 	}
 }
+impl From<Dimension> for GraphicElement {
+	fn from(dimension: Dimension) -> Self {
+		GraphicElement::Dimension(dimension)
+	}
+}
 
 impl Deref for GraphicGroup {
 	type Target = Vec<GraphicElement>;
@@ -274,6 +355,8 @@ where
 			elements: (vec![value.into()]),
 			transform: DAffine2::IDENTITY,
 			alpha_blending: AlphaBlending::default(),
+			svg_attributes: SvgAttributes::EMPTY,
+			mask: None,
 		}
 	}
 }
@@ -283,8 +366,16 @@ impl GraphicGroup {
 		elements: Vec::new(),
 		transform: DAffine2::IDENTITY,
 		alpha_blending: AlphaBlending::new(),
+		svg_attributes: SvgAttributes::EMPTY,
+		mask: None,
 	};
 
+	/// The number of bytes held by the pixels of the images in the group, including those of its mask, which make up most of the memory it holds.
+	pub fn memory_size(&self) -> usize {
+		let mask = self.mask.as_ref().map_or(0, |mask| mask.graphic_group.memory_size());
+		self.iter().map(GraphicElement::memory_size).sum::<usize>() + mask
+	}
+
 	pub fn to_usvg_tree(&self, resolution: UVec2, viewbox: [DVec2; 2]) -> usvg::Tree {
 		let mut root_node = usvg::Group::default();
 		let tree = usvg::Tree {
@@ -304,6 +395,16 @@ impl GraphicGroup {
 }
 
 impl GraphicElement {
+	/// The number of bytes held by the pixels of the images in the element.
+	pub fn memory_size(&self) -> usize {
+		match self {
+			GraphicElement::GraphicGroup(graphic_group) => graphic_group.memory_size(),
+			GraphicElement::ImageFrame(image_frame) => image_frame.image.memory_size(),
+			GraphicElement::Artboard(artboard) => artboard.graphic_group.memory_size(),
+			GraphicElement::VectorData(_) | GraphicElement::Text(_) | GraphicElement::Dimension(_) => 0,
+		}
+	}
+
 	fn to_usvg_node(&self) -> usvg::Node {
 		fn to_transform(transform: DAffine2) -> usvg::Transform {
 			let cols = transform.to_cols_array();
@@ -392,6 +493,53 @@ impl GraphicElement {
 			}
 			// TODO
 			GraphicElement::Artboard(_board) => usvg::Node::Group(Box::default()),
+			GraphicElement::Dimension(dimension) => dimension.to_usvg_node(),
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::renderer::{GraphicElementRendered, ImageRenderMode, RenderParams, RenderSvgSegmentList, SvgRender};
+	use super::*;
+
+	#[test]
+	fn svg_attributes_on_export() {
+		let attributes = SvgAttributes::parse(" logo ", "brand  dark", "data-role = banner\nnot valid=1\n\nTrack=\"a\" & b");
+		assert_eq!(attributes.id, "logo");
+		assert_eq!(attributes.classes, ["brand", "dark"]);
+		assert_eq!(attributes.data, [("role".to_string(), "banner".to_string()), ("track".to_string(), "\"a\" & b".to_string())]);
+
+		let mut graphic_group = GraphicGroup::EMPTY;
+		graphic_group.svg_attributes = attributes;
+		let render = |for_export| {
+			let mut render = SvgRender::new();
+			let render_params = RenderParams::new(crate::vector::style::ViewMode::Normal, ImageRenderMode::Base64, None, false, false, for_export);
+			graphic_group.render_svg(&mut render, &render_params);
+			render.svg.to_svg_string()
+		};
+
+		let exported = render(true);
+		assert!(exported.contains(r#"id="logo""#));
+		assert!(exported.contains(r#"class="brand dark""#));
+		assert!(exported.contains(r#"data-track="&quot;a&quot; &amp; b""#));
+		assert!(!render(false).contains("logo"));
+	}
+
+	#[test]
+	fn memory_size_counts_nested_images() {
+		let image_frame = ImageFrame {
+			image: crate::raster::Image::new(4, 2, Color::BLACK),
+			..Default::default()
+		};
+		let pixels = 8 * core::mem::size_of::<Color>();
+		assert_eq!(image_frame.image.memory_size(), pixels);
+
+		let mut inner = GraphicGroup::EMPTY;
+		inner.push(image_frame.clone().into());
+		inner.push(VectorData::empty().into());
+		let mut outer = GraphicGroup::from(image_frame);
+		outer.push(inner.into());
+		assert_eq!(outer.memory_size(), 2 * pixels);
+	}
+}
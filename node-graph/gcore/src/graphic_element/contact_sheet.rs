@@ -0,0 +1,228 @@
+use super::renderer::GraphicElementRendered;
+use super::{GraphicElement, GraphicGroup};
+use crate::application_io::EditorApi;
+use crate::raster::{Image, ImageFrame};
+use crate::text::{load_face, to_path, Font, TypesettingConfig};
+use crate::transform::Footprint;
+use crate::vector::style::Fill;
+use crate::vector::VectorData;
+use crate::{Color, Node};
+
+use dyn_any::{DynAny, StaticType};
+
+use core::future::Future;
+use glam::{DAffine2, DVec2};
+
+/// How each element is scaled to fit inside its cell of a contact sheet.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum CellFit {
+	/// Scale the element so it is entirely visible within the cell, leaving empty space along one axis.
+	#[default]
+	Contain,
+	/// Scale the element so it fills the entire cell. Images are cropped to the cell, other content overflows it.
+	Cover,
+}
+
+impl CellFit {
+	pub fn list() -> [CellFit; 2] {
+		[CellFit::Contain, CellFit::Cover]
+	}
+}
+
+impl core::fmt::Display for CellFit {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			CellFit::Contain => write!(f, "Contain"),
+			CellFit::Cover => write!(f, "Cover"),
+		}
+	}
+}
+
+/// Lays out each element of the input group in a grid of equally sized cells, reading left to right and then top to bottom.
+/// Each line of `captions` is placed as a label beneath the cell with the same index.
+pub struct ContactSheetNode<Elements, Columns, Gutter, CellSize, Fit, Captions, FontName, FontSize> {
+	elements: Elements,
+	columns: Columns,
+	gutter: Gutter,
+	cell_size: CellSize,
+	fit: Fit,
+	captions: Captions,
+	font_name: FontName,
+	font_size: FontSize,
+}
+
+#[node_macro::node_fn(ContactSheetNode)]
+async fn contact_sheet<'a: 'input, T, Fut: Future<Output = GraphicGroup>>(
+	editor: EditorApi<'a, T>,
+	elements: impl Node<Footprint, Output = Fut>,
+	columns: u32,
+	gutter: f64,
+	cell_size: DVec2,
+	fit: CellFit,
+	captions: String,
+	font_name: Font,
+	font_size: f64,
+) -> GraphicGroup {
+	// Each element is rescaled into its cell, so it is evaluated without the viewport transform, which would cull whatever lies outside the viewport before it is moved into view.
+	// The rest of the viewport footprint still applies, so exports get the elements at full quality.
+	let viewport = editor.render_config.viewport;
+	let footprint = Footprint {
+		resolution: viewport.resolution,
+		quality: viewport.quality,
+		target: viewport.target,
+		..Footprint::default()
+	};
+	let elements = self.elements.eval(footprint).await;
+
+	let columns = columns.max(1) as usize;
+	let gutter = gutter.max(0.);
+	let cell_size = cell_size.max(DVec2::ONE);
+	let font_size = font_size.max(0.);
+
+	let captions = captions.lines().collect::<Vec<_>>();
+	let caption_height = if captions.iter().any(|caption| !caption.is_empty()) { font_size * 1.5 } else { 0. };
+	let buzz_face = editor.font_cache.get(&font_name).and_then(|data| load_face(data));
+
+	let mut sheet = GraphicGroup::EMPTY;
+
+	for (index, element, cell_origin) in cell_layout(&elements, columns, gutter, cell_size, caption_height) {
+		if let Some(cell) = fit_element_in_cell(element, cell_origin, cell_size, fit) {
+			sheet.push(cell.into());
+		}
+
+		let Some(caption) = captions.get(index).filter(|caption| !caption.is_empty()) else { continue };
+		let mut caption_data = VectorData::from_subpaths(to_path(
+			caption,
+			buzz_face.clone(),
+			TypesettingConfig {
+				max_width: Some(cell_size.x),
+				..TypesettingConfig::new(font_size)
+			},
+		));
+		caption_data.style.set_fill(Fill::Solid(Color::BLACK));
+		caption_data.transform = DAffine2::from_translation(cell_origin + DVec2::new(0., cell_size.y + font_size * 0.25));
+		sheet.push(caption_data.into());
+	}
+
+	sheet
+}
+
+/// Pairs each element that is laid out with its index in the group and the origin of its cell.
+/// Text elements have no bounds yet, so they are left out of the layout without taking up a cell, but the indices still count them so the captions stay with the elements they were written for.
+fn cell_layout(elements: &GraphicGroup, columns: usize, gutter: f64, cell_size: DVec2, caption_height: f64) -> impl Iterator<Item = (usize, &GraphicElement, DVec2)> {
+	elements
+		.iter()
+		.enumerate()
+		.filter(|(_, element)| !matches!(element, GraphicElement::Text(_)))
+		.enumerate()
+		.map(move |(cell, (index, element))| {
+			let (column, row) = (cell % columns, cell / columns);
+			let cell_origin = DVec2::new(column as f64 * (cell_size.x + gutter), row as f64 * (cell_size.y + caption_height + gutter));
+			(index, element, cell_origin)
+		})
+}
+
+/// Wraps the element in a group that scales and centers it within the cell, or returns `None` if the element has no area to lay out.
+fn fit_element_in_cell(element: &GraphicElement, cell_origin: DVec2, cell_size: DVec2, fit: CellFit) -> Option<GraphicGroup> {
+	let element = match (element, fit) {
+		(GraphicElement::ImageFrame(image_frame), CellFit::Cover) => GraphicElement::from(crop_image_to_aspect_ratio(image_frame, cell_size.x / cell_size.y)),
+		_ => element.clone(),
+	};
+
+	let [min, max] = element.bounding_box(DAffine2::IDENTITY)?;
+	let size = max - min;
+	if size.x <= 0. || size.y <= 0. {
+		return None;
+	}
+
+	let ratios = cell_size / size;
+	let scale = match fit {
+		CellFit::Contain => ratios.min_element(),
+		CellFit::Cover => ratios.max_element(),
+	};
+	let offset = cell_origin + (cell_size - size * scale) / 2.;
+
+	let mut cell = GraphicGroup::EMPTY;
+	cell.push(element);
+	cell.transform = DAffine2::from_translation(offset) * DAffine2::from_scale(DVec2::splat(scale)) * DAffine2::from_translation(-min);
+	Some(cell)
+}
+
+/// Crops the center of the image so its layer space bounds match the given aspect ratio (width divided by height).
+fn crop_image_to_aspect_ratio(image_frame: &ImageFrame<Color>, aspect_ratio: f64) -> ImageFrame<Color> {
+	let (width, height) = (image_frame.image.width, image_frame.image.height);
+	let size = (image_frame.transform.transform_vector2(DVec2::X).length(), image_frame.transform.transform_vector2(DVec2::Y).length());
+	if width == 0 || height == 0 || size.0 <= 0. || size.1 <= 0. {
+		return image_frame.clone();
+	}
+
+	let current_aspect_ratio = size.0 / size.1;
+	let (crop_width, crop_height) = if current_aspect_ratio > aspect_ratio {
+		(((width as f64 * aspect_ratio / current_aspect_ratio).round() as u32).clamp(1, width), height)
+	} else {
+		(width, ((height as f64 * current_aspect_ratio / aspect_ratio).round() as u32).clamp(1, height))
+	};
+	let (offset_x, offset_y) = ((width - crop_width) / 2, (height - crop_height) / 2);
+
+	let mut data = Vec::with_capacity((crop_width * crop_height) as usize);
+	for y in offset_y..offset_y + crop_height {
+		let start = (y * width + offset_x) as usize;
+		data.extend_from_slice(&image_frame.image.data[start..start + crop_width as usize]);
+	}
+
+	let crop_transform = DAffine2::from_translation(DVec2::new(offset_x as f64 / width as f64, offset_y as f64 / height as f64))
+		* DAffine2::from_scale(DVec2::new(crop_width as f64 / width as f64, crop_height as f64 / height as f64));
+
+	ImageFrame {
+		image: Image {
+			width: crop_width,
+			height: crop_height,
+			data: data.into(),
+			base64_string: None,
+		},
+		transform: image_frame.transform * crop_transform,
+		alpha_blending: image_frame.alpha_blending,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use bezier_rs::Subpath;
+
+	fn square(size: f64) -> GraphicElement {
+		VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::splat(size))).into()
+	}
+
+	#[test]
+	fn text_elements_keep_their_captions_index() {
+		let mut elements = GraphicGroup::EMPTY;
+		elements.push(square(1.));
+		elements.push(GraphicElement::Text("Skipped".into()));
+		elements.push(square(1.));
+		elements.push(square(1.));
+
+		let layout = cell_layout(&elements, 2, 10., DVec2::splat(100.), 20.).map(|(index, _, origin)| (index, origin)).collect::<Vec<_>>();
+		assert_eq!(layout, [(0, DVec2::ZERO), (2, DVec2::new(110., 0.)), (3, DVec2::new(0., 130.))]);
+	}
+
+	#[test]
+	fn elements_are_centered_in_their_cells() {
+		let rectangle = VectorData::from_subpath(Subpath::new_rect(DVec2::new(5., 5.), DVec2::new(25., 15.))).into();
+		let cell_origin = DVec2::new(110., 0.);
+
+		let contained = fit_element_in_cell(&rectangle, cell_origin, DVec2::splat(100.), CellFit::Contain).unwrap();
+		let [min, max] = contained.bounding_box(DAffine2::IDENTITY).unwrap();
+		assert!(min.abs_diff_eq(DVec2::new(110., 25.), 1e-9) && max.abs_diff_eq(DVec2::new(210., 75.), 1e-9), "{min} {max}");
+
+		let covered = fit_element_in_cell(&rectangle, cell_origin, DVec2::splat(100.), CellFit::Cover).unwrap();
+		let [min, max] = covered.bounding_box(DAffine2::IDENTITY).unwrap();
+		assert!(min.abs_diff_eq(DVec2::new(60., 0.), 1e-9) && max.abs_diff_eq(DVec2::new(260., 100.), 1e-9), "{min} {max}");
+	}
+
+	#[test]
+	fn elements_without_area_take_no_cell_content() {
+		let line = VectorData::from_subpath(Subpath::new_line(DVec2::ZERO, DVec2::new(10., 0.))).into();
+		assert!(fit_element_in_cell(&line, DVec2::ZERO, DVec2::splat(100.), CellFit::Contain).is_none());
+	}
+}
@@ -0,0 +1,336 @@
+//! Dimensions, annotations which measure the distance or angle between two anchors of the artwork and label it in the units of the document.
+
+use super::renderer::{format_transform_matrix, ClickTarget, GraphicElementRendered, RenderParams, SvgRender};
+use super::{GraphicElement, GraphicGroup};
+use crate::raster::Color;
+use crate::transform::Footprint;
+use crate::vector::VectorData;
+use crate::Node;
+
+use bezier_rs::Subpath;
+use core::future::Future;
+use dyn_any::{DynAny, StaticType};
+use glam::{DAffine2, DVec2};
+
+/// The length of the arrowheads at the ends of the line measuring a distance.
+const ARROW_LENGTH: f64 = 8.;
+const LABEL_FONT_SIZE: f64 = 12.;
+/// How far labels are drawn from the line or arc they label.
+const LABEL_GAP: f64 = 4.;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+pub enum DimensionKind {
+	/// The straight line distance between the anchors.
+	#[default]
+	Distance,
+	/// The angle of the line from the first anchor to the second, in degrees clockwise from the right.
+	Angle,
+}
+
+impl core::fmt::Display for DimensionKind {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			DimensionKind::Distance => write!(f, "Distance"),
+			DimensionKind::Angle => write!(f, "Angle"),
+		}
+	}
+}
+
+impl DimensionKind {
+	pub fn list() -> [DimensionKind; 2] {
+		[DimensionKind::Distance, DimensionKind::Angle]
+	}
+}
+
+/// A measurement of the distance or angle between two points, drawn with its label like the dimensions of a technical drawing.
+///
+/// The label is written when the dimension is rendered, in the units of the [`RenderParams`], so changing the units of the document relabels every dimension.
+#[derive(Clone, Debug, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dimension {
+	pub start: DVec2,
+	pub end: DVec2,
+	pub kind: DimensionKind,
+	/// How far to the side of the measured points the line showing a distance is drawn, or the radius of the arc showing an angle.
+	pub offset: f64,
+	/// The number of digits after the decimal point in the label.
+	pub precision: u32,
+	pub color: Color,
+	pub transform: DAffine2,
+}
+
+impl core::hash::Hash for Dimension {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.start.to_array().iter().for_each(|x| x.to_bits().hash(state));
+		self.end.to_array().iter().for_each(|x| x.to_bits().hash(state));
+		self.kind.hash(state);
+		self.offset.to_bits().hash(state);
+		self.precision.hash(state);
+		self.color.hash(state);
+		self.transform.to_cols_array().iter().for_each(|x| x.to_bits().hash(state));
+	}
+}
+
+impl Dimension {
+	pub fn new(start: DVec2, end: DVec2, kind: DimensionKind) -> Self {
+		Self {
+			start,
+			end,
+			kind,
+			offset: 20.,
+			precision: 1,
+			color: Color::from_rgbaf32_unchecked(0.9, 0.2, 0.2, 1.),
+			transform: DAffine2::IDENTITY,
+		}
+	}
+
+	/// The measured points in the coordinates of the dimension's parent, where distances are measured.
+	fn points(&self) -> (DVec2, DVec2) {
+		(self.transform.transform_point2(self.start), self.transform.transform_point2(self.end))
+	}
+
+	/// The distance between the points in pixels, or the angle of the line between them in degrees.
+	pub fn measurement(&self) -> f64 {
+		let (start, end) = self.points();
+		match self.kind {
+			DimensionKind::Distance => start.distance(end),
+			DimensionKind::Angle => clockwise_angle(end - start).to_degrees(),
+		}
+	}
+
+	/// The measurement written in the units of the render, or in degrees for an angle.
+	pub fn label(&self, render_params: &RenderParams) -> String {
+		let precision = self.precision as usize;
+		match self.kind {
+			DimensionKind::Distance => render_params.units.format(self.measurement(), precision),
+			DimensionKind::Angle => format!("{:.precision$}°", self.measurement()),
+		}
+	}
+
+	/// The points the lines of the dimension pass through, in the coordinates of the dimension's parent.
+	fn outline(&self) -> Vec<DVec2> {
+		let (start, end) = self.points();
+		match self.kind {
+			DimensionKind::Distance => {
+				let side = (end - start).normalize_or_zero().perp() * self.offset;
+				vec![start, start + side, end + side, end]
+			}
+			DimensionKind::Angle => {
+				let radius = self.offset.abs();
+				let reference = start + DVec2::X * radius;
+				let measured = start + (end - start).normalize_or_zero() * radius;
+				vec![reference, start, end, measured]
+			}
+		}
+	}
+}
+
+/// The angle of the direction in radians, clockwise from the right since the y-axis points down.
+fn clockwise_angle(direction: DVec2) -> f64 {
+	direction.y.atan2(direction.x)
+}
+
+fn push_point(path: &mut String, command: char, point: DVec2) {
+	path.push_str(&format!("{command}{} {}", point.x, point.y));
+}
+
+impl GraphicElementRendered for Dimension {
+	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
+		let (start, end) = self.points();
+		let direction = (end - start).normalize_or_zero();
+		let mut path = String::new();
+
+		// The label sits beside the middle of the line or arc, turned to read along it without ever being upside down
+		let (label_position, label_angle) = match self.kind {
+			DimensionKind::Distance => {
+				let side = direction.perp() * self.offset;
+				let (from, to) = (start + side, end + side);
+				// Extension lines from the measured points out to the dimension line
+				if self.offset != 0. {
+					push_point(&mut path, 'M', start);
+					push_point(&mut path, 'L', from);
+					push_point(&mut path, 'M', end);
+					push_point(&mut path, 'L', to);
+				}
+				push_point(&mut path, 'M', from);
+				push_point(&mut path, 'L', to);
+
+				// Arrowheads pointing outward at both ends of the dimension line
+				let arrow_length = ARROW_LENGTH.min(start.distance(end) / 2.);
+				for (tip, inward) in [(from, direction), (to, -direction)] {
+					push_point(&mut path, 'M', tip + inward * arrow_length + inward.perp() * arrow_length / 3.);
+					push_point(&mut path, 'L', tip);
+					push_point(&mut path, 'L', tip + inward * arrow_length - inward.perp() * arrow_length / 3.);
+				}
+
+				let outward = if self.offset < 0. { -direction.perp() } else { direction.perp() };
+				let mut angle = clockwise_angle(direction).to_degrees();
+				if angle.abs() > 90. {
+					angle -= 180_f64.copysign(angle);
+				}
+				((from + to) / 2. + outward * (LABEL_GAP + LABEL_FONT_SIZE / 2.), angle)
+			}
+			DimensionKind::Angle => {
+				let radius = self.offset.abs();
+				let angle = clockwise_angle(direction);
+				// The horizontal reference line, the measured line, and the arc between them
+				push_point(&mut path, 'M', start + DVec2::X * radius * 1.25);
+				push_point(&mut path, 'L', start);
+				push_point(&mut path, 'L', end);
+				push_point(&mut path, 'M', start + DVec2::X * radius);
+				let arc_end = start + direction * radius;
+				path.push_str(&format!("A{radius} {radius} 0 0 {} {} {}", (angle > 0.) as u8, arc_end.x, arc_end.y));
+
+				let bisector = DVec2::from_angle(angle / 2.);
+				(start + bisector * (radius + LABEL_GAP + LABEL_FONT_SIZE / 2.), 0.)
+			}
+		};
+
+		let color = format!("#{}", render_params.output_color(self.color).rgb_hex());
+		render.parent_tag(
+			"g",
+			|attributes| {
+				attributes.push("class", "dimension");
+			},
+			|render| {
+				render.leaf_tag("path", |attributes| {
+					attributes.push("d", path);
+					attributes.push("fill", "none");
+					attributes.push("stroke", color.clone());
+				});
+				render.parent_tag(
+					"text",
+					|attributes| {
+						attributes.push("x", label_position.x.to_string());
+						attributes.push("y", label_position.y.to_string());
+						attributes.push("fill", color.clone());
+						attributes.push("font-size", format!("{LABEL_FONT_SIZE}px"));
+						attributes.push("text-anchor", "middle");
+						attributes.push("dominant-baseline", "middle");
+						if label_angle != 0. {
+							attributes.push(
+								"transform",
+								format_transform_matrix(DAffine2::from_angle_translation(label_angle.to_radians(), label_position) * DAffine2::from_translation(-label_position)),
+							);
+						}
+					},
+					|render| render.svg.push(self.label(render_params).into()),
+				);
+			},
+		);
+	}
+
+	fn bounding_box(&self, transform: DAffine2) -> Option<[DVec2; 2]> {
+		let points = self.outline().into_iter().map(|point| transform.transform_point2(point));
+		points.fold(None, |bounds, point| Some(bounds.map_or([point, point], |[min, max]: [DVec2; 2]| [min.min(point), max.max(point)])))
+	}
+
+	fn add_click_targets(&self, click_targets: &mut Vec<ClickTarget>) {
+		let mut subpath = Subpath::from_anchors(self.outline(), false);
+		subpath.apply_transform(self.transform.inverse());
+		click_targets.push(ClickTarget { stroke_width: ARROW_LENGTH, subpath });
+	}
+}
+
+/// The position of a point of the vector data in the coordinates of its parent, if it has a point at the index.
+fn anchor(vector_data: &VectorData, index: u32) -> Option<DVec2> {
+	let position = vector_data.point_domain.positions().get(index as usize)?;
+	Some(vector_data.transform.transform_point2(*position))
+}
+
+pub struct DimensionNode<FromShape, FromPoint, ToShape, ToPoint, Kind, Offset, Precision, Color> {
+	from: FromShape,
+	from_point: FromPoint,
+	to: ToShape,
+	to_point: ToPoint,
+	kind: Kind,
+	offset: Offset,
+	precision: Precision,
+	color: Color,
+}
+
+/// Measures between a point of one shape and a point of another (or the same) shape, so the dimension follows the points as the shapes are edited.
+/// The points are picked by their index among the points of their shapes, and the dimension is left out if either shape has no point at its index.
+#[node_macro::node_fn(DimensionNode)]
+async fn dimension<FromFuture: Future<Output = VectorData>, ToFuture: Future<Output = VectorData>>(
+	footprint: Footprint,
+	from: impl Node<Footprint, Output = FromFuture>,
+	from_point: u32,
+	to: impl Node<Footprint, Output = ToFuture>,
+	to_point: u32,
+	kind: DimensionKind,
+	offset: f64,
+	precision: u32,
+	color: Color,
+) -> GraphicGroup {
+	let from = self.from.eval(footprint).await;
+	let to = self.to.eval(footprint).await;
+
+	let mut group = GraphicGroup::EMPTY;
+	if let (Some(start), Some(end)) = (anchor(&from, from_point), anchor(&to, to_point)) {
+		group.push(GraphicElement::Dimension(Dimension {
+			offset,
+			precision,
+			color,
+			..Dimension::new(start, end, kind)
+		}));
+	}
+	group
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::renderer::ImageRenderMode;
+	use crate::units::{DocumentUnits, LengthUnit};
+	use crate::vector::style::ViewMode;
+
+	fn render_params(length_unit: LengthUnit) -> RenderParams {
+		let mut render_params = RenderParams::new(ViewMode::Normal, ImageRenderMode::Base64, None, false, false, false);
+		render_params.units = DocumentUnits::new(length_unit);
+		render_params
+	}
+
+	#[test]
+	fn label_distance_in_document_units() {
+		let dimension = Dimension {
+			precision: 2,
+			..Dimension::new(DVec2::ZERO, DVec2::new(0., 48.), DimensionKind::Distance)
+		};
+		assert_eq!(dimension.label(&render_params(LengthUnit::Pixels)), "48.00 px");
+		assert_eq!(dimension.label(&render_params(LengthUnit::Inches)), "0.50 in");
+		assert_eq!(dimension.label(&render_params(LengthUnit::Millimeters)), "12.70 mm");
+
+		let mut render = SvgRender::new();
+		dimension.render_svg(&mut render, &render_params(LengthUnit::Inches));
+		assert!(render.svg.to_svg_string().contains(">0.50 in</text>"));
+	}
+
+	#[test]
+	fn measure_after_transform() {
+		let dimension = Dimension {
+			transform: DAffine2::from_scale(DVec2::splat(2.)),
+			..Dimension::new(DVec2::ZERO, DVec2::new(3., 4.), DimensionKind::Distance)
+		};
+		assert_eq!(dimension.measurement(), 10.);
+		let [min, max] = dimension.bounding_box(DAffine2::IDENTITY).unwrap();
+		assert!(min.abs_diff_eq(DVec2::new(-16., 0.), 1e-9) && max.abs_diff_eq(DVec2::new(6., 20.), 1e-9), "{min} {max}");
+	}
+
+	#[test]
+	fn measure_angles_clockwise_from_the_right() {
+		let angle = Dimension::new(DVec2::new(10., 10.), DVec2::new(20., 20.), DimensionKind::Angle);
+		assert!((angle.measurement() - 45.).abs() < 1e-9);
+		assert_eq!(angle.label(&render_params(LengthUnit::Millimeters)), "45.0°");
+	}
+
+	#[test]
+	fn anchors_follow_the_shape() {
+		let mut rectangle = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::new(30., 10.)));
+		rectangle.transform = DAffine2::from_translation(DVec2::new(5., 0.));
+		assert_eq!(anchor(&rectangle, 1), Some(DVec2::new(35., 0.)));
+		assert_eq!(anchor(&rectangle, 4), None);
+	}
+}
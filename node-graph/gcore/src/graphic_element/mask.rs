@@ -0,0 +1,220 @@
+//! Masks, which hide the parts of a group lying outside the opaque (or bright) areas of other artwork when the group is composited.
+
+use super::renderer::{GraphicElementRendered, Quad, RenderParams, RenderSvgSegmentList, SvgRender};
+use super::GraphicGroup;
+use crate::transform::Footprint;
+use crate::uuid::generate_uuid;
+use crate::Node;
+
+use core::fmt::Write;
+use core::future::Future;
+use dyn_any::{DynAny, StaticType};
+use glam::DVec2;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+pub enum MaskMode {
+	/// The content is as opaque as the mask.
+	#[default]
+	Alpha,
+	/// The content is as opaque as the mask is bright, with the transparent parts of the mask counting as black.
+	Luminance,
+}
+
+impl core::fmt::Display for MaskMode {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			MaskMode::Alpha => write!(f, "Alpha"),
+			MaskMode::Luminance => write!(f, "Luminance"),
+		}
+	}
+}
+
+impl MaskMode {
+	pub fn list() -> [MaskMode; 2] {
+		[MaskMode::Alpha, MaskMode::Luminance]
+	}
+}
+
+/// Artwork whose alpha (or luminance) multiplies the alpha of the group it masks, applied when the group is composited rather than baked into its contents.
+///
+/// The mask is drawn in the coordinates of the masked group's contents, so it moves along with them.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mask {
+	pub graphic_group: GraphicGroup,
+	pub mode: MaskMode,
+	/// Shows the content where the mask is transparent (or dark) instead.
+	pub invert: bool,
+	/// The distance over which the edges of the mask are softened, in the coordinates of the masked contents.
+	pub feather: f64,
+}
+
+impl core::hash::Hash for Mask {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.graphic_group.hash(state);
+		self.mode.hash(state);
+		self.invert.hash(state);
+		self.feather.to_bits().hash(state);
+	}
+}
+
+impl Mask {
+	/// Writes the `<mask>` (and the `<filter>` turning the artwork into its alpha) into the defs of the render, returning the ID to reference it by.
+	/// The masked bounds are those of the masked contents in the coordinates the mask is drawn in, outside of which nothing needs masking.
+	pub(super) fn render_defs(&self, render: &mut SvgRender, masked_bounds: [DVec2; 2], render_params: &RenderParams) -> String {
+		let id = format!("mask-{}", generate_uuid());
+		let [min, max] = masked_bounds;
+
+		// The mask is drawn in its own render so it ends up in the defs, at the same transform as the masked contents
+		let mut mask_render = SvgRender::new();
+		mask_render.transform = render.transform;
+		self.graphic_group.render_svg(&mut mask_render, render_params);
+		render.svg_defs.push_str(&mask_render.svg_defs);
+		render.image_data.append(&mut mask_render.image_data);
+		render.linked_images.append(&mut mask_render.linked_images);
+		let mut content = mask_render.svg.to_svg_string();
+
+		// Feathering is measured in the coordinates of the masked contents, which are scaled by the transform they're drawn with
+		let scale = render.transform.matrix2.determinant().abs().sqrt();
+		let feather = if self.feather.is_finite() { self.feather.max(0.) * scale } else { 0. };
+
+		let mut filter = String::new();
+		if self.mode == MaskMode::Luminance {
+			// The luminance of the unpremultiplied color, multiplied by the alpha so transparent areas count as black
+			filter.push_str(r#"<feColorMatrix in="SourceGraphic" type="luminanceToAlpha" result="luminance" />"#);
+			filter.push_str(r#"<feComposite in="luminance" in2="SourceAlpha" operator="arithmetic" k1="1" />"#);
+		}
+		if feather > 0. {
+			let _ = write!(filter, r#"<feGaussianBlur stdDeviation="{}" />"#, feather / 2.);
+		}
+		if self.invert {
+			filter.push_str(r#"<feComponentTransfer><feFuncA type="table" tableValues="1 0" /></feComponentTransfer>"#);
+		}
+
+		let size = max - min;
+		if !filter.is_empty() {
+			// The filter covers all of the masked contents, so inverting leaves them showing beyond the mask, and reaches far enough past them to take in whatever is blurred into them
+			let margin = feather * 1.5;
+			let filter_id = format!("{id}-filter");
+			let _ = write!(
+				render.svg_defs,
+				r#"<filter id="{filter_id}" filterUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}" color-interpolation-filters="sRGB">{filter}</filter>"#,
+				min.x - margin,
+				min.y - margin,
+				size.x + margin * 2.,
+				size.y + margin * 2.,
+			);
+			content = format!(r#"<g filter="url(#{filter_id})">{content}</g>"#);
+		}
+
+		let _ = write!(
+			render.svg_defs,
+			r#"<mask id="{id}" maskUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}" mask-type="alpha">{content}</mask>"#,
+			min.x, min.y, size.x, size.y,
+		);
+		id
+	}
+}
+
+impl GraphicGroup {
+	/// The bounds of the contents of the group, in the coordinates its mask is drawn in.
+	pub(super) fn masked_bounds(&self, render: &SvgRender) -> Option<[DVec2; 2]> {
+		self.iter().filter_map(|element| element.bounding_box(render.transform)).reduce(Quad::combine_bounds)
+	}
+}
+
+pub struct MaskNode<Content, MaskGraphic, Mode, Invert, Feather> {
+	content: Content,
+	mask: MaskGraphic,
+	mode: Mode,
+	invert: Invert,
+	feather: Feather,
+}
+
+/// Shows the content only where the mask is opaque, or where it's bright in luminance mode, for cutting artwork to the shape of other artwork without changing either of them.
+/// The feather (in pixels) softens the edges of the mask.
+#[node_macro::node_fn(MaskNode)]
+async fn mask<ContentFuture: Future<Output = GraphicGroup>, MaskFuture: Future<Output = GraphicGroup>>(
+	footprint: Footprint,
+	content: impl Node<Footprint, Output = ContentFuture>,
+	mask: impl Node<Footprint, Output = MaskFuture>,
+	mode: MaskMode,
+	invert: bool,
+	feather: f64,
+) -> GraphicGroup {
+	let content = self.content.eval(footprint).await;
+	let mask = self.mask.eval(footprint).await;
+
+	// The content is wrapped in a group of its own, so the mask is drawn in the same coordinates as it whatever the content's transform is
+	let mut group = GraphicGroup::EMPTY;
+	group.push(content.into());
+	group.mask = Some(Box::new(Mask {
+		graphic_group: mask,
+		mode,
+		invert,
+		feather: feather.max(0.),
+	}));
+	group
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::renderer::ImageRenderMode;
+	use crate::vector::style::ViewMode;
+	use crate::vector::VectorData;
+
+	use bezier_rs::Subpath;
+
+	fn masked(mode: MaskMode, invert: bool, feather: f64) -> GraphicGroup {
+		let square = |size: f64| GraphicGroup::from(VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::splat(size))));
+
+		let mut group = GraphicGroup::EMPTY;
+		group.push(square(100.).into());
+		group.mask = Some(Box::new(Mask {
+			graphic_group: square(50.),
+			mode,
+			invert,
+			feather,
+		}));
+		group
+	}
+
+	fn render(group: &GraphicGroup) -> (String, String) {
+		let mut render = SvgRender::new();
+		let render_params = RenderParams::new(ViewMode::Normal, ImageRenderMode::Base64, None, false, false, true);
+		group.render_svg(&mut render, &render_params);
+		(render.svg.to_svg_string(), render.svg_defs)
+	}
+
+	#[test]
+	fn alpha_mask_is_referenced_by_the_group() {
+		let (svg, defs) = render(&masked(MaskMode::Alpha, false, 0.));
+		let id = defs.split(r#"<mask id=""#).nth(1).and_then(|rest| rest.split('"').next()).expect("The mask should be in the defs");
+		assert!(svg.contains(&format!(r#"mask="url(#{id})""#)));
+		assert!(defs.contains(r#"mask-type="alpha""#));
+		assert!(defs.contains(r#"x="0" y="0" width="100" height="100""#));
+		assert!(!defs.contains("<filter"));
+	}
+
+	#[test]
+	fn luminance_inverted_and_feathered_masks_are_filtered() {
+		let (_, defs) = render(&masked(MaskMode::Luminance, true, 10.));
+		assert!(defs.contains("luminanceToAlpha"));
+		assert!(defs.contains(r#"stdDeviation="5""#));
+		assert!(defs.contains(r#"tableValues="1 0""#));
+		// The filter reaches past the masked contents by the feather
+		assert!(defs.contains(r#"x="-15" y="-15" width="130" height="130""#));
+	}
+
+	#[test]
+	fn feather_follows_the_transform() {
+		let mut render = SvgRender::new();
+		render.transform = glam::DAffine2::from_scale(DVec2::splat(2.));
+		let render_params = RenderParams::new(ViewMode::Normal, ImageRenderMode::Base64, None, false, false, true);
+		masked(MaskMode::Alpha, false, 4.).render_svg(&mut render, &render_params);
+		assert!(render.svg_defs.contains(r#"stdDeviation="4""#));
+	}
+}
@@ -1,19 +1,38 @@
+mod image_export;
+mod image_preview;
+mod lottie;
+mod onion_skin;
+mod overlays;
+mod palette;
+mod proofing;
 mod quad;
+mod svg_optimizer;
 
 use crate::raster::{BlendMode, Image, ImageFrame};
 use crate::transform::Transform;
 use crate::uuid::generate_uuid;
+use crate::vector::style::{Fill, PathStyle, StrokeAlign, ViewMode};
 use crate::vector::PointId;
-use crate::{vector::VectorData, Artboard, Color, GraphicElement, GraphicGroup};
+use crate::{vector::VectorData, AlphaBlending, Artboard, Color, GraphicElement, GraphicGroup};
+pub use image_export::{ImageEncoding, ImageExportPolicy};
+pub use image_preview::{ImagePreviewCache, PendingImagePreview};
+pub use lottie::LottieShape;
+pub use onion_skin::{render_onion_skin, OnionSkin, OnionSkinFrame};
+pub use overlays::{OverlayLayer, OverlayShape};
+pub use palette::{Dithering, OutputPalette, PaletteMapper, PalettePreset};
+pub use proofing::{OutputProfile, Proofer, SoftProof};
 pub use quad::Quad;
+pub use svg_optimizer::SvgOptimization;
 
 use bezier_rs::Subpath;
 
 use base64::Engine;
-use glam::{DAffine2, DVec2};
+use glam::{DAffine2, DMat2, DVec2};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 /// Represents a clickable target for the layer
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClickTarget {
 	pub subpath: bezier_rs::Subpath<PointId>,
@@ -66,6 +85,8 @@ pub struct SvgRender {
 	pub svg_defs: String,
 	pub transform: DAffine2,
 	pub image_data: Vec<(u64, Image<Color>)>,
+	/// The files of the images linked to by the SVG rather than embedded in it, named by their path relative to the SVG.
+	pub linked_images: Vec<(String, Vec<u8>)>,
 	indent: usize,
 }
 
@@ -76,6 +97,7 @@ impl SvgRender {
 			svg_defs: String::new(),
 			transform: DAffine2::IDENTITY,
 			image_data: Vec::new(),
+			linked_images: Vec::new(),
 			indent: 0,
 		}
 	}
@@ -111,6 +133,25 @@ impl SvgRender {
 		self.svg.push("</g></svg>".into());
 	}
 
+	/// Rounds the coordinates of the `<defs />` and merges the identical ones (like the gradients of copies of a shape), pointing the references to merged ones at the one that's kept.
+	pub fn optimize_defs(&mut self, optimization: SvgOptimization) {
+		let (defs, merged_ids) = optimization.optimize_defs(&self.svg_defs);
+		self.svg_defs = defs;
+
+		for segment in &mut self.svg {
+			let SvgSegment::String(text) = segment else { continue };
+			for (from, to) in &merged_ids {
+				// References are written as either `url(#id)` or `url('#id')`
+				for end in [")", "'"] {
+					let reference = format!("#{from}{end}");
+					if text.contains(&reference) {
+						*text = text.replace(&reference, &format!("#{to}{end}"));
+					}
+				}
+			}
+		}
+	}
+
 	pub fn leaf_tag(&mut self, name: impl Into<SvgSegment>, attributes: impl FnOnce(&mut SvgRenderAttrs)) {
 		self.indent();
 		self.svg.push("<".into());
@@ -169,6 +210,22 @@ pub struct RenderParams {
 	pub hide_artboards: bool,
 	/// Are we exporting? Causes the text above an artboard to be hidden.
 	pub for_export: bool,
+	/// Shrinks the SVG by simplifying the group structure and rounding coordinates, which is only worth the extra work when exporting.
+	pub svg_optimization: Option<SvgOptimization>,
+	/// How images are resized, encoded, and embedded or linked when exporting.
+	pub image_export: Option<ImageExportPolicy>,
+	/// Faint renders of the adjacent frames of the animation, drawn beneath the artwork but over the backgrounds.
+	pub onion_skin: Arc<[OnionSkinFrame]>,
+	/// Draws the large images of the viewport from downscaled previews until they've been encoded at full resolution, rather than encoding them while rendering.
+	pub image_previews: Option<ImagePreviewCache>,
+	/// Mixes the colors of gradients in linear RGB, to match blending which is composited in linear RGB.
+	pub linear_compositing: bool,
+	/// Limits every color of the output to those of a palette, for retro, pixel art, or risograph work.
+	pub palette: Option<PaletteMapper>,
+	/// Simulates how the colors of the output look once printed with the inks and paper of a CMYK output profile.
+	pub proof: Option<Proofer>,
+	/// The unit which lengths measured by dimensions are labeled in.
+	pub units: crate::units::DocumentUnits,
 }
 
 impl RenderParams {
@@ -180,8 +237,74 @@ impl RenderParams {
 			thumbnail,
 			hide_artboards,
 			for_export,
+			svg_optimization: None,
+			image_export: None,
+			onion_skin: Vec::new().into(),
+			image_previews: None,
+			linear_compositing: false,
+			palette: None,
+			proof: None,
+			units: crate::units::DocumentUnits::default(),
 		}
 	}
+
+	/// The color as it's drawn, limited to the palette and proofed for print if the output is.
+	pub fn output_color(&self, color: Color) -> Color {
+		let color = self.palette.as_ref().map_or(color, |palette| palette.map_color(color));
+		self.proof.as_ref().map_or(color, |proof| proof.proof_color(color))
+	}
+
+	/// The fill and stroke of a shape as they're drawn, limited to the palette and proofed for print if the output is.
+	pub fn output_style<'a>(&self, style: Cow<'a, PathStyle>) -> Cow<'a, PathStyle> {
+		let style = match &self.palette {
+			Some(palette) => Cow::Owned(palette.map_style(&style, self.linear_compositing)),
+			None => style,
+		};
+		match &self.proof {
+			Some(proof) => Cow::Owned(proof.map_style(&style, self.linear_compositing)),
+			None => style,
+		}
+	}
+
+	/// The opacity that's drawn, which is either opaque or transparent when the output is limited to a palette, since partly transparent colors would mix to colors outside it.
+	pub fn output_opacity(&self, opacity: f32) -> f32 {
+		match self.palette {
+			Some(_) => opacity.round(),
+			None => opacity,
+		}
+	}
+
+	/// Whether blend modes are drawn, which they aren't when the output is limited to a palette, since blending mixes colors outside it.
+	pub fn blends(&self) -> bool {
+		self.palette.is_none()
+	}
+}
+
+/// Pushes the attributes applying the opacity and blend mode to an element, leaving out the ones which don't change how it's drawn.
+fn push_alpha_blending(attributes: &mut SvgRenderAttrs, alpha_blending: AlphaBlending, render_params: &RenderParams) {
+	let opacity = render_params.output_opacity(alpha_blending.opacity);
+	if opacity < 1. {
+		attributes.push("opacity", opacity.to_string());
+	}
+
+	if alpha_blending.blend_mode != BlendMode::default() && render_params.blends() {
+		attributes.push("style", alpha_blending.blend_mode.render());
+	}
+}
+
+/// Encodes the image as a PNG written into a `data:` URL.
+fn png_data_url(image: &Image<Color>) -> String {
+	let output = image.to_png();
+	let preamble = "data:image/png;base64,";
+	let mut base64_string = String::with_capacity(preamble.len() + output.len() * 4);
+	base64_string.push_str(preamble);
+	base64::engine::general_purpose::STANDARD.encode_string(output, &mut base64_string);
+	base64_string
+}
+
+/// Escapes the characters which can't appear as themselves in a double quoted attribute value.
+fn escape_attribute(value: &str) -> String {
+	value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
 }
 
 pub fn format_transform_matrix(transform: DAffine2) -> String {
@@ -207,6 +330,8 @@ pub trait GraphicElementRendered {
 	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams);
 	fn bounding_box(&self, transform: DAffine2) -> Option<[DVec2; 2]>;
 	fn add_click_targets(&self, click_targets: &mut Vec<ClickTarget>);
+	/// Adds the vector shapes this draws, in drawing order, to the snapshot of a frame exported as part of a Lottie animation.
+	fn collect_lottie_shapes(&self, _transform: DAffine2, _opacity: f64, _shapes: &mut Vec<LottieShape>) {}
 	fn to_usvg_node(&self) -> usvg::Node {
 		let mut render = SvgRender::new();
 		let render_params = RenderParams::new(crate::vector::style::ViewMode::Normal, ImageRenderMode::Base64, None, false, false, false);
@@ -240,20 +365,107 @@ pub trait GraphicElementRendered {
 	}
 }
 
+impl GraphicGroup {
+	/// Adds the attributes of the group's `<g>` tag other than its transform.
+	fn push_attributes(&self, attributes: &mut SvgRenderAttrs, render_params: &RenderParams) {
+		let opacity = render_params.output_opacity(self.alpha_blending.opacity);
+		if opacity < 1. {
+			attributes.push("opacity", opacity.to_string());
+		}
+
+		if self.alpha_blending.blend_mode != BlendMode::default() && render_params.blends() {
+			attributes.push("style", self.alpha_blending.blend_mode.render());
+		}
+
+		// A group with nothing in it draws nothing to mask
+		if let (Some(mask), Some(bounds)) = (&self.mask, self.masked_bounds(attributes.0)) {
+			let id = mask.render_defs(attributes.0, bounds, render_params);
+			attributes.push("mask", format!("url(#{id})"));
+		}
+
+		// These are left out of the viewport, where they could clash with the editor's own IDs and classes
+		if render_params.for_export {
+			let svg_attributes = &self.svg_attributes;
+			if !svg_attributes.id.is_empty() {
+				attributes.push("id", escape_attribute(&svg_attributes.id));
+			}
+			if !svg_attributes.classes.is_empty() {
+				attributes.push("class", escape_attribute(&svg_attributes.classes.join(" ")));
+			}
+			for (name, value) in &svg_attributes.data {
+				attributes.push(format!("data-{name}"), escape_attribute(value));
+			}
+		}
+	}
+
+	/// Whether anything in the group is drawn with a stroke, whose width would be changed by scaling the group's transform.
+	fn has_stroke(&self, render_params: &RenderParams) -> bool {
+		// Every path is drawn as an outline in the outline view mode
+		if render_params.view_mode == ViewMode::Outline {
+			return true;
+		}
+
+		self.iter().any(|element| match element {
+			GraphicElement::VectorData(vector_data) => vector_data.style.stroke().is_some_and(|stroke| stroke.weight() > 0.),
+			GraphicElement::GraphicGroup(graphic_group) => graphic_group.has_stroke(render_params),
+			GraphicElement::ImageFrame(_) => false,
+			_ => true,
+		})
+	}
+
+	/// Renders the group with as few `<g>` tags as possible, by applying its transform to the coordinates of its contents and leaving out the tag when the group has no other attributes.
+	///
+	/// Paths are drawn with the transform applied to their points rather than scaling their stroke, so the transform is only applied to the contents when it's a translation or nothing is stroked.
+	/// Otherwise, the transforms of nested groups are still merged into a single `<g>` tag.
+	fn render_svg_optimized(&self, render: &mut SvgRender, render_params: &RenderParams, optimization: SvgOptimization) {
+		let transform = render.transform * self.transform;
+		let can_apply_transform = !self.contains_artboard() && (transform.matrix2 == DMat2::IDENTITY || !self.has_stroke(render_params));
+		let has_attributes = render_params.output_opacity(self.alpha_blending.opacity) < 1.
+			|| (self.alpha_blending.blend_mode != BlendMode::default() && render_params.blends())
+			|| (render_params.for_export && !self.svg_attributes.is_empty())
+			|| self.mask.is_some();
+		let single_group = matches!(self.elements.as_slice(), [GraphicElement::GraphicGroup(_)]);
+
+		let parent_transform = render.transform;
+		if !has_attributes && (can_apply_transform || single_group) {
+			// The transform is passed on to the contents, which either apply it to their coordinates or write it on their own `<g>` tag
+			render.transform = transform;
+			for element in self.iter() {
+				element.render_svg(render, render_params);
+			}
+		} else {
+			render.transform = if can_apply_transform { transform } else { DAffine2::IDENTITY };
+			render.parent_tag(
+				"g",
+				|attributes| {
+					if !can_apply_transform && transform != DAffine2::IDENTITY {
+						attributes.push("transform", optimization.round_numbers(&format_transform_matrix(transform)));
+					}
+					self.push_attributes(attributes, render_params);
+				},
+				|render| {
+					for element in self.iter() {
+						element.render_svg(render, render_params);
+					}
+				},
+			);
+		}
+		render.transform = parent_transform;
+	}
+}
+
 impl GraphicElementRendered for GraphicGroup {
 	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
+		if let Some(optimization) = render_params.svg_optimization {
+			self.render_svg_optimized(render, render_params, optimization);
+			return;
+		}
+
 		render.parent_tag(
 			"g",
 			|attributes| {
 				attributes.push("transform", format_transform_matrix(self.transform));
-
-				if self.alpha_blending.opacity < 1. {
-					attributes.push("opacity", self.alpha_blending.opacity.to_string());
-				}
-
-				if self.alpha_blending.blend_mode != BlendMode::default() {
-					attributes.push("style", self.alpha_blending.blend_mode.render());
-				}
+				self.push_attributes(attributes, render_params);
 			},
 			|render| {
 				for element in self.iter() {
@@ -278,6 +490,12 @@ impl GraphicElementRendered for GraphicGroup {
 		}
 	}
 
+	fn collect_lottie_shapes(&self, transform: DAffine2, opacity: f64, shapes: &mut Vec<LottieShape>) {
+		for element in self.iter() {
+			element.collect_lottie_shapes(transform * self.transform, opacity * self.alpha_blending.opacity as f64, shapes);
+		}
+	}
+
 	fn to_usvg_node(&self) -> usvg::Node {
 		let mut root_node = usvg::Group::default();
 		for element in self.iter() {
@@ -291,38 +509,152 @@ impl GraphicElementRendered for GraphicGroup {
 	}
 }
 
+/// Adds the clip path (for an inside stroke) or mask (for an outside stroke) hiding the half of a stroke drawn at twice its weight which falls on the wrong side of the path,
+/// returning the attribute applying it along with its ID. The ID is derived from the path, so rendering the same artwork again produces the same SVG.
+/// The mask is used instead of painting the stroke beneath the fill, which only works for opaque fills, so the inner half stays hidden under translucent fills and paths without a fill.
+fn stroke_align_defs(svg_defs: &mut String, path: &str, stroke_align: StrokeAlign, bounds: [DVec2; 2], stroke_reach: f64) -> (&'static str, String) {
+	use std::fmt::Write;
+	use std::hash::{Hash, Hasher};
+
+	let mut hasher = std::collections::hash_map::DefaultHasher::new();
+	path.hash(&mut hasher);
+	stroke_reach.to_bits().hash(&mut hasher);
+	let hash = hasher.finish();
+
+	if stroke_align == StrokeAlign::Inside {
+		let id = format!("stroke-inside-{hash:x}");
+		let _ = write!(svg_defs, r##"<clipPath id="{id}"><path d="{path}" /></clipPath>"##);
+		return ("clip-path", id);
+	}
+
+	// The mask covers everything the stroke can reach, except for the inside of the path
+	let id = format!("stroke-outside-{hash:x}");
+	let [min, max] = [bounds[0] - DVec2::splat(stroke_reach), bounds[1] + DVec2::splat(stroke_reach)];
+	let (x, y, width, height) = (min.x, min.y, max.x - min.x, max.y - min.y);
+	let _ = write!(
+		svg_defs,
+		r##"<mask id="{id}" maskUnits="userSpaceOnUse" x="{x}" y="{y}" width="{width}" height="{height}"><rect x="{x}" y="{y}" width="{width}" height="{height}" fill="white" /><path d="{path}" fill="black" /></mask>"##
+	);
+	("mask", id)
+}
+
 impl GraphicElementRendered for VectorData {
 	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
 		let multiplied_transform = render.transform * self.transform;
 		let layer_bounds = self.bounding_box().unwrap_or_default();
 		let transformed_bounds = self.bounding_box_with_transform(multiplied_transform).unwrap_or_default();
 
+		// Subpaths of text runs with their own colors are drawn separately after the rest, so they can be filled with those colors
+		let mut text_fill_paths = vec![String::new(); self.text_fills.len()];
+
 		let mut path = String::new();
 		for (_, subpath) in self.region_bezier_paths() {
 			let _ = subpath.subpath_to_svg(&mut path, multiplied_transform);
 		}
-		for subpath in self.stroke_bezier_paths() {
-			let _ = subpath.subpath_to_svg(&mut path, multiplied_transform);
+		let mut closed = true;
+		for (index, subpath) in self.stroke_bezier_paths().enumerate() {
+			closed &= subpath.closed();
+			match self.text_fills.iter().position(|text_fill| text_fill.start <= index && index < text_fill.end) {
+				Some(text_fill_index) => {
+					let _ = subpath.subpath_to_svg(&mut text_fill_paths[text_fill_index], multiplied_transform);
+				}
+				None => {
+					let _ = subpath.subpath_to_svg(&mut path, multiplied_transform);
+				}
+			}
+		}
+		if let Some(optimization) = render_params.svg_optimization {
+			path = optimization.round_numbers(&path);
+			for text_fill_path in &mut text_fill_paths {
+				*text_fill_path = optimization.round_numbers(text_fill_path);
+			}
 		}
 
-		render.leaf_tag("path", |attributes| {
-			attributes.push("class", "vector-data");
+		// Stroke alignment only applies to closed paths, which have a well defined inside and outside
+		let stroke_align = self
+			.style
+			.stroke()
+			.map(|stroke| stroke.align)
+			.filter(|_| render_params.view_mode != ViewMode::Outline)
+			.unwrap_or_default();
+		let base_style = if stroke_align != StrokeAlign::Center && !closed {
+			let mut style = self.style.clone();
+			if let Some(stroke) = style.stroke() {
+				style.set_stroke(stroke.with_align(StrokeAlign::Center));
+			}
+			Cow::Owned(style)
+		} else {
+			Cow::Borrowed(&self.style)
+		};
+		let style = render_params.output_style(base_style.clone());
+
+		// The text runs with their own colors keep the stroke of the vector data
+		let text_fill_styles = self.text_fills.iter().map(|text_fill| {
+			let mut style = base_style.clone().into_owned();
+			style.set_fill(Fill::Solid(text_fill.color));
+			render_params.output_style(Cow::Owned(style))
+		});
+		let text_fill_paths = text_fill_paths.into_iter().zip(text_fill_styles).filter(|(path, _)| !path.is_empty());
+
+		// Inside and outside strokes are drawn at twice their weight, and the half on the wrong side of the path is hidden
+		let stroke_align = if closed { stroke_align } else { StrokeAlign::Center };
+		// How far a stroke drawn at twice its weight can reach beyond the path, including the points of its mitered joins
+		let stroke_reach = self.style.stroke().map_or(0., |stroke| stroke.weight() * stroke.line_join_miter_limit.max(1.));
+		let draw_path = |render: &mut SvgRender, path: &str, style: &PathStyle, alpha_blending: AlphaBlending, class: bool, stroke_align: StrokeAlign| {
+			render.leaf_tag("path", |attributes| {
+				if class {
+					attributes.push("class", "vector-data");
+				}
 
-			attributes.push("d", path);
+				if stroke_align != StrokeAlign::Center {
+					let (attribute, id) = stroke_align_defs(&mut attributes.0.svg_defs, path, stroke_align, transformed_bounds, stroke_reach);
+					attributes.push(attribute, format!("url(#{id})"));
+				}
 
-			let fill_and_stroke = self
-				.style
-				.render(render_params.view_mode, &mut attributes.0.svg_defs, multiplied_transform, layer_bounds, transformed_bounds);
-			attributes.push_val(fill_and_stroke);
+				attributes.push("d", path.to_string());
 
-			if self.alpha_blending.opacity < 1. {
-				attributes.push("opacity", self.alpha_blending.opacity.to_string());
-			}
+				let fill_and_stroke = style.render(
+					render_params.view_mode,
+					render_params.linear_compositing,
+					&mut attributes.0.svg_defs,
+					multiplied_transform,
+					layer_bounds,
+					transformed_bounds,
+				);
+				attributes.push_val(fill_and_stroke);
+				push_alpha_blending(attributes, alpha_blending, render_params);
 
-			if self.alpha_blending.blend_mode != BlendMode::default() {
-				attributes.push("style", self.alpha_blending.blend_mode.render());
+				// Antialiasing would blend the edges with the colors behind them, adding colors outside the palette
+				if render_params.palette.is_some() {
+					attributes.push("shape-rendering", "crispEdges");
+				}
+			});
+		};
+		for (path, style) in std::iter::once((path, style)).chain(text_fill_paths) {
+			// The mask hiding the inner half of an outside stroke would hide the fill too, so they're drawn as separate paths
+			let outside_stroke = stroke_align == StrokeAlign::Outside && style.stroke().is_some();
+			if (!style.composites_separately() && !outside_stroke) || render_params.view_mode == ViewMode::Outline {
+				draw_path(render, &path, &*style, self.alpha_blending, true, stroke_align);
+				continue;
 			}
-		});
+
+			// The fill and stroke with their own opacity or blend mode are drawn as separate paths, grouped so the layer's own opacity and blend mode apply to both together
+			render.parent_tag(
+				"g",
+				|attributes| {
+					attributes.push("class", "vector-data");
+					push_alpha_blending(attributes, self.alpha_blending, render_params);
+				},
+				|render| {
+					let (fill_style, stroke_style) = style.split_fill_and_stroke();
+					let fill = (fill_style, style.fill_blending(), StrokeAlign::Center);
+					let stroke = (stroke_style, style.stroke_blending(), stroke_align);
+					for (part_style, alpha_blending, stroke_align) in [fill, stroke].into_iter().filter(|(part_style, ..)| *part_style.fill() != Fill::None || part_style.stroke().is_some()) {
+						draw_path(render, &path, &part_style, alpha_blending, false, stroke_align);
+					}
+				},
+			);
+		}
 	}
 
 	fn bounding_box(&self, transform: DAffine2) -> Option<[DVec2; 2]> {
@@ -335,6 +667,20 @@ impl GraphicElementRendered for VectorData {
 		click_targets.extend(self.stroke_bezier_paths().map(|subpath| ClickTarget { stroke_width, subpath }));
 	}
 
+	fn collect_lottie_shapes(&self, transform: DAffine2, opacity: f64, shapes: &mut Vec<LottieShape>) {
+		let fill = match self.style.fill() {
+			Fill::None => None,
+			fill => Some(fill.color()),
+		};
+		shapes.push(LottieShape {
+			transform: transform * self.transform,
+			opacity: opacity * self.alpha_blending.opacity as f64,
+			subpaths: self.region_bezier_paths().map(|(_, subpath)| subpath).chain(self.stroke_bezier_paths()).collect(),
+			fill,
+			stroke: self.style.stroke(),
+		});
+	}
+
 	fn to_usvg_node(&self) -> usvg::Node {
 		use bezier_rs::BezierHandles;
 		use usvg::tiny_skia_path::PathBuilder;
@@ -376,13 +722,17 @@ impl GraphicElementRendered for Artboard {
 			// Background
 			render.leaf_tag("rect", |attributes| {
 				attributes.push("class", "artboard-bg");
-				attributes.push("fill", format!("#{}", self.background.rgba_hex()));
+				let background = render_params.output_color(self.background);
+				attributes.push("fill", format!("#{}", background.rgba_hex()));
 				attributes.push("x", self.location.x.min(self.location.x + self.dimensions.x).to_string());
 				attributes.push("y", self.location.y.min(self.location.y + self.dimensions.y).to_string());
 				attributes.push("width", self.dimensions.x.abs().to_string());
 				attributes.push("height", self.dimensions.y.abs().to_string());
 			});
 		}
+		// Onion skin frames show within the artboard, over its background but beneath its artwork
+		let [start, end] = [self.location.as_dvec2(), (self.location + self.dimensions).as_dvec2()];
+		render_onion_skin(&render_params.onion_skin, render, Some([start.min(end), start.max(end)]));
 		if !render_params.hide_artboards && !render_params.for_export {
 			// Label
 			render.parent_tag(
@@ -453,6 +803,10 @@ impl GraphicElementRendered for Artboard {
 		click_targets.push(ClickTarget { stroke_width: 0., subpath });
 	}
 
+	fn collect_lottie_shapes(&self, transform: DAffine2, opacity: f64, shapes: &mut Vec<LottieShape>) {
+		self.graphic_group.collect_lottie_shapes(transform, opacity, shapes);
+	}
+
 	fn contains_artboard(&self) -> bool {
 		true
 	}
@@ -471,36 +825,54 @@ impl GraphicElementRendered for crate::ArtboardGroup {
 		self.get_graphic_group().add_click_targets(click_targets);
 	}
 
+	fn collect_lottie_shapes(&self, transform: DAffine2, opacity: f64, shapes: &mut Vec<LottieShape>) {
+		for artboard in &self.artboards {
+			artboard.collect_lottie_shapes(transform, opacity, shapes);
+		}
+	}
+
 	fn contains_artboard(&self) -> bool {
 		self.artboards.len() > 0
 	}
 }
 impl GraphicElementRendered for ImageFrame<Color> {
 	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
-		let transform: String = format_transform_matrix(self.transform * render.transform);
+		let image_transform = render.transform * self.transform;
+		let mut transform: String = format_transform_matrix(image_transform);
+		if let Some(optimization) = render_params.svg_optimization {
+			transform = optimization.round_numbers(&transform);
+		}
 
 		match render_params.image_render_mode {
 			ImageRenderMode::Base64 => {
-				let image = &self.image;
-				if image.data.is_empty() {
+				if self.image.data.is_empty() {
 					return;
 				}
-
-				let base64_string = image.base64_string.clone().unwrap_or_else(|| {
-					let output = image.to_png();
-					let preamble = "data:image/png;base64,";
-					let mut base64_string = String::with_capacity(preamble.len() + output.len() * 4);
-					base64_string.push_str(preamble);
-					base64::engine::general_purpose::STANDARD.encode_string(output, &mut base64_string);
-					base64_string
-				});
+				let mapped_image = render_params.palette.as_ref().map(|palette| palette.map_image(&self.image));
+				let image = mapped_image.as_ref().unwrap_or(&self.image);
+				let proofed_image = render_params.proof.as_ref().map(|proof| proof.map_image(image));
+				let image = proofed_image.as_ref().unwrap_or(image);
+
+				let base64_string = match render_params.image_export {
+					Some(image_export) => image_export.image_href(image, image_transform, &mut render.linked_images),
+					None => image.base64_string.clone().unwrap_or_else(|| {
+						// Exports need every image at full resolution, while the viewport can show a preview until the image is encoded
+						match &render_params.image_previews {
+							Some(image_previews) if !render_params.for_export => image_previews.image_data_url(image, render_params.thumbnail),
+							_ => png_data_url(image),
+						}
+					}),
+				};
 				render.leaf_tag("image", |attributes| {
 					attributes.push("width", 1.to_string());
 					attributes.push("height", 1.to_string());
 					attributes.push("preserveAspectRatio", "none");
 					attributes.push("transform", transform);
 					attributes.push("href", base64_string);
-					if self.alpha_blending.blend_mode != BlendMode::default() {
+					if render_params.palette.is_some() {
+						// Smoothing the scaled pixels would blend them into colors outside the palette
+						attributes.push("style", "image-rendering: pixelated");
+					} else if self.alpha_blending.blend_mode != BlendMode::default() {
 						attributes.push("style", self.alpha_blending.blend_mode.render());
 					}
 				});
@@ -547,6 +919,7 @@ impl GraphicElementRendered for GraphicElement {
 			GraphicElement::Text(_) => todo!("Render a text GraphicElement"),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.render_svg(render, render_params),
 			GraphicElement::Artboard(artboard) => artboard.render_svg(render, render_params),
+			GraphicElement::Dimension(dimension) => dimension.render_svg(render, render_params),
 		}
 	}
 
@@ -557,6 +930,7 @@ impl GraphicElementRendered for GraphicElement {
 			GraphicElement::Text(_) => todo!("Bounds of a text GraphicElement"),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.bounding_box(transform),
 			GraphicElement::Artboard(artboard) => artboard.bounding_box(transform),
+			GraphicElement::Dimension(dimension) => dimension.bounding_box(transform),
 		}
 	}
 
@@ -567,6 +941,17 @@ impl GraphicElementRendered for GraphicElement {
 			GraphicElement::Text(_) => todo!("click target for text GraphicElement"),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.add_click_targets(click_targets),
 			GraphicElement::Artboard(artboard) => artboard.add_click_targets(click_targets),
+			GraphicElement::Dimension(dimension) => dimension.add_click_targets(click_targets),
+		}
+	}
+
+	fn collect_lottie_shapes(&self, transform: DAffine2, opacity: f64, shapes: &mut Vec<LottieShape>) {
+		match self {
+			GraphicElement::VectorData(vector_data) => vector_data.collect_lottie_shapes(transform, opacity, shapes),
+			GraphicElement::GraphicGroup(graphic_group) => graphic_group.collect_lottie_shapes(transform, opacity, shapes),
+			GraphicElement::Artboard(artboard) => artboard.collect_lottie_shapes(transform, opacity, shapes),
+			// Images, text, and dimensions aren't vector shapes, so they're left out
+			GraphicElement::ImageFrame(_) | GraphicElement::Text(_) | GraphicElement::Dimension(_) => {}
 		}
 	}
 
@@ -577,6 +962,7 @@ impl GraphicElementRendered for GraphicElement {
 			GraphicElement::Text(text) => text.to_usvg_node(),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.to_usvg_node(),
 			GraphicElement::Artboard(artboard) => artboard.to_usvg_node(),
+			GraphicElement::Dimension(dimension) => dimension.to_usvg_node(),
 		}
 	}
 
@@ -587,6 +973,7 @@ impl GraphicElementRendered for GraphicElement {
 			GraphicElement::Text(text) => text.contains_artboard(),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.contains_artboard(),
 			GraphicElement::Artboard(artboard) => artboard.contains_artboard(),
+			GraphicElement::Dimension(dimension) => dimension.contains_artboard(),
 		}
 	}
 }
@@ -742,3 +1129,62 @@ impl<'a> SvgRenderAttrs<'a> {
 		self.0.svg.push(value.into());
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::vector::style::Stroke;
+
+	fn square(fill: Fill, align: StrokeAlign) -> VectorData {
+		let mut vector_data = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::splat(10.)));
+		vector_data.style.set_fill(fill);
+		vector_data.style.set_stroke(Stroke::new(Some(Color::BLACK), 2.).with_align(align));
+		vector_data
+	}
+
+	fn render_svg(vector_data: &VectorData) -> (String, String) {
+		let mut render = SvgRender::new();
+		vector_data.render_svg(&mut render, &RenderParams::new(ViewMode::Normal, ImageRenderMode::Base64, None, false, false, false));
+		(render.svg.to_svg_string(), render.svg_defs)
+	}
+
+	#[test]
+	fn inside_stroke_is_clipped_to_the_path() {
+		let vector_data = square(Fill::Solid(Color::RED), StrokeAlign::Inside);
+		let (svg, defs) = render_svg(&vector_data);
+		assert!(svg.contains(r#"stroke-width="4""#));
+		assert!(svg.contains(r#"clip-path="url(#stroke-inside-"#));
+		assert!(defs.contains("<clipPath"));
+
+		// Rendering the same artwork again produces the same SVG
+		assert_eq!(render_svg(&vector_data), (svg, defs));
+	}
+
+	#[test]
+	fn outside_stroke_masks_out_the_inside_of_the_path() {
+		for fill in [Fill::None, Fill::Solid(Color::RED.with_alpha(0.5))] {
+			let vector_data = square(fill.clone(), StrokeAlign::Outside);
+			let (svg, defs) = render_svg(&vector_data);
+			assert!(!svg.contains("paint-order"));
+			assert!(defs.contains("<mask"));
+
+			// Only the stroke is masked, so a fill is drawn by a separate path which isn't
+			let paths = svg.split("<path").skip(1).collect::<Vec<_>>();
+			assert_eq!(paths.len(), if fill == Fill::None { 1 } else { 2 });
+			let stroke_path = paths.last().unwrap();
+			assert!(stroke_path.contains(r#"stroke-width="4""#) && stroke_path.contains(r#"mask="url(#stroke-outside-"#));
+			assert!(paths.iter().filter(|path| path.contains("mask=")).count() == 1);
+
+			assert_eq!(render_svg(&vector_data), (svg, defs));
+		}
+	}
+
+	#[test]
+	fn open_paths_keep_a_centered_stroke() {
+		let mut vector_data = VectorData::from_subpath(Subpath::new_line(DVec2::ZERO, DVec2::X * 10.));
+		vector_data.style.set_stroke(Stroke::new(Some(Color::BLACK), 2.).with_align(StrokeAlign::Outside));
+		let (svg, defs) = render_svg(&vector_data);
+		assert!(svg.contains(r#"stroke-width="2""#));
+		assert!(!svg.contains("mask=") && defs.is_empty());
+	}
+}
@@ -0,0 +1,125 @@
+use crate::raster::Image;
+use crate::Color;
+
+use base64::Engine;
+use glam::{DAffine2, DVec2};
+use std::borrow::Cow;
+
+/// The number of SVG user units (which are CSS pixels) in an inch.
+const CSS_PIXELS_PER_INCH: f64 = 96.;
+
+/// The file format that raster images are written as in exported SVGs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageEncoding {
+	/// Lossless and keeps transparency.
+	#[default]
+	Png,
+	/// Much smaller for photos, but lossy and drawn over a white background.
+	Jpeg,
+}
+
+/// How raster images are written into exported SVGs, which keeps documents full of photos from exporting to enormous files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageExportPolicy {
+	/// Images with more pixels per inch than this, at the size they're drawn in the document, are downsampled to it.
+	pub max_ppi: Option<f64>,
+	pub encoding: ImageEncoding,
+	/// The quality of JPEG images from 1 to 100.
+	pub jpeg_quality: u8,
+	/// Writes each image to a file of its own which the SVG links to, instead of embedding the image in the SVG.
+	pub link: bool,
+}
+
+impl Default for ImageExportPolicy {
+	fn default() -> Self {
+		Self {
+			max_ppi: None,
+			encoding: ImageEncoding::Png,
+			jpeg_quality: 85,
+			link: false,
+		}
+	}
+}
+
+impl ImageExportPolicy {
+	/// Encodes the image drawn with the given transform, returning the URL the SVG refers to it by.
+	/// Linked images are added to the list of files alongside the SVG, where identical images share a file.
+	pub fn image_href(&self, image: &Image<Color>, transform: DAffine2, linked_images: &mut Vec<(String, Vec<u8>)>) -> String {
+		let image = self.downsample(image, transform);
+		let (data, mime, extension) = match self.encoding {
+			ImageEncoding::Png => (image.to_png(), "image/png", "png"),
+			ImageEncoding::Jpeg => (image.to_jpeg(self.jpeg_quality), "image/jpeg", "jpg"),
+		};
+
+		if !self.link {
+			let preamble = format!("data:{mime};base64,");
+			let mut base64_string = String::with_capacity(preamble.len() + data.len() * 4 / 3 + 4);
+			base64_string.push_str(&preamble);
+			base64::engine::general_purpose::STANDARD.encode_string(data, &mut base64_string);
+			return base64_string;
+		}
+
+		if let Some((file_name, _)) = linked_images.iter().find(|(_, existing)| *existing == data) {
+			return file_name.clone();
+		}
+		let file_name = format!("images/image-{}.{extension}", linked_images.len() + 1);
+		linked_images.push((file_name.clone(), data));
+		file_name
+	}
+
+	/// Downsamples the image if it has more pixels per inch than allowed at the size it's drawn with the transform.
+	fn downsample<'a>(&self, image: &'a Image<Color>, transform: DAffine2) -> Cow<'a, Image<Color>> {
+		let Some(max_ppi) = self.max_ppi.filter(|max_ppi| *max_ppi > 0.) else {
+			return Cow::Borrowed(image);
+		};
+
+		// Images span the unit square before they're transformed
+		let drawn_size = DVec2::new(transform.transform_vector2(DVec2::X).length(), transform.transform_vector2(DVec2::Y).length());
+		let max_size = (drawn_size * max_ppi / CSS_PIXELS_PER_INCH).ceil().max(DVec2::ONE);
+		if image.width as f64 <= max_size.x && image.height as f64 <= max_size.y {
+			return Cow::Borrowed(image);
+		}
+
+		Cow::Owned(image.downsample(max_size.x as u32, max_size.y as u32))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn downsample_to_max_ppi() {
+		let image = Image::new(400, 200, Color::RED);
+		// Drawn one inch wide and half an inch tall, so 400 pixels across is 400 PPI
+		let transform = DAffine2::from_scale(DVec2::new(96., 48.));
+		let policy = ImageExportPolicy {
+			max_ppi: Some(100.),
+			..Default::default()
+		};
+
+		let downsampled = policy.downsample(&image, transform);
+		assert_eq!((downsampled.width, downsampled.height), (100, 50));
+		assert_eq!(downsampled.data[0], Color::RED);
+
+		let unlimited = ImageExportPolicy::default().downsample(&image, transform);
+		assert!(matches!(unlimited, Cow::Borrowed(_)));
+	}
+
+	#[test]
+	fn link_identical_images_once() {
+		let policy = ImageExportPolicy { link: true, ..Default::default() };
+		let mut linked_images = Vec::new();
+
+		let first = policy.image_href(&Image::new(2, 2, Color::RED), DAffine2::IDENTITY, &mut linked_images);
+		let second = policy.image_href(&Image::new(2, 2, Color::RED), DAffine2::IDENTITY, &mut linked_images);
+		let third = policy.image_href(&Image::new(2, 2, Color::BLUE), DAffine2::IDENTITY, &mut linked_images);
+
+		assert_eq!(first, "images/image-1.png");
+		assert_eq!(second, first);
+		assert_eq!(third, "images/image-2.png");
+		assert_eq!(linked_images.len(), 2);
+	}
+}
@@ -0,0 +1,228 @@
+//! The PNG `data:` URLs of the images drawn in the viewport, which are encoded away from the evaluation of the graph since encoding a large image takes far longer than rendering everything else.
+//!
+//! An image is first drawn from a downscaled preview, which is quick to encode, while the image itself waits to be encoded at full resolution once the editor is idle.
+//! Rendering again after that swaps the preview for the full resolution image. Both are kept by the hash of the pixels, so the images of unchanged layers are never encoded twice.
+
+use super::png_data_url;
+use crate::raster::{Image, PixelBuffer};
+use crate::Color;
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// The largest width or height of the downscaled previews, and of the images small enough to be encoded right away.
+pub const IMAGE_PREVIEW_SIZE: u32 = 256;
+
+/// The number of renders that an image can go without being drawn before its encodings are dropped, so toggling a layer on and off doesn't encode it again.
+const RENDERS_KEPT: u64 = 16;
+
+/// The encodings of the images drawn by one runtime, which are shared with the task encoding them at full resolution.
+#[derive(Clone, Default)]
+pub struct ImagePreviewCache(Arc<Mutex<ImagePreviews>>);
+
+#[derive(Default)]
+struct ImagePreviews {
+	/// The encodings of the images at full resolution, by the hash of their pixels, with the render each was last drawn in.
+	encoded: HashMap<u64, (String, u64)>,
+	/// The encodings of the downscaled previews drawn until the full resolution images are encoded, by the same hash.
+	downscaled: HashMap<u64, (String, u64)>,
+	/// The hashes of the pixels shared by the images of the graph, by their address and size, with the render each was last drawn in.
+	/// The pixels are held so they can't be changed or freed while their address is remembered, since changing shared pixels copies them first.
+	hashes: HashMap<(usize, u32, u32), (PixelBuffer<Color>, u64, u64)>,
+	/// The images waiting to be encoded at full resolution, in the order they were first drawn.
+	pending: VecDeque<(u64, Image<Color>)>,
+	/// Whether one of the images has been taken to be encoded and hasn't been finished yet.
+	encoding: bool,
+	/// Whether an image has been encoded since [`ImagePreviewCache::take_encoded`] was last called.
+	encoded_since_taken: bool,
+	/// The number of renders finished so far.
+	render: u64,
+}
+
+impl ImagePreviews {
+	fn finish_render(&mut self) {
+		let oldest = self.render.saturating_sub(RENDERS_KEPT);
+		self.encoded.retain(|_, (_, render)| *render >= oldest);
+		self.downscaled.retain(|_, (_, render)| *render >= oldest);
+		self.hashes.retain(|_, (_, _, render)| *render >= oldest);
+		let downscaled = &self.downscaled;
+		self.pending.retain(|(hash, _)| downscaled.contains_key(hash));
+		self.render += 1;
+	}
+
+	/// Hashes every pixel, unlike the [`Hash`] of the image which only samples some of them, since an edit to any pixel has to show.
+	/// The pixels kept by the graph between renders are only hashed the first time they're drawn.
+	fn pixel_hash(&mut self, image: &Image<Color>) -> u64 {
+		// Pixels which aren't shared are made fresh for this render, like those mapped to a palette, so they're hashed without being remembered
+		if !image.data.is_shared() {
+			return pixel_hash(image);
+		}
+
+		let render = self.render;
+		let (_, hash, last_drawn) = self
+			.hashes
+			.entry((image.data.as_ptr() as usize, image.width, image.height))
+			.or_insert_with(|| (image.data.clone(), pixel_hash(image), render));
+		*last_drawn = render;
+		*hash
+	}
+}
+
+fn pixel_hash(image: &Image<Color>) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	image.width.hash(&mut hasher);
+	image.height.hash(&mut hasher);
+	bytemuck::cast_slice::<Color, u8>(&image.data).hash(&mut hasher);
+	hasher.finish()
+}
+
+/// An image taken from the queue of an [`ImagePreviewCache`] to be encoded at full resolution.
+pub struct PendingImagePreview {
+	hash: u64,
+	image: Image<Color>,
+	previews: ImagePreviewCache,
+}
+
+impl PendingImagePreview {
+	/// Encodes the image, which the cache then gives in place of its preview. The cache isn't locked while encoding, so the document can still be rendered from it in the meantime.
+	pub fn encode(self) {
+		let url = png_data_url(&self.image);
+		self.previews.with_previews(|previews| {
+			let render = previews.downscaled.remove(&self.hash).map_or(previews.render, |(_, last_drawn)| last_drawn);
+			previews.encoded.insert(self.hash, (url, render));
+			previews.encoding = false;
+			previews.encoded_since_taken = true;
+		});
+	}
+}
+
+impl ImagePreviewCache {
+	fn with_previews<T>(&self, f: impl FnOnce(&mut ImagePreviews) -> T) -> T {
+		let mut previews = self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		f(&mut previews)
+	}
+
+	/// The URL an image is drawn from in the viewport. That's the image at full resolution once it's been encoded, or a downscaled preview until then, with the image queued to be encoded.
+	/// Thumbnails are always drawn from the preview, which is already larger than them.
+	pub(super) fn image_data_url(&self, image: &Image<Color>, thumbnail: bool) -> String {
+		if image.width <= IMAGE_PREVIEW_SIZE && image.height <= IMAGE_PREVIEW_SIZE {
+			return png_data_url(image);
+		}
+
+		self.with_previews(|previews| {
+			let hash = previews.pixel_hash(image);
+			let render = previews.render;
+			if !thumbnail {
+				if let Some((url, last_drawn)) = previews.encoded.get_mut(&hash) {
+					*last_drawn = render;
+					return url.clone();
+				}
+				if !previews.pending.iter().any(|(pending, _)| *pending == hash) {
+					previews.pending.push_back((hash, image.clone()));
+				}
+			}
+
+			let (url, last_drawn) = previews.downscaled.entry(hash).or_insert_with(|| {
+				let scale = IMAGE_PREVIEW_SIZE as f64 / image.width.max(image.height) as f64;
+				let (width, height) = ((image.width as f64 * scale).round() as u32, (image.height as f64 * scale).round() as u32);
+				(png_data_url(&image.downsample(width, height)), render)
+			});
+			*last_drawn = render;
+			url.clone()
+		})
+	}
+
+	/// Takes the image which has waited longest to be drawn at full resolution, unless another is still being encoded.
+	/// The editor encodes it away from the evaluation of the graph, then renders the document again to swap the image in for its preview.
+	pub fn take_pending(&self) -> Option<PendingImagePreview> {
+		let (hash, image) = self.with_previews(|previews| {
+			if previews.encoding {
+				return None;
+			}
+			let pending = previews.pending.pop_front();
+			previews.encoding = pending.is_some();
+			pending
+		})?;
+		Some(PendingImagePreview { hash, image, previews: self.clone() })
+	}
+
+	/// Whether an image has been encoded at full resolution since this was last called, so the document should be rendered again to draw it.
+	pub fn take_encoded(&self) -> bool {
+		self.with_previews(|previews| core::mem::take(&mut previews.encoded_since_taken))
+	}
+
+	/// Marks the end of a render of the viewport, dropping the encodings of the images which haven't been drawn for a while, along with any of them still waiting to be encoded.
+	pub fn finish_render(&self) {
+		self.with_previews(ImagePreviews::finish_render);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn image(size: u32, shade: f32) -> Image<Color> {
+		Image {
+			width: size,
+			height: size,
+			data: vec![Color::from_rgbaf32_unchecked(shade, shade, shade, 1.); (size * size) as usize].into(),
+			base64_string: None,
+		}
+	}
+
+	#[test]
+	fn large_images_are_drawn_from_previews_until_encoded() {
+		let previews = ImagePreviewCache::default();
+		let large = image(IMAGE_PREVIEW_SIZE * 2, 0.25);
+		let full_resolution = png_data_url(&large);
+
+		let preview = previews.image_data_url(&large, false);
+		assert_ne!(preview, full_resolution);
+		assert_eq!(previews.image_data_url(&large, false), preview);
+
+		assert!(!previews.take_encoded());
+		previews.take_pending().unwrap().encode();
+		assert!(previews.take_encoded() && !previews.take_encoded());
+		assert_eq!(previews.image_data_url(&large, false), full_resolution);
+		assert!(previews.take_pending().is_none());
+	}
+
+	#[test]
+	fn small_images_and_thumbnails_are_never_queued() {
+		let previews = ImagePreviewCache::default();
+		let small = image(IMAGE_PREVIEW_SIZE / 2, 0.5);
+		assert_eq!(previews.image_data_url(&small, false), png_data_url(&small));
+
+		let large = image(IMAGE_PREVIEW_SIZE * 2, 0.75);
+		previews.image_data_url(&large, true);
+		assert!(previews.take_pending().is_none());
+	}
+
+	#[test]
+	fn one_image_is_encoded_at_a_time() {
+		let previews = ImagePreviewCache::default();
+		previews.image_data_url(&image(IMAGE_PREVIEW_SIZE * 2, 0.25), false);
+		previews.image_data_url(&image(IMAGE_PREVIEW_SIZE * 2, 0.5), false);
+
+		let first = previews.take_pending().unwrap();
+		assert!(previews.take_pending().is_none());
+		first.encode();
+		assert!(previews.take_pending().is_some());
+	}
+
+	#[test]
+	fn shared_pixels_are_hashed_once_until_changed() {
+		let previews = ImagePreviewCache::default();
+		let mut large = image(IMAGE_PREVIEW_SIZE * 2, 0.25);
+		let shared = large.clone();
+		let preview = previews.image_data_url(&large, false);
+		assert_eq!(previews.with_previews(|previews| previews.hashes.len()), 1);
+
+		// Changing the pixels copies them away from those the cache holds, so the edit shows instead of the remembered hash
+		large.data[0] = Color::BLACK;
+		assert_ne!(previews.image_data_url(&large, false), preview);
+		assert_eq!(previews.image_data_url(&shared, false), preview);
+	}
+}
@@ -0,0 +1,73 @@
+//! Snapshots of the vector shapes drawn by the artwork at one frame of an animation, which the editor turns into the layers of a Lottie animation.
+
+use crate::vector::style::Stroke;
+use crate::vector::PointId;
+use crate::Color;
+
+use bezier_rs::Subpath;
+use glam::DAffine2;
+
+/// A vector shape as it's drawn at one frame, with the transforms and opacities of the groups it's in applied to it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LottieShape {
+	/// Places the shape's subpaths into the exported area.
+	pub transform: DAffine2,
+	pub opacity: f64,
+	pub subpaths: Vec<Subpath<PointId>>,
+	/// Gradients are exported as the color of their first stop.
+	pub fill: Option<Color>,
+	pub stroke: Option<Stroke>,
+}
+
+impl core::hash::Hash for LottieShape {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.transform.to_cols_array().iter().for_each(|value| value.to_bits().hash(state));
+		self.opacity.to_bits().hash(state);
+		self.subpaths.hash(state);
+		self.fill.hash(state);
+		self.stroke.hash(state);
+	}
+}
+
+impl LottieShape {
+	/// Whether the transform can be taken apart into the scale, rotation, and position of a Lottie layer, which can't be skewed.
+	pub fn has_decomposable_transform(&self) -> bool {
+		let (x_axis, y_axis) = (self.transform.matrix2.x_axis, self.transform.matrix2.y_axis);
+		x_axis.dot(y_axis).abs() <= 1e-9 * x_axis.length() * y_axis.length()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::renderer::GraphicElementRendered;
+	use crate::vector::VectorData;
+	use crate::GraphicGroup;
+
+	use glam::DVec2;
+
+	#[test]
+	fn collect_shapes_through_groups() {
+		let mut vector_data = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE));
+		vector_data.transform = DAffine2::from_scale(DVec2::splat(10.));
+		vector_data.alpha_blending.opacity = 0.5;
+
+		let mut group = GraphicGroup::from(vector_data);
+		group.transform = DAffine2::from_translation(DVec2::new(5., 0.));
+		group.alpha_blending.opacity = 0.5;
+
+		let mut shapes = Vec::new();
+		group.collect_lottie_shapes(DAffine2::IDENTITY, 1., &mut shapes);
+
+		assert_eq!(shapes.len(), 1);
+		assert_eq!(shapes[0].transform, DAffine2::from_scale_angle_translation(DVec2::splat(10.), 0., DVec2::new(5., 0.)));
+		assert_eq!(shapes[0].opacity, 0.25);
+		assert!(shapes[0].has_decomposable_transform());
+		assert!(!LottieShape {
+			transform: DAffine2::from_cols_array(&[1., 0., 1., 1., 0., 0.]),
+			..shapes[0].clone()
+		}
+		.has_decomposable_transform());
+	}
+}
@@ -0,0 +1,172 @@
+//! Onion skinning, which draws faint renders of the frames around the current time of an animation beneath the artwork so its motion can be judged while editing it.
+
+use super::{format_transform_matrix, png_data_url, SvgRender};
+use crate::raster::{Image, ImageFrame};
+use crate::uuid::generate_uuid;
+use crate::Color;
+
+use base64::Engine;
+use glam::{DAffine2, DVec2};
+
+/// Which frames around the current frame of an animation are shown, and how faintly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnionSkin {
+	/// The number of frames shown before the current frame.
+	pub frames_before: u32,
+	/// The number of frames shown after the current frame.
+	pub frames_after: u32,
+	/// The opacity of the frames right next to the current frame, from 0 to 1.
+	pub opacity: f64,
+	/// The factor that the opacity is multiplied by for each frame further away from the current frame.
+	pub falloff: f64,
+}
+
+impl Default for OnionSkin {
+	fn default() -> Self {
+		Self {
+			frames_before: 2,
+			frames_after: 2,
+			opacity: 0.4,
+			falloff: 0.5,
+		}
+	}
+}
+
+impl OnionSkin {
+	/// The offset in frames from the current frame of each frame shown, with its opacity.
+	/// They're ordered from the farthest to the nearest, so nearer frames are drawn over farther ones.
+	pub fn frame_offsets(&self) -> Vec<(i32, f64)> {
+		let before = (1..=self.frames_before as i32).map(|distance| -distance);
+		let after = 1..=self.frames_after as i32;
+
+		let mut offsets = before
+			.chain(after)
+			.map(|offset| (offset, self.opacity.clamp(0., 1.) * self.falloff.clamp(0., 1.).powi(offset.abs() - 1)))
+			.filter(|(_, opacity)| *opacity > 0.)
+			.collect::<Vec<_>>();
+		offsets.sort_by_key(|(offset, _)| core::cmp::Reverse(offset.abs()));
+		offsets
+	}
+}
+
+/// A render of the artwork at another frame of the animation, rasterized at the resolution of the viewport it was rendered for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnionSkinFrame {
+	/// The image of the frame, placed in document space by its transform.
+	pub raster: ImageFrame<Color>,
+	pub opacity: f64,
+}
+
+impl OnionSkinFrame {
+	/// Makes the raster of a frame from a PNG image of it, which is kept encoded since it's drawn as it is.
+	pub fn png_raster(png: &[u8], width: u32, height: u32, transform: DAffine2) -> ImageFrame<Color> {
+		let preamble = "data:image/png;base64,";
+		let mut base64_string = String::with_capacity(preamble.len() + png.len() * 4 / 3 + 4);
+		base64_string.push_str(preamble);
+		base64::engine::general_purpose::STANDARD.encode_string(png, &mut base64_string);
+
+		let image = Image {
+			width,
+			height,
+			data: Default::default(),
+			base64_string: Some(base64_string),
+		};
+		ImageFrame {
+			image,
+			transform,
+			..Default::default()
+		}
+	}
+}
+
+/// Draws the frames in order, only within the bounds if they're given (such as those of an artboard).
+pub fn render_onion_skin(frames: &[OnionSkinFrame], render: &mut SvgRender, bounds: Option<[DVec2; 2]>) {
+	if frames.is_empty() {
+		return;
+	}
+
+	render.parent_tag(
+		"g",
+		|attributes| {
+			attributes.push("class", "onion-skin");
+
+			if let Some([min, max]) = bounds {
+				let id = format!("onion-skin-{}", generate_uuid());
+				let size = max - min;
+				use std::fmt::Write;
+				write!(
+					&mut attributes.0.svg_defs,
+					r#"<clipPath id="{id}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+					min.x, min.y, size.x, size.y
+				)
+				.unwrap();
+				attributes.push("clip-path", format!("url(#{id})"));
+			}
+		},
+		|render| {
+			for frame in frames {
+				let image = &frame.raster.image;
+				if image.data.is_empty() && image.base64_string.is_none() {
+					continue;
+				}
+
+				render.leaf_tag("image", |attributes| {
+					attributes.push("width", "1");
+					attributes.push("height", "1");
+					attributes.push("preserveAspectRatio", "none");
+					attributes.push("transform", format_transform_matrix(frame.raster.transform));
+					attributes.push("opacity", frame.opacity.to_string());
+					attributes.push("href", image.base64_string.clone().unwrap_or_else(|| png_data_url(image)));
+				});
+			}
+		},
+	);
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::renderer::RenderSvgSegmentList;
+
+	#[test]
+	fn frame_offsets_fade_with_distance() {
+		let onion_skin = OnionSkin {
+			frames_before: 2,
+			frames_after: 1,
+			opacity: 0.8,
+			falloff: 0.5,
+		};
+		assert_eq!(onion_skin.frame_offsets(), vec![(-2, 0.4), (-1, 0.8), (1, 0.8)]);
+
+		let invisible = OnionSkin { opacity: 0., ..onion_skin };
+		assert!(invisible.frame_offsets().is_empty());
+	}
+
+	#[test]
+	fn render_frames_within_bounds() {
+		let frame = OnionSkinFrame {
+			raster: ImageFrame {
+				image: Image::new(1, 1, Color::RED),
+				..Default::default()
+			},
+			opacity: 0.25,
+		};
+		let mut render = SvgRender::new();
+		render_onion_skin(&[frame], &mut render, Some([DVec2::ZERO, DVec2::new(10., 20.)]));
+
+		let svg = render.svg.to_svg_string();
+		assert!(svg.contains(r#"opacity="0.25""#));
+		assert!(svg.contains("clip-path"));
+		assert!(render.svg_defs.contains(r#"width="10" height="20""#));
+	}
+
+	#[test]
+	fn png_rasters_are_drawn_without_decoding() {
+		let raster = OnionSkinFrame::png_raster(&[1, 2, 3], 4, 4, DAffine2::IDENTITY);
+		let mut render = SvgRender::new();
+		render_onion_skin(&[OnionSkinFrame { raster, opacity: 0.5 }], &mut render, None);
+
+		assert!(render.svg.to_svg_string().contains(r#"href="data:image/png;base64,AQID""#));
+	}
+}
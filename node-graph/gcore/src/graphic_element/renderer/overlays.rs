@@ -0,0 +1,188 @@
+//! Overlays, the handles, outlines, snapping indicators, and labels which tools draw above the document in viewport space.
+//!
+//! Tools describe their overlays as a list of shapes rather than drawing them, so every overlay is drawn the same way by whichever surface shows it:
+//! the editor draws them into the canvas above the viewport, and [`OverlayLayer::render_svg`] draws them as SVG, for instance for screenshots of the viewport.
+
+use bezier_rs::{Bezier, BezierHandles};
+use glam::DVec2;
+use std::fmt::Write;
+
+/// A shape drawn above the document, with its coordinates in viewport space.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlayShape {
+	/// A straight line, which is dashed with one pixel long dashes spaced `dash_width` apart if that's given.
+	Line { start: DVec2, end: DVec2, color: String, dash_width: Option<f64> },
+	/// Straight lines through the points, such as the edges of a bounding box or a wavy underline.
+	Polyline { points: Vec<DVec2>, closed: bool, color: String },
+	Circle { center: DVec2, radius: f64, fill: Option<String>, stroke: Option<String> },
+	/// A square centered on a point, such as the anchor of a path or a single highlighted pixel.
+	Square { center: DVec2, size: f64, fill: Option<String>, stroke: Option<String> },
+	/// The pivot which transforms rotate and scale around, drawn as a dot with a crosshair.
+	Pivot { center: DVec2, radius: f64, crosshair_radius: f64, color: String },
+	/// The outline of paths, with each subpath given as its curves and whether it's closed.
+	Outline { subpaths: Vec<(Vec<Bezier>, bool)>, color: String },
+	/// A label with its text in white over a background, with the bottom left corner of the background at the position.
+	Text { text: String, position: DVec2, background: String, padding: f64 },
+}
+
+/// The overlays a tool draws above the document for one frame, in the order they're drawn.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OverlayLayer {
+	shapes: Vec<OverlayShape>,
+}
+
+impl OverlayLayer {
+	pub fn push(&mut self, shape: OverlayShape) {
+		self.shapes.push(shape);
+	}
+
+	pub fn shapes(&self) -> &[OverlayShape] {
+		&self.shapes
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.shapes.is_empty()
+	}
+
+	/// Draws the overlays as an SVG document covering a viewport of the given size.
+	pub fn render_svg(&self, size: DVec2) -> String {
+		let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#, size.x, size.y, size.x, size.y);
+		for shape in &self.shapes {
+			render_shape_svg(shape, &mut svg);
+		}
+		svg.push_str("</svg>");
+		svg
+	}
+}
+
+impl Extend<OverlayShape> for OverlayLayer {
+	fn extend<T: IntoIterator<Item = OverlayShape>>(&mut self, shapes: T) {
+		self.shapes.extend(shapes);
+	}
+}
+
+fn paint(color: &Option<String>) -> &str {
+	color.as_deref().unwrap_or("none")
+}
+
+fn render_shape_svg(shape: &OverlayShape, svg: &mut String) {
+	let _ = match shape {
+		OverlayShape::Line { start, end, color, dash_width } => {
+			let dash = dash_width.map(|width| format!(r#" stroke-dasharray="1 {}""#, width - 1.)).unwrap_or_default();
+			write!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{color}"{dash} />"#, start.x, start.y, end.x, end.y)
+		}
+		OverlayShape::Polyline { points, closed, color } => {
+			let element = if *closed { "polygon" } else { "polyline" };
+			let points = points.iter().map(|point| format!("{},{}", point.x, point.y)).collect::<Vec<_>>().join(" ");
+			write!(svg, r#"<{element} points="{points}" fill="none" stroke="{color}" />"#)
+		}
+		OverlayShape::Circle { center, radius, fill, stroke } => {
+			write!(svg, r#"<circle cx="{}" cy="{}" r="{radius}" fill="{}" stroke="{}" />"#, center.x, center.y, paint(fill), paint(stroke))
+		}
+		OverlayShape::Square { center, size, fill, stroke } => {
+			let corner = *center - DVec2::splat(size / 2.);
+			write!(
+				svg,
+				r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="{}" stroke="{}" />"#,
+				corner.x,
+				corner.y,
+				paint(fill),
+				paint(stroke)
+			)
+		}
+		OverlayShape::Pivot {
+			center,
+			radius,
+			crosshair_radius,
+			color,
+		} => {
+			let DVec2 { x, y } = *center;
+			write!(
+				svg,
+				r#"<circle cx="{x}" cy="{y}" r="{radius}" fill="{color}" /><path d="M{} {y}H{}M{x} {}V{}" stroke="{color}" stroke-linecap="round" />"#,
+				x - crosshair_radius,
+				x + crosshair_radius,
+				y - crosshair_radius,
+				y + crosshair_radius
+			)
+		}
+		OverlayShape::Outline { subpaths, color } => {
+			let mut path = String::new();
+			for (curves, closed) in subpaths {
+				let Some(first) = curves.first() else { continue };
+				let _ = write!(path, "M{} {}", first.start.x, first.start.y);
+				for curve in curves {
+					let _ = match curve.handles {
+						BezierHandles::Linear => write!(path, "L{} {}", curve.end.x, curve.end.y),
+						BezierHandles::Quadratic { handle } => write!(path, "Q{} {} {} {}", handle.x, handle.y, curve.end.x, curve.end.y),
+						BezierHandles::Cubic { handle_start, handle_end } => write!(
+							path,
+							"C{} {} {} {} {} {}",
+							handle_start.x, handle_start.y, handle_end.x, handle_end.y, curve.end.x, curve.end.y
+						),
+					};
+				}
+				if *closed {
+					path.push('Z');
+				}
+			}
+			write!(svg, r#"<path d="{path}" fill="none" stroke="{color}" />"#)
+		}
+		OverlayShape::Text { text, position, background, padding } => {
+			// Text can't be measured here, so the background is drawn as a wide stroke around the glyphs instead of as a box around them
+			let text = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+			write!(
+				svg,
+				r#"<text x="{}" y="{}" fill="white" stroke="{background}" stroke-width="{}" stroke-linejoin="round" paint-order="stroke">{text}</text>"#,
+				position.x + padding,
+				position.y - padding,
+				padding * 2.
+			)
+		}
+	};
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn render_svg_in_order() {
+		let mut layer = OverlayLayer::default();
+		layer.push(OverlayShape::Line {
+			start: DVec2::new(0.5, 0.5),
+			end: DVec2::new(10.5, 0.5),
+			color: "#00a8ff".into(),
+			dash_width: Some(4.),
+		});
+		layer.push(OverlayShape::Square {
+			center: DVec2::new(5., 5.),
+			size: 4.,
+			fill: Some("white".into()),
+			stroke: None,
+		});
+		layer.push(OverlayShape::Outline {
+			subpaths: vec![(vec![Bezier::from_linear_dvec2(DVec2::ZERO, DVec2::X), Bezier::from_linear_dvec2(DVec2::X, DVec2::Y)], true)],
+			color: "#00a8ff".into(),
+		});
+
+		let svg = layer.render_svg(DVec2::new(20., 10.));
+		assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10""#));
+		let line = svg.find(r#"<line x1="0.5" y1="0.5" x2="10.5" y2="0.5" stroke="#00a8ff" stroke-dasharray="1 3" />"#).unwrap();
+		let square = svg.find(r#"<rect x="3" y="3" width="4" height="4" fill="white" stroke="none" />"#).unwrap();
+		let outline = svg.find(r#"<path d="M0 0L1 0L0 1Z" fill="none" stroke="#00a8ff" />"#).unwrap();
+		assert!(line < square && square < outline);
+	}
+
+	#[test]
+	fn escape_text() {
+		let mut layer = OverlayLayer::default();
+		layer.push(OverlayShape::Text {
+			text: "<a & b>".into(),
+			position: DVec2::new(0., 20.),
+			background: "black".into(),
+			padding: 2.,
+		});
+		assert!(layer.render_svg(DVec2::splat(40.)).contains(">&lt;a &amp; b&gt;</text>"));
+	}
+}
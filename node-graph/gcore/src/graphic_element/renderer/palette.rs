@@ -0,0 +1,266 @@
+//! Palette-constrained output, which draws every color of the artwork with the nearest color of a small palette for the look of retro hardware, pixel art, or risograph prints.
+
+use crate::raster::Image;
+use crate::vector::style::{Fill, GradientInterpolation, GradientStops, PathStyle};
+use crate::Color;
+
+/// The palettes that output can be limited to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PalettePreset {
+	/// Black and white.
+	#[default]
+	Monochrome,
+	/// The four greens of the original Game Boy screen.
+	GameBoy,
+	/// The sixteen colors of the PICO-8 fantasy console.
+	Pico8,
+	/// The black, white, cyan, and magenta of CGA graphics.
+	Cga,
+	/// Fluorescent pink and blue inks on white paper, as in two color risograph prints.
+	Risograph,
+}
+
+impl PalettePreset {
+	pub const ALL: [Self; 5] = [Self::Monochrome, Self::GameBoy, Self::Pico8, Self::Cga, Self::Risograph];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Monochrome => "Monochrome",
+			Self::GameBoy => "Game Boy",
+			Self::Pico8 => "PICO-8",
+			Self::Cga => "CGA",
+			Self::Risograph => "Risograph",
+		}
+	}
+
+	/// The colors of the palette as hexadecimal sRGB values.
+	fn hex_colors(self) -> &'static [u32] {
+		match self {
+			Self::Monochrome => &[0x000000, 0xFFFFFF],
+			Self::GameBoy => &[0x0F380F, 0x306230, 0x8BAC0F, 0x9BBC0F],
+			Self::Pico8 => &[
+				0x000000, 0x1D2B53, 0x7E2553, 0x008751, 0xAB5236, 0x5F574F, 0xC2C3C7, 0xFFF1E8, 0xFF004D, 0xFFA300, 0xFFEC27, 0x00E436, 0x29ADFF, 0x83769C, 0xFF77A8, 0xFFCCAA,
+			],
+			Self::Cga => &[0x000000, 0x55FFFF, 0xFF55FF, 0xFFFFFF],
+			Self::Risograph => &[0xFFFFFF, 0xFF48B0, 0x0078BF, 0x3D2B6B],
+		}
+	}
+}
+
+/// How images are dithered so areas of colors between those of the palette are drawn as patterns mixing the palette's colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dithering {
+	/// Each pixel takes the nearest color, giving flat bands of color.
+	#[default]
+	None,
+	/// Nudges the pixels by a repeating 4×4 Bayer matrix before finding their nearest color, giving a regular crosshatched pattern.
+	Ordered,
+	/// Spreads the difference between each pixel and its nearest color onto the pixels that follow, with the Floyd-Steinberg weights, giving a grainy pattern.
+	ErrorDiffusion,
+}
+
+/// Limits the colors of the output to those of a palette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutputPalette {
+	pub preset: PalettePreset,
+	pub dithering: Dithering,
+}
+
+impl OutputPalette {
+	/// Prepares the palette for finding the nearest of its colors.
+	pub fn mapper(&self) -> PaletteMapper {
+		let colors = self
+			.preset
+			.hex_colors()
+			.iter()
+			.map(|hex| {
+				let [_, red, green, blue] = hex.to_be_bytes();
+				let gamma = Color::from_rgbf32_unchecked(red as f32 / 255., green as f32 / 255., blue as f32 / 255.);
+				let linear = gamma.to_linear_srgb();
+				let [l, a, b, _] = linear.to_oklab();
+				PaletteColor { gamma, linear, oklab: [l, a, b] }
+			})
+			.collect();
+		PaletteMapper { colors, dithering: self.dithering }
+	}
+}
+
+/// A color of a palette, kept in each of the forms it's compared or drawn in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PaletteColor {
+	gamma: Color,
+	linear: Color,
+	/// The coordinates in OKLab, in which the nearest color is the one that looks most alike.
+	oklab: [f32; 3],
+}
+
+/// The colors of a palette, prepared for mapping the colors of the output to them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteMapper {
+	colors: Vec<PaletteColor>,
+	dithering: Dithering,
+}
+
+/// The thresholds of the ordered dithering pattern, which spread the values from 0 to 15 evenly over every 4×4 block of pixels.
+const BAYER_MATRIX: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+impl PaletteMapper {
+	/// The palette color nearest to the color with linear RGB channels, ignoring its alpha.
+	fn nearest(&self, color: Color) -> PaletteColor {
+		let [l, a, b, _] = color.with_alpha(1.).to_oklab();
+		let distance = |[pl, pa, pb]: [f32; 3]| (pl - l).powi(2) + (pa - a).powi(2) + (pb - b).powi(2);
+
+		self.colors.iter().copied().min_by(|x, y| distance(x.oklab).total_cmp(&distance(y.oklab))).unwrap_or(PaletteColor {
+			gamma: color.to_gamma_srgb(),
+			linear: color,
+			oklab: [l, a, b],
+		})
+	}
+
+	/// Maps a color of the vector artwork, which is gamma encoded sRGB, to the nearest palette color.
+	/// Palette colors are opaque, so colors are either made opaque or, if mostly transparent, fully transparent.
+	pub fn map_color(&self, color: Color) -> Color {
+		if color.a() < 0.5 {
+			return Color::TRANSPARENT;
+		}
+		self.nearest(color.to_linear_srgb()).gamma
+	}
+
+	/// Maps the colors of a shape's fill and stroke to the palette.
+	/// Gradients become bands of palette colors, with the colors mixed in their color space before they're mapped.
+	pub fn map_style(&self, style: &PathStyle, linear_compositing: bool) -> PathStyle {
+		let mut style = style.clone();
+
+		let fill = match style.fill() {
+			Fill::None => Fill::None,
+			Fill::Solid(color) => Fill::Solid(self.map_color(*color)),
+			Fill::Gradient(gradient) => {
+				let stops = gradient.stops.baked(gradient.interpolation.with_linear_compositing(linear_compositing));
+				let mut gradient = gradient.clone();
+				gradient.stops = self.map_stops(&stops);
+				gradient.interpolation = GradientInterpolation::Srgb;
+				Fill::Gradient(gradient)
+			}
+		};
+		style.set_fill(fill);
+
+		if let Some(mut stroke) = style.stroke() {
+			stroke.color = stroke.color.map(|color| self.map_color(color));
+			style.set_stroke(stroke);
+		}
+		style
+	}
+
+	/// Maps the stops to the palette, with a hard step halfway between neighboring stops mapped to different colors so the renderer doesn't blend them.
+	fn map_stops(&self, stops: &GradientStops) -> GradientStops {
+		let mut mapped: Vec<(f64, Color)> = Vec::with_capacity(stops.0.len() * 2);
+		for &(position, color) in &stops.0 {
+			let color = self.map_color(color);
+			if let Some(&(previous_position, previous_color)) = mapped.last() {
+				if previous_color != color {
+					let middle = (previous_position + position) / 2.;
+					mapped.push((middle, previous_color));
+					mapped.push((middle, color));
+				}
+			}
+			mapped.push((position, color));
+		}
+		GradientStops(mapped)
+	}
+
+	/// Maps every pixel of the image to the palette, dithered as chosen.
+	pub fn map_image(&self, image: &Image<Color>) -> Image<Color> {
+		let (width, height) = (image.width as usize, image.height as usize);
+		// Image pixels are premultiplied linear RGB
+		let mut pixels = image.data.iter().map(|pixel| pixel.to_unassociated_alpha()).collect::<Vec<_>>();
+		let spread = 1. / (self.colors.len().max(2) - 1) as f32;
+
+		for y in 0..height {
+			for x in 0..width {
+				let index = y * width + x;
+				let pixel = pixels[index];
+				if pixel.a() < 0.5 {
+					pixels[index] = Color::TRANSPARENT;
+					continue;
+				}
+
+				let target = match self.dithering {
+					// The threshold is applied to the gamma encoded values, which are spaced more evenly in lightness
+					Dithering::Ordered => {
+						let threshold = (BAYER_MATRIX[y % 4][x % 4] as f32 + 0.5) / 16. - 0.5;
+						pixel.to_gamma_srgb().map_rgb(|channel| (channel + threshold * spread).clamp(0., 1.)).to_linear_srgb()
+					}
+					_ => pixel,
+				};
+				let mapped = self.nearest(target).linear;
+				pixels[index] = mapped;
+
+				if self.dithering == Dithering::ErrorDiffusion {
+					let error = [pixel.r() - mapped.r(), pixel.g() - mapped.g(), pixel.b() - mapped.b()];
+					for (dx, dy, weight) in [(1, 0, 7.), (-1, 1, 3.), (0, 1, 5.), (1, 1, 1.)] {
+						let (nx, ny) = (x as isize + dx, y + dy);
+						if nx < 0 || nx as usize >= width || ny >= height {
+							continue;
+						}
+						let neighbor = &mut pixels[ny * width + nx as usize];
+						let factor = weight / 16.;
+						*neighbor = Color::from_rgbaf32_unchecked(
+							(neighbor.r() + error[0] * factor).clamp(0., 1.),
+							(neighbor.g() + error[1] * factor).clamp(0., 1.),
+							(neighbor.b() + error[2] * factor).clamp(0., 1.),
+							neighbor.a(),
+						);
+					}
+				}
+			}
+		}
+
+		Image {
+			width: image.width,
+			height: image.height,
+			data: pixels.into(),
+			base64_string: None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn map_colors_to_the_nearest() {
+		let mapper = OutputPalette::default().mapper();
+		assert_eq!(mapper.map_color(Color::from_rgbaf32_unchecked(0.2, 0.2, 0.2, 1.)), Color::BLACK);
+		assert_eq!(mapper.map_color(Color::from_rgbaf32_unchecked(0.9, 0.8, 0.9, 0.7)), Color::WHITE);
+		assert_eq!(mapper.map_color(Color::from_rgbaf32_unchecked(0.9, 0.8, 0.9, 0.2)), Color::TRANSPARENT);
+	}
+
+	#[test]
+	fn map_gradient_stops_to_hard_steps() {
+		let mapper = OutputPalette::default().mapper();
+		let stops = mapper.map_stops(&GradientStops(vec![(0., Color::BLACK), (0.5, Color::from_rgbaf32_unchecked(0.1, 0.1, 0.1, 1.)), (1., Color::WHITE)]));
+		assert_eq!(stops.0, vec![(0., Color::BLACK), (0.5, Color::BLACK), (0.75, Color::BLACK), (0.75, Color::WHITE), (1., Color::WHITE)]);
+	}
+
+	#[test]
+	fn dither_gray_into_a_pattern() {
+		let gray = Color::from_rgbaf32_unchecked(0.5, 0.5, 0.5, 1.).to_linear_srgb();
+		let image = Image::new(8, 8, gray);
+
+		for dithering in [Dithering::Ordered, Dithering::ErrorDiffusion] {
+			let mapper = OutputPalette { dithering, ..Default::default() }.mapper();
+			let (black, white) = (mapper.colors[0].linear, mapper.colors[1].linear);
+			let mapped = mapper.map_image(&image);
+			let white_count = mapped.data.iter().filter(|pixel| **pixel == white).count();
+			assert!(mapped.data.iter().all(|pixel| *pixel == white || *pixel == black));
+			assert!((8..=56).contains(&white_count), "{dithering:?} gave {white_count} white pixels");
+		}
+
+		let undithered = OutputPalette::default().mapper().map_image(&image);
+		assert!(undithered.data.windows(2).all(|pair| pair[0] == pair[1]));
+	}
+}
@@ -0,0 +1,381 @@
+//! Soft proofing, which simulates how the artwork looks once printed with the inks and on the paper of a CMYK printing condition, along with the separation of colors into those inks for print exports.
+//!
+//! Printing is modeled by multiplying the reflectance of the paper by how much of each channel of light every ink lets through where it covers the paper, with its coverage spread by dot gain.
+//! Colors are separated by solving for the ink coverages that print them, after the screen's white and black are mapped to those of the paper and the richest black the inks allow.
+
+use crate::raster::Image;
+use crate::vector::style::{Fill, GradientInterpolation, GradientStops, PathStyle};
+use crate::Color;
+
+/// How far the printed color of a separation can be from the color asked for, as a distance in OKLab, before the color counts as out of the printing condition's gamut.
+const GAMUT_TOLERANCE: f32 = 0.03;
+
+/// The color that the gamut warning draws in place of the colors that can't be printed, which is a neutral gray as in other design tools.
+const GAMUT_WARNING_COLOR: Color = Color::from_rgbf32_unchecked(0.5, 0.5, 0.5);
+
+/// How dark, from 0 for white to 1 for black, a color has to be before black ink starts replacing the mix of the other three inks.
+const BLACK_GENERATION_START: f32 = 0.3;
+
+/// The number of rounds of solving for the coverage of the cyan, magenta, and yellow inks, which each depend on the others since every ink absorbs a little of each channel.
+const SEPARATION_ITERATIONS: usize = 8;
+
+/// The color profiles of the printing conditions that the output can be proofed for and separated into CMYK inks for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputProfile {
+	/// The colors of screens, which need no proofing and are exported as RGB.
+	#[default]
+	Srgb,
+	/// Sheetfed offset printing on coated paper, as characterized by FOGRA39.
+	CoatedFogra39,
+	/// Sheetfed offset printing on uncoated paper, as characterized by FOGRA29.
+	UncoatedFogra29,
+	/// Web offset printing on coated paper in North America, as characterized by SWOP.
+	WebCoatedSwop,
+	/// Coldset printing on newsprint, as characterized by IFRA26.
+	Newsprint,
+}
+
+impl OutputProfile {
+	pub const ALL: [Self; 5] = [Self::Srgb, Self::CoatedFogra39, Self::UncoatedFogra29, Self::WebCoatedSwop, Self::Newsprint];
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Srgb => "sRGB (Screen)",
+			Self::CoatedFogra39 => "Coated FOGRA39",
+			Self::UncoatedFogra29 => "Uncoated FOGRA29",
+			Self::WebCoatedSwop => "U.S. Web Coated (SWOP)",
+			Self::Newsprint => "Newsprint (IFRA26)",
+		}
+	}
+
+	/// Whether the profile prints with CMYK inks, so its output is separated into them.
+	pub fn is_cmyk(self) -> bool {
+		self != Self::Srgb
+	}
+
+	/// The name of the printing condition in the registry of the International Color Consortium, by which PDF files declare the condition they're intended to be printed in.
+	pub fn output_condition_identifier(self) -> Option<&'static str> {
+		match self {
+			Self::Srgb => None,
+			Self::CoatedFogra39 => Some("FOGRA39"),
+			Self::UncoatedFogra29 => Some("FOGRA29"),
+			Self::WebCoatedSwop => Some("CGATS TR 001"),
+			Self::Newsprint => Some("IFRA26"),
+		}
+	}
+
+	/// The paper and inks of the printing condition, or `None` for sRGB.
+	fn printing_condition(self) -> Option<PrintingCondition> {
+		let condition = match self {
+			Self::Srgb => return None,
+			Self::CoatedFogra39 => PrintingCondition {
+				paper: 0xF5F5F3,
+				inks: [0x00A0E3, 0xE2007A, 0xFFED00, 0x231F20],
+				dot_gain: 0.14,
+				ink_limit: 3.3,
+			},
+			Self::UncoatedFogra29 => PrintingCondition {
+				paper: 0xF1F0EA,
+				inks: [0x0099D6, 0xD32978, 0xFFE61E, 0x3C3A3A],
+				dot_gain: 0.18,
+				ink_limit: 3.,
+			},
+			Self::WebCoatedSwop => PrintingCondition {
+				paper: 0xEDEDE7,
+				inks: [0x009EDB, 0xD60C7D, 0xFFE800, 0x2D2A2B],
+				dot_gain: 0.2,
+				ink_limit: 3.,
+			},
+			Self::Newsprint => PrintingCondition {
+				paper: 0xD8D6CC,
+				inks: [0x2A86BA, 0xB73A7A, 0xDECE36, 0x3E3D3D],
+				dot_gain: 0.26,
+				ink_limit: 2.4,
+			},
+		};
+		Some(condition)
+	}
+
+	/// Prepares the profile for proofing and separating colors, or `None` for sRGB which needs neither.
+	pub fn proofer(self, gamut_warning: bool) -> Option<Proofer> {
+		let condition = self.printing_condition()?;
+		let linear = |hex: u32| {
+			let [_, red, green, blue] = hex.to_be_bytes();
+			let color = Color::from_rgbf32_unchecked(red as f32 / 255., green as f32 / 255., blue as f32 / 255.).to_linear_srgb();
+			[color.r(), color.g(), color.b()]
+		};
+
+		let paper = linear(condition.paper);
+		// Each ink is described by the share of each channel of the paper's reflected light that it lets through
+		let inks = condition.inks.map(|ink| {
+			let ink = linear(ink);
+			[0, 1, 2].map(|channel| (ink[channel] / paper[channel]).min(1.))
+		});
+
+		let mut proofer = Proofer {
+			paper,
+			inks,
+			dot_gain: condition.dot_gain,
+			ink_limit: condition.ink_limit,
+			black: paper,
+			gamut_warning,
+		};
+		// The richest black is solid black ink over as much of the other inks as the ink limit leaves room for
+		let rich = ((condition.ink_limit - 1.) / 3.).clamp(0., 1.);
+		proofer.black = proofer.reflectance([rich, rich, rich, 1.]);
+		Some(proofer)
+	}
+}
+
+/// Simulates printing the output with an output profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftProof {
+	pub profile: OutputProfile,
+	/// Whether colors that can't be printed with the profile are drawn in a warning color, to show where the artwork is out of gamut.
+	pub gamut_warning: bool,
+}
+
+impl SoftProof {
+	/// Prepares the proof, or `None` for an sRGB profile which needs none.
+	pub fn proofer(&self) -> Option<Proofer> {
+		self.profile.proofer(self.gamut_warning)
+	}
+}
+
+/// The paper and inks of a printing condition, with the colors given as hexadecimal sRGB values as measured from printed samples.
+struct PrintingCondition {
+	paper: u32,
+	/// The cyan, magenta, yellow, and black inks each printed solid on the paper.
+	inks: [u32; 4],
+	/// How much darker a 50% tint of an ink prints than it's meant to, as the dots of ink spread into the paper.
+	dot_gain: f32,
+	/// The highest total coverage of the four inks, where 1 is one ink printed solid, beyond which the paper can't take more ink.
+	ink_limit: f32,
+}
+
+/// Simulates the printing condition of an output profile, and separates colors into the coverage of its CMYK inks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proofer {
+	/// The reflectance of the bare paper in linear RGB.
+	paper: [f32; 3],
+	/// The share of each channel of light that the cyan, magenta, yellow, and black inks let through.
+	inks: [[f32; 3]; 4],
+	dot_gain: f32,
+	ink_limit: f32,
+	/// The reflectance of the richest black that the inks can print, which is the darkest color of the proof.
+	black: [f32; 3],
+	/// Whether colors that can't be printed are drawn in a warning color instead of their proof.
+	gamut_warning: bool,
+}
+
+impl Proofer {
+	/// The coverage of an ink on the paper once its dots spread, for the coverage it's printed with.
+	fn with_dot_gain(&self, coverage: f32) -> f32 {
+		(coverage + 4. * self.dot_gain * coverage * (1. - coverage)).clamp(0., 1.)
+	}
+
+	/// The coverage an ink is printed with so that once its dots spread it covers the given area.
+	fn without_dot_gain(&self, coverage: f32) -> f32 {
+		if self.dot_gain <= 0. || coverage <= 0. {
+			return coverage.max(0.);
+		}
+		// Solving the quadratic of the dot gain for the coverage before it
+		let gain = 4. * self.dot_gain;
+		let discriminant = ((1. + gain).powi(2) - 4. * gain * coverage).max(0.);
+		((1. + gain - discriminant.sqrt()) / (2. * gain)).clamp(0., 1.)
+	}
+
+	/// The light reflected by the paper in linear RGB where it's printed with the coverage of each of the CMYK inks.
+	fn reflectance(&self, cmyk: [f32; 4]) -> [f32; 3] {
+		let coverage = cmyk.map(|coverage| self.with_dot_gain(coverage));
+		[0, 1, 2].map(|channel| {
+			let through = (0..4).map(|ink| 1. - coverage[ink] * (1. - self.inks[ink][channel])).product::<f32>();
+			self.paper[channel] * through
+		})
+	}
+
+	/// The reflectance in linear RGB that a color is printed as if it's in the gamut, with the screen's white and black mapped to those of the paper and the richest black.
+	fn target(&self, color: Color) -> [f32; 3] {
+		let linear = color.to_linear_srgb();
+		let channels = [linear.r(), linear.g(), linear.b()];
+		[0, 1, 2].map(|channel| self.black[channel] + channels[channel].clamp(0., 1.) * (self.paper[channel] - self.black[channel]))
+	}
+
+	/// The coverage of the cyan, magenta, yellow, and black inks, from 0 to 1, that print the reflectance as closely as the inks allow.
+	fn separate_target(&self, target: [f32; 3]) -> [f32; 4] {
+		// Black ink replaces more of the other inks the darker the color is
+		let darkness = 1. - (0..3).map(|channel| target[channel] / self.paper[channel]).fold(0., f32::max).clamp(0., 1.);
+		let black = ((darkness - BLACK_GENERATION_START) / (1. - BLACK_GENERATION_START)).clamp(0., 1.);
+
+		// Cyan, magenta, and yellow mostly absorb red, green, and blue light, so each is solved for the channel it absorbs given the coverage of the others
+		let mut coverage = [0., 0., 0., black];
+		for _ in 0..SEPARATION_ITERATIONS {
+			for ink in 0..3 {
+				let others = (0..4).filter(|&other| other != ink).map(|other| 1. - coverage[other] * (1. - self.inks[other][ink])).product::<f32>();
+				let through = target[ink] / (self.paper[ink] * others).max(f32::EPSILON);
+				coverage[ink] = ((1. - through) / (1. - self.inks[ink][ink]).max(f32::EPSILON)).clamp(0., 1.);
+			}
+		}
+
+		// The coverage is printed with less ink to make up for the dot gain, then cut back to the ink limit
+		let mut cmyk = coverage.map(|coverage| self.without_dot_gain(coverage));
+		let colored = cmyk[0] + cmyk[1] + cmyk[2];
+		let room = (self.ink_limit - cmyk[3]).max(0.);
+		if colored > room {
+			for ink in &mut cmyk[..3] {
+				*ink *= room / colored;
+			}
+		}
+		cmyk
+	}
+
+	/// The coverage of the cyan, magenta, yellow, and black inks, from 0 to 1, that the color is printed with.
+	/// The color is gamma encoded sRGB and its alpha is ignored.
+	pub fn separate(&self, color: Color) -> [f32; 4] {
+		self.separate_target(self.target(color))
+	}
+
+	/// The color, as gamma encoded sRGB, that the inks print on the paper.
+	pub fn print(&self, cmyk: [f32; 4]) -> Color {
+		let [red, green, blue] = self.reflectance(cmyk);
+		Color::from_rgbf32_unchecked(red, green, blue).to_gamma_srgb()
+	}
+
+	/// Whether the color, as gamma encoded sRGB, can be printed with the inks on the paper.
+	pub fn in_gamut(&self, color: Color) -> bool {
+		let target = self.target(color);
+		let printed = self.reflectance(self.separate_target(target));
+		let [target_l, target_a, target_b, _] = Color::from_rgbf32_unchecked(target[0], target[1], target[2]).to_oklab();
+		let [printed_l, printed_a, printed_b, _] = Color::from_rgbf32_unchecked(printed[0], printed[1], printed[2]).to_oklab();
+		let distance = ((target_l - printed_l).powi(2) + (target_a - printed_a).powi(2) + (target_b - printed_b).powi(2)).sqrt();
+		distance <= GAMUT_TOLERANCE
+	}
+
+	/// How the color of the vector artwork, which is gamma encoded sRGB, looks once printed, or the warning color if it's out of gamut and the gamut warning is on.
+	pub fn proof_color(&self, color: Color) -> Color {
+		if self.gamut_warning && !self.in_gamut(color) {
+			return GAMUT_WARNING_COLOR.with_alpha(color.a());
+		}
+		self.print(self.separate(color)).with_alpha(color.a())
+	}
+
+	/// Proofs the colors of a shape's fill and stroke.
+	/// Gradients are mixed in their color space before their colors are proofed, since the printed colors mix differently.
+	pub fn map_style(&self, style: &PathStyle, linear_compositing: bool) -> PathStyle {
+		let mut style = style.clone();
+
+		let fill = match style.fill() {
+			Fill::None => Fill::None,
+			Fill::Solid(color) => Fill::Solid(self.proof_color(*color)),
+			Fill::Gradient(gradient) => {
+				let stops = gradient.stops.baked(gradient.interpolation.with_linear_compositing(linear_compositing));
+				let mut gradient = gradient.clone();
+				gradient.stops = GradientStops(stops.0.iter().map(|&(position, color)| (position, self.proof_color(color))).collect());
+				gradient.interpolation = GradientInterpolation::Srgb;
+				Fill::Gradient(gradient)
+			}
+		};
+		style.set_fill(fill);
+
+		if let Some(mut stroke) = style.stroke() {
+			stroke.color = stroke.color.map(|color| self.proof_color(color));
+			style.set_stroke(stroke);
+		}
+		style
+	}
+
+	/// Proofs every pixel of the image, whose pixels are premultiplied linear RGB.
+	pub fn map_image(&self, image: &Image<Color>) -> Image<Color> {
+		let data = image
+			.data
+			.iter()
+			.map(|pixel| {
+				if pixel.a() == 0. {
+					return *pixel;
+				}
+				let alpha = pixel.a();
+				let color = pixel.to_unassociated_alpha().with_alpha(1.).to_gamma_srgb();
+				self.proof_color(color).to_linear_srgb().to_associated_alpha(alpha)
+			})
+			.collect();
+
+		Image {
+			width: image.width,
+			height: image.height,
+			data,
+			base64_string: None,
+		}
+	}
+
+	/// Separates pixels given as 8-bit gamma encoded RGBA, as read from a canvas, into 8-bit CMYK.
+	/// Transparent pixels are composited over the paper, which is printed without ink.
+	pub fn separate_rgba8(&self, rgba: &[u8]) -> Vec<u8> {
+		rgba.chunks_exact(4)
+			.flat_map(|pixel| {
+				let [red, green, blue, alpha] = [pixel[0], pixel[1], pixel[2], pixel[3]].map(|channel| channel as f32 / 255.);
+				let color = Color::from_rgbf32_unchecked(red, green, blue).map_rgb(|channel| channel * alpha + (1. - alpha));
+				self.separate(color).map(|coverage| (coverage * 255.).round() as u8)
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn proofer() -> Proofer {
+		OutputProfile::CoatedFogra39.proofer(false).unwrap()
+	}
+
+	#[test]
+	fn white_is_printed_as_bare_paper() {
+		let proofer = proofer();
+		assert_eq!(proofer.separate(Color::WHITE), [0.; 4]);
+		assert!(proofer.in_gamut(Color::WHITE));
+		assert!(OutputProfile::Srgb.proofer(false).is_none());
+	}
+
+	#[test]
+	fn neutral_colors_are_in_gamut() {
+		let proofer = proofer();
+		for value in [0., 0.2, 0.5, 0.8] {
+			let gray = Color::from_rgbf32_unchecked(value, value, value);
+			assert!(proofer.in_gamut(gray), "gray of {value} is out of gamut");
+		}
+		// Dark colors are printed mostly with black ink
+		let cmyk = proofer.separate(Color::from_rgbf32_unchecked(0.1, 0.1, 0.1));
+		assert!(cmyk[3] > cmyk[0] && cmyk[3] > cmyk[1] && cmyk[3] > cmyk[2]);
+	}
+
+	#[test]
+	fn saturated_screen_colors_are_out_of_gamut() {
+		let proofer = proofer();
+		assert!(!proofer.in_gamut(Color::from_rgbf32_unchecked(0., 0., 1.)));
+		assert!(!proofer.in_gamut(Color::from_rgbf32_unchecked(0., 1., 0.)));
+
+		let warning = OutputProfile::CoatedFogra39.proofer(true).unwrap();
+		assert_eq!(warning.proof_color(Color::from_rgbaf32_unchecked(0., 0., 1., 0.5)), GAMUT_WARNING_COLOR.with_alpha(0.5));
+	}
+
+	#[test]
+	fn separations_stay_within_the_ink_limit() {
+		for profile in OutputProfile::ALL.into_iter().filter(|profile| profile.is_cmyk()) {
+			let proofer = profile.proofer(false).unwrap();
+			for color in [Color::BLACK, Color::from_rgbf32_unchecked(0.1, 0., 0.2), Color::from_rgbf32_unchecked(0., 0.05, 0.)] {
+				let total = proofer.separate(color).iter().sum::<f32>();
+				assert!(total <= proofer.ink_limit + 1e-4, "{profile:?} uses {total} ink");
+			}
+		}
+	}
+
+	#[test]
+	fn dot_gain_is_undone_by_separation() {
+		let proofer = proofer();
+		for coverage in [0., 0.25, 0.5, 1.] {
+			assert!((proofer.with_dot_gain(proofer.without_dot_gain(coverage)) - coverage).abs() < 1e-4);
+		}
+	}
+}
@@ -0,0 +1,177 @@
+//! Shrinks the SVG written for an export, much like an SVG minifier would, but while the artwork is rendered so it can make use of what's known about the artwork.
+
+use std::collections::hash_map::{Entry, HashMap};
+
+/// The attributes of definitions (like gradients and clip paths) whose values are coordinates or lists of them, which get rounded to the precision.
+const GEOMETRY_ATTRIBUTES: [&str; 15] = ["d", "transform", "gradientTransform", "x", "y", "x1", "x2", "y1", "y2", "cx", "cy", "r", "width", "height", "offset"];
+
+/// Settings for the optimizer, which is only used for exported SVGs when they're given.
+///
+/// Besides rounding, the optimizer folds the transforms of groups into the coordinates of their contents, leaves out the tags of groups without any effects of their own,
+/// and merges the definitions (like gradients) which are identical apart from their IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SvgOptimization {
+	/// The number of decimal places that coordinates are rounded to.
+	pub precision: u8,
+}
+
+impl Default for SvgOptimization {
+	fn default() -> Self {
+		Self { precision: 3 }
+	}
+}
+
+impl SvgOptimization {
+	/// Rounds every number in a list of them, like path data or a transform, leaving out trailing zeros.
+	pub fn round_numbers(&self, text: &str) -> String {
+		let is_number_char = |c: char| c.is_ascii_digit() || c == '.';
+
+		let mut result = String::with_capacity(text.len());
+		let mut rest = text;
+		while let Some(start) = rest.find(is_number_char) {
+			result.push_str(&rest[..start]);
+			let length = rest[start..].find(|c: char| !is_number_char(c)).unwrap_or(rest.len() - start);
+			let number = &rest[start..start + length];
+			rest = &rest[start + length..];
+
+			match number.parse::<f64>() {
+				Ok(value) => {
+					let rounded = format_number(value, self.precision);
+					// A negative number rounded to zero loses its sign
+					if rounded == "0" && result.ends_with('-') {
+						result.pop();
+					}
+					result.push_str(&rounded);
+				}
+				Err(_) => result.push_str(number),
+			}
+		}
+		result.push_str(rest);
+		result
+	}
+
+	/// Rounds the coordinates of each definition and merges the definitions which are then identical apart from their IDs.
+	/// Returns the optimized definitions, along with the ID of each merged definition paired with the ID of the definition it was merged into.
+	pub fn optimize_defs(&self, defs: &str) -> (String, Vec<(String, String)>) {
+		let Some(elements) = split_elements(defs) else {
+			return (defs.to_string(), Vec::new());
+		};
+
+		let mut optimized = String::with_capacity(defs.len());
+		let mut ids_by_content = HashMap::new();
+		let mut merged_ids = Vec::new();
+		for element in elements {
+			let element = self.round_attributes(element);
+			let Some(id) = element.split_once(r#" id=""#).and_then(|(_, rest)| rest.split_once('"')).map(|(id, _)| id.to_string()) else {
+				optimized.push_str(&element);
+				continue;
+			};
+
+			let content = element.replacen(&format!(r#" id="{id}""#), "", 1);
+			match ids_by_content.entry(content) {
+				Entry::Occupied(entry) => merged_ids.push((id, entry.get().clone())),
+				Entry::Vacant(entry) => {
+					entry.insert(id);
+					optimized.push_str(&element);
+				}
+			}
+		}
+
+		(optimized, merged_ids)
+	}
+
+	/// Rounds the values of the [`GEOMETRY_ATTRIBUTES`] in the markup.
+	fn round_attributes(&self, markup: &str) -> String {
+		// Splitting at the quotes alternates between the markup outside the attribute values, which ends with the attribute's name, and the values themselves
+		let mut result = String::with_capacity(markup.len());
+		let mut name = "";
+		for (index, part) in markup.split('"').enumerate() {
+			if index > 0 {
+				result.push('"');
+			}
+			if index % 2 == 0 {
+				name = part.strip_suffix('=').and_then(|part| part.rsplit(char::is_whitespace).next()).unwrap_or_default();
+				result.push_str(part);
+			} else if GEOMETRY_ATTRIBUTES.contains(&name) {
+				result.push_str(&self.round_numbers(part));
+			} else {
+				result.push_str(part);
+			}
+		}
+		result
+	}
+}
+
+/// Writes the number with at most the given number of decimal places, without trailing zeros.
+fn format_number(value: f64, precision: u8) -> String {
+	let mut formatted = format!("{value:.*}", precision as usize);
+	if formatted.contains('.') {
+		let trimmed = formatted.trim_end_matches('0').trim_end_matches('.').len();
+		formatted.truncate(trimmed);
+	}
+	if formatted == "-0" {
+		formatted.remove(0);
+	}
+	formatted
+}
+
+/// Splits the markup into its top level elements, or gives `None` if the tags aren't balanced.
+fn split_elements(markup: &str) -> Option<Vec<&str>> {
+	let mut elements = Vec::new();
+	let mut depth = 0_usize;
+	let mut start = 0;
+	for (index, _) in markup.match_indices('<') {
+		let end = index + markup[index..].find('>')? + 1;
+		let tag = &markup[index..end];
+		if tag.starts_with("</") {
+			depth = depth.checked_sub(1)?;
+		} else if !tag.ends_with("/>") {
+			depth += 1;
+		}
+
+		if depth == 0 {
+			elements.push(&markup[start..end]);
+			start = end;
+		}
+	}
+	(depth == 0).then_some(elements)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn round_numbers() {
+		let optimization = SvgOptimization { precision: 2 };
+		assert_eq!(optimization.round_numbers("M10.123456,-0.001 L3 4.5C.999 1.005"), "M10.12,0 L3 4.5C1 1");
+		assert_eq!(optimization.round_numbers("matrix(1, 0, 0, 1, 20.00000000001, -7.256)"), "matrix(1, 0, 0, 1, 20, -7.26)");
+	}
+
+	#[test]
+	fn merge_identical_defs() {
+		let optimization = SvgOptimization { precision: 3 };
+		let defs = concat!(
+			r##"<linearGradient id="1" x1="0.10001" x2="1"><stop offset="0" stop-color="#ff0000" /></linearGradient>"##,
+			r##"<linearGradient id="2" x1="0.1" x2="1"><stop offset="0" stop-color="#ff0000" /></linearGradient>"##,
+			r##"<clipPath id="3"><rect x="0" y="0" width="10.5" height="20"/></clipPath>"##,
+		);
+
+		let (optimized, merged_ids) = optimization.optimize_defs(defs);
+		assert_eq!(
+			optimized,
+			concat!(
+				r##"<linearGradient id="1" x1="0.1" x2="1"><stop offset="0" stop-color="#ff0000" /></linearGradient>"##,
+				r##"<clipPath id="3"><rect x="0" y="0" width="10.5" height="20"/></clipPath>"##,
+			)
+		);
+		assert_eq!(merged_ids, vec![("2".to_string(), "1".to_string())]);
+	}
+
+	#[test]
+	fn unbalanced_defs_are_left_alone() {
+		let defs = r#"<clipPath id="1"><rect x="0.123456"/>"#;
+		assert_eq!(SvgOptimization::default().optimize_defs(defs), (defs.to_string(), Vec::new()));
+	}
+}
@@ -9,12 +9,18 @@ extern crate log;
 
 pub mod consts;
 pub mod generic;
+#[cfg(feature = "alloc")]
+pub mod list;
 pub mod logic;
 pub mod ops;
+#[cfg(feature = "std")]
+pub mod oscillator;
 pub mod structural;
 #[cfg(feature = "std")]
 pub mod text;
 #[cfg(feature = "std")]
+pub mod units;
+#[cfg(feature = "std")]
 pub mod uuid;
 pub mod value;
 
@@ -28,6 +34,10 @@ pub mod storage;
 pub mod raster;
 #[cfg(feature = "alloc")]
 pub mod transform;
+#[cfg(feature = "alloc")]
+pub mod warp;
+#[cfg(feature = "alloc")]
+pub mod projection;
 
 #[cfg(feature = "alloc")]
 mod graphic_element;
@@ -39,7 +49,12 @@ pub mod vector;
 #[cfg(feature = "alloc")]
 pub mod application_io;
 
+#[cfg(feature = "alloc")]
+pub mod testing;
+
 pub mod quantization;
+#[cfg(feature = "alloc")]
+pub mod random;
 
 use core::any::TypeId;
 pub use raster::Color;
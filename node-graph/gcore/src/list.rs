@@ -0,0 +1,131 @@
+//! Higher-order nodes which run a function on each item of a list or each element of a group.
+//!
+//! The function is the network connected to the node's function input, which is evaluated again for every item with that item as its input,
+//! the same way Copy to Points evaluates its instance network with a footprint. The network receives the item through a node taking its input
+//! from the caller (with manual composition), like the List Item node in the editor, so the rest of the network can do anything with it.
+
+use crate::{GraphicElement, GraphicGroup, Node};
+
+use alloc::vec::Vec;
+use core::future::Future;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MapListNode<Function> {
+	function: Function,
+}
+
+/// Replaces every item of the list with the function's result for it, keeping them in the same order.
+#[node_macro::node_fn(MapListNode)]
+async fn map_list<T, U, FU: Future<Output = U>>(list: Vec<T>, function: impl Node<T, Output = FU>) -> Vec<U> {
+	let mut result = Vec::with_capacity(list.len());
+	for item in list {
+		result.push(self.function.eval(item).await);
+	}
+	result
+}
+
+/// Replaces every element of the group with the function's result for it, keeping the group's own transform and blending.
+#[node_macro::node_impl(MapListNode)]
+async fn map_group<FE: Future<Output = GraphicElement>>(group: GraphicGroup, function: impl Node<GraphicElement, Output = FE>) -> GraphicGroup {
+	let mut group = group;
+	let elements = core::mem::take(&mut *group);
+	for element in elements {
+		group.push(self.function.eval(element).await);
+	}
+	group
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FilterListNode<Predicate> {
+	predicate: Predicate,
+}
+
+/// Keeps only the items for which the predicate gives true, in the same order.
+#[node_macro::node_fn(FilterListNode)]
+async fn filter_list<T: Clone, FB: Future<Output = bool>>(list: Vec<T>, predicate: impl Node<T, Output = FB>) -> Vec<T> {
+	let mut result = Vec::new();
+	for item in list {
+		if self.predicate.eval(item.clone()).await {
+			result.push(item);
+		}
+	}
+	result
+}
+
+/// Keeps only the elements of the group for which the predicate gives true.
+#[node_macro::node_impl(FilterListNode)]
+async fn filter_group<FB: Future<Output = bool>>(group: GraphicGroup, predicate: impl Node<GraphicElement, Output = FB>) -> GraphicGroup {
+	let mut group = group;
+	let elements = core::mem::take(&mut *group);
+	for element in elements {
+		if self.predicate.eval(element.clone()).await {
+			group.push(element);
+		}
+	}
+	group
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FoldListNode<Initial, Function> {
+	initial: Initial,
+	function: Function,
+}
+
+/// Combines the items into a single value, starting from the initial value and replacing it with the function's result for the pair of it and each item in turn.
+/// For example, adding the two parts of the pair sums the list.
+#[node_macro::node_fn(FoldListNode)]
+async fn fold_list<T, U, FU: Future<Output = U>>(list: Vec<T>, initial: U, function: impl Node<(U, T), Output = FU>) -> U {
+	let mut accumulator = initial;
+	for item in list {
+		accumulator = self.function.eval((accumulator, item)).await;
+	}
+	accumulator
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::generic::FnNode;
+	use crate::ops::{AddPairNode, MultiplyNode};
+	use crate::value::ClonedNode;
+	use crate::vector::VectorData;
+
+	use std::pin::Pin;
+
+	/// Stands in for the executor, which wraps the function network's output in a future.
+	pub struct FutureWrapperNode<N>(N);
+
+	impl<'i, T: 'i, N: Node<'i, T>> Node<'i, T> for FutureWrapperNode<N> {
+		type Output = Pin<Box<dyn Future<Output = N::Output> + 'i>>;
+		fn eval(&'i self, input: T) -> Self::Output {
+			Box::pin(async move { self.0.eval(input) })
+		}
+	}
+
+	#[tokio::test]
+	async fn map_filter_and_fold_lists() {
+		let double = FutureWrapperNode(MultiplyNode::new(ClonedNode(2.)));
+		let doubled = MapListNode::new(double).eval(vec![1., 2., 3.]).await;
+		assert_eq!(doubled, vec![2., 4., 6.]);
+
+		let is_even = FutureWrapperNode(FnNode::new(|x: u32| x % 2 == 0));
+		let evens = FilterListNode::new(is_even).eval((0..7).collect::<Vec<u32>>()).await;
+		assert_eq!(evens, vec![0, 2, 4, 6]);
+
+		let sum = FoldListNode::new(FutureWrapperNode(ClonedNode(10.)), FutureWrapperNode(AddPairNode::new()));
+		assert_eq!(sum.eval(vec![1., 2., 3.]).await, 16.);
+	}
+
+	#[tokio::test]
+	async fn filter_group_elements() {
+		let mut group = GraphicGroup::EMPTY;
+		group.push(VectorData::empty().into());
+		group.push(GraphicGroup::EMPTY.into());
+		group.transform = glam::DAffine2::from_scale(glam::DVec2::splat(2.));
+
+		let is_vector = FutureWrapperNode(FnNode::new(|element: GraphicElement| matches!(element, GraphicElement::VectorData(_))));
+		let vectors = FilterListNode::new(is_vector).eval(group.clone()).await;
+		assert_eq!(vectors.len(), 1);
+		assert_eq!(vectors.transform, group.transform);
+	}
+}
@@ -43,3 +43,107 @@ pub struct LogicNotNode;
 fn logic_not(first: bool) -> bool {
 	!first
 }
+
+#[cfg(feature = "alloc")]
+pub struct ConditionalNode<Condition, IfTrue, IfFalse> {
+	condition: Condition,
+	if_true: IfTrue,
+	if_false: IfFalse,
+}
+
+/// Gives the output of the branch the condition picks. Only that branch is evaluated, so the other one costs nothing however much work it would be.
+#[cfg(feature = "alloc")]
+#[node_macro::node_fn(ConditionalNode)]
+async fn conditional<I, T, FT: core::future::Future<Output = T>>(input: I, condition: bool, if_true: impl Node<I, Output = FT>, if_false: impl Node<I, Output = FT>) -> T {
+	if condition {
+		self.if_true.eval(input).await
+	} else {
+		self.if_false.eval(input).await
+	}
+}
+
+#[cfg(feature = "alloc")]
+pub struct SwitchNode<Index, Option0, Option1, Option2, Option3> {
+	index: Index,
+	option_0: Option0,
+	option_1: Option1,
+	option_2: Option2,
+	option_3: Option3,
+}
+
+/// Gives the output of the option at the index, counting from 0, with indices past the last option picking the last one.
+/// Only the picked option is evaluated, so the others cost nothing.
+#[cfg(feature = "alloc")]
+#[node_macro::node_fn(SwitchNode)]
+async fn switch<I, T, FT: core::future::Future<Output = T>>(
+	input: I,
+	index: u32,
+	option_0: impl Node<I, Output = FT>,
+	option_1: impl Node<I, Output = FT>,
+	option_2: impl Node<I, Output = FT>,
+	option_3: impl Node<I, Output = FT>,
+) -> T {
+	match index {
+		0 => self.option_0.eval(input).await,
+		1 => self.option_1.eval(input).await,
+		2 => self.option_2.eval(input).await,
+		_ => self.option_3.eval(input).await,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::testing::{evaluate, TestValueNode};
+	use crate::transform::Footprint;
+
+	use core::future::{ready, Ready};
+
+	/// A plain parameter of an async node, which gives a future of its value.
+	struct ReadyNode<T>(T);
+
+	impl<'i, T: Clone + 'i> Node<'i, ()> for ReadyNode<T> {
+		type Output = Ready<T>;
+		fn eval(&'i self, _input: ()) -> Self::Output {
+			ready(self.0.clone())
+		}
+	}
+
+	/// A branch which must never be taken.
+	struct UnreachableNode;
+
+	impl<'i> Node<'i, Footprint> for UnreachableNode {
+		type Output = Ready<u32>;
+		fn eval(&'i self, _input: Footprint) -> Self::Output {
+			panic!("A branch which wasn't picked was evaluated")
+		}
+	}
+
+	#[test]
+	fn conditional_only_evaluates_the_picked_branch() {
+		let taken = ConditionalNode::new(ReadyNode(true), TestValueNode::new(1), UnreachableNode);
+		assert_eq!(evaluate(&taken), 1);
+
+		let not_taken = ConditionalNode::new(ReadyNode(false), UnreachableNode, TestValueNode::new(2));
+		assert_eq!(evaluate(&not_taken), 2);
+	}
+
+	#[test]
+	fn switch_picks_the_option_at_the_index() {
+		let pick = |index| {
+			evaluate(&SwitchNode::new(
+				ReadyNode(index),
+				TestValueNode::new(10),
+				TestValueNode::new(11),
+				TestValueNode::new(12),
+				TestValueNode::new(13),
+			))
+		};
+		assert_eq!(pick(0), 10);
+		assert_eq!(pick(2), 12);
+		assert_eq!(pick(7), 13);
+
+		let lazy = SwitchNode::new(ReadyNode(1), UnreachableNode, TestValueNode::new(1), UnreachableNode, UnreachableNode);
+		assert_eq!(evaluate(&lazy), 1);
+	}
+}
@@ -147,6 +147,111 @@ fn ln(input: f64) -> f64 {
 	input.tan()
 }
 
+// Arcsine
+pub struct ArcsineNode;
+#[node_macro::node_fn(ArcsineNode)]
+fn asin(input: f64) -> f64 {
+	input.asin()
+}
+
+// Arccosine
+pub struct ArccosineNode;
+#[node_macro::node_fn(ArccosineNode)]
+fn acos(input: f64) -> f64 {
+	input.acos()
+}
+
+// Arctangent
+pub struct ArctangentNode;
+#[node_macro::node_fn(ArctangentNode)]
+fn atan(input: f64) -> f64 {
+	input.atan()
+}
+
+// Arctangent 2
+/// The angle in radians from the positive x-axis to the point (x, y), where the primary input is y, covering the full circle unlike the arctangent of y / x.
+pub struct Arctangent2Node<X> {
+	x: X,
+}
+#[node_macro::node_fn(Arctangent2Node)]
+fn atan2(y: f64, x: f64) -> f64 {
+	y.atan2(x)
+}
+
+// Square Root
+pub struct SquareRootNode;
+#[node_macro::node_fn(SquareRootNode)]
+fn sqrt(input: f64) -> f64 {
+	input.sqrt()
+}
+
+// Natural Exponent
+/// Euler's number raised to the power of the input, which undoes the natural logarithm.
+pub struct NaturalExponentNode;
+#[node_macro::node_fn(NaturalExponentNode)]
+fn natural_exp(input: f64) -> f64 {
+	input.exp()
+}
+
+// Clamp
+pub struct ClampNode<Min, Max> {
+	min: Min,
+	max: Max,
+}
+#[node_macro::node_fn(ClampNode)]
+fn clamp(input: f64, min: f64, max: f64) -> f64 {
+	// Unlike `f64::clamp`, swapped bounds are put in order rather than panicking
+	input.max(min.min(max)).min(max.max(min))
+}
+
+// Remap
+/// Maps the input from the range between the input min and max onto the range between the output min and max, optionally keeping it inside the output range.
+pub struct RemapNode<InputMin, InputMax, OutputMin, OutputMax, Clamped> {
+	input_min: InputMin,
+	input_max: InputMax,
+	output_min: OutputMin,
+	output_max: OutputMax,
+	clamped: Clamped,
+}
+#[node_macro::node_fn(RemapNode)]
+fn remap(input: f64, input_min: f64, input_max: f64, output_min: f64, output_max: f64, clamped: bool) -> f64 {
+	let range = input_max - input_min;
+	let mut factor = if range == 0. { 0. } else { (input - input_min) / range };
+	if clamped {
+		factor = factor.max(0.).min(1.);
+	}
+	output_min + (output_max - output_min) * factor
+}
+
+// Smoothstep
+/// Eases from 0 at the first edge to 1 at the second edge, starting and stopping gently, and stays flat outside of them.
+pub struct SmoothstepNode<Edge0, Edge1> {
+	edge0: Edge0,
+	edge1: Edge1,
+}
+#[node_macro::node_fn(SmoothstepNode)]
+fn smoothstep(input: f64, edge0: f64, edge1: f64) -> f64 {
+	if edge0 == edge1 {
+		return if input < edge0 { 0. } else { 1. };
+	}
+	let t = ((input - edge0) / (edge1 - edge0)).max(0.).min(1.);
+	t * t * (3. - 2. * t)
+}
+
+// Lerp
+/// Blends linearly from the primary input at a factor of 0 to the second input at a factor of 1, continuing past them for factors outside of that range.
+pub struct LerpNode<Second, Factor> {
+	second: Second,
+	factor: Factor,
+}
+#[node_macro::node_fn(LerpNode)]
+fn lerp<T>(first: T, second: T, factor: f64) -> T
+where
+	T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f64, Output = T>,
+{
+	first + (second - first) * factor
+}
+
 // Min
 pub struct MinimumNode<Second> {
 	second: Second,
@@ -201,6 +306,61 @@ fn construct_vector2(_primary: (), x: f64, y: f64) -> glam::DVec2 {
 	glam::DVec2::new(x, y)
 }
 
+// Dot Product
+pub struct DotProductNode<Second> {
+	second: Second,
+}
+#[node_macro::node_fn(DotProductNode)]
+fn dot(first: glam::DVec2, second: glam::DVec2) -> f64 {
+	first.dot(second)
+}
+
+// Cross Product
+/// The z component of the cross product of the two vectors, which is the signed area of the parallelogram they span, positive when the second is clockwise from the first with the y-axis pointing down.
+pub struct CrossProductNode<Second> {
+	second: Second,
+}
+#[node_macro::node_fn(CrossProductNode)]
+fn cross(first: glam::DVec2, second: glam::DVec2) -> f64 {
+	first.perp_dot(second)
+}
+
+// Vector Length
+pub struct VectorLengthNode;
+#[node_macro::node_fn(VectorLengthNode)]
+fn length(input: glam::DVec2) -> f64 {
+	input.length()
+}
+
+// Normalize
+/// Scales the vector to a length of 1 in the same direction, leaving a zero vector as it is.
+pub struct NormalizeNode;
+#[node_macro::node_fn(NormalizeNode)]
+fn normalize(input: glam::DVec2) -> glam::DVec2 {
+	input.normalize_or_zero()
+}
+
+// Construct Transform
+/// Builds the transform which scales, then rotates by the angle in degrees, then translates, in the same order as the Transform node.
+pub struct ConstructTransformNode<Translation, Rotation, Scale> {
+	translation: Translation,
+	rotation: Rotation,
+	scale: Scale,
+}
+#[node_macro::node_fn(ConstructTransformNode)]
+fn construct_transform(_primary: (), translation: glam::DVec2, rotation: f64, scale: glam::DVec2) -> glam::DAffine2 {
+	glam::DAffine2::from_scale_angle_translation(scale, rotation.to_radians(), translation)
+}
+
+// Transform Point
+pub struct TransformPointNode<Transform> {
+	transform: Transform,
+}
+#[node_macro::node_fn(TransformPointNode)]
+fn transform_point(point: glam::DVec2, transform: glam::DAffine2) -> glam::DVec2 {
+	transform.transform_point2(point)
+}
+
 // Size Of
 #[cfg(feature = "std")]
 struct SizeOfNode;
@@ -389,6 +549,37 @@ mod test {
 	use super::*;
 	use crate::{generic::*, structural::*, value::*};
 
+	#[test]
+	pub fn remap_and_smoothstep_nodes() {
+		let remap = |clamped| RemapNode::new(ClonedNode(0.), ClonedNode(10.), ClonedNode(100.), ClonedNode(200.), ClonedNode(clamped));
+		assert_eq!(remap(false).eval(2.5), 125.);
+		assert_eq!(remap(false).eval(20.), 300.);
+		assert_eq!(remap(true).eval(20.), 200.);
+
+		let smoothstep = SmoothstepNode::new(ClonedNode(1.), ClonedNode(3.));
+		assert_eq!(smoothstep.eval(0.), 0.);
+		assert_eq!(smoothstep.eval(2.), 0.5);
+		assert_eq!(smoothstep.eval(4.), 1.);
+
+		assert_eq!(ClampNode::new(ClonedNode(5.), ClonedNode(1.)).eval(7.), 5.);
+		assert_eq!(
+			LerpNode::new(ClonedNode(glam::DVec2::new(10., 20.)), ClonedNode(0.25)).eval(glam::DVec2::ZERO),
+			glam::DVec2::new(2.5, 5.)
+		);
+	}
+	#[test]
+	pub fn vector_and_transform_nodes() {
+		let (x, y) = (glam::DVec2::X, glam::DVec2::Y);
+		assert_eq!(DotProductNode::new(ClonedNode(y)).eval(x), 0.);
+		assert_eq!(CrossProductNode::new(ClonedNode(y)).eval(x), 1.);
+		assert_eq!(VectorLengthNode::new().eval(glam::DVec2::new(3., 4.)), 5.);
+		assert_eq!(NormalizeNode::new().eval(glam::DVec2::ZERO), glam::DVec2::ZERO);
+
+		let transform = ConstructTransformNode::new(ClonedNode(glam::DVec2::new(10., 0.)), ClonedNode(90.), ClonedNode(glam::DVec2::splat(2.))).eval(());
+		let point = TransformPointNode::new(ClonedNode(transform)).eval(x);
+		assert!(point.abs_diff_eq(glam::DVec2::new(10., 2.), 1e-9));
+		assert!((Arctangent2Node::new(ClonedNode(-1.)).eval(0.) - core::f64::consts::PI).abs() < 1e-9);
+	}
 	#[test]
 	pub fn duplicate_node() {
 		let value = ValueNode(4u32);
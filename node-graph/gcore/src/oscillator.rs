@@ -0,0 +1,131 @@
+//! Oscillators, which follow the time of the animation with a value rising and falling in a repeating wave, so any number input they're connected to is animated without keyframes.
+
+use crate::Node;
+
+use core::f64::consts::TAU;
+use dyn_any::{DynAny, StaticType};
+
+/// The shape of the wave an oscillator follows, which goes between -1 and 1 once per cycle.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
+pub enum Waveform {
+	Sine,
+	Triangle,
+	Square,
+	Sawtooth,
+	/// Drifts smoothly between random values, picking a new one each cycle, for an organic wobble rather than a steady beat.
+	Wobble,
+}
+
+impl core::fmt::Display for Waveform {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Waveform::Sine => write!(f, "Sine"),
+			Waveform::Triangle => write!(f, "Triangle"),
+			Waveform::Square => write!(f, "Square"),
+			Waveform::Sawtooth => write!(f, "Sawtooth"),
+			Waveform::Wobble => write!(f, "Wobble"),
+		}
+	}
+}
+
+impl Waveform {
+	pub fn list() -> &'static [Waveform; 5] {
+		&[Waveform::Sine, Waveform::Triangle, Waveform::Square, Waveform::Sawtooth, Waveform::Wobble]
+	}
+
+	/// The value of the wave, from -1 to 1, once the given number of cycles have passed.
+	/// The periodic waves start at 0 or their lowest point and peak within the first half of the cycle, rising and falling together with the sine wave.
+	pub fn sample(&self, cycles: f64) -> f64 {
+		let fraction = cycles - cycles.floor();
+		match self {
+			Waveform::Sine => (cycles * TAU).sin(),
+			Waveform::Triangle => 4. * ((fraction + 0.75) % 1. - 0.5).abs() - 1.,
+			Waveform::Square => {
+				if fraction < 0.5 {
+					1.
+				} else {
+					-1.
+				}
+			}
+			Waveform::Sawtooth => fraction * 2. - 1.,
+			Waveform::Wobble => {
+				let cycle = cycles.floor() as i64;
+				let (from, to) = (random_at(cycle), random_at(cycle + 1));
+				// Smoothstep eases in and out of each random value, so the wobble has no sudden changes in direction
+				let smooth = fraction * fraction * (3. - 2. * fraction);
+				from + (to - from) * smooth
+			}
+		}
+	}
+}
+
+/// A random value from -1 to 1 for each cycle, which is the same every time the cycle is sampled.
+fn random_at(cycle: i64) -> f64 {
+	// The SplitMix64 mixing function, which scrambles neighboring cycles into unrelated values
+	let mut x = (cycle as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+	x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+	x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+	x ^= x >> 31;
+	(x >> 11) as f64 / (1_u64 << 53) as f64 * 2. - 1.
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OscillatorNode<Time, Waveform, Frequency, Amplitude, Phase> {
+	pub time: Time,
+	pub waveform: Waveform,
+	pub frequency: Frequency,
+	pub amplitude: Amplitude,
+	pub phase: Phase,
+}
+
+/// The value of the wave at the time in seconds, with `frequency` cycles per second, scaled to go between `-amplitude` and `amplitude`, and shifted ahead by `phase` degrees of a cycle.
+#[node_macro::node_fn(OscillatorNode)]
+fn oscillator(_no_primary_input: (), time: f64, waveform: Waveform, frequency: f64, amplitude: f64, phase: f64) -> f64 {
+	amplitude * waveform.sample(time * frequency + phase / 360.)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::value::ClonedNode;
+
+	fn oscillate(waveform: Waveform, time: f64, phase: f64) -> f64 {
+		OscillatorNode {
+			time: ClonedNode(time),
+			waveform: ClonedNode(waveform),
+			frequency: ClonedNode(2.),
+			amplitude: ClonedNode(3.),
+			phase: ClonedNode(phase),
+		}
+		.eval(())
+	}
+
+	#[test]
+	fn periodic_waves_peak_together() {
+		// At 2 cycles per second, a quarter of a cycle has passed after an eighth of a second
+		for waveform in [Waveform::Sine, Waveform::Triangle, Waveform::Square] {
+			assert!((oscillate(waveform, 0.125, 0.) - 3.).abs() < 1e-9, "{waveform} should peak after a quarter cycle");
+			assert!((oscillate(waveform, 0.375, 0.) + 3.).abs() < 1e-9, "{waveform} should bottom out after three quarters of a cycle");
+		}
+		assert!(oscillate(Waveform::Triangle, 0., 0.).abs() < 1e-9);
+	}
+
+	#[test]
+	fn phase_shifts_the_wave() {
+		assert!((oscillate(Waveform::Sine, 0., 90.) - 3.).abs() < 1e-9);
+		assert!(oscillate(Waveform::Sawtooth, 0., 180.).abs() < 1e-9);
+	}
+
+	#[test]
+	fn wobble_passes_through_random_values_each_cycle() {
+		for cycle in -3..3 {
+			let value = Waveform::Wobble.sample(cycle as f64);
+			assert_eq!(value, random_at(cycle));
+			assert!((-1. ..=1.).contains(&value));
+		}
+		// Halfway between two cycles, the wobble is halfway between their values
+		assert!((Waveform::Wobble.sample(0.5) - (random_at(0) + random_at(1)) / 2.).abs() < 1e-9);
+	}
+}
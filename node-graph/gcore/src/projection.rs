@@ -0,0 +1,188 @@
+//! Axonometric projections, which lay flat artwork onto the faces of a box drawn with its edges at fixed angles, as in isometric technical illustrations.
+
+use crate::transform::{Footprint, Transform, TransformMut};
+use crate::{GraphicElement, GraphicGroup, Node};
+
+use core::future::Future;
+use dyn_any::{DynAny, StaticType};
+use glam::{DAffine2, DMat2, DVec2};
+
+/// The largest number of copies made by a depth stack.
+const MAX_DEPTH_STACK_COPIES: u32 = 1000;
+
+/// A face of a box drawn in an axonometric projection, onto which flat artwork is laid.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, specta::Type)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProjectionFace {
+	/// The face seen from above, with the artwork's x-axis along the right edge of the box and its y-axis along the left edge.
+	#[default]
+	Top,
+	/// The upright face on the left, with the artwork's x-axis along the bottom left edge of the box.
+	Left,
+	/// The upright face on the right, with the artwork's x-axis along the bottom right edge of the box.
+	Right,
+}
+
+impl core::fmt::Display for ProjectionFace {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			ProjectionFace::Top => write!(f, "Top"),
+			ProjectionFace::Left => write!(f, "Left"),
+			ProjectionFace::Right => write!(f, "Right"),
+		}
+	}
+}
+
+impl ProjectionFace {
+	pub fn list() -> [ProjectionFace; 3] {
+		[ProjectionFace::Top, ProjectionFace::Left, ProjectionFace::Right]
+	}
+}
+
+/// The directions the three axes of the box are drawn in, keeping their lengths, given the angles (in degrees below the horizontal) of the receding edges on each side.
+/// Both angles at 30° make an isometric projection, and both at about 26.57° (a slope of 1:2) the dimetric projection of pixel art.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Axonometry {
+	/// The edges receding to the right, towards the bottom right.
+	pub right: DVec2,
+	/// The edges receding to the left, towards the bottom left.
+	pub left: DVec2,
+	/// The upright edges, pointing up the page.
+	pub up: DVec2,
+}
+
+impl Axonometry {
+	pub const ISOMETRIC_ANGLE: f64 = 30.;
+
+	pub fn new(left_angle: f64, right_angle: f64) -> Self {
+		let (left_sin, left_cos) = left_angle.to_radians().sin_cos();
+		let (right_sin, right_cos) = right_angle.to_radians().sin_cos();
+		Self {
+			right: DVec2::new(right_cos, right_sin),
+			left: DVec2::new(-left_cos, left_sin),
+			up: DVec2::NEG_Y,
+		}
+	}
+
+	/// Maps flat artwork onto the face, keeping lengths along the edges of the box.
+	pub fn face_transform(&self, face: ProjectionFace) -> DAffine2 {
+		let matrix2 = match face {
+			ProjectionFace::Top => DMat2::from_cols(self.right, self.left),
+			ProjectionFace::Left => DMat2::from_cols(self.right, -self.up),
+			ProjectionFace::Right => DMat2::from_cols(-self.left, -self.up),
+		};
+		DAffine2::from_mat2(matrix2)
+	}
+
+	/// The direction out of the face towards the viewer, which copies stacked in front of the face are moved along.
+	pub fn depth_axis(&self, face: ProjectionFace) -> DVec2 {
+		match face {
+			ProjectionFace::Top => self.up,
+			ProjectionFace::Left => self.left,
+			ProjectionFace::Right => self.right,
+		}
+	}
+}
+
+pub struct AxonometricProjectionNode<Target, Face, LeftAngle, RightAngle> {
+	target: Target,
+	face: Face,
+	left_angle: LeftAngle,
+	right_angle: RightAngle,
+}
+
+/// Lays the artwork onto the top, left, or right face of a box drawn in an axonometric projection, whose receding edges slope down at the left and right angles (in degrees).
+/// The face is projected about the origin of the artwork's parent, and lengths along the edges of the box are kept, so a square of artwork becomes a face of a cube.
+#[node_macro::node_fn(AxonometricProjectionNode)]
+async fn axonometric_projection<Fut: Future>(mut footprint: Footprint, target: impl Node<Footprint, Output = Fut>, face: ProjectionFace, left_angle: f64, right_angle: f64) -> Fut::Output
+where
+	Fut::Output: TransformMut,
+{
+	let projection = Axonometry::new(left_angle, right_angle).face_transform(face);
+
+	// The artwork is rendered for the footprint as it's seen once it's projected, like the Transform node does
+	if !footprint.ignore_modifications {
+		*footprint.transform_mut() = footprint.transform() * projection;
+	}
+
+	let mut data = self.target.eval(footprint).await;
+	let data_transform = data.transform_mut();
+	*data_transform = projection * (*data_transform);
+
+	data
+}
+
+pub struct DepthStackNode<Face, Count, Spacing, LeftAngle, RightAngle> {
+	face: Face,
+	count: Count,
+	spacing: Spacing,
+	left_angle: LeftAngle,
+	right_angle: RightAngle,
+}
+
+/// Stacks copies of the artwork out of a face of an axonometric projection, spaced along the direction the face points (up from the top face, or out of the left or right face).
+/// The copies are drawn from the back to the front, so nearer ones cover farther ones whichever way they're stacked.
+#[node_macro::node_fn(DepthStackNode)]
+fn depth_stack<Data: TransformMut + Into<GraphicElement> + Clone>(data: Data, face: ProjectionFace, count: u32, spacing: f64, left_angle: f64, right_angle: f64) -> GraphicGroup {
+	let offset = Axonometry::new(left_angle, right_angle).depth_axis(face) * spacing;
+	let count = count.min(MAX_DEPTH_STACK_COPIES);
+
+	let mut group = GraphicGroup::EMPTY;
+	let mut copies = (0..count).collect::<Vec<_>>();
+	// Negative spacing stacks the copies behind the face, so the last copy is the farthest back
+	if spacing < 0. {
+		copies.reverse();
+	}
+	for index in copies {
+		let mut copy = data.clone();
+		copy.translate(offset * index as f64);
+		group.push(copy.into());
+	}
+	group
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::value::CopiedNode;
+	use crate::vector::VectorData;
+
+	use bezier_rs::Subpath;
+
+	#[test]
+	fn isometric_faces_follow_the_edges() {
+		let axonometry = Axonometry::new(Axonometry::ISOMETRIC_ANGLE, Axonometry::ISOMETRIC_ANGLE);
+		let [top, left, right] = ProjectionFace::list().map(|face| axonometry.face_transform(face));
+
+		// The unit edges keep their length
+		assert!((top.transform_vector2(DVec2::X).length() - 1.).abs() < 1e-12);
+		assert!((top.transform_point2(DVec2::X) - DVec2::new(3_f64.sqrt() / 2., 0.5)).length() < 1e-12);
+
+		// The upright faces hang from the front edges of the top face, with their y-axes pointing down the page
+		assert!(left.transform_vector2(DVec2::X).abs_diff_eq(top.transform_vector2(DVec2::X), 1e-12));
+		assert!(right.transform_vector2(DVec2::X).abs_diff_eq(-top.transform_vector2(DVec2::Y), 1e-12));
+		assert!(left.transform_vector2(DVec2::Y).abs_diff_eq(DVec2::Y, 1e-12) && right.transform_vector2(DVec2::Y).abs_diff_eq(DVec2::Y, 1e-12));
+
+		// Every face keeps the artwork the right way around
+		assert!([top, left, right].iter().all(|transform| transform.matrix2.determinant() > 0.));
+	}
+
+	#[test]
+	fn depth_stack_draws_back_to_front() {
+		let square = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE));
+		let stack = |spacing: f64| {
+			let node = DepthStackNode {
+				face: CopiedNode(ProjectionFace::Top),
+				count: CopiedNode(3),
+				spacing: CopiedNode(spacing),
+				left_angle: CopiedNode(30.),
+				right_angle: CopiedNode(30.),
+			};
+			node.eval(square.clone()).iter().map(|element| element.transform().translation).collect::<Vec<_>>()
+		};
+
+		// Stacked upwards, the copies higher up (and nearer to the viewer looking down) come last
+		assert_eq!(stack(10.), [DVec2::ZERO, DVec2::new(0., -10.), DVec2::new(0., -20.)]);
+		assert_eq!(stack(-10.), [DVec2::new(0., 20.), DVec2::new(0., 10.), DVec2::ZERO]);
+	}
+}
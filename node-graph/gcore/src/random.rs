@@ -0,0 +1,168 @@
+//! Random values which are fully determined by a seed and an instance index, so a graph draws the same values every time it's evaluated, in every document and on every platform.
+//!
+//! The generator is written out here rather than taken from the `rand` crate, whose generators may change between versions and would quietly reshuffle saved artwork.
+
+use crate::Node;
+
+use alloc::vec::Vec;
+use glam::DVec2;
+
+/// A SplitMix64 generator, which is small and fast and gives well scrambled values even for seeds that differ by a single bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeededRng {
+	state: u64,
+}
+
+impl SeededRng {
+	/// Starts a generator for one instance, such as one copy among many, which draws values unrelated to those of other seeds and instances.
+	pub fn new(seed: u32, index: u32) -> Self {
+		// The seed and index each fill half of the state, so no two pairs of them start at the same place
+		let mut rng = Self {
+			state: ((seed as u64) << 32) | index as u64,
+		};
+		// Scrambling once before drawing keeps the first values of neighboring instances from following each other
+		rng.state = rng.next_u64();
+		rng
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut x = self.state;
+		x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		x ^ (x >> 31)
+	}
+
+	/// A value from 0 up to but not including 1.
+	pub fn next_f64(&mut self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+	}
+
+	/// A value from `min` up to `max`, which may be given in either order.
+	pub fn range(&mut self, min: f64, max: f64) -> f64 {
+		min + (max - min) * self.next_f64()
+	}
+
+	/// A whole number from 0 up to but not including `bound`, without favoring any of them.
+	pub fn below(&mut self, bound: usize) -> usize {
+		// Multiplying into the high bits instead of taking the remainder avoids the bias towards small numbers
+		((self.next_u64() as u128 * bound as u128) >> 64) as usize
+	}
+
+	/// Shuffles the items in place with the Fisher-Yates shuffle, so every order is equally likely.
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		for i in (1..items.len()).rev() {
+			items.swap(i, self.below(i + 1));
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RandomNumberNode<Min, Max, Seed, Index> {
+	min: Min,
+	max: Max,
+	seed: Seed,
+	index: Index,
+}
+
+/// A random number from the min to the max, which changes only when the seed or index does.
+#[node_macro::node_fn(RandomNumberNode)]
+fn random_number(_no_primary_input: (), min: f64, max: f64, seed: u32, index: u32) -> f64 {
+	SeededRng::new(seed, index).range(min, max)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RandomVectorNode<Min, Max, Seed, Index> {
+	min: Min,
+	max: Max,
+	seed: Seed,
+	index: Index,
+}
+
+/// A random vector inside the box from the min to the max corner, which changes only when the seed or index does.
+#[node_macro::node_fn(RandomVectorNode)]
+fn random_vector(_no_primary_input: (), min: DVec2, max: DVec2, seed: u32, index: u32) -> DVec2 {
+	let mut rng = SeededRng::new(seed, index);
+	let x = rng.range(min.x, max.x);
+	let y = rng.range(min.y, max.y);
+	DVec2::new(x, y)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShuffleListNode<Seed, Index> {
+	seed: Seed,
+	index: Index,
+}
+
+/// Puts the items of the list in a random order, which changes only when the seed or index does.
+#[node_macro::node_fn(ShuffleListNode)]
+fn shuffle_list<T>(list: Vec<T>, seed: u32, index: u32) -> Vec<T> {
+	let mut list = list;
+	SeededRng::new(seed, index).shuffle(&mut list);
+	list
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::value::ClonedNode;
+
+	fn random_number(seed: u32, index: u32) -> f64 {
+		RandomNumberNode {
+			min: ClonedNode(-2.),
+			max: ClonedNode(3.),
+			seed: ClonedNode(seed),
+			index: ClonedNode(index),
+		}
+		.eval(())
+	}
+
+	#[test]
+	fn values_depend_only_on_seed_and_index() {
+		assert_eq!(random_number(7, 3), random_number(7, 3));
+		assert_ne!(random_number(7, 3), random_number(7, 4));
+		assert_ne!(random_number(7, 3), random_number(8, 3));
+		// Swapping the seed and index doesn't give the same value either
+		assert_ne!(random_number(3, 7), random_number(7, 3));
+
+		// The exact values are part of saved documents, so they must never change
+		let mut rng = SeededRng::new(0, 0);
+		assert_eq!(rng.next_u64(), 0xA706_DD2F_4D19_7E6F);
+
+		for index in 0..100 {
+			assert!((-2. ..3.).contains(&random_number(1, index)));
+		}
+	}
+
+	#[test]
+	fn random_vectors_stay_in_their_box() {
+		let node = RandomVectorNode {
+			min: ClonedNode(DVec2::new(-1., 10.)),
+			max: ClonedNode(DVec2::new(1., 20.)),
+			seed: ClonedNode(5),
+			index: ClonedNode(0),
+		};
+		let vector = node.eval(());
+		assert!((-1. ..1.).contains(&vector.x) && (10. ..20.).contains(&vector.y));
+		assert_eq!(vector, node.eval(()));
+	}
+
+	#[test]
+	fn shuffling_keeps_every_item() {
+		let shuffle = |seed| {
+			ShuffleListNode {
+				seed: ClonedNode(seed),
+				index: ClonedNode(0),
+			}
+			.eval((0..20).collect::<Vec<u32>>())
+		};
+
+		let shuffled = shuffle(1);
+		let mut sorted = shuffled.clone();
+		sorted.sort();
+		assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+		assert_ne!(shuffled, sorted);
+		assert_eq!(shuffled, shuffle(1));
+		assert_ne!(shuffled, shuffle(2));
+	}
+}
@@ -20,6 +20,8 @@ pub mod color;
 #[cfg(not(target_arch = "spirv"))]
 pub mod curve;
 pub mod discrete_srgb;
+#[cfg(feature = "std")]
+pub mod lanes;
 pub use adjustments::*;
 
 pub trait Linear {
@@ -615,9 +617,11 @@ fn dimensions_node<_P>(input: ImageSlice<'input, _P>) -> (u32, u32) {
 }
 
 #[cfg(feature = "alloc")]
-pub use self::image::{CollectNode, Image, ImageFrame, ImageRefNode, MapImageSliceNode};
+pub use self::image::{CollectNode, Image, ImageFrame, ImageRefNode, MapImageSliceNode, PixelBuffer};
 #[cfg(feature = "alloc")]
 pub(crate) mod image;
+#[cfg(feature = "std")]
+pub mod image_alignment;
 
 #[cfg(test)]
 mod test {
@@ -643,7 +647,7 @@ mod test {
 		let image = ValueNode::<_>::new(Image {
 			width: 5,
 			height: 5,
-			data: vec![Color::from_rgbf32_unchecked(1., 0., 0.); 25],
+			data: vec![Color::from_rgbf32_unchecked(1., 0., 0.); 25].into(),
 			base64_string: None,
 		});
 		let image = image.then(ImageRefNode::new());
@@ -656,6 +660,16 @@ mod test {
 		assert_eq!(vec.count(), 9);
 	}
 
+	#[test]
+	fn blend_in_gamma() {
+		// Half of white over black is mid gray in sRGB, which is much darker than half the light
+		let linear = blend_colors(Color::WHITE, Color::BLACK, BlendMode::Normal, 0.5);
+		let gamma = blend_colors_in_gamma(Color::WHITE, Color::BLACK, BlendMode::Normal, 0.5);
+		assert!((linear.r() - 0.5).abs() < 1e-5);
+		assert!((gamma.to_gamma_srgb().r() - 0.5).abs() < 1e-5);
+		assert_eq!(gamma.a(), 1.);
+	}
+
 	// TODO: I can't be bothered to fix this test rn
 	// #[test]
 	// fn blur_node() {
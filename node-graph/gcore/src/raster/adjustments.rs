@@ -2,6 +2,8 @@
 
 #[cfg(feature = "alloc")]
 use super::curve::{Curve, CurveManipulatorGroup, ValueMapperNode};
+#[cfg(feature = "std")]
+use super::lanes::{lanewise, lanewise2, map_lanes, zip_lanes, AdjustPixels, ColorLanes, Lanes};
 #[cfg(feature = "alloc")]
 use super::ImageFrame;
 use super::{Channel, Color, Node, RGBMut};
@@ -121,33 +123,10 @@ impl BlendMode {
 		]
 	}
 
-	/// The subset of [`BlendMode::list()`] that is supported by SVG.
-	pub fn list_svg_subset() -> [&'static [BlendMode]; 6] {
-		use BlendMode::*;
-		[
-			// Normal group
-			&[Normal],
-			// Darken group
-			&[Darken, Multiply, ColorBurn],
-			// Lighten group
-			&[Lighten, Screen, ColorDodge],
-			// Contrast group
-			&[Overlay, SoftLight, HardLight],
-			// Inversion group
-			&[Difference, Exclusion],
-			// Component group
-			&[Hue, Saturation, Color, Luminosity],
-		]
-	}
-
 	pub fn index_in_list(&self) -> Option<usize> {
 		Self::list().iter().flat_map(|x| x.iter()).position(|&blend_mode| blend_mode == *self)
 	}
 
-	pub fn index_in_list_svg_subset(&self) -> Option<usize> {
-		Self::list_svg_subset().iter().flat_map(|x| x.iter()).position(|&blend_mode| blend_mode == *self)
-	}
-
 	/// Convert the enum to the CSS string for the blend mode.
 	/// [Read more](https://developer.mozilla.org/en-US/docs/Web/CSS/blend-mode#values)
 	pub fn to_svg_style_name(&self) -> Option<&'static str> {
@@ -178,15 +157,29 @@ impl BlendMode {
 		}
 	}
 
-	/// Renders the blend mode CSS style declaration.
+	/// The closest blend mode supported by SVG, which stands in for this one when the document is rendered as SVG.
+	/// Modes without a CSS equivalent fall back to the mode they're built from or most resemble, so the result is close to what's composited by the CPU.
+	pub fn svg_fallback(&self) -> BlendMode {
+		match self {
+			BlendMode::LinearBurn => BlendMode::Multiply,
+			BlendMode::DarkerColor => BlendMode::Darken,
+			BlendMode::LinearDodge => BlendMode::Screen,
+			BlendMode::LighterColor => BlendMode::Lighten,
+			BlendMode::VividLight | BlendMode::LinearLight | BlendMode::PinLight | BlendMode::HardMix => BlendMode::HardLight,
+			BlendMode::Subtract => BlendMode::Difference,
+			BlendMode::Divide => BlendMode::ColorDodge,
+			BlendMode::Erase | BlendMode::Restore | BlendMode::MultiplyAlpha => BlendMode::Normal,
+			blend_mode => *blend_mode,
+		}
+	}
+
+	/// Renders the blend mode CSS style declaration, using [`BlendMode::svg_fallback`] for the modes CSS lacks.
 	pub fn render(&self) -> String {
-		format!(
-			r#" mix-blend-mode: {};"#,
-			self.to_svg_style_name().unwrap_or_else(|| {
-				warn!("Unsupported blend mode {self:?}");
-				"normal"
-			})
-		)
+		match self {
+			// Linear Dodge is exactly `plus-lighter`, which browsers lacking it ignore in favor of the preceding fallback
+			BlendMode::LinearDodge => r#" mix-blend-mode: screen; mix-blend-mode: plus-lighter;"#.to_string(),
+			_ => format!(r#" mix-blend-mode: {};"#, self.svg_fallback().to_svg_style_name().unwrap_or("normal")),
+		}
 	}
 }
 
@@ -255,15 +248,17 @@ pub struct ExtractChannelNode<TargetChannel> {
 	channel: TargetChannel,
 }
 
+/// Makes an opaque grayscale image of one channel, with the color channels taken without the alpha multiplied in so they can be combined again with any alpha.
 #[node_macro::node_fn(ExtractChannelNode)]
 fn extract_channel_node(color: Color, channel: RedGreenBlueAlpha) -> Color {
+	let unassociated = color.to_unassociated_alpha();
 	let extracted_value = match channel {
-		RedGreenBlueAlpha::Red => color.r(),
-		RedGreenBlueAlpha::Green => color.g(),
-		RedGreenBlueAlpha::Blue => color.b(),
+		RedGreenBlueAlpha::Red => unassociated.r(),
+		RedGreenBlueAlpha::Green => unassociated.g(),
+		RedGreenBlueAlpha::Blue => unassociated.b(),
 		RedGreenBlueAlpha::Alpha => color.a(),
 	};
-	color.map_rgba(|_| extracted_value)
+	Color::from_rgbaf32_unchecked(extracted_value, extracted_value, extracted_value, 1.)
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -286,49 +281,94 @@ pub struct LevelsNode<InputStart, InputMid, InputEnd, OutputStart, OutputEnd> {
 	output_end: OutputEnd,
 }
 
+/// The parameters of the [`LevelsNode`] worked out from its inputs, which only needs doing once for a whole image.
 // From https://stackoverflow.com/questions/39510072/algorithm-for-adjustment-of-image-levels
-#[node_macro::node_fn(LevelsNode)]
-fn levels_node(color: Color, input_start: f64, input_mid: f64, input_end: f64, output_start: f64, output_end: f64) -> Color {
-	let color = color.to_gamma_srgb();
-
-	// Input Range (Range: 0-1)
-	let input_shadows = (input_start / 100.) as f32;
-	let input_midtones = (input_mid / 100.) as f32;
-	let input_highlights = (input_end / 100.) as f32;
-
-	// Output Range (Range: 0-1)
-	let output_minimums = (output_start / 100.) as f32;
-	let output_maximums = (output_end / 100.) as f32;
-
-	// Midtones interpolation factor between minimums and maximums (Range: 0-1)
-	let midtones = output_minimums + (output_maximums - output_minimums) * input_midtones;
+#[derive(Debug, Clone, Copy)]
+struct Levels {
+	input_shadows: f32,
+	highlights_minus_shadows: f32,
+	inverse_gamma: f32,
+	output_minimums: f32,
+	output_maximums: f32,
+}
+
+impl Levels {
+	fn new(input_start: f64, input_mid: f64, input_end: f64, output_start: f64, output_end: f64) -> Self {
+		// Input Range (Range: 0-1)
+		let input_shadows = (input_start / 100.) as f32;
+		let input_midtones = (input_mid / 100.) as f32;
+		let input_highlights = (input_end / 100.) as f32;
+
+		// Output Range (Range: 0-1)
+		let output_minimums = (output_start / 100.) as f32;
+		let output_maximums = (output_end / 100.) as f32;
+
+		// Midtones interpolation factor between minimums and maximums (Range: 0-1)
+		let midtones = output_minimums + (output_maximums - output_minimums) * input_midtones;
+
+		// Gamma correction (Range: 0.01-10)
+		let gamma = if midtones < 0.5 {
+			// Range: 0-1
+			let x = 1. - midtones * 2.;
+			// Range: 1-10
+			1. + 9. * x
+		} else {
+			// Range: 0-0.5
+			let x = 1. - midtones;
+			// Range: 0-1
+			let x = x * 2.;
+			// Range: 0.01-1
+			x.max(0.01)
+		};
 
-	// Gamma correction (Range: 0.01-10)
-	let gamma = if midtones < 0.5 {
-		// Range: 0-1
-		let x = 1. - midtones * 2.;
-		// Range: 1-10
-		1. + 9. * x
-	} else {
-		// Range: 0-0.5
-		let x = 1. - midtones;
-		// Range: 0-1
-		let x = x * 2.;
-		// Range: 0.01-1
-		x.max(0.01)
-	};
+		Self {
+			input_shadows,
+			highlights_minus_shadows: (input_highlights - input_shadows).max(f32::EPSILON).min(1.),
+			inverse_gamma: 1. / gamma,
+			output_minimums,
+			output_maximums,
+		}
+	}
 
-	// Input levels (Range: 0-1)
-	let highlights_minus_shadows = (input_highlights - input_shadows).max(f32::EPSILON).min(1.);
-	let color = color.map_rgb(|c| ((c - input_shadows).max(0.) / highlights_minus_shadows).min(1.));
+	/// Adjusts a gamma encoded channel.
+	#[inline(always)]
+	fn channel(&self, c: f32) -> f32 {
+		// Input levels (Range: 0-1)
+		let c = ((c - self.input_shadows).max(0.) / self.highlights_minus_shadows).min(1.);
 
-	// Midtones (Range: 0-1)
-	let color = color.gamma(gamma);
+		// Midtones (Range: 0-1)
+		let c = c.powf(self.inverse_gamma);
 
-	// Output levels (Range: 0-1)
-	let color = color.map_rgb(|c| c * (output_maximums - output_minimums) + output_minimums);
+		// Output levels (Range: 0-1)
+		c * (self.output_maximums - self.output_minimums) + self.output_minimums
+	}
+}
 
-	color.to_linear_srgb()
+#[node_macro::node_fn(LevelsNode)]
+fn levels_node(color: Color, input_start: f64, input_mid: f64, input_end: f64, output_start: f64, output_end: f64) -> Color {
+	let levels = Levels::new(input_start, input_mid, input_end, output_start, output_end);
+	color.to_gamma_srgb().map_rgb(|c| levels.channel(c)).to_linear_srgb()
+}
+
+#[cfg(feature = "std")]
+impl<InputStart, InputMid, InputEnd, OutputStart, OutputEnd> AdjustPixels for LevelsNode<InputStart, InputMid, InputEnd, OutputStart, OutputEnd>
+where
+	InputStart: for<'i> Node<'i, (), Output = f64>,
+	InputMid: for<'i> Node<'i, (), Output = f64>,
+	InputEnd: for<'i> Node<'i, (), Output = f64>,
+	OutputStart: for<'i> Node<'i, (), Output = f64>,
+	OutputEnd: for<'i> Node<'i, (), Output = f64>,
+{
+	fn adjust_pixels(&self, pixels: &mut [Color]) {
+		let levels = Levels::new(
+			self.input_start.eval(()),
+			self.input_mid.eval(()),
+			self.input_end.eval(()),
+			self.output_start.eval(()),
+			self.output_end.eval(()),
+		);
+		map_lanes(pixels, |lanes| lanes.to_gamma_srgb().map_rgb(|c| levels.channel(c)).to_linear_srgb());
+	}
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -408,6 +448,35 @@ fn hue_shift_color_node(color: Color, hue_shift: f64, saturation_shift: f64, lig
 	color.to_linear_srgb()
 }
 
+#[cfg(feature = "std")]
+impl<Hue, Saturation, Lightness> AdjustPixels for HueSaturationNode<Hue, Saturation, Lightness>
+where
+	Hue: for<'i> Node<'i, (), Output = f64>,
+	Saturation: for<'i> Node<'i, (), Output = f64>,
+	Lightness: for<'i> Node<'i, (), Output = f64>,
+{
+	fn adjust_pixels(&self, pixels: &mut [Color]) {
+		let hue_shift = self.hue_shift.eval(()) as f32 / 360.;
+		let saturation_shift = self.saturation_shift.eval(()) as f32 / 100.;
+		let lightness_shift = self.lightness_shift.eval(()) as f32 / 100.;
+
+		map_lanes(pixels, |lanes| {
+			let [hue, saturation, lightness, alpha] = lanes.to_gamma_srgb().to_hsla();
+			ColorLanes::from_hsla(
+				// The same as `% 1.`, which can't be vectorized
+				lanewise(hue, |hue| {
+					let hue = hue + hue_shift;
+					hue - hue.trunc()
+				}),
+				lanewise(saturation, |saturation| (saturation + saturation_shift).clamp(0., 1.)),
+				lanewise(lightness, |lightness| (lightness + lightness_shift).clamp(0., 1.)),
+				alpha,
+			)
+			.to_linear_srgb()
+		});
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct InvertRGBNode;
 
@@ -471,6 +540,18 @@ fn blend_node(input: (Color, Color), blend_mode: BlendMode, opacity: f64) -> Col
 	blend_colors(input.0, input.1, blend_mode, opacity as f32 / 100.)
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct GammaBlendNode<BlendMode, Opacity> {
+	blend_mode: BlendMode,
+	opacity: Opacity,
+}
+
+/// Used in place of the [`BlendNode`] by documents which don't composite in linear RGB.
+#[node_macro::node_fn(GammaBlendNode)]
+fn gamma_blend_node(input: (Color, Color), blend_mode: BlendMode, opacity: f64) -> Color {
+	blend_colors_in_gamma(input.0, input.1, blend_mode, opacity as f32 / 100.)
+}
+
 pub fn apply_blend_mode(foreground: Color, background: Color, blend_mode: BlendMode) -> Color {
 	match blend_mode {
 		// Normal group
@@ -523,6 +604,84 @@ pub fn blend_colors(foreground: Color, background: Color, blend_mode: BlendMode,
 	background.alpha_blend(target_color.to_associated_alpha(opacity))
 }
 
+/// Blends the colors like [`blend_colors`], but with their channels gamma encoded as sRGB while they're blended, which is how browsers and most other software blend colors.
+/// The blended color is returned with linear channels again.
+pub fn blend_colors_in_gamma(foreground: Color, background: Color, blend_mode: BlendMode, opacity: f32) -> Color {
+	let encode = |color: Color| {
+		let gamma = color.to_unassociated_alpha().to_gamma_srgb();
+		Color::from_unassociated_alpha(gamma.r(), gamma.g(), gamma.b(), gamma.a())
+	};
+	let blended = blend_colors(encode(foreground), encode(background), blend_mode, opacity).to_unassociated_alpha().to_linear_srgb();
+	Color::from_unassociated_alpha(blended.r(), blended.g(), blended.b(), blended.a())
+}
+
+/// Blends each pixel of the foreground onto the pixel at the same index of the background, like [`blend_colors`] or, unless `linear`, like [`blend_colors_in_gamma`].
+/// The blend modes which blend each channel on its own are blended in lanes when `linear`, while the others are blended one pixel at a time.
+#[cfg(feature = "std")]
+pub fn blend_pixels(foreground: &[Color], background: &mut [Color], blend_mode: BlendMode, opacity: f32, linear: bool) {
+	if linear {
+		// Each blend function is its own type, so each arm gets its own loop with the function inlined into it
+		match blend_mode {
+			BlendMode::Normal => return blend_channels(foreground, background, opacity, Color::blend_normal),
+			BlendMode::Darken => return blend_channels(foreground, background, opacity, Color::blend_darken),
+			BlendMode::Multiply => return blend_channels(foreground, background, opacity, Color::blend_multiply),
+			BlendMode::ColorBurn => return blend_channels(foreground, background, opacity, Color::blend_color_burn),
+			BlendMode::LinearBurn => return blend_channels(foreground, background, opacity, Color::blend_linear_burn),
+			BlendMode::Lighten => return blend_channels(foreground, background, opacity, Color::blend_lighten),
+			BlendMode::Screen => return blend_channels(foreground, background, opacity, Color::blend_screen),
+			BlendMode::ColorDodge => return blend_channels(foreground, background, opacity, Color::blend_color_dodge),
+			BlendMode::LinearDodge => return blend_channels(foreground, background, opacity, Color::blend_linear_dodge),
+			BlendMode::SoftLight => return blend_channels(foreground, background, opacity, Color::blend_softlight),
+			BlendMode::HardLight => return blend_channels(foreground, background, opacity, Color::blend_hardlight),
+			BlendMode::VividLight => return blend_channels(foreground, background, opacity, Color::blend_vivid_light),
+			BlendMode::LinearLight => return blend_channels(foreground, background, opacity, Color::blend_linear_light),
+			BlendMode::PinLight => return blend_channels(foreground, background, opacity, Color::blend_pin_light),
+			BlendMode::HardMix => return blend_channels(foreground, background, opacity, Color::blend_hard_mix),
+			BlendMode::Difference => return blend_channels(foreground, background, opacity, Color::blend_difference),
+			BlendMode::Exclusion => return blend_channels(foreground, background, opacity, Color::blend_exclusion),
+			BlendMode::Subtract => return blend_channels(foreground, background, opacity, Color::blend_subtract),
+			BlendMode::Divide => return blend_channels(foreground, background, opacity, Color::blend_divide),
+			_ => {}
+		}
+	}
+
+	for (foreground, background) in foreground.iter().zip(background) {
+		*background = if linear {
+			blend_colors(*foreground, *background, blend_mode, opacity)
+		} else {
+			blend_colors_in_gamma(*foreground, *background, blend_mode, opacity)
+		};
+	}
+}
+
+/// Blends the pixels in lanes like [`Color::blend_rgb`] with the blend function, followed by [`Color::alpha_blend`] at the opacity.
+#[cfg(feature = "std")]
+#[inline(always)]
+fn blend_channels(foreground: &[Color], background: &mut [Color], opacity: f32, blend: impl Fn(f32, f32) -> f32) {
+	zip_lanes(foreground, background, |foreground, background| {
+		let unassociated = background.to_unassociated_alpha();
+		let target = |background: Lanes, foreground| lanewise2(background, foreground, |background, foreground| blend(background, foreground).clamp(0., 1.) * opacity);
+		let target = ColorLanes {
+			red: target(unassociated.red, foreground.red),
+			green: target(unassociated.green, foreground.green),
+			blue: target(unassociated.blue, foreground.blue),
+			alpha: lanewise(foreground.alpha, |alpha| alpha * opacity),
+		};
+
+		let inverse_alpha = lanewise(target.alpha, |alpha| 1. - alpha);
+		let over = |background, target| {
+			let background = lanewise2(background, inverse_alpha, |background, inverse_alpha| background * inverse_alpha);
+			lanewise2(background, target, |background, target| background + target)
+		};
+		ColorLanes {
+			red: over(background.red, target.red),
+			green: over(background.green, target.green),
+			blue: over(background.blue, target.blue),
+			alpha: over(background.alpha, target.alpha),
+		}
+	});
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct VibranceNode<Vibrance> {
 	vibrance: Vibrance,
@@ -619,6 +778,71 @@ impl core::fmt::Display for RedGreenBlueAlpha {
 	}
 }
 
+/// Where a channel of a shuffled image is taken from, as a channel of either the image itself or another image, or a constant.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum ChannelSource {
+	#[default]
+	Red,
+	Green,
+	Blue,
+	Alpha,
+	Luminance,
+	OtherRed,
+	OtherGreen,
+	OtherBlue,
+	OtherAlpha,
+	OtherLuminance,
+	Zero,
+	One,
+}
+
+impl core::fmt::Display for ChannelSource {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			ChannelSource::Red => write!(f, "Red"),
+			ChannelSource::Green => write!(f, "Green"),
+			ChannelSource::Blue => write!(f, "Blue"),
+			ChannelSource::Alpha => write!(f, "Alpha"),
+			ChannelSource::Luminance => write!(f, "Luminance"),
+			ChannelSource::OtherRed => write!(f, "Other Red"),
+			ChannelSource::OtherGreen => write!(f, "Other Green"),
+			ChannelSource::OtherBlue => write!(f, "Other Blue"),
+			ChannelSource::OtherAlpha => write!(f, "Other Alpha"),
+			ChannelSource::OtherLuminance => write!(f, "Other Luminance"),
+			ChannelSource::Zero => write!(f, "Zero"),
+			ChannelSource::One => write!(f, "One"),
+		}
+	}
+}
+
+impl ChannelSource {
+	/// The sources grouped into those from the image itself, those from the other image, and the constants.
+	pub fn list() -> [&'static [ChannelSource]; 3] {
+		use ChannelSource::*;
+		[&[Red, Green, Blue, Alpha, Luminance], &[OtherRed, OtherGreen, OtherBlue, OtherAlpha, OtherLuminance], &[Zero, One]]
+	}
+
+	/// The value of the channel given the (unassociated alpha) colors of the image and the other image.
+	pub fn value(&self, color: Color, other: Color) -> f32 {
+		match self {
+			ChannelSource::Red => color.r(),
+			ChannelSource::Green => color.g(),
+			ChannelSource::Blue => color.b(),
+			ChannelSource::Alpha => color.a(),
+			ChannelSource::Luminance => color.luminance_srgb(),
+			ChannelSource::OtherRed => other.r(),
+			ChannelSource::OtherGreen => other.g(),
+			ChannelSource::OtherBlue => other.b(),
+			ChannelSource::OtherAlpha => other.a(),
+			ChannelSource::OtherLuminance => other.luminance_srgb(),
+			ChannelSource::Zero => 0.,
+			ChannelSource::One => 1.,
+		}
+	}
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "std", derive(specta::Type))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
@@ -882,6 +1106,220 @@ impl core::fmt::Display for RelativeAbsolute {
 	}
 }
 
+/// How the differences between an image and its reference are visualized.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum PixelDiffMode {
+	/// Maps the magnitude of each pixel's difference onto a black-red-yellow heat ramp.
+	#[default]
+	Heatmap,
+	/// Dims the image to grayscale and marks every pixel that differs beyond the threshold in magenta.
+	Highlight,
+	/// Alternates between showing the image and its reference twice per second of the time input.
+	Blink,
+}
+
+impl core::fmt::Display for PixelDiffMode {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			PixelDiffMode::Heatmap => write!(f, "Heatmap"),
+			PixelDiffMode::Highlight => write!(f, "Highlight"),
+			PixelDiffMode::Blink => write!(f, "Blink"),
+		}
+	}
+}
+
+impl PixelDiffMode {
+	pub fn list() -> [PixelDiffMode; 3] {
+		[PixelDiffMode::Heatmap, PixelDiffMode::Highlight, PixelDiffMode::Blink]
+	}
+}
+
+/// The standard kernels which images can be convolved with, for finding edges or bringing out detail.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum ConvolutionPreset {
+	/// The strength of the horizontal and vertical gradients together, which outlines edges in any direction.
+	#[default]
+	Sobel,
+	/// The difference between each pixel and its neighbors, which outlines edges and fine detail.
+	Laplacian,
+	/// Lights the edges from the top left and shades them from the bottom right, as if the image were pressed into relief.
+	Emboss,
+	/// Increases the contrast between each pixel and its neighbors.
+	Sharpen,
+}
+
+impl core::fmt::Display for ConvolutionPreset {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			ConvolutionPreset::Sobel => write!(f, "Sobel"),
+			ConvolutionPreset::Laplacian => write!(f, "Laplacian"),
+			ConvolutionPreset::Emboss => write!(f, "Emboss"),
+			ConvolutionPreset::Sharpen => write!(f, "Sharpen"),
+		}
+	}
+}
+
+impl ConvolutionPreset {
+	pub fn list() -> [ConvolutionPreset; 4] {
+		[ConvolutionPreset::Sobel, ConvolutionPreset::Laplacian, ConvolutionPreset::Emboss, ConvolutionPreset::Sharpen]
+	}
+}
+
+/// What a filter reads for the pixels beyond the edges of an image.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum BorderMode {
+	/// Repeats the pixels along the edges outwards.
+	#[default]
+	Extend,
+	/// Reflects the image across its edges.
+	Mirror,
+	/// Continues from the opposite edge, as if the image were tiled.
+	Wrap,
+	/// Treats everything beyond the edges as transparent.
+	Transparent,
+}
+
+impl core::fmt::Display for BorderMode {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			BorderMode::Extend => write!(f, "Extend"),
+			BorderMode::Mirror => write!(f, "Mirror"),
+			BorderMode::Wrap => write!(f, "Wrap"),
+			BorderMode::Transparent => write!(f, "Transparent"),
+		}
+	}
+}
+
+impl BorderMode {
+	pub fn list() -> [BorderMode; 4] {
+		[BorderMode::Extend, BorderMode::Mirror, BorderMode::Wrap, BorderMode::Transparent]
+	}
+
+	/// The coordinate within `0..length` read for a coordinate which may lie beyond the edges, or `None` if it reads as transparent.
+	pub fn resolve(&self, coordinate: isize, length: usize) -> Option<usize> {
+		let length = length as isize;
+		if length == 0 {
+			return None;
+		}
+		let resolved = match self {
+			BorderMode::Extend => coordinate.clamp(0, length - 1),
+			BorderMode::Mirror => {
+				// Reflects about the edges themselves, so the edge pixels are repeated once as they are in a mirror image
+				let period = 2 * length;
+				let wrapped = coordinate.rem_euclid(period);
+				if wrapped < length {
+					wrapped
+				} else {
+					period - 1 - wrapped
+				}
+			}
+			BorderMode::Wrap => coordinate.rem_euclid(length),
+			BorderMode::Transparent => return (0..length).contains(&coordinate).then_some(coordinate as usize),
+		};
+		Some(resolved as usize)
+	}
+}
+
+/// How a morphology filter reshapes the image, by spreading or shrinking its bright or opaque areas.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum MorphologyOperation {
+	/// Grows the areas outwards by the radius.
+	#[default]
+	Dilate,
+	/// Shrinks the areas inwards by the radius.
+	Erode,
+	/// Erodes then dilates, removing specks and thin strands smaller than the radius while keeping the size of the rest.
+	Open,
+	/// Dilates then erodes, filling holes and gaps smaller than the radius while keeping the size of the rest.
+	Close,
+}
+
+impl core::fmt::Display for MorphologyOperation {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			MorphologyOperation::Dilate => write!(f, "Dilate"),
+			MorphologyOperation::Erode => write!(f, "Erode"),
+			MorphologyOperation::Open => write!(f, "Open"),
+			MorphologyOperation::Close => write!(f, "Close"),
+		}
+	}
+}
+
+impl MorphologyOperation {
+	pub fn list() -> [MorphologyOperation; 4] {
+		[MorphologyOperation::Dilate, MorphologyOperation::Erode, MorphologyOperation::Open, MorphologyOperation::Close]
+	}
+}
+
+/// The channel which a morphology filter compares pixels by, taking the whole color of the pixel which is highest or lowest in it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum MorphologyChannel {
+	#[default]
+	Alpha,
+	Luminance,
+}
+
+impl core::fmt::Display for MorphologyChannel {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			MorphologyChannel::Alpha => write!(f, "Alpha"),
+			MorphologyChannel::Luminance => write!(f, "Luminance"),
+		}
+	}
+}
+
+impl MorphologyChannel {
+	pub fn list() -> [MorphologyChannel; 2] {
+		[MorphologyChannel::Alpha, MorphologyChannel::Luminance]
+	}
+}
+
+/// The shape of the neighborhood a morphology filter looks across, which rounds or squares off the corners it grows and shrinks.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum MorphologyShape {
+	#[default]
+	Circle,
+	Square,
+	Diamond,
+}
+
+impl core::fmt::Display for MorphologyShape {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			MorphologyShape::Circle => write!(f, "Circle"),
+			MorphologyShape::Square => write!(f, "Square"),
+			MorphologyShape::Diamond => write!(f, "Diamond"),
+		}
+	}
+}
+
+impl MorphologyShape {
+	pub fn list() -> [MorphologyShape; 3] {
+		[MorphologyShape::Circle, MorphologyShape::Square, MorphologyShape::Diamond]
+	}
+
+	/// Whether the offset lies within the shape of the radius.
+	pub fn contains(&self, dx: i32, dy: i32, radius: i32) -> bool {
+		match self {
+			MorphologyShape::Circle => dx * dx + dy * dy <= radius * radius,
+			MorphologyShape::Square => dx.abs() <= radius && dy.abs() <= radius,
+			MorphologyShape::Diamond => dx.abs() + dy.abs() <= radius,
+		}
+	}
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "std", derive(specta::Type))]
@@ -1310,3 +1748,211 @@ mod index_node {
 		input.into_iter().nth(index as usize)
 	}
 }
+
+/// The hues picked around the color wheel from a base color to make a palette of colors that go well together.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
+pub enum ColorHarmony {
+	/// The base color and the hue opposite it.
+	Complementary,
+	/// The base color and the two hues a third of the way around the wheel on either side of it.
+	Triadic,
+	/// The base color and its neighboring hues on either side, set apart by the spread.
+	Analogous,
+	/// The base color and the hues a quarter, half, and three quarters of the way around the wheel from it.
+	Tetradic,
+}
+
+impl core::fmt::Display for ColorHarmony {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			ColorHarmony::Complementary => write!(f, "Complementary"),
+			ColorHarmony::Triadic => write!(f, "Triadic"),
+			ColorHarmony::Analogous => write!(f, "Analogous"),
+			ColorHarmony::Tetradic => write!(f, "Tetradic"),
+		}
+	}
+}
+
+impl ColorHarmony {
+	pub fn list() -> &'static [ColorHarmony; 4] {
+		&[ColorHarmony::Complementary, ColorHarmony::Triadic, ColorHarmony::Analogous, ColorHarmony::Tetradic]
+	}
+
+	/// The hues of the palette in degrees around the wheel from the base color, starting with the base color itself.
+	/// Only analogous palettes use the spread, which is the angle between neighboring hues.
+	#[cfg(feature = "alloc")]
+	pub fn hue_offsets(&self, spread: f32) -> Vec<f32> {
+		match self {
+			ColorHarmony::Complementary => vec![0., 180.],
+			ColorHarmony::Triadic => vec![0., 120., 240.],
+			ColorHarmony::Analogous => vec![0., -spread, spread],
+			ColorHarmony::Tetradic => vec![0., 90., 180., 270.],
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+pub use color_harmony_node::ColorHarmonyNode;
+
+#[cfg(feature = "alloc")]
+mod color_harmony_node {
+	use super::ColorHarmony;
+	use crate::raster::Color;
+	use crate::Node;
+
+	#[derive(Debug, Clone, Copy)]
+	pub struct ColorHarmonyNode<Harmony, Spread> {
+		pub harmony: Harmony,
+		pub spread: Spread,
+	}
+
+	/// The palette of colors in harmony with the base color, which keep its saturation, lightness, and alpha while their hues are spread around the color wheel.
+	#[node_macro::node_fn(ColorHarmonyNode)]
+	fn color_harmony_node(base: Color, harmony: ColorHarmony, spread: f64) -> Vec<Color> {
+		let [hue, saturation, lightness, alpha] = base.to_gamma_srgb().to_hsla();
+
+		// The spread is limited to half the wheel, so adding a whole turn keeps the hue positive before wrapping it
+		let spread = (spread as f32).clamp(0., 180.);
+		harmony
+			.hue_offsets(spread)
+			.into_iter()
+			.map(|offset| Color::from_hsla((hue + offset / 360. + 1.) % 1., saturation, lightness, alpha).to_linear_srgb())
+			.collect()
+	}
+
+	#[cfg(test)]
+	mod test {
+		use super::*;
+		use crate::value::ClonedNode;
+
+		fn harmony(base: Color, harmony: ColorHarmony, spread: f64) -> Vec<Color> {
+			ColorHarmonyNode {
+				harmony: ClonedNode(harmony),
+				spread: ClonedNode(spread),
+			}
+			.eval(base)
+		}
+
+		fn hue(color: &Color) -> f32 {
+			color.to_gamma_srgb().to_hsla()[0] * 360.
+		}
+
+		#[test]
+		fn complementary_of_red_is_cyan() {
+			let colors = harmony(Color::RED, ColorHarmony::Complementary, 0.);
+			assert_eq!(colors.len(), 2);
+			assert!(hue(&colors[0]).abs() < 0.01);
+			assert!((hue(&colors[1]) - 180.).abs() < 0.01);
+		}
+
+		#[test]
+		fn analogous_hues_wrap_around_the_wheel() {
+			let colors = harmony(Color::RED, ColorHarmony::Analogous, 30.);
+			let hues = colors.iter().map(hue).collect::<Vec<_>>();
+			assert!(hues[0].abs() < 0.01);
+			assert!((hues[1] - 330.).abs() < 0.01);
+			assert!((hues[2] - 30.).abs() < 0.01);
+		}
+
+		#[test]
+		fn tetradic_keeps_alpha() {
+			let base = Color::from_rgbaf32_unchecked(0.2, 0.4, 0.6, 0.5);
+			let colors = harmony(base, ColorHarmony::Tetradic, 0.);
+			assert_eq!(colors.len(), 4);
+			assert!(colors.iter().all(|color| color.a() == 0.5));
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn every_listed_blend_mode_renders_as_svg() {
+		for &blend_mode in BlendMode::list().iter().flat_map(|group| group.iter()) {
+			assert!(blend_mode.svg_fallback().to_svg_style_name().is_some(), "{blend_mode} has no SVG fallback");
+			assert!(!blend_mode.render().contains("normal") || blend_mode == BlendMode::Normal, "{blend_mode} falls back to normal in SVG");
+		}
+		assert!(BlendMode::LinearDodge.render().ends_with("mix-blend-mode: plus-lighter;"));
+	}
+
+	#[test]
+	fn blend_modes_composite_on_the_cpu() {
+		let background = Color::from_rgbf32_unchecked(0.5, 0.25, 0.);
+		let foreground = Color::from_rgbf32_unchecked(0.25, 0.75, 0.);
+		let blend = |blend_mode| {
+			let color = apply_blend_mode(foreground, background, blend_mode);
+			[color.r(), color.g(), color.b()]
+		};
+
+		assert_eq!(blend(BlendMode::LinearBurn), [0., 0., 0.]);
+		assert_eq!(blend(BlendMode::LinearDodge), [0.75, 1., 0.]);
+		assert_eq!(blend(BlendMode::VividLight), [0., 0.5, 0.]);
+		assert_eq!(blend(BlendMode::LinearLight), [0., 0.75, 0.]);
+		assert_eq!(blend(BlendMode::PinLight), [0.5, 0.5, 0.]);
+		assert_eq!(blend(BlendMode::HardMix), [0., 1., 0.]);
+		assert_eq!(blend(BlendMode::Subtract), [0.25, 0., 0.]);
+		assert_eq!(blend(BlendMode::Divide), [1., 1. / 3., 0.]);
+	}
+
+	fn pixels(count: usize, seed: usize) -> Vec<Color> {
+		(0..count)
+			.map(|i| {
+				let value = |offset: usize| ((i * 7 + offset * 3 + seed) % 11) as f32 / 10.;
+				Color::from_unassociated_alpha(value(0), value(1), value(2), value(3).max(0.1))
+			})
+			.collect()
+	}
+
+	#[test]
+	fn adjusting_pixels_in_lanes_matches_adjusting_each_color() {
+		use crate::value::ClonedNode;
+
+		let levels = LevelsNode {
+			input_start: ClonedNode(10.),
+			input_mid: ClonedNode(40.),
+			input_end: ClonedNode(90.),
+			output_start: ClonedNode(5.),
+			output_end: ClonedNode(95.),
+		};
+		let hue_saturation = HueSaturationNode {
+			hue_shift: ClonedNode(-150.),
+			saturation_shift: ClonedNode(20.),
+			lightness_shift: ClonedNode(-10.),
+		};
+
+		let original = pixels(21, 0);
+		let mut adjusted = original.clone();
+		levels.adjust_pixels(&mut adjusted);
+		assert_eq!(adjusted, original.iter().map(|&color| levels.eval(color)).collect::<Vec<_>>());
+
+		let mut adjusted = original.clone();
+		hue_saturation.adjust_pixels(&mut adjusted);
+		assert_eq!(adjusted, original.iter().map(|&color| hue_saturation.eval(color)).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn blending_pixels_in_lanes_matches_blending_each_color() {
+		let foreground = pixels(19, 0);
+		let background = pixels(19, 5);
+
+		for &blend_mode in BlendMode::list().iter().flat_map(|group| group.iter()) {
+			for linear in [true, false] {
+				let mut blended = background.clone();
+				blend_pixels(&foreground, &mut blended, blend_mode, 0.75, linear);
+
+				let expected = foreground.iter().zip(&background).map(|(&foreground, &background)| {
+					if linear {
+						blend_colors(foreground, background, blend_mode, 0.75)
+					} else {
+						blend_colors_in_gamma(foreground, background, blend_mode, 0.75)
+					}
+				});
+				assert_eq!(blended, expected.collect::<Vec<_>>(), "{blend_mode} differs");
+			}
+		}
+	}
+}
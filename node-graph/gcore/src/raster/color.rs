@@ -646,9 +646,9 @@ impl Color {
 
 	pub fn blend_vivid_light(c_b: f32, c_s: f32) -> f32 {
 		if c_s <= 0.5 {
-			Color::blend_color_burn(2. * c_s, c_b)
+			Color::blend_color_burn(c_b, 2. * c_s)
 		} else {
-			Color::blend_color_dodge(2. * c_s - 1., c_b)
+			Color::blend_color_dodge(c_b, 2. * c_s - 1.)
 		}
 	}
 
@@ -669,7 +669,7 @@ impl Color {
 	}
 
 	pub fn blend_hard_mix(c_b: f32, c_s: f32) -> f32 {
-		if Color::blend_linear_light(c_b, c_s) < 0.5 {
+		if c_b + c_s < 1. {
 			0.
 		} else {
 			1.
@@ -690,6 +690,8 @@ impl Color {
 
 	pub fn blend_divide(c_b: f32, c_s: f32) -> f32 {
 		if c_b == 0. {
+			0.
+		} else if c_s == 0. {
 			1.
 		} else {
 			c_b / c_s
@@ -813,6 +815,63 @@ impl Color {
 		[hue, saturation, lightness, self.alpha]
 	}
 
+	/// Converts the color, whose channels are linear RGB, to the perceptual OKLab color space as `[lightness, a, b, alpha]`.
+	/// Lightness is from 0 to 1 and `a` and `b` are roughly from -0.4 to 0.4, with equal steps appearing about equally different.
+	pub fn to_oklab(&self) -> [f32; 4] {
+		// From https://bottosson.github.io/posts/oklab/
+		let l = (0.41222147 * self.red + 0.53633254 * self.green + 0.05144599 * self.blue).cbrt();
+		let m = (0.21190350 * self.red + 0.68069954 * self.green + 0.10739696 * self.blue).cbrt();
+		let s = (0.08830246 * self.red + 0.28171884 * self.green + 0.62997878 * self.blue).cbrt();
+
+		[
+			0.21045426 * l + 0.79361779 * m - 0.00407205 * s,
+			1.97799850 * l - 2.42859221 * m + 0.45059371 * s,
+			0.02590404 * l + 0.78277177 * m - 0.80867577 * s,
+			self.alpha,
+		]
+	}
+
+	/// Creates a color with linear RGB channels from the OKLab lightness, `a`, `b`, and alpha given by [`Color::to_oklab`].
+	/// Colors outside the RGB gamut are clamped into it.
+	pub fn from_oklab(lightness: f32, a: f32, b: f32, alpha: f32) -> Color {
+		let l = (lightness + 0.39633778 * a + 0.21580376 * b).powi(3);
+		let m = (lightness - 0.10556135 * a - 0.06385417 * b).powi(3);
+		let s = (lightness - 0.08948418 * a - 1.29148555 * b).powi(3);
+
+		let red = 4.07674166 * l - 3.30771159 * m + 0.23096993 * s;
+		let green = -1.26843800 * l + 2.60975740 * m - 0.34131940 * s;
+		let blue = -0.00419609 * l - 0.70341861 * m + 1.70761470 * s;
+		Color::from_rgbaf32_unchecked(red.clamp(0., 1.), green.clamp(0., 1.), blue.clamp(0., 1.), alpha)
+	}
+
+	/// Converts the color, whose channels are linear RGB, to the CIELAB color space under the D65 white point as `[lightness, a, b, alpha]`.
+	/// Lightness is from 0 to 100 and `a` and `b` are roughly from -128 to 127.
+	pub fn to_cielab(&self) -> [f32; 4] {
+		let (red, green, blue) = (self.red, self.green, self.blue);
+		let x = (0.4124564 * red + 0.3575761 * green + 0.1804375 * blue) / 0.95047;
+		let y = 0.2126729 * red + 0.7151522 * green + 0.0721750 * blue;
+		let z = (0.0193339 * red + 0.1191920 * green + 0.9503041 * blue) / 1.08883;
+
+		// Below this, the cube root is replaced by a straight line to keep its slope finite near black
+		let f = |t: f32| if t > 216. / 24389. { t.cbrt() } else { (24389. / 27. * t + 16.) / 116. };
+		let (fx, fy, fz) = (f(x), f(y), f(z));
+		[116. * fy - 16., 500. * (fx - fy), 200. * (fy - fz), self.alpha]
+	}
+
+	/// Creates a color with linear RGB channels from the CIELAB lightness, `a`, `b`, and alpha given by [`Color::to_cielab`].
+	/// Colors outside the RGB gamut are clamped into it.
+	pub fn from_cielab(lightness: f32, a: f32, b: f32, alpha: f32) -> Color {
+		let fy = (lightness + 16.) / 116.;
+		let (fx, fz) = (fy + a / 500., fy - b / 200.);
+		let f_inverse = |f: f32| if f.powi(3) > 216. / 24389. { f.powi(3) } else { (116. * f - 16.) / (24389. / 27.) };
+		let (x, y, z) = (f_inverse(fx) * 0.95047, f_inverse(fy), f_inverse(fz) * 1.08883);
+
+		let red = 3.2404542 * x - 1.5371385 * y - 0.4985314 * z;
+		let green = -0.9692660 * x + 1.8760108 * y + 0.0415560 * z;
+		let blue = 0.0556434 * x - 0.2040259 * y + 1.0572252 * z;
+		Color::from_rgbaf32_unchecked(red.clamp(0., 1.), green.clamp(0., 1.), blue.clamp(0., 1.), alpha)
+	}
+
 	// TODO: Readd formatting
 
 	/// Creates a color from a 8-character RGBA hex string (without a # prefix).
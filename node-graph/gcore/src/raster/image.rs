@@ -1,8 +1,10 @@
 use super::discrete_srgb::float_to_srgb_u8;
 use super::{Color, ImageSlice};
 use crate::{AlphaBlending, Node};
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
 use dyn_any::StaticType;
 use glam::{DAffine2, DVec2};
 
@@ -11,10 +13,11 @@ mod base64_serde {
 	//! Basic wrapper for [`serde`] to perform [`base64`] encoding
 
 	use super::super::Pixel;
+	use super::PixelBuffer;
 	use base64::Engine;
 	use serde::{Deserialize, Deserializer, Serializer};
 
-	pub fn as_base64<S, P: Pixel>(key: &[P], serializer: S) -> Result<S::Ok, S::Error>
+	pub fn as_base64<S, P: Pixel>(key: &PixelBuffer<P>, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
@@ -22,7 +25,7 @@ mod base64_serde {
 		serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(u8_data))
 	}
 
-	pub fn from_base64<'a, D, P: Pixel>(deserializer: D) -> Result<Vec<P>, D::Error>
+	pub fn from_base64<'a, D, P: Pixel>(deserializer: D) -> Result<PixelBuffer<P>, D::Error>
 	where
 		D: Deserializer<'a>,
 	{
@@ -39,13 +42,115 @@ mod base64_serde {
 	}
 }
 
+/// The pixels of an image, which are shared by the clones of the image until one of them is changed. Images are cloned each time they're passed between nodes,
+/// so sharing the pixels saves copying them. Changing the pixels through [`DerefMut`] copies them first if they're still shared, leaving the other clones as they were.
+pub struct PixelBuffer<P>(Arc<Vec<P>>);
+
+impl<P> PixelBuffer<P> {
+	/// Whether the pixels are shared with another image, in which case changing them makes a copy.
+	pub fn is_shared(&self) -> bool {
+		Arc::strong_count(&self.0) > 1
+	}
+
+	/// Whether both buffers share the same pixels, rather than only having pixels of the same colors.
+	pub fn ptr_eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+
+	/// The address of the pixels, which is the same for every image sharing them.
+	pub fn as_ptr(&self) -> *const Vec<P> {
+		Arc::as_ptr(&self.0)
+	}
+}
+
+impl<P: Clone> PixelBuffer<P> {
+	/// Takes the pixels out of the buffer, which only copies them if they're still shared.
+	pub fn into_vec(self) -> Vec<P> {
+		Arc::try_unwrap(self.0).unwrap_or_else(|shared| shared.as_ref().clone())
+	}
+}
+
+impl<P> Clone for PixelBuffer<P> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
+impl<P> Default for PixelBuffer<P> {
+	fn default() -> Self {
+		Self(Arc::new(Vec::new()))
+	}
+}
+
+impl<P: PartialEq> PartialEq for PixelBuffer<P> {
+	fn eq(&self, other: &Self) -> bool {
+		self.ptr_eq(other) || self.0 == other.0
+	}
+}
+
+impl<P: Debug> Debug for PixelBuffer<P> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<P> Deref for PixelBuffer<P> {
+	type Target = Vec<P>;
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<P: Clone> DerefMut for PixelBuffer<P> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		Arc::make_mut(&mut self.0)
+	}
+}
+
+impl<P> From<Vec<P>> for PixelBuffer<P> {
+	fn from(pixels: Vec<P>) -> Self {
+		Self(Arc::new(pixels))
+	}
+}
+
+impl<P> FromIterator<P> for PixelBuffer<P> {
+	fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+		Vec::from_iter(iter).into()
+	}
+}
+
+impl<P: Clone> IntoIterator for PixelBuffer<P> {
+	type Item = P;
+	type IntoIter = alloc::vec::IntoIter<P>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.into_vec().into_iter()
+	}
+}
+
+impl<'a, P> IntoIterator for &'a PixelBuffer<P> {
+	type Item = &'a P;
+	type IntoIter = core::slice::Iter<'a, P>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.iter()
+	}
+}
+
+impl<'a, P: Clone> IntoIterator for &'a mut PixelBuffer<P> {
+	type Item = &'a mut P;
+	type IntoIter = core::slice::IterMut<'a, P>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.deref_mut().iter_mut()
+	}
+}
+
 #[derive(Clone, PartialEq, Default, specta::Type)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Image<P: Pixel> {
 	pub width: u32,
 	pub height: u32,
 	#[cfg_attr(feature = "serde", serde(serialize_with = "base64_serde::as_base64", deserialize_with = "base64_serde::from_base64"))]
-	pub data: Vec<P>,
+	#[specta(type = String)]
+	pub data: PixelBuffer<P>,
 	/// Optional: Stores a base64 string representation of the image which can be used to speed up the conversion
 	/// to an svg string. This is used as a cache in order to not have to encode the data on every graph evaluation.
 	#[cfg_attr(feature = "serde", serde(skip))]
@@ -107,11 +212,11 @@ impl<P: Hash + Pixel> Hash for Image<P> {
 }
 
 impl<P: Pixel> Image<P> {
-	pub const fn empty() -> Self {
+	pub fn empty() -> Self {
 		Self {
 			width: 0,
 			height: 0,
-			data: Vec::new(),
+			data: PixelBuffer::default(),
 			base64_string: None,
 		}
 	}
@@ -120,11 +225,16 @@ impl<P: Pixel> Image<P> {
 		Self {
 			width,
 			height,
-			data: vec![color; (width * height) as usize],
+			data: vec![color; (width * height) as usize].into(),
 			base64_string: None,
 		}
 	}
 
+	/// The number of bytes held by the pixels of the image, and by its base64 encoding if that's been made.
+	pub fn memory_size(&self) -> usize {
+		self.data.len() * core::mem::size_of::<P>() + self.base64_string.as_ref().map_or(0, String::len)
+	}
+
 	pub fn as_slice(&self) -> ImageSlice<P> {
 		ImageSlice {
 			width: self.width,
@@ -154,6 +264,59 @@ impl Image<Color> {
 		encoder.write_image(&data, width, height, ::image::ColorType::Rgba8).expect("failed to encode image as png");
 		png
 	}
+
+	/// Encodes the image as a JPEG of the given quality from 1 to 100, on a white background since JPEGs can't be transparent.
+	pub fn to_jpeg(&self, quality: u8) -> Vec<u8> {
+		use ::image::ImageEncoder;
+		let (data, width, height) = self.to_flat_u8();
+		let rgb = data
+			.chunks_exact(4)
+			.flat_map(|pixel| {
+				let alpha = pixel[3] as u32;
+				[0, 1, 2].map(|channel| ((pixel[channel] as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8)
+			})
+			.collect::<Vec<_>>();
+		let mut jpeg = Vec::new();
+		let encoder = ::image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, quality.clamp(1, 100));
+		encoder.write_image(&rgb, width, height, ::image::ColorType::Rgb8).expect("failed to encode image as jpeg");
+		jpeg
+	}
+
+	/// Shrinks the image to the given size by averaging the pixels covered by each new pixel. The image is never enlarged.
+	pub fn downsample(&self, width: u32, height: u32) -> Self {
+		let (width, height) = (width.clamp(1, self.width.max(1)), height.clamp(1, self.height.max(1)));
+		let scale = DVec2::new(self.width as f64 / width as f64, self.height as f64 / height as f64);
+		// The range of source pixels covered by a new pixel, which always includes at least one
+		let range = |index: u32, scale: f64, length: u32| {
+			let start = ((index as f64 * scale) as u32).min(length.saturating_sub(1));
+			let end = (((index + 1) as f64 * scale) as u32).clamp(start + 1, length);
+			start..end
+		};
+
+		let mut data = Vec::with_capacity((width * height) as usize);
+		for y in 0..height {
+			let rows = range(y, scale.y, self.height);
+			for x in 0..width {
+				let columns = range(x, scale.x, self.width);
+				let mut sum = [0_f32; 4];
+				for source_y in rows.clone() {
+					for source_x in columns.clone() {
+						let color = self.data[(source_y * self.width + source_x) as usize];
+						sum = [sum[0] + color.r(), sum[1] + color.g(), sum[2] + color.b(), sum[3] + color.a()];
+					}
+				}
+				let count = (rows.len() * columns.len()) as f32;
+				data.push(Color::from_rgbaf32_unchecked(sum[0] / count, sum[1] / count, sum[2] / count, sum[3] / count));
+			}
+		}
+
+		Image {
+			width,
+			height,
+			data: data.into(),
+			base64_string: None,
+		}
+	}
 }
 
 use super::*;
@@ -253,7 +416,7 @@ fn map_node<P: Pixel>(input: (u32, u32), data: Vec<P>) -> Image<P> {
 	Image {
 		width: input.0,
 		height: input.1,
-		data,
+		data: data.into(),
 		base64_string: None,
 	}
 }
@@ -320,7 +483,7 @@ where
 }
 
 impl<P: Copy + Pixel> ImageFrame<P> {
-	pub const fn empty() -> Self {
+	pub fn empty() -> Self {
 		Self {
 			image: Image::empty(),
 			transform: DAffine2::ZERO,
@@ -328,7 +491,7 @@ impl<P: Copy + Pixel> ImageFrame<P> {
 		}
 	}
 
-	pub const fn identity() -> Self {
+	pub fn identity() -> Self {
 		Self {
 			image: Image::empty(),
 			transform: DAffine2::IDENTITY,
@@ -414,3 +577,33 @@ impl From<ImageFrame<SRGBA8>> for ImageFrame<Color> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn clones_share_pixels_until_changed() {
+		let image = Image::new(2, 2, Color::BLACK);
+		let mut changed = image.clone();
+		assert!(changed.data.ptr_eq(&image.data));
+
+		changed.data[0] = Color::WHITE;
+		assert!(!changed.data.ptr_eq(&image.data));
+		assert!(!image.data.is_shared());
+		assert_eq!(image.data[0], Color::BLACK);
+		assert_eq!(changed.data[0], Color::WHITE);
+		assert_eq!(changed.data[1], Color::BLACK);
+	}
+
+	#[test]
+	fn unshared_pixels_are_changed_in_place() {
+		let mut image = Image::new(2, 2, Color::BLACK);
+		let pixels = image.data.as_ptr();
+		for pixel in &mut image.data {
+			*pixel = Color::WHITE;
+		}
+		assert_eq!(image.data.as_ptr(), pixels);
+		assert_eq!(image.data.into_vec().as_ptr(), pixels);
+	}
+}
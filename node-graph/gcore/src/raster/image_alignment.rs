@@ -0,0 +1,448 @@
+//! Automatic alignment of two overlapping images, such as scans of adjoining parts of a page or bracketed shots of the same scene.
+//!
+//! The images are compared by phase correlation, which finds the shift between two images from the phase difference of their Fourier transforms.
+//! Since it relies on the structure of the images rather than their brightness, it tolerates noise and differences in exposure.
+//! Rotation and scale are found first (with the Fourier-Mellin method) by phase correlating the log-polar resampled magnitude spectra of the images,
+//! which are unaffected by translation and turn rotation and scale into shifts. The translation is then found after undoing the rotation and scale.
+
+use super::{Color, Image};
+
+use core::f64::consts::PI;
+use glam::{DAffine2, DVec2};
+
+/// The number of samples along each side of the square grid that the images are compared on, which must be a power of two.
+const GRID_SIZE: usize = 256;
+
+/// The standard deviation, in frequency samples, of the low-pass filter applied while correlating, which suppresses the correlation of pixel noise.
+const NOISE_FILTER_SIGMA: f64 = GRID_SIZE as f64 / 6.;
+
+/// The alignment found between two images by [`align_images`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageAlignment {
+	/// The similarity transform, in the shared space of both images, which moves the moving image into alignment with the reference image.
+	pub transform: DAffine2,
+	/// How strongly the aligned images correlate, from 0 for unrelated images to 1 for identical ones.
+	pub confidence: f64,
+}
+
+/// Finds the rotation, uniform scale, and translation which best align the `moving` image onto the `reference` image where they overlap.
+///
+/// Each image is given with the transform from its pixel coordinates into a space shared by both images (such as document space), and the resulting transform is in that shared space.
+/// Returns `None` if either image is empty.
+pub fn align_images(reference: &Image<Color>, reference_transform: DAffine2, moving: &Image<Color>, moving_transform: DAffine2) -> Option<ImageAlignment> {
+	if reference.width == 0 || reference.height == 0 || moving.width == 0 || moving.height == 0 {
+		return None;
+	}
+
+	// Compare the images on a square grid covering both of them
+	let corners = |image: &Image<Color>, transform: DAffine2| {
+		let size = DVec2::new(image.width as f64, image.height as f64);
+		[DVec2::ZERO, DVec2::new(size.x, 0.), DVec2::new(0., size.y), size].map(|corner| transform.transform_point2(corner))
+	};
+	let points = corners(reference, reference_transform).into_iter().chain(corners(moving, moving_transform));
+	let (min, max) = points.fold((DVec2::splat(f64::INFINITY), DVec2::splat(f64::NEG_INFINITY)), |(min, max), point| (min.min(point), max.max(point)));
+	let cell_size = (max - min).max_element() / GRID_SIZE as f64;
+	if !cell_size.is_finite() || cell_size <= 0. {
+		return None;
+	}
+	let grid_to_shared = DAffine2::from_translation(min) * DAffine2::from_scale(DVec2::splat(cell_size));
+	let shared_to_grid = grid_to_shared.inverse();
+
+	let reference = Plane::from_image(reference, reference_transform, cell_size);
+	let moving = Plane::from_image(moving, moving_transform, cell_size);
+	let reference_grid = reference.rasterize(shared_to_grid * reference.to_shared);
+
+	// The magnitude spectra can't tell a rotation apart from the same rotation plus a half turn, so both are tried, along with no rotation or scale at all in case the estimate is thrown off
+	let (rotation, scale) = rotation_and_scale(&reference_grid, &moving.rasterize(shared_to_grid * moving.to_shared));
+	let center = DVec2::splat(GRID_SIZE as f64 / 2.);
+	let candidates = [(rotation, scale), (rotation + PI, scale), (0., 1.)]
+		.map(|(rotation, scale)| DAffine2::from_translation(center) * DAffine2::from_scale(DVec2::splat(scale)) * DAffine2::from_angle(rotation) * DAffine2::from_translation(-center));
+
+	let (grid_transform, confidence) = candidates
+		.into_iter()
+		.map(|rotation_and_scale| {
+			let moving_grid = moving.rasterize(rotation_and_scale * shared_to_grid * moving.to_shared);
+			let (shift, confidence) = phase_correlate(&reference_grid, &moving_grid);
+			(DAffine2::from_translation(-shift) * rotation_and_scale, confidence)
+		})
+		.max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+	Some(ImageAlignment {
+		transform: grid_to_shared * grid_transform * shared_to_grid,
+		confidence,
+	})
+}
+
+/// The luminance of an image, box filtered down to roughly the resolution of the comparison grid to avoid aliasing.
+struct Plane {
+	width: usize,
+	height: usize,
+	data: Vec<f64>,
+	/// The transform from the coordinates of this plane into the shared space.
+	to_shared: DAffine2,
+}
+
+impl Plane {
+	fn from_image(image: &Image<Color>, transform: DAffine2, cell_size: f64) -> Self {
+		let pixel_size = transform.matrix2.determinant().abs().sqrt();
+		let factor = ((cell_size / pixel_size).floor() as usize).max(1);
+
+		let (image_width, image_height) = (image.width as usize, image.height as usize);
+		let width = image_width.div_ceil(factor);
+		let height = image_height.div_ceil(factor);
+
+		let mut data = vec![0.; width * height];
+		let mut counts = vec![0_u32; width * height];
+		for (index, color) in image.data.iter().enumerate() {
+			let (x, y) = (index % image_width, index / image_width);
+			let plane_index = (y / factor) * width + x / factor;
+			data[plane_index] += (color.luminance_rec_601() * color.a()) as f64;
+			counts[plane_index] += 1;
+		}
+		for (value, count) in data.iter_mut().zip(counts) {
+			*value /= count.max(1) as f64;
+		}
+
+		Self {
+			width,
+			height,
+			data,
+			to_shared: transform * DAffine2::from_scale(DVec2::splat(factor as f64)),
+		}
+	}
+
+	/// Samples the plane with bilinear interpolation at the given position, measured in plane samples, or returns `None` if it's outside the plane.
+	fn sample(&self, position: DVec2) -> Option<f64> {
+		// Sample values represent the centers of their areas
+		let position = position - 0.5;
+		if position.x < 0. || position.y < 0. || position.x > (self.width - 1) as f64 || position.y > (self.height - 1) as f64 {
+			return None;
+		}
+
+		let (x, y) = (position.x.floor() as usize, position.y.floor() as usize);
+		let (next_x, next_y) = ((x + 1).min(self.width - 1), (y + 1).min(self.height - 1));
+		let (fraction_x, fraction_y) = (position.x - x as f64, position.y - y as f64);
+		let value = |x: usize, y: usize| self.data[y * self.width + x];
+
+		let top = value(x, y) * (1. - fraction_x) + value(next_x, y) * fraction_x;
+		let bottom = value(x, next_y) * (1. - fraction_x) + value(next_x, next_y) * fraction_x;
+		Some(top * (1. - fraction_y) + bottom * fraction_y)
+	}
+
+	/// Resamples the plane onto the comparison grid, given the transform from this plane's coordinates into grid cells.
+	/// The area outside the plane is filled with its average and the grid is tapered towards its edges, so neither the image's borders nor the grid's edges are mistaken for features.
+	fn rasterize(&self, to_grid: DAffine2) -> Vec<f64> {
+		let to_plane = to_grid.inverse();
+		let samples = (0..GRID_SIZE * GRID_SIZE)
+			.map(|index| {
+				let cell_center = DVec2::new((index % GRID_SIZE) as f64, (index / GRID_SIZE) as f64) + 0.5;
+				self.sample(to_plane.transform_point2(cell_center))
+			})
+			.collect::<Vec<_>>();
+
+		let covered = samples.iter().flatten();
+		let count = covered.clone().count().max(1);
+		let mean = covered.sum::<f64>() / count as f64;
+
+		samples
+			.into_iter()
+			.enumerate()
+			.map(|(index, sample)| (sample.unwrap_or(mean) - mean) * hann_window(index % GRID_SIZE) * hann_window(index / GRID_SIZE))
+			.collect()
+	}
+}
+
+fn hann_window(index: usize) -> f64 {
+	0.5 - 0.5 * (2. * PI * (index as f64 + 0.5) / GRID_SIZE as f64).cos()
+}
+
+/// Estimates the rotation (in radians) and scale which map the reference grid onto the moving grid, up to a half turn of ambiguity in the rotation.
+fn rotation_and_scale(reference: &[f64], moving: &[f64]) -> (f64, f64) {
+	let max_radius = (GRID_SIZE / 2 - 1) as f64;
+	let log_polar_reference = log_polar_spectrum(reference, max_radius);
+	let log_polar_moving = log_polar_spectrum(moving, max_radius);
+
+	// Columns are the logarithm of the radius and rows are the angle over a half turn, since the magnitude spectrum of a real image is symmetric
+	let (shift, _) = phase_correlate(&log_polar_reference, &log_polar_moving);
+	let rotation = -shift.y * PI / GRID_SIZE as f64;
+	let scale = (shift.x * max_radius.ln() / GRID_SIZE as f64).exp();
+	(rotation, scale)
+}
+
+/// Resamples the magnitude spectrum of the grid onto log-polar coordinates around its zero frequency.
+fn log_polar_spectrum(grid: &[f64], max_radius: f64) -> Vec<f64> {
+	let mut spectrum = grid.iter().map(|&value| Complex::new(value, 0.)).collect::<Vec<_>>();
+	fft_2d(&mut spectrum, false);
+
+	// Emphasize the higher frequencies, which hold the detail of the image, with the high-pass filter of Reddy and Chatterji
+	let magnitude = |x: isize, y: isize| {
+		let (u, v) = (x as f64 / GRID_SIZE as f64, y as f64 / GRID_SIZE as f64);
+		let emphasis = (1. - (PI * u).cos() * (PI * v).cos()) * (2. - (PI * u).cos() * (PI * v).cos());
+		let index = (y.rem_euclid(GRID_SIZE as isize) as usize) * GRID_SIZE + x.rem_euclid(GRID_SIZE as isize) as usize;
+		(1. + spectrum[index].norm()).ln() * emphasis
+	};
+
+	let log_polar = (0..GRID_SIZE * GRID_SIZE)
+		.map(|index| {
+			let angle = (index / GRID_SIZE) as f64 * PI / GRID_SIZE as f64;
+			let radius = ((index % GRID_SIZE) as f64 * max_radius.ln() / GRID_SIZE as f64).exp();
+			let position = DVec2::new(angle.cos(), angle.sin()) * radius;
+
+			let (x, y) = (position.x.floor(), position.y.floor());
+			let (fraction_x, fraction_y) = (position.x - x, position.y - y);
+			let (x, y) = (x as isize, y as isize);
+			let top = magnitude(x, y) * (1. - fraction_x) + magnitude(x + 1, y) * fraction_x;
+			let bottom = magnitude(x, y + 1) * (1. - fraction_x) + magnitude(x + 1, y + 1) * fraction_x;
+			top * (1. - fraction_y) + bottom * fraction_y
+		})
+		.collect::<Vec<_>>();
+
+	// The angle wraps around seamlessly but the radius doesn't, so the radius is tapered to avoid its ends being mistaken for features
+	let mean = log_polar.iter().sum::<f64>() / log_polar.len() as f64;
+	log_polar.into_iter().enumerate().map(|(index, value)| (value - mean) * hann_window(index % GRID_SIZE)).collect()
+}
+
+/// Finds the shift, with sub-sample precision, by which the `moving` grid is offset from the `reference` grid, along with the height of the correlation peak.
+fn phase_correlate(reference: &[f64], moving: &[f64]) -> (DVec2, f64) {
+	let mut reference_spectrum = reference.iter().map(|&value| Complex::new(value, 0.)).collect::<Vec<_>>();
+	let mut moving_spectrum = moving.iter().map(|&value| Complex::new(value, 0.)).collect::<Vec<_>>();
+	fft_2d(&mut reference_spectrum, false);
+	fft_2d(&mut moving_spectrum, false);
+
+	// The normalized cross-power spectrum keeps only the phase difference, which transforms back into a peak at the shift
+	let mut correlation = reference_spectrum
+		.iter()
+		.zip(moving_spectrum)
+		.enumerate()
+		.map(|(index, (reference, moving))| {
+			let cross_power = moving.mul(reference.conj());
+			let norm = cross_power.norm();
+			if norm < f64::EPSILON {
+				return Complex::default();
+			}
+
+			let frequency = |index: usize| if index < GRID_SIZE / 2 { index as f64 } else { index as f64 - GRID_SIZE as f64 };
+			let (u, v) = (frequency(index % GRID_SIZE), frequency(index / GRID_SIZE));
+			let noise_filter = (-(u * u + v * v) / (2. * NOISE_FILTER_SIGMA * NOISE_FILTER_SIGMA)).exp();
+			cross_power.scale(noise_filter / norm)
+		})
+		.collect::<Vec<_>>();
+	fft_2d(&mut correlation, true);
+
+	let value = |x: isize, y: isize| correlation[(y.rem_euclid(GRID_SIZE as isize) as usize) * GRID_SIZE + x.rem_euclid(GRID_SIZE as isize) as usize].re;
+	let (peak_index, peak) = correlation
+		.iter()
+		.enumerate()
+		.fold((0, f64::NEG_INFINITY), |best, (index, value)| if value.re > best.1 { (index, value.re) } else { best });
+	let (x, y) = ((peak_index % GRID_SIZE) as isize, (peak_index / GRID_SIZE) as isize);
+
+	// Refine the peak by fitting a parabola through it and its neighbors along each axis
+	let refine = |before: f64, after: f64| {
+		let curvature = before - 2. * peak + after;
+		if curvature.abs() < f64::EPSILON {
+			0.
+		} else {
+			(0.5 * (before - after) / curvature).clamp(-0.5, 0.5)
+		}
+	};
+	let offset = DVec2::new(refine(value(x - 1, y), value(x + 1, y)), refine(value(x, y - 1), value(x, y + 1)));
+
+	// Shifts past the middle of the grid wrap around to negative shifts
+	let wrap = |index: isize| if index > GRID_SIZE as isize / 2 { index - GRID_SIZE as isize } else { index };
+	let shift = DVec2::new(wrap(x) as f64, wrap(y) as f64) + offset;
+
+	// The filter lowers the peak even for identical images, so the peak is measured relative to the filter's total weight
+	let filter_weight = correlation_filter_weight();
+	(shift, (peak / filter_weight).clamp(0., 1.))
+}
+
+/// The height of the correlation peak for identical images, which is the average weight of the noise filter over all frequencies.
+fn correlation_filter_weight() -> f64 {
+	let frequency = |index: usize| if index < GRID_SIZE / 2 { index as f64 } else { index as f64 - GRID_SIZE as f64 };
+	let sum = (0..GRID_SIZE)
+		.map(|index| (-(frequency(index).powi(2)) / (2. * NOISE_FILTER_SIGMA * NOISE_FILTER_SIGMA)).exp())
+		.sum::<f64>();
+	sum * sum / (GRID_SIZE * GRID_SIZE) as f64
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Complex {
+	re: f64,
+	im: f64,
+}
+
+impl Complex {
+	fn new(re: f64, im: f64) -> Self {
+		Self { re, im }
+	}
+
+	fn mul(self, other: Self) -> Self {
+		Self::new(self.re * other.re - self.im * other.im, self.re * other.im + self.im * other.re)
+	}
+
+	fn conj(self) -> Self {
+		Self::new(self.re, -self.im)
+	}
+
+	fn scale(self, factor: f64) -> Self {
+		Self::new(self.re * factor, self.im * factor)
+	}
+
+	fn norm(self) -> f64 {
+		self.re.hypot(self.im)
+	}
+}
+
+/// Transforms a square grid of [`GRID_SIZE`] by [`GRID_SIZE`] samples into or (if `inverse`) out of the frequency domain.
+fn fft_2d(data: &mut [Complex], inverse: bool) {
+	let mut column = vec![Complex::default(); GRID_SIZE];
+	for row in data.chunks_exact_mut(GRID_SIZE) {
+		fft(row, inverse);
+	}
+	for x in 0..GRID_SIZE {
+		column.iter_mut().enumerate().for_each(|(y, value)| *value = data[y * GRID_SIZE + x]);
+		fft(&mut column, inverse);
+		column.iter().enumerate().for_each(|(y, value)| data[y * GRID_SIZE + x] = *value);
+	}
+}
+
+/// The iterative radix-2 Cooley-Tukey fast Fourier transform, where the length of the data must be a power of two. The inverse is normalized by the length.
+fn fft(data: &mut [Complex], inverse: bool) {
+	let length = data.len();
+
+	// Reorder the data by bit-reversed index
+	let mut j = 0;
+	for i in 1..length {
+		let mut bit = length >> 1;
+		while j & bit != 0 {
+			j ^= bit;
+			bit >>= 1;
+		}
+		j |= bit;
+		if i < j {
+			data.swap(i, j);
+		}
+	}
+
+	let sign = if inverse { 1. } else { -1. };
+	let mut size = 2;
+	while size <= length {
+		let angle = sign * 2. * PI / size as f64;
+		let step = Complex::new(angle.cos(), angle.sin());
+		for start in (0..length).step_by(size) {
+			let mut twiddle = Complex::new(1., 0.);
+			for offset in 0..size / 2 {
+				let even = data[start + offset];
+				let odd = data[start + offset + size / 2].mul(twiddle);
+				data[start + offset] = Complex::new(even.re + odd.re, even.im + odd.im);
+				data[start + offset + size / 2] = Complex::new(even.re - odd.re, even.im - odd.im);
+				twiddle = twiddle.mul(step);
+			}
+		}
+		size *= 2;
+	}
+
+	if inverse {
+		data.iter_mut().for_each(|value| *value = value.scale(1. / length as f64));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// A cheap deterministic pseudo-random number between 0 and 1.
+	fn hash(a: u32, b: u32) -> f64 {
+		let hash = a.wrapping_mul(2654435761) ^ b.wrapping_mul(2246822519).rotate_left(13);
+		(hash.wrapping_mul(3266489917) >> 8) as f64 / (1 << 24) as f64
+	}
+
+	/// A pattern of scattered blobs of various sizes, which has detail at several scales like a photo would.
+	fn pattern(position: DVec2) -> f64 {
+		let value = (0..300)
+			.map(|index| {
+				let center = DVec2::new(hash(index, 1), hash(index, 2)) * 300.;
+				let radius = 1.5 + 4. * hash(index, 3);
+				(-(position - center).length_squared() / (2. * radius * radius)).exp()
+			})
+			.sum::<f64>();
+		value.min(1.)
+	}
+
+	/// Renders the pattern into an image as seen through the given transform from pixel coordinates into pattern coordinates, with some added noise that differs for each seed.
+	fn render(size: u32, pixel_to_pattern: DAffine2, noise: f64, seed: u32) -> Image<Color> {
+		let data = (0..size * size)
+			.map(|index| {
+				let pixel = DVec2::new((index % size) as f64, (index / size) as f64) + 0.5;
+				let value = (pattern(pixel_to_pattern.transform_point2(pixel)) + noise * (hash(index, seed) - 0.5)) as f32;
+				Color::from_rgbaf32_unchecked(value, value, value, 1.)
+			})
+			.collect();
+		Image {
+			width: size,
+			height: size,
+			data,
+			base64_string: None,
+		}
+	}
+
+	fn assert_aligned(alignment: ImageAlignment, expected: DAffine2) {
+		for point in [DVec2::new(60., 60.), DVec2::new(200., 80.), DVec2::new(120., 180.)] {
+			let distance = alignment.transform.transform_point2(point).distance(expected.transform_point2(point));
+			assert!(distance < 1., "Point {point} is misaligned by {distance} with alignment {alignment:?}");
+		}
+	}
+
+	#[test]
+	fn align_translated_image() {
+		let offset = DAffine2::from_translation(DVec2::new(17., -9.));
+		let reference = render(256, DAffine2::IDENTITY, 0., 0);
+		let moving = render(256, offset, 0., 0);
+
+		// The moving image shows the part of the pattern at the offset, so it has to be moved by the offset to line up with the reference
+		let alignment = align_images(&reference, DAffine2::IDENTITY, &moving, DAffine2::IDENTITY).unwrap();
+		assert_aligned(alignment, offset);
+		assert!(alignment.confidence > 0.5);
+	}
+
+	#[test]
+	fn align_rotated_and_scaled_image() {
+		let center = DVec2::splat(128.);
+		let offset = DAffine2::from_translation(center) * DAffine2::from_scale(DVec2::splat(1.1)) * DAffine2::from_angle(-0.3) * DAffine2::from_translation(-center);
+		let reference = render(256, DAffine2::IDENTITY, 0., 0);
+		let moving = render(256, offset, 0., 0);
+
+		let alignment = align_images(&reference, DAffine2::IDENTITY, &moving, DAffine2::IDENTITY).unwrap();
+		assert_aligned(alignment, offset);
+	}
+
+	#[test]
+	fn align_noisy_partially_overlapping_scans() {
+		// Two scans of adjoining parts of the pattern, with the second roughly placed a little away from where it belongs
+		let reference = render(200, DAffine2::IDENTITY, 0.3, 4);
+		let moving = render(200, DAffine2::from_translation(DVec2::new(80., 40.)), 0.3, 5);
+		let moving_transform = DAffine2::from_translation(DVec2::new(70., 45.));
+
+		let alignment = align_images(&reference, DAffine2::IDENTITY, &moving, moving_transform).unwrap();
+		assert_aligned(alignment, DAffine2::from_translation(DVec2::new(10., -5.)));
+	}
+
+	#[test]
+	fn align_images_placed_in_document_space() {
+		// Both images have pixels twice the size of document units, and the moving image was placed 12 units right and 6 units up from where its content belongs
+		let pixel_scale = DAffine2::from_scale(DVec2::splat(2.));
+		let reference_transform = DAffine2::from_translation(DVec2::new(100., 50.)) * pixel_scale;
+		let moving_transform = DAffine2::from_translation(DVec2::new(112., 44.)) * pixel_scale;
+		let reference = render(128, DAffine2::IDENTITY, 0., 0);
+		let moving = render(128, DAffine2::IDENTITY, 0., 0);
+
+		let alignment = align_images(&reference, reference_transform, &moving, moving_transform).unwrap();
+		let expected = DAffine2::from_translation(DVec2::new(-12., 6.));
+		for pixel in [DVec2::new(20., 20.), DVec2::new(100., 40.), DVec2::new(60., 110.)] {
+			let point = moving_transform.transform_point2(pixel);
+			let distance = alignment.transform.transform_point2(point).distance(expected.transform_point2(point));
+			assert!(distance < 1., "Point {point} is misaligned by {distance} with alignment {alignment:?}");
+		}
+		assert!(alignment.confidence > 0.5);
+	}
+}
@@ -0,0 +1,125 @@
+//! Helpers for unit testing a node on its own, without assembling and compiling a type-erased graph around it.
+//!
+//! ```ignore
+//! let layer = node_with_values!(ConstructLayerNode, GraphicGroup::EMPTY, vector_data);
+//! let graphic_group = evaluate(&layer);
+//! ```
+
+use crate::transform::Footprint;
+use crate::Node;
+
+use core::future::{ready, Future, Ready};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// A node input which always gives the same value.
+/// Evaluating it with `()` gives the value itself, as for the plain parameters of nodes,
+/// and evaluating it with a [`Footprint`] gives a future of the value, as for the parameters of nodes which render depending on the footprint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestValueNode<T>(pub T);
+
+impl<T> TestValueNode<T> {
+	pub const fn new(value: T) -> Self {
+		Self(value)
+	}
+}
+
+impl<'i, T: Clone + 'i> Node<'i, ()> for TestValueNode<T> {
+	type Output = T;
+	#[inline(always)]
+	fn eval(&'i self, _input: ()) -> Self::Output {
+		self.0.clone()
+	}
+}
+
+impl<'i, T: Clone + 'i> Node<'i, Footprint> for TestValueNode<T> {
+	type Output = Ready<T>;
+	#[inline(always)]
+	fn eval(&'i self, _input: Footprint) -> Self::Output {
+		ready(self.0.clone())
+	}
+}
+
+/// Constructs a node generated by `node_fn` with each of its parameters given one of the values, in order.
+#[macro_export]
+macro_rules! node_with_values {
+	($($node:ident)::+ $(, $value:expr)* $(,)?) => {
+		$($node)::+::new($($crate::testing::TestValueNode::new($value)),*)
+	};
+}
+
+/// Evaluates a footprint-driven node with the default [`Footprint`], waiting for its output.
+#[track_caller]
+pub fn evaluate<'i, N: Node<'i, Footprint>>(node: &'i N) -> <N::Output as Future>::Output
+where
+	N::Output: Future,
+{
+	block_on(node.eval(Footprint::default()))
+}
+
+/// Polls the future to completion on the current thread.
+///
+/// Nodes evaluated on their own don't wait on anything external, so their futures are finished by the first poll,
+/// and this panics instead of blocking forever if one isn't.
+#[track_caller]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+	fn noop_raw_waker() -> RawWaker {
+		fn clone(_: *const ()) -> RawWaker {
+			noop_raw_waker()
+		}
+		fn noop(_: *const ()) {}
+		static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+		RawWaker::new(core::ptr::null(), &VTABLE)
+	}
+
+	// SAFETY: The waker's functions ignore the data pointer, so a null one is never dereferenced
+	let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+	let mut context = Context::from_waker(&waker);
+	let mut future = core::pin::pin!(future);
+	match future.as_mut().poll(&mut context) {
+		Poll::Ready(output) => output,
+		Poll::Pending => panic!("The node's future didn't finish, so it depends on something which isn't available to a node evaluated on its own"),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::vector::VectorData;
+	use crate::{ArtboardGroup, ConstructArtboardNode, ConstructLayerNode, GraphicGroup};
+	use crate::{Color, GraphicElement};
+
+	use bezier_rs::Subpath;
+	use glam::{DVec2, IVec2};
+
+	#[test]
+	fn construct_layer_and_artboard() {
+		let vector_data = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE));
+		let layer = node_with_values!(ConstructLayerNode, GraphicGroup::EMPTY, vector_data);
+		let graphic_group = evaluate(&layer);
+		assert_eq!(graphic_group.len(), 1);
+		assert!(matches!(graphic_group[0], GraphicElement::VectorData(_)));
+
+		let artboard = node_with_values!(ConstructArtboardNode, graphic_group, IVec2::new(10, 20), IVec2::new(-5, 5), Color::WHITE, false, ArtboardGroup::EMPTY);
+		let artboard = evaluate(&artboard);
+		assert_eq!(artboard.location, IVec2::new(5, 20));
+		assert_eq!(artboard.dimensions, IVec2::new(5, 5));
+		assert_eq!(artboard.graphic_group.len(), 1);
+	}
+
+	#[test]
+	fn linked_artboard_shows_the_contents_of_its_source() {
+		let vector_data = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE));
+		let source = node_with_values!(ConstructLayerNode, GraphicGroup::EMPTY, vector_data);
+		let source = node_with_values!(ConstructArtboardNode, evaluate(&source), IVec2::ZERO, IVec2::new(100, 100), Color::WHITE, false, ArtboardGroup::EMPTY);
+		let linked = ArtboardGroup { artboards: vec![evaluate(&source)] };
+
+		// The linked artboard's own contents are replaced, while its placement and background stay its own
+		let artboard = node_with_values!(ConstructArtboardNode, GraphicGroup::EMPTY, IVec2::new(200, 0), IVec2::new(50, 80), Color::BLACK, true, linked);
+		let artboard = evaluate(&artboard);
+		assert_eq!(artboard.graphic_group.len(), 1);
+		assert_eq!(artboard.location, IVec2::new(200, 0));
+		assert_eq!(artboard.dimensions, IVec2::new(50, 80));
+		assert_eq!(artboard.background, Color::BLACK);
+		assert!(artboard.clip);
+	}
+}
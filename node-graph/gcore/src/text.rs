@@ -1,21 +1,136 @@
 mod font_cache;
+mod kerning;
+mod runs;
 mod to_path;
+mod variations;
 
 use crate::application_io::EditorApi;
 pub use font_cache::*;
+pub use kerning::*;
 use node_macro::node_fn;
+pub use runs::*;
 pub use to_path::*;
+pub use variations::*;
 
 use crate::Node;
 
-pub struct TextGeneratorNode<Text, FontName, Size> {
+pub struct TextGeneratorNode<Text, FontName, Size, LineHeightRatio, BoxWidth, BoxHeight, Align, AutoResize, Variations, Runs, Kerning> {
 	text: Text,
 	font_name: FontName,
 	font_size: Size,
+	line_height_ratio: LineHeightRatio,
+	box_width: BoxWidth,
+	box_height: BoxHeight,
+	align: Align,
+	auto_resize: AutoResize,
+	variations: Variations,
+	runs: Runs,
+	kerning: Kerning,
 }
 
+/// Lays out the text in a text box, where a box width or height of 0 leaves the text free to extend in that direction.
+/// Text that doesn't fit the box is marked as overflowing, unless the box grows or the text shrinks to fit as chosen by the auto-resize mode.
+/// Variable fonts are instantiated at the given positions along their axes of variation.
+/// Styled runs of the text are shaped in their own font, size, weight, and tracking, and filled with their own color.
+/// The letters are kerned by the font's kerning pairs or optically by their shapes, with manual adjustments to pairs added on top, all of which is kept in the outlines of the glyphs.
 #[node_fn(TextGeneratorNode)]
-fn generate_text<'a: 'input, T>(editor: EditorApi<'a, T>, text: String, font_name: Font, font_size: f64) -> crate::vector::VectorData {
-	let buzz_face = editor.font_cache.get(&font_name).map(|data| load_face(data));
-	crate::vector::VectorData::from_subpaths(to_path(&text, buzz_face, font_size, None))
+fn generate_text<'a: 'input, T>(
+	editor: EditorApi<'a, T>,
+	text: String,
+	font_name: Font,
+	font_size: f64,
+	line_height_ratio: f64,
+	box_width: f64,
+	box_height: f64,
+	align: TextAlign,
+	auto_resize: TextAutoResize,
+	variations: FontVariations,
+	runs: TextRuns,
+	kerning: TextKerning,
+) -> crate::vector::VectorData {
+	let buzz_face = editor.font_cache.get(&font_name).and_then(|data| load_face_with_variations(data, &variations));
+	let config = TypesettingConfig {
+		font_size,
+		line_height_ratio,
+		max_width: (box_width > 0.).then_some(box_width),
+		max_height: (box_height > 0.).then_some(box_height),
+		align,
+		auto_resize,
+		kerning: kerning.mode,
+	};
+	let Some(buzz_face) = buzz_face else {
+		// Show blank layer if font has not loaded
+		return crate::vector::VectorData::empty();
+	};
+
+	if runs.is_empty() {
+		let layout = layout_rich_text(&text, &[&buzz_face], &[ShapingRun::plain(&text)], &kerning.pairs, config);
+		let mut vector_data = crate::vector::VectorData::from_subpaths(layout.to_path(&buzz_face));
+		vector_data.text_overflows = layout.overflows;
+		return vector_data;
+	}
+
+	let segments = runs.segments(text.len());
+	let (faces, shaping_runs) = load_run_faces(editor.font_cache, buzz_face, &font_name, font_size, &variations, &segments);
+	let faces = faces.iter().collect::<Vec<_>>();
+	let layout = layout_rich_text(&text, &faces, &shaping_runs, &kerning.pairs, config);
+	let paths = layout.run_paths(&faces);
+
+	// The consecutive subpaths of each run with its own color are filled with that color
+	let mut text_fills = Vec::new();
+	for (index, (run, _)) in paths.iter().enumerate() {
+		let Some(color) = segments.get(*run).and_then(|(_, style)| style.fill) else { continue };
+		match text_fills.last_mut() {
+			Some(crate::vector::TextFill { end, color: last_color, .. }) if *end == index && *last_color == color => *end += 1,
+			_ => text_fills.push(crate::vector::TextFill { start: index, end: index + 1, color }),
+		}
+	}
+
+	let mut vector_data = crate::vector::VectorData::from_subpaths(paths.into_iter().map(|(_, subpath)| subpath));
+	vector_data.text_overflows = layout.overflows;
+	vector_data.text_fills = text_fills;
+	vector_data
+}
+
+/// Loads a font face for each distinct font and weight of the styled segments of a text, along with the shaping runs which lay out each segment in its face, size, and tracking.
+/// The first face is the given face of the text's own font, which the segments fall back to when their font hasn't loaded yet.
+pub fn load_run_faces<'a>(
+	font_cache: &'a FontCache,
+	buzz_face: rustybuzz::Face<'a>,
+	font: &Font,
+	font_size: f64,
+	variations: &FontVariations,
+	segments: &[(core::ops::Range<usize>, TextStyle)],
+) -> (Vec<rustybuzz::Face<'a>>, Vec<ShapingRun>) {
+	let mut face_keys = vec![(font.clone(), None)];
+	let mut faces = vec![buzz_face];
+	let mut shaping_runs = Vec::with_capacity(segments.len());
+	for (range, style) in segments {
+		let key = (style.font.clone().unwrap_or_else(|| font.clone()), style.weight.map(f64::to_bits));
+		let face = match face_keys.iter().position(|existing| *existing == key) {
+			Some(index) => index,
+			None => {
+				let weighted = style.weight.map_or_else(|| variations.clone(), |weight| variations.with("wght", weight));
+				match font_cache.get(&key.0).and_then(|data| load_face_with_variations(data, &weighted)) {
+					Some(buzz_face) => {
+						faces.push(buzz_face);
+						face_keys.push(key);
+						faces.len() - 1
+					}
+					None => 0,
+				}
+			}
+		};
+		let size_ratio = match style.font_size {
+			Some(size) if font_size > 0. => size / font_size,
+			_ => 1.,
+		};
+		shaping_runs.push(ShapingRun {
+			range: range.clone(),
+			face,
+			size_ratio,
+			tracking: style.tracking.unwrap_or_default(),
+		});
+	}
+	(faces, shaping_runs)
 }
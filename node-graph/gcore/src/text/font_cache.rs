@@ -1,6 +1,6 @@
 use dyn_any::{DynAny, StaticType};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// A font type (storing font family and font style and an optional preview URL)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Hash, PartialEq, Eq, DynAny, specta::Type)]
@@ -14,6 +14,21 @@ impl Font {
 	pub fn new(font_family: String, font_style: String) -> Self {
 		Self { font_family, font_style }
 	}
+
+	/// Reads the family and style names of an OpenType or TrueType font file, preferring the typographic names which group the weights of a family together.
+	pub fn from_font_data(data: &[u8]) -> Option<Self> {
+		use rustybuzz::ttf_parser::{name_id, Face};
+
+		let face = Face::parse(data, 0).ok()?;
+		let name = |ids: [u16; 2]| {
+			ids.into_iter()
+				.find_map(|id| face.names().into_iter().filter(|name| name.name_id == id && name.is_unicode()).find_map(|name| name.to_string()))
+		};
+
+		let font_family = name([name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY])?;
+		let font_style = name([name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY]).unwrap_or_else(|| "Regular".to_string());
+		Some(Self::new(font_family, font_style))
+	}
 }
 
 /// A cache of all loaded font data and preview urls along with the default font (send from `init_app` in `editor_api.rs`)
@@ -25,6 +40,9 @@ pub struct FontCache {
 	preview_urls: HashMap<Font, String>,
 	/// The default font (used as a fallback)
 	default_font: Option<Font>,
+	/// Fonts loaded from files provided by the user instead of from the font catalog
+	#[serde(default)]
+	user_fonts: HashSet<Font>,
 }
 impl FontCache {
 	/// Returns the font family name if the font is cached, otherwise returns the default font family name if that is cached
@@ -55,6 +73,21 @@ impl FontCache {
 		self.preview_urls.insert(font, perview_url);
 	}
 
+	/// Insert a font file provided by the user, rather than one from the font catalog, returning the font it was named as if it's a valid OpenType or TrueType font.
+	pub fn insert_user_font(&mut self, preview_url: String, data: Vec<u8>) -> Option<Font> {
+		let font = Font::from_font_data(&data)?;
+		self.insert(font.clone(), preview_url, data, false);
+		self.user_fonts.insert(font.clone());
+		Some(font)
+	}
+
+	/// The fonts provided by the user, sorted by family and style.
+	pub fn user_fonts(&self) -> Vec<Font> {
+		let mut fonts = self.user_fonts.iter().cloned().collect::<Vec<_>>();
+		fonts.sort_by(|a, b| (&a.font_family, &a.font_style).cmp(&(&b.font_family, &b.font_style)));
+		fonts
+	}
+
 	/// Checks if the font cache has a default font
 	pub fn has_default(&self) -> bool {
 		self.default_font.is_some()
@@ -68,12 +101,56 @@ impl FontCache {
 
 impl core::hash::Hash for FontCache {
 	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
-		self.preview_urls.len().hash(state);
-		self.preview_urls.iter().for_each(|(font, url)| {
+		// The fonts are sorted since the iteration order of a `HashMap` differs between runs, while the hash is saved with documents
+		let sorted = |fonts: &mut Vec<&Font>| fonts.sort_unstable_by(|a, b| (&a.font_family, &a.font_style).cmp(&(&b.font_family, &b.font_style)));
+
+		let mut previewed_fonts = self.preview_urls.keys().collect::<Vec<_>>();
+		sorted(&mut previewed_fonts);
+		previewed_fonts.len().hash(state);
+		previewed_fonts.into_iter().for_each(|font| {
 			font.hash(state);
-			url.hash(state)
+			self.preview_urls[font].hash(state)
 		});
-		self.font_file_data.len().hash(state);
-		self.font_file_data.keys().for_each(|font| font.hash(state));
+
+		let mut loaded_fonts = self.font_file_data.keys().collect::<Vec<_>>();
+		sorted(&mut loaded_fonts);
+		loaded_fonts.len().hash(state);
+		loaded_fonts.into_iter().for_each(|font| font.hash(state));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn invalid_user_fonts_are_rejected() {
+		let mut font_cache = FontCache::default();
+		assert_eq!(font_cache.insert_user_font(String::new(), b"not a font".to_vec()), None);
+		assert!(font_cache.user_fonts().is_empty());
+		assert!(Font::from_font_data(&[]).is_none());
+	}
+
+	#[test]
+	fn hash_is_independent_of_insertion_order() {
+		use std::collections::hash_map::DefaultHasher;
+		use std::hash::{Hash, Hasher};
+
+		let fonts = (0..16).map(|index| Font::new(format!("Family {index}"), "Regular".to_string())).collect::<Vec<_>>();
+		let mut forwards = FontCache::default();
+		let mut backwards = FontCache::default();
+		for font in &fonts {
+			forwards.insert(font.clone(), format!("{}.woff2", font.font_family), Vec::new(), false);
+		}
+		for font in fonts.iter().rev() {
+			backwards.insert(font.clone(), format!("{}.woff2", font.font_family), Vec::new(), false);
+		}
+
+		let hash = |font_cache: &FontCache| {
+			let mut hasher = DefaultHasher::new();
+			font_cache.hash(&mut hasher);
+			hasher.finish()
+		};
+		assert_eq!(hash(&forwards), hash(&backwards));
 	}
 }
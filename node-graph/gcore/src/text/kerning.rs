@@ -0,0 +1,274 @@
+use dyn_any::{DynAny, StaticType};
+
+use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder, Tag};
+
+/// The number of horizontal bands that the sides of a glyph are measured in, from the font's descender up to its ascender.
+const PROFILE_BANDS: usize = 24;
+
+/// How far into the side of a glyph the gap in a band is counted at most, as a fraction of the em.
+/// This keeps the open sides of letters like "T" and "L" from being kerned as if they were empty space.
+const MAX_GAP_DEPTH: f64 = 0.12;
+
+/// The largest adjustment made by optical kerning, as a fraction of the em.
+const MAX_OPTICAL_ADJUSTMENT: f64 = 0.2;
+
+/// The number of points that each curve of a glyph's outline is sampled at when measuring its sides.
+const CURVE_SAMPLES: usize = 8;
+
+/// How the space between pairs of letters is adjusted when shaping the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub enum KerningMode {
+	/// Uses the kerning pairs designed into the font.
+	#[default]
+	Metrics,
+	/// Ignores the font's kerning pairs and instead evens out the space between letters by measuring the shapes of their sides.
+	/// This suits fonts with missing or poor kerning, and pairs of letters from different fonts.
+	Optical,
+	/// Places the letters by their advances alone.
+	None,
+}
+
+impl core::fmt::Display for KerningMode {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			KerningMode::Metrics => write!(f, "Metrics"),
+			KerningMode::Optical => write!(f, "Optical"),
+			KerningMode::None => write!(f, "None"),
+		}
+	}
+}
+
+impl KerningMode {
+	pub fn list() -> [KerningMode; 3] {
+		[KerningMode::Metrics, KerningMode::Optical, KerningMode::None]
+	}
+
+	/// The OpenType features the text is shaped with, which turn off the font's own kerning unless it's used.
+	pub fn features(self) -> Vec<rustybuzz::Feature> {
+		match self {
+			KerningMode::Metrics => Vec::new(),
+			KerningMode::Optical | KerningMode::None => vec![rustybuzz::Feature::new(Tag::from_bytes(b"kern"), 0, ..)],
+		}
+	}
+}
+
+/// A manual adjustment of the space between a character and the one before it, in thousandths of the em.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct KerningPair {
+	/// The byte index in the text of the second character of the pair.
+	pub index: usize,
+	pub adjustment: f64,
+}
+
+/// The kerning of a text, with the manual adjustments to pairs of its characters which are added on top of the kerning mode.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct TextKerning {
+	pub mode: KerningMode,
+	/// The adjusted pairs, kept in order of their index.
+	pub pairs: Vec<KerningPair>,
+}
+
+impl core::hash::Hash for TextKerning {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.mode.hash(state);
+		for pair in &self.pairs {
+			pair.index.hash(state);
+			pair.adjustment.to_bits().hash(state);
+		}
+	}
+}
+
+impl TextKerning {
+	/// The manual adjustment of the space before the character at the byte index, which is 0 if the pair hasn't been adjusted.
+	pub fn adjustment(&self, index: usize) -> f64 {
+		self.pairs.iter().find(|pair| pair.index == index).map_or(0., |pair| pair.adjustment)
+	}
+
+	/// Sets the manual adjustment of the space before the character at the byte index, removing the pair once it's no longer adjusted.
+	pub fn set_adjustment(&mut self, index: usize, adjustment: f64) {
+		self.pairs.retain(|pair| pair.index != index);
+		if adjustment != 0. {
+			let position = self.pairs.partition_point(|pair| pair.index < index);
+			self.pairs.insert(position, KerningPair { index, adjustment });
+		}
+	}
+
+	/// Moves the pairs to follow an edit which replaced the text from the start index to the removed end index with text of the inserted length.
+	/// Pairs whose characters were replaced are removed, since the characters they were adjusted for are gone.
+	pub fn edited(&mut self, start: usize, removed_end: usize, inserted_length: usize) {
+		self.pairs.retain(|pair| pair.index <= start || removed_end < pair.index);
+		for pair in &mut self.pairs {
+			if pair.index > removed_end {
+				pair.index = pair.index - (removed_end - start) + inserted_length;
+			}
+		}
+	}
+}
+
+/// The extent of a glyph's outline in each horizontal band, which optical kerning measures the space between glyphs with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphProfile {
+	/// The leftmost and rightmost points of the outline in each band from the descender up, in font units, or `None` for bands the outline doesn't reach.
+	bands: Vec<Option<(f64, f64)>>,
+	advance: f64,
+}
+
+impl GlyphProfile {
+	/// Measures the outline of the glyph, or returns `None` for glyphs without an outline like spaces.
+	pub fn new(buzz_face: &rustybuzz::Face, glyph: GlyphId) -> Option<Self> {
+		let mut builder = ProfileBuilder {
+			bands: vec![None; PROFILE_BANDS],
+			bottom: buzz_face.descender() as f64,
+			height: (buzz_face.ascender() as f64 - buzz_face.descender() as f64).max(1.),
+			start: (0., 0.),
+			current: (0., 0.),
+		};
+		buzz_face.outline_glyph(glyph, &mut builder)?;
+		let advance = buzz_face.glyph_hor_advance(glyph).unwrap_or_default() as f64;
+		Some(Self { bands: builder.bands, advance })
+	}
+
+	/// The average space between the right side of this glyph and the left side of the next one when placed by their advances, over the bands either one reaches.
+	fn gap_before(&self, next: &Self, units_per_em: f64) -> Option<f64> {
+		let depth = MAX_GAP_DEPTH * units_per_em;
+		let gaps = self.bands.iter().zip(&next.bands).filter_map(|(left, right)| {
+			let left_gap = left.map_or(depth, |(_, right_edge)| (self.advance - right_edge).min(depth));
+			let right_gap = right.map_or(depth, |(left_edge, _)| left_edge.min(depth));
+			(left.is_some() || right.is_some()).then_some(left_gap + right_gap)
+		});
+		let (total, count) = gaps.fold((0., 0), |(total, count), gap| (total + gap, count + 1));
+		(count > 0).then(|| total / count as f64)
+	}
+
+	/// The change to the advance of this glyph, in font units, which evens out the space before the next glyph to match the given reference gap.
+	pub fn optical_adjustment(&self, next: &Self, reference_gap: f64, units_per_em: f64) -> f64 {
+		let Some(gap) = self.gap_before(next, units_per_em) else { return 0. };
+		let limit = MAX_OPTICAL_ADJUSTMENT * units_per_em;
+		(reference_gap - gap).clamp(-limit, limit)
+	}
+}
+
+/// The gap that optical kerning spaces every pair of glyphs of the font face to, which is the gap between two of its "n" glyphs as the font's designer spaced them.
+pub fn optical_reference_gap(buzz_face: &rustybuzz::Face) -> Option<f64> {
+	let glyph = buzz_face.glyph_index('n')?;
+	let profile = GlyphProfile::new(buzz_face, glyph)?;
+	profile.gap_before(&profile, buzz_face.units_per_em() as f64)
+}
+
+/// Records the extent of an outline in each band as it's drawn, by following each of its segments across the bands.
+struct ProfileBuilder {
+	bands: Vec<Option<(f64, f64)>>,
+	bottom: f64,
+	height: f64,
+	start: (f64, f64),
+	current: (f64, f64),
+}
+
+impl ProfileBuilder {
+	fn band(&self, y: f64) -> usize {
+		(((y - self.bottom) / self.height * PROFILE_BANDS as f64).floor().max(0.) as usize).min(PROFILE_BANDS - 1)
+	}
+
+	fn include(&mut self, band: usize, x: f64) {
+		let extent = self.bands[band].get_or_insert((x, x));
+		extent.0 = extent.0.min(x);
+		extent.1 = extent.1.max(x);
+	}
+
+	/// Adds the line from the current point, including where it crosses each band in between its ends so steep lines don't skip bands.
+	fn segment(&mut self, to: (f64, f64)) {
+		let from = self.current;
+		let (from_band, to_band) = (self.band(from.1), self.band(to.1));
+		self.include(from_band, from.0);
+		self.include(to_band, to.0);
+		for band in from_band.min(to_band) + 1..=from_band.max(to_band) {
+			let y = self.bottom + band as f64 / PROFILE_BANDS as f64 * self.height;
+			let t = if to.1 == from.1 { 0. } else { (y - from.1) / (to.1 - from.1) };
+			let x = from.0 + (to.0 - from.0) * t.clamp(0., 1.);
+			self.include(band, x);
+			self.include(band - 1, x);
+		}
+		self.current = to;
+	}
+}
+
+impl OutlineBuilder for ProfileBuilder {
+	fn move_to(&mut self, x: f32, y: f32) {
+		self.start = (x as f64, y as f64);
+		self.current = self.start;
+	}
+
+	fn line_to(&mut self, x: f32, y: f32) {
+		self.segment((x as f64, y as f64));
+	}
+
+	fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+		let (start, control, end) = (self.current, (x1 as f64, y1 as f64), (x as f64, y as f64));
+		for sample in 1..=CURVE_SAMPLES {
+			let t = sample as f64 / CURVE_SAMPLES as f64;
+			let point = |a: f64, b: f64, c: f64| (1. - t) * (1. - t) * a + 2. * (1. - t) * t * b + t * t * c;
+			self.segment((point(start.0, control.0, end.0), point(start.1, control.1, end.1)));
+		}
+	}
+
+	fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+		let (start, first, second, end) = (self.current, (x1 as f64, y1 as f64), (x2 as f64, y2 as f64), (x as f64, y as f64));
+		for sample in 1..=CURVE_SAMPLES {
+			let t = sample as f64 / CURVE_SAMPLES as f64;
+			let point = |a: f64, b: f64, c: f64, d: f64| (1. - t).powi(3) * a + 3. * (1. - t).powi(2) * t * b + 3. * (1. - t) * t * t * c + t.powi(3) * d;
+			self.segment((point(start.0, first.0, second.0, end.0), point(start.1, first.1, second.1, end.1)));
+		}
+	}
+
+	fn close(&mut self) {
+		self.segment(self.start);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// A profile of a glyph 600 units wide, with ink from 50 to 550 units across the given bands.
+	fn profile(bands: core::ops::Range<usize>) -> GlyphProfile {
+		GlyphProfile {
+			bands: (0..PROFILE_BANDS).map(|band| bands.contains(&band).then_some((50., 550.))).collect(),
+			advance: 600.,
+		}
+	}
+
+	#[test]
+	fn pairs_follow_edits() {
+		let mut kerning = TextKerning::default();
+		kerning.set_adjustment(8, 20.);
+		kerning.set_adjustment(3, -40.);
+		assert_eq!(kerning.pairs.iter().map(|pair| pair.index).collect::<Vec<_>>(), vec![3, 8]);
+
+		// Typing two characters before both pairs moves them along
+		kerning.edited(1, 1, 2);
+		assert_eq!(kerning.adjustment(5), -40.);
+		assert_eq!(kerning.adjustment(10), 20.);
+
+		// Replacing the character before the second pair keeps it, while replacing the second character of a pair removes it
+		kerning.edited(4, 6, 1);
+		assert_eq!(kerning.pairs, vec![KerningPair { index: 9, adjustment: 20. }]);
+
+		kerning.set_adjustment(9, 0.);
+		assert!(kerning.pairs.is_empty());
+	}
+
+	#[test]
+	fn optical_kerning_evens_out_the_space_between_glyphs() {
+		let stem = profile(4..16);
+		let reference = stem.gap_before(&stem, 1000.).unwrap();
+		assert_eq!(stem.optical_adjustment(&stem, reference, 1000.), 0.);
+
+		// A glyph whose ink is only near the top, like the arm of a "T", leaves open space beside the next glyph which is closed up
+		let arm = profile(14..16);
+		assert!(arm.optical_adjustment(&stem, reference, 1000.) < 0.);
+
+		// The gap counted in each band is limited, so glyphs whose open sides face each other are only brought together so far
+		let low = profile(0..2);
+		assert!((arm.optical_adjustment(&low, reference, 1000.) - (reference - 50. - MAX_GAP_DEPTH * 1000.)).abs() < 1e-9);
+	}
+}
@@ -0,0 +1,237 @@
+use super::Font;
+use crate::Color;
+
+use dyn_any::{DynAny, StaticType};
+
+use core::ops::Range;
+
+/// The styling of a range of text, where each property that's set overrides the style of the Text node for that range.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct TextStyle {
+	pub font: Option<Font>,
+	pub font_size: Option<f64>,
+	/// The position along the weight axis of a variable font, from 1 to 1000 where 400 is regular and 700 is bold.
+	pub weight: Option<f64>,
+	pub fill: Option<Color>,
+	/// Space added after each character, in thousandths of the em, which is negative to bring the characters closer together.
+	pub tracking: Option<f64>,
+}
+
+impl core::hash::Hash for TextStyle {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.font.hash(state);
+		self.font_size.map(f64::to_bits).hash(state);
+		self.weight.map(f64::to_bits).hash(state);
+		self.fill.hash(state);
+		self.tracking.map(f64::to_bits).hash(state);
+	}
+}
+
+impl TextStyle {
+	pub fn is_empty(&self) -> bool {
+		self.font.is_none() && self.font_size.is_none() && self.weight.is_none() && self.fill.is_none() && self.tracking.is_none()
+	}
+
+	/// This style with the properties set in the other style overriding its own.
+	pub fn merged(&self, other: &Self) -> Self {
+		Self {
+			font: other.font.clone().or_else(|| self.font.clone()),
+			font_size: other.font_size.or(self.font_size),
+			weight: other.weight.or(self.weight),
+			fill: other.fill.or(self.fill),
+			tracking: other.tracking.or(self.tracking),
+		}
+	}
+}
+
+/// A range of text, given by the byte indices of its start and end, which is styled differently from the rest of the text.
+#[derive(Debug, Clone, PartialEq, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct TextRun {
+	pub start: usize,
+	pub end: usize,
+	pub style: TextStyle,
+}
+
+/// The styled runs of a text, which are kept in order without overlapping so each part of the text has one style.
+/// Parts of the text outside of every run have the style of the Text node.
+#[derive(Debug, Clone, Default, PartialEq, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct TextRuns(pub Vec<TextRun>);
+
+impl TextRuns {
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// The style of the character starting at the byte index.
+	pub fn style_at(&self, index: usize) -> TextStyle {
+		self.0.iter().find(|run| run.start <= index && index < run.end).map(|run| run.style.clone()).unwrap_or_default()
+	}
+
+	/// The style shared by the whole range, with only the properties that are the same throughout it.
+	pub fn common_style(&self, range: Range<usize>) -> TextStyle {
+		let mut styles = self
+			.split_at(range.clone())
+			.into_iter()
+			.filter(|(piece, _)| piece.start < range.end && range.start < piece.end)
+			.map(|(_, style)| style);
+		let Some(first) = styles.next() else { return TextStyle::default() };
+		styles.fold(first, |common, style| TextStyle {
+			font: common.font.filter(|font| style.font.as_ref() == Some(font)),
+			font_size: common.font_size.filter(|size| style.font_size == Some(*size)),
+			weight: common.weight.filter(|weight| style.weight == Some(*weight)),
+			fill: common.fill.filter(|fill| style.fill == Some(*fill)),
+			tracking: common.tracking.filter(|tracking| style.tracking == Some(*tracking)),
+		})
+	}
+
+	/// The pieces of a text of the given length in order, each with its style, covering the parts outside of every run with the default style.
+	pub fn segments(&self, length: usize) -> Vec<(Range<usize>, TextStyle)> {
+		self.split_at(0..length).into_iter().filter(|(piece, _)| piece.end <= length).collect()
+	}
+
+	/// Applies the style to the range, overriding the properties it sets while keeping the others that the range was styled with.
+	pub fn apply(&mut self, range: Range<usize>, style: &TextStyle) {
+		if range.is_empty() {
+			return;
+		}
+		let runs = self
+			.split_at(range.clone())
+			.into_iter()
+			.map(|(piece, existing)| {
+				let style = if range.start <= piece.start && piece.end <= range.end { existing.merged(style) } else { existing };
+				TextRun {
+					start: piece.start,
+					end: piece.end,
+					style,
+				}
+			})
+			.collect();
+		self.0 = runs;
+		self.normalize();
+	}
+
+	/// Removes the styling of the range, so it takes on the style of the Text node.
+	pub fn clear(&mut self, range: Range<usize>) {
+		let runs = self
+			.split_at(range.clone())
+			.into_iter()
+			.filter(|(piece, _)| piece.end <= range.start || range.end <= piece.start)
+			.map(|(piece, style)| TextRun {
+				start: piece.start,
+				end: piece.end,
+				style,
+			})
+			.collect();
+		self.0 = runs;
+		self.normalize();
+	}
+
+	/// Moves the runs to follow an edit which replaced the text from the start index to the removed end index with text of the inserted length.
+	/// Inserted text takes on the style of the text just before it, like typing continues with the style at the cursor.
+	pub fn edited(&mut self, start: usize, removed_end: usize, inserted_length: usize) {
+		let moved = |index: usize| {
+			if index <= removed_end {
+				start + inserted_length
+			} else {
+				index - (removed_end - start) + inserted_length
+			}
+		};
+		for run in &mut self.0 {
+			// Text at the very start takes on the style of the run beginning there, since there's no text before it
+			if run.start > start || (run.start == start && start > 0) {
+				run.start = moved(run.start);
+			}
+			if run.end >= start {
+				run.end = moved(run.end);
+			}
+		}
+		self.normalize();
+	}
+
+	/// Splits the existing runs at the ends of the range, returning the pieces of the runs along with any gaps covered by the range.
+	fn split_at(&self, range: Range<usize>) -> Vec<(Range<usize>, TextStyle)> {
+		let mut boundaries = vec![range.start, range.end];
+		boundaries.extend(self.0.iter().flat_map(|run| [run.start, run.end]));
+		boundaries.sort_unstable();
+		boundaries.dedup();
+
+		boundaries
+			.windows(2)
+			.map(|pair| pair[0]..pair[1])
+			.filter_map(|piece| {
+				let run = self.0.iter().find(|run| run.start <= piece.start && piece.end <= run.end);
+				let in_range = range.start <= piece.start && piece.end <= range.end;
+				match run {
+					Some(run) => Some((piece, run.style.clone())),
+					None if in_range => Some((piece, TextStyle::default())),
+					None => None,
+				}
+			})
+			.collect()
+	}
+
+	/// Drops the empty runs and joins neighboring runs with the same style.
+	fn normalize(&mut self) {
+		self.0.retain(|run| run.start < run.end && !run.style.is_empty());
+		self.0.sort_by_key(|run| run.start);
+
+		let mut runs: Vec<TextRun> = Vec::with_capacity(self.0.len());
+		for run in self.0.drain(..) {
+			match runs.last_mut() {
+				Some(last) if last.end == run.start && last.style == run.style => last.end = run.end,
+				_ => runs.push(run),
+			}
+		}
+		self.0 = runs;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn sized(size: f64) -> TextStyle {
+		TextStyle {
+			font_size: Some(size),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn styling_a_range_splits_and_merges_runs() {
+		let mut runs = TextRuns::default();
+		runs.apply(0..10, &sized(12.));
+		runs.apply(
+			4..6,
+			&TextStyle {
+				weight: Some(700.),
+				..Default::default()
+			},
+		);
+
+		assert_eq!(runs.0.len(), 3);
+		assert_eq!(runs.0[1].start..runs.0[1].end, 4..6);
+		assert_eq!(runs.style_at(5).font_size, Some(12.));
+		assert_eq!(runs.style_at(5).weight, Some(700.));
+		assert_eq!(runs.common_style(0..10).font_size, Some(12.));
+		assert_eq!(runs.common_style(0..10).weight, None);
+
+		runs.clear(4..6);
+		runs.apply(4..6, &sized(12.));
+		assert_eq!(runs.0, vec![TextRun { start: 0, end: 10, style: sized(12.) }]);
+	}
+
+	#[test]
+	fn runs_follow_edits_to_the_text() {
+		let mut runs = TextRuns(vec![TextRun { start: 2, end: 5, style: sized(12.) }, TextRun { start: 8, end: 10, style: sized(20.) }]);
+
+		// Typing at the end of the first run extends it, and moves the second run along
+		runs.edited(5, 5, 3);
+		assert_eq!(runs.0[0].start..runs.0[0].end, 2..8);
+		assert_eq!(runs.0[1].start..runs.0[1].end, 11..13);
+
+		// Deleting text across the boundary of the second run shortens it
+		runs.edited(9, 12, 0);
+		assert_eq!(runs.0[1].start..runs.0[1].end, 9..10);
+	}
+}
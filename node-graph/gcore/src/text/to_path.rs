@@ -1,24 +1,28 @@
+use super::{optical_reference_gap, Font, GlyphProfile, KerningMode, KerningPair};
 use crate::uuid::ManipulatorGroupId;
 
 use bezier_rs::{ManipulatorGroup, Subpath};
+use dyn_any::{DynAny, StaticType};
 
+use core::ops::Range;
 use glam::DVec2;
 use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder};
-use rustybuzz::{GlyphBuffer, UnicodeBuffer};
+use rustybuzz::{script, Direction, UnicodeBuffer};
 
 struct Builder {
 	current_subpath: Subpath<ManipulatorGroupId>,
 	other_subpaths: Vec<Subpath<ManipulatorGroupId>>,
 	pos: DVec2,
 	offset: DVec2,
-	ascender: f64,
+	/// The distance from the top of the line down to its baseline.
+	baseline: f64,
 	scale: f64,
 	id: ManipulatorGroupId,
 }
 
 impl Builder {
 	fn point(&self, x: f32, y: f32) -> DVec2 {
-		self.pos + self.offset + DVec2::new(x as f64, self.ascender - y as f64) * self.scale
+		self.pos + self.offset + DVec2::new(x as f64 * self.scale, self.baseline - y as f64 * self.scale)
 	}
 }
 
@@ -53,125 +57,834 @@ impl OutlineBuilder for Builder {
 	}
 }
 
-fn font_properties(buzz_face: &rustybuzz::Face, font_size: f64) -> (f64, f64, UnicodeBuffer) {
-	let scale = (buzz_face.units_per_em() as f64).recip() * font_size;
-	let line_height = font_size;
-	let buffer = UnicodeBuffer::new();
-	(scale, line_height, buffer)
+/// How the lines of a paragraph are placed across the width of the text.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum TextAlign {
+	/// Aligns the lines to the side the paragraph's script starts from, which is the left for most scripts and the right for scripts like Arabic and Hebrew.
+	#[default]
+	Start,
+	Center,
+	/// Aligns the lines to the side the paragraph's script ends on.
+	End,
+	/// Stretches the spaces between the words so each line fills the width, except for the last line of each paragraph.
+	Justify,
 }
 
-fn push_str(buffer: &mut UnicodeBuffer, word: &str, trailing_space: bool) {
-	buffer.push_str(word);
+impl core::fmt::Display for TextAlign {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextAlign::Start => write!(f, "Start"),
+			TextAlign::Center => write!(f, "Center"),
+			TextAlign::End => write!(f, "End"),
+			TextAlign::Justify => write!(f, "Justify"),
+		}
+	}
+}
 
-	if trailing_space {
-		buffer.push_str(" ");
+impl TextAlign {
+	pub fn list() -> [TextAlign; 4] {
+		[TextAlign::Start, TextAlign::Center, TextAlign::End, TextAlign::Justify]
 	}
 }
 
-fn wrap_word(line_width: Option<f64>, glyph_buffer: &GlyphBuffer, scale: f64, x_pos: f64) -> bool {
-	if let Some(line_width) = line_width {
-		let word_length: i32 = glyph_buffer.glyph_positions().iter().map(|pos| pos.x_advance).sum();
-		let scaled_word_length = word_length as f64 * scale;
+/// How the text box adapts when the text doesn't fit within its height.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, Default)]
+pub enum TextAutoResize {
+	/// Keeps the height of the text box, leaving out the lines below it and marking the text as overflowing.
+	#[default]
+	Fixed,
+	/// Extends the text box downward to fit all of the lines.
+	GrowHeight,
+	/// Reduces the font size until all of the lines fit within the text box.
+	ShrinkToFit,
+}
 
-		if scaled_word_length + x_pos > line_width {
-			return true;
+impl core::fmt::Display for TextAutoResize {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TextAutoResize::Fixed => write!(f, "Fixed"),
+			TextAutoResize::GrowHeight => write!(f, "Grow Height"),
+			TextAutoResize::ShrinkToFit => write!(f, "Shrink to Fit"),
 		}
 	}
-	false
 }
 
-pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, line_width: Option<f64>) -> Vec<Subpath<ManipulatorGroupId>> {
-	let buzz_face = match buzz_face {
-		Some(face) => face,
-		// Show blank layer if font has not loaded
-		None => return vec![],
+impl TextAutoResize {
+	pub fn list() -> [TextAutoResize; 3] {
+		[TextAutoResize::Fixed, TextAutoResize::GrowHeight, TextAutoResize::ShrinkToFit]
+	}
+}
+
+/// The smallest fraction of its font size that text is shrunk to when fitting it within its text box.
+const MIN_SHRINK_SCALE: f64 = 0.05;
+
+/// The size and arrangement of the lines that text is laid out in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypesettingConfig {
+	pub font_size: f64,
+	/// The distance from the top of one line to the next, as a multiple of the font size.
+	pub line_height_ratio: f64,
+	/// The width of the text box that words are wrapped within, or `None` to only break lines at new lines.
+	pub max_width: Option<f64>,
+	/// The height of the text box, below which lines are left out and the text is marked as overflowing.
+	pub max_height: Option<f64>,
+	pub align: TextAlign,
+	pub auto_resize: TextAutoResize,
+	/// How the space between pairs of letters is adjusted, on top of which the manual kerning of pairs given to the layout is added.
+	pub kerning: KerningMode,
+}
+
+impl Default for TypesettingConfig {
+	fn default() -> Self {
+		Self {
+			font_size: 24.,
+			line_height_ratio: 1.,
+			max_width: None,
+			max_height: None,
+			align: TextAlign::Start,
+			auto_resize: TextAutoResize::Fixed,
+			kerning: KerningMode::Metrics,
+		}
+	}
+}
+
+impl TypesettingConfig {
+	pub fn new(font_size: f64) -> Self {
+		Self { font_size, ..Default::default() }
+	}
+
+	fn line_height(&self) -> f64 {
+		self.font_size * self.line_height_ratio
+	}
+}
+
+/// A range of the text that's shaped with one of the font faces given to its layout, for text with runs in different fonts and sizes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapingRun {
+	/// The byte indices of the range in the text.
+	pub range: Range<usize>,
+	/// The index of the font face in the faces given to the layout.
+	pub face: usize,
+	/// The font size of the range as a multiple of the font size of the text, so it shrinks along with the rest of the text when the text shrinks to fit its text box.
+	pub size_ratio: f64,
+	/// Space added after each character of the range, in thousandths of the em.
+	pub tracking: f64,
+}
+
+impl ShapingRun {
+	/// A run covering the whole text in the first font face at the font size of the text.
+	pub fn plain(text: &str) -> Self {
+		Self {
+			range: 0..text.len(),
+			face: 0,
+			size_ratio: 1.,
+			tracking: 0.,
+		}
+	}
+}
+
+/// A glyph placed by the layout of the text, relative to the top left corner of the text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PlacedGlyph {
+	id: GlyphId,
+	/// The position of the glyph's origin on the top of its line.
+	position: DVec2,
+	/// The offset of the glyph's outline from its origin, such as for a combining mark.
+	offset: DVec2,
+	advance: DVec2,
+	/// The index of the shaping run the glyph was shaped in.
+	run: usize,
+	/// The byte index in the text of the start of the characters the glyph was shaped from.
+	cluster: usize,
+}
+
+/// A word (with the space following it) shaped into glyphs, which are in the order they're drawn from left to right even in right-to-left scripts.
+/// Each glyph has its ID, offset, advance, the index of the shaping run it was shaped in, and the byte index of its characters relative to the start of the word.
+#[derive(Debug, Clone, PartialEq)]
+struct ShapedWord {
+	glyphs: Vec<(GlyphId, DVec2, DVec2, usize, usize)>,
+	/// The byte index of the word in the text, which is updated when a word shaped by an earlier layout is reused after an edit moved it.
+	start: usize,
+	width: f64,
+	/// The width of the space following the word, which hangs past the edge of the line when the word ends it.
+	trailing_space: f64,
+	right_to_left: bool,
+	/// Whether the word's script sets the direction of its paragraph, which words of digits and punctuation don't.
+	strong: bool,
+}
+
+/// What the words of a text are shaped with.
+struct WordShaping<'a, 'f> {
+	faces: &'a [&'a rustybuzz::Face<'f>],
+	runs: &'a [ShapingRun],
+	/// The manual kerning of pairs of characters, which is added to the kerning of the kerning mode.
+	pairs: &'a [KerningPair],
+	font_size: f64,
+	kerning: KerningMode,
+}
+
+/// Shapes the word with rustybuzz, which applies the font's ligatures, kerning, and contextual forms for the word's script.
+/// The parts of the word in different shaping runs are shaped separately, each with the font face, size, and tracking of its run.
+fn shape_word(shaping: &WordShaping, buffer: UnicodeBuffer, word: &str, word_start: usize, trailing_space: bool) -> (ShapedWord, UnicodeBuffer) {
+	let WordShaping { faces, runs, font_size, .. } = *shaping;
+	let features = shaping.kerning.features();
+	let word_end = word_start + word.len();
+	let mut pieces = runs
+		.iter()
+		.enumerate()
+		.filter(|(_, run)| run.range.start < word_end && word_start < run.range.end)
+		.map(|(index, run)| (index, run.range.start.max(word_start)..run.range.end.min(word_end)))
+		.collect::<Vec<_>>();
+	// An empty word still needs its trailing space shaped, in the run it falls within
+	if pieces.is_empty() {
+		let index = runs
+			.iter()
+			.position(|run| run.range.start <= word_start && word_start < run.range.end)
+			.unwrap_or(runs.len().saturating_sub(1));
+		pieces.push((index, word_start..word_start));
+	}
+
+	let mut buffer = buffer;
+	let mut shaped = ShapedWord {
+		glyphs: Vec::new(),
+		start: word_start,
+		width: 0.,
+		trailing_space: 0.,
+		right_to_left: false,
+		strong: false,
 	};
+	let last_piece = pieces.len() - 1;
+	for (piece_index, (run_index, range)) in pieces.into_iter().enumerate() {
+		let run = runs.get(run_index).cloned().unwrap_or_else(|| ShapingRun::plain(word));
+		let Some(buzz_face) = faces.get(run.face).or(faces.first()) else { break };
+		let scale = (buzz_face.units_per_em() as f64).recip() * font_size * run.size_ratio;
+
+		let with_space = trailing_space && piece_index == last_piece;
+		let piece_start = range.start - word_start;
+		let piece = &word[piece_start..range.end - word_start];
+		// The space is pushed along with the piece so the clusters of its glyphs count from the start of the piece
+		if with_space {
+			buffer.push_str(&[piece, " "].concat());
+		} else {
+			buffer.push_str(piece);
+		}
+		buffer.guess_segment_properties();
+		let right_to_left = buffer.direction() == Direction::RightToLeft;
+		if piece_index == 0 {
+			shaped.right_to_left = right_to_left;
+		}
+		shaped.strong |= ![script::COMMON, script::INHERITED, script::UNKNOWN].contains(&buffer.script());
+
+		let glyph_buffer = rustybuzz::shape(buzz_face, &features, buffer);
+		let mut glyphs = glyph_buffer
+			.glyph_positions()
+			.iter()
+			.zip(glyph_buffer.glyph_infos())
+			.map(|(position, info)| {
+				let offset = DVec2::new(position.x_offset as f64, position.y_offset as f64) * scale;
+				let advance = DVec2::new(position.x_advance as f64, position.y_advance as f64) * scale;
+				(GlyphId(info.glyph_id as u16), offset, advance, run_index, piece_start + info.cluster as usize)
+			})
+			.collect::<Vec<_>>();
+		// Tracking is added after each glyph that advances, so the marks placed over a letter stay with it
+		if run.tracking != 0. {
+			let tracking = run.tracking / 1000. * font_size * run.size_ratio;
+			glyphs.iter_mut().filter(|glyph| glyph.2.x != 0.).for_each(|glyph| glyph.2.x += tracking);
+		}
+		if shaping.kerning == KerningMode::Optical {
+			optical_kerning(buzz_face, &mut glyphs, scale);
+		}
+		shaped.width += glyphs.iter().map(|glyph| glyph.2.x).sum::<f64>();
+		if with_space {
+			// The space is the last glyph in the logical order of the word, which is the first one drawn in right-to-left scripts
+			let space_glyph = if right_to_left { glyphs.first() } else { glyphs.last() };
+			shaped.trailing_space = space_glyph.map_or(0., |glyph| glyph.2.x);
+		}
+
+		// The pieces of right-to-left words are drawn from the last one to the first
+		if shaped.right_to_left {
+			shaped.glyphs.splice(0..0, glyphs);
+		} else {
+			shaped.glyphs.extend(glyphs);
+		}
+		buffer = glyph_buffer.clear();
+	}
 
-	let (scale, line_height, mut buffer) = font_properties(&buzz_face, font_size);
+	// Manual kerning widens or narrows the space before the second character of each pair within the word
+	for pair in shaping.pairs.iter().filter(|pair| word_start < pair.index && pair.index < word_end) {
+		let cluster = pair.index - word_start;
+		// The space before a character is after the glyph to its left, which is the glyph of the previous character unless the word is right-to-left
+		let glyph = match shaped.right_to_left {
+			true => shaped.glyphs.iter().rposition(|glyph| glyph.4 == cluster),
+			false => shaped.glyphs.iter().position(|glyph| glyph.4 == cluster).and_then(|index| index.checked_sub(1)),
+		};
+		let Some(glyph) = glyph.map(|index| &mut shaped.glyphs[index]) else { continue };
+		let adjustment = pair.adjustment / 1000. * font_size * runs.get(glyph.3).map_or(1., |run| run.size_ratio);
+		glyph.2.x += adjustment;
+		shaped.width += adjustment;
+	}
+
+	(shaped, buffer)
+}
 
-	let mut builder = Builder {
-		current_subpath: Subpath::new(Vec::new(), false),
-		other_subpaths: Vec::new(),
-		pos: DVec2::ZERO,
-		offset: DVec2::ZERO,
-		ascender: (buzz_face.ascender() as f64 / buzz_face.height() as f64) * font_size / scale,
-		scale,
-		id: ManipulatorGroupId::ZERO,
+/// Evens out the space between the glyphs shaped with the font face by measuring the shapes of their sides, adjusting the advances of the glyphs.
+/// The adjustment of each pair goes after the last glyph before the second glyph of the pair, so marks placed over the first glyph stay with it.
+fn optical_kerning(buzz_face: &rustybuzz::Face, glyphs: &mut [(GlyphId, DVec2, DVec2, usize, usize)], scale: f64) {
+	let Some(reference_gap) = optical_reference_gap(buzz_face) else { return };
+	let units_per_em = buzz_face.units_per_em() as f64;
+
+	let advancing = glyphs.iter().enumerate().filter(|(_, glyph)| glyph.2.x != 0.).map(|(index, _)| index).collect::<Vec<_>>();
+	let profiles = advancing.iter().map(|&index| GlyphProfile::new(buzz_face, glyphs[index].0)).collect::<Vec<_>>();
+	for (indices, pair) in advancing.windows(2).zip(profiles.windows(2)) {
+		if let [Some(left), Some(right)] = pair {
+			glyphs[indices[1] - 1].2.x += left.optical_adjustment(right, reference_gap, units_per_em) * scale;
+		}
+	}
+}
+
+/// Splits a word wider than the line into pieces which each fit on a line, so it can be broken across lines.
+fn split_word(word: ShapedWord, line_width: Option<f64>) -> Vec<ShapedWord> {
+	let Some(line_width) = line_width.filter(|line_width| word.width - word.trailing_space > *line_width) else {
+		return vec![word];
 	};
 
-	for line in str.split('\n') {
-		let length = line.split(' ').count();
-		for (index, word) in line.split(' ').enumerate() {
-			push_str(&mut buffer, word, index != length - 1);
-			let glyph_buffer = rustybuzz::shape(&buzz_face, &[], buffer);
+	let mut pieces = Vec::new();
+	let mut glyphs = Vec::new();
+	let mut width = 0.;
+	for glyph in word.glyphs {
+		if !glyphs.is_empty() && width + glyph.2.x >= line_width {
+			pieces.push(ShapedWord {
+				glyphs: core::mem::take(&mut glyphs),
+				width,
+				trailing_space: 0.,
+				..word
+			});
+			width = 0.;
+		}
+		width += glyph.2.x;
+		glyphs.push(glyph);
+	}
+	pieces.push(ShapedWord { glyphs, width, ..word });
+	pieces
+}
 
-			if wrap_word(line_width, &glyph_buffer, scale, builder.pos.x) {
-				builder.pos = DVec2::new(0., builder.pos.y + line_height);
-			}
+/// A word of a paragraph shaped by an earlier layout, kept so it needn't be shaped again when the text is laid out after an edit.
+#[derive(Debug, Clone, PartialEq)]
+struct CachedWord {
+	text: String,
+	trailing_space: bool,
+	shaped: ShapedWord,
+}
+
+/// The words of a paragraph broken into lines, which are placed across the width of the text once every paragraph is broken into lines.
+struct ParagraphLines {
+	lines: Vec<Vec<ShapedWord>>,
+	right_to_left: bool,
+}
+
+/// The width of the words on the line, without the space that hangs past its end.
+fn visible_width(line: &[ShapedWord]) -> f64 {
+	line.iter().map(|word| word.width).sum::<f64>() - line.last().map_or(0., |word| word.trailing_space)
+}
+
+/// The glyphs of laid out text, along with its size and whether it overflows its text box.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLayout {
+	glyphs: Vec<PlacedGlyph>,
+	/// The size of the text box, or of the lines of text in the directions that the text box doesn't limit.
+	pub size: DVec2,
+	/// Whether lines of text were left out because they fall below the bottom of the text box.
+	pub overflows: bool,
+	/// The number of lines that are shown.
+	pub line_count: usize,
+	/// The font size the text was laid out at, which is smaller than the one asked for if the text was shrunk to fit its text box.
+	pub font_size: f64,
+	/// The ranges of the text shaped in different font faces and sizes.
+	runs: Vec<ShapingRun>,
+}
 
-			for (glyph_position, glyph_info) in glyph_buffer.glyph_positions().iter().zip(glyph_buffer.glyph_infos()) {
-				if let Some(line_width) = line_width {
-					if builder.pos.x + (glyph_position.x_advance as f64 * builder.scale) >= line_width {
-						builder.pos = DVec2::new(0., builder.pos.y + line_height);
-					}
+/// Keeps the words shaped by the last layout of a text, so laying it out again after an edit only shapes the words from the first change onward.
+/// Typing at the end of the text, the most common edit, then only reshapes the word being typed.
+#[derive(Debug, Clone, Default)]
+pub struct TextLayoutCache {
+	/// The font, its variation coordinates, size, width, and kerning of the last layout, which all change how each word is shaped or split.
+	key: Option<(Font, Vec<i16>, u64, Option<u64>, KerningMode)>,
+	paragraphs: Vec<Vec<CachedWord>>,
+}
+
+impl TextLayoutCache {
+	/// Lays out the text, reusing the words shaped by the last layout if it used the same font, variations, size, width, and kerning mode.
+	/// The manual kerning of pairs is left out, since the characters it's given for move as the text is edited.
+	pub fn layout(&mut self, text: &str, buzz_face: &rustybuzz::Face, font: &Font, config: TypesettingConfig) -> TextLayout {
+		let runs = [ShapingRun::plain(text)];
+		let config = resized_config(config, |config| TextLayoutCache::default().layout_shaped(text, &[buzz_face], &runs, &[], config));
+		let coordinates = buzz_face.variation_coordinates().iter().map(|coordinate| coordinate.get()).collect();
+		let key = (font.clone(), coordinates, config.font_size.to_bits(), config.max_width.map(f64::to_bits), config.kerning);
+		if self.key.as_ref() != Some(&key) {
+			self.key = Some(key);
+			self.paragraphs.clear();
+		}
+		self.layout_shaped(text, &[buzz_face], &runs, &[], config)
+	}
+
+	fn layout_shaped(&mut self, text: &str, faces: &[&rustybuzz::Face], runs: &[ShapingRun], pairs: &[KerningPair], config: TypesettingConfig) -> TextLayout {
+		let mut buffer = UnicodeBuffer::new();
+		let shaping = WordShaping {
+			faces,
+			runs,
+			pairs,
+			font_size: config.font_size,
+			kerning: config.kerning,
+		};
+
+		let mut paragraphs = Vec::new();
+		let mut word_start = 0;
+		for (paragraph_index, paragraph) in text.split('\n').enumerate() {
+			let cached = self.paragraphs.get(paragraph_index).map(Vec::as_slice).unwrap_or_default();
+			let length = paragraph.split(' ').count();
+
+			let mut still_matching = true;
+			let mut words = Vec::with_capacity(length);
+			for (index, word) in paragraph.split(' ').enumerate() {
+				let trailing_space = index != length - 1;
+				// Words are reused until the first one that differs, after which the rest of the paragraph is shaped again
+				still_matching &= cached.get(index).is_some_and(|cached| cached.text == word && cached.trailing_space == trailing_space);
+				let start = word_start;
+				// The next word starts after this one and the space or new line following it
+				word_start += word.len() + 1;
+				if still_matching {
+					let mut cached_word = cached[index].clone();
+					cached_word.shaped.start = start;
+					words.push(cached_word);
+					continue;
 				}
-				builder.offset = DVec2::new(glyph_position.x_offset as f64, glyph_position.y_offset as f64) * builder.scale;
-				buzz_face.outline_glyph(GlyphId(glyph_info.glyph_id as u16), &mut builder);
-				if !builder.current_subpath.is_empty() {
-					builder.other_subpaths.push(core::mem::replace(&mut builder.current_subpath, Subpath::new(Vec::new(), false)));
+
+				let (shaped, cleared) = shape_word(&shaping, buffer, word, start, trailing_space);
+				buffer = cleared;
+				words.push(CachedWord {
+					text: word.to_string(),
+					trailing_space,
+					shaped,
+				});
+			}
+			paragraphs.push(words);
+		}
+		self.paragraphs = paragraphs;
+
+		let paragraphs = self.paragraphs.iter().map(|words| break_lines(words, config.max_width)).collect::<Vec<_>>();
+		TextLayout {
+			runs: runs.to_vec(),
+			..place_lines(&paragraphs, config)
+		}
+	}
+}
+
+/// Breaks the words of a paragraph into lines, putting words on each line until the next one doesn't fit within the line width.
+fn break_lines(words: &[CachedWord], line_width: Option<f64>) -> ParagraphLines {
+	let right_to_left = words.iter().find(|word| word.shaped.strong).is_some_and(|word| word.shaped.right_to_left);
+
+	let mut lines = vec![Vec::new()];
+	let mut x = 0.;
+	for word in words.iter().flat_map(|word| split_word(word.shaped.clone(), line_width)) {
+		let current_line = lines.last_mut().unwrap();
+		// The space after the word may hang past the end of the line
+		if line_width.is_some_and(|line_width| !current_line.is_empty() && x + word.width - word.trailing_space > line_width) {
+			lines.push(Vec::new());
+			x = 0.;
+		}
+		x += word.width;
+		lines.last_mut().unwrap().push(word);
+	}
+
+	ParagraphLines { lines, right_to_left }
+}
+
+/// Places the lines of the paragraphs one after another down the text box, with each line aligned across its width.
+/// Lines of paragraphs in right-to-left scripts like Arabic and Hebrew have their words placed from right to left.
+fn place_lines(paragraphs: &[ParagraphLines], config: TypesettingConfig) -> TextLayout {
+	let line_height = config.line_height();
+	// Without a text box, lines are aligned within the width of the widest line
+	let width = config
+		.max_width
+		.unwrap_or_else(|| paragraphs.iter().flat_map(|paragraph| &paragraph.lines).map(|line| visible_width(line)).fold(0., f64::max));
+	let total_lines = paragraphs.iter().map(|paragraph| paragraph.lines.len()).sum::<usize>();
+	let shown_lines = match config.max_height {
+		Some(max_height) => ((max_height / line_height + 1e-9).floor().max(0.) as usize).min(total_lines),
+		None => total_lines,
+	};
+
+	let mut glyphs = Vec::new();
+	let lines = paragraphs.iter().flat_map(|paragraph| {
+		let last = paragraph.lines.len() - 1;
+		paragraph.lines.iter().enumerate().map(move |(index, line)| (line, paragraph.right_to_left, index == last))
+	});
+	for (line_index, (line, right_to_left, last_in_paragraph)) in lines.take(shown_lines).enumerate() {
+		let free_space = width - visible_width(line);
+		let justify = config.align == TextAlign::Justify && !last_in_paragraph && line.len() > 1;
+		let word_spacing = if justify { free_space / (line.len() - 1) as f64 } else { 0. };
+		let leading_space = match config.align {
+			TextAlign::Start | TextAlign::Justify => 0.,
+			TextAlign::Center => free_space / 2.,
+			TextAlign::End => free_space,
+		};
+
+		// Right-to-left lines start at the right edge of the text box, and place each word to the left of the one before
+		let y = line_index as f64 * line_height;
+		let mut x = if right_to_left { width - leading_space } else { leading_space };
+		for word in line {
+			if right_to_left {
+				x -= word.width;
+			}
+			let mut position = DVec2::new(x, y);
+			for &(id, offset, advance, run, cluster) in &word.glyphs {
+				glyphs.push(PlacedGlyph {
+					id,
+					position,
+					offset,
+					advance,
+					run,
+					cluster: word.start + cluster,
+				});
+				position += advance;
+			}
+			if right_to_left {
+				x -= word_spacing;
+			} else {
+				x += word.width + word_spacing;
+			}
+		}
+	}
+
+	let lines_height = shown_lines as f64 * line_height;
+	let height = match config.max_height {
+		Some(max_height) => max_height.max(lines_height),
+		None => lines_height,
+	};
+	TextLayout {
+		glyphs,
+		size: DVec2::new(width, height),
+		overflows: shown_lines < total_lines,
+		line_count: shown_lines,
+		font_size: config.font_size,
+		runs: Vec::new(),
+	}
+}
+
+/// Adapts the configuration to the text's auto-resize mode, so the text fits when it wouldn't otherwise fit the height of its text box.
+/// The text is laid out with the given function while trying out configurations.
+fn resized_config(config: TypesettingConfig, layout: impl Fn(TypesettingConfig) -> TextLayout) -> TypesettingConfig {
+	let Some(max_height) = config.max_height else { return config };
+	match config.auto_resize {
+		TextAutoResize::Fixed => config,
+		// The height of the text box then only sets its minimum height
+		TextAutoResize::GrowHeight => {
+			let lines_height = layout(TypesettingConfig { max_height: None, ..config }).size.y;
+			TypesettingConfig {
+				max_height: Some(max_height.max(lines_height)),
+				..config
+			}
+		}
+		TextAutoResize::ShrinkToFit => {
+			let fits = |scale: f64| {
+				let config = TypesettingConfig {
+					font_size: config.font_size * scale,
+					..config
+				};
+				!layout(config).overflows
+			};
+			if fits(1.) {
+				return config;
+			}
+
+			// Fewer lines fit as the font size grows, so the largest size that fits is found by bisection
+			let (mut fitting, mut overflowing) = (MIN_SHRINK_SCALE, 1.);
+			for _ in 0..12 {
+				let middle = (fitting + overflowing) / 2.;
+				if fits(middle) {
+					fitting = middle;
+				} else {
+					overflowing = middle;
 				}
+			}
+			TypesettingConfig {
+				font_size: config.font_size * fitting,
+				..config
+			}
+		}
+	}
+}
+
+/// Places the glyphs of the text, wrapping its words onto new lines once they would pass the width of the text box.
+pub fn layout_text(text: &str, buzz_face: &rustybuzz::Face, config: TypesettingConfig) -> TextLayout {
+	layout_rich_text(text, &[buzz_face], &[ShapingRun::plain(text)], &[], config)
+}
+
+/// Places the glyphs of text whose runs are shaped with different font faces, sizes, and tracking, wrapping its words onto new lines once they would pass the width of the text box.
+/// The lines are spaced by the font size of the text, and the baseline of each line is set by the first font face.
+/// The manual kerning of the pairs of characters is added to the kerning chosen by the configuration.
+pub fn layout_rich_text(text: &str, faces: &[&rustybuzz::Face], runs: &[ShapingRun], pairs: &[KerningPair], config: TypesettingConfig) -> TextLayout {
+	let config = resized_config(config, |config| TextLayoutCache::default().layout_shaped(text, faces, runs, pairs, config));
+	TextLayoutCache::default().layout_shaped(text, faces, runs, pairs, config)
+}
 
-				builder.pos += DVec2::new(glyph_position.x_advance as f64, glyph_position.y_advance as f64) * builder.scale;
+impl TextLayout {
+	/// Converts the laid out glyphs into their outlines.
+	pub fn to_path(&self, buzz_face: &rustybuzz::Face) -> Vec<Subpath<ManipulatorGroupId>> {
+		self.run_paths(&[buzz_face]).into_iter().map(|(_, subpath)| subpath).collect()
+	}
+
+	/// Converts the laid out glyphs into their outlines in the font faces of their shaping runs, each with the index of the run it belongs to.
+	pub fn run_paths(&self, faces: &[&rustybuzz::Face]) -> Vec<(usize, Subpath<ManipulatorGroupId>)> {
+		let Some(first_face) = faces.first() else { return Vec::new() };
+		let font_size = self.font_size;
+		let mut builder = Builder {
+			current_subpath: Subpath::new(Vec::new(), false),
+			other_subpaths: Vec::new(),
+			pos: DVec2::ZERO,
+			offset: DVec2::ZERO,
+			baseline: (first_face.ascender() as f64 / first_face.height() as f64) * font_size,
+			scale: 1.,
+			id: ManipulatorGroupId::ZERO,
+		};
+
+		let mut runs = Vec::new();
+		for glyph in &self.glyphs {
+			let (buzz_face, size_ratio) = match self.runs.get(glyph.run) {
+				Some(run) => (faces.get(run.face).unwrap_or(first_face), run.size_ratio),
+				None => (first_face, 1.),
+			};
+			builder.scale = (buzz_face.units_per_em() as f64).recip() * font_size * size_ratio;
+			builder.pos = glyph.position;
+			builder.offset = glyph.offset;
+			buzz_face.outline_glyph(glyph.id, &mut builder);
+			if !builder.current_subpath.is_empty() {
+				builder.other_subpaths.push(core::mem::replace(&mut builder.current_subpath, Subpath::new(Vec::new(), false)));
 			}
+			runs.resize(builder.other_subpaths.len(), glyph.run);
+		}
+		runs.into_iter().zip(builder.other_subpaths).collect()
+	}
 
-			buffer = glyph_buffer.clear();
+	/// The lines under the part of the text in the byte range, one for each line of text it's laid out on, placed just below the baseline set by the font face.
+	/// This is used to mark ranges of the text, such as misspelled words.
+	pub fn underlines(&self, range: Range<usize>, buzz_face: &rustybuzz::Face) -> Vec<[DVec2; 2]> {
+		let baseline = (buzz_face.ascender() as f64 / buzz_face.height() as f64) * self.font_size;
+		let offset = DVec2::new(0., baseline + self.font_size * 0.1);
+		self.range_spans(range).into_iter().map(|[start, end]| [start + offset, end + offset]).collect()
+	}
+
+	/// The horizontal spans of the glyphs in the byte range along the top of each line they're on.
+	fn range_spans(&self, range: Range<usize>) -> Vec<[DVec2; 2]> {
+		let mut spans: Vec<[DVec2; 2]> = Vec::new();
+		for glyph in self.glyphs.iter().filter(|glyph| range.contains(&glyph.cluster)) {
+			let (start, end) = (glyph.position.x, glyph.position.x + glyph.advance.x);
+			match spans.last_mut() {
+				// The glyphs of right-to-left words on the line aren't in order, so the span covers the furthest glyphs on either side
+				Some(span) if span[0].y == glyph.position.y => {
+					span[0].x = span[0].x.min(start);
+					span[1].x = span[1].x.max(end);
+				}
+				_ => spans.push([DVec2::new(start, glyph.position.y), DVec2::new(end, glyph.position.y)]),
+			}
 		}
-		builder.pos = DVec2::new(0., builder.pos.y + line_height);
+		spans
 	}
-	builder.other_subpaths
 }
 
-pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, line_width: Option<f64>) -> DVec2 {
+/// Converts the text into the outlines of its glyphs, shaped and laid out with the font.
+pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, config: TypesettingConfig) -> Vec<Subpath<ManipulatorGroupId>> {
+	let buzz_face = match buzz_face {
+		Some(face) => face,
+		// Show blank layer if font has not loaded
+		None => return vec![],
+	};
+
+	layout_text(str, &buzz_face, config).to_path(&buzz_face)
+}
+
+/// The size of the text box that the text is laid out in.
+pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, config: TypesettingConfig) -> DVec2 {
 	let buzz_face = match buzz_face {
 		Some(face) => face,
 		// Show blank layer if font has not loaded
 		None => return DVec2::ZERO,
 	};
 
-	let (scale, line_height, mut buffer) = font_properties(&buzz_face, font_size);
+	layout_text(str, &buzz_face, config).size
+}
 
-	let mut pos = DVec2::ZERO;
-	let mut bounds = DVec2::ZERO;
+/// Loads the first face of a font file, or nothing if the file isn't a valid OpenType or TrueType font.
+pub fn load_face(data: &[u8]) -> Option<rustybuzz::Face> {
+	rustybuzz::Face::from_slice(data, 0)
+}
 
-	for line in str.split('\n') {
-		let length = line.split(' ').count();
-		for (index, word) in line.split(' ').enumerate() {
-			push_str(&mut buffer, word, index != length - 1);
+#[cfg(test)]
+mod test {
+	use super::*;
 
-			let glyph_buffer = rustybuzz::shape(&buzz_face, &[], buffer);
+	#[test]
+	fn invalid_font_data_is_not_loaded() {
+		assert!(load_face(b"not a font").is_none());
+		assert!(to_path("Text", load_face(&[]), TypesettingConfig::default()).is_empty());
+		assert_eq!(bounding_box("Text", None, TypesettingConfig::default()), DVec2::ZERO);
+	}
 
-			if wrap_word(line_width, &glyph_buffer, scale, pos.x) {
-				pos = DVec2::new(0., pos.y + line_height);
-			}
+	/// A word of single glyphs which are each 10 units wide, followed by a 5 unit wide space unless it ends its paragraph.
+	fn word(letters: usize, trailing_space: bool) -> CachedWord {
+		let mut glyphs = (0..letters).map(|letter| (GlyphId(1), DVec2::ZERO, DVec2::new(10., 0.), 0, letter)).collect::<Vec<_>>();
+		if trailing_space {
+			glyphs.push((GlyphId(2), DVec2::ZERO, DVec2::new(5., 0.), 0, letters));
+		}
+		let shaped = ShapedWord {
+			width: glyphs.iter().map(|glyph| glyph.2.x).sum(),
+			glyphs,
+			start: 0,
+			trailing_space: if trailing_space { 5. } else { 0. },
+			right_to_left: false,
+			strong: true,
+		};
+		CachedWord {
+			text: "x".repeat(letters),
+			trailing_space,
+			shaped,
+		}
+	}
 
-			for glyph_position in glyph_buffer.glyph_positions() {
-				if let Some(line_width) = line_width {
-					if pos.x + (glyph_position.x_advance as f64 * scale) >= line_width {
-						pos = DVec2::new(0., pos.y + line_height);
-					}
-				}
-				pos += DVec2::new(glyph_position.x_advance as f64, glyph_position.y_advance as f64) * scale;
+	fn line_starts(layout: &TextLayout) -> Vec<DVec2> {
+		let mut starts: Vec<DVec2> = Vec::new();
+		for glyph in &layout.glyphs {
+			if starts.last().map_or(true, |start| start.y != glyph.position.y) {
+				starts.push(glyph.position);
 			}
-			bounds = bounds.max(pos + DVec2::new(0., line_height));
-
-			buffer = glyph_buffer.clear();
 		}
-		pos = DVec2::new(0., pos.y + line_height);
+		starts
 	}
 
-	bounds
-}
+	#[test]
+	fn wrap_words_with_hanging_spaces() {
+		// The space after the second word hangs past the 75 unit wide line, so both words fit
+		let words = [word(3, true), word(4, true), word(2, false)];
+		let paragraph = break_lines(&words, Some(75.));
+		assert_eq!(paragraph.lines.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1]);
 
-pub fn load_face(data: &[u8]) -> rustybuzz::Face {
-	rustybuzz::Face::from_slice(data, 0).expect("Loading font failed")
+		// A word wider than the line is broken into pieces
+		let paragraph = break_lines(&[word(10, false)], Some(35.));
+		assert_eq!(paragraph.lines.iter().map(|line| visible_width(line)).collect::<Vec<_>>(), vec![30., 30., 30., 10.]);
+	}
+
+	#[test]
+	fn align_lines_in_the_text_box() {
+		let words = [word(3, true), word(4, true), word(2, false)];
+		let config = |align| TypesettingConfig {
+			font_size: 10.,
+			line_height_ratio: 1.5,
+			max_width: Some(90.),
+			align,
+			..Default::default()
+		};
+		let layout = |align| place_lines(&[break_lines(&words, Some(90.))], config(align));
+
+		// The first line holds the first two words (75 units without the hanging space), and the last line holds the third word (20 units)
+		assert_eq!(line_starts(&layout(TextAlign::Start)), vec![DVec2::new(0., 0.), DVec2::new(0., 15.)]);
+		assert_eq!(line_starts(&layout(TextAlign::Center)), vec![DVec2::new(7.5, 0.), DVec2::new(35., 15.)]);
+		assert_eq!(line_starts(&layout(TextAlign::End)), vec![DVec2::new(15., 0.), DVec2::new(70., 15.)]);
+
+		// Justified lines stretch the space between their words to fill the width, except for the last line of the paragraph
+		let justified = layout(TextAlign::Justify);
+		assert_eq!(justified.glyphs[4].position, DVec2::new(50., 0.));
+		assert_eq!(line_starts(&justified)[1], DVec2::new(0., 15.));
+		assert_eq!(justified.size, DVec2::new(90., 30.));
+	}
+
+	#[test]
+	fn underline_ranges_across_lines() {
+		// The words "xxx xxxx xx" with the first two on the first line
+		let mut words = [word(3, true), word(4, true), word(2, false)];
+		words[1].shaped.start = 4;
+		words[2].shaped.start = 9;
+		let config = TypesettingConfig {
+			font_size: 10.,
+			max_width: Some(90.),
+			..Default::default()
+		};
+		let layout = place_lines(&[break_lines(&words, Some(90.))], config);
+
+		assert_eq!(layout.range_spans(4..8), vec![[DVec2::new(35., 0.), DVec2::new(75., 0.)]]);
+		assert_eq!(layout.range_spans(6..11), vec![[DVec2::new(55., 0.), DVec2::new(80., 0.)], [DVec2::new(0., 10.), DVec2::new(20., 10.)]]);
+	}
+
+	#[test]
+	fn leave_out_lines_below_the_text_box() {
+		let paragraphs = [break_lines(&[word(3, false)], None), break_lines(&[word(3, false)], None), break_lines(&[word(3, false)], None)];
+		let config = TypesettingConfig {
+			font_size: 10.,
+			max_height: Some(25.),
+			..Default::default()
+		};
+
+		let layout = place_lines(&paragraphs, config);
+		assert!(layout.overflows);
+		assert_eq!(layout.line_count, 2);
+		assert_eq!(layout.size, DVec2::new(30., 25.));
+		assert!(!place_lines(&paragraphs, TypesettingConfig { max_height: None, ..config }).overflows);
+	}
+
+	#[test]
+	fn auto_resize_text_that_overflows() {
+		// Four words of three glyphs that each take a line of their own in the 50 unit wide text box at a font size of 10
+		let layout = |config: TypesettingConfig| {
+			let scale = config.font_size / 10.;
+			let words = (0..4)
+				.map(|index| {
+					let mut word = word(3, index != 3);
+					word.shaped.glyphs.iter_mut().for_each(|glyph| glyph.2 *= scale);
+					word.shaped.width *= scale;
+					word.shaped.trailing_space *= scale;
+					word
+				})
+				.collect::<Vec<_>>();
+			place_lines(&[break_lines(&words, config.max_width)], config)
+		};
+		let config = TypesettingConfig {
+			font_size: 10.,
+			max_width: Some(50.),
+			max_height: Some(25.),
+			..Default::default()
+		};
+		assert!(layout(config).overflows);
+
+		// Growing the height fits all four lines
+		let grown = resized_config(
+			TypesettingConfig {
+				auto_resize: TextAutoResize::GrowHeight,
+				..config
+			},
+			layout,
+		);
+		assert_eq!(grown.max_height, Some(40.));
+		assert!(!layout(grown).overflows);
+
+		// Shrinking puts two words on each of two lines, which fit the width at up to 50 / 65 of the font size
+		let shrunk = resized_config(
+			TypesettingConfig {
+				auto_resize: TextAutoResize::ShrinkToFit,
+				..config
+			},
+			layout,
+		);
+		assert!((shrunk.font_size - 10. * 50. / 65.).abs() < 0.01);
+		assert!(!layout(shrunk).overflows);
+
+		// Text that already fits is left as it is
+		let roomy = TypesettingConfig {
+			max_height: Some(100.),
+			auto_resize: TextAutoResize::ShrinkToFit,
+			..config
+		};
+		assert_eq!(resized_config(roomy, layout).font_size, 10.);
+	}
 }
@@ -0,0 +1,137 @@
+use dyn_any::{DynAny, StaticType};
+
+use rustybuzz::ttf_parser::Tag;
+
+/// The position of a variable font along one of its axes of variation, such as its weight.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct FontVariation {
+	/// The four letter tag of the axis, like `wght` for weight or `wdth` for width.
+	pub axis: String,
+	pub value: f64,
+}
+
+/// The positions along the axes of a variable font that its glyph outlines are instantiated at.
+/// Axes without a position, or which the font doesn't have, stay at the font's default.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub struct FontVariations(pub Vec<FontVariation>);
+
+impl core::hash::Hash for FontVariations {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.0.len().hash(state);
+		for variation in &self.0 {
+			variation.axis.hash(state);
+			variation.value.to_bits().hash(state);
+		}
+	}
+}
+
+impl FontVariations {
+	/// The position along the axis with the given tag, if one is set.
+	pub fn get(&self, axis: &str) -> Option<f64> {
+		self.0.iter().find(|variation| variation.axis == axis).map(|variation| variation.value)
+	}
+
+	/// Sets the position along the axis with the given tag, replacing any previous position.
+	pub fn with(&self, axis: &str, value: f64) -> Self {
+		let mut variations = self.clone();
+		match variations.0.iter_mut().find(|variation| variation.axis == axis) {
+			Some(variation) => variation.value = value,
+			None => variations.0.push(FontVariation { axis: axis.to_string(), value }),
+		}
+		variations
+	}
+
+	/// Blends the positions along each axis, for animating between two sets of variations.
+	/// Axes only set in one of them keep the position they have there.
+	pub fn lerp(&self, other: &Self, t: f64) -> Self {
+		let mut variations = self.clone();
+		for variation in &other.0 {
+			let value = match self.get(&variation.axis) {
+				Some(from) => from + (variation.value - from) * t,
+				None => variation.value,
+			};
+			variations = variations.with(&variation.axis, value);
+		}
+		variations
+	}
+
+	/// Instantiates the font at these positions, so its shaping and glyph outlines follow them.
+	pub fn apply(&self, buzz_face: &mut rustybuzz::Face) {
+		let axes = buzz_face.variation_axes();
+		let variations = self
+			.0
+			.iter()
+			.map(|variation| rustybuzz::Variation {
+				tag: Tag::from_bytes_lossy(variation.axis.as_bytes()),
+				value: variation.value as f32,
+			})
+			.filter(|variation| axes.into_iter().any(|axis| axis.tag == variation.tag))
+			.collect::<Vec<_>>();
+
+		if !variations.is_empty() {
+			buzz_face.set_variations(&variations);
+		}
+	}
+}
+
+/// Loads the font from its file, instantiated at the given positions along its axes of variation.
+pub fn load_face_with_variations<'a>(data: &'a [u8], variations: &FontVariations) -> Option<rustybuzz::Face<'a>> {
+	let mut buzz_face = super::load_face(data)?;
+	variations.apply(&mut buzz_face);
+	Some(buzz_face)
+}
+
+/// An axis that a variable font can be varied along, with the range of positions it supports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontAxis {
+	pub tag: String,
+	pub name: String,
+	pub min: f64,
+	pub default: f64,
+	pub max: f64,
+}
+
+/// The axes of variation of the font, which are empty if it isn't a variable font. Axes that the font hides from users are left out.
+pub fn font_axes(buzz_face: &rustybuzz::Face) -> Vec<FontAxis> {
+	buzz_face
+		.variation_axes()
+		.into_iter()
+		.filter(|axis| !axis.hidden)
+		.map(|axis| {
+			let tag = axis.tag.to_string();
+			// The axes registered by the OpenType specification have well-known names, while custom axes are named by their tag
+			let name = match tag.as_str() {
+				"wght" => "Weight".to_string(),
+				"wdth" => "Width".to_string(),
+				"slnt" => "Slant".to_string(),
+				"ital" => "Italic".to_string(),
+				"opsz" => "Optical Size".to_string(),
+				_ => tag.clone(),
+			};
+			FontAxis {
+				tag,
+				name,
+				min: axis.min_value as f64,
+				default: axis.def_value as f64,
+				max: axis.max_value as f64,
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn blend_variations_between_keyframes() {
+		let light = FontVariations::default().with("wght", 300.).with("wdth", 75.);
+		let bold = FontVariations::default().with("wght", 700.).with("slnt", -10.);
+
+		let halfway = light.lerp(&bold, 0.5);
+		assert_eq!(halfway.get("wght"), Some(500.));
+		assert_eq!(halfway.get("wdth"), Some(75.));
+		assert_eq!(halfway.get("slnt"), Some(-10.));
+		assert_eq!(light.with("wght", 400.).0.len(), 2);
+	}
+}
@@ -78,6 +78,7 @@ impl Transform for GraphicElement {
 			GraphicElement::Text(_) => todo!("Transform of text"),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.transform(),
 			GraphicElement::Artboard(artboard) => artboard.transform(),
+			GraphicElement::Dimension(dimension) => dimension.transform,
 		}
 	}
 	fn local_pivot(&self, pivot: DVec2) -> DVec2 {
@@ -87,6 +88,7 @@ impl Transform for GraphicElement {
 			GraphicElement::Text(_) => todo!("Transform of text"),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.local_pivot(pivot),
 			GraphicElement::Artboard(artboard) => artboard.local_pivot(pivot),
+			GraphicElement::Dimension(dimension) => dimension.transform.transform_point2(pivot),
 		}
 	}
 	fn decompose_scale(&self) -> DVec2 {
@@ -96,6 +98,7 @@ impl Transform for GraphicElement {
 			GraphicElement::Text(_) => todo!("Transform of text"),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.decompose_scale(),
 			GraphicElement::Artboard(artboard) => artboard.decompose_scale(),
+			GraphicElement::Dimension(dimension) => DVec2::new(dimension.transform.transform_vector2(DVec2::X).length(), dimension.transform.transform_vector2(DVec2::Y).length()),
 		}
 	}
 }
@@ -107,6 +110,7 @@ impl TransformMut for GraphicElement {
 			GraphicElement::Text(_) => todo!("Transform of text"),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.transform_mut(),
 			GraphicElement::Artboard(_) => todo!("Transform of artboard"),
+			GraphicElement::Dimension(dimension) => &mut dimension.transform,
 		}
 	}
 }
@@ -169,6 +173,28 @@ pub enum RenderQuality {
 	/// Render at full quality
 	Full,
 }
+
+impl RenderQuality {
+	/// The fraction of the full resolution which a raster rendered at this quality needs to have.
+	pub fn scale(&self) -> f64 {
+		match *self {
+			Self::Preview => 0.5,
+			Self::Scale(scale) => (scale as f64).clamp(0., 1.),
+			Self::Probabilty(_) | Self::Full => 1.,
+		}
+	}
+}
+
+/// What the graph is being rendered for, which decides the resolution that nodes baking rasters need to render at.
+#[derive(Debug, Default, Clone, Copy, dyn_any::DynAny, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RenderTarget {
+	/// Drawing the viewport, which may be rendered at a reduced quality and is redrawn whenever the view changes
+	#[default]
+	Preview,
+	/// Exporting a file, which is always rendered at the exact resolution of the output
+	Export,
+}
 #[derive(Debug, Clone, Copy, dyn_any::DynAny, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Footprint {
@@ -180,6 +206,9 @@ pub struct Footprint {
 	pub quality: RenderQuality,
 	/// When the transform is set downstream, all upsream modifications have to be ignored
 	pub ignore_modifications: bool,
+	/// Whether the render is a preview in the viewport or an export
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub target: RenderTarget,
 }
 
 impl Default for Footprint {
@@ -189,6 +218,7 @@ impl Default for Footprint {
 			resolution: glam::UVec2::new(1920, 1080),
 			quality: RenderQuality::Full,
 			ignore_modifications: false,
+			target: RenderTarget::Preview,
 		}
 	}
 }
@@ -200,6 +230,23 @@ impl Footprint {
 		let end = inverse.transform_point2(self.resolution.as_dvec2());
 		AxisAlignedBbox { start, end }
 	}
+
+	pub fn is_export(&self) -> bool {
+		self.target == RenderTarget::Export
+	}
+
+	/// The size in pixels of a raster baked to cover an area of the given size in local space.
+	/// Previews are reduced according to the render quality, while exports always get the exact pixel size of the area in the output.
+	pub fn raster_resolution(&self, local_size: DVec2) -> glam::UVec2 {
+		let size = DVec2::new(
+			self.transform.transform_vector2(DVec2::X * local_size.x).length(),
+			self.transform.transform_vector2(DVec2::Y * local_size.y).length(),
+		);
+		match self.target {
+			RenderTarget::Preview => (size * self.quality.scale()).round().as_uvec2(),
+			RenderTarget::Export => size.ceil().as_uvec2(),
+		}
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -216,7 +263,9 @@ fn cull_vector_data<T>(footprint: Footprint, vector_data: T) -> T {
 impl core::hash::Hash for Footprint {
 	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
 		self.transform.to_cols_array().iter().for_each(|x| x.to_le_bytes().hash(state));
-		self.resolution.hash(state)
+		self.resolution.hash(state);
+		// Rasters cached for a preview mustn't be reused for an export
+		self.target.hash(state);
 	}
 }
 
@@ -0,0 +1,146 @@
+//! Units of length which measurements of the document are shown in, converted from the pixels the document is laid out in.
+
+use dyn_any::{DynAny, StaticType};
+
+/// The number of pixels in an inch, which is fixed by CSS so documents measure the same in every browser.
+pub const PIXELS_PER_INCH: f64 = 96.;
+const MILLIMETERS_PER_INCH: f64 = 25.4;
+const POINTS_PER_INCH: f64 = 72.;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(specta::Type))]
+pub enum LengthUnit {
+	#[default]
+	Pixels,
+	Millimeters,
+	Centimeters,
+	Inches,
+	/// The typographic point, a 72nd of an inch.
+	Points,
+}
+
+impl core::fmt::Display for LengthUnit {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			LengthUnit::Pixels => write!(f, "Pixels"),
+			LengthUnit::Millimeters => write!(f, "Millimeters"),
+			LengthUnit::Centimeters => write!(f, "Centimeters"),
+			LengthUnit::Inches => write!(f, "Inches"),
+			LengthUnit::Points => write!(f, "Points"),
+		}
+	}
+}
+
+impl LengthUnit {
+	pub fn list() -> [LengthUnit; 5] {
+		[LengthUnit::Pixels, LengthUnit::Millimeters, LengthUnit::Centimeters, LengthUnit::Inches, LengthUnit::Points]
+	}
+
+	/// The abbreviation written after lengths in this unit.
+	pub fn symbol(&self) -> &'static str {
+		match self {
+			LengthUnit::Pixels => "px",
+			LengthUnit::Millimeters => "mm",
+			LengthUnit::Centimeters => "cm",
+			LengthUnit::Inches => "in",
+			LengthUnit::Points => "pt",
+		}
+	}
+
+	/// The number of this unit in an inch, or `None` for pixels whose physical size depends on the resolution.
+	pub fn per_inch(&self) -> Option<f64> {
+		match self {
+			LengthUnit::Pixels => None,
+			LengthUnit::Millimeters => Some(MILLIMETERS_PER_INCH),
+			LengthUnit::Centimeters => Some(MILLIMETERS_PER_INCH / 10.),
+			LengthUnit::Inches => Some(1.),
+			LengthUnit::Points => Some(POINTS_PER_INCH),
+		}
+	}
+}
+
+/// The unit that lengths in a document are shown and typed in, with the resolution relating physical units to the pixels the document is laid out in.
+#[derive(Debug, Clone, Copy, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentUnits {
+	pub length_unit: LengthUnit,
+	/// The number of document pixels in an inch, which sets the physical size of the artwork and the resolution of raster exports at a scale factor of 1.
+	pub dpi: f64,
+}
+
+impl Default for DocumentUnits {
+	fn default() -> Self {
+		Self::new(LengthUnit::Pixels)
+	}
+}
+
+impl core::hash::Hash for DocumentUnits {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.length_unit.hash(state);
+		self.dpi.to_bits().hash(state);
+	}
+}
+
+impl DocumentUnits {
+	/// Lengths in the unit at the resolution of [`PIXELS_PER_INCH`] used by CSS.
+	pub fn new(length_unit: LengthUnit) -> Self {
+		Self { length_unit, dpi: PIXELS_PER_INCH }
+	}
+
+	/// The number of document pixels in one of the unit.
+	pub fn pixels_per_unit(&self) -> f64 {
+		self.length_unit.per_inch().map_or(1., |per_inch| self.dpi / per_inch)
+	}
+
+	pub fn from_pixels(&self, pixels: f64) -> f64 {
+		pixels / self.pixels_per_unit()
+	}
+
+	pub fn to_pixels(&self, length: f64) -> f64 {
+		length * self.pixels_per_unit()
+	}
+
+	/// The suffix of number inputs for lengths in the unit, like `" mm"`.
+	pub fn input_unit(&self) -> String {
+		format!(" {}", self.length_unit.symbol())
+	}
+
+	/// Writes a length given in pixels in the unit, rounded to the number of digits after the decimal point, followed by the unit's symbol.
+	pub fn format(&self, pixels: f64, precision: usize) -> String {
+		format!("{:.precision$} {}", self.from_pixels(pixels), self.length_unit.symbol())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn format_lengths_in_each_unit() {
+		assert_eq!(DocumentUnits::new(LengthUnit::Pixels).format(96., 0), "96 px");
+		assert_eq!(DocumentUnits::new(LengthUnit::Inches).format(96., 2), "1.00 in");
+		assert_eq!(DocumentUnits::new(LengthUnit::Millimeters).format(96., 1), "25.4 mm");
+		assert_eq!(DocumentUnits::new(LengthUnit::Centimeters).format(96., 2), "2.54 cm");
+		assert_eq!(DocumentUnits::new(LengthUnit::Points).format(96., 0), "72 pt");
+
+		let millimeters = DocumentUnits::new(LengthUnit::Millimeters);
+		assert!((millimeters.to_pixels(millimeters.from_pixels(37.)) - 37.).abs() < 1e-12);
+	}
+
+	#[test]
+	fn physical_units_follow_the_dpi() {
+		let inches = DocumentUnits {
+			length_unit: LengthUnit::Inches,
+			dpi: 300.,
+		};
+		assert_eq!(inches.to_pixels(2.), 600.);
+		assert_eq!(inches.format(150., 1), "0.5 in");
+
+		let pixels = DocumentUnits {
+			dpi: 300.,
+			..DocumentUnits::default()
+		};
+		assert_eq!(pixels.to_pixels(2.), 2.);
+	}
+}
@@ -0,0 +1,749 @@
+//! Encoders for QR codes and 1D barcodes, which turn text into the grid of dark modules (or row of bars) that scanners read.
+
+use dyn_any::{DynAny, StaticType};
+
+/// How much of a QR code can be damaged or obscured while it still scans, at the cost of a larger code.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum QrErrorCorrection {
+	/// About 7% of the code can be restored.
+	Low,
+	/// About 15% of the code can be restored.
+	#[default]
+	Medium,
+	/// About 25% of the code can be restored.
+	Quartile,
+	/// About 30% of the code can be restored, enough to cover part of it with a logo.
+	High,
+}
+
+impl core::fmt::Display for QrErrorCorrection {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			QrErrorCorrection::Low => write!(f, "Low"),
+			QrErrorCorrection::Medium => write!(f, "Medium"),
+			QrErrorCorrection::Quartile => write!(f, "Quartile"),
+			QrErrorCorrection::High => write!(f, "High"),
+		}
+	}
+}
+
+impl QrErrorCorrection {
+	pub fn list() -> [QrErrorCorrection; 4] {
+		[QrErrorCorrection::Low, QrErrorCorrection::Medium, QrErrorCorrection::Quartile, QrErrorCorrection::High]
+	}
+
+	/// The bits identifying the level in the format information of the code.
+	fn format_bits(&self) -> u32 {
+		match self {
+			QrErrorCorrection::Low => 1,
+			QrErrorCorrection::Medium => 0,
+			QrErrorCorrection::Quartile => 3,
+			QrErrorCorrection::High => 2,
+		}
+	}
+}
+
+/// The number of error correction codewords in each block, by level and then version (with version 0 unused).
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+	[
+		0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+	],
+	[
+		0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+	],
+	[
+		0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+	],
+	[
+		0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+	],
+];
+
+/// The number of blocks the codewords are split into, by level and then version (with version 0 unused).
+const ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+	[
+		0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+	],
+	[
+		0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+	],
+	[
+		0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+	],
+	[
+		0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+	],
+];
+
+/// A QR code, as a square grid of modules which are dark (`true`) or light.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+	pub version: u32,
+	/// The number of modules along each side.
+	pub size: usize,
+	/// The modules row by row from the top left.
+	pub modules: Vec<bool>,
+	/// The modules of the finder, timing, and alignment patterns and format information, which aren't masked.
+	function: Vec<bool>,
+}
+
+impl QrCode {
+	/// Encodes the text (as UTF-8 bytes) in the smallest QR code which fits it at the error correction level.
+	/// Returns `None` if the text is too long for even the largest QR code.
+	pub fn encode(text: &str, error_correction: QrErrorCorrection) -> Option<Self> {
+		let bytes = text.as_bytes();
+		let level = error_correction as usize;
+
+		let version = (1..=40).find(|&version| {
+			let count_bits = if version < 10 { 8 } else { 16 };
+			4 + count_bits + bytes.len() * 8 <= data_codewords(version, level) * 8
+		})?;
+
+		// The data, in byte mode, followed by the terminator and padding
+		let mut bits = BitBuffer::default();
+		bits.push(0b0100, 4);
+		bits.push(bytes.len() as u32, if version < 10 { 8 } else { 16 });
+		for &byte in bytes {
+			bits.push(byte as u32, 8);
+		}
+		let capacity = data_codewords(version, level) * 8;
+		bits.push(0, (capacity - bits.len).min(4));
+		bits.push(0, (8 - bits.len % 8) % 8);
+		for &pad in [0xEC, 0x11].iter().cycle().take((capacity - bits.len) / 8) {
+			bits.push(pad, 8);
+		}
+
+		let codewords = add_error_correction(&bits.bytes, version, level);
+
+		let size = version as usize * 4 + 17;
+		let mut code = Self {
+			version,
+			size,
+			modules: vec![false; size * size],
+			function: vec![false; size * size],
+		};
+		code.draw_function_patterns();
+		code.draw_codewords(&codewords);
+
+		// Every mask gives a code which scans, but the one with the fewest confusing patterns scans most reliably
+		let mut best = None;
+		for mask in 0..8 {
+			let mut candidate = code.clone();
+			candidate.apply_mask(mask);
+			candidate.draw_format_bits(error_correction, mask);
+			let penalty = candidate.penalty();
+			if best.as_ref().map_or(true, |(best_penalty, _)| penalty < *best_penalty) {
+				best = Some((penalty, candidate));
+			}
+		}
+		best.map(|(_, code)| code)
+	}
+
+	pub fn get(&self, x: usize, y: usize) -> bool {
+		self.modules[y * self.size + x]
+	}
+
+	fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+		self.modules[y * self.size + x] = dark;
+		self.function[y * self.size + x] = true;
+	}
+
+	fn draw_function_patterns(&mut self) {
+		let size = self.size;
+		for i in 0..size {
+			self.set_function(6, i, i % 2 == 0);
+			self.set_function(i, 6, i % 2 == 0);
+		}
+
+		for (x, y) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+			for dy in -4..=4_isize {
+				for dx in -4..=4_isize {
+					let (px, py) = (x as isize + dx, y as isize + dy);
+					if (0..size as isize).contains(&px) && (0..size as isize).contains(&py) {
+						let distance = dx.abs().max(dy.abs());
+						self.set_function(px as usize, py as usize, distance != 2 && distance != 4);
+					}
+				}
+			}
+		}
+
+		let positions = alignment_positions(self.version);
+		let last = positions.len().saturating_sub(1);
+		for (i, &x) in positions.iter().enumerate() {
+			for (j, &y) in positions.iter().enumerate() {
+				// The alignment patterns which would overlap the finder patterns are left out
+				if (i == 0 && j == 0) || (i == 0 && j == last) || (i == last && j == 0) {
+					continue;
+				}
+				for dy in -2..=2_isize {
+					for dx in -2..=2_isize {
+						self.set_function((x as isize + dx) as usize, (y as isize + dy) as usize, dx.abs().max(dy.abs()) != 1);
+					}
+				}
+			}
+		}
+
+		// The format bits are reserved now and drawn once the mask is chosen
+		self.draw_format_bits(QrErrorCorrection::Medium, 0);
+
+		if self.version >= 7 {
+			let mut remainder = self.version;
+			for _ in 0..12 {
+				remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+			}
+			let bits = self.version << 12 | remainder;
+			for i in 0..18 {
+				let dark = (bits >> i) & 1 != 0;
+				let (a, b) = (size - 11 + i % 3, i / 3);
+				self.set_function(a, b, dark);
+				self.set_function(b, a, dark);
+			}
+		}
+	}
+
+	fn draw_format_bits(&mut self, error_correction: QrErrorCorrection, mask: u32) {
+		let data = error_correction.format_bits() << 3 | mask;
+		let mut remainder = data;
+		for _ in 0..10 {
+			remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+		}
+		let bits = (data << 10 | remainder) ^ 0x5412;
+		let bit = |i: usize| (bits >> i) & 1 != 0;
+
+		// The copy around the top left finder pattern
+		for i in 0..=5 {
+			self.set_function(8, i, bit(i));
+		}
+		self.set_function(8, 7, bit(6));
+		self.set_function(8, 8, bit(7));
+		self.set_function(7, 8, bit(8));
+		for i in 9..15 {
+			self.set_function(14 - i, 8, bit(i));
+		}
+
+		// The copy split between the other two finder patterns
+		let size = self.size;
+		for i in 0..8 {
+			self.set_function(size - 1 - i, 8, bit(i));
+		}
+		for i in 8..15 {
+			self.set_function(8, size - 15 + i, bit(i));
+		}
+		self.set_function(8, size - 8, true);
+	}
+
+	/// Places the codewords in two-module wide columns zigzagging up and down from the bottom right, around the function patterns.
+	fn draw_codewords(&mut self, codewords: &[u8]) {
+		let size = self.size;
+		let mut index = 0;
+		let mut right = size as isize - 1;
+		while right >= 1 {
+			// The vertical timing pattern is skipped over
+			if right == 6 {
+				right = 5;
+			}
+			for vertical in 0..size {
+				for j in 0..2 {
+					let x = right as usize - j;
+					let upward = (right + 1) & 2 == 0;
+					let y = if upward { size - 1 - vertical } else { vertical };
+					if !self.function[y * size + x] && index < codewords.len() * 8 {
+						self.modules[y * size + x] = (codewords[index >> 3] >> (7 - (index & 7))) & 1 != 0;
+						index += 1;
+					}
+				}
+			}
+			right -= 2;
+		}
+	}
+
+	fn apply_mask(&mut self, mask: u32) {
+		for y in 0..self.size {
+			for x in 0..self.size {
+				let invert = match mask {
+					0 => (x + y) % 2 == 0,
+					1 => y % 2 == 0,
+					2 => x % 3 == 0,
+					3 => (x + y) % 3 == 0,
+					4 => (x / 3 + y / 2) % 2 == 0,
+					5 => x * y % 2 + x * y % 3 == 0,
+					6 => (x * y % 2 + x * y % 3) % 2 == 0,
+					_ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+				};
+				let index = y * self.size + x;
+				self.modules[index] ^= invert && !self.function[index];
+			}
+		}
+	}
+
+	/// Scores how likely the code is to be misread, from long runs and blocks of one color, patterns looking like finder patterns, and an uneven balance of dark and light.
+	fn penalty(&self) -> usize {
+		let size = self.size;
+		let mut penalty = 0;
+
+		for transpose in [false, true] {
+			let get = |a: usize, b: usize| if transpose { self.get(b, a) } else { self.get(a, b) };
+			for b in 0..size {
+				let line = (0..size).map(|a| get(a, b)).collect::<Vec<_>>();
+
+				let mut run = 1;
+				for a in 1..=size {
+					if a < size && line[a] == line[a - 1] {
+						run += 1;
+					} else {
+						if run >= 5 {
+							penalty += run - 2;
+						}
+						run = 1;
+					}
+				}
+
+				const FINDER_LIKE: [bool; 11] = [true, false, true, true, true, false, true, false, false, false, false];
+				for window in line.windows(11) {
+					if window.iter().eq(FINDER_LIKE.iter()) || window.iter().eq(FINDER_LIKE.iter().rev()) {
+						penalty += 40;
+					}
+				}
+			}
+		}
+
+		for y in 0..size - 1 {
+			for x in 0..size - 1 {
+				let color = self.get(x, y);
+				if color == self.get(x + 1, y) && color == self.get(x, y + 1) && color == self.get(x + 1, y + 1) {
+					penalty += 3;
+				}
+			}
+		}
+
+		let dark = self.modules.iter().filter(|&&dark| dark).count();
+		let total = self.modules.len();
+		penalty += ((dark * 20).abs_diff(total * 10) + total - 1) / total * 10 - 10;
+
+		penalty
+	}
+}
+
+#[derive(Default)]
+struct BitBuffer {
+	bytes: Vec<u8>,
+	len: usize,
+}
+
+impl BitBuffer {
+	fn push(&mut self, value: u32, count: usize) {
+		for i in (0..count).rev() {
+			if self.len % 8 == 0 {
+				self.bytes.push(0);
+			}
+			if (value >> i) & 1 != 0 {
+				*self.bytes.last_mut().unwrap() |= 0x80 >> (self.len % 8);
+			}
+			self.len += 1;
+		}
+	}
+}
+
+/// The number of modules available for codewords, which is everything besides the function patterns.
+fn raw_data_modules(version: u32) -> usize {
+	let version = version as usize;
+	let mut result = (16 * version + 128) * version + 64;
+	if version >= 2 {
+		let alignments = version / 7 + 2;
+		result -= (25 * alignments - 10) * alignments - 55;
+		if version >= 7 {
+			result -= 36;
+		}
+	}
+	result
+}
+
+fn data_codewords(version: u32, level: usize) -> usize {
+	let version_index = version as usize;
+	raw_data_modules(version) / 8 - ECC_CODEWORDS_PER_BLOCK[level][version_index] as usize * ERROR_CORRECTION_BLOCKS[level][version_index] as usize
+}
+
+/// The centers of the alignment patterns along each axis.
+fn alignment_positions(version: u32) -> Vec<usize> {
+	if version == 1 {
+		return Vec::new();
+	}
+	let version = version as usize;
+	let count = version / 7 + 2;
+	let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+	let mut positions = vec![6; count];
+	let mut position = version * 4 + 17 - 7;
+	for slot in positions.iter_mut().skip(1).rev() {
+		*slot = position;
+		position -= step;
+	}
+	positions
+}
+
+/// Splits the data into blocks, adds the error correction codewords to each, and interleaves the blocks.
+fn add_error_correction(data: &[u8], version: u32, level: usize) -> Vec<u8> {
+	let blocks_count = ERROR_CORRECTION_BLOCKS[level][version as usize] as usize;
+	let ecc_length = ECC_CODEWORDS_PER_BLOCK[level][version as usize] as usize;
+	let raw_codewords = raw_data_modules(version) / 8;
+	// The blocks which are one data codeword shorter come first
+	let short_blocks = blocks_count - raw_codewords % blocks_count;
+	let short_block_length = raw_codewords / blocks_count;
+
+	let divisor = reed_solomon_divisor(ecc_length);
+	let mut blocks = Vec::with_capacity(blocks_count);
+	let mut start = 0;
+	for i in 0..blocks_count {
+		let length = short_block_length - ecc_length + usize::from(i >= short_blocks);
+		let mut block = data[start..start + length].to_vec();
+		start += length;
+		let ecc = reed_solomon_remainder(&block, &divisor);
+		if i < short_blocks {
+			block.push(0);
+		}
+		block.extend(ecc);
+		blocks.push(block);
+	}
+
+	let mut result = Vec::with_capacity(raw_codewords);
+	for i in 0..blocks[0].len() {
+		for (j, block) in blocks.iter().enumerate() {
+			// The placeholders padding out the short blocks are skipped
+			if i != short_block_length - ecc_length || j >= short_blocks {
+				result.push(block[i]);
+			}
+		}
+	}
+	result
+}
+
+/// Multiplies in the Galois field GF(2⁸) used by QR codes, with the reducing polynomial x⁸ + x⁴ + x³ + x² + 1.
+fn gf_multiply(x: u8, y: u8) -> u8 {
+	let mut z: u32 = 0;
+	for i in (0..8).rev() {
+		z = (z << 1) ^ ((z >> 7) * 0x11D);
+		z ^= ((y as u32 >> i) & 1) * x as u32;
+	}
+	z as u8
+}
+
+/// The coefficients (from the highest power down, leaving out the leading 1) of the generator polynomial of the given degree.
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+	let mut result = vec![0; degree];
+	result[degree - 1] = 1;
+	let mut root = 1;
+	for _ in 0..degree {
+		for j in 0..degree {
+			result[j] = gf_multiply(result[j], root);
+			if j + 1 < degree {
+				result[j] ^= result[j + 1];
+			}
+		}
+		root = gf_multiply(root, 0x02);
+	}
+	result
+}
+
+/// The error correction codewords of the data, the remainder of dividing it by the generator polynomial.
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+	let mut result = vec![0; divisor.len()];
+	for &byte in data {
+		let factor = byte ^ result.remove(0);
+		result.push(0);
+		for (value, &coefficient) in result.iter_mut().zip(divisor) {
+			*value ^= gf_multiply(coefficient, factor);
+		}
+	}
+	result
+}
+
+/// The kinds of 1D barcode which can be generated.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum BarcodeSymbology {
+	/// Any printable ASCII text, used for shipping and inventory labels.
+	#[default]
+	Code128,
+	/// 12 digits and a check digit, used on retail products around the world.
+	Ean13,
+	/// 11 digits and a check digit, used on retail products in North America.
+	UpcA,
+}
+
+impl core::fmt::Display for BarcodeSymbology {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			BarcodeSymbology::Code128 => write!(f, "Code 128"),
+			BarcodeSymbology::Ean13 => write!(f, "EAN-13"),
+			BarcodeSymbology::UpcA => write!(f, "UPC-A"),
+		}
+	}
+}
+
+impl BarcodeSymbology {
+	pub fn list() -> [BarcodeSymbology; 3] {
+		[BarcodeSymbology::Code128, BarcodeSymbology::Ean13, BarcodeSymbology::UpcA]
+	}
+
+	/// The modules of the barcode from left to right, which are bars (`true`) or spaces, not including the quiet zones on either side.
+	/// Returns `None` if the text can't be encoded, such as letters in an EAN-13 code or a check digit which doesn't match.
+	pub fn encode(&self, text: &str) -> Option<Vec<bool>> {
+		match self {
+			BarcodeSymbology::Code128 => encode_code_128(text),
+			BarcodeSymbology::Ean13 => encode_ean_13(text),
+			// A UPC-A code is drawn the same as the EAN-13 code of the same number with a leading 0
+			BarcodeSymbology::UpcA => {
+				let digits = text.trim();
+				if !matches!(digits.len(), 11 | 12) {
+					return None;
+				}
+				encode_ean_13(&format!("0{digits}"))
+			}
+		}
+	}
+}
+
+/// The bars and spaces of each Code 128 symbol, as 11 modules read from the most significant bit, with the 13 module stop symbol last.
+const CODE_128_PATTERNS: [u16; 107] = [
+	0b11011001100,
+	0b11001101100,
+	0b11001100110,
+	0b10010011000,
+	0b10010001100,
+	0b10001001100,
+	0b10011001000,
+	0b10011000100,
+	0b10001100100,
+	0b11001001000,
+	0b11001000100,
+	0b11000100100,
+	0b10110011100,
+	0b10011011100,
+	0b10011001110,
+	0b10111001100,
+	0b10011101100,
+	0b10011100110,
+	0b11001110010,
+	0b11001011100,
+	0b11001001110,
+	0b11011100100,
+	0b11001110100,
+	0b11101101110,
+	0b11101001100,
+	0b11100101100,
+	0b11100100110,
+	0b11101100100,
+	0b11100110100,
+	0b11100110010,
+	0b11011011000,
+	0b11011000110,
+	0b11000110110,
+	0b10100011000,
+	0b10001011000,
+	0b10001000110,
+	0b10110001000,
+	0b10001101000,
+	0b10001100010,
+	0b11010001000,
+	0b11000101000,
+	0b11000100010,
+	0b10110111000,
+	0b10110001110,
+	0b10001101110,
+	0b10111011000,
+	0b10111000110,
+	0b10001110110,
+	0b11101110110,
+	0b11010001110,
+	0b11000101110,
+	0b11011101000,
+	0b11011100010,
+	0b11011101110,
+	0b11101011000,
+	0b11101000110,
+	0b11100010110,
+	0b11101101000,
+	0b11101100010,
+	0b11100011010,
+	0b11101111010,
+	0b11001000010,
+	0b11110001010,
+	0b10100110000,
+	0b10100001100,
+	0b10010110000,
+	0b10010000110,
+	0b10000101100,
+	0b10000100110,
+	0b10110010000,
+	0b10110000100,
+	0b10011010000,
+	0b10011000010,
+	0b10000110100,
+	0b10000110010,
+	0b11000010010,
+	0b11001010000,
+	0b11110111010,
+	0b11000010100,
+	0b10001111010,
+	0b10100111100,
+	0b10010111100,
+	0b10010011110,
+	0b10111100100,
+	0b10011110100,
+	0b10011110010,
+	0b11110100100,
+	0b11110010100,
+	0b11110010010,
+	0b11011011110,
+	0b11011110110,
+	0b11110110110,
+	0b10101111000,
+	0b10100011110,
+	0b10001011110,
+	0b10111101000,
+	0b10111100010,
+	0b11110101000,
+	0b11110100010,
+	0b10111011110,
+	0b10111101110,
+	0b11101011110,
+	0b11110101110,
+	0b11010000100,
+	0b11010010000,
+	0b11010011100,
+	0b1100011101011,
+];
+const CODE_128_START_B: usize = 104;
+const CODE_128_STOP: usize = 106;
+
+fn push_pattern(modules: &mut Vec<bool>, pattern: u16, length: usize) {
+	modules.extend((0..length).rev().map(|bit| (pattern >> bit) & 1 != 0));
+}
+
+/// Encodes printable ASCII text in code set B, followed by the check symbol.
+fn encode_code_128(text: &str) -> Option<Vec<bool>> {
+	if text.is_empty() {
+		return None;
+	}
+	let values = text.bytes().map(|byte| (32..128).contains(&byte).then(|| (byte - 32) as usize)).collect::<Option<Vec<_>>>()?;
+	let check = values.iter().enumerate().fold(CODE_128_START_B, |sum, (index, value)| sum + (index + 1) * value) % 103;
+
+	let mut modules = Vec::with_capacity((values.len() + 3) * 11 + 2);
+	for value in core::iter::once(CODE_128_START_B).chain(values).chain(core::iter::once(check)) {
+		push_pattern(&mut modules, CODE_128_PATTERNS[value], 11);
+	}
+	push_pattern(&mut modules, CODE_128_PATTERNS[CODE_128_STOP], 13);
+	Some(modules)
+}
+
+/// The left hand digits with odd parity, of which the right hand digits are the inverse and the left hand digits with even parity are the reversed inverse.
+const EAN_L_CODES: [u16; 10] = [0b0001101, 0b0011001, 0b0010011, 0b0111101, 0b0100011, 0b0110001, 0b0101111, 0b0111011, 0b0110111, 0b0001011];
+/// Which of the left hand digits have even parity (from the most significant of the 6 bits), which encodes the first digit.
+const EAN_PARITIES: [u8; 10] = [0b000000, 0b001011, 0b001101, 0b001110, 0b010011, 0b011001, 0b011100, 0b010101, 0b010110, 0b011010];
+
+/// The check digit making the weighted sum of the digits (alternately weighted 1 and 3 from the left) a multiple of 10.
+fn ean_check_digit(digits: &[u8]) -> u8 {
+	let sum: u32 = digits.iter().enumerate().map(|(index, &digit)| digit as u32 * if index % 2 == 0 { 1 } else { 3 }).sum();
+	((10 - sum % 10) % 10) as u8
+}
+
+/// Encodes 12 digits, adding the check digit, or 13 digits whose last is the check digit.
+fn encode_ean_13(text: &str) -> Option<Vec<bool>> {
+	let mut digits = text.trim().chars().map(|c| c.to_digit(10).map(|digit| digit as u8)).collect::<Option<Vec<_>>>()?;
+	match digits.len() {
+		12 => digits.push(ean_check_digit(&digits)),
+		13 if digits[12] == ean_check_digit(&digits[..12]) => {}
+		_ => return None,
+	}
+
+	let mut modules = Vec::with_capacity(95);
+	push_pattern(&mut modules, 0b101, 3);
+	let parity = EAN_PARITIES[digits[0] as usize];
+	for (index, &digit) in digits[1..7].iter().enumerate() {
+		let code = EAN_L_CODES[digit as usize];
+		let even = (parity >> (5 - index)) & 1 != 0;
+		let code = if even { (!code & 0x7F).reverse_bits() >> 9 } else { code };
+		push_pattern(&mut modules, code, 7);
+	}
+	push_pattern(&mut modules, 0b01010, 5);
+	for &digit in &digits[7..] {
+		push_pattern(&mut modules, !EAN_L_CODES[digit as usize] & 0x7F, 7);
+	}
+	push_pattern(&mut modules, 0b101, 3);
+	Some(modules)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn reed_solomon_matches_the_standard_example() {
+		// The data codewords of "01234567" in a version 1-M code, from the worked example of the QR code standard
+		let data = [16, 32, 12, 86, 97, 128, 236, 17, 236, 17, 236, 17, 236, 17, 236, 17];
+		let ecc = reed_solomon_remainder(&data, &reed_solomon_divisor(10));
+		assert_eq!(ecc, [165, 36, 212, 193, 237, 54, 199, 135, 44, 85]);
+	}
+
+	#[test]
+	fn qr_code_grows_with_the_text() {
+		let small = QrCode::encode("HELLO WORLD", QrErrorCorrection::Medium).unwrap();
+		assert_eq!((small.version, small.size), (1, 21));
+		assert_eq!(QrCode::encode("HELLO WORLD", QrErrorCorrection::High).unwrap().version, 2);
+
+		let url = QrCode::encode("https://graphite.rs/learn/node-graph/procedural-generation/", QrErrorCorrection::Quartile).unwrap();
+		assert_eq!(url.version, 5);
+		assert_eq!(alignment_positions(7), [6, 22, 38]);
+		assert_eq!(alignment_positions(32), [6, 34, 60, 86, 112, 138]);
+
+		assert!(QrCode::encode(&"x".repeat(3000), QrErrorCorrection::Low).is_none());
+	}
+
+	#[test]
+	fn qr_code_has_finder_patterns_and_matching_format_copies() {
+		let code = QrCode::encode("Graphite", QrErrorCorrection::Low).unwrap();
+		let size = code.size;
+		for (x, y) in [(0, 0), (size - 7, 0), (0, size - 7)] {
+			assert!((0..7).all(|i| code.get(x + i, y) && code.get(x, y + i) && code.get(x + 6, y + i)));
+			assert!(code.get(x + 3, y + 3) && !code.get(x + 1, y + 1));
+		}
+		assert!(code.get(8, size - 8));
+
+		// Both copies of the format information say the same thing
+		let first = (0..=5)
+			.map(|i| code.get(8, i))
+			.chain([code.get(8, 7), code.get(8, 8), code.get(7, 8)])
+			.chain((9..15).map(|i| code.get(14 - i, 8)));
+		let second = (0..8).map(|i| code.get(size - 1 - i, 8)).chain((8..15).map(|i| code.get(8, size - 15 + i)));
+		assert!(first.eq(second));
+	}
+
+	#[test]
+	fn code_128_is_checked_and_stopped() {
+		// Every symbol has three bars and three spaces
+		for pattern in &CODE_128_PATTERNS[..CODE_128_STOP] {
+			assert_eq!(pattern >> 10, 1);
+			assert_eq!((pattern ^ (pattern >> 1)).count_ones() + (pattern & 1) as u32, 6);
+		}
+
+		let modules = encode_code_128("PJJ123C").unwrap();
+		assert_eq!(modules.len(), (7 + 3) * 11 + 13);
+		assert!(encode_code_128("naïve").is_none());
+	}
+
+	#[test]
+	fn ean_13_adds_and_checks_the_check_digit() {
+		assert_eq!(ean_check_digit(&[4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3]), 1);
+		let modules = BarcodeSymbology::Ean13.encode("400638133393").unwrap();
+		assert_eq!(modules.len(), 95);
+		assert_eq!(Some(modules), BarcodeSymbology::Ean13.encode("4006381333931"));
+		assert!(BarcodeSymbology::Ean13.encode("4006381333932").is_none());
+
+		// Every digit's code starts with a space and ends with a bar on the left, and the other way around on the right
+		let modules = BarcodeSymbology::Ean13.encode("400638133393").unwrap();
+		assert_eq!(modules[..3], [true, false, true]);
+		assert!(modules[3..45].chunks(7).all(|code| !code[0] && code[6]));
+		assert_eq!(modules[45..50], [false, true, false, true, false]);
+		assert!(modules[50..92].chunks(7).all(|code| code[0] && !code[6]));
+
+		assert_eq!(BarcodeSymbology::UpcA.encode("03600029145"), BarcodeSymbology::Ean13.encode("003600029145"));
+	}
+}
@@ -0,0 +1,309 @@
+//! Cutting paths apart, like scissors snipping a path at a point or a knife slicing along a line, and dividing closed shapes into pieces.
+//!
+//! These are used by the nodes which cut paths as well as by tools, which give the points and cutting paths in the same space as the vector data's transform maps into.
+
+use super::{PointId, VectorData};
+
+use bezier_rs::{Subpath, SubpathTValue};
+use glam::DVec2;
+
+/// The size of the bounding boxes which the search for crossings between paths narrows down to.
+const CROSSING_ERROR: f64 = 1e-6;
+/// How close points have to be to count as the same point, such as where a path is crossed at the anchor between two of its segments.
+const SAME_POINT_DISTANCE: f64 = 1e-3;
+
+fn parametric((segment_index, t): (usize, f64)) -> SubpathTValue {
+	SubpathTValue::Parametric { segment_index, t: t.clamp(0., 1.) }
+}
+
+/// Whether the place on the path is one of the ends of an open path, where cutting it wouldn't produce a new piece.
+fn is_open_end(path: &Subpath<PointId>, (segment_index, t): (usize, f64)) -> bool {
+	let start = path.evaluate(parametric((0, 0.)));
+	let end = path.evaluate(parametric((path.len_segments() - 1, 1.)));
+	let position = path.evaluate(parametric((segment_index, t)));
+	!path.closed() && (position.distance(start) < SAME_POINT_DISTANCE || position.distance(end) < SAME_POINT_DISTANCE)
+}
+
+/// The places where the path is crossed by the cutters, in order along the path, without repeating places at the same point or including the ends of open paths.
+fn crossings(path: &Subpath<PointId>, cutters: &[Subpath<PointId>]) -> Vec<(usize, f64)> {
+	let mut all_crossings = cutters.iter().flat_map(|cutter| path.subpath_intersections(cutter, Some(CROSSING_ERROR), None)).collect::<Vec<_>>();
+	all_crossings.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+	let mut crossings: Vec<(usize, f64)> = Vec::new();
+	for crossing in all_crossings {
+		let position = path.evaluate(parametric(crossing));
+		let repeated = crossings.last().is_some_and(|&last| path.evaluate(parametric(last)).distance(position) < SAME_POINT_DISTANCE);
+		if !repeated && !is_open_end(path, crossing) {
+			crossings.push(crossing);
+		}
+	}
+
+	// The first and last crossings of a closed path can be the same point, found on either side of the break between its ends
+	if let [first, .., last] = crossings[..] {
+		if path.closed() && path.evaluate(parametric(first)).distance(path.evaluate(parametric(last))) < SAME_POINT_DISTANCE {
+			crossings.pop();
+		}
+	}
+	crossings
+}
+
+/// Cuts the path at each of the places, in order along the path, into open pieces. A closed path cut in one place is opened up into a single piece.
+fn cut_at(path: &Subpath<PointId>, places: &[(usize, f64)]) -> Vec<Subpath<PointId>> {
+	let (Some(&first), Some(&last)) = (places.first(), places.last()) else {
+		return vec![path.clone()];
+	};
+
+	if path.closed() {
+		if places.len() == 1 {
+			return vec![path.split(parametric(first)).0];
+		}
+		let wrapping_piece = path.trim(parametric(last), parametric(first));
+		places.windows(2).map(|pair| path.trim(parametric(pair[0]), parametric(pair[1]))).chain([wrapping_piece]).collect()
+	} else {
+		let ends = [(0, 0.)].into_iter().chain(places.iter().copied()).chain([(path.len_segments() - 1, 1.)]).collect::<Vec<_>>();
+		ends.windows(2).map(|pair| path.trim(parametric(pair[0]), parametric(pair[1]))).collect()
+	}
+}
+
+/// Joins a path going from one point to another with a path going back again into a closed path.
+fn join_into_loop(there: &Subpath<PointId>, back: &Subpath<PointId>) -> Subpath<PointId> {
+	let mut groups = there.manipulator_groups().to_vec();
+	let mut back_groups = back.manipulator_groups().iter().cloned();
+	if let (Some(turn), Some(back_start)) = (groups.last_mut(), back_groups.next()) {
+		turn.out_handle = back_start.out_handle;
+	}
+	groups.extend(back_groups);
+
+	// The path back ends where the path there starts, so its last point becomes the first point of the loop
+	if groups.len() > 2 {
+		let end = groups.pop().unwrap();
+		groups[0].in_handle = end.in_handle;
+	}
+	Subpath::new(groups, true)
+}
+
+/// Splits a closed path into two closed paths along a chord between two points on its boundary, or gives `None` if the chord doesn't start and end on the boundary.
+fn split_by_chord(shape: &Subpath<PointId>, chord: &Subpath<PointId>) -> Option<(Subpath<PointId>, Subpath<PointId>)> {
+	let on_boundary = |point: DVec2| {
+		let place = shape.project(point)?;
+		(shape.evaluate(parametric(place)).distance(point) < SAME_POINT_DISTANCE).then_some(place)
+	};
+	let (start, end) = (chord.manipulator_groups().first()?.anchor, chord.manipulator_groups().last()?.anchor);
+	if start.distance(end) < SAME_POINT_DISTANCE {
+		return None;
+	}
+	let (start_place, end_place) = (on_boundary(start)?, on_boundary(end)?);
+
+	let forward = shape.trim(parametric(start_place), parametric(end_place));
+	let backward = shape.trim(parametric(end_place), parametric(start_place));
+	Some((join_into_loop(&forward, &chord.reverse()), join_into_loop(&backward, chord)))
+}
+
+impl VectorData {
+	/// A copy of the vector data with its paths replaced by the pieces. Each piece is given new points, so pieces cut from the same path don't share any.
+	fn with_pieces(&self, pieces: impl IntoIterator<Item = Subpath<PointId>>) -> VectorData {
+		let mut result = VectorData {
+			transform: self.transform,
+			style: self.style.clone(),
+			alpha_blending: self.alpha_blending,
+			text_overflows: self.text_overflows,
+			..VectorData::empty()
+		};
+		for mut piece in pieces {
+			if piece.len_segments() == 0 {
+				continue;
+			}
+			piece.manipulator_groups_mut().iter_mut().for_each(|group| group.id = PointId::generate());
+			result.append_subpath(piece);
+		}
+		result
+	}
+
+	/// The paths of the cutter in the coordinates of this vector data's paths.
+	fn cutting_paths(&self, cutter: &VectorData) -> Vec<Subpath<PointId>> {
+		let transform = self.transform.inverse() * cutter.transform;
+		cutter
+			.stroke_bezier_paths()
+			.map(|mut path| {
+				path.apply_transform(transform);
+				path
+			})
+			.collect()
+	}
+
+	fn is_invertible(&self) -> bool {
+		self.transform.matrix2.determinant() != 0.
+	}
+
+	/// Snips the path nearest to the point there, leaving an open path on either side, or opening up a closed path at that point.
+	pub fn split_at_point(&self, point: DVec2) -> VectorData {
+		if !self.is_invertible() {
+			return self.clone();
+		}
+		let point = self.transform.inverse().transform_point2(point);
+		let paths = self.stroke_bezier_paths().filter(|path| path.len_segments() > 0).collect::<Vec<_>>();
+
+		let nearest = paths
+			.iter()
+			.enumerate()
+			.filter_map(|(index, path)| path.project(point).map(|place| (index, place, path.evaluate(parametric(place)).distance_squared(point))))
+			.min_by(|a, b| a.2.total_cmp(&b.2));
+		let Some((nearest_index, place, _)) = nearest else { return self.clone() };
+
+		let pieces = paths.into_iter().enumerate().flat_map(|(index, path)| {
+			if index == nearest_index && !is_open_end(&path, place) {
+				cut_at(&path, &[place])
+			} else {
+				vec![path]
+			}
+		});
+		self.with_pieces(pieces.collect::<Vec<_>>())
+	}
+
+	/// Cuts the paths everywhere the paths of the cutter cross them, like a knife slicing along the cutter, leaving open paths between the cuts.
+	pub fn cut_along(&self, cutter: &VectorData) -> VectorData {
+		if !self.is_invertible() {
+			return self.clone();
+		}
+		let cutters = self.cutting_paths(cutter);
+		let pieces = self.stroke_bezier_paths().flat_map(|path| cut_at(&path, &crossings(&path, &cutters))).collect::<Vec<_>>();
+		self.with_pieces(pieces)
+	}
+
+	/// Divides the closed shapes into closed pieces wherever the paths of the cutter pass all the way through them, leaving open paths as they are.
+	///
+	/// Each stretch of the cutter running through the inside of a shape from one side to another divides the shape in two, so a cutter
+	/// crossing a shape several times, or several cutters crossing each other, divide it into as many pieces as they enclose.
+	pub fn divide(&self, cutter: &VectorData) -> VectorData {
+		if !self.is_invertible() {
+			return self.clone();
+		}
+		let cutters = self.cutting_paths(cutter);
+
+		let mut pieces = Vec::new();
+		for path in self.stroke_bezier_paths() {
+			if !path.closed() {
+				pieces.push(path);
+				continue;
+			}
+
+			let mut shape_pieces = vec![path];
+			for cutter in cutters.iter().filter(|cutter| cutter.len_segments() > 0) {
+				// The cutter is crossed against the pieces so far, so it's also cut where it crosses the cuts before it
+				let places = crossings(cutter, &shape_pieces);
+				let mut chords = places.windows(2).map(|pair| cutter.trim(parametric(pair[0]), parametric(pair[1]))).collect::<Vec<_>>();
+				if let (true, [first, .., last]) = (cutter.closed(), &places[..]) {
+					chords.push(cutter.trim(parametric(*last), parametric(*first)));
+				}
+
+				for chord in chords {
+					let middle = chord.evaluate(SubpathTValue::GlobalEuclidean(0.5));
+					let Some(index) = shape_pieces.iter().position(|piece| piece.point_inside(middle)) else {
+						continue;
+					};
+					if let Some((first, second)) = split_by_chord(&shape_pieces[index], &chord) {
+						shape_pieces.swap_remove(index);
+						shape_pieces.extend([first, second]);
+					}
+				}
+			}
+			pieces.extend(shape_pieces);
+		}
+		self.with_pieces(pieces)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use glam::DAffine2;
+
+	fn square() -> VectorData {
+		VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::splat(100.)))
+	}
+
+	fn line(start: DVec2, end: DVec2) -> VectorData {
+		VectorData::from_subpath(Subpath::new_line(start, end))
+	}
+
+	fn paths(vector_data: &VectorData) -> Vec<Subpath<PointId>> {
+		vector_data.stroke_bezier_paths().collect()
+	}
+
+	fn area(path: &Subpath<PointId>) -> f64 {
+		path.area(Some(1e-3), Some(1e-3)).abs()
+	}
+
+	#[test]
+	fn split_open_path_at_point() {
+		let split = line(DVec2::ZERO, DVec2::new(100., 0.)).split_at_point(DVec2::new(30., 5.));
+		let paths = paths(&split);
+		assert_eq!(paths.len(), 2);
+		assert!(paths.iter().all(|path| !path.closed()));
+		assert!(paths[0].manipulator_groups().last().unwrap().anchor.abs_diff_eq(DVec2::new(30., 0.), 1e-9));
+		assert!(paths[1].manipulator_groups().first().unwrap().anchor.abs_diff_eq(DVec2::new(30., 0.), 1e-9));
+	}
+
+	#[test]
+	fn split_closed_path_opens_it() {
+		let split = square().split_at_point(DVec2::new(50., -1.));
+		let paths = paths(&split);
+		assert_eq!(paths.len(), 1);
+		assert!(!paths[0].closed());
+		assert_eq!(split.region_bezier_paths().count(), 0);
+		assert!(paths[0].manipulator_groups().first().unwrap().anchor.abs_diff_eq(DVec2::new(50., 0.), 1e-9));
+	}
+
+	#[test]
+	fn cut_along_line() {
+		let cut = square().cut_along(&line(DVec2::new(50., -10.), DVec2::new(50., 110.)));
+		let paths = paths(&cut);
+		assert_eq!(paths.len(), 2);
+		assert!(paths.iter().all(|path| !path.closed()));
+		// Every point is new, so none are shared between the pieces
+		let ids = cut.point_domain.ids();
+		assert!(ids.iter().enumerate().all(|(index, id)| !ids[..index].contains(id)));
+	}
+
+	#[test]
+	fn cut_along_transformed_cutter() {
+		let mut cutter = line(DVec2::new(0., -10.), DVec2::new(0., 110.));
+		cutter.transform = DAffine2::from_translation(DVec2::new(25., 0.));
+		let cut = square().cut_along(&cutter);
+		let lengths = paths(&cut).iter().map(|path| path.length(None)).collect::<Vec<_>>();
+		assert_eq!(lengths.len(), 2);
+		assert!(lengths.iter().any(|length| (length - 100.).abs() < 1e-3));
+		assert!(lengths.iter().any(|length| (length - 300.).abs() < 1e-3));
+	}
+
+	#[test]
+	fn divide_square_in_two() {
+		let divided = square().divide(&line(DVec2::new(30., -10.), DVec2::new(30., 110.)));
+		let paths = paths(&divided);
+		assert_eq!(paths.len(), 2);
+		assert!(paths.iter().all(|path| path.closed()));
+		assert_eq!(divided.region_bezier_paths().count(), 2);
+		let mut areas = paths.iter().map(area).collect::<Vec<_>>();
+		areas.sort_by(f64::total_cmp);
+		assert!((areas[0] - 3000.).abs() < 1e-3 && (areas[1] - 7000.).abs() < 1e-3, "{areas:?}");
+	}
+
+	#[test]
+	fn divide_square_into_quarters() {
+		let mut cross = line(DVec2::new(50., -10.), DVec2::new(50., 110.));
+		cross.append_subpath(Subpath::<PointId>::new_line(DVec2::new(-10., 50.), DVec2::new(110., 50.)));
+		let paths = paths(&square().divide(&cross));
+		assert_eq!(paths.len(), 4);
+		assert!(paths.iter().all(|path| (area(path) - 2500.).abs() < 1e-3));
+	}
+
+	#[test]
+	fn divide_leaves_shapes_the_cutter_misses() {
+		let divided = square().divide(&line(DVec2::new(150., -10.), DVec2::new(150., 110.)));
+		assert_eq!(paths(&divided).len(), 1);
+		// A cutter stopping partway through doesn't divide the shape either
+		let divided = square().divide(&line(DVec2::new(50., -10.), DVec2::new(50., 50.)));
+		assert_eq!(paths(&divided).len(), 1);
+	}
+}
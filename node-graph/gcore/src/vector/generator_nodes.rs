@@ -1,8 +1,11 @@
 use crate::uuid::ManipulatorGroupId;
+use crate::vector::barcode::{BarcodeSymbology, QrCode, QrErrorCorrection};
+use crate::vector::misc::ArcType;
 use crate::vector::VectorData;
 use crate::Node;
 
-use bezier_rs::Subpath;
+use bezier_rs::{ManipulatorGroup, Subpath};
+use core::f64::consts::TAU;
 
 use glam::DVec2;
 
@@ -104,6 +107,70 @@ fn star_generator(_input: (), points: u32, radius: f64, inner_radius: f64) -> Ve
 	super::VectorData::from_subpath(Subpath::new_star_polygon(DVec2::splat(-diameter), points, diameter, inner_diameter))
 }
 
+/// Approximates with cubic segments the curve traced as the angle sweeps from its start to its end (in radians) at a distance from the center which grows evenly from the start radius to the end radius.
+/// A constant radius traces a circular arc and a changing radius traces an Archimedean spiral.
+fn polar_curve(start_angle: f64, end_angle: f64, start_radius: f64, end_radius: f64) -> Vec<ManipulatorGroup<ManipulatorGroupId>> {
+	let sweep = end_angle - start_angle;
+	// A segment for each eighth of a turn keeps the curve within a tiny fraction of a pixel of the true curve at everyday sizes
+	let segments = ((sweep.abs() / (TAU / 8.)).ceil() as usize).max(1);
+	let step = sweep / segments as f64;
+	let radius_per_angle = if sweep == 0. { 0. } else { (end_radius - start_radius) / sweep };
+	// The handle length, as a fraction of the radius, which best approximates a circular arc with a cubic segment
+	let circular_handle = 4. / 3. * (step / 4.).tan();
+
+	(0..=segments)
+		.map(|index| {
+			let angle = start_angle + step * index as f64;
+			let radius = start_radius + radius_per_angle * (angle - start_angle);
+			let direction = DVec2::from_angle(angle);
+			let anchor = direction * radius;
+			let tangent = direction.perp() * radius * circular_handle + direction * radius_per_angle * step / 3.;
+			ManipulatorGroup::new(anchor, (index > 0).then_some(anchor - tangent), (index < segments).then_some(anchor + tangent))
+		})
+		.collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SpiralGenerator<Turns, InnerRadius, OuterRadius> {
+	turns: Turns,
+	inner_radius: InnerRadius,
+	outer_radius: OuterRadius,
+}
+
+#[node_macro::node_fn(SpiralGenerator)]
+fn spiral_generator(_input: (), turns: f64, inner_radius: f64, outer_radius: f64) -> VectorData {
+	let groups = polar_curve(0., turns.max(0.) * TAU, inner_radius, outer_radius);
+	super::VectorData::from_subpath(Subpath::new(groups, false))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArcGenerator<Radius, StartAngle, SweepAngle, ArcType> {
+	radius: Radius,
+	start_angle: StartAngle,
+	sweep_angle: SweepAngle,
+	arc_type: ArcType,
+}
+
+/// An arc of a circle around the origin, starting at `start_angle` degrees clockwise from the right and sweeping `sweep_angle` degrees further clockwise, or counterclockwise if it's negative.
+#[node_macro::node_fn(ArcGenerator)]
+fn arc_generator(_input: (), radius: f64, start_angle: f64, sweep_angle: f64, arc_type: ArcType) -> VectorData {
+	let start_angle = start_angle.to_radians();
+	let sweep_angle = sweep_angle.clamp(-360., 360.).to_radians();
+	let mut groups = polar_curve(start_angle, start_angle + sweep_angle, radius, radius);
+
+	let full_circle = sweep_angle.abs() >= TAU;
+	let closed = arc_type != ArcType::Open;
+	if closed && full_circle {
+		// The end of a full circle is back at its start, so the two are joined into one point
+		if let Some(end) = groups.pop() {
+			groups[0].in_handle = end.in_handle;
+		}
+	} else if arc_type == ArcType::PieSlice {
+		groups.push(ManipulatorGroup::new(DVec2::ZERO, None, None));
+	}
+	super::VectorData::from_subpath(Subpath::new(groups, closed))
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct LineGenerator<Pos1, Pos2> {
 	pos_1: Pos1,
@@ -125,6 +192,78 @@ fn spline_generator(_input: (), positions: Vec<DVec2>) -> VectorData {
 	super::VectorData::from_subpath(Subpath::new_cubic_spline(positions))
 }
 
+#[derive(Debug, Clone)]
+pub struct QrCodeGenerator<Text, ErrorCorrection, QuietZone, ModuleSize> {
+	text: Text,
+	error_correction: ErrorCorrection,
+	quiet_zone: QuietZone,
+	module_size: ModuleSize,
+}
+
+/// Generates a QR code encoding the text, with each run of dark modules along a row as a rectangle.
+/// The top left of the quiet zone (the light margin, in modules, which scanners need around the code) is at the origin.
+#[node_macro::node_fn(QrCodeGenerator)]
+fn qr_code_generator(_input: (), text: String, error_correction: QrErrorCorrection, quiet_zone: u32, module_size: f64) -> VectorData {
+	let Some(code) = QrCode::encode(&text, error_correction) else {
+		return VectorData::empty();
+	};
+
+	let mut subpaths = Vec::new();
+	for y in 0..code.size {
+		let mut x = 0;
+		while x < code.size {
+			if !code.get(x, y) {
+				x += 1;
+				continue;
+			}
+			let start = x;
+			while x < code.size && code.get(x, y) {
+				x += 1;
+			}
+			let min = DVec2::new(start as f64, y as f64) + quiet_zone as f64;
+			let max = DVec2::new(x as f64, y as f64 + 1.) + quiet_zone as f64;
+			subpaths.push(Subpath::new_rect(min * module_size, max * module_size));
+		}
+	}
+	super::VectorData::from_subpaths(subpaths)
+}
+
+#[derive(Debug, Clone)]
+pub struct BarcodeGenerator<Text, Symbology, QuietZone, ModuleSize, Height> {
+	text: Text,
+	symbology: Symbology,
+	quiet_zone: QuietZone,
+	module_size: ModuleSize,
+	height: Height,
+}
+
+/// Generates a 1D barcode encoding the text, with each bar as a rectangle of the given height.
+/// The quiet zone (the light margin, in modules, which scanners need on either side) starts at the origin.
+/// Text which the symbology can't encode, such as a wrong check digit, gives no bars.
+#[node_macro::node_fn(BarcodeGenerator)]
+fn barcode_generator(_input: (), text: String, symbology: BarcodeSymbology, quiet_zone: u32, module_size: f64, height: f64) -> VectorData {
+	let Some(modules) = symbology.encode(&text) else {
+		return VectorData::empty();
+	};
+
+	let mut subpaths = Vec::new();
+	let mut index = 0;
+	while index < modules.len() {
+		if !modules[index] {
+			index += 1;
+			continue;
+		}
+		let start = index;
+		while index < modules.len() && modules[index] {
+			index += 1;
+		}
+		let left = (start as f64 + quiet_zone as f64) * module_size;
+		let right = (index as f64 + quiet_zone as f64) * module_size;
+		subpaths.push(Subpath::new_rect(DVec2::new(left, 0.), DVec2::new(right, height)));
+	}
+	super::VectorData::from_subpaths(subpaths)
+}
+
 // TODO(TrueDoctor): I removed the Arc requirement we should think about when it makes sense to use it vs making a generic value node
 #[derive(Debug, Clone)]
 pub struct PathGenerator<ColinearManipulators> {
@@ -138,6 +277,72 @@ fn generate_path(path_data: Vec<Subpath<ManipulatorGroupId>>, colinear_manipulat
 	vector_data
 }
 
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::value::ClonedNode;
+
+	fn arc(sweep_angle: f64, arc_type: ArcType) -> VectorData {
+		ArcGenerator {
+			radius: ClonedNode(10.),
+			start_angle: ClonedNode(0.),
+			sweep_angle: ClonedNode(sweep_angle),
+			arc_type: ClonedNode(arc_type),
+		}
+		.eval(())
+	}
+
+	#[test]
+	fn arc_follows_the_circle() {
+		let subpath = arc(90., ArcType::Open).stroke_bezier_paths().next().unwrap();
+		assert!(!subpath.closed());
+		let groups = subpath.manipulator_groups();
+		assert!(groups[0].anchor.abs_diff_eq(DVec2::new(10., 0.), 1e-9));
+		assert!(groups.last().unwrap().anchor.abs_diff_eq(DVec2::new(0., 10.), 1e-9));
+		// Everywhere along the arc stays within a tiny distance of the circle
+		for index in 0..=20 {
+			let point = subpath.evaluate(bezier_rs::SubpathTValue::GlobalParametric(index as f64 / 20.));
+			assert!((point.length() - 10.).abs() < 1e-3, "{point} is off the circle");
+		}
+	}
+
+	#[test]
+	fn arc_types_close_the_arc() {
+		let closed = arc(90., ArcType::Closed).stroke_bezier_paths().next().unwrap();
+		assert!(closed.closed());
+		assert_eq!(closed.len(), 3);
+
+		let pie_slice = arc(90., ArcType::PieSlice).stroke_bezier_paths().next().unwrap();
+		assert!(pie_slice.closed());
+		assert_eq!(pie_slice.manipulator_groups().last().unwrap().anchor, DVec2::ZERO);
+
+		// A full circle has no slice to cut out, so it's left as a circle without a point at the center
+		let circle = arc(360., ArcType::PieSlice).stroke_bezier_paths().next().unwrap();
+		assert!(circle.closed());
+		assert_eq!(circle.len(), 8);
+	}
+
+	#[test]
+	fn spiral_grows_from_inner_to_outer_radius() {
+		let spiral = SpiralGenerator {
+			turns: ClonedNode(2.5),
+			inner_radius: ClonedNode(5.),
+			outer_radius: ClonedNode(30.),
+		}
+		.eval(());
+		let subpath = spiral.stroke_bezier_paths().next().unwrap();
+		assert_eq!(subpath.len_segments(), 20);
+		assert!(subpath.manipulator_groups()[0].anchor.abs_diff_eq(DVec2::new(5., 0.), 1e-9));
+		assert!(subpath.manipulator_groups().last().unwrap().anchor.abs_diff_eq(DVec2::new(-30., 0.), 1e-9));
+		// Halfway around, the spiral has grown by a fifth of the way to the outer radius
+		let halfway = subpath.evaluate(bezier_rs::SubpathTValue::Parametric { segment_index: 4, t: 0. });
+		assert!(halfway.abs_diff_eq(DVec2::new(-10., 0.), 1e-9));
+		let between = subpath.evaluate(bezier_rs::SubpathTValue::Parametric { segment_index: 1, t: 0.5 });
+		let angle = TAU * 3. / 16.;
+		assert!((between.length() - (5. + 25. * angle / (2.5 * TAU))).abs() < 0.05);
+	}
+}
+
 // #[derive(Debug, Clone, Copy)]
 // pub struct BlitSubpath<P> {
 // 	path_data: P,
@@ -160,4 +365,35 @@ fn generate_path(path_data: Vec<Subpath<ManipulatorGroupId>>, colinear_manipulat
 // 	}
 
 // 	base_image
-// }
+// 
+
+	#[test]
+	fn qr_code_sits_inside_its_quiet_zone() {
+		let qr_code = QrCodeGenerator {
+			text: ClonedNode(String::from("HELLO WORLD")),
+			error_correction: ClonedNode(QrErrorCorrection::Medium),
+			quiet_zone: ClonedNode(4),
+			module_size: ClonedNode(2.),
+		}
+		.eval(());
+		// The finder patterns reach the corners of the 21 module code, inside the 4 module quiet zone on every side
+		let [min, max] = qr_code.bounding_box().unwrap();
+		assert_eq!(min, DVec2::splat(8.));
+		assert_eq!(max, DVec2::splat(50.));
+
+		let barcode = |text: &str| {
+			BarcodeGenerator {
+				text: ClonedNode(String::from(text)),
+				symbology: ClonedNode(BarcodeSymbology::Ean13),
+				quiet_zone: ClonedNode(11),
+				module_size: ClonedNode(1.),
+				height: ClonedNode(50.),
+			}
+			.eval(())
+		};
+		// Each digit has two bars, and the guards have two each on the sides and in the middle
+		assert_eq!(barcode("400638133393").stroke_bezier_paths().count(), 12 * 2 + 3 * 2);
+		assert_eq!(barcode("400638133393").bounding_box().unwrap(), [DVec2::new(11., 0.), DVec2::new(106., 50.)]);
+		assert_eq!(barcode("not a number").stroke_bezier_paths().count(), 0);
+	}
+}
@@ -0,0 +1,189 @@
+//! Lindenmayer systems, which grow plants and fractals by repeatedly rewriting a string of symbols with a set of rules and then drawing it with a turtle.
+
+use super::VectorData;
+use crate::uuid::ManipulatorGroupId;
+use crate::Node;
+
+use bezier_rs::Subpath;
+use glam::DVec2;
+use rand::{Rng, SeedableRng};
+
+/// The longest string the rules may grow to, beyond which further iterations are left out since they'd take too long to draw.
+const MAX_SYMBOLS: usize = 1_000_000;
+
+/// The rules rewriting each symbol, parsed from lines (or `;` separated entries) like `F=F+F--F+F` or `X -> F[+X]F[-X]+X`.
+/// Symbols without a rule are kept as they are.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LSystemRules {
+	rules: Vec<(char, String)>,
+}
+
+impl LSystemRules {
+	pub fn parse(rules: &str) -> Self {
+		let rules = rules
+			.split(['\n', ';'])
+			.filter_map(|rule| {
+				// A predecessor is a single symbol, so the separator can be told apart from the same characters in the successor
+				let rule = rule.trim_start();
+				let symbol = rule.chars().next()?;
+				let rest = rule[symbol.len_utf8()..].trim_start();
+				let successor = rest.strip_prefix("->").or_else(|| rest.strip_prefix('='))?;
+				Some((symbol, successor.chars().filter(|c| !c.is_whitespace()).collect()))
+			})
+			.collect();
+		Self { rules }
+	}
+
+	/// Rewrites every symbol of the axiom at once, the given number of times, stopping early if the string would grow too long.
+	pub fn expand(&self, axiom: &str, iterations: u32) -> String {
+		let mut current = axiom.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+		for _ in 0..iterations {
+			let mut next = String::with_capacity(current.len() * 2);
+			for symbol in current.chars() {
+				match self.rules.iter().find(|(predecessor, _)| *predecessor == symbol) {
+					Some((_, successor)) => next.push_str(successor),
+					None => next.push(symbol),
+				}
+				if next.len() > MAX_SYMBOLS {
+					return current;
+				}
+			}
+			if next == current {
+				break;
+			}
+			current = next;
+		}
+		current
+	}
+}
+
+/// Draws the symbols with a turtle starting at the origin facing up, which understands:
+/// - `F` and `G` to move forward drawing a line, and `f` to move forward without drawing
+/// - `+` and `-` to turn counterclockwise and clockwise by the angle (in degrees), and `|` to turn around
+/// - `[` and `]` to save and return to the position and heading, for drawing branches
+///
+/// Other symbols are only used by the rules and are skipped over.
+/// The angle and length of each move vary randomly by up to the angle randomness (in degrees) and the length randomness (as a fraction of the length).
+pub fn draw_l_system(symbols: &str, angle: f64, length: f64, angle_randomness: f64, length_randomness: f64, seed: u32) -> Vec<Subpath<ManipulatorGroupId>> {
+	let mut rng = rand::rngs::StdRng::seed_from_u64(seed as u64);
+	let mut jitter = |amount: f64| if amount == 0. { 0. } else { (rng.gen::<f64>() * 2. - 1.) * amount };
+
+	let mut position = DVec2::ZERO;
+	// Measured clockwise from the right, as the y-axis points down
+	let mut heading = -90_f64;
+	let mut stack = Vec::new();
+	let mut subpaths = Vec::new();
+	let mut line = vec![position];
+
+	let mut finish_line = |line: &mut Vec<DVec2>, position: DVec2| {
+		if line.len() > 1 {
+			subpaths.push(Subpath::from_anchors(line.drain(..), false));
+		}
+		line.clear();
+		line.push(position);
+	};
+
+	for symbol in symbols.chars() {
+		match symbol {
+			'F' | 'G' | 'f' => {
+				let distance = length * (1. + jitter(length_randomness.clamp(0., 1.)));
+				position += DVec2::from_angle(heading.to_radians()) * distance;
+				if symbol == 'f' {
+					finish_line(&mut line, position);
+				} else {
+					line.push(position);
+				}
+			}
+			'+' => heading -= angle + jitter(angle_randomness),
+			'-' => heading += angle + jitter(angle_randomness),
+			'|' => heading += 180.,
+			'[' => stack.push((position, heading)),
+			']' => {
+				if let Some((saved_position, saved_heading)) = stack.pop() {
+					position = saved_position;
+					heading = saved_heading;
+					finish_line(&mut line, position);
+				}
+			}
+			_ => {}
+		}
+	}
+	finish_line(&mut line, position);
+
+	subpaths
+}
+
+#[derive(Debug, Clone)]
+pub struct LSystemNode<Axiom, Rules, Iterations, Angle, Length, AngleRandomness, LengthRandomness, Seed> {
+	axiom: Axiom,
+	rules: Rules,
+	iterations: Iterations,
+	angle: Angle,
+	length: Length,
+	angle_randomness: AngleRandomness,
+	length_randomness: LengthRandomness,
+	seed: Seed,
+}
+
+/// Grows the axiom by applying the rules the given number of times, then draws the result as lines with a turtle (see [`draw_l_system`]).
+#[node_macro::node_fn(LSystemNode)]
+fn l_system(_input: (), axiom: String, rules: String, iterations: u32, angle: f64, length: f64, angle_randomness: f64, length_randomness: f64, seed: u32) -> VectorData {
+	let symbols = LSystemRules::parse(&rules).expand(&axiom, iterations);
+	VectorData::from_subpaths(draw_l_system(&symbols, angle, length, angle_randomness, length_randomness, seed))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::value::ClonedNode;
+
+	#[test]
+	fn rules_rewrite_every_symbol_at_once() {
+		let rules = LSystemRules::parse("A -> AB\nB=A; not a rule");
+		// The lengths of the strings follow the Fibonacci sequence, as in Lindenmayer's model of algae
+		assert_eq!(rules.expand("A", 4), "ABAABABA");
+		assert_eq!(rules.expand("A", 0), "A");
+
+		// The string stops growing before it becomes too long to draw
+		let doubling = LSystemRules::parse("F=FF");
+		assert_eq!(doubling.expand("F", 100).len(), 1 << 19);
+	}
+
+	#[test]
+	fn turtle_draws_branches() {
+		let subpaths = draw_l_system("F[+F]-fF", 90., 10., 0., 0., 0);
+		let anchors = subpaths.iter().map(|subpath| subpath.anchors()).collect::<Vec<_>>();
+		let close = |a: &[DVec2], b: &[DVec2]| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.abs_diff_eq(*b, 1e-9));
+
+		// Up the trunk and left along the branch, then back to the trunk to turn right and move before drawing again
+		assert_eq!(anchors.len(), 2);
+		assert!(close(&anchors[0], &[DVec2::ZERO, DVec2::new(0., -10.), DVec2::new(-10., -10.)]));
+		assert!(close(&anchors[1], &[DVec2::new(10., -10.), DVec2::new(20., -10.)]));
+	}
+
+	#[test]
+	fn koch_curve_and_randomness() {
+		let koch = |angle_randomness: f64, seed: u32| {
+			LSystemNode {
+				axiom: ClonedNode(String::from("F")),
+				rules: ClonedNode(String::from("F=F+F--F+F")),
+				iterations: ClonedNode(3),
+				angle: ClonedNode(60.),
+				length: ClonedNode(1.),
+				angle_randomness: ClonedNode(angle_randomness),
+				length_randomness: ClonedNode(0.),
+				seed: ClonedNode(seed),
+			}
+			.eval(())
+		};
+
+		let curve = koch(0., 0).stroke_bezier_paths().next().unwrap();
+		assert_eq!(curve.len_segments(), 64);
+		assert!(curve.anchors().last().unwrap().abs_diff_eq(DVec2::new(0., -27.), 1e-9));
+
+		// The same seed always grows the same way
+		let end = |seed| *koch(5., seed).stroke_bezier_paths().next().unwrap().anchors().last().unwrap();
+		assert_eq!(end(1), end(1));
+		assert_ne!(end(1), end(2));
+	}
+}
@@ -10,6 +10,18 @@ pub enum CentroidType {
 	Length,
 }
 
+/// How the ends of an arc are joined, if at all.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum ArcType {
+	/// Only the curve of the arc, with its ends left open.
+	#[default]
+	Open,
+	/// The curve of the arc closed by a straight line between its ends.
+	Closed,
+	/// The curve of the arc closed by straight lines from its ends to the center, like a slice of a pie.
+	PieSlice,
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
 pub enum BooleanOperation {
 	#[default]
@@ -1,6 +1,11 @@
+pub mod barcode;
 pub mod brush_stroke;
+mod cutting;
 pub mod generator_nodes;
+pub mod l_system;
 pub mod misc;
+pub mod point_cloud;
+pub mod voronoi;
 
 pub mod style;
 pub use style::PathStyle;
@@ -0,0 +1,532 @@
+//! Point clouds, sets of positions generated separately from the artwork placed at them, so the same points can drive any instancing or scattering node.
+
+use super::style::{GradientInterpolation, GradientStops};
+use super::{PointId, VectorData};
+use crate::transform::{Transform, TransformMut};
+use crate::{Color, Node};
+
+use bezier_rs::{Subpath, SubpathTValue};
+use dyn_any::{DynAny, StaticType};
+use glam::{DAffine2, DVec2};
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashMap};
+
+/// The most points a sampling node generates, to keep a tiny spacing from freezing the graph.
+const MAX_POINTS: usize = 100_000;
+
+/// How many random candidates are tried for each point a Poisson-disk sampling asks for, before giving up on finding room for more.
+const POISSON_DISK_ATTEMPTS: usize = 30;
+
+/// A set of positions, in the coordinates given by its transform, and any attributes giving each point its own values.
+#[derive(Clone, Debug, Default, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointCloud {
+	pub positions: Vec<DVec2>,
+	pub transform: DAffine2,
+	/// Values by name, each with one value for every point.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub attributes: BTreeMap<String, PointAttribute>,
+}
+
+impl core::hash::Hash for PointCloud {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.positions.iter().for_each(|position| position.to_array().iter().for_each(|x| x.to_bits().hash(state)));
+		self.transform.to_cols_array().iter().for_each(|x| x.to_bits().hash(state));
+		self.attributes.hash(state);
+	}
+}
+
+impl PointCloud {
+	pub const EMPTY: Self = Self {
+		positions: Vec::new(),
+		transform: DAffine2::IDENTITY,
+		attributes: BTreeMap::new(),
+	};
+
+	/// The attribute multiplying the size of the copies placed at the points, uniformly (for a float) or along each axis (for a vec2).
+	pub const SCALE: &'static str = "scale";
+	/// The attribute rotating the copies placed at the points, in degrees.
+	pub const ROTATION: &'static str = "rotation";
+	/// The attribute moving the copies placed at the points away from them.
+	pub const OFFSET: &'static str = "offset";
+	/// The attribute filling the vector shapes copied to the points.
+	pub const COLOR: &'static str = "color";
+
+	pub fn from_positions(positions: Vec<DVec2>, transform: DAffine2) -> Self {
+		Self {
+			positions,
+			transform,
+			attributes: BTreeMap::new(),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.positions.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.positions.is_empty()
+	}
+}
+
+/// The values of an attribute, one for each point of a point cloud.
+#[derive(Clone, Debug, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PointAttribute {
+	Float(Vec<f64>),
+	Vec2(Vec<DVec2>),
+	Color(Vec<Color>),
+}
+
+impl core::hash::Hash for PointAttribute {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		core::mem::discriminant(self).hash(state);
+		match self {
+			PointAttribute::Float(values) => values.iter().for_each(|value| value.to_bits().hash(state)),
+			PointAttribute::Vec2(values) => values.iter().for_each(|value| value.to_array().iter().for_each(|x| x.to_bits().hash(state))),
+			PointAttribute::Color(values) => values.hash(state),
+		}
+	}
+}
+
+impl PointAttribute {
+	/// The value of the point as a number, if the attribute holds numbers.
+	pub fn float(&self, index: usize) -> Option<f64> {
+		match self {
+			PointAttribute::Float(values) => values.get(index).copied(),
+			_ => None,
+		}
+	}
+
+	/// The value of the point as a vector, with a number used for both components.
+	pub fn vec2(&self, index: usize) -> Option<DVec2> {
+		match self {
+			PointAttribute::Float(values) => values.get(index).map(|&value| DVec2::splat(value)),
+			PointAttribute::Vec2(values) => values.get(index).copied(),
+			PointAttribute::Color(_) => None,
+		}
+	}
+
+	pub fn color(&self, index: usize) -> Option<Color> {
+		match self {
+			PointAttribute::Color(values) => values.get(index).copied(),
+			_ => None,
+		}
+	}
+}
+
+impl Transform for PointCloud {
+	fn transform(&self) -> DAffine2 {
+		self.transform
+	}
+}
+impl TransformMut for PointCloud {
+	fn transform_mut(&mut self) -> &mut DAffine2 {
+		&mut self.transform
+	}
+}
+
+/// Anything whose points artwork can be placed at by the instancing nodes.
+pub trait PointPositions {
+	/// The positions of the points, with their transform applied.
+	fn point_positions(&self) -> Vec<DVec2>;
+
+	/// The values of the attribute for each point, if the points have it.
+	fn point_attribute(&self, _name: &str) -> Option<&PointAttribute> {
+		None
+	}
+}
+
+impl PointPositions for PointCloud {
+	fn point_positions(&self) -> Vec<DVec2> {
+		self.positions.iter().map(|&position| self.transform.transform_point2(position)).collect()
+	}
+
+	fn point_attribute(&self, name: &str) -> Option<&PointAttribute> {
+		self.attributes.get(name)
+	}
+}
+
+/// The anchor points of the vector data.
+impl PointPositions for VectorData {
+	fn point_positions(&self) -> Vec<DVec2> {
+		self.point_domain.positions().iter().map(|&position| self.transform.transform_point2(position)).collect()
+	}
+}
+
+/// Where on a path points are sampled.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum PointRegion {
+	/// Along the outline of the path.
+	Outline,
+	/// Within the filled area of the path, using the nonzero fill rule.
+	#[default]
+	Inside,
+}
+
+impl core::fmt::Display for PointRegion {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			PointRegion::Outline => write!(f, "Outline"),
+			PointRegion::Inside => write!(f, "Inside"),
+		}
+	}
+}
+
+impl PointRegion {
+	pub fn list() -> [PointRegion; 2] {
+		[PointRegion::Outline, PointRegion::Inside]
+	}
+}
+
+/// How the points are spread over the region.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum PointSampling {
+	/// At the spacing apart along the outline, or on a square grid of the spacing inside.
+	Even,
+	/// Randomly but no closer together than the spacing, for a natural looking scatter.
+	#[default]
+	PoissonDisk,
+	/// Uniformly at random, which may leave clumps and gaps.
+	Random,
+}
+
+impl core::fmt::Display for PointSampling {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			PointSampling::Even => write!(f, "Even"),
+			PointSampling::PoissonDisk => write!(f, "Poisson-Disk"),
+			PointSampling::Random => write!(f, "Random"),
+		}
+	}
+}
+
+impl PointSampling {
+	pub fn list() -> [PointSampling; 3] {
+		[PointSampling::Even, PointSampling::PoissonDisk, PointSampling::Random]
+	}
+}
+
+/// The subpaths of vector data, which points can be sampled along or inside.
+struct SampledShape {
+	subpaths: Vec<Subpath<PointId>>,
+	/// The subpaths with the open ones closed, for testing which points are inside.
+	filled: Vec<Subpath<PointId>>,
+	lengths: Vec<f64>,
+	total_length: f64,
+	bounds: Option<[DVec2; 2]>,
+}
+
+impl SampledShape {
+	fn new(vector_data: &VectorData) -> Self {
+		let subpaths = vector_data.stroke_bezier_paths().collect::<Vec<_>>();
+		let lengths = subpaths.iter().map(|subpath| subpath.length(None)).collect::<Vec<_>>();
+		let total_length = lengths.iter().sum();
+		let bounds = subpaths.iter().filter_map(|subpath| subpath.bounding_box()).reduce(|a, b| [a[0].min(b[0]), a[1].max(b[1])]);
+		let filled = subpaths
+			.iter()
+			.map(|subpath| {
+				let mut filled = subpath.clone();
+				filled.set_closed(true);
+				filled
+			})
+			.collect();
+		Self {
+			subpaths,
+			filled,
+			lengths,
+			total_length,
+			bounds,
+		}
+	}
+
+	/// The point at the distance along the outline, measured through the subpaths one after another.
+	fn point_along(&self, mut distance: f64) -> Option<DVec2> {
+		for (subpath, &length) in self.subpaths.iter().zip(&self.lengths) {
+			if distance <= length && length > 0. {
+				return Some(subpath.evaluate(SubpathTValue::GlobalEuclidean(distance / length)));
+			}
+			distance -= length;
+		}
+		None
+	}
+
+	/// Whether the point is within the area filled with the nonzero fill rule, with open subpaths closed.
+	fn contains(&self, point: DVec2) -> bool {
+		self.filled.iter().map(|subpath| subpath.winding_order(point)).sum::<i32>() != 0
+	}
+
+	/// A point picked uniformly at random from the region, or `None` if the region is empty or a picked point missed it.
+	fn random_point(&self, region: PointRegion, rng: &mut impl Rng) -> Option<DVec2> {
+		match region {
+			PointRegion::Outline => (self.total_length > 0.).then(|| self.point_along(rng.gen::<f64>() * self.total_length)).flatten(),
+			PointRegion::Inside => {
+				let [min, max] = self.bounds?;
+				let point = min + DVec2::new(rng.gen(), rng.gen()) * (max - min);
+				self.contains(point).then_some(point)
+			}
+		}
+	}
+
+	fn even_points(&self, region: PointRegion, spacing: f64) -> Vec<DVec2> {
+		match region {
+			PointRegion::Outline => {
+				let mut points = Vec::new();
+				for (subpath, &length) in self.subpaths.iter().zip(&self.lengths) {
+					if length <= 0. {
+						continue;
+					}
+					// A closed subpath ends where it starts, so its last point would double up on its first
+					let steps = (length / spacing).floor() as usize;
+					let steps = if subpath.closed() && (steps as f64 * spacing - length).abs() < 1e-9 {
+						steps.saturating_sub(1)
+					} else {
+						steps
+					};
+					for step in 0..=steps.min(MAX_POINTS) {
+						points.push(subpath.evaluate(SubpathTValue::GlobalEuclidean((step as f64 * spacing / length).min(1.))));
+					}
+				}
+				points
+			}
+			PointRegion::Inside => {
+				let Some([min, max]) = self.bounds else { return Vec::new() };
+				// The grid is centered on the bounds, so the points sit evenly within symmetric shapes
+				let counts = ((max - min) / spacing).floor().max(DVec2::ONE);
+				if counts.x * counts.y > MAX_POINTS as f64 {
+					return Vec::new();
+				}
+				let start = (min + max) / 2. - (counts - 1.) * spacing / 2.;
+				(0..counts.y as usize)
+					.flat_map(|row| (0..counts.x as usize).map(move |column| start + DVec2::new(column as f64, row as f64) * spacing))
+					.filter(|&point| self.contains(point))
+					.collect()
+			}
+		}
+	}
+}
+
+/// Places points over the path no closer together than the spacing, by trying random points and keeping those with room around them.
+fn poisson_disk(count: usize, spacing: f64, mut candidate: impl FnMut() -> Option<DVec2>) -> Vec<DVec2> {
+	// Each accepted point is filed in a grid of cells the size of the spacing, so only the neighboring cells need checking
+	let cell_of = |point: DVec2| ((point.x / spacing).floor() as i64, (point.y / spacing).floor() as i64);
+	let mut grid: HashMap<(i64, i64), Vec<DVec2>> = HashMap::new();
+	let mut points = Vec::new();
+
+	for _ in 0..count.saturating_mul(POISSON_DISK_ATTEMPTS) {
+		if points.len() >= count {
+			break;
+		}
+		let Some(point) = candidate() else { continue };
+		let (x, y) = cell_of(point);
+		let crowded = (-1..=1).any(|dy| {
+			(-1..=1).any(|dx| {
+				grid.get(&(x + dx, y + dy))
+					.is_some_and(|cell| cell.iter().any(|other| other.distance_squared(point) < spacing * spacing))
+			})
+		});
+		if !crowded {
+			grid.entry((x, y)).or_default().push(point);
+			points.push(point);
+		}
+	}
+	points
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SamplePointCloudNode<Region, Sampling, Spacing, Count, Seed> {
+	region: Region,
+	sampling: Sampling,
+	spacing: Spacing,
+	count: Count,
+	seed: Seed,
+}
+
+/// Generates points along the outline of the input or inside its filled area, evenly at the spacing, randomly no closer than the spacing (Poisson-disk), or uniformly at random.
+/// The count limits the number of random points, and the seed picks a different random arrangement.
+/// The points are kept in the coordinates of the input, with its transform, so the spacing is measured before the transform.
+#[node_macro::node_fn(SamplePointCloudNode)]
+fn sample_point_cloud(vector_data: VectorData, region: PointRegion, sampling: PointSampling, spacing: f64, count: u32, seed: u32) -> PointCloud {
+	let shape = SampledShape::new(&vector_data);
+	let mut rng = rand::rngs::StdRng::seed_from_u64(seed as u64);
+	let count = (count as usize).min(MAX_POINTS);
+	let spacing_valid = spacing.is_finite() && spacing > 0.;
+
+	let positions = match sampling {
+		PointSampling::Even if spacing_valid => shape.even_points(region, spacing),
+		PointSampling::PoissonDisk if spacing_valid => poisson_disk(count, spacing, || shape.random_point(region, &mut rng)),
+		PointSampling::Random => (0..count.saturating_mul(POISSON_DISK_ATTEMPTS))
+			.filter_map(|_| shape.random_point(region, &mut rng))
+			.take(count)
+			.collect(),
+		_ => Vec::new(),
+	};
+
+	PointCloud::from_positions(positions, vector_data.transform)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PointCloudFromAnchorsNode;
+
+/// The anchor points of the vector data as a point cloud.
+#[node_macro::node_fn(PointCloudFromAnchorsNode)]
+fn point_cloud_from_anchors(vector_data: VectorData) -> PointCloud {
+	PointCloud::from_positions(vector_data.point_domain.positions().to_vec(), vector_data.transform)
+}
+
+#[derive(Debug, Clone)]
+pub struct RandomPointAttributeNode<Name, Min, Max, Seed> {
+	name: Name,
+	min: Min,
+	max: Max,
+	seed: Seed,
+}
+
+/// Gives each point a random number between the min and max as the named attribute, such as `scale` or `rotation` to vary the copies placed at the points.
+#[node_macro::node_fn(RandomPointAttributeNode)]
+fn random_point_attribute(mut point_cloud: PointCloud, name: String, min: f64, max: f64, seed: u32) -> PointCloud {
+	let mut rng = rand::rngs::StdRng::seed_from_u64(seed as u64);
+	let values = (0..point_cloud.len()).map(|_| min + rng.gen::<f64>() * (max - min)).collect();
+	point_cloud.attributes.insert(name, PointAttribute::Float(values));
+	point_cloud
+}
+
+#[derive(Debug, Clone)]
+pub struct RandomVec2PointAttributeNode<Name, Min, Max, Seed> {
+	name: Name,
+	min: Min,
+	max: Max,
+	seed: Seed,
+}
+
+/// Gives each point a random vector, with each component between those of the min and max, as the named attribute, such as `offset` to jitter the copies placed at the points.
+#[node_macro::node_fn(RandomVec2PointAttributeNode)]
+fn random_vec2_point_attribute(mut point_cloud: PointCloud, name: String, min: DVec2, max: DVec2, seed: u32) -> PointCloud {
+	let mut rng = rand::rngs::StdRng::seed_from_u64(seed as u64);
+	let values = (0..point_cloud.len()).map(|_| min + DVec2::new(rng.gen(), rng.gen()) * (max - min)).collect();
+	point_cloud.attributes.insert(name, PointAttribute::Vec2(values));
+	point_cloud
+}
+
+#[derive(Debug, Clone)]
+pub struct GradientPointAttributeNode<Name, Gradient, Start, End> {
+	name: Name,
+	gradient: Gradient,
+	start: Start,
+	end: End,
+}
+
+/// Gives each point the color of the gradient where it lies between the start and end (in the coordinates of the points) as the named attribute, such as `color` to fill the copies placed at the points.
+#[node_macro::node_fn(GradientPointAttributeNode)]
+fn gradient_point_attribute(mut point_cloud: PointCloud, name: String, gradient: GradientStops, start: DVec2, end: DVec2) -> PointCloud {
+	let direction = end - start;
+	let length_squared = direction.length_squared();
+	let values = point_cloud
+		.positions
+		.iter()
+		.map(|&position| {
+			let factor = if length_squared > 0. { (position - start).dot(direction) / length_squared } else { 0. };
+			gradient.evaluate(factor.clamp(0., 1.), GradientInterpolation::Srgb)
+		})
+		.collect();
+	point_cloud.attributes.insert(name, PointAttribute::Color(values));
+	point_cloud
+}
+
+#[derive(Debug, Clone)]
+pub struct PointAttributeValuesNode<Name> {
+	name: Name,
+}
+
+/// The numbers of the named attribute for each point, or nothing if the points don't have it or it doesn't hold numbers.
+#[node_macro::node_fn(PointAttributeValuesNode)]
+fn point_attribute_values(point_cloud: PointCloud, name: String) -> Vec<f64> {
+	match point_cloud.attributes.get(&name) {
+		Some(PointAttribute::Float(values)) => values.clone(),
+		_ => Vec::new(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::value::{ClonedNode, CopiedNode};
+
+	fn sample(region: PointRegion, sampling: PointSampling, spacing: f64, count: u32) -> PointCloud {
+		let node = SamplePointCloudNode {
+			region: CopiedNode(region),
+			sampling: CopiedNode(sampling),
+			spacing: CopiedNode(spacing),
+			count: CopiedNode(count),
+			seed: CopiedNode(0),
+		};
+		let mut square = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::splat(100.)));
+		square.transform = DAffine2::from_translation(DVec2::new(1000., 0.));
+		node.eval(square)
+	}
+
+	#[test]
+	fn even_points_are_spaced_along_and_inside() {
+		// The outline is 400 long, so 40 points fit without doubling up where it closes
+		let outline = sample(PointRegion::Outline, PointSampling::Even, 10., 0);
+		assert_eq!(outline.len(), 40);
+		assert!(outline
+			.positions
+			.iter()
+			.all(|point| point.x.abs() < 1e-6 || point.y.abs() < 1e-6 || (point.x - 100.).abs() < 1e-6 || (point.y - 100.).abs() < 1e-6));
+
+		// The grid is centered, so its points sit 5 inside the edges
+		let inside = sample(PointRegion::Inside, PointSampling::Even, 10., 0);
+		assert_eq!(inside.len(), 100);
+		assert!(inside.positions.contains(&DVec2::splat(5.)));
+		// The points keep the transform of the input
+		assert_eq!(inside.point_positions()[0], DVec2::new(1005., 5.));
+	}
+
+	#[test]
+	fn poisson_disk_points_keep_their_distance() {
+		let cloud = sample(PointRegion::Inside, PointSampling::PoissonDisk, 15., 1000);
+		assert!(cloud.len() > 20);
+		for (index, a) in cloud.positions.iter().enumerate() {
+			assert!((0. ..=100.).contains(&a.x) && (0. ..=100.).contains(&a.y));
+			assert!(cloud.positions[index + 1..].iter().all(|b| a.distance(*b) >= 15.));
+		}
+
+		let random = sample(PointRegion::Outline, PointSampling::Random, 0., 25);
+		assert_eq!(random.len(), 25);
+		assert_eq!(random, sample(PointRegion::Outline, PointSampling::Random, 0., 25));
+	}
+
+	#[test]
+	fn attributes_give_each_point_its_own_values() {
+		let cloud = PointCloud::from_positions(vec![DVec2::ZERO, DVec2::new(5., 0.), DVec2::new(10., 0.)], DAffine2::IDENTITY);
+		let cloud = RandomPointAttributeNode {
+			name: ClonedNode(String::from(PointCloud::SCALE)),
+			min: CopiedNode(1.),
+			max: CopiedNode(2.),
+			seed: CopiedNode(0),
+		}
+		.eval(cloud);
+		let cloud = GradientPointAttributeNode {
+			name: ClonedNode(String::from(PointCloud::COLOR)),
+			gradient: ClonedNode(GradientStops::default()),
+			start: CopiedNode(DVec2::ZERO),
+			end: CopiedNode(DVec2::new(10., 0.)),
+		}
+		.eval(cloud);
+
+		let scales = PointAttributeValuesNode {
+			name: ClonedNode(String::from(PointCloud::SCALE)),
+		}
+		.eval(cloud.clone());
+		assert_eq!(scales.len(), 3);
+		assert!(scales.iter().all(|scale| (1. ..=2.).contains(scale)));
+		// A number can be read as a vector for scaling both axes alike
+		assert_eq!(cloud.point_attribute(PointCloud::SCALE).and_then(|scale| scale.vec2(1)), Some(DVec2::splat(scales[1])));
+
+		let colors = cloud.point_attribute(PointCloud::COLOR).unwrap();
+		assert_eq!(colors.color(0), Some(Color::BLACK));
+		assert_eq!(colors.color(2), Some(Color::WHITE));
+		assert_eq!(colors.float(0), None);
+	}
+}
@@ -1,7 +1,7 @@
 //! Contains stylistic options for SVG elements.
 
 use crate::consts::{LAYER_OUTLINE_STROKE_COLOR, LAYER_OUTLINE_STROKE_WEIGHT};
-use crate::Color;
+use crate::{AlphaBlending, Color};
 
 use dyn_any::{DynAny, StaticType};
 use glam::{DAffine2, DVec2};
@@ -12,6 +12,9 @@ use std::fmt::{self, Display, Write};
 /// A value of 3 would correspond to a precision of 10^-3.
 const OPACITY_PRECISION: usize = 3;
 
+/// The number of stops added between each pair of stops of a gradient mixed in a color space other than sRGB, since renderers mix the colors between stops in sRGB.
+const BAKED_GRADIENT_SUBDIVISIONS: usize = 8;
+
 fn format_opacity(attribute: &str, opacity: f32) -> String {
 	if (opacity - 1.).abs() > 10_f32.powi(-(OPACITY_PRECISION as i32)) {
 		format!(r#" {attribute}="{opacity:.OPACITY_PRECISION$}""#)
@@ -27,6 +30,97 @@ pub enum GradientType {
 	Radial,
 }
 
+/// Which way around the color wheel the hues of a gradient mixed in a color space with hues go, like the CSS `<hue-interpolation-method>`.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub enum HueInterpolation {
+	/// Goes the short way around, never passing through more than half of the hues.
+	#[default]
+	Shorter,
+	/// Goes the long way around, passing through the hues that the shorter way skips.
+	Longer,
+	/// Goes around in the direction of increasing hue angle.
+	Increasing,
+	/// Goes around in the direction of decreasing hue angle.
+	Decreasing,
+}
+
+impl HueInterpolation {
+	/// The hue angles in degrees to mix between, with the second moved by whole turns to go around the color wheel the chosen way.
+	fn adjust(self, start: f32, end: f32) -> (f32, f32) {
+		let difference = (end - start).rem_euclid(360.);
+		let difference = match self {
+			Self::Shorter if difference > 180. => difference - 360.,
+			Self::Longer if difference > 0. && difference < 180. => difference - 360.,
+			Self::Decreasing if difference > 0. => difference - 360.,
+			_ => difference,
+		};
+		(start, start + difference)
+	}
+}
+
+/// The color space that the colors between the stops of a gradient are mixed in, which changes how the colors look halfway between them.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Hash, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
+pub enum GradientInterpolation {
+	/// Mixes the gamma encoded sRGB values, as SVG and CSS gradients do, or linear RGB in documents compositing in linear RGB.
+	#[default]
+	Srgb,
+	/// Mixes the light of the colors, which keeps the middle of gradients between saturated colors bright.
+	LinearRgb,
+	/// Mixes the colors in a perceptual space, giving even steps in lightness and keeping the middle from turning gray.
+	Oklab,
+	/// Mixes the lightness, chroma, and hue of OKLab, turning around the color wheel from one hue to the next.
+	Oklch(HueInterpolation),
+	/// Mixes the colors in CIELAB, the older perceptual color space.
+	Lab,
+}
+
+impl GradientInterpolation {
+	/// The color space that is actually mixed in, since sRGB gives way to linear RGB in documents compositing in linear RGB.
+	pub fn with_linear_compositing(self, linear_compositing: bool) -> Self {
+		match self {
+			Self::Srgb if linear_compositing => Self::LinearRgb,
+			interpolation => interpolation,
+		}
+	}
+
+	/// Mixes the colors, which are gamma encoded sRGB, at the fraction `time` of the way from `start` to `end`.
+	pub fn mix(self, start: Color, end: Color, time: f32) -> Color {
+		let lerp = |a: f32, b: f32| a + (b - a) * time;
+		let (start_linear, end_linear) = (start.to_linear_srgb(), end.to_linear_srgb());
+		let alpha = lerp(start.a(), end.a());
+
+		match self {
+			Self::Srgb => start.lerp(&end, time),
+			Self::LinearRgb => start_linear.lerp(&end_linear, time).to_gamma_srgb(),
+			Self::Oklab => {
+				let ([l1, a1, b1, _], [l2, a2, b2, _]) = (start_linear.to_oklab(), end_linear.to_oklab());
+				Color::from_oklab(lerp(l1, l2), lerp(a1, a2), lerp(b1, b2), alpha).to_gamma_srgb()
+			}
+			Self::Lab => {
+				let ([l1, a1, b1, _], [l2, a2, b2, _]) = (start_linear.to_cielab(), end_linear.to_cielab());
+				Color::from_cielab(lerp(l1, l2), lerp(a1, a2), lerp(b1, b2), alpha).to_gamma_srgb()
+			}
+			Self::Oklch(hue_interpolation) => {
+				let ([l1, a1, b1, _], [l2, a2, b2, _]) = (start_linear.to_oklab(), end_linear.to_oklab());
+				let (chroma1, chroma2) = ((a1 * a1 + b1 * b1).sqrt(), (a2 * a2 + b2 * b2).sqrt());
+				let (hue1, hue2) = (b1.atan2(a1).to_degrees(), b2.atan2(a2).to_degrees());
+
+				// Grays have no hue, so like CSS they take on the hue of the color they're mixed with rather than turning through red
+				const ACHROMATIC: f32 = 1e-4;
+				let (hue1, hue2) = match (chroma1 < ACHROMATIC, chroma2 < ACHROMATIC) {
+					(true, false) => (hue2, hue2),
+					(false, true) => (hue1, hue1),
+					_ => (hue1, hue2),
+				};
+				let (hue1, hue2) = hue_interpolation.adjust(hue1, hue2);
+
+				let (chroma, hue) = (lerp(chroma1, chroma2), lerp(hue1, hue2).to_radians());
+				Color::from_oklab(lerp(l1, l2), chroma * hue.cos(), chroma * hue.sin(), alpha).to_gamma_srgb()
+			}
+		}
+	}
+}
+
 // TODO: Someday we could switch this to a Box[T] to avoid over-allocation
 /// A list of colors associated with positions (in the range 0 to 1) along a gradient.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
@@ -42,6 +136,46 @@ impl std::hash::Hash for GradientStops {
 	}
 }
 
+impl GradientStops {
+	/// The color at the position along the gradient, mixed in the color space of the interpolation.
+	pub fn evaluate(&self, position: f64, interpolation: GradientInterpolation) -> Color {
+		let Some(&(first_position, first_color)) = self.0.first() else {
+			return Color::TRANSPARENT;
+		};
+		if position <= first_position {
+			return first_color;
+		}
+
+		let Some(index) = self.0.iter().position(|(stop_position, _)| *stop_position > position) else {
+			return self.0[self.0.len() - 1].1;
+		};
+		let ((start_position, start_color), (end_position, end_color)) = (self.0[index - 1], self.0[index]);
+		let time = ((position - start_position) / (end_position - start_position)).clamp(0., 1.) as f32;
+
+		interpolation.mix(start_color, end_color, time)
+	}
+
+	/// Adds stops between each pair of stops so a renderer mixing the colors in sRGB, as SVG does, closely matches the gradient mixed in the color space of the interpolation.
+	pub fn baked(&self, interpolation: GradientInterpolation) -> Self {
+		if interpolation == GradientInterpolation::Srgb {
+			return self.clone();
+		}
+
+		let mut stops = Vec::with_capacity(self.0.len() * (BAKED_GRADIENT_SUBDIVISIONS + 1));
+		for pair in self.0.windows(2) {
+			let ((start_position, start_color), (end_position, _)) = (pair[0], pair[1]);
+			stops.push((start_position, start_color));
+			for step in 1..=BAKED_GRADIENT_SUBDIVISIONS {
+				let position = start_position + (end_position - start_position) * step as f64 / (BAKED_GRADIENT_SUBDIVISIONS + 1) as f64;
+				stops.push((position, self.evaluate(position, interpolation)));
+			}
+		}
+		stops.extend(self.0.last().copied());
+
+		Self(stops)
+	}
+}
+
 impl Default for GradientStops {
 	fn default() -> Self {
 		Self(vec![(0., Color::BLACK), (1., Color::WHITE)])
@@ -59,6 +193,8 @@ pub struct Gradient {
 	pub start: DVec2,
 	pub end: DVec2,
 	pub transform: DAffine2,
+	#[serde(default)]
+	pub interpolation: GradientInterpolation,
 }
 
 impl Default for Gradient {
@@ -69,6 +205,7 @@ impl Default for Gradient {
 			start: DVec2::new(0., 0.5),
 			end: DVec2::new(1., 0.5),
 			transform: DAffine2::IDENTITY,
+			interpolation: GradientInterpolation::default(),
 		}
 	}
 }
@@ -84,6 +221,7 @@ impl core::hash::Hash for Gradient {
 			.for_each(|x| x.to_bits().hash(state));
 		self.stops.0.iter().for_each(|(_, color)| color.hash(state));
 		self.gradient_type.hash(state);
+		self.interpolation.hash(state);
 	}
 }
 
@@ -96,9 +234,15 @@ impl Gradient {
 			stops: GradientStops(vec![(0., start_color), (1., end_color)]),
 			transform,
 			gradient_type,
+			interpolation: GradientInterpolation::default(),
 		}
 	}
 
+	/// The color at the position along the gradient, mixed in linear RGB when the document composites in linear RGB and the gradient mixes in sRGB.
+	pub fn evaluate(&self, position: f64, linear_compositing: bool) -> Color {
+		self.stops.evaluate(position, self.interpolation.with_linear_compositing(linear_compositing))
+	}
+
 	pub fn lerp(&self, other: &Self, time: f64) -> Self {
 		let start = self.start + (other.start - self.start) * time;
 		let end = self.end + (other.end - self.end) * time;
@@ -116,6 +260,7 @@ impl Gradient {
 			.collect::<Vec<_>>();
 		let stops = GradientStops(stops);
 		let gradient_type = if time < 0.5 { self.gradient_type } else { other.gradient_type };
+		let interpolation = if time < 0.5 { self.interpolation } else { other.interpolation };
 
 		Self {
 			start,
@@ -123,17 +268,20 @@ impl Gradient {
 			transform,
 			stops,
 			gradient_type,
+			interpolation,
 		}
 	}
 
 	/// Adds the gradient def through mutating the first argument, returning the gradient ID.
-	fn render_defs(&self, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2]) -> u64 {
+	/// The colors between the stops are baked into extra stops unless they're mixed in sRGB, which is the only color space SVG gradients can mix in.
+	fn render_defs(&self, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2], linear_compositing: bool) -> u64 {
 		let bound_transform = DAffine2::from_scale_angle_translation(bounds[1] - bounds[0], 0., bounds[0]);
 		let transformed_bound_transform = DAffine2::from_scale_angle_translation(transformed_bounds[1] - transformed_bounds[0], 0., transformed_bounds[0]);
 		let updated_transform = multiplied_transform * bound_transform;
 
+		let stops = self.stops.baked(self.interpolation.with_linear_compositing(linear_compositing));
 		let mut stop = String::new();
-		for (position, color) in self.stops.0.iter() {
+		for (position, color) in stops.0.iter() {
 			let _ = write!(stop, r##"<stop offset="{}" stop-color="#{}" />"##, position, color.with_alpha(color.a()).rgba_hex());
 		}
 
@@ -172,8 +320,8 @@ impl Gradient {
 		gradient_id
 	}
 
-	/// Insert a stop into the gradient, the index if successful
-	pub fn insert_stop(&mut self, mouse: DVec2, transform: DAffine2) -> Option<usize> {
+	/// Insert a stop into the gradient, the index if successful. Its color is where it's inserted along the gradient, so inserting it doesn't change the gradient's look.
+	pub fn insert_stop(&mut self, mouse: DVec2, transform: DAffine2, linear_compositing: bool) -> Option<usize> {
 		// Transform the start and end positions to the same coordinate space as the mouse.
 		let (start, end) = (transform.transform_point2(self.start), transform.transform_point2(self.end));
 
@@ -185,24 +333,14 @@ impl Gradient {
 			return None;
 		}
 
-		// Compute the color of the inserted stop
-		let get_color = |index: usize, time: f64| match (self.stops.0[index].1, self.stops.0.get(index + 1).map(|(_, c)| *c)) {
-			// Lerp between the nearest colors if applicable
-			(a, Some(b)) => a.lerp(
-				&b,
-				((time - self.stops.0[index].0) / self.stops.0.get(index + 1).map(|end| end.0 - self.stops.0[index].0).unwrap_or_default()) as f32,
-			),
-			// Use the start or the end color if applicable
-			(v, _) => v,
-		};
-
 		// Compute the correct index to keep the positions in order
 		let mut index = 0;
 		while self.stops.0.len() > index && self.stops.0[index].0 <= new_position {
 			index += 1;
 		}
 
-		let new_color = get_color(index - 1, new_position);
+		// Compute the color of the inserted stop
+		let new_color = self.evaluate(new_position, linear_compositing);
 
 		// Insert the new stop
 		self.stops.0.insert(index, (new_position, new_color));
@@ -274,12 +412,12 @@ impl Fill {
 	}
 
 	/// Renders the fill, adding necessary defs through mutating the first argument.
-	pub fn render(&self, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2]) -> String {
+	pub fn render(&self, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2], linear_compositing: bool) -> String {
 		match self {
 			Self::None => r#" fill="none""#.to_string(),
 			Self::Solid(color) => format!(r##" fill="#{}"{}"##, color.rgb_hex(), format_opacity("fill-opacity", color.a())),
 			Self::Gradient(gradient) => {
-				let gradient_id = gradient.render_defs(svg_defs, multiplied_transform, bounds, transformed_bounds);
+				let gradient_id = gradient.render_defs(svg_defs, multiplied_transform, bounds, transformed_bounds, linear_compositing);
 				format!(r##" fill="url('#{gradient_id}')""##)
 			}
 		}
@@ -414,6 +552,26 @@ impl Display for LineJoin {
 	}
 }
 
+/// Where the stroke of a closed path is placed relative to the path. Open paths are always stroked along their center.
+#[repr(C)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum StrokeAlign {
+	#[default]
+	Center,
+	Inside,
+	Outside,
+}
+
+impl Display for StrokeAlign {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			StrokeAlign::Center => write!(f, "Center"),
+			StrokeAlign::Inside => write!(f, "Inside"),
+			StrokeAlign::Outside => write!(f, "Outside"),
+		}
+	}
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, DynAny, specta::Type)]
 pub struct Stroke {
@@ -426,6 +584,8 @@ pub struct Stroke {
 	pub line_cap: LineCap,
 	pub line_join: LineJoin,
 	pub line_join_miter_limit: f64,
+	#[serde(default)]
+	pub align: StrokeAlign,
 }
 
 impl core::hash::Hash for Stroke {
@@ -438,6 +598,7 @@ impl core::hash::Hash for Stroke {
 		self.line_cap.hash(state);
 		self.line_join.hash(state);
 		self.line_join_miter_limit.to_bits().hash(state);
+		self.align.hash(state);
 	}
 }
 
@@ -451,6 +612,7 @@ impl Stroke {
 			line_cap: LineCap::Butt,
 			line_join: LineJoin::Miter,
 			line_join_miter_limit: 4.,
+			align: StrokeAlign::Center,
 		}
 	}
 
@@ -463,6 +625,7 @@ impl Stroke {
 			line_cap: if time < 0.5 { self.line_cap } else { other.line_cap },
 			line_join: if time < 0.5 { self.line_join } else { other.line_join },
 			line_join_miter_limit: self.line_join_miter_limit + (other.line_join_miter_limit - self.line_join_miter_limit) * time,
+			align: if time < 0.5 { self.align } else { other.align },
 		}
 	}
 
@@ -501,13 +664,20 @@ impl Stroke {
 	}
 
 	/// Provide the SVG attributes for the stroke.
+	///
+	/// SVG only supports centered strokes, so an inside or outside stroke is drawn at twice its weight and the excess half is hidden
+	/// by the renderer, which clips an inside stroke to its own path and masks the inside of the path out of an outside stroke.
 	pub fn render(&self) -> String {
 		if let Some(color) = self.color {
+			let weight = match self.align {
+				StrokeAlign::Center => self.weight,
+				StrokeAlign::Inside | StrokeAlign::Outside => self.weight * 2.,
+			};
 			format!(
 				r##" stroke="#{}"{} stroke-width="{}" stroke-dasharray="{}" stroke-dashoffset="{}" stroke-linecap="{}" stroke-linejoin="{}" stroke-miterlimit="{}" "##,
 				color.rgb_hex(),
 				format_opacity("stroke-opacity", color.a()),
-				self.weight,
+				weight,
 				self.dash_lengths(),
 				self.dash_offset,
 				self.line_cap,
@@ -562,6 +732,11 @@ impl Stroke {
 		self.line_join_miter_limit = limit;
 		self
 	}
+
+	pub fn with_align(mut self, align: StrokeAlign) -> Self {
+		self.align = align;
+		self
+	}
 }
 
 // Having an alpha of 1 to start with leads to a better experience with the properties panel
@@ -575,6 +750,7 @@ impl Default for Stroke {
 			line_cap: LineCap::Butt,
 			line_join: LineJoin::Miter,
 			line_join_miter_limit: 4.,
+			align: StrokeAlign::Center,
 		}
 	}
 }
@@ -584,23 +760,43 @@ impl Default for Stroke {
 pub struct PathStyle {
 	stroke: Option<Stroke>,
 	fill: Fill,
+	/// The opacity and blend mode of the fill alone, which it's composited with before the stroke is drawn over it.
+	#[serde(default)]
+	fill_blending: AlphaBlending,
+	/// The opacity and blend mode of the stroke alone, which it's composited with over the fill.
+	#[serde(default)]
+	stroke_blending: AlphaBlending,
 }
 
 impl core::hash::Hash for PathStyle {
 	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
 		self.stroke.hash(state);
 		self.fill.hash(state);
+		self.fill_blending.hash(state);
+		self.stroke_blending.hash(state);
 	}
 }
 
 impl PathStyle {
 	pub const fn new(stroke: Option<Stroke>, fill: Fill) -> Self {
-		Self { stroke, fill }
+		Self {
+			stroke,
+			fill,
+			fill_blending: AlphaBlending::new(),
+			stroke_blending: AlphaBlending::new(),
+		}
 	}
 
 	pub fn lerp(&self, other: &Self, time: f64) -> Self {
+		let lerp_blending = |a: AlphaBlending, b: AlphaBlending| AlphaBlending {
+			opacity: a.opacity + (b.opacity - a.opacity) * time as f32,
+			blend_mode: if time < 0.5 { a.blend_mode } else { b.blend_mode },
+		};
+
 		Self {
 			fill: self.fill.lerp(&other.fill, time),
+			fill_blending: lerp_blending(self.fill_blending, other.fill_blending),
+			stroke_blending: lerp_blending(self.stroke_blending, other.stroke_blending),
 			stroke: match (self.stroke.as_ref(), other.stroke.as_ref()) {
 				(Some(a), Some(b)) => Some(a.lerp(b, time)),
 				(Some(a), None) => {
@@ -726,16 +922,48 @@ impl PathStyle {
 		self.stroke = None;
 	}
 
+	/// The opacity and blend mode of the fill alone.
+	pub fn fill_blending(&self) -> AlphaBlending {
+		self.fill_blending
+	}
+
+	/// The opacity and blend mode of the stroke alone.
+	pub fn stroke_blending(&self) -> AlphaBlending {
+		self.stroke_blending
+	}
+
+	pub fn set_fill_blending(&mut self, blending: AlphaBlending) {
+		self.fill_blending = blending;
+	}
+
+	pub fn set_stroke_blending(&mut self, blending: AlphaBlending) {
+		self.stroke_blending = blending;
+	}
+
+	/// Whether the fill or stroke has its own opacity or blend mode, so they must be drawn as separate elements composited one after the other instead of as one element.
+	pub fn composites_separately(&self) -> bool {
+		self.fill_blending != AlphaBlending::new() || (self.stroke.is_some() && self.stroke_blending != AlphaBlending::new())
+	}
+
+	/// Splits the style into one with only the fill and one with only the stroke, for drawing them as separate elements.
+	pub fn split_fill_and_stroke(&self) -> (Self, Self) {
+		let mut fill = self.clone();
+		fill.clear_stroke();
+		let mut stroke = self.clone();
+		stroke.clear_fill();
+		(fill, stroke)
+	}
+
 	/// Renders the shape's fill and stroke attributes as a string with them concatenated together.
-	pub fn render(&self, view_mode: ViewMode, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2]) -> String {
+	pub fn render(&self, view_mode: ViewMode, linear_compositing: bool, svg_defs: &mut String, multiplied_transform: DAffine2, bounds: [DVec2; 2], transformed_bounds: [DVec2; 2]) -> String {
 		match view_mode {
 			ViewMode::Outline => {
-				let fill_attribute = Fill::None.render(svg_defs, multiplied_transform, bounds, transformed_bounds);
+				let fill_attribute = Fill::None.render(svg_defs, multiplied_transform, bounds, transformed_bounds, linear_compositing);
 				let stroke_attribute = Stroke::new(Some(LAYER_OUTLINE_STROKE_COLOR), LAYER_OUTLINE_STROKE_WEIGHT).render();
 				format!("{fill_attribute}{stroke_attribute}")
 			}
 			_ => {
-				let fill_attribute = self.fill.render(svg_defs, multiplied_transform, bounds, transformed_bounds);
+				let fill_attribute = self.fill.render(svg_defs, multiplied_transform, bounds, transformed_bounds, linear_compositing);
 				let stroke_attribute = self.stroke.as_ref().map(|stroke| stroke.render()).unwrap_or_default();
 				format!("{fill_attribute}{stroke_attribute}")
 			}
@@ -754,3 +982,83 @@ pub enum ViewMode {
 	/// Render with normal coloration at the document resolution, showing the pixels when the current viewport resolution is higher
 	Pixels,
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn evaluate_gradient_stops() {
+		let stops = GradientStops(vec![(0.25, Color::BLACK), (0.75, Color::WHITE)]);
+		assert_eq!(stops.evaluate(0., GradientInterpolation::Srgb), Color::BLACK);
+		assert_eq!(stops.evaluate(1., GradientInterpolation::LinearRgb), Color::WHITE);
+		assert!((stops.evaluate(0.5, GradientInterpolation::Srgb).r() - 0.5).abs() < 1e-5);
+		// Mixing half the light of white and black is lighter than mid gray once encoded as sRGB
+		assert!((stops.evaluate(0.5, GradientInterpolation::LinearRgb).r() - Color::linear_to_srgb(0.5)).abs() < 1e-5);
+	}
+
+	#[test]
+	fn baked_gradient_stops() {
+		assert_eq!(GradientStops::default().baked(GradientInterpolation::Srgb), GradientStops::default());
+
+		let stops = GradientStops::default().baked(GradientInterpolation::Oklab);
+		assert_eq!(stops.0.len(), BAKED_GRADIENT_SUBDIVISIONS + 2);
+		assert_eq!(stops.0.first(), GradientStops::default().0.first());
+		assert_eq!(stops.0.last(), GradientStops::default().0.last());
+		assert!(stops.0.windows(2).all(|pair| pair[0].0 < pair[1].0));
+	}
+
+	#[test]
+	fn perceptual_color_spaces_round_trip() {
+		let color = Color::from_rgbaf32_unchecked(0.8, 0.3, 0.1, 0.5);
+		let [l, a, b, alpha] = color.to_oklab();
+		let oklab = Color::from_oklab(l, a, b, alpha);
+		let [l, a, b, alpha] = color.to_cielab();
+		let cielab = Color::from_cielab(l, a, b, alpha);
+
+		for round_trip in [oklab, cielab] {
+			assert!((round_trip.r() - color.r()).abs() < 1e-3 && (round_trip.g() - color.g()).abs() < 1e-3 && (round_trip.b() - color.b()).abs() < 1e-3);
+			assert_eq!(round_trip.a(), color.a());
+		}
+		assert!((Color::WHITE.to_oklab()[0] - 1.).abs() < 1e-3);
+		assert!((Color::WHITE.to_cielab()[0] - 100.).abs() < 1e-2);
+	}
+
+	#[test]
+	fn mix_hues_around_the_color_wheel() {
+		let (red, blue) = (Color::from_rgbaf32_unchecked(1., 0., 0., 1.), Color::from_rgbaf32_unchecked(0., 0., 1., 1.));
+		let hue = |color: Color| color.to_hsla()[0] * 360.;
+
+		// Red and blue are closest across magenta, so the shorter way passes through it and the longer way through green
+		let shorter = GradientInterpolation::Oklch(HueInterpolation::Shorter).mix(red, blue, 0.5);
+		let longer = GradientInterpolation::Oklch(HueInterpolation::Longer).mix(red, blue, 0.5);
+		assert!(hue(shorter) > 270. || hue(shorter) < 30., "{}", hue(shorter));
+		assert!((60.0..200.).contains(&hue(longer)), "{}", hue(longer));
+
+		assert_eq!(HueInterpolation::Increasing.adjust(350., 10.), (350., 370.));
+		assert_eq!(HueInterpolation::Decreasing.adjust(10., 350.), (10., -10.));
+		assert_eq!(HueInterpolation::Decreasing.adjust(10., 30.), (10., -330.));
+	}
+
+	#[test]
+	fn split_fill_and_stroke_blending() {
+		let mut style = PathStyle::new(Some(Stroke::new(Some(Color::BLACK), 2.)), Fill::Solid(Color::RED));
+		assert!(!style.composites_separately());
+
+		style.set_stroke_blending(AlphaBlending {
+			opacity: 0.5,
+			blend_mode: crate::raster::BlendMode::Multiply,
+		});
+		assert!(style.composites_separately());
+
+		let (fill, stroke) = style.split_fill_and_stroke();
+		assert_eq!(*fill.fill(), Fill::Solid(Color::RED));
+		assert_eq!(fill.stroke(), None);
+		assert_eq!(*stroke.fill(), Fill::None);
+		assert_eq!(stroke.stroke(), style.stroke());
+
+		// Without a stroke, only the fill's own blending needs it to be drawn separately
+		style.clear_stroke();
+		assert!(!style.composites_separately());
+	}
+}
@@ -25,6 +25,22 @@ pub struct VectorData {
 	pub point_domain: PointDomain,
 	pub segment_domain: SegmentDomain,
 	pub region_domain: RegionDomain,
+
+	/// Whether this is text that doesn't fit its text box, so the lines at its end were left out.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub text_overflows: bool,
+	/// Ranges of the subpaths of text with runs styled in their own colors, which are filled with those colors instead of the fill of the vector data.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub text_fills: Vec<TextFill>,
+}
+
+/// The subpaths from the start index up to the end index, in the order of [`VectorData::stroke_bezier_paths`], which are filled with their own color.
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextFill {
+	pub start: usize,
+	pub end: usize,
+	pub color: Color,
 }
 
 impl core::hash::Hash for VectorData {
@@ -36,6 +52,8 @@ impl core::hash::Hash for VectorData {
 		self.style.hash(state);
 		self.alpha_blending.hash(state);
 		self.colinear_manipulators.hash(state);
+		self.text_overflows.hash(state);
+		self.text_fills.hash(state);
 	}
 }
 
@@ -50,6 +68,8 @@ impl VectorData {
 			point_domain: PointDomain::new(),
 			segment_domain: SegmentDomain::new(),
 			region_domain: RegionDomain::new(),
+			text_overflows: false,
+			text_fills: Vec::new(),
 		}
 	}
 
@@ -1,9 +1,11 @@
 use super::misc::CentroidType;
-use super::style::{Fill, Stroke};
+use super::point_cloud::{PointCloud, PointPositions};
+use super::style::{Fill, Stroke, StrokeAlign};
 use super::{PointId, SegmentId, StrokeId, VectorData};
+use crate::raster::BlendMode;
 use crate::renderer::GraphicElementRendered;
 use crate::transform::{Footprint, Transform, TransformMut};
-use crate::{Color, GraphicGroup, Node};
+use crate::{AlphaBlending, Color, GraphicElement, GraphicGroup, Node};
 use core::future::Future;
 
 use bezier_rs::{Cap, Join, Subpath, SubpathTValue, TValue};
@@ -23,7 +25,7 @@ fn set_vector_data_fill<T: Into<Fill>>(mut vector_data: VectorData, fill: T) ->
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct SetStrokeNode<Color, Weight, DashLengths, DashOffset, LineCap, LineJoin, MiterLimit> {
+pub struct SetStrokeNode<Color, Weight, DashLengths, DashOffset, LineCap, LineJoin, MiterLimit, Align> {
 	color: Color,
 	weight: Weight,
 	dash_lengths: DashLengths,
@@ -31,6 +33,7 @@ pub struct SetStrokeNode<Color, Weight, DashLengths, DashOffset, LineCap, LineJo
 	line_cap: LineCap,
 	line_join: LineJoin,
 	miter_limit: MiterLimit,
+	align: Align,
 }
 
 #[node_macro::node_fn(SetStrokeNode)]
@@ -43,6 +46,7 @@ fn set_vector_data_stroke(
 	line_cap: super::style::LineCap,
 	line_join: super::style::LineJoin,
 	miter_limit: f64,
+	align: super::style::StrokeAlign,
 ) -> VectorData {
 	vector_data.style.set_stroke(Stroke {
 		color,
@@ -52,6 +56,39 @@ fn set_vector_data_stroke(
 		line_cap,
 		line_join,
 		line_join_miter_limit: miter_limit,
+		align,
+	});
+	vector_data
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SetFillBlendingNode<Opacity, BlendMode> {
+	opacity: Opacity,
+	blend_mode: BlendMode,
+}
+
+/// Sets the opacity (as a percentage) and blend mode which the fill alone is composited with, separately from the stroke.
+#[node_macro::node_fn(SetFillBlendingNode)]
+fn set_vector_data_fill_blending(mut vector_data: VectorData, opacity: f64, blend_mode: BlendMode) -> VectorData {
+	vector_data.style.set_fill_blending(AlphaBlending {
+		opacity: opacity as f32 / 100.,
+		blend_mode,
+	});
+	vector_data
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SetStrokeBlendingNode<Opacity, BlendMode> {
+	opacity: Opacity,
+	blend_mode: BlendMode,
+}
+
+/// Sets the opacity (as a percentage) and blend mode which the stroke alone is composited with over the fill.
+#[node_macro::node_fn(SetStrokeBlendingNode)]
+fn set_vector_data_stroke_blending(mut vector_data: VectorData, opacity: f64, blend_mode: BlendMode) -> VectorData {
+	vector_data.style.set_stroke_blending(AlphaBlending {
+		opacity: opacity as f32 / 100.,
+		blend_mode,
 	});
 	vector_data
 }
@@ -151,19 +188,40 @@ fn solidify_stroke(vector_data: VectorData) -> VectorData {
 		let transform = transform.clone();
 		subpath.apply_transform(transform);
 
+		let join = match stroke.line_join {
+			crate::vector::style::LineJoin::Miter => Join::Miter(Some(stroke.line_join_miter_limit)),
+			crate::vector::style::LineJoin::Bevel => Join::Bevel,
+			crate::vector::style::LineJoin::Round => Join::Round,
+		};
+		let cap = match stroke.line_cap {
+			crate::vector::style::LineCap::Butt => Cap::Butt,
+			crate::vector::style::LineCap::Round => Cap::Round,
+			crate::vector::style::LineCap::Square => Cap::Square,
+		};
+
+		// An aligned stroke on a closed subpath spans the full weight to one side of the path, with the path itself as the other edge
+		if subpath.closed() && stroke.align != StrokeAlign::Center {
+			let (same_winding, opposite_winding) = subpath.outline(stroke.weight, join, cap);
+			let Some(opposite_winding) = opposite_winding else { continue };
+
+			// Whichever offset encloses less area lies inside the path
+			let same_winding_is_inside = same_winding.area(None, None) < opposite_winding.area(None, None);
+			let (offset, offset_has_same_winding) = match (stroke.align, same_winding_is_inside) {
+				(StrokeAlign::Inside, true) | (StrokeAlign::Outside, false) => (same_winding, true),
+				_ => (opposite_winding, false),
+			};
+
+			// The path and its offset must wind in opposite directions so the area between them is what gets filled
+			result.append_subpath(if offset_has_same_winding { subpath.reverse() } else { subpath });
+			result.append_subpath(offset);
+			continue;
+		}
+
 		// Taking the existing stroke data and passing it to Bezier-rs to generate new paths.
 		let subpath_out = subpath.outline(
 			stroke.weight / 2., // Diameter to radius.
-			match stroke.line_join {
-				crate::vector::style::LineJoin::Miter => Join::Miter(Some(stroke.line_join_miter_limit)),
-				crate::vector::style::LineJoin::Bevel => Join::Bevel,
-				crate::vector::style::LineJoin::Round => Join::Round,
-			},
-			match stroke.line_cap {
-				crate::vector::style::LineCap::Butt => Cap::Butt,
-				crate::vector::style::LineCap::Round => Cap::Round,
-				crate::vector::style::LineCap::Square => Cap::Square,
-			},
+			join,
+			cap,
 		);
 
 		// This is where we determine whether we have a closed or open path. Ex: Oval vs line segment.
@@ -188,6 +246,12 @@ fn solidify_stroke(vector_data: VectorData) -> VectorData {
 
 pub trait ConcatElement {
 	fn concat(&mut self, other: &Self, transform: DAffine2);
+
+	/// Concatenates the other element with its vector shapes filled with the color.
+	/// Vector data concatenated into one shape has a single style, so it keeps the fill of the other element.
+	fn concat_with_fill(&mut self, other: &Self, transform: DAffine2, _fill: Color) {
+		self.concat(other, transform);
+	}
 }
 
 impl ConcatElement for GraphicGroup {
@@ -199,6 +263,22 @@ impl ConcatElement for GraphicGroup {
 		}
 		self.alpha_blending = other.alpha_blending;
 	}
+
+	fn concat_with_fill(&mut self, other: &Self, transform: DAffine2, fill: Color) {
+		fn set_fill(group: &mut GraphicGroup, fill: Color) {
+			for element in group.iter_mut() {
+				match element {
+					GraphicElement::VectorData(vector_data) => vector_data.style.set_fill(Fill::Solid(fill)),
+					GraphicElement::GraphicGroup(group) => set_fill(group, fill),
+					_ => {}
+				}
+			}
+		}
+
+		let mut filled = other.clone();
+		set_fill(&mut filled, fill);
+		self.concat(&filled, transform);
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -212,7 +292,7 @@ pub struct CopyToPoints<Points, Instance, RandomScaleMin, RandomScaleMax, Random
 }
 
 #[node_macro::node_fn(CopyToPoints)]
-async fn copy_to_points<I: GraphicElementRendered + Default + ConcatElement + TransformMut, FP: Future<Output = VectorData>, FI: Future<Output = I>>(
+async fn copy_to_points<I: GraphicElementRendered + Default + ConcatElement + TransformMut, P: PointPositions, FP: Future<Output = P>, FI: Future<Output = I>>(
 	footprint: Footprint,
 	points: impl Node<Footprint, Output = FP>,
 	instance: impl Node<Footprint, Output = FI>,
@@ -225,7 +305,7 @@ async fn copy_to_points<I: GraphicElementRendered + Default + ConcatElement + Tr
 	let instance = self.instance.eval(footprint).await;
 	let random_scale_difference = random_scale_max - random_scale_min;
 
-	let points_list = points.point_domain.positions();
+	let points_list = points.point_positions();
 
 	let instance_bounding_box = instance.bounding_box(DAffine2::IDENTITY).unwrap_or_default();
 	let instance_center = -0.5 * (instance_bounding_box[0] + instance_bounding_box[1]);
@@ -236,12 +316,15 @@ async fn copy_to_points<I: GraphicElementRendered + Default + ConcatElement + Tr
 	let do_scale = random_scale_difference.abs() > 1e-6;
 	let do_rotation = random_rotation.abs() > 1e-6;
 
+	let scale_attribute = points.point_attribute(PointCloud::SCALE);
+	let rotation_attribute = points.point_attribute(PointCloud::ROTATION);
+	let offset_attribute = points.point_attribute(PointCloud::OFFSET);
+	let color_attribute = points.point_attribute(PointCloud::COLOR);
+
 	let mut result = I::default();
-	for &point in points_list {
+	for (index, translation) in points_list.into_iter().enumerate() {
 		let center_transform = DAffine2::from_translation(instance_center);
 
-		let translation = points.transform.transform_point2(point);
-
 		let rotation = if do_rotation {
 			let degrees = (rotation_rng.gen::<f64>() - 0.5) * random_rotation;
 			degrees / 360. * std::f64::consts::TAU
@@ -263,7 +346,16 @@ async fn copy_to_points<I: GraphicElementRendered + Default + ConcatElement + Tr
 			random_scale_min
 		};
 
-		result.concat(&instance, DAffine2::from_scale_angle_translation(DVec2::splat(scale), rotation, translation) * center_transform);
+		// The attributes of the points vary the copies on top of the random variation
+		let scale = DVec2::splat(scale) * scale_attribute.and_then(|attribute| attribute.vec2(index)).unwrap_or(DVec2::ONE);
+		let rotation = rotation + rotation_attribute.and_then(|attribute| attribute.float(index)).unwrap_or_default().to_radians();
+		let translation = translation + offset_attribute.and_then(|attribute| attribute.vec2(index)).unwrap_or_default();
+		let transform = DAffine2::from_scale_angle_translation(scale, rotation, translation) * center_transform;
+
+		match color_attribute.and_then(|attribute| attribute.color(index)) {
+			Some(color) => result.concat_with_fill(&instance, transform, color),
+			None => result.concat(&instance, transform),
+		}
 	}
 
 	result
@@ -508,6 +600,48 @@ async fn morph<SourceFuture: Future<Output = VectorData>, TargetFuture: Future<O
 	result
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct SplitAtPointNode<Point> {
+	point: Point,
+}
+
+#[node_macro::node_fn(SplitAtPointNode)]
+fn split_at_point(vector_data: VectorData, point: DVec2) -> VectorData {
+	vector_data.split_at_point(point)
+}
+
+pub struct CutAlongPathNode<Path, Cutter> {
+	path: Path,
+	cutter: Cutter,
+}
+
+#[node_macro::node_fn(CutAlongPathNode)]
+async fn cut_along_path<PathFuture: Future<Output = VectorData>, CutterFuture: Future<Output = VectorData>>(
+	footprint: Footprint,
+	path: impl Node<Footprint, Output = PathFuture>,
+	cutter: impl Node<Footprint, Output = CutterFuture>,
+) -> VectorData {
+	let path = self.path.eval(footprint).await;
+	let cutter = self.cutter.eval(footprint).await;
+	path.cut_along(&cutter)
+}
+
+pub struct DivideShapesNode<Shapes, Cutter> {
+	shapes: Shapes,
+	cutter: Cutter,
+}
+
+#[node_macro::node_fn(DivideShapesNode)]
+async fn divide_shapes<ShapesFuture: Future<Output = VectorData>, CutterFuture: Future<Output = VectorData>>(
+	footprint: Footprint,
+	shapes: impl Node<Footprint, Output = ShapesFuture>,
+	cutter: impl Node<Footprint, Output = CutterFuture>,
+) -> VectorData {
+	let shapes = self.shapes.eval(footprint).await;
+	let cutter = self.cutter.eval(footprint).await;
+	shapes.divide(&cutter)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct AreaNode<VectorData> {
 	vector_data: VectorData,
@@ -582,6 +716,7 @@ mod test {
 	use super::*;
 	use crate::transform::CullNode;
 	use crate::value::ClonedNode;
+	use crate::vector::point_cloud::PointAttribute;
 
 	use bezier_rs::Bezier;
 
@@ -677,6 +812,35 @@ mod test {
 		}
 	}
 	#[tokio::test]
+	async fn copy_to_points_follows_point_attributes() {
+		let mut points = PointCloud::from_positions(vec![DVec2::ZERO, DVec2::new(100., 0.)], DAffine2::IDENTITY);
+		points.attributes.insert(PointCloud::SCALE.to_string(), PointAttribute::Float(vec![1., 3.]));
+		points.attributes.insert(PointCloud::OFFSET.to_string(), PointAttribute::Vec2(vec![DVec2::new(0., 10.), DVec2::ZERO]));
+		points.attributes.insert(PointCloud::COLOR.to_string(), PointAttribute::Color(vec![Color::RED, Color::BLUE]));
+		let instance = GraphicGroup::from(VectorData::from_subpath(Subpath::new_rect(DVec2::NEG_ONE, DVec2::ONE)));
+
+		let copies = CopyToPoints {
+			points: CullNode::new(FutureWrapperNode(ClonedNode(points))),
+			instance: CullNode::new(FutureWrapperNode(ClonedNode(instance))),
+			random_scale_min: FutureWrapperNode(ClonedNode(1.)),
+			random_scale_max: FutureWrapperNode(ClonedNode(1.)),
+			random_scale_bias: FutureWrapperNode(ClonedNode(0.)),
+			random_rotation: FutureWrapperNode(ClonedNode(0.)),
+		}
+		.eval(Footprint::default())
+		.await;
+
+		assert_eq!(copies.len(), 2);
+		let bounds = copies.iter().map(|copy| copy.bounding_box(DAffine2::IDENTITY).unwrap()).collect::<Vec<_>>();
+		assert_eq!(bounds[0], [DVec2::new(-1., 9.), DVec2::new(1., 11.)]);
+		assert_eq!(bounds[1], [DVec2::new(97., -3.), DVec2::new(103., 3.)]);
+		let fills = copies.iter().map(|copy| match copy {
+			GraphicElement::VectorData(vector_data) => vector_data.style.fill().clone(),
+			_ => Fill::None,
+		});
+		assert!(fills.eq([Fill::Solid(Color::RED), Fill::Solid(Color::BLUE)]));
+	}
+	#[tokio::test]
 	async fn sample_points() {
 		let path = VectorData::from_subpath(Subpath::from_bezier(&Bezier::from_cubic_dvec2(DVec2::ZERO, DVec2::ZERO, DVec2::X * 100., DVec2::X * 100.)));
 		let sample_points = SamplePoints {
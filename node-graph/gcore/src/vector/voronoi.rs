@@ -0,0 +1,339 @@
+//! Voronoi diagrams and Delaunay triangulations, which split an area into the cells nearest to each of a set of points or into triangles joining the points.
+
+use super::VectorData;
+use crate::Node;
+
+use bezier_rs::Subpath;
+use dyn_any::{DynAny, StaticType};
+use glam::DVec2;
+use rand::{Rng, SeedableRng};
+
+/// The most points a pattern is made from, as computing the cells takes time growing with the square of the number of points.
+const MAX_POINTS: usize = 5000;
+
+/// Where the points a Voronoi or Delaunay pattern is made from come from.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash, DynAny, specta::Type)]
+pub enum PointDistribution {
+	/// The anchor points of the input.
+	#[default]
+	Anchors,
+	/// Points scattered randomly over the bounds of the input.
+	Random,
+	/// Points at the centers of a grid of roughly square cells over the bounds of the input.
+	Grid,
+}
+
+impl core::fmt::Display for PointDistribution {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			PointDistribution::Anchors => write!(f, "Anchors"),
+			PointDistribution::Random => write!(f, "Random"),
+			PointDistribution::Grid => write!(f, "Grid"),
+		}
+	}
+}
+
+impl PointDistribution {
+	pub fn list() -> [PointDistribution; 3] {
+		[PointDistribution::Anchors, PointDistribution::Random, PointDistribution::Grid]
+	}
+
+	/// The points over the vector data, in its local coordinates, without any two at the same place.
+	pub fn points(&self, vector_data: &VectorData, bounds: [DVec2; 2], count: u32, seed: u32) -> Vec<DVec2> {
+		let count = (count as usize).min(MAX_POINTS);
+		let [min, max] = bounds;
+		let size = max - min;
+
+		let points = match self {
+			PointDistribution::Anchors => vector_data.point_domain.positions().iter().copied().take(MAX_POINTS).collect(),
+			PointDistribution::Random => {
+				let mut rng = rand::rngs::StdRng::seed_from_u64(seed as u64);
+				(0..count).map(|_| min + DVec2::new(rng.gen(), rng.gen()) * size).collect()
+			}
+			PointDistribution::Grid => {
+				if count == 0 {
+					return Vec::new();
+				}
+				let columns = ((count as f64 * size.x / size.y.max(f64::EPSILON)).sqrt().round() as usize).clamp(1, count);
+				let rows = count.div_ceil(columns);
+				let cell = size / DVec2::new(columns as f64, rows as f64);
+				(0..rows)
+					.flat_map(|row| (0..columns).map(move |column| min + (DVec2::new(column as f64, row as f64) + 0.5) * cell))
+					.collect()
+			}
+		};
+		deduplicate(points)
+	}
+}
+
+fn deduplicate(points: Vec<DVec2>) -> Vec<DVec2> {
+	let mut unique: Vec<DVec2> = Vec::with_capacity(points.len());
+	for point in points {
+		if point.is_finite() && !unique.iter().any(|other| other.abs_diff_eq(point, 1e-9)) {
+			unique.push(point);
+		}
+	}
+	unique
+}
+
+/// The cell of each point, the polygon (clockwise in the y-down coordinates) of the area within the bounds nearer to it than to any other point.
+pub fn voronoi_cells(points: &[DVec2], bounds: [DVec2; 2]) -> Vec<Vec<DVec2>> {
+	let [min, max] = bounds;
+	let rectangle = vec![min, DVec2::new(max.x, min.y), max, DVec2::new(min.x, max.y)];
+
+	points
+		.iter()
+		.map(|&site| {
+			// The nearer points are cut away first, so the cell shrinks quickly and the farther points can be skipped once they're too far to cut it
+			let mut others = points.iter().copied().filter(|&other| other != site).collect::<Vec<_>>();
+			others.sort_by(|a, b| a.distance_squared(site).total_cmp(&b.distance_squared(site)));
+
+			let mut cell = rectangle.clone();
+			for other in others {
+				let reach = cell.iter().map(|vertex| vertex.distance_squared(site)).fold(0., f64::max);
+				if other.distance_squared(site) > reach * 4. {
+					break;
+				}
+				cell = clip_to_nearer_half(&cell, site, other);
+				if cell.is_empty() {
+					break;
+				}
+			}
+			cell
+		})
+		.collect()
+}
+
+/// Cuts the convex polygon down to the half of the plane nearer to the site than to the other point.
+fn clip_to_nearer_half(polygon: &[DVec2], site: DVec2, other: DVec2) -> Vec<DVec2> {
+	let normal = other - site;
+	let midpoint = (site + other) / 2.;
+	let side = |point: DVec2| (point - midpoint).dot(normal);
+
+	let mut result = Vec::with_capacity(polygon.len() + 1);
+	for (index, &start) in polygon.iter().enumerate() {
+		let end = polygon[(index + 1) % polygon.len()];
+		let (start_side, end_side) = (side(start), side(end));
+		if start_side <= 0. {
+			result.push(start);
+		}
+		if (start_side < 0. && end_side > 0.) || (start_side > 0. && end_side < 0.) {
+			result.push(start.lerp(end, start_side / (start_side - end_side)));
+		}
+	}
+	result
+}
+
+/// The center of mass of the polygon, or the average of its vertices if it has no area.
+fn centroid(polygon: &[DVec2]) -> DVec2 {
+	let mut area = 0.;
+	let mut center = DVec2::ZERO;
+	for (index, &start) in polygon.iter().enumerate() {
+		let end = polygon[(index + 1) % polygon.len()];
+		let cross = start.perp_dot(end);
+		area += cross;
+		center += (start + end) * cross;
+	}
+	if area.abs() < 1e-12 {
+		return polygon.iter().sum::<DVec2>() / polygon.len().max(1) as f64;
+	}
+	center / (area * 3.)
+}
+
+/// Moves each point to the center of its cell the given number of times (Lloyd's relaxation), evening out the cells into a more organic honeycomb-like pattern.
+pub fn relax(mut points: Vec<DVec2>, bounds: [DVec2; 2], iterations: u32) -> Vec<DVec2> {
+	for _ in 0..iterations {
+		let cells = voronoi_cells(&points, bounds);
+		points = points.iter().zip(&cells).map(|(&point, cell)| if cell.is_empty() { point } else { centroid(cell) }).collect();
+		points = deduplicate(points);
+	}
+	points
+}
+
+/// Joins the points into triangles whose circumcircles contain none of the other points, using the Bowyer-Watson algorithm.
+/// Returns the triangles as the indices of their points, all wound clockwise as seen with the y-axis pointing down.
+pub fn delaunay_triangles(points: &[DVec2]) -> Vec<[usize; 3]> {
+	if points.len() < 3 {
+		return Vec::new();
+	}
+
+	// A triangle big enough to surround every point, whose corners are added after the points and removed at the end
+	let (min, max) = points.iter().fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), &point| (min.min(point), max.max(point)));
+	let center = (min + max) / 2.;
+	let extent = (max - min).max_element().max(1.) * 20.;
+	let mut vertices = points.to_vec();
+	vertices.extend([center + DVec2::new(-extent, -extent), center + DVec2::new(extent, -extent), center + DVec2::new(0., extent)]);
+	let super_triangle = [points.len(), points.len() + 1, points.len() + 2];
+
+	let circumcircle = |[a, b, c]: [usize; 3]| {
+		let (a, b, c) = (vertices[a], vertices[b], vertices[c]);
+		let (ab, ac) = (b - a, c - a);
+		let denominator = 2. * ab.perp_dot(ac);
+		let offset = DVec2::new(ac.y * ab.length_squared() - ab.y * ac.length_squared(), ab.x * ac.length_squared() - ac.x * ab.length_squared()) / denominator;
+		(a + offset, offset.length_squared())
+	};
+
+	let mut triangles = vec![(super_triangle, circumcircle(super_triangle))];
+	for index in 0..points.len() {
+		let point = vertices[index];
+
+		// The triangles whose circumcircles hold the new point are removed, leaving a hole which is filled with triangles fanning out from the point
+		let (bad, good): (Vec<_>, Vec<_>) = triangles.into_iter().partition(|&(_, (center, radius_squared))| point.distance_squared(center) < radius_squared);
+		triangles = good;
+
+		let mut edges: Vec<[usize; 2]> = Vec::new();
+		for ([a, b, c], _) in bad {
+			for edge in [[a, b], [b, c], [c, a]] {
+				// The edges shared by two removed triangles are inside the hole rather than around it
+				if let Some(shared) = edges.iter().position(|&[start, end]| start == edge[1] && end == edge[0]) {
+					edges.swap_remove(shared);
+				} else {
+					edges.push(edge);
+				}
+			}
+		}
+		for [start, end] in edges {
+			let triangle = [start, end, index];
+			triangles.push((triangle, circumcircle(triangle)));
+		}
+	}
+
+	triangles
+		.into_iter()
+		.map(|(triangle, _)| triangle)
+		.filter(|triangle| triangle.iter().all(|&vertex| vertex < points.len()))
+		.collect()
+}
+
+/// The points of the vector data (or scattered over its bounds), relaxed, and the bounds to clip the cells to.
+fn pattern_points(vector_data: &VectorData, distribution: PointDistribution, count: u32, seed: u32, relaxation: u32) -> (Vec<DVec2>, [DVec2; 2]) {
+	let positions = vector_data.point_domain.positions();
+	let point_bounds = positions.iter().fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), &point| (min.min(point), max.max(point)));
+	let bounds = vector_data.bounding_box().unwrap_or([point_bounds.0, point_bounds.1]);
+	if !bounds[0].is_finite() || !bounds[1].is_finite() {
+		return (Vec::new(), [DVec2::ZERO; 2]);
+	}
+
+	let points = distribution.points(vector_data, bounds, count, seed);
+	(relax(points, bounds, relaxation), bounds)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VoronoiNode<Distribution, Count, Seed, Relaxation> {
+	distribution: Distribution,
+	count: Count,
+	seed: Seed,
+	relaxation: Relaxation,
+}
+
+/// Splits the bounds of the input into the cells nearest to each point, from the input's anchors or scattered over it randomly or in a grid (with the count and seed).
+/// Each relaxation iteration moves the points to the centers of their cells, evening them out into a more organic pattern.
+#[node_macro::node_fn(VoronoiNode)]
+fn voronoi(vector_data: VectorData, distribution: PointDistribution, count: u32, seed: u32, relaxation: u32) -> VectorData {
+	let (points, bounds) = pattern_points(&vector_data, distribution, count, seed, relaxation);
+	let cells = voronoi_cells(&points, bounds).into_iter().filter(|cell| cell.len() >= 3);
+
+	let mut result = VectorData::from_subpaths(cells.map(|cell| Subpath::from_anchors(cell, true)));
+	result.transform = vector_data.transform;
+	result
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DelaunayNode<Distribution, Count, Seed, Relaxation> {
+	distribution: Distribution,
+	count: Count,
+	seed: Seed,
+	relaxation: Relaxation,
+}
+
+/// Joins the points into triangles as close to equilateral as possible, the points coming from the input's anchors or scattered over it randomly or in a grid (with the count and seed).
+/// Each relaxation iteration moves the points to the centers of their Voronoi cells, evening out the triangles.
+#[node_macro::node_fn(DelaunayNode)]
+fn delaunay(vector_data: VectorData, distribution: PointDistribution, count: u32, seed: u32, relaxation: u32) -> VectorData {
+	let (points, _) = pattern_points(&vector_data, distribution, count, seed, relaxation);
+	let triangles = delaunay_triangles(&points);
+
+	let mut result = VectorData::from_subpaths(triangles.into_iter().map(|triangle| Subpath::from_anchors(triangle.map(|index| points[index]), true)));
+	result.transform = vector_data.transform;
+	result
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::value::CopiedNode;
+
+	fn area(polygon: &[DVec2]) -> f64 {
+		polygon
+			.iter()
+			.enumerate()
+			.map(|(index, &start)| start.perp_dot(polygon[(index + 1) % polygon.len()]))
+			.sum::<f64>()
+			.abs() / 2.
+	}
+
+	#[test]
+	fn voronoi_cells_tile_the_bounds() {
+		let bounds = [DVec2::ZERO, DVec2::splat(100.)];
+		let points = PointDistribution::Random.points(&VectorData::empty(), bounds, 50, 7);
+		assert_eq!(points.len(), 50);
+
+		let cells = voronoi_cells(&points, bounds);
+		let total = cells.iter().map(|cell| area(cell)).sum::<f64>();
+		assert!((total - 100. * 100.).abs() < 1e-6);
+		// Every point is in its own cell, nearer to it than any other point
+		for (cell, &point) in cells.iter().zip(&points) {
+			let center = centroid(cell);
+			let nearest = points.iter().min_by(|a, b| a.distance_squared(center).total_cmp(&b.distance_squared(center))).unwrap();
+			assert_eq!(*nearest, point);
+		}
+	}
+
+	#[test]
+	fn grid_cells_are_squares_and_relaxation_keeps_them() {
+		let bounds = [DVec2::ZERO, DVec2::new(40., 20.)];
+		let points = PointDistribution::Grid.points(&VectorData::empty(), bounds, 8, 0);
+		assert_eq!(points.len(), 8);
+		assert_eq!(points[0], DVec2::new(5., 5.));
+
+		let relaxed = relax(points.clone(), bounds, 3);
+		assert!(relaxed.iter().zip(&points).all(|(a, b)| a.abs_diff_eq(*b, 1e-9)));
+		assert!(voronoi_cells(&relaxed, bounds).iter().all(|cell| (area(cell) - 100.).abs() < 1e-9));
+	}
+
+	#[test]
+	fn delaunay_triangles_have_empty_circumcircles() {
+		let bounds = [DVec2::ZERO, DVec2::splat(100.)];
+		let points = PointDistribution::Random.points(&VectorData::empty(), bounds, 40, 3);
+		let triangles = delaunay_triangles(&points);
+
+		// A triangulation of points in general position has 2n - 2 - h triangles, where h is the number of points on the convex hull
+		assert!(triangles.len() > points.len() && triangles.len() <= 2 * points.len() - 5);
+		for [a, b, c] in &triangles {
+			let (a, b, c) = (points[*a], points[*b], points[*c]);
+			for &point in &points {
+				// The sign of the in-circle determinant, with the triangle ordered counterclockwise (in y-up coordinates)
+				let rows = [a - point, b - point, c - point].map(|d| (d, d.length_squared()));
+				let determinant = rows[0].0.x * (rows[1].0.y * rows[2].1 - rows[2].0.y * rows[1].1) - rows[0].0.y * (rows[1].0.x * rows[2].1 - rows[2].0.x * rows[1].1)
+					+ rows[0].1 * (rows[1].0.x * rows[2].0.y - rows[2].0.x * rows[1].0.y);
+				let orientation = (b - a).perp_dot(c - a).signum();
+				assert!(determinant * orientation < 1e-6);
+			}
+		}
+	}
+
+	#[test]
+	fn voronoi_node_uses_the_anchors() {
+		let square = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::splat(10.)));
+		let node = VoronoiNode {
+			distribution: CopiedNode(PointDistribution::Anchors),
+			count: CopiedNode(0),
+			seed: CopiedNode(0),
+			relaxation: CopiedNode(0),
+		};
+		// The four corners each get a quarter of the square
+		let cells = node.eval(square);
+		assert_eq!(cells.stroke_bezier_paths().count(), 4);
+		assert!(cells.stroke_bezier_paths().all(|cell| (area(&cell.anchors()) - 25.).abs() < 1e-9));
+	}
+}
@@ -0,0 +1,378 @@
+//! Distortions which bend the plane, moving the corners of a frame around the artwork (and the inside of the frame along with them) to give it perspective or wrap it in an envelope.
+
+use crate::vector::{PointId, VectorData};
+
+use bezier_rs::{Bezier, BezierHandles, Subpath};
+use dyn_any::{DynAny, StaticType};
+use glam::{DAffine2, DMat2, DMat3, DVec2, DVec3};
+
+/// The number of cubic pieces each segment of a path is divided into, so the warped pieces can follow the bend of the warp.
+const SEGMENT_SUBDIVISIONS: usize = 8;
+/// The largest number of steps taken to find where a point of an envelope comes from.
+const MAX_NEWTON_STEPS: usize = 16;
+/// How close (in pixels) a point has to be found to the point it's looked up from.
+const NEWTON_TOLERANCE: f64 = 1e-6;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, DynAny, specta::Type)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WarpMode {
+	/// Moves the corners like a plane seen in perspective, keeping straight lines straight.
+	#[default]
+	Perspective,
+	/// Bends the artwork along a mesh of control points spanning its bounds, a bicubic Bézier patch whose corners are the corners of the warp.
+	Envelope,
+}
+
+impl core::fmt::Display for WarpMode {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			WarpMode::Perspective => write!(f, "Perspective"),
+			WarpMode::Envelope => write!(f, "Envelope"),
+		}
+	}
+}
+
+impl WarpMode {
+	pub fn list() -> [WarpMode; 2] {
+		[WarpMode::Perspective, WarpMode::Envelope]
+	}
+}
+
+/// A projective transform of the plane, which maps any four points (no three on a line) to any other four.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homography(pub DMat3);
+
+impl Homography {
+	/// Maps the corners of the unit square, in the order (0, 0), (1, 0), (1, 1), (0, 1), to the corners of the quad.
+	/// Returns `None` if three of the corners lie on a line.
+	pub fn from_unit_square(quad: [DVec2; 4]) -> Option<Self> {
+		let [p0, p1, p2, p3] = quad;
+		let d1 = p1 - p2;
+		let d2 = p3 - p2;
+		let d3 = p0 - p1 + p2 - p3;
+
+		let (g, h) = if d3.abs_diff_eq(DVec2::ZERO, 1e-12) {
+			(0., 0.)
+		} else {
+			let determinant = d1.perp_dot(d2);
+			if determinant.abs() < 1e-12 {
+				return None;
+			}
+			(d3.perp_dot(d2) / determinant, d1.perp_dot(d3) / determinant)
+		};
+
+		let matrix = DMat3::from_cols((p1 - p0 + g * p1).extend(g), (p3 - p0 + h * p3).extend(h), p0.extend(1.));
+		(matrix.determinant().abs() > 1e-12).then_some(Self(matrix))
+	}
+
+	/// Maps the corners of one quad to the corners of another, with the corners of each given in the same order.
+	pub fn from_quad_to_quad(from: [DVec2; 4], to: [DVec2; 4]) -> Option<Self> {
+		let from = Self::from_unit_square(from)?;
+		let to = Self::from_unit_square(to)?;
+		Some(Self(to.0 * from.0.inverse()))
+	}
+
+	pub fn inverse(&self) -> Self {
+		Self(self.0.inverse())
+	}
+
+	/// The homogeneous coordinates of the point, whose weight is negative for points mapped from beyond the horizon.
+	fn project(&self, point: DVec2) -> DVec3 {
+		self.0 * point.extend(1.)
+	}
+
+	pub fn transform_point2(&self, point: DVec2) -> DVec2 {
+		let projected = self.project(point);
+		projected.truncate() / projected.z
+	}
+
+	/// How the mapped point moves as the point moves, which is the local linear approximation of the homography.
+	pub fn jacobian(&self, point: DVec2) -> DMat2 {
+		let projected = self.project(point);
+		let mapped = projected.truncate() / projected.z;
+		let [column_x, column_y, _] = self.0.to_cols_array_2d();
+		let x_axis = (DVec2::new(column_x[0], column_x[1]) - mapped * column_x[2]) / projected.z;
+		let y_axis = (DVec2::new(column_y[0], column_y[1]) - mapped * column_y[2]) / projected.z;
+		DMat2::from_cols(x_axis, y_axis)
+	}
+}
+
+fn bernstein(t: f64) -> [f64; 4] {
+	let s = 1. - t;
+	[s * s * s, 3. * t * s * s, 3. * t * t * s, t * t * t]
+}
+
+fn bernstein_derivative(t: f64) -> [f64; 4] {
+	let s = 1. - t;
+	[-3. * s * s, 3. * s * s - 6. * t * s, 6. * t * s - 3. * t * t, 3. * t * t]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Mapping {
+	Perspective,
+	/// The 4×4 control points of the patch, row by row from the top.
+	Envelope([DVec2; 16]),
+}
+
+/// A distortion moving the corners of a frame, given by the transform of the unit square, and everything within the frame along with them.
+/// Points are warped according to where they are within the frame, so points outside of it are moved by extending the warp beyond the frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warp {
+	frame_inverse: DAffine2,
+	/// Maps the unit square of the frame to the warped corners, which is the warp itself in perspective mode and gives a first guess when looking up points of an envelope.
+	corners: Homography,
+	mapping: Mapping,
+}
+
+impl Warp {
+	/// Moves the corners of the frame by the offsets (in the order top left, top right, bottom right, bottom left), and in envelope mode also moves the control points of the mesh by the offsets of the mesh, given row by row from the top.
+	/// Returns `None` if the frame has no area or the corners are moved onto a line.
+	pub fn new(mode: WarpMode, frame: DAffine2, corner_offsets: [DVec2; 4], mesh_offsets: &[DVec2]) -> Option<Self> {
+		if frame.matrix2.determinant().abs() < 1e-12 {
+			return None;
+		}
+		let unit_corners = [DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y];
+		let corners = Homography::from_unit_square(core::array::from_fn(|index| frame.transform_point2(unit_corners[index]) + corner_offsets[index]))?;
+
+		let mapping = match mode {
+			WarpMode::Perspective => Mapping::Perspective,
+			WarpMode::Envelope => Mapping::Envelope(core::array::from_fn(|index| {
+				let uv = DVec2::new((index % 4) as f64, (index / 4) as f64) / 3.;
+				let top = corner_offsets[0].lerp(corner_offsets[1], uv.x);
+				let bottom = corner_offsets[3].lerp(corner_offsets[2], uv.x);
+				frame.transform_point2(uv) + top.lerp(bottom, uv.y) + mesh_offsets.get(index).copied().unwrap_or_default()
+			})),
+		};
+
+		Some(Self {
+			frame_inverse: frame.inverse(),
+			corners,
+			mapping,
+		})
+	}
+
+	/// The warped position of a point given by its coordinates within the unit square of the frame.
+	pub fn map_uv(&self, uv: DVec2) -> DVec2 {
+		match &self.mapping {
+			Mapping::Perspective => self.corners.transform_point2(uv),
+			Mapping::Envelope(mesh) => {
+				let (u, v) = (bernstein(uv.x), bernstein(uv.y));
+				(0..16).map(|index| mesh[index] * u[index % 4] * v[index / 4]).sum()
+			}
+		}
+	}
+
+	/// How the warped position moves as the coordinates within the unit square of the frame move.
+	fn jacobian_uv(&self, uv: DVec2) -> DMat2 {
+		match &self.mapping {
+			Mapping::Perspective => self.corners.jacobian(uv),
+			Mapping::Envelope(mesh) => {
+				let (u, v) = (bernstein(uv.x), bernstein(uv.y));
+				let (du, dv) = (bernstein_derivative(uv.x), bernstein_derivative(uv.y));
+				let x_axis = (0..16).map(|index| mesh[index] * du[index % 4] * v[index / 4]).sum();
+				let y_axis = (0..16).map(|index| mesh[index] * u[index % 4] * dv[index / 4]).sum();
+				DMat2::from_cols(x_axis, y_axis)
+			}
+		}
+	}
+
+	/// The warped position of a point.
+	pub fn map(&self, point: DVec2) -> DVec2 {
+		self.map_uv(self.frame_inverse.transform_point2(point))
+	}
+
+	/// How the warped position of a point moves as the point moves.
+	pub fn jacobian(&self, point: DVec2) -> DMat2 {
+		self.jacobian_uv(self.frame_inverse.transform_point2(point)) * self.frame_inverse.matrix2
+	}
+
+	/// Finds the coordinates within the unit square of the frame which are warped to the point, starting the search for an envelope from the guess if one is given.
+	/// Returns `None` if the point isn't reached by the warp, like points beyond the horizon of a perspective or those an envelope folds over.
+	pub fn unmap_uv(&self, point: DVec2, guess: Option<DVec2>) -> Option<DVec2> {
+		let inverse = self.corners.inverse();
+		let projected = inverse.project(point);
+		let perspective_uv = (projected.z.abs() > 1e-12).then(|| projected.truncate() / projected.z);
+
+		match &self.mapping {
+			Mapping::Perspective => perspective_uv.filter(|&uv| self.corners.project(uv).z > 0.),
+			Mapping::Envelope(_) => {
+				let mut uv = guess.or(perspective_uv)?;
+				for _ in 0..MAX_NEWTON_STEPS {
+					let error = self.map_uv(uv) - point;
+					if error.length_squared() < NEWTON_TOLERANCE * NEWTON_TOLERANCE {
+						return Some(uv);
+					}
+					let jacobian = self.jacobian_uv(uv);
+					if jacobian.determinant().abs() < 1e-12 {
+						return None;
+					}
+					uv -= jacobian.inverse() * error;
+				}
+				let error = self.map_uv(uv) - point;
+				(error.length() < 1e-3).then_some(uv)
+			}
+		}
+	}
+
+	/// The warped outline of the frame, traced with enough points to follow the bend of an envelope.
+	pub fn warped_outline(&self) -> Vec<DVec2> {
+		let steps = match self.mapping {
+			Mapping::Perspective => 1,
+			Mapping::Envelope(_) => SEGMENT_SUBDIVISIONS * 2,
+		};
+		let edges = [(DVec2::ZERO, DVec2::X), (DVec2::X, DVec2::ONE), (DVec2::ONE, DVec2::Y), (DVec2::Y, DVec2::ZERO)];
+		edges
+			.into_iter()
+			.flat_map(|(start, end)| (0..steps).map(move |step| start.lerp(end, step as f64 / steps as f64)))
+			.map(|uv| self.map_uv(uv))
+			.collect()
+	}
+
+	/// Warps a Bézier curve by dividing it into cubic pieces, each moved to its warped ends with its handles following the slope of the warp there.
+	/// Straight lines stay straight in perspective, so they're only moved to their warped ends.
+	fn warp_bezier(&self, bezier: Bezier, pieces: &mut Vec<Bezier>) {
+		if matches!(self.mapping, Mapping::Perspective) && matches!(bezier.handles, BezierHandles::Linear) {
+			pieces.push(Bezier::from_linear_dvec2(self.map(bezier.start), self.map(bezier.end)));
+			return;
+		}
+
+		let (start, end) = (bezier.start, bezier.end);
+		let [handle_start, handle_end] = match bezier.handles {
+			BezierHandles::Linear => [start.lerp(end, 1. / 3.), start.lerp(end, 2. / 3.)],
+			BezierHandles::Quadratic { handle } => [start.lerp(handle, 2. / 3.), end.lerp(handle, 2. / 3.)],
+			BezierHandles::Cubic { handle_start, handle_end } => [handle_start, handle_end],
+		};
+		let control_points = [start, handle_start, handle_end, end];
+		let position = |t: f64| control_points.iter().zip(bernstein(t)).map(|(&point, weight)| point * weight).sum::<DVec2>();
+		let velocity = |t: f64| {
+			let s = 1. - t;
+			3. * (s * s * (handle_start - start) + 2. * t * s * (handle_end - handle_start) + t * t * (end - handle_end))
+		};
+
+		let step = 1. / SEGMENT_SUBDIVISIONS as f64;
+		for index in 0..SEGMENT_SUBDIVISIONS {
+			let (t0, t1) = (index as f64 * step, (index + 1) as f64 * step);
+			let (p0, p1) = (position(t0), position(t1));
+			let (q0, q1) = (self.map(p0), self.map(p1));
+			let out_handle = q0 + self.jacobian(p0) * velocity(t0) * (step / 3.);
+			let in_handle = q1 - self.jacobian(p1) * velocity(t1) * (step / 3.);
+			pieces.push(Bezier::from_cubic_dvec2(q0, out_handle, in_handle, q1));
+		}
+	}
+}
+
+impl VectorData {
+	/// The frame spanning the bounds of the paths as they're drawn, which warps move the corners of.
+	pub fn warp_frame(&self) -> Option<DAffine2> {
+		let [min, max] = self.bounding_box_with_transform(self.transform)?;
+		Some(DAffine2::from_scale_angle_translation(max - min, 0., min))
+	}
+
+	/// Moves every path through the warp, which is given in the space the paths are drawn in, so the warped paths are left without a transform.
+	pub fn warp(&self, warp: &Warp) -> VectorData {
+		let mut result = VectorData {
+			style: self.style.clone(),
+			alpha_blending: self.alpha_blending,
+			text_overflows: self.text_overflows,
+			..VectorData::empty()
+		};
+
+		for mut subpath in self.stroke_bezier_paths() {
+			subpath.apply_transform(self.transform);
+			let mut pieces = Vec::new();
+			subpath.iter().for_each(|bezier| warp.warp_bezier(bezier, &mut pieces));
+			if pieces.is_empty() || (subpath.closed() && pieces.len() < 2) {
+				continue;
+			}
+			result.append_subpath(Subpath::<PointId>::from_beziers(&pieces, subpath.closed()));
+		}
+
+		result
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn assert_close(a: DVec2, b: DVec2) {
+		assert!(a.abs_diff_eq(b, 1e-6), "{a} != {b}");
+	}
+
+	fn square() -> DAffine2 {
+		DAffine2::from_scale(DVec2::splat(100.))
+	}
+
+	#[test]
+	fn homography_maps_the_corners() {
+		let quad = [DVec2::new(10., 10.), DVec2::new(90., 0.), DVec2::new(120., 110.), DVec2::new(0., 80.)];
+		let homography = Homography::from_unit_square(quad).unwrap();
+		for (corner, expected) in [DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y].into_iter().zip(quad) {
+			assert_close(homography.transform_point2(corner), expected);
+		}
+		let point = DVec2::new(40., 50.);
+		assert_close(homography.inverse().transform_point2(homography.transform_point2(point)), point);
+
+		assert!(Homography::from_unit_square([DVec2::ZERO, DVec2::X, DVec2::X * 2., DVec2::Y]).is_none());
+	}
+
+	#[test]
+	fn unmoved_corners_leave_points_in_place() {
+		for mode in WarpMode::list() {
+			let warp = Warp::new(mode, square(), [DVec2::ZERO; 4], &[]).unwrap();
+			let point = DVec2::new(25., 70.);
+			assert_close(warp.map(point), point);
+			assert_close(warp.unmap_uv(point, None).unwrap(), point / 100.);
+		}
+	}
+
+	#[test]
+	fn perspective_keeps_lines_straight() {
+		let offsets = [DVec2::new(30., 0.), DVec2::new(-30., 0.), DVec2::ZERO, DVec2::ZERO];
+		let warp = Warp::new(WarpMode::Perspective, square(), offsets, &[]).unwrap();
+
+		assert_close(warp.map(DVec2::ZERO), DVec2::new(30., 0.));
+		assert_close(warp.map(DVec2::new(100., 100.)), DVec2::new(100., 100.));
+		// The middle of the square is pulled up, towards the shorter edge further away
+		assert!(warp.map(DVec2::splat(50.)).y < 50.);
+
+		let diagonal = [warp.map(DVec2::ZERO), warp.map(DVec2::splat(25.)), warp.map(DVec2::splat(100.))];
+		assert!((diagonal[1] - diagonal[0]).perp_dot(diagonal[2] - diagonal[0]).abs() < 1e-6);
+	}
+
+	#[test]
+	fn envelope_follows_the_mesh() {
+		let mut mesh = [DVec2::ZERO; 16];
+		// Pull the middle control points of the top row up to arch the top edge
+		mesh[1] = DVec2::new(0., -40.);
+		mesh[2] = DVec2::new(0., -40.);
+		let warp = Warp::new(WarpMode::Envelope, square(), [DVec2::ZERO; 4], &mesh).unwrap();
+
+		assert_close(warp.map(DVec2::ZERO), DVec2::ZERO);
+		assert_close(warp.map(DVec2::new(50., 0.)), DVec2::new(50., -30.));
+		assert_close(warp.map(DVec2::new(50., 100.)), DVec2::new(50., 100.));
+
+		let point = DVec2::new(60., 20.);
+		let uv = warp.unmap_uv(warp.map(point), None).unwrap();
+		assert_close(uv * 100., point);
+	}
+
+	#[test]
+	fn warp_paths() {
+		let mut vector_data = VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::new(100., 100.)));
+		vector_data.transform = DAffine2::from_translation(DVec2::new(10., 10.));
+		let frame = vector_data.warp_frame().unwrap();
+		assert_close(frame.transform_point2(DVec2::ZERO), DVec2::new(10., 10.));
+
+		let offsets = [DVec2::new(20., 0.), DVec2::ZERO, DVec2::ZERO, DVec2::ZERO];
+		let warped = vector_data.warp(&Warp::new(WarpMode::Perspective, frame, offsets, &[]).unwrap());
+		assert_eq!(warped.transform, DAffine2::IDENTITY);
+		assert_eq!(warped.point_domain.positions().len(), 4);
+		assert_close(warped.point_domain.positions()[0], DVec2::new(30., 10.));
+		assert_close(warped.point_domain.positions()[2], DVec2::new(110., 110.));
+
+		let enveloped = vector_data.warp(&Warp::new(WarpMode::Envelope, frame, offsets, &[]).unwrap());
+		assert_eq!(enveloped.point_domain.positions().len(), 4 * SEGMENT_SUBDIVISIONS);
+		assert_close(enveloped.point_domain.positions()[0], DVec2::new(30., 10.));
+	}
+}
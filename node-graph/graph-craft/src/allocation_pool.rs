@@ -0,0 +1,142 @@
+//! Recycles the heap allocations of the intermediate values passed between type-erased nodes, each of which is boxed by the node outputting it and unboxed by the node taking it as input.
+//!
+//! Boxes can only use the global allocator on stable Rust, so the pool is a free list for each layout rather than an arena which values are bumped into: it keeps the allocation of each value moved out of its box
+//! and hands it to the next value of the same size and alignment. Most values in a graph are of only a few layouts, so after the first evaluation nearly every box is made without allocating.
+//! The pool is reset after each evaluation of a graph by the executor, freeing the allocations the next evaluation isn't expected to need.
+
+use crate::proto::Any;
+
+use dyn_any::StaticType;
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
+use std::alloc::dealloc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+	static POOL: RefCell<AllocationPool> = RefCell::new(AllocationPool::default());
+}
+
+#[derive(Default)]
+struct AllocationPool {
+	/// Allocations whose values were moved out, by their layout, ready to hold the next value of the same layout.
+	free: HashMap<Layout, Vec<NonNull<u8>>>,
+	/// The number of values of each layout boxed since the last reset, which is how many allocations of that layout are kept by the next one.
+	boxed: HashMap<Layout, usize>,
+}
+
+impl AllocationPool {
+	fn take(&mut self, layout: Layout) -> Option<NonNull<u8>> {
+		*self.boxed.entry(layout).or_default() += 1;
+		self.free.get_mut(&layout)?.pop()
+	}
+
+	fn give(&mut self, layout: Layout, allocation: NonNull<u8>) {
+		self.free.entry(layout).or_default().push(allocation);
+	}
+
+	fn reset(&mut self) {
+		let boxed = core::mem::take(&mut self.boxed);
+		self.free.retain(|layout, allocations| {
+			let keep = boxed.get(layout).copied().unwrap_or_default();
+			for allocation in allocations.drain(keep.min(allocations.len())..) {
+				// SAFETY: The allocation was made by the global allocator for this layout and isn't referenced from anywhere else
+				unsafe { dealloc(allocation.as_ptr(), *layout) };
+			}
+			!allocations.is_empty()
+		});
+	}
+}
+
+impl Drop for AllocationPool {
+	fn drop(&mut self) {
+		self.boxed.clear();
+		self.reset();
+	}
+}
+
+/// Boxes the value as an output of a type-erased node, in an allocation left over from an earlier value if there is one of the same layout.
+pub fn boxed<'i, T: StaticType + 'i>(value: T) -> Any<'i> {
+	let layout = Layout::new::<T>();
+	if layout.size() == 0 {
+		return Box::new(value);
+	}
+	// The pool is gone while the thread is shutting down, in which case the value is boxed as usual
+	let Some(allocation) = POOL.try_with(|pool| pool.borrow_mut().take(layout)).ok().flatten() else {
+		return Box::new(value);
+	};
+
+	let pointer = allocation.as_ptr() as *mut T;
+	// SAFETY: The allocation was made by the global allocator for a value of this layout, which is what `Box` requires, and its previous value was already moved out
+	unsafe {
+		pointer.write(value);
+		Box::from_raw(pointer)
+	}
+}
+
+/// Moves the value out of its box, keeping the allocation for the next value of the same layout boxed with [`boxed`].
+pub fn unbox<T>(value: Box<T>) -> T {
+	let layout = Layout::new::<T>();
+	if layout.size() == 0 {
+		return *value;
+	}
+
+	let pointer = Box::into_raw(value);
+	// SAFETY: The pointer came from a box, so it points to an initialized value, which is read out only once since the allocation is then either kept without it or freed without dropping it
+	let value = unsafe { pointer.read() };
+	let allocation = unsafe { NonNull::new_unchecked(pointer as *mut u8) };
+	if POOL.try_with(|pool| pool.borrow_mut().give(layout, allocation)).is_err() {
+		// SAFETY: As above, the allocation was made by the global allocator for this layout
+		unsafe { dealloc(allocation.as_ptr(), layout) };
+	}
+	value
+}
+
+/// Ends an evaluation, freeing the allocations beyond the number of each layout used since the last reset, so an evaluation with many more values than usual doesn't hold onto their memory.
+pub fn reset() {
+	let _ = POOL.try_with(|pool| pool.borrow_mut().reset());
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn address(value: &Any<'_>) -> *const u8 {
+		value.as_ref() as *const _ as *const u8
+	}
+
+	fn free_count<T>() -> usize {
+		POOL.with(|pool| pool.borrow().free.get(&Layout::new::<T>()).map_or(0, Vec::len))
+	}
+
+	#[test]
+	fn reuses_allocations_for_values_of_the_same_layout() {
+		let number = boxed(1_f64);
+		let number_address = address(&number);
+		assert_eq!(unbox(dyn_any::downcast::<f64>(number).unwrap()), 1.);
+		assert_eq!(free_count::<f64>(), 1);
+
+		let other_number = boxed(2_u64);
+		assert_eq!(address(&other_number), number_address);
+		assert_eq!(*dyn_any::downcast::<u64>(other_number).unwrap(), 2);
+		assert_eq!(free_count::<u64>(), 0);
+	}
+
+	#[test]
+	fn reset_keeps_only_the_allocations_used_since_the_last_reset() {
+		let values = (0..3).map(|i| boxed(i as u32)).collect::<Vec<_>>();
+		for value in values {
+			unbox(dyn_any::downcast::<u32>(value).unwrap());
+		}
+		reset();
+		assert_eq!(free_count::<u32>(), 3);
+
+		let value = boxed(4_u32);
+		unbox(dyn_any::downcast::<u32>(value).unwrap());
+		reset();
+		assert_eq!(free_count::<u32>(), 1);
+		reset();
+		assert_eq!(free_count::<u32>(), 0);
+	}
+}
@@ -3,13 +3,16 @@ use crate::proto::{ConstructionArgs, ProtoNetwork, ProtoNode, ProtoNodeInput};
 
 use dyn_any::{DynAny, StaticType};
 pub use graphene_core::uuid::generate_uuid;
-use graphene_core::{ProtoNodeIdentifier, Type};
+use graphene_core::{Color, ProtoNodeIdentifier, Type};
 
 use glam::{DAffine2, IVec2};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
+pub mod search;
+#[cfg(feature = "serde")]
+pub mod text_format;
 pub mod value;
 
 #[repr(transparent)]
@@ -256,7 +259,7 @@ pub struct DocumentNode {
 }
 
 /// Represents the original location of a node input/output when [`NodeNetwork::generate_node_paths`] was called, allowing the types and errors to be derived.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, DynAny)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, DynAny)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Source {
 	pub node: Vec<NodeId>,
@@ -270,8 +273,10 @@ pub struct OriginalLocation {
 	/// The original location to the document node - e.g. [grandparent_id, parent_id, node_id].
 	pub path: Option<Vec<NodeId>>,
 	/// Each document input source maps to one proto node input (however one proto node input may come from several sources)
+	#[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sources_sorted", deserialize_with = "deserialize_sources"))]
 	pub inputs_source: HashMap<Source, usize>,
 	/// A list of document sources for the node's output
+	#[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sources_sorted", deserialize_with = "deserialize_sources"))]
 	pub outputs_source: HashMap<Source, usize>,
 	pub inputs_exposed: Vec<bool>,
 	/// Skipping inputs is useful for the manual composition thing - whereby a hidden `Footprint` input is added as the first input.
@@ -301,12 +306,42 @@ impl Default for DocumentNode {
 impl Hash for OriginalLocation {
 	fn hash<H: Hasher>(&self, state: &mut H) {
 		self.path.hash(state);
-		self.inputs_source.iter().for_each(|val| val.hash(state));
-		self.outputs_source.iter().for_each(|val| val.hash(state));
+		sorted_sources(&self.inputs_source).hash(state);
+		sorted_sources(&self.outputs_source).hash(state);
 		self.inputs_exposed.hash(state);
 		self.skip_inputs.hash(state);
 	}
 }
+
+/// The sources ordered by their path and index, since the iteration order of a [`HashMap`] differs between runs.
+fn sorted_sources(sources: &HashMap<Source, usize>) -> Vec<(&Source, &usize)> {
+	let mut sorted = sources.iter().collect::<Vec<_>>();
+	sorted.sort();
+	sorted
+}
+
+/// Writes the sources as a list of pairs ordered by source, because a [`Source`] can't be the key of a JSON object and so the output is always the same.
+#[cfg(feature = "serde")]
+fn serialize_sources_sorted<S: serde::Serializer>(sources: &HashMap<Source, usize>, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.collect_seq(sorted_sources(sources))
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_sources<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<HashMap<Source, usize>, D::Error> {
+	// Sources were once written as a map, which could only be read back when it was empty
+	#[derive(serde::Deserialize)]
+	#[serde(untagged)]
+	enum SourcesFormat {
+		List(Vec<(Source, usize)>),
+		Map(HashMap<Source, usize>),
+	}
+
+	Ok(match SourcesFormat::deserialize(deserializer)? {
+		SourcesFormat::List(sources) => sources.into_iter().collect(),
+		SourcesFormat::Map(sources) => sources,
+	})
+}
+
 impl OriginalLocation {
 	pub fn inputs(&self, index: usize) -> impl Iterator<Item = Source> + '_ {
 		[(index >= self.skip_inputs).then(|| Source {
@@ -638,6 +673,16 @@ where
 	Ok(inputs)
 }
 
+/// Writes the nodes ordered by ID, so serializing the same network always gives the same output.
+fn serialize_nodes_sorted<S>(nodes: &HashMap<NodeId, DocumentNode>, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	let mut sorted_nodes = nodes.iter().collect::<Vec<_>>();
+	sorted_nodes.sort_by_key(|(id, _)| **id);
+	serializer.collect_map(sorted_nodes)
+}
+
 // TODO: Eventually remove this (probably starting late 2024)
 fn default_import_metadata() -> (NodeId, IVec2) {
 	(NodeId(generate_uuid()), IVec2::new(-25, -4))
@@ -647,6 +692,27 @@ fn default_export_metadata() -> (NodeId, IVec2) {
 	(NodeId(generate_uuid()), IVec2::new(8, -4))
 }
 
+/// A titled and colored rectangle drawn behind the nodes of a network to group them visually.
+/// It only organizes the graph, so it's ignored when the network is compiled.
+#[derive(Clone, Debug, PartialEq, DynAny)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommentFrame {
+	pub id: u64,
+	pub title: String,
+	pub color: Color,
+	/// The top left corner in grid units of the node graph.
+	pub position: IVec2,
+	/// The width and height in grid units of the node graph.
+	pub size: IVec2,
+}
+
+impl CommentFrame {
+	/// Whether the node at this position lies within the frame.
+	pub fn contains(&self, position: IVec2) -> bool {
+		position.cmpge(self.position).all() && position.cmplt(self.position + self.size).all()
+	}
+}
+
 #[derive(Clone, Debug, DynAny)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A network (subgraph) of nodes containing each [`DocumentNode`] and its ID, as well as list mapping each export to its connected node, or a value if disconnected
@@ -656,6 +722,7 @@ pub struct NodeNetwork {
 	#[serde(alias = "outputs", deserialize_with = "deserialize_exports")] // TODO: Eventually remove this alias (probably starting late 2024)
 	pub exports: Vec<NodeInput>,
 	/// The list of all nodes in this network.
+	#[serde(serialize_with = "serialize_nodes_sorted")]
 	pub nodes: HashMap<NodeId, DocumentNode>,
 	/// Indicates whether the network is currently rendered with a particular node that is previewed, and if so, which connection should be restored when the preview ends.
 	#[serde(default)]
@@ -668,6 +735,9 @@ pub struct NodeNetwork {
 	/// Transform from node graph space to viewport space.
 	#[serde(default)]
 	pub node_graph_to_viewport: DAffine2,
+	/// The comment frames drawn behind the nodes, in the order they're drawn.
+	#[serde(default)]
+	pub comment_frames: Vec<CommentFrame>,
 }
 
 impl std::hash::Hash for NodeNetwork {
@@ -691,12 +761,17 @@ impl Default for NodeNetwork {
 			imports_metadata: default_import_metadata(),
 			exports_metadata: default_export_metadata(),
 			node_graph_to_viewport: DAffine2::default(),
+			comment_frames: Vec::new(),
 		}
 	}
 }
 impl PartialEq for NodeNetwork {
 	fn eq(&self, other: &Self) -> bool {
-		self.exports == other.exports && self.previewing == other.previewing && self.imports_metadata == other.imports_metadata && self.exports_metadata == other.exports_metadata
+		self.exports == other.exports
+			&& self.previewing == other.previewing
+			&& self.imports_metadata == other.imports_metadata
+			&& self.exports_metadata == other.exports_metadata
+			&& self.comment_frames == other.comment_frames
 	}
 }
 
@@ -939,6 +1014,105 @@ impl NodeNetwork {
 		}
 		true
 	}
+
+	/// Moves the given nodes into a nested network implemented by a new node, which takes their place in this network.
+	///
+	/// Wires coming from the rest of this network or its imports, along with exposed value inputs, become imports of the nested network and therefore inputs of the new node.
+	/// Wires leading to the rest of this network or its exports become exports of the nested network and therefore outputs of the new node.
+	/// The `import_type` function gives the type of the nested node input (by node ID and input index) that an import is created for.
+	///
+	/// Returns `None` without modifying the network if a node isn't found, or if collapsing would create a cycle because the nodes depend on each other through nodes outside of them.
+	pub fn collapse_into_subgraph(&mut self, node_ids: &[NodeId], subgraph_node_id: NodeId, name: String, import_type: impl Fn(NodeId, usize) -> Type) -> Option<CollapsedSubgraph> {
+		if node_ids.is_empty() || node_ids.iter().any(|node_id| !self.nodes.contains_key(node_id)) || self.nodes.contains_key(&subgraph_node_id) {
+			return None;
+		}
+
+		let mut network = self.clone();
+		let mut nested_network = NodeNetwork::default();
+		let mut collapsed = CollapsedSubgraph::default();
+		let mut subgraph_inputs = Vec::new();
+
+		for &node_id in node_ids {
+			let mut node = network.nodes.remove(&node_id)?;
+			for (input_index, input) in node.inputs.iter_mut().enumerate() {
+				let outside_input = match input {
+					NodeInput::Node { node_id, .. } if node_ids.contains(node_id) => continue,
+					NodeInput::Node { .. } | NodeInput::Network { .. } | NodeInput::Value { exposed: true, .. } => input.clone(),
+					NodeInput::Value { exposed: false, .. } | NodeInput::Inline(_) => continue,
+				};
+
+				// Wires from the same source share an import, while each exposed value gets its own so it can be edited separately
+				let existing_import = match outside_input {
+					NodeInput::Value { .. } => None,
+					_ => subgraph_inputs.iter().position(|subgraph_input| *subgraph_input == outside_input),
+				};
+				let import_index = existing_import.unwrap_or_else(|| {
+					subgraph_inputs.push(outside_input);
+					collapsed.imports.push((node_id, input_index));
+					subgraph_inputs.len() - 1
+				});
+
+				*input = NodeInput::network(import_type(node_id, input_index), import_index);
+			}
+			nested_network.nodes.insert(node_id, node);
+		}
+
+		// Redirect the wires that lead out of the collapsed nodes to the outputs of the new node
+		let mut export = |input: &mut NodeInput| {
+			let NodeInput::Node { node_id, output_index, lambda } = *input else { return };
+			if !node_ids.contains(&node_id) {
+				return;
+			}
+
+			let export_index = collapsed.exports.iter().position(|&export| export == (node_id, output_index)).unwrap_or_else(|| {
+				collapsed.exports.push((node_id, output_index));
+				collapsed.exports.len() - 1
+			});
+			*input = NodeInput::Node {
+				node_id: subgraph_node_id,
+				output_index: export_index,
+				lambda,
+			};
+		};
+		network.nodes.values_mut().flat_map(|node| node.inputs.iter_mut()).for_each(&mut export);
+		network.exports.iter_mut().for_each(&mut export);
+		nested_network.exports = collapsed.exports.iter().map(|&(node_id, output_index)| NodeInput::node(node_id, output_index)).collect();
+
+		// Place the new node at the center of the nodes it replaces
+		let position_sum = node_ids
+			.iter()
+			.filter_map(|node_id| nested_network.nodes.get(node_id))
+			.map(|node| node.metadata.position)
+			.sum::<IVec2>();
+		let position = position_sum / node_ids.len() as i32;
+
+		network.nodes.insert(
+			subgraph_node_id,
+			DocumentNode {
+				name,
+				inputs: subgraph_inputs,
+				implementation: DocumentNodeImplementation::Network(nested_network),
+				metadata: DocumentNodeMetadata { position },
+				..Default::default()
+			},
+		);
+
+		if !network.is_acyclic() {
+			return None;
+		}
+
+		*self = network;
+		Some(collapsed)
+	}
+}
+
+/// Describes where the imports and exports of a nested network created by [`NodeNetwork::collapse_into_subgraph`] connect to within it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CollapsedSubgraph {
+	/// For each import, the node ID and input index of the first nested node input it was created for.
+	pub imports: Vec<(NodeId, usize)>,
+	/// For each export, the node ID and output index of the nested node that it exports.
+	pub exports: Vec<(NodeId, usize)>,
 }
 
 #[derive(PartialEq)]
@@ -1114,8 +1288,16 @@ impl NodeNetwork {
 	}
 
 	/// Remove all nodes that contain [`DocumentNodeImplementation::Network`] by moving the nested nodes into the parent network.
+	/// The value nodes which replace value inputs are numbered in the order they're created, so flattening the same network always gives the same IDs.
 	pub fn flatten(&mut self, node: NodeId) {
-		self.flatten_with_fns(node, merge_ids, || NodeId(generate_uuid()))
+		let value_node_count = std::cell::Cell::new(0_u64);
+		let gen_id = || {
+			let count = value_node_count.get();
+			value_node_count.set(count + 1);
+			// Hashed so the IDs don't conflict with the small IDs commonly used by the nodes of inner networks
+			merge_ids(NodeId(u64::MAX), NodeId(count))
+		};
+		self.flatten_with_fns(node, merge_ids, gen_id)
 	}
 
 	/// Remove all nodes that contain [`DocumentNodeImplementation::Network`] by moving the nested nodes into the parent network.
@@ -1204,7 +1386,9 @@ impl NodeNetwork {
 		if let DocumentNodeImplementation::Network(mut inner_network) = node.implementation {
 			// Connect all network inputs to either the parent network nodes, or newly created value nodes for the parent node.
 			inner_network.map_ids(|inner_id| map_ids(id, inner_id));
-			let new_nodes = inner_network.nodes.keys().cloned().collect::<Vec<_>>();
+			// Sorted so the nested nodes are flattened in the same order each time
+			let mut new_nodes = inner_network.nodes.keys().cloned().collect::<Vec<_>>();
+			new_nodes.sort();
 			// Match the document node input and the inputs of the inner network
 			for (nested_node_id, mut nested_node) in inner_network.nodes.into_iter() {
 				for (nested_input_index, nested_input) in nested_node.clone().inputs.iter().enumerate() {
@@ -1596,6 +1780,126 @@ mod test {
 		assert_eq!(flat_network, network);
 	}
 
+	#[test]
+	fn flatten_gives_the_same_ids_each_time() {
+		let flattened_ids = || {
+			let mut network = NodeNetwork {
+				exports: vec![NodeInput::node(NodeId(1), 0)],
+				nodes: [(
+					NodeId(1),
+					DocumentNode {
+						name: "Inc".into(),
+						inputs: vec![
+							NodeInput::Value {
+								tagged_value: TaggedValue::U32(1),
+								exposed: false,
+							},
+							NodeInput::Value {
+								tagged_value: TaggedValue::U32(2),
+								exposed: false,
+							},
+						],
+						implementation: DocumentNodeImplementation::Network(add_network()),
+						..Default::default()
+					},
+				)]
+				.into_iter()
+				.collect(),
+				..Default::default()
+			};
+			network.generate_node_paths(&[]);
+			network.flatten(NodeId(1));
+
+			let mut nodes = network.nodes.into_iter().map(|(id, node)| (id, node.original_location.path)).collect::<Vec<_>>();
+			nodes.sort();
+			nodes
+		};
+
+		let first = flattened_ids();
+		assert_eq!(first.len(), 4);
+		assert_eq!(first, flattened_ids());
+	}
+
+	#[test]
+	fn collapse_into_subgraph() {
+		let mut network = add_network();
+		let collapsed = network.collapse_into_subgraph(&[NodeId(0), NodeId(1)], NodeId(2), "Add Inputs".into(), |_, _| concrete!(u32)).unwrap();
+
+		assert_eq!(collapsed.imports, vec![(NodeId(0), 0), (NodeId(0), 1)]);
+		assert_eq!(collapsed.exports, vec![(NodeId(1), 0)]);
+		assert_eq!(network.exports, vec![NodeInput::node(NodeId(2), 0)]);
+		assert_eq!(network.nodes.len(), 1);
+
+		let subgraph_node = &network.nodes[&NodeId(2)];
+		assert_eq!(subgraph_node.name, "Add Inputs");
+		assert_eq!(subgraph_node.inputs, vec![NodeInput::network(concrete!(u32), 0), NodeInput::network(concrete!(u32), 1)]);
+
+		let DocumentNodeImplementation::Network(nested_network) = &subgraph_node.implementation else {
+			panic!("The collapsed node should be implemented by a network");
+		};
+		let mut add_network = add_network();
+		add_network.imports_metadata = nested_network.imports_metadata;
+		add_network.exports_metadata = nested_network.exports_metadata;
+		assert_eq!(nested_network, &add_network);
+	}
+
+	#[test]
+	fn collapse_into_subgraph_rejects_cycles() {
+		let identity = |input| DocumentNode {
+			name: "Identity".into(),
+			inputs: vec![input],
+			implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::ops::IdentityNode")),
+			..Default::default()
+		};
+		let mut network = NodeNetwork {
+			exports: vec![NodeInput::node(NodeId(2), 0)],
+			nodes: [
+				(NodeId(0), identity(NodeInput::network(concrete!(u32), 0))),
+				(NodeId(1), identity(NodeInput::node(NodeId(0), 0))),
+				(NodeId(2), identity(NodeInput::node(NodeId(1), 0))),
+			]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		};
+		let original = network.clone();
+
+		// Node 0 feeds node 1, which feeds node 2, so collapsing nodes 0 and 2 without node 1 would make the new node depend on itself
+		assert!(network.collapse_into_subgraph(&[NodeId(0), NodeId(2)], NodeId(3), "Cycle".into(), |_, _| concrete!(u32)).is_none());
+		assert_eq!(network, original);
+	}
+
+	#[test]
+	fn organizational_entities_are_not_compiled() {
+		let mut network = add_network();
+		let plain_hash = network.current_hash();
+
+		network.comment_frames.push(CommentFrame {
+			id: 0,
+			title: "Sum".into(),
+			color: Color::BLACK,
+			position: IVec2::new(-1, -1),
+			size: IVec2::new(12, 4),
+		});
+		assert_eq!(network.current_hash(), plain_hash, "Comment frames shouldn't change what the network compiles to");
+		assert!(network.comment_frames[0].contains(IVec2::ZERO));
+		assert!(!network.comment_frames[0].contains(IVec2::new(11, 0)));
+
+		let reroute = DocumentNode {
+			name: "Reroute".into(),
+			inputs: vec![NodeInput::node(NodeId(1), 0)],
+			implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::ops::IdentityNode")),
+			..Default::default()
+		};
+		network.nodes.insert(NodeId(2), reroute);
+		network.exports = vec![NodeInput::node(NodeId(2), 0)];
+
+		network.remove_redundant_id_nodes();
+		assert!(!network.nodes.contains_key(&NodeId(2)), "The reroute node should pass its value straight through");
+		assert_eq!(network.exports, vec![NodeInput::node(NodeId(1), 0)]);
+		assert_eq!(network.comment_frames.len(), 1);
+	}
+
 	#[test]
 	fn resolve_proto_node_add() {
 		let document_node = DocumentNode {
@@ -0,0 +1,172 @@
+//! Finding nodes in a network, and the networks nested in it, by their name, the type of their output, or the values of their inputs.
+
+use super::value::TaggedValue;
+use super::{DocumentNodeImplementation, NodeId, NodeNetwork};
+
+use glam::IVec2;
+
+/// What a node was found by.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchMatch {
+	/// The name of the node, or the name of the definition it was made from.
+	Name,
+	/// The type of the node's primary output.
+	Type(String),
+	/// The value of one of the node's inputs.
+	Value { input_index: usize, value: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+	/// The IDs of the nodes containing the networks the node is nested in, followed by the ID of the node itself.
+	pub node_path: Vec<NodeId>,
+	/// The name shown for the node, which is its alias if it has one.
+	pub name: String,
+	/// The position of the node in grid units of the network it's in.
+	pub position: IVec2,
+	pub found_by: SearchMatch,
+}
+
+/// Finds the nodes whose name, output type, or input values contain the query, ignoring case. A number in the query instead matches number inputs of equal value.
+///
+/// Each node is listed once, by the first of those it matches. Nodes come in order of how deeply they're nested, then from top to bottom and left to right.
+/// The type of the node at a path is given by `output_type`, for the nodes whose types are known.
+pub fn search_network(network: &NodeNetwork, query: &str, output_type: impl Fn(&[NodeId]) -> Option<String>) -> Vec<SearchResult> {
+	let query = query.trim().to_lowercase();
+	if query.is_empty() {
+		return Vec::new();
+	}
+
+	let mut results = Vec::new();
+	search_nested(network, &mut Vec::new(), &query, &output_type, &mut results);
+	results.sort_by_key(|result| (result.node_path.len(), result.position.y, result.position.x));
+	results
+}
+
+fn search_nested(network: &NodeNetwork, path: &mut Vec<NodeId>, query: &str, output_type: &impl Fn(&[NodeId]) -> Option<String>, results: &mut Vec<SearchResult>) {
+	for (&node_id, node) in &network.nodes {
+		path.push(node_id);
+
+		let name = if node.alias.is_empty() { node.name.clone() } else { node.alias.clone() };
+		let found_by = if name.to_lowercase().contains(query) || node.name.to_lowercase().contains(query) {
+			Some(SearchMatch::Name)
+		} else if let Some(ty) = output_type(path).filter(|ty| ty.to_lowercase().contains(query)) {
+			Some(SearchMatch::Type(ty))
+		} else {
+			node.inputs.iter().enumerate().find_map(|(input_index, input)| {
+				let value = matching_value(input.as_value()?, query)?;
+				Some(SearchMatch::Value { input_index, value })
+			})
+		};
+		if let Some(found_by) = found_by {
+			results.push(SearchResult {
+				node_path: path.clone(),
+				name,
+				position: node.metadata.position,
+				found_by,
+			});
+		}
+
+		if let DocumentNodeImplementation::Network(nested_network) = &node.implementation {
+			search_nested(nested_network, path, query, output_type, results);
+		}
+		path.pop();
+	}
+}
+
+/// The value as it's written in the search results, if it matches the query.
+fn matching_value(value: &TaggedValue, query: &str) -> Option<String> {
+	let number = match value {
+		TaggedValue::F64(number) => Some(*number),
+		TaggedValue::U32(number) => Some(*number as f64),
+		TaggedValue::U64(number) => Some(*number as f64),
+		_ => None,
+	};
+	if let Some(number) = number {
+		let query_number = query.parse::<f64>().ok()?;
+		return (number == query_number).then(|| number.to_string());
+	}
+
+	let text = match value {
+		TaggedValue::String(text) => text.clone(),
+		TaggedValue::Bool(boolean) => boolean.to_string(),
+		TaggedValue::BlendMode(blend_mode) => blend_mode.to_string(),
+		_ => return None,
+	};
+	text.to_lowercase().contains(query).then_some(text)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::document::{DocumentNode, DocumentNodeMetadata, NodeInput};
+
+	fn node(name: &str, alias: &str, value: TaggedValue, position: (i32, i32)) -> DocumentNode {
+		DocumentNode {
+			name: name.into(),
+			alias: alias.into(),
+			inputs: vec![NodeInput::value(value, false)],
+			implementation: DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"),
+			metadata: DocumentNodeMetadata::position(position),
+			..Default::default()
+		}
+	}
+
+	fn network() -> NodeNetwork {
+		let nested = NodeNetwork {
+			nodes: [(NodeId(10), node("Blur", "", TaggedValue::F64(5.), (0, 0)))].into_iter().collect(),
+			..Default::default()
+		};
+		let mut group = node("Group", "Effects", TaggedValue::None, (0, 4));
+		group.implementation = DocumentNodeImplementation::Network(nested);
+
+		NodeNetwork {
+			nodes: [
+				(NodeId(1), node("Number", "Margin", TaggedValue::F64(5.), (8, 0))),
+				(NodeId(2), node("Text", "", TaggedValue::String("Blurry title".into()), (0, 0))),
+				(NodeId(3), group),
+			]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn finds_by_name_then_value() {
+		let results = search_network(&network(), "blur", |_| None);
+
+		let found = results.iter().map(|result| (result.node_path.clone(), result.found_by.clone())).collect::<Vec<_>>();
+		assert_eq!(
+			found,
+			vec![
+				(
+					vec![NodeId(2)],
+					SearchMatch::Value {
+						input_index: 0,
+						value: "Blurry title".into()
+					}
+				),
+				(vec![NodeId(3), NodeId(10)], SearchMatch::Name),
+			]
+		);
+	}
+
+	#[test]
+	fn finds_numbers_by_equal_value() {
+		let results = search_network(&network(), "5", |_| None);
+		let paths = results.iter().map(|result| result.node_path.clone()).collect::<Vec<_>>();
+		assert_eq!(paths, vec![vec![NodeId(1)], vec![NodeId(3), NodeId(10)]]);
+		assert_eq!(results[0].name, "Margin");
+		assert_eq!(results[0].position, IVec2::new(8, 0));
+
+		assert!(search_network(&network(), "50", |_| None).is_empty());
+	}
+
+	#[test]
+	fn finds_by_output_type() {
+		let results = search_network(&network(), "vectordata", |path| (path == [NodeId(2)]).then(|| "VectorData".to_string()));
+		assert_eq!(results.len(), 1);
+		assert_eq!(results[0].found_by, SearchMatch::Type("VectorData".into()));
+	}
+}
@@ -0,0 +1,168 @@
+//! A text format for node networks on their own, without the rest of a document, which stays the same as long as the graph does.
+//! Nodes are written in order and renumbered by where they sit in the graph, so the files can be kept under version control and diffed meaningfully.
+
+use super::{DocumentNodeImplementation, NodeId, NodeInput, NodeNetwork};
+
+use glam::DAffine2;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Incremented whenever the format changes in a way older versions of the editor can't read.
+pub const NETWORK_TEXT_FORMAT_VERSION: u32 = 1;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct NetworkText<N> {
+	format_version: u32,
+	network: N,
+}
+
+/// Renumbers the nodes of the network, and of the networks nested in it, so the same graph always gets the same IDs regardless of the random ones it was built with.
+///
+/// Nodes are numbered in the order they're reached walking upstream from the exports, followed by the nodes not connected to the exports ordered by their position in the graph.
+pub fn assign_deterministic_ids(network: &mut NodeNetwork) {
+	fn visit(node_id: NodeId, network: &NodeNetwork, visited: &mut HashSet<NodeId>, order: &mut Vec<NodeId>) {
+		let Some(node) = network.nodes.get(&node_id) else { return };
+		if !visited.insert(node_id) {
+			return;
+		}
+		order.push(node_id);
+		for input in &node.inputs {
+			if let NodeInput::Node { node_id, .. } = input {
+				visit(*node_id, network, visited, order);
+			}
+		}
+	}
+
+	let mut visited = HashSet::new();
+	let mut order = Vec::new();
+	for export in &network.exports {
+		if let NodeInput::Node { node_id, .. } = export {
+			visit(*node_id, network, &mut visited, &mut order);
+		}
+	}
+
+	let mut disconnected = network.nodes.iter().filter(|(id, _)| !visited.contains(id)).collect::<Vec<_>>();
+	disconnected.sort_by_key(|(id, node)| (node.metadata.position.x, node.metadata.position.y, **id));
+	for (&node_id, _) in disconnected {
+		if !visited.contains(&node_id) {
+			visit(node_id, network, &mut visited, &mut order);
+		}
+	}
+
+	let new_ids = order.iter().enumerate().map(|(index, &old_id)| (old_id, NodeId(index as u64))).collect::<HashMap<_, _>>();
+	network.map_ids(|id| new_ids.get(&id).copied().unwrap_or(id));
+	network.imports_metadata.0 = NodeId(order.len() as u64);
+	network.exports_metadata.0 = NodeId(order.len() as u64 + 1);
+	// Where the graph was panned to is view state rather than part of the graph
+	network.node_graph_to_viewport = DAffine2::IDENTITY;
+	for (index, frame) in network.comment_frames.iter_mut().enumerate() {
+		frame.id = index as u64;
+	}
+
+	for node in network.nodes.values_mut() {
+		if let DocumentNodeImplementation::Network(nested_network) = &mut node.implementation {
+			assign_deterministic_ids(nested_network);
+		}
+	}
+}
+
+/// Writes the network as tab indented JSON with deterministic node IDs and the nodes sorted by ID.
+pub fn network_to_text(network: &NodeNetwork) -> Result<String, String> {
+	let mut network = network.clone();
+	assign_deterministic_ids(&mut network);
+
+	let text = NetworkText {
+		format_version: NETWORK_TEXT_FORMAT_VERSION,
+		network: &network,
+	};
+	let mut output = Vec::new();
+	let mut serializer = serde_json::Serializer::with_formatter(&mut output, serde_json::ser::PrettyFormatter::with_indent(b"\t"));
+	text.serialize(&mut serializer).map_err(|error| error.to_string())?;
+
+	let mut output = String::from_utf8(output).map_err(|error| error.to_string())?;
+	output.push('\n');
+	Ok(output)
+}
+
+/// Reads a network written by [`network_to_text`]. The nodes keep the IDs from the text, so they need new ones before being added to an existing network.
+pub fn network_from_text(text: &str) -> Result<NodeNetwork, String> {
+	let text: NetworkText<NodeNetwork> = serde_json::from_str(text).map_err(|error| error.to_string())?;
+	if text.format_version > NETWORK_TEXT_FORMAT_VERSION {
+		return Err(format!(
+			"The node graph was written by a newer version of Graphite (format version {}, but only up to {NETWORK_TEXT_FORMAT_VERSION} is supported)",
+			text.format_version
+		));
+	}
+	Ok(text.network)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::document::value::TaggedValue;
+	use crate::document::{DocumentNode, DocumentNodeMetadata};
+
+	use graphene_core::ProtoNodeIdentifier;
+
+	fn network(ids: [NodeId; 3]) -> NodeNetwork {
+		let [value, add, disconnected] = ids;
+		NodeNetwork {
+			exports: vec![NodeInput::node(add, 0)],
+			nodes: [
+				(
+					value,
+					DocumentNode {
+						name: "Number".into(),
+						inputs: vec![NodeInput::value(TaggedValue::F64(2.), false)],
+						implementation: DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"),
+						metadata: DocumentNodeMetadata::position((0, 0)),
+						..Default::default()
+					},
+				),
+				(
+					add,
+					DocumentNode {
+						name: "Add".into(),
+						inputs: vec![NodeInput::node(value, 0), NodeInput::value(TaggedValue::F64(3.), false)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::ops::AddNode<_>")),
+						metadata: DocumentNodeMetadata::position((8, 0)),
+						..Default::default()
+					},
+				),
+				(
+					disconnected,
+					DocumentNode {
+						name: "Number".into(),
+						inputs: vec![NodeInput::value(TaggedValue::F64(4.), false)],
+						implementation: DocumentNodeImplementation::proto("graphene_core::ops::IdentityNode"),
+						metadata: DocumentNodeMetadata::position((0, 8)),
+						..Default::default()
+					},
+				),
+			]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn same_graph_same_text() {
+		let first = network_to_text(&network([NodeId(10), NodeId(20), NodeId(30)])).unwrap();
+		let second = network_to_text(&network([NodeId(300), NodeId(200), NodeId(100)])).unwrap();
+		assert_eq!(first, second);
+	}
+
+	#[test]
+	fn round_trip() {
+		let text = network_to_text(&network([NodeId(10), NodeId(20), NodeId(30)])).unwrap();
+		let network = network_from_text(&text).unwrap();
+
+		// The exported node comes first, then what it depends on, then the disconnected node
+		assert_eq!(network.exports, vec![NodeInput::node(NodeId(0), 0)]);
+		assert_eq!(network.nodes[&NodeId(0)].inputs[0], NodeInput::node(NodeId(1), 0));
+		assert_eq!(network.nodes[&NodeId(2)].inputs[0], NodeInput::value(TaggedValue::F64(4.), false));
+
+		assert_eq!(network_to_text(&network).unwrap(), text);
+	}
+}
@@ -49,8 +49,11 @@ pub enum TaggedValue {
 	VecDVec2(Vec<DVec2>),
 	RedGreenBlue(graphene_core::raster::RedGreenBlue),
 	RedGreenBlueAlpha(graphene_core::raster::RedGreenBlueAlpha),
+	ChannelSource(graphene_core::raster::ChannelSource),
 	NoiseType(graphene_core::raster::NoiseType),
 	FractalType(graphene_core::raster::FractalType),
+	ColorHarmony(graphene_core::raster::ColorHarmony),
+	Waveform(graphene_core::oscillator::Waveform),
 	CellularDistanceFunction(graphene_core::raster::CellularDistanceFunction),
 	CellularReturnType(graphene_core::raster::CellularReturnType),
 	DomainWarpType(graphene_core::raster::DomainWarpType),
@@ -58,6 +61,7 @@ pub enum TaggedValue {
 	SelectiveColorChoice(graphene_core::raster::SelectiveColorChoice),
 	LineCap(graphene_core::vector::style::LineCap),
 	LineJoin(graphene_core::vector::style::LineJoin),
+	StrokeAlign(graphene_core::vector::style::StrokeAlign),
 	FillType(graphene_core::vector::style::FillType),
 	FillChoice(graphene_core::vector::style::FillChoice),
 	Gradient(graphene_core::vector::style::Gradient),
@@ -81,7 +85,30 @@ pub enum TaggedValue {
 	RenderOutput(RenderOutput),
 	Palette(Vec<Color>),
 	CentroidType(graphene_core::vector::misc::CentroidType),
+	ArcType(graphene_core::vector::misc::ArcType),
+	WarpMode(graphene_core::warp::WarpMode),
+	ProjectionFace(graphene_core::projection::ProjectionFace),
+	QrErrorCorrection(graphene_core::vector::barcode::QrErrorCorrection),
+	BarcodeSymbology(graphene_core::vector::barcode::BarcodeSymbology),
+	PointDistribution(graphene_core::vector::voronoi::PointDistribution),
+	PointRegion(graphene_core::vector::point_cloud::PointRegion),
+	PointSampling(graphene_core::vector::point_cloud::PointSampling),
+	PointCloud(graphene_core::vector::point_cloud::PointCloud),
 	BooleanOperation(graphene_core::vector::misc::BooleanOperation),
+	DimensionKind(graphene_core::DimensionKind),
+	MaskMode(graphene_core::MaskMode),
+	CellFit(graphene_core::CellFit),
+	PixelDiffMode(graphene_core::raster::PixelDiffMode),
+	ConvolutionPreset(graphene_core::raster::ConvolutionPreset),
+	BorderMode(graphene_core::raster::BorderMode),
+	MorphologyOperation(graphene_core::raster::MorphologyOperation),
+	MorphologyChannel(graphene_core::raster::MorphologyChannel),
+	MorphologyShape(graphene_core::raster::MorphologyShape),
+	TextAlign(graphene_core::text::TextAlign),
+	TextAutoResize(graphene_core::text::TextAutoResize),
+	FontVariations(graphene_core::text::FontVariations),
+	TextRuns(graphene_core::text::TextRuns),
+	TextKerning(graphene_core::text::TextKerning),
 }
 
 #[allow(clippy::derived_hash_with_manual_eq)]
@@ -123,8 +150,11 @@ impl Hash for TaggedValue {
 			Self::VecDVec2(x) => x.iter().for_each(|val| val.to_array().iter().for_each(|x| x.to_bits().hash(state))),
 			Self::RedGreenBlue(x) => x.hash(state),
 			Self::RedGreenBlueAlpha(x) => x.hash(state),
+			Self::ChannelSource(x) => x.hash(state),
 			Self::NoiseType(x) => x.hash(state),
 			Self::FractalType(x) => x.hash(state),
+			Self::ColorHarmony(x) => x.hash(state),
+			Self::Waveform(x) => x.hash(state),
 			Self::CellularDistanceFunction(x) => x.hash(state),
 			Self::CellularReturnType(x) => x.hash(state),
 			Self::DomainWarpType(x) => x.hash(state),
@@ -132,6 +162,7 @@ impl Hash for TaggedValue {
 			Self::SelectiveColorChoice(x) => x.hash(state),
 			Self::LineCap(x) => x.hash(state),
 			Self::LineJoin(x) => x.hash(state),
+			Self::StrokeAlign(x) => x.hash(state),
 			Self::FillType(x) => x.hash(state),
 			Self::FillChoice(x) => x.hash(state),
 			Self::Gradient(x) => x.hash(state),
@@ -164,7 +195,30 @@ impl Hash for TaggedValue {
 			Self::RenderOutput(x) => x.hash(state),
 			Self::Palette(x) => x.hash(state),
 			Self::CentroidType(x) => x.hash(state),
+			Self::ArcType(x) => x.hash(state),
+			Self::WarpMode(x) => x.hash(state),
+			Self::ProjectionFace(x) => x.hash(state),
+			Self::QrErrorCorrection(x) => x.hash(state),
+			Self::BarcodeSymbology(x) => x.hash(state),
+			Self::PointDistribution(x) => x.hash(state),
+			Self::PointRegion(x) => x.hash(state),
+			Self::PointSampling(x) => x.hash(state),
+			Self::PointCloud(x) => x.hash(state),
 			Self::BooleanOperation(x) => x.hash(state),
+			Self::DimensionKind(x) => x.hash(state),
+			Self::MaskMode(x) => x.hash(state),
+			Self::CellFit(x) => x.hash(state),
+			Self::PixelDiffMode(x) => x.hash(state),
+			Self::ConvolutionPreset(x) => x.hash(state),
+			Self::BorderMode(x) => x.hash(state),
+			Self::MorphologyOperation(x) => x.hash(state),
+			Self::MorphologyChannel(x) => x.hash(state),
+			Self::MorphologyShape(x) => x.hash(state),
+			Self::TextAlign(x) => x.hash(state),
+			Self::TextAutoResize(x) => x.hash(state),
+			Self::FontVariations(x) => x.hash(state),
+			Self::TextRuns(x) => x.hash(state),
+			Self::TextKerning(x) => x.hash(state),
 		}
 	}
 }
@@ -203,8 +257,11 @@ impl<'a> TaggedValue {
 			TaggedValue::VecDVec2(x) => Box::new(x),
 			TaggedValue::RedGreenBlue(x) => Box::new(x),
 			TaggedValue::RedGreenBlueAlpha(x) => Box::new(x),
+			TaggedValue::ChannelSource(x) => Box::new(x),
 			TaggedValue::NoiseType(x) => Box::new(x),
 			TaggedValue::FractalType(x) => Box::new(x),
+			TaggedValue::ColorHarmony(x) => Box::new(x),
+			TaggedValue::Waveform(x) => Box::new(x),
 			TaggedValue::CellularDistanceFunction(x) => Box::new(x),
 			TaggedValue::CellularReturnType(x) => Box::new(x),
 			TaggedValue::DomainWarpType(x) => Box::new(x),
@@ -212,6 +269,7 @@ impl<'a> TaggedValue {
 			TaggedValue::SelectiveColorChoice(x) => Box::new(x),
 			TaggedValue::LineCap(x) => Box::new(x),
 			TaggedValue::LineJoin(x) => Box::new(x),
+			TaggedValue::StrokeAlign(x) => Box::new(x),
 			TaggedValue::FillType(x) => Box::new(x),
 			TaggedValue::FillChoice(x) => Box::new(x),
 			TaggedValue::Gradient(x) => Box::new(x),
@@ -234,7 +292,30 @@ impl<'a> TaggedValue {
 			TaggedValue::RenderOutput(x) => Box::new(x),
 			TaggedValue::Palette(x) => Box::new(x),
 			TaggedValue::CentroidType(x) => Box::new(x),
+			TaggedValue::ArcType(x) => Box::new(x),
+			TaggedValue::WarpMode(x) => Box::new(x),
+			TaggedValue::ProjectionFace(x) => Box::new(x),
+			TaggedValue::QrErrorCorrection(x) => Box::new(x),
+			TaggedValue::BarcodeSymbology(x) => Box::new(x),
+			TaggedValue::PointDistribution(x) => Box::new(x),
+			TaggedValue::PointRegion(x) => Box::new(x),
+			TaggedValue::PointSampling(x) => Box::new(x),
+			TaggedValue::PointCloud(x) => Box::new(x),
 			TaggedValue::BooleanOperation(x) => Box::new(x),
+			TaggedValue::DimensionKind(x) => Box::new(x),
+			TaggedValue::MaskMode(x) => Box::new(x),
+			TaggedValue::CellFit(x) => Box::new(x),
+			TaggedValue::PixelDiffMode(x) => Box::new(x),
+			TaggedValue::ConvolutionPreset(x) => Box::new(x),
+			TaggedValue::BorderMode(x) => Box::new(x),
+			TaggedValue::MorphologyOperation(x) => Box::new(x),
+			TaggedValue::MorphologyChannel(x) => Box::new(x),
+			TaggedValue::MorphologyShape(x) => Box::new(x),
+			TaggedValue::TextAlign(x) => Box::new(x),
+			TaggedValue::TextAutoResize(x) => Box::new(x),
+			TaggedValue::FontVariations(x) => Box::new(x),
+			TaggedValue::TextRuns(x) => Box::new(x),
+			TaggedValue::TextKerning(x) => Box::new(x),
 		}
 	}
 
@@ -284,8 +365,11 @@ impl<'a> TaggedValue {
 			TaggedValue::VecDVec2(_) => concrete!(Vec<DVec2>),
 			TaggedValue::RedGreenBlue(_) => concrete!(graphene_core::raster::RedGreenBlue),
 			TaggedValue::RedGreenBlueAlpha(_) => concrete!(graphene_core::raster::RedGreenBlueAlpha),
+			TaggedValue::ChannelSource(_) => concrete!(graphene_core::raster::ChannelSource),
 			TaggedValue::NoiseType(_) => concrete!(graphene_core::raster::NoiseType),
 			TaggedValue::FractalType(_) => concrete!(graphene_core::raster::FractalType),
+			TaggedValue::ColorHarmony(_) => concrete!(graphene_core::raster::ColorHarmony),
+			TaggedValue::Waveform(_) => concrete!(graphene_core::oscillator::Waveform),
 			TaggedValue::CellularDistanceFunction(_) => concrete!(graphene_core::raster::CellularDistanceFunction),
 			TaggedValue::CellularReturnType(_) => concrete!(graphene_core::raster::CellularReturnType),
 			TaggedValue::DomainWarpType(_) => concrete!(graphene_core::raster::DomainWarpType),
@@ -293,6 +377,7 @@ impl<'a> TaggedValue {
 			TaggedValue::SelectiveColorChoice(_) => concrete!(graphene_core::raster::SelectiveColorChoice),
 			TaggedValue::LineCap(_) => concrete!(graphene_core::vector::style::LineCap),
 			TaggedValue::LineJoin(_) => concrete!(graphene_core::vector::style::LineJoin),
+			TaggedValue::StrokeAlign(_) => concrete!(graphene_core::vector::style::StrokeAlign),
 			TaggedValue::FillType(_) => concrete!(graphene_core::vector::style::FillType),
 			TaggedValue::FillChoice(_) => concrete!(graphene_core::vector::style::FillChoice),
 			TaggedValue::Gradient(_) => concrete!(graphene_core::vector::style::Gradient),
@@ -315,7 +400,30 @@ impl<'a> TaggedValue {
 			TaggedValue::RenderOutput(_) => concrete!(RenderOutput),
 			TaggedValue::Palette(_) => concrete!(Vec<Color>),
 			TaggedValue::CentroidType(_) => concrete!(graphene_core::vector::misc::CentroidType),
+			TaggedValue::ArcType(_) => concrete!(graphene_core::vector::misc::ArcType),
+			TaggedValue::WarpMode(_) => concrete!(graphene_core::warp::WarpMode),
+			TaggedValue::ProjectionFace(_) => concrete!(graphene_core::projection::ProjectionFace),
+			TaggedValue::QrErrorCorrection(_) => concrete!(graphene_core::vector::barcode::QrErrorCorrection),
+			TaggedValue::BarcodeSymbology(_) => concrete!(graphene_core::vector::barcode::BarcodeSymbology),
+			TaggedValue::PointDistribution(_) => concrete!(graphene_core::vector::voronoi::PointDistribution),
+			TaggedValue::PointRegion(_) => concrete!(graphene_core::vector::point_cloud::PointRegion),
+			TaggedValue::PointSampling(_) => concrete!(graphene_core::vector::point_cloud::PointSampling),
+			TaggedValue::PointCloud(_) => concrete!(graphene_core::vector::point_cloud::PointCloud),
 			TaggedValue::BooleanOperation(_) => concrete!(graphene_core::vector::misc::BooleanOperation),
+			TaggedValue::DimensionKind(_) => concrete!(graphene_core::DimensionKind),
+			TaggedValue::MaskMode(_) => concrete!(graphene_core::MaskMode),
+			TaggedValue::CellFit(_) => concrete!(graphene_core::CellFit),
+			TaggedValue::PixelDiffMode(_) => concrete!(graphene_core::raster::PixelDiffMode),
+			TaggedValue::ConvolutionPreset(_) => concrete!(graphene_core::raster::ConvolutionPreset),
+			TaggedValue::BorderMode(_) => concrete!(graphene_core::raster::BorderMode),
+			TaggedValue::MorphologyOperation(_) => concrete!(graphene_core::raster::MorphologyOperation),
+			TaggedValue::MorphologyChannel(_) => concrete!(graphene_core::raster::MorphologyChannel),
+			TaggedValue::MorphologyShape(_) => concrete!(graphene_core::raster::MorphologyShape),
+			TaggedValue::TextAlign(_) => concrete!(graphene_core::text::TextAlign),
+			TaggedValue::TextAutoResize(_) => concrete!(graphene_core::text::TextAutoResize),
+			TaggedValue::FontVariations(_) => concrete!(graphene_core::text::FontVariations),
+			TaggedValue::TextRuns(_) => concrete!(graphene_core::text::TextRuns),
+			TaggedValue::TextKerning(_) => concrete!(graphene_core::text::TextKerning),
 		}
 	}
 
@@ -353,8 +461,11 @@ impl<'a> TaggedValue {
 			x if x == TypeId::of::<Vec<DVec2>>() => Ok(TaggedValue::VecDVec2(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::RedGreenBlue>() => Ok(TaggedValue::RedGreenBlue(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::RedGreenBlueAlpha>() => Ok(TaggedValue::RedGreenBlueAlpha(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::ChannelSource>() => Ok(TaggedValue::ChannelSource(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::NoiseType>() => Ok(TaggedValue::NoiseType(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::FractalType>() => Ok(TaggedValue::FractalType(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::ColorHarmony>() => Ok(TaggedValue::ColorHarmony(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::oscillator::Waveform>() => Ok(TaggedValue::Waveform(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::CellularDistanceFunction>() => Ok(TaggedValue::CellularDistanceFunction(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::CellularReturnType>() => Ok(TaggedValue::CellularReturnType(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::raster::DomainWarpType>() => Ok(TaggedValue::DomainWarpType(*downcast(input).unwrap())),
@@ -362,6 +473,7 @@ impl<'a> TaggedValue {
 			x if x == TypeId::of::<graphene_core::raster::SelectiveColorChoice>() => Ok(TaggedValue::SelectiveColorChoice(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::LineCap>() => Ok(TaggedValue::LineCap(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::LineJoin>() => Ok(TaggedValue::LineJoin(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::style::StrokeAlign>() => Ok(TaggedValue::StrokeAlign(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::FillType>() => Ok(TaggedValue::FillType(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::FillChoice>() => Ok(TaggedValue::FillChoice(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::style::Gradient>() => Ok(TaggedValue::Gradient(*downcast(input).unwrap())),
@@ -387,7 +499,30 @@ impl<'a> TaggedValue {
 			x if x == TypeId::of::<graphene_core::transform::Footprint>() => Ok(TaggedValue::Footprint(*downcast(input).unwrap())),
 			x if x == TypeId::of::<Vec<Color>>() => Ok(TaggedValue::Palette(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::misc::CentroidType>() => Ok(TaggedValue::CentroidType(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::misc::ArcType>() => Ok(TaggedValue::ArcType(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::warp::WarpMode>() => Ok(TaggedValue::WarpMode(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::projection::ProjectionFace>() => Ok(TaggedValue::ProjectionFace(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::barcode::QrErrorCorrection>() => Ok(TaggedValue::QrErrorCorrection(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::barcode::BarcodeSymbology>() => Ok(TaggedValue::BarcodeSymbology(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::voronoi::PointDistribution>() => Ok(TaggedValue::PointDistribution(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::point_cloud::PointRegion>() => Ok(TaggedValue::PointRegion(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::point_cloud::PointSampling>() => Ok(TaggedValue::PointSampling(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::vector::point_cloud::PointCloud>() => Ok(TaggedValue::PointCloud(*downcast(input).unwrap())),
 			x if x == TypeId::of::<graphene_core::vector::misc::BooleanOperation>() => Ok(TaggedValue::BooleanOperation(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::DimensionKind>() => Ok(TaggedValue::DimensionKind(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::MaskMode>() => Ok(TaggedValue::MaskMode(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::CellFit>() => Ok(TaggedValue::CellFit(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::PixelDiffMode>() => Ok(TaggedValue::PixelDiffMode(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::ConvolutionPreset>() => Ok(TaggedValue::ConvolutionPreset(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::BorderMode>() => Ok(TaggedValue::BorderMode(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::MorphologyOperation>() => Ok(TaggedValue::MorphologyOperation(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::MorphologyChannel>() => Ok(TaggedValue::MorphologyChannel(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::raster::MorphologyShape>() => Ok(TaggedValue::MorphologyShape(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::text::TextAlign>() => Ok(TaggedValue::TextAlign(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::text::TextAutoResize>() => Ok(TaggedValue::TextAutoResize(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::text::FontVariations>() => Ok(TaggedValue::FontVariations(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::text::TextRuns>() => Ok(TaggedValue::TextRuns(*downcast(input).unwrap())),
+			x if x == TypeId::of::<graphene_core::text::TextKerning>() => Ok(TaggedValue::TextKerning(*downcast(input).unwrap())),
 			_ => Err(format!("Cannot convert {:?} to TaggedValue", DynAny::type_name(input.as_ref()))),
 		}
 	}
@@ -434,8 +569,11 @@ impl<'a> TaggedValue {
 					x if x == TypeId::of::<Vec<DVec2>>() => TaggedValue::VecDVec2(Default::default()),
 					x if x == TypeId::of::<graphene_core::raster::RedGreenBlue>() => TaggedValue::RedGreenBlue(graphene_core::raster::RedGreenBlue::Red),
 					x if x == TypeId::of::<graphene_core::raster::RedGreenBlueAlpha>() => TaggedValue::RedGreenBlueAlpha(graphene_core::raster::RedGreenBlueAlpha::Red),
+					x if x == TypeId::of::<graphene_core::raster::ChannelSource>() => TaggedValue::ChannelSource(Default::default()),
 					x if x == TypeId::of::<graphene_core::raster::NoiseType>() => TaggedValue::NoiseType(graphene_core::raster::NoiseType::Perlin),
 					x if x == TypeId::of::<graphene_core::raster::FractalType>() => TaggedValue::FractalType(graphene_core::raster::FractalType::None),
+					x if x == TypeId::of::<graphene_core::raster::ColorHarmony>() => TaggedValue::ColorHarmony(graphene_core::raster::ColorHarmony::Complementary),
+					x if x == TypeId::of::<graphene_core::oscillator::Waveform>() => TaggedValue::Waveform(graphene_core::oscillator::Waveform::Sine),
 					x if x == TypeId::of::<graphene_core::raster::CellularDistanceFunction>() => TaggedValue::CellularDistanceFunction(graphene_core::raster::CellularDistanceFunction::Euclidean),
 					x if x == TypeId::of::<graphene_core::raster::CellularReturnType>() => TaggedValue::CellularReturnType(graphene_core::raster::CellularReturnType::Nearest),
 					x if x == TypeId::of::<graphene_core::raster::DomainWarpType>() => TaggedValue::DomainWarpType(graphene_core::raster::DomainWarpType::None),
@@ -443,6 +581,7 @@ impl<'a> TaggedValue {
 					x if x == TypeId::of::<graphene_core::raster::SelectiveColorChoice>() => TaggedValue::SelectiveColorChoice(graphene_core::raster::SelectiveColorChoice::Reds),
 					x if x == TypeId::of::<graphene_core::vector::style::LineCap>() => TaggedValue::LineCap(graphene_core::vector::style::LineCap::Butt),
 					x if x == TypeId::of::<graphene_core::vector::style::LineJoin>() => TaggedValue::LineJoin(graphene_core::vector::style::LineJoin::Miter),
+					x if x == TypeId::of::<graphene_core::vector::style::StrokeAlign>() => TaggedValue::StrokeAlign(Default::default()),
 					x if x == TypeId::of::<graphene_core::vector::style::FillType>() => TaggedValue::FillType(graphene_core::vector::style::FillType::Solid),
 					x if x == TypeId::of::<graphene_core::vector::style::GradientType>() => TaggedValue::GradientType(Default::default()),
 					x if x == TypeId::of::<graphene_core::vector::style::GradientStops>() => TaggedValue::GradientStops(Default::default()),
@@ -467,7 +606,30 @@ impl<'a> TaggedValue {
 					x if x == TypeId::of::<graphene_core::transform::Footprint>() => TaggedValue::Footprint(Default::default()),
 					x if x == TypeId::of::<Vec<Color>>() => TaggedValue::Palette(Default::default()),
 					x if x == TypeId::of::<graphene_core::vector::misc::CentroidType>() => TaggedValue::CentroidType(Default::default()),
+					x if x == TypeId::of::<graphene_core::vector::misc::ArcType>() => TaggedValue::ArcType(Default::default()),
+					x if x == TypeId::of::<graphene_core::warp::WarpMode>() => TaggedValue::WarpMode(Default::default()),
+					x if x == TypeId::of::<graphene_core::projection::ProjectionFace>() => TaggedValue::ProjectionFace(Default::default()),
+					x if x == TypeId::of::<graphene_core::vector::barcode::QrErrorCorrection>() => TaggedValue::QrErrorCorrection(Default::default()),
+					x if x == TypeId::of::<graphene_core::vector::barcode::BarcodeSymbology>() => TaggedValue::BarcodeSymbology(Default::default()),
+					x if x == TypeId::of::<graphene_core::vector::voronoi::PointDistribution>() => TaggedValue::PointDistribution(Default::default()),
+					x if x == TypeId::of::<graphene_core::vector::point_cloud::PointRegion>() => TaggedValue::PointRegion(Default::default()),
+					x if x == TypeId::of::<graphene_core::vector::point_cloud::PointSampling>() => TaggedValue::PointSampling(Default::default()),
+					x if x == TypeId::of::<graphene_core::vector::point_cloud::PointCloud>() => TaggedValue::PointCloud(Default::default()),
 					x if x == TypeId::of::<graphene_core::vector::misc::BooleanOperation>() => TaggedValue::BooleanOperation(Default::default()),
+					x if x == TypeId::of::<graphene_core::DimensionKind>() => TaggedValue::DimensionKind(Default::default()),
+					x if x == TypeId::of::<graphene_core::MaskMode>() => TaggedValue::MaskMode(Default::default()),
+					x if x == TypeId::of::<graphene_core::CellFit>() => TaggedValue::CellFit(Default::default()),
+					x if x == TypeId::of::<graphene_core::raster::PixelDiffMode>() => TaggedValue::PixelDiffMode(Default::default()),
+					x if x == TypeId::of::<graphene_core::raster::ConvolutionPreset>() => TaggedValue::ConvolutionPreset(Default::default()),
+					x if x == TypeId::of::<graphene_core::raster::BorderMode>() => TaggedValue::BorderMode(Default::default()),
+					x if x == TypeId::of::<graphene_core::raster::MorphologyOperation>() => TaggedValue::MorphologyOperation(Default::default()),
+					x if x == TypeId::of::<graphene_core::raster::MorphologyChannel>() => TaggedValue::MorphologyChannel(Default::default()),
+					x if x == TypeId::of::<graphene_core::raster::MorphologyShape>() => TaggedValue::MorphologyShape(Default::default()),
+					x if x == TypeId::of::<graphene_core::text::TextAlign>() => TaggedValue::TextAlign(Default::default()),
+					x if x == TypeId::of::<graphene_core::text::TextAutoResize>() => TaggedValue::TextAutoResize(Default::default()),
+					x if x == TypeId::of::<graphene_core::text::FontVariations>() => TaggedValue::FontVariations(Default::default()),
+					x if x == TypeId::of::<graphene_core::text::TextRuns>() => TaggedValue::TextRuns(Default::default()),
+					x if x == TypeId::of::<graphene_core::text::TextKerning>() => TaggedValue::TextKerning(Default::default()),
 					_ => TaggedValue::None,
 				}
 			}
@@ -487,7 +649,7 @@ impl<'input> Node<'input, DAny<'input>> for UpcastNode {
 	type Output = FutureAny<'input>;
 
 	fn eval(&'input self, _: DAny<'input>) -> Self::Output {
-		Box::pin(async move { self.value.clone().to_any() })
+		Box::pin(async move { Ok(self.value.clone().to_any()) })
 	}
 }
 impl UpcastNode {
@@ -501,5 +663,12 @@ impl UpcastNode {
 pub enum RenderOutput {
 	CanvasFrame(graphene_core::SurfaceFrame),
 	Svg(String),
+	/// An exported SVG along with the image files it links to, named by their path relative to the SVG.
+	SvgWithLinkedImages {
+		svg: String,
+		images: Vec<(String, Vec<u8>)>,
+	},
 	Image(Vec<u8>),
+	/// The vector shapes drawn at one frame of an animation exported to Lottie.
+	LottieFrame(Vec<graphene_core::renderer::LottieShape>),
 }
@@ -10,7 +10,8 @@ pub struct Compiler {}
 impl Compiler {
 	pub fn compile(&self, mut network: NodeNetwork) -> Result<impl Iterator<Item = ProtoNetwork>, String> {
 		println!("flattening");
-		let node_ids = network.nodes.keys().copied().collect::<Vec<_>>();
+		let mut node_ids = network.nodes.keys().copied().collect::<Vec<_>>();
+		node_ids.sort();
 		for id in node_ids {
 			network.flatten(id);
 		}
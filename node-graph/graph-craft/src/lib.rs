@@ -5,6 +5,7 @@ extern crate log;
 extern crate graphene_core;
 pub use graphene_core::{concrete, generic, ProtoNodeIdentifier, Type, TypeDescriptor};
 
+pub mod allocation_pool;
 pub mod document;
 pub mod proto;
 
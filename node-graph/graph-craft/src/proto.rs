@@ -15,7 +15,7 @@ use std::pin::Pin;
 pub type DynFuture<'n, T> = Pin<Box<dyn core::future::Future<Output = T> + 'n>>;
 pub type LocalFuture<'n, T> = Pin<Box<dyn core::future::Future<Output = T> + 'n>>;
 pub type Any<'n> = Box<dyn DynAny<'n> + 'n>;
-pub type FutureAny<'n> = DynFuture<'n, Any<'n>>;
+pub type FutureAny<'n> = DynFuture<'n, Result<Any<'n>, NodeError>>;
 // TODO: is this safe? This is assumed to be send+sync.
 pub type TypeErasedNode<'n> = dyn for<'i> NodeIO<'i, Any<'i>, Output = FutureAny<'i>> + 'n;
 pub type TypeErasedPinnedRef<'n> = Pin<&'n TypeErasedNode<'n>>;
@@ -23,6 +23,43 @@ pub type TypeErasedRef<'n> = &'n TypeErasedNode<'n>;
 pub type TypeErasedBox<'n> = Box<TypeErasedNode<'n>>;
 pub type TypeErasedPinned<'n> = Pin<Box<TypeErasedNode<'n>>>;
 
+/// The start of the proto node identifiers of plugin nodes, which are followed by `<plugin name>/<node name>`.
+pub const PLUGIN_NODE_PREFIX: &str = "plugin::";
+
+/// An error which stopped a type-erased node from producing its output, which in turn fails every node downstream of it.
+#[derive(Clone, Debug, PartialEq, DynAny)]
+pub struct NodeError {
+	/// The proto node which the error came from, once the executor has attributed it.
+	pub node: Option<NodeId>,
+	/// The implementation of the node which the error came from, for diagnostic purposes.
+	pub node_name: &'static str,
+	pub message: String,
+}
+
+impl NodeError {
+	pub fn new(node_name: &'static str, message: impl Into<String>) -> Self {
+		Self {
+			node: None,
+			node_name,
+			message: message.into(),
+		}
+	}
+
+	/// Attributes the error to the given proto node, unless it was already attributed to the node it originated from further upstream.
+	pub fn with_source(mut self, node: NodeId) -> Self {
+		self.node.get_or_insert(node);
+		self
+	}
+}
+
+impl core::fmt::Display for NodeError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{} (in {})", self.message, self.node_name)
+	}
+}
+
+impl std::error::Error for NodeError {}
+
 pub type SharedNodeContainer = std::rc::Rc<NodeContainer>;
 
 pub type NodeConstructor = fn(Vec<SharedNodeContainer>) -> DynFuture<'static, TypeErasedBox<'static>>;
@@ -579,9 +616,28 @@ pub enum GraphErrorType {
 	NoConstructor,
 	InvalidImplementations { parameters: String, error_inputs: Vec<Vec<(usize, (Type, Type))>> },
 	MultipleImplementations { parameters: String, valid: Vec<NodeIOTypes> },
+	// The value flowing into the input at `index` among all inputs of the document node, including hidden ones, doesn't have any type that the input accepts
+	MismatchedConnection { index: usize, expected: Type, found: Type },
+	// The node failed while the network was being evaluated
+	EvaluationFailed(String),
+}
+impl GraphErrorType {
+	/// Describes an input of a node by its `index`, as used in the graph UI.
+	pub fn describe_input(index: usize) -> String {
+		let ordinal = |x: usize| match x.to_string().as_str() {
+			x if x.ends_with('1') && !x.ends_with("11") => format!("{x}st"),
+			x if x.ends_with('2') && !x.ends_with("12") => format!("{x}nd"),
+			x if x.ends_with('3') && !x.ends_with("13") => format!("{x}rd"),
+			x => format!("{x}th"),
+		};
+		if index == 0 {
+			"primary".to_string()
+		} else {
+			format!("{} parameter", ordinal(index))
+		}
+	}
 }
 impl core::fmt::Debug for GraphErrorType {
-	// TODO: format with the document graph context so the input index is the same as in the graph UI.
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
 			GraphErrorType::NodeNotFound(id) => write!(f, "Input node {id} is not present in the typing context"),
@@ -590,14 +646,7 @@ impl core::fmt::Debug for GraphErrorType {
 			GraphErrorType::NoImplementations => write!(f, "No implementations found"),
 			GraphErrorType::NoConstructor => write!(f, "No construct found for node"),
 			GraphErrorType::InvalidImplementations { parameters, error_inputs } => {
-				let ordinal = |x: usize| match x.to_string().as_str() {
-					x if x.ends_with('1') && !x.ends_with("11") => format!("{x}st"),
-					x if x.ends_with('2') && !x.ends_with("12") => format!("{x}nd"),
-					x if x.ends_with('3') && !x.ends_with("13") => format!("{x}rd"),
-					x => format!("{x}th"),
-				};
-				let format_index = |index: usize| if index == 0 { "primary".to_string() } else { format!("{} parameter", ordinal(index)) };
-				let format_error = |(index, (real, expected)): &(usize, (Type, Type))| format!("• The {} input expected {} but found {}", format_index(*index), expected, real);
+				let format_error = |(index, (real, expected)): &(usize, (Type, Type))| format!("• The {} input expected {} but found {}", Self::describe_input(*index), expected, real);
 				let format_error_list = |errors: &Vec<(usize, (Type, Type))>| errors.iter().map(format_error).collect::<Vec<_>>().join("\n");
 				let errors = error_inputs.iter().map(format_error_list).collect::<Vec<_>>();
 				write!(
@@ -614,6 +663,10 @@ impl core::fmt::Debug for GraphErrorType {
 				)
 			}
 			GraphErrorType::MultipleImplementations { parameters, valid } => write!(f, "Multiple implementations found ({parameters}):\n{valid:#?}"),
+			GraphErrorType::MismatchedConnection { index, expected, found } => {
+				write!(f, "The {} input expected {expected} but found {found}", Self::describe_input(*index))
+			}
+			GraphErrorType::EvaluationFailed(message) => write!(f, "Evaluation failed: {message}"),
 		}
 	}
 }
@@ -660,15 +713,47 @@ impl TypingContext {
 		}
 	}
 
+	/// Adds a node implementation which isn't known at compile time, such as a node of a plugin loaded at runtime, replacing any other implementations with the same identifier.
+	pub fn register_node(&mut self, identifier: ProtoNodeIdentifier, types: NodeIOTypes, constructor: NodeConstructor) {
+		self.lookup.to_mut().insert(identifier, HashMap::from([(types, constructor)]));
+	}
+
 	/// Updates the `TypingContext` with a given proto network. This will infer the types of the nodes
 	/// and store them in the `inferred` field. The proto network has to be topologically sorted
 	/// and contain fully resolved stable node ids.
+	///
+	/// Type checking continues past nodes with errors, so the errors of every node are reported at once.
+	/// Nodes downstream of a node with an error are skipped, since their types can't be known.
+	/// The types of nodes removed from the network are only forgotten once it type checks, since the previous network keeps being executed until then.
 	pub fn update(&mut self, network: &ProtoNetwork) -> Result<(), GraphErrors> {
 		let mut deleted_nodes = self.inferred.keys().copied().collect::<HashSet<_>>();
+		let mut failed_nodes = HashSet::new();
+		let mut errors = Vec::new();
 
 		for (id, node) in network.nodes.iter() {
-			self.infer(*id, node)?;
 			deleted_nodes.remove(id);
+
+			let input_node = match node.input {
+				ProtoNodeInput::Node(input_id) | ProtoNodeInput::NodeLambda(input_id) => Some(input_id),
+				_ => None,
+			};
+			let parameter_nodes = match &node.construction_args {
+				ConstructionArgs::Nodes(nodes) => nodes.iter().map(|(parameter_id, _)| *parameter_id).collect(),
+				_ => Vec::new(),
+			};
+			if input_node.into_iter().chain(parameter_nodes).any(|upstream_node| failed_nodes.contains(&upstream_node)) {
+				failed_nodes.insert(*id);
+				continue;
+			}
+
+			if let Err(node_errors) = self.infer(*id, node) {
+				failed_nodes.insert(*id);
+				errors.extend(node_errors);
+			}
+		}
+
+		if !errors.is_empty() {
+			return Err(errors);
 		}
 
 		for node in deleted_nodes {
@@ -788,7 +873,6 @@ impl TypingContext {
 
 		match valid_impls.as_slice() {
 			[] => {
-				dbg!(&self.inferred);
 				let mut best_errors = usize::MAX;
 				let mut error_inputs = Vec::new();
 				for node_io in impls.keys() {
@@ -799,7 +883,6 @@ impl TypingContext {
 						.zip([&node_io.input].into_iter().chain(&node_io.parameters).cloned())
 						.enumerate()
 						.filter(|(_, (p1, p2))| !valid_subtype(p1, p2))
-						.map(|(index, ty)| (node.original_location.inputs(index).min_by_key(|s| s.node.len()).map(|s| s.index).unwrap_or(index), ty))
 						.collect::<Vec<_>>();
 					if current_errors.len() < best_errors {
 						best_errors = current_errors.len();
@@ -809,6 +892,29 @@ impl TypingContext {
 						error_inputs.push(current_errors);
 					}
 				}
+
+				// If a single implementation comes closest to matching, its inputs are what the node expects, so each mismatched input can be reported on its own.
+				// The inputs of the proto node are offset from those of the document node by the call argument added for manual composition, which has no document input to report.
+				let skip_inputs = node.original_location.skip_inputs;
+				if let Some(mismatched_inputs) = error_inputs
+					.first()
+					.filter(|inputs| error_inputs.len() == 1 && !inputs.is_empty() && inputs.iter().all(|(index, _)| *index >= skip_inputs))
+				{
+					return Err(mismatched_inputs
+						.iter()
+						.map(|(index, (found, expected))| {
+							let error = GraphErrorType::MismatchedConnection {
+								index: index - skip_inputs,
+								expected: expected.clone(),
+								found: found.clone(),
+							};
+							GraphError::new(node, error)
+						})
+						.collect());
+				}
+
+				let ui_index = |index| node.original_location.inputs(index).min_by_key(|s| s.node.len()).map(|s| s.index).unwrap_or(index);
+				let error_inputs = error_inputs.into_iter().map(|inputs| inputs.into_iter().map(|(index, ty)| (ui_index(index), ty)).collect()).collect();
 				let parameters = [&input].into_iter().chain(&parameters).map(|t| t.to_string()).collect::<Vec<_>>().join(", ");
 				Err(vec![GraphError::new(node, GraphErrorType::InvalidImplementations { parameters, error_inputs })])
 			}
@@ -940,6 +1046,105 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn type_errors_are_reported_for_each_mismatched_connection() {
+		fn constructor(_: Vec<SharedNodeContainer>) -> DynFuture<'static, TypeErasedBox<'static>> {
+			unimplemented!()
+		}
+		let float_identifier = ProtoNodeIdentifier::new("float");
+		let implementations: HashMap<_, _> = [(NodeIOTypes::new(concrete!(f64), concrete!(f64), vec![]), constructor as NodeConstructor)].into_iter().collect();
+		let lookup: &'static HashMap<_, _> = Box::leak(Box::new([(float_identifier.clone(), implementations)].into_iter().collect()));
+		let mut typing_context = TypingContext::new(lookup);
+
+		let value_node = |value| ProtoNode {
+			identifier: "value".into(),
+			input: ProtoNodeInput::None,
+			construction_args: ConstructionArgs::Value(value),
+			..Default::default()
+		};
+		let float_node = |input| ProtoNode {
+			identifier: float_identifier.clone(),
+			input: ProtoNodeInput::Node(input),
+			construction_args: ConstructionArgs::Nodes(vec![]),
+			..Default::default()
+		};
+		let network = ProtoNetwork {
+			inputs: vec![],
+			output: NodeId(5),
+			nodes: vec![
+				(NodeId(1), value_node(value::TaggedValue::U32(2))),
+				(NodeId(2), float_node(NodeId(1))),
+				(NodeId(3), float_node(NodeId(2))),
+				(NodeId(4), value_node(value::TaggedValue::String("2".into()))),
+				(NodeId(5), float_node(NodeId(4))),
+			],
+		};
+
+		// Both mismatched connections are reported, but not the node downstream of the first one, whose input type can't be known
+		let errors = typing_context.update(&network).expect_err("The network should not type check");
+		let found_types = errors
+			.iter()
+			.map(|error| match &error.error {
+				GraphErrorType::MismatchedConnection { index: 0, expected, found } if *expected == concrete!(f64) => found.clone(),
+				error => panic!("Unexpected error {error:?}"),
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(found_types, vec![concrete!(u32), concrete!(String)]);
+	}
+
+	#[test]
+	fn type_errors_of_manually_composed_nodes_refer_to_their_document_inputs() {
+		fn constructor(_: Vec<SharedNodeContainer>) -> DynFuture<'static, TypeErasedBox<'static>> {
+			unimplemented!()
+		}
+		let float_identifier = ProtoNodeIdentifier::new("float");
+		let implementations: HashMap<_, _> = [(NodeIOTypes::new(concrete!(u32), concrete!(f64), vec![fn_type!(f64)]), constructor as NodeConstructor)]
+			.into_iter()
+			.collect();
+		let lookup: &'static HashMap<_, _> = Box::leak(Box::new([(float_identifier.clone(), implementations)].into_iter().collect()));
+		let mut typing_context = TypingContext::new(lookup);
+
+		let network = |value| ProtoNetwork {
+			inputs: vec![],
+			output: NodeId(2),
+			nodes: vec![
+				(
+					NodeId(1),
+					ProtoNode {
+						identifier: "value".into(),
+						input: ProtoNodeInput::None,
+						construction_args: ConstructionArgs::Value(value),
+						..Default::default()
+					},
+				),
+				(
+					NodeId(2),
+					ProtoNode {
+						identifier: float_identifier.clone(),
+						input: ProtoNodeInput::ManualComposition(concrete!(u32)),
+						construction_args: ConstructionArgs::Nodes(vec![(NodeId(1), false)]),
+						original_location: OriginalLocation { skip_inputs: 1, ..Default::default() },
+						..Default::default()
+					},
+				),
+			],
+		};
+		typing_context.update(&network(value::TaggedValue::F64(2.))).expect("The network should type check");
+
+		// The first parameter of the proto node is the first input of the document node, since the call argument isn't a document input
+		let errors = typing_context.update(&network(value::TaggedValue::String("2".into()))).expect_err("The network should not type check");
+		assert!(matches!(
+			&errors[..],
+			[GraphError {
+				error: GraphErrorType::MismatchedConnection { index: 0, .. },
+				..
+			}]
+		));
+
+		// The types of the network which type checked are kept, since it keeps being executed
+		assert_eq!(typing_context.type_of(NodeId(2)).map(|types| types.output.clone()), Some(concrete!(f64)));
+	}
+
 	fn test_network() -> ProtoNetwork {
 		ProtoNetwork {
 			inputs: vec![NodeId(10)],
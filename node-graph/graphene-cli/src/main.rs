@@ -1,69 +1,279 @@
+use graph_craft::document::value::TaggedValue;
 use graph_craft::document::*;
-use graph_craft::graphene_compiler::Executor;
+use graph_craft::graphene_compiler::{Compiler, Executor};
 use graph_craft::imaginate_input::ImaginatePreferences;
-use graph_craft::{concrete, ProtoNodeIdentifier};
-use graphene_core::application_io::{ApplicationIo, NodeGraphUpdateSender};
+use graph_craft::{concrete, generic, ProtoNodeIdentifier};
+use graphene_core::application_io::{ApplicationIo, ExportFormat, NodeGraphUpdateSender, RenderConfig};
+use graphene_core::renderer::SvgOptimization;
 use graphene_core::text::FontCache;
-use graphene_std::wasm_application_io::{WasmApplicationIo, WasmEditorApi};
+use graphene_core::transform::{Footprint, RenderTarget};
+use graphene_std::wasm_application_io::{RenderOutput, WasmApplicationIo, WasmEditorApi};
 use interpreted_executor::dynamic_executor::DynamicExecutor;
 
 use fern::colors::{Color, ColoredLevelConfig};
-use futures::executor::block_on;
+use glam::{DAffine2, DVec2, UVec2};
+use std::path::{Path, PathBuf};
 use std::{error::Error, sync::Arc};
 
+const USAGE: &str = "\
+Renders the artboards of a Graphite document without the editor
+
+Usage: graphene-cli <DOCUMENT> [OPTIONS]
+
+The document can be a saved document (.graphite) or a node graph saved as text (.graphite-graph).
+
+Options:
+  -o, --output <PATH>       Where to write the render, named after each artboard when rendering several [default: the document path]
+  -f, --format <FORMAT>     svg or png [default: taken from the output extension, otherwise svg]
+  -a, --artboard <NAME>     Render only the artboard with this name, may be given more than once [default: every artboard]
+  -s, --scale <FACTOR>      Multiplies the size of each artboard to get the output resolution [default: 1]
+  -r, --resolution <WxH>    Renders each artboard at exactly this resolution, stretching it if the aspect ratio differs
+  -p, --precision <DIGITS>  Optimizes the SVG, merging groups and definitions and rounding coordinates to this many decimal places
+  -i, --image <PATH>        Image made available to the graph as the `null` resource
+  -v, --verbose             Logs everything the graph does while rendering
+  -h, --help                Prints this message";
+
 struct UpdateLogger {}
 
 impl NodeGraphUpdateSender for UpdateLogger {
 	fn send(&self, message: graphene_core::application_io::NodeGraphUpdateMessage) {
-		println!("{message:?}");
+		log::debug!("{message:?}");
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+	Svg,
+	Png,
+}
+
+impl OutputFormat {
+	fn from_extension(path: &Path) -> Option<Self> {
+		match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+			"svg" => Some(Self::Svg),
+			"png" => Some(Self::Png),
+			_ => None,
+		}
+	}
+
+	fn extension(&self) -> &'static str {
+		match self {
+			Self::Svg => "svg",
+			Self::Png => "png",
+		}
+	}
+}
+
+#[derive(Debug, Default)]
+struct Options {
+	document_path: PathBuf,
+	output_path: Option<PathBuf>,
+	format: Option<OutputFormat>,
+	artboards: Vec<String>,
+	scale: Option<f64>,
+	resolution: Option<UVec2>,
+	svg_optimization: Option<SvgOptimization>,
+	image_path: Option<PathBuf>,
+	verbose: bool,
+}
+
+impl Options {
+	fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+		let mut options = Self::default();
+		let mut document_path = None;
+
+		while let Some(arg) = args.next() {
+			let mut value = |name: &str| args.next().ok_or_else(|| format!("Missing value for {name}"));
+			match arg.as_str() {
+				"-h" | "--help" => return Err(USAGE.to_string()),
+				"-o" | "--output" => options.output_path = Some(value(&arg)?.into()),
+				"-f" | "--format" => {
+					let format = value(&arg)?;
+					options.format = Some(OutputFormat::from_extension(Path::new(&format!("output.{format}"))).ok_or_else(|| format!("Unknown format `{format}`, expected svg or png"))?);
+				}
+				"-a" | "--artboard" => options.artboards.push(value(&arg)?),
+				"-s" | "--scale" => {
+					let scale = value(&arg)?;
+					options.scale = Some(scale.parse().ok().filter(|scale: &f64| *scale > 0.).ok_or_else(|| format!("Invalid scale `{scale}`"))?);
+				}
+				"-r" | "--resolution" => {
+					let resolution = value(&arg)?;
+					let parsed = resolution.split_once('x').and_then(|(width, height)| Some(UVec2::new(width.parse().ok()?, height.parse().ok()?)));
+					options.resolution = Some(
+						parsed
+							.filter(|resolution| resolution.cmpgt(UVec2::ZERO).all())
+							.ok_or_else(|| format!("Invalid resolution `{resolution}`, expected WIDTHxHEIGHT"))?,
+					);
+				}
+				"-p" | "--precision" => {
+					let precision = value(&arg)?;
+					let precision = precision.parse().map_err(|_| format!("Invalid precision `{precision}`, expected a number of decimal places"))?;
+					options.svg_optimization = Some(SvgOptimization { precision });
+				}
+				"-i" | "--image" => options.image_path = Some(value(&arg)?.into()),
+				"-v" | "--verbose" => options.verbose = true,
+				_ if arg.starts_with('-') => return Err(format!("Unknown option `{arg}`\n\n{USAGE}")),
+				_ if document_path.is_none() => document_path = Some(PathBuf::from(arg)),
+				_ => return Err(format!("Unexpected argument `{arg}`\n\n{USAGE}")),
+			}
+		}
+
+		if options.scale.is_some() && options.resolution.is_some() {
+			return Err("Only one of --scale and --resolution can be given".to_string());
+		}
+		options.document_path = document_path.ok_or_else(|| format!("No document path provided\n\n{USAGE}"))?;
+		Ok(options)
+	}
+
+	fn format(&self) -> OutputFormat {
+		self.format.or_else(|| self.output_path.as_deref().and_then(OutputFormat::from_extension)).unwrap_or(OutputFormat::Svg)
+	}
+
+	/// The file to write the render of the artboard to, which is named after the artboard if more than one is being rendered.
+	fn output_path(&self, artboard: &Artboard, artboard_count: usize) -> PathBuf {
+		let format = self.format();
+		let path = self.output_path.clone().unwrap_or_else(|| self.document_path.with_extension(format.extension()));
+		if artboard_count <= 1 {
+			return path;
+		}
+
+		let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+		let extension = path
+			.extension()
+			.map(|extension| extension.to_string_lossy().into_owned())
+			.unwrap_or_else(|| format.extension().to_string());
+		path.with_file_name(format!("{stem} - {}.{extension}", artboard.name))
+	}
+}
+
+/// A region of the document to render on its own.
+#[derive(Debug, Clone, PartialEq)]
+struct Artboard {
+	name: String,
+	/// The bounds of the artboard in document space.
+	bounds: [DVec2; 2],
+}
+
+impl Artboard {
+	/// The footprint rendering the artboard to the output, at the requested scale or resolution.
+	fn footprint(&self, options: &Options) -> Footprint {
+		let size = (self.bounds[1] - self.bounds[0]).max(DVec2::ONE);
+		let (scale, resolution) = match options.resolution {
+			Some(resolution) => (resolution.as_dvec2() / size, resolution),
+			None => {
+				let scale = options.scale.unwrap_or(1.);
+				(DVec2::splat(scale), (size * scale).round().as_uvec2().max(UVec2::ONE))
+			}
+		};
+
+		Footprint {
+			transform: DAffine2::from_scale(scale) * DAffine2::from_translation(-self.bounds[0]),
+			resolution,
+			target: RenderTarget::Export,
+			..Default::default()
+		}
 	}
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-	init_logging();
+	let options = match Options::parse(std::env::args().skip(1)) {
+		Ok(options) => options,
+		Err(message) => {
+			// Asking for help isn't a failure
+			if message == USAGE {
+				println!("{message}");
+				return Ok(());
+			}
+			eprintln!("{message}");
+			std::process::exit(2);
+		}
+	};
+	init_logging(options.verbose);
 
-	let document_path = std::env::args().nth(1).expect("No document path provided");
+	let document_string = std::fs::read_to_string(&options.document_path).map_err(|error| format!("Failed to read {}: {error}", options.document_path.display()))?;
+	let network = load_network(&options.document_path, &document_string)?;
 
-	let image_path = std::env::args().nth(2);
+	let mut artboards = document_artboards(&network);
+	if artboards.is_empty() {
+		// Documents without artboards are rendered from the origin of the document, at the size of the default footprint
+		let size = Footprint::default().resolution.as_dvec2();
+		artboards.push(Artboard {
+			name: "Document".to_string(),
+			bounds: [DVec2::ZERO, size],
+		});
+	}
+	if !options.artboards.is_empty() {
+		if let Some(missing) = options.artboards.iter().find(|name| !artboards.iter().any(|artboard| &artboard.name == *name)) {
+			let available = artboards.iter().map(|artboard| artboard.name.as_str()).collect::<Vec<_>>().join(", ");
+			return Err(format!("The document has no artboard named `{missing}` (it has: {available})").into());
+		}
+		artboards.retain(|artboard| options.artboards.contains(&artboard.name));
+	}
 
-	let document_string = std::fs::read_to_string(&document_path).expect("Failed to read document");
+	let executor = create_executor(network).await?;
+	let mut application_io = WasmApplicationIo::new().await;
+	if let Some(image_path) = &options.image_path {
+		let image = std::fs::read(image_path).map_err(|error| format!("Failed to read {}: {error}", image_path.display()))?;
+		application_io.resources.insert("null".to_string(), Arc::from(image));
+	}
 
-	let executor = create_executor(document_string)?;
-	println!("creating gpu context",);
-	let mut application_io = block_on(WasmApplicationIo::new());
-	if let Some(image_path) = image_path {
-		application_io.resources.insert("null".to_string(), Arc::from(std::fs::read(image_path).expect("Failed to read image")));
+	// Nodes running on the GPU wait for the device to finish their work, which nothing else drives without a window
+	if let Some(gpu_executor) = application_io.gpu_executor() {
+		let device = gpu_executor.context.device.clone();
+		std::thread::spawn(move || loop {
+			std::thread::sleep(std::time::Duration::from_millis(1));
+			device.poll(wgpu::Maintain::Poll);
+		});
 	}
 
-	let device = application_io.gpu_executor().unwrap().context.device.clone();
-	std::thread::spawn(move || loop {
-		std::thread::sleep(std::time::Duration::from_nanos(10));
-		device.poll(wgpu::Maintain::Poll);
-	});
+	let font_cache = FontCache::default();
+	let imaginate_preferences = ImaginatePreferences::default();
+	for artboard in &artboards {
+		let footprint = artboard.footprint(&options);
+		let editor_api = WasmEditorApi {
+			image_frame: None,
+			font_cache: &font_cache,
+			application_io: &application_io,
+			node_graph_message_sender: &UpdateLogger {},
+			imaginate_preferences: &imaginate_preferences,
+			render_config: RenderConfig {
+				viewport: footprint,
+				export_format: ExportFormat::Svg,
+				for_export: true,
+				svg_optimization: options.svg_optimization,
+				..Default::default()
+			},
+			onion_skin: Vec::new().into(),
+			image_previews: Default::default(),
+		};
 
-	let editor_api = WasmEditorApi {
-		image_frame: None,
-		font_cache: &FontCache::default(),
-		application_io: &application_io,
-		node_graph_message_sender: &UpdateLogger {},
-		imaginate_preferences: &ImaginatePreferences::default(),
-		render_config: graphene_core::application_io::RenderConfig::default(),
-	};
+		let TaggedValue::RenderOutput(RenderOutput::Svg(svg)) = (&executor).execute(editor_api).await? else {
+			return Err("The graph didn't render an SVG".into());
+		};
+		// A node which failed was drawn as a placeholder, which mustn't be written as if it were the artwork
+		if let Some(error) = executor.take_evaluation_errors().into_iter().next() {
+			return Err(format!("Failed to render {}: {error}", artboard.name).into());
+		}
 
-	loop {
-		let _result = (&executor).execute(editor_api.clone()).await?;
-		std::thread::sleep(std::time::Duration::from_millis(16));
+		let output_path = options.output_path(artboard, artboards.len());
+		match options.format() {
+			OutputFormat::Svg => std::fs::write(&output_path, svg)?,
+			OutputFormat::Png => rasterize(&svg, footprint.resolution)?.save(&output_path)?,
+		}
+		log::info!("Rendered {} at {}x{} to {}", artboard.name, footprint.resolution.x, footprint.resolution.y, output_path.display());
 	}
+
+	Ok(())
 }
 
-fn init_logging() {
+fn init_logging(verbose: bool) {
 	let colors = ColoredLevelConfig::new().debug(Color::Magenta).info(Color::Green).error(Color::Red);
 	fern::Dispatch::new()
-		.chain(std::io::stdout())
-		.level_for("iced", log::LevelFilter::Trace)
-		.level_for("wgpu", log::LevelFilter::Debug)
-		.level(log::LevelFilter::Trace)
+		.chain(std::io::stderr())
+		.level_for("wgpu", log::LevelFilter::Warn)
+		.level_for("naga", log::LevelFilter::Warn)
+		.level(if verbose { log::LevelFilter::Trace } else { log::LevelFilter::Info })
 		.format(move |out, message, record| {
 			out.finish(format_args!(
 				"[{}]{} {}",
@@ -77,17 +287,128 @@ fn init_logging() {
 		.unwrap();
 }
 
-fn create_executor(_document_string: String) -> Result<DynamicExecutor, Box<dyn Error>> {
-	// let document: serde_json::Value = serde_json::from_str(&document_string).expect("Failed to parse document");
-	// let document = serde_json::from_value::<Document>(document["document_legacy"].clone()).expect("Failed to parse document");
-	// let Some(LegacyLayerType::Layer(ref network)) = document.root.iter().find(|layer| matches!(layer, LegacyLayerType::Layer(_))) else {
-	panic!("Failed to extract node graph from document")
-	// };
-	// let wrapped_network = wrap_network_in_scope(network.clone());
-	// let compiler = Compiler {};
-	// let protograph = compiler.compile_single(wrapped_network)?;
-	// let executor = block_on(DynamicExecutor::new(protograph))?;
-	// Ok(executor)
+/// Reads the document network from a saved document, or from a node graph saved as text.
+fn load_network(path: &Path, document_string: &str) -> Result<NodeNetwork, Box<dyn Error>> {
+	if path.to_string_lossy().ends_with(".graphite-graph") {
+		return Ok(graph_craft::document::text_format::network_from_text(document_string)?);
+	}
+
+	let mut document: serde_json::Value = serde_json::from_str(document_string)?;
+	let network = document.get_mut("network").map(serde_json::Value::take).ok_or("The document has no node graph")?;
+	Ok(serde_json::from_value(network)?)
+}
+
+/// Finds the artboards in the document network, in the order they were created, by reading the inputs of their nodes.
+fn document_artboards(network: &NodeNetwork) -> Vec<Artboard> {
+	let mut nodes = network.nodes.iter().filter(|(_, node)| node.name == "Artboard").collect::<Vec<_>>();
+	nodes.sort_by_key(|(id, _)| **id);
+
+	nodes
+		.into_iter()
+		.filter_map(|(_, node)| {
+			let value = |index: usize| match node.inputs.get(index)?.as_value()? {
+				TaggedValue::IVec2(value) => Some(value.as_dvec2()),
+				_ => None,
+			};
+			let (location, dimensions) = (value(2)?, value(3)?);
+			let corners = [location, location + dimensions];
+			Some(Artboard {
+				name: if node.alias.is_empty() { node.name.clone() } else { node.alias.clone() },
+				bounds: [corners[0].min(corners[1]), corners[0].max(corners[1])],
+			})
+		})
+		.collect()
+}
+
+async fn create_executor(network: NodeNetwork) -> Result<DynamicExecutor, Box<dyn Error>> {
+	let wrapped_network = wrap_network_in_scope(network);
+	let compiler = Compiler {};
+	let protograph = compiler.compile_single(wrapped_network)?;
+	let executor = DynamicExecutor::new(protograph).await.map_err(|errors| format!("Failed to build the graph: {errors:?}"))?;
+	Ok(executor)
+}
+
+/// Rasterizes the rendered SVG at the resolution it was rendered for.
+fn rasterize(svg: &str, resolution: UVec2) -> Result<image::RgbaImage, Box<dyn Error>> {
+	let tree = resvg::usvg::Tree::from_str(svg, &resvg::usvg::Options::default())?;
+	let mut pixmap = resvg::tiny_skia::Pixmap::new(resolution.x, resolution.y).ok_or("The resolution is too large to rasterize")?;
+	resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+	let pixels = pixmap.pixels().iter().flat_map(|pixel| {
+		let pixel = pixel.demultiply();
+		[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+	});
+	Ok(image::RgbaImage::from_vec(resolution.x, resolution.y, pixels.collect()).expect("The pixmap has the size of the image"))
+}
+
+/// Wraps the document network in the scope that gives its nodes access to the editor API, followed by the node rendering its output.
+/// This mirrors how the editor's node runtime wraps the document network.
+fn wrap_network_in_scope(mut network: NodeNetwork) -> NodeNetwork {
+	network.generate_node_paths(&[]);
+
+	let inner_network = DocumentNode {
+		name: "Scope".to_string(),
+		implementation: DocumentNodeImplementation::Network(network),
+		inputs: vec![NodeInput::node(NodeId(0), 1)],
+		..Default::default()
+	};
+
+	let render_node = DocumentNode {
+		name: "Output".into(),
+		inputs: vec![NodeInput::node(NodeId(1), 0), NodeInput::node(NodeId(0), 1)],
+		implementation: DocumentNodeImplementation::Network(NodeNetwork {
+			exports: vec![NodeInput::node(NodeId(2), 0)],
+			nodes: [
+				DocumentNode {
+					name: "Create Canvas".to_string(),
+					inputs: vec![NodeInput::network(concrete!(WasmEditorApi), 1)],
+					implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_std::wasm_application_io::CreateSurfaceNode")),
+					skip_deduplication: true,
+					..Default::default()
+				},
+				DocumentNode {
+					name: "Cache".to_string(),
+					manual_composition: Some(concrete!(())),
+					inputs: vec![NodeInput::node(NodeId(0), 0)],
+					implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::memo::MemoNode<_, _>")),
+					..Default::default()
+				},
+				DocumentNode {
+					name: "RenderNode".to_string(),
+					inputs: vec![
+						NodeInput::network(concrete!(WasmEditorApi), 1),
+						NodeInput::network(graphene_core::Type::Fn(Box::new(concrete!(Footprint)), Box::new(generic!(T))), 0),
+						NodeInput::node(NodeId(1), 0),
+					],
+					implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_std::wasm_application_io::RenderNode<_, _, _>")),
+					..Default::default()
+				},
+			]
+			.into_iter()
+			.enumerate()
+			.map(|(id, node)| (NodeId(id as u64), node))
+			.collect(),
+			..Default::default()
+		}),
+		..Default::default()
+	};
+
+	let end_scope = DocumentNode {
+		name: "End Scope".to_string(),
+		inputs: vec![NodeInput::node(NodeId(0), 0), NodeInput::node(NodeId(2), 0)],
+		implementation: DocumentNodeImplementation::proto("graphene_core::memo::EndLetNode<_, _>"),
+		..Default::default()
+	};
+
+	NodeNetwork {
+		exports: vec![NodeInput::node(NodeId(3), 0)],
+		nodes: [begin_scope(), inner_network, render_node, end_scope]
+			.into_iter()
+			.enumerate()
+			.map(|(id, node)| (NodeId(id as u64), node))
+			.collect(),
+		..Default::default()
+	}
 }
 
 fn begin_scope() -> DocumentNode {
@@ -110,7 +431,7 @@ fn begin_scope() -> DocumentNode {
 				},
 				DocumentNode {
 					name: "RefNode".to_string(),
-					manual_composition: Some(concrete!(WasmEditorApi)),
+					manual_composition: Some(concrete!(())),
 					inputs: vec![NodeInput::lambda(NodeId(1), 0)],
 					implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::memo::RefNode<_, _>")),
 					..Default::default()
@@ -128,41 +449,49 @@ fn begin_scope() -> DocumentNode {
 	}
 }
 
-// #[cfg(test)]
-// mod test {
-// 	use super::*;
-
-// 	#[tokio::test]
-// 	#[cfg_attr(not(feature = "wayland"), ignore)]
-// 	async fn grays_scale() {
-// 		let document_string = include_str!("../test_files/gray.graphite");
-// 		let executor = create_executor(document_string.to_string()).unwrap();
-// 		let editor_api = WasmEditorApi {
-// 			image_frame: None,
-// 			font_cache: &FontCache::default(),
-// 			application_io: &block_on(WasmApplicationIo::new()),
-// 			node_graph_message_sender: &UpdateLogger {},
-// 			imaginate_preferences: &ImaginatePreferences::default(),
-// 			render_config: graphene_core::application_io::RenderConfig::default(),
-// 		};
-// 		let result = (&executor).execute(editor_api.clone()).await.unwrap();
-// 		println!("result: {result:?}");
-// 	}
-
-// 	#[tokio::test]
-// 	#[cfg_attr(not(feature = "wayland"), ignore)]
-// 	async fn hue() {
-// 		let document_string = include_str!("../test_files/hue.graphite");
-// 		let executor = create_executor(document_string.to_string()).unwrap();
-// 		let editor_api = WasmEditorApi {
-// 			image_frame: None,
-// 			font_cache: &FontCache::default(),
-// 			application_io: &block_on(WasmApplicationIo::new()),
-// 			node_graph_message_sender: &UpdateLogger {},
-// 			imaginate_preferences: &ImaginatePreferences::default(),
-// 			render_config: graphene_core::application_io::RenderConfig::default(),
-// 		};
-// 		let result = (&executor).execute(editor_api.clone()).await.unwrap();
-// 		println!("result: {result:?}");
-// 	}
-// }
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn options(args: &[&str]) -> Result<Options, String> {
+		Options::parse(args.iter().map(|arg| arg.to_string()))
+	}
+
+	#[test]
+	fn parse_options() {
+		let parsed = options(&["art.graphite", "-o", "out.png", "--artboard", "Cover", "-r", "640x480"]).unwrap();
+		assert_eq!(parsed.document_path, PathBuf::from("art.graphite"));
+		assert_eq!(parsed.format(), OutputFormat::Png);
+		assert_eq!(parsed.artboards, ["Cover"]);
+		assert_eq!(parsed.resolution, Some(UVec2::new(640, 480)));
+
+		assert!(options(&[]).is_err());
+		assert!(options(&["art.graphite", "-s", "2", "-r", "640x480"]).is_err());
+		assert!(options(&["art.graphite", "-r", "640"]).is_err());
+		assert!(options(&["art.graphite", "--format", "gif"]).is_err());
+
+		let parsed = options(&["art.graphite", "--precision", "2"]).unwrap();
+		assert_eq!(parsed.svg_optimization, Some(SvgOptimization { precision: 2 }));
+		assert!(options(&["art.graphite", "-p", "-1"]).is_err());
+	}
+
+	#[test]
+	fn artboard_footprint_and_output_path() {
+		let artboard = Artboard {
+			name: "Cover".to_string(),
+			bounds: [DVec2::new(100., 50.), DVec2::new(300., 150.)],
+		};
+
+		let footprint = artboard.footprint(&options(&["art.graphite", "-s", "2"]).unwrap());
+		assert_eq!(footprint.resolution, UVec2::new(400, 200));
+		assert_eq!(footprint.transform.transform_point2(DVec2::new(100., 50.)), DVec2::ZERO);
+		assert!(footprint.is_export());
+
+		let footprint = artboard.footprint(&options(&["art.graphite", "-r", "100x100"]).unwrap());
+		assert_eq!(footprint.transform.transform_point2(DVec2::new(300., 150.)), DVec2::new(100., 100.));
+
+		let parsed = options(&["art.graphite"]).unwrap();
+		assert_eq!(parsed.output_path(&artboard, 1), PathBuf::from("art.svg"));
+		assert_eq!(parsed.output_path(&artboard, 2), PathBuf::from("art - Cover.svg"));
+	}
+}
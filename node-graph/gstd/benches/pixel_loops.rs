@@ -0,0 +1,78 @@
+//! Compares adjusting and blending the pixels of a canvas one at a time with doing so in lanes.
+//! Run with `cargo bench -p graphene-std --bench pixel_loops`, which prints the time each takes for a 1920 × 1080 canvas.
+
+use graphene_core::raster::lanes::AdjustPixels;
+use graphene_core::raster::{blend_colors, blend_pixels, BlendMode, HueSaturationNode, LevelsNode};
+use graphene_core::value::ClonedNode;
+use graphene_core::{Color, Node};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WIDTH: usize = 1920;
+const HEIGHT: usize = 1080;
+const RUNS: u32 = 10;
+
+fn canvas(seed: usize) -> Vec<Color> {
+	(0..WIDTH * HEIGHT)
+		.map(|i| {
+			let value = |offset: usize| ((i * 7 + offset * 13 + seed) % 256) as f32 / 255.;
+			Color::from_unassociated_alpha(value(0), value(1), value(2), value(3))
+		})
+		.collect()
+}
+
+/// The fastest of several runs, each on a fresh copy of the pixels.
+fn time(pixels: &[Color], mut run: impl FnMut(&mut [Color])) -> Duration {
+	(0..RUNS)
+		.map(|_| {
+			let mut pixels = pixels.to_vec();
+			let start = Instant::now();
+			run(black_box(&mut pixels));
+			let elapsed = start.elapsed();
+			black_box(pixels);
+			elapsed
+		})
+		.min()
+		.unwrap_or_default()
+}
+
+fn report(name: &str, scalar: Duration, lanes: Duration) {
+	let speedup = scalar.as_secs_f64() / lanes.as_secs_f64();
+	println!(
+		"{name:<24} one at a time {:>8.2} ms   in lanes {:>8.2} ms   {speedup:.1}× faster",
+		scalar.as_secs_f64() * 1000.,
+		lanes.as_secs_f64() * 1000.
+	);
+}
+
+fn bench_adjustment<N>(name: &str, pixels: &[Color], node: N)
+where
+	N: AdjustPixels + for<'i> Node<'i, Color, Output = Color>,
+{
+	let scalar = time(pixels, |pixels| pixels.iter_mut().for_each(|pixel| *pixel = node.eval(*pixel)));
+	let lanes = time(pixels, |pixels| node.adjust_pixels(pixels));
+	report(name, scalar, lanes);
+}
+
+fn main() {
+	let background = canvas(0);
+	let foreground = canvas(101);
+
+	bench_adjustment(
+		"Levels",
+		&background,
+		LevelsNode::new(ClonedNode::new(10.), ClonedNode::new(40.), ClonedNode::new(90.), ClonedNode::new(5.), ClonedNode::new(95.)),
+	);
+	bench_adjustment("Hue/Saturation", &background, HueSaturationNode::new(ClonedNode::new(30.), ClonedNode::new(20.), ClonedNode::new(-10.)));
+
+	for blend_mode in [BlendMode::Normal, BlendMode::Multiply, BlendMode::Screen, BlendMode::SoftLight] {
+		let scalar = time(&background, |background| {
+			for (foreground, background) in foreground.iter().zip(background) {
+				*background = blend_colors(*foreground, *background, blend_mode, 0.75);
+			}
+		});
+		let lanes = time(&background, |background| blend_pixels(&foreground, background, blend_mode, 0.75, true));
+		report(&format!("Blend ({blend_mode})"), scalar, lanes);
+	}
+}
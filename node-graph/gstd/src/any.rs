@@ -1,14 +1,86 @@
-pub use graph_craft::proto::{Any, NodeContainer, TypeErasedBox, TypeErasedNode};
+use graph_craft::allocation_pool;
+pub use graph_craft::proto::{Any, NodeContainer, NodeError, TypeErasedBox, TypeErasedNode};
 use graph_craft::proto::{DynFuture, FutureAny, SharedNodeContainer};
 use graphene_core::NodeIO;
 pub use graphene_core::{generic, ops, Node};
 
 use dyn_any::StaticType;
 
+use core::future::Future;
+use core::task::{Context, Poll};
+use std::cell::RefCell;
 use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+
+/// Where the [`DowncastBothNode`]s supplying the inputs of a typed node report the failure of the type-erased nodes they evaluate.
+/// A typed node can't be given an input which failed, so the [`DynAnyNode`] evaluating it, which shares these errors with its inputs, drops it and resolves to the error instead.
+#[derive(Clone, Default)]
+pub struct InputErrors(Rc<RefCell<Option<NodeError>>>);
+
+impl InputErrors {
+	fn report(&self, error: NodeError) {
+		self.0.borrow_mut().get_or_insert(error);
+	}
+
+	fn take(&self) -> Option<NodeError> {
+		self.0.borrow_mut().take()
+	}
+}
+
+/// Drives the future of a typed node, resolving to the error of the first of its inputs to fail, without resuming the typed node which was waiting for that input.
+struct FallibleInputs<'a, F> {
+	future: F,
+	errors: &'a InputErrors,
+}
+
+impl<F: Future + Unpin> Future for FallibleInputs<'_, F> {
+	type Output = Result<F::Output, NodeError>;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let poll = Pin::new(&mut self.future).poll(cx);
+		// The inputs only report errors while they're evaluated, which happens while the typed node is polled, so any error now came from this typed node
+		match (poll, self.errors.take()) {
+			(Poll::Ready(output), _) => Poll::Ready(Ok(output)),
+			(Poll::Pending, Some(error)) => Poll::Ready(Err(error)),
+			(Poll::Pending, None) => Poll::Pending,
+		}
+	}
+}
+
+/// Evaluates a type-erased node with the input and downcasts its output, carrying the failure of either in the `Result`.
+/// This is how the inputs which a node evaluates up front while it's constructed are evaluated, since there's no typed node to stop yet.
+pub fn evaluate_input<'i, I: StaticType + 'i, O: StaticType + 'i>(node: &'i SharedNodeContainer, input: I) -> DynFuture<'i, Result<O, NodeError>> {
+	let node_name = node.node_name();
+	let output = node.eval(allocation_pool::boxed(input));
+	Box::pin(async move {
+		let output = dyn_any::downcast(output.await?).map_err(|e| NodeError::new(node_name, format!("DowncastBothNode Input {e}")))?;
+		Ok(allocation_pool::unbox(output))
+	})
+}
+
+/// Stands in for a node whose construction failed because one of the inputs it evaluates up front failed, failing each evaluation with that error.
+pub struct FailedNode {
+	error: NodeError,
+}
+
+impl<'i> Node<'i, Any<'i>> for FailedNode {
+	type Output = FutureAny<'i>;
+
+	fn eval(&'i self, _: Any<'i>) -> Self::Output {
+		Box::pin(async move { Err(self.error.clone()) })
+	}
+}
+
+impl FailedNode {
+	pub const fn new(error: NodeError) -> Self {
+		Self { error }
+	}
+}
 
 pub struct DynAnyNode<I, O, Node> {
 	node: Node,
+	errors: InputErrors,
 	_i: PhantomData<I>,
 	_o: PhantomData<O>,
 }
@@ -22,11 +94,15 @@ where
 	fn eval(&'input self, input: Any<'input>) -> Self::Output {
 		let node_name = core::any::type_name::<N>();
 		let output = |input| async move {
-			let result = self.node.eval(input).await;
-			Box::new(result) as Any<'input>
+			let result = FallibleInputs {
+				future: self.node.eval(input),
+				errors: &self.errors,
+			}
+			.await?;
+			Ok(allocation_pool::boxed(result))
 		};
 		match dyn_any::downcast(input) {
-			Ok(input) => Box::pin(output(*input)),
+			Ok(input) => Box::pin(output(allocation_pool::unbox(input))),
 			// If the input type of the node is `()` and we supply an invalid type, we can still call the
 			// node and just ignore the input and call it with the unit type instead.
 			Err(_) if core::any::TypeId::of::<_I::Static>() == core::any::TypeId::of::<()>() => {
@@ -34,7 +110,8 @@ where
 				// Rust can't know, that `_I` and `()` are the same size, so we have to use a `transmute_copy()` here
 				Box::pin(output(unsafe { std::mem::transmute_copy(&()) }))
 			}
-			Err(e) => panic!("DynAnyNode Input, {0} in:\n{1}", e, node_name),
+			// The type checker rejects networks with mismatched connections before they're executed, so this is only reached if it has missed one
+			Err(e) => Box::pin(async move { Err(NodeError::new(node_name, format!("DynAnyNode Input, {e}"))) }),
 		}
 	}
 
@@ -50,15 +127,72 @@ impl<'input, _I: 'input + StaticType, _O: 'input + StaticType, N: 'input> DynAny
 where
 	N: Node<'input, _I, Output = DynFuture<'input, _O>>,
 {
-	pub const fn new(node: N) -> Self {
+	/// Wraps a typed node which doesn't evaluate any type-erased nodes while it's evaluated.
+	pub fn new(node: N) -> Self {
+		Self::with_input_errors(node, InputErrors::default())
+	}
+
+	/// Wraps a typed node whose inputs are [`DowncastBothNode`]s reporting to the `errors`, so it fails if any of them fail.
+	pub fn with_input_errors(node: N, errors: InputErrors) -> Self {
 		Self {
 			node,
+			errors,
 			_i: core::marker::PhantomData,
 			_o: core::marker::PhantomData,
 		}
 	}
 }
 
+/// Like the [`DynAnyNode`], but for a typed node which can fail by itself, whose output carries the failure in a `Result`.
+pub struct FallibleDynAnyNode<I, O, Node> {
+	node: DynAnyNode<I, Result<O, NodeError>, Node>,
+}
+
+impl<'input, _I: 'input + StaticType, _O: 'input + StaticType, N: 'input> Node<'input, Any<'input>> for FallibleDynAnyNode<_I, _O, N>
+where
+	N: Node<'input, _I, Output = DynFuture<'input, Result<_O, NodeError>>>,
+{
+	type Output = FutureAny<'input>;
+	#[inline]
+	fn eval(&'input self, input: Any<'input>) -> Self::Output {
+		let node_name = core::any::type_name::<N>();
+		let output = |input| async move {
+			let result = FallibleInputs {
+				future: self.node.node.eval(input),
+				errors: &self.node.errors,
+			}
+			.await??;
+			Ok(allocation_pool::boxed(result))
+		};
+		match dyn_any::downcast(input) {
+			Ok(input) => Box::pin(output(allocation_pool::unbox(input))),
+			Err(e) => Box::pin(async move { Err(NodeError::new(node_name, format!("FallibleDynAnyNode Input, {e}"))) }),
+		}
+	}
+
+	fn reset(&self) {
+		self.node.node.reset();
+	}
+
+	fn serialize(&self) -> Option<std::sync::Arc<dyn core::any::Any>> {
+		self.node.node.serialize()
+	}
+}
+impl<'input, _I: 'input + StaticType, _O: 'input + StaticType, N: 'input> FallibleDynAnyNode<_I, _O, N>
+where
+	N: Node<'input, _I, Output = DynFuture<'input, Result<_O, NodeError>>>,
+{
+	pub fn new(node: N) -> Self {
+		Self::with_input_errors(node, InputErrors::default())
+	}
+
+	pub fn with_input_errors(node: N, errors: InputErrors) -> Self {
+		Self {
+			node: DynAnyNode::with_input_errors(node, errors),
+		}
+	}
+}
+
 pub struct DynAnyRefNode<I, O, Node> {
 	node: Node,
 	_i: PhantomData<(I, O)>,
@@ -70,10 +204,10 @@ where
 	type Output = FutureAny<'input>;
 	fn eval(&'input self, input: Any<'input>) -> Self::Output {
 		let node_name = core::any::type_name::<N>();
-		let input: Box<_I> = dyn_any::downcast(input).unwrap_or_else(|e| panic!("DynAnyRefNode Input, {e} in:\n{node_name}"));
-		let result = self.node.eval(*input);
-		let output = async move { Box::new(result) as Any<'input> };
-		Box::pin(output)
+		let result = dyn_any::downcast::<_I>(input)
+			.map(|input| allocation_pool::boxed(self.node.eval(allocation_pool::unbox(input))))
+			.map_err(|e| NodeError::new(node_name, format!("DynAnyRefNode Input, {e}")));
+		Box::pin(async move { result })
 	}
 	fn reset(&self) {
 		self.node.reset();
@@ -91,6 +225,7 @@ impl<_I, _O, S0> DynAnyRefNode<_I, _O, S0> {
 
 pub struct DynAnyInRefNode<I, O, Node> {
 	node: Node,
+	errors: InputErrors,
 	_i: PhantomData<(I, O)>,
 }
 impl<'input, _I: 'input + StaticType, _O: 'input + StaticType, N: 'input> Node<'input, Any<'input>> for DynAnyInRefNode<_I, _O, N>
@@ -99,17 +234,27 @@ where
 {
 	type Output = FutureAny<'input>;
 	fn eval(&'input self, input: Any<'input>) -> Self::Output {
-		{
-			let node_name = core::any::type_name::<N>();
-			let input: Box<&_I> = dyn_any::downcast(input).unwrap_or_else(|e| panic!("DynAnyInRefNode Input, {e} in:\n{node_name}"));
-			let result = self.node.eval(*input);
-			Box::pin(async move { Box::new(result.await) as Any<'_> })
+		let node_name = core::any::type_name::<N>();
+		match dyn_any::downcast::<&_I>(input) {
+			Ok(input) => {
+				let future = self.node.eval(allocation_pool::unbox(input));
+				Box::pin(async move { Ok(allocation_pool::boxed(FallibleInputs { future, errors: &self.errors }.await?)) })
+			}
+			Err(e) => Box::pin(async move { Err(NodeError::new(node_name, format!("DynAnyInRefNode Input, {e}"))) }),
 		}
 	}
 }
 impl<_I, _O, S0> DynAnyInRefNode<_I, _O, S0> {
-	pub const fn new(node: S0) -> Self {
-		Self { node, _i: core::marker::PhantomData }
+	pub fn new(node: S0) -> Self {
+		Self::with_input_errors(node, InputErrors::default())
+	}
+
+	pub fn with_input_errors(node: S0, errors: InputErrors) -> Self {
+		Self {
+			node,
+			errors,
+			_i: core::marker::PhantomData,
+		}
 	}
 }
 
@@ -165,20 +310,24 @@ impl<N: Clone, O: StaticType> Clone for DowncastNode<O, N> {
 impl<N: Copy, O: StaticType> Copy for DowncastNode<O, N> {}
 
 #[node_macro::node_fn(DowncastNode<_O>)]
-fn downcast<N: 'input, _O: StaticType>(input: Any<'input>, node: &'input N) -> _O
+fn downcast<N: 'input, _O: StaticType>(input: Any<'input>, node: &'input N) -> Result<_O, NodeError>
 where
 	N: for<'any_input> Node<'any_input, Any<'any_input>, Output = Any<'any_input>> + 'input,
 {
 	let node_name = core::any::type_name::<N>();
-	let out = dyn_any::downcast(node.eval(input)).unwrap_or_else(|e| panic!("DowncastNode Input {e} in:\n{node_name}"));
-	*out
+	let out = dyn_any::downcast(node.eval(input)).map_err(|e| NodeError::new(node_name, format!("DowncastNode Input {e}")))?;
+	Ok(allocation_pool::unbox(out))
 }
 
 /// Boxes the input and downcasts the output.
 /// Wraps around a node taking Box<dyn DynAny> and returning Box<dyn DynAny>
+///
+/// The typed node evaluating it can't be given an output which failed, so the failure is reported to the [`InputErrors`] shared with the [`DynAnyNode`] evaluating the typed node,
+/// which drops the typed node and resolves to the error. [`DowncastBothNode::try_eval`] instead carries the failure in its `Result`.
 #[derive(Clone)]
 pub struct DowncastBothNode<I, O> {
 	node: SharedNodeContainer,
+	errors: InputErrors,
 	_i: PhantomData<I>,
 	_o: PhantomData<O>,
 }
@@ -186,50 +335,66 @@ impl<'input, O: 'input + StaticType, I: 'input + StaticType> Node<'input, I> for
 	type Output = DynFuture<'input, O>;
 	#[inline]
 	fn eval(&'input self, input: I) -> Self::Output {
-		{
-			let node_name = self.node.node_name();
-			let input = Box::new(input);
-			let future = self.node.eval(input);
-			Box::pin(async move {
-				let out = dyn_any::downcast(future.await).unwrap_or_else(|e| panic!("DowncastBothNode Input {e} in: \n{node_name}"));
-				*out
-			})
-		}
+		let output = self.try_eval(input);
+		Box::pin(async move {
+			match output.await {
+				Ok(output) => output,
+				Err(error) => {
+					self.errors.report(error);
+					// The `DynAnyNode` polling the typed node awaiting this drops it once the error is reported, so it never resumes
+					core::future::pending().await
+				}
+			}
+		})
 	}
 }
 impl<I, O> DowncastBothNode<I, O> {
-	pub const fn new(node: SharedNodeContainer) -> Self {
+	pub fn new(node: SharedNodeContainer, errors: &InputErrors) -> Self {
 		Self {
 			node,
+			errors: errors.clone(),
 			_i: core::marker::PhantomData,
 			_o: core::marker::PhantomData,
 		}
 	}
 }
+impl<'input, O: 'input + StaticType, I: 'input + StaticType> DowncastBothNode<I, O> {
+	/// Evaluates the type-erased node and downcasts its output, carrying the failure of either in the `Result`.
+	pub fn try_eval(&'input self, input: I) -> DynFuture<'input, Result<O, NodeError>> {
+		evaluate_input(&self.node, input)
+	}
+}
 /// Boxes the input and downcasts the output.
 /// Wraps around a node taking Box<dyn DynAny> and returning Box<dyn DynAny>
 #[derive(Clone)]
 pub struct DowncastBothRefNode<I, O> {
 	node: SharedNodeContainer,
+	errors: InputErrors,
 	_i: PhantomData<(I, O)>,
 }
 impl<'input, O: 'input + StaticType, I: 'input + StaticType> Node<'input, I> for DowncastBothRefNode<I, O> {
 	type Output = DynFuture<'input, &'input O>;
 	#[inline]
 	fn eval(&'input self, input: I) -> Self::Output {
-		{
-			let node_name = self.node.node_name();
-			let input = Box::new(input);
-			Box::pin(async move {
-				let out: Box<&_> = dyn_any::downcast::<&O>(self.node.eval(input).await).unwrap_or_else(|e| panic!("DowncastBothRefNode Input {e} in {node_name}"));
-				*out
-			})
-		}
+		let output = evaluate_input::<I, &O>(&self.node, input);
+		Box::pin(async move {
+			match output.await {
+				Ok(output) => output,
+				Err(error) => {
+					self.errors.report(error);
+					core::future::pending().await
+				}
+			}
+		})
 	}
 }
 impl<I, O> DowncastBothRefNode<I, O> {
-	pub const fn new(node: SharedNodeContainer) -> Self {
-		Self { node, _i: core::marker::PhantomData }
+	pub fn new(node: SharedNodeContainer, errors: &InputErrors) -> Self {
+		Self {
+			node,
+			errors: errors.clone(),
+			_i: core::marker::PhantomData,
+		}
 	}
 }
 
@@ -239,10 +404,10 @@ pub struct ComposeTypeErased {
 }
 
 impl<'i, 'a: 'i> Node<'i, Any<'i>> for ComposeTypeErased {
-	type Output = DynFuture<'i, Any<'i>>;
+	type Output = FutureAny<'i>;
 	fn eval(&'i self, input: Any<'i>) -> Self::Output {
 		Box::pin(async move {
-			let arg = self.first.eval(input).await;
+			let arg = self.first.eval(input).await?;
 			self.second.eval(arg).await
 		})
 	}
@@ -254,11 +419,11 @@ impl ComposeTypeErased {
 	}
 }
 
-pub fn input_node<O: StaticType>(n: SharedNodeContainer) -> DowncastBothNode<(), O> {
-	downcast_node(n)
+pub fn input_node<O: StaticType>(n: SharedNodeContainer, errors: &InputErrors) -> DowncastBothNode<(), O> {
+	downcast_node(n, errors)
 }
-pub fn downcast_node<I: StaticType, O: StaticType>(n: SharedNodeContainer) -> DowncastBothNode<I, O> {
-	DowncastBothNode::new(n)
+pub fn downcast_node<I: StaticType, O: StaticType>(n: SharedNodeContainer, errors: &InputErrors) -> DowncastBothNode<I, O> {
+	DowncastBothNode::new(n, errors)
 }
 
 pub struct PanicNode<I, O>(PhantomData<I>, PhantomData<O>);
@@ -283,15 +448,44 @@ mod test {
 	use graphene_core::{ops::AddPairNode, ops::IdentityNode};
 
 	#[test]
-	#[should_panic]
-	pub fn dyn_input_invalid_eval_panic() {
+	pub fn dyn_input_invalid_eval_error() {
 		// let add = DynAnyNode::new(AddPairNode::new()).into_type_erased();
 		// add.eval(Box::new(&("32", 32u32)));
 		let dyn_any = DynAnyNode::<(u32, u32), u32, _>::new(FutureWrapperNode { node: AddPairNode::new() });
 		let type_erased = Box::new(dyn_any) as TypeErasedBox;
 		let _ref_type_erased = type_erased.as_ref();
 		// let type_erased = Box::pin(dyn_any) as TypeErasedBox<'_>;
-		type_erased.eval(Box::new(&("32", 32u32)));
+		assert!(futures::executor::block_on(type_erased.eval(Box::new(&("32", 32u32)))).is_err());
+	}
+
+	#[test]
+	pub fn downcast_error_fails_downstream_nodes() {
+		// The downstream node expects a `u32`, but the node upstream of it produces a `(u32, u32)`
+		let pair = DynAnyNode::<(), (u32, u32), _>::new(FutureWrapperNode::new(graphene_core::value::CopiedNode::new((4u32, 2u32))));
+		let pair = NodeContainer::new(Box::new(pair) as TypeErasedBox);
+		let errors = InputErrors::default();
+		let number = DynAnyNode::<(), u32, _>::with_input_errors(DowncastBothNode::<(), u32>::new(pair.clone(), &errors), errors);
+		let type_erased = Box::new(number) as TypeErasedBox;
+
+		let error = futures::executor::block_on(type_erased.eval(Box::new(())))
+			.err()
+			.expect("The mismatched connection should fail the evaluation");
+		assert!(error.message.starts_with("DowncastBothNode"));
+
+		// Evaluating the input directly carries the same failure in its result
+		let error = futures::executor::block_on(evaluate_input::<(), u32>(&pair, ()))
+			.err()
+			.expect("The mismatched connection should fail the evaluation");
+		assert!(error.message.starts_with("DowncastBothNode"));
+	}
+
+	#[test]
+	pub fn failed_construction_fails_each_evaluation() {
+		let failed = FailedNode::new(NodeError::new("TestNode", "Failed to construct")).into_type_erased();
+		for _ in 0..2 {
+			let error = futures::executor::block_on(failed.eval(Box::new(()))).err().expect("The failed node should fail the evaluation");
+			assert_eq!(error.message, "Failed to construct");
+		}
 	}
 
 	#[test]
@@ -0,0 +1,270 @@
+//! Analysis of audio files, giving numbers that follow the loudness of the sound or of its frequency bands over time, so artwork can be animated to music.
+
+use graphene_core::Node;
+
+use std::f32::consts::PI;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// The number of samples analyzed around the time being sampled, which is a power of two for the FFT.
+/// At the common sample rate of 44.1 kHz this spans 46 ms, enough to resolve the low frequencies of a bass line.
+const WINDOW_SIZE: usize = 2048;
+
+/// The lowest frequency that the bands of the spectrum start from, at the bottom of human hearing.
+const LOWEST_FREQUENCY: f32 = 20.;
+
+#[derive(Debug, Clone, Copy)]
+pub struct AudioAnalysisNode<Time, Band, BandCount, Gain> {
+	time: Time,
+	band: Band,
+	band_count: BandCount,
+	gain: Gain,
+}
+
+/// The loudness of an audio file at the time, from 0 for silence to around 1 for the loudest sounds, multiplied by the gain.
+/// Band 0 measures the whole sound, and bands 1 up to the band count measure the frequencies within each of that many bands spaced evenly in pitch from low to high.
+#[node_macro::node_fn(AudioAnalysisNode)]
+fn audio_analysis(data: Arc<[u8]>, time: f64, band: u32, band_count: u32, gain: f64) -> f64 {
+	let Some(wav) = Wav::parse(&data) else {
+		warn!("Only uncompressed WAV files can be analyzed");
+		return 0.;
+	};
+	let window = wav.window_at(&data, time);
+
+	let level = match band {
+		0 => amplitude(&window),
+		band => band_levels(&window, wav.sample_rate, band_count.max(1) as usize).get(band as usize - 1).copied().unwrap_or_default(),
+	};
+	level as f64 * gain
+}
+
+/// The layout of the samples in an uncompressed WAV file, read from its header.
+#[derive(Debug, Clone, PartialEq)]
+struct Wav {
+	channels: usize,
+	sample_rate: u32,
+	bytes_per_sample: usize,
+	float: bool,
+	/// The bytes of the file holding the samples.
+	samples: Range<usize>,
+}
+
+impl Wav {
+	fn parse(data: &[u8]) -> Option<Self> {
+		if data.get(0..4)? != b"RIFF" || data.get(8..12)? != b"WAVE" {
+			return None;
+		}
+
+		let (mut format, mut samples) = (None, None);
+		let mut offset = 12;
+		while let Some(header) = data.get(offset..offset + 8) {
+			let size = u32::from_le_bytes(header[4..8].try_into().unwrap()) as usize;
+			let body = offset + 8..(offset + 8 + size).min(data.len());
+			match &header[0..4] {
+				b"fmt " => format = data.get(body.clone()),
+				b"data" => samples = Some(body),
+				_ => {}
+			}
+			// Chunks are padded to an even length
+			offset += 8 + size + size % 2;
+		}
+
+		let format = format?;
+		let encoding = u16::from_le_bytes(format.get(0..2)?.try_into().unwrap());
+		let channels = u16::from_le_bytes(format.get(2..4)?.try_into().unwrap()) as usize;
+		let sample_rate = u32::from_le_bytes(format.get(4..8)?.try_into().unwrap());
+		let bits_per_sample = u16::from_le_bytes(format.get(14..16)?.try_into().unwrap());
+		// The extensible format gives its encoding in the first two bytes of its subformat GUID
+		let encoding = if encoding == 0xFFFE {
+			u16::from_le_bytes(format.get(24..26)?.try_into().unwrap())
+		} else {
+			encoding
+		};
+
+		let float = match (encoding, bits_per_sample) {
+			(1, 8 | 16 | 24 | 32) => false,
+			(3, 32) => true,
+			_ => return None,
+		};
+		if channels == 0 || sample_rate == 0 {
+			return None;
+		}
+
+		Some(Self {
+			channels,
+			sample_rate,
+			bytes_per_sample: bits_per_sample as usize / 8,
+			float,
+			samples: samples?,
+		})
+	}
+
+	/// The sample at the index of each frame, with the channels mixed down to one and scaled from -1 to 1.
+	fn sample(&self, data: &[u8], frame: usize) -> f32 {
+		let frame_size = self.channels * self.bytes_per_sample;
+		let start = self.samples.start + frame * frame_size;
+		if start + frame_size > self.samples.end {
+			return 0.;
+		}
+
+		let channel_sum = data[start..start + frame_size]
+			.chunks_exact(self.bytes_per_sample)
+			.map(|bytes| match (self.bytes_per_sample, self.float) {
+				(4, true) => f32::from_le_bytes(bytes.try_into().unwrap()),
+				// Eight bit samples are the only unsigned ones
+				(1, _) => (bytes[0] as f32 - 128.) / 128.,
+				(2, _) => i16::from_le_bytes(bytes.try_into().unwrap()) as f32 / 32768.,
+				(3, _) => i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) as f32 / 2147483648.,
+				_ => i32::from_le_bytes(bytes.try_into().unwrap()) as f32 / 2147483648.,
+			})
+			.sum::<f32>();
+		channel_sum / self.channels as f32
+	}
+
+	/// The [`WINDOW_SIZE`] samples centered on the time in seconds, which are silent before the start and after the end of the sound.
+	/// Only these samples are read, so the file never has to be decoded in full.
+	fn window_at(&self, data: &[u8], time: f64) -> Vec<f32> {
+		let center = (time * self.sample_rate as f64).round() as i64;
+		let first = center - WINDOW_SIZE as i64 / 2;
+		(first..first + WINDOW_SIZE as i64)
+			.map(|frame| if frame < 0 { 0. } else { self.sample(data, frame as usize) })
+			.collect()
+	}
+}
+
+/// The root mean square of the samples, scaled so a sine wave reaching the loudest level measures 1.
+fn amplitude(samples: &[f32]) -> f32 {
+	let mean_square = samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len().max(1) as f32;
+	mean_square.sqrt() * std::f32::consts::SQRT_2
+}
+
+/// The strength of the frequencies within each of the bands, which are spaced evenly in pitch from [`LOWEST_FREQUENCY`] up to the highest frequency of the sample rate.
+/// A sine wave reaching the loudest level measures about 1 in the band containing its frequency.
+fn band_levels(samples: &[f32], sample_rate: u32, band_count: usize) -> Vec<f32> {
+	// The Hann window fades the ends of the samples to keep the frequencies of the cut off waves from smearing across the spectrum
+	let size = samples.len();
+	let mut spectrum = samples
+		.iter()
+		.enumerate()
+		.map(|(index, sample)| (sample * 0.5 * (1. - (2. * PI * index as f32 / size as f32).cos()), 0.))
+		.collect::<Vec<_>>();
+	fft(&mut spectrum);
+
+	// The window halves the amplitude, and each frequency is split between its positive and negative halves of the spectrum
+	let magnitudes = spectrum[..size / 2]
+		.iter()
+		.map(|(real, imaginary)| (real * real + imaginary * imaginary).sqrt() * 4. / size as f32)
+		.collect::<Vec<_>>();
+
+	let nyquist = sample_rate as f32 / 2.;
+	let bin_frequency = sample_rate as f32 / size as f32;
+	let octaves = (nyquist / LOWEST_FREQUENCY).log2();
+	(0..band_count)
+		.map(|band| {
+			let low = LOWEST_FREQUENCY * 2_f32.powf(octaves * band as f32 / band_count as f32);
+			let high = LOWEST_FREQUENCY * 2_f32.powf(octaves * (band + 1) as f32 / band_count as f32);
+			let bins = (low / bin_frequency).floor() as usize..((high / bin_frequency).ceil() as usize).min(magnitudes.len());
+			magnitudes.get(bins).map_or(0., |bins| bins.iter().copied().fold(0., f32::max))
+		})
+		.collect()
+}
+
+/// Transforms the complex values, whose count must be a power of two, from the time domain into the frequency domain in place with the iterative radix-2 Cooley-Tukey algorithm.
+fn fft(values: &mut [(f32, f32)]) {
+	let size = values.len();
+	debug_assert!(size.is_power_of_two());
+
+	// Reorders the values by the bit reversal of their indices, so each pass combines neighboring halves
+	let mut j = 0;
+	for i in 1..size {
+		let mut bit = size >> 1;
+		while j & bit != 0 {
+			j ^= bit;
+			bit >>= 1;
+		}
+		j |= bit;
+		if i < j {
+			values.swap(i, j);
+		}
+	}
+
+	let mut length = 2;
+	while length <= size {
+		let angle = -2. * PI / length as f32;
+		for start in (0..size).step_by(length) {
+			for k in 0..length / 2 {
+				let (sin, cos) = (angle * k as f32).sin_cos();
+				let (even, odd) = (values[start + k], values[start + k + length / 2]);
+				let twiddled = (odd.0 * cos - odd.1 * sin, odd.0 * sin + odd.1 * cos);
+				values[start + k] = (even.0 + twiddled.0, even.1 + twiddled.1);
+				values[start + k + length / 2] = (even.0 - twiddled.0, even.1 - twiddled.1);
+			}
+		}
+		length <<= 1;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// A WAV file of 16 bit mono samples.
+	fn wav(sample_rate: u32, samples: &[f32]) -> Vec<u8> {
+		let data = samples.iter().flat_map(|sample| ((sample * 32767.) as i16).to_le_bytes()).collect::<Vec<_>>();
+		let mut file = b"RIFF".to_vec();
+		file.extend((36 + data.len() as u32).to_le_bytes());
+		file.extend(b"WAVEfmt ");
+		file.extend(16_u32.to_le_bytes());
+		file.extend(1_u16.to_le_bytes());
+		file.extend(1_u16.to_le_bytes());
+		file.extend(sample_rate.to_le_bytes());
+		file.extend((sample_rate * 2).to_le_bytes());
+		file.extend(2_u16.to_le_bytes());
+		file.extend(16_u16.to_le_bytes());
+		file.extend(b"data");
+		file.extend((data.len() as u32).to_le_bytes());
+		file.extend(data);
+		file
+	}
+
+	fn sine(sample_rate: u32, frequency: f32, seconds: f32) -> Vec<f32> {
+		(0..(sample_rate as f32 * seconds) as usize)
+			.map(|index| (2. * PI * frequency * index as f32 / sample_rate as f32).sin())
+			.collect()
+	}
+
+	#[test]
+	fn parse_wav_header() {
+		let file = wav(8000, &[0., 0.5, -0.5]);
+		let wav = Wav::parse(&file).unwrap();
+		assert_eq!((wav.channels, wav.sample_rate, wav.bytes_per_sample, wav.float), (1, 8000, 2, false));
+		assert!((wav.sample(&file, 1) - 0.5).abs() < 1e-3);
+		assert_eq!(wav.sample(&file, 3), 0.);
+		assert!(Wav::parse(b"not a wav file").is_none());
+	}
+
+	#[test]
+	fn amplitude_follows_the_sound() {
+		let sample_rate = 44100;
+		let mut samples = sine(sample_rate, 440., 1.);
+		samples.extend(vec![0.; sample_rate as usize]);
+		let file: Arc<[u8]> = wav(sample_rate, &samples).into();
+
+		let loud = audio_analysis(file.clone(), 0.5, 0, 8, 1.);
+		let silent = audio_analysis(file.clone(), 1.5, 0, 8, 1.);
+		assert!((loud - 1.).abs() < 0.01, "{loud}");
+		assert_eq!(silent, 0.);
+	}
+
+	#[test]
+	fn band_levels_find_the_frequency() {
+		let sample_rate = 44100;
+		let samples = sine(sample_rate, 100., 0.1);
+		let levels = band_levels(&samples[..WINDOW_SIZE], sample_rate, 10);
+
+		// Each of the ten bands spans about one octave above 20 Hz, so 100 Hz is in the third
+		let loudest = levels.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).unwrap().0;
+		assert_eq!(loudest, 2);
+		assert!(levels[2] > 0.5 && levels[9] < 0.01, "{levels:?}");
+	}
+}
@@ -0,0 +1,294 @@
+use graphene_core::raster::{BorderMode, ConvolutionPreset, Image, ImageFrame};
+use graphene_core::{Color, Node};
+
+const SOBEL_HORIZONTAL: [f32; 9] = [-1., 0., 1., -2., 0., 2., -1., 0., 1.];
+const SOBEL_VERTICAL: [f32; 9] = [-1., -2., -1., 0., 0., 0., 1., 2., 1.];
+const LAPLACIAN: [f32; 9] = [0., 1., 0., 1., -4., 1., 0., 1., 0.];
+const EMBOSS: [f32; 9] = [-2., -1., 0., -1., 1., 1., 0., 1., 2.];
+const SHARPEN: [f32; 9] = [0., -1., 0., -1., 5., -1., 0., -1., 0.];
+
+/// The weighted sums of the (premultiplied) channels of the pixels under a square kernel of odd width centered on each pixel, in the order red, green, blue, alpha.
+/// The sums aren't clamped, so they can be combined further before becoming colors.
+fn convolve(image: &Image<Color>, kernel: &[f32], border: BorderMode) -> Vec<[f32; 4]> {
+	let size = (kernel.len() as f64).sqrt() as usize;
+	let radius = (size / 2) as isize;
+	let (width, height) = (image.width as usize, image.height as usize);
+
+	let mut result = Vec::with_capacity(width * height);
+	for y in 0..height as isize {
+		for x in 0..width as isize {
+			let mut sum = [0.; 4];
+			for (index, &weight) in kernel.iter().enumerate() {
+				if weight == 0. {
+					continue;
+				}
+				let (dx, dy) = ((index % size) as isize - radius, (index / size) as isize - radius);
+				let (Some(source_x), Some(source_y)) = (border.resolve(x + dx, width), border.resolve(y + dy, height)) else {
+					continue;
+				};
+				let color = image.data[source_y * width + source_x];
+				for (channel, value) in sum.iter_mut().zip([color.r(), color.g(), color.b(), color.a()]) {
+					*channel += weight * value;
+				}
+			}
+			result.push(sum);
+		}
+	}
+	result
+}
+
+/// Blurs along one axis with the symmetric weights, given from the center outwards.
+fn blur_pass(image: &Image<Color>, weights: &[f32], horizontal: bool, border: BorderMode) -> Image<Color> {
+	let (width, height) = (image.width as usize, image.height as usize);
+	let data = (0..height as isize)
+		.flat_map(|y| (0..width as isize).map(move |x| (x, y)))
+		.map(|(x, y)| {
+			let mut sum = [0.; 4];
+			for offset in 1 - weights.len() as isize..weights.len() as isize {
+				let (source_x, source_y) = if horizontal { (x + offset, y) } else { (x, y + offset) };
+				let (Some(source_x), Some(source_y)) = (border.resolve(source_x, width), border.resolve(source_y, height)) else {
+					continue;
+				};
+				let color = image.data[source_y * width + source_x];
+				let weight = weights[offset.unsigned_abs()];
+				for (channel, value) in sum.iter_mut().zip([color.r(), color.g(), color.b(), color.a()]) {
+					*channel += weight * value;
+				}
+			}
+			Color::from_rgbaf32_unchecked(sum[0], sum[1], sum[2], sum[3])
+		})
+		.collect();
+
+	Image {
+		width: image.width,
+		height: image.height,
+		data,
+		base64_string: None,
+	}
+}
+
+/// Blurs the image with a Gaussian whose standard deviation is the radius, in pixels.
+fn gaussian_blur(image: &Image<Color>, radius: f64) -> Image<Color> {
+	let sigma = radius.max(0.01);
+	let extent = (sigma * 3.).ceil() as usize;
+	let mut weights = (0..=extent).map(|offset| (-((offset * offset) as f64) / (2. * sigma * sigma)).exp() as f32).collect::<Vec<_>>();
+	let total = weights[0] + 2. * weights[1..].iter().sum::<f32>();
+	weights.iter_mut().for_each(|weight| *weight /= total);
+
+	blur_pass(&blur_pass(image, &weights, true, BorderMode::Extend), &weights, false, BorderMode::Extend)
+}
+
+/// Makes valid premultiplied colors from convolved channels, with the alpha clamped to 0 to 1 and each color channel clamped to between 0 and the alpha.
+fn to_color([red, green, blue, alpha]: [f32; 4]) -> Color {
+	let alpha = alpha.clamp(0., 1.);
+	Color::from_rgbaf32_unchecked(red.clamp(0., alpha), green.clamp(0., alpha), blue.clamp(0., alpha), alpha)
+}
+
+fn with_data(image_frame: ImageFrame<Color>, data: Vec<Color>) -> ImageFrame<Color> {
+	ImageFrame {
+		image: Image {
+			width: image_frame.image.width,
+			height: image_frame.image.height,
+			data: data.into(),
+			base64_string: None,
+		},
+		..image_frame
+	}
+}
+
+/// Replaces each pixel by the weighted sum of the pixels around it, with the weights given by a square kernel row by row from the top left.
+/// The kernel has an odd width, so 9 weights for a 3×3 kernel or 25 for a 5×5 kernel, and is divided by the sum of its weights when normalized so it keeps the overall brightness.
+/// The border mode sets what the kernel reads beyond the edges of the image.
+pub struct ConvolveNode<Kernel, Normalize, Border> {
+	kernel: Kernel,
+	normalize: Normalize,
+	border: Border,
+}
+
+#[node_macro::node_fn(ConvolveNode)]
+fn convolve_node(image_frame: ImageFrame<Color>, kernel: Vec<f64>, normalize: bool, border: BorderMode) -> ImageFrame<Color> {
+	let size = (kernel.len() as f64).sqrt().round() as usize;
+	if size * size != kernel.len() || size % 2 == 0 {
+		warn!("A convolution kernel needs a square number of weights with an odd width, but {} were given", kernel.len());
+		return image_frame;
+	}
+
+	let sum = kernel.iter().sum::<f64>();
+	let scale = if normalize && sum.abs() > f64::EPSILON { sum.recip() } else { 1. };
+	let kernel = kernel.iter().map(|&weight| (weight * scale) as f32).collect::<Vec<_>>();
+
+	let data = convolve(&image_frame.image, &kernel, border).into_iter().map(to_color).collect();
+	with_data(image_frame, data)
+}
+
+/// Convolves the image with one of the standard kernels for finding edges or bringing out detail.
+/// The edges found by the Sobel and Laplacian presets are drawn brighter the stronger they are, with the transparency of the image kept as it was.
+pub struct ConvolutionPresetNode<Preset, Border> {
+	preset: Preset,
+	border: Border,
+}
+
+#[node_macro::node_fn(ConvolutionPresetNode)]
+fn convolution_preset(image_frame: ImageFrame<Color>, preset: ConvolutionPreset, border: BorderMode) -> ImageFrame<Color> {
+	let image = &image_frame.image;
+	let colors = |sums: Vec<[f32; 4]>| -> Vec<[f32; 3]> { sums.into_iter().map(|[red, green, blue, _]| [red, green, blue]).collect() };
+	let edges = |sums: Vec<[f32; 4]>| -> Vec<[f32; 3]> { colors(sums).into_iter().map(|channels| channels.map(f32::abs)).collect() };
+
+	let color_channels = match preset {
+		ConvolutionPreset::Sobel => {
+			let horizontal = edges(convolve(image, &SOBEL_HORIZONTAL, border));
+			let vertical = edges(convolve(image, &SOBEL_VERTICAL, border));
+			horizontal
+				.into_iter()
+				.zip(vertical)
+				.map(|(horizontal, vertical)| [0, 1, 2].map(|channel| horizontal[channel].hypot(vertical[channel])))
+				.collect()
+		}
+		ConvolutionPreset::Laplacian => edges(convolve(image, &LAPLACIAN, border)),
+		ConvolutionPreset::Emboss => colors(convolve(image, &EMBOSS, border)),
+		ConvolutionPreset::Sharpen => colors(convolve(image, &SHARPEN, border)),
+	};
+
+	let data = image
+		.data
+		.iter()
+		.zip(color_channels)
+		.map(|(color, [red, green, blue])| to_color([red, green, blue, color.a()]))
+		.collect();
+	with_data(image_frame, data)
+}
+
+/// Sharpens the image by adding back the difference between it and a blurred copy, the way darkrooms sharpened prints with a blurred negative.
+/// The radius (in pixels) is the size of the detail which is sharpened, the amount (as a percentage) is how strongly, and differences smaller than the threshold (as a percentage of full brightness) are left alone to avoid sharpening noise.
+pub struct UnsharpMaskNode<Amount, Radius, Threshold> {
+	amount: Amount,
+	radius: Radius,
+	threshold: Threshold,
+}
+
+#[node_macro::node_fn(UnsharpMaskNode)]
+fn unsharp_mask(image_frame: ImageFrame<Color>, amount: f64, radius: f64, threshold: f64) -> ImageFrame<Color> {
+	if image_frame.image.width == 0 || image_frame.image.height == 0 || amount == 0. || radius <= 0. {
+		return image_frame;
+	}
+
+	let blurred = gaussian_blur(&image_frame.image, radius);
+	let amount = amount.max(0.) as f32 / 100.;
+	let threshold = threshold.clamp(0., 100.) as f32 / 100.;
+
+	let data = image_frame
+		.image
+		.data
+		.iter()
+		.zip(&blurred.data)
+		.map(|(color, blurred)| {
+			let original = [color.r(), color.g(), color.b()];
+			let difference = [color.r() - blurred.r(), color.g() - blurred.g(), color.b() - blurred.b()];
+			if difference.iter().all(|difference| difference.abs() <= threshold) {
+				return *color;
+			}
+			let [red, green, blue] = [0, 1, 2].map(|channel| original[channel] + amount * difference[channel]);
+			to_color([red, green, blue, color.a()])
+		})
+		.collect();
+	with_data(image_frame, data)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::value::{ClonedNode, CopiedNode};
+
+	/// A 6×6 image whose left half is black and right half is white.
+	fn step_image() -> ImageFrame<Color> {
+		let mut image = Image::new(6, 6, Color::BLACK);
+		for (index, pixel) in image.data.iter_mut().enumerate() {
+			if index % 6 >= 3 {
+				*pixel = Color::WHITE;
+			}
+		}
+		ImageFrame { image, ..Default::default() }
+	}
+
+	#[test]
+	fn border_modes_resolve_coordinates() {
+		assert_eq!(BorderMode::Extend.resolve(-2, 4), Some(0));
+		assert_eq!(BorderMode::Extend.resolve(5, 4), Some(3));
+		assert_eq!(BorderMode::Mirror.resolve(-1, 4), Some(0));
+		assert_eq!(BorderMode::Mirror.resolve(-2, 4), Some(1));
+		assert_eq!(BorderMode::Mirror.resolve(4, 4), Some(3));
+		assert_eq!(BorderMode::Wrap.resolve(-1, 4), Some(3));
+		assert_eq!(BorderMode::Wrap.resolve(4, 4), Some(0));
+		assert_eq!(BorderMode::Transparent.resolve(-1, 4), None);
+		assert_eq!(BorderMode::Transparent.resolve(2, 4), Some(2));
+	}
+
+	#[test]
+	fn convolve_with_custom_kernel() {
+		let image = step_image();
+		let node = ConvolveNode {
+			kernel: ClonedNode(vec![1.; 9]),
+			normalize: CopiedNode(true),
+			border: CopiedNode(BorderMode::Extend),
+		};
+		let blurred = node.eval(image.clone());
+
+		// The box blur averages across the step, while extending the edges leaves the flat areas untouched
+		let brightness = |image: &ImageFrame<Color>, x: usize| image.image.data[x].r();
+		assert!(brightness(&blurred, 0).abs() < 1e-6);
+		assert!((brightness(&blurred, 2) - 1. / 3.).abs() < 1e-6);
+		assert!((brightness(&blurred, 3) - 2. / 3.).abs() < 1e-6);
+		assert!((brightness(&blurred, 5) - 1.).abs() < 1e-6);
+
+		// Wrapping brings the black left edge around to the white right edge
+		let node = ConvolveNode {
+			kernel: ClonedNode(vec![1.; 9]),
+			normalize: CopiedNode(true),
+			border: CopiedNode(BorderMode::Wrap),
+		};
+		assert!(brightness(&node.eval(image.clone()), 5) < 0.9);
+
+		// Kernels without an odd square size leave the image as it was
+		let node = ConvolveNode {
+			kernel: ClonedNode(vec![1.; 4]),
+			normalize: CopiedNode(true),
+			border: CopiedNode(BorderMode::Extend),
+		};
+		assert_eq!(node.eval(image.clone()).image, image.image);
+	}
+
+	#[test]
+	fn edge_presets_outline_the_step() {
+		for preset in [ConvolutionPreset::Sobel, ConvolutionPreset::Laplacian] {
+			let node = ConvolutionPresetNode {
+				preset: CopiedNode(preset),
+				border: CopiedNode(BorderMode::Extend),
+			};
+			let edges = node.eval(step_image());
+
+			assert_eq!(edges.image.data[0], Color::BLACK, "{preset}");
+			assert!(edges.image.data[3 * 6 + 2].r() > 0.5, "{preset}");
+			assert_eq!(edges.image.data[3 * 6 + 5], Color::BLACK, "{preset}");
+		}
+	}
+
+	#[test]
+	fn unsharp_mask_respects_the_threshold() {
+		let mut image = Image::new(9, 9, Color::from_rgbaf32_unchecked(0.5, 0.5, 0.5, 1.));
+		image.data[4 * 9 + 4] = Color::from_rgbaf32_unchecked(0.6, 0.6, 0.6, 1.);
+		let frame = ImageFrame { image, ..Default::default() };
+
+		let sharpen = |threshold| {
+			UnsharpMaskNode {
+				amount: CopiedNode(100.),
+				radius: CopiedNode(1.),
+				threshold: CopiedNode(threshold),
+			}
+			.eval(frame.clone())
+		};
+
+		assert!(sharpen(0.).image.data[4 * 9 + 4].r() > 0.6);
+		assert_eq!(sharpen(50.).image, frame.image);
+	}
+}
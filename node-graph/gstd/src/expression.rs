@@ -0,0 +1,775 @@
+use graphene_core::Node;
+
+use glam::DVec2;
+use std::cell::RefCell;
+use std::fmt;
+
+/// The names under which the inputs of the expression nodes can be referenced from within an expression.
+pub const EXPRESSION_VARIABLES: [&str; 4] = ["x", "y", "u", "v"];
+
+/// How deeply parentheses, function calls, and operators can be nested, since the parser and the evaluator recurse once for each level.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// The most operations an expression can have, which also limits the depth of long chains of operators such as `1 + 1 + 1 + ...`.
+const MAX_OPERATIONS: usize = 1000;
+
+/// The result of evaluating an expression or one of its subexpressions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+	Number(f64),
+	Vector(DVec2),
+}
+
+impl Value {
+	fn type_name(self) -> &'static str {
+		match self {
+			Self::Number(_) => "number",
+			Self::Vector(_) => "vector",
+		}
+	}
+
+	fn number(self, context: &str) -> Result<f64, ExpressionError> {
+		match self {
+			Self::Number(number) => Ok(number),
+			Self::Vector(_) => Err(ExpressionError::new(format!("{context} expects a number but was given a vector"))),
+		}
+	}
+
+	fn vector(self, context: &str) -> Result<DVec2, ExpressionError> {
+		match self {
+			Self::Vector(vector) => Ok(vector),
+			Self::Number(_) => Err(ExpressionError::new(format!("{context} expects a vector but was given a number"))),
+		}
+	}
+
+	/// Applies a function to a number, or to each component of a vector.
+	fn map(self, f: impl Fn(f64) -> f64) -> Self {
+		match self {
+			Self::Number(number) => Self::Number(f(number)),
+			Self::Vector(vector) => Self::Vector(DVec2::new(f(vector.x), f(vector.y))),
+		}
+	}
+
+	/// Combines two numbers, or two vectors component by component.
+	fn zip(self, other: Self, context: &str, f: impl Fn(f64, f64) -> f64) -> Result<Self, ExpressionError> {
+		match (self, other) {
+			(Self::Number(a), Self::Number(b)) => Ok(Self::Number(f(a, b))),
+			(Self::Vector(a), Self::Vector(b)) => Ok(Self::Vector(DVec2::new(f(a.x, b.x), f(a.y, b.y)))),
+			(a, b) => Err(ExpressionError::new(format!("{context} can't combine a {} with a {}", a.type_name(), b.type_name()))),
+		}
+	}
+}
+
+impl From<f64> for Value {
+	fn from(number: f64) -> Self {
+		Self::Number(number)
+	}
+}
+
+impl From<DVec2> for Value {
+	fn from(vector: DVec2) -> Self {
+		Self::Vector(vector)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError {
+	pub message: String,
+}
+
+impl ExpressionError {
+	fn new(message: impl Into<String>) -> Self {
+		Self { message: message.into() }
+	}
+}
+
+impl fmt::Display for ExpressionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+impl std::error::Error for ExpressionError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+	Number(f64),
+	Identifier(usize, usize),
+	Symbol(char),
+	End,
+}
+
+/// Splits the source into tokens, each paired with its byte offset for error messages.
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ExpressionError> {
+	let mut tokens = Vec::new();
+	let mut chars = source.char_indices().peekable();
+
+	while let Some(&(start, c)) = chars.peek() {
+		if c.is_whitespace() {
+			chars.next();
+		} else if c.is_ascii_digit() || (c == '.' && source[start + 1..].starts_with(|c: char| c.is_ascii_digit())) {
+			let mut end = start;
+			let mut previous = c;
+			while let Some(&(index, c)) = chars.peek() {
+				let exponent_sign = (c == '+' || c == '-') && (previous == 'e' || previous == 'E');
+				if !(c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign) {
+					break;
+				}
+				end = index + c.len_utf8();
+				previous = c;
+				chars.next();
+			}
+			let number = source[start..end]
+				.parse()
+				.map_err(|_| ExpressionError::new(format!("Invalid number '{}' at position {start}", &source[start..end])))?;
+			tokens.push((start, Token::Number(number)));
+		} else if c.is_alphabetic() || c == '_' {
+			let mut end = start;
+			while let Some(&(index, c)) = chars.peek() {
+				if !(c.is_alphanumeric() || c == '_') {
+					break;
+				}
+				end = index + c.len_utf8();
+				chars.next();
+			}
+			tokens.push((start, Token::Identifier(start, end)));
+		} else if "+-*/%^(),.".contains(c) {
+			tokens.push((start, Token::Symbol(c)));
+			chars.next();
+		} else {
+			return Err(ExpressionError::new(format!("Unexpected character '{c}' at position {start}")));
+		}
+	}
+
+	tokens.push((source.len(), Token::End));
+	Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOperator {
+	Add,
+	Subtract,
+	Multiply,
+	Divide,
+	Modulo,
+	Power,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+	Sin,
+	Cos,
+	Tan,
+	Asin,
+	Acos,
+	Atan,
+	Atan2,
+	Sinh,
+	Cosh,
+	Tanh,
+	Sqrt,
+	Cbrt,
+	Exp,
+	Ln,
+	Log,
+	Log2,
+	Log10,
+	Pow,
+	Hypot,
+	Abs,
+	Sign,
+	Floor,
+	Ceil,
+	Round,
+	Trunc,
+	Fract,
+	Min,
+	Max,
+	Clamp,
+	Lerp,
+	Radians,
+	Degrees,
+	Vec,
+	Length,
+	Normalize,
+	Dot,
+	Cross,
+	Distance,
+	Angle,
+	Rotate,
+	Perp,
+}
+
+/// Every function that can be called from an expression, by name.
+const FUNCTIONS: [(&str, Function); 41] = [
+	("sin", Function::Sin),
+	("cos", Function::Cos),
+	("tan", Function::Tan),
+	("asin", Function::Asin),
+	("acos", Function::Acos),
+	("atan", Function::Atan),
+	("atan2", Function::Atan2),
+	("sinh", Function::Sinh),
+	("cosh", Function::Cosh),
+	("tanh", Function::Tanh),
+	("sqrt", Function::Sqrt),
+	("cbrt", Function::Cbrt),
+	("exp", Function::Exp),
+	("ln", Function::Ln),
+	("log", Function::Log),
+	("log2", Function::Log2),
+	("log10", Function::Log10),
+	("pow", Function::Pow),
+	("hypot", Function::Hypot),
+	("abs", Function::Abs),
+	("sign", Function::Sign),
+	("floor", Function::Floor),
+	("ceil", Function::Ceil),
+	("round", Function::Round),
+	("trunc", Function::Trunc),
+	("fract", Function::Fract),
+	("min", Function::Min),
+	("max", Function::Max),
+	("clamp", Function::Clamp),
+	("lerp", Function::Lerp),
+	("radians", Function::Radians),
+	("degrees", Function::Degrees),
+	("vec", Function::Vec),
+	("length", Function::Length),
+	("normalize", Function::Normalize),
+	("dot", Function::Dot),
+	("cross", Function::Cross),
+	("distance", Function::Distance),
+	("angle", Function::Angle),
+	("rotate", Function::Rotate),
+	("perp", Function::Perp),
+];
+
+impl Function {
+	fn from_name(name: &str) -> Option<Self> {
+		FUNCTIONS.iter().find(|(known, _)| *known == name).map(|&(_, function)| function)
+	}
+
+	fn name(self) -> &'static str {
+		FUNCTIONS.iter().find(|&&(_, function)| function == self).map_or("function", |&(name, _)| name)
+	}
+
+	fn arity(self) -> usize {
+		match self {
+			Self::Atan2 | Self::Log | Self::Pow | Self::Hypot | Self::Min | Self::Max | Self::Vec | Self::Dot | Self::Cross | Self::Distance | Self::Rotate => 2,
+			Self::Clamp | Self::Lerp => 3,
+			_ => 1,
+		}
+	}
+
+	fn apply(self, args: &[Value]) -> Result<Value, ExpressionError> {
+		let name = self.name();
+		let number = |index: usize| args[index].number(name);
+		let vector = |index: usize| args[index].vector(name);
+
+		let value = match self {
+			Self::Sin => number(0)?.sin().into(),
+			Self::Cos => number(0)?.cos().into(),
+			Self::Tan => number(0)?.tan().into(),
+			Self::Asin => number(0)?.asin().into(),
+			Self::Acos => number(0)?.acos().into(),
+			Self::Atan => number(0)?.atan().into(),
+			Self::Atan2 => number(0)?.atan2(number(1)?).into(),
+			Self::Sinh => number(0)?.sinh().into(),
+			Self::Cosh => number(0)?.cosh().into(),
+			Self::Tanh => number(0)?.tanh().into(),
+			Self::Sqrt => number(0)?.sqrt().into(),
+			Self::Cbrt => number(0)?.cbrt().into(),
+			Self::Exp => number(0)?.exp().into(),
+			Self::Ln => number(0)?.ln().into(),
+			Self::Log => number(0)?.log(number(1)?).into(),
+			Self::Log2 => number(0)?.log2().into(),
+			Self::Log10 => number(0)?.log10().into(),
+			Self::Pow => number(0)?.powf(number(1)?).into(),
+			Self::Hypot => number(0)?.hypot(number(1)?).into(),
+			Self::Abs => args[0].map(f64::abs),
+			Self::Sign => args[0].map(|x| if x == 0. { 0. } else { x.signum() }),
+			Self::Floor => args[0].map(f64::floor),
+			Self::Ceil => args[0].map(f64::ceil),
+			Self::Round => args[0].map(f64::round),
+			Self::Trunc => args[0].map(f64::trunc),
+			Self::Fract => args[0].map(|x| x - x.floor()),
+			Self::Min => args[0].zip(args[1], name, f64::min)?,
+			Self::Max => args[0].zip(args[1], name, f64::max)?,
+			Self::Clamp => {
+				let (min, max) = (number(1)?, number(2)?);
+				if min > max {
+					return Err(ExpressionError::new(format!("clamp's minimum {min} is greater than its maximum {max}")));
+				}
+				args[0].map(|x| x.clamp(min, max))
+			}
+			Self::Lerp => {
+				let t = number(2)?;
+				args[0].zip(args[1], name, |a, b| a + (b - a) * t)?
+			}
+			Self::Radians => number(0)?.to_radians().into(),
+			Self::Degrees => number(0)?.to_degrees().into(),
+			Self::Vec => DVec2::new(number(0)?, number(1)?).into(),
+			Self::Length => vector(0)?.length().into(),
+			Self::Normalize => vector(0)?.normalize_or_zero().into(),
+			Self::Dot => vector(0)?.dot(vector(1)?).into(),
+			Self::Cross => vector(0)?.perp_dot(vector(1)?).into(),
+			Self::Distance => vector(0)?.distance(vector(1)?).into(),
+			Self::Angle => {
+				let vector = vector(0)?;
+				vector.y.atan2(vector.x).into()
+			}
+			Self::Rotate => DVec2::from_angle(number(1)?).rotate(vector(0)?).into(),
+			Self::Perp => vector(0)?.perp().into(),
+		};
+		Ok(value)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Ast {
+	Constant(f64),
+	Variable(usize),
+	Negate(Box<Ast>),
+	Binary(BinaryOperator, Box<Ast>, Box<Ast>),
+	Call(Function, Vec<Ast>),
+	Component(Box<Ast>, usize),
+}
+
+struct Parser<'a> {
+	source: &'a str,
+	variables: &'a [&'a str],
+	tokens: Vec<(usize, Token)>,
+	index: usize,
+	depth: usize,
+	operations: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn peek(&self) -> Token {
+		self.tokens[self.index].1
+	}
+
+	fn position(&self) -> usize {
+		self.tokens[self.index].0
+	}
+
+	fn next(&mut self) -> Token {
+		let token = self.peek();
+		if token != Token::End {
+			self.index += 1;
+		}
+		token
+	}
+
+	fn eat(&mut self, symbol: char) -> bool {
+		let matches = self.peek() == Token::Symbol(symbol);
+		if matches {
+			self.index += 1;
+		}
+		matches
+	}
+
+	fn unexpected(&self) -> ExpressionError {
+		let position = self.position();
+		if self.peek() == Token::End {
+			return ExpressionError::new("Unexpected end of expression");
+		}
+		let text = self.source[position..].split_whitespace().next().unwrap_or_default();
+		ExpressionError::new(format!("Unexpected '{text}' at position {position}"))
+	}
+
+	fn expect(&mut self, symbol: char) -> Result<(), ExpressionError> {
+		if self.eat(symbol) {
+			Ok(())
+		} else {
+			Err(self.unexpected())
+		}
+	}
+
+	/// Parses a part of the expression nested one level deeper, so inputs like `((((...` are an error rather than overflowing the stack.
+	fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<Ast, ExpressionError>) -> Result<Ast, ExpressionError> {
+		if self.depth >= MAX_NESTING_DEPTH {
+			return Err(ExpressionError::new(format!("The expression is nested more than {MAX_NESTING_DEPTH} levels deep")));
+		}
+		self.depth += 1;
+		let result = parse(self);
+		self.depth -= 1;
+		result
+	}
+
+	fn operation(&mut self, ast: Ast) -> Result<Ast, ExpressionError> {
+		self.operations += 1;
+		if self.operations > MAX_OPERATIONS {
+			return Err(ExpressionError::new(format!("The expression has more than {MAX_OPERATIONS} operations")));
+		}
+		Ok(ast)
+	}
+
+	// additive := multiplicative (("+" | "-") multiplicative)*
+	fn additive(&mut self) -> Result<Ast, ExpressionError> {
+		let mut left = self.multiplicative()?;
+		loop {
+			let operator = match self.peek() {
+				Token::Symbol('+') => BinaryOperator::Add,
+				Token::Symbol('-') => BinaryOperator::Subtract,
+				_ => return Ok(left),
+			};
+			self.next();
+			let right = self.multiplicative()?;
+			left = self.operation(Ast::Binary(operator, Box::new(left), Box::new(right)))?;
+		}
+	}
+
+	// multiplicative := unary (("*" | "/" | "%") unary)*
+	fn multiplicative(&mut self) -> Result<Ast, ExpressionError> {
+		let mut left = self.unary()?;
+		loop {
+			let operator = match self.peek() {
+				Token::Symbol('*') => BinaryOperator::Multiply,
+				Token::Symbol('/') => BinaryOperator::Divide,
+				Token::Symbol('%') => BinaryOperator::Modulo,
+				_ => return Ok(left),
+			};
+			self.next();
+			let right = self.unary()?;
+			left = self.operation(Ast::Binary(operator, Box::new(left), Box::new(right)))?;
+		}
+	}
+
+	// unary := ("-" | "+") unary | power
+	fn unary(&mut self) -> Result<Ast, ExpressionError> {
+		if self.eat('-') {
+			let value = self.nested(Self::unary)?;
+			self.operation(Ast::Negate(Box::new(value)))
+		} else if self.eat('+') {
+			self.nested(Self::unary)
+		} else {
+			self.power()
+		}
+	}
+
+	// power := postfix ("^" unary)?, which is right associative and binds tighter than negation on its left so -2^2 is -4
+	fn power(&mut self) -> Result<Ast, ExpressionError> {
+		let base = self.postfix()?;
+		if self.eat('^') {
+			let exponent = self.nested(Self::unary)?;
+			self.operation(Ast::Binary(BinaryOperator::Power, Box::new(base), Box::new(exponent)))
+		} else {
+			Ok(base)
+		}
+	}
+
+	// postfix := primary ("." ("x" | "y"))*
+	fn postfix(&mut self) -> Result<Ast, ExpressionError> {
+		let mut value = self.primary()?;
+		while self.eat('.') {
+			let component = match self.peek() {
+				Token::Identifier(start, end) if &self.source[start..end] == "x" => 0,
+				Token::Identifier(start, end) if &self.source[start..end] == "y" => 1,
+				_ => return Err(self.unexpected()),
+			};
+			self.next();
+			value = self.operation(Ast::Component(Box::new(value), component))?;
+		}
+		Ok(value)
+	}
+
+	// primary := number | identifier | identifier "(" arguments ")" | "(" additive ")"
+	fn primary(&mut self) -> Result<Ast, ExpressionError> {
+		let position = self.position();
+		let token = self.peek();
+		if !matches!(token, Token::Number(_) | Token::Identifier(..) | Token::Symbol('(')) {
+			return Err(self.unexpected());
+		}
+		self.next();
+
+		match token {
+			Token::Number(number) => Ok(Ast::Constant(number)),
+			Token::Symbol('(') => {
+				let value = self.nested(Self::additive)?;
+				self.expect(')')?;
+				Ok(value)
+			}
+			Token::Identifier(start, end) => {
+				let name = &self.source[start..end];
+				if self.eat('(') {
+					let Some(function) = Function::from_name(name) else {
+						return Err(ExpressionError::new(format!("Unknown function '{name}' at position {position}")));
+					};
+					let mut args = Vec::new();
+					if !self.eat(')') {
+						loop {
+							args.push(self.nested(Self::additive)?);
+							if self.eat(')') {
+								break;
+							}
+							self.expect(',')?;
+						}
+					}
+					if args.len() != function.arity() {
+						return Err(ExpressionError::new(format!("'{name}' takes {} argument(s) but was given {}", function.arity(), args.len())));
+					}
+					self.operation(Ast::Call(function, args))
+				} else if let Some(index) = self.variables.iter().position(|&variable| variable == name) {
+					Ok(Ast::Variable(index))
+				} else {
+					match name {
+						"pi" => Ok(Ast::Constant(std::f64::consts::PI)),
+						"tau" => Ok(Ast::Constant(std::f64::consts::TAU)),
+						"e" => Ok(Ast::Constant(std::f64::consts::E)),
+						_ => Err(ExpressionError::new(format!("Unknown variable '{name}' at position {position}"))),
+					}
+				}
+			}
+			_ => unreachable!("Other tokens were rejected above"),
+		}
+	}
+}
+
+/// A parsed expression which can be evaluated repeatedly with different values for its variables.
+///
+/// Expressions support numbers, the constants `pi`, `tau` and `e`, the operators `+ - * / % ^`, parentheses, function calls such as
+/// `sin(x)` or `lerp(a, b, t)`, vectors built with `vec(x, y)` and their components accessed with `.x` and `.y`. Arithmetic between
+/// two vectors is componentwise, and vectors can be multiplied and divided by numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+	ast: Ast,
+	variable_count: usize,
+}
+
+impl Expression {
+	/// Parses the source, resolving the names of variables to their index in `variables`.
+	pub fn parse(source: &str, variables: &[&str]) -> Result<Self, ExpressionError> {
+		let mut parser = Parser {
+			source,
+			variables,
+			tokens: tokenize(source)?,
+			index: 0,
+			depth: 0,
+			operations: 0,
+		};
+		if parser.peek() == Token::End {
+			return Err(ExpressionError::new("The expression is empty"));
+		}
+		let ast = parser.additive()?;
+		if parser.peek() != Token::End {
+			return Err(parser.unexpected());
+		}
+		Ok(Self { ast, variable_count: variables.len() })
+	}
+
+	/// Evaluates the expression, where `values` holds the value of each variable in the order they were given to [`Expression::parse`].
+	pub fn evaluate(&self, values: &[Value]) -> Result<Value, ExpressionError> {
+		if values.len() != self.variable_count {
+			return Err(ExpressionError::new(format!("Expected {} variable values but was given {}", self.variable_count, values.len())));
+		}
+		evaluate(&self.ast, values)
+	}
+}
+
+fn evaluate(ast: &Ast, values: &[Value]) -> Result<Value, ExpressionError> {
+	match ast {
+		Ast::Constant(number) => Ok(Value::Number(*number)),
+		Ast::Variable(index) => Ok(values[*index]),
+		Ast::Negate(value) => Ok(evaluate(value, values)?.map(|x| -x)),
+		Ast::Component(value, component) => Ok(Value::Number(evaluate(value, values)?.vector("Component access")?[*component])),
+		Ast::Call(function, args) => {
+			let args = args.iter().map(|arg| evaluate(arg, values)).collect::<Result<Vec<_>, _>>()?;
+			function.apply(&args)
+		}
+		Ast::Binary(operator, left, right) => {
+			let (left, right) = (evaluate(left, values)?, evaluate(right, values)?);
+			match (operator, left, right) {
+				(BinaryOperator::Add, left, right) => left.zip(right, "Addition", |a, b| a + b),
+				(BinaryOperator::Subtract, left, right) => left.zip(right, "Subtraction", |a, b| a - b),
+				(BinaryOperator::Multiply, Value::Number(scalar), vector) | (BinaryOperator::Multiply, vector, Value::Number(scalar)) => Ok(vector.map(|x| x * scalar)),
+				(BinaryOperator::Multiply, left, right) => left.zip(right, "Multiplication", |a, b| a * b),
+				(BinaryOperator::Divide, Value::Vector(vector), Value::Number(scalar)) => Ok(Value::Vector(vector / scalar)),
+				(BinaryOperator::Divide, Value::Number(scalar), Value::Vector(vector)) => Ok(Value::Vector(scalar / vector)),
+				(BinaryOperator::Divide, left, right) => left.zip(right, "Division", |a, b| a / b),
+				(BinaryOperator::Modulo, Value::Vector(vector), Value::Number(scalar)) => Ok(Value::Vector(vector % scalar)),
+				(BinaryOperator::Modulo, left, right) => left.zip(right, "Modulo", |a, b| a % b),
+				(BinaryOperator::Power, left, right) => Ok(Value::Number(left.number("Exponentiation")?.powf(right.number("Exponentiation")?))),
+			}
+		}
+	}
+}
+
+/// The most recently compiled expression of a node, so the source is only parsed again once it changes.
+type ExpressionCache = RefCell<Option<(String, Result<Expression, ExpressionError>)>>;
+
+fn evaluate_cached(cache: &ExpressionCache, source: String, values: [Value; 4]) -> Result<Value, ExpressionError> {
+	let mut cache = cache.borrow_mut();
+	if cache.as_ref().map_or(true, |(cached_source, _)| *cached_source != source) {
+		let compiled = Expression::parse(&source, &EXPRESSION_VARIABLES);
+		*cache = Some((source, compiled));
+	}
+	let (_, compiled) = cache.as_ref().expect("The cache was just filled");
+	compiled.as_ref().map_err(Clone::clone)?.evaluate(&values)
+}
+
+#[derive(Debug)]
+pub struct ExpressionNode<Expression, Y, U, V> {
+	expression: Expression,
+	y: Y,
+	u: U,
+	v: V,
+	compiled: ExpressionCache,
+}
+
+impl<Expression, Y, U, V> ExpressionNode<Expression, Y, U, V> {
+	pub fn new(expression: Expression, y: Y, u: U, v: V) -> Self {
+		Self {
+			expression,
+			y,
+			u,
+			v,
+			compiled: RefCell::new(None),
+		}
+	}
+}
+
+#[node_macro::node_impl(ExpressionNode)]
+fn expression_node(x: f64, expression: String, y: f64, u: DVec2, v: DVec2) -> f64 {
+	let values = [Value::Number(x), Value::Number(y), Value::Vector(u), Value::Vector(v)];
+	match evaluate_cached(&self.compiled, expression, values).and_then(|value| value.number("The Expression node")) {
+		Ok(number) => number,
+		Err(error) => {
+			warn!("Failed to evaluate expression: {error}");
+			0.
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct VectorExpressionNode<Expression, Y, U, V> {
+	expression: Expression,
+	y: Y,
+	u: U,
+	v: V,
+	compiled: ExpressionCache,
+}
+
+impl<Expression, Y, U, V> VectorExpressionNode<Expression, Y, U, V> {
+	pub fn new(expression: Expression, y: Y, u: U, v: V) -> Self {
+		Self {
+			expression,
+			y,
+			u,
+			v,
+			compiled: RefCell::new(None),
+		}
+	}
+}
+
+#[node_macro::node_impl(VectorExpressionNode)]
+fn vector_expression_node(x: f64, expression: String, y: f64, u: DVec2, v: DVec2) -> DVec2 {
+	let values = [Value::Number(x), Value::Number(y), Value::Vector(u), Value::Vector(v)];
+	match evaluate_cached(&self.compiled, expression, values).and_then(|value| value.vector("The Vector Expression node")) {
+		Ok(vector) => vector,
+		Err(error) => {
+			warn!("Failed to evaluate expression: {error}");
+			DVec2::ZERO
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn eval(source: &str, values: &[(&str, Value)]) -> Result<Value, ExpressionError> {
+		let names = values.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+		let values = values.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+		Expression::parse(source, &names)?.evaluate(&values)
+	}
+
+	#[test]
+	fn precedence_and_associativity() {
+		assert_eq!(eval("1 + 2 * 3", &[]), Ok(Value::Number(7.)));
+		assert_eq!(eval("(1 + 2) * 3", &[]), Ok(Value::Number(9.)));
+		assert_eq!(eval("10 - 4 - 3", &[]), Ok(Value::Number(3.)));
+		assert_eq!(eval("2 ^ 3 ^ 2", &[]), Ok(Value::Number(512.)));
+		assert_eq!(eval("-2 ^ 2", &[]), Ok(Value::Number(-4.)));
+		assert_eq!(eval("2 ^ -1", &[]), Ok(Value::Number(0.5)));
+		assert_eq!(eval("7 % 4 + 1.5e1", &[]), Ok(Value::Number(18.)));
+	}
+
+	#[test]
+	fn variables_and_functions() {
+		let x = ("x", Value::Number(3.));
+		let y = ("y", Value::Number(4.));
+		assert_eq!(eval("hypot(x, y)", &[x, y]), Ok(Value::Number(5.)));
+		assert_eq!(eval("clamp(x * y, 0, 10)", &[x, y]), Ok(Value::Number(10.)));
+		assert_eq!(eval("lerp(x, y, 0.5)", &[x, y]), Ok(Value::Number(3.5)));
+		assert_eq!(eval("cos(pi)", &[]), Ok(Value::Number(-1.)));
+	}
+
+	#[test]
+	fn vector_operations() {
+		let u = ("u", Value::Vector(DVec2::new(3., 4.)));
+		let v = ("v", Value::Vector(DVec2::new(1., 2.)));
+		assert_eq!(eval("u + v * 2", &[u, v]), Ok(Value::Vector(DVec2::new(5., 8.))));
+		assert_eq!(eval("length(u)", &[u]), Ok(Value::Number(5.)));
+		assert_eq!(eval("dot(u, v)", &[u, v]), Ok(Value::Number(11.)));
+		assert_eq!(eval("vec(u.y, -u.x) / 2", &[u]), Ok(Value::Vector(DVec2::new(2., -1.5))));
+		assert_eq!(eval("abs(-u)", &[u]), Ok(Value::Vector(DVec2::new(3., 4.))));
+	}
+
+	#[test]
+	fn errors() {
+		let u = ("u", Value::Vector(DVec2::ONE));
+		assert!(eval("", &[]).is_err());
+		assert!(eval("1 +", &[]).is_err());
+		assert!(eval("(1 + 2", &[]).is_err());
+		assert!(eval("1 2", &[]).is_err());
+		assert!(eval("foo(1)", &[]).is_err());
+		assert!(eval("z", &[]).is_err());
+		assert!(eval("sin(1, 2)", &[]).is_err());
+		assert!(eval("u + 1", &[u]).is_err());
+		assert!(eval("sin(u)", &[u]).is_err());
+	}
+
+	#[test]
+	fn deep_nesting_is_an_error() {
+		let deep = 100_000;
+		for source in [
+			format!("{}1", "-".repeat(deep)),
+			format!("{}1{}", "(".repeat(deep), ")".repeat(deep)),
+			format!("{}1", "sin(".repeat(deep)),
+			format!("{}2", "2^".repeat(deep)),
+			format!("{}1", "1+".repeat(deep)),
+		] {
+			assert!(Expression::parse(&source, &[]).is_err());
+		}
+
+		let limit = MAX_NESTING_DEPTH;
+		assert_eq!(eval(&format!("{}1", "-".repeat(limit)), &[]), Ok(Value::Number(1.)));
+		assert_eq!(eval(&format!("{}1{}", "(".repeat(limit), ")".repeat(limit)), &[]), Ok(Value::Number(1.)));
+		assert!(eval(&format!("{}1{}", "(".repeat(limit + 1), ")".repeat(limit + 1)), &[]).is_err());
+	}
+
+	#[test]
+	fn cached_errors_are_returned_without_parsing_again() {
+		let cache = ExpressionCache::default();
+		let values = [Value::Number(0.); 4];
+		let error = evaluate_cached(&cache, "1 +".to_string(), values).unwrap_err();
+		assert_eq!(evaluate_cached(&cache, "1 +".to_string(), values), Err(error.clone()));
+		assert!(matches!(&*cache.borrow(), Some((_, Err(cached))) if *cached == error));
+	}
+
+	#[test]
+	fn compiles_once_per_source() {
+		let node = ExpressionNode::new(
+			graphene_core::value::ClonedNode::new(String::from("x * y + u.x")),
+			graphene_core::value::ClonedNode::new(2.),
+			graphene_core::value::ClonedNode::new(DVec2::new(1., 0.)),
+			graphene_core::value::ClonedNode::new(DVec2::ZERO),
+		);
+		assert_eq!(node.eval(3.), 7.);
+		let compiled = node.compiled.borrow().clone();
+		assert_eq!(node.eval(4.), 9.);
+		assert_eq!(*node.compiled.borrow(), compiled);
+	}
+}
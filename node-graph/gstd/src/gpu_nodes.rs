@@ -78,7 +78,7 @@ async fn map_gpu<'a: 'input>(image: ImageFrame<Color>, node: DocumentNode, edito
 	log::debug!("quantization: {quantization:?}");
 
 	#[cfg(feature = "image-compare")]
-	let img: image::DynamicImage = image::Rgba32FImage::from_raw(image.image.width, image.image.height, bytemuck::cast_vec(image.image.data.clone()))
+	let img: image::DynamicImage = image::Rgba32FImage::from_raw(image.image.width, image.image.height, bytemuck::cast_vec(image.image.data.to_vec()))
 		.unwrap()
 		.into();
 
@@ -138,7 +138,7 @@ async fn map_gpu<'a: 'input>(image: ImageFrame<Color>, node: DocumentNode, edito
 
 	ImageFrame {
 		image: Image {
-			data: colors,
+			data: colors.into(),
 			width: image.image.width,
 			height: image.image.height,
 			..Default::default()
@@ -309,7 +309,7 @@ async fn create_compute_pass_descriptor<T: Clone + Pixel + StaticTypeSized>(
 	let quantization_uniform = executor.create_uniform_buffer(quantization).unwrap();
 	let storage_buffer = executor
 		.create_storage_buffer(
-			image.image.data.clone(),
+			image.image.data.to_vec(),
 			StorageBufferOptions {
 				cpu_writable: false,
 				gpu_writable: true,
@@ -511,7 +511,7 @@ async fn blend_gpu_image(foreground: ImageFrame<Color>, background: ImageFrame<C
 	let width_uniform = executor.create_uniform_buffer(background.image.width).unwrap();
 	let bg_storage_buffer = executor
 		.create_storage_buffer(
-			background.image.data.clone(),
+			background.image.data.to_vec(),
 			StorageBufferOptions {
 				cpu_writable: false,
 				gpu_writable: true,
@@ -522,7 +522,7 @@ async fn blend_gpu_image(foreground: ImageFrame<Color>, background: ImageFrame<C
 		.unwrap();
 	let fg_storage_buffer = executor
 		.create_storage_buffer(
-			foreground.image.data.clone(),
+			foreground.image.data.to_vec(),
 			StorageBufferOptions {
 				cpu_writable: false,
 				gpu_writable: true,
@@ -583,7 +583,7 @@ async fn blend_gpu_image(foreground: ImageFrame<Color>, background: ImageFrame<C
 
 	ImageFrame {
 		image: Image {
-			data: colors,
+			data: colors.into(),
 			width: background.image.width,
 			height: background.image.height,
 			..Default::default()
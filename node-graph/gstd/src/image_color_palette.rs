@@ -73,7 +73,7 @@ mod test {
 				image: Image {
 					width: 100,
 					height: 100,
-					data: vec![Color::from_rgbaf32(0.0, 0.0, 0.0, 1.0).unwrap(); 10000],
+					data: vec![Color::from_rgbaf32(0.0, 0.0, 0.0, 1.0).unwrap(); 10000].into(),
 					base64_string: None,
 				},
 				..Default::default()
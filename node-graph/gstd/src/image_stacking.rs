@@ -0,0 +1,422 @@
+use crate::panorama::{pixel_to_layer, sample_bilinear};
+
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::{Color, Node};
+
+use glam::DVec2;
+
+/// Merges a stack of aligned images of the same scene focused at different depths, keeping the sharpest image at each pixel.
+/// Sharpness is measured over a neighborhood of `smoothness` pixels, which trades detail at focus boundaries for fewer speckles where no image is sharp.
+pub struct FocusStackNode<Smoothness> {
+	smoothness: Smoothness,
+}
+
+#[node_macro::node_fn(FocusStackNode)]
+fn focus_stack(images: Vec<ImageFrame<Color>>, smoothness: f64) -> ImageFrame<Color> {
+	let Some(stack) = Stack::resample(&images) else {
+		return ImageFrame::empty();
+	};
+	let radius = smoothness.clamp(0., 100.).round() as usize;
+
+	let sharpness = stack.layers.iter().map(|layer| layer.luminance().laplacian().abs().box_blur(radius)).collect::<Vec<_>>();
+
+	// Each pixel is taken entirely from the image which is sharpest there, with the pyramid blend hiding the seams between them
+	let mut weights = stack.layers.iter().map(|layer| Plane::new(layer.coverage.width, layer.coverage.height)).collect::<Vec<_>>();
+	for pixel in 0..weights[0].data.len() {
+		let sharpest = (0..stack.layers.len())
+			.filter(|&index| stack.layers[index].coverage.data[pixel] > 0.)
+			.max_by(|&a, &b| sharpness[a].data[pixel].total_cmp(&sharpness[b].data[pixel]));
+		if let Some(sharpest) = sharpest {
+			weights[sharpest].data[pixel] = 1.;
+		}
+	}
+
+	stack.blend(weights)
+}
+
+/// Merges a stack of aligned images of the same scene taken at different exposures into one image with detail in both its shadows and highlights.
+/// Following the exposure fusion of Mertens et al., each pixel is weighted by its local contrast, its color saturation, and how close it is to a mid-tone, with each measure raised to the given power.
+pub struct ExposureFusionNode<ContrastWeight, SaturationWeight, ExposureWeight> {
+	contrast_weight: ContrastWeight,
+	saturation_weight: SaturationWeight,
+	exposure_weight: ExposureWeight,
+}
+
+#[node_macro::node_fn(ExposureFusionNode)]
+fn exposure_fusion(images: Vec<ImageFrame<Color>>, contrast_weight: f64, saturation_weight: f64, exposure_weight: f64) -> ImageFrame<Color> {
+	let Some(stack) = Stack::resample(&images) else {
+		return ImageFrame::empty();
+	};
+	let [contrast_weight, saturation_weight, exposure_weight] = [contrast_weight, saturation_weight, exposure_weight].map(|weight| weight.max(0.) as f32);
+
+	// How well exposed a mid-tone has to be, as the standard deviation of the bell curve around middle gray
+	const EXPOSEDNESS_SIGMA: f32 = 0.2;
+
+	let weights = stack
+		.layers
+		.iter()
+		.map(|layer| {
+			let contrast = layer.luminance().laplacian().abs();
+			let data = layer
+				.colors
+				.iter()
+				.zip(&contrast.data)
+				.zip(&layer.coverage.data)
+				.map(|((color, contrast), coverage)| {
+					// The measures are taken on gamma-encoded colors, as the method was designed for display-referred images
+					let color = color.to_unassociated_alpha().to_gamma_srgb();
+					let channels = [color.r(), color.g(), color.b()];
+
+					let mean = channels.iter().sum::<f32>() / 3.;
+					let saturation = (channels.iter().map(|channel| (channel - mean).powi(2)).sum::<f32>() / 3.).sqrt();
+					let exposedness = channels.iter().map(|channel| (-(channel - 0.5).powi(2) / (2. * EXPOSEDNESS_SIGMA.powi(2))).exp()).product::<f32>();
+
+					coverage * contrast.powf(contrast_weight) * saturation.powf(saturation_weight) * exposedness.powf(exposure_weight)
+				})
+				.collect();
+			Plane { data, ..contrast }
+		})
+		.collect::<Vec<_>>();
+
+	stack.blend(weights)
+}
+
+/// The images of a stack, resampled onto the pixel grid of the first one.
+struct Stack<'a> {
+	reference: &'a ImageFrame<Color>,
+	layers: Vec<Layer>,
+}
+
+struct Layer {
+	colors: Vec<Color>,
+	/// 1 where the image covers the reference image, and 0 beyond its edges.
+	coverage: Plane,
+}
+
+impl<'a> Stack<'a> {
+	fn resample(images: &'a [ImageFrame<Color>]) -> Option<Self> {
+		let mut frames = images.iter().filter(|frame| frame.image.width > 0 && frame.image.height > 0);
+		let reference = frames.next()?;
+		let (width, height) = (reference.image.width as usize, reference.image.height as usize);
+		let reference_to_layer = pixel_to_layer(reference);
+
+		let layers = core::iter::once(reference)
+			.chain(frames)
+			.map(|frame| {
+				let size = DVec2::new(frame.image.width as f64, frame.image.height as f64);
+				let reference_to_pixel = pixel_to_layer(frame).inverse() * reference_to_layer;
+
+				let mut colors = Vec::with_capacity(width * height);
+				let mut coverage = Plane::new(width, height);
+				for y in 0..height {
+					for x in 0..width {
+						let position = reference_to_pixel.transform_point2(DVec2::new(x as f64 + 0.5, y as f64 + 0.5));
+						if position.cmpge(DVec2::ZERO).all() && position.cmple(size).all() {
+							colors.push(sample_bilinear(&frame.image, position));
+							coverage.data[y * width + x] = 1.;
+						} else {
+							colors.push(Color::TRANSPARENT);
+						}
+					}
+				}
+				Layer { colors, coverage }
+			})
+			.collect();
+
+		Some(Self { reference, layers })
+	}
+
+	/// Blends the layers with a per-pixel weight for each one, combining them separately at each scale of a Laplacian pyramid so the blend has no visible seams.
+	fn blend(&self, mut weights: Vec<Plane>) -> ImageFrame<Color> {
+		let (width, height) = (self.reference.image.width as usize, self.reference.image.height as usize);
+
+		// Normalize the weights, falling back to an even split wherever none of the images contribute
+		for pixel in 0..width * height {
+			let total = weights.iter().map(|weights| weights.data[pixel]).sum::<f32>();
+			for weights in &mut weights {
+				weights.data[pixel] = if total > 1e-12 { weights.data[pixel] / total } else { 1. / self.layers.len() as f32 };
+			}
+		}
+
+		let levels = pyramid_levels(width, height);
+		let mut blended: Option<[Vec<Plane>; 4]> = None;
+		for (layer, weights) in self.layers.iter().zip(weights) {
+			let weight_pyramid = gaussian_pyramid(weights, levels);
+
+			let channels: [Plane; 4] = core::array::from_fn(|channel| Plane {
+				width,
+				height,
+				data: layer.colors.iter().map(|color| [color.r(), color.g(), color.b(), color.a()][channel]).collect(),
+			});
+
+			let contributions = channels.map(|channel| {
+				laplacian_pyramid(channel, levels)
+					.into_iter()
+					.zip(&weight_pyramid)
+					.map(|(detail, weight)| Plane {
+						data: detail.data.iter().zip(&weight.data).map(|(detail, weight)| detail * weight).collect(),
+						..detail
+					})
+					.collect::<Vec<_>>()
+			});
+
+			blended = Some(match blended {
+				None => contributions,
+				Some(mut blended) => {
+					for (blended, contribution) in blended.iter_mut().zip(contributions) {
+						for (blended, contribution) in blended.iter_mut().zip(contribution) {
+							blended.data.iter_mut().zip(contribution.data).for_each(|(blended, contribution)| *blended += contribution);
+						}
+					}
+					blended
+				}
+			});
+		}
+		let Some(blended) = blended else { return ImageFrame::empty() };
+
+		let [red, green, blue, alpha] = blended.map(collapse_pyramid);
+		let data = (0..width * height)
+			.map(|pixel| {
+				let alpha = alpha.data[pixel].clamp(0., 1.);
+				let [red, green, blue] = [&red, &green, &blue].map(|channel| channel.data[pixel].clamp(0., alpha));
+				Color::from_rgbaf32_unchecked(red, green, blue, alpha)
+			})
+			.collect();
+
+		ImageFrame {
+			image: Image {
+				width: width as u32,
+				height: height as u32,
+				data,
+				base64_string: None,
+			},
+			transform: self.reference.transform,
+			alpha_blending: self.reference.alpha_blending,
+		}
+	}
+}
+
+/// A single channel of floating point values.
+#[derive(Clone)]
+struct Plane {
+	width: usize,
+	height: usize,
+	data: Vec<f32>,
+}
+
+impl Plane {
+	fn new(width: usize, height: usize) -> Self {
+		Self {
+			width,
+			height,
+			data: vec![0.; width * height],
+		}
+	}
+
+	/// Reads a value, extending the edges of the plane beyond its bounds.
+	fn get(&self, x: isize, y: isize) -> f32 {
+		let x = x.clamp(0, self.width as isize - 1) as usize;
+		let y = y.clamp(0, self.height as isize - 1) as usize;
+		self.data[y * self.width + x]
+	}
+
+	fn map(&self, f: impl Fn(f32) -> f32) -> Self {
+		Self {
+			data: self.data.iter().copied().map(f).collect(),
+			..*self
+		}
+	}
+
+	fn abs(&self) -> Self {
+		self.map(f32::abs)
+	}
+
+	fn laplacian(&self) -> Self {
+		let mut result = Self::new(self.width, self.height);
+		for y in 0..self.height as isize {
+			for x in 0..self.width as isize {
+				let neighbors = self.get(x - 1, y) + self.get(x + 1, y) + self.get(x, y - 1) + self.get(x, y + 1);
+				result.data[y as usize * self.width + x as usize] = neighbors - 4. * self.get(x, y);
+			}
+		}
+		result
+	}
+
+	/// Averages each value with its neighbors up to `radius` pixels away along both axes.
+	fn box_blur(&self, radius: usize) -> Self {
+		if radius == 0 {
+			return self.clone();
+		}
+		let radius = radius as isize;
+		let pass = |plane: &Self, horizontal: bool| {
+			let mut result = Self::new(plane.width, plane.height);
+			for y in 0..plane.height as isize {
+				for x in 0..plane.width as isize {
+					let sum = (-radius..=radius)
+						.map(|offset| if horizontal { plane.get(x + offset, y) } else { plane.get(x, y + offset) })
+						.sum::<f32>();
+					result.data[y as usize * plane.width + x as usize] = sum / (2 * radius + 1) as f32;
+				}
+			}
+			result
+		};
+		pass(&pass(self, true), false)
+	}
+
+	/// Blurs with a 5-tap binomial filter and keeps every other pixel along both axes.
+	fn downsample(&self) -> Self {
+		const KERNEL: [f32; 5] = [1. / 16., 4. / 16., 6. / 16., 4. / 16., 1. / 16.];
+
+		let (width, height) = (self.width.div_ceil(2), self.height.div_ceil(2));
+		let mut horizontal = Self::new(width, self.height);
+		for y in 0..self.height as isize {
+			for x in 0..width as isize {
+				horizontal.data[y as usize * width + x as usize] = KERNEL.iter().enumerate().map(|(tap, weight)| weight * self.get(2 * x + tap as isize - 2, y)).sum();
+			}
+		}
+
+		let mut result = Self::new(width, height);
+		for y in 0..height as isize {
+			for x in 0..width as isize {
+				result.data[y as usize * width + x as usize] = KERNEL.iter().enumerate().map(|(tap, weight)| weight * horizontal.get(x, 2 * y + tap as isize - 2)).sum();
+			}
+		}
+		result
+	}
+
+	/// Bilinearly scales the plane up to the given size, which is at most twice its own along each axis.
+	fn upsample(&self, width: usize, height: usize) -> Self {
+		let mut result = Self::new(width, height);
+		for y in 0..height {
+			for x in 0..width {
+				let source = DVec2::new(x as f64 + 0.5, y as f64 + 0.5) / 2. - 0.5;
+				let (x0, y0) = (source.x.floor() as isize, source.y.floor() as isize);
+				let fraction = (source - source.floor()).as_vec2();
+
+				let top = self.get(x0, y0) * (1. - fraction.x) + self.get(x0 + 1, y0) * fraction.x;
+				let bottom = self.get(x0, y0 + 1) * (1. - fraction.x) + self.get(x0 + 1, y0 + 1) * fraction.x;
+				result.data[y * width + x] = top * (1. - fraction.y) + bottom * fraction.y;
+			}
+		}
+		result
+	}
+}
+
+impl Layer {
+	fn luminance(&self) -> Plane {
+		Plane {
+			width: self.coverage.width,
+			height: self.coverage.height,
+			data: self.colors.iter().map(|color| color.luminance_srgb()).collect(),
+		}
+	}
+}
+
+/// How many levels a pyramid has for an image of this size, stopping before the smallest level shrinks below a few pixels.
+fn pyramid_levels(width: usize, height: usize) -> usize {
+	const SMALLEST_LEVEL: usize = 8;
+
+	let mut levels = 1;
+	let mut size = width.min(height);
+	while size / 2 >= SMALLEST_LEVEL {
+		size = size.div_ceil(2);
+		levels += 1;
+	}
+	levels
+}
+
+fn gaussian_pyramid(plane: Plane, levels: usize) -> Vec<Plane> {
+	let mut pyramid = vec![plane];
+	while pyramid.len() < levels {
+		let next = pyramid.last().unwrap().downsample();
+		pyramid.push(next);
+	}
+	pyramid
+}
+
+/// Splits the plane into the detail at each scale, from finest to coarsest, ending with the remaining low-frequency base.
+fn laplacian_pyramid(plane: Plane, levels: usize) -> Vec<Plane> {
+	let mut gaussian = gaussian_pyramid(plane, levels);
+	for level in 0..levels - 1 {
+		let (finer, coarser) = gaussian.split_at_mut(level + 1);
+		let finer = &mut finer[level];
+		let expanded = coarser[0].upsample(finer.width, finer.height);
+		finer.data.iter_mut().zip(expanded.data).for_each(|(value, expanded)| *value -= expanded);
+	}
+	gaussian
+}
+
+/// Reassembles a plane from its Laplacian pyramid.
+fn collapse_pyramid(mut pyramid: Vec<Plane>) -> Plane {
+	let mut result = pyramid.pop().unwrap();
+	while let Some(mut detail) = pyramid.pop() {
+		let expanded = result.upsample(detail.width, detail.height);
+		detail.data.iter_mut().zip(expanded.data).for_each(|(value, expanded)| *value += expanded);
+		result = detail;
+	}
+	result
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::value::CopiedNode;
+
+	use glam::DAffine2;
+
+	fn frame(width: u32, height: u32, pixel: impl Fn(u32, u32) -> f32) -> ImageFrame<Color> {
+		let data = (0..height)
+			.flat_map(|y| (0..width).map(move |x| (x, y)))
+			.map(|(x, y)| Color::from_rgbf32_unchecked(pixel(x, y), pixel(x, y), pixel(x, y)))
+			.collect();
+		ImageFrame {
+			image: Image {
+				width,
+				height,
+				data,
+				base64_string: None,
+			},
+			transform: DAffine2::from_scale(DVec2::new(width as f64, height as f64)),
+			..Default::default()
+		}
+	}
+
+	fn checker(x: u32, y: u32) -> f32 {
+		((x / 2 + y / 2) % 2) as f32
+	}
+
+	#[test]
+	fn focus_stack_keeps_the_sharp_half_of_each_image() {
+		// Each image has a checkerboard in one half, and its blurred-out average in the other
+		let left_in_focus = frame(64, 64, |x, y| if x < 32 { checker(x, y) } else { 0.5 });
+		let right_in_focus = frame(64, 64, |x, y| if x >= 32 { checker(x, y) } else { 0.5 });
+
+		let result = FocusStackNode { smoothness: CopiedNode(2.) }.eval(vec![left_in_focus, right_in_focus]);
+		assert_eq!((result.image.width, result.image.height), (64, 64));
+
+		for y in 8..56 {
+			for x in (4..24).chain(40..60) {
+				let value = result.image.data[y * 64 + x].r();
+				assert!((value - checker(x as u32, y as u32)).abs() < 0.1, "pixel ({x}, {y}) is {value}");
+			}
+		}
+	}
+
+	#[test]
+	fn exposure_fusion_of_identical_images_is_unchanged() {
+		let image = frame(40, 30, |x, y| (x as f32 / 40. + y as f32 / 60.) % 1.);
+
+		let result = ExposureFusionNode {
+			contrast_weight: CopiedNode(1.),
+			saturation_weight: CopiedNode(1.),
+			exposure_weight: CopiedNode(1.),
+		}
+		.eval(vec![image.clone(), image.clone()]);
+
+		for (result, expected) in result.image.data.iter().zip(&image.image.data) {
+			assert!((result.r() - expected.r()).abs() < 1e-4);
+			assert!((result.a() - expected.a()).abs() < 1e-4);
+		}
+	}
+}
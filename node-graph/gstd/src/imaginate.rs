@@ -458,10 +458,10 @@ fn image_to_base64<P: Pixel>(image: Image<P>) -> Result<String, Error> {
 	}
 
 	let image: DynamicImage = match TypeId::of::<P>() {
-		id if id == TypeId::of::<Color>() => cast_with_f32::<_, image::Rgba<f32>>(data, width, height)?
+		id if id == TypeId::of::<Color>() => cast_with_f32::<_, image::Rgba<f32>>(data.into_vec(), width, height)?
 			// we need to do this cast, because png does not support rgba32f
 			.to_rgba16().into(),
-		id if id == TypeId::of::<Luma>() => cast_with_f32::<_, image::Luma<f32>>(data, width, height)?
+		id if id == TypeId::of::<Luma>() => cast_with_f32::<_, image::Luma<f32>>(data.into_vec(), width, height)?
 			// we need to do this cast, because png does not support luma32f
 			.to_luma16().into(),
 		_ => return Err(Error::UnsupportedPixelType(core::any::type_name::<P>())),
@@ -486,7 +486,7 @@ fn base64_to_image<D: AsRef<[u8]>, P: Pixel>(base64_data: D) -> Result<Image<P>,
 	};
 
 	Ok(Image {
-		data: result_data,
+		data: result_data.into(),
 		width,
 		height,
 		base64_string: None,
@@ -25,8 +25,32 @@ pub mod image_segmentation;
 
 pub mod image_color_palette;
 
+pub mod panorama;
+
+pub mod warp;
+
+pub mod stylize;
+
+pub mod convolution;
+
+pub mod morphology;
+
+pub mod image_stacking;
+
 pub mod brush;
 
+pub mod expression;
+
+pub mod audio;
+
+pub mod validation;
+
+#[cfg(feature = "serde")]
+pub mod snapshot;
+
+#[cfg(feature = "plugins")]
+pub mod plugin;
+
 #[cfg(feature = "wasm")]
 pub mod wasm_application_io;
 
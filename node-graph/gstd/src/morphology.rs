@@ -0,0 +1,128 @@
+use graphene_core::raster::{Image, ImageFrame, MorphologyChannel, MorphologyOperation, MorphologyShape};
+use graphene_core::{Color, Node};
+
+/// The largest radius (in pixels) of a morphology filter, which looks across a neighborhood of every pixel within it.
+const MAX_MORPHOLOGY_RADIUS: f64 = 100.;
+
+/// Replaces each pixel by the pixel within the radius which is highest in the channel when dilating, or lowest when eroding.
+/// Pixels beyond the edges of the image are ignored, so the edges themselves neither grow nor shrink the image.
+fn rank_filter(image: &Image<Color>, offsets: &[(i32, i32)], channel: MorphologyChannel, dilate: bool) -> Image<Color> {
+	let (width, height) = (image.width as i32, image.height as i32);
+	let value = |color: &Color| match channel {
+		MorphologyChannel::Alpha => color.a(),
+		MorphologyChannel::Luminance => color.luminance_srgb(),
+	};
+
+	let mut data = Vec::with_capacity(image.data.len());
+	for y in 0..height {
+		for x in 0..width {
+			let neighbors = offsets
+				.iter()
+				.map(|&(dx, dy)| (x + dx, y + dy))
+				.filter(|&(x, y)| x >= 0 && y >= 0 && x < width && y < height)
+				.map(|(x, y)| &image.data[(y * width + x) as usize]);
+			let comparison = |a: &&Color, b: &&Color| value(a).total_cmp(&value(b));
+			let chosen = if dilate { neighbors.max_by(comparison) } else { neighbors.min_by(comparison) };
+			data.push(*chosen.unwrap_or(&image.data[(y * width + x) as usize]));
+		}
+	}
+
+	Image {
+		width: image.width,
+		height: image.height,
+		data: data.into(),
+		base64_string: None,
+	}
+}
+
+/// Grows or shrinks the opaque (or bright) areas of the image by the radius, for expanding or contracting masks and selections or making outlines from the difference with the original.
+/// Each pixel takes on the whole color of the pixel in its neighborhood which is most or least opaque (or bright), so colors stay as they were while their areas change shape.
+pub struct MorphologyNode<Operation, Channel, Radius, Shape> {
+	operation: Operation,
+	channel: Channel,
+	radius: Radius,
+	shape: Shape,
+}
+
+#[node_macro::node_fn(MorphologyNode)]
+fn morphology(image_frame: ImageFrame<Color>, operation: MorphologyOperation, channel: MorphologyChannel, radius: f64, shape: MorphologyShape) -> ImageFrame<Color> {
+	let radius = radius.clamp(0., MAX_MORPHOLOGY_RADIUS).round() as i32;
+	if radius == 0 || image_frame.image.width == 0 || image_frame.image.height == 0 {
+		return image_frame;
+	}
+
+	let offsets = (-radius..=radius)
+		.flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+		.filter(|&(dx, dy)| shape.contains(dx, dy, radius))
+		.collect::<Vec<_>>();
+	let filter = |image: &Image<Color>, dilate: bool| rank_filter(image, &offsets, channel, dilate);
+
+	let image = match operation {
+		MorphologyOperation::Dilate => filter(&image_frame.image, true),
+		MorphologyOperation::Erode => filter(&image_frame.image, false),
+		MorphologyOperation::Open => filter(&filter(&image_frame.image, false), true),
+		MorphologyOperation::Close => filter(&filter(&image_frame.image, true), false),
+	};
+
+	ImageFrame { image, ..image_frame }
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::value::CopiedNode;
+
+	/// A 9×9 transparent image with the opaque pixels at the given coordinates.
+	fn mask(opaque: &[(usize, usize)]) -> ImageFrame<Color> {
+		let mut image = Image::new(9, 9, Color::TRANSPARENT);
+		for &(x, y) in opaque {
+			image.data[y * 9 + x] = Color::WHITE;
+		}
+		ImageFrame { image, ..Default::default() }
+	}
+
+	fn apply(frame: ImageFrame<Color>, operation: MorphologyOperation, radius: f64, shape: MorphologyShape) -> Vec<bool> {
+		let node = MorphologyNode {
+			operation: CopiedNode(operation),
+			channel: CopiedNode(MorphologyChannel::Alpha),
+			radius: CopiedNode(radius),
+			shape: CopiedNode(shape),
+		};
+		node.eval(frame).image.data.iter().map(|color| color.a() > 0.5).collect()
+	}
+
+	#[test]
+	fn dilate_grows_in_the_shape() {
+		let dilated = |shape| apply(mask(&[(4, 4)]), MorphologyOperation::Dilate, 2., shape);
+		let count = |pixels: Vec<bool>| pixels.into_iter().filter(|&opaque| opaque).count();
+
+		assert_eq!(count(dilated(MorphologyShape::Square)), 25);
+		assert_eq!(count(dilated(MorphologyShape::Diamond)), 13);
+		assert_eq!(count(dilated(MorphologyShape::Circle)), 13);
+		assert!(dilated(MorphologyShape::Square)[2 * 9 + 2]);
+		assert!(!dilated(MorphologyShape::Diamond)[2 * 9 + 2]);
+	}
+
+	#[test]
+	fn open_and_close_remove_small_features() {
+		// A 5×5 block with a speck beside it
+		let mut pixels = (2..7).flat_map(|y| (2..7).map(move |x| (x, y))).collect::<Vec<_>>();
+		pixels.push((0, 0));
+		let frame = mask(&pixels);
+
+		let eroded = apply(frame.clone(), MorphologyOperation::Erode, 1., MorphologyShape::Square);
+		assert_eq!(eroded.iter().filter(|&&opaque| opaque).count(), 9);
+
+		let opened = apply(frame.clone(), MorphologyOperation::Open, 1., MorphologyShape::Square);
+		assert!(!opened[0]);
+		assert_eq!(opened.iter().filter(|&&opaque| opaque).count(), 25);
+
+		// A hole in the middle of the block is filled by closing
+		let mut holed = frame.clone();
+		holed.image.data[4 * 9 + 4] = Color::TRANSPARENT;
+		let closed = apply(holed, MorphologyOperation::Close, 1., MorphologyShape::Square);
+		assert!(closed[4 * 9 + 4]);
+		assert!(closed[0]);
+	}
+}
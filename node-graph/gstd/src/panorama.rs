@@ -0,0 +1,206 @@
+use graphene_core::raster::image_alignment::align_images;
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::transform::Footprint;
+use graphene_core::{Color, GraphicElement, GraphicGroup, Node};
+
+use core::future::Future;
+use glam::{DAffine2, DVec2};
+
+/// Alignments correlating less strongly than this are treated as failures, leaving the image where it was placed.
+const MIN_ALIGNMENT_CONFIDENCE: f64 = 0.1;
+
+/// The largest width or height of a stitched panorama, beyond which its resolution is reduced to fit.
+const MAX_PANORAMA_SIZE: f64 = 8192.;
+
+/// Collects the images of a group in stacking order and moves each one into alignment with the images before it where they overlap.
+/// The first image stays in place and serves as the reference for the rest.
+pub struct AlignImagesNode<Images> {
+	images: Images,
+}
+
+#[node_macro::node_fn(AlignImagesNode)]
+async fn align_images_node<Fut: Future<Output = GraphicGroup>>(_footprint: Footprint, images: impl Node<Footprint, Output = Fut>) -> Vec<ImageFrame<Color>> {
+	// TODO: Pass through the footprint once the alignment can be reused between renders of different areas
+	let images = self.images.eval(Footprint::default()).await;
+
+	let mut frames = Vec::new();
+	collect_image_frames(&images, DAffine2::IDENTITY, &mut frames);
+
+	let mut placed: Vec<ImageFrame<Color>> = Vec::with_capacity(frames.len());
+	for mut frame in frames {
+		let moving_transform = pixel_to_layer(&frame);
+
+		let best = placed
+			.iter()
+			.filter_map(|reference| align_images(&reference.image, pixel_to_layer(reference), &frame.image, moving_transform))
+			.max_by(|a, b| a.confidence.total_cmp(&b.confidence));
+
+		match best {
+			Some(alignment) if alignment.confidence >= MIN_ALIGNMENT_CONFIDENCE => frame.transform = alignment.transform * frame.transform,
+			_ if !placed.is_empty() => warn!("Could not find where an image overlaps the rest of the panorama, so it was left in place"),
+			_ => {}
+		}
+
+		placed.push(frame);
+	}
+
+	placed
+}
+
+/// Flattens the images within a group and its nested groups, with each image's transform taken into the space of the outermost group.
+fn collect_image_frames(group: &GraphicGroup, parent_transform: DAffine2, frames: &mut Vec<ImageFrame<Color>>) {
+	let transform = parent_transform * group.transform;
+
+	for element in group.iter() {
+		match element {
+			GraphicElement::ImageFrame(frame) if frame.image.width > 0 && frame.image.height > 0 => {
+				frames.push(ImageFrame {
+					transform: transform * frame.transform,
+					..frame.clone()
+				});
+			}
+			GraphicElement::GraphicGroup(group) => collect_image_frames(group, transform, frames),
+			_ => {}
+		}
+	}
+}
+
+/// The transform from an image's pixel coordinates into its layer space.
+pub(crate) fn pixel_to_layer(frame: &ImageFrame<Color>) -> DAffine2 {
+	frame.transform * DAffine2::from_scale(DVec2::new(frame.image.width as f64, frame.image.height as f64).recip())
+}
+
+/// Merges aligned images into a single image covering all of them.
+/// Where the images overlap, each one fades out towards its edges over the `feather` distance, given as a percentage of half its shorter side, hiding the seams between them.
+pub struct BlendPanoramaNode<Feather> {
+	feather: Feather,
+}
+
+#[node_macro::node_fn(BlendPanoramaNode)]
+fn blend_panorama(images: Vec<ImageFrame<Color>>, feather: f64) -> ImageFrame<Color> {
+	let frames = images.iter().filter(|frame| frame.image.width > 0 && frame.image.height > 0).collect::<Vec<_>>();
+	let Some(first) = frames.first() else {
+		return ImageFrame::empty();
+	};
+
+	// The panorama takes on the resolution of its most detailed image and covers the bounds of all of them
+	let mut pixel_size = f64::INFINITY;
+	let (mut min, mut max) = (DVec2::INFINITY, DVec2::NEG_INFINITY);
+	for frame in &frames {
+		pixel_size = pixel_size.min(pixel_to_layer(frame).matrix2.determinant().abs().sqrt());
+
+		for corner in [DVec2::ZERO, DVec2::X, DVec2::Y, DVec2::ONE] {
+			let corner = frame.transform.transform_point2(corner);
+			min = min.min(corner);
+			max = max.max(corner);
+		}
+	}
+	let extent = max - min;
+	if !pixel_size.is_finite() || pixel_size <= 0. || !extent.is_finite() {
+		return ImageFrame::empty();
+	}
+	pixel_size = pixel_size.max(extent.max_element() / MAX_PANORAMA_SIZE);
+
+	let width = (extent.x / pixel_size).ceil().max(1.) as u32;
+	let height = (extent.y / pixel_size).ceil().max(1.) as u32;
+	let output_to_layer = DAffine2::from_translation(min) * DAffine2::from_scale(DVec2::splat(pixel_size));
+
+	let feather = feather.clamp(0., 100.) / 100.;
+	let sources = frames
+		.iter()
+		.map(|frame| {
+			let size = DVec2::new(frame.image.width as f64, frame.image.height as f64);
+			let output_to_pixel = pixel_to_layer(frame).inverse() * output_to_layer;
+			let ramp = feather * size.min_element() / 2.;
+			(&frame.image, size, output_to_pixel, ramp)
+		})
+		.collect::<Vec<_>>();
+
+	let mut data = Vec::with_capacity(width as usize * height as usize);
+	for y in 0..height {
+		for x in 0..width {
+			let center = DVec2::new(x as f64 + 0.5, y as f64 + 0.5);
+
+			let mut sum = [0.; 4];
+			let mut total_weight = 0.;
+			for &(image, size, output_to_pixel, ramp) in &sources {
+				let position = output_to_pixel.transform_point2(center);
+				if position.x < 0. || position.y < 0. || position.x > size.x || position.y > size.y {
+					continue;
+				}
+
+				let edge_distance = position.min(size - position).min_element();
+				let weight = if ramp > 0. { (edge_distance / ramp).clamp(f64::EPSILON, 1.) } else { 1. };
+
+				let color = sample_bilinear(image, position);
+				for (channel, value) in sum.iter_mut().zip([color.r(), color.g(), color.b(), color.a()]) {
+					*channel += weight * value as f64;
+				}
+				total_weight += weight;
+			}
+
+			data.push(if total_weight > 0. {
+				let [r, g, b, a] = sum.map(|channel| (channel / total_weight) as f32);
+				Color::from_rgbaf32_unchecked(r, g, b, a)
+			} else {
+				Color::TRANSPARENT
+			});
+		}
+	}
+
+	ImageFrame {
+		image: Image {
+			width,
+			height,
+			data: data.into(),
+			base64_string: None,
+		},
+		transform: output_to_layer * DAffine2::from_scale(DVec2::new(width as f64, height as f64)),
+		alpha_blending: first.alpha_blending,
+	}
+}
+
+/// Bilinearly samples the (premultiplied) image at a position in pixel coordinates, where pixel centers lie at half-integer positions.
+pub(crate) fn sample_bilinear(image: &Image<Color>, position: DVec2) -> Color {
+	let max = DVec2::new(image.width as f64 - 1., image.height as f64 - 1.);
+	let position = (position - 0.5).clamp(DVec2::ZERO, max);
+	let (x0, y0) = (position.x.floor() as usize, position.y.floor() as usize);
+	let (x1, y1) = ((x0 + 1).min(image.width as usize - 1), (y0 + 1).min(image.height as usize - 1));
+	let fraction = position - position.floor();
+
+	let pixel = |x: usize, y: usize| image.data[y * image.width as usize + x];
+	let top = pixel(x0, y0).lerp(&pixel(x1, y0), fraction.x as f32);
+	let bottom = pixel(x0, y1).lerp(&pixel(x1, y1), fraction.x as f32);
+	top.lerp(&bottom, fraction.y as f32)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::value::CopiedNode;
+
+	fn solid_frame(color: Color, transform: DAffine2) -> ImageFrame<Color> {
+		ImageFrame {
+			image: Image::new(100, 100, color),
+			transform,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn blend_panorama_feathers_the_overlap() {
+		let left = solid_frame(Color::BLACK, DAffine2::from_scale(DVec2::splat(100.)));
+		let right = solid_frame(Color::WHITE, DAffine2::from_scale_angle_translation(DVec2::splat(100.), 0., DVec2::new(50., 0.)));
+
+		let panorama = BlendPanoramaNode { feather: CopiedNode(100.) }.eval(vec![left, right]);
+		assert_eq!((panorama.image.width, panorama.image.height), (150, 100));
+		assert_eq!(panorama.transform, DAffine2::from_scale(DVec2::new(150., 100.)));
+
+		let brightness = |x: usize| panorama.image.data[50 * panorama.image.width as usize + x].r();
+		assert!(brightness(10) < 1e-4, "only the left image covers the left side");
+		assert!(brightness(140) > 1. - 1e-4, "only the right image covers the right side");
+		assert!((brightness(75) - 0.5).abs() < 0.02, "both images are weighted equally halfway across the overlap");
+		assert!(brightness(60) < brightness(75) && brightness(75) < brightness(90), "the overlap fades from one image into the other");
+	}
+}
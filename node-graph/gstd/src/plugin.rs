@@ -0,0 +1,466 @@
+//! Nodes provided by third-party plugins, which are compiled to WebAssembly and loaded while the editor is running.
+//!
+//! A plugin consists of a WASM module and a [`PluginManifest`] declaring the nodes it exports along with their input and output types.
+//! The manifest is shipped as JSON inside the module, in a custom section named [`MANIFEST_SECTION`], so a plugin is a single file.
+//! Each evaluation of a plugin node runs in a fresh instance of the module with no imports, so plugins can't reach anything outside
+//! of the values passed to them, can't keep state between evaluations, and are stopped if they run for too long or use too much memory.
+//!
+//! Values cross the boundary serialized as JSON-encoded [`TaggedValue`]s. The module must export its `memory` along with these functions:
+//! - `alloc(length: i32) -> i32`, which reserves `length` bytes for the host to write the inputs into and returns their address.
+//! - One function per node, named by the node's `export` in the manifest, with the signature `(address: i32, length: i32) -> i64`.
+//!   It receives the array of input values and returns the address of its output value in the upper 32 bits and its length in the lower 32 bits.
+
+use crate::any::{Any, NodeError, TypeErasedBox};
+
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::{DocumentNode, DocumentNodeImplementation, NodeInput};
+use graph_craft::proto::{DynFuture, FutureAny, SharedNodeContainer};
+use graphene_core::raster::ImageFrame;
+use graphene_core::vector::VectorData;
+use graphene_core::{concrete, Color, GraphicGroup, Node, NodeIOTypes, ProtoNodeIdentifier, Type};
+
+use glam::DVec2;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use wasmi::{Config, Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+pub use graph_craft::proto::PLUGIN_NODE_PREFIX;
+
+/// The amount of fuel (roughly one unit per WASM instruction) an evaluation of a plugin node may use before it's stopped.
+const PLUGIN_FUEL_LIMIT: u64 = 5_000_000_000;
+
+/// The number of bytes of linear memory an instance of a plugin module may have.
+const PLUGIN_MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+/// The name of the custom section of a plugin's WASM module which holds its manifest.
+pub const MANIFEST_SECTION: &str = "graphite_plugin";
+
+/// A plugin's description of the nodes it provides, shipped alongside its WASM module as JSON.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PluginManifest {
+	pub name: String,
+	pub nodes: Vec<PluginNodeManifest>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PluginNodeManifest {
+	pub name: String,
+	/// The name of the function exported by the module which evaluates the node.
+	pub export: String,
+	#[serde(default)]
+	pub inputs: Vec<PluginInput>,
+	pub output: PluginValueType,
+}
+
+impl PluginNodeManifest {
+	/// The type signature of the proto node, whose first parameter is the name of the node followed by the inputs declared in the manifest.
+	pub fn node_io_types(&self) -> NodeIOTypes {
+		let parameter = |ty: Type| Type::Fn(Box::new(concrete!(())), Box::new(ty));
+		let parameters = core::iter::once(concrete!(String)).chain(self.inputs.iter().map(|input| input.ty.ty())).map(parameter).collect();
+		NodeIOTypes::new(concrete!(()), self.output.ty(), parameters)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PluginInput {
+	pub name: String,
+	#[serde(rename = "type")]
+	pub ty: PluginValueType,
+}
+
+/// The types of values which can be passed to and returned from plugin nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PluginValueType {
+	Bool,
+	U32,
+	F64,
+	String,
+	DVec2,
+	Color,
+	VecF64,
+	VecDVec2,
+	ImageFrame,
+	VectorData,
+	GraphicGroup,
+}
+
+impl PluginValueType {
+	pub fn ty(self) -> Type {
+		match self {
+			Self::Bool => concrete!(bool),
+			Self::U32 => concrete!(u32),
+			Self::F64 => concrete!(f64),
+			Self::String => concrete!(String),
+			Self::DVec2 => concrete!(DVec2),
+			Self::Color => concrete!(Color),
+			Self::VecF64 => concrete!(Vec<f64>),
+			Self::VecDVec2 => concrete!(Vec<DVec2>),
+			Self::ImageFrame => concrete!(ImageFrame<Color>),
+			Self::VectorData => concrete!(VectorData),
+			Self::GraphicGroup => concrete!(GraphicGroup),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PluginError {
+	InvalidName(String),
+	InvalidModule(String),
+	MissingExport(String),
+	UnknownNode(String),
+	NotLoaded(String),
+	Serialization(String),
+	Runtime(String),
+	WrongOutputType { expected: Type, found: Type },
+}
+
+impl core::fmt::Display for PluginError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::InvalidName(name) => write!(f, "Plugin and node names can't be empty or contain '/', but found \"{name}\""),
+			Self::InvalidModule(message) => write!(f, "Invalid plugin module: {message}"),
+			Self::MissingExport(name) => write!(f, "The plugin module doesn't export a suitable \"{name}\""),
+			Self::UnknownNode(name) => write!(f, "The plugin has no node named \"{name}\""),
+			Self::NotLoaded(name) => write!(f, "The plugin \"{name}\" isn't loaded"),
+			Self::Serialization(message) => write!(f, "Could not pass a value to or from the plugin: {message}"),
+			Self::Runtime(message) => write!(f, "The plugin failed: {message}"),
+			Self::WrongOutputType { expected, found } => write!(f, "The plugin returned a {found} where its manifest declares a {expected}"),
+		}
+	}
+}
+
+impl std::error::Error for PluginError {}
+
+/// Reads an unsigned LEB128 integer, which is how the WASM binary format stores lengths, advancing the position past it.
+fn read_length(bytes: &[u8], position: &mut usize) -> Option<usize> {
+	let mut value = 0_u64;
+	for shift in (0..35).step_by(7) {
+		let byte = *bytes.get(*position)?;
+		*position += 1;
+		value |= ((byte & 0x7F) as u64) << shift;
+		if byte & 0x80 == 0 {
+			return usize::try_from(value).ok();
+		}
+	}
+	None
+}
+
+/// Reads the manifest from the [`MANIFEST_SECTION`] custom section of a plugin's WASM module.
+pub fn embedded_manifest(wasm: &[u8]) -> Result<PluginManifest, PluginError> {
+	let invalid = || PluginError::InvalidModule("The module is truncated".to_string());
+
+	if wasm.get(..4) != Some(b"\0asm".as_slice()) {
+		return Err(PluginError::InvalidModule("This isn't a WebAssembly module".to_string()));
+	}
+	let mut position = 8;
+	while position < wasm.len() {
+		let id = wasm[position];
+		position += 1;
+		let length = read_length(wasm, &mut position).ok_or_else(invalid)?;
+		let section = wasm.get(position..position.checked_add(length).ok_or_else(invalid)?).ok_or_else(invalid)?;
+		position += length;
+		if id != 0 {
+			continue;
+		}
+
+		let mut name_end = 0;
+		let name_length = read_length(section, &mut name_end).ok_or_else(invalid)?;
+		let name = section.get(name_end..name_end.checked_add(name_length).ok_or_else(invalid)?).ok_or_else(invalid)?;
+		if name == MANIFEST_SECTION.as_bytes() {
+			return serde_json::from_slice(&section[name_end + name_length..]).map_err(|error| PluginError::Serialization(error.to_string()));
+		}
+	}
+
+	Err(PluginError::MissingExport(MANIFEST_SECTION.to_string()))
+}
+
+fn runtime_error(error: impl core::fmt::Display) -> PluginError {
+	PluginError::Runtime(error.to_string())
+}
+
+/// A compiled plugin module along with its manifest.
+pub struct Plugin {
+	pub manifest: PluginManifest,
+	engine: Engine,
+	module: Module,
+}
+
+impl Plugin {
+	pub fn new(manifest: PluginManifest, wasm: &[u8]) -> Result<Self, PluginError> {
+		let mut names = core::iter::once(&manifest.name).chain(manifest.nodes.iter().map(|node| &node.name));
+		if let Some(name) = names.find(|name| name.is_empty() || name.contains('/')) {
+			return Err(PluginError::InvalidName(name.clone()));
+		}
+
+		let mut config = Config::default();
+		config.consume_fuel(true);
+		let engine = Engine::new(&config);
+		let module = Module::new(&engine, wasm).map_err(|error| PluginError::InvalidModule(error.to_string()))?;
+		let plugin = Self { manifest, engine, module };
+
+		// Check up front that the module exports everything the manifest promises, rather than failing once a node is evaluated
+		let (store, instance) = plugin.instantiate()?;
+		for node in &plugin.manifest.nodes {
+			plugin.node_function(&store, &instance, &node.export)?;
+		}
+
+		Ok(plugin)
+	}
+
+	fn instantiate(&self) -> Result<(Store<StoreLimits>, Instance), PluginError> {
+		let limits = StoreLimitsBuilder::new().memory_size(PLUGIN_MEMORY_LIMIT).build();
+		let mut store = Store::new(&self.engine, limits);
+		store.limiter(|limits| limits);
+		store.add_fuel(PLUGIN_FUEL_LIMIT).map_err(runtime_error)?;
+
+		let instance = Linker::<StoreLimits>::new(&self.engine)
+			.instantiate(&mut store, &self.module)
+			.and_then(|instance| instance.start(&mut store))
+			.map_err(|error| PluginError::InvalidModule(error.to_string()))?;
+		if instance.get_memory(&store, "memory").is_none() {
+			return Err(PluginError::MissingExport("memory".to_string()));
+		}
+		instance.get_typed_func::<i32, i32>(&store, "alloc").map_err(|_| PluginError::MissingExport("alloc".to_string()))?;
+
+		Ok((store, instance))
+	}
+
+	fn node_function(&self, store: &Store<StoreLimits>, instance: &Instance, export: &str) -> Result<wasmi::TypedFunc<(i32, i32), i64>, PluginError> {
+		instance.get_typed_func::<(i32, i32), i64>(store, export).map_err(|_| PluginError::MissingExport(export.to_string()))
+	}
+
+	/// Evaluates one of the plugin's nodes in a fresh instance of its module.
+	pub fn call(&self, node: &str, inputs: &[TaggedValue]) -> Result<TaggedValue, PluginError> {
+		let node = self
+			.manifest
+			.nodes
+			.iter()
+			.find(|candidate| candidate.name == node)
+			.ok_or_else(|| PluginError::UnknownNode(node.to_string()))?;
+		let input = serde_json::to_vec(inputs).map_err(|error| PluginError::Serialization(error.to_string()))?;
+
+		let (mut store, instance) = self.instantiate()?;
+		let function = self.node_function(&store, &instance, &node.export)?;
+		let memory = instance.get_memory(&store, "memory").ok_or_else(|| PluginError::MissingExport("memory".to_string()))?;
+		let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc").map_err(|_| PluginError::MissingExport("alloc".to_string()))?;
+
+		let length = i32::try_from(input.len()).map_err(|_| PluginError::Serialization("The inputs are too large".to_string()))?;
+		let address = alloc.call(&mut store, length).map_err(runtime_error)?;
+		memory.write(&mut store, address as u32 as usize, &input).map_err(runtime_error)?;
+
+		let result = function.call(&mut store, (address, length)).map_err(runtime_error)? as u64;
+		let (address, length) = ((result >> 32) as usize, (result & 0xFFFF_FFFF) as usize);
+		// The output's location comes from the plugin, so it's checked before anything is allocated for it
+		if address.checked_add(length).map_or(true, |end| end > memory.data(&store).len()) {
+			return Err(PluginError::Runtime(format!("The output at {address} with a length of {length} is outside the plugin's memory")));
+		}
+		let mut output = vec![0; length];
+		memory.read(&store, address, &mut output).map_err(runtime_error)?;
+
+		let value: TaggedValue = serde_json::from_slice(&output).map_err(|error| PluginError::Serialization(error.to_string()))?;
+		let (expected, found) = (node.output.ty(), value.ty());
+		if expected != found {
+			return Err(PluginError::WrongOutputType { expected, found });
+		}
+		Ok(value)
+	}
+}
+
+static PLUGINS: OnceLock<RwLock<HashMap<String, Arc<Plugin>>>> = OnceLock::new();
+
+/// Counts the changes to the loaded plugins, so executors know when to update the node types they know about.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn plugins() -> &'static RwLock<HashMap<String, Arc<Plugin>>> {
+	PLUGINS.get_or_init(Default::default)
+}
+
+/// Compiles a plugin and makes its nodes available, replacing any loaded version of the plugin with the same name.
+/// Plugin nodes already in a graph run the new version of the plugin the next time they are evaluated.
+pub fn load_plugin(manifest: PluginManifest, wasm: &[u8]) -> Result<(), PluginError> {
+	let plugin = Plugin::new(manifest, wasm)?;
+	plugins()
+		.write()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.insert(plugin.manifest.name.clone(), Arc::new(plugin));
+	GENERATION.fetch_add(1, Ordering::Relaxed);
+	Ok(())
+}
+
+pub fn unload_plugin(name: &str) {
+	plugins().write().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(name);
+	GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn plugin(name: &str) -> Option<Arc<Plugin>> {
+	plugins().read().unwrap_or_else(|poisoned| poisoned.into_inner()).get(name).cloned()
+}
+
+/// Every loaded plugin, ordered by name.
+pub fn loaded_plugins() -> Vec<Arc<Plugin>> {
+	let mut plugins: Vec<_> = plugins().read().unwrap_or_else(|poisoned| poisoned.into_inner()).values().cloned().collect();
+	plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+	plugins
+}
+
+/// A number which changes whenever a plugin is loaded or unloaded.
+pub fn plugin_generation() -> u64 {
+	GENERATION.load(Ordering::Relaxed)
+}
+
+pub fn plugin_node_identifier(plugin: &str, node: &str) -> ProtoNodeIdentifier {
+	ProtoNodeIdentifier {
+		name: Cow::Owned(format!("{PLUGIN_NODE_PREFIX}{plugin}/{node}")),
+	}
+}
+
+/// The identifiers and type signatures of the nodes of every loaded plugin, to be registered with the type checker.
+pub fn plugin_node_types() -> Vec<(ProtoNodeIdentifier, NodeIOTypes)> {
+	let plugins = plugins().read().unwrap_or_else(|poisoned| poisoned.into_inner());
+	plugins
+		.values()
+		.flat_map(|plugin| {
+			plugin
+				.manifest
+				.nodes
+				.iter()
+				.map(|node| (plugin_node_identifier(&plugin.manifest.name, &node.name), node.node_io_types()))
+		})
+		.collect()
+}
+
+/// Creates a document node which evaluates a plugin node, with its inputs set to the default values of their types.
+pub fn plugin_document_node(plugin: &str, node: &PluginNodeManifest) -> DocumentNode {
+	let identifier = plugin_node_identifier(plugin, &node.name);
+	let name_input = NodeInput::value(TaggedValue::String(identifier.name.to_string()), false);
+	let inputs = node.inputs.iter().map(|input| NodeInput::value(TaggedValue::from_type(&input.ty.ty()), true));
+
+	DocumentNode {
+		name: node.name.clone(),
+		inputs: core::iter::once(name_input).chain(inputs).collect(),
+		manual_composition: Some(concrete!(())),
+		implementation: DocumentNodeImplementation::ProtoNode(identifier),
+		..Default::default()
+	}
+}
+
+/// Evaluates a node of a plugin, sandboxed in its own instance of the plugin's WASM module.
+/// The first parameter names the node as its proto node identifier, and the rest are the inputs passed to the plugin.
+pub struct WasmNode {
+	parameters: Vec<SharedNodeContainer>,
+}
+
+impl<'i> Node<'i, Any<'i>> for WasmNode {
+	type Output = FutureAny<'i>;
+
+	fn eval(&'i self, _input: Any<'i>) -> Self::Output {
+		Box::pin(async move {
+			let error = |message: String| NodeError::new("WasmNode", message);
+			let Some((name, inputs)) = self.parameters.split_first() else {
+				return Err(error("Plugin nodes need the name of the node as their first parameter".to_string()));
+			};
+
+			let name = name.eval(Box::new(())).await?;
+			let name = *dyn_any::downcast::<String>(name).map_err(error)?;
+			let Some((plugin_name, node_name)) = name.strip_prefix(PLUGIN_NODE_PREFIX).and_then(|name| name.split_once('/')) else {
+				return Err(error(format!("\"{name}\" doesn't name a plugin node")));
+			};
+
+			let mut values = Vec::with_capacity(inputs.len());
+			for input in inputs {
+				let value = input.eval(Box::new(())).await?;
+				values.push(TaggedValue::try_from_any(value).map_err(error)?);
+			}
+
+			let plugin = plugin(plugin_name).ok_or_else(|| error(PluginError::NotLoaded(plugin_name.to_string()).to_string()))?;
+			let output = plugin.call(node_name, &values).map_err(|e| error(e.to_string()))?;
+			Ok(output.to_any())
+		})
+	}
+}
+
+/// The constructor of every plugin node, which is registered with the type checker along with each plugin node's types.
+pub fn wasm_node_constructor(parameters: Vec<SharedNodeContainer>) -> DynFuture<'static, TypeErasedBox<'static>> {
+	Box::pin(async move { Box::new(WasmNode { parameters }) as TypeErasedBox<'static> })
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn manifest() -> PluginManifest {
+		serde_json::from_str(
+			r#"{
+				"name": "Example",
+				"nodes": [{ "name": "Scale", "export": "scale", "inputs": [{ "name": "Factor", "type": "F64" }], "output": "VecDVec2" }]
+			}"#,
+		)
+		.unwrap()
+	}
+
+	/// A plugin whose node returns a constant value, along with one returning an output outside of its memory.
+	const TEST_PLUGIN: &str = r#"
+		(module
+			(@custom "graphite_plugin" "{\"name\": \"Test\", \"nodes\": [{\"name\": \"Constant\", \"export\": \"constant\", \"output\": \"F64\"}, {\"name\": \"Overflow\", \"export\": \"overflow\", \"output\": \"F64\"}]}")
+			(memory (export "memory") 1)
+			(data (i32.const 16) "{\"F64\":2.5}")
+			(func (export "alloc") (param i32) (result i32) i32.const 1024)
+			(func (export "constant") (param i32 i32) (result i64) i64.const 0x10_0000000b)
+			(func (export "overflow") (param i32 i32) (result i64) i64.const 0xFFFF_FFFF)
+		)
+	"#;
+
+	#[test]
+	fn manifest_declares_node_types() {
+		let manifest = manifest();
+		let types = manifest.nodes[0].node_io_types();
+
+		assert_eq!(types.input, concrete!(()));
+		assert_eq!(types.output, concrete!(Vec<DVec2>));
+		assert_eq!(
+			types.parameters,
+			vec![
+				Type::Fn(Box::new(concrete!(())), Box::new(concrete!(String))),
+				Type::Fn(Box::new(concrete!(())), Box::new(concrete!(f64)))
+			]
+		);
+		assert_eq!(plugin_node_identifier(&manifest.name, &manifest.nodes[0].name).name, "plugin::Example/Scale");
+	}
+
+	#[test]
+	fn invalid_plugins_are_rejected() {
+		assert!(matches!(Plugin::new(manifest(), b"not a wasm module"), Err(PluginError::InvalidModule(_))));
+
+		let mut manifest = manifest();
+		manifest.name = "Example/Nested".to_string();
+		assert!(matches!(Plugin::new(manifest, &[]), Err(PluginError::InvalidName(_))));
+	}
+
+	#[test]
+	fn plugin_nodes_are_evaluated() {
+		let wasm = wat::parse_str(TEST_PLUGIN).unwrap();
+		let manifest = embedded_manifest(&wasm).unwrap();
+		assert_eq!(manifest.name, "Test");
+
+		let plugin = Plugin::new(manifest, &wasm).unwrap();
+		assert_eq!(plugin.call("Constant", &[]), Ok(TaggedValue::F64(2.5)));
+		assert!(matches!(plugin.call("Overflow", &[]), Err(PluginError::Runtime(_))));
+		assert!(matches!(plugin.call("Missing", &[]), Err(PluginError::UnknownNode(_))));
+	}
+
+	#[test]
+	fn plugin_memory_is_limited() {
+		let pages = PLUGIN_MEMORY_LIMIT / 65536 + 1;
+		let wasm = wat::parse_str(format!(r#"(module (memory (export "memory") {pages}) (func (export "alloc") (param i32) (result i32) i32.const 0))"#)).unwrap();
+		let manifest = PluginManifest {
+			name: "Large".to_string(),
+			nodes: Vec::new(),
+		};
+		assert!(matches!(Plugin::new(manifest, &wasm), Err(PluginError::InvalidModule(_))));
+
+		assert!(matches!(embedded_manifest(&wasm), Err(PluginError::MissingExport(_))));
+		assert!(matches!(embedded_manifest(&wasm[..wasm.len() - 1]), Err(PluginError::InvalidModule(_))));
+	}
+}
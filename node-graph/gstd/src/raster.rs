@@ -4,9 +4,10 @@ use dyn_any::{DynAny, StaticType};
 use graph_craft::imaginate_input::{ImaginateController, ImaginateMaskStartingFill, ImaginateSamplingMethod};
 use graph_craft::proto::DynFuture;
 use graphene_core::raster::bbox::{AxisAlignedBbox, Bbox};
+use graphene_core::raster::lanes::{AdjustPixels, PixelsMut};
 use graphene_core::raster::{
-	Alpha, Bitmap, BitmapMut, BlendMode, BlendNode, CellularDistanceFunction, CellularReturnType, DomainWarpType, FractalType, Image, ImageFrame, Linear, LinearChannel, Luminance, NoiseType, Pixel,
-	RGBMut, RedGreenBlue, Sample,
+	blend_pixels, Alpha, Bitmap, BitmapMut, BlendMode, BlendNode, CellularDistanceFunction, CellularReturnType, ChannelSource, DomainWarpType, FractalType, Image, ImageFrame, Linear, LinearChannel,
+	Luminance, NoiseType, Pixel, PixelDiffMode, RGBMut, RedGreenBlue, Sample,
 };
 use graphene_core::transform::{Footprint, Transform};
 use graphene_core::value::CopiedNode;
@@ -69,7 +70,7 @@ pub struct SampleNode<ImageFrame> {
 fn sample(footprint: Footprint, image_frame: ImageFrame<Color>) -> ImageFrame<Color> {
 	// resize the image using the image crate
 	let image = image_frame.image;
-	let data = bytemuck::cast_vec(image.data);
+	let data = bytemuck::cast_vec(image.data.into_vec());
 
 	let viewport_bounds = footprint.viewport_bounds_in_local_space();
 	let image_bounds = Bbox::from_transform(image_frame.transform).to_axis_aligned_bbox();
@@ -90,15 +91,14 @@ fn sample(footprint: Footprint, image_frame: ImageFrame<Color>) -> ImageFrame<Co
 	let offset_px = image_size.transform_vector2(offset).as_uvec2();
 	let cropped = dynamic_image.crop_imm(offset_px.x, offset_px.y, size_px.x, size_px.y);
 
-	let viewport_resolution_x = footprint.transform.transform_vector2(DVec2::X * size.x).length();
-	let viewport_resolution_y = footprint.transform.transform_vector2(DVec2::Y * size.y).length();
+	let target_resolution = footprint.raster_resolution(size);
 	let mut new_width = size_px.x;
 	let mut new_height = size_px.y;
 
 	// Only downscale the image for now
 	let resized = if new_width < image.width || new_height < image.height {
-		new_width = viewport_resolution_x as u32;
-		new_height = viewport_resolution_y as u32;
+		new_width = target_resolution.x;
+		new_height = target_resolution.y;
 		// TODO: choose filter based on quality requirements
 		cropped.resize_exact(new_width, new_height, image::imageops::Triangle)
 	} else {
@@ -106,11 +106,11 @@ fn sample(footprint: Footprint, image_frame: ImageFrame<Color>) -> ImageFrame<Co
 	};
 	let buffer = resized.to_rgba32f();
 	let buffer = buffer.into_raw();
-	let vec = bytemuck::cast_vec(buffer);
+	let vec: Vec<Color> = bytemuck::cast_vec(buffer);
 	let image = Image {
 		width: new_width,
 		height: new_height,
-		data: vec,
+		data: vec.into(),
 		base64_string: None,
 	};
 	// we need to adjust the offset if we truncate the offset calculation
@@ -140,6 +140,23 @@ where
 	image
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct AdjustImageNode<Adjustment> {
+	adjustment: Adjustment,
+}
+
+/// Adjusts every pixel of the image like the [`MapImageNode`] with the adjustment, but working out its parameters once and processing the pixels in lanes.
+#[node_macro::node_fn(AdjustImageNode)]
+fn adjust_image<Adjustment, Img: PixelsMut>(image: Img, adjustment: &'input Adjustment) -> Img
+where
+	Adjustment: AdjustPixels + 'input,
+{
+	let mut image = image;
+
+	adjustment.adjust_pixels(image.pixels_mut());
+	image
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct InsertChannelNode<P, S, Insertion, TargetChannel> {
 	insertion: Insertion,
@@ -188,6 +205,53 @@ where
 	image
 }
 
+/// Rebuilds each channel of the image from a channel of the image itself, a channel of another image, or a constant, for swapping channels or using one image's luminance as another's alpha.
+/// The other image is sampled where it overlaps the image in the layer, and channels are taken without the alpha multiplied in.
+#[derive(Debug, Clone, Copy)]
+pub struct ShuffleChannelsNode<Other, Red, Green, Blue, Alpha> {
+	other: Other,
+	red: Red,
+	green: Green,
+	blue: Blue,
+	alpha: Alpha,
+}
+
+#[node_macro::node_fn(ShuffleChannelsNode)]
+fn shuffle_channels(image: ImageFrame<Color>, other: ImageFrame<Color>, red: ChannelSource, green: ChannelSource, blue: ChannelSource, alpha: ChannelSource) -> ImageFrame<Color> {
+	let image_size = DVec2::new(image.image.width as f64, image.image.height as f64);
+	if image_size.x == 0. || image_size.y == 0. {
+		return image;
+	}
+
+	let image_to_layer = image.transform * DAffine2::from_scale(1. / image_size);
+	let area = image_to_layer.transform_vector2(DVec2::ONE);
+	let uses_other = [red, green, blue, alpha].iter().any(|source| {
+		matches!(
+			source,
+			ChannelSource::OtherRed | ChannelSource::OtherGreen | ChannelSource::OtherBlue | ChannelSource::OtherAlpha | ChannelSource::OtherLuminance
+		)
+	});
+
+	let mut result = image.clone();
+	for y in 0..image.image.height {
+		for x in 0..image.image.width {
+			let other_pixel = if uses_other {
+				let layer_point = image_to_layer.transform_point2(DVec2::new(x as f64 + 0.5, y as f64 + 0.5));
+				other.sample(layer_point, area).unwrap_or(Color::TRANSPARENT).to_unassociated_alpha()
+			} else {
+				Color::TRANSPARENT
+			};
+			let Some(pixel) = result.get_pixel_mut(x, y) else { continue };
+
+			let color = pixel.to_unassociated_alpha();
+			let [r, g, b, a] = [red, green, blue, alpha].map(|source| source.value(color, other_pixel).clamp(0., 1.));
+			*pixel = Color::from_rgbaf32_unchecked(r * a, g * a, b * a, a);
+		}
+	}
+
+	result
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct MaskImageNode<P, S, Stencil> {
 	stencil: Stencil,
@@ -240,6 +304,71 @@ fn mask_image<
 	image
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct PixelDiffNode<Reference, Mode, Threshold, Time> {
+	reference: Reference,
+	mode: Mode,
+	threshold: Threshold,
+	time: Time,
+}
+
+#[node_macro::node_fn(PixelDiffNode)]
+fn pixel_diff_node(image: ImageFrame<Color>, reference: ImageFrame<Color>, mode: PixelDiffMode, threshold: f64, time: f64) -> ImageFrame<Color> {
+	if mode == PixelDiffMode::Blink {
+		return if (time * 2.).floor().rem_euclid(2.) < 1. { image } else { reference };
+	}
+
+	let image_size = DVec2::new(image.image.width as f64, image.image.height as f64);
+	if image_size.x == 0. || image_size.y == 0. {
+		return image;
+	}
+
+	let threshold = (threshold / 100.).clamp(0., 1.) as f32;
+
+	// Transforms a pixel center of the image into layer space, where the reference is sampled
+	let image_to_layer = image.transform * DAffine2::from_scale(1. / image_size);
+	let area = image_to_layer.transform_vector2(DVec2::ONE);
+
+	let mut result = image.clone();
+	for y in 0..image.image.height {
+		for x in 0..image.image.width {
+			let layer_point = image_to_layer.transform_point2(DVec2::new(x as f64 + 0.5, y as f64 + 0.5));
+			let reference_pixel = reference.sample(layer_point, area).unwrap_or(Color::TRANSPARENT);
+			let Some(pixel) = result.get_pixel_mut(x, y) else { continue };
+
+			let difference = pixel_difference(*pixel, reference_pixel);
+			*pixel = match mode {
+				PixelDiffMode::Heatmap if difference <= threshold => Color::BLACK,
+				PixelDiffMode::Heatmap => {
+					let heat = if threshold < 1. { (difference - threshold) / (1. - threshold) } else { 1. };
+					if heat < 0.5 {
+						Color::BLACK.lerp(&Color::RED, heat * 2.)
+					} else {
+						Color::RED.lerp(&Color::YELLOW, heat * 2. - 1.)
+					}
+				}
+				PixelDiffMode::Highlight if difference > threshold => Color::MAGENTA,
+				PixelDiffMode::Highlight => {
+					let gray = Color::srgb_to_linear(Color::linear_to_srgb(pixel.to_unassociated_alpha().luminance_srgb()) * 0.5 + 0.25);
+					Color::from_rgbf32_unchecked(gray, gray, gray).apply_opacity(pixel.a())
+				}
+				PixelDiffMode::Blink => unreachable!(),
+			};
+		}
+	}
+
+	result
+}
+
+/// The largest difference of any channel between the two colors, measured in gamma space with unassociated alpha.
+fn pixel_difference(a: Color, b: Color) -> f32 {
+	let (a, b) = (a.to_unassociated_alpha().to_gamma_srgb(), b.to_unassociated_alpha().to_gamma_srgb());
+	// Fully transparent pixels are considered identical regardless of their color
+	let color_weight = a.a().max(b.a());
+	let color_difference = (a.r() - b.r()).abs().max((a.g() - b.g()).abs()).max((a.b() - b.b()).abs());
+	(color_difference * color_weight).max((a.a() - b.a()).abs())
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct BlendImageTupleNode<P, Fg, MapFn> {
 	map_fn: MapFn,
@@ -273,6 +402,33 @@ async fn blend_image_node<_P: Alpha + Pixel + Debug, Forground: Sample<Pixel = _
 	blend_new_image(foreground, background, &self.map_fn)
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct BlendColorImageNode<Background, BlendMode, Opacity, Linear> {
+	background: Background,
+	blend_mode: BlendMode,
+	opacity: Opacity,
+	linear: Linear,
+}
+
+/// Blends the image onto the background like the [`BlendImageNode`] with a [`BlendNode`], or with a [`GammaBlendNode`](graphene_core::raster::GammaBlendNode) unless `linear`.
+/// Images covering each other pixel for pixel, like those rendered at the same footprint, are blended in lanes without sampling the foreground.
+#[node_macro::node_fn(BlendColorImageNode)]
+async fn blend_color_image_node(foreground: ImageFrame<Color>, background: ImageFrame<Color>, blend_mode: BlendMode, opacity: f64, linear: bool) -> ImageFrame<Color> {
+	let aligned = foreground.transform == background.transform && foreground.image.width == background.image.width && foreground.image.height == background.image.height;
+	if aligned {
+		let mut background = background;
+		blend_pixels(&foreground.image.data, &mut background.image.data, blend_mode, opacity as f32 / 100., linear);
+		return background;
+	}
+
+	let (blend_mode, opacity) = (CopiedNode::new(blend_mode), CopiedNode::new(opacity));
+	if linear {
+		blend_new_image(foreground, background, &BlendNode::new(blend_mode, opacity))
+	} else {
+		blend_new_image(foreground, background, &graphene_core::raster::GammaBlendNode::new(blend_mode, opacity))
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct BlendReverseImageNode<P, Background, MapFn> {
 	background: Background,
@@ -733,8 +889,8 @@ fn mandelbrot_node(footprint: Footprint) -> ImageFrame<Color> {
 
 	let offset = (intersection.start - image_bounds.start).max(DVec2::ZERO);
 
-	let width = footprint.transform.transform_vector2(DVec2::X * size.x).length() as u32;
-	let height = footprint.transform.transform_vector2(DVec2::Y * size.y).length() as u32;
+	let resolution = footprint.raster_resolution(size);
+	let (width, height) = (resolution.x, resolution.y);
 
 	let mut data = Vec::with_capacity(width as usize * height as usize);
 	let max_iter = 255;
@@ -754,7 +910,7 @@ fn mandelbrot_node(footprint: Footprint) -> ImageFrame<Color> {
 		image: Image {
 			width,
 			height,
-			data,
+			data: data.into(),
 			..Default::default()
 		},
 		transform: DAffine2::from_translation(offset) * DAffine2::from_scale(size),
@@ -781,6 +937,33 @@ fn map_color(iter: usize, max_iter: usize) -> Color {
 
 #[cfg(test)]
 mod test {
+	use super::*;
+
+	use graphene_core::value::ClonedNode;
+
+	#[test]
+	fn shuffle_channels_uses_the_other_luminance_as_alpha() {
+		let frame = |color: Color| ImageFrame {
+			image: Image::new(2, 2, color),
+			transform: DAffine2::from_scale(DVec2::splat(10.)),
+			..Default::default()
+		};
+		let gray = Color::from_rgbaf32_unchecked(0.5, 0.5, 0.5, 1.);
+		let node = ShuffleChannelsNode {
+			other: ClonedNode(frame(gray)),
+			red: CopiedNode(ChannelSource::Blue),
+			green: CopiedNode(ChannelSource::Green),
+			blue: CopiedNode(ChannelSource::Red),
+			alpha: CopiedNode(ChannelSource::OtherLuminance),
+		};
+		let shuffled = node.eval(frame(Color::from_rgbaf32_unchecked(1., 0.5, 0., 1.)));
+
+		let pixel = shuffled.image.data[0];
+		let alpha = gray.luminance_srgb();
+		assert!((pixel.a() - alpha).abs() < 1e-6);
+		let straight = pixel.to_unassociated_alpha();
+		assert!((straight.r() - 0.).abs() < 1e-6 && (straight.g() - 0.5).abs() < 1e-6 && (straight.b() - 1.).abs() < 1e-6);
+	}
 
 	#[test]
 	fn load_image() {
@@ -0,0 +1,374 @@
+//! Regression tests which evaluate a graph and compare its output against a golden snapshot stored next to the tests.
+//!
+//! Outputs are reduced to hashes so the golden files stay small: raster images get one hash per tile, vector data one hash per subpath,
+//! and any other value a single hash of its serialized form. Coordinates and colors are quantized before hashing so that the
+//! last bits of floating point results don't make a snapshot flaky.
+//!
+//! Running the tests with the `GRAPHENE_UPDATE_SNAPSHOTS` environment variable set writes the current outputs as the new golden files.
+
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeNetwork;
+use graphene_core::raster::{Color, Image};
+use graphene_core::vector::VectorData;
+
+use glam::DAffine2;
+use std::path::{Path, PathBuf};
+
+/// The environment variable which makes [`SnapshotHarness`] write golden files instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_VARIABLE: &str = "GRAPHENE_UPDATE_SNAPSHOTS";
+
+/// The width and height in pixels of the tiles which raster output is split into before hashing.
+pub const SNAPSHOT_TILE_SIZE: u32 = 64;
+
+/// Evaluates a node network which takes no input. Implemented by the executors, since the node registry isn't part of this crate.
+pub trait GraphEvaluator {
+	fn evaluate(&self, network: NodeNetwork) -> Result<TaggedValue, String>;
+}
+
+impl<F: Fn(NodeNetwork) -> Result<TaggedValue, String>> GraphEvaluator for F {
+	fn evaluate(&self, network: NodeNetwork) -> Result<TaggedValue, String> {
+		self(network)
+	}
+}
+
+/// The hashed output of a graph, which is what gets stored in a golden file.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Snapshot {
+	/// The type of the value the graph evaluated to.
+	pub output_type: String,
+	/// The width and height of raster output, which the tiles are laid out in row by row.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub size: Option<(u32, u32)>,
+	/// The hash of everything besides the tiles or subpaths, such as transforms and styles.
+	pub header: String,
+	/// One hash per tile for raster output, one per subpath for vector output, and otherwise a single hash of the whole value.
+	pub hashes: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnapshotError {
+	Deserialization(String),
+	Evaluation(String),
+	/// Evaluating the same graph twice gave different outputs.
+	Nondeterministic {
+		first: Snapshot,
+		second: Snapshot,
+	},
+	MissingGolden(PathBuf),
+	Mismatch {
+		name: String,
+		expected: Snapshot,
+		actual: Snapshot,
+	},
+	Io(String),
+}
+
+impl core::fmt::Display for SnapshotError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Deserialization(error) => write!(f, "The graph could not be deserialized: {error}"),
+			Self::Evaluation(error) => write!(f, "The graph could not be evaluated: {error}"),
+			Self::Nondeterministic { first, second } => write!(f, "Evaluating the graph twice gave different outputs:\n{first:#?}\n{second:#?}"),
+			Self::MissingGolden(path) => write!(f, "There is no golden file at {}, run the test with {UPDATE_SNAPSHOTS_VARIABLE}=1 to create it", path.display()),
+			Self::Mismatch { name, expected, actual } => {
+				write!(f, "The output of `{name}` doesn't match its golden file")?;
+				if expected.output_type != actual.output_type || expected.size != actual.size {
+					write!(
+						f,
+						"\nexpected a {} of size {:?} but got a {} of size {:?}",
+						expected.output_type, expected.size, actual.output_type, actual.size
+					)?;
+				}
+				if expected.header != actual.header {
+					write!(f, "\nthe transform or style differs")?;
+				}
+				let differing = (0..expected.hashes.len().max(actual.hashes.len()))
+					.filter(|&index| expected.hashes.get(index) != actual.hashes.get(index))
+					.collect::<Vec<_>>();
+				if !differing.is_empty() {
+					write!(f, "\n{} of {} tiles or subpaths differ, starting at index {}", differing.len(), actual.hashes.len(), differing[0])?;
+				}
+				write!(f, "\nrun the test with {UPDATE_SNAPSHOTS_VARIABLE}=1 if the change is intended")
+			}
+			Self::Io(error) => write!(f, "The golden file could not be accessed: {error}"),
+		}
+	}
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// The 64 bit FNV-1a hash, which unlike the standard library's hasher is guaranteed to stay the same across platforms and Rust versions.
+#[derive(Clone, Copy)]
+struct StableHasher(u64);
+
+impl StableHasher {
+	fn new() -> Self {
+		Self(0xcbf29ce484222325)
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		for &byte in bytes {
+			self.0 ^= byte as u64;
+			self.0 = self.0.wrapping_mul(0x100000001b3);
+		}
+	}
+
+	fn write_u32(&mut self, value: u32) {
+		self.write(&value.to_le_bytes());
+	}
+
+	/// Rounds to a millionth so that rounding errors in the last bits don't change the hash.
+	fn write_f64(&mut self, value: f64) {
+		let quantized = (value * 1e6).round() as i64;
+		self.write(&quantized.to_le_bytes());
+	}
+
+	fn write_transform(&mut self, transform: DAffine2) {
+		for value in transform.to_cols_array() {
+			self.write_f64(value);
+		}
+	}
+
+	fn finish(&self) -> String {
+		format!("{:016x}", self.0)
+	}
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+	let mut hasher = StableHasher::new();
+	hasher.write(bytes);
+	hasher.finish()
+}
+
+fn image_tile_hashes(image: &Image<Color>) -> Vec<String> {
+	let mut hashes = Vec::new();
+	for tile_y in (0..image.height).step_by(SNAPSHOT_TILE_SIZE as usize) {
+		for tile_x in (0..image.width).step_by(SNAPSHOT_TILE_SIZE as usize) {
+			let mut hasher = StableHasher::new();
+			for y in tile_y..(tile_y + SNAPSHOT_TILE_SIZE).min(image.height) {
+				for x in tile_x..(tile_x + SNAPSHOT_TILE_SIZE).min(image.width) {
+					// Quantizing to 8 bits per channel compares what would actually be displayed
+					hasher.write(&image.data[(y * image.width + x) as usize].to_rgba8_srgb());
+				}
+			}
+			hashes.push(hasher.finish());
+		}
+	}
+	hashes
+}
+
+fn vector_snapshot(vector_data: &VectorData) -> (String, Vec<String>) {
+	let mut header = StableHasher::new();
+	header.write_transform(vector_data.transform);
+	// The derived debug output of the style is deterministic and saves hashing every field by hand
+	header.write(format!("{:?}{:?}", vector_data.style, vector_data.alpha_blending).as_bytes());
+
+	let subpaths = vector_data
+		.stroke_bezier_paths()
+		.map(|subpath| {
+			let mut hasher = StableHasher::new();
+			hasher.write(&[subpath.closed() as u8]);
+			for group in subpath.manipulator_groups() {
+				for position in [Some(group.anchor), group.in_handle, group.out_handle] {
+					match position {
+						Some(position) => {
+							hasher.write(&[1]);
+							hasher.write_f64(position.x);
+							hasher.write_f64(position.y);
+						}
+						None => hasher.write(&[0]),
+					}
+				}
+			}
+			hasher.finish()
+		})
+		.collect();
+
+	(header.finish(), subpaths)
+}
+
+impl Snapshot {
+	/// Hashes the output of a graph.
+	pub fn of(value: &TaggedValue) -> Self {
+		let output_type = value.ty().to_string();
+		let image_snapshot = |image: &Image<Color>, transform: DAffine2| {
+			let mut header = StableHasher::new();
+			header.write_u32(image.width);
+			header.write_u32(image.height);
+			header.write_transform(transform);
+			(Some((image.width, image.height)), header.finish(), image_tile_hashes(image))
+		};
+
+		let (size, header, hashes) = match value {
+			TaggedValue::Image(image) => image_snapshot(image, DAffine2::IDENTITY),
+			TaggedValue::ImageFrame(image_frame) => image_snapshot(&image_frame.image, image_frame.transform),
+			TaggedValue::VectorData(vector_data) => {
+				let (header, hashes) = vector_snapshot(vector_data);
+				(None, header, hashes)
+			}
+			_ => {
+				let serialized = serde_json::to_string(value).unwrap_or_else(|error| format!("unserializable: {error}"));
+				(None, hash_bytes(output_type.as_bytes()), vec![hash_bytes(serialized.as_bytes())])
+			}
+		};
+
+		Self { output_type, size, header, hashes }
+	}
+}
+
+/// Evaluates graphs and compares their outputs against the golden files in a directory.
+pub struct SnapshotHarness<E: GraphEvaluator> {
+	evaluator: E,
+	golden_directory: PathBuf,
+	update: bool,
+}
+
+impl<E: GraphEvaluator> SnapshotHarness<E> {
+	pub fn new(evaluator: E, golden_directory: impl Into<PathBuf>) -> Self {
+		Self {
+			evaluator,
+			golden_directory: golden_directory.into(),
+			update: std::env::var_os(UPDATE_SNAPSHOTS_VARIABLE).is_some(),
+		}
+	}
+
+	/// Overrides whether golden files get written, which otherwise depends on the environment variable.
+	pub fn with_update(mut self, update: bool) -> Self {
+		self.update = update;
+		self
+	}
+
+	pub fn golden_path(&self, name: &str) -> PathBuf {
+		self.golden_directory.join(format!("{name}.json"))
+	}
+
+	/// Evaluates the network twice, each time from scratch, and hashes the output as long as both evaluations agree.
+	pub fn snapshot(&self, network: &NodeNetwork) -> Result<Snapshot, SnapshotError> {
+		let evaluate = || self.evaluator.evaluate(network.clone()).map(|output| Snapshot::of(&output)).map_err(SnapshotError::Evaluation);
+		let first = evaluate()?;
+		let second = evaluate()?;
+		if first != second {
+			return Err(SnapshotError::Nondeterministic { first, second });
+		}
+		Ok(first)
+	}
+
+	/// Checks the output of the network against the golden file called `name`.
+	pub fn check_network(&self, name: &str, network: &NodeNetwork) -> Result<(), SnapshotError> {
+		let actual = self.snapshot(network)?;
+		let path = self.golden_path(name);
+
+		if self.update {
+			return write_golden(&path, &actual);
+		}
+
+		let expected = read_golden(&path)?;
+		if expected != actual {
+			return Err(SnapshotError::Mismatch {
+				name: name.to_string(),
+				expected,
+				actual,
+			});
+		}
+		Ok(())
+	}
+
+	/// Checks the output of a network serialized as JSON, the same way as documents store it, against the golden file called `name`.
+	pub fn check(&self, name: &str, serialized_network: &str) -> Result<(), SnapshotError> {
+		let network = serde_json::from_str(serialized_network).map_err(|error| SnapshotError::Deserialization(error.to_string()))?;
+		self.check_network(name, &network)
+	}
+
+	/// Like [`SnapshotHarness::check`], but panics with a description of the difference, for use in tests.
+	#[track_caller]
+	pub fn assert_snapshot(&self, name: &str, serialized_network: &str) {
+		if let Err(error) = self.check(name, serialized_network) {
+			panic!("{error}");
+		}
+	}
+
+	/// Like [`SnapshotHarness::check_network`], but panics with a description of the difference, for use in tests.
+	#[track_caller]
+	pub fn assert_network_snapshot(&self, name: &str, network: &NodeNetwork) {
+		if let Err(error) = self.check_network(name, network) {
+			panic!("{error}");
+		}
+	}
+}
+
+fn read_golden(path: &Path) -> Result<Snapshot, SnapshotError> {
+	let contents = match std::fs::read_to_string(path) {
+		Ok(contents) => contents,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Err(SnapshotError::MissingGolden(path.to_path_buf())),
+		Err(error) => return Err(SnapshotError::Io(error.to_string())),
+	};
+	serde_json::from_str(&contents).map_err(|error| SnapshotError::Io(format!("{}: {error}", path.display())))
+}
+
+fn write_golden(path: &Path, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+	if let Some(directory) = path.parent() {
+		std::fs::create_dir_all(directory).map_err(|error| SnapshotError::Io(error.to_string()))?;
+	}
+	let mut contents = serde_json::to_string_pretty(snapshot).map_err(|error| SnapshotError::Io(error.to_string()))?;
+	contents.push('\n');
+	std::fs::write(path, contents).map_err(|error| SnapshotError::Io(error.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use std::cell::Cell;
+
+	fn constant(value: TaggedValue) -> impl Fn(NodeNetwork) -> Result<TaggedValue, String> {
+		move |_| Ok(value.clone())
+	}
+
+	#[test]
+	fn image_tiles() {
+		let mut image = Image::new(SNAPSHOT_TILE_SIZE + 1, 2, Color::BLACK);
+		let snapshot = Snapshot::of(&TaggedValue::Image(image.clone()));
+		assert_eq!(snapshot.size, Some((SNAPSHOT_TILE_SIZE + 1, 2)));
+		assert_eq!(snapshot.hashes.len(), 2);
+
+		// Changing a pixel in the second tile leaves the first one's hash alone
+		image.data[SNAPSHOT_TILE_SIZE as usize] = Color::WHITE;
+		let changed = Snapshot::of(&TaggedValue::Image(image.clone()));
+		assert_eq!(changed.hashes[0], snapshot.hashes[0]);
+		assert_ne!(changed.hashes[1], snapshot.hashes[1]);
+
+		// But a difference too small to be displayed doesn't count
+		image.data[SNAPSHOT_TILE_SIZE as usize] = Color::from_rgbaf32_unchecked(1., 1., 0.99999, 1.);
+		assert_eq!(Snapshot::of(&TaggedValue::Image(image)), changed);
+	}
+
+	#[test]
+	fn golden_files() {
+		let directory = std::env::temp_dir().join(format!("graphene-snapshot-test-{}", std::process::id()));
+		let network = NodeNetwork::default();
+
+		let harness = SnapshotHarness::new(constant(TaggedValue::F64(1.)), &directory).with_update(false);
+		assert_eq!(harness.check_network("number", &network), Err(SnapshotError::MissingGolden(harness.golden_path("number"))));
+		harness.with_update(true).check_network("number", &network).unwrap();
+
+		let harness = SnapshotHarness::new(constant(TaggedValue::F64(1.)), &directory).with_update(false);
+		harness.assert_network_snapshot("number", &network);
+		let harness = SnapshotHarness::new(constant(TaggedValue::F64(2.)), &directory).with_update(false);
+		assert!(matches!(harness.check_network("number", &network), Err(SnapshotError::Mismatch { .. })));
+
+		std::fs::remove_dir_all(directory).unwrap();
+	}
+
+	#[test]
+	fn nondeterministic_output() {
+		let count = Cell::new(0.);
+		let harness = SnapshotHarness::new(
+			|_: NodeNetwork| {
+				count.set(count.get() + 1.);
+				Ok(TaggedValue::F64(count.get()))
+			},
+			"",
+		);
+		assert!(matches!(harness.snapshot(&NodeNetwork::default()), Err(SnapshotError::Nondeterministic { .. })));
+	}
+}
@@ -0,0 +1,248 @@
+use crate::panorama::{pixel_to_layer, sample_bilinear};
+
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::{Color, Node};
+
+use glam::DVec2;
+
+/// Maps each pixel of the image through the function given its color and center in the layer space of the image.
+/// Measuring effects in layer space rather than in pixels keeps them the same size however finely the image has been sampled for the current view.
+fn map_pixels_in_layer_space(image_frame: ImageFrame<Color>, map: impl Fn(Color, DVec2) -> Color) -> ImageFrame<Color> {
+	let image = &image_frame.image;
+	let to_layer = pixel_to_layer(&image_frame);
+
+	let mut data = Vec::with_capacity(image.data.len());
+	for y in 0..image.height {
+		for x in 0..image.width {
+			let color = image.data[(y * image.width + x) as usize];
+			data.push(map(color, to_layer.transform_point2(DVec2::new(x as f64 + 0.5, y as f64 + 0.5))));
+		}
+	}
+
+	ImageFrame {
+		image: Image {
+			width: image.width,
+			height: image.height,
+			data: data.into(),
+			base64_string: None,
+		},
+		..image_frame
+	}
+}
+
+/// Splits the red and blue channels apart in opposite directions, like the colored fringes left by a lens which doesn't focus every wavelength at the same place.
+/// The amount is the distance (in document pixels) each of the two channels is moved, along the angle (in degrees clockwise from the right) for red and the opposite way for blue.
+pub struct ChromaticAberrationNode<Amount, Angle> {
+	amount: Amount,
+	angle: Angle,
+}
+
+#[node_macro::node_fn(ChromaticAberrationNode)]
+fn chromatic_aberration(image_frame: ImageFrame<Color>, amount: f64, angle: f64) -> ImageFrame<Color> {
+	let image = &image_frame.image;
+	if image.width == 0 || image.height == 0 || amount == 0. {
+		return image_frame;
+	}
+
+	let to_pixel = pixel_to_layer(&image_frame).inverse();
+	let offset = to_pixel.transform_vector2(DVec2::from_angle(angle.to_radians()) * amount);
+	if !offset.is_finite() {
+		return image_frame;
+	}
+
+	let source = image.clone();
+	let size = DVec2::new(image.width as f64, image.height as f64);
+	let sample = |position: DVec2| {
+		let inside = position.cmpge(DVec2::ZERO).all() && position.cmple(size).all();
+		if inside {
+			sample_bilinear(&source, position)
+		} else {
+			Color::TRANSPARENT
+		}
+	};
+
+	map_pixels_in_layer_space(image_frame, |green, position| {
+		let position = to_pixel.transform_point2(position);
+		let (red, blue) = (sample(position - offset), sample(position + offset));
+
+		// Each channel keeps the coverage it was moved with, so the fringes stay visible past the edges of the image
+		let alpha = red.a().max(green.a()).max(blue.a());
+		Color::from_rgbaf32_unchecked(red.r(), green.g(), blue.b(), alpha)
+	})
+}
+
+/// Darkens the edges of the image towards its corners for positive amounts, or lightens them for negative amounts.
+/// The vignette starts at the size (as a percentage of the distance from the center to the edges) and fades in over the feather (as a further percentage of that distance).
+/// Its shape follows the proportions of the image at a roundness of 0%, or is a circle at a roundness of 100%.
+pub struct VignetteNode<Amount, Size, Feather, Roundness> {
+	amount: Amount,
+	size: Size,
+	feather: Feather,
+	roundness: Roundness,
+}
+
+#[node_macro::node_fn(VignetteNode)]
+fn vignette(image_frame: ImageFrame<Color>, amount: f64, size: f64, feather: f64, roundness: f64) -> ImageFrame<Color> {
+	if image_frame.image.width == 0 || image_frame.image.height == 0 || amount == 0. {
+		return image_frame;
+	}
+
+	let to_unit = image_frame.transform.inverse();
+	let extent = DVec2::new(image_frame.transform.matrix2.x_axis.length(), image_frame.transform.matrix2.y_axis.length());
+	if !to_unit.is_finite() || extent.min_element() <= 0. {
+		return image_frame;
+	}
+	let circle_scale = extent / extent.min_element();
+
+	let amount = amount.clamp(-100., 100.) as f32 / 100.;
+	let start = size.max(0.) / 100.;
+	let feather = feather.max(0.) / 100.;
+	let roundness = roundness.clamp(0., 100.) / 100.;
+
+	map_pixels_in_layer_space(image_frame, |color, position| {
+		let offset = (to_unit.transform_point2(position) - 0.5) * 2.;
+		let distance = (offset * DVec2::ONE.lerp(circle_scale, roundness)).length();
+		let strength = if feather > 0. {
+			let t = ((distance - start) / feather).clamp(0., 1.);
+			t * t * (3. - 2. * t)
+		} else if distance >= start {
+			1.
+		} else {
+			0.
+		};
+		let strength = strength as f32;
+
+		let alpha = color.a();
+		if amount > 0. {
+			color.map_rgb(|channel| channel * (1. - amount * strength))
+		} else {
+			color.map_rgb(|channel| channel + (alpha - channel) * -amount * strength)
+		}
+	})
+}
+
+/// Adds the speckled texture of photographic film, as grains of random brightness.
+/// The amount is the strength of the grain (as a percentage), the size is the width of a grain (in document pixels), and the seed picks a different pattern of grains.
+pub struct GrainNode<Amount, Size, Seed> {
+	amount: Amount,
+	size: Size,
+	seed: Seed,
+}
+
+#[node_macro::node_fn(GrainNode)]
+fn grain(image_frame: ImageFrame<Color>, amount: f64, size: f64, seed: u32) -> ImageFrame<Color> {
+	if image_frame.image.width == 0 || image_frame.image.height == 0 || amount == 0. || size <= 0. {
+		return image_frame;
+	}
+
+	let amount = amount.clamp(0., 100.) as f32 / 100.;
+	map_pixels_in_layer_space(image_frame, |color, position| {
+		// The grains are laid out in the document rather than on the pixels, so they stay in place as the image is resampled
+		let cell = position / size - 0.5;
+		let corner = cell.floor();
+		let fraction = cell - corner;
+		let (x, y) = (corner.x as i64, corner.y as i64);
+		let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+		let top = lerp(grain_value(seed, x, y), grain_value(seed, x + 1, y), fraction.x);
+		let bottom = lerp(grain_value(seed, x, y + 1), grain_value(seed, x + 1, y + 1), fraction.x);
+		let noise = lerp(top, bottom, fraction.y) as f32;
+
+		let alpha = color.a();
+		color.map_rgb(|channel| (channel + noise * amount * alpha).clamp(0., alpha))
+	})
+}
+
+/// A random value between -1 and 1 for the grain in the cell, weighted towards 0 like the sum of two dice.
+fn grain_value(seed: u32, x: i64, y: i64) -> f64 {
+	let mut hash = (seed as u64) ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+	// The finalizer of SplitMix64, which mixes every bit of the input into every bit of the output
+	hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+	hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+	hash ^= hash >> 31;
+
+	let first = (hash & 0xFFFF_FFFF) as f64 / u32::MAX as f64;
+	let second = (hash >> 32) as f64 / u32::MAX as f64;
+	first + second - 1.
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use glam::DAffine2;
+	use graphene_core::value::CopiedNode;
+
+	fn gray_frame(transform: DAffine2) -> ImageFrame<Color> {
+		ImageFrame {
+			image: Image::new(20, 20, Color::from_rgbaf32_unchecked(0.5, 0.5, 0.5, 1.)),
+			transform,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn chromatic_aberration_splits_the_edges() {
+		let mut frame = gray_frame(DAffine2::from_scale(DVec2::splat(20.)));
+		for y in 0..20 {
+			for x in 10..20 {
+				frame.image.data[y * 20 + x] = Color::WHITE;
+			}
+		}
+
+		let node = ChromaticAberrationNode {
+			amount: CopiedNode(2.),
+			angle: CopiedNode(0.),
+		};
+		let split = node.eval(frame.clone());
+
+		// Red is moved to the right and blue to the left, so blue reaches the bright half before red does, leaving a blue fringe along the edge
+		for x in [9, 10] {
+			let pixel = split.image.data[10 * 20 + x];
+			assert!(pixel.b() > pixel.r());
+		}
+		assert_eq!(split.image.data[10 * 20 + 15], Color::WHITE);
+		assert_eq!(split.image.data[10 * 20 + 4], frame.image.data[10 * 20 + 4]);
+	}
+
+	#[test]
+	fn vignette_darkens_the_corners() {
+		let frame = gray_frame(DAffine2::from_scale(DVec2::new(400., 200.)));
+		let node = VignetteNode {
+			amount: CopiedNode(100.),
+			size: CopiedNode(50.),
+			feather: CopiedNode(50.),
+			roundness: CopiedNode(0.),
+		};
+		let vignetted = node.eval(frame.clone());
+
+		assert_eq!(vignetted.image.data[10 * 20 + 10], frame.image.data[10 * 20 + 10]);
+		assert!(vignetted.image.data[0].r() < 0.05);
+		assert_eq!(vignetted.image.data[0].a(), 1.);
+	}
+
+	#[test]
+	fn grain_follows_the_document() {
+		let node = GrainNode {
+			amount: CopiedNode(50.),
+			size: CopiedNode(4.),
+			seed: CopiedNode(7),
+		};
+		let grained = node.eval(gray_frame(DAffine2::from_scale(DVec2::splat(40.))));
+		assert!(grained.image.data.iter().any(|pixel| (pixel.r() - 0.5).abs() > 0.01));
+		assert!(grained.image.data.iter().all(|pixel| pixel.r() == pixel.g() && pixel.g() == pixel.b()));
+
+		// The same part of the document gets the same grain when the image is sampled at twice the resolution
+		let mut fine = gray_frame(DAffine2::from_scale(DVec2::splat(40.)));
+		fine.image = Image::new(40, 40, Color::from_rgbaf32_unchecked(0.5, 0.5, 0.5, 1.));
+		let fine = GrainNode {
+			amount: CopiedNode(50.),
+			size: CopiedNode(4.),
+			seed: CopiedNode(7),
+		}
+		.eval(fine);
+		// The center of pixel (3, 3) in the coarse image lies on the corner between four pixels of the fine one, which average to it
+		let coarse_pixel = grained.image.data[3 * 20 + 3].r();
+		let fine_pixels = [(6, 6), (6, 7), (7, 6), (7, 7)].map(|(x, y)| fine.image.data[y * 40 + x].r());
+		assert!((coarse_pixel - fine_pixels.iter().sum::<f32>() / 4.).abs() < 0.02);
+	}
+}
@@ -0,0 +1,136 @@
+//! Nodes which check the values flowing through a graph against expectations, so example documents can test themselves.
+//!
+//! Each node passes its input through unchanged while the check holds. Once it fails, the node fails its evaluation with a message
+//! describing the mismatch, which the editor shows on the node in the graph and the command line interface reports with a failing exit code.
+
+use crate::any::NodeError;
+use graphene_core::raster::Color;
+use graphene_core::renderer::GraphicElementRendered;
+use graphene_core::Node;
+
+use glam::{DAffine2, DVec2};
+
+/// Values which [`AssertEqualNode`] can compare, with the tolerance allowing for rounding errors in numbers and ignored for anything else.
+pub trait AssertEq: core::fmt::Debug {
+	fn assert_eq(&self, expected: &Self, tolerance: f64) -> bool;
+}
+
+impl AssertEq for f64 {
+	fn assert_eq(&self, expected: &Self, tolerance: f64) -> bool {
+		(self - expected).abs() <= tolerance
+	}
+}
+
+impl AssertEq for u32 {
+	fn assert_eq(&self, expected: &Self, tolerance: f64) -> bool {
+		(*self as f64 - *expected as f64).abs() <= tolerance
+	}
+}
+
+impl AssertEq for bool {
+	fn assert_eq(&self, expected: &Self, _tolerance: f64) -> bool {
+		self == expected
+	}
+}
+
+impl AssertEq for String {
+	fn assert_eq(&self, expected: &Self, _tolerance: f64) -> bool {
+		self == expected
+	}
+}
+
+impl AssertEq for DVec2 {
+	fn assert_eq(&self, expected: &Self, tolerance: f64) -> bool {
+		self.abs_diff_eq(*expected, tolerance)
+	}
+}
+
+impl AssertEq for Color {
+	fn assert_eq(&self, expected: &Self, tolerance: f64) -> bool {
+		let (value, expected) = (self.components(), expected.components());
+		let differences = [value.0 - expected.0, value.1 - expected.1, value.2 - expected.2, value.3 - expected.3];
+		differences.iter().all(|difference| difference.abs() as f64 <= tolerance)
+	}
+}
+
+pub struct AssertEqualNode<Expected, Tolerance> {
+	expected: Expected,
+	tolerance: Tolerance,
+}
+
+#[node_macro::node_fn(AssertEqualNode)]
+async fn assert_equal<T: AssertEq>(value: T, expected: T, tolerance: f64) -> Result<T, NodeError> {
+	if !value.assert_eq(&expected, tolerance) {
+		let within = if tolerance > 0. { format!(" within {tolerance}") } else { String::new() };
+		return Err(NodeError::new("AssertEqualNode", format!("Assertion failed: expected {expected:?}{within} but got {value:?}")));
+	}
+	Ok(value)
+}
+
+pub struct AssertBoundsNode<Min, Max, Tolerance> {
+	min: Min,
+	max: Max,
+	tolerance: Tolerance,
+}
+
+/// Checks that the corners of the bounding box of the rendered graphic, in the coordinates of the graphic's parent, are within the tolerance of `min` and `max`.
+#[node_macro::node_fn(AssertBoundsNode)]
+async fn assert_bounds<T: GraphicElementRendered>(graphic: T, min: DVec2, max: DVec2, tolerance: f64) -> Result<T, NodeError> {
+	let Some([found_min, found_max]) = graphic.bounding_box(DAffine2::IDENTITY) else {
+		return Err(NodeError::new(
+			"AssertBoundsNode",
+			format!("Assertion failed: expected bounds from {min} to {max} but the graphic is empty"),
+		));
+	};
+	if !found_min.abs_diff_eq(min, tolerance) || !found_max.abs_diff_eq(max, tolerance) {
+		return Err(NodeError::new(
+			"AssertBoundsNode",
+			format!("Assertion failed: expected bounds from {min} to {max} but got bounds from {found_min} to {found_max}"),
+		));
+	}
+	Ok(graphic)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::any::{FallibleDynAnyNode, FutureWrapperNode, IntoTypeErasedNode};
+	use graphene_core::value::ClonedNode;
+	use graphene_core::vector::VectorData;
+
+	use futures::executor::block_on;
+
+	#[test]
+	fn assert_equal_passes_within_tolerance() {
+		let node = AssertEqualNode::new(FutureWrapperNode::new(ClonedNode::new(1.)), FutureWrapperNode::new(ClonedNode::new(0.01)));
+		let type_erased = FallibleDynAnyNode::<f64, f64, _>::new(node).into_type_erased();
+		let output = block_on(type_erased.eval(Box::new(1.005))).unwrap();
+		assert_eq!(*dyn_any::downcast::<f64>(output).unwrap(), 1.005);
+	}
+
+	#[test]
+	fn assert_equal_fails_evaluation() {
+		let node = AssertEqualNode::new(FutureWrapperNode::new(ClonedNode::new(String::from("a"))), FutureWrapperNode::new(ClonedNode::new(0.)));
+		let type_erased = FallibleDynAnyNode::<String, String, _>::new(node).into_type_erased();
+		let error = block_on(type_erased.eval(Box::new(String::from("b")))).err().unwrap();
+		assert_eq!(error.node_name, "AssertEqualNode");
+		assert_eq!(error.message, r#"Assertion failed: expected "a" but got "b""#);
+	}
+
+	#[test]
+	fn assert_bounds_of_vector_data() {
+		let square = VectorData::from_subpath(bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::splat(10.)));
+		let node = AssertBoundsNode::new(
+			FutureWrapperNode::new(ClonedNode::new(DVec2::ZERO)),
+			FutureWrapperNode::new(ClonedNode::new(DVec2::splat(10.))),
+			FutureWrapperNode::new(ClonedNode::new(1e-6)),
+		);
+		let type_erased = FallibleDynAnyNode::<VectorData, VectorData, _>::new(node).into_type_erased();
+		assert!(block_on(type_erased.eval(Box::new(square.clone()))).is_ok());
+
+		let mut moved = square;
+		moved.transform = DAffine2::from_translation(DVec2::X);
+		let error = block_on(type_erased.eval(Box::new(moved))).err().unwrap();
+		assert!(error.message.contains("but got bounds from [1, 0] to [11, 10]"), "{}", error.message);
+	}
+}
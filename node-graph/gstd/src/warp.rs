@@ -0,0 +1,281 @@
+use crate::panorama::{pixel_to_layer, sample_bilinear};
+
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::vector::VectorData;
+use graphene_core::warp::{Homography, Warp, WarpMode};
+use graphene_core::{Color, Node};
+
+use glam::{DAffine2, DVec2};
+
+/// The largest width or height of a warped image, beyond which its resolution is reduced to fit.
+const MAX_WARPED_IMAGE_SIZE: f64 = 8192.;
+
+/// Data which can be distorted by a [`Warp`] moving the corners of its bounds.
+pub trait Warpable {
+	/// The frame spanning the data, whose corners are moved by the warp.
+	fn warp_frame(&self) -> Option<DAffine2>;
+
+	fn warp(&self, warp: &Warp) -> Self;
+}
+
+impl Warpable for VectorData {
+	fn warp_frame(&self) -> Option<DAffine2> {
+		VectorData::warp_frame(self)
+	}
+
+	fn warp(&self, warp: &Warp) -> Self {
+		VectorData::warp(self, warp)
+	}
+}
+
+impl Warpable for ImageFrame<Color> {
+	fn warp_frame(&self) -> Option<DAffine2> {
+		(self.image.width > 0 && self.image.height > 0).then_some(self.transform)
+	}
+
+	/// Resamples the image across the bounds of its warped outline, at the resolution of the original image.
+	fn warp(&self, warp: &Warp) -> Self {
+		let (mut min, mut max) = (DVec2::INFINITY, DVec2::NEG_INFINITY);
+		for point in warp.warped_outline() {
+			min = min.min(point);
+			max = max.max(point);
+		}
+		let extent = max - min;
+		let mut pixel_size = pixel_to_layer(self).matrix2.determinant().abs().sqrt();
+		if !pixel_size.is_finite() || pixel_size <= 0. || !extent.is_finite() {
+			return ImageFrame::empty();
+		}
+		pixel_size = pixel_size.max(extent.max_element() / MAX_WARPED_IMAGE_SIZE);
+
+		// Rounding errors in the warped corners shouldn't add a row or column of pixels
+		let width = (extent.x / pixel_size - 1e-6).ceil().max(1.) as u32;
+		let height = (extent.y / pixel_size - 1e-6).ceil().max(1.) as u32;
+		let output_to_layer = DAffine2::from_translation(min) * DAffine2::from_scale(DVec2::splat(pixel_size));
+		let size = DVec2::new(self.image.width as f64, self.image.height as f64);
+
+		let mut data = Vec::with_capacity(width as usize * height as usize);
+		for y in 0..height {
+			// Neighboring pixels come from nearby places in the source image, so each one starts looking from where the last one was found
+			let mut guess = None;
+			for x in 0..width {
+				let point = output_to_layer.transform_point2(DVec2::new(x as f64 + 0.5, y as f64 + 0.5));
+				guess = warp.unmap_uv(point, guess);
+
+				data.push(match guess {
+					Some(uv) if uv.cmpge(DVec2::ZERO).all() && uv.cmple(DVec2::ONE).all() => sample_bilinear(&self.image, uv * size),
+					_ => Color::TRANSPARENT,
+				});
+			}
+		}
+
+		ImageFrame {
+			image: Image {
+				width,
+				height,
+				data: data.into(),
+				base64_string: None,
+			},
+			transform: output_to_layer * DAffine2::from_scale(DVec2::new(width as f64, height as f64)),
+			alpha_blending: self.alpha_blending,
+		}
+	}
+}
+
+/// Distorts the data by moving the corners of its bounds by the corner offsets, either in perspective or (in envelope mode) bending it along a 4×4 mesh of control points spanning the bounds.
+/// The mesh offsets move the control points of the envelope row by row from the top left, with missing ones left unmoved.
+pub struct WarpNode<Mode, TopLeft, TopRight, BottomRight, BottomLeft, Mesh> {
+	mode: Mode,
+	top_left: TopLeft,
+	top_right: TopRight,
+	bottom_right: BottomRight,
+	bottom_left: BottomLeft,
+	mesh: Mesh,
+}
+
+#[node_macro::node_fn(WarpNode)]
+fn warp_node<Data: Warpable>(data: Data, mode: WarpMode, top_left: DVec2, top_right: DVec2, bottom_right: DVec2, bottom_left: DVec2, mesh: Vec<DVec2>) -> Data {
+	let Some(frame) = data.warp_frame() else {
+		return data;
+	};
+	match Warp::new(mode, frame, [top_left, top_right, bottom_right, bottom_left], &mesh) {
+		Some(warp) => data.warp(&warp),
+		None => data,
+	}
+}
+
+/// Straightens lines bowed by a camera lens, outwards by barrel distortion for positive amounts or inwards by pincushion distortion for negative amounts.
+/// The amount (in percent) is how far the corners of the image move towards or away from its center, following the radial distortion model used by cameras.
+pub struct LensCorrectionNode<Amount> {
+	amount: Amount,
+}
+
+#[node_macro::node_fn(LensCorrectionNode)]
+fn lens_correction(image_frame: ImageFrame<Color>, amount: f64) -> ImageFrame<Color> {
+	let image = &image_frame.image;
+	if image.width == 0 || image.height == 0 || amount == 0. {
+		return image_frame;
+	}
+
+	let size = DVec2::new(image.width as f64, image.height as f64);
+	let center = size / 2.;
+	// Distances are measured relative to the corners, so the amount means the same thing at any resolution and aspect ratio
+	let radius = center.length();
+	let coefficient = -amount.clamp(-100., 100.) / 100.;
+
+	let mut data = Vec::with_capacity(image.data.len());
+	for y in 0..image.height {
+		for x in 0..image.width {
+			let offset = (DVec2::new(x as f64 + 0.5, y as f64 + 0.5) - center) / radius;
+			let position = center + offset * (1. + coefficient * offset.length_squared()) * radius;
+
+			let inside = position.cmpge(DVec2::ZERO).all() && position.cmple(size).all();
+			data.push(if inside { sample_bilinear(image, position) } else { Color::TRANSPARENT });
+		}
+	}
+
+	ImageFrame {
+		image: Image {
+			width: image.width,
+			height: image.height,
+			data: data.into(),
+			base64_string: None,
+		},
+		..image_frame
+	}
+}
+
+/// Cuts out the quad between the corners of an image and straightens it into a rectangle, like a photo of a page or whiteboard taken at an angle.
+/// The corners are given as fractions of the image's width and height, and the rectangle keeps the resolution of the longer of each pair of opposite edges.
+pub struct PerspectiveCropNode<TopLeft, TopRight, BottomRight, BottomLeft> {
+	top_left: TopLeft,
+	top_right: TopRight,
+	bottom_right: BottomRight,
+	bottom_left: BottomLeft,
+}
+
+#[node_macro::node_fn(PerspectiveCropNode)]
+fn perspective_crop(image_frame: ImageFrame<Color>, top_left: DVec2, top_right: DVec2, bottom_right: DVec2, bottom_left: DVec2) -> ImageFrame<Color> {
+	let image = &image_frame.image;
+	if image.width == 0 || image.height == 0 {
+		return image_frame;
+	}
+
+	let size = DVec2::new(image.width as f64, image.height as f64);
+	let quad = [top_left, top_right, bottom_right, bottom_left].map(|corner| corner * size);
+	let Some(homography) = Homography::from_unit_square(quad) else {
+		return ImageFrame::empty();
+	};
+
+	let edge = |a: usize, b: usize| quad[a].distance(quad[b]);
+	let extent = DVec2::new(edge(0, 1).max(edge(3, 2)), edge(0, 3).max(edge(1, 2)));
+	let scale = (extent.max_element() / MAX_WARPED_IMAGE_SIZE).max(1.);
+	let width = (extent.x / scale).round().max(1.) as u32;
+	let height = (extent.y / scale).round().max(1.) as u32;
+
+	let mut data = Vec::with_capacity(width as usize * height as usize);
+	for y in 0..height {
+		for x in 0..width {
+			let uv = DVec2::new((x as f64 + 0.5) / width as f64, (y as f64 + 0.5) / height as f64);
+			let position = homography.transform_point2(uv);
+
+			let inside = position.cmpge(DVec2::ZERO).all() && position.cmple(size).all();
+			data.push(if inside { sample_bilinear(image, position) } else { Color::TRANSPARENT });
+		}
+	}
+
+	// The straightened image is placed at the top left corner of the quad, with the pixels the same size as the original's
+	let pixel_scale = DVec2::new(width as f64, height as f64) * scale / size;
+	ImageFrame {
+		image: Image {
+			width,
+			height,
+			data: data.into(),
+			base64_string: None,
+		},
+		transform: image_frame.transform * DAffine2::from_scale_angle_translation(pixel_scale, 0., top_left),
+		alpha_blending: image_frame.alpha_blending,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use graphene_core::value::{ClonedNode, CopiedNode};
+
+	#[test]
+	fn warp_image_in_perspective() {
+		let frame = ImageFrame {
+			image: Image::new(10, 10, Color::WHITE),
+			transform: DAffine2::from_scale(DVec2::splat(100.)),
+			..Default::default()
+		};
+		let node = WarpNode {
+			mode: CopiedNode(WarpMode::Perspective),
+			top_left: CopiedNode(DVec2::new(40., 0.)),
+			top_right: CopiedNode(DVec2::new(-40., 0.)),
+			bottom_right: CopiedNode(DVec2::ZERO),
+			bottom_left: CopiedNode(DVec2::ZERO),
+			mesh: ClonedNode(Vec::new()),
+		};
+		let warped = node.eval(frame);
+
+		assert_eq!((warped.image.width, warped.image.height), (10, 10));
+		assert!(warped.transform.abs_diff_eq(DAffine2::from_scale(DVec2::splat(100.)), 1e-6));
+		let pixel = |x: usize, y: usize| warped.image.data[y * 10 + x];
+		// The top edge is pulled in, leaving its corners empty while the middle and bottom stay covered
+		assert_eq!(pixel(0, 0), Color::TRANSPARENT);
+		assert_eq!(pixel(5, 0), Color::WHITE);
+		assert_eq!(pixel(0, 9), Color::WHITE);
+	}
+
+	fn gradient_frame() -> ImageFrame<Color> {
+		let mut image = Image::new(20, 10, Color::BLACK);
+		for (index, pixel) in image.data.iter_mut().enumerate() {
+			let x = (index % 20) as f32 / 19.;
+			*pixel = Color::from_rgbaf32_unchecked(x, x, x, 1.);
+		}
+		ImageFrame {
+			image,
+			transform: DAffine2::from_scale(DVec2::new(200., 100.)),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn lens_correction_keeps_the_center() {
+		let frame = gradient_frame();
+		let unchanged = LensCorrectionNode { amount: CopiedNode(0.) }.eval(frame.clone());
+		assert_eq!(unchanged.image, frame.image);
+
+		// Correcting barrel distortion stretches the image out from its center, pulling in the edges and leaving the corners empty
+		let corrected = LensCorrectionNode { amount: CopiedNode(50.) }.eval(frame.clone());
+		assert_eq!((corrected.image.width, corrected.image.height), (20, 10));
+		assert_eq!(corrected.transform, frame.transform);
+		assert!((corrected.image.data[5 * 20 + 10].r() - frame.image.data[5 * 20 + 10].r()).abs() < 0.05);
+		assert!(corrected.image.data[5 * 20 + 18].r() < frame.image.data[5 * 20 + 18].r());
+
+		let pincushion = LensCorrectionNode { amount: CopiedNode(-50.) }.eval(frame.clone());
+		assert_eq!(pincushion.image.data[0], Color::TRANSPARENT);
+	}
+
+	#[test]
+	fn perspective_crop_straightens_the_quad() {
+		let frame = gradient_frame();
+		let node = PerspectiveCropNode {
+			top_left: CopiedNode(DVec2::new(0.5, 0.)),
+			top_right: CopiedNode(DVec2::new(1., 0.)),
+			bottom_right: CopiedNode(DVec2::new(1., 1.)),
+			bottom_left: CopiedNode(DVec2::new(0.5, 1.)),
+		};
+		let cropped = node.eval(frame.clone());
+
+		assert_eq!((cropped.image.width, cropped.image.height), (10, 10));
+		assert!(cropped
+			.transform
+			.abs_diff_eq(DAffine2::from_scale_angle_translation(DVec2::new(100., 100.), 0., DVec2::new(100., 0.)), 1e-9));
+		for x in 0..10 {
+			assert!((cropped.image.data[3 * 10 + x].r() - frame.image.data[3 * 20 + 10 + x].r()).abs() < 1e-5);
+		}
+	}
+}
@@ -2,7 +2,7 @@ use dyn_any::StaticType;
 use graphene_core::application_io::{ApplicationError, ApplicationIo, ExportFormat, RenderConfig, ResourceFuture, SurfaceHandle, SurfaceHandleFrame, SurfaceId};
 use graphene_core::raster::Image;
 use graphene_core::raster::{color::SRGBA8, ImageFrame};
-use graphene_core::renderer::{format_transform_matrix, GraphicElementRendered, ImageRenderMode, RenderParams, RenderSvgSegmentList, SvgRender};
+use graphene_core::renderer::{format_transform_matrix, render_onion_skin, GraphicElementRendered, ImageRenderMode, RenderParams, RenderSvgSegmentList, SvgRender};
 use graphene_core::transform::Footprint;
 use graphene_core::Color;
 use graphene_core::Node;
@@ -304,11 +304,28 @@ fn render_svg(data: impl GraphicElementRendered, mut render: SvgRender, render_p
 			attributes.push("fill", "white");
 		});
 	}
+	if !data.contains_artboard() {
+		render_onion_skin(&render_params.onion_skin, &mut render, None);
+	}
 
 	data.render_svg(&mut render, &render_params);
+	if let Some(optimization) = render_params.svg_optimization {
+		render.optimize_defs(optimization);
+	}
 	render.wrap_with_transform(footprint.transform, Some(footprint.resolution.as_dvec2()));
 
-	RenderOutput::Svg(render.svg.to_svg_string())
+	let svg = render.svg.to_svg_string();
+	if render.linked_images.is_empty() {
+		RenderOutput::Svg(svg)
+	} else {
+		RenderOutput::SvgWithLinkedImages { svg, images: render.linked_images }
+	}
+}
+
+fn lottie_frame(data: impl GraphicElementRendered, footprint: Footprint) -> RenderOutput {
+	let mut shapes = Vec::new();
+	data.collect_lottie_shapes(footprint.transform, 1., &mut shapes);
+	RenderOutput::LottieFrame(shapes)
 }
 
 #[cfg(any(feature = "resvg", feature = "vello"))]
@@ -377,12 +394,33 @@ where
 		Box::pin(async move {
 			let footprint = editor.render_config.viewport;
 
-			let RenderConfig { hide_artboards, for_export, .. } = editor.render_config;
-			let render_params = RenderParams::new(editor.render_config.view_mode, ImageRenderMode::Base64, None, false, hide_artboards, for_export);
+			let RenderConfig {
+				hide_artboards,
+				for_export,
+				svg_optimization,
+				image_export,
+				linear_compositing,
+				palette,
+				proof,
+				units,
+				..
+			} = editor.render_config;
+			let mut render_params = RenderParams::new(editor.render_config.view_mode, ImageRenderMode::Base64, None, false, hide_artboards, for_export);
+			render_params.svg_optimization = svg_optimization;
+			render_params.image_export = image_export;
+			render_params.linear_compositing = linear_compositing;
+			render_params.palette = palette.map(|palette| palette.mapper());
+			render_params.proof = proof.and_then(|proof| proof.proofer());
+			render_params.units = units;
+			if !for_export {
+				render_params.onion_skin = editor.onion_skin.clone();
+				render_params.image_previews = Some(editor.image_previews.clone());
+			}
 
 			let output_format = editor.render_config.export_format;
 			match output_format {
 				ExportFormat::Svg => render_svg(self.data.eval(footprint).await, SvgRender::new(), render_params, footprint),
+				ExportFormat::Lottie => lottie_frame(self.data.eval(footprint).await, footprint),
 				#[cfg(any(feature = "resvg", feature = "vello"))]
 				ExportFormat::Canvas => render_canvas(self.data.eval(footprint).await, SvgRender::new(), render_params, footprint, editor, self.surface_handle.eval(()).await),
 				_ => todo!("Non-SVG render output for {output_format:?}"),
@@ -405,12 +443,33 @@ where
 		Box::pin(async move {
 			let footprint = editor.render_config.viewport;
 
-			let RenderConfig { hide_artboards, for_export, .. } = editor.render_config;
-			let render_params = RenderParams::new(editor.render_config.view_mode, ImageRenderMode::Base64, None, false, hide_artboards, for_export);
+			let RenderConfig {
+				hide_artboards,
+				for_export,
+				svg_optimization,
+				image_export,
+				linear_compositing,
+				palette,
+				proof,
+				units,
+				..
+			} = editor.render_config;
+			let mut render_params = RenderParams::new(editor.render_config.view_mode, ImageRenderMode::Base64, None, false, hide_artboards, for_export);
+			render_params.svg_optimization = svg_optimization;
+			render_params.image_export = image_export;
+			render_params.linear_compositing = linear_compositing;
+			render_params.palette = palette.map(|palette| palette.mapper());
+			render_params.proof = proof.and_then(|proof| proof.proofer());
+			render_params.units = units;
+			if !for_export {
+				render_params.onion_skin = editor.onion_skin.clone();
+				render_params.image_previews = Some(editor.image_previews.clone());
+			}
 
 			let output_format = editor.render_config.export_format;
 			match output_format {
 				ExportFormat::Svg => render_svg(self.data.eval(()).await, SvgRender::new(), render_params, footprint),
+				ExportFormat::Lottie => lottie_frame(self.data.eval(()).await, footprint),
 				#[cfg(any(feature = "resvg", feature = "vello"))]
 				ExportFormat::Canvas => render_canvas(self.data.eval(()).await, SvgRender::new(), render_params, footprint, editor, self.surface_handle.eval(()).await),
 				_ => todo!("Non-SVG render output for {output_format:?}"),
@@ -1,15 +1,25 @@
+use crate::evaluation_cache::{EvaluationCache, EvaluationCacheChanges};
+use crate::memory_budget::{MemoryBudget, MemoryUsage};
+use crate::node_outputs::{InspectedValue, OutputRecorder};
 use crate::node_registry;
+use crate::profiling::{FrameProfile, Profiler};
 
 use dyn_any::StaticType;
+use graph_craft::allocation_pool;
 use graph_craft::document::value::{TaggedValue, UpcastNode};
-use graph_craft::document::{NodeId, Source};
-use graph_craft::graphene_compiler::Executor;
-use graph_craft::proto::{ConstructionArgs, GraphError, LocalFuture, NodeContainer, ProtoNetwork, ProtoNode, SharedNodeContainer, TypeErasedBox, TypingContext};
-use graph_craft::proto::{GraphErrorType, GraphErrors};
+use graph_craft::document::{NodeId, NodeNetwork, Source};
+use graph_craft::graphene_compiler::{Compiler, Executor};
+use graph_craft::proto::{Any, ConstructionArgs, FutureAny, GraphError, LocalFuture, NodeContainer, ProtoNetwork, ProtoNode, SharedNodeContainer, TypeErasedBox, TypingContext};
+use graph_craft::proto::{GraphErrorType, GraphErrors, NodeError};
 use graph_craft::Type;
+use graphene_core::raster::ImageFrame;
+use graphene_core::vector::VectorData;
+use graphene_core::{ArtboardGroup, GraphicElement, GraphicGroup, Node, ProtoNodeIdentifier};
 
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::rc::Rc;
 use std::sync::Arc;
 
 /// An executor of a node graph that does not require an online compilation server, and instead uses `Box<dyn ...>`.
@@ -21,6 +31,11 @@ pub struct DynamicExecutor {
 	typing_context: TypingContext,
 	// This allows us to keep the nodes around for one more frame which is used for introspection
 	orphaned_nodes: Vec<NodeId>,
+	/// The path of the document node whose output is described each time it's evaluated.
+	inspected_node: Option<Vec<NodeId>>,
+	/// The version of the loaded plugins whose nodes are known to the typing context.
+	#[cfg(feature = "plugins")]
+	plugin_generation: u64,
 }
 
 impl Default for DynamicExecutor {
@@ -28,12 +43,26 @@ impl Default for DynamicExecutor {
 		Self {
 			output: Default::default(),
 			tree: Default::default(),
-			typing_context: TypingContext::new(&node_registry::NODE_REGISTRY),
+			typing_context: typing_context(),
 			orphaned_nodes: Vec::new(),
+			inspected_node: None,
+			#[cfg(feature = "plugins")]
+			plugin_generation: graphene_std::plugin::plugin_generation(),
 		}
 	}
 }
 
+/// Creates a typing context that knows about the nodes in the registry, as well as those of the currently loaded plugins.
+fn typing_context() -> TypingContext {
+	#[allow(unused_mut)]
+	let mut typing_context = TypingContext::new(&node_registry::NODE_REGISTRY);
+	#[cfg(feature = "plugins")]
+	for (identifier, types) in graphene_std::plugin::plugin_node_types() {
+		typing_context.register_node(identifier, types, graphene_std::plugin::wasm_node_constructor);
+	}
+	typing_context
+}
+
 #[derive(PartialEq, Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResolvedDocumentNodeTypes {
@@ -43,7 +72,9 @@ pub struct ResolvedDocumentNodeTypes {
 
 impl DynamicExecutor {
 	pub async fn new(proto_network: ProtoNetwork) -> Result<Self, GraphErrors> {
-		let mut typing_context = TypingContext::new(&node_registry::NODE_REGISTRY);
+		#[cfg(feature = "plugins")]
+		let plugin_generation = graphene_std::plugin::plugin_generation();
+		let mut typing_context = typing_context();
 		typing_context.update(&proto_network)?;
 		let output = proto_network.output;
 		let tree = BorrowTree::new(proto_network, &typing_context).await?;
@@ -53,13 +84,33 @@ impl DynamicExecutor {
 			output,
 			typing_context,
 			orphaned_nodes: Vec::new(),
+			inspected_node: None,
+			#[cfg(feature = "plugins")]
+			plugin_generation,
 		})
 	}
 
 	/// Updates the existing [`BorrowTree`] to reflect the new [`ProtoNetwork`], reusing nodes where possible.
 	pub async fn update(&mut self, proto_network: ProtoNetwork) -> Result<(), GraphErrors> {
-		self.output = proto_network.output;
+		// Loading a plugin may change the types of its nodes, so everything is type checked and constructed again to pick up the new version
+		#[cfg(feature = "plugins")]
+		if self.plugin_generation != graphene_std::plugin::plugin_generation() {
+			self.plugin_generation = graphene_std::plugin::plugin_generation();
+			self.typing_context = typing_context();
+			self.tree = BorrowTree {
+				profiler: self.tree.profiler.clone(),
+				output_recorder: self.tree.output_recorder.clone(),
+				evaluation_cache: self.tree.evaluation_cache.clone(),
+				memory_budget: self.tree.memory_budget.clone(),
+				failures: self.tree.failures.clone(),
+				..Default::default()
+			};
+			self.orphaned_nodes.clear();
+		}
+
+		// The previous network keeps being executed if the new one doesn't type check
 		self.typing_context.update(&proto_network)?;
+		self.output = proto_network.output;
 		let mut orphans = self.tree.update(proto_network, &self.typing_context).await?;
 		core::mem::swap(&mut self.orphaned_nodes, &mut orphans);
 		for node_id in orphans {
@@ -67,6 +118,8 @@ impl DynamicExecutor {
 				self.tree.free_node(node_id)
 			}
 		}
+		// The inspected document node may have been compiled into a different proto node
+		self.update_inspected_node();
 		Ok(())
 	}
 
@@ -100,11 +153,119 @@ impl DynamicExecutor {
 		}
 		resolved_document_node_types
 	}
+
+	/// Starts or stops recording the evaluation time of each node, which is then collected with [`DynamicExecutor::take_frame_profile`] after each execution.
+	pub fn set_profiling(&self, enabled: bool) {
+		self.tree.profiler.set_enabled(enabled);
+	}
+
+	/// Takes the timings recorded since the last call, if profiling is enabled.
+	pub fn take_frame_profile(&self) -> Option<FrameProfile> {
+		let node_paths: HashMap<NodeId, &Vec<NodeId>> = self.tree.source_map.iter().map(|(path, &id)| (id, path)).collect();
+		self.tree.profiler.finish_frame(|id| node_paths.get(&id).map(|path| path.to_vec()).unwrap_or_default())
+	}
+
+	/// Starts or stops keeping the graphical output of each node, which is then collected with [`DynamicExecutor::take_node_outputs`] after each execution.
+	pub fn set_recording_outputs(&self, enabled: bool) {
+		self.tree.output_recorder.set_enabled(enabled);
+	}
+
+	/// Takes the graphical outputs recorded since the last call, by the path of the document node whose primary output they are.
+	pub fn take_node_outputs(&self) -> HashMap<Vec<NodeId>, GraphicElement> {
+		let mut outputs = self.tree.output_recorder.take_outputs();
+		self.tree
+			.outputs_source_map
+			.iter()
+			.filter(|(source, _)| source.index == 0)
+			.filter_map(|(source, id)| Some((source.node.clone(), outputs.remove(id)?)))
+			.collect()
+	}
+
+	/// Starts or stops describing the output of the document node at the path each time it's evaluated, which is then read with [`DynamicExecutor::inspection`].
+	pub fn set_inspected_node(&mut self, node_path: Option<Vec<NodeId>>) {
+		self.inspected_node = node_path;
+		self.update_inspected_node();
+	}
+
+	fn update_inspected_node(&self) {
+		let proto_node = self
+			.inspected_node
+			.as_ref()
+			.and_then(|node_path| self.tree.outputs_source_map.get(&Source { node: node_path.clone(), index: 0 }).copied());
+		self.tree.output_recorder.set_inspected(proto_node);
+	}
+
+	/// Describes what the inspected document node output the last time it was evaluated.
+	pub fn inspection(&self) -> Option<InspectedValue> {
+		self.tree.output_recorder.inspection()
+	}
+
+	/// Adds the outputs of cache nodes saved earlier, which are used instead of evaluating those cache nodes while they're in the graph.
+	pub fn load_evaluation_cache(&self, outputs: impl IntoIterator<Item = (NodeId, TaggedValue)>) {
+		self.tree.evaluation_cache.load(outputs);
+	}
+
+	/// Takes the outputs of the cache nodes recorded or dropped since the last call, by the stable ID of the cache node, if there are any.
+	pub fn take_evaluation_cache_changes(&self) -> Option<EvaluationCacheChanges> {
+		self.tree.evaluation_cache.take_changes()
+	}
+
+	/// Sets the number of bytes the outputs of the cache nodes may hold before the least recently used of them are cleared, or no limit if `None`.
+	pub fn set_memory_budget(&self, budget: Option<usize>) {
+		self.tree.memory_budget.set_budget(budget);
+	}
+
+	/// Clears the caches used least recently until the rest fit within the memory budget, returning the memory they hold afterwards. This is called after each execution.
+	pub fn enforce_memory_budget(&self) -> MemoryUsage {
+		for node_id in self.tree.memory_budget.evict() {
+			if let Some(node) = self.tree.nodes.get(&node_id) {
+				node.reset();
+			}
+			self.tree.evaluation_cache.remove(node_id);
+		}
+		self.tree.memory_budget.usage()
+	}
+
+	/// Takes the errors of the graphical nodes which failed during the last execution, whose outputs were replaced by empty placeholders so the rest of the graph could still be drawn.
+	pub fn take_evaluation_errors(&self) -> Vec<NodeError> {
+		self.tree.failures.take()
+	}
+
+	/// Describes an error raised while executing the network as an error of the document node it came from, to be shown in the graph.
+	pub fn graph_error(&self, error: &NodeError) -> Option<GraphError> {
+		let node = error.node?;
+		let node_path = self.tree.source_map.iter().find(|(_, &id)| id == node).map(|(path, _)| path.clone())?;
+
+		Some(GraphError {
+			node_path,
+			identifier: error.node_name.into(),
+			error: GraphErrorType::EvaluationFailed(error.message.clone()),
+		})
+	}
 }
 
 impl<'a, I: StaticType + 'a> Executor<I, TaggedValue> for &'a DynamicExecutor {
 	fn execute(&self, input: I) -> LocalFuture<Result<TaggedValue, Box<dyn Error>>> {
-		Box::pin(async move { self.tree.eval_tagged_value(self.output, input).await.map_err(|e| e.into()) })
+		Box::pin(async move {
+			self.tree.failures.borrow_mut().clear();
+			self.tree.eval_tagged_value(self.output, input).await
+		})
+	}
+}
+
+/// Compiles and evaluates networks which take no input from scratch, for checking their output against golden files with a [`SnapshotHarness`](graphene_std::snapshot::SnapshotHarness).
+pub struct SnapshotEvaluator;
+
+impl graphene_std::snapshot::GraphEvaluator for SnapshotEvaluator {
+	fn evaluate(&self, network: NodeNetwork) -> Result<TaggedValue, String> {
+		let proto_network = Compiler {}.compile_single(network)?;
+		let executor = futures::executor::block_on(DynamicExecutor::new(proto_network)).map_err(|errors| format!("{errors:?}"))?;
+		let output = futures::executor::block_on((&executor).execute(())).map_err(|error| error.to_string())?;
+		// A node which failed has been drawn as a placeholder, which mustn't be mistaken for the expected output
+		match executor.take_evaluation_errors().first() {
+			Some(error) => Err(error.to_string()),
+			None => Ok(output),
+		}
 	}
 }
 
@@ -119,6 +280,16 @@ pub struct BorrowTree {
 	inputs_source_map: HashMap<Source, (NodeId, usize)>,
 	/// A mapping of document input sources to the (single) proto node output
 	outputs_source_map: HashMap<Source, NodeId>,
+	/// Shared with every node in the tree to record how long they take to evaluate.
+	profiler: Rc<Profiler>,
+	/// Shared with every node in the tree to keep their graphical outputs for thumbnails.
+	output_recorder: Rc<OutputRecorder>,
+	/// Shared with the cache nodes in the tree to keep their outputs for saving with the document.
+	evaluation_cache: Rc<EvaluationCache>,
+	/// Shared with the cache nodes in the tree to account for the memory held by their outputs.
+	memory_budget: Rc<MemoryBudget>,
+	/// Shared with every node in the tree to collect the errors of the graphical nodes drawn as placeholders instead.
+	failures: Rc<RefCell<Vec<NodeError>>>,
 }
 
 impl BorrowTree {
@@ -145,6 +316,8 @@ impl BorrowTree {
 		self.inputs_source_map.retain(|_, (nid, _)| !old_nodes.contains(nid));
 		self.outputs_source_map.retain(|_, nid| !old_nodes.contains(nid));
 		self.nodes.retain(|nid, _| !old_nodes.contains(nid));
+		self.evaluation_cache.retain(|nid| self.nodes.contains_key(&nid));
+		self.memory_budget.retain(|nid| self.nodes.contains_key(&nid));
 		Ok(old_nodes.into_iter().collect())
 	}
 
@@ -170,15 +343,20 @@ impl BorrowTree {
 	/// Evaluate the output node of the [`BorrowTree`].
 	pub async fn eval<'i, I: StaticType + 'i, O: StaticType + 'i>(&'i self, id: NodeId, input: I) -> Option<O> {
 		let node = self.nodes.get(&id).cloned()?;
-		let output = node.eval(Box::new(input));
-		dyn_any::downcast::<O>(output.await).ok().map(|o| *o)
+		let output = node.eval(allocation_pool::boxed(input)).await;
+		// Every intermediate value of the evaluation has been passed on by now, leaving the allocations the next evaluation is expected to need
+		allocation_pool::reset();
+		dyn_any::downcast::<O>(output.ok()?).ok().map(allocation_pool::unbox)
 	}
 	/// Evaluate the output node of the [`BorrowTree`] and cast it to a tagged value.
 	/// This ensures that no borrowed data can escape the node graph.
-	pub async fn eval_tagged_value<'i, I: StaticType + 'i>(&'i self, id: NodeId, input: I) -> Result<TaggedValue, String> {
+	/// A node failing during the evaluation results in a [`NodeError`] attributed to that node.
+	pub async fn eval_tagged_value<'i, I: StaticType + 'i>(&'i self, id: NodeId, input: I) -> Result<TaggedValue, Box<dyn Error>> {
 		let node = self.nodes.get(&id).cloned().ok_or("Output node not found in executor")?;
-		let output = node.eval(Box::new(input));
-		TaggedValue::try_from_any(output.await)
+		let output = node.eval(allocation_pool::boxed(input)).await;
+		// Every intermediate value of the evaluation has been passed on by now, leaving the allocations the next evaluation is expected to need
+		allocation_pool::reset();
+		Ok(TaggedValue::try_from_any(output?)?)
 	}
 
 	pub fn free_node(&mut self, id: NodeId) {
@@ -216,8 +394,20 @@ impl BorrowTree {
 				let ids: Vec<_> = ids.iter().map(|(id, _)| *id).collect();
 				let construction_nodes = self.node_deps(&ids);
 				let constructor = typing_context.constructor(id).ok_or_else(|| vec![GraphError::new(&proto_node, GraphErrorType::NoConstructor)])?;
+				// Constructors which evaluate their inputs up front construct a node failing each evaluation if those inputs fail
 				let node = constructor(construction_nodes).await;
-				let node = NodeContainer::new(node);
+				let node = NodeContainer::new(Box::new(ErrorSourceNode {
+					node,
+					id,
+					identifier: proto_node.identifier.clone(),
+					placeholder: typing_context.type_of(id).and_then(|types| error_placeholder(&types.output)),
+					failures: self.failures.clone(),
+					profiler: self.profiler.clone(),
+					output_recorder: self.output_recorder.clone(),
+					is_cache: proto_node.identifier.name == CACHE_NODE,
+					evaluation_cache: self.evaluation_cache.clone(),
+					memory_budget: self.memory_budget.clone(),
+				}));
 				self.store_node(node, id);
 			}
 		};
@@ -233,6 +423,92 @@ impl BorrowTree {
 	}
 }
 
+/// The cache node whose output, which only depends on the nodes upstream of it, is kept in the [`EvaluationCache`] and counted against the [`MemoryBudget`].
+const CACHE_NODE: &str = "graphene_core::memo::MemoNode<_, _>";
+
+/// An empty graphic of the given type, which stands in for the output of a graphical node that failed so the rest of the graph is still drawn.
+fn error_placeholder(output: &Type) -> Option<TaggedValue> {
+	[
+		TaggedValue::VectorData(VectorData::empty()),
+		TaggedValue::GraphicGroup(GraphicGroup::EMPTY),
+		TaggedValue::ArtboardGroup(ArtboardGroup::EMPTY),
+		TaggedValue::ImageFrame(ImageFrame::empty()),
+	]
+	.into_iter()
+	.find(|placeholder| &placeholder.ty() == output)
+}
+
+/// Attributes the errors raised while evaluating a node to its proto node, so they can be shown on the document node they came from.
+/// A graphical node which fails outputs an empty placeholder instead, keeping its error to be shown, so a single failing node doesn't stop the whole graph from being drawn.
+/// It also times each evaluation of the node while profiling is enabled, keeps its graphical output while thumbnails are shown, describes its output while it's inspected,
+/// and keeps the output of a cache node so it can be saved with the document while accounting for the memory it holds.
+struct ErrorSourceNode {
+	node: TypeErasedBox<'static>,
+	id: NodeId,
+	identifier: ProtoNodeIdentifier,
+	/// The output standing in for that of the node if it fails, if the node is graphical.
+	placeholder: Option<TaggedValue>,
+	failures: Rc<RefCell<Vec<NodeError>>>,
+	profiler: Rc<Profiler>,
+	output_recorder: Rc<OutputRecorder>,
+	/// Whether this is a cache node, whose output is kept in the evaluation cache and counted against the memory budget.
+	is_cache: bool,
+	evaluation_cache: Rc<EvaluationCache>,
+	memory_budget: Rc<MemoryBudget>,
+}
+
+impl<'i> Node<'i, Any<'i>> for ErrorSourceNode {
+	type Output = FutureAny<'i>;
+
+	fn eval(&'i self, input: Any<'i>) -> Self::Output {
+		Box::pin(async move {
+			let evaluation = self.profiler.begin(self.id, &self.identifier);
+			let failures = self.failures.borrow().len();
+			// An output saved with the document stands in for evaluating the cache node, and the expensive nodes upstream of it, for the first time
+			let output = match self.is_cache.then(|| self.evaluation_cache.get(self.id)).flatten() {
+				Some(output) => Ok(output),
+				None => self.node.eval(input).await,
+			};
+			self.profiler.end(evaluation);
+			let output = match (output, &self.placeholder) {
+				(Err(error), Some(placeholder)) => {
+					self.failures.borrow_mut().push(error.with_source(self.id));
+					Ok(placeholder.clone().to_any())
+				}
+				(output, _) => output,
+			};
+			// An output made from placeholders isn't kept by a cache node, so the node is evaluated again once the failing nodes upstream of it are fixed
+			let failed_upstream = self.failures.borrow().len() > failures;
+			if self.is_cache && failed_upstream {
+				self.node.reset();
+			}
+			output
+				.map(|output| {
+					if self.is_cache && !failed_upstream {
+						let output = self.evaluation_cache.record(self.id, output);
+						self.memory_budget.record(self.id, output, self.evaluation_cache.memory_size(self.id))
+					} else {
+						output
+					}
+				})
+				.map(|output| self.output_recorder.record(self.id, output))
+				.map_err(|error| error.with_source(self.id))
+		})
+	}
+
+	fn reset(&self) {
+		self.node.reset();
+	}
+
+	fn node_name(&self) -> &'static str {
+		self.node.node_name()
+	}
+
+	fn serialize(&self) -> Option<Arc<dyn std::any::Any>> {
+		self.node.serialize()
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -250,4 +526,76 @@ mod test {
 		let result = futures::executor::block_on(tree.eval(NodeId(0), ()));
 		assert_eq!(result, Some(2u32));
 	}
+
+	/// Counts how many of the vector data inputs aren't empty.
+	struct CountShapesNode {
+		inputs: Vec<SharedNodeContainer>,
+	}
+
+	impl<'i> Node<'i, Any<'i>> for CountShapesNode {
+		type Output = FutureAny<'i>;
+
+		fn eval(&'i self, _: Any<'i>) -> Self::Output {
+			Box::pin(async move {
+				let mut count = 0_u32;
+				for input in &self.inputs {
+					let shape: VectorData = graphene_std::any::evaluate_input(input, ()).await?;
+					count += shape.bounding_box().is_some() as u32;
+				}
+				Ok(allocation_pool::boxed(count))
+			})
+		}
+	}
+
+	#[test]
+	fn failing_graphical_node_is_drawn_as_placeholder() {
+		fn failing(_: Vec<SharedNodeContainer>) -> graph_craft::proto::DynFuture<'static, TypeErasedBox<'static>> {
+			Box::pin(async { Box::new(graphene_std::any::FailedNode::new(NodeError::new("FailingNode", "Failed"))) as TypeErasedBox })
+		}
+		fn count_shapes(inputs: Vec<SharedNodeContainer>) -> graph_craft::proto::DynFuture<'static, TypeErasedBox<'static>> {
+			Box::pin(async { Box::new(CountShapesNode { inputs }) as TypeErasedBox })
+		}
+		let mut typing_context = TypingContext::new(&node_registry::NODE_REGISTRY);
+		let failing_types = graphene_core::NodeIOTypes::new(graphene_core::concrete!(()), graphene_core::concrete!(VectorData), vec![]);
+		typing_context.register_node(ProtoNodeIdentifier::new("failing"), failing_types, failing);
+		let count_types = graphene_core::NodeIOTypes::new(
+			graphene_core::concrete!(()),
+			graphene_core::concrete!(u32),
+			vec![graphene_core::fn_type!(VectorData), graphene_core::fn_type!(VectorData)],
+		);
+		typing_context.register_node(ProtoNodeIdentifier::new("count_shapes"), count_types, count_shapes);
+
+		let square = VectorData::from_subpath(graphene_core::vector::bezier_rs::Subpath::new_rect(glam::DVec2::ZERO, glam::DVec2::ONE));
+		let network = ProtoNetwork {
+			inputs: vec![],
+			output: NodeId(2),
+			nodes: vec![
+				(NodeId(0), ProtoNode::value(ConstructionArgs::Value(TaggedValue::VectorData(square)), vec![])),
+				(
+					NodeId(1),
+					ProtoNode {
+						identifier: ProtoNodeIdentifier::new("failing"),
+						construction_args: ConstructionArgs::Nodes(vec![]),
+						..Default::default()
+					},
+				),
+				(
+					NodeId(2),
+					ProtoNode {
+						identifier: ProtoNodeIdentifier::new("count_shapes"),
+						construction_args: ConstructionArgs::Nodes(vec![(NodeId(0), false), (NodeId(1), false)]),
+						..Default::default()
+					},
+				),
+			],
+		};
+		typing_context.update(&network).expect("The network should type check");
+		let tree = futures::executor::block_on(BorrowTree::new(network, &typing_context)).expect("The nodes should be constructed");
+
+		// The square is still counted, while the failing node is drawn as an empty placeholder
+		assert_eq!(futures::executor::block_on(tree.eval::<(), u32>(NodeId(2), ())), Some(1));
+		let failures = tree.failures.take();
+		assert_eq!(failures.len(), 1);
+		assert_eq!(failures[0].node, Some(NodeId(1)));
+	}
 }
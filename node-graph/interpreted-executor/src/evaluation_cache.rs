@@ -0,0 +1,169 @@
+use dyn_any::DynAny;
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeId;
+use graph_craft::proto::Any;
+use graphene_core::quantization::QuantizationChannels;
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::vector::VectorData;
+use graphene_core::{Color, GraphicGroup};
+
+use glam::DVec2;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// The outputs of the cache nodes in the graph, kept so they can be saved with a document and used in place of evaluating the expensive nodes behind the caches, like traced vectors,
+/// decoded images, and rendered noise, when the document is reopened. Each output is keyed by the stable ID of its cache node, which is a hash of the node and everything upstream of it,
+/// so a saved output stops being used as soon as anything feeding its cache changes.
+#[derive(Default)]
+pub struct EvaluationCache {
+	outputs: RefCell<HashMap<NodeId, TaggedValue>>,
+	/// The cache nodes whose outputs were recorded or dropped since the changes were last taken.
+	changed: RefCell<HashSet<NodeId>>,
+}
+
+/// The outputs of the cache nodes recorded since the changes were last taken, and the cache nodes whose outputs were dropped, for updating the outputs saved with the document.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EvaluationCacheChanges {
+	pub recorded: Vec<(NodeId, TaggedValue)>,
+	pub removed: Vec<NodeId>,
+}
+
+impl EvaluationCache {
+	/// Adds outputs saved earlier, which are used instead of evaluating their cache nodes while those are in the graph.
+	pub fn load(&self, outputs: impl IntoIterator<Item = (NodeId, TaggedValue)>) {
+		self.outputs.borrow_mut().extend(outputs);
+	}
+
+	/// The output kept for the cache node, if there is one.
+	pub fn get<'i>(&self, node: NodeId) -> Option<Any<'i>> {
+		self.outputs.borrow().get(&node).map(|output| output.clone().to_any())
+	}
+
+	/// Keeps a copy of the output of the cache node if it can be saved, handing back the output to be passed on.
+	pub fn record<'i>(&self, node: NodeId, output: Any<'i>) -> Any<'i> {
+		if self.outputs.borrow().contains_key(&node) || !is_saved(&output) {
+			return output;
+		}
+		let output = TaggedValue::try_from_any(output).expect("Only the types which can be converted to a tagged value are saved");
+		self.outputs.borrow_mut().insert(node, output.clone());
+		self.changed.borrow_mut().insert(node);
+		output.to_any()
+	}
+
+	/// The approximate number of bytes held by the copy of the cache node's output kept here, beyond those it shares with the output passed on.
+	/// The pixels of images are shared between the two, so only the points are counted again, which are the rest of what the memory budget counts.
+	pub fn memory_size(&self, node: NodeId) -> usize {
+		match self.outputs.borrow().get(&node) {
+			Some(TaggedValue::VecDVec2(points)) => points.len() * core::mem::size_of::<DVec2>(),
+			_ => 0,
+		}
+	}
+
+	/// Drops the output of the cache node, which is then evaluated again the next time it's needed.
+	pub fn remove(&self, node: NodeId) {
+		if self.outputs.borrow_mut().remove(&node).is_some() {
+			self.changed.borrow_mut().insert(node);
+		}
+	}
+
+	/// Drops the outputs of the cache nodes which aren't in the graph anymore.
+	pub fn retain(&self, in_graph: impl Fn(NodeId) -> bool) {
+		let mut changed = self.changed.borrow_mut();
+		self.outputs.borrow_mut().retain(|&node, _| {
+			let keep = in_graph(node);
+			if !keep {
+				changed.insert(node);
+			}
+			keep
+		});
+	}
+
+	/// The outputs recorded and dropped since the last call, if there are any, without those of the cache nodes which didn't change.
+	pub fn take_changes(&self) -> Option<EvaluationCacheChanges> {
+		let changed = self.changed.take();
+		if changed.is_empty() {
+			return None;
+		}
+
+		let outputs = self.outputs.borrow();
+		let mut changes = EvaluationCacheChanges::default();
+		for node in changed {
+			match outputs.get(&node) {
+				Some(output) => changes.recorded.push((node, output.clone())),
+				None => changes.removed.push(node),
+			}
+		}
+		Some(changes)
+	}
+}
+
+/// Whether the output is of a type worth saving, which leaves out the outputs tied to the viewport or the GPU.
+fn is_saved(output: &Any<'_>) -> bool {
+	let type_id = DynAny::type_id(output.as_ref());
+	[
+		TypeId::of::<Image<Color>>(),
+		TypeId::of::<ImageFrame<Color>>(),
+		TypeId::of::<QuantizationChannels>(),
+		TypeId::of::<Vec<DVec2>>(),
+		TypeId::of::<VectorData>(),
+		TypeId::of::<GraphicGroup>(),
+	]
+	.contains(&type_id)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn records_only_outputs_which_can_be_saved() {
+		let cache = EvaluationCache::default();
+		let output = cache.record(NodeId(0), Box::new(vec![DVec2::ONE]));
+		let _ = cache.record(NodeId(1), Box::new(1_f64));
+		assert_eq!(*dyn_any::downcast::<Vec<DVec2>>(output).unwrap(), vec![DVec2::ONE]);
+
+		let changes = EvaluationCacheChanges {
+			recorded: vec![(NodeId(0), TaggedValue::VecDVec2(vec![DVec2::ONE]))],
+			removed: Vec::new(),
+		};
+		assert_eq!(cache.take_changes(), Some(changes));
+		assert_eq!(cache.take_changes(), None);
+		assert!(cache.get(NodeId(1)).is_none());
+		assert_eq!(cache.memory_size(NodeId(0)), core::mem::size_of::<DVec2>());
+	}
+
+	#[test]
+	fn takes_only_the_outputs_changed_since_the_last_call() {
+		let cache = EvaluationCache::default();
+		let _ = cache.record(NodeId(0), Box::new(vec![DVec2::X]));
+		let _ = cache.record(NodeId(1), Box::new(vec![DVec2::Y]));
+		assert!(cache.take_changes().is_some());
+
+		let _ = cache.record(NodeId(2), Box::new(vec![DVec2::ONE]));
+		cache.remove(NodeId(0));
+		let changes = EvaluationCacheChanges {
+			recorded: vec![(NodeId(2), TaggedValue::VecDVec2(vec![DVec2::ONE]))],
+			removed: vec![NodeId(0)],
+		};
+		assert_eq!(cache.take_changes(), Some(changes));
+	}
+
+	#[test]
+	fn uses_loaded_outputs_until_their_nodes_leave_the_graph() {
+		let cache = EvaluationCache::default();
+		cache.load([(NodeId(0), TaggedValue::VecDVec2(vec![DVec2::X])), (NodeId(1), TaggedValue::VecDVec2(vec![DVec2::Y]))]);
+		assert_eq!(cache.take_changes(), None);
+
+		let output = cache.get(NodeId(1)).unwrap();
+		assert_eq!(*dyn_any::downcast::<Vec<DVec2>>(output).unwrap(), vec![DVec2::Y]);
+
+		cache.retain(|node| node == NodeId(1));
+		assert!(cache.get(NodeId(0)).is_none());
+		let changes = EvaluationCacheChanges {
+			recorded: Vec::new(),
+			removed: vec![NodeId(0)],
+		};
+		assert_eq!(cache.take_changes(), Some(changes));
+	}
+}
@@ -1,5 +1,9 @@
 pub mod dynamic_executor;
+pub mod evaluation_cache;
+pub mod memory_budget;
+pub mod node_outputs;
 pub mod node_registry;
+pub mod profiling;
 
 #[cfg(test)]
 mod tests {
@@ -76,6 +80,86 @@ mod tests {
 		assert_eq!(result, TaggedValue::U32(33));
 	}
 
+	#[test]
+	fn map_list_runs_function_network_per_item() {
+		use graph_craft::document::*;
+		use graph_craft::*;
+
+		let network = NodeNetwork {
+			exports: vec![NodeInput::node(NodeId(2), 0)],
+			nodes: [
+				// Receives each item from the Map List node as it evaluates the function network
+				(
+					NodeId(0),
+					DocumentNode {
+						name: "List Item".into(),
+						manual_composition: Some(concrete!(f64)),
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::ops::IdentityNode")),
+						..Default::default()
+					},
+				),
+				(
+					NodeId(1),
+					DocumentNode {
+						name: "Multiply".into(),
+						inputs: vec![NodeInput::node(NodeId(0), 0), NodeInput::value(TaggedValue::F64(2.), false)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::ops::MultiplyNode<_>")),
+						..Default::default()
+					},
+				),
+				(
+					NodeId(2),
+					DocumentNode {
+						name: "Map List".into(),
+						inputs: vec![NodeInput::network(concrete!(Vec<f64>), 0), NodeInput::node(NodeId(1), 0)],
+						implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::list::MapListNode<_>")),
+						..Default::default()
+					},
+				),
+			]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		};
+
+		use crate::dynamic_executor::DynamicExecutor;
+		use graph_craft::graphene_compiler::{Compiler, Executor};
+
+		let compiler = Compiler {};
+		let protograph = compiler.compile_single(network).expect("Graph should be generated");
+
+		let exec = block_on(DynamicExecutor::new(protograph)).unwrap_or_else(|e| panic!("Failed to create executor: {e:?}"));
+
+		let result = block_on((&exec).execute(vec![1_f64, 2., 3.])).unwrap();
+		assert_eq!(result, TaggedValue::VecF64(vec![2., 4., 6.]));
+	}
+
+	#[test]
+	fn snapshot_add() {
+		use crate::dynamic_executor::SnapshotEvaluator;
+		use graph_craft::document::*;
+		use graphene_std::snapshot::SnapshotHarness;
+
+		let network = NodeNetwork {
+			exports: vec![NodeInput::node(NodeId(0), 0)],
+			nodes: [(
+				NodeId(0),
+				DocumentNode {
+					name: "Add".into(),
+					inputs: vec![NodeInput::value(TaggedValue::F64(2.), false), NodeInput::value(TaggedValue::F64(3.), false)],
+					implementation: DocumentNodeImplementation::ProtoNode(ProtoNodeIdentifier::new("graphene_core::ops::AddNode<_>")),
+					..Default::default()
+				},
+			)]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		};
+
+		let harness = SnapshotHarness::new(SnapshotEvaluator, concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots"));
+		harness.assert_network_snapshot("add", &network);
+	}
+
 	#[test]
 	fn double_number() {
 		use graph_craft::document::*;
@@ -0,0 +1,162 @@
+use crate::node_outputs::map_output;
+
+use graph_craft::document::NodeId;
+use graph_craft::proto::Any;
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::{Color, GraphicElement, GraphicGroup};
+
+use glam::DVec2;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// The memory held by the outputs of the cache nodes, in bytes, along with the budget they're kept within.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryUsage {
+	pub used: usize,
+	/// The number of bytes the caches may hold, or no limit if `None`.
+	pub budget: Option<usize>,
+}
+
+struct CacheEntry {
+	bytes: usize,
+	last_used: u64,
+}
+
+/// Accounts for the memory held by the outputs of the cache nodes, so the caches used least recently can be cleared when together they hold more than the budget.
+/// This keeps large raster documents, whose images are cached at every step, from using up all the memory.
+#[derive(Default)]
+pub struct MemoryBudget {
+	budget: Cell<Option<usize>>,
+	/// The size of the output held by each cache node, and the execution of the graph it was last used in.
+	entries: RefCell<HashMap<NodeId, CacheEntry>>,
+	/// Counts the executions of the graph, to tell which caches were used least recently.
+	execution: Cell<u64>,
+}
+
+impl MemoryBudget {
+	pub fn set_budget(&self, budget: Option<usize>) {
+		self.budget.set(budget);
+	}
+
+	/// Notes that the cache node was used in this execution of the graph, holding the output, which is handed back to be passed on,
+	/// along with the given number of bytes held elsewhere on its behalf, like by the copy of the output kept in the evaluation cache.
+	pub fn record<'i>(&self, node: NodeId, output: Any<'i>, saved_bytes: usize) -> Any<'i> {
+		let (bytes, output) = memory_size(output);
+		self.entries.borrow_mut().insert(
+			node,
+			CacheEntry {
+				bytes: bytes + saved_bytes,
+				last_used: self.execution.get(),
+			},
+		);
+		output
+	}
+
+	/// Stops accounting for the cache nodes which aren't in the graph anymore.
+	pub fn retain(&self, in_graph: impl Fn(NodeId) -> bool) {
+		self.entries.borrow_mut().retain(|&node, _| in_graph(node));
+	}
+
+	pub fn usage(&self) -> MemoryUsage {
+		MemoryUsage {
+			used: self.entries.borrow().values().map(|entry| entry.bytes).sum(),
+			budget: self.budget.get(),
+		}
+	}
+
+	/// Finishes this execution of the graph, returning the cache nodes to clear, least recently used first, until the rest fit within the budget.
+	/// The caches used in this execution are never picked, since the next execution would just fill them again.
+	pub fn evict(&self) -> Vec<NodeId> {
+		let execution = self.execution.replace(self.execution.get() + 1);
+		let Some(budget) = self.budget.get() else { return Vec::new() };
+
+		let mut used = self.usage().used;
+		if used <= budget {
+			return Vec::new();
+		}
+
+		let mut entries = self.entries.borrow_mut();
+		let mut unused = entries
+			.iter()
+			.filter(|(_, entry)| entry.last_used < execution && entry.bytes > 0)
+			.map(|(&node, entry)| (entry.last_used, node))
+			.collect::<Vec<_>>();
+		unused.sort_unstable();
+
+		let mut evicted = Vec::new();
+		for (_, node) in unused {
+			if used <= budget {
+				break;
+			}
+			if let Some(entry) = entries.remove(&node) {
+				used -= entry.bytes;
+				evicted.push(node);
+			}
+		}
+		evicted
+	}
+}
+
+/// The approximate number of bytes held by the output, counting the pixels of the images in it, handing back the output.
+fn memory_size(output: Any<'_>) -> (usize, Any<'_>) {
+	map_output(output, Image::<Color>::memory_size)
+		.or_else(|output| map_output(output, |image_frame: &ImageFrame<Color>| image_frame.image.memory_size()))
+		.or_else(|output| map_output(output, GraphicGroup::memory_size))
+		.or_else(|output| map_output(output, GraphicElement::memory_size))
+		.or_else(|output| map_output(output, |points: &Vec<DVec2>| points.len() * core::mem::size_of::<DVec2>()))
+		.unwrap_or_else(|output| (0, output))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn image(width: u32, height: u32) -> Any<'static> {
+		Box::new(Image::new(width, height, Color::BLACK))
+	}
+
+	#[test]
+	fn evicts_least_recently_used_caches_beyond_the_budget() {
+		let pixel = core::mem::size_of::<Color>();
+		let budget = MemoryBudget::default();
+		budget.set_budget(Some(10 * pixel));
+
+		let _ = budget.record(NodeId(0), image(4, 1), 0);
+		let _ = budget.record(NodeId(1), image(4, 1), 0);
+		assert!(budget.evict().is_empty());
+
+		let _ = budget.record(NodeId(1), image(4, 1), 0);
+		let _ = budget.record(NodeId(2), image(4, 1), 0);
+		let _ = budget.record(NodeId(3), Box::new(1_f64), 0);
+		assert_eq!(budget.usage().used, 12 * pixel);
+		assert_eq!(budget.evict(), vec![NodeId(0)]);
+		assert_eq!(
+			budget.usage(),
+			MemoryUsage {
+				used: 8 * pixel,
+				budget: Some(10 * pixel)
+			}
+		);
+	}
+
+	#[test]
+	fn keeps_caches_used_in_the_last_execution_even_beyond_the_budget() {
+		let budget = MemoryBudget::default();
+		budget.set_budget(Some(0));
+		let _ = budget.record(NodeId(0), image(2, 2), 0);
+		assert!(budget.evict().is_empty());
+		assert!(budget.usage().used > 0);
+
+		budget.retain(|_| false);
+		assert_eq!(budget.usage().used, 0);
+	}
+
+	#[test]
+	fn counts_the_bytes_held_on_behalf_of_the_cache() {
+		let pixel = core::mem::size_of::<Color>();
+		let budget = MemoryBudget::default();
+		let _ = budget.record(NodeId(0), image(2, 2), 3);
+		assert_eq!(budget.usage().used, 4 * pixel + 3);
+	}
+}
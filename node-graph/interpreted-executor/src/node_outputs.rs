@@ -0,0 +1,201 @@
+use dyn_any::{DynAny, StaticType};
+use graph_craft::document::NodeId;
+use graph_craft::proto::Any;
+use graphene_core::raster::ImageFrame;
+use graphene_core::vector::VectorData;
+use graphene_core::{Artboard, Color, GraphicElement, GraphicGroup};
+
+use glam::{DVec2, IVec2};
+use std::any::TypeId;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// What an inspected node output the last time it was evaluated, for finding out why a result further down the graph is wrong.
+/// Numbers, text, and colors are kept whole, while graphics are described by what they're made of.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InspectedValue {
+	Number(f64),
+	Bool(bool),
+	Text(String),
+	Color(Color),
+	Vector(DVec2),
+	VectorData {
+		points: usize,
+		segments: usize,
+		subpaths: usize,
+		bounds: Option<[DVec2; 2]>,
+	},
+	Image {
+		width: u32,
+		height: u32,
+	},
+	GraphicGroup {
+		elements: usize,
+	},
+	Artboard {
+		location: IVec2,
+		dimensions: IVec2,
+		elements: usize,
+	},
+	/// A value of a type which can't be described, although its type is known from the graph.
+	Opaque,
+}
+
+/// Keeps the last graphical output of each node, while enabled, so thumbnails of the nodes can be rendered once the graph has been evaluated.
+/// It also describes the output of the inspected node, if there is one.
+#[derive(Default)]
+pub struct OutputRecorder {
+	enabled: Cell<bool>,
+	outputs: RefCell<HashMap<NodeId, GraphicElement>>,
+	inspected: Cell<Option<NodeId>>,
+	inspection: RefCell<Option<InspectedValue>>,
+}
+
+impl OutputRecorder {
+	pub fn enabled(&self) -> bool {
+		self.enabled.get()
+	}
+
+	pub fn set_enabled(&self, enabled: bool) {
+		self.enabled.set(enabled);
+		if !enabled {
+			self.outputs.borrow_mut().clear();
+		}
+	}
+
+	/// Sets the node whose output is described each time it's evaluated, if any.
+	pub fn set_inspected(&self, node: Option<NodeId>) {
+		if self.inspected.replace(node) != node {
+			self.inspection.borrow_mut().take();
+		}
+	}
+
+	/// Keeps a copy of the output of the node if it's graphical, handing back the output to be passed on.
+	pub fn record<'i>(&self, node: NodeId, output: Any<'i>) -> Any<'i> {
+		let output = if self.inspected.get() == Some(node) {
+			let (inspection, output) = inspect(output);
+			*self.inspection.borrow_mut() = Some(inspection);
+			output
+		} else {
+			output
+		};
+		if !self.enabled.get() {
+			return output;
+		}
+
+		let recorded = map_output(output, |vector_data: &VectorData| vector_data.clone().into())
+			.or_else(|output| map_output(output, |image_frame: &ImageFrame<Color>| image_frame.clone().into()))
+			.or_else(|output| map_output(output, |graphic_group: &GraphicGroup| graphic_group.clone().into()))
+			.or_else(|output| map_output(output, GraphicElement::clone))
+			.or_else(|output| map_output(output, |artboard: &Artboard| artboard.clone().into()));
+		match recorded {
+			Ok((graphic_element, output)) => {
+				self.outputs.borrow_mut().insert(node, graphic_element);
+				output
+			}
+			Err(output) => output,
+		}
+	}
+
+	/// Takes the outputs recorded since the last call.
+	pub fn take_outputs(&self) -> HashMap<NodeId, GraphicElement> {
+		core::mem::take(&mut *self.outputs.borrow_mut())
+	}
+
+	/// The description of what the inspected node output when it was last evaluated.
+	pub fn inspection(&self) -> Option<InspectedValue> {
+		self.inspection.borrow().clone()
+	}
+}
+
+/// Describes the output, handing it back to be passed on.
+fn inspect(output: Any<'_>) -> (InspectedValue, Any<'_>) {
+	let inspected = map_output(output, |number: &f64| InspectedValue::Number(*number))
+		.or_else(|output| map_output(output, |number: &f32| InspectedValue::Number(*number as f64)))
+		.or_else(|output| map_output(output, |number: &u32| InspectedValue::Number(*number as f64)))
+		.or_else(|output| map_output(output, |number: &u64| InspectedValue::Number(*number as f64)))
+		.or_else(|output| map_output(output, |boolean: &bool| InspectedValue::Bool(*boolean)))
+		.or_else(|output| map_output(output, |text: &String| InspectedValue::Text(text.clone())))
+		.or_else(|output| map_output(output, |color: &Color| InspectedValue::Color(*color)))
+		.or_else(|output| map_output(output, |vector: &DVec2| InspectedValue::Vector(*vector)))
+		.or_else(|output| {
+			map_output(output, |vector_data: &VectorData| InspectedValue::VectorData {
+				points: vector_data.point_domain.ids().len(),
+				segments: vector_data.segment_bezier_iter().count(),
+				subpaths: vector_data.stroke_bezier_paths().count(),
+				bounds: vector_data.bounding_box_with_transform(vector_data.transform),
+			})
+		})
+		.or_else(|output| {
+			map_output(output, |image_frame: &ImageFrame<Color>| InspectedValue::Image {
+				width: image_frame.image.width,
+				height: image_frame.image.height,
+			})
+		})
+		.or_else(|output| map_output(output, |graphic_group: &GraphicGroup| InspectedValue::GraphicGroup { elements: graphic_group.len() }))
+		.or_else(|output| {
+			map_output(output, |artboard: &Artboard| InspectedValue::Artboard {
+				location: artboard.location,
+				dimensions: artboard.dimensions,
+				elements: artboard.graphic_group.len(),
+			})
+		});
+	inspected.unwrap_or_else(|output| (InspectedValue::Opaque, output))
+}
+
+/// Applies the function to the output if it's of type `T`, handing back the output either way.
+pub(crate) fn map_output<'i, T: StaticType + 'i, R>(output: Any<'i>, f: impl FnOnce(&T) -> R) -> Result<(R, Any<'i>), Any<'i>> {
+	if DynAny::type_id(output.as_ref()) != TypeId::of::<T::Static>() {
+		return Err(output);
+	}
+	let value = dyn_any::downcast::<T>(output).expect("The type of the output was just checked");
+	let result = f(&value);
+	Ok((result, value as Any<'i>))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn records_only_graphical_outputs_while_enabled() {
+		let recorder = OutputRecorder::default();
+		let output = recorder.record(NodeId(0), Box::new(VectorData::empty()));
+		assert!(recorder.take_outputs().is_empty());
+
+		recorder.set_enabled(true);
+		let output = recorder.record(NodeId(0), output);
+		let number = recorder.record(NodeId(1), Box::new(1_f64));
+		assert_eq!(*dyn_any::downcast::<VectorData>(output).unwrap(), VectorData::empty());
+		assert_eq!(*dyn_any::downcast::<f64>(number).unwrap(), 1.);
+
+		let outputs = recorder.take_outputs();
+		assert_eq!(outputs.len(), 1);
+		assert_eq!(outputs[&NodeId(0)], GraphicElement::VectorData(Box::new(VectorData::empty())));
+		assert!(recorder.take_outputs().is_empty());
+	}
+
+	#[test]
+	fn describes_the_output_of_the_inspected_node() {
+		let recorder = OutputRecorder::default();
+		recorder.set_inspected(Some(NodeId(1)));
+
+		let _ = recorder.record(NodeId(0), Box::new(1_f64));
+		assert_eq!(recorder.inspection(), None);
+		let _ = recorder.record(NodeId(1), Box::new(2_u32));
+		assert_eq!(recorder.inspection(), Some(InspectedValue::Number(2.)));
+
+		let image = ImageFrame {
+			image: graphene_core::raster::Image::new(3, 2, Color::RED),
+			..Default::default()
+		};
+		let _ = recorder.record(NodeId(1), Box::new(image));
+		assert_eq!(recorder.inspection(), Some(InspectedValue::Image { width: 3, height: 2 }));
+		let _ = recorder.record(NodeId(1), Box::new(()));
+		assert_eq!(recorder.inspection(), Some(InspectedValue::Opaque));
+
+		recorder.set_inspected(None);
+		assert_eq!(recorder.inspection(), None);
+	}
+}
@@ -15,7 +15,7 @@ use graphene_core::{concrete, generic, Artboard, ArtboardGroup, GraphicGroup};
 use graphene_core::{fn_type, raster::*};
 use graphene_core::{Cow, ProtoNodeIdentifier, Type};
 use graphene_core::{Node, NodeIO, NodeIOTypes};
-use graphene_std::any::{ComposeTypeErased, DowncastBothNode, DynAnyNode, FutureWrapperNode, IntoTypeErasedNode};
+use graphene_std::any::{evaluate_input, ComposeTypeErased, DowncastBothNode, DynAnyNode, FailedNode, FallibleDynAnyNode, FutureWrapperNode, InputErrors, IntoTypeErasedNode};
 use graphene_std::wasm_application_io::*;
 
 #[cfg(feature = "gpu")]
@@ -37,17 +37,27 @@ macro_rules! construct_node {
 		args.reverse();
 		let node = <$path>::new($(
 				{
-					let node = graphene_std::any::downcast_node::<$arg, $type>(args.pop().expect("Not enough arguments provided to construct node"));
-					let value = node.eval(()).await;
+					let node = args.pop().expect("Not enough arguments provided to construct node");
+					let value = graphene_std::any::evaluate_input::<$arg, $type>(&node, ()).await?;
 					graphene_core::value::ClonedNode::new(value)
 				}
 			),*
 		);
-		node
+		Ok::<_, graph_craft::proto::NodeError>(node)
 
 	}}
 }
 
+/// Constructs the node with the values of its inputs, or a [`FailedNode`](graphene_std::any::FailedNode) in its place if evaluating any of them failed.
+macro_rules! try_construct_node {
+	($args: ident, $path:ty, [$($arg:ty => $type:ty),*]) => {
+		match construct_node!($args, $path, [$($arg => $type),*]).await {
+			Ok(node) => node,
+			Err(error) => return Box::new(graphene_std::any::FailedNode::new(error)) as TypeErasedBox,
+		}
+	};
+}
+
 macro_rules! register_node {
 	($path:ty, input: $input:ty, params: [ $($type:ty),*]) => {
 		register_node!($path, input: $input, fn_params: [ $(() => $type),*])
@@ -58,7 +68,7 @@ macro_rules! register_node {
 			ProtoNodeIdentifier::new(stringify!($path)),
 			|args| {
 				Box::pin(async move {
-				let node = construct_node!(args, $path, [$($arg => $type),*]).await;
+				let node = try_construct_node!(args, $path, [$($arg => $type),*]);
 				let node = graphene_std::any::FutureWrapperNode::new(node);
 				let any: DynAnyNode<$input, _, _> = graphene_std::any::DynAnyNode::new(node);
 				Box::new(any) as TypeErasedBox
@@ -78,20 +88,16 @@ macro_rules! register_node {
 	};
 }
 macro_rules! async_node {
-	// TODO: we currently need to annotate the type here because the compiler would otherwise (correctly)
-	// assign a Pin<Box<dyn Future<Output=T>>> type to the node, which is not what we want for now.
-	($path:ty, input: $input:ty, output: $output:ty, params: [ $($type:ty),*]) => {
-		async_node!($path, input: $input, output: $output, fn_params: [ $(() => $type),*])
-	};
-	($path:ty, input: $input:ty, output: $output:ty, fn_params: [  $($arg:ty => $type:ty),*]) => {
+	(@$dyn_any_node:ident $path:ty, input: $input:ty, output: $output:ty, fn_params: [  $($arg:ty => $type:ty),*]) => {
 		vec![
 		(
 			ProtoNodeIdentifier::new(stringify!($path)),
 			|mut args| {
 				Box::pin(async move {
 				args.reverse();
-				let node = <$path>::new($(graphene_std::any::downcast_node::<$arg, $type>(args.pop().expect("Not enough arguments provided to construct node"))),*);
-				let any: DynAnyNode<$input, _, _> = graphene_std::any::DynAnyNode::new(node);
+				let errors = graphene_std::any::InputErrors::default();
+				let node = <$path>::new($(graphene_std::any::downcast_node::<$arg, $type>(args.pop().expect("Not enough arguments provided to construct node"), &errors)),*);
+				let any: $dyn_any_node<$input, _, _> = graphene_std::any::$dyn_any_node::with_input_errors(node, errors);
 				Box::new(any) as TypeErasedBox
 				})
 			},
@@ -111,9 +117,27 @@ macro_rules! async_node {
 		)
 		]
 	};
+	// TODO: we currently need to annotate the type here because the compiler would otherwise (correctly)
+	// assign a Pin<Box<dyn Future<Output=T>>> type to the node, which is not what we want for now.
+	($path:ty, input: $input:ty, output: $output:ty, params: [ $($type:ty),*]) => {
+		async_node!($path, input: $input, output: $output, fn_params: [ $(() => $type),*])
+	};
+	($path:ty, input: $input:ty, output: $output:ty, fn_params: [  $($arg:ty => $type:ty),*]) => {
+		async_node!(@DynAnyNode $path, input: $input, output: $output, fn_params: [ $($arg => $type),*])
+	};
+}
+/// Registers an async node whose output is a `Result`, which fails its evaluation with the error.
+macro_rules! fallible_async_node {
+	($path:ty, input: $input:ty, output: $output:ty, params: [ $($type:ty),*]) => {
+		async_node!(@FallibleDynAnyNode $path, input: $input, output: $output, fn_params: [ $(() => $type),*])
+	};
 }
 macro_rules! raster_node {
-	($path:ty, params: [$($type:ty),*]) => {{
+	($path:ty, params: [$($type:ty),*]) => {
+		raster_node!($path, params: [$($type),*], map_image: MapImageNode)
+	};
+	// Adjustments implementing `AdjustPixels` are applied to images by the `AdjustImageNode`, which processes their pixels in lanes
+	($path:ty, params: [$($type:ty),*], map_image: $map_image:ident) => {{
 		// this function could also be inlined but serves as a workaround for
 		// [wasm-pack#981](https://github.com/rustwasm/wasm-pack/issues/981).
 		// The non-inlining function leads to fewer locals in the resulting
@@ -128,7 +152,7 @@ macro_rules! raster_node {
 				ProtoNodeIdentifier::new(stringify!($path)),
 				|args| {
 					Box::pin(async move {
-						let node = construct_node!(args, $path, [$(() => $type),*]).await;
+						let node = try_construct_node!(args, $path, [$(() => $type),*]);
 						let node = graphene_std::any::FutureWrapperNode::new(node);
 						let any: DynAnyNode<Color, _, _> = graphene_std::any::DynAnyNode::new(node);
 						any.into_type_erased()
@@ -143,8 +167,8 @@ macro_rules! raster_node {
 				ProtoNodeIdentifier::new(stringify!($path)),
 				|args| {
 					Box::pin(async move {
-						let node = construct_node!(args, $path, [$(() => $type),*]).await;
-						let map_node = graphene_std::raster::MapImageNode::new(graphene_core::value::ValueNode::new(node));
+						let node = try_construct_node!(args, $path, [$(() => $type),*]);
+						let map_node = graphene_std::raster::$map_image::new(graphene_core::value::ValueNode::new(node));
 						let map_node = graphene_std::any::FutureWrapperNode::new(map_node);
 						let any: DynAnyNode<Image<Color>, _, _> = graphene_std::any::DynAnyNode::new(map_node);
 						any.into_type_erased()
@@ -159,8 +183,8 @@ macro_rules! raster_node {
 				ProtoNodeIdentifier::new(stringify!($path)),
 				|args| {
 					Box::pin(async move {
-						let node = construct_node!(args, $path, [$(() => $type),*]).await;
-						let map_node = graphene_std::raster::MapImageNode::new(graphene_core::value::ValueNode::new(node));
+						let node = try_construct_node!(args, $path, [$(() => $type),*]);
+						let map_node = graphene_std::raster::$map_image::new(graphene_core::value::ValueNode::new(node));
 						let map_node = graphene_std::any::FutureWrapperNode::new(map_node);
 						let any: DynAnyNode<ImageFrame<Color>, _, _> = graphene_std::any::DynAnyNode::new(map_node);
 						any.into_type_erased()
@@ -226,6 +250,8 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_core::ops::DivideNode<_>, input: f64, params: [f64]),
 		register_node!(graphene_core::ops::DivideNode<_>, input: glam::DVec2, params: [f64]),
 		register_node!(graphene_core::ops::DivideNode<_>, input: glam::DVec2, params: [glam::DVec2]),
+		register_node!(graphene_std::expression::ExpressionNode<_, _, _, _>, input: f64, params: [String, f64, DVec2, DVec2]),
+		register_node!(graphene_std::expression::VectorExpressionNode<_, _, _, _>, input: f64, params: [String, f64, DVec2, DVec2]),
 		register_node!(graphene_core::ops::MultiplyNode<_>, input: u32, params: [u32]),
 		register_node!(graphene_core::ops::MultiplyNode<_>, input: &u32, params: [u32]),
 		register_node!(graphene_core::ops::MultiplyNode<_>, input: u32, params: [&u32]),
@@ -254,6 +280,17 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_core::ops::SineNode, input: f64, params: []),
 		register_node!(graphene_core::ops::CosineNode, input: f64, params: []),
 		register_node!(graphene_core::ops::TangentNode, input: f64, params: []),
+		register_node!(graphene_core::ops::ArcsineNode, input: f64, params: []),
+		register_node!(graphene_core::ops::ArccosineNode, input: f64, params: []),
+		register_node!(graphene_core::ops::ArctangentNode, input: f64, params: []),
+		register_node!(graphene_core::ops::Arctangent2Node<_>, input: f64, params: [f64]),
+		register_node!(graphene_core::ops::SquareRootNode, input: f64, params: []),
+		register_node!(graphene_core::ops::NaturalExponentNode, input: f64, params: []),
+		register_node!(graphene_core::ops::ClampNode<_, _>, input: f64, params: [f64, f64]),
+		register_node!(graphene_core::ops::RemapNode<_, _, _, _, _>, input: f64, params: [f64, f64, f64, f64, bool]),
+		register_node!(graphene_core::ops::SmoothstepNode<_, _>, input: f64, params: [f64, f64]),
+		register_node!(graphene_core::ops::LerpNode<_, _>, input: f64, params: [f64, f64]),
+		register_node!(graphene_core::ops::LerpNode<_, _>, input: DVec2, params: [DVec2, f64]),
 		register_node!(graphene_core::ops::MaximumNode<_>, input: u32, params: [u32]),
 		register_node!(graphene_core::ops::MaximumNode<_>, input: f64, params: [f64]),
 		register_node!(graphene_core::ops::MinimumNode<_>, input: u32, params: [u32]),
@@ -269,6 +306,26 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_core::ops::ModuloNode<_>, input: f64, params: [&f64]),
 		register_node!(graphene_core::ops::ModuloNode<_>, input: &f64, params: [&f64]),
 		register_node!(graphene_core::ops::ConstructVector2<_, _>, input: (), params: [f64, f64]),
+		register_node!(graphene_core::ops::DotProductNode<_>, input: DVec2, params: [DVec2]),
+		register_node!(graphene_core::ops::CrossProductNode<_>, input: DVec2, params: [DVec2]),
+		register_node!(graphene_core::ops::VectorLengthNode, input: DVec2, params: []),
+		register_node!(graphene_core::ops::NormalizeNode, input: DVec2, params: []),
+		register_node!(graphene_core::ops::ConstructTransformNode<_, _, _>, input: (), params: [DVec2, f64, DVec2]),
+		register_node!(graphene_core::ops::TransformPointNode<_>, input: DVec2, params: [DAffine2]),
+		async_node!(graphene_core::list::MapListNode<_>, input: Vec<f64>, output: Vec<f64>, fn_params: [f64 => f64]),
+		async_node!(graphene_core::list::MapListNode<_>, input: Vec<DVec2>, output: Vec<DVec2>, fn_params: [DVec2 => DVec2]),
+		async_node!(graphene_core::list::MapListNode<_>, input: GraphicGroup, output: GraphicGroup, fn_params: [graphene_core::GraphicElement => graphene_core::GraphicElement]),
+		async_node!(graphene_core::list::FilterListNode<_>, input: Vec<f64>, output: Vec<f64>, fn_params: [f64 => bool]),
+		async_node!(graphene_core::list::FilterListNode<_>, input: Vec<DVec2>, output: Vec<DVec2>, fn_params: [DVec2 => bool]),
+		async_node!(graphene_core::list::FilterListNode<_>, input: GraphicGroup, output: GraphicGroup, fn_params: [graphene_core::GraphicElement => bool]),
+		async_node!(graphene_core::list::FoldListNode<_, _>, input: Vec<f64>, output: f64, fn_params: [() => f64, (f64, f64) => f64]),
+		register_node!(graphene_core::ops::FirstOfPairNode, input: (f64, f64), params: []),
+		register_node!(graphene_core::ops::SecondOfPairNode, input: (f64, f64), params: []),
+		register_node!(graphene_core::random::RandomNumberNode<_, _, _, _>, input: (), params: [f64, f64, u32, u32]),
+		register_node!(graphene_core::random::RandomVectorNode<_, _, _, _>, input: (), params: [DVec2, DVec2, u32, u32]),
+		register_node!(graphene_core::random::ShuffleListNode<_, _>, input: Vec<f64>, params: [u32, u32]),
+		register_node!(graphene_core::random::ShuffleListNode<_, _>, input: Vec<DVec2>, params: [u32, u32]),
+		register_node!(graphene_core::oscillator::OscillatorNode<_, _, _, _, _>, input: (), params: [f64, graphene_core::oscillator::Waveform, f64, f64, f64]),
 		register_node!(graphene_core::ops::SomeNode, input: WasmEditorApi, params: []),
 		register_node!(graphene_core::logic::LogToConsoleNode, input: bool, params: []),
 		register_node!(graphene_core::logic::LogToConsoleNode, input: f64, params: []),
@@ -283,6 +340,25 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_core::logic::LogicAndNode<_>, input: bool, params: [bool]),
 		register_node!(graphene_core::logic::LogicXorNode<_>, input: bool, params: [bool]),
 		register_node!(graphene_core::logic::LogicNotNode, input: bool, params: []),
+		async_node!(graphene_core::logic::ConditionalNode<_, _, _>, input: Footprint, output: VectorData, fn_params: [() => bool, Footprint => VectorData, Footprint => VectorData]),
+		async_node!(graphene_core::logic::ConditionalNode<_, _, _>, input: Footprint, output: GraphicGroup, fn_params: [() => bool, Footprint => GraphicGroup, Footprint => GraphicGroup]),
+		async_node!(graphene_core::logic::ConditionalNode<_, _, _>, input: Footprint, output: ImageFrame<Color>, fn_params: [() => bool, Footprint => ImageFrame<Color>, Footprint => ImageFrame<Color>]),
+		async_node!(graphene_core::logic::ConditionalNode<_, _, _>, input: Footprint, output: f64, fn_params: [() => bool, Footprint => f64, Footprint => f64]),
+		async_node!(graphene_core::logic::ConditionalNode<_, _, _>, input: Footprint, output: String, fn_params: [() => bool, Footprint => String, Footprint => String]),
+		async_node!(graphene_core::logic::SwitchNode<_, _, _, _, _>, input: Footprint, output: VectorData, fn_params: [() => u32, Footprint => VectorData, Footprint => VectorData, Footprint => VectorData, Footprint => VectorData]),
+		async_node!(graphene_core::logic::SwitchNode<_, _, _, _, _>, input: Footprint, output: GraphicGroup, fn_params: [() => u32, Footprint => GraphicGroup, Footprint => GraphicGroup, Footprint => GraphicGroup, Footprint => GraphicGroup]),
+		async_node!(graphene_core::logic::SwitchNode<_, _, _, _, _>, input: Footprint, output: ImageFrame<Color>, fn_params: [() => u32, Footprint => ImageFrame<Color>, Footprint => ImageFrame<Color>, Footprint => ImageFrame<Color>, Footprint => ImageFrame<Color>]),
+		async_node!(graphene_core::logic::SwitchNode<_, _, _, _, _>, input: Footprint, output: f64, fn_params: [() => u32, Footprint => f64, Footprint => f64, Footprint => f64, Footprint => f64]),
+		async_node!(graphene_core::logic::SwitchNode<_, _, _, _, _>, input: Footprint, output: String, fn_params: [() => u32, Footprint => String, Footprint => String, Footprint => String, Footprint => String]),
+		fallible_async_node!(graphene_std::validation::AssertEqualNode<_, _>, input: f64, output: f64, params: [f64, f64]),
+		fallible_async_node!(graphene_std::validation::AssertEqualNode<_, _>, input: u32, output: u32, params: [u32, f64]),
+		fallible_async_node!(graphene_std::validation::AssertEqualNode<_, _>, input: bool, output: bool, params: [bool, f64]),
+		fallible_async_node!(graphene_std::validation::AssertEqualNode<_, _>, input: String, output: String, params: [String, f64]),
+		fallible_async_node!(graphene_std::validation::AssertEqualNode<_, _>, input: DVec2, output: DVec2, params: [DVec2, f64]),
+		fallible_async_node!(graphene_std::validation::AssertEqualNode<_, _>, input: Color, output: Color, params: [Color, f64]),
+		fallible_async_node!(graphene_std::validation::AssertBoundsNode<_, _, _>, input: VectorData, output: VectorData, params: [DVec2, DVec2, f64]),
+		fallible_async_node!(graphene_std::validation::AssertBoundsNode<_, _, _>, input: GraphicGroup, output: GraphicGroup, params: [DVec2, DVec2, f64]),
+		fallible_async_node!(graphene_std::validation::AssertBoundsNode<_, _, _>, input: ImageFrame<Color>, output: ImageFrame<Color>, params: [DVec2, DVec2, f64]),
 		async_node!(graphene_core::ops::IntoNode<_, ImageFrame<SRGBA8>>, input: ImageFrame<Color>, output: ImageFrame<SRGBA8>, params: []),
 		async_node!(graphene_core::ops::IntoNode<_, ImageFrame<Color>>, input: ImageFrame<SRGBA8>, output: ImageFrame<Color>, params: []),
 		async_node!(graphene_core::ops::IntoNode<_, GraphicGroup>, input: ImageFrame<Color>, output: GraphicGroup, params: []),
@@ -293,6 +369,8 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		async_node!(graphene_core::ops::IntoNode<_, &WgpuExecutor>, input: WasmEditorApi, output: &WgpuExecutor, params: []),
 		register_node!(graphene_std::raster::MaskImageNode<_, _, _>, input: ImageFrame<Color>, params: [ImageFrame<Color>]),
 		register_node!(graphene_std::raster::MaskImageNode<_, _, _>, input: ImageFrame<Color>, params: [ImageFrame<Luma>]),
+		register_node!(graphene_std::raster::PixelDiffNode<_, _, _, _>, input: ImageFrame<Color>, params: [ImageFrame<Color>, PixelDiffMode, f64, f64]),
+		register_node!(graphene_std::raster::ShuffleChannelsNode<_, _, _, _, _>, input: ImageFrame<Color>, params: [ImageFrame<Color>, ChannelSource, ChannelSource, ChannelSource, ChannelSource]),
 		register_node!(graphene_std::raster::InsertChannelNode<_, _, _, _>, input: ImageFrame<Color>, params: [ImageFrame<Color>, RedGreenBlue]),
 		register_node!(graphene_std::raster::InsertChannelNode<_, _, _, _>, input: ImageFrame<Color>, params: [ImageFrame<Luma>, RedGreenBlue]),
 		vec![(
@@ -302,10 +380,18 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 					use graphene_core::raster::*;
 					use graphene_core::value::*;
 
-					let channel_r: ImageFrame<Color> = DowncastBothNode::new(args[0].clone()).eval(()).await;
-					let channel_g: ImageFrame<Color> = DowncastBothNode::new(args[1].clone()).eval(()).await;
-					let channel_b: ImageFrame<Color> = DowncastBothNode::new(args[2].clone()).eval(()).await;
-					let channel_a: ImageFrame<Color> = DowncastBothNode::new(args[3].clone()).eval(()).await;
+					let channels = async {
+						Ok::<_, graph_craft::proto::NodeError>([
+							evaluate_input(&args[0], ()).await?,
+							evaluate_input(&args[1], ()).await?,
+							evaluate_input(&args[2], ()).await?,
+							evaluate_input(&args[3], ()).await?,
+						])
+					};
+					let [channel_r, channel_g, channel_b, channel_a]: [ImageFrame<Color>; 4] = match channels.await {
+						Ok(channels) => channels,
+						Err(error) => return Box::new(FailedNode::new(error)) as TypeErasedBox,
+					};
 
 					let insert_r = InsertChannelNode::new(ClonedNode::new(channel_r.clone()), CopiedNode::new(RedGreenBlue::Red));
 					let insert_g = InsertChannelNode::new(ClonedNode::new(channel_g.clone()), CopiedNode::new(RedGreenBlue::Green));
@@ -348,6 +434,7 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		async_node!(graphene_core::memo::MonitorNode<_, _, _>, input: Footprint, output: Artboard, fn_params: [Footprint => graphene_core::Artboard]),
 		async_node!(graphene_std::wasm_application_io::LoadResourceNode<_>, input: WasmEditorApi, output: Arc<[u8]>, params: [String]),
 		register_node!(graphene_std::wasm_application_io::DecodeImageNode, input: Arc<[u8]>, params: []),
+		register_node!(graphene_std::audio::AudioAnalysisNode<_, _, _, _>, input: Arc<[u8]>, params: [f64, u32, u32, f64]),
 		async_node!(graphene_std::wasm_application_io::CreateSurfaceNode, input: WasmEditorApi, output: Arc<SurfaceHandle<<graphene_std::wasm_application_io::WasmApplicationIo as graphene_core::application_io::ApplicationIo>::Surface>>, params: []),
 		async_node!(
 			graphene_std::wasm_application_io::DrawImageFrameNode<_>,
@@ -398,11 +485,12 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 			ProtoNodeIdentifier::new("graphene_std::executor::MapGpuSingleImageNode<_>"),
 			|args| {
 				Box::pin(async move {
-					let document_node: DowncastBothNode<(), graph_craft::document::DocumentNode> = DowncastBothNode::new(args[0].clone());
-					let editor_api: DowncastBothNode<(), WasmEditorApi> = DowncastBothNode::new(args[1].clone());
+					let errors = InputErrors::default();
+					let document_node: DowncastBothNode<(), graph_craft::document::DocumentNode> = DowncastBothNode::new(args[0].clone(), &errors);
+					let editor_api: DowncastBothNode<(), WasmEditorApi> = DowncastBothNode::new(args[1].clone(), &errors);
 					// let document_node = ClonedNode::new(document_node.eval(()));
 					let node = graphene_std::gpu_nodes::MapGpuNode::new(document_node, editor_api);
-					let any: DynAnyNode<ImageFrame<Color>, _, _> = graphene_std::any::DynAnyNode::new(node);
+					let any: DynAnyNode<ImageFrame<Color>, _, _> = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
 					any.into_type_erased()
 				})
 			},
@@ -417,11 +505,12 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 			ProtoNodeIdentifier::new("graphene_std::executor::BlendGpuImageNode<_, _, _>"),
 			|args| {
 				Box::pin(async move {
-					let background: DowncastBothNode<(), ImageFrame<Color>> = DowncastBothNode::new(args[0].clone());
-					let blend_mode: DowncastBothNode<(), BlendMode> = DowncastBothNode::new(args[1].clone());
-					let opacity: DowncastBothNode<(), f64> = DowncastBothNode::new(args[2].clone());
+					let errors = InputErrors::default();
+					let background: DowncastBothNode<(), ImageFrame<Color>> = DowncastBothNode::new(args[0].clone(), &errors);
+					let blend_mode: DowncastBothNode<(), BlendMode> = DowncastBothNode::new(args[1].clone(), &errors);
+					let opacity: DowncastBothNode<(), f64> = DowncastBothNode::new(args[2].clone(), &errors);
 					let node = graphene_std::gpu_nodes::BlendGpuImageNode::new(background, blend_mode, opacity);
-					let any: DynAnyNode<ImageFrame<Color>, _, _> = graphene_std::any::DynAnyNode::new(node);
+					let any: DynAnyNode<ImageFrame<Color>, _, _> = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
 
 					any.into_type_erased()
 				})
@@ -454,34 +543,40 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		raster_node!(graphene_core::raster::LuminanceNode<_>, params: [LuminanceCalculation]),
 		raster_node!(graphene_core::raster::ExtractChannelNode<_>, params: [RedGreenBlueAlpha]),
 		raster_node!(graphene_core::raster::ExtractOpaqueNode<>, params: []),
-		raster_node!(graphene_core::raster::LevelsNode<_, _, _, _, _>, params: [f64, f64, f64, f64, f64]),
+		raster_node!(graphene_core::raster::LevelsNode<_, _, _, _, _>, params: [f64, f64, f64, f64, f64], map_image: AdjustImageNode),
 		register_node!(graphene_std::image_segmentation::ImageSegmentationNode<_>, input: ImageFrame<Color>, params: [ImageFrame<Color>]),
 		register_node!(graphene_std::image_color_palette::ImageColorPaletteNode<_>, input: ImageFrame<Color>, params: [u32]),
 		register_node!(graphene_core::raster::IndexNode<_>, input: Vec<ImageFrame<Color>>, params: [u32]),
+		async_node!(graphene_std::panorama::AlignImagesNode<_>, input: Footprint, output: Vec<ImageFrame<Color>>, fn_params: [Footprint => GraphicGroup]),
+		register_node!(graphene_std::panorama::BlendPanoramaNode<_>, input: Vec<ImageFrame<Color>>, params: [f64]),
+		register_node!(graphene_std::image_stacking::FocusStackNode<_>, input: Vec<ImageFrame<Color>>, params: [f64]),
+		register_node!(graphene_std::image_stacking::ExposureFusionNode<_, _, _>, input: Vec<ImageFrame<Color>>, params: [f64, f64, f64]),
 		register_node!(graphene_core::raster::adjustments::ColorFillNode<_>, input: ImageFrame<Color>, params: [Color]),
 		register_node!(graphene_core::raster::adjustments::ColorOverlayNode<_, _, _>, input: ImageFrame<Color>, params: [Color, BlendMode, f64]),
 		register_node!(graphene_core::raster::IndexNode<_>, input: Vec<Color>, params: [u32]),
+		register_node!(graphene_core::raster::ColorHarmonyNode<_, _>, input: Color, params: [ColorHarmony, f64]),
 		vec![(
 			ProtoNodeIdentifier::new("graphene_core::raster::BlendNode<_, _, _, _>"),
 			|args| {
 				Box::pin(async move {
-					let image: DowncastBothNode<(), ImageFrame<Color>> = DowncastBothNode::new(args[0].clone());
-					let blend_mode: DowncastBothNode<(), BlendMode> = DowncastBothNode::new(args[1].clone());
-					let opacity: DowncastBothNode<(), f64> = DowncastBothNode::new(args[2].clone());
-					let blend_node = graphene_core::raster::BlendNode::new(CopiedNode::new(blend_mode.eval(()).await), CopiedNode::new(opacity.eval(()).await));
-					let node = graphene_std::raster::BlendImageNode::new(image, blend_node);
-					let any: DynAnyNode<ImageFrame<Color>, _, _> = graphene_std::any::DynAnyNode::new(node);
+					let errors = InputErrors::default();
+					let image: DowncastBothNode<(), ImageFrame<Color>> = DowncastBothNode::new(args[0].clone(), &errors);
+					let blend_mode: DowncastBothNode<(), BlendMode> = DowncastBothNode::new(args[1].clone(), &errors);
+					let opacity: DowncastBothNode<(), f64> = DowncastBothNode::new(args[2].clone(), &errors);
+					let linear: DowncastBothNode<(), bool> = DowncastBothNode::new(args[3].clone(), &errors);
+					let node = graphene_std::raster::BlendColorImageNode::new(image, blend_mode, opacity, linear);
+					let any: DynAnyNode<ImageFrame<Color>, _, _> = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
 					any.into_type_erased()
 				})
 			},
 			NodeIOTypes::new(
 				concrete!(ImageFrame<Color>),
 				concrete!(ImageFrame<Color>),
-				vec![fn_type!(ImageFrame<Color>), fn_type!(BlendMode), fn_type!(f64)],
+				vec![fn_type!(ImageFrame<Color>), fn_type!(BlendMode), fn_type!(f64), fn_type!(bool)],
 			),
 		)],
 		raster_node!(graphene_core::raster::BlackAndWhiteNode<_, _, _, _, _, _, _>, params: [Color, f64, f64, f64, f64, f64, f64]),
-		raster_node!(graphene_core::raster::HueSaturationNode<_, _, _>, params: [f64, f64, f64]),
+		raster_node!(graphene_core::raster::HueSaturationNode<_, _, _>, params: [f64, f64, f64], map_image: AdjustImageNode),
 		raster_node!(graphene_core::raster::InvertRGBNode, params: []),
 		raster_node!(graphene_core::raster::ThresholdNode<_, _, _>, params: [f64, f64, LuminanceCalculation]),
 		raster_node!(graphene_core::raster::VibranceNode<_>, params: [f64]),
@@ -499,13 +594,15 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				Box::pin(async move {
 					use graphene_core::raster::brightness_contrast::*;
 
-					let brightness: DowncastBothNode<(), f64> = DowncastBothNode::new(args[0].clone());
-					let brightness = ClonedNode::new(brightness.eval(()).await);
-					let contrast: DowncastBothNode<(), f64> = DowncastBothNode::new(args[1].clone());
-					let contrast = ClonedNode::new(contrast.eval(()).await);
-					let use_legacy: DowncastBothNode<(), bool> = DowncastBothNode::new(args[2].clone());
+					let inputs = async { Ok::<_, graph_craft::proto::NodeError>((evaluate_input(&args[0], ()).await?, evaluate_input(&args[1], ()).await?, evaluate_input(&args[2], ()).await?)) };
+					let (brightness, contrast, use_legacy): (f64, f64, bool) = match inputs.await {
+						Ok(inputs) => inputs,
+						Err(error) => return Box::new(FailedNode::new(error)) as TypeErasedBox,
+					};
+					let brightness = ClonedNode::new(brightness);
+					let contrast = ClonedNode::new(contrast);
 
-					if use_legacy.eval(()).await {
+					if use_legacy {
 						let generate_brightness_contrast_legacy_mapper_node = GenerateBrightnessContrastLegacyMapperNode::new(brightness, contrast);
 						let map_image_frame_node = graphene_std::raster::MapImageNode::new(ValueNode::new(generate_brightness_contrast_legacy_mapper_node.eval(())));
 						let map_image_frame_node = FutureWrapperNode::new(map_image_frame_node);
@@ -527,9 +624,12 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				ProtoNodeIdentifier::new("graphene_core::raster::CurvesNode<_>"),
 				|args| {
 					use graphene_core::raster::{curve::Curve, GenerateCurvesNode};
-					let curve: DowncastBothNode<(), Curve> = DowncastBothNode::new(args[0].clone());
 					Box::pin(async move {
-						let curve = ClonedNode::new(curve.eval(()).await);
+						let curve: Curve = match evaluate_input(&args[0], ()).await {
+							Ok(curve) => curve,
+							Err(error) => return Box::new(FailedNode::new(error)) as TypeErasedBox,
+						};
+						let curve = ClonedNode::new(curve);
 
 						let generate_curves_node = GenerateCurvesNode::<f32, _>::new(curve);
 						let map_image_frame_node = graphene_std::raster::MapImageNode::new(ValueNode::new(generate_curves_node.eval(())));
@@ -545,9 +645,12 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				ProtoNodeIdentifier::new("graphene_core::raster::CurvesNode<_>"),
 				|args| {
 					use graphene_core::raster::{curve::Curve, GenerateCurvesNode};
-					let curve: DowncastBothNode<(), Curve> = DowncastBothNode::new(args[0].clone());
 					Box::pin(async move {
-						let curve = ClonedNode::new(curve.eval(()).await);
+						let curve: Curve = match evaluate_input(&args[0], ()).await {
+							Ok(curve) => curve,
+							Err(error) => return Box::new(FailedNode::new(error)) as TypeErasedBox,
+						};
+						let curve = ClonedNode::new(curve);
 
 						let generate_curves_node = GenerateCurvesNode::<f32, _>::new(curve);
 						let map_image_frame_node = graphene_std::raster::MapImageNode::new(ValueNode::new(generate_curves_node.eval(())));
@@ -614,9 +717,10 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				ProtoNodeIdentifier::new("graphene_core::memo::RefNode<_, _>"),
 				|args| {
 					Box::pin(async move {
-						let node: DowncastBothNode<Option<WasmEditorApi>, WasmEditorApi> = graphene_std::any::DowncastBothNode::new(args[0].clone());
+						let errors = InputErrors::default();
+						let node: DowncastBothNode<Option<WasmEditorApi>, WasmEditorApi> = graphene_std::any::DowncastBothNode::new(args[0].clone(), &errors);
 						let node = <graphene_core::memo::RefNode<_, _>>::new(node);
-						let any: DynAnyNode<(), _, _> = graphene_std::any::DynAnyNode::new(node);
+						let any: DynAnyNode<(), _, _> = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
 
 						any.into_type_erased()
 					})
@@ -628,11 +732,12 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				|args: Vec<graph_craft::proto::SharedNodeContainer>| {
 					Box::pin(async move {
 						use graphene_std::raster::ImaginateNode;
+						let errors = InputErrors::default();
 						macro_rules! instantiate_imaginate_node {
-							($($i:expr,)*) => { ImaginateNode::new($(graphene_std::any::input_node(args[$i].clone()),)* ) };
+							($($i:expr,)*) => { ImaginateNode::new($(graphene_std::any::input_node(args[$i].clone(), &errors),)* ) };
 						}
 						let node: ImaginateNode<Color, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _> = instantiate_imaginate_node!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,);
-						let any = graphene_std::any::DynAnyNode::new(node);
+						let any = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
 						any.into_type_erased()
 					})
 				},
@@ -706,11 +811,45 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: ImageFrame<Color>, params: [ImageFrame<Color>]),
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: VectorData, params: [DAffine2]),
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: ImageFrame<Color>, params: [DAffine2]),
+		register_node!(graphene_std::warp::WarpNode<_, _, _, _, _, _>, input: VectorData, params: [graphene_core::warp::WarpMode, DVec2, DVec2, DVec2, DVec2, Vec<DVec2>]),
+		register_node!(graphene_std::warp::WarpNode<_, _, _, _, _, _>, input: ImageFrame<Color>, params: [graphene_core::warp::WarpMode, DVec2, DVec2, DVec2, DVec2, Vec<DVec2>]),
+		async_node!(
+			graphene_core::projection::AxonometricProjectionNode<_, _, _, _>,
+			input: Footprint,
+			output: VectorData,
+			fn_params: [Footprint => VectorData, () => graphene_core::projection::ProjectionFace, () => f64, () => f64]
+		),
+		async_node!(
+			graphene_core::projection::AxonometricProjectionNode<_, _, _, _>,
+			input: Footprint,
+			output: ImageFrame<Color>,
+			fn_params: [Footprint => ImageFrame<Color>, () => graphene_core::projection::ProjectionFace, () => f64, () => f64]
+		),
+		async_node!(
+			graphene_core::projection::AxonometricProjectionNode<_, _, _, _>,
+			input: Footprint,
+			output: GraphicGroup,
+			fn_params: [Footprint => GraphicGroup, () => graphene_core::projection::ProjectionFace, () => f64, () => f64]
+		),
+		register_node!(graphene_core::projection::DepthStackNode<_, _, _, _, _>, input: VectorData, params: [graphene_core::projection::ProjectionFace, u32, f64, f64, f64]),
+		register_node!(graphene_core::projection::DepthStackNode<_, _, _, _, _>, input: ImageFrame<Color>, params: [graphene_core::projection::ProjectionFace, u32, f64, f64, f64]),
+		register_node!(graphene_core::projection::DepthStackNode<_, _, _, _, _>, input: GraphicGroup, params: [graphene_core::projection::ProjectionFace, u32, f64, f64, f64]),
+		register_node!(graphene_std::warp::LensCorrectionNode<_>, input: ImageFrame<Color>, params: [f64]),
+		register_node!(graphene_std::warp::PerspectiveCropNode<_, _, _, _>, input: ImageFrame<Color>, params: [DVec2, DVec2, DVec2, DVec2]),
+		register_node!(graphene_std::stylize::ChromaticAberrationNode<_, _>, input: ImageFrame<Color>, params: [f64, f64]),
+		register_node!(graphene_std::stylize::VignetteNode<_, _, _, _>, input: ImageFrame<Color>, params: [f64, f64, f64, f64]),
+		register_node!(graphene_std::stylize::GrainNode<_, _, _>, input: ImageFrame<Color>, params: [f64, f64, u32]),
+		register_node!(graphene_std::convolution::ConvolveNode<_, _, _>, input: ImageFrame<Color>, params: [Vec<f64>, bool, graphene_core::raster::BorderMode]),
+		register_node!(graphene_std::convolution::ConvolutionPresetNode<_, _>, input: ImageFrame<Color>, params: [graphene_core::raster::ConvolutionPreset, graphene_core::raster::BorderMode]),
+		register_node!(graphene_std::convolution::UnsharpMaskNode<_, _, _>, input: ImageFrame<Color>, params: [f64, f64, f64]),
+		register_node!(graphene_std::morphology::MorphologyNode<_, _, _, _>, input: ImageFrame<Color>, params: [graphene_core::raster::MorphologyOperation, graphene_core::raster::MorphologyChannel, f64, graphene_core::raster::MorphologyShape]),
 		register_node!(graphene_core::vector::SetFillNode<_>, input: VectorData, params: [graphene_std::vector::style::Fill]),
 		register_node!(graphene_core::vector::SetFillNode<_>, input: VectorData, params: [Color]),
 		register_node!(graphene_core::vector::SetFillNode<_>, input: VectorData, params: [Option<Color>]),
 		register_node!(graphene_core::vector::SetFillNode<_>, input: VectorData, params: [graphene_std::vector::style::Gradient]),
-		register_node!(graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _>, input: VectorData, params: [Option<graphene_core::Color>, f64, Vec<f64>, f64, graphene_core::vector::style::LineCap, graphene_core::vector::style::LineJoin, f64]),
+		register_node!(graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _, _>, input: VectorData, params: [Option<graphene_core::Color>, f64, Vec<f64>, f64, graphene_core::vector::style::LineCap, graphene_core::vector::style::LineJoin, f64, graphene_core::vector::style::StrokeAlign]),
+		register_node!(graphene_core::vector::SetFillBlendingNode<_, _>, input: VectorData, params: [f64, BlendMode]),
+		register_node!(graphene_core::vector::SetStrokeBlendingNode<_, _>, input: VectorData, params: [f64, BlendMode]),
 		register_node!(graphene_core::vector::RepeatNode<_, _, _>, input: VectorData, params: [DVec2, f64, u32]),
 		register_node!(graphene_core::vector::BoundingBoxNode, input: VectorData, params: []),
 		register_node!(graphene_core::vector::SolidifyStrokeNode, input: VectorData, params: []),
@@ -722,9 +861,13 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				Box::pin(async move {
 					let mut args = args.clone();
 					args.reverse();
-					let node = <graphene_core::transform::CullNode<_>>::new(graphene_std::any::input_node::<VectorData>(args.pop().expect("Not enough arguments provided to construct node")));
-					let any: DynAnyNode<Footprint, _, _> = graphene_std::any::DynAnyNode::new(node);
-					Box::new(any) as Box<dyn for<'i> NodeIO<'i, graph_craft::proto::Any<'i>, Output = core::pin::Pin<Box<dyn core::future::Future<Output = graph_craft::proto::Any<'i>> + 'i>>> + '_>
+					let errors = InputErrors::default();
+					let node = <graphene_core::transform::CullNode<_>>::new(graphene_std::any::input_node::<VectorData>(
+						args.pop().expect("Not enough arguments provided to construct node"),
+						&errors,
+					));
+					let any: DynAnyNode<Footprint, _, _> = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
+					Box::new(any) as TypeErasedBox<'_>
 				})
 			},
 			{
@@ -743,9 +886,13 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				Box::pin(async move {
 					let mut args = args.clone();
 					args.reverse();
-					let node = <graphene_core::transform::CullNode<_>>::new(graphene_std::any::input_node::<ArtboardGroup>(args.pop().expect("Not enough arguments provided to construct node")));
-					let any: DynAnyNode<Footprint, _, _> = graphene_std::any::DynAnyNode::new(node);
-					Box::new(any) as Box<dyn for<'i> NodeIO<'i, graph_craft::proto::Any<'i>, Output = core::pin::Pin<Box<dyn core::future::Future<Output = graph_craft::proto::Any<'i>> + 'i>>> + '_>
+					let errors = InputErrors::default();
+					let node = <graphene_core::transform::CullNode<_>>::new(graphene_std::any::input_node::<ArtboardGroup>(
+						args.pop().expect("Not enough arguments provided to construct node"),
+						&errors,
+					));
+					let any: DynAnyNode<Footprint, _, _> = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
+					Box::new(any) as TypeErasedBox<'_>
 				})
 			},
 			{
@@ -762,9 +909,13 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 				Box::pin(async move {
 					let mut args = args.clone();
 					args.reverse();
-					let node = <graphene_core::transform::CullNode<_>>::new(graphene_std::any::input_node::<GraphicGroup>(args.pop().expect("Not enough arguments provided to construct node")));
-					let any: DynAnyNode<Footprint, _, _> = graphene_std::any::DynAnyNode::new(node);
-					Box::new(any) as Box<dyn for<'i> NodeIO<'i, graph_craft::proto::Any<'i>, Output = core::pin::Pin<Box<dyn core::future::Future<Output = graph_craft::proto::Any<'i>> + 'i>>> + '_>
+					let errors = InputErrors::default();
+					let node = <graphene_core::transform::CullNode<_>>::new(graphene_std::any::input_node::<GraphicGroup>(
+						args.pop().expect("Not enough arguments provided to construct node"),
+						&errors,
+					));
+					let any: DynAnyNode<Footprint, _, _> = graphene_std::any::DynAnyNode::with_input_errors(node, errors);
+					Box::new(any) as TypeErasedBox<'_>
 				})
 			},
 			{
@@ -779,27 +930,45 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_std::raster::MandelbrotNode, input: Footprint, params: []),
 		async_node!(graphene_core::vector::CopyToPoints<_, _, _, _, _, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData, Footprint => VectorData, () => f64, () => f64, () => f64, () => f64]),
 		async_node!(graphene_core::vector::CopyToPoints<_, _, _, _, _, _>, input: Footprint, output: GraphicGroup, fn_params: [Footprint => VectorData, Footprint => GraphicGroup, () => f64, () => f64, () => f64, () => f64]),
+		async_node!(graphene_core::vector::CopyToPoints<_, _, _, _, _, _>, input: Footprint, output: VectorData, fn_params: [Footprint => graphene_core::vector::point_cloud::PointCloud, Footprint => VectorData, () => f64, () => f64, () => f64, () => f64]),
+		async_node!(graphene_core::vector::CopyToPoints<_, _, _, _, _, _>, input: Footprint, output: GraphicGroup, fn_params: [Footprint => graphene_core::vector::point_cloud::PointCloud, Footprint => GraphicGroup, () => f64, () => f64, () => f64, () => f64]),
+		register_node!(graphene_core::vector::point_cloud::SamplePointCloudNode<_, _, _, _, _>, input: VectorData, params: [graphene_core::vector::point_cloud::PointRegion, graphene_core::vector::point_cloud::PointSampling, f64, u32, u32]),
+		register_node!(graphene_core::vector::point_cloud::PointCloudFromAnchorsNode, input: VectorData, params: []),
+		register_node!(graphene_core::vector::point_cloud::RandomPointAttributeNode<_, _, _, _>, input: graphene_core::vector::point_cloud::PointCloud, params: [String, f64, f64, u32]),
+		register_node!(graphene_core::vector::point_cloud::RandomVec2PointAttributeNode<_, _, _, _>, input: graphene_core::vector::point_cloud::PointCloud, params: [String, DVec2, DVec2, u32]),
+		register_node!(graphene_core::vector::point_cloud::GradientPointAttributeNode<_, _, _, _>, input: graphene_core::vector::point_cloud::PointCloud, params: [String, graphene_core::vector::style::GradientStops, DVec2, DVec2]),
+		register_node!(graphene_core::vector::point_cloud::PointAttributeValuesNode<_>, input: graphene_core::vector::point_cloud::PointCloud, params: [String]),
 		async_node!(graphene_core::vector::SamplePoints<_, _, _, _, _, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData, () => f64, () => f64, () => f64, () => bool, Footprint => Vec<f64>]),
 		register_node!(graphene_core::vector::PoissonDiskPoints<_>, input: VectorData, params: [f64]),
+		register_node!(graphene_core::vector::voronoi::VoronoiNode<_, _, _, _>, input: VectorData, params: [graphene_core::vector::voronoi::PointDistribution, u32, u32, u32]),
+		register_node!(graphene_core::vector::voronoi::DelaunayNode<_, _, _, _>, input: VectorData, params: [graphene_core::vector::voronoi::PointDistribution, u32, u32, u32]),
 		register_node!(graphene_core::vector::LengthsOfSegmentsOfSubpaths, input: VectorData, params: []),
 		register_node!(graphene_core::vector::SplinesFromPointsNode, input: VectorData, params: []),
 		async_node!(graphene_core::vector::AreaNode<_>, input: (), output: f64, fn_params: [Footprint => VectorData]),
 		async_node!(graphene_core::vector::CentroidNode<_, _>, input: (), output: DVec2, fn_params: [Footprint => VectorData, () => graphene_core::vector::misc::CentroidType]),
 		async_node!(graphene_core::vector::MorphNode<_, _, _, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData, Footprint => VectorData, () => u32, () => f64]),
+		register_node!(graphene_core::vector::SplitAtPointNode<_>, input: VectorData, params: [DVec2]),
+		async_node!(graphene_core::vector::CutAlongPathNode<_, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData, Footprint => VectorData]),
+		async_node!(graphene_core::vector::DivideShapesNode<_, _>, input: Footprint, output: VectorData, fn_params: [Footprint => VectorData, Footprint => VectorData]),
 		register_node!(graphene_core::vector::generator_nodes::CircleGenerator<_>, input: (), params: [f64]),
 		register_node!(graphene_core::vector::generator_nodes::EllipseGenerator<_, _>, input: (), params: [f64, f64]),
 		register_node!(graphene_core::vector::generator_nodes::RectangleGenerator<_, _, _, _, _>, input: (), params: [f64, f64, bool, f64, bool]),
 		register_node!(graphene_core::vector::generator_nodes::RectangleGenerator<_, _, _, _, _>, input: (), params: [f64, f64, bool, [f64; 4], bool]),
 		register_node!(graphene_core::vector::generator_nodes::RegularPolygonGenerator<_, _>, input: (), params: [u32, f64]),
 		register_node!(graphene_core::vector::generator_nodes::StarGenerator<_, _, _>, input: (), params: [u32, f64, f64]),
+		register_node!(graphene_core::vector::generator_nodes::SpiralGenerator<_, _, _>, input: (), params: [f64, f64, f64]),
+		register_node!(graphene_core::vector::generator_nodes::ArcGenerator<_, _, _, _>, input: (), params: [f64, f64, f64, graphene_core::vector::misc::ArcType]),
 		register_node!(graphene_core::vector::generator_nodes::LineGenerator<_, _>, input: (), params: [DVec2, DVec2]),
 		register_node!(graphene_core::vector::generator_nodes::SplineGenerator<_>, input: (), params: [Vec<DVec2>]),
+		register_node!(graphene_core::vector::l_system::LSystemNode<_, _, _, _, _, _, _, _>, input: (), params: [String, String, u32, f64, f64, f64, f64, u32]),
+		register_node!(graphene_core::vector::generator_nodes::QrCodeGenerator<_, _, _, _>, input: (), params: [String, graphene_core::vector::barcode::QrErrorCorrection, u32, f64]),
+		register_node!(graphene_core::vector::generator_nodes::BarcodeGenerator<_, _, _, _, _>, input: (), params: [String, graphene_core::vector::barcode::BarcodeSymbology, u32, f64, f64]),
 		register_node!(
 			graphene_core::vector::generator_nodes::PathGenerator<_>,
 			input: Vec<graphene_core::vector::bezier_rs::Subpath<graphene_core::uuid::ManipulatorGroupId>>,
 			params: [Vec<graphene_core::uuid::ManipulatorGroupId>]
 		),
-		register_node!(graphene_core::text::TextGeneratorNode<_, _, _>, input: WasmEditorApi, params: [String, graphene_core::text::Font, f64]),
+		register_node!(graphene_core::text::TextGeneratorNode<_, _, _, _, _, _, _, _, _, _, _>, input: WasmEditorApi, params: [String, graphene_core::text::Font, f64, f64, f64, f64, graphene_core::text::TextAlign, graphene_core::text::TextAutoResize, graphene_core::text::FontVariations, graphene_core::text::TextRuns, graphene_core::text::TextKerning]),
 		register_node!(graphene_std::brush::VectorPointsNode, input: VectorData, params: []),
 		register_node!(graphene_core::ExtractImageFrame, input: WasmEditorApi, params: []),
 		async_node!(graphene_core::ConstructLayerNode<_, _>, input: Footprint, output: GraphicGroup, fn_params: [Footprint => GraphicGroup, Footprint => graphene_core::GraphicElement]),
@@ -811,8 +980,34 @@ fn node_registry() -> HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeCons
 		register_node!(graphene_core::ToGraphicGroupNode, input: ImageFrame<Color>, params: []),
 		register_node!(graphene_core::ToGraphicGroupNode, input: GraphicGroup, params: []),
 		register_node!(graphene_core::ToGraphicGroupNode, input: Artboard, params: []),
-		async_node!(graphene_core::ConstructArtboardNode<_, _, _, _, _>, input: Footprint, output: Artboard, fn_params: [Footprint => GraphicGroup, () => glam::IVec2, () => glam::IVec2, () => Color, () => bool]),
+		register_node!(graphene_core::SvgAttributesNode<_, _, _>, input: graphene_core::vector::VectorData, params: [String, String, String]),
+		register_node!(graphene_core::SvgAttributesNode<_, _, _>, input: ImageFrame<Color>, params: [String, String, String]),
+		register_node!(graphene_core::SvgAttributesNode<_, _, _>, input: GraphicGroup, params: [String, String, String]),
+		async_node!(
+			graphene_core::ConstructArtboardNode<_, _, _, _, _, _>,
+			input: Footprint,
+			output: Artboard,
+			fn_params: [Footprint => GraphicGroup, () => glam::IVec2, () => glam::IVec2, () => Color, () => bool, Footprint => ArtboardGroup]
+		),
 		async_node!(graphene_core::AddArtboardNode<_, _>, input: Footprint, output: ArtboardGroup, fn_params: [Footprint => ArtboardGroup, Footprint => Artboard]),
+		async_node!(
+			graphene_core::ContactSheetNode<_, _, _, _, _, _, _, _>,
+			input: WasmEditorApi,
+			output: GraphicGroup,
+			fn_params: [Footprint => GraphicGroup, () => u32, () => f64, () => DVec2, () => graphene_core::CellFit, () => String, () => graphene_core::text::Font, () => f64]
+		),
+		async_node!(
+			graphene_core::DimensionNode<_, _, _, _, _, _, _, _>,
+			input: Footprint,
+			output: GraphicGroup,
+			fn_params: [Footprint => VectorData, () => u32, Footprint => VectorData, () => u32, () => graphene_core::DimensionKind, () => f64, () => u32, () => Color]
+		),
+		async_node!(
+			graphene_core::MaskNode<_, _, _, _, _>,
+			input: Footprint,
+			output: GraphicGroup,
+			fn_params: [Footprint => GraphicGroup, Footprint => GraphicGroup, () => graphene_core::MaskMode, () => bool, () => f64]
+		),
 	];
 	let mut map: HashMap<ProtoNodeIdentifier, HashMap<NodeIOTypes, NodeConstructor>> = HashMap::new();
 	for (id, c, types) in node_types.into_iter().flatten() {
@@ -0,0 +1,233 @@
+use graph_craft::document::NodeId;
+use graphene_core::ProtoNodeIdentifier;
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::Duration;
+use web_time::Instant;
+
+/// The time spent on one evaluation of a node, in milliseconds relative to the start of the frame.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeEvaluation {
+	/// The index of the evaluated node in [`FrameProfile::nodes`].
+	pub node: usize,
+	pub start: f64,
+	pub duration: f64,
+	/// How many evaluations of other nodes this one is nested inside of, which is its row in a flame graph.
+	pub depth: usize,
+}
+
+/// The evaluations of one node during a frame, summed up.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeProfile {
+	/// The path of the document node this proto node was compiled from.
+	pub node_path: Vec<NodeId>,
+	pub identifier: String,
+	pub evaluations: u32,
+	/// The time spent evaluating this node including the nodes it evaluated in turn, in milliseconds.
+	pub total_time: f64,
+	/// The time spent evaluating only this node itself, in milliseconds.
+	pub self_time: f64,
+	/// How often a caching node returned its stored value instead of evaluating its input.
+	pub cache_hits: u32,
+	pub cache_misses: u32,
+}
+
+/// Everything recorded while executing the graph once.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameProfile {
+	/// The wall time of the whole frame, in milliseconds.
+	pub duration: f64,
+	pub nodes: Vec<NodeProfile>,
+	/// Every node evaluation in the order they began, which is the data for drawing a flame graph.
+	pub evaluations: Vec<NodeEvaluation>,
+}
+
+struct RecordedEvaluation {
+	node: NodeId,
+	start: Duration,
+	duration: Duration,
+	depth: usize,
+	nested_evaluations: usize,
+}
+
+#[derive(Default)]
+struct RecordedFrame {
+	start: Option<Instant>,
+	evaluations: Vec<RecordedEvaluation>,
+	/// Indices into `evaluations` of the nodes being evaluated right now, innermost last.
+	stack: Vec<usize>,
+	identifiers: HashMap<NodeId, ProtoNodeIdentifier>,
+}
+
+/// Records how long each node takes to evaluate, while profiling is enabled.
+#[derive(Default)]
+pub struct Profiler {
+	enabled: Cell<bool>,
+	frame: RefCell<RecordedFrame>,
+}
+
+/// Memo nodes skip evaluating their input when they have a cached value, which is how their hits and misses are told apart.
+fn is_cache_node(identifier: &ProtoNodeIdentifier) -> bool {
+	identifier.name.starts_with("graphene_core::memo::") && identifier.name.contains("MemoNode")
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+	duration.as_secs_f64() * 1000.
+}
+
+impl Profiler {
+	pub fn enabled(&self) -> bool {
+		self.enabled.get()
+	}
+
+	pub fn set_enabled(&self, enabled: bool) {
+		self.enabled.set(enabled);
+		if !enabled {
+			*self.frame.borrow_mut() = RecordedFrame::default();
+		}
+	}
+
+	/// Starts timing an evaluation of the node, returning a token to pass to [`Profiler::end`] once it has finished.
+	pub fn begin(&self, node: NodeId, identifier: &ProtoNodeIdentifier) -> Option<usize> {
+		if !self.enabled.get() {
+			return None;
+		}
+
+		let mut frame = self.frame.borrow_mut();
+		let now = Instant::now();
+		let start = now - *frame.start.get_or_insert(now);
+		if let Some(&parent) = frame.stack.last() {
+			frame.evaluations[parent].nested_evaluations += 1;
+		}
+		frame.identifiers.entry(node).or_insert_with(|| identifier.clone());
+
+		let index = frame.evaluations.len();
+		let depth = frame.stack.len();
+		frame.evaluations.push(RecordedEvaluation {
+			node,
+			start,
+			duration: Duration::ZERO,
+			depth,
+			nested_evaluations: 0,
+		});
+		frame.stack.push(index);
+		Some(index)
+	}
+
+	pub fn end(&self, token: Option<usize>) {
+		let Some(index) = token else { return };
+
+		let mut frame = self.frame.borrow_mut();
+		let Some(start) = frame.start else { return };
+		let Some(evaluation) = frame.evaluations.get_mut(index) else { return };
+		evaluation.duration = start.elapsed().saturating_sub(evaluation.start);
+
+		// Nested evaluations which were dropped before finishing are popped along with this one
+		if let Some(position) = frame.stack.iter().rposition(|&evaluating| evaluating == index) {
+			frame.stack.truncate(position);
+		}
+	}
+
+	/// Takes everything recorded since the last frame, looking up the document node path of each proto node with `node_path`.
+	pub fn finish_frame(&self, node_path: impl Fn(NodeId) -> Vec<NodeId>) -> Option<FrameProfile> {
+		if !self.enabled.get() {
+			return None;
+		}
+		let frame = core::mem::take(&mut *self.frame.borrow_mut());
+
+		let mut profile = FrameProfile::default();
+		let mut node_indices = HashMap::new();
+		// The nodes whose evaluations enclose the current one, outermost first
+		let mut enclosing: Vec<usize> = Vec::new();
+		for recorded in &frame.evaluations {
+			let identifier = &frame.identifiers[&recorded.node];
+			let node = *node_indices.entry(recorded.node).or_insert_with(|| {
+				profile.nodes.push(NodeProfile {
+					node_path: node_path(recorded.node),
+					identifier: identifier.name.to_string(),
+					evaluations: 0,
+					total_time: 0.,
+					self_time: 0.,
+					cache_hits: 0,
+					cache_misses: 0,
+				});
+				profile.nodes.len() - 1
+			});
+			enclosing.truncate(recorded.depth);
+
+			let duration = milliseconds(recorded.duration);
+			let node_profile = &mut profile.nodes[node];
+			node_profile.evaluations += 1;
+			node_profile.self_time += duration;
+			// Time spent in a node evaluating itself again is already part of the outer evaluation's total
+			if !enclosing.contains(&node) {
+				node_profile.total_time += duration;
+			}
+			if is_cache_node(identifier) {
+				if recorded.nested_evaluations == 0 {
+					node_profile.cache_hits += 1;
+				} else {
+					node_profile.cache_misses += 1;
+				}
+			}
+
+			match enclosing.last() {
+				Some(&parent) => profile.nodes[parent].self_time -= duration,
+				None => profile.duration += duration,
+			}
+			enclosing.push(node);
+
+			profile.evaluations.push(NodeEvaluation {
+				node,
+				start: milliseconds(recorded.start),
+				duration,
+				depth: recorded.depth,
+			});
+		}
+		for node in &mut profile.nodes {
+			// Timer imprecision can leave a tiny negative remainder
+			node.self_time = node.self_time.max(0.);
+		}
+
+		Some(profile)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn nested_evaluations_and_cache_hits() {
+		let profiler = Profiler::default();
+		profiler.set_enabled(true);
+		let memo = ProtoNodeIdentifier::new("graphene_core::memo::MemoNode<_, _>");
+		let add = ProtoNodeIdentifier::new("graphene_core::ops::AddNode<_>");
+
+		// The memo node evaluates its input on the first frame
+		let outer = profiler.begin(NodeId(0), &memo);
+		let inner = profiler.begin(NodeId(1), &add);
+		profiler.end(inner);
+		profiler.end(outer);
+		// And returns the cached value afterwards
+		let outer = profiler.begin(NodeId(0), &memo);
+		profiler.end(outer);
+
+		let profile = profiler.finish_frame(|node| vec![node]).unwrap();
+		assert_eq!(profile.nodes.len(), 2);
+		assert_eq!(profile.evaluations.iter().map(|evaluation| evaluation.depth).collect::<Vec<_>>(), [0, 1, 0]);
+
+		let memo_profile = &profile.nodes[0];
+		assert_eq!((memo_profile.evaluations, memo_profile.cache_hits, memo_profile.cache_misses), (2, 1, 1));
+		assert!(memo_profile.self_time <= memo_profile.total_time);
+		assert_eq!(profile.nodes[1].node_path, [NodeId(1)]);
+
+		assert!(profiler.finish_frame(|node| vec![node]).unwrap().evaluations.is_empty());
+		profiler.set_enabled(false);
+		assert!(profiler.begin(NodeId(0), &memo).is_none());
+	}
+}